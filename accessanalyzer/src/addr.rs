@@ -0,0 +1,70 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum AccessAnalyzerResourceAddress {
+    Analyzer {
+        region: String,
+        name:   String,
+    },
+    ArchiveRule {
+        region:   String,
+        analyzer: String,
+        name:     String,
+    },
+}
+
+impl ResourceAddress for AccessAnalyzerResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            AccessAnalyzerResourceAddress::Analyzer { region, name } => {
+                PathBuf::from(format!("aws/accessanalyzer/{region}/analyzers/{name}.ron"))
+            }
+            AccessAnalyzerResourceAddress::ArchiveRule { region, analyzer, name } => PathBuf::from(format!(
+                "aws/accessanalyzer/{region}/analyzers/{analyzer}/archive_rules/{name}.ron"
+            )),
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "accessanalyzer", region, "analyzers", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(AccessAnalyzerResourceAddress::Analyzer {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "accessanalyzer", region, "analyzers", analyzer, "archive_rules", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(AccessAnalyzerResourceAddress::ArchiveRule {
+                    region: region.to_string(),
+                    analyzer: analyzer.to_string(),
+                    name,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for AccessAnalyzerResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/accessanalyzer/<region>/analyzers/<name>.ron",
+                description: "An analyzer for external or unused access, across the account or the organization",
+                example:     "aws/accessanalyzer/us-east-1/analyzers/account.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/accessanalyzer/<region>/analyzers/<analyzer>/archive_rules/<name>.ron",
+                description: "Archives an analyzer's new findings that match its filter",
+                example:     "aws/accessanalyzer/us-east-1/analyzers/account/archive_rules/trusted-accounts.ron",
+            },
+        ]
+    }
+}
@@ -0,0 +1,224 @@
+pub use crate::addr::AccessAnalyzerResourceAddress;
+pub use crate::op::AccessAnalyzerConnectorOp;
+pub use crate::resource::AccessAnalyzerResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+pub mod task_exec;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse, TaskExecResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::AccessAnalyzerConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{Analyzer, ArchiveRule, Criterion};
+use crate::tags::Tags;
+use crate::task::{AccessAnalyzerTask, AccessAnalyzerTaskAddress, ListFindings};
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct AccessAnalyzerConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_accessanalyzer::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<AccessAnalyzerConnectorConfig>,
+    prefix: PathBuf,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl AccessAnalyzerConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_accessanalyzer::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_accessanalyzer, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for AccessAnalyzerConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = AccessAnalyzerResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else if let Ok(_addr) = AccessAnalyzerTaskAddress::from_path(addr) {
+            Ok(FilterResponse::Task)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(AccessAnalyzerConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let accessanalyzer_config: AccessAnalyzerConnectorConfig = AccessAnalyzerConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = accessanalyzer_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(accessanalyzer_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = accessanalyzer_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn task_exec(
+        &self,
+        addr: &Path,
+        body: Vec<u8>,
+        arg: Option<Vec<u8>>,
+        state: Option<Vec<u8>>,
+    ) -> anyhow::Result<TaskExecResponse> {
+        self.do_task_exec(addr, body, arg, state).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        res.push(skeleton!(
+            AccessAnalyzerResourceAddress::Analyzer {
+                region: String::from("[region]"),
+                name:   String::from("[analyzer_name]"),
+            },
+            AccessAnalyzerResource::Analyzer(Analyzer {
+                analyzer_type:     String::from("ACCOUNT"),
+                unused_access_age: None,
+                tags:              Tags::default(),
+            })
+        ));
+
+        // Archives findings for access granted to an account you trust
+        res.push(skeleton!(
+            AccessAnalyzerResourceAddress::ArchiveRule {
+                region:   String::from("[region]"),
+                analyzer: String::from("[analyzer_name]"),
+                name:     String::from("[rule_name]"),
+            },
+            AccessAnalyzerResource::ArchiveRule(ArchiveRule {
+                filter: HashMap::from([(
+                    String::from("principal.AWS"),
+                    Criterion {
+                        eq: vec![String::from("[account_id]")],
+                        ..Default::default()
+                    },
+                )]),
+            })
+        ));
+
+        // Active findings report task skeleton
+        res.push(skeleton!(
+            AccessAnalyzerTaskAddress::ListFindings {
+                name: String::from("[task_name]"),
+            },
+            AccessAnalyzerTask::ListFindings(ListFindings {
+                region:      String::from("[region]"),
+                analyzer:    String::from("[analyzer_name]"),
+                filter:      HashMap::new(),
+                report_path: None, // Defaults to aws/accessanalyzer/reports/[task_name].ron
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = AccessAnalyzerResourceAddress::from_path(addr)?;
+
+        match addr {
+            AccessAnalyzerResourceAddress::Analyzer { .. } => ron_check_eq::<Analyzer>(a, b),
+            AccessAnalyzerResourceAddress::ArchiveRule { .. } => ron_check_eq::<ArchiveRule>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        if let Ok(addr) = AccessAnalyzerTaskAddress::from_path(addr) {
+            return match addr {
+                AccessAnalyzerTaskAddress::ListFindings { .. } => ron_check_syntax::<ListFindings>(a),
+            };
+        }
+
+        let addr = AccessAnalyzerResourceAddress::from_path(addr)?;
+
+        match addr {
+            AccessAnalyzerResourceAddress::Analyzer { .. } => ron_check_syntax::<Analyzer>(a),
+            AccessAnalyzerResourceAddress::ArchiveRule { .. } => ron_check_syntax::<ArchiveRule>(a),
+        }
+    }
+}
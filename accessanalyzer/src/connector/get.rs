@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_accessanalyzer::types::AnalyzerConfiguration;
+
+use crate::{
+    addr::AccessAnalyzerResourceAddress,
+    op_impl::{find_analyzer, find_archive_rule},
+    resource::{AccessAnalyzerResource, Analyzer, ArchiveRule},
+    tags::Tags,
+    util::filter_from_sdk,
+};
+
+use super::AccessAnalyzerConnector;
+
+impl AccessAnalyzerConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = AccessAnalyzerResourceAddress::from_path(addr)?;
+
+        match &addr {
+            AccessAnalyzerResourceAddress::Analyzer { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(analyzer) = find_analyzer(&client, name).await? else {
+                    return Ok(None);
+                };
+
+                let unused_access_age = match &analyzer.configuration {
+                    Some(AnalyzerConfiguration::UnusedAccess(config)) => config.unused_access_age,
+                    _ => None,
+                };
+
+                let resource = Analyzer {
+                    analyzer_type: analyzer.r#type.as_str().to_string(),
+                    unused_access_age,
+                    tags: Tags::from(analyzer.tags),
+                };
+
+                get_resource_response!(
+                    AccessAnalyzerResource::Analyzer(resource),
+                    [(String::from("analyzer_arn"), analyzer.arn)]
+                )
+            }
+            AccessAnalyzerResourceAddress::ArchiveRule { region, analyzer, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(rule) = find_archive_rule(&client, analyzer, name).await? else {
+                    return Ok(None);
+                };
+
+                Ok(Some(GetResourceResponse {
+                    resource_definition: AccessAnalyzerResource::ArchiveRule(ArchiveRule {
+                        filter: filter_from_sdk(rule.filter),
+                    })
+                    .to_bytes()?,
+                    virt_addr: None,
+                    outputs: None,
+                }))
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::addr::AccessAnalyzerResourceAddress;
+
+use super::AccessAnalyzerConnector;
+
+impl AccessAnalyzerConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut next_token = None;
+            loop {
+                let resp = client.list_analyzers().set_next_token(next_token).send().await?;
+
+                for analyzer in resp.analyzers {
+                    results.push(
+                        AccessAnalyzerResourceAddress::Analyzer {
+                            region: region.clone(),
+                            name:   analyzer.name.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+
+                    let mut rules_next_token = None;
+                    loop {
+                        let rules_resp = client
+                            .list_archive_rules()
+                            .analyzer_name(&analyzer.name)
+                            .set_next_token(rules_next_token)
+                            .send()
+                            .await?;
+
+                        for rule in rules_resp.archive_rules {
+                            results.push(
+                                AccessAnalyzerResourceAddress::ArchiveRule {
+                                    region:   region.clone(),
+                                    analyzer: analyzer.name.clone(),
+                                    name:     rule.rule_name,
+                                }
+                                .to_path_buf(),
+                            );
+                        }
+
+                        rules_next_token = rules_resp.next_token;
+                        if rules_next_token.is_none() {
+                            break;
+                        }
+                    }
+                }
+
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
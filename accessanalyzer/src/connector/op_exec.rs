@@ -0,0 +1,47 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{ConnectorOp, OpExecResponse, ResourceAddress},
+    error_util::invalid_op,
+};
+
+use crate::{addr::AccessAnalyzerResourceAddress, op::AccessAnalyzerConnectorOp, op_impl};
+
+use super::AccessAnalyzerConnector;
+
+impl AccessAnalyzerConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = AccessAnalyzerResourceAddress::from_path(addr)?;
+        let op = AccessAnalyzerConnectorOp::from_str(op)?;
+
+        match &addr {
+            AccessAnalyzerResourceAddress::Analyzer { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    AccessAnalyzerConnectorOp::CreateAnalyzer(analyzer) => op_impl::create_analyzer(&client, name, &analyzer).await,
+                    AccessAnalyzerConnectorOp::UpdateAnalyzer(analyzer) => op_impl::update_analyzer(&client, name, &analyzer).await,
+                    AccessAnalyzerConnectorOp::UpdateAnalyzerTags(old_tags, new_tags) => {
+                        op_impl::update_analyzer_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    AccessAnalyzerConnectorOp::DeleteAnalyzer => op_impl::delete_analyzer(&client, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            AccessAnalyzerResourceAddress::ArchiveRule { region, analyzer, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    AccessAnalyzerConnectorOp::CreateArchiveRule(rule) => {
+                        op_impl::create_archive_rule(&client, analyzer, name, &rule).await
+                    }
+                    AccessAnalyzerConnectorOp::UpdateArchiveRule(rule) => {
+                        op_impl::update_archive_rule(&client, analyzer, name, &rule).await
+                    }
+                    AccessAnalyzerConnectorOp::DeleteArchiveRule => op_impl::delete_archive_rule(&client, analyzer, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::{
+    resource::{Analyzer, ArchiveRule},
+    util::is_unused_access,
+};
+
+use super::{AccessAnalyzerConnector, AccessAnalyzerConnectorOp, AccessAnalyzerResourceAddress};
+
+const ANALYZER_TYPES: &[&str] = &["ACCOUNT", "ORGANIZATION", "ACCOUNT_UNUSED_ACCESS", "ORGANIZATION_UNUSED_ACCESS"];
+
+fn check_analyzer(name: &str, analyzer: &Analyzer) -> anyhow::Result<()> {
+    if !ANALYZER_TYPES.contains(&analyzer.analyzer_type.as_str()) {
+        bail!(
+            "Analyzer {} has type {}: expected one of {}",
+            name,
+            analyzer.analyzer_type,
+            ANALYZER_TYPES.join(", ")
+        );
+    }
+    if let Some(age) = analyzer.unused_access_age {
+        if !is_unused_access(&analyzer.analyzer_type) {
+            bail!(
+                "Analyzer {} has unused_access_age, but only unused access analyzers have one",
+                name
+            );
+        }
+        if !(1..=365).contains(&age) {
+            bail!("Analyzer {} has unused access age {}: expected 1 to 365 days", name, age);
+        }
+    }
+    Ok(())
+}
+
+fn check_archive_rule(analyzer: &str, name: &str, rule: &ArchiveRule) -> anyhow::Result<()> {
+    if rule.filter.is_empty() {
+        bail!("Archive rule {} on analyzer {} has an empty filter", name, analyzer);
+    }
+    for (key, criterion) in &rule.filter {
+        if criterion.eq.is_empty() && criterion.neq.is_empty() && criterion.contains.is_empty() && criterion.exists.is_none() {
+            bail!(
+                "Archive rule {} on analyzer {} has no criterion for {}: set eq, neq, contains or exists",
+                name,
+                analyzer,
+                key
+            );
+        }
+    }
+    Ok(())
+}
+
+impl AccessAnalyzerConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = AccessAnalyzerResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            AccessAnalyzerResourceAddress::Analyzer { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_analyzer)) => {
+                    let new_analyzer: Analyzer = RON.from_str(&new_analyzer)?;
+                    check_analyzer(name, &new_analyzer)?;
+                    Ok(vec![connector_op!(
+                        AccessAnalyzerConnectorOp::CreateAnalyzer(new_analyzer),
+                        format!("Create new analyzer {} in {}", name, region)
+                    )])
+                }
+                (Some(_old_analyzer), None) => Ok(vec![connector_op!(
+                    AccessAnalyzerConnectorOp::DeleteAnalyzer,
+                    format!("DELETE analyzer {} in {}, along with its archive rules and findings", name, region)
+                )]),
+                (Some(old_analyzer), Some(new_analyzer)) => {
+                    let old_analyzer: Analyzer = RON.from_str(&old_analyzer)?;
+                    let mut new_analyzer: Analyzer = RON.from_str(&new_analyzer)?;
+                    check_analyzer(name, &new_analyzer)?;
+
+                    if old_analyzer.analyzer_type != new_analyzer.analyzer_type {
+                        bail!(
+                            "Analyzer {} can't change analyzer_type after creation. Create a new analyzer under another name instead.",
+                            name
+                        );
+                    }
+                    // Access Analyzer reports the default unused access age once the analyzer exists
+                    if new_analyzer.unused_access_age.is_none() {
+                        new_analyzer.unused_access_age = old_analyzer.unused_access_age;
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_analyzer.tags != new_analyzer.tags {
+                        let diff = diff_ron_values(&old_analyzer.tags, &new_analyzer.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            AccessAnalyzerConnectorOp::UpdateAnalyzerTags(old_analyzer.tags.clone(), new_analyzer.tags.clone()),
+                            format!("Modify tags for analyzer `{}` in {}\n{}", name, region, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_analyzer.clone();
+                    old_settings.tags = new_analyzer.tags.clone();
+                    if old_settings != new_analyzer {
+                        let diff = diff_ron_values(&old_settings, &new_analyzer).unwrap_or_default();
+                        ops.push(connector_op!(
+                            AccessAnalyzerConnectorOp::UpdateAnalyzer(new_analyzer),
+                            format!("Modify analyzer `{}` in {}\n{}", name, region, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            AccessAnalyzerResourceAddress::ArchiveRule { region, analyzer, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_rule)) => {
+                    let new_rule: ArchiveRule = RON.from_str(&new_rule)?;
+                    check_archive_rule(analyzer, name, &new_rule)?;
+                    Ok(vec![connector_op!(
+                        AccessAnalyzerConnectorOp::CreateArchiveRule(new_rule),
+                        format!("Create new archive rule {} on analyzer {} in {}", name, analyzer, region)
+                    )])
+                }
+                (Some(_old_rule), None) => Ok(vec![connector_op!(
+                    AccessAnalyzerConnectorOp::DeleteArchiveRule,
+                    format!(
+                        "DELETE archive rule {} on analyzer {} in {}. Findings it already archived stay archived.",
+                        name, analyzer, region
+                    )
+                )]),
+                (Some(old_rule), Some(new_rule)) => {
+                    let old_rule: ArchiveRule = RON.from_str(&old_rule)?;
+                    let new_rule: ArchiveRule = RON.from_str(&new_rule)?;
+                    check_archive_rule(analyzer, name, &new_rule)?;
+
+                    if old_rule == new_rule {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_rule, &new_rule).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        AccessAnalyzerConnectorOp::UpdateArchiveRule(new_rule),
+                        format!("Modify archive rule `{}` on analyzer `{}` in {}\n{}", name, analyzer, region, diff)
+                    )])
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use anyhow::{Context, Result};
+use autoschematic_core::{
+    connector::{Resource, ResourceAddress, TaskExecResponse},
+    util::{PrettyConfig, RON},
+};
+use aws_smithy_types::{DateTime, date_time::Format};
+
+use crate::{
+    op_impl::find_analyzer,
+    resource::Criterion,
+    task::{AccessAnalyzerTask, AccessAnalyzerTaskAddress, FindingsReport, ListFindings, ReportedFinding},
+    util::filter_to_sdk,
+};
+
+use super::AccessAnalyzerConnector;
+
+impl AccessAnalyzerConnector {
+    pub async fn do_task_exec(
+        &self,
+        addr: &Path,
+        body: Vec<u8>,
+        _arg: Option<Vec<u8>>,
+        _state: Option<Vec<u8>>,
+    ) -> Result<TaskExecResponse> {
+        let addr = AccessAnalyzerTaskAddress::from_path(addr)?;
+
+        let task = AccessAnalyzerTask::from_bytes(&addr, &body)?;
+        match (&addr, task) {
+            (AccessAnalyzerTaskAddress::ListFindings { name }, AccessAnalyzerTask::ListFindings(list)) => {
+                self.list_findings(name, list).await
+            }
+        }
+    }
+
+    async fn list_findings(&self, task_name: &str, list: ListFindings) -> Result<TaskExecResponse> {
+        let client = self.get_or_init_client(&list.region).await?;
+
+        let analyzer_arn = find_analyzer(&client, &list.analyzer)
+            .await?
+            .map(|analyzer| analyzer.arn)
+            .with_context(|| format!("Analyzer {} not found in {}", list.analyzer, list.region))?;
+
+        // Archived and resolved findings are never reported
+        let mut filter = list.filter.clone();
+        filter.insert(
+            String::from("status"),
+            Criterion {
+                eq: vec![String::from("ACTIVE")],
+                ..Default::default()
+            },
+        );
+        let filter = filter_to_sdk(&filter);
+
+        let mut findings = Vec::new();
+        let mut next_token = None;
+        loop {
+            let resp = client
+                .list_findings_v2()
+                .analyzer_arn(&analyzer_arn)
+                .set_filter(Some(filter.clone()))
+                .set_next_token(next_token)
+                .send()
+                .await?;
+
+            for finding in resp.findings {
+                findings.push(ReportedFinding {
+                    id: finding.id,
+                    finding_type: finding.finding_type.map(|t| t.as_str().to_string()),
+                    resource_type: finding.resource_type.as_str().to_string(),
+                    resource: finding.resource,
+                    resource_owner_account: finding.resource_owner_account,
+                    updated_at: finding.updated_at.fmt(Format::DateTime)?,
+                });
+            }
+
+            next_token = resp.next_token;
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        findings.sort_by(|a, b| (&a.resource_type, &a.resource, &a.id).cmp(&(&b.resource_type, &b.resource, &b.id)));
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for finding in &findings {
+            let finding_type = finding.finding_type.clone().unwrap_or_else(|| String::from("Unknown"));
+            *counts.entry(finding_type).or_default() += 1;
+        }
+        let mut outputs: HashMap<String, Option<String>> = counts
+            .into_iter()
+            .map(|(finding_type, count)| (format!("{}/count", finding_type), Some(count.to_string())))
+            .collect();
+        outputs.insert(String::from("total/count"), Some(findings.len().to_string()));
+
+        let report_path = PathBuf::from(
+            list.report_path
+                .unwrap_or_else(|| format!("aws/accessanalyzer/reports/{}.ron", task_name)),
+        );
+        let full_path = self.prefix.join(&report_path);
+
+        let friendly_message = format!(
+            "Analyzer {} in {} has {} active findings; wrote the report to {}",
+            list.analyzer,
+            list.region,
+            findings.len(),
+            report_path.display()
+        );
+
+        let report = FindingsReport {
+            region: list.region,
+            analyzer: list.analyzer,
+            generated_at: DateTime::from(SystemTime::now()).fmt(Format::DateTime)?,
+            findings,
+        };
+
+        if let Some(parent) = full_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&full_path, RON.to_string_pretty(&report, PrettyConfig::default())?)?;
+
+        Ok(TaskExecResponse {
+            modified_files: Some(vec![report_path]),
+            outputs: Some(outputs),
+            friendly_message: Some(friendly_message),
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,36 @@
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{Analyzer, ArchiveRule},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum AccessAnalyzerConnectorOp {
+    CreateAnalyzer(Analyzer),
+    /// Applies an unused access analyzer's unused access age.
+    UpdateAnalyzer(Analyzer),
+    UpdateAnalyzerTags(Tags, Tags),
+    /// Deletes the analyzer along with its archive rules and findings.
+    DeleteAnalyzer,
+
+    CreateArchiveRule(ArchiveRule),
+    UpdateArchiveRule(ArchiveRule),
+    DeleteArchiveRule,
+}
+
+impl ConnectorOp for AccessAnalyzerConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
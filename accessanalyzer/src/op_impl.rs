@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use autoschematic_core::{connector::OpExecResponse, op_exec_output};
+use aws_sdk_accessanalyzer::types::{AnalyzerConfiguration, Type, UnusedAccessConfiguration};
+
+use crate::{
+    resource::{Analyzer, ArchiveRule},
+    tags::{Tags, tag_diff},
+    util::{filter_to_sdk, is_unused_access},
+};
+
+fn client_token() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+pub async fn find_analyzer(
+    client: &aws_sdk_accessanalyzer::Client,
+    name: &str,
+) -> anyhow::Result<Option<aws_sdk_accessanalyzer::types::AnalyzerSummary>> {
+    match client.get_analyzer().analyzer_name(name).send().await {
+        Ok(resp) => Ok(resp.analyzer),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub async fn find_archive_rule(
+    client: &aws_sdk_accessanalyzer::Client,
+    analyzer: &str,
+    name: &str,
+) -> anyhow::Result<Option<aws_sdk_accessanalyzer::types::ArchiveRuleSummary>> {
+    match client.get_archive_rule().analyzer_name(analyzer).rule_name(name).send().await {
+        Ok(resp) => Ok(resp.archive_rule),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn analyzer_arn(client: &aws_sdk_accessanalyzer::Client, name: &str) -> anyhow::Result<String> {
+    find_analyzer(client, name)
+        .await?
+        .map(|analyzer| analyzer.arn)
+        .with_context(|| format!("Analyzer {} not found", name))
+}
+
+/// Only unused access analyzers have a configuration.
+fn analyzer_configuration(analyzer: &Analyzer) -> Option<AnalyzerConfiguration> {
+    if !is_unused_access(&analyzer.analyzer_type) {
+        return None;
+    }
+    Some(AnalyzerConfiguration::UnusedAccess(
+        UnusedAccessConfiguration::builder()
+            .set_unused_access_age(analyzer.unused_access_age)
+            .build(),
+    ))
+}
+
+pub async fn create_analyzer(
+    client: &aws_sdk_accessanalyzer::Client,
+    name: &str,
+    analyzer: &Analyzer,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_analyzer()
+        .analyzer_name(name)
+        .r#type(Type::from(analyzer.analyzer_type.as_str()))
+        .set_configuration(analyzer_configuration(analyzer))
+        .set_tags(analyzer.tags.clone().into())
+        .client_token(client_token())
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("analyzer_arn"), resp.arn)])),
+        friendly_message: Some(format!("Created analyzer {}", name)),
+    })
+}
+
+pub async fn update_analyzer(
+    client: &aws_sdk_accessanalyzer::Client,
+    name: &str,
+    analyzer: &Analyzer,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .update_analyzer()
+        .analyzer_name(name)
+        .set_configuration(analyzer_configuration(analyzer))
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Updated analyzer {}", name))
+}
+
+pub async fn update_analyzer_tags(
+    client: &aws_sdk_accessanalyzer::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = analyzer_arn(client, name).await?;
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(&arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client.tag_resource().resource_arn(&arn).set_tags(Some(new_tagset)).send().await?;
+    }
+
+    op_exec_output!(format!("Updated tags for analyzer {}", name))
+}
+
+pub async fn delete_analyzer(client: &aws_sdk_accessanalyzer::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    client
+        .delete_analyzer()
+        .analyzer_name(name)
+        .client_token(client_token())
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("analyzer_arn"), None)])),
+        friendly_message: Some(format!("Deleted analyzer {}", name)),
+    })
+}
+
+pub async fn create_archive_rule(
+    client: &aws_sdk_accessanalyzer::Client,
+    analyzer: &str,
+    name: &str,
+    rule: &ArchiveRule,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .create_archive_rule()
+        .analyzer_name(analyzer)
+        .rule_name(name)
+        .set_filter(Some(filter_to_sdk(&rule.filter)))
+        .client_token(client_token())
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Created archive rule {} on analyzer {}", name, analyzer))
+}
+
+pub async fn update_archive_rule(
+    client: &aws_sdk_accessanalyzer::Client,
+    analyzer: &str,
+    name: &str,
+    rule: &ArchiveRule,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .update_archive_rule()
+        .analyzer_name(analyzer)
+        .rule_name(name)
+        .set_filter(Some(filter_to_sdk(&rule.filter)))
+        .client_token(client_token())
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Updated archive rule {} on analyzer {}", name, analyzer))
+}
+
+pub async fn delete_archive_rule(
+    client: &aws_sdk_accessanalyzer::Client,
+    analyzer: &str,
+    name: &str,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .delete_archive_rule()
+        .analyzer_name(analyzer)
+        .rule_name(name)
+        .client_token(client_token())
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Deleted archive rule {} on analyzer {}", name, analyzer))
+}
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::AccessAnalyzerResourceAddress, tags::Tags};
+
+/// An analyzer. Its type is fixed at creation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Analyzer {
+    /// ACCOUNT or ORGANIZATION for external access, or ACCOUNT_UNUSED_ACCESS or ORGANIZATION_UNUSED_ACCESS.
+    /// Organization analyzers must be created in the management account or a delegated administrator.
+    pub analyzer_type: String,
+    /// For unused access analyzers, the days without use after which access is reported as unused.
+    /// If None, 90 days.
+    pub unused_access_age: Option<i32>,
+    pub tags: Tags,
+}
+
+/// Matches one finding attribute. Each field that's set has to match.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Criterion {
+    #[serde(default)]
+    pub eq: Vec<String>,
+    #[serde(default)]
+    pub neq: Vec<String>,
+    #[serde(default)]
+    pub contains: Vec<String>,
+    #[serde(default)]
+    pub exists: Option<bool>,
+}
+
+/// Archives new and updated findings that match every criterion in the filter.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ArchiveRule {
+    /// Keyed by finding attribute, e.g. `{"principal.AWS": Criterion(eq: ["123456789012"])}`.
+    pub filter: HashMap<String, Criterion>,
+}
+
+pub enum AccessAnalyzerResource {
+    Analyzer(Analyzer),
+    ArchiveRule(ArchiveRule),
+}
+
+impl Resource for AccessAnalyzerResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            AccessAnalyzerResource::Analyzer(analyzer) => Ok(RON.to_string_pretty(&analyzer, pretty_config)?.into()),
+            AccessAnalyzerResource::ArchiveRule(rule) => Ok(RON.to_string_pretty(&rule, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = AccessAnalyzerResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            AccessAnalyzerResourceAddress::Analyzer { .. } => Ok(AccessAnalyzerResource::Analyzer(RON.from_str(s)?)),
+            AccessAnalyzerResourceAddress::ArchiveRule { .. } => Ok(AccessAnalyzerResource::ArchiveRule(RON.from_str(s)?)),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+// Access Analyzer takes tags as a plain map rather than a list of Tag structs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Tags(HashMap<String, String>);
+
+impl From<Option<HashMap<String, String>>> for Tags {
+    fn from(value: Option<HashMap<String, String>>) -> Self {
+        Tags(value.unwrap_or_default())
+    }
+}
+
+impl From<Tags> for Option<HashMap<String, String>> {
+    fn from(val: Tags) -> Self {
+        if val.0.is_empty() { None } else { Some(val.0) }
+    }
+}
+
+// From a pair of tag maps, determine the keys to untag and the tags to set respectively
+pub fn tag_diff(old_tags: &Tags, new_tags: &Tags) -> (Vec<String>, HashMap<String, String>) {
+    let untag_keys = old_tags.0.keys().filter(|k| !new_tags.0.contains_key(*k)).cloned().collect();
+
+    let new_tagset = new_tags
+        .0
+        .iter()
+        .filter(|(k, v)| old_tags.0.get(*k) != Some(*v))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+
+    (untag_keys, new_tagset)
+}
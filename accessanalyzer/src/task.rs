@@ -0,0 +1,123 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::{PrettyConfig, RON};
+
+use crate::resource::Criterion;
+
+#[derive(Debug, Clone)]
+pub enum AccessAnalyzerTaskAddress {
+    ListFindings { name: String },
+}
+
+impl ResourceAddress for AccessAnalyzerTaskAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            AccessAnalyzerTaskAddress::ListFindings { name } => {
+                PathBuf::from(format!("aws/accessanalyzer/tasks/list-findings/{name}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        let path_components: Vec<&str> = path
+            .components()
+            .map(|s| s.as_os_str().to_str().context("Path component is not valid UTF-8"))
+            .collect::<Result<Vec<&str>, anyhow::Error>>()?;
+
+        match &path_components[..] {
+            ["aws", "accessanalyzer", "tasks", "list-findings", name] if name.ends_with(".ron") => {
+                Ok(AccessAnalyzerTaskAddress::ListFindings {
+                    name: name.strip_suffix(".ron").context("File name must end with .ron")?.to_string(),
+                })
+            }
+            _ => Err(anyhow::anyhow!("Invalid Access Analyzer task address: {}", path.display())),
+        }
+    }
+}
+
+impl DescribeAddresses for AccessAnalyzerTaskAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![AddressPattern {
+            pattern:     "aws/accessanalyzer/tasks/list-findings/<name>.ron",
+            description: "Task: write a report of an analyzer's active findings",
+            example:     "aws/accessanalyzer/tasks/list-findings/weekly-review.ron",
+        }]
+    }
+}
+
+/// Lists the active findings of `analyzer` in `region`, and writes them to a report file.
+/// The number of findings of each type is reported in the task outputs as `{finding_type}/count`,
+/// alongside `total/count`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ListFindings {
+    pub region: String,
+    pub analyzer: String,
+    /// Only findings matching every criterion, e.g. `{"resourceType": Criterion(eq: ["AWS::S3::Bucket"])}`.
+    #[serde(default)]
+    pub filter: HashMap<String, Criterion>,
+    /// Where to write the report, relative to the repo root.
+    /// Defaults to aws/accessanalyzer/reports/<task name>.ron.
+    #[serde(default)]
+    pub report_path: Option<String>,
+}
+
+/// The report written by a ListFindings task.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FindingsReport {
+    pub region: String,
+    pub analyzer: String,
+    pub generated_at: String,
+    pub findings: Vec<ReportedFinding>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReportedFinding {
+    pub id: String,
+    /// e.g. ExternalAccess or UnusedIAMRole.
+    pub finding_type: Option<String>,
+    pub resource_type: String,
+    pub resource: Option<String>,
+    pub resource_owner_account: String,
+    pub updated_at: String,
+}
+
+pub enum AccessAnalyzerTask {
+    ListFindings(ListFindings),
+}
+
+impl Resource for AccessAnalyzerTask {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = PrettyConfig::default().struct_names(true);
+        match self {
+            AccessAnalyzerTask::ListFindings(list) => match RON.to_string_pretty(&list, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = AccessAnalyzerTaskAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+        match addr {
+            AccessAnalyzerTaskAddress::ListFindings { .. } => Ok(AccessAnalyzerTask::ListFindings(RON.from_str(s)?)),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+use crate::resource::Criterion;
+
+fn non_empty(values: &[String]) -> Option<Vec<String>> {
+    if values.is_empty() { None } else { Some(values.to_vec()) }
+}
+
+pub fn filter_to_sdk(filter: &HashMap<String, Criterion>) -> HashMap<String, aws_sdk_accessanalyzer::types::Criterion> {
+    filter
+        .iter()
+        .map(|(key, criterion)| {
+            (
+                key.clone(),
+                aws_sdk_accessanalyzer::types::Criterion::builder()
+                    .set_eq(non_empty(&criterion.eq))
+                    .set_neq(non_empty(&criterion.neq))
+                    .set_contains(non_empty(&criterion.contains))
+                    .set_exists(criterion.exists)
+                    .build(),
+            )
+        })
+        .collect()
+}
+
+pub fn filter_from_sdk(filter: HashMap<String, aws_sdk_accessanalyzer::types::Criterion>) -> HashMap<String, Criterion> {
+    filter
+        .into_iter()
+        .map(|(key, criterion)| {
+            (
+                key,
+                Criterion {
+                    eq:       criterion.eq.unwrap_or_default(),
+                    neq:      criterion.neq.unwrap_or_default(),
+                    contains: criterion.contains.unwrap_or_default(),
+                    exists:   criterion.exists,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Whether the analyzer type reports unused access rather than external access.
+pub fn is_unused_access(analyzer_type: &str) -> bool {
+    analyzer_type.ends_with("_UNUSED_ACCESS")
+}
@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
 use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 
 #[derive(Debug, Clone)]
@@ -16,19 +17,6 @@ impl AcmResourceAddress {
             }
         }
     }
-
-    /// Extract certificate ID from a certificate ARN
-    pub fn from_certificate_arn(arn: &str) -> Option<String> {
-        // ACM ARN format: arn:aws:acm:region:account:certificate/certificate-id
-        arn.split('/').next_back().map(|s| s.to_string())
-    }
-
-    /// Get the region from this address
-    pub fn region(&self) -> &str {
-        match self {
-            AcmResourceAddress::Certificate { region, .. } => region,
-        }
-    }
 }
 
 impl ResourceAddress for AcmResourceAddress {
@@ -55,3 +43,27 @@ impl ResourceAddress for AcmResourceAddress {
         }
     }
 }
+
+impl DescribeAddresses for AcmResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/acm/<region>/certificates/<certificate_id>.ron",
+                description: "An ACM certificate, by the ID at the end of its ARN",
+                example:     "aws/acm/us-east-1/certificates/0a1b2c3d-4e5f-6789-abcd-ef0123456789.ron",
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use autoschematic_connector_aws_core::addr_doc::DescribeAddresses;
+
+    use super::AcmResourceAddress;
+
+    #[test]
+    fn address_examples_parse() {
+        AcmResourceAddress::checked_address_patterns().unwrap();
+    }
+}
@@ -15,6 +15,8 @@ pub struct AcmConnectorConfig {
     pub timeout_config:  Option<TimeoutConfig>,
     pub sts_region:      String,
     pub enabled_regions: Vec<String>,
+    #[serde(default)]
+    pub max_concurrent_ops: Option<usize>,
 }
 
 impl_aws_config!(AcmConnectorConfig, "aws/acm/config.ron");
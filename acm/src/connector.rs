@@ -4,13 +4,18 @@ use std::{
     sync::Arc,
 };
 
-use crate::{addr::AcmResourceAddress, config::AcmConnectorConfig, resource::AcmCertificate};
+use crate::{
+    addr::AcmResourceAddress,
+    config::AcmConnectorConfig,
+    resource::AcmCertificate,
+    task::{AcmTask, AcmTaskAddress, ExportCertificate},
+};
 use async_trait::async_trait;
-use autoschematic_connector_aws_core::config::AwsServiceConfig;
+use autoschematic_connector_aws_core::{audit::AuditLog, concurrency::OpExecLimiter, config::AwsServiceConfig};
 use autoschematic_core::{
     connector::{
         Connector, ConnectorOutbox, DocIdent, FilterResponse, GetDocResponse, GetResourceResponse, OpExecResponse, PlanResponseElement,
-        Resource, ResourceAddress, SkeletonResponse, VirtToPhyResponse,
+        Resource, ResourceAddress, SkeletonResponse, TaskExecResponse, VirtToPhyResponse,
     },
     diag::DiagnosticResponse,
     skeleton,
@@ -28,6 +33,7 @@ mod get_doc;
 mod list;
 mod op_exec;
 mod plan;
+mod task_exec;
 
 pub mod client_cache;
 
@@ -37,6 +43,8 @@ pub struct AcmConnector {
     pub config: RwLock<AcmConnectorConfig>,
     pub account_id: RwLock<Option<String>>,
     pub prefix: PathBuf,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
 }
 
 #[async_trait]
@@ -44,6 +52,8 @@ impl Connector for AcmConnector {
     async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
         if let Ok(_addr) = AcmResourceAddress::from_path(addr) {
             Ok(FilterResponse::Resource)
+        } else if let Ok(_addr) = AcmTaskAddress::from_path(addr) {
+            Ok(FilterResponse::Task)
         } else {
             Ok(FilterResponse::None)
         }
@@ -65,6 +75,8 @@ impl Connector for AcmConnector {
         let account_id = ecr_config.verify_sts().await?;
 
         *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(ecr_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
         *self.config.write().await = ecr_config;
         *self.account_id.write().await = Some(account_id);
         Ok(())
@@ -99,7 +111,19 @@ impl Connector for AcmConnector {
     }
 
     async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
-        self.do_op_exec(addr, op).await
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn task_exec(
+        &self,
+        addr: &Path,
+        body: Vec<u8>,
+        arg: Option<Vec<u8>>,
+        state: Option<Vec<u8>>,
+    ) -> anyhow::Result<TaskExecResponse> {
+        self.do_task_exec(addr, body, arg, state).await
     }
 
     async fn addr_virt_to_phy(&self, addr: &Path) -> anyhow::Result<VirtToPhyResponse> {
@@ -158,6 +182,8 @@ impl Connector for AcmConnector {
                     }
                 ],
                 certificate_transparency_logging_preference: Some(String::from("ENABLED")),
+                certificate_authority_arn: None,
+                export: Some(String::from("DISABLED")),
                 tags: Tags::default(),
             })
         ));
@@ -177,10 +203,43 @@ impl Connector for AcmConnector {
                     validation_domain: Some(String::from("example.com")),
                 }],
                 certificate_transparency_logging_preference: Some(String::from("DISABLED")),
+                certificate_authority_arn: None,
+                export: None,
                 tags: Tags::default(),
             })
         ));
 
+        // ACM Certificate skeleton for a private certificate issued by AWS Private CA
+        res.push(skeleton!(
+            AcmResourceAddress::Certificate {
+                region: String::from("us-east-1"),
+                certificate_id: String::from("[certificate-id-private]"),
+            },
+            AcmResource::Certificate(AcmCertificate {
+                domain_name: String::from("internal.example.com"),
+                subject_alternative_names: vec![],
+                validation_method: String::from("DNS"), // Ignored for private certificates
+                validation_options: vec![],
+                certificate_transparency_logging_preference: Some(String::from("DISABLED")),
+                certificate_authority_arn: Some(String::from(
+                    "arn:aws:acm-pca:us-east-1:[account_id]:certificate-authority/[ca-id]"
+                )),
+                export: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        // Certificate export task skeleton
+        res.push(skeleton!(
+            AcmTaskAddress::ExportCertificate {
+                name: String::from("[task_name]"),
+            },
+            AcmTask::ExportCertificate(ExportCertificate {
+                certificate_arn: String::from("arn:aws:acm:us-east-1:[account_id]:certificate/[certificate-id]"),
+                secret_dir: String::from("aws/acm/us-east-1/exported/[certificate-id]"),
+            })
+        ));
+
         Ok(res)
     }
 
@@ -1,6 +1,7 @@
 use std::{sync::Arc, time::Duration};
 
 use anyhow::bail;
+use autoschematic_connector_aws_core::audited_client;
 use aws_config::{meta::region::RegionProviderChain, timeout::TimeoutConfig, BehaviorVersion};
 
 use super::AcmConnector;
@@ -25,7 +26,7 @@ impl AcmConnector {
                 )
                 .load()
                 .await;
-            let client = aws_sdk_acm::Client::new(&config);
+            let client = audited_client!(aws_sdk_acm, &config);
             cache.insert(region_s.to_string(), Arc::new(client));
         };
 
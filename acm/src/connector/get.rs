@@ -6,9 +6,6 @@ use autoschematic_core::{
     get_resource_response,
 };
 
-use anyhow::Context;
-use std::collections::HashMap;
-
 use crate::{
     addr::AcmResourceAddress,
     resource::{AcmCertificate, AcmResource, ValidationOption},
@@ -78,12 +75,19 @@ impl AcmConnector {
                                 .and_then(|opts| opts.certificate_transparency_logging_preference())
                                 .map(|pref| pref.as_str().to_string());
 
+                            let export = certificate
+                                .options()
+                                .and_then(|opts| opts.export())
+                                .map(|export| export.as_str().to_string());
+
                             let acm_certificate = AcmCertificate {
                                 domain_name,
                                 subject_alternative_names,
                                 validation_method,
                                 validation_options,
                                 certificate_transparency_logging_preference,
+                                certificate_authority_arn: certificate.certificate_authority_arn().map(|s| s.to_string()),
+                                export,
                                 tags,
                             };
                             let certificate_arn = certificate.certificate_arn.unwrap_or_default();
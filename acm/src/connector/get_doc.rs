@@ -4,7 +4,7 @@ use anyhow::Result;
 use super::AcmConnector;
 
 impl AcmConnector {
-    pub async fn do_get_doc(&self, ident: DocIdent) -> Result<Option<GetDocResponse>> {
+    pub async fn do_get_doc(&self, _ident: DocIdent) -> Result<Option<GetDocResponse>> {
         Ok(None)
     }
 }
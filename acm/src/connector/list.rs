@@ -17,34 +17,31 @@ impl AcmConnector {
                 continue;
             }
 
-            let client = self.get_or_init_client(region).await.unwrap();
+            let client = self.get_or_init_client(region).await?;
 
             let mut next_token: Option<String> = None;
-            let mut request = client.list_certificates();
-            if let Some(token) = &next_token {
-                request = request.next_token(token);
-            }
-
-            let response = request.send().await?;
-
-            if let Some(cert_list) = response.certificate_summary_list {
-                for cert in cert_list {
-                    let Some(certificate_id) = cert.certificate_arn.and_then(|arn| extract_certificate_id(&arn)) else {
-                        bail!("Cert has no ARN?");
-                    };
-                    results.push(
-                        AcmResourceAddress::Certificate {
-                            region: region.into(),
-                            certificate_id,
-                        }
-                        .to_path_buf(),
-                    );
+            loop {
+                let response = client.list_certificates().set_next_token(next_token).send().await?;
+
+                if let Some(cert_list) = response.certificate_summary_list {
+                    for cert in cert_list {
+                        let Some(certificate_id) = cert.certificate_arn.and_then(|arn| extract_certificate_id(&arn)) else {
+                            bail!("Cert has no ARN?");
+                        };
+                        results.push(
+                            AcmResourceAddress::Certificate {
+                                region: region.into(),
+                                certificate_id,
+                            }
+                            .to_path_buf(),
+                        );
+                    }
                 }
-            }
 
-            next_token = response.next_token;
-            if next_token.is_none() {
-                break;
+                next_token = response.next_token;
+                if next_token.is_none() {
+                    break;
+                }
             }
         }
 
@@ -27,7 +27,7 @@ impl AcmConnector {
 
         match addr {
             AcmResourceAddress::Certificate { region, .. } => {
-                let client = self.get_or_init_client(&region).await.unwrap();
+                let client = self.get_or_init_client(&region).await?;
                 match op {
                     AcmConnectorOp::RequestCertificate(cert_config) => {
                         // Request a new certificate
@@ -39,30 +39,46 @@ impl AcmConnector {
                                 request.set_subject_alternative_names(Some(cert_config.subject_alternative_names.clone()));
                         }
 
-                        // Set validation method
-                        let validation_method = match cert_config.validation_method.as_str() {
-                            "DNS" => aws_sdk_acm::types::ValidationMethod::Dns,
-                            "EMAIL" => aws_sdk_acm::types::ValidationMethod::Email,
-                            _ => aws_sdk_acm::types::ValidationMethod::Dns, // Default to DNS
-                        };
-                        request = request.validation_method(validation_method);
-
-                        // Set certificate transparency logging preference
-                        if let Some(ct_pref) = &cert_config.certificate_transparency_logging_preference {
-                            let ct_logging = match ct_pref.as_str() {
-                                "ENABLED" => aws_sdk_acm::types::CertificateTransparencyLoggingPreference::Enabled,
-                                "DISABLED" => aws_sdk_acm::types::CertificateTransparencyLoggingPreference::Disabled,
-                                _ => aws_sdk_acm::types::CertificateTransparencyLoggingPreference::Enabled,
+                        if let Some(certificate_authority_arn) = &cert_config.certificate_authority_arn {
+                            // Private certificates are issued by the CA directly, without validation
+                            request = request.certificate_authority_arn(certificate_authority_arn);
+                        } else {
+                            // Set validation method
+                            let validation_method = match cert_config.validation_method.as_str() {
+                                "DNS" => aws_sdk_acm::types::ValidationMethod::Dns,
+                                "EMAIL" => aws_sdk_acm::types::ValidationMethod::Email,
+                                _ => aws_sdk_acm::types::ValidationMethod::Dns, // Default to DNS
                             };
-                            request = request.options(
-                                aws_sdk_acm::types::CertificateOptions::builder()
-                                    .certificate_transparency_logging_preference(ct_logging)
-                                    .build(),
-                            );
+                            request = request.validation_method(validation_method);
+                        }
+
+                        // Set certificate transparency logging preference and export option
+                        if cert_config.certificate_transparency_logging_preference.is_some() || cert_config.export.is_some() {
+                            let mut options = aws_sdk_acm::types::CertificateOptions::builder();
+
+                            if let Some(ct_pref) = &cert_config.certificate_transparency_logging_preference {
+                                let ct_logging = match ct_pref.as_str() {
+                                    "ENABLED" => aws_sdk_acm::types::CertificateTransparencyLoggingPreference::Enabled,
+                                    "DISABLED" => aws_sdk_acm::types::CertificateTransparencyLoggingPreference::Disabled,
+                                    _ => aws_sdk_acm::types::CertificateTransparencyLoggingPreference::Enabled,
+                                };
+                                options = options.certificate_transparency_logging_preference(ct_logging);
+                            }
+
+                            if let Some(export) = &cert_config.export {
+                                let export = match export.as_str() {
+                                    "ENABLED" => aws_sdk_acm::types::CertificateExport::Enabled,
+                                    "DISABLED" => aws_sdk_acm::types::CertificateExport::Disabled,
+                                    _ => bail!("Invalid export option {}, expected ENABLED or DISABLED", export),
+                                };
+                                options = options.export(export);
+                            }
+
+                            request = request.options(options.build());
                         }
 
                         // Add domain validation options if provided
-                        if !cert_config.validation_options.is_empty() {
+                        if cert_config.certificate_authority_arn.is_none() && !cert_config.validation_options.is_empty() {
                             let domain_validation_options: Vec<aws_sdk_acm::types::DomainValidationOption> = cert_config
                                 .validation_options
                                 .iter()
@@ -79,7 +95,7 @@ impl AcmConnector {
                         }
 
                         // Add tags if provided
-                        if cert_config.tags.len() > 0 {
+                        if !cert_config.tags.0.is_empty() {
                             let tags: Option<Vec<aws_sdk_acm::types::Tag>> = cert_config.tags.into();
                             if let Some(tags) = tags {
                                 request = request.set_tags(Some(tags));
@@ -30,12 +30,20 @@ impl AcmConnector {
                         // Certificate doesn't exist, need to create it
                         let desired_cert: AcmCertificate = RON.from_str(&desired_str)?;
 
-                        ops.push(connector_op!(
-                            AcmConnectorOp::RequestCertificate(desired_cert.clone()),
-                            format!(
+                        let message = match &desired_cert.certificate_authority_arn {
+                            Some(certificate_authority_arn) => format!(
+                                "Request new private ACM certificate for domain '{}' from {}",
+                                desired_cert.domain_name, certificate_authority_arn
+                            ),
+                            None => format!(
                                 "Request new ACM certificate for domain '{}' using {} validation",
                                 desired_cert.domain_name, desired_cert.validation_method
-                            )
+                            ),
+                        };
+
+                        ops.push(connector_op!(
+                            AcmConnectorOp::RequestCertificate(desired_cert.clone()),
+                            message
                         ));
                     }
                     (Some(current_str), Some(desired_str)) => {
@@ -75,6 +83,14 @@ impl AcmConnector {
                                 "Cannot modify a certificate's domain, alternative names, or validation method - you must delete and recreate to continue."
                             );
                         }
+
+                        if current_cert.certificate_authority_arn != desired_cert.certificate_authority_arn
+                            || current_cert.export != desired_cert.export
+                        {
+                            bail!(
+                                "Cannot modify a certificate's issuing certificate authority or export option - you must delete and recreate to continue."
+                            );
+                        }
                     }
                     (Some(_), None) => {
                         ops.push(connector_op!(
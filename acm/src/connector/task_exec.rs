@@ -0,0 +1,101 @@
+use std::{collections::HashMap, path::{Path, PathBuf}};
+
+use anyhow::{Result, bail};
+use autoschematic_core::connector::{Resource, ResourceAddress, TaskExecResponse};
+use aws_sdk_acm::{
+    primitives::Blob,
+    types::{CertificateExport, CertificateStatus, CertificateType},
+};
+
+use crate::{
+    task::{AcmTask, AcmTaskAddress, ExportCertificate},
+    util::extract_region_from_arn,
+};
+
+use super::AcmConnector;
+
+impl AcmConnector {
+    pub async fn do_task_exec(
+        &self,
+        addr: &Path,
+        body: Vec<u8>,
+        _arg: Option<Vec<u8>>,
+        _state: Option<Vec<u8>>,
+    ) -> Result<TaskExecResponse> {
+        let addr = AcmTaskAddress::from_path(addr)?;
+
+        let task = AcmTask::from_bytes(&addr, &body)?;
+        match task {
+            AcmTask::ExportCertificate(export) => self.export_certificate(export).await,
+        }
+    }
+
+    async fn export_certificate(&self, export: ExportCertificate) -> Result<TaskExecResponse> {
+        let Some(region) = extract_region_from_arn(&export.certificate_arn) else {
+            bail!("Invalid certificate ARN: {}", export.certificate_arn);
+        };
+        let client = self.get_or_init_client(&region).await?;
+
+        let describe = client
+            .describe_certificate()
+            .certificate_arn(&export.certificate_arn)
+            .send()
+            .await?;
+        let Some(certificate) = describe.certificate else {
+            bail!("Certificate {} not found", export.certificate_arn);
+        };
+
+        if certificate.status() != Some(&CertificateStatus::Issued) {
+            bail!(
+                "Certificate {} can't be exported until it has been issued",
+                export.certificate_arn
+            );
+        }
+
+        let is_private = certificate.r#type() == Some(&CertificateType::Private);
+        let is_exportable = certificate.options().and_then(|o| o.export()) == Some(&CertificateExport::Enabled);
+        if !is_private && !is_exportable {
+            bail!(
+                "Certificate {} is not exportable: only Private CA certificates and public certificates requested with export enabled can be exported",
+                export.certificate_arn
+            );
+        }
+
+        // The private key is only ever handed out encrypted
+        let passphrase = uuid::Uuid::new_v4().simple().to_string();
+
+        let exported = client
+            .export_certificate()
+            .certificate_arn(&export.certificate_arn)
+            .passphrase(Blob::new(passphrase.as_bytes()))
+            .send()
+            .await?;
+
+        let mut secrets = HashMap::new();
+        secrets.insert(
+            PathBuf::from(format!("{}/certificate.pem", export.secret_dir)),
+            exported.certificate,
+        );
+        secrets.insert(
+            PathBuf::from(format!("{}/certificate_chain.pem", export.secret_dir)),
+            exported.certificate_chain,
+        );
+        secrets.insert(
+            PathBuf::from(format!("{}/private_key.pem", export.secret_dir)),
+            exported.private_key,
+        );
+        secrets.insert(
+            PathBuf::from(format!("{}/passphrase", export.secret_dir)),
+            Some(passphrase),
+        );
+
+        Ok(TaskExecResponse {
+            secrets: Some(secrets),
+            friendly_message: Some(format!(
+                "Exported certificate {} to {}",
+                export.certificate_arn, export.secret_dir
+            )),
+            ..Default::default()
+        })
+    }
+}
@@ -1,4 +1,5 @@
-mod addr;
-mod config;
-mod resource;
-mod tags;
+pub mod addr;
+pub mod config;
+pub mod op;
+pub mod resource;
+pub mod tags;
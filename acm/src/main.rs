@@ -1,5 +1,8 @@
+use addr::AcmResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
 use autoschematic_core::tarpc_bridge::tarpc_connector_main;
 use connector::AcmConnector;
+use task::AcmTaskAddress;
 
 pub mod connector;
 pub mod addr;
@@ -7,10 +10,18 @@ pub mod op;
 pub mod resource;
 pub mod config;
 pub mod tags;
+pub mod task;
 pub mod util;
 
 #[tokio::main]
 pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let mut patterns = AcmResourceAddress::checked_address_patterns()?;
+        patterns.extend(AcmTaskAddress::checked_address_patterns()?);
+        print!("{}", render_address_docs("aws/acm", &patterns));
+        return Ok(());
+    }
+
     tarpc_connector_main::<AcmConnector>().await?;
     Ok(())
 }
@@ -20,6 +20,16 @@ pub struct AcmCertificate {
     pub validation_options: Vec<ValidationOption>,
     /// Certificate transparency logging preference: "ENABLED" or "DISABLED"
     pub certificate_transparency_logging_preference: Option<String>,
+    /// The ARN of an AWS Private CA to issue a private certificate from.
+    /// Private certificates need no validation, so validation_method and validation_options are ignored.
+    #[serde(default)]
+    pub certificate_authority_arn: Option<String>,
+    /// Whether the certificate's private key can be exported: "ENABLED" or "DISABLED".
+    /// Public certificates are only exportable if requested with "ENABLED", which ACM charges for.
+    /// Private CA certificates can always be exported.
+    /// See the ExportCertificate task.
+    #[serde(default)]
+    pub export: Option<String>,
     /// A set of Key: Value tags. Each key and value can only be a string.
     pub tags: Tags,
 }
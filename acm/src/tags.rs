@@ -37,12 +37,6 @@ impl From<Tags> for Option<Vec<Tag>> {
     }
 }
 
-impl Tags {
-    pub fn len(&self) -> usize {
-        self.0.len()
-    }
-}
-
 // From a pair of hashmap, determine the set of aws_acm::Tag structs to pass to untag and add_tags respectively
 pub fn tag_diff(old_tags: &Tags, new_tags: &Tags) -> anyhow::Result<(Vec<String>, Vec<Tag>)> {
     let mut untag_keys = Vec::new();
@@ -0,0 +1,106 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::{PrettyConfig, RON};
+
+#[derive(Debug, Clone)]
+pub enum AcmTaskAddress {
+    ExportCertificate { name: String },
+}
+
+impl ResourceAddress for AcmTaskAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            AcmTaskAddress::ExportCertificate { name } => PathBuf::from(format!("aws/acm/tasks/export-certificate/{name}.ron")),
+        }
+    }
+
+    fn from_path(path: &Path) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        let path_components: Vec<&str> = path
+            .components()
+            .map(|s| s.as_os_str().to_str().context("Path component is not valid UTF-8"))
+            .collect::<Result<Vec<&str>, anyhow::Error>>()?;
+
+        match &path_components[..] {
+            ["aws", "acm", "tasks", "export-certificate", name] if name.ends_with(".ron") => {
+                Ok(AcmTaskAddress::ExportCertificate {
+                    name: name.strip_suffix(".ron").context("File name must end with .ron")?.to_string(),
+                })
+            }
+            _ => Err(anyhow::anyhow!("Invalid ACM task address: {}", path.display())),
+        }
+    }
+}
+
+impl DescribeAddresses for AcmTaskAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/acm/tasks/export-certificate/<name>.ron",
+                description: "Task: export a certificate, its chain and its encrypted private key as secrets",
+                example:     "aws/acm/tasks/export-certificate/web.ron",
+            },
+        ]
+    }
+}
+
+/// Exports an issued certificate along with its chain and private key.
+/// The private key is encrypted with a generated passphrase, and all four are only ever
+/// returned as secrets, written under `secret_dir` (`certificate.pem`, `certificate_chain.pem`,
+/// `private_key.pem` and `passphrase`), never as plain outputs.
+/// Only Private CA certificates and public certificates requested with `export: Some("ENABLED")`
+/// can be exported.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ExportCertificate {
+    pub certificate_arn: String,
+    pub secret_dir: String,
+}
+
+pub enum AcmTask {
+    ExportCertificate(ExportCertificate),
+}
+
+impl Resource for AcmTask {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = PrettyConfig::default().struct_names(true);
+        match self {
+            AcmTask::ExportCertificate(export) => match RON.to_string_pretty(&export, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = AcmTaskAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+        match addr {
+            AcmTaskAddress::ExportCertificate { .. } => Ok(AcmTask::ExportCertificate(RON.from_str(s)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use autoschematic_connector_aws_core::addr_doc::DescribeAddresses;
+
+    use super::AcmTaskAddress;
+
+    #[test]
+    fn address_examples_parse() {
+        AcmTaskAddress::checked_address_patterns().unwrap();
+    }
+}
@@ -0,0 +1,132 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum ApiGatewayResourceAddress {
+    /// REST API IDs are assigned by API Gateway, so `api_id` is virtual until the API exists.
+    RestApi { region: String, api_id: String },
+    /// Deployment IDs are assigned by API Gateway, so `deployment_id` is virtual until the deployment exists.
+    Deployment {
+        region:        String,
+        api_id:        String,
+        deployment_id: String,
+    },
+    Stage {
+        region:     String,
+        api_id:     String,
+        stage_name: String,
+    },
+    /// Usage plan IDs are assigned by API Gateway, so `usage_plan_id` is virtual until the plan exists.
+    UsagePlan { region: String, usage_plan_id: String },
+    /// API key IDs are assigned by API Gateway, so `api_key_id` is virtual until the key exists.
+    ApiKey { region: String, api_key_id: String },
+}
+
+impl ResourceAddress for ApiGatewayResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            ApiGatewayResourceAddress::RestApi { region, api_id } => {
+                PathBuf::from(format!("aws/apigateway/{region}/restapis/{api_id}.ron"))
+            }
+            ApiGatewayResourceAddress::Deployment {
+                region,
+                api_id,
+                deployment_id,
+            } => PathBuf::from(format!(
+                "aws/apigateway/{region}/restapis/{api_id}/deployments/{deployment_id}.ron"
+            )),
+            ApiGatewayResourceAddress::Stage {
+                region,
+                api_id,
+                stage_name,
+            } => PathBuf::from(format!("aws/apigateway/{region}/restapis/{api_id}/stages/{stage_name}.ron")),
+            ApiGatewayResourceAddress::UsagePlan { region, usage_plan_id } => {
+                PathBuf::from(format!("aws/apigateway/{region}/usage_plans/{usage_plan_id}.ron"))
+            }
+            ApiGatewayResourceAddress::ApiKey { region, api_key_id } => {
+                PathBuf::from(format!("aws/apigateway/{region}/api_keys/{api_key_id}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "apigateway", region, "restapis", api_id] if api_id.ends_with(".ron") => {
+                let api_id = api_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(ApiGatewayResourceAddress::RestApi {
+                    region: region.to_string(),
+                    api_id,
+                })
+            }
+            ["aws", "apigateway", region, "restapis", api_id, "deployments", deployment_id]
+                if deployment_id.ends_with(".ron") =>
+            {
+                let deployment_id = deployment_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(ApiGatewayResourceAddress::Deployment {
+                    region: region.to_string(),
+                    api_id: api_id.to_string(),
+                    deployment_id,
+                })
+            }
+            ["aws", "apigateway", region, "restapis", api_id, "stages", stage_name] if stage_name.ends_with(".ron") => {
+                let stage_name = stage_name.strip_suffix(".ron").unwrap().to_string();
+                Ok(ApiGatewayResourceAddress::Stage {
+                    region: region.to_string(),
+                    api_id: api_id.to_string(),
+                    stage_name,
+                })
+            }
+            ["aws", "apigateway", region, "usage_plans", usage_plan_id] if usage_plan_id.ends_with(".ron") => {
+                let usage_plan_id = usage_plan_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(ApiGatewayResourceAddress::UsagePlan {
+                    region: region.to_string(),
+                    usage_plan_id,
+                })
+            }
+            ["aws", "apigateway", region, "api_keys", api_key_id] if api_key_id.ends_with(".ron") => {
+                let api_key_id = api_key_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(ApiGatewayResourceAddress::ApiKey {
+                    region: region.to_string(),
+                    api_key_id,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for ApiGatewayResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/apigateway/<region>/restapis/<api_id>.ron",
+                description: "A REST API, with its resources and methods defined inline or by an OpenAPI document",
+                example:     "aws/apigateway/us-east-1/restapis/orders.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/apigateway/<region>/restapis/<api_id>/deployments/<deployment_id>.ron",
+                description: "A deployment, i.e. a snapshot of a REST API that stages can serve",
+                example:     "aws/apigateway/us-east-1/restapis/orders/deployments/v1.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/apigateway/<region>/restapis/<api_id>/stages/<stage_name>.ron",
+                description: "A stage of a REST API",
+                example:     "aws/apigateway/us-east-1/restapis/orders/stages/prod.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/apigateway/<region>/usage_plans/<usage_plan_id>.ron",
+                description: "A usage plan, with its API stages, throttling, quota and API keys",
+                example:     "aws/apigateway/us-east-1/usage_plans/partners.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/apigateway/<region>/api_keys/<api_key_id>.ron",
+                description: "An API key",
+                example:     "aws/apigateway/us-east-1/api_keys/acme-corp.ron",
+            },
+        ]
+    }
+}
@@ -0,0 +1,524 @@
+pub use crate::addr::ApiGatewayResourceAddress;
+pub use crate::op::ApiGatewayConnectorOp;
+pub use crate::resource::ApiGatewayResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse, VirtToPhyResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    template::ReadOutput,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::ApiGatewayConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{
+    ApiDefinition, ApiKey, ApiResource, Deployment, Integration, Method, Quota, RestApi, Stage, Throttling, UsagePlan,
+    UsagePlanStage,
+};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct ApiGatewayConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_apigateway::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    config: Mutex<ApiGatewayConnectorConfig>,
+    prefix: PathBuf,
+}
+
+impl ApiGatewayConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_apigateway::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+            let config = aws_config::defaults(BehaviorVersion::latest())
+                .region(region)
+                .timeout_config(
+                    TimeoutConfig::builder()
+                        .connect_timeout(Duration::from_secs(30))
+                        .operation_timeout(Duration::from_secs(30))
+                        .operation_attempt_timeout(Duration::from_secs(30))
+                        .read_timeout(Duration::from_secs(30))
+                        .build(),
+                )
+                .load()
+                .await;
+            let client = audited_client!(aws_sdk_apigateway, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+    /// The ID of the REST API that a usage plan refers to by name. Names that aren't REST APIs
+    /// in this repository are taken to be the IDs of APIs made elsewhere.
+    pub fn resolve_api_id(&self, region: &str, api_id: &str) -> anyhow::Result<String> {
+        let rest_api = ApiGatewayResourceAddress::RestApi {
+            region: region.to_string(),
+            api_id: api_id.to_string(),
+        };
+        Ok(rest_api.get_output(&self.prefix, "api_id")?.unwrap_or_else(|| api_id.to_string()))
+    }
+
+    /// The ID of the API key that a usage plan refers to by name, falling back to the name itself as for `resolve_api_id`.
+    pub fn resolve_api_key_id(&self, region: &str, api_key_id: &str) -> anyhow::Result<String> {
+        let api_key = ApiGatewayResourceAddress::ApiKey {
+            region: region.to_string(),
+            api_key_id: api_key_id.to_string(),
+        };
+        Ok(api_key.get_output(&self.prefix, "api_key_id")?.unwrap_or_else(|| api_key_id.to_string()))
+    }
+
+    /// The ID of the deployment that a stage refers to by name. `api_id` is the API's real ID,
+    /// since stages are only ever changed once their API exists.
+    pub fn resolve_deployment_id(&self, region: &str, api_id: &str, deployment: &str) -> anyhow::Result<String> {
+        let rest_api = ApiGatewayResourceAddress::RestApi {
+            region: region.to_string(),
+            api_id: api_id.to_string(),
+        };
+        let virt_api_id = match rest_api.phy_to_virt(&self.prefix)? {
+            Some(ApiGatewayResourceAddress::RestApi { api_id, .. }) => api_id,
+            _ => api_id.to_string(),
+        };
+
+        let deployment_addr = ApiGatewayResourceAddress::Deployment {
+            region: region.to_string(),
+            api_id: virt_api_id,
+            deployment_id: deployment.to_string(),
+        };
+        Ok(deployment_addr
+            .get_output(&self.prefix, "deployment_id")?
+            .unwrap_or_else(|| deployment.to_string()))
+    }
+
+    /// A usage plan with its API and key names replaced by their IDs.
+    pub fn resolve_usage_plan(&self, region: &str, usage_plan: &UsagePlan) -> anyhow::Result<UsagePlan> {
+        let mut api_stages = Vec::new();
+        for api_stage in &usage_plan.api_stages {
+            api_stages.push(UsagePlanStage {
+                api_id: self.resolve_api_id(region, &api_stage.api_id)?,
+                stage:  api_stage.stage.clone(),
+            });
+        }
+
+        let mut api_keys = Vec::new();
+        for api_key in &usage_plan.api_keys {
+            api_keys.push(self.resolve_api_key_id(region, api_key)?);
+        }
+
+        Ok(UsagePlan {
+            api_stages,
+            api_keys,
+            ..usage_plan.clone()
+        })
+    }
+}
+
+#[async_trait]
+impl Connector for ApiGatewayConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = ApiGatewayResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(ApiGatewayConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let apigateway_config: ApiGatewayConnectorConfig = ApiGatewayConnectorConfig::try_load(&self.prefix).await?;
+
+        // API Gateway ARNs don't include the account ID, so it's only needed to check the credentials
+        apigateway_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(apigateway_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = apigateway_config;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn addr_virt_to_phy(&self, addr: &Path) -> anyhow::Result<VirtToPhyResponse> {
+        let addr = ApiGatewayResourceAddress::from_path(addr)?;
+
+        match &addr {
+            ApiGatewayResourceAddress::RestApi { region, .. } => {
+                let Some(api_id) = addr.get_output(&self.prefix, "api_id")? else {
+                    return Ok(VirtToPhyResponse::NotPresent);
+                };
+                Ok(VirtToPhyResponse::Present(
+                    ApiGatewayResourceAddress::RestApi {
+                        region: region.clone(),
+                        api_id,
+                    }
+                    .to_path_buf(),
+                ))
+            }
+            ApiGatewayResourceAddress::Deployment { region, api_id, .. } => {
+                let parent_api = ApiGatewayResourceAddress::RestApi {
+                    region: region.clone(),
+                    api_id: api_id.clone(),
+                };
+
+                let Some(api_id) = parent_api.get_output(&self.prefix, "api_id")? else {
+                    return Ok(VirtToPhyResponse::Deferred(vec![ReadOutput {
+                        addr: parent_api.to_path_buf(),
+                        key:  String::from("api_id"),
+                    }]));
+                };
+
+                let Some(deployment_id) = addr.get_output(&self.prefix, "deployment_id")? else {
+                    return Ok(VirtToPhyResponse::NotPresent);
+                };
+
+                Ok(VirtToPhyResponse::Present(
+                    ApiGatewayResourceAddress::Deployment {
+                        region: region.clone(),
+                        api_id,
+                        deployment_id,
+                    }
+                    .to_path_buf(),
+                ))
+            }
+            ApiGatewayResourceAddress::Stage {
+                region,
+                api_id,
+                stage_name,
+            } => {
+                let parent_api = ApiGatewayResourceAddress::RestApi {
+                    region: region.clone(),
+                    api_id: api_id.clone(),
+                };
+
+                let Some(api_id) = parent_api.get_output(&self.prefix, "api_id")? else {
+                    return Ok(VirtToPhyResponse::Deferred(vec![ReadOutput {
+                        addr: parent_api.to_path_buf(),
+                        key:  String::from("api_id"),
+                    }]));
+                };
+
+                Ok(VirtToPhyResponse::Present(
+                    ApiGatewayResourceAddress::Stage {
+                        region: region.clone(),
+                        api_id,
+                        stage_name: stage_name.clone(),
+                    }
+                    .to_path_buf(),
+                ))
+            }
+            ApiGatewayResourceAddress::UsagePlan { region, .. } => {
+                let Some(usage_plan_id) = addr.get_output(&self.prefix, "usage_plan_id")? else {
+                    return Ok(VirtToPhyResponse::NotPresent);
+                };
+                Ok(VirtToPhyResponse::Present(
+                    ApiGatewayResourceAddress::UsagePlan {
+                        region: region.clone(),
+                        usage_plan_id,
+                    }
+                    .to_path_buf(),
+                ))
+            }
+            ApiGatewayResourceAddress::ApiKey { region, .. } => {
+                let Some(api_key_id) = addr.get_output(&self.prefix, "api_key_id")? else {
+                    return Ok(VirtToPhyResponse::NotPresent);
+                };
+                Ok(VirtToPhyResponse::Present(
+                    ApiGatewayResourceAddress::ApiKey {
+                        region: region.clone(),
+                        api_key_id,
+                    }
+                    .to_path_buf(),
+                ))
+            }
+        }
+    }
+
+    async fn addr_phy_to_virt(&self, addr: &Path) -> anyhow::Result<Option<PathBuf>> {
+        let addr = ApiGatewayResourceAddress::from_path(addr)?;
+
+        match &addr {
+            ApiGatewayResourceAddress::Deployment { region, api_id, .. } => {
+                let parent_api = ApiGatewayResourceAddress::RestApi {
+                    region: region.clone(),
+                    api_id: api_id.clone(),
+                };
+
+                if let Some(ApiGatewayResourceAddress::RestApi {
+                    api_id: virt_api_id, ..
+                }) = parent_api.phy_to_virt(&self.prefix)?
+                    && let Some(ApiGatewayResourceAddress::Deployment { deployment_id, .. }) =
+                        addr.phy_to_virt(&self.prefix)?
+                {
+                    return Ok(Some(
+                        ApiGatewayResourceAddress::Deployment {
+                            region: region.clone(),
+                            api_id: virt_api_id,
+                            deployment_id,
+                        }
+                        .to_path_buf(),
+                    ));
+                }
+                // Not created from this repository, so there's no virtual address to map back to
+                Ok(Some(addr.to_path_buf()))
+            }
+            ApiGatewayResourceAddress::Stage {
+                region,
+                api_id,
+                stage_name,
+            } => {
+                let parent_api = ApiGatewayResourceAddress::RestApi {
+                    region: region.clone(),
+                    api_id: api_id.clone(),
+                };
+
+                if let Some(ApiGatewayResourceAddress::RestApi {
+                    api_id: virt_api_id, ..
+                }) = parent_api.phy_to_virt(&self.prefix)?
+                {
+                    return Ok(Some(
+                        ApiGatewayResourceAddress::Stage {
+                            region: region.clone(),
+                            api_id: virt_api_id,
+                            stage_name: stage_name.clone(),
+                        }
+                        .to_path_buf(),
+                    ));
+                }
+                Ok(Some(addr.to_path_buf()))
+            }
+            _ => {
+                if let Some(virt_addr) = addr.phy_to_virt(&self.prefix)? {
+                    return Ok(Some(virt_addr.to_path_buf()));
+                }
+                Ok(Some(addr.to_path_buf()))
+            }
+        }
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // REST API skeleton, proxying every request to a Lambda function
+        res.push(skeleton!(
+            ApiGatewayResourceAddress::RestApi {
+                region: String::from("[region]"),
+                api_id: String::from("[api_name]"),
+            },
+            ApiGatewayResource::RestApi(RestApi {
+                name: String::from("[api_name]"),
+                description: None,
+                endpoint_type: String::from("REGIONAL"),
+                binary_media_types: Vec::new(),
+                api_key_source: None,
+                minimum_compression_size: None,
+                disable_execute_api_endpoint: false,
+                policy: None,
+                definition: ApiDefinition::Inline {
+                    resources: BTreeMap::from([(
+                        String::from("/{proxy+}"),
+                        ApiResource {
+                            methods: BTreeMap::from([(
+                                String::from("ANY"),
+                                Method {
+                                    authorization_type: String::from("NONE"),
+                                    authorizer_id: None,
+                                    api_key_required: false,
+                                    request_parameters: BTreeMap::new(),
+                                    integration: Some(Integration {
+                                        integration_type: String::from("AWS_PROXY"),
+                                        http_method: Some(String::from("POST")),
+                                        uri: Some(String::from(
+                                            "arn:aws:apigateway:[region]:lambda:path/2015-03-31/functions/arn:aws:lambda:[region]:[account_id]:function:[function_name]/invocations",
+                                        )),
+                                        credentials: None,
+                                        request_parameters: BTreeMap::new(),
+                                        request_templates: BTreeMap::new(),
+                                        passthrough_behavior: None,
+                                        timeout_in_millis: None,
+                                    }),
+                                },
+                            )]),
+                        },
+                    )]),
+                },
+                tags: Tags::default(),
+            })
+        ));
+
+        // REST API skeleton, defined by an OpenAPI document in the repository
+        res.push(skeleton!(
+            ApiGatewayResourceAddress::RestApi {
+                region: String::from("[region]"),
+                api_id: String::from("[openapi_api_name]"),
+            },
+            ApiGatewayResource::RestApi(RestApi {
+                name: String::from("[openapi_api_name]"),
+                description: None,
+                endpoint_type: String::from("REGIONAL"),
+                binary_media_types: Vec::new(),
+                api_key_source: None,
+                minimum_compression_size: None,
+                disable_execute_api_endpoint: false,
+                policy: None,
+                definition: ApiDefinition::OpenApi {
+                    path: String::from("[path/to/openapi.yaml]"),
+                },
+                tags: Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            ApiGatewayResourceAddress::Deployment {
+                region: String::from("[region]"),
+                api_id: String::from("[api_name]"),
+                deployment_id: String::from("[deployment_name]"),
+            },
+            ApiGatewayResource::Deployment(Deployment {
+                description: Some(String::from("[deployment_description]")),
+            })
+        ));
+
+        res.push(skeleton!(
+            ApiGatewayResourceAddress::Stage {
+                region: String::from("[region]"),
+                api_id: String::from("[api_name]"),
+                stage_name: String::from("[stage_name]"),
+            },
+            ApiGatewayResource::Stage(Stage {
+                deployment: String::from("[deployment_name]"),
+                description: None,
+                variables: BTreeMap::new(),
+                throttling: Some(Throttling {
+                    burst_limit: 100,
+                    rate_limit:  50.0,
+                }),
+                tracing_enabled: false,
+                tags: Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            ApiGatewayResourceAddress::UsagePlan {
+                region: String::from("[region]"),
+                usage_plan_id: String::from("[usage_plan_name]"),
+            },
+            ApiGatewayResource::UsagePlan(UsagePlan {
+                name: String::from("[usage_plan_name]"),
+                description: None,
+                api_stages: vec![UsagePlanStage {
+                    api_id: String::from("[api_name]"),
+                    stage:  String::from("[stage_name]"),
+                }],
+                throttle: Some(Throttling {
+                    burst_limit: 20,
+                    rate_limit:  10.0,
+                }),
+                quota: Some(Quota {
+                    limit:  10000,
+                    period: String::from("MONTH"),
+                    offset: 0,
+                }),
+                api_keys: vec![String::from("[api_key_name]")],
+                tags: Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            ApiGatewayResourceAddress::ApiKey {
+                region: String::from("[region]"),
+                api_key_id: String::from("[api_key_name]"),
+            },
+            ApiGatewayResource::ApiKey(ApiKey {
+                name: String::from("[api_key_name]"),
+                description: None,
+                enabled: true,
+                tags: Tags::default(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = ApiGatewayResourceAddress::from_path(addr)?;
+
+        match addr {
+            ApiGatewayResourceAddress::RestApi { .. } => ron_check_eq::<RestApi>(a, b),
+            ApiGatewayResourceAddress::Deployment { .. } => ron_check_eq::<Deployment>(a, b),
+            ApiGatewayResourceAddress::Stage { .. } => ron_check_eq::<Stage>(a, b),
+            ApiGatewayResourceAddress::UsagePlan { .. } => ron_check_eq::<UsagePlan>(a, b),
+            ApiGatewayResourceAddress::ApiKey { .. } => ron_check_eq::<ApiKey>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = ApiGatewayResourceAddress::from_path(addr)?;
+
+        match addr {
+            ApiGatewayResourceAddress::RestApi { .. } => ron_check_syntax::<RestApi>(a),
+            ApiGatewayResourceAddress::Deployment { .. } => ron_check_syntax::<Deployment>(a),
+            ApiGatewayResourceAddress::Stage { .. } => ron_check_syntax::<Stage>(a),
+            ApiGatewayResourceAddress::UsagePlan { .. } => ron_check_syntax::<UsagePlan>(a),
+            ApiGatewayResourceAddress::ApiKey { .. } => ron_check_syntax::<ApiKey>(a),
+        }
+    }
+}
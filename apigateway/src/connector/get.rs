@@ -0,0 +1,235 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+    util::RON,
+};
+use aws_sdk_apigateway::operation::{
+    get_api_key::GetApiKeyError, get_deployment::GetDeploymentError, get_rest_api::GetRestApiError,
+    get_stage::GetStageError, get_usage_plan::GetUsagePlanError,
+};
+
+use crate::{
+    addr::ApiGatewayResourceAddress,
+    resource::{ApiGatewayResource, ApiKey, Deployment, Quota, Stage, Throttling, UsagePlan, UsagePlanStage},
+    util::{inline_definition, live_resources, resolve_api_definition, rest_api_from_sdk},
+};
+
+use super::ApiGatewayConnector;
+
+impl ApiGatewayConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = ApiGatewayResourceAddress::from_path(addr)?;
+
+        match &addr {
+            ApiGatewayResourceAddress::RestApi { region, api_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.get_rest_api().rest_api_id(api_id).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(GetRestApiError::NotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let live = live_resources(&client, api_id).await?;
+
+                // Imports record their outputs against the API's address in this repository
+                let virt_addr = addr.phy_to_virt(&self.prefix)?.unwrap_or_else(|| addr.clone());
+                let recorded_source = match virt_addr.get_output(&self.prefix, "definition_source")? {
+                    Some(source) => RON.from_str(&source).ok(),
+                    None => None,
+                };
+                let definition = resolve_api_definition(
+                    &self.prefix,
+                    inline_definition(&live),
+                    recorded_source,
+                    virt_addr.get_output(&self.prefix, "definition_sha256")?,
+                )?;
+
+                let rest_api = rest_api_from_sdk(resp, definition)?;
+
+                get_resource_response!(ApiGatewayResource::RestApi(rest_api), [(String::from("api_id"), api_id.clone())])
+            }
+            ApiGatewayResourceAddress::Deployment {
+                region,
+                api_id,
+                deployment_id,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client
+                    .get_deployment()
+                    .rest_api_id(api_id)
+                    .deployment_id(deployment_id)
+                    .send()
+                    .await
+                {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(GetDeploymentError::NotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let deployment = Deployment {
+                    description: resp.description.filter(|d| !d.is_empty()),
+                };
+
+                get_resource_response!(
+                    ApiGatewayResource::Deployment(deployment),
+                    [(String::from("deployment_id"), deployment_id.clone())]
+                )
+            }
+            ApiGatewayResourceAddress::Stage {
+                region,
+                api_id,
+                stage_name,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.get_stage().rest_api_id(api_id).stage_name(stage_name).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(GetStageError::NotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                // Refer to the deployment by its name in this repository, if it has one
+                let deployment_id = resp.deployment_id.clone().unwrap_or_default();
+                let deployment_addr = ApiGatewayResourceAddress::Deployment {
+                    region: region.clone(),
+                    api_id: api_id.clone(),
+                    deployment_id: deployment_id.clone(),
+                };
+                let deployment = match deployment_addr.phy_to_virt(&self.prefix)? {
+                    Some(ApiGatewayResourceAddress::Deployment { deployment_id, .. }) => deployment_id,
+                    _ => deployment_id,
+                };
+
+                // Stage-wide throttling is the method setting for every resource and method, `*/*`
+                let throttling = resp
+                    .method_settings
+                    .as_ref()
+                    .and_then(|settings| settings.get("*/*"))
+                    .filter(|setting| setting.throttling_burst_limit >= 0 && setting.throttling_rate_limit >= 0.0)
+                    .map(|setting| Throttling {
+                        burst_limit: setting.throttling_burst_limit,
+                        rate_limit:  setting.throttling_rate_limit,
+                    });
+
+                let stage = Stage {
+                    deployment,
+                    description: resp.description.filter(|d| !d.is_empty()),
+                    variables: resp.variables.unwrap_or_default().into_iter().collect(),
+                    throttling,
+                    tracing_enabled: resp.tracing_enabled,
+                    tags: resp.tags.into(),
+                };
+
+                get_resource_response!(ApiGatewayResource::Stage(stage))
+            }
+            ApiGatewayResourceAddress::UsagePlan { region, usage_plan_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.get_usage_plan().usage_plan_id(usage_plan_id).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(GetUsagePlanError::NotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                // Refer to APIs and keys by their names in this repository, if they have them
+                let mut api_stages = Vec::new();
+                for api_stage in resp.api_stages.unwrap_or_default() {
+                    let api_id = api_stage.api_id.unwrap_or_default();
+                    let rest_api = ApiGatewayResourceAddress::RestApi {
+                        region: region.clone(),
+                        api_id: api_id.clone(),
+                    };
+                    let api_id = match rest_api.phy_to_virt(&self.prefix)? {
+                        Some(ApiGatewayResourceAddress::RestApi { api_id, .. }) => api_id,
+                        _ => api_id,
+                    };
+                    api_stages.push(UsagePlanStage {
+                        api_id,
+                        stage: api_stage.stage.unwrap_or_default(),
+                    });
+                }
+                api_stages.sort_by(|a, b| (&a.api_id, &a.stage).cmp(&(&b.api_id, &b.stage)));
+
+                let mut api_keys = Vec::new();
+                let mut pages = client
+                    .get_usage_plan_keys()
+                    .usage_plan_id(usage_plan_id)
+                    .into_paginator()
+                    .items()
+                    .send();
+                while let Some(key) = pages.next().await {
+                    let Some(key_id) = key?.id else {
+                        continue;
+                    };
+                    let api_key = ApiGatewayResourceAddress::ApiKey {
+                        region: region.clone(),
+                        api_key_id: key_id.clone(),
+                    };
+                    match api_key.phy_to_virt(&self.prefix)? {
+                        Some(ApiGatewayResourceAddress::ApiKey { api_key_id, .. }) => api_keys.push(api_key_id),
+                        _ => api_keys.push(key_id),
+                    }
+                }
+                api_keys.sort();
+
+                let usage_plan = UsagePlan {
+                    name: resp.name.unwrap_or_default(),
+                    description: resp.description.filter(|d| !d.is_empty()),
+                    api_stages,
+                    throttle: resp.throttle.map(|throttle| Throttling {
+                        burst_limit: throttle.burst_limit,
+                        rate_limit:  throttle.rate_limit,
+                    }),
+                    quota: resp.quota.map(|quota| Quota {
+                        limit:  quota.limit,
+                        period: quota.period.map(|p| p.as_str().to_string()).unwrap_or_default(),
+                        offset: quota.offset,
+                    }),
+                    api_keys,
+                    tags: resp.tags.into(),
+                };
+
+                get_resource_response!(
+                    ApiGatewayResource::UsagePlan(usage_plan),
+                    [(String::from("usage_plan_id"), usage_plan_id.clone())]
+                )
+            }
+            ApiGatewayResourceAddress::ApiKey { region, api_key_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                // The key's value is deliberately not fetched
+                let resp = match client.get_api_key().api_key(api_key_id).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(GetApiKeyError::NotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let api_key = ApiKey {
+                    name: resp.name.unwrap_or_default(),
+                    description: resp.description.filter(|d| !d.is_empty()),
+                    enabled: resp.enabled,
+                    tags: resp.tags.into(),
+                };
+
+                get_resource_response!(
+                    ApiGatewayResource::ApiKey(api_key),
+                    [(String::from("api_key_id"), api_key_id.clone())]
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::addr::ApiGatewayResourceAddress;
+
+use super::ApiGatewayConnector;
+
+impl ApiGatewayConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut rest_apis = client.get_rest_apis().into_paginator().items().send();
+            while let Some(rest_api) = rest_apis.next().await {
+                let Some(api_id) = rest_api?.id else {
+                    continue;
+                };
+
+                results.push(
+                    ApiGatewayResourceAddress::RestApi {
+                        region: region.clone(),
+                        api_id: api_id.clone(),
+                    }
+                    .to_path_buf(),
+                );
+
+                let mut deployments = client
+                    .get_deployments()
+                    .rest_api_id(&api_id)
+                    .into_paginator()
+                    .items()
+                    .send();
+                while let Some(deployment) = deployments.next().await {
+                    if let Some(deployment_id) = deployment?.id {
+                        results.push(
+                            ApiGatewayResourceAddress::Deployment {
+                                region: region.clone(),
+                                api_id: api_id.clone(),
+                                deployment_id,
+                            }
+                            .to_path_buf(),
+                        );
+                    }
+                }
+
+                let stages = client.get_stages().rest_api_id(&api_id).send().await?;
+                for stage in stages.item.unwrap_or_default() {
+                    if let Some(stage_name) = stage.stage_name {
+                        results.push(
+                            ApiGatewayResourceAddress::Stage {
+                                region: region.clone(),
+                                api_id: api_id.clone(),
+                                stage_name,
+                            }
+                            .to_path_buf(),
+                        );
+                    }
+                }
+            }
+
+            let mut usage_plans = client.get_usage_plans().into_paginator().items().send();
+            while let Some(usage_plan) = usage_plans.next().await {
+                if let Some(usage_plan_id) = usage_plan?.id {
+                    results.push(
+                        ApiGatewayResourceAddress::UsagePlan {
+                            region: region.clone(),
+                            usage_plan_id,
+                        }
+                        .to_path_buf(),
+                    );
+                }
+            }
+
+            let mut api_keys = client.get_api_keys().into_paginator().items().send();
+            while let Some(api_key) = api_keys.next().await {
+                if let Some(api_key_id) = api_key?.id {
+                    results.push(
+                        ApiGatewayResourceAddress::ApiKey {
+                            region: region.clone(),
+                            api_key_id,
+                        }
+                        .to_path_buf(),
+                    );
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
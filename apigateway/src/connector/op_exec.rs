@@ -0,0 +1,122 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::connector::{ConnectorOp, OpExecResponse, ResourceAddress};
+
+use crate::{
+    addr::ApiGatewayResourceAddress,
+    op::ApiGatewayConnectorOp,
+    op_impl,
+    util::{api_key_arn, rest_api_arn, stage_arn, usage_plan_arn},
+};
+
+use super::ApiGatewayConnector;
+
+impl ApiGatewayConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = ApiGatewayResourceAddress::from_path(addr)?;
+        let op = ApiGatewayConnectorOp::from_str(op)?;
+
+        match &addr {
+            ApiGatewayResourceAddress::RestApi { region, api_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    ApiGatewayConnectorOp::CreateRestApi(rest_api) => {
+                        op_impl::create_rest_api(&client, &self.prefix, &rest_api).await
+                    }
+                    ApiGatewayConnectorOp::UpdateRestApi(rest_api) => op_impl::update_rest_api(&client, api_id, &rest_api).await,
+                    ApiGatewayConnectorOp::UpdateRestApiTags(old_tags, new_tags) => {
+                        op_impl::update_tags(&client, &rest_api_arn(region, api_id), &old_tags, &new_tags).await
+                    }
+                    ApiGatewayConnectorOp::ImportOpenApi(rest_api) => {
+                        op_impl::import_openapi(&client, &self.prefix, api_id, &rest_api).await
+                    }
+                    ApiGatewayConnectorOp::PutResources(resources) => {
+                        op_impl::put_resources(&client, api_id, &resources).await
+                    }
+                    ApiGatewayConnectorOp::DeleteRestApi => op_impl::delete_rest_api(&client, api_id).await,
+                    _ => bail!("Invalid operation for API Gateway REST API resource"),
+                }
+            }
+            ApiGatewayResourceAddress::Deployment {
+                region,
+                api_id,
+                deployment_id,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    ApiGatewayConnectorOp::CreateDeployment(deployment) => {
+                        op_impl::create_deployment(&client, api_id, &deployment).await
+                    }
+                    ApiGatewayConnectorOp::ReplaceDeployment(deployment) => {
+                        op_impl::replace_deployment(&client, api_id, deployment_id, &deployment).await
+                    }
+                    ApiGatewayConnectorOp::DeleteDeployment => {
+                        op_impl::delete_deployment(&client, api_id, deployment_id).await
+                    }
+                    _ => bail!("Invalid operation for API Gateway deployment resource"),
+                }
+            }
+            ApiGatewayResourceAddress::Stage {
+                region,
+                api_id,
+                stage_name,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    ApiGatewayConnectorOp::CreateStage(stage) => {
+                        let deployment_id = self.resolve_deployment_id(region, api_id, &stage.deployment)?;
+                        op_impl::create_stage(&client, api_id, stage_name, &stage, &deployment_id).await
+                    }
+                    ApiGatewayConnectorOp::UpdateStage(old_stage, new_stage) => {
+                        let deployment_id = self.resolve_deployment_id(region, api_id, &new_stage.deployment)?;
+                        op_impl::update_stage(&client, api_id, stage_name, &old_stage, &new_stage, &deployment_id).await
+                    }
+                    ApiGatewayConnectorOp::UpdateStageTags(old_tags, new_tags) => {
+                        op_impl::update_tags(&client, &stage_arn(region, api_id, stage_name), &old_tags, &new_tags).await
+                    }
+                    ApiGatewayConnectorOp::DeleteStage => op_impl::delete_stage(&client, api_id, stage_name).await,
+                    _ => bail!("Invalid operation for API Gateway stage resource"),
+                }
+            }
+            ApiGatewayResourceAddress::UsagePlan { region, usage_plan_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    ApiGatewayConnectorOp::CreateUsagePlan(usage_plan) => {
+                        let usage_plan = self.resolve_usage_plan(region, &usage_plan)?;
+                        op_impl::create_usage_plan(&client, &usage_plan).await
+                    }
+                    ApiGatewayConnectorOp::UpdateUsagePlan(old_usage_plan, new_usage_plan) => {
+                        let old_usage_plan = self.resolve_usage_plan(region, &old_usage_plan)?;
+                        let new_usage_plan = self.resolve_usage_plan(region, &new_usage_plan)?;
+                        op_impl::update_usage_plan(&client, usage_plan_id, &old_usage_plan, &new_usage_plan).await
+                    }
+                    ApiGatewayConnectorOp::UpdateUsagePlanTags(old_tags, new_tags) => {
+                        op_impl::update_tags(&client, &usage_plan_arn(region, usage_plan_id), &old_tags, &new_tags).await
+                    }
+                    ApiGatewayConnectorOp::DeleteUsagePlan => op_impl::delete_usage_plan(&client, usage_plan_id).await,
+                    _ => bail!("Invalid operation for API Gateway usage plan resource"),
+                }
+            }
+            ApiGatewayResourceAddress::ApiKey { region, api_key_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    ApiGatewayConnectorOp::CreateApiKey(api_key) => op_impl::create_api_key(&client, &api_key).await,
+                    ApiGatewayConnectorOp::UpdateApiKey(old_api_key, new_api_key) => {
+                        op_impl::update_api_key(&client, api_key_id, &old_api_key, &new_api_key).await
+                    }
+                    ApiGatewayConnectorOp::UpdateApiKeyTags(old_tags, new_tags) => {
+                        op_impl::update_tags(&client, &api_key_arn(region, api_key_id), &old_tags, &new_tags).await
+                    }
+                    ApiGatewayConnectorOp::DeleteApiKey => op_impl::delete_api_key(&client, api_key_id).await,
+                    _ => bail!("Invalid operation for API Gateway API key resource"),
+                }
+            }
+        }
+    }
+}
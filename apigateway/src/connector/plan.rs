@@ -0,0 +1,272 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::{
+    resource::{ApiDefinition, ApiKey, Deployment, RestApi, Stage, UsagePlan},
+    util::check_definition,
+};
+
+use super::{ApiGatewayConnector, ApiGatewayConnectorOp, ApiGatewayResourceAddress};
+
+fn check_rest_api(prefix: &Path, rest_api: &RestApi) -> anyhow::Result<()> {
+    if !["EDGE", "REGIONAL", "PRIVATE"].contains(&rest_api.endpoint_type.as_str()) {
+        bail!(
+            "REST API {} has an invalid endpoint_type {}; expected EDGE, REGIONAL or PRIVATE",
+            rest_api.name,
+            rest_api.endpoint_type
+        );
+    }
+    check_definition(prefix, &rest_api.name, &rest_api.definition)
+}
+
+impl ApiGatewayConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = ApiGatewayResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            ApiGatewayResourceAddress::RestApi { region, api_id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_api)) => {
+                    let new_api: RestApi = RON.from_str(&new_api)?;
+                    check_rest_api(&self.prefix, &new_api)?;
+                    Ok(vec![connector_op!(
+                        ApiGatewayConnectorOp::CreateRestApi(new_api.clone()),
+                        format!("Create new API Gateway REST API {} in region {}", new_api.name, region)
+                    )])
+                }
+                (Some(_old_api), None) => Ok(vec![connector_op!(
+                    ApiGatewayConnectorOp::DeleteRestApi,
+                    format!(
+                        "DELETE API Gateway REST API `{}` in region {}, along with its deployments and stages",
+                        api_id, region
+                    )
+                )]),
+                (Some(old_api), Some(new_api)) => {
+                    let old_api: RestApi = RON.from_str(&old_api)?;
+                    let new_api: RestApi = RON.from_str(&new_api)?;
+                    check_rest_api(&self.prefix, &new_api)?;
+                    let mut ops = Vec::new();
+
+                    if old_api.tags != new_api.tags {
+                        let diff = diff_ron_values(&old_api.tags, &new_api.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ApiGatewayConnectorOp::UpdateRestApiTags(old_api.tags.clone(), new_api.tags.clone()),
+                            format!("Modify tags for API Gateway REST API `{}`\n{}", api_id, diff)
+                        ));
+                    }
+
+                    if old_api.definition != new_api.definition {
+                        match &new_api.definition {
+                            ApiDefinition::OpenApi { path } => ops.push(connector_op!(
+                                ApiGatewayConnectorOp::ImportOpenApi(new_api.clone()),
+                                format!(
+                                    "Import OpenAPI document {} into API Gateway REST API `{}`, overwriting its resources and methods",
+                                    path, api_id
+                                )
+                            )),
+                            ApiDefinition::Inline { resources } => {
+                                let diff = diff_ron_values(&old_api.definition, &new_api.definition).unwrap_or_default();
+                                ops.push(connector_op!(
+                                    ApiGatewayConnectorOp::PutResources(resources.clone()),
+                                    format!("Modify resources and methods for API Gateway REST API `{}`\n{}", api_id, diff)
+                                ));
+                            }
+                        }
+                    }
+
+                    let old_settings = RestApi {
+                        definition: new_api.definition.clone(),
+                        tags: new_api.tags.clone(),
+                        ..old_api
+                    };
+                    if old_settings != new_api {
+                        let diff = diff_ron_values(&old_settings, &new_api).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ApiGatewayConnectorOp::UpdateRestApi(new_api),
+                            format!("Modify API Gateway REST API `{}`\n{}", api_id, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            ApiGatewayResourceAddress::Deployment {
+                api_id, deployment_id, ..
+            } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_deployment)) => {
+                    let new_deployment: Deployment = RON.from_str(&new_deployment)?;
+                    Ok(vec![connector_op!(
+                        ApiGatewayConnectorOp::CreateDeployment(new_deployment),
+                        format!("Create new deployment `{}` of API Gateway REST API `{}`", deployment_id, api_id)
+                    )])
+                }
+                (Some(_old_deployment), None) => Ok(vec![connector_op!(
+                    ApiGatewayConnectorOp::DeleteDeployment,
+                    format!("DELETE deployment `{}` of API Gateway REST API `{}`", deployment_id, api_id)
+                )]),
+                (Some(old_deployment), Some(new_deployment)) => {
+                    let old_deployment: Deployment = RON.from_str(&old_deployment)?;
+                    let new_deployment: Deployment = RON.from_str(&new_deployment)?;
+
+                    if old_deployment == new_deployment {
+                        return Ok(Vec::new());
+                    }
+
+                    Ok(vec![connector_op!(
+                        ApiGatewayConnectorOp::ReplaceDeployment(new_deployment),
+                        format!(
+                            "REPLACE deployment `{}` of API Gateway REST API `{}` (requires replacement: description), moving its stages to the new deployment",
+                            deployment_id, api_id
+                        )
+                    )])
+                }
+            },
+            ApiGatewayResourceAddress::Stage { api_id, stage_name, .. } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_stage)) => {
+                    let new_stage: Stage = RON.from_str(&new_stage)?;
+                    Ok(vec![connector_op!(
+                        ApiGatewayConnectorOp::CreateStage(new_stage.clone()),
+                        format!(
+                            "Create new stage `{}` of API Gateway REST API `{}`, serving deployment `{}`",
+                            stage_name, api_id, new_stage.deployment
+                        )
+                    )])
+                }
+                (Some(_old_stage), None) => Ok(vec![connector_op!(
+                    ApiGatewayConnectorOp::DeleteStage,
+                    format!("DELETE stage `{}` of API Gateway REST API `{}`", stage_name, api_id)
+                )]),
+                (Some(old_stage), Some(new_stage)) => {
+                    let old_stage: Stage = RON.from_str(&old_stage)?;
+                    let new_stage: Stage = RON.from_str(&new_stage)?;
+                    let mut ops = Vec::new();
+
+                    if old_stage.tags != new_stage.tags {
+                        let diff = diff_ron_values(&old_stage.tags, &new_stage.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ApiGatewayConnectorOp::UpdateStageTags(old_stage.tags.clone(), new_stage.tags.clone()),
+                            format!("Modify tags for stage `{}` of API Gateway REST API `{}`\n{}", stage_name, api_id, diff)
+                        ));
+                    }
+
+                    let old_settings = Stage {
+                        tags: new_stage.tags.clone(),
+                        ..old_stage
+                    };
+                    if old_settings != new_stage {
+                        let diff = diff_ron_values(&old_settings, &new_stage).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ApiGatewayConnectorOp::UpdateStage(old_settings, new_stage),
+                            format!("Modify stage `{}` of API Gateway REST API `{}`\n{}", stage_name, api_id, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            ApiGatewayResourceAddress::UsagePlan { region, usage_plan_id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_usage_plan)) => {
+                    let new_usage_plan: UsagePlan = RON.from_str(&new_usage_plan)?;
+                    Ok(vec![connector_op!(
+                        ApiGatewayConnectorOp::CreateUsagePlan(new_usage_plan.clone()),
+                        format!("Create new API Gateway usage plan {} in region {}", new_usage_plan.name, region)
+                    )])
+                }
+                (Some(_old_usage_plan), None) => Ok(vec![connector_op!(
+                    ApiGatewayConnectorOp::DeleteUsagePlan,
+                    format!("DELETE API Gateway usage plan `{}` in region {}", usage_plan_id, region)
+                )]),
+                (Some(old_usage_plan), Some(new_usage_plan)) => {
+                    let old_usage_plan: UsagePlan = RON.from_str(&old_usage_plan)?;
+                    let new_usage_plan: UsagePlan = RON.from_str(&new_usage_plan)?;
+                    let mut ops = Vec::new();
+
+                    if old_usage_plan.tags != new_usage_plan.tags {
+                        let diff = diff_ron_values(&old_usage_plan.tags, &new_usage_plan.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ApiGatewayConnectorOp::UpdateUsagePlanTags(
+                                old_usage_plan.tags.clone(),
+                                new_usage_plan.tags.clone()
+                            ),
+                            format!("Modify tags for API Gateway usage plan `{}`\n{}", usage_plan_id, diff)
+                        ));
+                    }
+
+                    let old_settings = UsagePlan {
+                        tags: new_usage_plan.tags.clone(),
+                        ..old_usage_plan
+                    };
+                    if old_settings != new_usage_plan {
+                        let diff = diff_ron_values(&old_settings, &new_usage_plan).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ApiGatewayConnectorOp::UpdateUsagePlan(old_settings, new_usage_plan),
+                            format!("Modify API Gateway usage plan `{}`\n{}", usage_plan_id, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            ApiGatewayResourceAddress::ApiKey { region, api_key_id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_api_key)) => {
+                    let new_api_key: ApiKey = RON.from_str(&new_api_key)?;
+                    Ok(vec![connector_op!(
+                        ApiGatewayConnectorOp::CreateApiKey(new_api_key.clone()),
+                        format!("Create new API Gateway API key {} in region {}", new_api_key.name, region)
+                    )])
+                }
+                (Some(_old_api_key), None) => Ok(vec![connector_op!(
+                    ApiGatewayConnectorOp::DeleteApiKey,
+                    format!("DELETE API Gateway API key `{}` in region {}", api_key_id, region)
+                )]),
+                (Some(old_api_key), Some(new_api_key)) => {
+                    let old_api_key: ApiKey = RON.from_str(&old_api_key)?;
+                    let new_api_key: ApiKey = RON.from_str(&new_api_key)?;
+                    let mut ops = Vec::new();
+
+                    if old_api_key.tags != new_api_key.tags {
+                        let diff = diff_ron_values(&old_api_key.tags, &new_api_key.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ApiGatewayConnectorOp::UpdateApiKeyTags(old_api_key.tags.clone(), new_api_key.tags.clone()),
+                            format!("Modify tags for API Gateway API key `{}`\n{}", api_key_id, diff)
+                        ));
+                    }
+
+                    let old_settings = ApiKey {
+                        tags: new_api_key.tags.clone(),
+                        ..old_api_key
+                    };
+                    if old_settings != new_api_key {
+                        let diff = diff_ron_values(&old_settings, &new_api_key).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ApiGatewayConnectorOp::UpdateApiKey(old_settings, new_api_key),
+                            format!("Modify API Gateway API key `{}`\n{}", api_key_id, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+        }
+    }
+}
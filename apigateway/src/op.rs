@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{ApiKey, ApiResource, Deployment, RestApi, Stage, UsagePlan},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ApiGatewayConnectorOp {
+    // REST API operations
+    CreateRestApi(RestApi),
+    /// Brings the API's settings (everything but its definition and tags) in line with the given API.
+    UpdateRestApi(RestApi),
+    UpdateRestApiTags(Tags, Tags),
+    /// Reimports the API's resources and methods from its OpenAPI document, overwriting them.
+    ImportOpenApi(RestApi),
+    /// Creates, updates and deletes resources and methods until the API has exactly these.
+    PutResources(BTreeMap<String, ApiResource>),
+    DeleteRestApi,
+
+    // Deployment operations
+    CreateDeployment(Deployment),
+    /// Creates a new deployment, moves the old deployment's stages onto it, and deletes the old one.
+    ReplaceDeployment(Deployment),
+    DeleteDeployment,
+
+    // Stage operations
+    CreateStage(Stage),
+    UpdateStage(Stage, Stage),
+    UpdateStageTags(Tags, Tags),
+    DeleteStage,
+
+    // Usage plan operations
+    CreateUsagePlan(UsagePlan),
+    UpdateUsagePlan(UsagePlan, UsagePlan),
+    UpdateUsagePlanTags(Tags, Tags),
+    DeleteUsagePlan,
+
+    // API key operations
+    CreateApiKey(ApiKey),
+    UpdateApiKey(ApiKey, ApiKey),
+    UpdateApiKeyTags(Tags, Tags),
+    DeleteApiKey,
+}
+
+impl ConnectorOp for ApiGatewayConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
@@ -0,0 +1,722 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::Path,
+};
+
+use anyhow::{Context, bail};
+use autoschematic_core::{connector::OpExecResponse, util::RON};
+use aws_sdk_apigateway::{
+    primitives::Blob,
+    types::{
+        ApiKeySourceType, ApiStage, EndpointConfiguration, EndpointType, IntegrationType, Op, PatchOperation, PutMode,
+        QuotaPeriodType, QuotaSettings, ThrottleSettings,
+    },
+};
+
+use crate::{
+    resource::{ApiDefinition, ApiKey, ApiResource, Deployment, Method, RestApi, Stage, UsagePlan},
+    tags::{Tags, tag_diff},
+    util::{
+        ancestor_paths, api_key_patch_ops, definition_sha256, live_resources, rest_api_from_sdk, rest_api_patch_ops,
+        ron_to_json, stage_patch_ops, usage_plan_patch_ops,
+    },
+};
+
+/// The outputs that `get` uses to tell whether the API's definition came from an OpenAPI document.
+fn definition_outputs(definition: &ApiDefinition, sha256: Option<String>) -> anyhow::Result<HashMap<String, Option<String>>> {
+    let mut outputs = HashMap::new();
+    match definition {
+        ApiDefinition::OpenApi { .. } => {
+            outputs.insert(String::from("definition_source"), Some(RON.to_string(definition)?));
+            outputs.insert(String::from("definition_sha256"), sha256);
+        }
+        ApiDefinition::Inline { .. } => {
+            outputs.insert(String::from("definition_source"), None);
+            outputs.insert(String::from("definition_sha256"), None);
+        }
+    }
+    Ok(outputs)
+}
+
+/// Creates a REST API and its resources and methods
+pub async fn create_rest_api(
+    client: &aws_sdk_apigateway::Client,
+    prefix: &Path,
+    rest_api: &RestApi,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let policy = match &rest_api.policy {
+        Some(policy) => Some(ron_to_json(policy)?),
+        None => None,
+    };
+
+    let resp = client
+        .create_rest_api()
+        .name(&rest_api.name)
+        .set_description(rest_api.description.clone())
+        .endpoint_configuration(
+            EndpointConfiguration::builder()
+                .types(EndpointType::from(rest_api.endpoint_type.as_str()))
+                .build(),
+        )
+        .set_binary_media_types(Some(rest_api.binary_media_types.clone()))
+        .set_api_key_source(rest_api.api_key_source.as_deref().map(ApiKeySourceType::from))
+        .set_minimum_compression_size(rest_api.minimum_compression_size)
+        .disable_execute_api_endpoint(rest_api.disable_execute_api_endpoint)
+        .set_policy(policy)
+        .set_tags(rest_api.tags.clone().into())
+        .send()
+        .await?;
+
+    let api_id = resp.id.context("No REST API ID returned")?;
+
+    let definition = match &rest_api.definition {
+        ApiDefinition::OpenApi { .. } => import_openapi(client, prefix, &api_id, rest_api).await?,
+        ApiDefinition::Inline { resources } => put_resources(client, &api_id, resources).await?,
+    };
+
+    let mut outputs = definition.outputs.unwrap_or_default();
+    outputs.insert(String::from("api_id"), Some(api_id.clone()));
+
+    Ok(OpExecResponse {
+        outputs: Some(outputs),
+        friendly_message: Some(format!("Created REST API {} ({})", rest_api.name, api_id)),
+    })
+}
+
+/// Brings the API's settings in line with `rest_api`, comparing against what API Gateway has now
+pub async fn update_rest_api(
+    client: &aws_sdk_apigateway::Client,
+    api_id: &str,
+    rest_api: &RestApi,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let resp = client.get_rest_api().rest_api_id(api_id).send().await?;
+    let live = rest_api_from_sdk(resp, rest_api.definition.clone())?;
+
+    let patch_ops = rest_api_patch_ops(&live, rest_api)?;
+    if !patch_ops.is_empty() {
+        client
+            .update_rest_api()
+            .rest_api_id(api_id)
+            .set_patch_operations(Some(patch_ops))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated REST API {}", api_id)),
+    })
+}
+
+/// Updates tags on any API Gateway resource
+pub async fn update_tags(
+    client: &aws_sdk_apigateway::Client,
+    resource_arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(resource_arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client
+            .tag_resource()
+            .resource_arn(resource_arn)
+            .set_tags(Some(new_tagset))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for {}", resource_arn)),
+    })
+}
+
+/// Overwrites the API's resources and methods with those in its OpenAPI document. The import can
+/// also change the API's name, description and other settings, so those are put back afterwards.
+pub async fn import_openapi(
+    client: &aws_sdk_apigateway::Client,
+    prefix: &Path,
+    api_id: &str,
+    rest_api: &RestApi,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let ApiDefinition::OpenApi { path } = &rest_api.definition else {
+        bail!("REST API {} is not defined by an OpenAPI document", rest_api.name);
+    };
+
+    let full_path = prefix.join(path);
+    let document =
+        std::fs::read(&full_path).with_context(|| format!("Failed to read OpenAPI document {}", full_path.display()))?;
+    let sha256 = definition_sha256(&document);
+
+    client
+        .put_rest_api()
+        .rest_api_id(api_id)
+        .mode(PutMode::Overwrite)
+        .fail_on_warnings(true)
+        .body(Blob::new(document))
+        .send()
+        .await
+        .with_context(|| format!("Failed to import OpenAPI document {} into REST API {}", path, api_id))?;
+
+    update_rest_api(client, api_id, rest_api).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(definition_outputs(&rest_api.definition, Some(sha256))?),
+        friendly_message: Some(format!("Imported OpenAPI document {} into REST API {}", path, api_id)),
+    })
+}
+
+async fn put_method(
+    client: &aws_sdk_apigateway::Client,
+    api_id: &str,
+    resource_id: &str,
+    http_method: &str,
+    method: &Method,
+) -> anyhow::Result<()> {
+    client
+        .put_method()
+        .rest_api_id(api_id)
+        .resource_id(resource_id)
+        .http_method(http_method)
+        .authorization_type(&method.authorization_type)
+        .set_authorizer_id(method.authorizer_id.clone())
+        .api_key_required(method.api_key_required)
+        .set_request_parameters(Some(method.request_parameters.clone().into_iter().collect()))
+        .send()
+        .await?;
+
+    if let Some(integration) = &method.integration {
+        client
+            .put_integration()
+            .rest_api_id(api_id)
+            .resource_id(resource_id)
+            .http_method(http_method)
+            .r#type(IntegrationType::from(integration.integration_type.as_str()))
+            .set_integration_http_method(integration.http_method.clone())
+            .set_uri(integration.uri.clone())
+            .set_credentials(integration.credentials.clone())
+            .set_request_parameters(Some(integration.request_parameters.clone().into_iter().collect()))
+            .set_request_templates(Some(integration.request_templates.clone().into_iter().collect()))
+            .set_passthrough_behavior(integration.passthrough_behavior.clone())
+            .set_timeout_in_millis(integration.timeout_in_millis)
+            .send()
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Creates, replaces and deletes resources and methods until the API has exactly `resources`.
+/// Resources that only exist as the parents of declared ones are created as needed.
+pub async fn put_resources(
+    client: &aws_sdk_apigateway::Client,
+    api_id: &str,
+    resources: &BTreeMap<String, ApiResource>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let live = live_resources(client, api_id).await?;
+    let mut resource_ids: BTreeMap<String, String> = live.iter().map(|(path, (id, _))| (path.clone(), id.clone())).collect();
+
+    // The root resource always exists, so its methods are removed if it isn't declared
+    let mut desired = resources.clone();
+    desired.entry(String::from("/")).or_default();
+
+    let mut needed: BTreeSet<String> = BTreeSet::new();
+    for path in desired.keys() {
+        needed.extend(ancestor_paths(path));
+        needed.insert(path.clone());
+    }
+
+    // Parents before children
+    let mut to_create: Vec<&String> = needed.iter().filter(|path| !resource_ids.contains_key(*path)).collect();
+    to_create.sort_by_key(|path| path.matches('/').count());
+    for path in to_create {
+        let Some((parent, path_part)) = path.rsplit_once('/') else {
+            bail!("Invalid resource path {}", path);
+        };
+        let parent = if parent.is_empty() { "/" } else { parent };
+        let Some(parent_id) = resource_ids.get(parent) else {
+            bail!("Parent resource {} of {} does not exist", parent, path);
+        };
+
+        let resp = client
+            .create_resource()
+            .rest_api_id(api_id)
+            .parent_id(parent_id)
+            .path_part(path_part)
+            .send()
+            .await
+            .with_context(|| format!("Failed to create resource {} in REST API {}", path, api_id))?;
+        resource_ids.insert(path.clone(), resp.id.context("No resource ID returned")?);
+    }
+
+    let mut changed_methods = 0;
+    for (path, resource) in &desired {
+        let resource_id = &resource_ids[path];
+        let live_methods = live.get(path).map(|(_, live_resource)| &live_resource.methods);
+
+        if let Some(live_methods) = live_methods {
+            for http_method in live_methods.keys() {
+                if !resource.methods.contains_key(http_method) {
+                    client
+                        .delete_method()
+                        .rest_api_id(api_id)
+                        .resource_id(resource_id)
+                        .http_method(http_method)
+                        .send()
+                        .await?;
+                    changed_methods += 1;
+                }
+            }
+        }
+
+        for (http_method, method) in &resource.methods {
+            // A method has to be deleted before it can be put again
+            match live_methods.and_then(|m| m.get(http_method)) {
+                Some(live_method) if live_method == method => continue,
+                Some(_) => {
+                    client
+                        .delete_method()
+                        .rest_api_id(api_id)
+                        .resource_id(resource_id)
+                        .http_method(http_method)
+                        .send()
+                        .await?;
+                }
+                None => {}
+            }
+
+            put_method(client, api_id, resource_id, http_method, method)
+                .await
+                .with_context(|| format!("Failed to put method {} {} in REST API {}", http_method, path, api_id))?;
+            changed_methods += 1;
+        }
+    }
+
+    // Children before parents
+    let mut to_delete: Vec<(&String, &String)> = live
+        .iter()
+        .filter(|(path, _)| !needed.contains(*path))
+        .map(|(path, (id, _))| (path, id))
+        .collect();
+    to_delete.sort_by_key(|(path, _)| std::cmp::Reverse(path.matches('/').count()));
+    let deleted_resources = to_delete.len();
+    for (_, resource_id) in to_delete {
+        client
+            .delete_resource()
+            .rest_api_id(api_id)
+            .resource_id(resource_id)
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: Some(definition_outputs(
+            &ApiDefinition::Inline {
+                resources: resources.clone(),
+            },
+            None,
+        )?),
+        friendly_message: Some(format!(
+            "Updated resources for REST API {}: {} methods put or deleted, {} resources deleted",
+            api_id, changed_methods, deleted_resources
+        )),
+    })
+}
+
+/// Deletes a REST API, along with its deployments and stages
+pub async fn delete_rest_api(client: &aws_sdk_apigateway::Client, api_id: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_rest_api().rest_api_id(api_id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("api_id"), None),
+            (String::from("definition_source"), None),
+            (String::from("definition_sha256"), None),
+        ])),
+        friendly_message: Some(format!("Deleted REST API {}", api_id)),
+    })
+}
+
+/// Deploys the API as it is now
+pub async fn create_deployment(
+    client: &aws_sdk_apigateway::Client,
+    api_id: &str,
+    deployment: &Deployment,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let resp = client
+        .create_deployment()
+        .rest_api_id(api_id)
+        .set_description(deployment.description.clone())
+        .send()
+        .await?;
+
+    let deployment_id = resp.id.context("No deployment ID returned")?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("deployment_id"), Some(deployment_id.clone()))])),
+        friendly_message: Some(format!("Created deployment {} of REST API {}", deployment_id, api_id)),
+    })
+}
+
+/// Creates a new deployment and moves every stage serving the old one onto it,
+/// since a deployment can't be deleted while stages use it
+pub async fn replace_deployment(
+    client: &aws_sdk_apigateway::Client,
+    api_id: &str,
+    old_deployment_id: &str,
+    deployment: &Deployment,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let resp = client
+        .create_deployment()
+        .rest_api_id(api_id)
+        .set_description(deployment.description.clone())
+        .send()
+        .await?;
+
+    let new_deployment_id = resp.id.context("No deployment ID returned")?;
+
+    let stages = client
+        .get_stages()
+        .rest_api_id(api_id)
+        .deployment_id(old_deployment_id)
+        .send()
+        .await?;
+
+    let mut moved_stages = Vec::new();
+    for stage in stages.item.unwrap_or_default() {
+        let Some(stage_name) = stage.stage_name else {
+            continue;
+        };
+        client
+            .update_stage()
+            .rest_api_id(api_id)
+            .stage_name(&stage_name)
+            .patch_operations(
+                PatchOperation::builder()
+                    .op(Op::Replace)
+                    .path("/deploymentId")
+                    .value(&new_deployment_id)
+                    .build(),
+            )
+            .send()
+            .await?;
+        moved_stages.push(stage_name);
+    }
+
+    client
+        .delete_deployment()
+        .rest_api_id(api_id)
+        .deployment_id(old_deployment_id)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("deployment_id"), Some(new_deployment_id.clone()))])),
+        friendly_message: Some(format!(
+            "Replaced deployment {} of REST API {} with {}, moving stages [{}]",
+            old_deployment_id,
+            api_id,
+            new_deployment_id,
+            moved_stages.join(", ")
+        )),
+    })
+}
+
+/// Deletes a deployment. API Gateway refuses if any stage still serves it.
+pub async fn delete_deployment(
+    client: &aws_sdk_apigateway::Client,
+    api_id: &str,
+    deployment_id: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .delete_deployment()
+        .rest_api_id(api_id)
+        .deployment_id(deployment_id)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("deployment_id"), None)])),
+        friendly_message: Some(format!("Deleted deployment {} of REST API {}", deployment_id, api_id)),
+    })
+}
+
+/// Creates a stage serving `deployment_id`, which `stage.deployment` resolves to
+pub async fn create_stage(
+    client: &aws_sdk_apigateway::Client,
+    api_id: &str,
+    stage_name: &str,
+    stage: &Stage,
+    deployment_id: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .create_stage()
+        .rest_api_id(api_id)
+        .stage_name(stage_name)
+        .deployment_id(deployment_id)
+        .set_description(stage.description.clone())
+        .set_variables(Some(stage.variables.clone().into_iter().collect()))
+        .tracing_enabled(stage.tracing_enabled)
+        .set_tags(stage.tags.clone().into())
+        .send()
+        .await?;
+
+    // Throttling can only be set once the stage exists
+    let unthrottled = Stage {
+        throttling: None,
+        ..stage.clone()
+    };
+    let patch_ops = stage_patch_ops(&unthrottled, stage, deployment_id);
+    if !patch_ops.is_empty() {
+        client
+            .update_stage()
+            .rest_api_id(api_id)
+            .stage_name(stage_name)
+            .set_patch_operations(Some(patch_ops))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Created stage {} of REST API {}", stage_name, api_id)),
+    })
+}
+
+pub async fn update_stage(
+    client: &aws_sdk_apigateway::Client,
+    api_id: &str,
+    stage_name: &str,
+    old_stage: &Stage,
+    new_stage: &Stage,
+    deployment_id: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let patch_ops = stage_patch_ops(old_stage, new_stage, deployment_id);
+    if !patch_ops.is_empty() {
+        client
+            .update_stage()
+            .rest_api_id(api_id)
+            .stage_name(stage_name)
+            .set_patch_operations(Some(patch_ops))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated stage {} of REST API {}", stage_name, api_id)),
+    })
+}
+
+pub async fn delete_stage(
+    client: &aws_sdk_apigateway::Client,
+    api_id: &str,
+    stage_name: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .delete_stage()
+        .rest_api_id(api_id)
+        .stage_name(stage_name)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Deleted stage {} of REST API {}", stage_name, api_id)),
+    })
+}
+
+fn throttle_settings(throttling: &crate::resource::Throttling) -> ThrottleSettings {
+    ThrottleSettings::builder()
+        .burst_limit(throttling.burst_limit)
+        .rate_limit(throttling.rate_limit)
+        .build()
+}
+
+/// Creates a usage plan. The plan's API IDs and key IDs must already be resolved.
+pub async fn create_usage_plan(client: &aws_sdk_apigateway::Client, usage_plan: &UsagePlan) -> Result<OpExecResponse, anyhow::Error> {
+    let api_stages = usage_plan
+        .api_stages
+        .iter()
+        .map(|s| ApiStage::builder().api_id(&s.api_id).stage(&s.stage).build())
+        .collect();
+
+    let quota = usage_plan.quota.as_ref().map(|quota| {
+        QuotaSettings::builder()
+            .limit(quota.limit)
+            .offset(quota.offset)
+            .period(QuotaPeriodType::from(quota.period.as_str()))
+            .build()
+    });
+
+    let resp = client
+        .create_usage_plan()
+        .name(&usage_plan.name)
+        .set_description(usage_plan.description.clone())
+        .set_api_stages(Some(api_stages))
+        .set_throttle(usage_plan.throttle.as_ref().map(throttle_settings))
+        .set_quota(quota)
+        .set_tags(usage_plan.tags.clone().into())
+        .send()
+        .await?;
+
+    let usage_plan_id = resp.id.context("No usage plan ID returned")?;
+
+    for api_key_id in &usage_plan.api_keys {
+        client
+            .create_usage_plan_key()
+            .usage_plan_id(&usage_plan_id)
+            .key_id(api_key_id)
+            .key_type("API_KEY")
+            .send()
+            .await
+            .with_context(|| format!("Failed to add API key {} to usage plan {}", api_key_id, usage_plan_id))?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("usage_plan_id"), Some(usage_plan_id.clone()))])),
+        friendly_message: Some(format!("Created usage plan {} ({})", usage_plan.name, usage_plan_id)),
+    })
+}
+
+/// Updates a usage plan. Both plans' API IDs and key IDs must already be resolved.
+pub async fn update_usage_plan(
+    client: &aws_sdk_apigateway::Client,
+    usage_plan_id: &str,
+    old_usage_plan: &UsagePlan,
+    new_usage_plan: &UsagePlan,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let patch_ops = usage_plan_patch_ops(old_usage_plan, new_usage_plan);
+    if !patch_ops.is_empty() {
+        client
+            .update_usage_plan()
+            .usage_plan_id(usage_plan_id)
+            .set_patch_operations(Some(patch_ops))
+            .send()
+            .await?;
+    }
+
+    for api_key_id in &old_usage_plan.api_keys {
+        if !new_usage_plan.api_keys.contains(api_key_id) {
+            client
+                .delete_usage_plan_key()
+                .usage_plan_id(usage_plan_id)
+                .key_id(api_key_id)
+                .send()
+                .await?;
+        }
+    }
+
+    for api_key_id in &new_usage_plan.api_keys {
+        if !old_usage_plan.api_keys.contains(api_key_id) {
+            client
+                .create_usage_plan_key()
+                .usage_plan_id(usage_plan_id)
+                .key_id(api_key_id)
+                .key_type("API_KEY")
+                .send()
+                .await
+                .with_context(|| format!("Failed to add API key {} to usage plan {}", api_key_id, usage_plan_id))?;
+        }
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated usage plan {}", usage_plan_id)),
+    })
+}
+
+/// Deletes a usage plan. API Gateway refuses while it has API stages, so those are removed first.
+pub async fn delete_usage_plan(
+    client: &aws_sdk_apigateway::Client,
+    usage_plan_id: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let usage_plan = client.get_usage_plan().usage_plan_id(usage_plan_id).send().await?;
+
+    let patch_ops: Vec<_> = usage_plan
+        .api_stages
+        .unwrap_or_default()
+        .iter()
+        .map(|s| {
+            PatchOperation::builder()
+                .op(Op::Remove)
+                .path("/apiStages")
+                .value(format!("{}:{}", s.api_id().unwrap_or_default(), s.stage().unwrap_or_default()))
+                .build()
+        })
+        .collect();
+    if !patch_ops.is_empty() {
+        client
+            .update_usage_plan()
+            .usage_plan_id(usage_plan_id)
+            .set_patch_operations(Some(patch_ops))
+            .send()
+            .await?;
+    }
+
+    client.delete_usage_plan().usage_plan_id(usage_plan_id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("usage_plan_id"), None)])),
+        friendly_message: Some(format!("Deleted usage plan {}", usage_plan_id)),
+    })
+}
+
+pub async fn create_api_key(client: &aws_sdk_apigateway::Client, api_key: &ApiKey) -> Result<OpExecResponse, anyhow::Error> {
+    let resp = client
+        .create_api_key()
+        .name(&api_key.name)
+        .set_description(api_key.description.clone())
+        .enabled(api_key.enabled)
+        .set_tags(api_key.tags.clone().into())
+        .send()
+        .await?;
+
+    let api_key_id = resp.id.context("No API key ID returned")?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("api_key_id"), Some(api_key_id.clone()))])),
+        friendly_message: Some(format!("Created API key {} ({})", api_key.name, api_key_id)),
+    })
+}
+
+pub async fn update_api_key(
+    client: &aws_sdk_apigateway::Client,
+    api_key_id: &str,
+    old_api_key: &ApiKey,
+    new_api_key: &ApiKey,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let patch_ops = api_key_patch_ops(old_api_key, new_api_key);
+    if !patch_ops.is_empty() {
+        client
+            .update_api_key()
+            .api_key(api_key_id)
+            .set_patch_operations(Some(patch_ops))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated API key {}", api_key_id)),
+    })
+}
+
+pub async fn delete_api_key(client: &aws_sdk_apigateway::Client, api_key_id: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_api_key().api_key(api_key_id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("api_key_id"), None)])),
+        friendly_message: Some(format!("Deleted API key {}", api_key_id)),
+    })
+}
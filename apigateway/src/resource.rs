@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::ApiGatewayResourceAddress, tags::Tags};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RestApi {
+    pub name: String,
+    pub description: Option<String>,
+    pub endpoint_type: String, // EDGE, REGIONAL or PRIVATE
+    #[serde(default)]
+    pub binary_media_types: Vec<String>,
+    pub api_key_source: Option<String>, // HEADER (the default) or AUTHORIZER
+    /// Payloads at least this many bytes long are compressed. If None, compression is disabled.
+    pub minimum_compression_size: Option<i32>,
+    #[serde(default)]
+    pub disable_execute_api_endpoint: bool,
+    /// The API's resource policy.
+    pub policy: Option<ron::Value>,
+    pub definition: ApiDefinition,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ApiDefinition {
+    /// An OpenAPI 3.0 or Swagger 2.0 document (JSON or YAML) in this repository, relative to the
+    /// repository root. The API's resources and methods are reimported from the document,
+    /// overwriting them, whenever its contents change.
+    OpenApi { path: String },
+    /// Resources and their methods, keyed by path such as `/orders/{order_id}`. Resources without
+    /// methods of their own, such as `/orders` here, are created as needed and aren't listed.
+    /// Response mappings for non-proxy integrations aren't supported inline; use an OpenAPI document for those.
+    Inline { resources: BTreeMap<String, ApiResource> },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ApiResource {
+    /// Keyed by HTTP method, or ANY.
+    #[serde(default)]
+    pub methods: BTreeMap<String, Method>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Method {
+    pub authorization_type: String, // NONE, AWS_IAM, CUSTOM or COGNITO_USER_POOLS
+    pub authorizer_id: Option<String>,
+    #[serde(default)]
+    pub api_key_required: bool,
+    /// e.g. `method.request.querystring.page`, mapped to whether the parameter is required.
+    #[serde(default)]
+    pub request_parameters: BTreeMap<String, bool>,
+    pub integration: Option<Integration>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Integration {
+    pub integration_type: String, // AWS, AWS_PROXY, HTTP, HTTP_PROXY or MOCK
+    /// The method used to call the backend. Required for every type but MOCK, and always POST for Lambda functions.
+    pub http_method: Option<String>,
+    pub uri: Option<String>,
+    /// An IAM role ARN for API Gateway to assume when calling the backend.
+    pub credentials: Option<String>,
+    #[serde(default)]
+    pub request_parameters: BTreeMap<String, String>,
+    /// Mapping templates keyed by content type.
+    #[serde(default)]
+    pub request_templates: BTreeMap<String, String>,
+    pub passthrough_behavior: Option<String>, // WHEN_NO_MATCH, WHEN_NO_TEMPLATES or NEVER
+    /// If None, the default of 29 seconds applies.
+    pub timeout_in_millis: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Deployment {
+    /// Deployments are snapshots of the API as it was when they were created, and can't be changed.
+    /// Changing the description replaces the deployment, which moves its stages onto the new snapshot.
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Stage {
+    /// The name of a deployment under this API's `deployments/`, or the ID of a deployment made outside this repository.
+    pub deployment: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+    /// Default throttling for every method in the stage.
+    pub throttling: Option<Throttling>,
+    #[serde(default)]
+    pub tracing_enabled: bool,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Throttling {
+    pub burst_limit: i32,
+    /// Requests per second.
+    pub rate_limit: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct UsagePlan {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub api_stages: Vec<UsagePlanStage>,
+    pub throttle: Option<Throttling>,
+    pub quota: Option<Quota>,
+    /// The names of API keys under `api_keys/`, or the IDs of keys made outside this repository.
+    #[serde(default)]
+    pub api_keys: Vec<String>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct UsagePlanStage {
+    /// The name of a REST API under `restapis/`, or the ID of an API made outside this repository.
+    pub api_id: String,
+    pub stage: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Quota {
+    pub limit: i32,
+    pub period: String, // DAY, WEEK or MONTH
+    /// Days into the period at which the quota resets.
+    #[serde(default)]
+    pub offset: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKey {
+    /// The key's value is generated by API Gateway and is never stored in the repository.
+    pub name: String,
+    pub description: Option<String>,
+    pub enabled: bool,
+    pub tags: Tags,
+}
+
+pub enum ApiGatewayResource {
+    RestApi(RestApi),
+    Deployment(Deployment),
+    Stage(Stage),
+    UsagePlan(UsagePlan),
+    ApiKey(ApiKey),
+}
+
+impl Resource for ApiGatewayResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            ApiGatewayResource::RestApi(rest_api) => Ok(RON.to_string_pretty(&rest_api, pretty_config)?.into()),
+            ApiGatewayResource::Deployment(deployment) => Ok(RON.to_string_pretty(&deployment, pretty_config)?.into()),
+            ApiGatewayResource::Stage(stage) => Ok(RON.to_string_pretty(&stage, pretty_config)?.into()),
+            ApiGatewayResource::UsagePlan(usage_plan) => Ok(RON.to_string_pretty(&usage_plan, pretty_config)?.into()),
+            ApiGatewayResource::ApiKey(api_key) => Ok(RON.to_string_pretty(&api_key, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = ApiGatewayResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            ApiGatewayResourceAddress::RestApi { .. } => Ok(ApiGatewayResource::RestApi(RON.from_str(s)?)),
+            ApiGatewayResourceAddress::Deployment { .. } => Ok(ApiGatewayResource::Deployment(RON.from_str(s)?)),
+            ApiGatewayResourceAddress::Stage { .. } => Ok(ApiGatewayResource::Stage(RON.from_str(s)?)),
+            ApiGatewayResourceAddress::UsagePlan { .. } => Ok(ApiGatewayResource::UsagePlan(RON.from_str(s)?)),
+            ApiGatewayResourceAddress::ApiKey { .. } => Ok(ApiGatewayResource::ApiKey(RON.from_str(s)?)),
+        }
+    }
+}
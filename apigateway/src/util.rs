@@ -0,0 +1,458 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+use anyhow::{Context, bail};
+use autoschematic_core::util::RON;
+use aws_sdk_apigateway::{
+    operation::get_rest_api::GetRestApiOutput,
+    types::{Op, PatchOperation},
+};
+use sha2::{Digest, Sha256};
+
+use crate::resource::{ApiDefinition, ApiKey, ApiResource, Integration, Method, RestApi, Stage, UsagePlan};
+
+const HTTP_METHODS: [&str; 8] = ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "ANY"];
+
+/// The integration timeout API Gateway reports when none was set.
+const DEFAULT_TIMEOUT_IN_MILLIS: i32 = 29000;
+
+pub fn rest_api_arn(region: &str, api_id: &str) -> String {
+    format!("arn:aws:apigateway:{region}::/restapis/{api_id}")
+}
+
+pub fn stage_arn(region: &str, api_id: &str, stage_name: &str) -> String {
+    format!("arn:aws:apigateway:{region}::/restapis/{api_id}/stages/{stage_name}")
+}
+
+pub fn usage_plan_arn(region: &str, usage_plan_id: &str) -> String {
+    format!("arn:aws:apigateway:{region}::/usageplans/{usage_plan_id}")
+}
+
+pub fn api_key_arn(region: &str, api_key_id: &str) -> String {
+    format!("arn:aws:apigateway:{region}::/apikeys/{api_key_id}")
+}
+
+/// The hex-encoded SHA-256 of an OpenAPI document.
+pub fn definition_sha256(document: &[u8]) -> String {
+    Sha256::digest(document).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Works out whether the API's resources and methods came from an OpenAPI document in this repository.
+///
+/// `recorded_source` and `recorded_sha256` are the outputs written when this repository last imported
+/// the API's definition. The document is only reported as the source if it still matches what was
+/// imported, so that editing the document shows up as a diff; otherwise the live resources are reported.
+/// Changes made to the API outside this repository after an import aren't detected.
+pub fn resolve_api_definition(
+    prefix: &Path,
+    live: ApiDefinition,
+    recorded_source: Option<ApiDefinition>,
+    recorded_sha256: Option<String>,
+) -> anyhow::Result<ApiDefinition> {
+    match (recorded_source, recorded_sha256) {
+        (Some(ApiDefinition::OpenApi { path }), Some(recorded_sha256)) => {
+            let full_path = prefix.join(&path);
+            if full_path.is_file() && definition_sha256(&std::fs::read(&full_path)?) == recorded_sha256 {
+                Ok(ApiDefinition::OpenApi { path })
+            } else {
+                Ok(live)
+            }
+        }
+        _ => Ok(live),
+    }
+}
+
+pub fn json_to_ron(json: &str) -> anyhow::Result<ron::Value> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    Ok(RON.from_str(&RON.to_string(&value)?)?)
+}
+
+pub fn ron_to_json(value: &ron::Value) -> anyhow::Result<String> {
+    serde_json::to_string(value).context("Failed to serialize policy as JSON")
+}
+
+/// API Gateway returns resource policies with their quotes (and sometimes slashes) backslash-escaped.
+pub fn policy_to_ron(policy: &str) -> anyhow::Result<ron::Value> {
+    match json_to_ron(policy) {
+        Ok(policy) => Ok(policy),
+        Err(_) => json_to_ron(&policy.replace("\\\"", "\"").replace("\\/", "/")),
+    }
+}
+
+fn policies_equal(a: &Option<ron::Value>, b: &Option<ron::Value>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => match (ron_to_json(a), ron_to_json(b)) {
+            (Ok(a), Ok(b)) => serde_json::from_str::<serde_json::Value>(&a).ok() == serde_json::from_str(&b).ok(),
+            _ => false,
+        },
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Reads a REST API's settings back, with the definition and tags supplied by the caller.
+pub fn rest_api_from_sdk(resp: GetRestApiOutput, definition: ApiDefinition) -> anyhow::Result<RestApi> {
+    let policy = match resp.policy.filter(|p| !p.is_empty()) {
+        Some(policy) => Some(policy_to_ron(&policy)?),
+        None => None,
+    };
+
+    let endpoint_type = resp
+        .endpoint_configuration
+        .and_then(|c| c.types)
+        .and_then(|t| t.into_iter().next())
+        .map(|t| t.as_str().to_string())
+        .unwrap_or_else(|| String::from("EDGE"));
+
+    let mut binary_media_types = resp.binary_media_types.unwrap_or_default();
+    binary_media_types.sort();
+
+    Ok(RestApi {
+        name: resp.name.unwrap_or_default(),
+        description: resp.description.filter(|d| !d.is_empty()),
+        endpoint_type,
+        binary_media_types,
+        // HEADER is the default
+        api_key_source: resp
+            .api_key_source
+            .map(|s| s.as_str().to_string())
+            .filter(|s| s != "HEADER"),
+        minimum_compression_size: resp.minimum_compression_size,
+        disable_execute_api_endpoint: resp.disable_execute_api_endpoint,
+        policy,
+        definition,
+        tags: resp.tags.into(),
+    })
+}
+
+pub fn method_from_sdk(method: &aws_sdk_apigateway::types::Method) -> Method {
+    let integration = method.method_integration().map(|integration| Integration {
+        integration_type: integration.r#type().map(|t| t.as_str().to_string()).unwrap_or_default(),
+        http_method: integration.http_method().map(String::from),
+        uri: integration.uri().map(String::from).filter(|u| !u.is_empty()),
+        credentials: integration.credentials().map(String::from),
+        request_parameters: integration
+            .request_parameters()
+            .map(|p| p.clone().into_iter().collect())
+            .unwrap_or_default(),
+        request_templates: integration
+            .request_templates()
+            .map(|t| t.clone().into_iter().collect())
+            .unwrap_or_default(),
+        passthrough_behavior: integration.passthrough_behavior().map(String::from),
+        timeout_in_millis: Some(integration.timeout_in_millis).filter(|t| *t != 0 && *t != DEFAULT_TIMEOUT_IN_MILLIS),
+    });
+
+    Method {
+        authorization_type: method.authorization_type().unwrap_or("NONE").to_string(),
+        authorizer_id: method.authorizer_id().map(String::from),
+        api_key_required: method.api_key_required().unwrap_or(false),
+        request_parameters: method
+            .request_parameters()
+            .map(|p| p.clone().into_iter().collect())
+            .unwrap_or_default(),
+        integration,
+    }
+}
+
+/// An API's resources as API Gateway has them, keyed by path, along with each resource's ID.
+pub async fn live_resources(
+    client: &aws_sdk_apigateway::Client,
+    api_id: &str,
+) -> anyhow::Result<BTreeMap<String, (String, ApiResource)>> {
+    let mut resources = BTreeMap::new();
+
+    let mut pages = client
+        .get_resources()
+        .rest_api_id(api_id)
+        .embed("methods")
+        .into_paginator()
+        .items()
+        .send();
+    while let Some(resource) = pages.next().await {
+        let resource = resource?;
+        let (Some(id), Some(path)) = (resource.id.clone(), resource.path.clone()) else {
+            continue;
+        };
+
+        let methods = resource
+            .resource_methods()
+            .map(|methods| {
+                methods
+                    .iter()
+                    .map(|(http_method, method)| (http_method.clone(), method_from_sdk(method)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        resources.insert(path, (id, ApiResource { methods }));
+    }
+
+    Ok(resources)
+}
+
+/// The live resources as an inline definition. Resources without methods are left out,
+/// since they're only there as the parents of other resources (or, for `/`, because every API has one).
+pub fn inline_definition(live: &BTreeMap<String, (String, ApiResource)>) -> ApiDefinition {
+    ApiDefinition::Inline {
+        resources: live
+            .iter()
+            .filter(|(_, (_, resource))| !resource.methods.is_empty())
+            .map(|(path, (_, resource))| (path.clone(), resource.clone()))
+            .collect(),
+    }
+}
+
+/// Every ancestor of a resource path, nearest the root first, e.g. `/a/b/c` gives `/a` and `/a/b`.
+pub fn ancestor_paths(path: &str) -> Vec<String> {
+    let mut ancestors = Vec::new();
+    let mut current = String::new();
+    let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    for segment in &segments[..segments.len().saturating_sub(1)] {
+        current.push('/');
+        current.push_str(segment);
+        ancestors.push(current.clone());
+    }
+    ancestors
+}
+
+/// Catches definitions that API Gateway would reject at plan time.
+pub fn check_definition(prefix: &Path, api_name: &str, definition: &ApiDefinition) -> anyhow::Result<()> {
+    match definition {
+        ApiDefinition::OpenApi { path } => {
+            if !prefix.join(path).is_file() {
+                bail!("OpenAPI document {} for REST API {} does not exist", path, api_name);
+            }
+        }
+        ApiDefinition::Inline { resources } => {
+            for (path, resource) in resources {
+                if !path.starts_with('/') || (path != "/" && (path.ends_with('/') || path.contains("//"))) {
+                    bail!("REST API {} has an invalid resource path {}", api_name, path);
+                }
+
+                // `get` doesn't report resources without methods, so listing one would never stop diffing
+                if resource.methods.is_empty() {
+                    bail!(
+                        "Resource {} in REST API {} has no methods; parent resources are created as needed and needn't be listed",
+                        path,
+                        api_name
+                    );
+                }
+
+                for (http_method, method) in &resource.methods {
+                    if !HTTP_METHODS.contains(&http_method.as_str()) {
+                        bail!(
+                            "REST API {} has an invalid HTTP method {} for resource {}; expected one of {}",
+                            api_name,
+                            http_method,
+                            path,
+                            HTTP_METHODS.join(", ")
+                        );
+                    }
+
+                    if let Some(integration) = &method.integration
+                        && integration.integration_type != "MOCK"
+                        && integration.http_method.is_none()
+                    {
+                        bail!(
+                            "The {} integration for {} {} in REST API {} needs an http_method",
+                            integration.integration_type,
+                            http_method,
+                            path,
+                            api_name
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Escapes a JSON pointer segment for use in a patch path.
+fn escape_patch_path(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+fn patch(op: Op, path: impl Into<String>, value: Option<String>) -> PatchOperation {
+    PatchOperation::builder().op(op).path(path).set_value(value).build()
+}
+
+fn replace(path: impl Into<String>, value: impl Into<String>) -> PatchOperation {
+    patch(Op::Replace, path, Some(value.into()))
+}
+
+/// Patch operations that turn the settings of `old` into those of `new`. The definition and tags aren't compared.
+pub fn rest_api_patch_ops(old: &RestApi, new: &RestApi) -> anyhow::Result<Vec<PatchOperation>> {
+    let mut ops = Vec::new();
+
+    if old.name != new.name {
+        ops.push(replace("/name", &new.name));
+    }
+
+    if old.description != new.description {
+        ops.push(replace("/description", new.description.clone().unwrap_or_default()));
+    }
+
+    if old.endpoint_type != new.endpoint_type {
+        ops.push(replace(
+            format!("/endpointConfiguration/types/{}", old.endpoint_type),
+            &new.endpoint_type,
+        ));
+    }
+
+    let old_types: BTreeSet<&String> = old.binary_media_types.iter().collect();
+    let new_types: BTreeSet<&String> = new.binary_media_types.iter().collect();
+    for removed in old_types.difference(&new_types) {
+        ops.push(patch(
+            Op::Remove,
+            format!("/binaryMediaTypes/{}", escape_patch_path(removed)),
+            None,
+        ));
+    }
+    for added in new_types.difference(&old_types) {
+        ops.push(patch(Op::Add, format!("/binaryMediaTypes/{}", escape_patch_path(added)), None));
+    }
+
+    if old.api_key_source != new.api_key_source {
+        ops.push(replace(
+            "/apiKeySource",
+            new.api_key_source.clone().unwrap_or_else(|| String::from("HEADER")),
+        ));
+    }
+
+    // An empty value disables compression
+    if old.minimum_compression_size != new.minimum_compression_size {
+        ops.push(replace(
+            "/minimumCompressionSize",
+            new.minimum_compression_size.map(|s| s.to_string()).unwrap_or_default(),
+        ));
+    }
+
+    if old.disable_execute_api_endpoint != new.disable_execute_api_endpoint {
+        ops.push(replace(
+            "/disableExecuteApiEndpoint",
+            new.disable_execute_api_endpoint.to_string(),
+        ));
+    }
+
+    if !policies_equal(&old.policy, &new.policy) {
+        let policy = match &new.policy {
+            Some(policy) => ron_to_json(policy)?,
+            None => String::new(),
+        };
+        ops.push(replace("/policy", policy));
+    }
+
+    Ok(ops)
+}
+
+/// Patch operations that turn `old` into `new`, where `deployment_id` is the ID `new.deployment` resolves to.
+/// Tags aren't compared.
+pub fn stage_patch_ops(old: &Stage, new: &Stage, deployment_id: &str) -> Vec<PatchOperation> {
+    let mut ops = Vec::new();
+
+    if old.deployment != new.deployment {
+        ops.push(replace("/deploymentId", deployment_id));
+    }
+
+    if old.description != new.description {
+        ops.push(replace("/description", new.description.clone().unwrap_or_default()));
+    }
+
+    for name in old.variables.keys() {
+        if !new.variables.contains_key(name) {
+            ops.push(patch(Op::Remove, format!("/variables/{}", escape_patch_path(name)), None));
+        }
+    }
+    for (name, value) in &new.variables {
+        if old.variables.get(name) != Some(value) {
+            ops.push(replace(format!("/variables/{}", escape_patch_path(name)), value));
+        }
+    }
+
+    if old.tracing_enabled != new.tracing_enabled {
+        ops.push(replace("/tracingEnabled", new.tracing_enabled.to_string()));
+    }
+
+    // Stage-wide throttling is the method setting for every resource and method, `*/*`
+    if old.throttling != new.throttling {
+        match &new.throttling {
+            Some(throttling) => {
+                ops.push(replace("/*/*/throttling/burstLimit", throttling.burst_limit.to_string()));
+                ops.push(replace("/*/*/throttling/rateLimit", throttling.rate_limit.to_string()));
+            }
+            None => ops.push(patch(Op::Remove, "/*/*", None)),
+        }
+    }
+
+    ops
+}
+
+/// Patch operations that turn `old` into `new`. Both must already have their API IDs resolved.
+/// API keys and tags aren't compared.
+pub fn usage_plan_patch_ops(old: &UsagePlan, new: &UsagePlan) -> Vec<PatchOperation> {
+    let mut ops = Vec::new();
+
+    if old.name != new.name {
+        ops.push(replace("/name", &new.name));
+    }
+
+    if old.description != new.description {
+        ops.push(replace("/description", new.description.clone().unwrap_or_default()));
+    }
+
+    let api_stage = |s: &crate::resource::UsagePlanStage| format!("{}:{}", s.api_id, s.stage);
+    let old_stages: BTreeSet<String> = old.api_stages.iter().map(api_stage).collect();
+    let new_stages: BTreeSet<String> = new.api_stages.iter().map(api_stage).collect();
+    for removed in old_stages.difference(&new_stages) {
+        ops.push(patch(Op::Remove, "/apiStages", Some(removed.clone())));
+    }
+    for added in new_stages.difference(&old_stages) {
+        ops.push(patch(Op::Add, "/apiStages", Some(added.clone())));
+    }
+
+    if old.throttle != new.throttle {
+        match &new.throttle {
+            Some(throttle) => {
+                ops.push(replace("/throttle/burstLimit", throttle.burst_limit.to_string()));
+                ops.push(replace("/throttle/rateLimit", throttle.rate_limit.to_string()));
+            }
+            None => ops.push(patch(Op::Remove, "/throttle", None)),
+        }
+    }
+
+    if old.quota != new.quota {
+        match &new.quota {
+            Some(quota) => {
+                ops.push(replace("/quota/limit", quota.limit.to_string()));
+                ops.push(replace("/quota/period", &quota.period));
+                ops.push(replace("/quota/offset", quota.offset.to_string()));
+            }
+            None => ops.push(patch(Op::Remove, "/quota", None)),
+        }
+    }
+
+    ops
+}
+
+/// Patch operations that turn `old` into `new`. Tags aren't compared.
+pub fn api_key_patch_ops(old: &ApiKey, new: &ApiKey) -> Vec<PatchOperation> {
+    let mut ops = Vec::new();
+
+    if old.name != new.name {
+        ops.push(replace("/name", &new.name));
+    }
+
+    if old.description != new.description {
+        ops.push(replace("/description", new.description.clone().unwrap_or_default()));
+    }
+
+    if old.enabled != new.enabled {
+        ops.push(replace("/enabled", new.enabled.to_string()));
+    }
+
+    ops
+}
@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
 use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 
 type Region = String;
@@ -119,3 +120,47 @@ impl ResourceAddress for ApiGatewayV2ResourceAddress {
         }
     }
 }
+
+impl DescribeAddresses for ApiGatewayV2ResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/apigatewayv2/<region>/apis/<api_id>.ron",
+                description: "An HTTP or WebSocket API",
+                example:     "aws/apigatewayv2/us-east-1/apis/a1b2c3d4e5.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/apigatewayv2/<region>/apis/<api_id>/routes/<route_id>.ron",
+                description: "An API's route",
+                example:     "aws/apigatewayv2/us-east-1/apis/a1b2c3d4e5/routes/abc1234.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/apigatewayv2/<region>/apis/<api_id>/integrations/<integration_id>.ron",
+                description: "An API's integration",
+                example:     "aws/apigatewayv2/us-east-1/apis/a1b2c3d4e5/integrations/def5678.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/apigatewayv2/<region>/apis/<api_id>/stages/<stage_name>.ron",
+                description: "An API's stage",
+                example:     "aws/apigatewayv2/us-east-1/apis/a1b2c3d4e5/stages/prod.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/apigatewayv2/<region>/apis/<api_id>/authorizers/<authorizer_id>.ron",
+                description: "An API's authorizer",
+                example:     "aws/apigatewayv2/us-east-1/apis/a1b2c3d4e5/authorizers/ghi9012.ron",
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use autoschematic_connector_aws_core::addr_doc::DescribeAddresses;
+
+    use super::ApiGatewayV2ResourceAddress;
+
+    #[test]
+    fn address_examples_parse() {
+        ApiGatewayV2ResourceAddress::checked_address_patterns().unwrap();
+    }
+}
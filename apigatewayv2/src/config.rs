@@ -15,6 +15,8 @@ pub struct ApiGatewayV2ConnectorConfig {
     pub timeout_config:  Option<TimeoutConfig>,
     pub sts_region:      String,
     pub enabled_regions: Vec<String>,
+    #[serde(default)]
+    pub max_concurrent_ops: Option<usize>,
 }
 
 impl_aws_config!(ApiGatewayV2ConnectorConfig, "aws/apigatewayv2/config.ron");
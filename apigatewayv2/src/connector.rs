@@ -26,7 +26,7 @@ use crate::{
 };
 
 pub use addr::ApiGatewayV2ResourceAddress;
-use autoschematic_connector_aws_core::config::AwsServiceConfig;
+use autoschematic_connector_aws_core::{audit::AuditLog, concurrency::OpExecLimiter, config::AwsServiceConfig};
 use autoschematic_core::skeleton;
 pub use op::ApiGatewayV2ConnectorOp;
 
@@ -42,6 +42,8 @@ pub struct ApiGatewayV2Connector {
     account_id: RwLock<String>,
     config: RwLock<ApiGatewayV2ConnectorConfig>,
     prefix: PathBuf,
+    op_limiter: tokio::sync::Mutex<Arc<OpExecLimiter>>,
+    audit_log: tokio::sync::Mutex<Arc<AuditLog>>,
 }
 
 #[async_trait]
@@ -70,6 +72,8 @@ impl Connector for ApiGatewayV2Connector {
         let account_id = secrets_config.verify_sts().await?;
 
         *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(secrets_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
         *self.config.write().await = secrets_config;
         *self.account_id.write().await = account_id;
         Ok(())
@@ -86,16 +90,16 @@ impl Connector for ApiGatewayV2Connector {
             }
 
             let apis = self.list_apis(&region).await?;
-            results.extend(apis);
 
             let mut api_resource_addresses = Vec::new();
-            for api in &results {
+            for api in &apis {
                 if let Ok(ApiGatewayV2ResourceAddress::Api { region: r, api_id: id }) =
                     ApiGatewayV2ResourceAddress::from_path(api)
                 {
                     api_resource_addresses.push((r, id));
                 }
             }
+            results.extend(apis);
 
             for (region, api_id) in api_resource_addresses {
                 let routes = self.list_routes(&region, &api_id).await?;
@@ -138,7 +142,9 @@ impl Connector for ApiGatewayV2Connector {
     }
 
     async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
-        self.do_op_exec(addr, op).await
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
     }
 
     async fn addr_virt_to_phy(&self, addr: &Path) -> anyhow::Result<VirtToPhyResponse> {
@@ -171,18 +177,18 @@ impl Connector for ApiGatewayV2Connector {
                     }]));
                 };
 
-                if let Some(route_id) = addr.get_output(&self.prefix, "route_id")? {
-                    return Ok(VirtToPhyResponse::Present(
-                        ApiGatewayV2ResourceAddress::Route {
-                            region,
-                            api_id,
-                            route_id,
-                        }
-                        .to_path_buf(),
-                    ));
-                } else {
+                let Some(route_id) = addr.get_output(&self.prefix, "route_id")? else {
                     return Ok(VirtToPhyResponse::NotPresent);
                 };
+
+                Ok(VirtToPhyResponse::Present(
+                    ApiGatewayV2ResourceAddress::Route {
+                        region,
+                        api_id,
+                        route_id,
+                    }
+                    .to_path_buf(),
+                ))
             }
             ApiGatewayV2ResourceAddress::Integration { region, api_id, .. } => {
                 let region = region.clone();
@@ -6,8 +6,6 @@ use autoschematic_core::{
     get_resource_response,
 };
 
-use anyhow::Context;
-
 use crate::{addr::ApiGatewayV2ResourceAddress, resource::ApiGatewayV2Resource};
 
 use super::ApiGatewayV2Connector;
@@ -32,7 +30,7 @@ impl ApiGatewayV2Connector {
 
                         get_resource_response!(ApiGatewayV2Resource::Api(api))
                     }
-                    Err(e) => Ok(None),
+                    Err(_) => Ok(None),
                 }
             }
             ApiGatewayV2ResourceAddress::Route {
@@ -51,7 +49,7 @@ impl ApiGatewayV2Connector {
                         };
                         get_resource_response!(ApiGatewayV2Resource::Route(route))
                     }
-                    Err(e) => Ok(None),
+                    Err(_) => Ok(None),
                 }
             }
             ApiGatewayV2ResourceAddress::Integration {
@@ -76,7 +74,7 @@ impl ApiGatewayV2Connector {
 
                         get_resource_response!(ApiGatewayV2Resource::Integration(integration))
                     }
-                    Err(e) => Ok(None),
+                    Err(_) => Ok(None),
                 }
             }
             ApiGatewayV2ResourceAddress::Stage {
@@ -97,7 +95,7 @@ impl ApiGatewayV2Connector {
 
                         get_resource_response!(ApiGatewayV2Resource::Stage(stage))
                     }
-                    Err(e) => Ok(None),
+                    Err(_) => Ok(None),
                 }
             }
             ApiGatewayV2ResourceAddress::Authorizer {
@@ -122,7 +120,7 @@ impl ApiGatewayV2Connector {
                         };
                         get_resource_response!(ApiGatewayV2Resource::Authorizer(authorizer))
                     }
-                    Err(e) => Ok(None),
+                    Err(_) => Ok(None),
                 }
             }
         }
@@ -11,7 +11,7 @@ use crate::{
     op_impl::{
         create_api, create_authorizer, create_integration, create_route, create_stage, delete_api, delete_authorizer,
         delete_integration, delete_route, delete_stage, update_api, update_api_tags, update_authorizer, update_integration,
-        update_route, update_stage,
+        update_route, update_stage, update_stage_tags,
     },
 };
 
@@ -33,7 +33,7 @@ impl ApiGatewayV2Connector {
                         update_api(&client, &account_id, region, api_id, old_api, new_api).await
                     }
                     ApiGatewayV2ConnectorOp::UpdateApiTags(old_tags, new_tags) => {
-                        update_api_tags(&client, &account_id, region, api_id, old_tags, new_tags).await
+                        update_api_tags(&client, region, api_id, old_tags, new_tags).await
                     }
                     ApiGatewayV2ConnectorOp::DeleteApi => delete_api(&client, region, api_id).await,
                     _ => Err(invalid_op(&addr, &op)),
@@ -84,6 +84,9 @@ impl ApiGatewayV2Connector {
                     ApiGatewayV2ConnectorOp::UpdateStage(old_stage, new_stage) => {
                         update_stage(&client, region, api_id, stage_name, old_stage, new_stage).await
                     }
+                    ApiGatewayV2ConnectorOp::UpdateStageTags(old_tags, new_tags) => {
+                        update_stage_tags(&client, region, api_id, stage_name, old_tags, new_tags).await
+                    }
                     ApiGatewayV2ConnectorOp::DeleteStage => delete_stage(&client, region, api_id, stage_name).await,
                     _ => Err(invalid_op(&addr, &op)),
                 }
@@ -70,7 +70,7 @@ impl ApiGatewayV2Connector {
                 }
             },
             ApiGatewayV2ResourceAddress::Route {
-                region,
+                region: _,
                 api_id,
                 route_id,
             } => match (current, desired) {
@@ -105,7 +105,7 @@ impl ApiGatewayV2Connector {
                 }
             },
             ApiGatewayV2ResourceAddress::Integration {
-                region,
+                region: _,
                 api_id,
                 integration_id,
             } => match (current, desired) {
@@ -143,7 +143,7 @@ impl ApiGatewayV2Connector {
                 }
             },
             ApiGatewayV2ResourceAddress::Stage {
-                region,
+                region: _,
                 api_id,
                 stage_name,
             } => match (current, desired) {
@@ -194,7 +194,7 @@ impl ApiGatewayV2Connector {
                 }
             },
             ApiGatewayV2ResourceAddress::Authorizer {
-                region,
+                region: _,
                 api_id,
                 authorizer_id,
             } => match (current, desired) {
@@ -2,6 +2,7 @@ use std::{path::PathBuf, sync::Arc, time::Duration};
 
 use crate::{addr::ApiGatewayV2ResourceAddress, connector::ApiGatewayV2Connector};
 use anyhow::bail;
+use autoschematic_connector_aws_core::audited_client;
 use autoschematic_core::connector::ResourceAddress;
 use aws_config::{BehaviorVersion, Region, meta::region::RegionProviderChain, timeout::TimeoutConfig};
 
@@ -24,7 +25,7 @@ impl ApiGatewayV2Connector {
                 )
                 .load()
                 .await;
-            let client = aws_sdk_apigatewayv2::Client::new(&config);
+            let client = audited_client!(aws_sdk_apigatewayv2, &config);
             cache.insert(region_s.to_string(), Arc::new(client));
         };
 
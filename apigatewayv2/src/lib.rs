@@ -1,5 +1,6 @@
-mod addr;
-mod config;
-mod op_impl;
-mod resource;
-mod tags;
+pub mod addr;
+pub mod config;
+pub mod op;
+pub mod op_impl;
+pub mod resource;
+pub mod tags;
@@ -1,3 +1,5 @@
+use addr::ApiGatewayV2ResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
 use autoschematic_core::tarpc_bridge::tarpc_connector_main;
 use connector::ApiGatewayV2Connector;
 
@@ -13,6 +15,12 @@ pub mod util;
 
 #[tokio::main]
 pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let patterns = ApiGatewayV2ResourceAddress::checked_address_patterns()?;
+        print!("{}", render_address_docs("aws/apigatewayv2", &patterns));
+        return Ok(());
+    }
+
     tarpc_connector_main::<ApiGatewayV2Connector>().await?;
     Ok(())
 }
@@ -10,7 +10,47 @@ use crate::{
 
 use autoschematic_core::op_exec_output;
 
-pub async fn create_api(client: &Client, account_id: &str, region: &str, api: Api) -> Result<OpExecResponse, anyhow::Error> {
+/// API Gateway ARNs carry no account ID.
+fn api_arn(region: &str, api_id: &str) -> String {
+    format!("arn:aws:apigateway:{region}::/apis/{api_id}")
+}
+
+fn stage_arn(region: &str, api_id: &str, stage_name: &str) -> String {
+    format!("arn:aws:apigateway:{region}::/apis/{api_id}/stages/{stage_name}")
+}
+
+async fn update_resource_tags(
+    client: &Client,
+    resource_arn: &str,
+    old_tags: &HashMap<String, String>,
+    new_tags: &HashMap<String, String>,
+) -> Result<(), anyhow::Error> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags).context("Failed to generate tag diff")?;
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(resource_arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await
+            .context("Failed to remove tags")?;
+    }
+
+    if !new_tagset.is_empty() {
+        client
+            .tag_resource()
+            .resource_arn(resource_arn)
+            .set_tags(Some(new_tagset))
+            .send()
+            .await
+            .context("Failed to add new tags")?;
+    }
+
+    Ok(())
+}
+
+pub async fn create_api(client: &Client, _account_id: &str, region: &str, api: Api) -> Result<OpExecResponse, anyhow::Error> {
     let create_api_output = client
         .create_api()
         .name(&api.name)
@@ -60,36 +100,14 @@ pub async fn update_api(
 
 pub async fn update_api_tags(
     client: &Client,
-    account_id: &str,
     region: &str,
     api_id: &str,
     old_tags: HashMap<String, String>,
     new_tags: HashMap<String, String>,
 ) -> Result<OpExecResponse, anyhow::Error> {
-    let (untag_keys, new_tagset) = tag_diff(&old_tags, &new_tags).context("Failed to generate tag diff")?;
-
-    if !untag_keys.is_empty() {
-        client
-            .untag_resource()
-            .resource_arn(format!(
-                "arn:aws:apigatewayv2:{}:{}/apis/{}/tags",
-                region, "ACCOUNT_ID_PLACEHOLDER", api_id
-            )) // TODO: Get actual account ID
-            .set_tag_keys(Some(untag_keys))
-            .send()
-            .await
-            .context("Failed to remove tags from API")?;
-    }
-
-    if !new_tagset.is_empty() {
-        client
-            .tag_resource()
-            .resource_arn(format!("arn:aws:apigatewayv2:{region}:{account_id}/apis/{api_id}")) // TODO: Get actual account ID
-            .set_tags(Some(new_tagset))
-            .send()
-            .await
-            .context("Failed to add new tags to API")?;
-    }
+    update_resource_tags(client, &api_arn(region, api_id), &old_tags, &new_tags)
+        .await
+        .context("Failed to update tags for API")?;
 
     op_exec_output!(format!(
         "Updated tags for API Gateway V2 API `{}` in region `{}`",
@@ -290,6 +308,24 @@ pub async fn update_stage(
     ))
 }
 
+pub async fn update_stage_tags(
+    client: &Client,
+    region: &str,
+    api_id: &str,
+    stage_name: &str,
+    old_tags: HashMap<String, String>,
+    new_tags: HashMap<String, String>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    update_resource_tags(client, &stage_arn(region, api_id, stage_name), &old_tags, &new_tags)
+        .await
+        .context("Failed to update tags for stage")?;
+
+    op_exec_output!(format!(
+        "Updated tags for API Gateway V2 Stage `{}` for API `{}` in region `{}`",
+        stage_name, api_id, region
+    ))
+}
+
 pub async fn delete_stage(
     client: &Client,
     region: &str,
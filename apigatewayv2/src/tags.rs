@@ -1,48 +1,5 @@
 use std::collections::HashMap;
 
-use serde::{Deserialize, Serialize};
-
-// Define Tags similar to S3 implementation
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
-pub struct Tags(HashMap<String, String>);
-
-impl From<Option<HashMap<String, String>>> for Tags {
-    fn from(value: Option<HashMap<String, String>>) -> Self {
-        match value {
-            Some(tags) => Tags(tags),
-            None => Tags(HashMap::new()),
-        }
-    }
-}
-
-// impl Into<Option<Vec<Tag>>> for Tags {
-//     fn into(self) -> Option<Vec<Tag>> {
-//         let mut out_vec = Vec::new();
-
-//         for (k, v) in self.0 {
-//             out_vec.push(Tag::builder().key(k).value(v).build());
-//         }
-
-//         Some(out_vec)
-//     }
-// }
-
-impl Tags {
-    pub fn len(&self) -> usize {
-        self.0.len()
-    }
-
-    pub fn to_vec(&self) -> anyhow::Result<Vec<(String, String)>> {
-        let mut out_vec = Vec::new();
-
-        for (k, v) in &self.0 {
-            out_vec.push((k.clone(), v.clone()));
-        }
-
-        Ok(out_vec)
-    }
-}
-
 // From a pair of hashmap determine the set of aws_ecs::Tag structs to pass to untag and set_tags respectively
 pub fn tag_diff(
     old_tags: &HashMap<String, String>,
@@ -0,0 +1,91 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum BackupResourceAddress {
+    BackupVault {
+        region: String,
+        name:   String,
+    },
+    /// Backup plans are addressed by name. Their IDs are generated by AWS Backup and looked up as needed.
+    BackupPlan {
+        region: String,
+        name:   String,
+    },
+    /// A selection of resources to back up according to a plan.
+    BackupSelection {
+        region:         String,
+        plan_name:      String,
+        selection_name: String,
+    },
+}
+
+impl ResourceAddress for BackupResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            BackupResourceAddress::BackupVault { region, name } => PathBuf::from(format!("aws/backup/{region}/vaults/{name}.ron")),
+            BackupResourceAddress::BackupPlan { region, name } => PathBuf::from(format!("aws/backup/{region}/plans/{name}.ron")),
+            BackupResourceAddress::BackupSelection {
+                region,
+                plan_name,
+                selection_name,
+            } => PathBuf::from(format!(
+                "aws/backup/{region}/plans/{plan_name}/selections/{selection_name}.ron"
+            )),
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "backup", region, "vaults", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(BackupResourceAddress::BackupVault {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "backup", region, "plans", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(BackupResourceAddress::BackupPlan {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "backup", region, "plans", plan_name, "selections", selection_name] if selection_name.ends_with(".ron") => {
+                let selection_name = selection_name.strip_suffix(".ron").unwrap().to_string();
+                Ok(BackupResourceAddress::BackupSelection {
+                    region: region.to_string(),
+                    plan_name: plan_name.to_string(),
+                    selection_name,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for BackupResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/backup/<region>/vaults/<vault_name>.ron",
+                description: "A backup vault, where recovery points are stored",
+                example:     "aws/backup/us-east-1/vaults/production.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/backup/<region>/plans/<plan_name>.ron",
+                description: "A backup plan, with its schedule and retention rules",
+                example:     "aws/backup/us-east-1/plans/daily-35-day-retention.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/backup/<region>/plans/<plan_name>/selections/<selection_name>.ron",
+                description: "A selection of resources backed up by a plan",
+                example:     "aws/backup/us-east-1/plans/daily-35-day-retention/selections/databases.ron",
+            },
+        ]
+    }
+}
@@ -0,0 +1,225 @@
+pub use crate::addr::BackupResourceAddress;
+pub use crate::op::BackupConnectorOp;
+pub use crate::resource::BackupResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::BackupConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{BackupPlan, BackupRule, BackupSelection, BackupVault, Lifecycle, TagCondition, VaultLock};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct BackupConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_backup::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<BackupConnectorConfig>,
+    prefix: PathBuf,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl BackupConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_backup::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_backup, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for BackupConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = BackupResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(BackupConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let backup_config: BackupConnectorConfig = BackupConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = backup_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(backup_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = backup_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // Backup vault skeleton. Leave lock unset unless retention must be enforced.
+        res.push(skeleton!(
+            BackupResourceAddress::BackupVault {
+                region: String::from("[region]"),
+                name:   String::from("[vault_name]"),
+            },
+            BackupResource::BackupVault(BackupVault {
+                encryption_key_arn: None,
+                lock: Some(VaultLock {
+                    min_retention_days: Some(7),
+                    max_retention_days: Some(365),
+                    changeable_for_days: None,
+                }),
+                tags: Tags::default(),
+            })
+        ));
+
+        // Backup plan skeleton: daily backups, kept for 35 days
+        res.push(skeleton!(
+            BackupResourceAddress::BackupPlan {
+                region: String::from("[region]"),
+                name:   String::from("[plan_name]"),
+            },
+            BackupResource::BackupPlan(BackupPlan {
+                rules: vec![BackupRule {
+                    rule_name: String::from("daily"),
+                    target_vault_name: String::from("[vault_name]"),
+                    schedule_expression: Some(String::from("cron(0 5 ? * * *)")),
+                    schedule_expression_timezone: None,
+                    start_window_minutes: Some(60),
+                    completion_window_minutes: Some(180),
+                    lifecycle: Some(Lifecycle {
+                        move_to_cold_storage_after_days: None,
+                        delete_after_days: Some(35),
+                        opt_in_to_archive_for_supported_resources: false,
+                    }),
+                    copy_actions: Vec::new(),
+                    enable_continuous_backup: false,
+                    recovery_point_tags: HashMap::new(),
+                }],
+                tags: Tags::default(),
+            })
+        ));
+
+        // Backup selection skeleton, backing up every resource with a given tag
+        res.push(skeleton!(
+            BackupResourceAddress::BackupSelection {
+                region:         String::from("[region]"),
+                plan_name:      String::from("[plan_name]"),
+                selection_name: String::from("[selection_name]"),
+            },
+            BackupResource::BackupSelection(BackupSelection {
+                iam_role_arn: String::from("arn:aws:iam::[account_id]:role/service-role/AWSBackupDefaultServiceRole"),
+                resources: Vec::new(),
+                not_resources: Vec::new(),
+                list_of_tags: vec![TagCondition {
+                    key:   String::from("backup"),
+                    value: String::from("[plan_name]"),
+                }],
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = BackupResourceAddress::from_path(addr)?;
+
+        match addr {
+            BackupResourceAddress::BackupVault { .. } => ron_check_eq::<BackupVault>(a, b),
+            BackupResourceAddress::BackupPlan { .. } => ron_check_eq::<BackupPlan>(a, b),
+            BackupResourceAddress::BackupSelection { .. } => ron_check_eq::<BackupSelection>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = BackupResourceAddress::from_path(addr)?;
+
+        match addr {
+            BackupResourceAddress::BackupVault { .. } => ron_check_syntax::<BackupVault>(a),
+            BackupResourceAddress::BackupPlan { .. } => ron_check_syntax::<BackupPlan>(a),
+            BackupResourceAddress::BackupSelection { .. } => ron_check_syntax::<BackupSelection>(a),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+
+use crate::{
+    addr::BackupResourceAddress,
+    resource::{BackupPlan, BackupResource, BackupSelection, BackupVault, VaultLock},
+    tags::Tags,
+    util::{find_backup_plan_id, find_backup_selection_id, from_backup_rules, from_tag_conditions},
+};
+
+use super::BackupConnector;
+
+impl BackupConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = BackupResourceAddress::from_path(addr)?;
+
+        match &addr {
+            BackupResourceAddress::BackupVault { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let vault = match client.describe_backup_vault().backup_vault_name(name).send().await {
+                    Ok(vault) => vault,
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => return Ok(None),
+                    Err(e) => return Err(e.into()),
+                };
+
+                let Some(arn) = vault.backup_vault_arn.clone() else {
+                    return Ok(None);
+                };
+
+                let tags_resp = client.list_tags().resource_arn(&arn).send().await?;
+
+                let lock = if vault.min_retention_days.is_some() || vault.max_retention_days.is_some() {
+                    Some(VaultLock {
+                        min_retention_days: vault.min_retention_days,
+                        max_retention_days: vault.max_retention_days,
+                        changeable_for_days: None,
+                    })
+                } else {
+                    None
+                };
+
+                let backup_vault = BackupVault {
+                    encryption_key_arn: vault.encryption_key_arn.clone(),
+                    lock,
+                    tags: Tags::from(tags_resp.tags),
+                };
+
+                get_resource_response!(
+                    BackupResource::BackupVault(backup_vault),
+                    [(String::from("backup_vault_arn"), arn)]
+                )
+            }
+            BackupResourceAddress::BackupPlan { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(plan_id) = find_backup_plan_id(&client, name).await? else {
+                    return Ok(None);
+                };
+
+                let plan_resp = match client.get_backup_plan().backup_plan_id(&plan_id).send().await {
+                    Ok(plan_resp) => plan_resp,
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => return Ok(None),
+                    Err(e) => return Err(e.into()),
+                };
+
+                let Some(plan) = &plan_resp.backup_plan else {
+                    return Ok(None);
+                };
+
+                let tags = match &plan_resp.backup_plan_arn {
+                    Some(arn) => Tags::from(client.list_tags().resource_arn(arn).send().await?.tags),
+                    None => Tags::default(),
+                };
+
+                let backup_plan = BackupPlan {
+                    rules: from_backup_rules(plan.rules()),
+                    tags,
+                };
+
+                get_resource_response!(
+                    BackupResource::BackupPlan(backup_plan),
+                    [
+                        (String::from("backup_plan_id"), plan_id),
+                        (String::from("backup_plan_arn"), plan_resp.backup_plan_arn.clone().unwrap_or_default()),
+                        (String::from("version_id"), plan_resp.version_id.clone().unwrap_or_default())
+                    ]
+                )
+            }
+            BackupResourceAddress::BackupSelection {
+                region,
+                plan_name,
+                selection_name,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(plan_id) = find_backup_plan_id(&client, plan_name).await? else {
+                    return Ok(None);
+                };
+
+                let Some(selection_id) = find_backup_selection_id(&client, &plan_id, selection_name).await? else {
+                    return Ok(None);
+                };
+
+                let selection_resp = match client
+                    .get_backup_selection()
+                    .backup_plan_id(&plan_id)
+                    .selection_id(&selection_id)
+                    .send()
+                    .await
+                {
+                    Ok(selection_resp) => selection_resp,
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => return Ok(None),
+                    Err(e) => return Err(e.into()),
+                };
+
+                let Some(selection) = &selection_resp.backup_selection else {
+                    return Ok(None);
+                };
+
+                let backup_selection = BackupSelection {
+                    iam_role_arn: selection.iam_role_arn.clone(),
+                    resources: selection.resources().to_vec(),
+                    not_resources: selection.not_resources().to_vec(),
+                    list_of_tags: from_tag_conditions(selection.list_of_tags()),
+                };
+
+                get_resource_response!(
+                    BackupResource::BackupSelection(backup_selection),
+                    [(String::from("selection_id"), selection_id)]
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::addr::BackupResourceAddress;
+
+use super::BackupConnector;
+
+impl BackupConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut next_token = None;
+            loop {
+                let resp = client.list_backup_vaults().set_next_token(next_token).send().await?;
+
+                for vault in resp.backup_vault_list() {
+                    let Some(vault_name) = &vault.backup_vault_name else {
+                        continue;
+                    };
+
+                    // Vaults managed by other services, such as aws/efs/automatic-backup-vault, aren't ours to manage
+                    if vault_name.contains('/') {
+                        continue;
+                    }
+
+                    results.push(
+                        BackupResourceAddress::BackupVault {
+                            region: region.clone(),
+                            name:   vault_name.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+
+            let mut next_token = None;
+            loop {
+                let resp = client.list_backup_plans().set_next_token(next_token).send().await?;
+
+                for plan in resp.backup_plans_list() {
+                    let (Some(plan_id), Some(plan_name)) = (&plan.backup_plan_id, &plan.backup_plan_name) else {
+                        continue;
+                    };
+
+                    results.push(
+                        BackupResourceAddress::BackupPlan {
+                            region: region.clone(),
+                            name:   plan_name.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+
+                    let mut selections_next_token = None;
+                    loop {
+                        let selections = client
+                            .list_backup_selections()
+                            .backup_plan_id(plan_id)
+                            .set_next_token(selections_next_token)
+                            .send()
+                            .await?;
+
+                        for selection in selections.backup_selections_list() {
+                            let Some(selection_name) = &selection.selection_name else {
+                                continue;
+                            };
+
+                            results.push(
+                                BackupResourceAddress::BackupSelection {
+                                    region:         region.clone(),
+                                    plan_name:      plan_name.clone(),
+                                    selection_name: selection_name.clone(),
+                                }
+                                .to_path_buf(),
+                            );
+                        }
+
+                        selections_next_token = selections.next_token;
+                        if selections_next_token.is_none() {
+                            break;
+                        }
+                    }
+                }
+
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
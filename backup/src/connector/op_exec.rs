@@ -0,0 +1,62 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::connector::{ConnectorOp, OpExecResponse, ResourceAddress};
+
+use crate::{addr::BackupResourceAddress, op::BackupConnectorOp, op_impl};
+
+use super::BackupConnector;
+
+impl BackupConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = BackupResourceAddress::from_path(addr)?;
+        let op = BackupConnectorOp::from_str(op)?;
+
+        match &addr {
+            BackupResourceAddress::BackupVault { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    BackupConnectorOp::CreateBackupVault(vault) => op_impl::create_backup_vault(&client, name, &vault).await,
+                    BackupConnectorOp::PutVaultLock(lock) => op_impl::put_vault_lock(&client, name, &lock).await,
+                    BackupConnectorOp::DeleteVaultLock => op_impl::delete_vault_lock(&client, name).await,
+                    BackupConnectorOp::UpdateBackupVaultTags(old_tags, new_tags) => {
+                        op_impl::update_backup_vault_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    BackupConnectorOp::DeleteBackupVault => op_impl::delete_backup_vault(&client, name).await,
+                    _ => bail!("Invalid operation for backup vault resource"),
+                }
+            }
+            BackupResourceAddress::BackupPlan { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    BackupConnectorOp::CreateBackupPlan(plan) => op_impl::create_backup_plan(&client, name, &plan).await,
+                    BackupConnectorOp::UpdateBackupPlan(plan) => op_impl::update_backup_plan(&client, name, &plan).await,
+                    BackupConnectorOp::UpdateBackupPlanTags(old_tags, new_tags) => {
+                        op_impl::update_backup_plan_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    BackupConnectorOp::DeleteBackupPlan => op_impl::delete_backup_plan(&client, name).await,
+                    _ => bail!("Invalid operation for backup plan resource"),
+                }
+            }
+            BackupResourceAddress::BackupSelection {
+                region,
+                plan_name,
+                selection_name,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    BackupConnectorOp::CreateBackupSelection(selection) => {
+                        op_impl::create_backup_selection(&client, plan_name, selection_name, &selection).await
+                    }
+                    BackupConnectorOp::DeleteBackupSelection => {
+                        op_impl::delete_backup_selection(&client, plan_name, selection_name).await
+                    }
+                    _ => bail!("Invalid operation for backup selection resource"),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::{collections::HashSet, path::Path};
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::resource::{BackupPlan, BackupSelection, BackupVault, Lifecycle, VaultLock};
+
+use super::{BackupConnector, BackupConnectorOp, BackupResourceAddress};
+
+/// Combinations that AWS Backup would reject, caught at plan time instead.
+fn check_vault_lock(vault_name: &str, lock: &VaultLock) -> anyhow::Result<()> {
+    if let Some(min_retention_days) = lock.min_retention_days
+        && min_retention_days < 1
+    {
+        bail!("Backup vault {} has a vault lock min_retention_days of {}: it must be at least 1", vault_name, min_retention_days);
+    }
+
+    if let (Some(min_retention_days), Some(max_retention_days)) = (lock.min_retention_days, lock.max_retention_days)
+        && max_retention_days < min_retention_days
+    {
+        bail!(
+            "Backup vault {} has a vault lock max_retention_days of {}, below its min_retention_days of {}",
+            vault_name,
+            max_retention_days,
+            min_retention_days
+        );
+    }
+
+    if let Some(changeable_for_days) = lock.changeable_for_days
+        && changeable_for_days < 3
+    {
+        bail!(
+            "Backup vault {} has a vault lock changeable_for_days of {}: it must be at least 3",
+            vault_name,
+            changeable_for_days
+        );
+    }
+
+    Ok(())
+}
+
+fn check_lifecycle(plan_name: &str, rule_name: &str, lifecycle: &Lifecycle) -> anyhow::Result<()> {
+    if let (Some(move_to_cold), Some(delete_after)) = (lifecycle.move_to_cold_storage_after_days, lifecycle.delete_after_days)
+        && delete_after < move_to_cold + 90
+    {
+        bail!(
+            "Rule {} of backup plan {} deletes recovery points after {} days, but recovery points must stay in cold storage for at least 90 days: delete_after_days must be at least {}",
+            rule_name,
+            plan_name,
+            delete_after,
+            move_to_cold + 90
+        );
+    }
+    Ok(())
+}
+
+fn check_backup_plan(plan_name: &str, plan: &BackupPlan) -> anyhow::Result<()> {
+    if plan.rules.is_empty() {
+        bail!("Backup plan {} has no rules", plan_name);
+    }
+
+    let mut rule_names = HashSet::new();
+    for rule in &plan.rules {
+        if !rule_names.insert(&rule.rule_name) {
+            bail!("Backup plan {} has more than one rule named {}", plan_name, rule.rule_name);
+        }
+
+        if let Some(schedule_expression) = &rule.schedule_expression
+            && !schedule_expression.starts_with("cron(")
+        {
+            bail!(
+                "Rule {} of backup plan {} has schedule_expression {}: expected a cron expression such as cron(0 5 ? * * *)",
+                rule.rule_name,
+                plan_name,
+                schedule_expression
+            );
+        }
+
+        if let Some(start_window_minutes) = rule.start_window_minutes
+            && start_window_minutes < 60
+        {
+            bail!(
+                "Rule {} of backup plan {} has a start window of {} minutes: it must be at least 60",
+                rule.rule_name,
+                plan_name,
+                start_window_minutes
+            );
+        }
+
+        if let (Some(start_window_minutes), Some(completion_window_minutes)) =
+            (rule.start_window_minutes, rule.completion_window_minutes)
+            && completion_window_minutes < start_window_minutes + 60
+        {
+            bail!(
+                "Rule {} of backup plan {} has a completion window of {} minutes: it must be at least 60 minutes longer than the start window",
+                rule.rule_name,
+                plan_name,
+                completion_window_minutes
+            );
+        }
+
+        if let Some(lifecycle) = &rule.lifecycle {
+            check_lifecycle(plan_name, &rule.rule_name, lifecycle)?;
+
+            // Continuous backups are kept for at most 35 days, and never in cold storage
+            if rule.enable_continuous_backup
+                && (lifecycle.move_to_cold_storage_after_days.is_some() || lifecycle.delete_after_days.is_some_and(|days| days > 35))
+            {
+                bail!(
+                    "Rule {} of backup plan {} enables continuous backup, which supports retention of at most 35 days and no cold storage",
+                    rule.rule_name,
+                    plan_name
+                );
+            }
+        }
+
+        for copy_action in &rule.copy_actions {
+            if let Some(lifecycle) = &copy_action.lifecycle {
+                check_lifecycle(plan_name, &rule.rule_name, lifecycle)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn check_backup_selection(selection_name: &str, selection: &BackupSelection) -> anyhow::Result<()> {
+    if selection.resources.is_empty() && selection.list_of_tags.is_empty() {
+        bail!(
+            "Backup selection {} selects nothing: set resources, list_of_tags or both",
+            selection_name
+        );
+    }
+    Ok(())
+}
+
+/// AWS Backup doesn't keep the order of a selection's resources or tags.
+fn normalize_selection(selection: &mut BackupSelection) {
+    selection.resources.sort();
+    selection.not_resources.sort();
+    selection
+        .list_of_tags
+        .sort_by(|a, b| (&a.key, &a.value).cmp(&(&b.key, &b.value)));
+}
+
+impl BackupConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = BackupResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            BackupResourceAddress::BackupVault { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_vault)) => {
+                    let new_vault: BackupVault = RON.from_str(&new_vault)?;
+                    if let Some(lock) = &new_vault.lock {
+                        check_vault_lock(name, lock)?;
+                    }
+
+                    let mut message = format!("Create new backup vault {} in region {}", name, region);
+                    if let Some(VaultLock {
+                        changeable_for_days: Some(changeable_for_days),
+                        ..
+                    }) = &new_vault.lock
+                    {
+                        message.push_str(&format!(
+                            "\nIts vault lock enters compliance mode after {} days, after which it can never be changed or removed",
+                            changeable_for_days
+                        ));
+                    }
+
+                    Ok(vec![connector_op!(BackupConnectorOp::CreateBackupVault(new_vault), message)])
+                }
+                (Some(_old_vault), None) => Ok(vec![connector_op!(
+                    BackupConnectorOp::DeleteBackupVault,
+                    format!("DELETE backup vault {} in region {}", name, region)
+                )]),
+                (Some(old_vault), Some(new_vault)) => {
+                    let mut old_vault: BackupVault = RON.from_str(&old_vault)?;
+                    let mut new_vault: BackupVault = RON.from_str(&new_vault)?;
+                    if let Some(lock) = &new_vault.lock {
+                        check_vault_lock(name, lock)?;
+                    }
+
+                    // An unset key means AWS Backup's default key, which is reported by ARN
+                    if new_vault.encryption_key_arn.is_none() {
+                        new_vault.encryption_key_arn = old_vault.encryption_key_arn.clone();
+                    }
+
+                    if old_vault.encryption_key_arn != new_vault.encryption_key_arn {
+                        bail!(
+                            "Backup vault {} can't change encryption_key_arn after creation: create a new vault and point backup plans at it instead",
+                            name
+                        );
+                    }
+
+                    // changeable_for_days isn't reported back, so only the retention limits are compared
+                    if let (Some(old_lock), Some(new_lock)) = (&mut old_vault.lock, &new_vault.lock) {
+                        old_lock.changeable_for_days = new_lock.changeable_for_days;
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_vault.tags != new_vault.tags {
+                        let diff = diff_ron_values(&old_vault.tags, &new_vault.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            BackupConnectorOp::UpdateBackupVaultTags(old_vault.tags.clone(), new_vault.tags.clone()),
+                            format!("Modify tags for backup vault `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    if old_vault.lock != new_vault.lock {
+                        let diff = diff_ron_values(&old_vault.lock, &new_vault.lock).unwrap_or_default();
+                        match &new_vault.lock {
+                            Some(lock) => ops.push(connector_op!(
+                                BackupConnectorOp::PutVaultLock(lock.clone()),
+                                format!(
+                                    "Modify vault lock for backup vault `{}` (fails if the lock is already in compliance mode)\n{}",
+                                    name, diff
+                                )
+                            )),
+                            None => ops.push(connector_op!(
+                                BackupConnectorOp::DeleteVaultLock,
+                                format!(
+                                    "Remove vault lock from backup vault `{}` (fails if the lock is already in compliance mode)",
+                                    name
+                                )
+                            )),
+                        }
+                    }
+
+                    Ok(ops)
+                }
+            },
+            BackupResourceAddress::BackupPlan { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_plan)) => {
+                    let new_plan: BackupPlan = RON.from_str(&new_plan)?;
+                    check_backup_plan(name, &new_plan)?;
+                    Ok(vec![connector_op!(
+                        BackupConnectorOp::CreateBackupPlan(new_plan),
+                        format!("Create new backup plan {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_plan), None) => Ok(vec![connector_op!(
+                    BackupConnectorOp::DeleteBackupPlan,
+                    format!(
+                        "DELETE backup plan {} in region {} (existing recovery points are kept)",
+                        name, region
+                    )
+                )]),
+                (Some(old_plan), Some(new_plan)) => {
+                    let old_plan: BackupPlan = RON.from_str(&old_plan)?;
+                    let new_plan: BackupPlan = RON.from_str(&new_plan)?;
+                    check_backup_plan(name, &new_plan)?;
+
+                    let mut ops = Vec::new();
+
+                    if old_plan.tags != new_plan.tags {
+                        let diff = diff_ron_values(&old_plan.tags, &new_plan.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            BackupConnectorOp::UpdateBackupPlanTags(old_plan.tags.clone(), new_plan.tags.clone()),
+                            format!("Modify tags for backup plan `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    if old_plan.rules != new_plan.rules {
+                        let diff = diff_ron_values(&old_plan.rules, &new_plan.rules).unwrap_or_default();
+                        ops.push(connector_op!(
+                            BackupConnectorOp::UpdateBackupPlan(new_plan.clone()),
+                            format!(
+                                "Modify rules for backup plan `{}` (new lifecycles apply to existing recovery points too)\n{}",
+                                name, diff
+                            )
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            BackupResourceAddress::BackupSelection {
+                region,
+                plan_name,
+                selection_name,
+            } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_selection)) => {
+                    let new_selection: BackupSelection = RON.from_str(&new_selection)?;
+                    check_backup_selection(selection_name, &new_selection)?;
+                    Ok(vec![connector_op!(
+                        BackupConnectorOp::CreateBackupSelection(new_selection),
+                        format!(
+                            "Create new resource selection {} for backup plan {} in region {}",
+                            selection_name, plan_name, region
+                        )
+                    )])
+                }
+                (Some(_old_selection), None) => Ok(vec![connector_op!(
+                    BackupConnectorOp::DeleteBackupSelection,
+                    format!(
+                        "DELETE resource selection {} from backup plan {} in region {}",
+                        selection_name, plan_name, region
+                    )
+                )]),
+                (Some(old_selection), Some(new_selection)) => {
+                    let mut old_selection: BackupSelection = RON.from_str(&old_selection)?;
+                    let mut new_selection: BackupSelection = RON.from_str(&new_selection)?;
+                    check_backup_selection(selection_name, &new_selection)?;
+
+                    normalize_selection(&mut old_selection);
+                    normalize_selection(&mut new_selection);
+
+                    if old_selection == new_selection {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_selection, &new_selection).unwrap_or_default();
+                    Ok(vec![
+                        connector_op!(
+                            BackupConnectorOp::DeleteBackupSelection,
+                            format!(
+                                "REPLACE resource selection `{}` of backup plan `{}` (requires replacement: selections are immutable)\n{}",
+                                selection_name, plan_name, diff
+                            )
+                        ),
+                        connector_op!(
+                            BackupConnectorOp::CreateBackupSelection(new_selection),
+                            format!(
+                                "Create new resource selection {} for backup plan {} in region {}",
+                                selection_name, plan_name, region
+                            )
+                        ),
+                    ])
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{BackupPlan, BackupSelection, BackupVault, VaultLock},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum BackupConnectorOp {
+    // Backup vault operations
+    CreateBackupVault(BackupVault),
+    PutVaultLock(VaultLock),
+    DeleteVaultLock,
+    UpdateBackupVaultTags(Tags, Tags),
+    DeleteBackupVault,
+
+    // Backup plan operations
+    CreateBackupPlan(BackupPlan),
+    UpdateBackupPlan(BackupPlan),
+    UpdateBackupPlanTags(Tags, Tags),
+    DeleteBackupPlan,
+
+    // Backup selection operations
+    CreateBackupSelection(BackupSelection),
+    DeleteBackupSelection,
+}
+
+impl ConnectorOp for BackupConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, bail};
+use autoschematic_core::connector::OpExecResponse;
+use aws_sdk_backup::types::BackupSelection as SdkBackupSelection;
+
+use crate::{
+    resource::{BackupPlan, BackupSelection, BackupVault, VaultLock},
+    tags::{Tags, tag_diff},
+    util::{find_backup_plan_id, find_backup_selection_id, to_backup_plan_input, to_tag_conditions},
+};
+
+async fn update_tags(client: &aws_sdk_backup::Client, arn: &str, old_tags: &Tags, new_tags: &Tags) -> anyhow::Result<()> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(arn)
+            .set_tag_key_list(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client.tag_resource().resource_arn(arn).set_tags(Some(new_tagset)).send().await?;
+    }
+
+    Ok(())
+}
+
+async fn backup_vault_arn(client: &aws_sdk_backup::Client, vault_name: &str) -> anyhow::Result<String> {
+    let resp = client.describe_backup_vault().backup_vault_name(vault_name).send().await?;
+    resp.backup_vault_arn
+        .with_context(|| format!("Backup vault {} has no ARN", vault_name))
+}
+
+async fn backup_plan_id(client: &aws_sdk_backup::Client, plan_name: &str) -> anyhow::Result<String> {
+    find_backup_plan_id(client, plan_name)
+        .await?
+        .with_context(|| format!("Backup plan {} not found", plan_name))
+}
+
+pub async fn create_backup_vault(
+    client: &aws_sdk_backup::Client,
+    vault_name: &str,
+    vault: &BackupVault,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_backup_vault()
+        .backup_vault_name(vault_name)
+        .set_encryption_key_arn(vault.encryption_key_arn.clone())
+        .set_backup_vault_tags(vault.tags.clone().into())
+        .send()
+        .await?;
+
+    if let Some(lock) = &vault.lock {
+        put_vault_lock(client, vault_name, lock).await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("backup_vault_arn"), resp.backup_vault_arn)])),
+        friendly_message: Some(format!("Created backup vault {}", vault_name)),
+    })
+}
+
+pub async fn put_vault_lock(client: &aws_sdk_backup::Client, vault_name: &str, lock: &VaultLock) -> anyhow::Result<OpExecResponse> {
+    client
+        .put_backup_vault_lock_configuration()
+        .backup_vault_name(vault_name)
+        .set_min_retention_days(lock.min_retention_days)
+        .set_max_retention_days(lock.max_retention_days)
+        .set_changeable_for_days(lock.changeable_for_days)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Set vault lock for backup vault {}", vault_name)),
+    })
+}
+
+pub async fn delete_vault_lock(client: &aws_sdk_backup::Client, vault_name: &str) -> anyhow::Result<OpExecResponse> {
+    client
+        .delete_backup_vault_lock_configuration()
+        .backup_vault_name(vault_name)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Removed vault lock from backup vault {}", vault_name)),
+    })
+}
+
+pub async fn update_backup_vault_tags(
+    client: &aws_sdk_backup::Client,
+    vault_name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = backup_vault_arn(client, vault_name).await?;
+    update_tags(client, &arn, old_tags, new_tags).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for backup vault {}", vault_name)),
+    })
+}
+
+/// AWS Backup won't delete a vault that still holds recovery points, and they have to be deleted
+/// one at a time, so this is left to the user rather than done here.
+pub async fn delete_backup_vault(client: &aws_sdk_backup::Client, vault_name: &str) -> anyhow::Result<OpExecResponse> {
+    let recovery_points = client
+        .list_recovery_points_by_backup_vault()
+        .backup_vault_name(vault_name)
+        .max_results(1)
+        .send()
+        .await?;
+
+    if !recovery_points.recovery_points().is_empty() {
+        bail!(
+            "Backup vault {} still holds recovery points: delete them before deleting the vault",
+            vault_name
+        );
+    }
+
+    client.delete_backup_vault().backup_vault_name(vault_name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("backup_vault_arn"), None)])),
+        friendly_message: Some(format!("Deleted backup vault {}", vault_name)),
+    })
+}
+
+pub async fn create_backup_plan(
+    client: &aws_sdk_backup::Client,
+    plan_name: &str,
+    plan: &BackupPlan,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_backup_plan()
+        .backup_plan(to_backup_plan_input(plan_name, plan)?)
+        .set_backup_plan_tags(plan.tags.clone().into())
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("backup_plan_id"), resp.backup_plan_id),
+            (String::from("backup_plan_arn"), resp.backup_plan_arn),
+            (String::from("version_id"), resp.version_id),
+        ])),
+        friendly_message: Some(format!("Created backup plan {}", plan_name)),
+    })
+}
+
+/// UpdateBackupPlan replaces all of a plan's rules at once, and creates a new version of the plan.
+pub async fn update_backup_plan(
+    client: &aws_sdk_backup::Client,
+    plan_name: &str,
+    plan: &BackupPlan,
+) -> anyhow::Result<OpExecResponse> {
+    let plan_id = backup_plan_id(client, plan_name).await?;
+
+    let resp = client
+        .update_backup_plan()
+        .backup_plan_id(&plan_id)
+        .backup_plan(to_backup_plan_input(plan_name, plan)?)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("version_id"), resp.version_id)])),
+        friendly_message: Some(format!("Updated rules for backup plan {}", plan_name)),
+    })
+}
+
+pub async fn update_backup_plan_tags(
+    client: &aws_sdk_backup::Client,
+    plan_name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let plan_id = backup_plan_id(client, plan_name).await?;
+
+    let resp = client.get_backup_plan().backup_plan_id(&plan_id).send().await?;
+    let Some(arn) = resp.backup_plan_arn else {
+        bail!("Backup plan {} has no ARN", plan_name);
+    };
+
+    update_tags(client, &arn, old_tags, new_tags).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for backup plan {}", plan_name)),
+    })
+}
+
+pub async fn delete_backup_plan(client: &aws_sdk_backup::Client, plan_name: &str) -> anyhow::Result<OpExecResponse> {
+    let plan_id = backup_plan_id(client, plan_name).await?;
+
+    let selections = client.list_backup_selections().backup_plan_id(&plan_id).send().await?;
+    let remaining: Vec<&str> = selections
+        .backup_selections_list()
+        .iter()
+        .filter_map(|selection| selection.selection_name.as_deref())
+        .collect();
+
+    if !remaining.is_empty() {
+        bail!(
+            "Backup plan {} still has resource selections ({}): delete them before deleting the plan",
+            plan_name,
+            remaining.join(", ")
+        );
+    }
+
+    client.delete_backup_plan().backup_plan_id(&plan_id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("backup_plan_id"), None),
+            (String::from("backup_plan_arn"), None),
+            (String::from("version_id"), None),
+        ])),
+        friendly_message: Some(format!("Deleted backup plan {}", plan_name)),
+    })
+}
+
+pub async fn create_backup_selection(
+    client: &aws_sdk_backup::Client,
+    plan_name: &str,
+    selection_name: &str,
+    selection: &BackupSelection,
+) -> anyhow::Result<OpExecResponse> {
+    let plan_id = backup_plan_id(client, plan_name).await?;
+
+    let backup_selection = SdkBackupSelection::builder()
+        .selection_name(selection_name)
+        .iam_role_arn(&selection.iam_role_arn)
+        .set_resources(Some(selection.resources.clone()))
+        .set_not_resources(Some(selection.not_resources.clone()))
+        .set_list_of_tags(Some(to_tag_conditions(&selection.list_of_tags)?))
+        .build()?;
+
+    let resp = client
+        .create_backup_selection()
+        .backup_plan_id(&plan_id)
+        .backup_selection(backup_selection)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("selection_id"), resp.selection_id)])),
+        friendly_message: Some(format!(
+            "Created resource selection {} for backup plan {}",
+            selection_name, plan_name
+        )),
+    })
+}
+
+pub async fn delete_backup_selection(
+    client: &aws_sdk_backup::Client,
+    plan_name: &str,
+    selection_name: &str,
+) -> anyhow::Result<OpExecResponse> {
+    let plan_id = backup_plan_id(client, plan_name).await?;
+
+    let Some(selection_id) = find_backup_selection_id(client, &plan_id, selection_name).await? else {
+        bail!("Backup plan {} has no resource selection {}", plan_name, selection_name);
+    };
+
+    client
+        .delete_backup_selection()
+        .backup_plan_id(&plan_id)
+        .selection_id(&selection_id)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("selection_id"), None)])),
+        friendly_message: Some(format!(
+            "Deleted resource selection {} from backup plan {}",
+            selection_name, plan_name
+        )),
+    })
+}
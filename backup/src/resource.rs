@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::BackupResourceAddress, tags::Tags};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BackupVault {
+    /// The KMS key to encrypt recovery points with. If None, AWS Backup uses its default key.
+    /// Can't be changed after creation.
+    pub encryption_key_arn: Option<String>,
+    /// If set, AWS Backup enforces these retention limits on every recovery point in the vault.
+    pub lock: Option<VaultLock>,
+    pub tags: Tags,
+}
+
+/// A vault lock starts in governance mode, where it can be changed or removed. If `changeable_for_days`
+/// is set, it switches to compliance mode once that many days have passed, after which nobody can
+/// change or remove it, including the root user.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct VaultLock {
+    pub min_retention_days: Option<i64>,
+    pub max_retention_days: Option<i64>,
+    /// At least 3. AWS Backup doesn't report this back, so it's only compared when the lock is first set.
+    pub changeable_for_days: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BackupPlan {
+    pub rules: Vec<BackupRule>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BackupRule {
+    pub rule_name: String,
+    /// The name of the vault to store recovery points in.
+    pub target_vault_name: String,
+    /// A cron expression, e.g. `cron(0 5 ? * * *)`. If None, AWS Backup picks a daily time.
+    pub schedule_expression: Option<String>,
+    /// e.g. Europe/London. Defaults to UTC.
+    pub schedule_expression_timezone: Option<String>,
+    /// How long after the scheduled time a backup may start before it's cancelled. At least 60.
+    pub start_window_minutes: Option<i64>,
+    /// How long a backup may run before it's cancelled.
+    pub completion_window_minutes: Option<i64>,
+    /// When recovery points move to cold storage and expire. If None, they're kept forever.
+    pub lifecycle: Option<Lifecycle>,
+    /// Copies of each recovery point to keep in other vaults, possibly in other regions or accounts.
+    #[serde(default)]
+    pub copy_actions: Vec<CopyAction>,
+    /// Take continuous backups for point-in-time restore, where supported.
+    #[serde(default)]
+    pub enable_continuous_backup: bool,
+    #[serde(default)]
+    pub recovery_point_tags: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Lifecycle {
+    pub move_to_cold_storage_after_days: Option<i64>,
+    /// If recovery points move to cold storage, at least 90 days after move_to_cold_storage_after_days.
+    pub delete_after_days: Option<i64>,
+    #[serde(default)]
+    pub opt_in_to_archive_for_supported_resources: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CopyAction {
+    pub destination_vault_arn: String,
+    /// The lifecycle of the copies. If None, copies follow the rule's own lifecycle.
+    pub lifecycle: Option<Lifecycle>,
+}
+
+/// Selections can't be modified after creation: any change replaces the selection.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BackupSelection {
+    /// The role AWS Backup assumes to back up the selected resources.
+    pub iam_role_arn: String,
+    /// ARNs of resources to back up. `*` selects every supported resource.
+    #[serde(default)]
+    pub resources: Vec<String>,
+    /// ARNs of resources to leave out.
+    #[serde(default)]
+    pub not_resources: Vec<String>,
+    /// Resources with any of these tags are backed up as well.
+    #[serde(default)]
+    pub list_of_tags: Vec<TagCondition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TagCondition {
+    pub key:   String,
+    pub value: String,
+}
+
+pub enum BackupResource {
+    BackupVault(BackupVault),
+    BackupPlan(BackupPlan),
+    BackupSelection(BackupSelection),
+}
+
+impl Resource for BackupResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            BackupResource::BackupVault(vault) => Ok(RON.to_string_pretty(&vault, pretty_config)?.into()),
+            BackupResource::BackupPlan(plan) => Ok(RON.to_string_pretty(&plan, pretty_config)?.into()),
+            BackupResource::BackupSelection(selection) => Ok(RON.to_string_pretty(&selection, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = BackupResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            BackupResourceAddress::BackupVault { .. } => Ok(BackupResource::BackupVault(RON.from_str(s)?)),
+            BackupResourceAddress::BackupPlan { .. } => Ok(BackupResource::BackupPlan(RON.from_str(s)?)),
+            BackupResourceAddress::BackupSelection { .. } => Ok(BackupResource::BackupSelection(RON.from_str(s)?)),
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use aws_sdk_backup::types::{
+    BackupPlanInput, BackupRuleInput, Condition, ConditionType, CopyAction as SdkCopyAction, Lifecycle as SdkLifecycle,
+};
+
+use crate::resource::{BackupPlan, BackupRule, CopyAction, Lifecycle, TagCondition};
+
+/// Backup plans are addressed by name, but the API only takes their generated IDs.
+pub async fn find_backup_plan_id(client: &aws_sdk_backup::Client, plan_name: &str) -> anyhow::Result<Option<String>> {
+    let mut next_token = None;
+    loop {
+        let resp = client.list_backup_plans().set_next_token(next_token).send().await?;
+
+        for plan in resp.backup_plans_list() {
+            if plan.backup_plan_name.as_deref() == Some(plan_name) {
+                return Ok(plan.backup_plan_id.clone());
+            }
+        }
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            return Ok(None);
+        }
+    }
+}
+
+pub async fn find_backup_selection_id(
+    client: &aws_sdk_backup::Client,
+    plan_id: &str,
+    selection_name: &str,
+) -> anyhow::Result<Option<String>> {
+    let mut next_token = None;
+    loop {
+        let resp = client
+            .list_backup_selections()
+            .backup_plan_id(plan_id)
+            .set_next_token(next_token)
+            .send()
+            .await?;
+
+        for selection in resp.backup_selections_list() {
+            if selection.selection_name.as_deref() == Some(selection_name) {
+                return Ok(selection.selection_id.clone());
+            }
+        }
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            return Ok(None);
+        }
+    }
+}
+
+pub fn to_lifecycle(lifecycle: &Option<Lifecycle>) -> Option<SdkLifecycle> {
+    lifecycle.as_ref().map(|lifecycle| {
+        SdkLifecycle::builder()
+            .set_move_to_cold_storage_after_days(lifecycle.move_to_cold_storage_after_days)
+            .set_delete_after_days(lifecycle.delete_after_days)
+            .opt_in_to_archive_for_supported_resources(lifecycle.opt_in_to_archive_for_supported_resources)
+            .build()
+    })
+}
+
+pub fn from_lifecycle(lifecycle: Option<&SdkLifecycle>) -> Option<Lifecycle> {
+    let lifecycle = lifecycle?;
+
+    // AWS Backup reports an empty lifecycle for rules that keep recovery points forever
+    if lifecycle.move_to_cold_storage_after_days.is_none() && lifecycle.delete_after_days.is_none() {
+        return None;
+    }
+
+    Some(Lifecycle {
+        move_to_cold_storage_after_days: lifecycle.move_to_cold_storage_after_days,
+        delete_after_days: lifecycle.delete_after_days,
+        opt_in_to_archive_for_supported_resources: lifecycle.opt_in_to_archive_for_supported_resources.unwrap_or(false),
+    })
+}
+
+pub fn to_backup_plan_input(plan_name: &str, plan: &BackupPlan) -> anyhow::Result<BackupPlanInput> {
+    let mut rules = Vec::new();
+
+    for rule in &plan.rules {
+        let mut copy_actions = Vec::new();
+        for copy_action in &rule.copy_actions {
+            copy_actions.push(
+                SdkCopyAction::builder()
+                    .destination_backup_vault_arn(&copy_action.destination_vault_arn)
+                    .set_lifecycle(to_lifecycle(&copy_action.lifecycle))
+                    .build()?,
+            );
+        }
+
+        rules.push(
+            BackupRuleInput::builder()
+                .rule_name(&rule.rule_name)
+                .target_backup_vault_name(&rule.target_vault_name)
+                .set_schedule_expression(rule.schedule_expression.clone())
+                .set_schedule_expression_timezone(rule.schedule_expression_timezone.clone())
+                .set_start_window_minutes(rule.start_window_minutes)
+                .set_completion_window_minutes(rule.completion_window_minutes)
+                .set_lifecycle(to_lifecycle(&rule.lifecycle))
+                .set_copy_actions(Some(copy_actions))
+                .enable_continuous_backup(rule.enable_continuous_backup)
+                .set_recovery_point_tags(if rule.recovery_point_tags.is_empty() {
+                    None
+                } else {
+                    Some(rule.recovery_point_tags.clone())
+                })
+                .build()?,
+        );
+    }
+
+    Ok(BackupPlanInput::builder()
+        .backup_plan_name(plan_name)
+        .set_rules(Some(rules))
+        .build()?)
+}
+
+pub fn from_backup_rules(rules: &[aws_sdk_backup::types::BackupRule]) -> Vec<BackupRule> {
+    rules
+        .iter()
+        .map(|rule| BackupRule {
+            rule_name: rule.rule_name.clone(),
+            target_vault_name: rule.target_backup_vault_name.clone(),
+            schedule_expression: rule.schedule_expression.clone(),
+            schedule_expression_timezone: rule.schedule_expression_timezone.clone(),
+            start_window_minutes: rule.start_window_minutes,
+            completion_window_minutes: rule.completion_window_minutes,
+            lifecycle: from_lifecycle(rule.lifecycle.as_ref()),
+            copy_actions: rule
+                .copy_actions()
+                .iter()
+                .map(|copy_action| CopyAction {
+                    destination_vault_arn: copy_action.destination_backup_vault_arn.clone(),
+                    lifecycle: from_lifecycle(copy_action.lifecycle.as_ref()),
+                })
+                .collect(),
+            enable_continuous_backup: rule.enable_continuous_backup.unwrap_or(false),
+            recovery_point_tags: rule.recovery_point_tags.clone().unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Only STRINGEQUALS conditions can be given in ListOfTags.
+pub fn to_tag_conditions(list_of_tags: &[TagCondition]) -> anyhow::Result<Vec<Condition>> {
+    let mut conditions = Vec::new();
+    for tag in list_of_tags {
+        conditions.push(
+            Condition::builder()
+                .condition_type(ConditionType::Stringequals)
+                .condition_key(&tag.key)
+                .condition_value(&tag.value)
+                .build()?,
+        );
+    }
+    Ok(conditions)
+}
+
+pub fn from_tag_conditions(conditions: &[Condition]) -> Vec<TagCondition> {
+    conditions
+        .iter()
+        .map(|condition| TagCondition {
+            key:   condition.condition_key.clone(),
+            value: condition.condition_value.clone(),
+        })
+        .collect()
+}
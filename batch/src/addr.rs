@@ -0,0 +1,92 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum BatchResourceAddress {
+    ComputeEnvironment { region: String, name: String },
+    JobQueue { region: String, name: String },
+    JobDefinition { region: String, name: String },
+    SchedulingPolicy { region: String, name: String },
+}
+
+impl ResourceAddress for BatchResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            BatchResourceAddress::ComputeEnvironment { region, name } => {
+                PathBuf::from(format!("aws/batch/{region}/compute_environments/{name}.ron"))
+            }
+            BatchResourceAddress::JobQueue { region, name } => PathBuf::from(format!("aws/batch/{region}/job_queues/{name}.ron")),
+            BatchResourceAddress::JobDefinition { region, name } => {
+                PathBuf::from(format!("aws/batch/{region}/job_definitions/{name}.ron"))
+            }
+            BatchResourceAddress::SchedulingPolicy { region, name } => {
+                PathBuf::from(format!("aws/batch/{region}/scheduling_policies/{name}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "batch", region, "compute_environments", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(BatchResourceAddress::ComputeEnvironment {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "batch", region, "job_queues", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(BatchResourceAddress::JobQueue {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "batch", region, "job_definitions", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(BatchResourceAddress::JobDefinition {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "batch", region, "scheduling_policies", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(BatchResourceAddress::SchedulingPolicy {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for BatchResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/batch/<region>/compute_environments/<name>.ron",
+                description: "A Batch compute environment: the EC2, Spot or Fargate capacity that jobs run on",
+                example:     "aws/batch/us-east-1/compute_environments/spot.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/batch/<region>/job_queues/<name>.ron",
+                description: "A Batch job queue, which places jobs onto its compute environments in order",
+                example:     "aws/batch/us-east-1/job_queues/default.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/batch/<region>/job_definitions/<name>.ron",
+                description: "The latest active revision of a Batch container job definition",
+                example:     "aws/batch/us-east-1/job_definitions/nightly-report.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/batch/<region>/scheduling_policies/<name>.ron",
+                description: "A fair-share scheduling policy for job queues",
+                example:     "aws/batch/us-east-1/scheduling_policies/teams.ron",
+            },
+        ]
+    }
+}
@@ -0,0 +1,267 @@
+pub use crate::addr::BatchResourceAddress;
+pub use crate::op::BatchConnectorOp;
+pub use crate::resource::BatchResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::BatchConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{
+    ComputeEnvironment, ComputeEnvironmentOrder, ComputeResources, ContainerProperties, JobDefinition, JobQueue, LogConfiguration,
+    SchedulingPolicy,
+};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct BatchConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_batch::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<BatchConnectorConfig>,
+    prefix: PathBuf,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl BatchConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_batch::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_batch, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for BatchConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = BatchResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(BatchConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let batch_config: BatchConnectorConfig = BatchConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = batch_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(batch_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = batch_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // Managed Spot capacity that scales to zero when idle
+        res.push(skeleton!(
+            BatchResourceAddress::ComputeEnvironment {
+                region: String::from("[region]"),
+                name:   String::from("[compute_environment_name]"),
+            },
+            BatchResource::ComputeEnvironment(ComputeEnvironment {
+                r#type: String::from("MANAGED"),
+                enabled: true,
+                service_role: None,
+                compute_resources: Some(ComputeResources {
+                    r#type: String::from("SPOT"),
+                    allocation_strategy: Some(String::from("SPOT_CAPACITY_OPTIMIZED")),
+                    min_vcpus: Some(0),
+                    max_vcpus: 256,
+                    desired_vcpus: None,
+                    instance_types: vec![String::from("optimal")],
+                    subnets: vec![String::from("[subnet_id]")],
+                    security_group_ids: vec![String::from("[security_group_id]")],
+                    instance_role: Some(String::from("arn:aws:iam::[account_id]:instance-profile/ecsInstanceRole")),
+                    ec2_key_pair: None,
+                    bid_percentage: None,
+                    spot_iam_fleet_role: None,
+                    launch_template: None,
+                    tags: Tags::default(),
+                }),
+                tags: Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            BatchResourceAddress::JobQueue {
+                region: String::from("[region]"),
+                name:   String::from("[job_queue_name]"),
+            },
+            BatchResource::JobQueue(JobQueue {
+                priority: 1,
+                enabled: true,
+                compute_environments: vec![ComputeEnvironmentOrder {
+                    order: 1,
+                    compute_environment: String::from("[compute_environment_name]"),
+                }],
+                scheduling_policy_arn: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        // A container job on Fargate that logs to CloudWatch
+        res.push(skeleton!(
+            BatchResourceAddress::JobDefinition {
+                region: String::from("[region]"),
+                name:   String::from("[job_definition_name]"),
+            },
+            BatchResource::JobDefinition(JobDefinition {
+                platform_capabilities: vec![String::from("FARGATE")],
+                container: ContainerProperties {
+                    image: String::from("public.ecr.aws/amazonlinux/amazonlinux:latest"),
+                    command: vec![String::from("echo"), String::from("Ref::message")],
+                    job_role_arn: None,
+                    execution_role_arn: Some(String::from("arn:aws:iam::[account_id]:role/ecsTaskExecutionRole")),
+                    resource_requirements: BTreeMap::from([
+                        (String::from("VCPU"), String::from("1")),
+                        (String::from("MEMORY"), String::from("2048")),
+                    ]),
+                    environment: BTreeMap::new(),
+                    secrets: BTreeMap::new(),
+                    log_configuration: Some(LogConfiguration {
+                        log_driver: String::from("awslogs"),
+                        options:    BTreeMap::new(),
+                    }),
+                    readonly_root_filesystem: false,
+                    privileged: false,
+                    user: None,
+                    assign_public_ip: Some(false),
+                    fargate_platform_version: None,
+                },
+                parameters: BTreeMap::from([(String::from("message"), String::from("hello"))]),
+                retry_attempts: Some(2),
+                timeout_seconds: Some(3600),
+                propagate_tags: true,
+                scheduling_priority: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        // Two teams sharing a queue, with the second getting half the capacity of the first
+        res.push(skeleton!(
+            BatchResourceAddress::SchedulingPolicy {
+                region: String::from("[region]"),
+                name:   String::from("[scheduling_policy_name]"),
+            },
+            BatchResource::SchedulingPolicy(SchedulingPolicy {
+                share_decay_seconds: Some(3600),
+                compute_reservation: None,
+                share_distribution: BTreeMap::from([(String::from("team-a"), 1.0), (String::from("team-b"), 2.0)]),
+                tags: Tags::default(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = BatchResourceAddress::from_path(addr)?;
+
+        match addr {
+            BatchResourceAddress::ComputeEnvironment { .. } => ron_check_eq::<ComputeEnvironment>(a, b),
+            BatchResourceAddress::JobQueue { .. } => ron_check_eq::<JobQueue>(a, b),
+            BatchResourceAddress::JobDefinition { .. } => ron_check_eq::<JobDefinition>(a, b),
+            BatchResourceAddress::SchedulingPolicy { .. } => ron_check_eq::<SchedulingPolicy>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = BatchResourceAddress::from_path(addr)?;
+
+        match addr {
+            BatchResourceAddress::ComputeEnvironment { .. } => ron_check_syntax::<ComputeEnvironment>(a),
+            BatchResourceAddress::JobQueue { .. } => ron_check_syntax::<JobQueue>(a),
+            BatchResourceAddress::JobDefinition { .. } => ron_check_syntax::<JobDefinition>(a),
+            BatchResourceAddress::SchedulingPolicy { .. } => ron_check_syntax::<SchedulingPolicy>(a),
+        }
+    }
+}
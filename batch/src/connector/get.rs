@@ -0,0 +1,136 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_batch::types::{CeState, JqState};
+
+use crate::{
+    addr::BatchResourceAddress,
+    op_impl::{find_compute_environment, find_job_definitions, find_job_queue, find_scheduling_policy},
+    resource::{BatchResource, ComputeEnvironment, ComputeEnvironmentOrder, JobDefinition, JobQueue, SchedulingPolicy},
+    tags::Tags,
+    util::{compute_environment_name, from_compute_resource, from_container_properties, from_fairshare_policy},
+};
+
+use super::BatchConnector;
+
+impl BatchConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = BatchResourceAddress::from_path(addr)?;
+
+        match &addr {
+            BatchResourceAddress::ComputeEnvironment { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(ce) = find_compute_environment(&client, name).await? else {
+                    return Ok(None);
+                };
+
+                let compute_environment = ComputeEnvironment {
+                    r#type: ce.r#type.map(|t| t.as_str().to_string()).unwrap_or_default(),
+                    enabled: ce.state != Some(CeState::Disabled),
+                    // Environments created without a role report Batch's service-linked role
+                    service_role: ce
+                        .service_role
+                        .filter(|role| !role.contains("/aws-service-role/batch.amazonaws.com/")),
+                    compute_resources: ce.compute_resources.map(from_compute_resource),
+                    tags: Tags::from(ce.tags),
+                };
+
+                get_resource_response!(
+                    BatchResource::ComputeEnvironment(compute_environment),
+                    [(
+                        String::from("compute_environment_arn"),
+                        ce.compute_environment_arn.unwrap_or_default()
+                    )]
+                )
+            }
+            BatchResourceAddress::JobQueue { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(jq) = find_job_queue(&client, name).await? else {
+                    return Ok(None);
+                };
+
+                let mut compute_environments: Vec<ComputeEnvironmentOrder> = jq
+                    .compute_environment_order
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|ce| ComputeEnvironmentOrder {
+                        order: ce.order.unwrap_or_default(),
+                        compute_environment: compute_environment_name(&ce.compute_environment.unwrap_or_default()),
+                    })
+                    .collect();
+                compute_environments.sort_by_key(|ce| ce.order);
+
+                let job_queue = JobQueue {
+                    priority: jq.priority.unwrap_or_default(),
+                    enabled: jq.state != Some(JqState::Disabled),
+                    compute_environments,
+                    scheduling_policy_arn: jq.scheduling_policy_arn,
+                    tags: Tags::from(jq.tags),
+                };
+
+                get_resource_response!(
+                    BatchResource::JobQueue(job_queue),
+                    [(String::from("job_queue_arn"), jq.job_queue_arn)]
+                )
+            }
+            BatchResourceAddress::JobDefinition { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(latest) = find_job_definitions(&client, name).await?.into_iter().next() else {
+                    return Ok(None);
+                };
+                let Some(container_properties) = latest.container_properties else {
+                    // Multi-node and EKS job definitions aren't supported
+                    return Ok(None);
+                };
+
+                let job_definition = JobDefinition {
+                    platform_capabilities: latest
+                        .platform_capabilities
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|capability| capability.as_str().to_string())
+                        .collect(),
+                    container: from_container_properties(container_properties),
+                    parameters: latest.parameters.unwrap_or_default().into_iter().collect(),
+                    retry_attempts: latest.retry_strategy.and_then(|retry_strategy| retry_strategy.attempts),
+                    timeout_seconds: latest.timeout.and_then(|timeout| timeout.attempt_duration_seconds),
+                    propagate_tags: latest.propagate_tags.unwrap_or(false),
+                    scheduling_priority: latest.scheduling_priority,
+                    tags: Tags::from(latest.tags),
+                };
+
+                get_resource_response!(
+                    BatchResource::JobDefinition(job_definition),
+                    [(String::from("job_definition_arn"), latest.job_definition_arn)]
+                )
+            }
+            BatchResourceAddress::SchedulingPolicy { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(policy) = find_scheduling_policy(&client, name).await? else {
+                    return Ok(None);
+                };
+
+                let (share_decay_seconds, compute_reservation, share_distribution) = from_fairshare_policy(policy.fairshare_policy);
+
+                let scheduling_policy = SchedulingPolicy {
+                    share_decay_seconds,
+                    compute_reservation,
+                    share_distribution,
+                    tags: Tags::from(policy.tags),
+                };
+
+                get_resource_response!(
+                    BatchResource::SchedulingPolicy(scheduling_policy),
+                    [(String::from("scheduling_policy_arn"), policy.arn)]
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+use aws_sdk_batch::types::{CeStatus, JqStatus};
+
+use crate::addr::BatchResourceAddress;
+
+use super::BatchConnector;
+
+impl BatchConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut compute_environments = client.describe_compute_environments().into_paginator().items().send();
+            while let Some(ce) = compute_environments.next().await {
+                let ce = ce?;
+                if matches!(ce.status, Some(CeStatus::Deleting | CeStatus::Deleted)) {
+                    continue;
+                }
+                let Some(name) = ce.compute_environment_name else {
+                    continue;
+                };
+
+                results.push(BatchResourceAddress::ComputeEnvironment { region: region.clone(), name }.to_path_buf());
+            }
+
+            let mut job_queues = client.describe_job_queues().into_paginator().items().send();
+            while let Some(jq) = job_queues.next().await {
+                let jq = jq?;
+                if matches!(jq.status, Some(JqStatus::Deleting | JqStatus::Deleted)) {
+                    continue;
+                }
+
+                results.push(
+                    BatchResourceAddress::JobQueue {
+                        region: region.clone(),
+                        name:   jq.job_queue_name,
+                    }
+                    .to_path_buf(),
+                );
+            }
+
+            // Each revision is listed separately, but they all belong to one address
+            let mut job_definition_names = Vec::new();
+            let mut job_definitions = client
+                .describe_job_definitions()
+                .status("ACTIVE")
+                .into_paginator()
+                .items()
+                .send();
+            while let Some(job_definition) = job_definitions.next().await {
+                let job_definition = job_definition?;
+                if job_definition.r#type != "container" || job_definition.container_properties.is_none() {
+                    continue;
+                }
+                if !job_definition_names.contains(&job_definition.job_definition_name) {
+                    job_definition_names.push(job_definition.job_definition_name);
+                }
+            }
+            for name in job_definition_names {
+                results.push(BatchResourceAddress::JobDefinition { region: region.clone(), name }.to_path_buf());
+            }
+
+            let mut scheduling_policies = client.list_scheduling_policies().into_paginator().items().send();
+            while let Some(policy) = scheduling_policies.next().await {
+                let policy = policy?;
+                let Some((_, name)) = policy.arn.split_once(":scheduling-policy/") else {
+                    continue;
+                };
+
+                results.push(
+                    BatchResourceAddress::SchedulingPolicy {
+                        region: region.clone(),
+                        name:   name.to_string(),
+                    }
+                    .to_path_buf(),
+                );
+            }
+        }
+
+        Ok(results)
+    }
+}
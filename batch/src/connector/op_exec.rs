@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{ConnectorOp, OpExecResponse, ResourceAddress},
+    error_util::invalid_op,
+};
+
+use crate::{addr::BatchResourceAddress, op::BatchConnectorOp, op_impl};
+
+use super::BatchConnector;
+
+impl BatchConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = BatchResourceAddress::from_path(addr)?;
+        let op = BatchConnectorOp::from_str(op)?;
+
+        match &addr {
+            BatchResourceAddress::ComputeEnvironment { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    BatchConnectorOp::CreateComputeEnvironment(compute_environment) => {
+                        op_impl::create_compute_environment(&client, name, &compute_environment).await
+                    }
+                    BatchConnectorOp::UpdateComputeEnvironment(compute_environment) => {
+                        op_impl::update_compute_environment(&client, name, &compute_environment).await
+                    }
+                    BatchConnectorOp::UpdateComputeEnvironmentTags(old_tags, new_tags) => {
+                        op_impl::update_compute_environment_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    BatchConnectorOp::DeleteComputeEnvironment => op_impl::delete_compute_environment(&client, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            BatchResourceAddress::JobQueue { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    BatchConnectorOp::CreateJobQueue(job_queue) => op_impl::create_job_queue(&client, name, &job_queue).await,
+                    BatchConnectorOp::UpdateJobQueue(job_queue) => op_impl::update_job_queue(&client, name, &job_queue).await,
+                    BatchConnectorOp::UpdateJobQueueTags(old_tags, new_tags) => {
+                        op_impl::update_job_queue_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    BatchConnectorOp::DeleteJobQueue => op_impl::delete_job_queue(&client, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            BatchResourceAddress::JobDefinition { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    BatchConnectorOp::RegisterJobDefinition(job_definition) => {
+                        op_impl::register_job_definition(&client, name, &job_definition).await
+                    }
+                    BatchConnectorOp::DeregisterJobDefinition => op_impl::deregister_job_definition(&client, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            BatchResourceAddress::SchedulingPolicy { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    BatchConnectorOp::CreateSchedulingPolicy(policy) => {
+                        op_impl::create_scheduling_policy(&client, name, &policy).await
+                    }
+                    BatchConnectorOp::UpdateSchedulingPolicy(policy) => {
+                        op_impl::update_scheduling_policy(&client, name, &policy).await
+                    }
+                    BatchConnectorOp::UpdateSchedulingPolicyTags(old_tags, new_tags) => {
+                        op_impl::update_scheduling_policy_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    BatchConnectorOp::DeleteSchedulingPolicy => op_impl::delete_scheduling_policy(&client, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+        }
+    }
+}
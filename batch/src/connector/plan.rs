@@ -0,0 +1,442 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::resource::{ComputeEnvironment, JobDefinition, JobQueue, SchedulingPolicy};
+
+use super::{BatchConnector, BatchConnectorOp, BatchResourceAddress};
+
+const COMPUTE_RESOURCE_TYPES: &[&str] = &["EC2", "SPOT", "FARGATE", "FARGATE_SPOT"];
+const PLATFORM_CAPABILITIES: &[&str] = &["EC2", "FARGATE"];
+
+/// Combinations that Batch would reject, caught at plan time instead.
+fn check_compute_environment(name: &str, ce: &ComputeEnvironment) -> anyhow::Result<()> {
+    let resources = match (ce.r#type.as_str(), &ce.compute_resources) {
+        ("MANAGED", Some(resources)) => resources,
+        ("MANAGED", None) => bail!("Batch compute environment {} is MANAGED, which requires compute_resources", name),
+        ("UNMANAGED", None) => return Ok(()),
+        ("UNMANAGED", Some(_)) => bail!(
+            "Batch compute environment {} is UNMANAGED, so it can't set compute_resources",
+            name
+        ),
+        (other, _) => bail!(
+            "Batch compute environment {} has type {}: expected MANAGED or UNMANAGED",
+            name,
+            other
+        ),
+    };
+
+    if !COMPUTE_RESOURCE_TYPES.contains(&resources.r#type.as_str()) {
+        bail!(
+            "Batch compute environment {} has compute resource type {}: expected one of {}",
+            name,
+            resources.r#type,
+            COMPUTE_RESOURCE_TYPES.join(", ")
+        );
+    }
+    if resources.subnets.is_empty() {
+        bail!("Batch compute environment {} has no subnets", name);
+    }
+    if let Some(min_vcpus) = resources.min_vcpus
+        && min_vcpus > resources.max_vcpus
+    {
+        bail!(
+            "Batch compute environment {} has min_vcpus {} above max_vcpus {}",
+            name,
+            min_vcpus,
+            resources.max_vcpus
+        );
+    }
+
+    if resources.r#type.starts_with("FARGATE") {
+        let mut ec2_only = Vec::new();
+        if resources.allocation_strategy.is_some() {
+            ec2_only.push("allocation_strategy");
+        }
+        if resources.min_vcpus.is_some() {
+            ec2_only.push("min_vcpus");
+        }
+        if resources.desired_vcpus.is_some() {
+            ec2_only.push("desired_vcpus");
+        }
+        if !resources.instance_types.is_empty() {
+            ec2_only.push("instance_types");
+        }
+        if resources.instance_role.is_some() {
+            ec2_only.push("instance_role");
+        }
+        if resources.ec2_key_pair.is_some() {
+            ec2_only.push("ec2_key_pair");
+        }
+        if resources.bid_percentage.is_some() {
+            ec2_only.push("bid_percentage");
+        }
+        if resources.launch_template.is_some() {
+            ec2_only.push("launch_template");
+        }
+        if !ec2_only.is_empty() {
+            bail!(
+                "Batch compute environment {} is {}, but sets {}, which only EC2 and SPOT compute resources support",
+                name,
+                resources.r#type,
+                ec2_only.join(", ")
+            );
+        }
+    } else {
+        if resources.instance_role.is_none() {
+            bail!(
+                "Batch compute environment {} is {}, which requires instance_role",
+                name,
+                resources.r#type
+            );
+        }
+        if resources.instance_types.is_empty() {
+            bail!(
+                "Batch compute environment {} is {}, which requires instance_types, e.g. [\"optimal\"]",
+                name,
+                resources.r#type
+            );
+        }
+    }
+
+    if resources.bid_percentage.is_some() && resources.r#type != "SPOT" {
+        bail!("Batch compute environment {} sets bid_percentage, which only SPOT compute resources support", name);
+    }
+
+    Ok(())
+}
+
+/// Unset fields that Batch fills in or manages itself keep whatever Batch has for them.
+fn normalize_compute_environment(old: &ComputeEnvironment, new: &mut ComputeEnvironment) {
+    if let (Some(old_resources), Some(new_resources)) = (&old.compute_resources, &mut new.compute_resources) {
+        if new_resources.desired_vcpus.is_none() {
+            new_resources.desired_vcpus = old_resources.desired_vcpus;
+        }
+        if new_resources.allocation_strategy.is_none() {
+            new_resources.allocation_strategy = old_resources.allocation_strategy.clone();
+        }
+    }
+}
+
+/// Fields that Batch can't change on an existing compute environment.
+fn compute_environment_fixed_fields(old: &ComputeEnvironment, new: &ComputeEnvironment) -> Vec<String> {
+    let mut fields = Vec::new();
+    if old.r#type != new.r#type {
+        fields.push(String::from("type"));
+    }
+    if old.compute_resources.as_ref().map(|resources| &resources.r#type)
+        != new.compute_resources.as_ref().map(|resources| &resources.r#type)
+    {
+        fields.push(String::from("compute_resources.type"));
+    }
+    fields
+}
+
+fn check_job_queue(name: &str, job_queue: &JobQueue) -> anyhow::Result<()> {
+    if !(0..=1000).contains(&job_queue.priority) {
+        bail!(
+            "Batch job queue {} has priority {}: expected 0 to 1000",
+            name,
+            job_queue.priority
+        );
+    }
+    if job_queue.compute_environments.is_empty() || job_queue.compute_environments.len() > 3 {
+        bail!(
+            "Batch job queue {} has {} compute environments: expected 1 to 3",
+            name,
+            job_queue.compute_environments.len()
+        );
+    }
+
+    let mut orders: Vec<i32> = job_queue.compute_environments.iter().map(|ce| ce.order).collect();
+    orders.sort();
+    orders.dedup();
+    if orders.len() != job_queue.compute_environments.len() {
+        bail!("Batch job queue {} gives two compute environments the same order", name);
+    }
+
+    Ok(())
+}
+
+fn check_job_definition(name: &str, job_definition: &JobDefinition) -> anyhow::Result<()> {
+    if job_definition.platform_capabilities.is_empty() {
+        bail!("Batch job definition {} has no platform_capabilities", name);
+    }
+    for capability in &job_definition.platform_capabilities {
+        if !PLATFORM_CAPABILITIES.contains(&capability.as_str()) {
+            bail!(
+                "Batch job definition {} has platform capability {}: expected EC2 or FARGATE",
+                name,
+                capability
+            );
+        }
+    }
+
+    let container = &job_definition.container;
+    for required in ["VCPU", "MEMORY"] {
+        if !container.resource_requirements.contains_key(required) {
+            bail!("Batch job definition {} has no {} resource requirement", name, required);
+        }
+    }
+
+    let fargate = job_definition.platform_capabilities.iter().any(|capability| capability == "FARGATE");
+    if fargate {
+        if container.execution_role_arn.is_none() {
+            bail!("Batch job definition {} runs on FARGATE, which requires execution_role_arn", name);
+        }
+        if container.privileged {
+            bail!("Batch job definition {} runs on FARGATE, which doesn't support privileged containers", name);
+        }
+    } else if container.assign_public_ip.is_some() || container.fargate_platform_version.is_some() {
+        bail!(
+            "Batch job definition {} sets assign_public_ip or fargate_platform_version, which only FARGATE jobs support",
+            name
+        );
+    }
+
+    if let Some(retry_attempts) = job_definition.retry_attempts
+        && !(1..=10).contains(&retry_attempts)
+    {
+        bail!(
+            "Batch job definition {} has retry_attempts {}: expected 1 to 10",
+            name,
+            retry_attempts
+        );
+    }
+
+    Ok(())
+}
+
+fn check_scheduling_policy(name: &str, policy: &SchedulingPolicy) -> anyhow::Result<()> {
+    if let Some(compute_reservation) = policy.compute_reservation
+        && !(0..=99).contains(&compute_reservation)
+    {
+        bail!(
+            "Batch scheduling policy {} has compute_reservation {}: expected 0 to 99",
+            name,
+            compute_reservation
+        );
+    }
+    for (share_identifier, weight_factor) in &policy.share_distribution {
+        if !(0.0001..=999.9999).contains(weight_factor) {
+            bail!(
+                "Batch scheduling policy {} gives {} a weight factor of {}: expected 0.0001 to 999.9999",
+                name,
+                share_identifier,
+                weight_factor
+            );
+        }
+    }
+
+    Ok(())
+}
+
+impl BatchConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = BatchResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            BatchResourceAddress::ComputeEnvironment { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_ce)) => {
+                    let new_ce: ComputeEnvironment = RON.from_str(&new_ce)?;
+                    check_compute_environment(name, &new_ce)?;
+                    Ok(vec![connector_op!(
+                        BatchConnectorOp::CreateComputeEnvironment(new_ce),
+                        format!("Create new Batch compute environment {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_ce), None) => Ok(vec![connector_op!(
+                    BatchConnectorOp::DeleteComputeEnvironment,
+                    format!(
+                        "DELETE Batch compute environment {} in region {}. It's disabled first, and must not be used by any job queue.",
+                        name, region
+                    )
+                )]),
+                (Some(old_ce), Some(new_ce)) => {
+                    let old_ce: ComputeEnvironment = RON.from_str(&old_ce)?;
+                    let mut new_ce: ComputeEnvironment = RON.from_str(&new_ce)?;
+                    check_compute_environment(name, &new_ce)?;
+                    normalize_compute_environment(&old_ce, &mut new_ce);
+
+                    let fixed_fields = compute_environment_fixed_fields(&old_ce, &new_ce);
+                    if !fixed_fields.is_empty() {
+                        bail!(
+                            "Batch compute environment {} can't change {} in place. Create a new compute environment under another name and move the job queues to it instead.",
+                            name,
+                            fixed_fields.join(", ")
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_ce.tags != new_ce.tags {
+                        let diff = diff_ron_values(&old_ce.tags, &new_ce.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            BatchConnectorOp::UpdateComputeEnvironmentTags(old_ce.tags.clone(), new_ce.tags.clone()),
+                            format!("Modify tags for Batch compute environment `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_ce.clone();
+                    old_settings.tags = new_ce.tags.clone();
+                    if old_settings != new_ce {
+                        let diff = diff_ron_values(&old_settings, &new_ce).unwrap_or_default();
+                        ops.push(connector_op!(
+                            BatchConnectorOp::UpdateComputeEnvironment(new_ce),
+                            format!("Modify Batch compute environment `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            BatchResourceAddress::JobQueue { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_jq)) => {
+                    let new_jq: JobQueue = RON.from_str(&new_jq)?;
+                    check_job_queue(name, &new_jq)?;
+                    Ok(vec![connector_op!(
+                        BatchConnectorOp::CreateJobQueue(new_jq),
+                        format!("Create new Batch job queue {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_jq), None) => Ok(vec![connector_op!(
+                    BatchConnectorOp::DeleteJobQueue,
+                    format!(
+                        "DELETE Batch job queue {} in region {}. Jobs still in the queue are terminated.",
+                        name, region
+                    )
+                )]),
+                (Some(old_jq), Some(new_jq)) => {
+                    let old_jq: JobQueue = RON.from_str(&old_jq)?;
+                    let new_jq: JobQueue = RON.from_str(&new_jq)?;
+                    check_job_queue(name, &new_jq)?;
+
+                    if old_jq.scheduling_policy_arn.is_some() != new_jq.scheduling_policy_arn.is_some() {
+                        bail!(
+                            "Batch job queue {} can't switch between first-in first-out and fair-share scheduling in place. Create a new job queue under another name instead.",
+                            name
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_jq.tags != new_jq.tags {
+                        let diff = diff_ron_values(&old_jq.tags, &new_jq.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            BatchConnectorOp::UpdateJobQueueTags(old_jq.tags.clone(), new_jq.tags.clone()),
+                            format!("Modify tags for Batch job queue `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_jq.clone();
+                    old_settings.tags = new_jq.tags.clone();
+                    if old_settings != new_jq {
+                        let diff = diff_ron_values(&old_settings, &new_jq).unwrap_or_default();
+                        ops.push(connector_op!(
+                            BatchConnectorOp::UpdateJobQueue(new_jq),
+                            format!("Modify Batch job queue `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            BatchResourceAddress::JobDefinition { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_jd)) => {
+                    let new_jd: JobDefinition = RON.from_str(&new_jd)?;
+                    check_job_definition(name, &new_jd)?;
+                    Ok(vec![connector_op!(
+                        BatchConnectorOp::RegisterJobDefinition(new_jd),
+                        format!("Register new Batch job definition {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_jd), None) => Ok(vec![connector_op!(
+                    BatchConnectorOp::DeregisterJobDefinition,
+                    format!(
+                        "Deregister all revisions of Batch job definition {} in region {}",
+                        name, region
+                    )
+                )]),
+                (Some(old_jd), Some(new_jd)) => {
+                    let old_jd: JobDefinition = RON.from_str(&old_jd)?;
+                    let new_jd: JobDefinition = RON.from_str(&new_jd)?;
+
+                    if old_jd == new_jd {
+                        return Ok(Vec::new());
+                    }
+
+                    // Job definitions are immutable, so every change is a new revision
+                    check_job_definition(name, &new_jd)?;
+                    let diff = diff_ron_values(&old_jd, &new_jd).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        BatchConnectorOp::RegisterJobDefinition(new_jd),
+                        format!("Register a new revision of Batch job definition `{}`\n{}", name, diff)
+                    )])
+                }
+            },
+            BatchResourceAddress::SchedulingPolicy { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_policy)) => {
+                    let new_policy: SchedulingPolicy = RON.from_str(&new_policy)?;
+                    check_scheduling_policy(name, &new_policy)?;
+                    Ok(vec![connector_op!(
+                        BatchConnectorOp::CreateSchedulingPolicy(new_policy),
+                        format!("Create new Batch scheduling policy {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_policy), None) => Ok(vec![connector_op!(
+                    BatchConnectorOp::DeleteSchedulingPolicy,
+                    format!(
+                        "DELETE Batch scheduling policy {} in region {}. It must not be used by any job queue.",
+                        name, region
+                    )
+                )]),
+                (Some(old_policy), Some(new_policy)) => {
+                    let old_policy: SchedulingPolicy = RON.from_str(&old_policy)?;
+                    let new_policy: SchedulingPolicy = RON.from_str(&new_policy)?;
+                    check_scheduling_policy(name, &new_policy)?;
+
+                    let mut ops = Vec::new();
+
+                    if old_policy.tags != new_policy.tags {
+                        let diff = diff_ron_values(&old_policy.tags, &new_policy.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            BatchConnectorOp::UpdateSchedulingPolicyTags(old_policy.tags.clone(), new_policy.tags.clone()),
+                            format!("Modify tags for Batch scheduling policy `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_policy.clone();
+                    old_settings.tags = new_policy.tags.clone();
+                    if old_settings != new_policy {
+                        let diff = diff_ron_values(&old_settings, &new_policy).unwrap_or_default();
+                        ops.push(connector_op!(
+                            BatchConnectorOp::UpdateSchedulingPolicy(new_policy),
+                            format!("Modify Batch scheduling policy `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+        }
+    }
+}
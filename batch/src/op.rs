@@ -0,0 +1,47 @@
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{ComputeEnvironment, JobDefinition, JobQueue, SchedulingPolicy},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum BatchConnectorOp {
+    CreateComputeEnvironment(ComputeEnvironment),
+    /// Applies the environment's state, service role and compute resources.
+    UpdateComputeEnvironment(ComputeEnvironment),
+    UpdateComputeEnvironmentTags(Tags, Tags),
+    /// Disables the environment, then deletes it.
+    DeleteComputeEnvironment,
+
+    CreateJobQueue(JobQueue),
+    UpdateJobQueue(JobQueue),
+    UpdateJobQueueTags(Tags, Tags),
+    /// Disables the queue, then deletes it.
+    DeleteJobQueue,
+
+    RegisterJobDefinition(JobDefinition),
+    /// Deregisters every active revision.
+    DeregisterJobDefinition,
+
+    CreateSchedulingPolicy(SchedulingPolicy),
+    UpdateSchedulingPolicy(SchedulingPolicy),
+    UpdateSchedulingPolicyTags(Tags, Tags),
+    DeleteSchedulingPolicy,
+}
+
+impl ConnectorOp for BatchConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
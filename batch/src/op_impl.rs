@@ -0,0 +1,484 @@
+use std::{collections::HashMap, future::Future, time::Duration};
+
+use anyhow::{Context, bail};
+use autoschematic_core::{connector::OpExecResponse, op_exec_output};
+use aws_sdk_batch::types::{
+    CeState, CeStatus, CeType, ComputeEnvironmentDetail, JobDefinitionType, JobQueueDetail, JobTimeout, JqState, JqStatus,
+    PlatformCapability, RetryStrategy, SchedulingPolicyDetail,
+};
+
+use crate::{
+    resource::{ComputeEnvironment, JobDefinition, JobQueue, SchedulingPolicy},
+    tags::{Tags, tag_diff},
+    util::{to_compute_resource, to_compute_resource_update, to_container_properties, to_fairshare_policy},
+};
+
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Compute environments can take several minutes to create, update or delete.
+const STATUS_MAX_POLLS: usize = 180;
+
+/// Batch rejects changes to compute environments and job queues while they're being created or
+/// updated. Polls `status` until it returns `target`, or until the resource no longer exists if
+/// `target` is None.
+async fn wait_for_status<F, Fut>(description: &str, target: Option<&str>, status: F) -> anyhow::Result<()>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = anyhow::Result<Option<(String, Option<String>)>>>,
+{
+    for _ in 0..STATUS_MAX_POLLS {
+        let state = status().await?;
+        if state.as_ref().map(|(status, _)| status.as_str()) == target {
+            return Ok(());
+        }
+        if let Some((status, reason)) = &state
+            && status == "INVALID"
+        {
+            bail!("{} is INVALID: {}", description, reason.as_deref().unwrap_or("no reason given"));
+        }
+        tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    }
+
+    match target {
+        Some(target) => bail!("Timed out waiting for {} to become {}", description, target),
+        None => bail!("Timed out waiting for {} to be deleted", description),
+    }
+}
+
+pub async fn find_compute_environment(client: &aws_sdk_batch::Client, name: &str) -> anyhow::Result<Option<ComputeEnvironmentDetail>> {
+    let resp = client.describe_compute_environments().compute_environments(name).send().await?;
+
+    Ok(resp
+        .compute_environments()
+        .iter()
+        .find(|ce| ce.status != Some(CeStatus::Deleted))
+        .cloned())
+}
+
+pub async fn find_job_queue(client: &aws_sdk_batch::Client, name: &str) -> anyhow::Result<Option<JobQueueDetail>> {
+    let resp = client.describe_job_queues().job_queues(name).send().await?;
+
+    Ok(resp
+        .job_queues()
+        .iter()
+        .find(|jq| jq.status != Some(JqStatus::Deleted))
+        .cloned())
+}
+
+/// The active revisions of a job definition, newest first.
+pub async fn find_job_definitions(
+    client: &aws_sdk_batch::Client,
+    name: &str,
+) -> anyhow::Result<Vec<aws_sdk_batch::types::JobDefinition>> {
+    let mut revisions = Vec::new();
+
+    let mut pages = client
+        .describe_job_definitions()
+        .job_definition_name(name)
+        .status("ACTIVE")
+        .into_paginator()
+        .items()
+        .send();
+
+    while let Some(job_definition) = pages.next().await {
+        revisions.push(job_definition?);
+    }
+
+    revisions.sort_by_key(|job_definition| std::cmp::Reverse(job_definition.revision));
+    Ok(revisions)
+}
+
+/// DescribeSchedulingPolicies only takes ARNs, so this finds the policy by listing them.
+pub async fn find_scheduling_policy(client: &aws_sdk_batch::Client, name: &str) -> anyhow::Result<Option<SchedulingPolicyDetail>> {
+    let suffix = format!(":scheduling-policy/{}", name);
+
+    let mut pages = client.list_scheduling_policies().into_paginator().items().send();
+    while let Some(policy) = pages.next().await {
+        let policy = policy?;
+        if !policy.arn.ends_with(&suffix) {
+            continue;
+        }
+
+        let resp = client.describe_scheduling_policies().arns(&policy.arn).send().await?;
+        return Ok(resp.scheduling_policies().first().cloned());
+    }
+
+    Ok(None)
+}
+
+async fn compute_environment_status(
+    client: &aws_sdk_batch::Client,
+    name: &str,
+) -> anyhow::Result<Option<(String, Option<String>)>> {
+    Ok(find_compute_environment(client, name).await?.map(|ce| {
+        (
+            ce.status.map(|status| status.as_str().to_string()).unwrap_or_default(),
+            ce.status_reason,
+        )
+    }))
+}
+
+async fn job_queue_status(client: &aws_sdk_batch::Client, name: &str) -> anyhow::Result<Option<(String, Option<String>)>> {
+    Ok(find_job_queue(client, name).await?.map(|jq| {
+        (
+            jq.status.map(|status| status.as_str().to_string()).unwrap_or_default(),
+            jq.status_reason,
+        )
+    }))
+}
+
+async fn wait_for_compute_environment(client: &aws_sdk_batch::Client, name: &str, target: Option<&str>) -> anyhow::Result<()> {
+    wait_for_status(&format!("Batch compute environment {}", name), target, || {
+        compute_environment_status(client, name)
+    })
+    .await
+}
+
+async fn wait_for_job_queue(client: &aws_sdk_batch::Client, name: &str, target: Option<&str>) -> anyhow::Result<()> {
+    wait_for_status(&format!("Batch job queue {}", name), target, || job_queue_status(client, name)).await
+}
+
+fn ce_state(enabled: bool) -> CeState {
+    if enabled { CeState::Enabled } else { CeState::Disabled }
+}
+
+fn jq_state(enabled: bool) -> JqState {
+    if enabled { JqState::Enabled } else { JqState::Disabled }
+}
+
+pub async fn create_compute_environment(
+    client: &aws_sdk_batch::Client,
+    name: &str,
+    compute_environment: &ComputeEnvironment,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_compute_environment()
+        .compute_environment_name(name)
+        .r#type(CeType::from(compute_environment.r#type.as_str()))
+        .state(ce_state(compute_environment.enabled))
+        .set_service_role(compute_environment.service_role.clone())
+        .set_compute_resources(compute_environment.compute_resources.as_ref().map(to_compute_resource))
+        .set_tags(compute_environment.tags.clone().into())
+        .send()
+        .await?;
+
+    let arn = resp
+        .compute_environment_arn
+        .context("CreateComputeEnvironment returned no ARN")?;
+
+    wait_for_compute_environment(client, name, Some("VALID")).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("compute_environment_arn"), Some(arn))])),
+        friendly_message: Some(format!("Created Batch compute environment {}", name)),
+    })
+}
+
+pub async fn update_compute_environment(
+    client: &aws_sdk_batch::Client,
+    name: &str,
+    compute_environment: &ComputeEnvironment,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .update_compute_environment()
+        .compute_environment(name)
+        .state(ce_state(compute_environment.enabled))
+        .set_service_role(compute_environment.service_role.clone())
+        .set_compute_resources(
+            compute_environment
+                .compute_resources
+                .as_ref()
+                .map(to_compute_resource_update),
+        )
+        .send()
+        .await?;
+
+    wait_for_compute_environment(client, name, Some("VALID")).await?;
+
+    op_exec_output!(format!("Updated Batch compute environment {}", name))
+}
+
+/// TagResource and UntagResource take the resource's ARN.
+async fn update_tags(client: &aws_sdk_batch::Client, arn: &str, old_tags: &Tags, new_tags: &Tags) -> anyhow::Result<()> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client.tag_resource().resource_arn(arn).set_tags(Some(new_tagset)).send().await?;
+    }
+
+    Ok(())
+}
+
+pub async fn update_compute_environment_tags(
+    client: &aws_sdk_batch::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = find_compute_environment(client, name)
+        .await?
+        .and_then(|ce| ce.compute_environment_arn)
+        .with_context(|| format!("Batch compute environment {} not found", name))?;
+
+    update_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for Batch compute environment {}", name))
+}
+
+/// Compute environments must be disabled before they can be deleted, and Batch refuses to delete
+/// one that a job queue still uses.
+pub async fn delete_compute_environment(client: &aws_sdk_batch::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    let ce = find_compute_environment(client, name)
+        .await?
+        .with_context(|| format!("Batch compute environment {} not found", name))?;
+
+    if ce.state != Some(CeState::Disabled) {
+        client
+            .update_compute_environment()
+            .compute_environment(name)
+            .state(CeState::Disabled)
+            .send()
+            .await?;
+        wait_for_compute_environment(client, name, Some("VALID")).await?;
+    }
+
+    client.delete_compute_environment().compute_environment(name).send().await?;
+    wait_for_compute_environment(client, name, None).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("compute_environment_arn"), None)])),
+        friendly_message: Some(format!("Deleted Batch compute environment {}", name)),
+    })
+}
+
+fn compute_environment_order(job_queue: &JobQueue) -> anyhow::Result<Vec<aws_sdk_batch::types::ComputeEnvironmentOrder>> {
+    let mut order = Vec::new();
+    for ce in &job_queue.compute_environments {
+        order.push(
+            aws_sdk_batch::types::ComputeEnvironmentOrder::builder()
+                .order(ce.order)
+                .compute_environment(&ce.compute_environment)
+                .build()?,
+        );
+    }
+    Ok(order)
+}
+
+pub async fn create_job_queue(client: &aws_sdk_batch::Client, name: &str, job_queue: &JobQueue) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_job_queue()
+        .job_queue_name(name)
+        .state(jq_state(job_queue.enabled))
+        .priority(job_queue.priority)
+        .set_compute_environment_order(Some(compute_environment_order(job_queue)?))
+        .set_scheduling_policy_arn(job_queue.scheduling_policy_arn.clone())
+        .set_tags(job_queue.tags.clone().into())
+        .send()
+        .await?;
+
+    wait_for_job_queue(client, name, Some("VALID")).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("job_queue_arn"), Some(resp.job_queue_arn))])),
+        friendly_message: Some(format!("Created Batch job queue {}", name)),
+    })
+}
+
+pub async fn update_job_queue(client: &aws_sdk_batch::Client, name: &str, job_queue: &JobQueue) -> anyhow::Result<OpExecResponse> {
+    client
+        .update_job_queue()
+        .job_queue(name)
+        .state(jq_state(job_queue.enabled))
+        .priority(job_queue.priority)
+        .set_compute_environment_order(Some(compute_environment_order(job_queue)?))
+        .set_scheduling_policy_arn(job_queue.scheduling_policy_arn.clone())
+        .send()
+        .await?;
+
+    wait_for_job_queue(client, name, Some("VALID")).await?;
+
+    op_exec_output!(format!("Updated Batch job queue {}", name))
+}
+
+pub async fn update_job_queue_tags(
+    client: &aws_sdk_batch::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = find_job_queue(client, name)
+        .await?
+        .map(|jq| jq.job_queue_arn)
+        .with_context(|| format!("Batch job queue {} not found", name))?;
+
+    update_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for Batch job queue {}", name))
+}
+
+/// Job queues must be disabled before they can be deleted. Jobs still in the queue are terminated.
+pub async fn delete_job_queue(client: &aws_sdk_batch::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    let jq = find_job_queue(client, name)
+        .await?
+        .with_context(|| format!("Batch job queue {} not found", name))?;
+
+    if jq.state != Some(JqState::Disabled) {
+        client
+            .update_job_queue()
+            .job_queue(name)
+            .state(JqState::Disabled)
+            .send()
+            .await?;
+        wait_for_job_queue(client, name, Some("VALID")).await?;
+    }
+
+    client.delete_job_queue().job_queue(name).send().await?;
+    wait_for_job_queue(client, name, None).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("job_queue_arn"), None)])),
+        friendly_message: Some(format!("Deleted Batch job queue {}", name)),
+    })
+}
+
+pub async fn register_job_definition(
+    client: &aws_sdk_batch::Client,
+    name: &str,
+    job_definition: &JobDefinition,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .register_job_definition()
+        .job_definition_name(name)
+        .r#type(JobDefinitionType::Container)
+        .set_platform_capabilities(Some(
+            job_definition
+                .platform_capabilities
+                .iter()
+                .map(|capability| PlatformCapability::from(capability.as_str()))
+                .collect(),
+        ))
+        .container_properties(to_container_properties(&job_definition.container)?)
+        .set_parameters(Some(job_definition.parameters.clone().into_iter().collect()))
+        .set_retry_strategy(
+            job_definition
+                .retry_attempts
+                .map(|attempts| RetryStrategy::builder().attempts(attempts).build()),
+        )
+        .set_timeout(
+            job_definition
+                .timeout_seconds
+                .map(|seconds| JobTimeout::builder().attempt_duration_seconds(seconds).build()),
+        )
+        .propagate_tags(job_definition.propagate_tags)
+        .set_scheduling_priority(job_definition.scheduling_priority)
+        .set_tags(job_definition.tags.clone().into())
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(
+            String::from("job_definition_arn"),
+            Some(resp.job_definition_arn.clone()),
+        )])),
+        friendly_message: Some(format!("Registered Batch job definition {} revision {}", name, resp.revision)),
+    })
+}
+
+/// Deregisters every active revision, so that the job definition no longer shows up at all.
+/// Jobs already submitted with a revision keep running.
+pub async fn deregister_job_definition(client: &aws_sdk_batch::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    let revisions = find_job_definitions(client, name).await?;
+
+    for revision in &revisions {
+        client
+            .deregister_job_definition()
+            .job_definition(&revision.job_definition_arn)
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("job_definition_arn"), None)])),
+        friendly_message: Some(format!(
+            "Deregistered {} revisions of Batch job definition {}",
+            revisions.len(),
+            name
+        )),
+    })
+}
+
+pub async fn create_scheduling_policy(
+    client: &aws_sdk_batch::Client,
+    name: &str,
+    policy: &SchedulingPolicy,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_scheduling_policy()
+        .name(name)
+        .fairshare_policy(to_fairshare_policy(policy)?)
+        .set_tags(policy.tags.clone().into())
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("scheduling_policy_arn"), Some(resp.arn))])),
+        friendly_message: Some(format!("Created Batch scheduling policy {}", name)),
+    })
+}
+
+async fn scheduling_policy_arn(client: &aws_sdk_batch::Client, name: &str) -> anyhow::Result<String> {
+    Ok(find_scheduling_policy(client, name)
+        .await?
+        .with_context(|| format!("Batch scheduling policy {} not found", name))?
+        .arn)
+}
+
+pub async fn update_scheduling_policy(
+    client: &aws_sdk_batch::Client,
+    name: &str,
+    policy: &SchedulingPolicy,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = scheduling_policy_arn(client, name).await?;
+
+    client
+        .update_scheduling_policy()
+        .arn(&arn)
+        .fairshare_policy(to_fairshare_policy(policy)?)
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Updated Batch scheduling policy {}", name))
+}
+
+pub async fn update_scheduling_policy_tags(
+    client: &aws_sdk_batch::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = scheduling_policy_arn(client, name).await?;
+
+    update_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for Batch scheduling policy {}", name))
+}
+
+/// Batch refuses to delete a scheduling policy that a job queue still uses.
+pub async fn delete_scheduling_policy(client: &aws_sdk_batch::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    let arn = scheduling_policy_arn(client, name).await?;
+
+    client.delete_scheduling_policy().arn(&arn).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("scheduling_policy_arn"), None)])),
+        friendly_message: Some(format!("Deleted Batch scheduling policy {}", name)),
+    })
+}
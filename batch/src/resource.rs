@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::BatchResourceAddress, tags::Tags};
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_compute_environment_type() -> String {
+    String::from("MANAGED")
+}
+
+fn default_platform_capabilities() -> Vec<String> {
+    vec![String::from("EC2")]
+}
+
+/// A Batch compute environment. Managed environments have their capacity scaled by Batch between
+/// min_vcpus and max_vcpus; unmanaged environments run on an ECS cluster you provide instances for.
+///
+/// The environment type and the compute resource type are fixed at creation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ComputeEnvironment {
+    /// MANAGED or UNMANAGED.
+    #[serde(default = "default_compute_environment_type")]
+    pub r#type: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// If None, Batch uses its service-linked role.
+    pub service_role: Option<String>,
+    /// Required for MANAGED environments.
+    pub compute_resources: Option<ComputeResources>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ComputeResources {
+    /// EC2, SPOT, FARGATE or FARGATE_SPOT.
+    pub r#type: String,
+    /// e.g. BEST_FIT_PROGRESSIVE or SPOT_CAPACITY_OPTIMIZED. EC2 and Spot only.
+    pub allocation_strategy: Option<String>,
+    /// EC2 and Spot only.
+    pub min_vcpus: Option<i32>,
+    pub max_vcpus: i32,
+    /// Batch adjusts this as jobs come and go, so it's only used when set.
+    pub desired_vcpus: Option<i32>,
+    /// e.g. ["optimal"] or ["c5", "m5.large"]. EC2 and Spot only.
+    #[serde(default)]
+    pub instance_types: Vec<String>,
+    pub subnets: Vec<String>,
+    #[serde(default)]
+    pub security_group_ids: Vec<String>,
+    /// The instance profile for EC2 and Spot instances, by name or ARN.
+    pub instance_role: Option<String>,
+    pub ec2_key_pair: Option<String>,
+    /// The maximum Spot price, as a percentage of the On-Demand price. Spot only.
+    pub bid_percentage: Option<i32>,
+    /// Spot only, and only with the BEST_FIT allocation strategy.
+    pub spot_iam_fleet_role: Option<String>,
+    pub launch_template: Option<LaunchTemplate>,
+    /// Tags for the instances Batch launches. EC2 and Spot only.
+    #[serde(default)]
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LaunchTemplate {
+    /// Set this or launch_template_name.
+    pub launch_template_id: Option<String>,
+    pub launch_template_name: Option<String>,
+    /// e.g. $Latest or $Default. If None, the template's default version is used.
+    pub version: Option<String>,
+}
+
+/// A Batch job queue.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct JobQueue {
+    /// Queues with a higher priority are scheduled first on compute environments they share.
+    pub priority: i32,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// One to three compute environments, tried in ascending `order`.
+    pub compute_environments: Vec<ComputeEnvironmentOrder>,
+    /// A fair-share scheduling policy. A queue without one schedules jobs first in, first out,
+    /// and this can't be added or removed after the queue is created.
+    pub scheduling_policy_arn: Option<String>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ComputeEnvironmentOrder {
+    pub order: i32,
+    /// The compute environment's name, or its ARN.
+    pub compute_environment: String,
+}
+
+/// The latest active revision of a Batch container job definition. Job definitions are immutable,
+/// so any change registers a new revision.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct JobDefinition {
+    /// EC2, FARGATE, or both.
+    #[serde(default = "default_platform_capabilities")]
+    pub platform_capabilities: Vec<String>,
+    pub container: ContainerProperties,
+    /// Default values for Ref::name placeholders in the command.
+    #[serde(default)]
+    pub parameters: BTreeMap<String, String>,
+    /// How many times to try a job before failing it, from 1 to 10.
+    pub retry_attempts: Option<i32>,
+    /// Jobs still running after this many seconds are terminated.
+    pub timeout_seconds: Option<i32>,
+    /// Copy the job definition's tags to the ECS tasks of its jobs.
+    #[serde(default)]
+    pub propagate_tags: bool,
+    /// Used by fair-share queues to order jobs within a share.
+    pub scheduling_priority: Option<i32>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ContainerProperties {
+    pub image: String,
+    #[serde(default)]
+    pub command: Vec<String>,
+    /// The role the job's containers can assume.
+    pub job_role_arn: Option<String>,
+    /// The role ECS uses to pull images and fetch secrets. Required on Fargate.
+    pub execution_role_arn: Option<String>,
+    /// VCPU and MEMORY (in MiB) are required; GPU is optional.
+    pub resource_requirements: BTreeMap<String, String>,
+    #[serde(default)]
+    pub environment: BTreeMap<String, String>,
+    /// Environment variables taken from Secrets Manager or SSM Parameter Store, by ARN.
+    #[serde(default)]
+    pub secrets: BTreeMap<String, String>,
+    pub log_configuration: Option<LogConfiguration>,
+    #[serde(default)]
+    pub readonly_root_filesystem: bool,
+    /// EC2 only.
+    #[serde(default)]
+    pub privileged: bool,
+    pub user: Option<String>,
+    /// Fargate only. Jobs in public subnets need a public IP to pull images.
+    pub assign_public_ip: Option<bool>,
+    /// Fargate only, e.g. LATEST or 1.4.0.
+    pub fargate_platform_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LogConfiguration {
+    /// e.g. awslogs
+    pub log_driver: String,
+    #[serde(default)]
+    pub options: BTreeMap<String, String>,
+}
+
+/// A fair-share scheduling policy, which divides a queue's capacity between share identifiers.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SchedulingPolicy {
+    /// How far back, in seconds, usage counts against a share. 0 counts only current usage.
+    pub share_decay_seconds: Option<i32>,
+    /// Holds back a share of the queue's vCPUs for share identifiers that aren't active yet.
+    pub compute_reservation: Option<i32>,
+    /// Share identifier to weight factor. Identifiers with a lower weight get more of the capacity;
+    /// identifiers not listed have a weight of 1.
+    #[serde(default)]
+    pub share_distribution: BTreeMap<String, f32>,
+    pub tags: Tags,
+}
+
+pub enum BatchResource {
+    ComputeEnvironment(ComputeEnvironment),
+    JobQueue(JobQueue),
+    JobDefinition(JobDefinition),
+    SchedulingPolicy(SchedulingPolicy),
+}
+
+impl Resource for BatchResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            BatchResource::ComputeEnvironment(compute_environment) => {
+                Ok(RON.to_string_pretty(&compute_environment, pretty_config)?.into())
+            }
+            BatchResource::JobQueue(job_queue) => Ok(RON.to_string_pretty(&job_queue, pretty_config)?.into()),
+            BatchResource::JobDefinition(job_definition) => Ok(RON.to_string_pretty(&job_definition, pretty_config)?.into()),
+            BatchResource::SchedulingPolicy(policy) => Ok(RON.to_string_pretty(&policy, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = BatchResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            BatchResourceAddress::ComputeEnvironment { .. } => Ok(BatchResource::ComputeEnvironment(RON.from_str(s)?)),
+            BatchResourceAddress::JobQueue { .. } => Ok(BatchResource::JobQueue(RON.from_str(s)?)),
+            BatchResourceAddress::JobDefinition { .. } => Ok(BatchResource::JobDefinition(RON.from_str(s)?)),
+            BatchResourceAddress::SchedulingPolicy { .. } => Ok(BatchResource::SchedulingPolicy(RON.from_str(s)?)),
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::BTreeMap;
+
+use aws_sdk_batch::types::{
+    AssignPublicIp, ComputeResource, ComputeResourceUpdate, CrAllocationStrategy, CrType, CrUpdateAllocationStrategy,
+    FairsharePolicy, FargatePlatformConfiguration, KeyValuePair, LaunchTemplateSpecification, LogDriver, NetworkConfiguration,
+    ResourceRequirement, ResourceType, Secret, ShareAttributes,
+};
+
+use crate::{
+    resource::{ComputeResources, ContainerProperties, LaunchTemplate, LogConfiguration, SchedulingPolicy},
+    tags::Tags,
+};
+
+/// Job queues report their compute environments by ARN, but are usually written with names.
+pub fn compute_environment_name(name_or_arn: &str) -> String {
+    match name_or_arn.split_once(":compute-environment/") {
+        Some((_, name)) => name.to_string(),
+        None => name_or_arn.to_string(),
+    }
+}
+
+fn non_empty(values: &[String]) -> Option<Vec<String>> {
+    if values.is_empty() { None } else { Some(values.to_vec()) }
+}
+
+fn to_launch_template(launch_template: &LaunchTemplate) -> LaunchTemplateSpecification {
+    LaunchTemplateSpecification::builder()
+        .set_launch_template_id(launch_template.launch_template_id.clone())
+        .set_launch_template_name(launch_template.launch_template_name.clone())
+        .set_version(launch_template.version.clone())
+        .build()
+}
+
+pub fn to_compute_resource(resources: &ComputeResources) -> ComputeResource {
+    ComputeResource::builder()
+        .r#type(CrType::from(resources.r#type.as_str()))
+        .set_allocation_strategy(resources.allocation_strategy.as_deref().map(CrAllocationStrategy::from))
+        .set_minv_cpus(resources.min_vcpus)
+        .maxv_cpus(resources.max_vcpus)
+        .set_desiredv_cpus(resources.desired_vcpus)
+        .set_instance_types(non_empty(&resources.instance_types))
+        .set_subnets(Some(resources.subnets.clone()))
+        .set_security_group_ids(non_empty(&resources.security_group_ids))
+        .set_instance_role(resources.instance_role.clone())
+        .set_ec2_key_pair(resources.ec2_key_pair.clone())
+        .set_bid_percentage(resources.bid_percentage)
+        .set_spot_iam_fleet_role(resources.spot_iam_fleet_role.clone())
+        .set_launch_template(resources.launch_template.as_ref().map(to_launch_template))
+        .set_tags(resources.tags.clone().into())
+        .build()
+}
+
+/// Most compute resource fields can only be updated in place on environments that use the
+/// BEST_FIT_PROGRESSIVE or SPOT_CAPACITY_OPTIMIZED allocation strategies. Batch rejects the update
+/// otherwise.
+pub fn to_compute_resource_update(resources: &ComputeResources) -> ComputeResourceUpdate {
+    ComputeResourceUpdate::builder()
+        .set_allocation_strategy(
+            resources
+                .allocation_strategy
+                .as_deref()
+                .map(CrUpdateAllocationStrategy::from),
+        )
+        .set_minv_cpus(resources.min_vcpus)
+        .maxv_cpus(resources.max_vcpus)
+        .set_desiredv_cpus(resources.desired_vcpus)
+        .set_instance_types(non_empty(&resources.instance_types))
+        .set_subnets(Some(resources.subnets.clone()))
+        .set_security_group_ids(Some(resources.security_group_ids.clone()))
+        .set_instance_role(resources.instance_role.clone())
+        .set_ec2_key_pair(resources.ec2_key_pair.clone())
+        .set_bid_percentage(resources.bid_percentage)
+        .set_launch_template(resources.launch_template.as_ref().map(to_launch_template))
+        .set_tags(resources.tags.clone().into())
+        .build()
+}
+
+pub fn from_compute_resource(resource: ComputeResource) -> ComputeResources {
+    let fargate = matches!(resource.r#type, Some(CrType::Fargate | CrType::FargateSpot));
+
+    ComputeResources {
+        r#type: resource.r#type.map(|t| t.as_str().to_string()).unwrap_or_default(),
+        allocation_strategy: resource.allocation_strategy.map(|s| s.as_str().to_string()),
+        // Fargate environments report zeroes for fields they don't have
+        min_vcpus: if fargate { None } else { resource.minv_cpus },
+        max_vcpus: resource.maxv_cpus.unwrap_or_default(),
+        desired_vcpus: if fargate { None } else { resource.desiredv_cpus },
+        instance_types: resource.instance_types.unwrap_or_default(),
+        subnets: resource.subnets.unwrap_or_default(),
+        security_group_ids: resource.security_group_ids.unwrap_or_default(),
+        instance_role: resource.instance_role,
+        ec2_key_pair: resource.ec2_key_pair,
+        bid_percentage: resource.bid_percentage.filter(|_| !fargate),
+        spot_iam_fleet_role: resource.spot_iam_fleet_role,
+        launch_template: resource.launch_template.map(|lt| LaunchTemplate {
+            launch_template_id:   lt.launch_template_id,
+            launch_template_name: lt.launch_template_name,
+            version:              lt.version,
+        }),
+        tags: Tags::from(resource.tags),
+    }
+}
+
+pub fn to_container_properties(container: &ContainerProperties) -> anyhow::Result<aws_sdk_batch::types::ContainerProperties> {
+    let mut resource_requirements = Vec::new();
+    for (r#type, value) in &container.resource_requirements {
+        resource_requirements.push(
+            ResourceRequirement::builder()
+                .r#type(ResourceType::from(r#type.as_str()))
+                .value(value)
+                .build()?,
+        );
+    }
+
+    let mut secrets = Vec::new();
+    for (name, value_from) in &container.secrets {
+        secrets.push(Secret::builder().name(name).value_from(value_from).build()?);
+    }
+
+    let environment = container
+        .environment
+        .iter()
+        .map(|(name, value)| KeyValuePair::builder().name(name).value(value).build())
+        .collect();
+
+    let log_configuration = match &container.log_configuration {
+        Some(log_configuration) => Some(
+            aws_sdk_batch::types::LogConfiguration::builder()
+                .log_driver(LogDriver::from(log_configuration.log_driver.as_str()))
+                .set_options(Some(log_configuration.options.clone().into_iter().collect()))
+                .build()?,
+        ),
+        None => None,
+    };
+
+    Ok(aws_sdk_batch::types::ContainerProperties::builder()
+        .image(&container.image)
+        .set_command(non_empty(&container.command))
+        .set_job_role_arn(container.job_role_arn.clone())
+        .set_execution_role_arn(container.execution_role_arn.clone())
+        .set_resource_requirements(Some(resource_requirements))
+        .set_environment(Some(environment))
+        .set_secrets(Some(secrets))
+        .set_log_configuration(log_configuration)
+        .readonly_root_filesystem(container.readonly_root_filesystem)
+        .privileged(container.privileged)
+        .set_user(container.user.clone())
+        .set_network_configuration(container.assign_public_ip.map(|assign_public_ip| {
+            NetworkConfiguration::builder()
+                .assign_public_ip(if assign_public_ip {
+                    AssignPublicIp::Enabled
+                } else {
+                    AssignPublicIp::Disabled
+                })
+                .build()
+        }))
+        .set_fargate_platform_configuration(
+            container
+                .fargate_platform_version
+                .as_ref()
+                .map(|version| FargatePlatformConfiguration::builder().platform_version(version).build()),
+        )
+        .build())
+}
+
+pub fn from_container_properties(container: aws_sdk_batch::types::ContainerProperties) -> ContainerProperties {
+    ContainerProperties {
+        image: container.image.unwrap_or_default(),
+        command: container.command.unwrap_or_default(),
+        job_role_arn: container.job_role_arn,
+        execution_role_arn: container.execution_role_arn,
+        resource_requirements: container
+            .resource_requirements
+            .unwrap_or_default()
+            .into_iter()
+            .map(|requirement| (requirement.r#type.as_str().to_string(), requirement.value))
+            .collect(),
+        environment: container
+            .environment
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|kv| Some((kv.name?, kv.value?)))
+            .collect(),
+        secrets: container
+            .secrets
+            .unwrap_or_default()
+            .into_iter()
+            .map(|secret| (secret.name, secret.value_from))
+            .collect(),
+        log_configuration: container.log_configuration.map(|log_configuration| LogConfiguration {
+            log_driver: log_configuration.log_driver.as_str().to_string(),
+            options:    log_configuration.options.unwrap_or_default().into_iter().collect(),
+        }),
+        readonly_root_filesystem: container.readonly_root_filesystem.unwrap_or(false),
+        privileged: container.privileged.unwrap_or(false),
+        user: container.user,
+        assign_public_ip: container
+            .network_configuration
+            .and_then(|network_configuration| network_configuration.assign_public_ip)
+            .map(|assign_public_ip| assign_public_ip == AssignPublicIp::Enabled),
+        fargate_platform_version: container
+            .fargate_platform_configuration
+            .and_then(|fargate| fargate.platform_version),
+    }
+}
+
+pub fn to_fairshare_policy(policy: &SchedulingPolicy) -> anyhow::Result<FairsharePolicy> {
+    let mut share_distribution = Vec::new();
+    for (share_identifier, weight_factor) in &policy.share_distribution {
+        share_distribution.push(
+            ShareAttributes::builder()
+                .share_identifier(share_identifier)
+                .weight_factor(*weight_factor)
+                .build()?,
+        );
+    }
+
+    Ok(FairsharePolicy::builder()
+        .set_share_decay_seconds(policy.share_decay_seconds)
+        .set_compute_reservation(policy.compute_reservation)
+        .set_share_distribution(Some(share_distribution))
+        .build())
+}
+
+/// Returns share_decay_seconds, compute_reservation and share_distribution.
+pub fn from_fairshare_policy(policy: Option<FairsharePolicy>) -> (Option<i32>, Option<i32>, BTreeMap<String, f32>) {
+    let Some(policy) = policy else {
+        return (None, None, BTreeMap::new());
+    };
+
+    let share_distribution = policy
+        .share_distribution
+        .unwrap_or_default()
+        .into_iter()
+        .map(|share| (share.share_identifier, share.weight_factor.unwrap_or(1.0)))
+        .collect();
+
+    (policy.share_decay_seconds, policy.compute_reservation, share_distribution)
+}
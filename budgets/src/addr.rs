@@ -0,0 +1,95 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+/// Budgets and Cost Anomaly Detection are account-wide, so nothing here is addressed by region.
+/// Anomaly monitors and subscriptions get generated ARNs, so they're addressed by name.
+#[derive(Debug, Clone)]
+pub enum BudgetsResourceAddress {
+    Budget { name: String },
+    AnomalyMonitor { name: String },
+    AnomalySubscription { name: String },
+}
+
+impl ResourceAddress for BudgetsResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            BudgetsResourceAddress::Budget { name } => PathBuf::from(format!("aws/budgets/budgets/{name}.ron")),
+            BudgetsResourceAddress::AnomalyMonitor { name } => {
+                PathBuf::from(format!("aws/budgets/anomaly_monitors/{name}.ron"))
+            }
+            BudgetsResourceAddress::AnomalySubscription { name } => {
+                PathBuf::from(format!("aws/budgets/anomaly_subscriptions/{name}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "budgets", "budgets", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(BudgetsResourceAddress::Budget { name })
+            }
+            ["aws", "budgets", "anomaly_monitors", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(BudgetsResourceAddress::AnomalyMonitor { name })
+            }
+            ["aws", "budgets", "anomaly_subscriptions", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(BudgetsResourceAddress::AnomalySubscription { name })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for BudgetsResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/budgets/budgets/<name>.ron",
+                description: "A cost or usage budget and its notifications. Subscribers can name SNS topic addresses",
+                example:     "aws/budgets/budgets/monthly-total.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/budgets/anomaly_monitors/<name>.ron",
+                description: "A Cost Anomaly Detection monitor, scoped to services, linked accounts, a tag or a cost category",
+                example:     "aws/budgets/anomaly_monitors/services.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/budgets/anomaly_subscriptions/<name>.ron",
+                description: "Alerts for anomalies found by one or more monitors",
+                example:     "aws/budgets/anomaly_subscriptions/finops-daily.ron",
+            },
+        ]
+    }
+}
+
+/// The address of a topic managed by the SNS connector. A subscriber can name one of these in `address`
+/// instead of a topic ARN, and it's resolved through the topic's `topic_arn` output.
+#[derive(Debug, Clone)]
+pub struct SnsTopicAddress {
+    pub region: String,
+    pub name:   String,
+}
+
+impl ResourceAddress for SnsTopicAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        PathBuf::from(format!("aws/sns/{}/topics/{}.ron", self.region, self.name))
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "sns", region, "topics", name] if name.ends_with(".ron") => Ok(SnsTopicAddress {
+                region: region.to_string(),
+                name:   name.strip_suffix(".ron").unwrap().to_string(),
+            }),
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
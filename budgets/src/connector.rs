@@ -0,0 +1,244 @@
+pub use crate::addr::BudgetsResourceAddress;
+pub use crate::op::BudgetsConnectorOp;
+pub use crate::resource::BudgetsResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::BudgetsConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{AnomalyMonitor, AnomalySubscription, Budget, MonitorScope, Notification, Spend, Subscriber};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct BudgetsConnector {
+    client: Mutex<Option<Arc<aws_sdk_budgets::Client>>>,
+    ce_client: Mutex<Option<Arc<aws_sdk_costexplorer::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<BudgetsConnectorConfig>,
+    prefix: PathBuf,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl BudgetsConnector {
+    pub async fn get_or_init_client(&self) -> anyhow::Result<Arc<aws_sdk_budgets::Client>> {
+        let mut client = self.client.lock().await;
+
+        if let Some(client) = &*client {
+            return Ok(client.clone());
+        }
+
+        // Budgets is global, but its API is served from us-east-1
+        let config = load_sdk_config("us-east-1").await;
+        let new_client = Arc::new(audited_client!(aws_sdk_budgets, &config));
+        *client = Some(new_client.clone());
+
+        Ok(new_client)
+    }
+
+    /// Cost Anomaly Detection is part of the Cost Explorer API, which is also served from us-east-1.
+    pub async fn get_or_init_ce_client(&self) -> anyhow::Result<Arc<aws_sdk_costexplorer::Client>> {
+        let mut ce_client = self.ce_client.lock().await;
+
+        if let Some(ce_client) = &*ce_client {
+            return Ok(ce_client.clone());
+        }
+
+        let config = load_sdk_config("us-east-1").await;
+        let new_client = Arc::new(audited_client!(aws_sdk_costexplorer, &config));
+        *ce_client = Some(new_client.clone());
+
+        Ok(new_client)
+    }
+}
+
+#[async_trait]
+impl Connector for BudgetsConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = BudgetsResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(BudgetsConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let budgets_config: BudgetsConnectorConfig = BudgetsConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = budgets_config.verify_sts().await?;
+
+        *self.client.lock().await = None;
+        *self.ce_client.lock().await = None;
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(budgets_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = budgets_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        res.push(skeleton!(
+            BudgetsResourceAddress::Budget {
+                name: String::from("[budget_name]"),
+            },
+            BudgetsResource::Budget(Budget {
+                budget_type:   String::from("COST"),
+                time_unit:     String::from("MONTHLY"),
+                limit:         Some(Spend {
+                    amount: String::from("1000.0"),
+                    unit:   String::from("USD"),
+                }),
+                cost_filters:  HashMap::new(),
+                notifications: vec![
+                    Notification {
+                        notification_type:   String::from("ACTUAL"),
+                        comparison_operator: String::from("GREATER_THAN"),
+                        threshold:           80.0,
+                        threshold_type:      String::from("PERCENTAGE"),
+                        subscribers:         vec![Subscriber {
+                            subscription_type: String::from("EMAIL"),
+                            address:           String::from("[email_address]"),
+                        }],
+                    },
+                    // Notifies a topic managed by the SNS connector
+                    Notification {
+                        notification_type:   String::from("FORECASTED"),
+                        comparison_operator: String::from("GREATER_THAN"),
+                        threshold:           100.0,
+                        threshold_type:      String::from("PERCENTAGE"),
+                        subscribers:         vec![Subscriber {
+                            subscription_type: String::from("SNS"),
+                            address:           String::from("aws/sns/[region]/topics/[topic_name].ron"),
+                        }],
+                    },
+                ],
+                tags:          Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            BudgetsResourceAddress::AnomalyMonitor {
+                name: String::from("[monitor_name]"),
+            },
+            BudgetsResource::AnomalyMonitor(AnomalyMonitor {
+                scope: MonitorScope::Services,
+                tags:  Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            BudgetsResourceAddress::AnomalySubscription {
+                name: String::from("[subscription_name]"),
+            },
+            BudgetsResource::AnomalySubscription(AnomalySubscription {
+                monitors: vec![String::from("[monitor_name]")],
+                frequency: String::from("DAILY"),
+                impact_absolute: Some(100.0),
+                impact_percentage: None,
+                subscribers: vec![Subscriber {
+                    subscription_type: String::from("EMAIL"),
+                    address:           String::from("[email_address]"),
+                }],
+                tags: Tags::default(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = BudgetsResourceAddress::from_path(addr)?;
+
+        match addr {
+            BudgetsResourceAddress::Budget { .. } => ron_check_eq::<Budget>(a, b),
+            BudgetsResourceAddress::AnomalyMonitor { .. } => ron_check_eq::<AnomalyMonitor>(a, b),
+            BudgetsResourceAddress::AnomalySubscription { .. } => ron_check_eq::<AnomalySubscription>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = BudgetsResourceAddress::from_path(addr)?;
+
+        match addr {
+            BudgetsResourceAddress::Budget { .. } => ron_check_syntax::<Budget>(a),
+            BudgetsResourceAddress::AnomalyMonitor { .. } => ron_check_syntax::<AnomalyMonitor>(a),
+            BudgetsResourceAddress::AnomalySubscription { .. } => ron_check_syntax::<AnomalySubscription>(a),
+        }
+    }
+}
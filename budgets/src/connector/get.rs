@@ -0,0 +1,128 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+
+use crate::{
+    addr::BudgetsResourceAddress,
+    op_impl::{
+        anomaly_monitor_names_by_arn, find_anomaly_monitor, find_anomaly_subscription, find_budget, list_budget_tags,
+        list_ce_tags, list_notifications,
+    },
+    resource::{AnomalyMonitor, AnomalySubscription, Budget, BudgetsResource, Spend, Subscriber},
+    util::{budget_arn, impact_thresholds, monitor_scope_from_sdk, subscribers_by_address, topic_addresses_by_arn},
+};
+
+use super::BudgetsConnector;
+
+impl BudgetsConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = BudgetsResourceAddress::from_path(addr)?;
+
+        match &addr {
+            BudgetsResourceAddress::Budget { name } => {
+                let client = self.get_or_init_client().await?;
+                let account_id = self.account_id.lock().await.clone();
+
+                let Some(budget) = find_budget(&client, &account_id, name).await? else {
+                    return Ok(None);
+                };
+
+                let arn = budget_arn(&account_id, name);
+                let topic_addresses = topic_addresses_by_arn(&self.prefix)?;
+
+                let mut notifications = list_notifications(&client, &account_id, name).await?;
+                for notification in &mut notifications {
+                    subscribers_by_address(&mut notification.subscribers, &topic_addresses);
+                }
+
+                let mut cost_filters = budget.cost_filters.unwrap_or_default();
+                for values in cost_filters.values_mut() {
+                    values.sort();
+                }
+
+                let resource = Budget {
+                    budget_type: budget.budget_type.as_str().to_string(),
+                    time_unit: budget.time_unit.as_str().to_string(),
+                    limit: budget.budget_limit.map(|limit| Spend {
+                        amount: limit.amount,
+                        unit:   limit.unit,
+                    }),
+                    cost_filters,
+                    notifications,
+                    tags: list_budget_tags(&client, &arn).await?,
+                };
+
+                get_resource_response!(BudgetsResource::Budget(resource), [(String::from("budget_arn"), arn)])
+            }
+            BudgetsResourceAddress::AnomalyMonitor { name } => {
+                let client = self.get_or_init_ce_client().await?;
+
+                let Some(monitor) = find_anomaly_monitor(&client, name).await? else {
+                    return Ok(None);
+                };
+
+                let arn = monitor.monitor_arn.clone().unwrap_or_default();
+
+                let resource = AnomalyMonitor {
+                    scope: monitor_scope_from_sdk(&monitor)?,
+                    tags:  list_ce_tags(&client, &arn).await?,
+                };
+
+                get_resource_response!(BudgetsResource::AnomalyMonitor(resource), [(String::from("monitor_arn"), arn)])
+            }
+            BudgetsResourceAddress::AnomalySubscription { name } => {
+                let client = self.get_or_init_ce_client().await?;
+
+                let Some(subscription) = find_anomaly_subscription(&client, name).await? else {
+                    return Ok(None);
+                };
+
+                let arn = subscription.subscription_arn.clone().unwrap_or_default();
+
+                // Monitors are reported by name, unless they've since been deleted
+                let monitor_names = anomaly_monitor_names_by_arn(&client).await?;
+                let mut monitors: Vec<String> = subscription
+                    .monitor_arn_list
+                    .into_iter()
+                    .map(|arn| monitor_names.get(&arn).cloned().unwrap_or(arn))
+                    .collect();
+                monitors.sort();
+
+                let mut subscribers: Vec<Subscriber> = subscription
+                    .subscribers
+                    .into_iter()
+                    .map(|subscriber| Subscriber {
+                        subscription_type: subscriber.r#type.map(|t| t.as_str().to_string()).unwrap_or_default(),
+                        address: subscriber.address.unwrap_or_default(),
+                    })
+                    .collect();
+                subscribers_by_address(&mut subscribers, &topic_addresses_by_arn(&self.prefix)?);
+
+                let (mut impact_absolute, impact_percentage) = impact_thresholds(subscription.threshold_expression.as_ref());
+                // Subscriptions from before threshold expressions only have the deprecated absolute threshold
+                #[allow(deprecated)]
+                let legacy_threshold = subscription.threshold;
+                if impact_absolute.is_none() && impact_percentage.is_none() {
+                    impact_absolute = legacy_threshold;
+                }
+
+                let resource = AnomalySubscription {
+                    monitors,
+                    frequency: subscription.frequency.as_str().to_string(),
+                    impact_absolute,
+                    impact_percentage,
+                    subscribers,
+                    tags: list_ce_tags(&client, &arn).await?,
+                };
+
+                get_resource_response!(
+                    BudgetsResource::AnomalySubscription(resource),
+                    [(String::from("subscription_arn"), arn)]
+                )
+            }
+        }
+    }
+}
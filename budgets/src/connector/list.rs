@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::addr::BudgetsResourceAddress;
+
+use super::BudgetsConnector;
+
+impl BudgetsConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let client = self.get_or_init_client().await?;
+        let account_id = self.account_id.lock().await.clone();
+
+        let mut next_token = None;
+        loop {
+            let resp = client
+                .describe_budgets()
+                .account_id(&account_id)
+                .set_next_token(next_token)
+                .send()
+                .await?;
+
+            for budget in resp.budgets.unwrap_or_default() {
+                results.push(BudgetsResourceAddress::Budget { name: budget.budget_name }.to_path_buf());
+            }
+
+            next_token = resp.next_token;
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        let ce_client = self.get_or_init_ce_client().await?;
+
+        let mut next_page_token = None;
+        loop {
+            let resp = ce_client
+                .get_anomaly_monitors()
+                .set_next_page_token(next_page_token)
+                .send()
+                .await?;
+
+            for monitor in resp.anomaly_monitors {
+                results.push(
+                    BudgetsResourceAddress::AnomalyMonitor {
+                        name: monitor.monitor_name,
+                    }
+                    .to_path_buf(),
+                );
+            }
+
+            next_page_token = resp.next_page_token;
+            if next_page_token.is_none() {
+                break;
+            }
+        }
+
+        let mut next_page_token = None;
+        loop {
+            let resp = ce_client
+                .get_anomaly_subscriptions()
+                .set_next_page_token(next_page_token)
+                .send()
+                .await?;
+
+            for subscription in resp.anomaly_subscriptions {
+                results.push(
+                    BudgetsResourceAddress::AnomalySubscription {
+                        name: subscription.subscription_name,
+                    }
+                    .to_path_buf(),
+                );
+            }
+
+            next_page_token = resp.next_page_token;
+            if next_page_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(results)
+    }
+}
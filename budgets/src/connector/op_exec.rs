@@ -0,0 +1,104 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{ConnectorOp, OpExecResponse, ResourceAddress},
+    error_util::invalid_op,
+};
+
+use crate::{
+    addr::BudgetsResourceAddress,
+    op::BudgetsConnectorOp,
+    op_impl,
+    resource::{AnomalySubscription, Notification},
+    util::resolve_topics,
+};
+
+use super::BudgetsConnector;
+
+impl BudgetsConnector {
+    /// Replaces SNS topic addresses on each notification's subscribers with their ARNs.
+    fn resolve_notifications(&self, notifications: Vec<Notification>) -> anyhow::Result<Vec<Notification>> {
+        let mut resolved = Vec::new();
+        for notification in notifications {
+            resolved.push(Notification {
+                subscribers: resolve_topics(&self.prefix, notification.subscribers)?,
+                ..notification
+            });
+        }
+        Ok(resolved)
+    }
+
+    /// Replaces SNS topic addresses on the subscription's subscribers with their ARNs.
+    fn resolve_subscription(&self, subscription: AnomalySubscription) -> anyhow::Result<AnomalySubscription> {
+        Ok(AnomalySubscription {
+            subscribers: resolve_topics(&self.prefix, subscription.subscribers)?,
+            ..subscription
+        })
+    }
+
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = BudgetsResourceAddress::from_path(addr)?;
+        let op = BudgetsConnectorOp::from_str(op)?;
+
+        match &addr {
+            BudgetsResourceAddress::Budget { name } => {
+                let client = self.get_or_init_client().await?;
+                let account_id = self.account_id.lock().await.clone();
+
+                match op {
+                    BudgetsConnectorOp::CreateBudget(mut budget) => {
+                        budget.notifications = self.resolve_notifications(budget.notifications)?;
+                        op_impl::create_budget(&client, &account_id, name, &budget).await
+                    }
+                    BudgetsConnectorOp::UpdateBudget(budget) => op_impl::update_budget(&client, &account_id, name, &budget).await,
+                    BudgetsConnectorOp::UpdateBudgetNotifications(old_notifications, new_notifications) => {
+                        let old_notifications = self.resolve_notifications(old_notifications)?;
+                        let new_notifications = self.resolve_notifications(new_notifications)?;
+                        op_impl::update_budget_notifications(&client, &account_id, name, &old_notifications, &new_notifications)
+                            .await
+                    }
+                    BudgetsConnectorOp::UpdateBudgetTags(old_tags, new_tags) => {
+                        op_impl::update_budget_tags(&client, &account_id, name, &old_tags, &new_tags).await
+                    }
+                    BudgetsConnectorOp::DeleteBudget => op_impl::delete_budget(&client, &account_id, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            BudgetsResourceAddress::AnomalyMonitor { name } => {
+                let client = self.get_or_init_ce_client().await?;
+
+                match op {
+                    BudgetsConnectorOp::CreateAnomalyMonitor(monitor) => {
+                        op_impl::create_anomaly_monitor(&client, name, &monitor).await
+                    }
+                    BudgetsConnectorOp::UpdateAnomalyMonitorTags(old_tags, new_tags) => {
+                        op_impl::update_anomaly_monitor_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    BudgetsConnectorOp::DeleteAnomalyMonitor => op_impl::delete_anomaly_monitor(&client, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            BudgetsResourceAddress::AnomalySubscription { name } => {
+                let client = self.get_or_init_ce_client().await?;
+
+                match op {
+                    BudgetsConnectorOp::CreateAnomalySubscription(subscription) => {
+                        let subscription = self.resolve_subscription(subscription)?;
+                        op_impl::create_anomaly_subscription(&client, name, &subscription).await
+                    }
+                    BudgetsConnectorOp::UpdateAnomalySubscription(subscription) => {
+                        let subscription = self.resolve_subscription(subscription)?;
+                        op_impl::update_anomaly_subscription(&client, name, &subscription).await
+                    }
+                    BudgetsConnectorOp::UpdateAnomalySubscriptionTags(old_tags, new_tags) => {
+                        op_impl::update_anomaly_subscription_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    BudgetsConnectorOp::DeleteAnomalySubscription => {
+                        op_impl::delete_anomaly_subscription(&client, name).await
+                    }
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::{
+    resource::{AnomalyMonitor, AnomalySubscription, Budget, MonitorScope, Notification},
+    util::check_subscribers,
+};
+
+use super::{BudgetsConnector, BudgetsConnectorOp, BudgetsResourceAddress};
+
+const BUDGET_TYPES: &[&str] = &[
+    "COST",
+    "USAGE",
+    "RI_UTILIZATION",
+    "RI_COVERAGE",
+    "SAVINGS_PLANS_UTILIZATION",
+    "SAVINGS_PLANS_COVERAGE",
+];
+const TIME_UNITS: &[&str] = &["DAILY", "MONTHLY", "QUARTERLY", "ANNUALLY"];
+const NOTIFICATION_TYPES: &[&str] = &["ACTUAL", "FORECASTED"];
+const COMPARISON_OPERATORS: &[&str] = &["GREATER_THAN", "LESS_THAN", "EQUAL_TO"];
+const THRESHOLD_TYPES: &[&str] = &["PERCENTAGE", "ABSOLUTE_VALUE"];
+const FREQUENCIES: &[&str] = &["DAILY", "IMMEDIATE", "WEEKLY"];
+
+fn check_notification(name: &str, notification: &Notification) -> anyhow::Result<()> {
+    if !NOTIFICATION_TYPES.contains(&notification.notification_type.as_str()) {
+        bail!(
+            "Budget {} has a notification of type {}: expected one of {}",
+            name,
+            notification.notification_type,
+            NOTIFICATION_TYPES.join(", ")
+        );
+    }
+    if !COMPARISON_OPERATORS.contains(&notification.comparison_operator.as_str()) {
+        bail!(
+            "Budget {} has a notification with comparison operator {}: expected one of {}",
+            name,
+            notification.comparison_operator,
+            COMPARISON_OPERATORS.join(", ")
+        );
+    }
+    if !THRESHOLD_TYPES.contains(&notification.threshold_type.as_str()) {
+        bail!(
+            "Budget {} has a notification with threshold type {}: expected one of {}",
+            name,
+            notification.threshold_type,
+            THRESHOLD_TYPES.join(", ")
+        );
+    }
+    if notification.subscribers.is_empty() || notification.subscribers.len() > 11 {
+        bail!(
+            "Budget {} has a notification with {} subscribers: expected 1 to 11",
+            name,
+            notification.subscribers.len()
+        );
+    }
+    if notification.subscribers.iter().filter(|s| s.subscription_type == "SNS").count() > 1 {
+        bail!("Budget {} has a notification with more than one SNS subscriber", name);
+    }
+    let problems = check_subscribers(&notification.subscribers);
+    if !problems.is_empty() {
+        bail!("Budget {} has a notification with invalid subscribers: {}", name, problems.join("; "));
+    }
+    Ok(())
+}
+
+fn check_budget(name: &str, budget: &Budget) -> anyhow::Result<()> {
+    if !BUDGET_TYPES.contains(&budget.budget_type.as_str()) {
+        bail!(
+            "Budget {} has type {}: expected one of {}",
+            name,
+            budget.budget_type,
+            BUDGET_TYPES.join(", ")
+        );
+    }
+    if !TIME_UNITS.contains(&budget.time_unit.as_str()) {
+        bail!(
+            "Budget {} has time unit {}: expected one of {}",
+            name,
+            budget.time_unit,
+            TIME_UNITS.join(", ")
+        );
+    }
+    if matches!(budget.budget_type.as_str(), "COST" | "USAGE") && budget.limit.is_none() {
+        bail!("Budget {} is a {} budget, which needs a limit", name, budget.budget_type);
+    }
+    for notification in &budget.notifications {
+        check_notification(name, notification)?;
+    }
+    Ok(())
+}
+
+fn check_anomaly_monitor(name: &str, monitor: &AnomalyMonitor) -> anyhow::Result<()> {
+    let empty = match &monitor.scope {
+        MonitorScope::Services => false,
+        MonitorScope::LinkedAccounts(accounts) => accounts.is_empty(),
+        MonitorScope::Tag { values, .. } | MonitorScope::CostCategory { values, .. } => values.is_empty(),
+    };
+    if empty {
+        bail!("Anomaly monitor {} has an empty scope", name);
+    }
+    Ok(())
+}
+
+fn check_anomaly_subscription(name: &str, subscription: &AnomalySubscription) -> anyhow::Result<()> {
+    if !FREQUENCIES.contains(&subscription.frequency.as_str()) {
+        bail!(
+            "Anomaly subscription {} has frequency {}: expected one of {}",
+            name,
+            subscription.frequency,
+            FREQUENCIES.join(", ")
+        );
+    }
+    if subscription.monitors.is_empty() {
+        bail!("Anomaly subscription {} has no monitors", name);
+    }
+    if subscription.impact_absolute.is_none() && subscription.impact_percentage.is_none() {
+        bail!(
+            "Anomaly subscription {} has no threshold: set impact_absolute, impact_percentage or both",
+            name
+        );
+    }
+    if subscription.subscribers.is_empty() {
+        bail!("Anomaly subscription {} has no subscribers", name);
+    }
+    // Immediate alerts are only sent to SNS, and daily or weekly summaries only by email
+    let expected_type = if subscription.frequency == "IMMEDIATE" { "SNS" } else { "EMAIL" };
+    if let Some(subscriber) = subscription
+        .subscribers
+        .iter()
+        .find(|s| s.subscription_type != expected_type)
+    {
+        bail!(
+            "Anomaly subscription {} has {} subscriber {}, but {} subscriptions only support {} subscribers",
+            name,
+            subscriber.subscription_type,
+            subscriber.address,
+            subscription.frequency,
+            expected_type
+        );
+    }
+    let problems = check_subscribers(&subscription.subscribers);
+    if !problems.is_empty() {
+        bail!("Anomaly subscription {} has invalid subscribers: {}", name, problems.join("; "));
+    }
+    Ok(())
+}
+
+/// Sorts notifications and their subscribers, since Budgets doesn't keep them in any order.
+fn sort_notifications(notifications: &mut [Notification]) {
+    for notification in notifications.iter_mut() {
+        notification.subscribers.sort();
+    }
+    notifications.sort_by(|a, b| {
+        (&a.notification_type, &a.comparison_operator, &a.threshold_type)
+            .cmp(&(&b.notification_type, &b.comparison_operator, &b.threshold_type))
+            .then(a.threshold.total_cmp(&b.threshold))
+    });
+}
+
+/// Budgets reports limits with its own formatting (e.g. 100.0 for 100), so equal amounts aren't a change.
+fn normalize_budget(old: &Budget, new: &mut Budget) {
+    if let (Some(old_limit), Some(new_limit)) = (&old.limit, &mut new.limit)
+        && old_limit.unit == new_limit.unit
+        && old_limit.amount.parse::<f64>().ok() == new_limit.amount.parse::<f64>().ok()
+    {
+        new_limit.amount = old_limit.amount.clone();
+    }
+    for values in new.cost_filters.values_mut() {
+        values.sort();
+    }
+    sort_notifications(&mut new.notifications);
+}
+
+impl BudgetsConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = BudgetsResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            BudgetsResourceAddress::Budget { name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_budget)) => {
+                    let new_budget: Budget = RON.from_str(&new_budget)?;
+                    check_budget(name, &new_budget)?;
+                    Ok(vec![connector_op!(
+                        BudgetsConnectorOp::CreateBudget(new_budget),
+                        format!("Create new budget {}", name)
+                    )])
+                }
+                (Some(_old_budget), None) => Ok(vec![connector_op!(
+                    BudgetsConnectorOp::DeleteBudget,
+                    format!("DELETE budget {} and its notifications", name)
+                )]),
+                (Some(old_budget), Some(new_budget)) => {
+                    let mut old_budget: Budget = RON.from_str(&old_budget)?;
+                    let mut new_budget: Budget = RON.from_str(&new_budget)?;
+                    check_budget(name, &new_budget)?;
+                    sort_notifications(&mut old_budget.notifications);
+                    normalize_budget(&old_budget, &mut new_budget);
+
+                    if old_budget.budget_type != new_budget.budget_type {
+                        bail!(
+                            "Budget {} can't change budget_type after creation. Create a new budget under another name instead.",
+                            name
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_budget.tags != new_budget.tags {
+                        let diff = diff_ron_values(&old_budget.tags, &new_budget.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            BudgetsConnectorOp::UpdateBudgetTags(old_budget.tags.clone(), new_budget.tags.clone()),
+                            format!("Modify tags for budget `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    if old_budget.notifications != new_budget.notifications {
+                        let diff = diff_ron_values(&old_budget.notifications, &new_budget.notifications).unwrap_or_default();
+                        ops.push(connector_op!(
+                            BudgetsConnectorOp::UpdateBudgetNotifications(
+                                old_budget.notifications.clone(),
+                                new_budget.notifications.clone()
+                            ),
+                            format!("Modify notifications for budget `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_budget.clone();
+                    old_settings.tags = new_budget.tags.clone();
+                    old_settings.notifications = new_budget.notifications.clone();
+                    if old_settings != new_budget {
+                        let diff = diff_ron_values(&old_settings, &new_budget).unwrap_or_default();
+                        ops.push(connector_op!(
+                            BudgetsConnectorOp::UpdateBudget(new_budget),
+                            format!("Modify budget `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            BudgetsResourceAddress::AnomalyMonitor { name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_monitor)) => {
+                    let new_monitor: AnomalyMonitor = RON.from_str(&new_monitor)?;
+                    check_anomaly_monitor(name, &new_monitor)?;
+                    Ok(vec![connector_op!(
+                        BudgetsConnectorOp::CreateAnomalyMonitor(new_monitor),
+                        format!("Create new anomaly monitor {}", name)
+                    )])
+                }
+                (Some(_old_monitor), None) => Ok(vec![connector_op!(
+                    BudgetsConnectorOp::DeleteAnomalyMonitor,
+                    format!(
+                        "DELETE anomaly monitor {}. Subscriptions that only use this monitor must be deleted first.",
+                        name
+                    )
+                )]),
+                (Some(old_monitor), Some(new_monitor)) => {
+                    let old_monitor: AnomalyMonitor = RON.from_str(&old_monitor)?;
+                    let new_monitor: AnomalyMonitor = RON.from_str(&new_monitor)?;
+                    check_anomaly_monitor(name, &new_monitor)?;
+
+                    if old_monitor.scope != new_monitor.scope {
+                        bail!(
+                            "Anomaly monitor {} can't change scope after creation. Create a new anomaly monitor under another name instead.",
+                            name
+                        );
+                    }
+
+                    if old_monitor.tags == new_monitor.tags {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_monitor.tags, &new_monitor.tags).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        BudgetsConnectorOp::UpdateAnomalyMonitorTags(old_monitor.tags, new_monitor.tags),
+                        format!("Modify tags for anomaly monitor `{}`\n{}", name, diff)
+                    )])
+                }
+            },
+            BudgetsResourceAddress::AnomalySubscription { name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_subscription)) => {
+                    let new_subscription: AnomalySubscription = RON.from_str(&new_subscription)?;
+                    check_anomaly_subscription(name, &new_subscription)?;
+                    Ok(vec![connector_op!(
+                        BudgetsConnectorOp::CreateAnomalySubscription(new_subscription),
+                        format!("Create new anomaly subscription {}", name)
+                    )])
+                }
+                (Some(_old_subscription), None) => Ok(vec![connector_op!(
+                    BudgetsConnectorOp::DeleteAnomalySubscription,
+                    format!("DELETE anomaly subscription {}", name)
+                )]),
+                (Some(old_subscription), Some(new_subscription)) => {
+                    let old_subscription: AnomalySubscription = RON.from_str(&old_subscription)?;
+                    let mut new_subscription: AnomalySubscription = RON.from_str(&new_subscription)?;
+                    check_anomaly_subscription(name, &new_subscription)?;
+                    new_subscription.monitors.sort();
+                    new_subscription.subscribers.sort();
+
+                    let mut ops = Vec::new();
+
+                    if old_subscription.tags != new_subscription.tags {
+                        let diff = diff_ron_values(&old_subscription.tags, &new_subscription.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            BudgetsConnectorOp::UpdateAnomalySubscriptionTags(
+                                old_subscription.tags.clone(),
+                                new_subscription.tags.clone()
+                            ),
+                            format!("Modify tags for anomaly subscription `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_subscription.clone();
+                    old_settings.tags = new_subscription.tags.clone();
+                    if old_settings != new_subscription {
+                        let diff = diff_ron_values(&old_settings, &new_subscription).unwrap_or_default();
+                        ops.push(connector_op!(
+                            BudgetsConnectorOp::UpdateAnomalySubscription(new_subscription),
+                            format!("Modify anomaly subscription `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+        }
+    }
+}
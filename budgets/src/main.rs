@@ -0,0 +1,25 @@
+use addr::BudgetsResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
+use autoschematic_core::tarpc_bridge::tarpc_connector_main;
+use connector::BudgetsConnector;
+
+pub mod connector;
+pub mod addr;
+pub mod config;
+pub mod op;
+pub mod op_impl;
+pub mod resource;
+pub mod tags;
+pub mod util;
+
+#[tokio::main]
+pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let patterns = BudgetsResourceAddress::checked_address_patterns()?;
+        print!("{}", render_address_docs("aws/budgets", &patterns));
+        return Ok(());
+    }
+
+    tarpc_connector_main::<BudgetsConnector>().await?;
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{AnomalyMonitor, AnomalySubscription, Budget, Notification},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum BudgetsConnectorOp {
+    /// Creates the budget along with its notifications and their subscribers.
+    CreateBudget(Budget),
+    /// Applies the budget's time unit, limit and cost filters. Notifications are updated separately.
+    UpdateBudget(Budget),
+    /// Deletes notifications that were removed, creates new ones, and updates the subscribers of the rest.
+    UpdateBudgetNotifications(Vec<Notification>, Vec<Notification>),
+    UpdateBudgetTags(Tags, Tags),
+    DeleteBudget,
+
+    CreateAnomalyMonitor(AnomalyMonitor),
+    UpdateAnomalyMonitorTags(Tags, Tags),
+    DeleteAnomalyMonitor,
+
+    CreateAnomalySubscription(AnomalySubscription),
+    UpdateAnomalySubscription(AnomalySubscription),
+    UpdateAnomalySubscriptionTags(Tags, Tags),
+    DeleteAnomalySubscription,
+}
+
+impl ConnectorOp for BudgetsConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
@@ -0,0 +1,555 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use autoschematic_core::{connector::OpExecResponse, op_exec_output};
+use aws_sdk_budgets::types::{
+    BudgetType, ComparisonOperator, NotificationType, NotificationWithSubscribers, SubscriptionType, ThresholdType, TimeUnit,
+};
+use aws_sdk_costexplorer::types::{AnomalySubscriptionFrequency, SubscriberType};
+
+use crate::{
+    resource::{AnomalyMonitor, AnomalySubscription, Budget, Notification, Spend, Subscriber},
+    tags::{Tags, tag_diff},
+    util::{budget_arn, monitor_scope_to_sdk, same_notification, threshold_expression},
+};
+
+pub async fn find_budget(
+    client: &aws_sdk_budgets::Client,
+    account_id: &str,
+    name: &str,
+) -> anyhow::Result<Option<aws_sdk_budgets::types::Budget>> {
+    match client.describe_budget().account_id(account_id).budget_name(name).send().await {
+        Ok(resp) => Ok(resp.budget),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_not_found_exception()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub async fn list_budget_tags(client: &aws_sdk_budgets::Client, arn: &str) -> anyhow::Result<Tags> {
+    let resp = client.list_tags_for_resource().resource_arn(arn).send().await?;
+    Ok(Tags::from(resp.resource_tags.unwrap_or_default()))
+}
+
+async fn list_subscribers(
+    client: &aws_sdk_budgets::Client,
+    account_id: &str,
+    name: &str,
+    notification: &aws_sdk_budgets::types::Notification,
+) -> anyhow::Result<Vec<Subscriber>> {
+    let mut subscribers = Vec::new();
+    let mut next_token = None;
+    loop {
+        let resp = client
+            .describe_subscribers_for_notification()
+            .account_id(account_id)
+            .budget_name(name)
+            .notification(notification.clone())
+            .set_next_token(next_token)
+            .send()
+            .await?;
+
+        for subscriber in resp.subscribers.unwrap_or_default() {
+            subscribers.push(Subscriber {
+                subscription_type: subscriber.subscription_type.as_str().to_string(),
+                address: subscriber.address,
+            });
+        }
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            return Ok(subscribers);
+        }
+    }
+}
+
+/// Lists the budget's notifications along with their subscribers.
+pub async fn list_notifications(
+    client: &aws_sdk_budgets::Client,
+    account_id: &str,
+    name: &str,
+) -> anyhow::Result<Vec<Notification>> {
+    let mut notifications = Vec::new();
+    let mut next_token = None;
+    loop {
+        let resp = client
+            .describe_notifications_for_budget()
+            .account_id(account_id)
+            .budget_name(name)
+            .set_next_token(next_token)
+            .send()
+            .await?;
+
+        for notification in resp.notifications.unwrap_or_default() {
+            let subscribers = list_subscribers(client, account_id, name, &notification).await?;
+            notifications.push(Notification {
+                notification_type: notification.notification_type.as_str().to_string(),
+                comparison_operator: notification.comparison_operator.as_str().to_string(),
+                threshold: notification.threshold,
+                threshold_type: notification
+                    .threshold_type
+                    .map(|t| t.as_str().to_string())
+                    .unwrap_or_else(|| String::from("PERCENTAGE")),
+                subscribers,
+            });
+        }
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            return Ok(notifications);
+        }
+    }
+}
+
+fn sdk_budget(name: &str, budget: &Budget) -> anyhow::Result<aws_sdk_budgets::types::Budget> {
+    let limit = match &budget.limit {
+        Some(Spend { amount, unit }) => Some(aws_sdk_budgets::types::Spend::builder().amount(amount).unit(unit).build()?),
+        None => None,
+    };
+
+    Ok(aws_sdk_budgets::types::Budget::builder()
+        .budget_name(name)
+        .budget_type(BudgetType::from(budget.budget_type.as_str()))
+        .time_unit(TimeUnit::from(budget.time_unit.as_str()))
+        .set_budget_limit(limit)
+        // An empty map removes all cost filters
+        .set_cost_filters(Some(budget.cost_filters.clone()))
+        .build()?)
+}
+
+fn sdk_notification(notification: &Notification) -> anyhow::Result<aws_sdk_budgets::types::Notification> {
+    Ok(aws_sdk_budgets::types::Notification::builder()
+        .notification_type(NotificationType::from(notification.notification_type.as_str()))
+        .comparison_operator(ComparisonOperator::from(notification.comparison_operator.as_str()))
+        .threshold(notification.threshold)
+        .threshold_type(ThresholdType::from(notification.threshold_type.as_str()))
+        .build()?)
+}
+
+fn sdk_budget_subscriber(subscriber: &Subscriber) -> anyhow::Result<aws_sdk_budgets::types::Subscriber> {
+    Ok(aws_sdk_budgets::types::Subscriber::builder()
+        .subscription_type(SubscriptionType::from(subscriber.subscription_type.as_str()))
+        .address(&subscriber.address)
+        .build()?)
+}
+
+fn sdk_budget_subscribers(subscribers: &[Subscriber]) -> anyhow::Result<Vec<aws_sdk_budgets::types::Subscriber>> {
+    subscribers.iter().map(sdk_budget_subscriber).collect()
+}
+
+/// Creates the budget with its notifications. SNS subscribers must already be resolved to topic ARNs.
+pub async fn create_budget(
+    client: &aws_sdk_budgets::Client,
+    account_id: &str,
+    name: &str,
+    budget: &Budget,
+) -> anyhow::Result<OpExecResponse> {
+    let mut notifications = Vec::new();
+    for notification in &budget.notifications {
+        notifications.push(
+            NotificationWithSubscribers::builder()
+                .notification(sdk_notification(notification)?)
+                .set_subscribers(Some(sdk_budget_subscribers(&notification.subscribers)?))
+                .build()?,
+        );
+    }
+
+    client
+        .create_budget()
+        .account_id(account_id)
+        .budget(sdk_budget(name, budget)?)
+        .set_notifications_with_subscribers(if notifications.is_empty() { None } else { Some(notifications) })
+        .set_resource_tags(budget.tags.to_budgets_sdk()?)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("budget_arn"), Some(budget_arn(account_id, name)))])),
+        friendly_message: Some(format!("Created budget {}", name)),
+    })
+}
+
+pub async fn update_budget(
+    client: &aws_sdk_budgets::Client,
+    account_id: &str,
+    name: &str,
+    budget: &Budget,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .update_budget()
+        .account_id(account_id)
+        .new_budget(sdk_budget(name, budget)?)
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Updated budget {}", name))
+}
+
+/// Notifications are identified by their type, operator and threshold, so a notification whose threshold
+/// changed is deleted and created again. SNS subscribers must already be resolved to topic ARNs.
+pub async fn update_budget_notifications(
+    client: &aws_sdk_budgets::Client,
+    account_id: &str,
+    name: &str,
+    old_notifications: &[Notification],
+    new_notifications: &[Notification],
+) -> anyhow::Result<OpExecResponse> {
+    for old_notification in old_notifications {
+        if new_notifications.iter().any(|n| same_notification(n, old_notification)) {
+            continue;
+        }
+        client
+            .delete_notification()
+            .account_id(account_id)
+            .budget_name(name)
+            .notification(sdk_notification(old_notification)?)
+            .send()
+            .await?;
+    }
+
+    for new_notification in new_notifications {
+        let Some(old_notification) = old_notifications.iter().find(|n| same_notification(n, new_notification)) else {
+            client
+                .create_notification()
+                .account_id(account_id)
+                .budget_name(name)
+                .notification(sdk_notification(new_notification)?)
+                .set_subscribers(Some(sdk_budget_subscribers(&new_notification.subscribers)?))
+                .send()
+                .await?;
+            continue;
+        };
+
+        for subscriber in &new_notification.subscribers {
+            if old_notification.subscribers.contains(subscriber) {
+                continue;
+            }
+            client
+                .create_subscriber()
+                .account_id(account_id)
+                .budget_name(name)
+                .notification(sdk_notification(new_notification)?)
+                .subscriber(sdk_budget_subscriber(subscriber)?)
+                .send()
+                .await?;
+        }
+
+        for subscriber in &old_notification.subscribers {
+            if new_notification.subscribers.contains(subscriber) {
+                continue;
+            }
+            client
+                .delete_subscriber()
+                .account_id(account_id)
+                .budget_name(name)
+                .notification(sdk_notification(old_notification)?)
+                .subscriber(sdk_budget_subscriber(subscriber)?)
+                .send()
+                .await?;
+        }
+    }
+
+    op_exec_output!(format!("Updated notifications for budget {}", name))
+}
+
+pub async fn update_budget_tags(
+    client: &aws_sdk_budgets::Client,
+    account_id: &str,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = budget_arn(account_id, name);
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(&arn)
+            .set_resource_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if let Some(new_tagset) = new_tagset.to_budgets_sdk()? {
+        client
+            .tag_resource()
+            .resource_arn(&arn)
+            .set_resource_tags(Some(new_tagset))
+            .send()
+            .await?;
+    }
+
+    op_exec_output!(format!("Updated tags for budget {}", name))
+}
+
+pub async fn delete_budget(client: &aws_sdk_budgets::Client, account_id: &str, name: &str) -> anyhow::Result<OpExecResponse> {
+    client.delete_budget().account_id(account_id).budget_name(name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("budget_arn"), None)])),
+        friendly_message: Some(format!("Deleted budget {}", name)),
+    })
+}
+
+pub async fn list_ce_tags(client: &aws_sdk_costexplorer::Client, arn: &str) -> anyhow::Result<Tags> {
+    let resp = client.list_tags_for_resource().resource_arn(arn).send().await?;
+    Ok(Tags::from(resp.resource_tags.unwrap_or_default()))
+}
+
+async fn update_ce_tags(client: &aws_sdk_costexplorer::Client, arn: &str, old_tags: &Tags, new_tags: &Tags) -> anyhow::Result<()> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(arn)
+            .set_resource_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if let Some(new_tagset) = new_tagset.to_ce_sdk()? {
+        client.tag_resource().resource_arn(arn).set_resource_tags(Some(new_tagset)).send().await?;
+    }
+
+    Ok(())
+}
+
+/// GetAnomalyMonitors can't filter by name, so this pages through all of them.
+pub async fn find_anomaly_monitor(
+    client: &aws_sdk_costexplorer::Client,
+    name: &str,
+) -> anyhow::Result<Option<aws_sdk_costexplorer::types::AnomalyMonitor>> {
+    let mut next_page_token = None;
+    loop {
+        let resp = client.get_anomaly_monitors().set_next_page_token(next_page_token).send().await?;
+
+        if let Some(monitor) = resp.anomaly_monitors.into_iter().find(|monitor| monitor.monitor_name == name) {
+            return Ok(Some(monitor));
+        }
+
+        next_page_token = resp.next_page_token;
+        if next_page_token.is_none() {
+            return Ok(None);
+        }
+    }
+}
+
+/// GetAnomalySubscriptions can't filter by name, so this pages through all of them.
+pub async fn find_anomaly_subscription(
+    client: &aws_sdk_costexplorer::Client,
+    name: &str,
+) -> anyhow::Result<Option<aws_sdk_costexplorer::types::AnomalySubscription>> {
+    let mut next_page_token = None;
+    loop {
+        let resp = client
+            .get_anomaly_subscriptions()
+            .set_next_page_token(next_page_token)
+            .send()
+            .await?;
+
+        if let Some(subscription) = resp
+            .anomaly_subscriptions
+            .into_iter()
+            .find(|subscription| subscription.subscription_name == name)
+        {
+            return Ok(Some(subscription));
+        }
+
+        next_page_token = resp.next_page_token;
+        if next_page_token.is_none() {
+            return Ok(None);
+        }
+    }
+}
+
+/// The names of all anomaly monitors, keyed by ARN. Used to report a subscription's monitors by name.
+pub async fn anomaly_monitor_names_by_arn(client: &aws_sdk_costexplorer::Client) -> anyhow::Result<HashMap<String, String>> {
+    let mut names = HashMap::new();
+    let mut next_page_token = None;
+    loop {
+        let resp = client.get_anomaly_monitors().set_next_page_token(next_page_token).send().await?;
+
+        for monitor in resp.anomaly_monitors {
+            if let Some(arn) = monitor.monitor_arn {
+                names.insert(arn, monitor.monitor_name);
+            }
+        }
+
+        next_page_token = resp.next_page_token;
+        if next_page_token.is_none() {
+            return Ok(names);
+        }
+    }
+}
+
+async fn anomaly_monitor_arn(client: &aws_sdk_costexplorer::Client, name: &str) -> anyhow::Result<String> {
+    find_anomaly_monitor(client, name)
+        .await?
+        .and_then(|monitor| monitor.monitor_arn)
+        .with_context(|| format!("Anomaly monitor {} not found", name))
+}
+
+async fn anomaly_subscription_arn(client: &aws_sdk_costexplorer::Client, name: &str) -> anyhow::Result<String> {
+    find_anomaly_subscription(client, name)
+        .await?
+        .and_then(|subscription| subscription.subscription_arn)
+        .with_context(|| format!("Anomaly subscription {} not found", name))
+}
+
+/// Looks up the ARN of each monitor named by a subscription. Monitors given by ARN are passed through.
+async fn resolve_monitor_arns(client: &aws_sdk_costexplorer::Client, monitors: &[String]) -> anyhow::Result<Vec<String>> {
+    let mut arns = Vec::new();
+    for monitor in monitors {
+        if monitor.starts_with("arn:") {
+            arns.push(monitor.clone());
+        } else {
+            arns.push(anomaly_monitor_arn(client, monitor).await?);
+        }
+    }
+    Ok(arns)
+}
+
+fn sdk_anomaly_subscribers(subscribers: &[Subscriber]) -> Vec<aws_sdk_costexplorer::types::Subscriber> {
+    subscribers
+        .iter()
+        .map(|subscriber| {
+            aws_sdk_costexplorer::types::Subscriber::builder()
+                .r#type(SubscriberType::from(subscriber.subscription_type.as_str()))
+                .address(&subscriber.address)
+                .build()
+        })
+        .collect()
+}
+
+pub async fn create_anomaly_monitor(
+    client: &aws_sdk_costexplorer::Client,
+    name: &str,
+    monitor: &AnomalyMonitor,
+) -> anyhow::Result<OpExecResponse> {
+    let (monitor_type, monitor_dimension, monitor_specification) = monitor_scope_to_sdk(&monitor.scope);
+
+    let resp = client
+        .create_anomaly_monitor()
+        .anomaly_monitor(
+            aws_sdk_costexplorer::types::AnomalyMonitor::builder()
+                .monitor_name(name)
+                .monitor_type(monitor_type)
+                .set_monitor_dimension(monitor_dimension)
+                .set_monitor_specification(monitor_specification)
+                .build()?,
+        )
+        .set_resource_tags(monitor.tags.to_ce_sdk()?)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("monitor_arn"), Some(resp.monitor_arn))])),
+        friendly_message: Some(format!("Created anomaly monitor {}", name)),
+    })
+}
+
+pub async fn update_anomaly_monitor_tags(
+    client: &aws_sdk_costexplorer::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = anomaly_monitor_arn(client, name).await?;
+
+    update_ce_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for anomaly monitor {}", name))
+}
+
+pub async fn delete_anomaly_monitor(client: &aws_sdk_costexplorer::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    let arn = anomaly_monitor_arn(client, name).await?;
+
+    client.delete_anomaly_monitor().monitor_arn(&arn).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("monitor_arn"), None)])),
+        friendly_message: Some(format!("Deleted anomaly monitor {}", name)),
+    })
+}
+
+/// SNS subscribers must already be resolved to topic ARNs.
+pub async fn create_anomaly_subscription(
+    client: &aws_sdk_costexplorer::Client,
+    name: &str,
+    subscription: &AnomalySubscription,
+) -> anyhow::Result<OpExecResponse> {
+    let monitor_arns = resolve_monitor_arns(client, &subscription.monitors).await?;
+
+    let resp = client
+        .create_anomaly_subscription()
+        .anomaly_subscription(
+            aws_sdk_costexplorer::types::AnomalySubscription::builder()
+                .subscription_name(name)
+                .set_monitor_arn_list(Some(monitor_arns))
+                .set_subscribers(Some(sdk_anomaly_subscribers(&subscription.subscribers)))
+                .frequency(AnomalySubscriptionFrequency::from(subscription.frequency.as_str()))
+                .set_threshold_expression(threshold_expression(
+                    subscription.impact_absolute,
+                    subscription.impact_percentage,
+                ))
+                .build()?,
+        )
+        .set_resource_tags(subscription.tags.to_ce_sdk()?)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("subscription_arn"), Some(resp.subscription_arn))])),
+        friendly_message: Some(format!("Created anomaly subscription {}", name)),
+    })
+}
+
+/// SNS subscribers must already be resolved to topic ARNs.
+pub async fn update_anomaly_subscription(
+    client: &aws_sdk_costexplorer::Client,
+    name: &str,
+    subscription: &AnomalySubscription,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = anomaly_subscription_arn(client, name).await?;
+    let monitor_arns = resolve_monitor_arns(client, &subscription.monitors).await?;
+
+    client
+        .update_anomaly_subscription()
+        .subscription_arn(&arn)
+        .set_monitor_arn_list(Some(monitor_arns))
+        .set_subscribers(Some(sdk_anomaly_subscribers(&subscription.subscribers)))
+        .frequency(AnomalySubscriptionFrequency::from(subscription.frequency.as_str()))
+        .set_threshold_expression(threshold_expression(
+            subscription.impact_absolute,
+            subscription.impact_percentage,
+        ))
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Updated anomaly subscription {}", name))
+}
+
+pub async fn update_anomaly_subscription_tags(
+    client: &aws_sdk_costexplorer::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = anomaly_subscription_arn(client, name).await?;
+
+    update_ce_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for anomaly subscription {}", name))
+}
+
+pub async fn delete_anomaly_subscription(client: &aws_sdk_costexplorer::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    let arn = anomaly_subscription_arn(client, name).await?;
+
+    client.delete_anomaly_subscription().subscription_arn(&arn).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("subscription_arn"), None)])),
+        friendly_message: Some(format!("Deleted anomaly subscription {}", name)),
+    })
+}
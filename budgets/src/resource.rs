@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::BudgetsResourceAddress, tags::Tags};
+
+fn percentage() -> String {
+    String::from("PERCENTAGE")
+}
+
+/// An amount of money or usage, e.g. `Spend(amount: "100.0", unit: "USD")`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Spend {
+    pub amount: String,
+    /// USD for cost budgets, or the usage type's unit (e.g. GB or Hrs) for usage budgets.
+    pub unit:   String,
+}
+
+/// Someone notified by a budget or an anomaly subscription.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(deny_unknown_fields)]
+pub struct Subscriber {
+    /// EMAIL or SNS.
+    pub subscription_type: String,
+    /// An email address, or for SNS, a topic ARN or an SNS topic address like `aws/sns/us-east-1/topics/alerts.ron`.
+    /// The topic's policy must let budgets.amazonaws.com or costalerts.amazonaws.com publish to it.
+    pub address: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Notification {
+    /// ACTUAL or FORECASTED.
+    pub notification_type: String,
+    /// GREATER_THAN, LESS_THAN or EQUAL_TO.
+    pub comparison_operator: String,
+    pub threshold: f64,
+    /// PERCENTAGE of the budget's limit, or ABSOLUTE_VALUE.
+    #[serde(default = "percentage")]
+    pub threshold_type: String,
+    /// From 1 to 11 subscribers, of which at most one can be SNS.
+    pub subscribers: Vec<Subscriber>,
+}
+
+/// A budget that tracks cost or usage over each period, and notifies subscribers as it passes its thresholds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Budget {
+    /// COST, USAGE, RI_UTILIZATION, RI_COVERAGE, SAVINGS_PLANS_UTILIZATION or SAVINGS_PLANS_COVERAGE.
+    pub budget_type: String,
+    /// DAILY, MONTHLY, QUARTERLY or ANNUALLY.
+    pub time_unit: String,
+    /// Required for COST and USAGE budgets. Utilization and coverage budgets track a percentage instead.
+    pub limit: Option<Spend>,
+    /// Cost Explorer dimensions to restrict the budget to, e.g. `{"Service": ["Amazon Elastic Compute Cloud - Compute"]}`.
+    #[serde(default)]
+    pub cost_filters: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub notifications: Vec<Notification>,
+    pub tags: Tags,
+}
+
+/// What an anomaly monitor watches. `Services` evaluates each AWS service's spend on its own.
+/// The others evaluate the combined spend of the accounts, tag values or cost category values given.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum MonitorScope {
+    Services,
+    LinkedAccounts(Vec<String>),
+    Tag { key: String, values: Vec<String> },
+    CostCategory { key: String, values: Vec<String> },
+}
+
+/// A Cost Anomaly Detection monitor. Its scope is fixed at creation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AnomalyMonitor {
+    pub scope: MonitorScope,
+    pub tags:  Tags,
+}
+
+/// Alerts subscribers about anomalies found by its monitors, once they pass the impact thresholds.
+/// With both thresholds set, an anomaly has to pass both.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AnomalySubscription {
+    /// The names of anomaly monitors under aws/budgets/anomaly_monitors/, or monitor ARNs.
+    pub monitors: Vec<String>,
+    /// DAILY or WEEKLY summaries by email, or IMMEDIATE alerts, which are only sent to SNS.
+    pub frequency: String,
+    /// The anomaly's total cost impact in USD.
+    pub impact_absolute: Option<f64>,
+    /// The anomaly's total cost impact as a percentage of expected spend.
+    pub impact_percentage: Option<f64>,
+    pub subscribers: Vec<Subscriber>,
+    pub tags: Tags,
+}
+
+pub enum BudgetsResource {
+    Budget(Budget),
+    AnomalyMonitor(AnomalyMonitor),
+    AnomalySubscription(AnomalySubscription),
+}
+
+impl Resource for BudgetsResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            BudgetsResource::Budget(budget) => Ok(RON.to_string_pretty(&budget, pretty_config)?.into()),
+            BudgetsResource::AnomalyMonitor(monitor) => Ok(RON.to_string_pretty(&monitor, pretty_config)?.into()),
+            BudgetsResource::AnomalySubscription(subscription) => {
+                Ok(RON.to_string_pretty(&subscription, pretty_config)?.into())
+            }
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = BudgetsResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            BudgetsResourceAddress::Budget { .. } => Ok(BudgetsResource::Budget(RON.from_str(s)?)),
+            BudgetsResourceAddress::AnomalyMonitor { .. } => Ok(BudgetsResource::AnomalyMonitor(RON.from_str(s)?)),
+            BudgetsResourceAddress::AnomalySubscription { .. } => Ok(BudgetsResource::AnomalySubscription(RON.from_str(s)?)),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Tags(HashMap<String, String>);
+
+impl From<Vec<aws_sdk_budgets::types::ResourceTag>> for Tags {
+    fn from(tags: Vec<aws_sdk_budgets::types::ResourceTag>) -> Self {
+        Tags(tags.into_iter().map(|tag| (tag.key, tag.value)).collect())
+    }
+}
+
+impl From<Vec<aws_sdk_costexplorer::types::ResourceTag>> for Tags {
+    fn from(tags: Vec<aws_sdk_costexplorer::types::ResourceTag>) -> Self {
+        Tags(tags.into_iter().map(|tag| (tag.key, tag.value)).collect())
+    }
+}
+
+impl Tags {
+    /// Budgets and Cost Explorer each have their own tag type. Both have a required key and value,
+    /// so building them can fail.
+    pub fn to_budgets_sdk(&self) -> anyhow::Result<Option<Vec<aws_sdk_budgets::types::ResourceTag>>> {
+        if self.0.is_empty() {
+            return Ok(None);
+        }
+        let mut out_vec = Vec::new();
+        for (k, v) in &self.0 {
+            out_vec.push(aws_sdk_budgets::types::ResourceTag::builder().key(k).value(v).build()?);
+        }
+        Ok(Some(out_vec))
+    }
+
+    pub fn to_ce_sdk(&self) -> anyhow::Result<Option<Vec<aws_sdk_costexplorer::types::ResourceTag>>> {
+        if self.0.is_empty() {
+            return Ok(None);
+        }
+        let mut out_vec = Vec::new();
+        for (k, v) in &self.0 {
+            out_vec.push(aws_sdk_costexplorer::types::ResourceTag::builder().key(k).value(v).build()?);
+        }
+        Ok(Some(out_vec))
+    }
+}
+
+// From a pair of tag maps, determine the keys to untag and the tags to set respectively
+pub fn tag_diff(old_tags: &Tags, new_tags: &Tags) -> (Vec<String>, Tags) {
+    let untag_keys = old_tags.0.keys().filter(|k| !new_tags.0.contains_key(*k)).cloned().collect();
+
+    let new_tagset = new_tags
+        .0
+        .iter()
+        .filter(|(k, v)| old_tags.0.get(*k) != Some(*v))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+
+    (untag_keys, Tags(new_tagset))
+}
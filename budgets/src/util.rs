@@ -0,0 +1,275 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::bail;
+use autoschematic_core::connector::ResourceAddress;
+use aws_sdk_costexplorer::types::{
+    CostCategoryValues, Dimension, DimensionValues, Expression, MatchOption, MonitorDimension, MonitorType, TagValues,
+};
+
+use crate::{
+    addr::SnsTopicAddress,
+    resource::{MonitorScope, Notification, Subscriber},
+};
+
+/// The Cost Explorer dimensions an anomaly subscription's threshold expression is built from.
+const IMPACT_ABSOLUTE: &str = "ANOMALY_TOTAL_IMPACT_ABSOLUTE";
+const IMPACT_PERCENTAGE: &str = "ANOMALY_TOTAL_IMPACT_PERCENTAGE";
+
+/// Budgets have no ARN in the API's responses, but tagging needs one.
+pub fn budget_arn(account_id: &str, name: &str) -> String {
+    format!("arn:aws:budgets::{}:budget/{}", account_id, name)
+}
+
+/// Whether two notifications are the same notification. Budgets identifies a notification by everything except its subscribers.
+pub fn same_notification(a: &Notification, b: &Notification) -> bool {
+    a.notification_type == b.notification_type
+        && a.comparison_operator == b.comparison_operator
+        && a.threshold == b.threshold
+        && a.threshold_type == b.threshold_type
+}
+
+/// Whether a subscriber's `address` names an SNS topic address rather than an email address or topic ARN.
+pub fn is_topic_address(address: &str) -> bool {
+    address.starts_with("aws/sns/")
+}
+
+/// Checks that each SNS subscriber's address is a topic ARN or a valid SNS topic address.
+pub fn check_subscribers(subscribers: &[Subscriber]) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for subscriber in subscribers {
+        match subscriber.subscription_type.as_str() {
+            "EMAIL" => {
+                if is_topic_address(&subscriber.address) || subscriber.address.starts_with("arn:") {
+                    problems.push(format!(
+                        "{} is an SNS topic, but the subscriber's type is EMAIL",
+                        subscriber.address
+                    ));
+                }
+            }
+            "SNS" => {
+                if is_topic_address(&subscriber.address) {
+                    if SnsTopicAddress::from_path(Path::new(&subscriber.address)).is_err() {
+                        problems.push(format!(
+                            "{} is not an SNS topic address; expected aws/sns/<region>/topics/<name>.ron",
+                            subscriber.address
+                        ));
+                    }
+                } else if !subscriber.address.starts_with("arn:") {
+                    problems.push(format!(
+                        "{} is neither an SNS topic ARN nor an SNS topic address",
+                        subscriber.address
+                    ));
+                }
+            }
+            other => problems.push(format!(
+                "subscriber {} has type {}: expected one of EMAIL, SNS",
+                subscriber.address, other
+            )),
+        }
+    }
+
+    problems
+}
+
+/// Replaces each SNS topic address on `subscribers` with the ARN from that topic's outputs.
+/// Fails if a referenced topic hasn't been created yet.
+pub fn resolve_topics(prefix: &Path, subscribers: Vec<Subscriber>) -> anyhow::Result<Vec<Subscriber>> {
+    let mut resolved = Vec::new();
+
+    for mut subscriber in subscribers {
+        if is_topic_address(&subscriber.address) {
+            let addr = SnsTopicAddress::from_path(Path::new(&subscriber.address))?;
+            let Some(arn) = addr.get_output(prefix, "topic_arn")? else {
+                bail!("SNS topic {} has no topic_arn output. Has it been created yet?", subscriber.address);
+            };
+            subscriber.address = arn;
+        }
+        resolved.push(subscriber);
+    }
+
+    Ok(resolved)
+}
+
+/// The addresses of the SNS topics in every region that have been created, keyed by ARN.
+/// Used to report SNS subscribers by address when the repo manages their topics.
+pub fn topic_addresses_by_arn(prefix: &Path) -> anyhow::Result<HashMap<String, String>> {
+    let mut addresses = HashMap::new();
+
+    let sns_dir = prefix.join("aws/sns");
+    if !sns_dir.is_dir() {
+        return Ok(addresses);
+    }
+
+    for region_entry in std::fs::read_dir(&sns_dir)? {
+        let region_dir = region_entry?.path();
+        let Some(region) = region_dir.file_name().and_then(|f| f.to_str()) else {
+            continue;
+        };
+        let topics_dir = region_dir.join("topics");
+        if !topics_dir.is_dir() {
+            continue;
+        }
+
+        for entry in std::fs::read_dir(&topics_dir)? {
+            let path = entry?.path();
+            let Some(file_name) = path.file_name().and_then(|f| f.to_str()) else {
+                continue;
+            };
+            let Some(name) = file_name.strip_suffix(".ron") else {
+                continue;
+            };
+
+            let addr = SnsTopicAddress {
+                region: region.to_string(),
+                name:   name.to_string(),
+            };
+            if let Some(arn) = addr.get_output(prefix, "topic_arn")? {
+                addresses.insert(arn, addr.to_path_buf().to_string_lossy().to_string());
+            }
+        }
+    }
+
+    Ok(addresses)
+}
+
+/// Reports each SNS subscriber whose topic the repo manages by that topic's address.
+pub fn subscribers_by_address(subscribers: &mut [Subscriber], topic_addresses: &HashMap<String, String>) {
+    for subscriber in subscribers.iter_mut() {
+        if let Some(address) = topic_addresses.get(&subscriber.address) {
+            subscriber.address = address.clone();
+        }
+    }
+    subscribers.sort();
+}
+
+/// The monitor type, dimension and specification Cost Explorer describes a monitor's scope with.
+pub fn monitor_scope_to_sdk(scope: &MonitorScope) -> (MonitorType, Option<MonitorDimension>, Option<Expression>) {
+    match scope {
+        MonitorScope::Services => (MonitorType::Dimensional, Some(MonitorDimension::Service), None),
+        MonitorScope::LinkedAccounts(accounts) => (
+            MonitorType::Custom,
+            None,
+            Some(
+                Expression::builder()
+                    .dimensions(
+                        DimensionValues::builder()
+                            .key(Dimension::LinkedAccount)
+                            .set_values(Some(accounts.clone()))
+                            .build(),
+                    )
+                    .build(),
+            ),
+        ),
+        MonitorScope::Tag { key, values } => (
+            MonitorType::Custom,
+            None,
+            Some(
+                Expression::builder()
+                    .tags(TagValues::builder().key(key).set_values(Some(values.clone())).build())
+                    .build(),
+            ),
+        ),
+        MonitorScope::CostCategory { key, values } => (
+            MonitorType::Custom,
+            None,
+            Some(
+                Expression::builder()
+                    .cost_categories(CostCategoryValues::builder().key(key).set_values(Some(values.clone())).build())
+                    .build(),
+            ),
+        ),
+    }
+}
+
+pub fn monitor_scope_from_sdk(monitor: &aws_sdk_costexplorer::types::AnomalyMonitor) -> anyhow::Result<MonitorScope> {
+    if monitor.monitor_type == MonitorType::Dimensional {
+        return Ok(MonitorScope::Services);
+    }
+
+    let Some(spec) = &monitor.monitor_specification else {
+        bail!("Custom anomaly monitor {} has no specification", monitor.monitor_name);
+    };
+
+    if let Some(dimensions) = &spec.dimensions
+        && dimensions.key == Some(Dimension::LinkedAccount)
+    {
+        return Ok(MonitorScope::LinkedAccounts(dimensions.values.clone().unwrap_or_default()));
+    }
+    if let Some(tags) = &spec.tags {
+        return Ok(MonitorScope::Tag {
+            key:    tags.key.clone().unwrap_or_default(),
+            values: tags.values.clone().unwrap_or_default(),
+        });
+    }
+    if let Some(cost_categories) = &spec.cost_categories {
+        return Ok(MonitorScope::CostCategory {
+            key:    cost_categories.key.clone().unwrap_or_default(),
+            values: cost_categories.values.clone().unwrap_or_default(),
+        });
+    }
+
+    bail!(
+        "Anomaly monitor {} has a specification this connector can't represent",
+        monitor.monitor_name
+    )
+}
+
+fn impact_expression(key: &str, threshold: f64) -> Expression {
+    Expression::builder()
+        .dimensions(
+            DimensionValues::builder()
+                .key(Dimension::from(key))
+                .values(threshold.to_string())
+                .match_options(MatchOption::GreaterThanOrEqual)
+                .build(),
+        )
+        .build()
+}
+
+/// Builds a subscription's threshold expression from its impact thresholds, joined with AND when both are set.
+pub fn threshold_expression(impact_absolute: Option<f64>, impact_percentage: Option<f64>) -> Option<Expression> {
+    match (impact_absolute, impact_percentage) {
+        (None, None) => None,
+        (Some(absolute), None) => Some(impact_expression(IMPACT_ABSOLUTE, absolute)),
+        (None, Some(percentage)) => Some(impact_expression(IMPACT_PERCENTAGE, percentage)),
+        (Some(absolute), Some(percentage)) => Some(
+            Expression::builder()
+                .and(impact_expression(IMPACT_ABSOLUTE, absolute))
+                .and(impact_expression(IMPACT_PERCENTAGE, percentage))
+                .build(),
+        ),
+    }
+}
+
+/// Reads the impact thresholds back out of a threshold expression. Thresholds joined with OR aren't represented.
+pub fn impact_thresholds(expression: Option<&Expression>) -> (Option<f64>, Option<f64>) {
+    let Some(expression) = expression else {
+        return (None, None);
+    };
+
+    let mut leaves = vec![expression];
+    if let Some(and) = &expression.and {
+        leaves = and.iter().collect();
+    }
+
+    let mut absolute = None;
+    let mut percentage = None;
+    for leaf in leaves {
+        let Some(dimensions) = &leaf.dimensions else {
+            continue;
+        };
+        let value = dimensions
+            .values
+            .as_ref()
+            .and_then(|values| values.first())
+            .and_then(|value| value.parse().ok());
+        match dimensions.key.as_ref().map(|key| key.as_str()) {
+            Some(IMPACT_ABSOLUTE) => absolute = value,
+            Some(IMPACT_PERCENTAGE) => percentage = value,
+            _ => {}
+        }
+    }
+
+    (absolute, percentage)
+}
@@ -1,11 +1,13 @@
 use std::path::{Path, PathBuf};
 
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
 use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 
 #[derive(Debug, Clone)]
 pub enum CloudFrontResourceAddress {
     Distribution { distribution_id: String },
     OriginAccessControl { oac_id: String },
+    OriginAccessIdentity { oai_id: String },
     CachePolicy { policy_id: String },
     OriginRequestPolicy { policy_id: String },
     ResponseHeadersPolicy { policy_id: String },
@@ -16,6 +18,9 @@ pub enum CloudFrontResourceAddress {
     FieldLevelEncryptionConfig { config_id: String },
     FieldLevelEncryptionProfile { profile_id: String },
     StreamingDistribution { distribution_id: String },
+    DistributionTenant { tenant_id: String },
+    ConnectionGroup { connection_group_id: String },
+    ContinuousDeploymentPolicy { policy_id: String },
 }
 
 impl ResourceAddress for CloudFrontResourceAddress {
@@ -27,6 +32,9 @@ impl ResourceAddress for CloudFrontResourceAddress {
             CloudFrontResourceAddress::OriginAccessControl { oac_id } => {
                 PathBuf::from(format!("aws/cloudfront/origin_access_controls/{oac_id}.ron"))
             }
+            CloudFrontResourceAddress::OriginAccessIdentity { oai_id } => {
+                PathBuf::from(format!("aws/cloudfront/origin_access_identities/{oai_id}.ron"))
+            }
             CloudFrontResourceAddress::CachePolicy { policy_id } => {
                 PathBuf::from(format!("aws/cloudfront/cache_policies/{policy_id}.ron"))
             }
@@ -55,6 +63,15 @@ impl ResourceAddress for CloudFrontResourceAddress {
             CloudFrontResourceAddress::StreamingDistribution { distribution_id } => {
                 PathBuf::from(format!("aws/cloudfront/streaming_distributions/{distribution_id}.ron"))
             }
+            CloudFrontResourceAddress::DistributionTenant { tenant_id } => {
+                PathBuf::from(format!("aws/cloudfront/distribution_tenants/{tenant_id}.ron"))
+            }
+            CloudFrontResourceAddress::ConnectionGroup { connection_group_id } => {
+                PathBuf::from(format!("aws/cloudfront/connection_groups/{connection_group_id}.ron"))
+            }
+            CloudFrontResourceAddress::ContinuousDeploymentPolicy { policy_id } => {
+                PathBuf::from(format!("aws/cloudfront/continuous_deployment_policies/{policy_id}.ron"))
+            }
         }
     }
 
@@ -70,6 +87,10 @@ impl ResourceAddress for CloudFrontResourceAddress {
                 let oac_id = oac_id.strip_suffix(".ron").unwrap().to_string();
                 Ok(CloudFrontResourceAddress::OriginAccessControl { oac_id })
             }
+            ["aws", "cloudfront", "origin_access_identities", oai_id] if oai_id.ends_with(".ron") => {
+                let oai_id = oai_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(CloudFrontResourceAddress::OriginAccessIdentity { oai_id })
+            }
             ["aws", "cloudfront", "cache_policies", policy_id] if policy_id.ends_with(".ron") => {
                 let policy_id = policy_id.strip_suffix(".ron").unwrap().to_string();
                 Ok(CloudFrontResourceAddress::CachePolicy { policy_id })
@@ -110,7 +131,106 @@ impl ResourceAddress for CloudFrontResourceAddress {
                 let distribution_id = distribution_id.strip_suffix(".ron").unwrap().to_string();
                 Ok(CloudFrontResourceAddress::StreamingDistribution { distribution_id })
             }
+            ["aws", "cloudfront", "distribution_tenants", tenant_id] if tenant_id.ends_with(".ron") => {
+                let tenant_id = tenant_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(CloudFrontResourceAddress::DistributionTenant { tenant_id })
+            }
+            ["aws", "cloudfront", "connection_groups", connection_group_id] if connection_group_id.ends_with(".ron") => {
+                let connection_group_id = connection_group_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(CloudFrontResourceAddress::ConnectionGroup { connection_group_id })
+            }
+            ["aws", "cloudfront", "continuous_deployment_policies", policy_id] if policy_id.ends_with(".ron") => {
+                let policy_id = policy_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(CloudFrontResourceAddress::ContinuousDeploymentPolicy { policy_id })
+            }
             _ => Err(invalid_addr_path(path)),
         }
     }
 }
+
+impl DescribeAddresses for CloudFrontResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/cloudfront/distributions/<distribution_id>.ron",
+                description: "A CloudFront distribution",
+                example:     "aws/cloudfront/distributions/E2QWRUHEXAMPLE.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/origin_access_controls/<oac_id>.ron",
+                description: "An origin access control",
+                example:     "aws/cloudfront/origin_access_controls/E1LTSAVYEXAMPLE.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/origin_access_identities/<oai_id>.ron",
+                description: "A legacy origin access identity (OAI) for S3 origins",
+                example:     "aws/cloudfront/origin_access_identities/E74FTE3AEXAMPLE.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/cache_policies/<policy_id>.ron",
+                description: "A cache policy",
+                example:     "aws/cloudfront/cache_policies/658327ea-f89d-4fab-a63d-7e88639e58f6.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/origin_request_policies/<policy_id>.ron",
+                description: "An origin request policy",
+                example:     "aws/cloudfront/origin_request_policies/88a5eaf4-2fd4-4709-b370-b4c650ea3fcf.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/response_headers_policies/<policy_id>.ron",
+                description: "A response headers policy",
+                example:     "aws/cloudfront/response_headers_policies/67f7725c-6f97-4210-82d7-5512b31e9d03.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/realtime_log_configs/<name>.ron",
+                description: "A real-time log configuration",
+                example:     "aws/cloudfront/realtime_log_configs/access-logs.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/functions/<name>.ron",
+                description: "A CloudFront function",
+                example:     "aws/cloudfront/functions/rewrite-index.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/key_groups/<key_group_id>.ron",
+                description: "A key group for signed URLs and cookies",
+                example:     "aws/cloudfront/key_groups/K3D5EWEUEXAMPLE.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/public_keys/<public_key_id>.ron",
+                description: "A public key for signed URLs and cookies",
+                example:     "aws/cloudfront/public_keys/K2JCJMDEHXQW5F.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/field_level_encryption_configs/<config_id>.ron",
+                description: "A field-level encryption configuration",
+                example:     "aws/cloudfront/field_level_encryption_configs/C3KM2WVD605UAY.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/field_level_encryption_profiles/<profile_id>.ron",
+                description: "A field-level encryption profile",
+                example:     "aws/cloudfront/field_level_encryption_profiles/P3KM2WVD605UAY.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/streaming_distributions/<distribution_id>.ron",
+                description: "An RTMP streaming distribution",
+                example:     "aws/cloudfront/streaming_distributions/EGTXBD79EXAMPLE.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/distribution_tenants/<tenant_id>.ron",
+                description: "A tenant of a multi-tenant distribution",
+                example:     "aws/cloudfront/distribution_tenants/dt_2wjDZi3hD1ivOXf6rpZJOSNE1AB.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/connection_groups/<connection_group_id>.ron",
+                description: "A connection group that distribution tenants are served through",
+                example:     "aws/cloudfront/connection_groups/cg_2wjLpjbHkLUdhWAjHllcOeABCDE.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/continuous_deployment_policies/<policy_id>.ron",
+                description: "A continuous deployment policy that routes part of a distribution's traffic to a staging distribution",
+                example:     "aws/cloudfront/continuous_deployment_policies/f1cbc71e-3c1f-4dcc-b4d8-5ef4c6a1b2c3.ron",
+            },
+        ]
+    }
+}
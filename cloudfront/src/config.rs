@@ -7,6 +7,25 @@ use autoschematic_connector_aws_core::{
 use autoschematic_core::util::RON;
 use serde::{Deserialize, Serialize};
 
+/// Checks a distribution's CloudWatch metrics after it's been updated, and fails the op
+/// if they exceed the thresholds. The bake window starts once the update has deployed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PostDeployVerification {
+    /// How long to watch the distribution's metrics for.
+    pub bake_window_seconds: u64,
+    /// The highest acceptable 5xxErrorRate, in percent, averaged over the bake window.
+    #[serde(default)]
+    pub max_5xx_error_rate: Option<f64>,
+    /// The highest acceptable OriginLatency, in milliseconds, averaged over the bake window.
+    /// CloudFront only reports OriginLatency for distributions with additional metrics enabled.
+    #[serde(default)]
+    pub max_origin_latency_ms: Option<f64>,
+    /// Only verify these distribution IDs. If None, every distribution is verified.
+    #[serde(default)]
+    pub distribution_ids: Option<Vec<String>>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CloudFrontConnectorConfig {
     pub account_id:      Option<String>,
@@ -14,6 +33,10 @@ pub struct CloudFrontConnectorConfig {
     pub timeout_config:  Option<TimeoutConfig>,
     pub sts_region:      String,
     pub enabled_regions: Vec<String>,
+    #[serde(default)]
+    pub max_concurrent_ops: Option<usize>,
+    #[serde(default)]
+    pub post_deploy_verification: Option<PostDeployVerification>,
 }
 
-impl_aws_config!(CloudFrontConnectorConfig, "aws/cloudfront/config.ron");
+impl_aws_config!(CloudFrontConnectorConfig, "aws/cloudfront/config.ron", post_deploy_verification);
@@ -5,6 +5,10 @@ mod get;
 mod list;
 mod op_exec;
 mod plan;
+mod task_exec;
+mod verify;
+#[cfg(test)]
+mod test;
 
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::{
@@ -15,8 +19,9 @@ use std::{
 };
 
 use crate::config::CloudFrontConnectorConfig;
+use crate::task::{CloudFrontTask, CloudFrontTaskAddress, ImportDistribution, PromoteStagingDistribution};
 use async_trait::async_trait;
-use autoschematic_connector_aws_core::config::AwsServiceConfig;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
 use autoschematic_core::connector::{TaskExecResponse, VirtToPhyResponse};
 use autoschematic_core::util::{RON, ron_check_eq, ron_check_syntax};
 use autoschematic_core::{
@@ -34,10 +39,13 @@ use tokio::sync::Mutex;
 
 #[derive(Default)]
 pub struct CloudFrontConnector {
-    client:     Mutex<Option<Arc<aws_sdk_cloudfront::Client>>>,
+    client: Mutex<Option<Arc<aws_sdk_cloudfront::Client>>>,
+    cloudwatch_client: Mutex<Option<Arc<aws_sdk_cloudwatch::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
     account_id: Mutex<String>,
-    config:     Mutex<CloudFrontConnectorConfig>,
-    prefix:     PathBuf,
+    config: Mutex<CloudFrontConnectorConfig>,
+    prefix: PathBuf,
 }
 
 impl CloudFrontConnector {
@@ -62,13 +70,38 @@ impl CloudFrontConnector {
             )
             .load()
             .await;
-        let new_client = Arc::new(aws_sdk_cloudfront::Client::new(&config));
+        let new_client = Arc::new(audited_client!(aws_sdk_cloudfront, &config));
         *self.client.lock().await = Some(new_client.clone());
         Ok(new_client)
 
         // Ok(*self.client.clone())
     }
 
+    /// CloudFront publishes its metrics to CloudWatch in us-east-1.
+    pub async fn get_or_init_cloudwatch_client(&self) -> anyhow::Result<Arc<aws_sdk_cloudwatch::Client>> {
+        if let Some(client) = &*self.cloudwatch_client.lock().await {
+            return Ok(client.clone());
+        }
+
+        let region = RegionProviderChain::first_try(Region::new("us-east-1"));
+
+        let config = aws_config::defaults(BehaviorVersion::latest())
+            .region(region)
+            .timeout_config(
+                TimeoutConfig::builder()
+                    .connect_timeout(Duration::from_secs(30))
+                    .operation_timeout(Duration::from_secs(30))
+                    .operation_attempt_timeout(Duration::from_secs(30))
+                    .read_timeout(Duration::from_secs(30))
+                    .build(),
+            )
+            .load()
+            .await;
+        let new_client = Arc::new(audited_client!(aws_sdk_cloudwatch, &config));
+        *self.cloudwatch_client.lock().await = Some(new_client.clone());
+        Ok(new_client)
+    }
+
     pub async fn get_resource_arn(&self, addr: &CloudFrontResourceAddress) -> anyhow::Result<String> {
         match addr {
             CloudFrontResourceAddress::Distribution { distribution_id } => Ok(format!(
@@ -81,6 +114,11 @@ impl CloudFrontConnector {
                 self.account_id.lock().await,
                 oac_id
             )),
+            CloudFrontResourceAddress::OriginAccessIdentity { oai_id } => Ok(format!(
+                "arn:aws:cloudfront::{}:origin-access-identity/{}",
+                self.account_id.lock().await,
+                oai_id
+            )),
             CloudFrontResourceAddress::CachePolicy { policy_id } => Ok(format!(
                 "arn:aws:cloudfront::{}:policy/{}",
                 self.account_id.lock().await,
@@ -131,6 +169,21 @@ impl CloudFrontConnector {
                 self.account_id.lock().await,
                 distribution_id
             )),
+            CloudFrontResourceAddress::DistributionTenant { tenant_id } => Ok(format!(
+                "arn:aws:cloudfront::{}:distribution-tenant/{}",
+                self.account_id.lock().await,
+                tenant_id
+            )),
+            CloudFrontResourceAddress::ConnectionGroup { connection_group_id } => Ok(format!(
+                "arn:aws:cloudfront::{}:connection-group/{}",
+                self.account_id.lock().await,
+                connection_group_id
+            )),
+            CloudFrontResourceAddress::ContinuousDeploymentPolicy { policy_id } => Ok(format!(
+                "arn:aws:cloudfront::{}:continuous-deployment-policy/{}",
+                self.account_id.lock().await,
+                policy_id
+            )),
         }
     }
 
@@ -175,6 +228,8 @@ impl Connector for CloudFrontConnector {
         let account_id = config.verify_sts().await?;
 
         // *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
         *self.config.lock().await = config;
         *self.account_id.lock().await = account_id;
         // self.get_or_init_client();
@@ -187,6 +242,8 @@ impl Connector for CloudFrontConnector {
                 CloudFrontResourceAddress::Distribution { .. } => Ok(FilterResponse::Resource | FilterResponse::Task),
                 _ => Ok(FilterResponse::Resource),
             }
+        } else if let Ok(_addr) = CloudFrontTaskAddress::from_path(addr) {
+            Ok(FilterResponse::Task)
         } else {
             Ok(FilterResponse::None)
         }
@@ -200,6 +257,10 @@ impl Connector for CloudFrontConnector {
         arg: Option<Vec<u8>>,
         state: Option<Vec<u8>>,
     ) -> anyhow::Result<TaskExecResponse> {
+        if CloudFrontTaskAddress::from_path(addr).is_ok() {
+            return self.do_task_exec(addr, body, arg, state).await;
+        }
+
         let Ok(CloudFrontResourceAddress::Distribution { distribution_id }) = CloudFrontResourceAddress::from_path(addr) else {
             return Ok(TaskExecResponse::default());
         };
@@ -249,6 +310,10 @@ impl Connector for CloudFrontConnector {
                             return Ok(TaskExecResponse::default());
                         };
 
+                        let friendly_message = format!(
+                            "Created invalidation `{}` for distribution `{}` ({})",
+                            invalidation.id, distribution_id, invalidation.status
+                        );
                         let next_state = TaskState::Invalidating {
                             invalidation_id: invalidation.id,
                             status: invalidation.status,
@@ -257,7 +322,7 @@ impl Connector for CloudFrontConnector {
                         let now_secs = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
                         return Ok(TaskExecResponse {
                             next_state: Some(RON.to_string(&next_state)?.into_bytes()),
-                            friendly_message: Some(String::from("Created invalidation for distribution ID {}")),
+                            friendly_message: Some(friendly_message),
                             delay_until: Some(now_secs + 10),
                             ..Default::default()
                         });
@@ -283,6 +348,16 @@ impl Connector for CloudFrontConnector {
 
                         let now_secs = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
 
+                        let friendly_message = match invalidation.status.as_str() {
+                            "Completed" => format!(
+                                "Invalidation `{}` for distribution `{}` completed",
+                                invalidation.id, distribution_id
+                            ),
+                            _ => format!(
+                                "Waiting for invalidation `{}` to complete for distribution `{}` ({})",
+                                invalidation.id, distribution_id, invalidation.status
+                            ),
+                        };
                         let next_state = match invalidation.status.as_str() {
                             "Completed" => None,
                             _ => Some(
@@ -297,7 +372,7 @@ impl Connector for CloudFrontConnector {
                         return Ok(TaskExecResponse {
                             next_state,
                             delay_until: Some(now_secs + 10),
-                            friendly_message: Some(String::from("Waiting for invalidation to complete for distribution ID {}")),
+                            friendly_message: Some(friendly_message),
                             ..Default::default()
                         });
                     }
@@ -341,7 +416,9 @@ impl Connector for CloudFrontConnector {
     }
 
     async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
-        self.do_op_exec(addr, op).await
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
     }
 
     // async fn addr_virt_to_phy(&self, addr: &Path) -> anyhow::Result<Option<PathBuf>> {
@@ -408,12 +485,40 @@ impl Connector for CloudFrontConnector {
         match &addr {
             CloudFrontResourceAddress::Distribution { distribution_id } => {
                 let Some(distribution_id) = addr.get_output(&self.prefix, "distribution_id")? else {
+                    // Files written by the import-distribution task are named after the real distribution ID
+                    // and have no recorded outputs.
+                    if is_distribution_id(distribution_id) {
+                        return Ok(VirtToPhyResponse::Present(addr_buf));
+                    }
                     return Ok(VirtToPhyResponse::NotPresent);
                 };
                 Ok(VirtToPhyResponse::Present(
                     CloudFrontResourceAddress::Distribution { distribution_id }.to_path_buf(),
                 ))
             }
+            CloudFrontResourceAddress::DistributionTenant { tenant_id } => {
+                let Some(tenant_id) = addr.get_output(&self.prefix, "distribution_tenant_id")? else {
+                    // Tenants imported by `list` are already named after their ID
+                    if tenant_id.starts_with("dt_") {
+                        return Ok(VirtToPhyResponse::Present(addr_buf));
+                    }
+                    return Ok(VirtToPhyResponse::NotPresent);
+                };
+                Ok(VirtToPhyResponse::Present(
+                    CloudFrontResourceAddress::DistributionTenant { tenant_id }.to_path_buf(),
+                ))
+            }
+            CloudFrontResourceAddress::ConnectionGroup { connection_group_id } => {
+                let Some(connection_group_id) = addr.get_output(&self.prefix, "connection_group_id")? else {
+                    if connection_group_id.starts_with("cg_") {
+                        return Ok(VirtToPhyResponse::Present(addr_buf));
+                    }
+                    return Ok(VirtToPhyResponse::NotPresent);
+                };
+                Ok(VirtToPhyResponse::Present(
+                    CloudFrontResourceAddress::ConnectionGroup { connection_group_id }.to_path_buf(),
+                ))
+            }
             _ => Ok(VirtToPhyResponse::Null(addr_buf)),
         }
         // virt_to_phy!(
@@ -474,14 +579,96 @@ impl Connector for CloudFrontConnector {
                         max_ttl:     Some(31536000),
                         min_ttl:     None,
                     },
+                    function_associations: vec![],
+                    lambda_function_associations: vec![],
                 },
                 cache_behaviors: vec![],
                 comment: Some(String::from("[comment]")),
                 price_class: Some(String::from("PriceClass_All")),
+                viewer_certificate: Some(resource::ViewerCertificate {
+                    acm_certificate_arn: Some(String::from("[acm_certificate_arn]")),
+                    iam_certificate_id: None,
+                    ssl_support_method: Some(String::from("sni-only")),
+                    minimum_protocol_version: Some(String::from("TLSv1.2_2021")),
+                }),
+                anycast_ip_list_id: None,
+                restrictions: None,
+                connection_mode: None,
+                tenant_config: None,
+                http_version: Some(String::from("http2and3")),
+                is_ipv6_enabled: true,
+                web_acl_id: None,
+                logging: Some(resource::LoggingConfig {
+                    bucket: String::from("[log_bucket].s3.amazonaws.com"),
+                    prefix: Some(String::from("[prefix]/")),
+                    include_cookies: false,
+                }),
+                custom_error_responses: vec![resource::CustomErrorResponse {
+                    error_code: 404,
+                    response_page_path: Some(String::from("/404.html")),
+                    response_code: Some(String::from("404")),
+                    error_caching_min_ttl: Some(10),
+                }],
+                staging: false,
+                continuous_deployment_policy_id: None,
                 tags: std::collections::HashMap::new(),
             })
         ));
 
+        // Distribution tenant of a multi-tenant distribution
+        let tenant_id = String::from("[tenant_name]");
+        res.push(skeleton!(
+            CloudFrontResourceAddress::DistributionTenant { tenant_id },
+            CloudFrontResource::DistributionTenant(resource::DistributionTenant {
+                name: String::from("[tenant_name]"),
+                distribution_id: String::from("[multi_tenant_distribution_id]"),
+                domains: vec![String::from("[tenant_domain]")],
+                enabled: true,
+                parameters: std::collections::HashMap::from([(String::from("[parameter_name]"), String::from("[value]"))]),
+                customizations: Some(resource::TenantCustomizations {
+                    web_acl: None,
+                    certificate_arn: Some(String::from("[acm_certificate_arn]")),
+                    geo_restrictions: None,
+                }),
+                connection_group_id: None,
+                tags: std::collections::HashMap::new(),
+            })
+        ));
+
+        // Connection group
+        let connection_group_id = String::from("[connection_group_name]");
+        res.push(skeleton!(
+            CloudFrontResourceAddress::ConnectionGroup { connection_group_id },
+            CloudFrontResource::ConnectionGroup(resource::ConnectionGroup {
+                name: String::from("[connection_group_name]"),
+                ipv6_enabled: true,
+                anycast_ip_list_id: None,
+                enabled: true,
+                tags: std::collections::HashMap::new(),
+            })
+        ));
+
+        // Continuous deployment policy, routing 5% of the primary distribution's traffic to its staging distribution
+        let policy_id = String::from("[continuous_deployment_policy_id]");
+        res.push(skeleton!(
+            CloudFrontResourceAddress::ContinuousDeploymentPolicy { policy_id },
+            CloudFrontResource::ContinuousDeploymentPolicy(resource::ContinuousDeploymentPolicy {
+                staging_distribution_dns_names: vec![String::from("[staging_distribution_domain_name]")],
+                enabled: true,
+                traffic_config: Some(resource::TrafficConfig {
+                    config_type: String::from("SingleWeight"),
+                    single_weight_config: Some(resource::SingleWeightConfig {
+                        weight: 0.05,
+                        session_stickiness_config: Some(resource::SessionStickinessConfig {
+                            idle_ttl: 300,
+                            maximum_ttl: 600,
+                        }),
+                    }),
+                    single_header_config: None,
+                }),
+            })
+        ));
+
         // Origin Access Control
         let oac_id = String::from("[oac_id]");
         res.push(skeleton!(
@@ -495,6 +682,15 @@ impl Connector for CloudFrontConnector {
             })
         ));
 
+        // Origin Access Identity (legacy)
+        let oai_id = String::from("[oai_id]");
+        res.push(skeleton!(
+            CloudFrontResourceAddress::OriginAccessIdentity { oai_id },
+            CloudFrontResource::OriginAccessIdentity(resource::OriginAccessIdentity {
+                comment: String::from("[comment]"),
+            })
+        ));
+
         // Cache Policy
         let policy_id = String::from("[cache_policy_id]");
         res.push(skeleton!(
@@ -505,7 +701,99 @@ impl Connector for CloudFrontConnector {
                 default_ttl: Some(86400),
                 max_ttl: Some(31536000),
                 min_ttl: None,
-                parameters_in_cache_key_and_forwarded_to_origin: None,
+                parameters_in_cache_key_and_forwarded_to_origin: Some(resource::CacheKeyParameters {
+                    enable_accept_encoding_gzip: true,
+                    enable_accept_encoding_brotli: true,
+                    headers_config: resource::PolicyItems {
+                        behavior: String::from("none"),
+                        items: vec![],
+                    },
+                    cookies_config: resource::PolicyItems {
+                        behavior: String::from("none"),
+                        items: vec![],
+                    },
+                    query_strings_config: resource::PolicyItems {
+                        behavior: String::from("whitelist"),
+                        items: vec![String::from("[query_string]")],
+                    },
+                }),
+            })
+        ));
+
+        // Origin Request Policy
+        let policy_id = String::from("[origin_request_policy_id]");
+        res.push(skeleton!(
+            CloudFrontResourceAddress::OriginRequestPolicy { policy_id },
+            CloudFrontResource::OriginRequestPolicy(resource::OriginRequestPolicy {
+                name: String::from("[origin_request_policy_name]"),
+                comment: Some(String::from("[comment]")),
+                cookies_config: None,
+                headers_config: Some(resource::PolicyItems {
+                    behavior: String::from("whitelist"),
+                    items: vec![String::from("Origin")],
+                }),
+                query_strings_config: Some(resource::PolicyItems {
+                    behavior: String::from("all"),
+                    items: vec![],
+                }),
+            })
+        ));
+
+        // Response Headers Policy
+        let policy_id = String::from("[response_headers_policy_id]");
+        res.push(skeleton!(
+            CloudFrontResourceAddress::ResponseHeadersPolicy { policy_id },
+            CloudFrontResource::ResponseHeadersPolicy(resource::ResponseHeadersPolicy {
+                name: String::from("[response_headers_policy_name]"),
+                comment: Some(String::from("[comment]")),
+                cors_config: None,
+                custom_headers_config: vec![resource::CustomHeader {
+                    header: String::from("[header_name]"),
+                    value: String::from("[value]"),
+                    r#override: true,
+                }],
+                security_headers_config: Some(resource::SecurityHeadersConfig {
+                    content_security_policy: None,
+                    content_type_options: Some(resource::ContentTypeOptions { r#override: true }),
+                    frame_options: Some(resource::FrameOptions {
+                        r#override: true,
+                        frame_option: String::from("DENY"),
+                    }),
+                    referrer_policy: Some(resource::ReferrerPolicy {
+                        r#override: true,
+                        referrer_policy: String::from("strict-origin-when-cross-origin"),
+                    }),
+                    strict_transport_security: Some(resource::StrictTransportSecurity {
+                        r#override: true,
+                        access_control_max_age_sec: 31536000,
+                        include_subdomains: true,
+                        preload: false,
+                    }),
+                    xss_protection: None,
+                }),
+            })
+        ));
+
+        // Realtime Log Config
+        let name = String::from("[realtime_log_config_name]");
+        res.push(skeleton!(
+            CloudFrontResourceAddress::RealtimeLogConfig { name },
+            CloudFrontResource::RealtimeLogConfig(resource::RealtimeLogConfig {
+                name: String::from("[realtime_log_config_name]"),
+                end_points: vec![resource::EndPoint {
+                    stream_type: String::from("Kinesis"),
+                    kinesis_stream_config: Some(resource::KinesisStreamConfig {
+                        role_arn: String::from("[role_arn]"),
+                        stream_arn: String::from("[kinesis_stream_arn]"),
+                    }),
+                }],
+                fields: vec![
+                    String::from("timestamp"),
+                    String::from("c-ip"),
+                    String::from("sc-status"),
+                    String::from("cs-uri-stem"),
+                ],
+                sampling_rate: 100.0,
             })
         ));
 
@@ -516,10 +804,33 @@ impl Connector for CloudFrontConnector {
             CloudFrontResource::Function(resource::Function {
                 name: String::from("[function_name]"),
                 function_code: String::from("function handler(event) { return event.request; }"),
+                code_file: None,
                 runtime: String::from("cloudfront-js-1.0"),
             })
         ));
 
+        // Distribution import task
+        res.push(skeleton!(
+            CloudFrontTaskAddress::ImportDistribution {
+                name: String::from("[task_name]"),
+            },
+            CloudFrontTask::ImportDistribution(ImportDistribution {
+                distribution_id: String::from("[distribution_id]"),
+                overwrite: false,
+            })
+        ));
+
+        // Staging distribution promotion task
+        res.push(skeleton!(
+            CloudFrontTaskAddress::PromoteStagingDistribution {
+                name: String::from("[task_name]"),
+            },
+            CloudFrontTask::PromoteStagingDistribution(PromoteStagingDistribution {
+                distribution_id: String::from("[distribution_id]"),
+                staging_distribution_id: String::from("[staging_distribution_id]"),
+            })
+        ));
+
         // Key Group
         let key_group_id = String::from("[key_group_id]");
         res.push(skeleton!(
@@ -542,6 +853,41 @@ impl Connector for CloudFrontConnector {
             })
         ));
 
+        // Field-Level Encryption Profile
+        let profile_id = String::from("[field_level_encryption_profile_id]");
+        res.push(skeleton!(
+            CloudFrontResourceAddress::FieldLevelEncryptionProfile { profile_id },
+            CloudFrontResource::FieldLevelEncryptionProfile(resource::FieldLevelEncryptionProfile {
+                name: String::from("[profile_name]"),
+                comment: Some(String::from("[comment]")),
+                caller_reference: String::from("[caller_reference]"),
+                encryption_entities: vec![resource::EncryptionEntity {
+                    public_key_id: String::from("[public_key_id]"),
+                    provider_id: String::from("[provider_id]"),
+                    field_patterns: vec![String::from("[field_name]")],
+                }],
+            })
+        ));
+
+        // Field-Level Encryption Config
+        let config_id = String::from("[field_level_encryption_config_id]");
+        res.push(skeleton!(
+            CloudFrontResourceAddress::FieldLevelEncryptionConfig { config_id },
+            CloudFrontResource::FieldLevelEncryptionConfig(resource::FieldLevelEncryptionConfig {
+                comment: Some(String::from("[comment]")),
+                caller_reference: String::from("[caller_reference]"),
+                content_type_profile_config: Some(resource::ContentTypeProfileConfig {
+                    forward_when_content_type_is_unknown: true,
+                    content_type_profiles: vec![resource::ContentTypeProfile {
+                        format: String::from("URLEncoded"),
+                        profile_id: Some(String::from("[field_level_encryption_profile_id]")),
+                        content_type: String::from("application/x-www-form-urlencoded"),
+                    }],
+                }),
+                query_arg_profile_config: None,
+            })
+        ));
+
         // Streaming Distribution
         let distribution_id = String::from("[streaming_distribution_id]");
         res.push(skeleton!(
@@ -569,6 +915,7 @@ impl Connector for CloudFrontConnector {
         match addr {
             CloudFrontResourceAddress::Distribution { .. } => ron_check_eq::<resource::Distribution>(a, b),
             CloudFrontResourceAddress::OriginAccessControl { .. } => ron_check_eq::<resource::OriginAccessControl>(a, b),
+            CloudFrontResourceAddress::OriginAccessIdentity { .. } => ron_check_eq::<resource::OriginAccessIdentity>(a, b),
             CloudFrontResourceAddress::CachePolicy { .. } => ron_check_eq::<resource::CachePolicy>(a, b),
             CloudFrontResourceAddress::OriginRequestPolicy { .. } => ron_check_eq::<resource::OriginRequestPolicy>(a, b),
             CloudFrontResourceAddress::ResponseHeadersPolicy { .. } => ron_check_eq::<resource::ResponseHeadersPolicy>(a, b),
@@ -583,6 +930,11 @@ impl Connector for CloudFrontConnector {
                 ron_check_eq::<resource::FieldLevelEncryptionProfile>(a, b)
             }
             CloudFrontResourceAddress::StreamingDistribution { .. } => ron_check_eq::<resource::StreamingDistribution>(a, b),
+            CloudFrontResourceAddress::DistributionTenant { .. } => ron_check_eq::<resource::DistributionTenant>(a, b),
+            CloudFrontResourceAddress::ConnectionGroup { .. } => ron_check_eq::<resource::ConnectionGroup>(a, b),
+            CloudFrontResourceAddress::ContinuousDeploymentPolicy { .. } => {
+                ron_check_eq::<resource::ContinuousDeploymentPolicy>(a, b)
+            }
         }
     }
 
@@ -591,6 +943,7 @@ impl Connector for CloudFrontConnector {
         match addr {
             CloudFrontResourceAddress::Distribution { .. } => ron_check_syntax::<resource::Distribution>(a),
             CloudFrontResourceAddress::OriginAccessControl { .. } => ron_check_syntax::<resource::OriginAccessControl>(a),
+            CloudFrontResourceAddress::OriginAccessIdentity { .. } => ron_check_syntax::<resource::OriginAccessIdentity>(a),
             CloudFrontResourceAddress::CachePolicy { .. } => ron_check_syntax::<resource::CachePolicy>(a),
             CloudFrontResourceAddress::OriginRequestPolicy { .. } => ron_check_syntax::<resource::OriginRequestPolicy>(a),
             CloudFrontResourceAddress::ResponseHeadersPolicy { .. } => ron_check_syntax::<resource::ResponseHeadersPolicy>(a),
@@ -605,6 +958,16 @@ impl Connector for CloudFrontConnector {
                 ron_check_syntax::<resource::FieldLevelEncryptionProfile>(a)
             }
             CloudFrontResourceAddress::StreamingDistribution { .. } => ron_check_syntax::<resource::StreamingDistribution>(a),
+            CloudFrontResourceAddress::DistributionTenant { .. } => ron_check_syntax::<resource::DistributionTenant>(a),
+            CloudFrontResourceAddress::ConnectionGroup { .. } => ron_check_syntax::<resource::ConnectionGroup>(a),
+            CloudFrontResourceAddress::ContinuousDeploymentPolicy { .. } => {
+                ron_check_syntax::<resource::ContinuousDeploymentPolicy>(a)
+            }
         }
     }
 }
+
+/// Whether `id` has the form of a distribution ID assigned by CloudFront, e.g. `E2QWRUHAPOMQZL`.
+fn is_distribution_id(id: &str) -> bool {
+    id.len() >= 13 && id.starts_with('E') && id.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
@@ -1,11 +1,21 @@
-use std::{collections::HashMap, path::Path};
+use std::path::Path;
 
 use anyhow::{Context, bail};
 use autoschematic_core::connector::{GetResourceResponse, Resource, ResourceAddress};
 use autoschematic_core::get_resource_response;
 use aws_sdk_cloudfront::operation::get_key_group::GetKeyGroupError;
 
-use crate::{addr::CloudFrontResourceAddress, resource::*};
+use crate::{
+    addr::CloudFrontResourceAddress,
+    resource::*,
+    util::{
+        from_cache_key_parameters, from_continuous_deployment_policy_config, from_custom_error_responses, from_customizations,
+        from_end_points,
+        from_field_level_encryption_config, from_field_level_encryption_profile_config, from_function_associations,
+        from_lambda_function_associations, from_logging, from_origin_request_policy_config, from_response_headers_policy_config,
+        from_restrictions, from_tenant_config, resolve_function_code,
+    },
+};
 
 use super::CloudFrontConnector;
 
@@ -74,6 +84,10 @@ impl CloudFrontConnector {
                                     max_ttl:     dcb.max_ttl,
                                     min_ttl:     dcb.min_ttl,
                                 },
+                                function_associations: from_function_associations(dcb.function_associations.as_ref()),
+                                lambda_function_associations: from_lambda_function_associations(
+                                    dcb.lambda_function_associations.as_ref(),
+                                ),
                             })
                             .unwrap_or_else(|| CacheBehavior {
                                 id: String::new(),
@@ -88,6 +102,8 @@ impl CloudFrontConnector {
                                     max_ttl:     None,
                                     min_ttl:     None,
                                 },
+                                function_associations: vec![],
+                                lambda_function_associations: vec![],
                             });
 
                         let cache_behaviors = config
@@ -117,6 +133,12 @@ impl CloudFrontConnector {
                                             max_ttl:     behavior.max_ttl,
                                             min_ttl:     behavior.min_ttl
                                         },
+                                        function_associations: from_function_associations(
+                                            behavior.function_associations.as_ref(),
+                                        ),
+                                        lambda_function_associations: from_lambda_function_associations(
+                                            behavior.lambda_function_associations.as_ref(),
+                                        ),
                                     })
                                     .collect()
                             })
@@ -133,6 +155,39 @@ impl CloudFrontConnector {
                             cache_behaviors,
                             comment: Some(config.comment),
                             price_class: config.price_class.map(|pc| pc.as_str().to_string()),
+                            viewer_certificate: config.viewer_certificate.and_then(|vc| {
+                                // The default certificate is represented by the absence of a viewer_certificate
+                                if vc.cloud_front_default_certificate == Some(true) {
+                                    return None;
+                                }
+                                Some(ViewerCertificate {
+                                    acm_certificate_arn: vc.acm_certificate_arn,
+                                    iam_certificate_id: vc.iam_certificate_id,
+                                    ssl_support_method: vc.ssl_support_method.map(|m| m.as_str().to_string()),
+                                    minimum_protocol_version: vc.minimum_protocol_version.map(|v| v.as_str().to_string()),
+                                })
+                            }),
+                            anycast_ip_list_id: config.anycast_ip_list_id,
+                            restrictions: from_restrictions(config.restrictions.as_ref()),
+                            // Standard distributions are represented by the absence of a connection mode
+                            connection_mode: config
+                                .connection_mode
+                                .map(|mode| mode.as_str().to_string())
+                                .filter(|mode| mode != "direct"),
+                            tenant_config: from_tenant_config(config.tenant_config.as_ref()),
+                            // HTTP/2 is the default, so it's represented by the absence of an http_version
+                            http_version: config
+                                .http_version
+                                .map(|version| version.as_str().to_string())
+                                .filter(|version| version != "http2"),
+                            is_ipv6_enabled: config.is_ipv6_enabled.unwrap_or(false),
+                            web_acl_id: config.web_acl_id.filter(|id| !id.is_empty()),
+                            logging: from_logging(config.logging.as_ref()),
+                            custom_error_responses: from_custom_error_responses(config.custom_error_responses.as_ref()),
+                            staging: config.staging.unwrap_or(false),
+                            continuous_deployment_policy_id: config
+                                .continuous_deployment_policy_id
+                                .filter(|policy_id| !policy_id.is_empty()),
                             tags,
                         };
 
@@ -189,6 +244,40 @@ impl CloudFrontConnector {
                 }
             }
 
+            CloudFrontResourceAddress::OriginAccessIdentity { oai_id } => {
+                let result = client.get_cloud_front_origin_access_identity().id(oai_id).send().await;
+
+                match result {
+                    Ok(output) => {
+                        let Some(oai) = output.cloud_front_origin_access_identity else {
+                            return Ok(None);
+                        };
+
+                        let Some(config) = oai.cloud_front_origin_access_identity_config else {
+                            return Ok(None);
+                        };
+
+                        let origin_access_identity = OriginAccessIdentity { comment: config.comment };
+
+                        get_resource_response!(
+                            CloudFrontResource::OriginAccessIdentity(origin_access_identity),
+                            [
+                                (String::from("oai_id"), oai_id.into()),
+                                (String::from("s3_canonical_user_id"), oai.s3_canonical_user_id)
+                            ]
+                        )
+                    }
+                    Err(e) => {
+                        if let Some(service_error) = e.as_service_error() {
+                            if service_error.is_no_such_cloud_front_origin_access_identity() {
+                                return Ok(None);
+                            }
+                        }
+                        Err(e.into())
+                    }
+                }
+            }
+
             CloudFrontResourceAddress::CachePolicy { policy_id } => {
                 let result = client.get_cache_policy().id(policy_id).send().await;
 
@@ -208,7 +297,9 @@ impl CloudFrontConnector {
                             default_ttl: config.default_ttl,
                             max_ttl: config.max_ttl,
                             min_ttl: Some(config.min_ttl),
-                            parameters_in_cache_key_and_forwarded_to_origin: None, // Simplified for now
+                            parameters_in_cache_key_and_forwarded_to_origin: from_cache_key_parameters(
+                                config.parameters_in_cache_key_and_forwarded_to_origin.as_ref(),
+                            ),
                         };
 
                         get_resource_response!(
@@ -235,10 +326,23 @@ impl CloudFrontConnector {
                         let function_code =
                             String::from_utf8(output.function_code.unwrap_or_default().into_inner()).unwrap_or_default();
 
+                        let runtime = client
+                            .describe_function()
+                            .name(name)
+                            .send()
+                            .await?
+                            .function_summary
+                            .and_then(|summary| summary.function_config)
+                            .map(|config| config.runtime.as_str().to_string())
+                            .unwrap_or_else(|| String::from("cloudfront-js-1.0"));
+
+                        let (function_code, code_file) = resolve_function_code(&self.prefix, &addr, function_code)?;
+
                         let function = Function {
                             name: name.clone(),
                             function_code,
-                            runtime: "cloudfront-js-1.0".to_string(), // Default runtime
+                            code_file,
+                            runtime,
                         };
 
                         get_resource_response!(
@@ -270,13 +374,7 @@ impl CloudFrontConnector {
                             return Ok(None);
                         };
 
-                        let origin_request_policy = OriginRequestPolicy {
-                            name: config.name,
-                            comment: config.comment,
-                            cookies_config: None,       // Simplified for now
-                            headers_config: None,       // Simplified for now
-                            query_strings_config: None, // Simplified for now
-                        };
+                        let origin_request_policy = from_origin_request_policy_config(config);
 
                         get_resource_response!(
                             CloudFrontResource::OriginRequestPolicy(origin_request_policy),
@@ -307,13 +405,7 @@ impl CloudFrontConnector {
                             return Ok(None);
                         };
 
-                        let response_headers_policy = ResponseHeadersPolicy {
-                            name: config.name,
-                            comment: config.comment,
-                            cors_config: None,             // Simplified for now
-                            custom_headers_config: None,   // Simplified for now
-                            security_headers_config: None, // Simplified for now
-                        };
+                        let response_headers_policy = from_response_headers_policy_config(config);
 
                         get_resource_response!(
                             CloudFrontResource::ResponseHeadersPolicy(response_headers_policy),
@@ -342,7 +434,7 @@ impl CloudFrontConnector {
 
                         let realtime_log_config = RealtimeLogConfig {
                             name: config.name,
-                            end_points: vec![], // Simplified for now
+                            end_points: from_end_points(&config.end_points),
                             fields: config.fields,
                             sampling_rate: config.sampling_rate as f64,
                         };
@@ -440,12 +532,7 @@ impl CloudFrontConnector {
                             return Ok(None);
                         };
 
-                        let field_level_encryption_config = FieldLevelEncryptionConfig {
-                            comment: config.comment,
-                            caller_reference: config.caller_reference,
-                            content_type_profile_config: None, // Simplified for now
-                            query_arg_profile_config: None,    // Simplified for now
-                        };
+                        let field_level_encryption_config = from_field_level_encryption_config(config);
 
                         get_resource_response!(
                             CloudFrontResource::FieldLevelEncryptionConfig(field_level_encryption_config),
@@ -476,12 +563,7 @@ impl CloudFrontConnector {
                             return Ok(None);
                         };
 
-                        let field_level_encryption_profile = FieldLevelEncryptionProfile {
-                            name: config.name,
-                            comment: config.comment,
-                            caller_reference: config.caller_reference,
-                            encryption_entities: HashMap::new(), // Simplified for now
-                        };
+                        let field_level_encryption_profile = from_field_level_encryption_profile_config(config);
 
                         get_resource_response!(
                             CloudFrontResource::FieldLevelEncryptionProfile(field_level_encryption_profile),
@@ -548,6 +630,113 @@ impl CloudFrontConnector {
                     }
                 }
             }
+            CloudFrontResourceAddress::DistributionTenant { tenant_id } => {
+                let result = client.get_distribution_tenant().identifier(tenant_id).send().await;
+
+                match result {
+                    Ok(output) => {
+                        let Some(tenant) = output.distribution_tenant else {
+                            return Ok(None);
+                        };
+
+                        let tags = self.get_tags_for_resource(&addr, client).await?;
+
+                        let distribution_tenant = DistributionTenant {
+                            name: tenant.name.unwrap_or_default(),
+                            distribution_id: tenant.distribution_id.unwrap_or_default(),
+                            domains: tenant.domains.unwrap_or_default().into_iter().filter_map(|d| d.domain).collect(),
+                            enabled: tenant.enabled.unwrap_or(false),
+                            parameters: tenant
+                                .parameters
+                                .unwrap_or_default()
+                                .into_iter()
+                                .map(|parameter| (parameter.name, parameter.value))
+                                .collect(),
+                            customizations: from_customizations(tenant.customizations.as_ref()),
+                            connection_group_id: tenant.connection_group_id,
+                            tags,
+                        };
+
+                        get_resource_response!(
+                            CloudFrontResource::DistributionTenant(distribution_tenant),
+                            [(String::from("distribution_tenant_id"), tenant_id.into())]
+                        )
+                    }
+                    Err(e) => {
+                        if let Some(service_error) = e.as_service_error() {
+                            if service_error.is_entity_not_found() {
+                                return Ok(None);
+                            }
+                        }
+                        Err(e.into())
+                    }
+                }
+            }
+
+            CloudFrontResourceAddress::ConnectionGroup { connection_group_id } => {
+                let result = client.get_connection_group().identifier(connection_group_id).send().await;
+
+                match result {
+                    Ok(output) => {
+                        let Some(group) = output.connection_group else {
+                            return Ok(None);
+                        };
+
+                        let tags = self.get_tags_for_resource(&addr, client).await?;
+
+                        let connection_group = ConnectionGroup {
+                            name: group.name.unwrap_or_default(),
+                            ipv6_enabled: group.ipv6_enabled.unwrap_or(false),
+                            anycast_ip_list_id: group.anycast_ip_list_id,
+                            enabled: group.enabled.unwrap_or(false),
+                            tags,
+                        };
+
+                        get_resource_response!(
+                            CloudFrontResource::ConnectionGroup(connection_group),
+                            [
+                                (String::from("connection_group_id"), connection_group_id.into()),
+                                (String::from("routing_endpoint"), group.routing_endpoint.unwrap_or_default())
+                            ]
+                        )
+                    }
+                    Err(e) => {
+                        if let Some(service_error) = e.as_service_error() {
+                            if service_error.is_entity_not_found() {
+                                return Ok(None);
+                            }
+                        }
+                        Err(e.into())
+                    }
+                }
+            }
+
+            CloudFrontResourceAddress::ContinuousDeploymentPolicy { policy_id } => {
+                let result = client.get_continuous_deployment_policy_config().id(policy_id).send().await;
+
+                match result {
+                    Ok(output) => {
+                        let Some(config) = output.continuous_deployment_policy_config else {
+                            return Ok(None);
+                        };
+
+                        let policy = from_continuous_deployment_policy_config(&config);
+
+                        get_resource_response!(
+                            CloudFrontResource::ContinuousDeploymentPolicy(policy),
+                            [(String::from("policy_id"), policy_id.into())]
+                        )
+                    }
+                    Err(e) => {
+                        if let Some(service_error) = e.as_service_error() {
+                            if service_error.is_entity_not_found() {
+                                return Ok(None);
+                            }
+                        }
+                        Err(e.into())
+                    }
+                }
+            }
         }
     }
 }
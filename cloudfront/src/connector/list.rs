@@ -57,6 +57,26 @@ impl CloudFrontConnector {
             }
         }
 
+        // List Origin Access Identities
+        let mut next_marker: Option<String> = None;
+        loop {
+            let oais = client.list_cloud_front_origin_access_identities().set_marker(next_marker).send().await?;
+            let Some(oai_list) = oais.cloud_front_origin_access_identity_list() else {
+                break;
+            };
+
+            if let Some(items) = &oai_list.items {
+                for oai in items {
+                    results.push(CloudFrontResourceAddress::OriginAccessIdentity { oai_id: oai.id.clone() }.to_path_buf());
+                }
+            }
+
+            next_marker = oai_list.next_marker.clone();
+            if next_marker.is_none() {
+                break;
+            }
+        }
+
         // List Cache Policies
         let mut next_marker: Option<String> = None;
         loop {
@@ -323,6 +343,77 @@ impl CloudFrontConnector {
             }
         }
 
+        // List Distribution Tenants
+        let mut next_marker: Option<String> = None;
+        loop {
+            let tenants = client.list_distribution_tenants().set_marker(next_marker).send().await?;
+
+            for tenant in tenants.distribution_tenant_list() {
+                results.push(
+                    CloudFrontResourceAddress::DistributionTenant {
+                        tenant_id: tenant.id.clone(),
+                    }
+                    .to_path_buf(),
+                );
+            }
+
+            next_marker = tenants.next_marker.clone();
+            if next_marker.is_none() {
+                break;
+            }
+        }
+
+        // List Connection Groups
+        let mut next_marker: Option<String> = None;
+        loop {
+            let groups = client.list_connection_groups().set_marker(next_marker).send().await?;
+
+            for group in groups.connection_groups() {
+                // The default connection group is created by CloudFront and can't be deleted
+                if group.is_default == Some(true) {
+                    continue;
+                }
+                results.push(
+                    CloudFrontResourceAddress::ConnectionGroup {
+                        connection_group_id: group.id.clone(),
+                    }
+                    .to_path_buf(),
+                );
+            }
+
+            next_marker = groups.next_marker.clone();
+            if next_marker.is_none() {
+                break;
+            }
+        }
+
+        // List Continuous Deployment Policies
+        let mut next_marker: Option<String> = None;
+        loop {
+            let policies = client.list_continuous_deployment_policies().set_marker(next_marker).send().await?;
+            let Some(policy_list) = policies.continuous_deployment_policy_list() else {
+                break;
+            };
+
+            if let Some(items) = &policy_list.items {
+                for policy in items {
+                    if let Some(continuous_deployment_policy) = &policy.continuous_deployment_policy {
+                        results.push(
+                            CloudFrontResourceAddress::ContinuousDeploymentPolicy {
+                                policy_id: continuous_deployment_policy.id.clone(),
+                            }
+                            .to_path_buf(),
+                        );
+                    }
+                }
+            }
+
+            next_marker = policy_list.next_marker.clone();
+            if next_marker.is_none() {
+                break;
+            }
+        }
+
         Ok(results)
     }
 }
@@ -7,12 +7,29 @@ use autoschematic_core::{
     op_exec_output,
 };
 use aws_sdk_cloudfront::types::{
-    Aliases, ParametersInCacheKeyAndForwardedToOrigin, PriceClass, Tag, TagKeys, Tags, builders::AliasesBuilder,
+    Aliases, ConnectionMode, DomainItem, HttpVersion, Parameter, PriceClass, Tag, TagKeys, Tags, builders::AliasesBuilder,
 };
 
-use crate::{addr::CloudFrontResourceAddress, op::CloudFrontConnectorOp, tags::tag_diff, util::get_distribution_config};
+use crate::{
+    addr::CloudFrontResourceAddress,
+    op::CloudFrontConnectorOp,
+    resource::{
+        DistributionTenant, FieldLevelEncryptionConfig, FieldLevelEncryptionProfile, OriginRequestPolicy, ResponseHeadersPolicy,
+    },
+    tags::tag_diff,
+    util::{
+        build_cache_key_parameters, build_continuous_deployment_policy_config, build_custom_error_responses,
+        build_customizations, build_end_points, build_field_level_encryption_config,
+        build_field_level_encryption_profile_config, build_function_associations, build_lambda_function_associations,
+        build_logging, build_origin_request_policy_config, build_response_headers_policy_config, build_restrictions,
+        build_tenant_config, build_viewer_certificate, from_field_level_encryption_profile_config, get_distribution_config,
+    },
+};
 
-use super::CloudFrontConnector;
+use super::{
+    CloudFrontConnector,
+    verify::{is_distribution_update, waits_for_deployment},
+};
 
 impl CloudFrontConnector {
     pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
@@ -48,7 +65,14 @@ impl CloudFrontConnector {
 
         match &addr {
             CloudFrontResourceAddress::Distribution { distribution_id } => {
-                match op {
+                let verify_after = is_distribution_update(&op);
+
+                // CloudFront can reject a change while an earlier one is still deploying
+                if waits_for_deployment(&op) {
+                    self.wait_for_distribution_deployed(distribution_id).await?;
+                }
+
+                let mut output = match op {
                     CloudFrontConnectorOp::CreateDistribution(distribution) => {
                         let mut distribution_config = aws_sdk_cloudfront::types::DistributionConfig::builder()
                             .caller_reference(&format!("autoschematic-{}", uuid::Uuid::new_v4()))
@@ -117,12 +141,32 @@ impl CloudFrontConnector {
                             .cache_policy_id(distribution.default_cache_behavior.id)
                             .compress(distribution.default_cache_behavior.compress)
                             .set_min_ttl(distribution.default_cache_behavior.ttl_settings.min_ttl)
+                            .function_associations(build_function_associations(
+                                &distribution.default_cache_behavior.function_associations,
+                            )?)
+                            .lambda_function_associations(build_lambda_function_associations(
+                                &distribution.default_cache_behavior.lambda_function_associations,
+                            )?)
                             .build()
                             .map_err(|e| anyhow::anyhow!("Failed to build default cache behavior: {}", e))?;
 
                         distribution_config = distribution_config
                             .origins(origins)
-                            .default_cache_behavior(default_cache_behavior);
+                            .default_cache_behavior(default_cache_behavior)
+                            .viewer_certificate(build_viewer_certificate(&distribution.viewer_certificate))
+                            .restrictions(build_restrictions(&distribution.restrictions)?)
+                            .set_anycast_ip_list_id(distribution.anycast_ip_list_id.clone())
+                            .set_connection_mode(distribution.connection_mode.as_deref().map(ConnectionMode::from))
+                            .set_http_version(distribution.http_version.as_deref().map(HttpVersion::from))
+                            .is_ipv6_enabled(distribution.is_ipv6_enabled)
+                            .set_web_acl_id(distribution.web_acl_id.clone())
+                            .logging(build_logging(&distribution.logging))
+                            .custom_error_responses(build_custom_error_responses(&distribution.custom_error_responses)?)
+                            .set_continuous_deployment_policy_id(distribution.continuous_deployment_policy_id.clone());
+
+                        if let Some(tenant_config) = &distribution.tenant_config {
+                            distribution_config = distribution_config.tenant_config(build_tenant_config(tenant_config)?);
+                        }
 
                         let response = client
                             .create_distribution()
@@ -171,6 +215,18 @@ impl CloudFrontConnector {
                             .comment(config.comment().to_string())
                             .set_default_cache_behavior(config.default_cache_behavior().cloned())
                             .set_origins(config.origins().cloned())
+                            .set_viewer_certificate(config.viewer_certificate().cloned())
+                            .set_anycast_ip_list_id(config.anycast_ip_list_id().map(String::from))
+                            .set_restrictions(config.restrictions().cloned())
+                            .set_connection_mode(config.connection_mode().cloned())
+                            .set_tenant_config(config.tenant_config().cloned())
+                            .set_http_version(config.http_version().cloned())
+                            .set_is_ipv6_enabled(config.is_ipv6_enabled())
+                            .set_web_acl_id(config.web_acl_id().map(String::from))
+                            .set_logging(config.logging().cloned())
+                            .set_custom_error_responses(config.custom_error_responses().cloned())
+                            .set_staging(config.staging())
+                            .set_continuous_deployment_policy_id(config.continuous_deployment_policy_id().map(String::from))
                             .enabled(true)
                             .build()
                             .map_err(|e| anyhow::anyhow!("Failed to build updated distribution config: {}", e))?;
@@ -198,6 +254,18 @@ impl CloudFrontConnector {
                             .comment(config.comment().to_string())
                             .set_default_cache_behavior(config.default_cache_behavior().cloned())
                             .set_origins(config.origins().cloned())
+                            .set_viewer_certificate(config.viewer_certificate().cloned())
+                            .set_anycast_ip_list_id(config.anycast_ip_list_id().map(String::from))
+                            .set_restrictions(config.restrictions().cloned())
+                            .set_connection_mode(config.connection_mode().cloned())
+                            .set_tenant_config(config.tenant_config().cloned())
+                            .set_http_version(config.http_version().cloned())
+                            .set_is_ipv6_enabled(config.is_ipv6_enabled())
+                            .set_web_acl_id(config.web_acl_id().map(String::from))
+                            .set_logging(config.logging().cloned())
+                            .set_custom_error_responses(config.custom_error_responses().cloned())
+                            .set_staging(config.staging())
+                            .set_continuous_deployment_policy_id(config.continuous_deployment_policy_id().map(String::from))
                             .enabled(false)
                             .build()
                             .map_err(|e| anyhow::anyhow!("Failed to build updated distribution config: {}", e))?;
@@ -213,7 +281,11 @@ impl CloudFrontConnector {
                         op_exec_output!(format!("Disabled CloudFront distribution `{}`", distribution_id))
                     }
 
-                    CloudFrontConnectorOp::CreateInvalidation { paths, caller_reference } => {
+                    CloudFrontConnectorOp::CreateInvalidation {
+                        paths,
+                        caller_reference,
+                        wait,
+                    } => {
                         let invalidation_batch = aws_sdk_cloudfront::types::InvalidationBatch::builder()
                             .paths(
                                 aws_sdk_cloudfront::types::Paths::builder()
@@ -233,21 +305,35 @@ impl CloudFrontConnector {
                             .send()
                             .await?;
 
-                        let invalidation_id = response.invalidation().context("No invalidation in response")?.id();
+                        let invalidation = response.invalidation().context("No invalidation in response")?;
+                        let invalidation_id = invalidation.id();
 
-                        op_exec_output!(
-                            Some([("invalidation_id", Some(invalidation_id.to_string()))]),
+                        let message = if wait {
+                            let elapsed = self.wait_for_invalidation(distribution_id, invalidation_id).await?;
                             format!(
-                                "Created invalidation `{}` for distribution `{}`",
-                                invalidation_id, distribution_id
+                                "Created invalidation `{}` for distribution `{}`, which completed after {}s",
+                                invalidation_id,
+                                distribution_id,
+                                elapsed.as_secs()
                             )
-                        )
+                        } else {
+                            format!(
+                                "Created invalidation `{}` for distribution `{}` ({})",
+                                invalidation_id,
+                                distribution_id,
+                                invalidation.status()
+                            )
+                        };
+
+                        op_exec_output!(Some([("invalidation_id", Some(invalidation_id.to_string()))]), message)
                     }
 
                     CloudFrontConnectorOp::UpdateDistribution {
                         default_root_object,
                         comment,
                         price_class,
+                        http_version,
+                        is_ipv6_enabled,
                     } => {
                         let (etag, mut config) = get_distribution_config(distribution_id, &client).await?;
 
@@ -261,6 +347,9 @@ impl CloudFrontConnector {
                             config.price_class = Some(PriceClass::from_str(&price_class)?);
                         }
 
+                        config.http_version = Some(HttpVersion::from(http_version.as_deref().unwrap_or("http2")));
+                        config.is_ipv6_enabled = Some(is_ipv6_enabled);
+
                         client
                             .update_distribution()
                             .id(distribution_id)
@@ -377,6 +466,14 @@ impl CloudFrontConnector {
                             default_cache_behavior_builder = default_cache_behavior_builder.min_ttl(min_ttl);
                         }
 
+                        default_cache_behavior_builder = default_cache_behavior_builder
+                            .function_associations(build_function_associations(
+                                &default_cache_behavior.function_associations,
+                            )?)
+                            .lambda_function_associations(build_lambda_function_associations(
+                                &default_cache_behavior.lambda_function_associations,
+                            )?);
+
                         let new_default_cache_behavior = default_cache_behavior_builder
                             .build()
                             .map_err(|e| anyhow::anyhow!("Failed to build default cache behavior: {}", e))?;
@@ -390,6 +487,18 @@ impl CloudFrontConnector {
                             .set_cache_behaviors(config.cache_behaviors.clone())
                             .enabled(config.enabled)
                             .set_price_class(config.price_class.clone())
+                            .set_viewer_certificate(config.viewer_certificate.clone())
+                            .set_anycast_ip_list_id(config.anycast_ip_list_id.clone())
+                            .set_restrictions(config.restrictions.clone())
+                            .set_connection_mode(config.connection_mode.clone())
+                            .set_tenant_config(config.tenant_config.clone())
+                            .set_http_version(config.http_version.clone())
+                            .set_is_ipv6_enabled(config.is_ipv6_enabled)
+                            .set_web_acl_id(config.web_acl_id.clone())
+                            .set_logging(config.logging.clone())
+                            .set_custom_error_responses(config.custom_error_responses.clone())
+                            .set_staging(config.staging)
+                            .set_continuous_deployment_policy_id(config.continuous_deployment_policy_id.clone())
                             .build()
                             .map_err(|e| anyhow::anyhow!("Failed to build distribution config: {}", e))?;
 
@@ -407,6 +516,184 @@ impl CloudFrontConnector {
                         ))
                     }
 
+                    CloudFrontConnectorOp::UpdateDistributionViewerCertificate { viewer_certificate } => {
+                        let (etag, mut config) = get_distribution_config(distribution_id, &client).await?;
+
+                        config.viewer_certificate = Some(build_viewer_certificate(&viewer_certificate));
+
+                        client
+                            .update_distribution()
+                            .id(distribution_id)
+                            .distribution_config(config)
+                            .if_match(etag)
+                            .send()
+                            .await?;
+
+                        op_exec_output!(format!(
+                            "Updated viewer certificate for CloudFront distribution `{}`",
+                            distribution_id
+                        ))
+                    }
+
+                    CloudFrontConnectorOp::UpdateDistributionCustomErrorResponses { custom_error_responses } => {
+                        let (etag, mut config) = get_distribution_config(distribution_id, &client).await?;
+
+                        config.custom_error_responses = Some(build_custom_error_responses(&custom_error_responses)?);
+
+                        client
+                            .update_distribution()
+                            .id(distribution_id)
+                            .distribution_config(config)
+                            .if_match(etag)
+                            .send()
+                            .await?;
+
+                        op_exec_output!(format!(
+                            "Updated custom error responses for CloudFront distribution `{}`",
+                            distribution_id
+                        ))
+                    }
+
+                    CloudFrontConnectorOp::AssociateWebAcl { web_acl_id } => {
+                        let (etag, mut config) = get_distribution_config(distribution_id, &client).await?;
+
+                        config.web_acl_id = Some(web_acl_id.clone());
+
+                        client
+                            .update_distribution()
+                            .id(distribution_id)
+                            .distribution_config(config)
+                            .if_match(etag)
+                            .send()
+                            .await?;
+
+                        op_exec_output!(format!(
+                            "Associated web ACL {} with CloudFront distribution `{}`",
+                            web_acl_id, distribution_id
+                        ))
+                    }
+
+                    CloudFrontConnectorOp::DisassociateWebAcl => {
+                        let (etag, mut config) = get_distribution_config(distribution_id, &client).await?;
+
+                        // An empty web ACL ID disassociates the current one
+                        config.web_acl_id = Some(String::new());
+
+                        client
+                            .update_distribution()
+                            .id(distribution_id)
+                            .distribution_config(config)
+                            .if_match(etag)
+                            .send()
+                            .await?;
+
+                        op_exec_output!(format!(
+                            "Disassociated the web ACL from CloudFront distribution `{}`",
+                            distribution_id
+                        ))
+                    }
+
+                    CloudFrontConnectorOp::UpdateDistributionLogging { logging } => {
+                        let (etag, mut config) = get_distribution_config(distribution_id, &client).await?;
+
+                        config.logging = Some(build_logging(&logging));
+
+                        client
+                            .update_distribution()
+                            .id(distribution_id)
+                            .distribution_config(config)
+                            .if_match(etag)
+                            .send()
+                            .await?;
+
+                        op_exec_output!(format!(
+                            "Updated access logging for CloudFront distribution `{}`",
+                            distribution_id
+                        ))
+                    }
+
+                    CloudFrontConnectorOp::UpdateDistributionAnycastIpList { anycast_ip_list_id } => {
+                        let (etag, mut config) = get_distribution_config(distribution_id, &client).await?;
+
+                        config.anycast_ip_list_id = anycast_ip_list_id;
+
+                        client
+                            .update_distribution()
+                            .id(distribution_id)
+                            .distribution_config(config)
+                            .if_match(etag)
+                            .send()
+                            .await?;
+
+                        op_exec_output!(format!(
+                            "Updated Anycast static IP list for CloudFront distribution `{}`",
+                            distribution_id
+                        ))
+                    }
+
+                    CloudFrontConnectorOp::UpdateDistributionRestrictions { restrictions } => {
+                        let (etag, mut config) = get_distribution_config(distribution_id, &client).await?;
+
+                        config.restrictions = Some(build_restrictions(&restrictions)?);
+
+                        client
+                            .update_distribution()
+                            .id(distribution_id)
+                            .distribution_config(config)
+                            .if_match(etag)
+                            .send()
+                            .await?;
+
+                        op_exec_output!(format!(
+                            "Updated geo restrictions for CloudFront distribution `{}`",
+                            distribution_id
+                        ))
+                    }
+
+                    CloudFrontConnectorOp::UpdateDistributionTenantConfig { tenant_config } => {
+                        let (etag, mut config) = get_distribution_config(distribution_id, &client).await?;
+
+                        config.tenant_config = match &tenant_config {
+                            Some(tenant_config) => Some(build_tenant_config(tenant_config)?),
+                            None => None,
+                        };
+
+                        client
+                            .update_distribution()
+                            .id(distribution_id)
+                            .distribution_config(config)
+                            .if_match(etag)
+                            .send()
+                            .await?;
+
+                        op_exec_output!(format!(
+                            "Updated tenant parameters for CloudFront distribution `{}`",
+                            distribution_id
+                        ))
+                    }
+
+                    CloudFrontConnectorOp::UpdateDistributionContinuousDeploymentPolicy {
+                        continuous_deployment_policy_id,
+                    } => {
+                        let (etag, mut config) = get_distribution_config(distribution_id, &client).await?;
+
+                        // An empty policy ID detaches the current one
+                        config.continuous_deployment_policy_id = Some(continuous_deployment_policy_id.unwrap_or_default());
+
+                        client
+                            .update_distribution()
+                            .id(distribution_id)
+                            .distribution_config(config)
+                            .if_match(etag)
+                            .send()
+                            .await?;
+
+                        op_exec_output!(format!(
+                            "Updated continuous deployment policy for CloudFront distribution `{}`",
+                            distribution_id
+                        ))
+                    }
+
                     CloudFrontConnectorOp::UpdateDistributionCacheBehaviors { cache_behaviors } => {
                         let (etag, config) = get_distribution_config(distribution_id, &client).await?;
 
@@ -427,6 +714,12 @@ impl CloudFrontConnector {
                                 cache_behavior_builder = cache_behavior_builder.min_ttl(min_ttl);
                             }
 
+                            cache_behavior_builder = cache_behavior_builder
+                                .function_associations(build_function_associations(&cache_behavior.function_associations)?)
+                                .lambda_function_associations(build_lambda_function_associations(
+                                    &cache_behavior.lambda_function_associations,
+                                )?);
+
                             cache_behaviors_builder = cache_behaviors_builder.items(
                                 cache_behavior_builder
                                     .build()
@@ -445,6 +738,18 @@ impl CloudFrontConnector {
                             .cache_behaviors(new_cache_behaviors)
                             .enabled(config.enabled)
                             .set_price_class(config.price_class.clone())
+                            .set_viewer_certificate(config.viewer_certificate.clone())
+                            .set_anycast_ip_list_id(config.anycast_ip_list_id.clone())
+                            .set_restrictions(config.restrictions.clone())
+                            .set_connection_mode(config.connection_mode.clone())
+                            .set_tenant_config(config.tenant_config.clone())
+                            .set_http_version(config.http_version.clone())
+                            .set_is_ipv6_enabled(config.is_ipv6_enabled)
+                            .set_web_acl_id(config.web_acl_id.clone())
+                            .set_logging(config.logging.clone())
+                            .set_custom_error_responses(config.custom_error_responses.clone())
+                            .set_staging(config.staging)
+                            .set_continuous_deployment_policy_id(config.continuous_deployment_policy_id.clone())
                             .build()
                             .map_err(|e| anyhow::anyhow!("Failed to build distribution config: {}", e))?;
 
@@ -463,7 +768,21 @@ impl CloudFrontConnector {
                     }
 
                     _ => Err(invalid_op(&addr, &op)),
+                }?;
+
+                let verification = self.config.lock().await.post_deploy_verification.clone();
+                if verify_after
+                    && let Some(verification) = verification
+                    && verification.applies_to(distribution_id)
+                {
+                    let summary = self.verify_distribution_deployment(distribution_id, &verification).await?;
+                    output.friendly_message = Some(match output.friendly_message {
+                        Some(message) => format!("{}\n{}", message, summary),
+                        None => summary,
+                    });
                 }
+
+                Ok(output)
             }
 
             CloudFrontResourceAddress::OriginAccessControl { oac_id } => match op {
@@ -596,6 +915,10 @@ impl CloudFrontConnector {
                         cache_policy_config
                     };
 
+                    let cache_policy_config = cache_policy_config.parameters_in_cache_key_and_forwarded_to_origin(
+                        build_cache_key_parameters(&policy.parameters_in_cache_key_and_forwarded_to_origin)?,
+                    );
+
                     let response = client
                         .create_cache_policy()
                         .cache_policy_config(cache_policy_config.build()?)
@@ -622,7 +945,6 @@ impl CloudFrontConnector {
                     let get_response = client.get_cache_policy().id(policy_id).send().await?;
                     let current_policy = get_response.cache_policy().context("No cache policy in response")?;
                     let etag = get_response.e_tag().context("No ETag in response")?;
-                    let current_config = current_policy.cache_policy_config();
 
                     let Some(mut current_config) = current_policy.cache_policy_config.clone() else {
                         bail!("UpdateCachePolicy: cache_policy_config is None");
@@ -648,15 +970,9 @@ impl CloudFrontConnector {
                         current_config.max_ttl = Some(max_ttl);
                     }
 
-                    if let Some(parameters_in_cache_key_and_forwarded_to_origin) =
-                        parameters_in_cache_key_and_forwarded_to_origin
-                    {
-                        // TODO this needs to be modelled in the resource/
-                        todo!();
-                        // let params = ParametersInCacheKeyAndForwardedToOrigin::builder();
-                        // current_config.parameters_in_cache_key_and_forwarded_to_origin =
-                        //     Some(parameters_in_cache_key_and_forwarded_to_origin);
-                    }
+                    current_config.parameters_in_cache_key_and_forwarded_to_origin = Some(build_cache_key_parameters(
+                        &parameters_in_cache_key_and_forwarded_to_origin,
+                    )?);
 
                     client
                         .update_cache_policy()
@@ -682,6 +998,334 @@ impl CloudFrontConnector {
                 _ => Err(invalid_op(&addr, &op)),
             },
 
+            CloudFrontResourceAddress::OriginRequestPolicy { policy_id } => match op {
+                CloudFrontConnectorOp::CreateOriginRequestPolicy(policy) => {
+                    let response = client
+                        .create_origin_request_policy()
+                        .origin_request_policy_config(build_origin_request_policy_config(&policy)?)
+                        .send()
+                        .await?;
+
+                    let policy_id = response
+                        .origin_request_policy()
+                        .context("No origin request policy in response")?
+                        .id();
+
+                    op_exec_output!(
+                        Some([("policy_id", Some(policy_id.to_string()))]),
+                        format!("Created CloudFront origin request policy `{}`", policy_id)
+                    )
+                }
+
+                CloudFrontConnectorOp::UpdateOriginRequestPolicy {
+                    name,
+                    comment,
+                    cookies_config,
+                    headers_config,
+                    query_strings_config,
+                } => {
+                    let get_response = client.get_origin_request_policy_config().id(policy_id).send().await?;
+                    let current_config = get_response
+                        .origin_request_policy_config()
+                        .context("No origin request policy config in response")?;
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    let policy = OriginRequestPolicy {
+                        name: name.unwrap_or_else(|| current_config.name().to_string()),
+                        comment,
+                        cookies_config,
+                        headers_config,
+                        query_strings_config,
+                    };
+
+                    client
+                        .update_origin_request_policy()
+                        .id(policy_id)
+                        .origin_request_policy_config(build_origin_request_policy_config(&policy)?)
+                        .if_match(etag)
+                        .send()
+                        .await?;
+
+                    op_exec_output!(format!("Updated CloudFront origin request policy `{}`", policy_id))
+                }
+
+                CloudFrontConnectorOp::DeleteOriginRequestPolicy => {
+                    let get_response = client.get_origin_request_policy().id(policy_id).send().await?;
+
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    client.delete_origin_request_policy().id(policy_id).if_match(etag).send().await?;
+
+                    op_exec_output!(format!("Deleted CloudFront origin request policy `{}`", policy_id))
+                }
+
+                _ => Err(invalid_op(&addr, &op)),
+            },
+
+            CloudFrontResourceAddress::ResponseHeadersPolicy { policy_id } => match op {
+                CloudFrontConnectorOp::CreateResponseHeadersPolicy(policy) => {
+                    let response = client
+                        .create_response_headers_policy()
+                        .response_headers_policy_config(build_response_headers_policy_config(&policy)?)
+                        .send()
+                        .await?;
+
+                    let policy_id = response
+                        .response_headers_policy()
+                        .context("No response headers policy in response")?
+                        .id();
+
+                    op_exec_output!(
+                        Some([("policy_id", Some(policy_id.to_string()))]),
+                        format!("Created CloudFront response headers policy `{}`", policy_id)
+                    )
+                }
+
+                CloudFrontConnectorOp::UpdateResponseHeadersPolicy {
+                    name,
+                    comment,
+                    cors_config,
+                    custom_headers_config,
+                    security_headers_config,
+                } => {
+                    let get_response = client.get_response_headers_policy_config().id(policy_id).send().await?;
+                    let current_config = get_response
+                        .response_headers_policy_config()
+                        .context("No response headers policy config in response")?;
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    let policy = ResponseHeadersPolicy {
+                        name: name.unwrap_or_else(|| current_config.name().to_string()),
+                        comment,
+                        cors_config,
+                        custom_headers_config,
+                        security_headers_config,
+                    };
+
+                    client
+                        .update_response_headers_policy()
+                        .id(policy_id)
+                        .response_headers_policy_config(build_response_headers_policy_config(&policy)?)
+                        .if_match(etag)
+                        .send()
+                        .await?;
+
+                    op_exec_output!(format!("Updated CloudFront response headers policy `{}`", policy_id))
+                }
+
+                CloudFrontConnectorOp::DeleteResponseHeadersPolicy => {
+                    let get_response = client.get_response_headers_policy().id(policy_id).send().await?;
+
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    client
+                        .delete_response_headers_policy()
+                        .id(policy_id)
+                        .if_match(etag)
+                        .send()
+                        .await?;
+
+                    op_exec_output!(format!("Deleted CloudFront response headers policy `{}`", policy_id))
+                }
+
+                _ => Err(invalid_op(&addr, &op)),
+            },
+
+            CloudFrontResourceAddress::RealtimeLogConfig { name } => match op {
+                CloudFrontConnectorOp::CreateRealtimeLogConfig(config) => {
+                    let response = client
+                        .create_realtime_log_config()
+                        .name(&config.name)
+                        .set_end_points(Some(build_end_points(&config.end_points)?))
+                        .set_fields(Some(config.fields.clone()))
+                        .sampling_rate(config.sampling_rate as i64)
+                        .send()
+                        .await?;
+
+                    let arn = response
+                        .realtime_log_config()
+                        .context("No realtime log config in response")?
+                        .arn();
+
+                    op_exec_output!(
+                        Some([("realtime_log_config_arn", Some(arn.to_string()))]),
+                        format!("Created CloudFront realtime log config `{}`", name)
+                    )
+                }
+
+                CloudFrontConnectorOp::UpdateRealtimeLogConfig {
+                    name: _,
+                    end_points,
+                    fields,
+                    sampling_rate,
+                } => {
+                    let end_points = match end_points {
+                        Some(end_points) => Some(build_end_points(&end_points)?),
+                        None => None,
+                    };
+
+                    // Realtime log configs are addressed by name, and don't use ETags
+                    client
+                        .update_realtime_log_config()
+                        .name(name)
+                        .set_end_points(end_points)
+                        .set_fields(fields)
+                        .set_sampling_rate(sampling_rate.map(|rate| rate as i64))
+                        .send()
+                        .await?;
+
+                    op_exec_output!(format!("Updated CloudFront realtime log config `{}`", name))
+                }
+
+                CloudFrontConnectorOp::DeleteRealtimeLogConfig => {
+                    client.delete_realtime_log_config().name(name).send().await?;
+
+                    op_exec_output!(format!("Deleted CloudFront realtime log config `{}`", name))
+                }
+
+                _ => Err(invalid_op(&addr, &op)),
+            },
+
+            CloudFrontResourceAddress::FieldLevelEncryptionConfig { config_id } => match op {
+                CloudFrontConnectorOp::CreateFieldLevelEncryptionConfig(config) => {
+                    let response = client
+                        .create_field_level_encryption_config()
+                        .field_level_encryption_config(build_field_level_encryption_config(&config)?)
+                        .send()
+                        .await?;
+
+                    let config_id = response
+                        .field_level_encryption()
+                        .context("No field-level encryption config in response")?
+                        .id();
+
+                    op_exec_output!(
+                        Some([("config_id", Some(config_id.to_string()))]),
+                        format!("Created CloudFront field-level encryption config `{}`", config_id)
+                    )
+                }
+
+                CloudFrontConnectorOp::UpdateFieldLevelEncryptionConfig {
+                    comment,
+                    content_type_profile_config,
+                    query_arg_profile_config,
+                } => {
+                    let get_response = client.get_field_level_encryption_config().id(config_id).send().await?;
+                    let current_config = get_response
+                        .field_level_encryption_config()
+                        .context("No field-level encryption config in response")?;
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    // The caller reference identifies the config, and can't change
+                    let config = FieldLevelEncryptionConfig {
+                        comment,
+                        caller_reference: current_config.caller_reference().to_string(),
+                        content_type_profile_config,
+                        query_arg_profile_config,
+                    };
+
+                    client
+                        .update_field_level_encryption_config()
+                        .id(config_id)
+                        .field_level_encryption_config(build_field_level_encryption_config(&config)?)
+                        .if_match(etag)
+                        .send()
+                        .await?;
+
+                    op_exec_output!(format!("Updated CloudFront field-level encryption config `{}`", config_id))
+                }
+
+                CloudFrontConnectorOp::DeleteFieldLevelEncryptionConfig => {
+                    let get_response = client.get_field_level_encryption_config().id(config_id).send().await?;
+
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    client
+                        .delete_field_level_encryption_config()
+                        .id(config_id)
+                        .if_match(etag)
+                        .send()
+                        .await?;
+
+                    op_exec_output!(format!("Deleted CloudFront field-level encryption config `{}`", config_id))
+                }
+
+                _ => Err(invalid_op(&addr, &op)),
+            },
+
+            CloudFrontResourceAddress::FieldLevelEncryptionProfile { profile_id } => match op {
+                CloudFrontConnectorOp::CreateFieldLevelEncryptionProfile(profile) => {
+                    let response = client
+                        .create_field_level_encryption_profile()
+                        .field_level_encryption_profile_config(build_field_level_encryption_profile_config(&profile)?)
+                        .send()
+                        .await?;
+
+                    let profile_id = response
+                        .field_level_encryption_profile()
+                        .context("No field-level encryption profile in response")?
+                        .id();
+
+                    op_exec_output!(
+                        Some([("profile_id", Some(profile_id.to_string()))]),
+                        format!("Created CloudFront field-level encryption profile `{}`", profile_id)
+                    )
+                }
+
+                CloudFrontConnectorOp::UpdateFieldLevelEncryptionProfile {
+                    name,
+                    comment,
+                    encryption_entities,
+                } => {
+                    let get_response = client
+                        .get_field_level_encryption_profile_config()
+                        .id(profile_id)
+                        .send()
+                        .await?;
+                    let current_config = get_response
+                        .field_level_encryption_profile_config()
+                        .context("No field-level encryption profile config in response")?;
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    let profile = FieldLevelEncryptionProfile {
+                        name: name.unwrap_or_else(|| current_config.name().to_string()),
+                        comment,
+                        caller_reference: current_config.caller_reference().to_string(),
+                        encryption_entities: match encryption_entities {
+                            Some(encryption_entities) => encryption_entities,
+                            None => from_field_level_encryption_profile_config(current_config.clone()).encryption_entities,
+                        },
+                    };
+
+                    client
+                        .update_field_level_encryption_profile()
+                        .id(profile_id)
+                        .field_level_encryption_profile_config(build_field_level_encryption_profile_config(&profile)?)
+                        .if_match(etag)
+                        .send()
+                        .await?;
+
+                    op_exec_output!(format!("Updated CloudFront field-level encryption profile `{}`", profile_id))
+                }
+
+                CloudFrontConnectorOp::DeleteFieldLevelEncryptionProfile => {
+                    let get_response = client.get_field_level_encryption_profile().id(profile_id).send().await?;
+
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    client
+                        .delete_field_level_encryption_profile()
+                        .id(profile_id)
+                        .if_match(etag)
+                        .send()
+                        .await?;
+
+                    op_exec_output!(format!("Deleted CloudFront field-level encryption profile `{}`", profile_id))
+                }
+
+                _ => Err(invalid_op(&addr, &op)),
+            },
+
             CloudFrontResourceAddress::Function { name } => match op {
                 CloudFrontConnectorOp::CreateFunction(function) => {
                     let function_code = function.function_code.as_bytes();
@@ -1007,8 +1651,287 @@ impl CloudFrontConnector {
                 _ => Err(invalid_op(&addr, &op)),
             },
 
+            CloudFrontResourceAddress::DistributionTenant { tenant_id } => match op {
+                CloudFrontConnectorOp::CreateDistributionTenant(tenant) => {
+                    let (_, tags) = tag_diff(&HashMap::new(), &tenant.tags)?;
+
+                    let mut request = client
+                        .create_distribution_tenant()
+                        .name(&tenant.name)
+                        .distribution_id(&tenant.distribution_id)
+                        .set_domains(Some(tenant_domains(&tenant)?))
+                        .set_parameters(Some(tenant_parameters(&tenant)?))
+                        .set_connection_group_id(tenant.connection_group_id.clone())
+                        .enabled(tenant.enabled)
+                        .tags(Tags::builder().set_items(Some(tags)).build());
+
+                    if let Some(customizations) = &tenant.customizations {
+                        request = request.customizations(build_customizations(customizations)?);
+                    }
+
+                    let response = request.send().await?;
+
+                    let tenant_result = response.distribution_tenant().context("No distribution tenant in response")?;
+                    let tenant_id = tenant_result.id().context("No ID for distribution tenant")?;
+
+                    op_exec_output!(
+                        Some([
+                            ("distribution_tenant_id", Some(tenant_id.to_string())),
+                            ("distribution_tenant_arn", tenant_result.arn().map(String::from))
+                        ]),
+                        format!("Created CloudFront distribution tenant `{}` ({})", tenant.name, tenant_id)
+                    )
+                }
+
+                CloudFrontConnectorOp::UpdateDistributionTenant(tenant) => {
+                    let get_response = client.get_distribution_tenant().identifier(tenant_id).send().await?;
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    let mut request = client
+                        .update_distribution_tenant()
+                        .id(tenant_id)
+                        .if_match(etag)
+                        .distribution_id(&tenant.distribution_id)
+                        .set_domains(Some(tenant_domains(&tenant)?))
+                        .set_parameters(Some(tenant_parameters(&tenant)?))
+                        .set_connection_group_id(tenant.connection_group_id.clone())
+                        .enabled(tenant.enabled);
+
+                    if let Some(customizations) = &tenant.customizations {
+                        request = request.customizations(build_customizations(customizations)?);
+                    }
+
+                    request.send().await?;
+
+                    op_exec_output!(format!("Updated CloudFront distribution tenant `{}`", tenant_id))
+                }
+
+                CloudFrontConnectorOp::DeleteDistributionTenant => {
+                    let get_response = client.get_distribution_tenant().identifier(tenant_id).send().await?;
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    client.delete_distribution_tenant().id(tenant_id).if_match(etag).send().await?;
+
+                    op_exec_output!(format!("Deleted CloudFront distribution tenant `{}`", tenant_id))
+                }
+
+                _ => Err(invalid_op(&addr, &op)),
+            },
+
+            CloudFrontResourceAddress::ConnectionGroup { connection_group_id } => match op {
+                CloudFrontConnectorOp::CreateConnectionGroup(group) => {
+                    let (_, tags) = tag_diff(&HashMap::new(), &group.tags)?;
+
+                    let response = client
+                        .create_connection_group()
+                        .name(&group.name)
+                        .ipv6_enabled(group.ipv6_enabled)
+                        .set_anycast_ip_list_id(group.anycast_ip_list_id.clone())
+                        .enabled(group.enabled)
+                        .tags(Tags::builder().set_items(Some(tags)).build())
+                        .send()
+                        .await?;
+
+                    let group_result = response.connection_group().context("No connection group in response")?;
+                    let connection_group_id = group_result.id().context("No ID for connection group")?;
+
+                    op_exec_output!(
+                        Some([
+                            ("connection_group_id", Some(connection_group_id.to_string())),
+                            ("routing_endpoint", group_result.routing_endpoint().map(String::from))
+                        ]),
+                        format!("Created CloudFront connection group `{}` ({})", group.name, connection_group_id)
+                    )
+                }
+
+                CloudFrontConnectorOp::UpdateConnectionGroup(group) => {
+                    let get_response = client.get_connection_group().identifier(connection_group_id).send().await?;
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    client
+                        .update_connection_group()
+                        .id(connection_group_id)
+                        .if_match(etag)
+                        .ipv6_enabled(group.ipv6_enabled)
+                        .set_anycast_ip_list_id(group.anycast_ip_list_id.clone())
+                        .enabled(group.enabled)
+                        .send()
+                        .await?;
+
+                    op_exec_output!(format!("Updated CloudFront connection group `{}`", connection_group_id))
+                }
+
+                CloudFrontConnectorOp::DeleteConnectionGroup => {
+                    let get_response = client.get_connection_group().identifier(connection_group_id).send().await?;
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    client
+                        .delete_connection_group()
+                        .id(connection_group_id)
+                        .if_match(etag)
+                        .send()
+                        .await?;
+
+                    op_exec_output!(format!("Deleted CloudFront connection group `{}`", connection_group_id))
+                }
+
+                _ => Err(invalid_op(&addr, &op)),
+            },
+
+            CloudFrontResourceAddress::OriginAccessIdentity { oai_id } => match op {
+                CloudFrontConnectorOp::CreateOriginAccessIdentity(oai) => {
+                    let oai_config = aws_sdk_cloudfront::types::CloudFrontOriginAccessIdentityConfig::builder()
+                        .caller_reference(format!("autoschematic-{}", uuid::Uuid::new_v4()))
+                        .comment(&oai.comment)
+                        .build()
+                        .map_err(|e| anyhow::anyhow!("Failed to build origin access identity config: {}", e))?;
+
+                    let response = client
+                        .create_cloud_front_origin_access_identity()
+                        .cloud_front_origin_access_identity_config(oai_config)
+                        .send()
+                        .await?;
+
+                    let oai_result = response
+                        .cloud_front_origin_access_identity()
+                        .context("No origin access identity in response")?;
+                    let oai_id = oai_result.id();
+
+                    op_exec_output!(
+                        Some([
+                            ("oai_id", Some(oai_id.to_string())),
+                            ("s3_canonical_user_id", Some(oai_result.s3_canonical_user_id().to_string()))
+                        ]),
+                        format!("Created CloudFront origin access identity `{}`", oai_id)
+                    )
+                }
+
+                CloudFrontConnectorOp::UpdateOriginAccessIdentity(oai) => {
+                    let get_response = client
+                        .get_cloud_front_origin_access_identity_config()
+                        .id(oai_id)
+                        .send()
+                        .await?;
+                    let current_config = get_response
+                        .cloud_front_origin_access_identity_config()
+                        .context("No origin access identity config in response")?;
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    // The caller reference identifies the origin access identity, and can't change
+                    let oai_config = aws_sdk_cloudfront::types::CloudFrontOriginAccessIdentityConfig::builder()
+                        .caller_reference(current_config.caller_reference())
+                        .comment(&oai.comment)
+                        .build()
+                        .map_err(|e| anyhow::anyhow!("Failed to build origin access identity config: {}", e))?;
+
+                    client
+                        .update_cloud_front_origin_access_identity()
+                        .id(oai_id)
+                        .cloud_front_origin_access_identity_config(oai_config)
+                        .if_match(etag)
+                        .send()
+                        .await?;
+
+                    op_exec_output!(format!("Updated CloudFront origin access identity `{}`", oai_id))
+                }
+
+                CloudFrontConnectorOp::DeleteOriginAccessIdentity => {
+                    let get_response = client.get_cloud_front_origin_access_identity().id(oai_id).send().await?;
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    client
+                        .delete_cloud_front_origin_access_identity()
+                        .id(oai_id)
+                        .if_match(etag)
+                        .send()
+                        .await?;
+
+                    op_exec_output!(format!("Deleted CloudFront origin access identity `{}`", oai_id))
+                }
+
+                _ => Err(invalid_op(&addr, &op)),
+            },
+
+            CloudFrontResourceAddress::ContinuousDeploymentPolicy { policy_id } => match op {
+                CloudFrontConnectorOp::CreateContinuousDeploymentPolicy(policy) => {
+                    let response = client
+                        .create_continuous_deployment_policy()
+                        .continuous_deployment_policy_config(build_continuous_deployment_policy_config(&policy)?)
+                        .send()
+                        .await?;
+
+                    let policy_id = response
+                        .continuous_deployment_policy()
+                        .context("No continuous deployment policy in response")?
+                        .id();
+
+                    op_exec_output!(
+                        Some([("policy_id", Some(policy_id.to_string()))]),
+                        format!("Created CloudFront continuous deployment policy `{}`", policy_id)
+                    )
+                }
+
+                CloudFrontConnectorOp::UpdateContinuousDeploymentPolicy(policy) => {
+                    let get_response = client.get_continuous_deployment_policy_config().id(policy_id).send().await?;
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    client
+                        .update_continuous_deployment_policy()
+                        .id(policy_id)
+                        .continuous_deployment_policy_config(build_continuous_deployment_policy_config(&policy)?)
+                        .if_match(etag)
+                        .send()
+                        .await?;
+
+                    op_exec_output!(format!("Updated CloudFront continuous deployment policy `{}`", policy_id))
+                }
+
+                CloudFrontConnectorOp::DeleteContinuousDeploymentPolicy => {
+                    let get_response = client.get_continuous_deployment_policy_config().id(policy_id).send().await?;
+                    let etag = get_response.e_tag().context("No ETag in response")?;
+
+                    client
+                        .delete_continuous_deployment_policy()
+                        .id(policy_id)
+                        .if_match(etag)
+                        .send()
+                        .await?;
+
+                    op_exec_output!(format!("Deleted CloudFront continuous deployment policy `{}`", policy_id))
+                }
+
+                _ => Err(invalid_op(&addr, &op)),
+            },
+
             // For resource types that don't have implemented operations yet
             _ => Err(invalid_op(&addr, &op)),
         }
     }
 }
+
+fn tenant_domains(tenant: &DistributionTenant) -> anyhow::Result<Vec<DomainItem>> {
+    let mut domains = Vec::new();
+    for domain in &tenant.domains {
+        domains.push(
+            DomainItem::builder()
+                .domain(domain)
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build domain {}: {}", domain, e))?,
+        );
+    }
+    Ok(domains)
+}
+
+fn tenant_parameters(tenant: &DistributionTenant) -> anyhow::Result<Vec<Parameter>> {
+    let mut parameters = Vec::new();
+    for (name, value) in &tenant.parameters {
+        parameters.push(
+            Parameter::builder()
+                .name(name)
+                .value(value)
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build parameter {}: {}", name, e))?,
+        );
+    }
+    Ok(parameters)
+}
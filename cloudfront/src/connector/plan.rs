@@ -1,5 +1,7 @@
 use std::path::Path;
 
+use anyhow::bail;
+
 use autoschematic_core::{
     connector::{ConnectorOp, PlanResponseElement, ResourceAddress},
     connector_op,
@@ -10,13 +12,40 @@ use crate::{
     addr::CloudFrontResourceAddress,
     op::CloudFrontConnectorOp,
     resource::{
-        CachePolicy, Distribution, FieldLevelEncryptionConfig, FieldLevelEncryptionProfile, Function, KeyGroup,
-        OriginAccessControl, OriginRequestPolicy, PublicKey, RealtimeLogConfig, ResponseHeadersPolicy, StreamingDistribution,
-    }, 
+        CachePolicy, ConnectionGroup, ContinuousDeploymentPolicy, Distribution, DistributionTenant, FieldLevelEncryptionConfig,
+        FieldLevelEncryptionProfile, Function, GeoRestriction, KeyGroup, OriginAccessControl, OriginAccessIdentity, OriginRequestPolicy, PolicyItems,
+        PublicKey, RealtimeLogConfig, ResponseHeadersPolicy, StreamingDistribution,
+    },
+    util::{function_code, sorted_locations},
 };
 
 use super::CloudFrontConnector;
 
+const HTTP_VERSIONS: &[&str] = &["http1.1", "http2", "http3", "http2and3"];
+const GEO_RESTRICTION_TYPES: &[&str] = &["whitelist", "blacklist"];
+const TRAFFIC_CONFIG_TYPES: &[&str] = &["SingleWeight", "SingleHeader"];
+/// CloudFront routes at most 15% of a primary distribution's requests to its staging distribution.
+const MAX_STAGING_WEIGHT: f32 = 0.15;
+const CACHE_KEY_HEADER_BEHAVIORS: &[&str] = &["none", "whitelist"];
+const CACHE_KEY_COOKIE_BEHAVIORS: &[&str] = &["none", "whitelist", "allExcept", "all"];
+const CACHE_KEY_QUERY_STRING_BEHAVIORS: &[&str] = &["none", "whitelist", "allExcept", "all"];
+const ORIGIN_REQUEST_HEADER_BEHAVIORS: &[&str] =
+    &["none", "whitelist", "allViewer", "allViewerAndWhitelistCloudFront", "allExcept"];
+const ORIGIN_REQUEST_COOKIE_BEHAVIORS: &[&str] = &["none", "whitelist", "all", "allExcept"];
+const ORIGIN_REQUEST_QUERY_STRING_BEHAVIORS: &[&str] = &["none", "whitelist", "all", "allExcept"];
+const CORS_METHODS: &[&str] = &["GET", "POST", "OPTIONS", "PUT", "DELETE", "PATCH", "HEAD", "ALL"];
+const FRAME_OPTIONS: &[&str] = &["DENY", "SAMEORIGIN"];
+const REFERRER_POLICIES: &[&str] = &[
+    "no-referrer",
+    "no-referrer-when-downgrade",
+    "origin",
+    "origin-when-cross-origin",
+    "same-origin",
+    "strict-origin",
+    "strict-origin-when-cross-origin",
+    "unsafe-url",
+];
+
 impl CloudFrontConnector {
     pub async fn do_plan(
         &self,
@@ -33,10 +62,24 @@ impl CloudFrontConnector {
                 match (current, desired) {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_distribution)) => {
-                        let new_distribution: Distribution = RON.from_str(&new_distribution)?;
+                        let mut new_distribution: Distribution = RON.from_str(&new_distribution)?;
+                        check_tenant_config(&distribution_id, &new_distribution)?;
+                        check_distribution(&distribution_id, &new_distribution)?;
+                        normalize_restrictions(&mut new_distribution.restrictions);
+                        if new_distribution.staging {
+                            bail!(
+                                "CloudFront distribution `{}`: staging distributions can only be created by copying their primary distribution",
+                                distribution_id
+                            );
+                        }
+                        let cost_warning = format!(
+                            "{}{}",
+                            dedicated_ip_cost_warning(None, &new_distribution),
+                            anycast_ip_cost_warning(None, &new_distribution)
+                        );
                         Ok(vec![connector_op!(
                             CloudFrontConnectorOp::CreateDistribution(new_distribution),
-                            format!("Create new CloudFront distribution {}", distribution_id)
+                            format!("Create new CloudFront distribution {}{}", distribution_id, cost_warning)
                         )])
                     }
                     (Some(_old_distribution), None) => Ok(vec![connector_op!(
@@ -45,9 +88,30 @@ impl CloudFrontConnector {
                     )]),
                     (Some(old_distribution), Some(new_distribution)) => {
                         let old_distribution: Distribution = RON.from_str(&old_distribution)?;
-                        let new_distribution: Distribution = RON.from_str(&new_distribution)?;
+                        let mut new_distribution: Distribution = RON.from_str(&new_distribution)?;
+                        check_tenant_config(&distribution_id, &new_distribution)?;
+                        check_distribution(&distribution_id, &new_distribution)?;
+                        normalize_restrictions(&mut new_distribution.restrictions);
                         let mut ops = Vec::new();
 
+                        if old_distribution.connection_mode != new_distribution.connection_mode {
+                            bail!(
+                                "CloudFront distribution `{}`: connection_mode can't be changed after creation ({:?} -> {:?})",
+                                distribution_id,
+                                old_distribution.connection_mode,
+                                new_distribution.connection_mode
+                            );
+                        }
+
+                        if old_distribution.staging != new_distribution.staging {
+                            bail!(
+                                "CloudFront distribution `{}`: staging can't be changed after creation ({} -> {})",
+                                distribution_id,
+                                old_distribution.staging,
+                                new_distribution.staging
+                            );
+                        }
+
                         // Check for tag changes
                         if old_distribution.tags != new_distribution.tags {
                             let diff = diff_ron_values(&old_distribution.tags, &new_distribution.tags).unwrap_or_default();
@@ -75,6 +139,14 @@ impl CloudFrontConnector {
                             distribution_changed = true;
                             message.push_str(&format!(" price_class={:?}", new_distribution.price_class));
                         }
+                        if old_distribution.http_version != new_distribution.http_version {
+                            distribution_changed = true;
+                            message.push_str(&format!(" http_version={:?}", new_distribution.http_version));
+                        }
+                        if old_distribution.is_ipv6_enabled != new_distribution.is_ipv6_enabled {
+                            distribution_changed = true;
+                            message.push_str(&format!(" is_ipv6_enabled={}", new_distribution.is_ipv6_enabled));
+                        }
 
                         if distribution_changed {
                             ops.push(connector_op!(
@@ -82,6 +154,8 @@ impl CloudFrontConnector {
                                     default_root_object: new_distribution.default_root_object.clone(),
                                     comment: new_distribution.comment.clone(),
                                     price_class: new_distribution.price_class.clone(),
+                                    http_version: new_distribution.http_version.clone(),
+                                    is_ipv6_enabled: new_distribution.is_ipv6_enabled,
                                 },
                                 format!("Update CloudFront distribution `{}`: {}", distribution_id, message)
                             ));
@@ -129,6 +203,129 @@ impl CloudFrontConnector {
                             ));
                         }
 
+                        if old_distribution.viewer_certificate != new_distribution.viewer_certificate {
+                            let diff = diff_ron_values(&old_distribution.viewer_certificate, &new_distribution.viewer_certificate)
+                                .unwrap_or_default();
+                            ops.push(connector_op!(
+                                CloudFrontConnectorOp::UpdateDistributionViewerCertificate {
+                                    viewer_certificate: new_distribution.viewer_certificate.clone(),
+                                },
+                                format!(
+                                    "Update viewer certificate for CloudFront distribution `{}`\n{}{}",
+                                    distribution_id,
+                                    diff,
+                                    dedicated_ip_cost_warning(Some(&old_distribution), &new_distribution)
+                                )
+                            ));
+                        }
+
+                        if old_distribution.custom_error_responses != new_distribution.custom_error_responses {
+                            let diff = diff_ron_values(
+                                &old_distribution.custom_error_responses,
+                                &new_distribution.custom_error_responses,
+                            )
+                            .unwrap_or_default();
+                            ops.push(connector_op!(
+                                CloudFrontConnectorOp::UpdateDistributionCustomErrorResponses {
+                                    custom_error_responses: new_distribution.custom_error_responses.clone(),
+                                },
+                                format!(
+                                    "Update custom error responses for CloudFront distribution `{}`\n{}",
+                                    distribution_id, diff
+                                )
+                            ));
+                        }
+
+                        if old_distribution.web_acl_id != new_distribution.web_acl_id {
+                            match &new_distribution.web_acl_id {
+                                Some(web_acl_id) => ops.push(connector_op!(
+                                    CloudFrontConnectorOp::AssociateWebAcl {
+                                        web_acl_id: web_acl_id.clone(),
+                                    },
+                                    format!(
+                                        "Associate web ACL {} with CloudFront distribution `{}`{}",
+                                        web_acl_id,
+                                        distribution_id,
+                                        match &old_distribution.web_acl_id {
+                                            Some(old_web_acl_id) => format!(", replacing {}", old_web_acl_id),
+                                            None => String::new(),
+                                        }
+                                    )
+                                )),
+                                None => ops.push(connector_op!(
+                                    CloudFrontConnectorOp::DisassociateWebAcl,
+                                    format!(
+                                        "Disassociate web ACL {} from CloudFront distribution `{}`. WAF will no longer filter its requests.",
+                                        old_distribution.web_acl_id.as_deref().unwrap_or_default(),
+                                        distribution_id
+                                    )
+                                )),
+                            }
+                        }
+
+                        if old_distribution.logging != new_distribution.logging {
+                            let diff = diff_ron_values(&old_distribution.logging, &new_distribution.logging).unwrap_or_default();
+                            ops.push(connector_op!(
+                                CloudFrontConnectorOp::UpdateDistributionLogging {
+                                    logging: new_distribution.logging.clone(),
+                                },
+                                format!("Update access logging for CloudFront distribution `{}`\n{}", distribution_id, diff)
+                            ));
+                        }
+
+                        if old_distribution.anycast_ip_list_id != new_distribution.anycast_ip_list_id {
+                            let cost_warning = anycast_ip_cost_warning(Some(&old_distribution), &new_distribution);
+                            ops.push(connector_op!(
+                                CloudFrontConnectorOp::UpdateDistributionAnycastIpList {
+                                    anycast_ip_list_id: new_distribution.anycast_ip_list_id.clone(),
+                                },
+                                format!(
+                                    "Set Anycast static IP list for CloudFront distribution `{}` to {:?}{}",
+                                    distribution_id, new_distribution.anycast_ip_list_id, cost_warning
+                                )
+                            ));
+                        }
+
+                        if old_distribution.restrictions != new_distribution.restrictions {
+                            let diff = diff_ron_values(&old_distribution.restrictions, &new_distribution.restrictions)
+                                .unwrap_or_default();
+                            ops.push(connector_op!(
+                                CloudFrontConnectorOp::UpdateDistributionRestrictions {
+                                    restrictions: new_distribution.restrictions.clone(),
+                                },
+                                format!(
+                                    "Update geo restrictions for CloudFront distribution `{}`\n{}",
+                                    distribution_id, diff
+                                )
+                            ));
+                        }
+
+                        if old_distribution.tenant_config != new_distribution.tenant_config {
+                            let diff = diff_ron_values(&old_distribution.tenant_config, &new_distribution.tenant_config)
+                                .unwrap_or_default();
+                            ops.push(connector_op!(
+                                CloudFrontConnectorOp::UpdateDistributionTenantConfig {
+                                    tenant_config: new_distribution.tenant_config.clone(),
+                                },
+                                format!(
+                                    "Update tenant parameters for CloudFront distribution `{}`\n{}",
+                                    distribution_id, diff
+                                )
+                            ));
+                        }
+
+                        if old_distribution.continuous_deployment_policy_id != new_distribution.continuous_deployment_policy_id {
+                            ops.push(connector_op!(
+                                CloudFrontConnectorOp::UpdateDistributionContinuousDeploymentPolicy {
+                                    continuous_deployment_policy_id: new_distribution.continuous_deployment_policy_id.clone(),
+                                },
+                                format!(
+                                    "Set continuous deployment policy for CloudFront distribution `{}` to {:?}",
+                                    distribution_id, new_distribution.continuous_deployment_policy_id
+                                )
+                            ));
+                        }
+
                         // Handle enable/disable operations
                         if old_distribution.enabled && !new_distribution.enabled {
                             ops.push(connector_op!(
@@ -202,11 +399,44 @@ impl CloudFrontConnector {
                 }
             }
 
+            CloudFrontResourceAddress::OriginAccessIdentity { oai_id } => match (current, desired) {
+                (None, None) => Ok(vec![]),
+                (None, Some(new_oai)) => {
+                    let new_oai: OriginAccessIdentity = RON.from_str(&new_oai)?;
+                    Ok(vec![connector_op!(
+                        CloudFrontConnectorOp::CreateOriginAccessIdentity(new_oai),
+                        format!("Create new CloudFront origin access identity {}", oai_id)
+                    )])
+                }
+                (Some(_old_oai), None) => Ok(vec![connector_op!(
+                    CloudFrontConnectorOp::DeleteOriginAccessIdentity,
+                    format!(
+                        "DELETE CloudFront origin access identity {}. S3 origins that use it must be moved to an origin access control first.",
+                        oai_id
+                    )
+                )]),
+                (Some(old_oai), Some(new_oai)) => {
+                    let old_oai: OriginAccessIdentity = RON.from_str(&old_oai)?;
+                    let new_oai: OriginAccessIdentity = RON.from_str(&new_oai)?;
+
+                    if old_oai == new_oai {
+                        return Ok(vec![]);
+                    }
+
+                    let diff = diff_ron_values(&old_oai, &new_oai).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        CloudFrontConnectorOp::UpdateOriginAccessIdentity(new_oai),
+                        format!("Update CloudFront origin access identity `{}`\n{}", oai_id, diff)
+                    )])
+                }
+            },
+
             CloudFrontResourceAddress::CachePolicy { policy_id } => {
                 match (current, desired) {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_policy)) => {
                         let new_policy: CachePolicy = RON.from_str(&new_policy)?;
+                        check_cache_policy(&policy_id, &new_policy)?;
                         Ok(vec![connector_op!(
                             CloudFrontConnectorOp::CreateCachePolicy(new_policy),
                             format!("Create new CloudFront cache policy {}", policy_id)
@@ -219,6 +449,7 @@ impl CloudFrontConnector {
                     (Some(old_policy), Some(new_policy)) => {
                         let old_policy: CachePolicy = RON.from_str(&old_policy)?;
                         let new_policy: CachePolicy = RON.from_str(&new_policy)?;
+                        check_cache_policy(&policy_id, &new_policy)?;
                         let mut ops = Vec::new();
 
                         // Check for cache policy property changes
@@ -256,7 +487,11 @@ impl CloudFrontConnector {
                                         .parameters_in_cache_key_and_forwarded_to_origin
                                         .clone(),
                                 },
-                                format!("Update CloudFront cache policy `{}`", policy_id)
+                                format!(
+                                    "Update CloudFront cache policy `{}`\n{}",
+                                    policy_id,
+                                    diff_ron_values(&old_policy, &new_policy).unwrap_or_default()
+                                )
                             ));
                         }
 
@@ -270,6 +505,7 @@ impl CloudFrontConnector {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_policy)) => {
                         let new_policy: OriginRequestPolicy = RON.from_str(&new_policy)?;
+                        check_origin_request_policy(&policy_id, &new_policy)?;
                         Ok(vec![connector_op!(
                             CloudFrontConnectorOp::CreateOriginRequestPolicy(new_policy),
                             format!("Create new CloudFront origin request policy {}", policy_id)
@@ -282,6 +518,7 @@ impl CloudFrontConnector {
                     (Some(old_policy), Some(new_policy)) => {
                         let old_policy: OriginRequestPolicy = RON.from_str(&old_policy)?;
                         let new_policy: OriginRequestPolicy = RON.from_str(&new_policy)?;
+                        check_origin_request_policy(&policy_id, &new_policy)?;
                         let mut ops = Vec::new();
 
                         // Check for origin request policy property changes
@@ -311,7 +548,11 @@ impl CloudFrontConnector {
                                     headers_config: new_policy.headers_config.clone(),
                                     query_strings_config: new_policy.query_strings_config.clone(),
                                 },
-                                format!("Update CloudFront origin request policy `{}`", policy_id)
+                                format!(
+                                    "Update CloudFront origin request policy `{}`\n{}",
+                                    policy_id,
+                                    diff_ron_values(&old_policy, &new_policy).unwrap_or_default()
+                                )
                             ));
                         }
 
@@ -325,6 +566,7 @@ impl CloudFrontConnector {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_policy)) => {
                         let new_policy: ResponseHeadersPolicy = RON.from_str(&new_policy)?;
+                        check_response_headers_policy(&policy_id, &new_policy)?;
                         Ok(vec![connector_op!(
                             CloudFrontConnectorOp::CreateResponseHeadersPolicy(new_policy),
                             format!("Create new CloudFront response headers policy {}", policy_id)
@@ -337,6 +579,7 @@ impl CloudFrontConnector {
                     (Some(old_policy), Some(new_policy)) => {
                         let old_policy: ResponseHeadersPolicy = RON.from_str(&old_policy)?;
                         let new_policy: ResponseHeadersPolicy = RON.from_str(&new_policy)?;
+                        check_response_headers_policy(&policy_id, &new_policy)?;
                         let mut ops = Vec::new();
 
                         // Check for response headers policy property changes
@@ -366,7 +609,11 @@ impl CloudFrontConnector {
                                     custom_headers_config: new_policy.custom_headers_config.clone(),
                                     security_headers_config: new_policy.security_headers_config.clone(),
                                 },
-                                format!("Update CloudFront response headers policy `{}`", policy_id)
+                                format!(
+                                    "Update CloudFront response headers policy `{}`\n{}",
+                                    policy_id,
+                                    diff_ron_values(&old_policy, &new_policy).unwrap_or_default()
+                                )
                             ));
                         }
 
@@ -380,6 +627,7 @@ impl CloudFrontConnector {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_config)) => {
                         let new_config: RealtimeLogConfig = RON.from_str(&new_config)?;
+                        check_realtime_log_config(&name, &new_config)?;
                         Ok(vec![connector_op!(
                             CloudFrontConnectorOp::CreateRealtimeLogConfig(new_config),
                             format!("Create new CloudFront realtime log config {}", name)
@@ -392,13 +640,20 @@ impl CloudFrontConnector {
                     (Some(old_config), Some(new_config)) => {
                         let old_config: RealtimeLogConfig = RON.from_str(&old_config)?;
                         let new_config: RealtimeLogConfig = RON.from_str(&new_config)?;
+                        check_realtime_log_config(&name, &new_config)?;
                         let mut ops = Vec::new();
 
-                        // Check for realtime log config property changes
-                        let mut config_changed = false;
                         if old_config.name != new_config.name {
-                            config_changed = true;
+                            bail!(
+                                "CloudFront realtime log config `{}`: name can't be changed after creation ({} -> {})",
+                                name,
+                                old_config.name,
+                                new_config.name
+                            );
                         }
+
+                        // Check for realtime log config property changes
+                        let mut config_changed = false;
                         if old_config.end_points != new_config.end_points {
                             config_changed = true;
                         }
@@ -417,7 +672,11 @@ impl CloudFrontConnector {
                                     fields: Some(new_config.fields.clone()),
                                     sampling_rate: Some(new_config.sampling_rate),
                                 },
-                                format!("Update CloudFront realtime log config `{}`", name)
+                                format!(
+                                    "Update CloudFront realtime log config `{}`\n{}",
+                                    name,
+                                    diff_ron_values(&old_config, &new_config).unwrap_or_default()
+                                )
                             ));
                         }
 
@@ -430,7 +689,8 @@ impl CloudFrontConnector {
                 match (current, desired) {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_function)) => {
-                        let new_function: Function = RON.from_str(&new_function)?;
+                        let mut new_function: Function = RON.from_str(&new_function)?;
+                        new_function.function_code = self.checked_function_code(&new_function)?;
                         Ok(vec![connector_op!(
                             CloudFrontConnectorOp::CreateFunction(new_function),
                             format!("Create new CloudFront function {}", name)
@@ -461,7 +721,7 @@ impl CloudFrontConnector {
                             ops.push(connector_op!(
                                 CloudFrontConnectorOp::UpdateFunction {
                                     name: Some(new_function.name.clone()),
-                                    function_code: Some(new_function.function_code.clone()),
+                                    function_code: Some(self.checked_function_code(&new_function)?),
                                     runtime: Some(new_function.runtime.clone()),
                                 },
                                 format!("Update CloudFront function `{}`", name)
@@ -572,6 +832,7 @@ impl CloudFrontConnector {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_config)) => {
                         let new_config: FieldLevelEncryptionConfig = RON.from_str(&new_config)?;
+                        check_field_level_encryption_config(&config_id, &new_config)?;
                         Ok(vec![connector_op!(
                             CloudFrontConnectorOp::CreateFieldLevelEncryptionConfig(new_config),
                             format!("Create new CloudFront field level encryption config {}", config_id)
@@ -584,8 +845,16 @@ impl CloudFrontConnector {
                     (Some(old_config), Some(new_config)) => {
                         let old_config: FieldLevelEncryptionConfig = RON.from_str(&old_config)?;
                         let new_config: FieldLevelEncryptionConfig = RON.from_str(&new_config)?;
+                        check_field_level_encryption_config(&config_id, &new_config)?;
                         let mut ops = Vec::new();
 
+                        if old_config.caller_reference != new_config.caller_reference {
+                            bail!(
+                                "CloudFront field level encryption config `{}`: caller_reference can't be changed after creation",
+                                config_id
+                            );
+                        }
+
                         // Check for field level encryption config property changes
                         let mut config_changed = false;
                         if old_config.comment != new_config.comment {
@@ -605,7 +874,11 @@ impl CloudFrontConnector {
                                     content_type_profile_config: new_config.content_type_profile_config.clone(),
                                     query_arg_profile_config: new_config.query_arg_profile_config.clone(),
                                 },
-                                format!("Update CloudFront field level encryption config `{}`", config_id)
+                                format!(
+                                    "Update CloudFront field level encryption config `{}`\n{}",
+                                    config_id,
+                                    diff_ron_values(&old_config, &new_config).unwrap_or_default()
+                                )
                             ));
                         }
 
@@ -619,6 +892,7 @@ impl CloudFrontConnector {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_profile)) => {
                         let new_profile: FieldLevelEncryptionProfile = RON.from_str(&new_profile)?;
+                        check_field_level_encryption_profile(&profile_id, &new_profile)?;
                         Ok(vec![connector_op!(
                             CloudFrontConnectorOp::CreateFieldLevelEncryptionProfile(new_profile),
                             format!("Create new CloudFront field level encryption profile {}", profile_id)
@@ -631,8 +905,16 @@ impl CloudFrontConnector {
                     (Some(old_profile), Some(new_profile)) => {
                         let old_profile: FieldLevelEncryptionProfile = RON.from_str(&old_profile)?;
                         let new_profile: FieldLevelEncryptionProfile = RON.from_str(&new_profile)?;
+                        check_field_level_encryption_profile(&profile_id, &new_profile)?;
                         let mut ops = Vec::new();
 
+                        if old_profile.caller_reference != new_profile.caller_reference {
+                            bail!(
+                                "CloudFront field level encryption profile `{}`: caller_reference can't be changed after creation",
+                                profile_id
+                            );
+                        }
+
                         // Check for field level encryption profile property changes
                         let mut profile_changed = false;
                         if old_profile.name != new_profile.name {
@@ -652,7 +934,11 @@ impl CloudFrontConnector {
                                     comment: new_profile.comment.clone(),
                                     encryption_entities: Some(new_profile.encryption_entities.clone()),
                                 },
-                                format!("Update CloudFront field level encryption profile `{}`", profile_id)
+                                format!(
+                                    "Update CloudFront field level encryption profile `{}`\n{}",
+                                    profile_id,
+                                    diff_ron_values(&old_profile, &new_profile).unwrap_or_default()
+                                )
                             ));
                         }
 
@@ -722,6 +1008,585 @@ impl CloudFrontConnector {
                     }
                 }
             }
+
+            CloudFrontResourceAddress::DistributionTenant { tenant_id } => {
+                match (current, desired) {
+                    (None, None) => Ok(vec![]),
+                    (None, Some(new_tenant)) => {
+                        let mut new_tenant: DistributionTenant = RON.from_str(&new_tenant)?;
+                        check_tenant_customizations(&tenant_id, &mut new_tenant)?;
+                        Ok(vec![connector_op!(
+                            CloudFrontConnectorOp::CreateDistributionTenant(new_tenant.clone()),
+                            format!(
+                                "Create new CloudFront distribution tenant {} for distribution `{}` serving {}",
+                                tenant_id,
+                                new_tenant.distribution_id,
+                                new_tenant.domains.join(", ")
+                            )
+                        )])
+                    }
+                    (Some(_old_tenant), None) => Ok(vec![connector_op!(
+                        CloudFrontConnectorOp::DeleteDistributionTenant,
+                        format!("DELETE CloudFront distribution tenant {}", tenant_id)
+                    )]),
+                    (Some(old_tenant), Some(new_tenant)) => {
+                        let old_tenant: DistributionTenant = RON.from_str(&old_tenant)?;
+                        let mut new_tenant: DistributionTenant = RON.from_str(&new_tenant)?;
+                        check_tenant_customizations(&tenant_id, &mut new_tenant)?;
+                        let mut ops = Vec::new();
+
+                        if old_tenant.name != new_tenant.name {
+                            bail!(
+                                "CloudFront distribution tenant `{}`: name can't be changed after creation ({} -> {})",
+                                tenant_id,
+                                old_tenant.name,
+                                new_tenant.name
+                            );
+                        }
+
+                        if old_tenant.tags != new_tenant.tags {
+                            let diff = diff_ron_values(&old_tenant.tags, &new_tenant.tags).unwrap_or_default();
+                            ops.push(connector_op!(
+                                CloudFrontConnectorOp::UpdateTags {
+                                    old_tags: old_tenant.tags.clone(),
+                                    new_tags: new_tenant.tags.clone()
+                                },
+                                format!("Modify tags for CloudFront distribution tenant `{}`\n{}", tenant_id, diff)
+                            ));
+                        }
+
+                        // Everything else is set together through UpdateDistributionTenant
+                        let old_settings = DistributionTenant {
+                            tags: new_tenant.tags.clone(),
+                            ..old_tenant
+                        };
+                        if old_settings != new_tenant {
+                            let diff = diff_ron_values(&old_settings, &new_tenant).unwrap_or_default();
+                            ops.push(connector_op!(
+                                CloudFrontConnectorOp::UpdateDistributionTenant(new_tenant),
+                                format!("Update CloudFront distribution tenant `{}`\n{}", tenant_id, diff)
+                            ));
+                        }
+
+                        Ok(ops)
+                    }
+                }
+            }
+
+            CloudFrontResourceAddress::ConnectionGroup { connection_group_id } => {
+                match (current, desired) {
+                    (None, None) => Ok(vec![]),
+                    (None, Some(new_group)) => {
+                        let new_group: ConnectionGroup = RON.from_str(&new_group)?;
+                        Ok(vec![connector_op!(
+                            CloudFrontConnectorOp::CreateConnectionGroup(new_group),
+                            format!("Create new CloudFront connection group {}", connection_group_id)
+                        )])
+                    }
+                    (Some(_old_group), None) => Ok(vec![connector_op!(
+                        CloudFrontConnectorOp::DeleteConnectionGroup,
+                        format!("DELETE CloudFront connection group {}", connection_group_id)
+                    )]),
+                    (Some(old_group), Some(new_group)) => {
+                        let old_group: ConnectionGroup = RON.from_str(&old_group)?;
+                        let new_group: ConnectionGroup = RON.from_str(&new_group)?;
+                        let mut ops = Vec::new();
+
+                        if old_group.name != new_group.name {
+                            bail!(
+                                "CloudFront connection group `{}`: name can't be changed after creation ({} -> {})",
+                                connection_group_id,
+                                old_group.name,
+                                new_group.name
+                            );
+                        }
+
+                        if old_group.tags != new_group.tags {
+                            let diff = diff_ron_values(&old_group.tags, &new_group.tags).unwrap_or_default();
+                            ops.push(connector_op!(
+                                CloudFrontConnectorOp::UpdateTags {
+                                    old_tags: old_group.tags.clone(),
+                                    new_tags: new_group.tags.clone()
+                                },
+                                format!(
+                                    "Modify tags for CloudFront connection group `{}`\n{}",
+                                    connection_group_id, diff
+                                )
+                            ));
+                        }
+
+                        if old_group.ipv6_enabled != new_group.ipv6_enabled
+                            || old_group.anycast_ip_list_id != new_group.anycast_ip_list_id
+                            || old_group.enabled != new_group.enabled
+                        {
+                            let old_settings = ConnectionGroup {
+                                tags: new_group.tags.clone(),
+                                ..old_group
+                            };
+                            let diff = diff_ron_values(&old_settings, &new_group).unwrap_or_default();
+                            ops.push(connector_op!(
+                                CloudFrontConnectorOp::UpdateConnectionGroup(new_group),
+                                format!("Update CloudFront connection group `{}`\n{}", connection_group_id, diff)
+                            ));
+                        }
+
+                        Ok(ops)
+                    }
+                }
+            }
+
+            CloudFrontResourceAddress::ContinuousDeploymentPolicy { policy_id } => match (current, desired) {
+                (None, None) => Ok(vec![]),
+                (None, Some(new_policy)) => {
+                    let new_policy: ContinuousDeploymentPolicy = RON.from_str(&new_policy)?;
+                    check_continuous_deployment_policy(&policy_id, &new_policy)?;
+                    Ok(vec![connector_op!(
+                        CloudFrontConnectorOp::CreateContinuousDeploymentPolicy(new_policy),
+                        format!("Create new CloudFront continuous deployment policy {}", policy_id)
+                    )])
+                }
+                (Some(_old_policy), None) => Ok(vec![connector_op!(
+                    CloudFrontConnectorOp::DeleteContinuousDeploymentPolicy,
+                    format!(
+                        "DELETE CloudFront continuous deployment policy {}. It must first be detached from its primary distribution.",
+                        policy_id
+                    )
+                )]),
+                (Some(old_policy), Some(new_policy)) => {
+                    let old_policy: ContinuousDeploymentPolicy = RON.from_str(&old_policy)?;
+                    let new_policy: ContinuousDeploymentPolicy = RON.from_str(&new_policy)?;
+                    check_continuous_deployment_policy(&policy_id, &new_policy)?;
+
+                    if old_policy == new_policy {
+                        return Ok(vec![]);
+                    }
+
+                    let diff = diff_ron_values(&old_policy, &new_policy).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        CloudFrontConnectorOp::UpdateContinuousDeploymentPolicy(new_policy),
+                        format!("Update CloudFront continuous deployment policy `{}`\n{}", policy_id, diff)
+                    )])
+                }
+            },
         }
     }
 }
+
+impl CloudFrontConnector {
+    /// Resolves the code to deploy for `function`, which may be inline or in a code file, but not both.
+    fn checked_function_code(&self, function: &Function) -> anyhow::Result<String> {
+        if function.code_file.is_some() && !function.function_code.is_empty() {
+            bail!(
+                "CloudFront function {} sets both function_code and code_file; set only one",
+                function.name
+            );
+        }
+        function_code(&self.prefix, function)
+    }
+}
+
+/// Only multi-tenant distributions have tenants, so only they can define tenant parameters.
+fn check_tenant_config(distribution_id: &str, distribution: &Distribution) -> anyhow::Result<()> {
+    if distribution.tenant_config.is_some() && distribution.connection_mode.as_deref() != Some("tenant-only") {
+        bail!(
+            "CloudFront distribution `{}` sets tenant_config, which requires connection_mode: Some(\"tenant-only\")",
+            distribution_id
+        );
+    }
+    Ok(())
+}
+
+fn check_distribution(distribution_id: &str, distribution: &Distribution) -> anyhow::Result<()> {
+    if let Some(http_version) = &distribution.http_version
+        && !HTTP_VERSIONS.contains(&http_version.as_str())
+    {
+        bail!(
+            "CloudFront distribution `{}` has http_version {}: expected one of {}",
+            distribution_id,
+            http_version,
+            HTTP_VERSIONS.join(", ")
+        );
+    }
+    if let Some(restrictions) = &distribution.restrictions {
+        check_geo_restriction(&format!("CloudFront distribution `{}`", distribution_id), restrictions)?;
+    }
+    // WAFv2 web ACLs for CloudFront live in the global (CLOUDFRONT) scope; WAF Classic ones are plain IDs
+    if let Some(web_acl_id) = &distribution.web_acl_id
+        && web_acl_id.starts_with("arn:")
+        && !web_acl_id.contains(":global/webacl/")
+    {
+        bail!(
+            "CloudFront distribution `{}` has web ACL {}: expected a web ACL with the CLOUDFRONT scope, created in us-east-1",
+            distribution_id,
+            web_acl_id
+        );
+    }
+    if distribution.staging && distribution.continuous_deployment_policy_id.is_some() {
+        bail!(
+            "CloudFront distribution `{}` is a staging distribution: attach the continuous deployment policy to its primary distribution instead",
+            distribution_id
+        );
+    }
+    if let Some(logging) = &distribution.logging
+        && !logging.bucket.ends_with(".s3.amazonaws.com")
+    {
+        bail!(
+            "CloudFront distribution `{}` logs to bucket {}: expected the bucket's domain name, e.g. {}.s3.amazonaws.com",
+            distribution_id,
+            logging.bucket,
+            logging.bucket
+        );
+    }
+    for response in &distribution.custom_error_responses {
+        if !(400..=599).contains(&response.error_code) {
+            bail!(
+                "CloudFront distribution `{}` has a custom error response for {}: expected a 4xx or 5xx error code",
+                distribution_id,
+                response.error_code
+            );
+        }
+        if distribution
+            .custom_error_responses
+            .iter()
+            .filter(|other| other.error_code == response.error_code)
+            .count()
+            > 1
+        {
+            bail!(
+                "CloudFront distribution `{}` has more than one custom error response for {}",
+                distribution_id,
+                response.error_code
+            );
+        }
+        if let Some(path) = &response.response_page_path {
+            if !path.starts_with('/') {
+                bail!(
+                    "CloudFront distribution `{}`: response_page_path {} for error {} must start with /",
+                    distribution_id,
+                    path,
+                    response.error_code
+                );
+            }
+            if response.response_code.is_none() {
+                bail!(
+                    "CloudFront distribution `{}`: the custom error response for {} sets response_page_path, which requires response_code",
+                    distribution_id,
+                    response.error_code
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_cache_policy(policy_id: &str, policy: &CachePolicy) -> anyhow::Result<()> {
+    let Some(parameters) = &policy.parameters_in_cache_key_and_forwarded_to_origin else {
+        return Ok(());
+    };
+    let resource = format!("CloudFront cache policy `{}`", policy_id);
+    check_policy_items(&resource, "headers_config", &parameters.headers_config, CACHE_KEY_HEADER_BEHAVIORS)?;
+    check_policy_items(&resource, "cookies_config", &parameters.cookies_config, CACHE_KEY_COOKIE_BEHAVIORS)?;
+    check_policy_items(
+        &resource,
+        "query_strings_config",
+        &parameters.query_strings_config,
+        CACHE_KEY_QUERY_STRING_BEHAVIORS,
+    )
+}
+
+fn check_origin_request_policy(policy_id: &str, policy: &OriginRequestPolicy) -> anyhow::Result<()> {
+    let resource = format!("CloudFront origin request policy `{}`", policy_id);
+    if let Some(headers_config) = &policy.headers_config {
+        check_policy_items(&resource, "headers_config", headers_config, ORIGIN_REQUEST_HEADER_BEHAVIORS)?;
+    }
+    if let Some(cookies_config) = &policy.cookies_config {
+        check_policy_items(&resource, "cookies_config", cookies_config, ORIGIN_REQUEST_COOKIE_BEHAVIORS)?;
+    }
+    if let Some(query_strings_config) = &policy.query_strings_config {
+        check_policy_items(
+            &resource,
+            "query_strings_config",
+            query_strings_config,
+            ORIGIN_REQUEST_QUERY_STRING_BEHAVIORS,
+        )?;
+    }
+    Ok(())
+}
+
+/// "whitelist" and "allExcept" apply to the listed names, and every other behavior to none of them.
+/// "allViewerAndWhitelistCloudFront" lists the CloudFront headers to add to the viewer's.
+fn check_policy_items(resource: &str, name: &str, config: &PolicyItems, behaviors: &[&str]) -> anyhow::Result<()> {
+    if !behaviors.contains(&config.behavior.as_str()) {
+        bail!(
+            "{} has {} behavior {}: expected one of {}",
+            resource,
+            name,
+            config.behavior,
+            behaviors.join(", ")
+        );
+    }
+    let takes_items = matches!(
+        config.behavior.as_str(),
+        "whitelist" | "allExcept" | "allViewerAndWhitelistCloudFront"
+    );
+    if takes_items && config.items.is_empty() {
+        bail!("{}: {} with behavior {} needs at least one item", resource, name, config.behavior);
+    }
+    if !takes_items && !config.items.is_empty() {
+        bail!("{}: {} with behavior {} can't list items", resource, name, config.behavior);
+    }
+    Ok(())
+}
+
+fn check_response_headers_policy(policy_id: &str, policy: &ResponseHeadersPolicy) -> anyhow::Result<()> {
+    if let Some(cors) = &policy.cors_config {
+        for method in &cors.access_control_allow_methods {
+            if !CORS_METHODS.contains(&method.as_str()) {
+                bail!(
+                    "CloudFront response headers policy `{}` allows CORS method {}: expected one of {}",
+                    policy_id,
+                    method,
+                    CORS_METHODS.join(", ")
+                );
+            }
+        }
+        if cors.access_control_allow_origins.is_empty()
+            || cors.access_control_allow_headers.is_empty()
+            || cors.access_control_allow_methods.is_empty()
+        {
+            bail!(
+                "CloudFront response headers policy `{}`: cors_config needs at least one allowed origin, header and method",
+                policy_id
+            );
+        }
+    }
+    if let Some(security_headers) = &policy.security_headers_config {
+        if let Some(frame_options) = &security_headers.frame_options
+            && !FRAME_OPTIONS.contains(&frame_options.frame_option.as_str())
+        {
+            bail!(
+                "CloudFront response headers policy `{}` has frame_option {}: expected one of {}",
+                policy_id,
+                frame_options.frame_option,
+                FRAME_OPTIONS.join(", ")
+            );
+        }
+        if let Some(referrer_policy) = &security_headers.referrer_policy
+            && !REFERRER_POLICIES.contains(&referrer_policy.referrer_policy.as_str())
+        {
+            bail!(
+                "CloudFront response headers policy `{}` has referrer_policy {}: expected one of {}",
+                policy_id,
+                referrer_policy.referrer_policy,
+                REFERRER_POLICIES.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
+
+fn check_realtime_log_config(name: &str, config: &RealtimeLogConfig) -> anyhow::Result<()> {
+    if config.sampling_rate.fract() != 0.0 || !(1.0..=100.0).contains(&config.sampling_rate) {
+        bail!(
+            "CloudFront realtime log config `{}` has sampling rate {}: expected a whole number from 1 to 100",
+            name,
+            config.sampling_rate
+        );
+    }
+    if config.fields.is_empty() {
+        bail!("CloudFront realtime log config `{}` doesn't log any fields", name);
+    }
+    if config.end_points.is_empty() {
+        bail!("CloudFront realtime log config `{}` has no endpoints", name);
+    }
+    for end_point in &config.end_points {
+        if end_point.stream_type != "Kinesis" || end_point.kinesis_stream_config.is_none() {
+            bail!(
+                "CloudFront realtime log config `{}`: every endpoint must have stream_type \"Kinesis\" and a kinesis_stream_config",
+                name
+            );
+        }
+    }
+    Ok(())
+}
+
+fn check_field_level_encryption_config(config_id: &str, config: &FieldLevelEncryptionConfig) -> anyhow::Result<()> {
+    if config.content_type_profile_config.is_none() && config.query_arg_profile_config.is_none() {
+        bail!(
+            "CloudFront field level encryption config `{}` needs a content_type_profile_config or a query_arg_profile_config",
+            config_id
+        );
+    }
+    if let Some(content_type_config) = &config.content_type_profile_config {
+        for profile in &content_type_config.content_type_profiles {
+            if profile.format != "URLEncoded" {
+                bail!(
+                    "CloudFront field level encryption config `{}` has format {} for content type {}: expected URLEncoded",
+                    config_id,
+                    profile.format,
+                    profile.content_type
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_field_level_encryption_profile(profile_id: &str, profile: &FieldLevelEncryptionProfile) -> anyhow::Result<()> {
+    if profile.encryption_entities.is_empty() {
+        bail!("CloudFront field level encryption profile `{}` has no encryption entities", profile_id);
+    }
+    for entity in &profile.encryption_entities {
+        if entity.field_patterns.is_empty() {
+            bail!(
+                "CloudFront field level encryption profile `{}`: the encryption entity for public key {} has no field patterns",
+                profile_id,
+                entity.public_key_id
+            );
+        }
+    }
+    Ok(())
+}
+
+fn check_continuous_deployment_policy(policy_id: &str, policy: &ContinuousDeploymentPolicy) -> anyhow::Result<()> {
+    if policy.staging_distribution_dns_names.is_empty() {
+        bail!(
+            "CloudFront continuous deployment policy `{}` has no staging_distribution_dns_names",
+            policy_id
+        );
+    }
+    let Some(traffic_config) = &policy.traffic_config else {
+        return Ok(());
+    };
+    if !TRAFFIC_CONFIG_TYPES.contains(&traffic_config.config_type.as_str()) {
+        bail!(
+            "CloudFront continuous deployment policy `{}` has traffic config type {}: expected one of {}",
+            policy_id,
+            traffic_config.config_type,
+            TRAFFIC_CONFIG_TYPES.join(", ")
+        );
+    }
+    match traffic_config.config_type.as_str() {
+        "SingleWeight" => {
+            let Some(weight_config) = &traffic_config.single_weight_config else {
+                bail!(
+                    "CloudFront continuous deployment policy `{}` has a SingleWeight traffic config without a single_weight_config",
+                    policy_id
+                );
+            };
+            if !(0.0..=MAX_STAGING_WEIGHT).contains(&weight_config.weight) {
+                bail!(
+                    "CloudFront continuous deployment policy `{}` has weight {}: expected 0.0 to {}",
+                    policy_id,
+                    weight_config.weight,
+                    MAX_STAGING_WEIGHT
+                );
+            }
+            if let Some(stickiness) = &weight_config.session_stickiness_config {
+                if !(300..=3600).contains(&stickiness.idle_ttl) || !(300..=3600).contains(&stickiness.maximum_ttl) {
+                    bail!(
+                        "CloudFront continuous deployment policy `{}` has session stickiness TTLs {} and {}: expected 300 to 3600 seconds",
+                        policy_id,
+                        stickiness.idle_ttl,
+                        stickiness.maximum_ttl
+                    );
+                }
+                if stickiness.maximum_ttl < stickiness.idle_ttl {
+                    bail!(
+                        "CloudFront continuous deployment policy `{}` has a maximum_ttl shorter than its idle_ttl",
+                        policy_id
+                    );
+                }
+            }
+        }
+        _ => {
+            let Some(header_config) = &traffic_config.single_header_config else {
+                bail!(
+                    "CloudFront continuous deployment policy `{}` has a SingleHeader traffic config without a single_header_config",
+                    policy_id
+                );
+            };
+            if !header_config.header.starts_with("aws-cf-cd-") {
+                bail!(
+                    "CloudFront continuous deployment policy `{}` routes on header {}: expected a header that starts with aws-cf-cd-",
+                    policy_id,
+                    header_config.header
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_geo_restriction(resource: &str, restriction: &GeoRestriction) -> anyhow::Result<()> {
+    if !GEO_RESTRICTION_TYPES.contains(&restriction.restriction_type.as_str()) {
+        bail!(
+            "{} has geo restriction type {}: expected one of {}. Leave restrictions unset to serve every country.",
+            resource,
+            restriction.restriction_type,
+            GEO_RESTRICTION_TYPES.join(", ")
+        );
+    }
+    if restriction.locations.is_empty() {
+        bail!("{} has a {} geo restriction with no locations", resource, restriction.restriction_type);
+    }
+    for location in &restriction.locations {
+        if location.len() != 2 || !location.chars().all(|c| c.is_ascii_uppercase()) {
+            bail!(
+                "{} has geo restriction location {}: expected an ISO 3166-1 alpha-2 country code such as US",
+                resource,
+                location
+            );
+        }
+    }
+    Ok(())
+}
+
+fn check_tenant_customizations(tenant_id: &str, tenant: &mut DistributionTenant) -> anyhow::Result<()> {
+    if let Some(customizations) = &mut tenant.customizations {
+        if let Some(geo_restrictions) = &customizations.geo_restrictions {
+            check_geo_restriction(&format!("CloudFront distribution tenant `{}`", tenant_id), geo_restrictions)?;
+        }
+        normalize_restrictions(&mut customizations.geo_restrictions);
+    }
+    Ok(())
+}
+
+fn normalize_restrictions(restrictions: &mut Option<GeoRestriction>) {
+    if let Some(restrictions) = restrictions {
+        restrictions.locations = sorted_locations(&restrictions.locations);
+    }
+}
+
+fn uses_dedicated_ip_ssl(distribution: &Distribution) -> bool {
+    distribution
+        .viewer_certificate
+        .as_ref()
+        .and_then(|vc| vc.ssl_support_method.as_deref())
+        == Some("vip")
+}
+
+/// Dedicated IP SSL is billed per month regardless of traffic, so call it out whenever a plan starts using it.
+fn dedicated_ip_cost_warning(old: Option<&Distribution>, new: &Distribution) -> String {
+    if uses_dedicated_ip_ssl(new) && !old.is_some_and(uses_dedicated_ip_ssl) {
+        String::from(
+            "\nWARNING: ssl_support_method \"vip\" serves this distribution from dedicated IP addresses, \
+             which is billed at a fixed monthly charge (currently $600/month) on top of usage. \
+             Use \"sni-only\" unless you must support clients without SNI.",
+        )
+    } else {
+        String::new()
+    }
+}
+
+/// Likewise, Anycast static IP lists are billed per month for the reserved addresses.
+fn anycast_ip_cost_warning(old: Option<&Distribution>, new: &Distribution) -> String {
+    if new.anycast_ip_list_id.is_some() && old.is_none_or(|old| old.anycast_ip_list_id.is_none()) {
+        String::from(
+            "\nWARNING: Serving a distribution from an Anycast static IP list incurs an additional monthly charge \
+             for the static IP addresses.",
+        )
+    } else {
+        String::new()
+    }
+}
@@ -0,0 +1,209 @@
+use std::{collections::BTreeSet, path::Path};
+
+use anyhow::{Context, bail};
+use autoschematic_core::{
+    connector::{Resource, ResourceAddress, TaskExecResponse},
+    util::RON,
+};
+use aws_sdk_cloudfront::types::FunctionStage;
+
+use crate::{
+    addr::CloudFrontResourceAddress,
+    resource::{CloudFrontResource, Distribution, Function},
+    task::{CloudFrontTask, CloudFrontTaskAddress, ImportDistribution, PromoteStagingDistribution},
+    util::{function_name_from_arn, get_distribution_config},
+};
+
+use super::CloudFrontConnector;
+
+impl CloudFrontConnector {
+    pub async fn do_task_exec(
+        &self,
+        addr: &Path,
+        body: Vec<u8>,
+        _arg: Option<Vec<u8>>,
+        _state: Option<Vec<u8>>,
+    ) -> anyhow::Result<TaskExecResponse> {
+        let addr = CloudFrontTaskAddress::from_path(addr)?;
+
+        let task = CloudFrontTask::from_bytes(&addr, &body)?;
+        match task {
+            CloudFrontTask::ImportDistribution(import) => self.import_distribution(import).await,
+            CloudFrontTask::PromoteStagingDistribution(promote) => self.promote_staging_distribution(promote).await,
+        }
+    }
+
+    async fn import_distribution(&self, import: ImportDistribution) -> anyhow::Result<TaskExecResponse> {
+        let distribution_addr = CloudFrontResourceAddress::Distribution {
+            distribution_id: import.distribution_id.clone(),
+        };
+        let distribution_path = distribution_addr.to_path_buf();
+
+        let Some(get_resp) = self.do_get(&distribution_path).await? else {
+            bail!("CloudFront distribution {} does not exist", import.distribution_id);
+        };
+        let distribution: Distribution = RON.from_str(std::str::from_utf8(&get_resp.resource_definition)?)?;
+
+        let mut modified_files = Vec::new();
+        let mut skipped_files = Vec::new();
+
+        if self.write_import_file(&distribution_path, &get_resp.resource_definition, import.overwrite)? {
+            modified_files.push(distribution_path);
+        } else {
+            skipped_files.push(distribution_path);
+        }
+
+        // Lambda@Edge functions are managed by the Lambda connector, so only CloudFront Functions are imported here.
+        let function_names: BTreeSet<String> = std::iter::once(&distribution.default_cache_behavior)
+            .chain(distribution.cache_behaviors.iter())
+            .flat_map(|behavior| behavior.function_associations.iter())
+            .map(|association| function_name_from_arn(&association.function_arn))
+            .collect();
+
+        for name in function_names {
+            let function_addr = CloudFrontResourceAddress::Function { name: name.clone() };
+            let function_path = function_addr.to_path_buf();
+            let code_path = function_path.with_extension("js");
+
+            if !import.overwrite && (self.prefix.join(&function_path).exists() || self.prefix.join(&code_path).exists()) {
+                skipped_files.push(function_path);
+                continue;
+            }
+
+            let (function, function_code) = self.get_live_function(&name, &code_path).await?;
+
+            self.write_import_file(&code_path, function_code.as_bytes(), true)?;
+            self.write_import_file(&function_path, &CloudFrontResource::Function(function).to_bytes()?, true)?;
+            modified_files.push(function_path);
+            modified_files.push(code_path);
+        }
+
+        let mut friendly_message = format!(
+            "Imported CloudFront distribution `{}` into {} files",
+            import.distribution_id,
+            modified_files.len()
+        );
+        for path in &modified_files {
+            friendly_message.push_str(&format!("\n  {}", path.display()));
+        }
+        if !skipped_files.is_empty() {
+            friendly_message.push_str("\nSkipped files that already exist (set `overwrite: true` to replace them):");
+            for path in &skipped_files {
+                friendly_message.push_str(&format!("\n  {}", path.display()));
+            }
+        }
+
+        Ok(TaskExecResponse {
+            modified_files: if modified_files.is_empty() { None } else { Some(modified_files) },
+            friendly_message: Some(friendly_message),
+            ..Default::default()
+        })
+    }
+
+    async fn promote_staging_distribution(&self, promote: PromoteStagingDistribution) -> anyhow::Result<TaskExecResponse> {
+        let client = self.get_or_init_client().await?;
+
+        let (primary_etag, primary_config) = get_distribution_config(&promote.distribution_id, &client).await?;
+        let (staging_etag, staging_config) = get_distribution_config(&promote.staging_distribution_id, &client).await?;
+
+        if staging_config.staging() != Some(true) {
+            bail!(
+                "CloudFront distribution {} isn't a staging distribution",
+                promote.staging_distribution_id
+            );
+        }
+        if primary_config.continuous_deployment_policy_id().unwrap_or_default().is_empty() {
+            bail!(
+                "CloudFront distribution {} has no continuous deployment policy, so it can't have a staging distribution",
+                promote.distribution_id
+            );
+        }
+
+        // The update is conditional on both distributions being unchanged since they were read
+        client
+            .update_distribution_with_staging_config()
+            .id(&promote.distribution_id)
+            .staging_distribution_id(&promote.staging_distribution_id)
+            .if_match(format!("{}, {}", primary_etag, staging_etag))
+            .send()
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to promote staging distribution {} to {}",
+                    promote.staging_distribution_id, promote.distribution_id
+                )
+            })?;
+
+        let distribution_path = CloudFrontResourceAddress::Distribution {
+            distribution_id: promote.distribution_id.clone(),
+        }
+        .to_path_buf();
+        let Some(get_resp) = self.do_get(&distribution_path).await? else {
+            bail!("CloudFront distribution {} does not exist", promote.distribution_id);
+        };
+        self.write_import_file(&distribution_path, &get_resp.resource_definition, true)?;
+
+        Ok(TaskExecResponse {
+            modified_files: Some(vec![distribution_path.clone()]),
+            friendly_message: Some(format!(
+                "Promoted staging distribution `{}` to CloudFront distribution `{}`, and wrote its new config to {}",
+                promote.staging_distribution_id,
+                promote.distribution_id,
+                distribution_path.display()
+            )),
+            ..Default::default()
+        })
+    }
+
+    /// Fetches the published (LIVE) version of a CloudFront Function, which is the version that
+    /// distributions run. The returned Function reads its code from `code_path`.
+    async fn get_live_function(&self, name: &str, code_path: &Path) -> anyhow::Result<(Function, String)> {
+        let client = self.get_or_init_client().await?;
+
+        let code_resp = client
+            .get_function()
+            .name(name)
+            .stage(FunctionStage::Live)
+            .send()
+            .await
+            .with_context(|| format!("Failed to get the LIVE code of CloudFront function {}", name))?;
+        let function_code = String::from_utf8(code_resp.function_code.unwrap_or_default().into_inner())
+            .with_context(|| format!("CloudFront function {} has code that isn't valid UTF-8", name))?;
+
+        let runtime = client
+            .describe_function()
+            .name(name)
+            .stage(FunctionStage::Live)
+            .send()
+            .await?
+            .function_summary
+            .and_then(|summary| summary.function_config)
+            .map(|config| config.runtime.as_str().to_string())
+            .unwrap_or_else(|| String::from("cloudfront-js-1.0"));
+
+        let function = Function {
+            name: name.to_string(),
+            function_code: String::new(),
+            code_file: Some(code_path.to_string_lossy().to_string()),
+            runtime,
+        };
+
+        Ok((function, function_code))
+    }
+
+    /// Writes `contents` to `path` under the repository, unless the file exists and `overwrite` isn't set.
+    /// Returns whether the file was written.
+    fn write_import_file(&self, path: &Path, contents: &[u8], overwrite: bool) -> anyhow::Result<bool> {
+        let full_path = self.prefix.join(path);
+        if full_path.exists() && !overwrite {
+            return Ok(false);
+        }
+
+        if let Some(parent) = full_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&full_path, contents)?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,73 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::replay::{fixture, unrecorded, with_cassette};
+use autoschematic_core::{
+    connector::{ConnectorOp, Resource, ResourceAddress},
+    util::RON,
+};
+
+use crate::{
+    addr::CloudFrontResourceAddress,
+    op::CloudFrontConnectorOp,
+    resource::{CloudFrontResource, OriginAccessControl},
+};
+
+use super::CloudFrontConnector;
+
+fn cassette(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/cassettes").join(name)
+}
+
+fn connector() -> CloudFrontConnector {
+    CloudFrontConnector {
+        prefix: std::env::temp_dir().join("autoschematic-connector-aws-cloudfront-test"),
+        ..Default::default()
+    }
+}
+
+/// Records against an origin access control (`oac_id`) named `assets`, with the description
+/// `Assets bucket`.
+#[tokio::test]
+async fn update_origin_access_control() -> anyhow::Result<()> {
+    let cassette = cassette("update_origin_access_control.json");
+    if unrecorded(&cassette)? {
+        return Ok(());
+    }
+    let oac_id = fixture(&cassette, "oac_id")?;
+
+    with_cassette(&cassette, async {
+        let connector = connector();
+        let addr = CloudFrontResourceAddress::OriginAccessControl { oac_id: oac_id.clone() }
+        .to_path_buf();
+
+        let current = connector.do_get(&addr).await?.expect("origin access control exists");
+        let oac: OriginAccessControl = RON.from_str(str::from_utf8(&current.resource_definition)?)?;
+        assert_eq!(oac.name, "assets");
+        assert_eq!(oac.description.as_deref(), Some("Assets bucket"));
+
+        let desired = CloudFrontResource::OriginAccessControl(OriginAccessControl {
+            description: Some(String::from("Signs requests to the assets bucket")),
+            ..oac
+        })
+        .to_bytes()?;
+
+        let ops = connector
+            .do_plan(&addr, Some(current.resource_definition), Some(desired))
+            .await?;
+        assert_eq!(ops.len(), 1);
+        assert!(matches!(
+            CloudFrontConnectorOp::from_str(&ops[0].op_definition)?,
+            CloudFrontConnectorOp::UpdateOriginAccessControl { description: Some(description), .. }
+                if description == "Signs requests to the assets bucket"
+        ));
+
+        let output = connector.do_op_exec(&addr, &ops[0].op_definition).await?;
+        assert_eq!(
+            output.friendly_message,
+            Some(format!("Updated CloudFront origin access control `{}`", oac_id))
+        );
+
+        anyhow::Ok(())
+    })
+    .await?
+}
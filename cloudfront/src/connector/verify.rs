@@ -0,0 +1,220 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, bail};
+use aws_sdk_cloudwatch::{
+    primitives::DateTime,
+    types::{Dimension, Statistic},
+};
+
+use crate::{config::PostDeployVerification, op::CloudFrontConnectorOp};
+
+use super::CloudFrontConnector;
+
+const DEPLOY_POLL_INTERVAL: Duration = Duration::from_secs(30);
+/// Distribution updates usually take a few minutes to reach every edge location.
+const DEPLOY_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+const INVALIDATION_POLL_INTERVAL: Duration = Duration::from_secs(10);
+const INVALIDATION_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Ops that change a live distribution's behaviour, and so are followed by post-deploy verification.
+pub fn is_distribution_update(op: &CloudFrontConnectorOp) -> bool {
+    matches!(
+        op,
+        CloudFrontConnectorOp::UpdateDistribution { .. }
+            | CloudFrontConnectorOp::UpdateDistributionOrigins { .. }
+            | CloudFrontConnectorOp::UpdateDistributionAliases { .. }
+            | CloudFrontConnectorOp::UpdateDistributionDefaultCacheBehavior { .. }
+            | CloudFrontConnectorOp::UpdateDistributionCacheBehaviors { .. }
+            | CloudFrontConnectorOp::UpdateDistributionCustomErrorResponses { .. }
+            | CloudFrontConnectorOp::UpdateDistributionViewerCertificate { .. }
+            | CloudFrontConnectorOp::AssociateWebAcl { .. }
+            | CloudFrontConnectorOp::DisassociateWebAcl
+            | CloudFrontConnectorOp::UpdateDistributionLogging { .. }
+            | CloudFrontConnectorOp::UpdateDistributionAnycastIpList { .. }
+            | CloudFrontConnectorOp::UpdateDistributionRestrictions { .. }
+            | CloudFrontConnectorOp::UpdateDistributionTenantConfig { .. }
+            | CloudFrontConnectorOp::UpdateDistributionContinuousDeploymentPolicy { .. }
+            | CloudFrontConnectorOp::EnableDistribution
+    )
+}
+
+/// Ops that CloudFront rejects, or that would conflict, while an earlier change to the distribution is still deploying.
+pub fn waits_for_deployment(op: &CloudFrontConnectorOp) -> bool {
+    is_distribution_update(op)
+        || matches!(
+            op,
+            CloudFrontConnectorOp::DisableDistribution | CloudFrontConnectorOp::DeleteDistribution
+        )
+}
+
+impl PostDeployVerification {
+    pub fn applies_to(&self, distribution_id: &str) -> bool {
+        match &self.distribution_ids {
+            Some(distribution_ids) => distribution_ids.iter().any(|id| id == distribution_id),
+            None => true,
+        }
+    }
+}
+
+impl CloudFrontConnector {
+    /// Waits for the distribution to finish deploying, watches it for the bake window, then fails
+    /// if its average 5xx error rate or origin latency over the window exceeded the thresholds.
+    /// Returns a summary of the metrics for the op's friendly message.
+    pub async fn verify_distribution_deployment(
+        &self,
+        distribution_id: &str,
+        verification: &PostDeployVerification,
+    ) -> anyhow::Result<String> {
+        self.wait_for_distribution_deployed(distribution_id)
+            .await
+            .context("Post-deploy verification could not run")?;
+
+        let start_secs = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        tokio::time::sleep(Duration::from_secs(verification.bake_window_seconds)).await;
+        let end_secs = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        let mut summary = Vec::new();
+        let mut failures = Vec::new();
+
+        let checks = [
+            ("5xxErrorRate", verification.max_5xx_error_rate, "%"),
+            ("OriginLatency", verification.max_origin_latency_ms, "ms"),
+        ];
+
+        for (metric_name, threshold, unit) in checks {
+            let Some(threshold) = threshold else {
+                continue;
+            };
+
+            match self
+                .get_distribution_metric_average(distribution_id, metric_name, start_secs, end_secs)
+                .await?
+            {
+                Some(average) => {
+                    summary.push(format!(
+                        "{}: {:.2}{} (threshold {}{})",
+                        metric_name, average, unit, threshold, unit
+                    ));
+                    if average > threshold {
+                        failures.push(format!(
+                            "{} averaged {:.2}{}, above the threshold of {}{}",
+                            metric_name, average, unit, threshold, unit
+                        ));
+                    }
+                }
+                // No requests reached the distribution during the window
+                None => summary.push(format!("{}: no datapoints", metric_name)),
+            }
+        }
+
+        if !failures.is_empty() {
+            bail!(
+                "Post-deploy verification failed for CloudFront distribution `{}` over a {}s bake window: {}. The update has already been applied; revert it to roll back.",
+                distribution_id,
+                verification.bake_window_seconds,
+                failures.join("; ")
+            );
+        }
+
+        Ok(format!(
+            "Post-deploy verification passed over a {}s bake window: {}",
+            verification.bake_window_seconds,
+            summary.join(", ")
+        ))
+    }
+
+    /// Waits until the distribution's status is Deployed, which it already is unless a change is still
+    /// reaching the edge locations.
+    pub async fn wait_for_distribution_deployed(&self, distribution_id: &str) -> anyhow::Result<()> {
+        let client = self.get_or_init_client().await?;
+
+        let deadline = SystemTime::now() + DEPLOY_TIMEOUT;
+        loop {
+            let resp = client.get_distribution().id(distribution_id).send().await?;
+            let status = resp.distribution().context("No distribution in response")?.status();
+            if status == "Deployed" {
+                return Ok(());
+            }
+            if SystemTime::now() > deadline {
+                bail!(
+                    "CloudFront distribution `{}` was still {} after {} minutes",
+                    distribution_id,
+                    status,
+                    DEPLOY_TIMEOUT.as_secs() / 60
+                );
+            }
+            tracing::info!("Waiting for CloudFront distribution `{}` to deploy ({})", distribution_id, status);
+            tokio::time::sleep(DEPLOY_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Waits until the invalidation's status is Completed. Returns how long that took.
+    pub async fn wait_for_invalidation(&self, distribution_id: &str, invalidation_id: &str) -> anyhow::Result<Duration> {
+        let client = self.get_or_init_client().await?;
+
+        let start = SystemTime::now();
+        let deadline = start + INVALIDATION_TIMEOUT;
+        loop {
+            let resp = client
+                .get_invalidation()
+                .distribution_id(distribution_id)
+                .id(invalidation_id)
+                .send()
+                .await?;
+            let status = resp.invalidation().context("No invalidation in response")?.status();
+            if status == "Completed" {
+                return Ok(start.elapsed().unwrap_or_default());
+            }
+            if SystemTime::now() > deadline {
+                bail!(
+                    "Invalidation `{}` for CloudFront distribution `{}` was still {} after {} minutes",
+                    invalidation_id,
+                    distribution_id,
+                    status,
+                    INVALIDATION_TIMEOUT.as_secs() / 60
+                );
+            }
+            tokio::time::sleep(INVALIDATION_POLL_INTERVAL).await;
+        }
+    }
+
+    /// The average of a distribution metric between `start_secs` and `end_secs`, or None if there were no datapoints.
+    async fn get_distribution_metric_average(
+        &self,
+        distribution_id: &str,
+        metric_name: &str,
+        start_secs: u64,
+        end_secs: u64,
+    ) -> anyhow::Result<Option<f64>> {
+        let client = self.get_or_init_cloudwatch_client().await?;
+
+        // CloudFront metrics have a one-minute resolution, so the period has to be a multiple of 60
+        let period = (end_secs - start_secs).div_ceil(60).max(1) * 60;
+
+        let resp = client
+            .get_metric_statistics()
+            .namespace("AWS/CloudFront")
+            .metric_name(metric_name)
+            .dimensions(Dimension::builder().name("DistributionId").value(distribution_id).build())
+            .dimensions(Dimension::builder().name("Region").value("Global").build())
+            .start_time(DateTime::from_secs(start_secs as i64))
+            .end_time(DateTime::from_secs(end_secs as i64))
+            .period(period as i32)
+            .statistics(Statistic::Average)
+            .send()
+            .await?;
+
+        let averages: Vec<f64> = resp
+            .datapoints
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|d| d.average)
+            .collect();
+
+        if averages.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(averages.iter().sum::<f64>() / averages.len() as f64))
+    }
+}
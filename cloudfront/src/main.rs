@@ -1,5 +1,8 @@
+use addr::CloudFrontResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
 use autoschematic_core::tarpc_bridge::tarpc_connector_main;
 use connector::CloudFrontConnector;
+use task::CloudFrontTaskAddress;
 
 mod connector;
 // pub mod client_cache;
@@ -9,10 +12,18 @@ mod op;
 // pub mod op_impl;
 mod resource;
 mod tags;
+mod task;
 mod util;
 
 #[tokio::main]
 pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let mut patterns = CloudFrontResourceAddress::checked_address_patterns()?;
+        patterns.extend(CloudFrontTaskAddress::checked_address_patterns()?);
+        print!("{}", render_address_docs("aws/cloudfront", &patterns));
+        return Ok(());
+    }
+
     tarpc_connector_main::<CloudFrontConnector>().await?;
     Ok(())
 }
@@ -1,13 +1,14 @@
 use autoschematic_core::{connector::ConnectorOp, util::RON};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 use crate::tags::Tags;
 
 use super::resource::{
-    CacheBehavior, CachePolicy, Distribution, EndPoint, FieldLevelEncryptionConfig, FieldLevelEncryptionProfile, Function,
-    KeyGroup, Origin, OriginAccessControl, OriginRequestPolicy, PublicKey, RealtimeLogConfig, ResponseHeadersPolicy,
-    StreamingDistribution,
+    CacheBehavior, CacheKeyParameters, CachePolicy, ConnectionGroup, ContentTypeProfileConfig, ContinuousDeploymentPolicy,
+    CorsConfig, CustomErrorResponse, CustomHeader, Distribution, DistributionTenant, EncryptionEntity, EndPoint,
+    FieldLevelEncryptionConfig, FieldLevelEncryptionProfile, Function, GeoRestriction, KeyGroup, LoggingConfig, Origin,
+    OriginAccessControl, OriginAccessIdentity, OriginRequestPolicy, PolicyItems, PublicKey, QueryArgProfileConfig,
+    RealtimeLogConfig, ResponseHeadersPolicy, SecurityHeadersConfig, StreamingDistribution, TenantConfig, ViewerCertificate,
 };
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -18,6 +19,8 @@ pub enum CloudFrontConnectorOp {
         default_root_object: Option<String>,
         comment: Option<String>,
         price_class: Option<String>,
+        http_version: Option<String>,
+        is_ipv6_enabled: bool,
     },
     UpdateDistributionOrigins {
         origins: Vec<Origin>,
@@ -31,11 +34,39 @@ pub enum CloudFrontConnectorOp {
     UpdateDistributionCacheBehaviors {
         cache_behaviors: Vec<CacheBehavior>,
     },
+    UpdateDistributionCustomErrorResponses {
+        custom_error_responses: Vec<CustomErrorResponse>,
+    },
+    UpdateDistributionViewerCertificate {
+        viewer_certificate: Option<ViewerCertificate>,
+    },
+    AssociateWebAcl {
+        web_acl_id: String,
+    },
+    DisassociateWebAcl,
+    UpdateDistributionLogging {
+        logging: Option<LoggingConfig>,
+    },
+    UpdateDistributionAnycastIpList {
+        anycast_ip_list_id: Option<String>,
+    },
+    UpdateDistributionRestrictions {
+        restrictions: Option<GeoRestriction>,
+    },
+    UpdateDistributionTenantConfig {
+        tenant_config: Option<TenantConfig>,
+    },
+    UpdateDistributionContinuousDeploymentPolicy {
+        continuous_deployment_policy_id: Option<String>,
+    },
     EnableDistribution,
     DisableDistribution,
     CreateInvalidation {
         paths: Vec<String>,
         caller_reference: String,
+        /// Wait for the invalidation to complete before the op finishes.
+        #[serde(default)]
+        wait: bool,
     },
     DeleteDistribution,
 
@@ -50,6 +81,11 @@ pub enum CloudFrontConnectorOp {
     },
     DeleteOriginAccessControl,
 
+    // Origin Access Identity operations
+    CreateOriginAccessIdentity(OriginAccessIdentity),
+    UpdateOriginAccessIdentity(OriginAccessIdentity),
+    DeleteOriginAccessIdentity,
+
     // Cache Policy operations
     CreateCachePolicy(CachePolicy),
     UpdateCachePolicy {
@@ -58,7 +94,7 @@ pub enum CloudFrontConnectorOp {
         default_ttl: Option<i64>,
         max_ttl: Option<i64>,
         min_ttl: Option<i64>,
-        parameters_in_cache_key_and_forwarded_to_origin: Option<HashMap<String, serde_json::Value>>,
+        parameters_in_cache_key_and_forwarded_to_origin: Option<CacheKeyParameters>,
     },
     DeleteCachePolicy,
 
@@ -67,9 +103,9 @@ pub enum CloudFrontConnectorOp {
     UpdateOriginRequestPolicy {
         name: Option<String>,
         comment: Option<String>,
-        cookies_config: Option<HashMap<String, serde_json::Value>>,
-        headers_config: Option<HashMap<String, serde_json::Value>>,
-        query_strings_config: Option<HashMap<String, serde_json::Value>>,
+        cookies_config: Option<PolicyItems>,
+        headers_config: Option<PolicyItems>,
+        query_strings_config: Option<PolicyItems>,
     },
     DeleteOriginRequestPolicy,
 
@@ -78,9 +114,9 @@ pub enum CloudFrontConnectorOp {
     UpdateResponseHeadersPolicy {
         name: Option<String>,
         comment: Option<String>,
-        cors_config: Option<HashMap<String, serde_json::Value>>,
-        custom_headers_config: Option<HashMap<String, serde_json::Value>>,
-        security_headers_config: Option<HashMap<String, serde_json::Value>>,
+        cors_config: Option<CorsConfig>,
+        custom_headers_config: Vec<CustomHeader>,
+        security_headers_config: Option<SecurityHeadersConfig>,
     },
     DeleteResponseHeadersPolicy,
 
@@ -134,8 +170,8 @@ pub enum CloudFrontConnectorOp {
     CreateFieldLevelEncryptionConfig(FieldLevelEncryptionConfig),
     UpdateFieldLevelEncryptionConfig {
         comment: Option<String>,
-        content_type_profile_config: Option<HashMap<String, serde_json::Value>>,
-        query_arg_profile_config: Option<HashMap<String, serde_json::Value>>,
+        content_type_profile_config: Option<ContentTypeProfileConfig>,
+        query_arg_profile_config: Option<QueryArgProfileConfig>,
     },
     DeleteFieldLevelEncryptionConfig,
 
@@ -144,7 +180,7 @@ pub enum CloudFrontConnectorOp {
     UpdateFieldLevelEncryptionProfile {
         name: Option<String>,
         comment: Option<String>,
-        encryption_entities: Option<HashMap<String, serde_json::Value>>,
+        encryption_entities: Option<Vec<EncryptionEntity>>,
     },
     DeleteFieldLevelEncryptionProfile,
 
@@ -157,6 +193,21 @@ pub enum CloudFrontConnectorOp {
     },
     DeleteStreamingDistribution,
 
+    // Distribution Tenant operations
+    CreateDistributionTenant(DistributionTenant),
+    UpdateDistributionTenant(DistributionTenant),
+    DeleteDistributionTenant,
+
+    // Connection Group operations
+    CreateConnectionGroup(ConnectionGroup),
+    UpdateConnectionGroup(ConnectionGroup),
+    DeleteConnectionGroup,
+
+    // Continuous Deployment Policy operations
+    CreateContinuousDeploymentPolicy(ContinuousDeploymentPolicy),
+    UpdateContinuousDeploymentPolicy(ContinuousDeploymentPolicy),
+    DeleteContinuousDeploymentPolicy,
+
     UpdateTags{ old_tags: Tags, new_tags: Tags }
 }
 
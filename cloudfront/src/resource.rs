@@ -18,9 +18,217 @@ pub struct Distribution {
     pub cache_behaviors: Vec<CacheBehavior>,
     pub comment: Option<String>,
     pub price_class: Option<String>,
+    /// If None, the distribution uses the default *.cloudfront.net certificate.
+    #[serde(default)]
+    pub viewer_certificate: Option<ViewerCertificate>,
+    /// The ID of an Anycast static IP list to serve this distribution from.
+    #[serde(default)]
+    pub anycast_ip_list_id: Option<String>,
+    /// If None, the distribution is served in every country.
+    #[serde(default)]
+    pub restrictions: Option<GeoRestriction>,
+    /// "tenant-only" for a multi-tenant distribution, which serves traffic only through its
+    /// distribution tenants. If None, the distribution is a standard one. Can't be changed after creation.
+    #[serde(default)]
+    pub connection_mode: Option<String>,
+    /// The parameters that tenants of a multi-tenant distribution can set.
+    #[serde(default)]
+    pub tenant_config: Option<TenantConfig>,
+    /// "http1.1", "http2", "http3" or "http2and3". If None, the distribution serves HTTP/2.
+    #[serde(default)]
+    pub http_version: Option<String>,
+    #[serde(default)]
+    pub is_ipv6_enabled: bool,
+    /// The ARN of a WAFv2 web ACL, or the ID of a WAF Classic one.
+    #[serde(default)]
+    pub web_acl_id: Option<String>,
+    /// If None, standard access logging is off.
+    #[serde(default)]
+    pub logging: Option<LoggingConfig>,
+    #[serde(default)]
+    pub custom_error_responses: Vec<CustomErrorResponse>,
+    /// Whether this is a staging distribution, which serves only the traffic that a continuous
+    /// deployment policy routes to it. Can't be changed after creation.
+    #[serde(default)]
+    pub staging: bool,
+    /// The continuous deployment policy that routes some of this distribution's traffic to a staging distribution.
+    #[serde(default)]
+    pub continuous_deployment_policy_id: Option<String>,
     pub tags: HashMap<String, String>,
 }
 
+/// Controls what CloudFront returns to viewers when the origin responds with an HTTP error.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CustomErrorResponse {
+    /// The HTTP 4xx or 5xx status code from the origin.
+    pub error_code: i32,
+    /// The page to serve instead, e.g. `/errors/404.html`. Requires `response_code`.
+    #[serde(default)]
+    pub response_page_path: Option<String>,
+    /// The status code to return to viewers along with the custom page, e.g. "200".
+    #[serde(default)]
+    pub response_code: Option<String>,
+    /// How long CloudFront caches the error, in seconds. If None, CloudFront caches it for 10 seconds.
+    #[serde(default)]
+    pub error_caching_min_ttl: Option<i64>,
+}
+
+/// Standard (legacy) access logging to an S3 bucket.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LoggingConfig {
+    /// The bucket's domain name, e.g. `my-logs.s3.amazonaws.com`.
+    pub bucket: String,
+    #[serde(default)]
+    pub prefix: Option<String>,
+    #[serde(default)]
+    pub include_cookies: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TenantConfig {
+    pub parameter_definitions: Vec<ParameterDefinition>,
+}
+
+/// A parameter that origins and cache behaviors can refer to as `{{name}}`, set per tenant.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ParameterDefinition {
+    pub name: String,
+    pub required: bool,
+    #[serde(default)]
+    pub default_value: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// A tenant of a multi-tenant distribution, serving the distribution's configuration on its own domains.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DistributionTenant {
+    pub name: String,
+    /// The ID of the multi-tenant distribution this tenant uses.
+    pub distribution_id: String,
+    pub domains: Vec<String>,
+    pub enabled: bool,
+    /// Values for the parameters defined in the distribution's tenant_config.
+    #[serde(default)]
+    pub parameters: HashMap<String, String>,
+    #[serde(default)]
+    pub customizations: Option<TenantCustomizations>,
+    /// If None, the tenant is served through the account's default connection group.
+    #[serde(default)]
+    pub connection_group_id: Option<String>,
+    pub tags: HashMap<String, String>,
+}
+
+/// Overrides of the distribution's settings for a single tenant.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TenantCustomizations {
+    #[serde(default)]
+    pub web_acl: Option<WebAclCustomization>,
+    /// An ACM certificate in us-east-1 covering the tenant's domains.
+    #[serde(default)]
+    pub certificate_arn: Option<String>,
+    #[serde(default)]
+    pub geo_restrictions: Option<GeoRestriction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WebAclCustomization {
+    /// "override" to use `arn` instead of the distribution's web ACL, or "disable" to use none.
+    pub action: String,
+    #[serde(default)]
+    pub arn: Option<String>,
+}
+
+/// The routing endpoint and network settings that distribution tenants are served through.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectionGroup {
+    pub name: String,
+    pub ipv6_enabled: bool,
+    #[serde(default)]
+    pub anycast_ip_list_id: Option<String>,
+    pub enabled: bool,
+    pub tags: HashMap<String, String>,
+}
+
+/// Routes part of a primary distribution's traffic to a staging distribution, so that
+/// config changes can be tested on real traffic before they're promoted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ContinuousDeploymentPolicy {
+    /// The domain names of the staging distribution, e.g. `d111111abcdef8.cloudfront.net`.
+    pub staging_distribution_dns_names: Vec<String>,
+    pub enabled: bool,
+    /// If None, no traffic is routed to the staging distribution.
+    #[serde(default)]
+    pub traffic_config: Option<TrafficConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TrafficConfig {
+    /// "SingleWeight" to route a share of all requests, or "SingleHeader" to route requests with a given header.
+    pub config_type: String,
+    #[serde(default)]
+    pub single_weight_config: Option<SingleWeightConfig>,
+    #[serde(default)]
+    pub single_header_config: Option<SingleHeaderConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SingleWeightConfig {
+    /// The share of requests to route to the staging distribution, from 0.0 to 0.15.
+    pub weight: f32,
+    /// If set, requests from a viewer that was routed to the staging distribution keep going there.
+    #[serde(default)]
+    pub session_stickiness_config: Option<SessionStickinessConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SessionStickinessConfig {
+    /// In seconds, from 300 to 3600.
+    pub idle_ttl: i32,
+    /// In seconds, from 300 to 3600. At least idle_ttl.
+    pub maximum_ttl: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SingleHeaderConfig {
+    /// The header name, which must start with `aws-cf-cd-`.
+    pub header: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct GeoRestriction {
+    /// "whitelist" or "blacklist".
+    pub restriction_type: String,
+    /// ISO 3166-1 alpha-2 country codes.
+    pub locations: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ViewerCertificate {
+    pub acm_certificate_arn: Option<String>,
+    pub iam_certificate_id: Option<String>,
+    /// "sni-only", "vip" (dedicated IP addresses), or "static-ip".
+    /// Dedicated IP SSL carries a large fixed monthly charge.
+    pub ssl_support_method: Option<String>,
+    pub minimum_protocol_version: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct Origin {
@@ -57,6 +265,29 @@ pub struct CacheBehavior {
     pub cached_methods: Vec<String>,
     pub compress: bool,
     pub ttl_settings: TtlSettings,
+    #[serde(default)]
+    pub function_associations: Vec<FunctionAssociation>,
+    #[serde(default)]
+    pub lambda_function_associations: Vec<LambdaFunctionAssociation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FunctionAssociation {
+    /// "viewer-request" or "viewer-response".
+    pub event_type: String,
+    pub function_arn: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LambdaFunctionAssociation {
+    /// "viewer-request", "viewer-response", "origin-request" or "origin-response".
+    pub event_type: String,
+    /// Must include the function version, e.g. `arn:aws:lambda:us-east-1:123456789012:function:my-function:3`.
+    pub lambda_function_arn: String,
+    #[serde(default)]
+    pub include_body: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -77,6 +308,14 @@ pub struct OriginAccessControl {
     pub signing_protocol: String,
 }
 
+/// A legacy origin access identity, which S3 origins reference as
+/// `origin-access-identity/cloudfront/{oai_id}`. Origin access controls replace them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct OriginAccessIdentity {
+    pub comment: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct CachePolicy {
@@ -85,7 +324,35 @@ pub struct CachePolicy {
     pub default_ttl: Option<i64>,
     pub max_ttl: Option<i64>,
     pub min_ttl: Option<i64>,
-    pub parameters_in_cache_key_and_forwarded_to_origin: Option<HashMap<String, serde_json::Value>>,
+    /// If None, the cache key holds only the URL path, and nothing else is forwarded to the origin.
+    #[serde(default)]
+    pub parameters_in_cache_key_and_forwarded_to_origin: Option<CacheKeyParameters>,
+}
+
+/// The request values CloudFront includes in the cache key, which it also forwards to the origin.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CacheKeyParameters {
+    #[serde(default)]
+    pub enable_accept_encoding_gzip: bool,
+    #[serde(default)]
+    pub enable_accept_encoding_brotli: bool,
+    /// `behavior` is "none" or "whitelist".
+    pub headers_config: PolicyItems,
+    /// `behavior` is "none", "whitelist", "allExcept" or "all".
+    pub cookies_config: PolicyItems,
+    /// `behavior` is "none", "whitelist", "allExcept" or "all".
+    pub query_strings_config: PolicyItems,
+}
+
+/// A set of header, cookie or query string names, and how a cache or origin request policy applies them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyItems {
+    pub behavior: String,
+    /// The names to include ("whitelist") or exclude ("allExcept"). Empty for "none" and "all".
+    #[serde(default)]
+    pub items: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -93,9 +360,16 @@ pub struct CachePolicy {
 pub struct OriginRequestPolicy {
     pub name: String,
     pub comment: Option<String>,
-    pub cookies_config: Option<HashMap<String, serde_json::Value>>,
-    pub headers_config: Option<HashMap<String, serde_json::Value>>,
-    pub query_strings_config: Option<HashMap<String, serde_json::Value>>,
+    /// `behavior` is "none", "whitelist", "all" or "allExcept". If None, no cookies are forwarded.
+    #[serde(default)]
+    pub cookies_config: Option<PolicyItems>,
+    /// `behavior` is "none", "whitelist", "allViewer", "allViewerAndWhitelistCloudFront" or "allExcept".
+    /// If None, no headers are forwarded.
+    #[serde(default)]
+    pub headers_config: Option<PolicyItems>,
+    /// `behavior` is "none", "whitelist", "all" or "allExcept". If None, no query strings are forwarded.
+    #[serde(default)]
+    pub query_strings_config: Option<PolicyItems>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -103,9 +377,108 @@ pub struct OriginRequestPolicy {
 pub struct ResponseHeadersPolicy {
     pub name: String,
     pub comment: Option<String>,
-    pub cors_config: Option<HashMap<String, serde_json::Value>>,
-    pub custom_headers_config: Option<HashMap<String, serde_json::Value>>,
-    pub security_headers_config: Option<HashMap<String, serde_json::Value>>,
+    #[serde(default)]
+    pub cors_config: Option<CorsConfig>,
+    #[serde(default)]
+    pub custom_headers_config: Vec<CustomHeader>,
+    #[serde(default)]
+    pub security_headers_config: Option<SecurityHeadersConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CorsConfig {
+    pub access_control_allow_origins: Vec<String>,
+    pub access_control_allow_headers: Vec<String>,
+    /// "GET", "POST", "OPTIONS", "PUT", "DELETE", "PATCH", "HEAD" or "ALL".
+    pub access_control_allow_methods: Vec<String>,
+    pub access_control_allow_credentials: bool,
+    #[serde(default)]
+    pub access_control_expose_headers: Vec<String>,
+    #[serde(default)]
+    pub access_control_max_age_sec: Option<i32>,
+    /// Whether these CORS headers replace the ones the origin sends.
+    pub origin_override: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CustomHeader {
+    pub header: String,
+    pub value: String,
+    /// Whether this header replaces one of the same name from the origin.
+    pub r#override: bool,
+}
+
+/// Each header is only added to responses when it's set. `override` replaces the origin's value for it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SecurityHeadersConfig {
+    #[serde(default)]
+    pub content_security_policy: Option<ContentSecurityPolicy>,
+    /// Adds `X-Content-Type-Options: nosniff`.
+    #[serde(default)]
+    pub content_type_options: Option<ContentTypeOptions>,
+    #[serde(default)]
+    pub frame_options: Option<FrameOptions>,
+    #[serde(default)]
+    pub referrer_policy: Option<ReferrerPolicy>,
+    #[serde(default)]
+    pub strict_transport_security: Option<StrictTransportSecurity>,
+    #[serde(default)]
+    pub xss_protection: Option<XssProtection>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ContentSecurityPolicy {
+    pub r#override: bool,
+    pub content_security_policy: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ContentTypeOptions {
+    pub r#override: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FrameOptions {
+    pub r#override: bool,
+    /// "DENY" or "SAMEORIGIN".
+    pub frame_option: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ReferrerPolicy {
+    pub r#override: bool,
+    /// e.g. "strict-origin-when-cross-origin".
+    pub referrer_policy: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StrictTransportSecurity {
+    pub r#override: bool,
+    pub access_control_max_age_sec: i32,
+    #[serde(default)]
+    pub include_subdomains: bool,
+    #[serde(default)]
+    pub preload: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct XssProtection {
+    pub r#override: bool,
+    /// Whether to turn on the browser's XSS filter (`X-XSS-Protection: 1`).
+    pub protection: bool,
+    #[serde(default)]
+    pub mode_block: bool,
+    #[serde(default)]
+    pub report_uri: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -113,22 +486,38 @@ pub struct ResponseHeadersPolicy {
 pub struct RealtimeLogConfig {
     pub name: String,
     pub end_points: Vec<EndPoint>,
+    /// The request fields to include in each log record, e.g. "timestamp", "c-ip", "cs-uri-stem".
     pub fields: Vec<String>,
+    /// The percentage of requests to log, as a whole number from 1 to 100.
     pub sampling_rate: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct EndPoint {
+    /// Always "Kinesis".
     pub stream_type: String,
-    pub kinesis_stream_config: Option<HashMap<String, String>>,
+    pub kinesis_stream_config: Option<KinesisStreamConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct KinesisStreamConfig {
+    /// A role that CloudFront can assume to write to the stream.
+    pub role_arn: String,
+    pub stream_arn: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct Function {
     pub name: String,
+    /// The function's code inline. Leave empty when using `code_file`.
+    #[serde(default)]
     pub function_code: String,
+    /// A .js file in this repository holding the function's code, relative to the repository root.
+    #[serde(default)]
+    pub code_file: Option<String>,
     pub runtime: String,
 }
 
@@ -153,8 +542,47 @@ pub struct PublicKey {
 pub struct FieldLevelEncryptionConfig {
     pub comment: Option<String>,
     pub caller_reference: String,
-    pub content_type_profile_config: Option<HashMap<String, serde_json::Value>>,
-    pub query_arg_profile_config: Option<HashMap<String, serde_json::Value>>,
+    #[serde(default)]
+    pub content_type_profile_config: Option<ContentTypeProfileConfig>,
+    #[serde(default)]
+    pub query_arg_profile_config: Option<QueryArgProfileConfig>,
+}
+
+/// Picks the field-level encryption profile to apply to a request by its content type.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ContentTypeProfileConfig {
+    /// Whether to forward requests whose content type has no profile, rather than reject them.
+    pub forward_when_content_type_is_unknown: bool,
+    #[serde(default)]
+    pub content_type_profiles: Vec<ContentTypeProfile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ContentTypeProfile {
+    /// Always "URLEncoded".
+    pub format: String,
+    #[serde(default)]
+    pub profile_id: Option<String>,
+    pub content_type: String,
+}
+
+/// Lets a request pick its field-level encryption profile through a query argument.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct QueryArgProfileConfig {
+    /// Whether to forward requests whose query argument names no known profile, rather than reject them.
+    pub forward_when_query_arg_profile_is_unknown: bool,
+    #[serde(default)]
+    pub query_arg_profiles: Vec<QueryArgProfile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct QueryArgProfile {
+    pub query_arg: String,
+    pub profile_id: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -163,7 +591,18 @@ pub struct FieldLevelEncryptionProfile {
     pub name: String,
     pub comment: Option<String>,
     pub caller_reference: String,
-    pub encryption_entities: HashMap<String, serde_json::Value>,
+    pub encryption_entities: Vec<EncryptionEntity>,
+}
+
+/// The request body fields to encrypt with one public key.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptionEntity {
+    pub public_key_id: String,
+    /// The name of the provider that holds the private key, used when decrypting.
+    pub provider_id: String,
+    /// Field names to encrypt. A trailing `*` matches any suffix.
+    pub field_patterns: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -196,6 +635,7 @@ pub struct TrustedSigners {
 pub enum CloudFrontResource {
     Distribution(Distribution),
     OriginAccessControl(OriginAccessControl),
+    OriginAccessIdentity(OriginAccessIdentity),
     CachePolicy(CachePolicy),
     OriginRequestPolicy(OriginRequestPolicy),
     ResponseHeadersPolicy(ResponseHeadersPolicy),
@@ -206,6 +646,9 @@ pub enum CloudFrontResource {
     FieldLevelEncryptionConfig(FieldLevelEncryptionConfig),
     FieldLevelEncryptionProfile(FieldLevelEncryptionProfile),
     StreamingDistribution(StreamingDistribution),
+    DistributionTenant(DistributionTenant),
+    ConnectionGroup(ConnectionGroup),
+    ContinuousDeploymentPolicy(ContinuousDeploymentPolicy),
 }
 
 impl Resource for CloudFrontResource {
@@ -220,6 +663,10 @@ impl Resource for CloudFrontResource {
                 Ok(s) => Ok(s.into()),
                 Err(e) => Err(e.into()),
             },
+            CloudFrontResource::OriginAccessIdentity(oai) => match RON.to_string_pretty(&oai, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
             CloudFrontResource::CachePolicy(policy) => match RON.to_string_pretty(&policy, pretty_config) {
                 Ok(s) => Ok(s.into()),
                 Err(e) => Err(e.into()),
@@ -260,6 +707,18 @@ impl Resource for CloudFrontResource {
                 Ok(s) => Ok(s.into()),
                 Err(e) => Err(e.into()),
             },
+            CloudFrontResource::DistributionTenant(tenant) => match RON.to_string_pretty(&tenant, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
+            CloudFrontResource::ConnectionGroup(group) => match RON.to_string_pretty(&group, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
+            CloudFrontResource::ContinuousDeploymentPolicy(policy) => match RON.to_string_pretty(&policy, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
         }
     }
 
@@ -275,6 +734,9 @@ impl Resource for CloudFrontResource {
             CloudFrontResourceAddress::OriginAccessControl { .. } => {
                 Ok(CloudFrontResource::OriginAccessControl(RON.from_str(s)?))
             }
+            CloudFrontResourceAddress::OriginAccessIdentity { .. } => {
+                Ok(CloudFrontResource::OriginAccessIdentity(RON.from_str(s)?))
+            }
             CloudFrontResourceAddress::CachePolicy { .. } => Ok(CloudFrontResource::CachePolicy(RON.from_str(s)?)),
             CloudFrontResourceAddress::OriginRequestPolicy { .. } => {
                 Ok(CloudFrontResource::OriginRequestPolicy(RON.from_str(s)?))
@@ -295,6 +757,11 @@ impl Resource for CloudFrontResource {
             CloudFrontResourceAddress::StreamingDistribution { .. } => {
                 Ok(CloudFrontResource::StreamingDistribution(RON.from_str(s)?))
             }
+            CloudFrontResourceAddress::DistributionTenant { .. } => Ok(CloudFrontResource::DistributionTenant(RON.from_str(s)?)),
+            CloudFrontResourceAddress::ConnectionGroup { .. } => Ok(CloudFrontResource::ConnectionGroup(RON.from_str(s)?)),
+            CloudFrontResourceAddress::ContinuousDeploymentPolicy { .. } => {
+                Ok(CloudFrontResource::ContinuousDeploymentPolicy(RON.from_str(s)?))
+            }
         }
     }
 }
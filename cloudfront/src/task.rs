@@ -0,0 +1,131 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::{PrettyConfig, RON};
+
+#[derive(Debug, Clone)]
+pub enum CloudFrontTaskAddress {
+    ImportDistribution { name: String },
+    PromoteStagingDistribution { name: String },
+}
+
+impl ResourceAddress for CloudFrontTaskAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            CloudFrontTaskAddress::ImportDistribution { name } => {
+                PathBuf::from(format!("aws/cloudfront/tasks/import-distribution/{name}.ron"))
+            }
+            CloudFrontTaskAddress::PromoteStagingDistribution { name } => {
+                PathBuf::from(format!("aws/cloudfront/tasks/promote-staging-distribution/{name}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        let path_components: Vec<&str> = path
+            .components()
+            .map(|s| s.as_os_str().to_str().context("Path component is not valid UTF-8"))
+            .collect::<Result<Vec<&str>, anyhow::Error>>()?;
+
+        match &path_components[..] {
+            ["aws", "cloudfront", "tasks", "import-distribution", name] if name.ends_with(".ron") => {
+                Ok(CloudFrontTaskAddress::ImportDistribution {
+                    name: name.strip_suffix(".ron").context("File name must end with .ron")?.to_string(),
+                })
+            }
+            ["aws", "cloudfront", "tasks", "promote-staging-distribution", name] if name.ends_with(".ron") => {
+                Ok(CloudFrontTaskAddress::PromoteStagingDistribution {
+                    name: name.strip_suffix(".ron").context("File name must end with .ron")?.to_string(),
+                })
+            }
+            _ => Err(anyhow::anyhow!("Invalid CloudFront task address: {}", path.display())),
+        }
+    }
+}
+
+impl DescribeAddresses for CloudFrontTaskAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/cloudfront/tasks/import-distribution/<name>.ron",
+                description: "Task: write the resource files for an existing distribution and its CloudFront Functions",
+                example:     "aws/cloudfront/tasks/import-distribution/www.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudfront/tasks/promote-staging-distribution/<name>.ron",
+                description: "Task: copy a staging distribution's config onto its primary distribution",
+                example:     "aws/cloudfront/tasks/promote-staging-distribution/www.ron",
+            },
+        ]
+    }
+}
+
+/// Writes the complete configuration of an existing distribution to
+/// `aws/cloudfront/distributions/{distribution_id}.ron`: origins, every cache behavior along with its
+/// function associations, geo restrictions and viewer certificate.
+/// Each CloudFront Function associated with a cache behavior is written to
+/// `aws/cloudfront/functions/{name}.ron`, with its live code in an adjacent `{name}.js` code file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ImportDistribution {
+    pub distribution_id: String,
+    /// Replace resource files that already exist. Otherwise they're left as they are.
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+/// Copies the config of a staging distribution onto its primary distribution, once the changes
+/// have been tested on the traffic that a continuous deployment policy routed to it.
+/// Then rewrites `aws/cloudfront/distributions/{distribution_id}.ron` with the promoted config.
+/// The continuous deployment policy stays attached; disable it to stop routing traffic to the staging distribution.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PromoteStagingDistribution {
+    /// The primary distribution.
+    pub distribution_id: String,
+    pub staging_distribution_id: String,
+}
+
+pub enum CloudFrontTask {
+    ImportDistribution(ImportDistribution),
+    PromoteStagingDistribution(PromoteStagingDistribution),
+}
+
+impl Resource for CloudFrontTask {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = PrettyConfig::default().struct_names(true);
+        match self {
+            CloudFrontTask::ImportDistribution(import) => match RON.to_string_pretty(&import, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
+            CloudFrontTask::PromoteStagingDistribution(promote) => match RON.to_string_pretty(&promote, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = CloudFrontTaskAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+        match addr {
+            CloudFrontTaskAddress::ImportDistribution { .. } => Ok(CloudFrontTask::ImportDistribution(RON.from_str(s)?)),
+            CloudFrontTaskAddress::PromoteStagingDistribution { .. } => {
+                Ok(CloudFrontTask::PromoteStagingDistribution(RON.from_str(s)?))
+            }
+        }
+    }
+}
@@ -1,5 +1,37 @@
+use std::path::Path;
+
 use anyhow::Context;
-use aws_sdk_cloudfront::types::DistributionConfig;
+use autoschematic_core::{connector::ResourceAddress, util::RON};
+use aws_sdk_cloudfront::types::{
+    CachePolicyCookieBehavior, CachePolicyCookiesConfig, CachePolicyHeaderBehavior, CachePolicyHeadersConfig,
+    CachePolicyQueryStringBehavior, CachePolicyQueryStringsConfig, Certificate, ContinuousDeploymentPolicyType, CookieNames,
+    CustomErrorResponses, CustomizationActionType, Customizations, DistributionConfig, EventType, FrameOptionsList, FunctionAssociations,
+    GeoRestrictionCustomization, GeoRestrictionType, Headers, LambdaFunctionAssociations, MinimumProtocolVersion,
+    OriginRequestPolicyConfig, OriginRequestPolicyCookieBehavior, OriginRequestPolicyCookiesConfig,
+    OriginRequestPolicyHeaderBehavior, OriginRequestPolicyHeadersConfig, OriginRequestPolicyQueryStringBehavior,
+    OriginRequestPolicyQueryStringsConfig, ParameterDefinitionSchema, ParametersInCacheKeyAndForwardedToOrigin,
+    QueryStringNames, ReferrerPolicyList, ResponseHeadersPolicyAccessControlAllowHeaders,
+    ResponseHeadersPolicyAccessControlAllowMethods, ResponseHeadersPolicyAccessControlAllowMethodsValues,
+    ResponseHeadersPolicyAccessControlAllowOrigins, ResponseHeadersPolicyAccessControlExposeHeaders,
+    ResponseHeadersPolicyConfig, ResponseHeadersPolicyContentSecurityPolicy, ResponseHeadersPolicyContentTypeOptions,
+    ResponseHeadersPolicyCorsConfig, ResponseHeadersPolicyCustomHeader, ResponseHeadersPolicyCustomHeadersConfig,
+    ResponseHeadersPolicyFrameOptions, ResponseHeadersPolicyReferrerPolicy, ResponseHeadersPolicySecurityHeadersConfig,
+    ResponseHeadersPolicyStrictTransportSecurity, ResponseHeadersPolicyXssProtection, Restrictions, SslSupportMethod,
+    StagingDistributionDnsNames, StringSchemaConfig, WebAclCustomization,
+};
+
+use crate::{
+    addr::CloudFrontResourceAddress,
+    resource::{
+        CacheKeyParameters, ContentSecurityPolicy, ContentTypeOptions, ContentTypeProfile, ContentTypeProfileConfig,
+        ContinuousDeploymentPolicy, CorsConfig, CustomErrorResponse, CustomHeader, EncryptionEntity, EndPoint,
+        FieldLevelEncryptionConfig, FieldLevelEncryptionProfile, FrameOptions, Function, FunctionAssociation, GeoRestriction,
+        KinesisStreamConfig, LambdaFunctionAssociation, LoggingConfig, OriginRequestPolicy, ParameterDefinition, PolicyItems,
+        QueryArgProfile, QueryArgProfileConfig, ReferrerPolicy, ResponseHeadersPolicy, SecurityHeadersConfig,
+        SessionStickinessConfig, SingleHeaderConfig, SingleWeightConfig, StrictTransportSecurity, TenantConfig,
+        TenantCustomizations, TrafficConfig, ViewerCertificate, XssProtection,
+    },
+};
 
 pub async fn get_distribution_config(distribution_id: &str, client: &aws_sdk_cloudfront::Client) -> anyhow::Result<(String, DistributionConfig)> {
     let get_response = client.get_distribution_config().id(distribution_id).send().await?;
@@ -8,3 +40,1085 @@ pub async fn get_distribution_config(distribution_id: &str, client: &aws_sdk_clo
     let etag = get_response.e_tag().context("No ETag in response")?;
     Ok((etag.to_string(), config))
 }
+
+/// Converts a viewer certificate into its SDK form. None selects the default CloudFront certificate.
+pub fn build_viewer_certificate(viewer_certificate: &Option<ViewerCertificate>) -> aws_sdk_cloudfront::types::ViewerCertificate {
+    let Some(viewer_certificate) = viewer_certificate else {
+        return aws_sdk_cloudfront::types::ViewerCertificate::builder()
+            .cloud_front_default_certificate(true)
+            .build();
+    };
+
+    aws_sdk_cloudfront::types::ViewerCertificate::builder()
+        .cloud_front_default_certificate(false)
+        .set_acm_certificate_arn(viewer_certificate.acm_certificate_arn.clone())
+        .set_iam_certificate_id(viewer_certificate.iam_certificate_id.clone())
+        .set_ssl_support_method(viewer_certificate.ssl_support_method.as_deref().map(SslSupportMethod::from))
+        .set_minimum_protocol_version(
+            viewer_certificate
+                .minimum_protocol_version
+                .as_deref()
+                .map(MinimumProtocolVersion::from),
+        )
+        .build()
+}
+
+/// Converts a logging config into its SDK form. CloudFront turns logging off with an empty, disabled config.
+pub fn build_logging(logging: &Option<LoggingConfig>) -> aws_sdk_cloudfront::types::LoggingConfig {
+    let Some(logging) = logging else {
+        return aws_sdk_cloudfront::types::LoggingConfig::builder()
+            .enabled(false)
+            .include_cookies(false)
+            .bucket("")
+            .prefix("")
+            .build();
+    };
+
+    aws_sdk_cloudfront::types::LoggingConfig::builder()
+        .enabled(true)
+        .include_cookies(logging.include_cookies)
+        .bucket(&logging.bucket)
+        .prefix(logging.prefix.clone().unwrap_or_default())
+        .build()
+}
+
+/// Reads back a logging config. A disabled config is represented by the absence of one.
+pub fn from_logging(logging: Option<&aws_sdk_cloudfront::types::LoggingConfig>) -> Option<LoggingConfig> {
+    let logging = logging?;
+    if logging.enabled != Some(true) {
+        return None;
+    }
+    Some(LoggingConfig {
+        bucket: logging.bucket.clone().unwrap_or_default(),
+        prefix: logging.prefix.clone().filter(|prefix| !prefix.is_empty()),
+        include_cookies: logging.include_cookies.unwrap_or(false),
+    })
+}
+
+pub fn build_custom_error_responses(custom_error_responses: &[CustomErrorResponse]) -> anyhow::Result<CustomErrorResponses> {
+    let mut items = Vec::new();
+    for response in custom_error_responses {
+        items.push(
+            aws_sdk_cloudfront::types::CustomErrorResponse::builder()
+                .error_code(response.error_code)
+                .set_response_page_path(response.response_page_path.clone())
+                .set_response_code(response.response_code.clone())
+                .set_error_caching_min_ttl(response.error_caching_min_ttl)
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build custom error response: {}", e))?,
+        );
+    }
+
+    CustomErrorResponses::builder()
+        .quantity(items.len() as i32)
+        .set_items(Some(items))
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build custom error responses: {}", e))
+}
+
+pub fn from_custom_error_responses(custom_error_responses: Option<&CustomErrorResponses>) -> Vec<CustomErrorResponse> {
+    custom_error_responses
+        .map(|responses| {
+            responses
+                .items()
+                .iter()
+                .map(|response| CustomErrorResponse {
+                    error_code: response.error_code(),
+                    response_page_path: response.response_page_path().filter(|path| !path.is_empty()).map(String::from),
+                    response_code: response.response_code().filter(|code| !code.is_empty()).map(String::from),
+                    error_caching_min_ttl: response.error_caching_min_ttl(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn no_policy_items() -> PolicyItems {
+    PolicyItems {
+        behavior: String::from("none"),
+        items: Vec::new(),
+    }
+}
+
+fn header_names(items: &[String]) -> anyhow::Result<Headers> {
+    Headers::builder()
+        .quantity(items.len() as i32)
+        .set_items(Some(items.to_vec()))
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build header names: {}", e))
+}
+
+fn cookie_names(items: &[String]) -> anyhow::Result<CookieNames> {
+    CookieNames::builder()
+        .quantity(items.len() as i32)
+        .set_items(Some(items.to_vec()))
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build cookie names: {}", e))
+}
+
+fn query_string_names(items: &[String]) -> anyhow::Result<QueryStringNames> {
+    QueryStringNames::builder()
+        .quantity(items.len() as i32)
+        .set_items(Some(items.to_vec()))
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build query string names: {}", e))
+}
+
+/// Converts cache key parameters into their SDK form. CloudFront requires them on every cache policy,
+/// so None becomes a cache key that holds nothing but the URL path.
+pub fn build_cache_key_parameters(
+    parameters: &Option<CacheKeyParameters>,
+) -> anyhow::Result<ParametersInCacheKeyAndForwardedToOrigin> {
+    let none = no_policy_items();
+    let (gzip, brotli, headers, cookies, query_strings) = match parameters {
+        Some(parameters) => (
+            parameters.enable_accept_encoding_gzip,
+            parameters.enable_accept_encoding_brotli,
+            &parameters.headers_config,
+            &parameters.cookies_config,
+            &parameters.query_strings_config,
+        ),
+        None => (false, false, &none, &none, &none),
+    };
+
+    let headers_config = CachePolicyHeadersConfig::builder()
+        .header_behavior(CachePolicyHeaderBehavior::from(headers.behavior.as_str()))
+        .headers(header_names(&headers.items)?)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build cache key headers config: {}", e))?;
+
+    let cookies_config = CachePolicyCookiesConfig::builder()
+        .cookie_behavior(CachePolicyCookieBehavior::from(cookies.behavior.as_str()))
+        .cookies(cookie_names(&cookies.items)?)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build cache key cookies config: {}", e))?;
+
+    let query_strings_config = CachePolicyQueryStringsConfig::builder()
+        .query_string_behavior(CachePolicyQueryStringBehavior::from(query_strings.behavior.as_str()))
+        .query_strings(query_string_names(&query_strings.items)?)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build cache key query strings config: {}", e))?;
+
+    ParametersInCacheKeyAndForwardedToOrigin::builder()
+        .enable_accept_encoding_gzip(gzip)
+        .enable_accept_encoding_brotli(brotli)
+        .headers_config(headers_config)
+        .cookies_config(cookies_config)
+        .query_strings_config(query_strings_config)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build cache key parameters: {}", e))
+}
+
+/// Reads back cache key parameters. A cache key of just the URL path is represented by their absence.
+pub fn from_cache_key_parameters(parameters: Option<&ParametersInCacheKeyAndForwardedToOrigin>) -> Option<CacheKeyParameters> {
+    let parameters = parameters?;
+
+    let headers_config = parameters.headers_config().map(|config| PolicyItems {
+        behavior: config.header_behavior().as_str().to_string(),
+        items: config.headers().map(|headers| headers.items().to_vec()).unwrap_or_default(),
+    });
+    let cookies_config = parameters.cookies_config().map(|config| PolicyItems {
+        behavior: config.cookie_behavior().as_str().to_string(),
+        items: config.cookies().map(|cookies| cookies.items().to_vec()).unwrap_or_default(),
+    });
+    let query_strings_config = parameters.query_strings_config().map(|config| PolicyItems {
+        behavior: config.query_string_behavior().as_str().to_string(),
+        items: config
+            .query_strings()
+            .map(|query_strings| query_strings.items().to_vec())
+            .unwrap_or_default(),
+    });
+    let none = no_policy_items();
+
+    let parameters = CacheKeyParameters {
+        enable_accept_encoding_gzip: parameters.enable_accept_encoding_gzip(),
+        enable_accept_encoding_brotli: parameters.enable_accept_encoding_brotli().unwrap_or(false),
+        headers_config: headers_config.unwrap_or_else(|| none.clone()),
+        cookies_config: cookies_config.unwrap_or_else(|| none.clone()),
+        query_strings_config: query_strings_config.unwrap_or_else(|| none.clone()),
+    };
+
+    let is_empty = !parameters.enable_accept_encoding_gzip
+        && !parameters.enable_accept_encoding_brotli
+        && parameters.headers_config == none
+        && parameters.cookies_config == none
+        && parameters.query_strings_config == none;
+    if is_empty { None } else { Some(parameters) }
+}
+
+/// Converts an origin request policy into its SDK form. Unset configs forward nothing.
+pub fn build_origin_request_policy_config(policy: &OriginRequestPolicy) -> anyhow::Result<OriginRequestPolicyConfig> {
+    let none = no_policy_items();
+    let headers = policy.headers_config.as_ref().unwrap_or(&none);
+    let cookies = policy.cookies_config.as_ref().unwrap_or(&none);
+    let query_strings = policy.query_strings_config.as_ref().unwrap_or(&none);
+
+    OriginRequestPolicyConfig::builder()
+        .name(&policy.name)
+        .set_comment(policy.comment.clone())
+        .headers_config(
+            OriginRequestPolicyHeadersConfig::builder()
+                .header_behavior(OriginRequestPolicyHeaderBehavior::from(headers.behavior.as_str()))
+                .headers(header_names(&headers.items)?)
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build origin request headers config: {}", e))?,
+        )
+        .cookies_config(
+            OriginRequestPolicyCookiesConfig::builder()
+                .cookie_behavior(OriginRequestPolicyCookieBehavior::from(cookies.behavior.as_str()))
+                .cookies(cookie_names(&cookies.items)?)
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build origin request cookies config: {}", e))?,
+        )
+        .query_strings_config(
+            OriginRequestPolicyQueryStringsConfig::builder()
+                .query_string_behavior(OriginRequestPolicyQueryStringBehavior::from(query_strings.behavior.as_str()))
+                .query_strings(query_string_names(&query_strings.items)?)
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build origin request query strings config: {}", e))?,
+        )
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build origin request policy config: {}", e))
+}
+
+/// Reads back an origin request policy. Configs that forward nothing are represented by their absence.
+pub fn from_origin_request_policy_config(config: OriginRequestPolicyConfig) -> OriginRequestPolicy {
+    let forwarded = |items: PolicyItems| if items == no_policy_items() { None } else { Some(items) };
+
+    OriginRequestPolicy {
+        name: config.name,
+        comment: config.comment,
+        cookies_config: config
+            .cookies_config
+            .map(|cookies| PolicyItems {
+                behavior: cookies.cookie_behavior().as_str().to_string(),
+                items: cookies.cookies().map(|names| names.items().to_vec()).unwrap_or_default(),
+            })
+            .and_then(forwarded),
+        headers_config: config
+            .headers_config
+            .map(|headers| PolicyItems {
+                behavior: headers.header_behavior().as_str().to_string(),
+                items: headers.headers().map(|names| names.items().to_vec()).unwrap_or_default(),
+            })
+            .and_then(forwarded),
+        query_strings_config: config
+            .query_strings_config
+            .map(|query_strings| PolicyItems {
+                behavior: query_strings.query_string_behavior().as_str().to_string(),
+                items: query_strings
+                    .query_strings()
+                    .map(|names| names.items().to_vec())
+                    .unwrap_or_default(),
+            })
+            .and_then(forwarded),
+    }
+}
+
+pub fn build_response_headers_policy_config(policy: &ResponseHeadersPolicy) -> anyhow::Result<ResponseHeadersPolicyConfig> {
+    let cors_config = match &policy.cors_config {
+        Some(cors) => Some(build_cors_config(cors)?),
+        None => None,
+    };
+
+    let custom_headers_config = if policy.custom_headers_config.is_empty() {
+        None
+    } else {
+        let mut items = Vec::new();
+        for header in &policy.custom_headers_config {
+            items.push(
+                ResponseHeadersPolicyCustomHeader::builder()
+                    .header(&header.header)
+                    .value(&header.value)
+                    .r#override(header.r#override)
+                    .build()
+                    .map_err(|e| anyhow::anyhow!("Failed to build custom header {}: {}", header.header, e))?,
+            );
+        }
+        Some(
+            ResponseHeadersPolicyCustomHeadersConfig::builder()
+                .quantity(items.len() as i32)
+                .set_items(Some(items))
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build custom headers config: {}", e))?,
+        )
+    };
+
+    let security_headers_config = match &policy.security_headers_config {
+        Some(security_headers) => Some(build_security_headers_config(security_headers)?),
+        None => None,
+    };
+
+    ResponseHeadersPolicyConfig::builder()
+        .name(&policy.name)
+        .set_comment(policy.comment.clone())
+        .set_cors_config(cors_config)
+        .set_custom_headers_config(custom_headers_config)
+        .set_security_headers_config(security_headers_config)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build response headers policy config: {}", e))
+}
+
+fn build_cors_config(cors: &CorsConfig) -> anyhow::Result<ResponseHeadersPolicyCorsConfig> {
+    let expose_headers = if cors.access_control_expose_headers.is_empty() {
+        None
+    } else {
+        Some(
+            ResponseHeadersPolicyAccessControlExposeHeaders::builder()
+                .quantity(cors.access_control_expose_headers.len() as i32)
+                .set_items(Some(cors.access_control_expose_headers.clone()))
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build CORS expose headers: {}", e))?,
+        )
+    };
+
+    ResponseHeadersPolicyCorsConfig::builder()
+        .access_control_allow_origins(
+            ResponseHeadersPolicyAccessControlAllowOrigins::builder()
+                .quantity(cors.access_control_allow_origins.len() as i32)
+                .set_items(Some(cors.access_control_allow_origins.clone()))
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build CORS allowed origins: {}", e))?,
+        )
+        .access_control_allow_headers(
+            ResponseHeadersPolicyAccessControlAllowHeaders::builder()
+                .quantity(cors.access_control_allow_headers.len() as i32)
+                .set_items(Some(cors.access_control_allow_headers.clone()))
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build CORS allowed headers: {}", e))?,
+        )
+        .access_control_allow_methods(
+            ResponseHeadersPolicyAccessControlAllowMethods::builder()
+                .quantity(cors.access_control_allow_methods.len() as i32)
+                .set_items(Some(
+                    cors.access_control_allow_methods
+                        .iter()
+                        .map(|method| ResponseHeadersPolicyAccessControlAllowMethodsValues::from(method.as_str()))
+                        .collect(),
+                ))
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build CORS allowed methods: {}", e))?,
+        )
+        .access_control_allow_credentials(cors.access_control_allow_credentials)
+        .set_access_control_expose_headers(expose_headers)
+        .set_access_control_max_age_sec(cors.access_control_max_age_sec)
+        .origin_override(cors.origin_override)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build CORS config: {}", e))
+}
+
+fn build_security_headers_config(
+    security_headers: &SecurityHeadersConfig,
+) -> anyhow::Result<ResponseHeadersPolicySecurityHeadersConfig> {
+    let mut builder = ResponseHeadersPolicySecurityHeadersConfig::builder();
+
+    if let Some(csp) = &security_headers.content_security_policy {
+        builder = builder.content_security_policy(
+            ResponseHeadersPolicyContentSecurityPolicy::builder()
+                .r#override(csp.r#override)
+                .content_security_policy(&csp.content_security_policy)
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build Content-Security-Policy header: {}", e))?,
+        );
+    }
+    if let Some(content_type_options) = &security_headers.content_type_options {
+        builder = builder.content_type_options(
+            ResponseHeadersPolicyContentTypeOptions::builder()
+                .r#override(content_type_options.r#override)
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build X-Content-Type-Options header: {}", e))?,
+        );
+    }
+    if let Some(frame_options) = &security_headers.frame_options {
+        builder = builder.frame_options(
+            ResponseHeadersPolicyFrameOptions::builder()
+                .r#override(frame_options.r#override)
+                .frame_option(FrameOptionsList::from(frame_options.frame_option.as_str()))
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build X-Frame-Options header: {}", e))?,
+        );
+    }
+    if let Some(referrer_policy) = &security_headers.referrer_policy {
+        builder = builder.referrer_policy(
+            ResponseHeadersPolicyReferrerPolicy::builder()
+                .r#override(referrer_policy.r#override)
+                .referrer_policy(ReferrerPolicyList::from(referrer_policy.referrer_policy.as_str()))
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build Referrer-Policy header: {}", e))?,
+        );
+    }
+    if let Some(sts) = &security_headers.strict_transport_security {
+        builder = builder.strict_transport_security(
+            ResponseHeadersPolicyStrictTransportSecurity::builder()
+                .r#override(sts.r#override)
+                .access_control_max_age_sec(sts.access_control_max_age_sec)
+                .include_subdomains(sts.include_subdomains)
+                .preload(sts.preload)
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build Strict-Transport-Security header: {}", e))?,
+        );
+    }
+    if let Some(xss_protection) = &security_headers.xss_protection {
+        builder = builder.xss_protection(
+            ResponseHeadersPolicyXssProtection::builder()
+                .r#override(xss_protection.r#override)
+                .protection(xss_protection.protection)
+                .mode_block(xss_protection.mode_block)
+                .set_report_uri(xss_protection.report_uri.clone())
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build X-XSS-Protection header: {}", e))?,
+        );
+    }
+
+    Ok(builder.build())
+}
+
+pub fn from_response_headers_policy_config(config: ResponseHeadersPolicyConfig) -> ResponseHeadersPolicy {
+    let cors_config = config.cors_config.map(|cors| CorsConfig {
+        access_control_allow_origins: cors
+            .access_control_allow_origins()
+            .map(|origins| origins.items().to_vec())
+            .unwrap_or_default(),
+        access_control_allow_headers: cors
+            .access_control_allow_headers()
+            .map(|headers| headers.items().to_vec())
+            .unwrap_or_default(),
+        access_control_allow_methods: cors
+            .access_control_allow_methods()
+            .map(|methods| methods.items().iter().map(|m| m.as_str().to_string()).collect())
+            .unwrap_or_default(),
+        access_control_allow_credentials: cors.access_control_allow_credentials(),
+        access_control_expose_headers: cors
+            .access_control_expose_headers()
+            .map(|headers| headers.items().to_vec())
+            .unwrap_or_default(),
+        access_control_max_age_sec: cors.access_control_max_age_sec(),
+        origin_override: cors.origin_override(),
+    });
+
+    let custom_headers_config = config
+        .custom_headers_config
+        .map(|headers| {
+            headers
+                .items()
+                .iter()
+                .map(|header| CustomHeader {
+                    header: header.header().to_string(),
+                    value: header.value().to_string(),
+                    r#override: header.r#override(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let security_headers_config = config.security_headers_config.map(|security_headers| SecurityHeadersConfig {
+        content_security_policy: security_headers.content_security_policy().map(|csp| ContentSecurityPolicy {
+            r#override: csp.r#override(),
+            content_security_policy: csp.content_security_policy().to_string(),
+        }),
+        content_type_options: security_headers
+            .content_type_options()
+            .map(|content_type_options| ContentTypeOptions {
+                r#override: content_type_options.r#override(),
+            }),
+        frame_options: security_headers.frame_options().map(|frame_options| FrameOptions {
+            r#override: frame_options.r#override(),
+            frame_option: frame_options.frame_option().as_str().to_string(),
+        }),
+        referrer_policy: security_headers.referrer_policy().map(|referrer_policy| ReferrerPolicy {
+            r#override: referrer_policy.r#override(),
+            referrer_policy: referrer_policy.referrer_policy().as_str().to_string(),
+        }),
+        strict_transport_security: security_headers.strict_transport_security().map(|sts| StrictTransportSecurity {
+            r#override: sts.r#override(),
+            access_control_max_age_sec: sts.access_control_max_age_sec(),
+            include_subdomains: sts.include_subdomains().unwrap_or(false),
+            preload: sts.preload().unwrap_or(false),
+        }),
+        xss_protection: security_headers.xss_protection().map(|xss_protection| XssProtection {
+            r#override: xss_protection.r#override(),
+            protection: xss_protection.protection(),
+            mode_block: xss_protection.mode_block().unwrap_or(false),
+            report_uri: xss_protection.report_uri().map(String::from),
+        }),
+    });
+
+    ResponseHeadersPolicy {
+        name: config.name,
+        comment: config.comment,
+        cors_config,
+        custom_headers_config,
+        security_headers_config,
+    }
+}
+
+pub fn build_end_points(end_points: &[EndPoint]) -> anyhow::Result<Vec<aws_sdk_cloudfront::types::EndPoint>> {
+    let mut items = Vec::new();
+    for end_point in end_points {
+        let kinesis_stream_config = match &end_point.kinesis_stream_config {
+            Some(kinesis) => Some(
+                aws_sdk_cloudfront::types::KinesisStreamConfig::builder()
+                    .role_arn(&kinesis.role_arn)
+                    .stream_arn(&kinesis.stream_arn)
+                    .build()
+                    .map_err(|e| anyhow::anyhow!("Failed to build Kinesis stream config: {}", e))?,
+            ),
+            None => None,
+        };
+        items.push(
+            aws_sdk_cloudfront::types::EndPoint::builder()
+                .stream_type(&end_point.stream_type)
+                .set_kinesis_stream_config(kinesis_stream_config)
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build realtime log endpoint: {}", e))?,
+        );
+    }
+    Ok(items)
+}
+
+pub fn from_end_points(end_points: &[aws_sdk_cloudfront::types::EndPoint]) -> Vec<EndPoint> {
+    end_points
+        .iter()
+        .map(|end_point| EndPoint {
+            stream_type: end_point.stream_type().to_string(),
+            kinesis_stream_config: end_point.kinesis_stream_config().map(|kinesis| KinesisStreamConfig {
+                role_arn: kinesis.role_arn().to_string(),
+                stream_arn: kinesis.stream_arn().to_string(),
+            }),
+        })
+        .collect()
+}
+
+pub fn build_field_level_encryption_config(
+    config: &FieldLevelEncryptionConfig,
+) -> anyhow::Result<aws_sdk_cloudfront::types::FieldLevelEncryptionConfig> {
+    let content_type_profile_config = match &config.content_type_profile_config {
+        Some(content_type_config) => {
+            let mut items = Vec::new();
+            for profile in &content_type_config.content_type_profiles {
+                items.push(
+                    aws_sdk_cloudfront::types::ContentTypeProfile::builder()
+                        .format(aws_sdk_cloudfront::types::Format::from(profile.format.as_str()))
+                        .set_profile_id(profile.profile_id.clone())
+                        .content_type(&profile.content_type)
+                        .build()
+                        .map_err(|e| anyhow::anyhow!("Failed to build content type profile {}: {}", profile.content_type, e))?,
+                );
+            }
+            Some(
+                aws_sdk_cloudfront::types::ContentTypeProfileConfig::builder()
+                    .forward_when_content_type_is_unknown(content_type_config.forward_when_content_type_is_unknown)
+                    .content_type_profiles(
+                        aws_sdk_cloudfront::types::ContentTypeProfiles::builder()
+                            .quantity(items.len() as i32)
+                            .set_items(Some(items))
+                            .build()
+                            .map_err(|e| anyhow::anyhow!("Failed to build content type profiles: {}", e))?,
+                    )
+                    .build()
+                    .map_err(|e| anyhow::anyhow!("Failed to build content type profile config: {}", e))?,
+            )
+        }
+        None => None,
+    };
+
+    let query_arg_profile_config = match &config.query_arg_profile_config {
+        Some(query_arg_config) => {
+            let mut items = Vec::new();
+            for profile in &query_arg_config.query_arg_profiles {
+                items.push(
+                    aws_sdk_cloudfront::types::QueryArgProfile::builder()
+                        .query_arg(&profile.query_arg)
+                        .profile_id(&profile.profile_id)
+                        .build()
+                        .map_err(|e| anyhow::anyhow!("Failed to build query argument profile {}: {}", profile.query_arg, e))?,
+                );
+            }
+            Some(
+                aws_sdk_cloudfront::types::QueryArgProfileConfig::builder()
+                    .forward_when_query_arg_profile_is_unknown(query_arg_config.forward_when_query_arg_profile_is_unknown)
+                    .query_arg_profiles(
+                        aws_sdk_cloudfront::types::QueryArgProfiles::builder()
+                            .quantity(items.len() as i32)
+                            .set_items(Some(items))
+                            .build()
+                            .map_err(|e| anyhow::anyhow!("Failed to build query argument profiles: {}", e))?,
+                    )
+                    .build()
+                    .map_err(|e| anyhow::anyhow!("Failed to build query argument profile config: {}", e))?,
+            )
+        }
+        None => None,
+    };
+
+    aws_sdk_cloudfront::types::FieldLevelEncryptionConfig::builder()
+        .caller_reference(&config.caller_reference)
+        .set_comment(config.comment.clone())
+        .set_content_type_profile_config(content_type_profile_config)
+        .set_query_arg_profile_config(query_arg_profile_config)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build field-level encryption config: {}", e))
+}
+
+pub fn from_field_level_encryption_config(config: aws_sdk_cloudfront::types::FieldLevelEncryptionConfig) -> FieldLevelEncryptionConfig {
+    FieldLevelEncryptionConfig {
+        comment: config.comment.filter(|comment| !comment.is_empty()),
+        caller_reference: config.caller_reference,
+        content_type_profile_config: config.content_type_profile_config.map(|content_type_config| ContentTypeProfileConfig {
+            forward_when_content_type_is_unknown: content_type_config.forward_when_content_type_is_unknown(),
+            content_type_profiles: content_type_config
+                .content_type_profiles()
+                .map(|profiles| {
+                    profiles
+                        .items()
+                        .iter()
+                        .map(|profile| ContentTypeProfile {
+                            format: profile.format().as_str().to_string(),
+                            profile_id: profile.profile_id().map(String::from),
+                            content_type: profile.content_type().to_string(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }),
+        query_arg_profile_config: config.query_arg_profile_config.map(|query_arg_config| QueryArgProfileConfig {
+            forward_when_query_arg_profile_is_unknown: query_arg_config.forward_when_query_arg_profile_is_unknown(),
+            query_arg_profiles: query_arg_config
+                .query_arg_profiles()
+                .map(|profiles| {
+                    profiles
+                        .items()
+                        .iter()
+                        .map(|profile| QueryArgProfile {
+                            query_arg: profile.query_arg().to_string(),
+                            profile_id: profile.profile_id().to_string(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }),
+    }
+}
+
+pub fn build_field_level_encryption_profile_config(
+    profile: &FieldLevelEncryptionProfile,
+) -> anyhow::Result<aws_sdk_cloudfront::types::FieldLevelEncryptionProfileConfig> {
+    let mut items = Vec::new();
+    for entity in &profile.encryption_entities {
+        items.push(
+            aws_sdk_cloudfront::types::EncryptionEntity::builder()
+                .public_key_id(&entity.public_key_id)
+                .provider_id(&entity.provider_id)
+                .field_patterns(
+                    aws_sdk_cloudfront::types::FieldPatterns::builder()
+                        .quantity(entity.field_patterns.len() as i32)
+                        .set_items(Some(entity.field_patterns.clone()))
+                        .build()
+                        .map_err(|e| anyhow::anyhow!("Failed to build field patterns: {}", e))?,
+                )
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build encryption entity: {}", e))?,
+        );
+    }
+
+    aws_sdk_cloudfront::types::FieldLevelEncryptionProfileConfig::builder()
+        .name(&profile.name)
+        .caller_reference(&profile.caller_reference)
+        .set_comment(profile.comment.clone())
+        .encryption_entities(
+            aws_sdk_cloudfront::types::EncryptionEntities::builder()
+                .quantity(items.len() as i32)
+                .set_items(Some(items))
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build encryption entities: {}", e))?,
+        )
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build field-level encryption profile config: {}", e))
+}
+
+pub fn from_field_level_encryption_profile_config(
+    config: aws_sdk_cloudfront::types::FieldLevelEncryptionProfileConfig,
+) -> FieldLevelEncryptionProfile {
+    FieldLevelEncryptionProfile {
+        encryption_entities: config
+            .encryption_entities()
+            .map(|entities| {
+                entities
+                    .items()
+                    .iter()
+                    .map(|entity| EncryptionEntity {
+                        public_key_id: entity.public_key_id().to_string(),
+                        provider_id: entity.provider_id().to_string(),
+                        field_patterns: entity
+                            .field_patterns()
+                            .map(|patterns| patterns.items().to_vec())
+                            .unwrap_or_default(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        name: config.name,
+        comment: config.comment.filter(|comment| !comment.is_empty()),
+        caller_reference: config.caller_reference,
+    }
+}
+
+pub fn build_continuous_deployment_policy_config(
+    policy: &ContinuousDeploymentPolicy,
+) -> anyhow::Result<aws_sdk_cloudfront::types::ContinuousDeploymentPolicyConfig> {
+    let traffic_config = match &policy.traffic_config {
+        Some(traffic_config) => {
+            let single_weight_config = match &traffic_config.single_weight_config {
+                Some(weight_config) => {
+                    let session_stickiness_config = match &weight_config.session_stickiness_config {
+                        Some(stickiness) => Some(
+                            aws_sdk_cloudfront::types::SessionStickinessConfig::builder()
+                                .idle_ttl(stickiness.idle_ttl)
+                                .maximum_ttl(stickiness.maximum_ttl)
+                                .build()
+                                .map_err(|e| anyhow::anyhow!("Failed to build session stickiness config: {}", e))?,
+                        ),
+                        None => None,
+                    };
+                    Some(
+                        aws_sdk_cloudfront::types::ContinuousDeploymentSingleWeightConfig::builder()
+                            .weight(weight_config.weight)
+                            .set_session_stickiness_config(session_stickiness_config)
+                            .build()
+                            .map_err(|e| anyhow::anyhow!("Failed to build single weight config: {}", e))?,
+                    )
+                }
+                None => None,
+            };
+            let single_header_config = match &traffic_config.single_header_config {
+                Some(header_config) => Some(
+                    aws_sdk_cloudfront::types::ContinuousDeploymentSingleHeaderConfig::builder()
+                        .header(&header_config.header)
+                        .value(&header_config.value)
+                        .build()
+                        .map_err(|e| anyhow::anyhow!("Failed to build single header config: {}", e))?,
+                ),
+                None => None,
+            };
+            Some(
+                aws_sdk_cloudfront::types::TrafficConfig::builder()
+                    .r#type(ContinuousDeploymentPolicyType::from(traffic_config.config_type.as_str()))
+                    .set_single_weight_config(single_weight_config)
+                    .set_single_header_config(single_header_config)
+                    .build()
+                    .map_err(|e| anyhow::anyhow!("Failed to build traffic config: {}", e))?,
+            )
+        }
+        None => None,
+    };
+
+    aws_sdk_cloudfront::types::ContinuousDeploymentPolicyConfig::builder()
+        .staging_distribution_dns_names(
+            StagingDistributionDnsNames::builder()
+                .quantity(policy.staging_distribution_dns_names.len() as i32)
+                .set_items(Some(policy.staging_distribution_dns_names.clone()))
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build staging distribution DNS names: {}", e))?,
+        )
+        .enabled(policy.enabled)
+        .set_traffic_config(traffic_config)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build continuous deployment policy config: {}", e))
+}
+
+pub fn from_continuous_deployment_policy_config(
+    config: &aws_sdk_cloudfront::types::ContinuousDeploymentPolicyConfig,
+) -> ContinuousDeploymentPolicy {
+    ContinuousDeploymentPolicy {
+        staging_distribution_dns_names: config
+            .staging_distribution_dns_names()
+            .map(|names| names.items().to_vec())
+            .unwrap_or_default(),
+        enabled: config.enabled(),
+        traffic_config: config.traffic_config().map(|traffic_config| TrafficConfig {
+            config_type: traffic_config.r#type().as_str().to_string(),
+            single_weight_config: traffic_config.single_weight_config().map(|weight_config| SingleWeightConfig {
+                weight: weight_config.weight(),
+                session_stickiness_config: weight_config
+                    .session_stickiness_config()
+                    .map(|stickiness| SessionStickinessConfig {
+                        idle_ttl: stickiness.idle_ttl(),
+                        maximum_ttl: stickiness.maximum_ttl(),
+                    }),
+            }),
+            single_header_config: traffic_config.single_header_config().map(|header_config| SingleHeaderConfig {
+                header: header_config.header().to_string(),
+                value: header_config.value().to_string(),
+            }),
+        }),
+    }
+}
+
+pub fn build_function_associations(function_associations: &[FunctionAssociation]) -> anyhow::Result<FunctionAssociations> {
+    let mut items = Vec::new();
+    for association in function_associations {
+        items.push(
+            aws_sdk_cloudfront::types::FunctionAssociation::builder()
+                .event_type(EventType::from(association.event_type.as_str()))
+                .function_arn(&association.function_arn)
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build function association: {}", e))?,
+        );
+    }
+
+    FunctionAssociations::builder()
+        .quantity(items.len() as i32)
+        .set_items(Some(items))
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build function associations: {}", e))
+}
+
+pub fn build_lambda_function_associations(
+    lambda_function_associations: &[LambdaFunctionAssociation],
+) -> anyhow::Result<LambdaFunctionAssociations> {
+    let mut items = Vec::new();
+    for association in lambda_function_associations {
+        items.push(
+            aws_sdk_cloudfront::types::LambdaFunctionAssociation::builder()
+                .event_type(EventType::from(association.event_type.as_str()))
+                .lambda_function_arn(&association.lambda_function_arn)
+                .include_body(association.include_body)
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build Lambda@Edge function association: {}", e))?,
+        );
+    }
+
+    LambdaFunctionAssociations::builder()
+        .quantity(items.len() as i32)
+        .set_items(Some(items))
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build Lambda@Edge function associations: {}", e))
+}
+
+/// Converts geo restrictions into their SDK form. None serves the distribution in every country.
+pub fn build_restrictions(restrictions: &Option<GeoRestriction>) -> anyhow::Result<Restrictions> {
+    let geo_restriction = match restrictions {
+        Some(restrictions) => aws_sdk_cloudfront::types::GeoRestriction::builder()
+            .restriction_type(GeoRestrictionType::from(restrictions.restriction_type.as_str()))
+            .quantity(restrictions.locations.len() as i32)
+            .set_items(Some(restrictions.locations.clone())),
+        None => aws_sdk_cloudfront::types::GeoRestriction::builder()
+            .restriction_type(GeoRestrictionType::None)
+            .quantity(0),
+    };
+
+    Restrictions::builder()
+        .geo_restriction(
+            geo_restriction
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build geo restriction: {}", e))?,
+        )
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build restrictions: {}", e))
+}
+
+pub fn from_function_associations(function_associations: Option<&FunctionAssociations>) -> Vec<FunctionAssociation> {
+    function_associations
+        .map(|associations| {
+            associations
+                .items()
+                .iter()
+                .map(|association| FunctionAssociation {
+                    event_type: association.event_type().as_str().to_string(),
+                    function_arn: association.function_arn().to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn from_lambda_function_associations(
+    lambda_function_associations: Option<&LambdaFunctionAssociations>,
+) -> Vec<LambdaFunctionAssociation> {
+    lambda_function_associations
+        .map(|associations| {
+            associations
+                .items()
+                .iter()
+                .map(|association| LambdaFunctionAssociation {
+                    event_type: association.event_type().as_str().to_string(),
+                    lambda_function_arn: association.lambda_function_arn().to_string(),
+                    include_body: association.include_body().unwrap_or(false),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Reads geo restrictions back from their SDK form. A restriction type of "none" is represented as None.
+pub fn from_restrictions(restrictions: Option<&Restrictions>) -> Option<GeoRestriction> {
+    let geo_restriction = restrictions?.geo_restriction()?;
+    if *geo_restriction.restriction_type() == GeoRestrictionType::None {
+        return None;
+    }
+
+    Some(GeoRestriction {
+        restriction_type: geo_restriction.restriction_type().as_str().to_string(),
+        locations: sorted_locations(geo_restriction.items()),
+    })
+}
+
+/// CloudFront doesn't preserve the order of country codes, so they're kept sorted on both sides of a diff.
+pub fn sorted_locations(locations: &[String]) -> Vec<String> {
+    let mut locations = locations.to_vec();
+    locations.sort();
+    locations.dedup();
+    locations
+}
+
+pub fn build_tenant_config(tenant_config: &TenantConfig) -> anyhow::Result<aws_sdk_cloudfront::types::TenantConfig> {
+    let mut parameter_definitions = Vec::new();
+    for definition in &tenant_config.parameter_definitions {
+        let string_schema = StringSchemaConfig::builder()
+            .required(definition.required)
+            .set_default_value(definition.default_value.clone())
+            .set_comment(definition.comment.clone())
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to build parameter schema for {}: {}", definition.name, e))?;
+
+        parameter_definitions.push(
+            aws_sdk_cloudfront::types::ParameterDefinition::builder()
+                .name(&definition.name)
+                .definition(ParameterDefinitionSchema::builder().string_schema(string_schema).build())
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build parameter definition {}: {}", definition.name, e))?,
+        );
+    }
+
+    Ok(aws_sdk_cloudfront::types::TenantConfig::builder()
+        .set_parameter_definitions(Some(parameter_definitions))
+        .build())
+}
+
+pub fn from_tenant_config(tenant_config: Option<&aws_sdk_cloudfront::types::TenantConfig>) -> Option<TenantConfig> {
+    let tenant_config = tenant_config?;
+
+    Some(TenantConfig {
+        parameter_definitions: tenant_config
+            .parameter_definitions()
+            .iter()
+            .map(|definition| {
+                let string_schema = definition.definition().and_then(|schema| schema.string_schema());
+                ParameterDefinition {
+                    name: definition.name().to_string(),
+                    required: string_schema.map(|schema| schema.required()).unwrap_or(false),
+                    default_value: string_schema.and_then(|schema| schema.default_value()).map(String::from),
+                    comment: string_schema.and_then(|schema| schema.comment()).map(String::from),
+                }
+            })
+            .collect(),
+    })
+}
+
+pub fn build_customizations(customizations: &TenantCustomizations) -> anyhow::Result<Customizations> {
+    let mut builder = Customizations::builder();
+
+    if let Some(web_acl) = &customizations.web_acl {
+        builder = builder.web_acl(
+            WebAclCustomization::builder()
+                .action(CustomizationActionType::from(web_acl.action.as_str()))
+                .set_arn(web_acl.arn.clone())
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build web ACL customization: {}", e))?,
+        );
+    }
+
+    if let Some(certificate_arn) = &customizations.certificate_arn {
+        builder = builder.certificate(
+            Certificate::builder()
+                .arn(certificate_arn)
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build certificate customization: {}", e))?,
+        );
+    }
+
+    if let Some(geo_restrictions) = &customizations.geo_restrictions {
+        builder = builder.geo_restrictions(
+            GeoRestrictionCustomization::builder()
+                .restriction_type(GeoRestrictionType::from(geo_restrictions.restriction_type.as_str()))
+                .set_locations(Some(geo_restrictions.locations.clone()))
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build geo restriction customization: {}", e))?,
+        );
+    }
+
+    Ok(builder.build())
+}
+
+/// Reads tenant customizations back from their SDK form. A tenant with none set is represented as None.
+pub fn from_customizations(customizations: Option<&Customizations>) -> Option<TenantCustomizations> {
+    let customizations = customizations?;
+
+    let customizations = TenantCustomizations {
+        web_acl: customizations.web_acl().map(|web_acl| crate::resource::WebAclCustomization {
+            action: web_acl.action().as_str().to_string(),
+            arn: web_acl.arn().map(String::from),
+        }),
+        certificate_arn: customizations.certificate().map(|certificate| certificate.arn().to_string()),
+        geo_restrictions: customizations.geo_restrictions().map(|geo_restrictions| GeoRestriction {
+            restriction_type: geo_restrictions.restriction_type().as_str().to_string(),
+            locations: sorted_locations(geo_restrictions.locations()),
+        }),
+    };
+
+    if customizations.web_acl.is_none() && customizations.certificate_arn.is_none() && customizations.geo_restrictions.is_none() {
+        return None;
+    }
+    Some(customizations)
+}
+
+/// The name of a CloudFront Function, from its ARN:
+/// `arn:aws:cloudfront::123456789012:function/my-function` becomes `my-function`.
+pub fn function_name_from_arn(function_arn: &str) -> String {
+    match function_arn.rsplit_once(":function/") {
+        Some((_, name)) => name.to_string(),
+        None => function_arn.to_string(),
+    }
+}
+
+/// Works out whether a function's deployed code came from a code file in this repository.
+///
+/// If the function's file at `addr` points at a `code_file` whose contents are exactly `deployed_code`,
+/// returns an empty inline code along with that code file, so that the remote state reads back the same
+/// as the file. Otherwise returns `deployed_code` inline, so that editing the code file shows up as a diff.
+pub fn resolve_function_code(
+    prefix: &Path,
+    addr: &CloudFrontResourceAddress,
+    deployed_code: String,
+) -> anyhow::Result<(String, Option<String>)> {
+    let function_path = prefix.join(addr.to_path_buf());
+    if !function_path.is_file() {
+        return Ok((deployed_code, None));
+    }
+
+    let Ok(Function {
+        code_file: Some(code_file), ..
+    }) = RON.from_str::<Function>(&std::fs::read_to_string(&function_path)?)
+    else {
+        return Ok((deployed_code, None));
+    };
+
+    let code_path = prefix.join(&code_file);
+    if code_path.is_file() && std::fs::read_to_string(&code_path)? == deployed_code {
+        Ok((String::new(), Some(code_file)))
+    } else {
+        Ok((deployed_code, None))
+    }
+}
+
+/// Returns the code to deploy for `function`, reading it from its code file if it has one.
+pub fn function_code(prefix: &Path, function: &Function) -> anyhow::Result<String> {
+    match &function.code_file {
+        Some(code_file) => {
+            let code_path = prefix.join(code_file);
+            std::fs::read_to_string(&code_path)
+                .with_context(|| format!("Failed to read code file {} for CloudFront function {}", code_path.display(), function.name))
+        }
+        None => Ok(function.function_code.clone()),
+    }
+}
@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
 use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 
 type Region = String;
@@ -14,6 +15,7 @@ type EventRuleName = String;
 #[derive(Debug, Clone)]
 pub enum CloudWatchResourceAddress {
     Alarm(Region, AlarmName),
+    CompositeAlarm(Region, AlarmName),
     Dashboard(Region, DashboardName),
     LogGroup(Region, LogGroupName),
     LogStream(Region, LogGroupName, LogStreamName),
@@ -24,8 +26,9 @@ pub enum CloudWatchResourceAddress {
 impl ResourceAddress for CloudWatchResourceAddress {
     fn to_path_buf(&self) -> PathBuf {
         match &self {
-            CloudWatchResourceAddress::Alarm(region, name) => {
-                PathBuf::from(format!("aws/cloudwatch/{region}/alarms/{name}.ron"))
+            CloudWatchResourceAddress::Alarm(region, name) => PathBuf::from(format!("aws/cloudwatch/{region}/alarms/{name}.ron")),
+            CloudWatchResourceAddress::CompositeAlarm(region, name) => {
+                PathBuf::from(format!("aws/cloudwatch/{region}/composite_alarms/{name}.ron"))
             }
             CloudWatchResourceAddress::Dashboard(region, name) => {
                 PathBuf::from(format!("aws/cloudwatch/{region}/dashboards/{name}.ron"))
@@ -53,6 +56,10 @@ impl ResourceAddress for CloudWatchResourceAddress {
                 let name = name.strip_suffix(".ron").unwrap().to_string();
                 Ok(CloudWatchResourceAddress::Alarm(region.to_string(), name))
             }
+            ["aws", "cloudwatch", region, "composite_alarms", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(CloudWatchResourceAddress::CompositeAlarm(region.to_string(), name))
+            }
             ["aws", "cloudwatch", region, "dashboards", name] if name.ends_with(".ron") => {
                 let name = name.strip_suffix(".ron").unwrap().to_string();
                 Ok(CloudWatchResourceAddress::Dashboard(region.to_string(), name))
@@ -87,3 +94,45 @@ impl ResourceAddress for CloudWatchResourceAddress {
         }
     }
 }
+
+impl DescribeAddresses for CloudWatchResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/cloudwatch/<region>/alarms/<alarm_name>.ron",
+                description: "A metric alarm",
+                example:     "aws/cloudwatch/us-east-1/alarms/jobs-queue-age.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudwatch/<region>/composite_alarms/<alarm_name>.ron",
+                description: "A composite alarm",
+                example:     "aws/cloudwatch/us-east-1/composite_alarms/web-unhealthy.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudwatch/<region>/dashboards/<dashboard_name>.ron",
+                description: "A dashboard",
+                example:     "aws/cloudwatch/us-east-1/dashboards/overview.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudwatch/<region>/log_groups/<log_group_name>.ron",
+                description: "A CloudWatch Logs log group",
+                example:     "aws/cloudwatch/us-east-1/log_groups/api.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudwatch/<region>/log_groups/<log_group_name>/streams/<log_stream_name>.ron",
+                description: "A log stream in a log group",
+                example:     "aws/cloudwatch/us-east-1/log_groups/api/streams/deploys.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudwatch/<region>/metrics/<namespace>/<metric_name>.ron",
+                description: "A custom metric",
+                example:     "aws/cloudwatch/us-east-1/metrics/Jobs/Processed.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cloudwatch/<region>/event_rules/<rule_name>.ron",
+                description: "An EventBridge rule on the default event bus",
+                example:     "aws/cloudwatch/us-east-1/event_rules/nightly-report.ron",
+            },
+        ]
+    }
+}
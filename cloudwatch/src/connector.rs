@@ -1,63 +1,113 @@
 pub use crate::addr::CloudWatchResourceAddress;
-use crate::{
-    config, resource,
-    resource::{
-        Alarm, AlarmAction, CloudWatchResource, Dashboard, Dimension, EventRule, EventTarget, LogGroup, LogStream, Metric,
-        StatOptions,
-    },
-    tags::Tags,
-};
+pub use crate::op::CloudWatchConnectorOp;
+pub use crate::resource::CloudWatchResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
 
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
+use anyhow::bail;
 use async_trait::async_trait;
-use autoschematic_connector_aws_core::config::AwsServiceConfig;
 use autoschematic_core::{
     connector::{
-        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource, ResourceAddress,
-        SkeletonResponse,
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
     },
     diag::DiagnosticResponse,
     skeleton,
     util::{ron_check_eq, ron_check_syntax},
 };
-use aws_config::{BehaviorVersion, Region};
-use config::CloudWatchConnectorConfig;
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::CloudWatchConnectorConfig;
 use tokio::sync::Mutex;
-mod get;
-mod list;
-mod op_exec;
-mod plan;
+
+use crate::resource::{
+    ActionsSuppressor, Alarm, CompositeAlarm, Dashboard, Dimension, EventRule, EventTarget, LogGroup, LogStream, Metric,
+    MetricDataQuery, MetricStat, RetentionPolicy, StatOptions,
+};
+use crate::tags::Tags;
+use crate::util::json_to_ron;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
 
 #[derive(Default)]
 pub struct CloudWatchConnector {
     client_cache: Mutex<HashMap<String, Arc<aws_sdk_cloudwatch::Client>>>,
+    logs_client_cache: Mutex<HashMap<String, Arc<aws_sdk_cloudwatchlogs::Client>>>,
+    events_client_cache: Mutex<HashMap<String, Arc<aws_sdk_eventbridge::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
     account_id: Mutex<String>,
     config: Mutex<CloudWatchConnectorConfig>,
     prefix: PathBuf,
 }
 
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
 impl CloudWatchConnector {
-    pub async fn get_or_init_client(&self, region: &str) -> anyhow::Result<Arc<aws_sdk_cloudwatch::Client>> {
-        let mut client_cache = self.client_cache.lock().await;
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_cloudwatch::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_cloudwatch, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
 
-        if let Some(client) = client_cache.get(region) {
+    pub async fn get_or_init_logs_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_cloudwatchlogs::Client>> {
+        let mut cache = self.logs_client_cache.lock().await;
+
+        if let Some(client) = cache.get(region_s) {
             return Ok(client.clone());
         }
 
-        let config = self.config.lock().await;
+        let config = load_sdk_config(region_s).await;
+        let client = Arc::new(audited_client!(aws_sdk_cloudwatchlogs, &config));
+        cache.insert(region_s.to_string(), client.clone());
+
+        Ok(client)
+    }
+
+    pub async fn get_or_init_events_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_eventbridge::Client>> {
+        let mut cache = self.events_client_cache.lock().await;
 
-        let aws_config = aws_config::defaults(BehaviorVersion::latest())
-            .region(Region::new(region.to_string()))
-            .load()
-            .await;
+        if let Some(client) = cache.get(region_s) {
+            return Ok(client.clone());
+        }
 
-        let client = Arc::new(aws_sdk_cloudwatch::Client::new(&aws_config));
-        client_cache.insert(region.to_string(), client.clone());
+        let config = load_sdk_config(region_s).await;
+        let client = Arc::new(audited_client!(aws_sdk_eventbridge, &config));
+        cache.insert(region_s.to_string(), client.clone());
 
         Ok(client)
     }
@@ -84,12 +134,16 @@ impl Connector for CloudWatchConnector {
     }
 
     async fn init(&self) -> anyhow::Result<()> {
-        let secrets_config: CloudWatchConnectorConfig = CloudWatchConnectorConfig::try_load(&self.prefix).await?;
+        let cloudwatch_config: CloudWatchConnectorConfig = CloudWatchConnectorConfig::try_load(&self.prefix).await?;
 
-        let account_id = secrets_config.verify_sts().await?;
+        let account_id = cloudwatch_config.verify_sts().await?;
 
         *self.client_cache.lock().await = HashMap::new();
-        *self.config.lock().await = secrets_config;
+        *self.logs_client_cache.lock().await = HashMap::new();
+        *self.events_client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(cloudwatch_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = cloudwatch_config;
         *self.account_id.lock().await = account_id;
         Ok(())
     }
@@ -112,64 +166,146 @@ impl Connector for CloudWatchConnector {
     }
 
     async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
-        self.do_op_exec(addr, op).await
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
     }
 
     async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
         let mut res = Vec::new();
 
-        let region = String::from("[region]");
-        let alarm_name = String::from("[alarm_name]");
-
-        // CloudWatch Alarm
+        // Single-metric alarm skeleton
         res.push(skeleton!(
-            CloudWatchResourceAddress::Alarm(region.clone(), alarm_name),
+            CloudWatchResourceAddress::Alarm(String::from("[region]"), String::from("[alarm_name]")),
             CloudWatchResource::Alarm(Alarm {
-                name: String::from("[alarm_name]"),
                 description: Some(String::from("[description]")),
-                metric_name: Some(String::from("[metric_name]")),
-                namespace: Some(String::from("[namespace]")),
-                statistic: Some(String::from("Average")),
+                namespace: Some(String::from("AWS/SQS")),
+                metric_name: Some(String::from("ApproximateAgeOfOldestMessage")),
+                dimensions: HashMap::from([(String::from("QueueName"), String::from("[queue_name]"))]),
+                statistic: Some(String::from("Maximum")),
                 extended_statistic: None,
-                dimensions: Some(vec![Dimension {
-                    name: String::from("[dimension_name]"),
-                    value: String::from("[dimension_value]"),
-                }]),
                 period: Some(300),
-                unit: Some(String::from("Count")),
-                evaluation_periods: 2,
+                unit: None,
+                metrics: None,
+                comparison_operator: String::from("GreaterThanThreshold"),
+                threshold: Some(600.0),
+                threshold_metric_id: None,
+                evaluation_periods: 3,
                 datapoints_to_alarm: Some(2),
-                threshold: Some(10.0),
+                treat_missing_data: Some(String::from("notBreaching")),
+                evaluate_low_sample_count_percentile: None,
+                actions_enabled: true,
+                alarm_actions: vec![String::from("arn:aws:sns:[region]:[account_id]:[topic_name]")],
+                ok_actions: Vec::new(),
+                insufficient_data_actions: Vec::new(),
+                tags: Tags::default(),
+            })
+        ));
+
+        // Metric math alarm skeleton: the percentage of requests that returned a 5xx
+        res.push(skeleton!(
+            CloudWatchResourceAddress::Alarm(String::from("[region]"), String::from("[error_rate_alarm_name]")),
+            CloudWatchResource::Alarm(Alarm {
+                description: Some(String::from("[description]")),
+                namespace: None,
+                metric_name: None,
+                dimensions: HashMap::new(),
+                statistic: None,
+                extended_statistic: None,
+                period: None,
+                unit: None,
+                metrics: Some(vec![
+                    MetricDataQuery {
+                        id: String::from("errors"),
+                        metric_stat: Some(MetricStat {
+                            namespace: String::from("AWS/ApplicationELB"),
+                            metric_name: String::from("HTTPCode_Target_5XX_Count"),
+                            dimensions: HashMap::from([(String::from("LoadBalancer"), String::from("[load_balancer]"))]),
+                            period: 60,
+                            stat: String::from("Sum"),
+                            unit: None,
+                        }),
+                        expression: None,
+                        label: None,
+                        return_data: false,
+                        period: None,
+                    },
+                    MetricDataQuery {
+                        id: String::from("requests"),
+                        metric_stat: Some(MetricStat {
+                            namespace: String::from("AWS/ApplicationELB"),
+                            metric_name: String::from("RequestCount"),
+                            dimensions: HashMap::from([(String::from("LoadBalancer"), String::from("[load_balancer]"))]),
+                            period: 60,
+                            stat: String::from("Sum"),
+                            unit: None,
+                        }),
+                        expression: None,
+                        label: None,
+                        return_data: false,
+                        period: None,
+                    },
+                    MetricDataQuery {
+                        id: String::from("error_rate"),
+                        metric_stat: None,
+                        expression: Some(String::from("100 * errors / requests")),
+                        label: Some(String::from("5xx error rate")),
+                        return_data: true,
+                        period: None,
+                    },
+                ]),
                 comparison_operator: String::from("GreaterThanThreshold"),
-                treat_missing_data: Some(String::from("missing")),
+                threshold: Some(5.0),
+                threshold_metric_id: None,
+                evaluation_periods: 5,
+                datapoints_to_alarm: Some(3),
+                treat_missing_data: Some(String::from("notBreaching")),
                 evaluate_low_sample_count_percentile: None,
-                metrics: None,
-                alarm_actions: Some(vec![AlarmAction {
-                    arn: String::from("[sns_topic_arn]"),
-                }]),
-                ok_actions: None,
-                insufficient_data_actions: None,
+                actions_enabled: true,
+                alarm_actions: vec![String::from("arn:aws:sns:[region]:[account_id]:[topic_name]")],
+                ok_actions: Vec::new(),
+                insufficient_data_actions: Vec::new(),
                 tags: Tags::default(),
             })
         ));
 
-        // CloudWatch Dashboard
-        let dashboard_name = String::from("[dashboard_name]");
+        // Composite alarm skeleton
         res.push(skeleton!(
-            CloudWatchResourceAddress::Dashboard(region.clone(), dashboard_name),
-            CloudWatchResource::Dashboard(Dashboard {
-                name: String::from("[dashboard_name]"),
-                body: ron::Value::String(String::from("{\"widgets\":[]}")),
+            CloudWatchResourceAddress::CompositeAlarm(String::from("[region]"), String::from("[composite_alarm_name]")),
+            CloudWatchResource::CompositeAlarm(CompositeAlarm {
+                description: Some(String::from("[description]")),
+                alarm_rule: String::from(r#"ALARM("[alarm_name]") OR ALARM("[error_rate_alarm_name]")"#),
+                actions_enabled: true,
+                alarm_actions: vec![String::from("arn:aws:sns:[region]:[account_id]:[topic_name]")],
+                ok_actions: Vec::new(),
+                insufficient_data_actions: Vec::new(),
+                actions_suppressor: Some(ActionsSuppressor {
+                    alarm: String::from("[maintenance_alarm_name]"),
+                    wait_period: Some(60),
+                    extension_period: Some(300),
+                }),
                 tags: Tags::default(),
             })
         ));
 
+        // Dashboard skeleton
+        res.push(skeleton!(
+            CloudWatchResourceAddress::Dashboard(String::from("[region]"), String::from("[dashboard_name]")),
+            CloudWatchResource::Dashboard(Dashboard {
+                body: json_to_ron(
+                    r#"{"widgets": [{"type": "alarm", "x": 0, "y": 0, "width": 24, "height": 3, "properties": {"title": "Alarms", "alarms": ["arn:aws:cloudwatch:[region]:[account_id]:alarm:[alarm_name]"]}}]}"#
+                )?,
+            })
+        ));
+
+        let region = String::from("[region]");
+
         // CloudWatch Log Group
         let log_group_name = String::from("[log_group_name]");
         res.push(skeleton!(
             CloudWatchResourceAddress::LogGroup(region.clone(), log_group_name.clone()),
             CloudWatchResource::LogGroup(LogGroup {
-                retention_policy: Some(crate::resource::RetentionPolicy { retention_in_days: 14 }),
+                retention_policy: Some(RetentionPolicy { retention_in_days: 14 }),
                 kms_key_id: None,
                 metric_filters: None,
                 tags: Tags::default(),
@@ -237,12 +373,13 @@ impl Connector for CloudWatchConnector {
         let addr = CloudWatchResourceAddress::from_path(addr)?;
 
         match addr {
-            CloudWatchResourceAddress::Alarm(_, _) => ron_check_eq::<resource::Alarm>(a, b),
-            CloudWatchResourceAddress::Dashboard(_, _) => ron_check_eq::<resource::Dashboard>(a, b),
-            CloudWatchResourceAddress::LogGroup(_, _) => ron_check_eq::<resource::LogGroup>(a, b),
-            CloudWatchResourceAddress::LogStream(_, _, _) => ron_check_eq::<resource::LogStream>(a, b),
-            CloudWatchResourceAddress::Metric(_, _, _) => ron_check_eq::<resource::Metric>(a, b),
-            CloudWatchResourceAddress::EventRule(_, _) => ron_check_eq::<resource::EventRule>(a, b),
+            CloudWatchResourceAddress::Alarm(_, _) => ron_check_eq::<Alarm>(a, b),
+            CloudWatchResourceAddress::CompositeAlarm(_, _) => ron_check_eq::<CompositeAlarm>(a, b),
+            CloudWatchResourceAddress::Dashboard(_, _) => ron_check_eq::<Dashboard>(a, b),
+            CloudWatchResourceAddress::LogGroup(_, _) => ron_check_eq::<LogGroup>(a, b),
+            CloudWatchResourceAddress::LogStream(_, _, _) => ron_check_eq::<LogStream>(a, b),
+            CloudWatchResourceAddress::Metric(_, _, _) => ron_check_eq::<Metric>(a, b),
+            CloudWatchResourceAddress::EventRule(_, _) => ron_check_eq::<EventRule>(a, b),
         }
     }
 
@@ -250,12 +387,13 @@ impl Connector for CloudWatchConnector {
         let addr = CloudWatchResourceAddress::from_path(addr)?;
 
         match addr {
-            CloudWatchResourceAddress::Alarm(_, _) => ron_check_syntax::<resource::Alarm>(a),
-            CloudWatchResourceAddress::Dashboard(_, _) => ron_check_syntax::<resource::Dashboard>(a),
-            CloudWatchResourceAddress::LogGroup(_, _) => ron_check_syntax::<resource::LogGroup>(a),
-            CloudWatchResourceAddress::LogStream(_, _, _) => ron_check_syntax::<resource::LogStream>(a),
-            CloudWatchResourceAddress::Metric(_, _, _) => ron_check_syntax::<resource::Metric>(a),
-            CloudWatchResourceAddress::EventRule(_, _) => ron_check_syntax::<resource::EventRule>(a),
+            CloudWatchResourceAddress::Alarm(_, _) => ron_check_syntax::<Alarm>(a),
+            CloudWatchResourceAddress::CompositeAlarm(_, _) => ron_check_syntax::<CompositeAlarm>(a),
+            CloudWatchResourceAddress::Dashboard(_, _) => ron_check_syntax::<Dashboard>(a),
+            CloudWatchResourceAddress::LogGroup(_, _) => ron_check_syntax::<LogGroup>(a),
+            CloudWatchResourceAddress::LogStream(_, _, _) => ron_check_syntax::<LogStream>(a),
+            CloudWatchResourceAddress::Metric(_, _, _) => ron_check_syntax::<Metric>(a),
+            CloudWatchResourceAddress::EventRule(_, _) => ron_check_syntax::<EventRule>(a),
         }
     }
 }
@@ -1,11 +1,21 @@
 use std::{collections::HashMap, path::Path};
 
-use crate::addr::CloudWatchResourceAddress;
-use crate::resource::*;
-use crate::tags::Tags;
-use anyhow::Context;
-use autoschematic_core::connector::{GetResourceResponse, Resource, ResourceAddress};
-use autoschematic_core::get_resource_response;
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_cloudwatch::{operation::get_dashboard::GetDashboardError, types::AlarmType};
+use aws_sdk_eventbridge::operation::describe_rule::DescribeRuleError;
+
+use crate::{
+    addr::CloudWatchResourceAddress,
+    resource::{
+        ActionsSuppressor, Alarm, CloudWatchResource, CompositeAlarm, Dashboard, Dimension, EventPattern, EventRule, LogGroup,
+        LogStream, Metric, RetentionPolicy,
+    },
+    tags::Tags,
+    util::{alarm_arn, from_sdk_dimensions, from_sdk_metric_data_query, json_to_ron, log_group_arn},
+};
 
 use super::CloudWatchConnector;
 
@@ -13,261 +23,286 @@ impl CloudWatchConnector {
     pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
         let addr = CloudWatchResourceAddress::from_path(addr)?;
 
-        match addr {
+        let account_id = self.account_id.lock().await.clone();
+
+        match &addr {
             CloudWatchResourceAddress::Alarm(region, alarm_name) => {
-                let client = self.get_or_init_client(&region).await?;
-
-                let alarm_response = client.describe_alarms().alarm_names(&alarm_name).send().await?;
-
-                if let Some(metric_alarms) = alarm_response.metric_alarms {
-                    if let Some(aws_alarm) = metric_alarms.into_iter().next() {
-                        let dimensions = aws_alarm.dimensions.map(|dims| {
-                            dims.into_iter()
-                                .filter_map(|d| {
-                                    if let (Some(name), Some(value)) = (d.name, d.value) {
-                                        Some(Dimension { name, value })
-                                    } else {
-                                        None
-                                    }
-                                })
-                                .collect()
-                        });
-
-                        let alarm = Alarm {
-                            name: aws_alarm.alarm_name.unwrap_or_default(),
-                            description: aws_alarm.alarm_description,
-                            metric_name: aws_alarm.metric_name,
-                            namespace: aws_alarm.namespace,
-                            statistic: aws_alarm.statistic.map(|s| s.as_str().to_string()),
-                            extended_statistic: aws_alarm.extended_statistic,
-                            dimensions,
-                            period: aws_alarm.period.map(|p| p as i64),
-                            unit: aws_alarm.unit.map(|u| u.as_str().to_string()),
-                            evaluation_periods: aws_alarm.evaluation_periods.unwrap_or(1) as i64,
-                            datapoints_to_alarm: aws_alarm.datapoints_to_alarm.map(|d| d as i64),
-                            threshold: aws_alarm.threshold,
-                            comparison_operator: aws_alarm
-                                .comparison_operator
-                                .map(|c| c.as_str().to_string())
-                                .unwrap_or_default(),
-                            treat_missing_data: aws_alarm.treat_missing_data,
-                            evaluate_low_sample_count_percentile: aws_alarm.evaluate_low_sample_count_percentile,
-                            metrics: None, // TODO: Handle composite alarms
-                            alarm_actions: aws_alarm
-                                .alarm_actions
-                                .map(|actions| actions.into_iter().map(|arn| AlarmAction { arn }).collect()),
-                            ok_actions: aws_alarm
-                                .ok_actions
-                                .map(|actions| actions.into_iter().map(|arn| AlarmAction { arn }).collect()),
-                            insufficient_data_actions: aws_alarm
-                                .insufficient_data_actions
-                                .map(|actions| actions.into_iter().map(|arn| AlarmAction { arn }).collect()),
-                            tags: Tags::default(), // TODO: Fetch tags
-                        };
-
-                        get_resource_response!(
-                            CloudWatchResource::Alarm(alarm),
-                            [(String::from("alarm_name"), alarm_name)]
-                        )
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = client
+                    .describe_alarms()
+                    .alarm_names(alarm_name)
+                    .alarm_types(AlarmType::MetricAlarm)
+                    .send()
+                    .await?;
+
+                let Some(metric_alarm) = resp.metric_alarms.unwrap_or_default().into_iter().next() else {
+                    return Ok(None);
+                };
+
+                let alarm_arn = metric_alarm
+                    .alarm_arn
+                    .clone()
+                    .unwrap_or_else(|| alarm_arn(region, &account_id, alarm_name));
+
+                let tags_resp = client.list_tags_for_resource().resource_arn(&alarm_arn).send().await?;
+
+                let alarm = Alarm {
+                    description: metric_alarm.alarm_description.filter(|d| !d.is_empty()),
+                    namespace: metric_alarm.namespace,
+                    metric_name: metric_alarm.metric_name,
+                    dimensions: from_sdk_dimensions(metric_alarm.dimensions),
+                    statistic: metric_alarm.statistic.map(|s| s.as_str().to_string()),
+                    extended_statistic: metric_alarm.extended_statistic,
+                    period: metric_alarm.period,
+                    unit: metric_alarm.unit.map(|u| u.as_str().to_string()),
+                    metrics: metric_alarm
+                        .metrics
+                        .filter(|m| !m.is_empty())
+                        .map(|m| m.into_iter().map(from_sdk_metric_data_query).collect()),
+                    comparison_operator: metric_alarm
+                        .comparison_operator
+                        .map(|c| c.as_str().to_string())
+                        .unwrap_or_default(),
+                    threshold: if metric_alarm.threshold_metric_id.is_some() {
+                        None
                     } else {
-                        Ok(None)
-                    }
-                } else {
-                    Ok(None)
-                }
+                        metric_alarm.threshold
+                    },
+                    threshold_metric_id: metric_alarm.threshold_metric_id,
+                    evaluation_periods: metric_alarm.evaluation_periods.unwrap_or(1),
+                    datapoints_to_alarm: metric_alarm.datapoints_to_alarm,
+                    treat_missing_data: metric_alarm.treat_missing_data,
+                    evaluate_low_sample_count_percentile: metric_alarm
+                        .evaluate_low_sample_count_percentile
+                        .filter(|e| !e.is_empty()),
+                    actions_enabled: metric_alarm.actions_enabled.unwrap_or(true),
+                    alarm_actions: metric_alarm.alarm_actions.unwrap_or_default(),
+                    ok_actions: metric_alarm.ok_actions.unwrap_or_default(),
+                    insufficient_data_actions: metric_alarm.insufficient_data_actions.unwrap_or_default(),
+                    tags: tags_resp.tags.into(),
+                };
+
+                get_resource_response!(CloudWatchResource::Alarm(alarm), [(String::from("alarm_arn"), alarm_arn)])
+            }
+
+            CloudWatchResourceAddress::CompositeAlarm(region, alarm_name) => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = client
+                    .describe_alarms()
+                    .alarm_names(alarm_name)
+                    .alarm_types(AlarmType::CompositeAlarm)
+                    .send()
+                    .await?;
+
+                let Some(composite_alarm) = resp.composite_alarms.unwrap_or_default().into_iter().next() else {
+                    return Ok(None);
+                };
+
+                let alarm_arn = composite_alarm
+                    .alarm_arn
+                    .clone()
+                    .unwrap_or_else(|| alarm_arn(region, &account_id, alarm_name));
+
+                let tags_resp = client.list_tags_for_resource().resource_arn(&alarm_arn).send().await?;
+
+                let actions_suppressor = composite_alarm.actions_suppressor.map(|alarm| ActionsSuppressor {
+                    alarm,
+                    wait_period: composite_alarm.actions_suppressor_wait_period,
+                    extension_period: composite_alarm.actions_suppressor_extension_period,
+                });
+
+                let alarm = CompositeAlarm {
+                    description: composite_alarm.alarm_description.filter(|d| !d.is_empty()),
+                    alarm_rule: composite_alarm.alarm_rule.unwrap_or_default(),
+                    actions_enabled: composite_alarm.actions_enabled.unwrap_or(true),
+                    alarm_actions: composite_alarm.alarm_actions.unwrap_or_default(),
+                    ok_actions: composite_alarm.ok_actions.unwrap_or_default(),
+                    insufficient_data_actions: composite_alarm.insufficient_data_actions.unwrap_or_default(),
+                    actions_suppressor,
+                    tags: tags_resp.tags.into(),
+                };
+
+                get_resource_response!(
+                    CloudWatchResource::CompositeAlarm(alarm),
+                    [(String::from("alarm_arn"), alarm_arn)]
+                )
             }
 
             CloudWatchResourceAddress::Dashboard(region, dashboard_name) => {
-                let client = self.get_or_init_client(&region).await?;
-
-                let dashboard_response = client.get_dashboard().dashboard_name(&dashboard_name).send().await;
-
-                match dashboard_response {
-                    Ok(response) => {
-                        if let Some(dashboard_body) = response.dashboard_body {
-                            let body: ron::Value = serde_json::from_str(&dashboard_body)
-                                .map_err(|e| anyhow::anyhow!("Failed to parse dashboard body: {}", e))?;
-
-                            let dashboard = Dashboard {
-                                name: dashboard_name.clone(),
-                                body,
-                                tags: Tags::default(), // TODO: Fetch tags
-                            };
-
-                            get_resource_response!(
-                                CloudWatchResource::Dashboard(dashboard),
-                                [(String::from("dashboard_name"), dashboard_name)]
-                            )
-                        } else {
-                            Ok(None)
-                        }
-                    }
-                    Err(_) => Ok(None),
-                }
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.get_dashboard().dashboard_name(dashboard_name).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(GetDashboardError::DashboardNotFoundError(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let Some(body) = resp.dashboard_body else {
+                    return Ok(None);
+                };
+
+                let dashboard = Dashboard {
+                    body: json_to_ron(&body)?,
+                };
+
+                get_resource_response!(
+                    CloudWatchResource::Dashboard(dashboard),
+                    [(String::from("dashboard_arn"), resp.dashboard_arn.unwrap_or_default())]
+                )
             }
 
             CloudWatchResourceAddress::LogGroup(region, log_group_name) => {
-                let logs_client = aws_sdk_cloudwatchlogs::Client::new(
-                    &aws_config::defaults(aws_config::BehaviorVersion::latest())
-                        .region(aws_config::Region::new(region.clone()))
-                        .load()
-                        .await,
-                );
+                let logs_client = self.get_or_init_logs_client(region).await?;
 
                 let log_group_response = logs_client
                     .describe_log_groups()
-                    .log_group_name_prefix(&log_group_name)
+                    .log_group_name_prefix(log_group_name)
                     .send()
                     .await?;
 
-                if let Some(log_groups) = log_group_response.log_groups {
-                    if let Some(aws_log_group) = log_groups
-                        .into_iter()
-                        .find(|lg| lg.log_group_name.as_ref() == Some(&log_group_name))
-                    {
-                        let retention_policy = aws_log_group
-                            .retention_in_days
-                            .map(|days| RetentionPolicy { retention_in_days: days });
-
-                        let log_group = LogGroup {
-                            retention_policy,
-                            kms_key_id: aws_log_group.kms_key_id,
-                            metric_filters: None,  // TODO: Fetch metric filters
-                            tags: Tags::default(), // TODO: Fetch tags
-                        };
-
-                        get_resource_response!(
-                            CloudWatchResource::LogGroup(log_group),
-                            [(String::from("log_group_name"), log_group_name)]
-                        )
-                    } else {
-                        Ok(None)
-                    }
-                } else {
-                    Ok(None)
-                }
+                let Some(aws_log_group) = log_group_response
+                    .log_groups
+                    .unwrap_or_default()
+                    .into_iter()
+                    .find(|lg| lg.log_group_name.as_ref() == Some(log_group_name))
+                else {
+                    return Ok(None);
+                };
+
+                let log_group_arn = log_group_arn(region, &account_id, log_group_name);
+                let tags_resp = logs_client
+                    .list_tags_for_resource()
+                    .resource_arn(&log_group_arn)
+                    .send()
+                    .await?;
+
+                let log_group = LogGroup {
+                    retention_policy: aws_log_group
+                        .retention_in_days
+                        .map(|days| RetentionPolicy { retention_in_days: days }),
+                    kms_key_id: aws_log_group.kms_key_id,
+                    metric_filters: None, // TODO: Fetch metric filters
+                    tags: tags_resp.tags.into(),
+                };
+
+                get_resource_response!(
+                    CloudWatchResource::LogGroup(log_group),
+                    [(String::from("log_group_arn"), log_group_arn)]
+                )
             }
 
             CloudWatchResourceAddress::LogStream(region, log_group_name, log_stream_name) => {
-                let logs_client = aws_sdk_cloudwatchlogs::Client::new(
-                    &aws_config::defaults(aws_config::BehaviorVersion::latest())
-                        .region(aws_config::Region::new(region.clone()))
-                        .load()
-                        .await,
-                );
+                let logs_client = self.get_or_init_logs_client(region).await?;
 
                 let log_stream_response = logs_client
                     .describe_log_streams()
-                    .log_group_name(&log_group_name)
-                    .log_stream_name_prefix(&log_stream_name)
+                    .log_group_name(log_group_name)
+                    .log_stream_name_prefix(log_stream_name)
                     .send()
                     .await?;
 
-                if let Some(log_streams) = log_stream_response.log_streams {
-                    if let Some(_aws_log_stream) = log_streams
-                        .into_iter()
-                        .find(|ls| ls.log_stream_name.as_ref() == Some(&log_stream_name))
-                    {
-                        let log_stream = LogStream {
-                            name: log_stream_name.clone(),
-                            log_group_name: log_group_name.clone(),
-                        };
-
-                        get_resource_response!(
-                            CloudWatchResource::LogStream(log_stream),
-                            [(String::from("log_stream_name"), log_stream_name)]
-                        )
-                    } else {
-                        Ok(None)
-                    }
-                } else {
-                    Ok(None)
+                if !log_stream_response
+                    .log_streams
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|ls| ls.log_stream_name.as_ref() == Some(log_stream_name))
+                {
+                    return Ok(None);
                 }
+
+                let log_stream = LogStream {
+                    name: log_stream_name.clone(),
+                    log_group_name: log_group_name.clone(),
+                };
+
+                get_resource_response!(
+                    CloudWatchResource::LogStream(log_stream),
+                    [(String::from("log_stream_name"), log_stream_name.clone())]
+                )
             }
 
             CloudWatchResourceAddress::Metric(region, namespace, metric_name) => {
-                let client = self.get_or_init_client(&region).await?;
+                let client = self.get_or_init_client(region).await?;
 
                 let metrics_response = client
                     .list_metrics()
-                    .namespace(&namespace)
-                    .metric_name(&metric_name)
+                    .namespace(namespace)
+                    .metric_name(metric_name)
                     .send()
                     .await?;
 
-                if let Some(metrics) = metrics_response.metrics {
-                    if let Some(aws_metric) = metrics.into_iter().next() {
-                        let dimensions = aws_metric.dimensions.map(|dims| {
-                            dims.into_iter()
-                                .filter_map(|d| {
-                                    if let (Some(name), Some(value)) = (d.name, d.value) {
-                                        Some(Dimension { name, value })
-                                    } else {
-                                        None
-                                    }
-                                })
-                                .collect()
-                        });
-
-                        let metric = Metric {
-                            namespace: namespace.clone(),
-                            name: metric_name.clone(),
-                            dimensions,
-                            stat_options: None,
-                            tags: Tags::default(),
-                        };
-
-                        get_resource_response!(
-                            CloudWatchResource::Metric(metric),
-                            [(String::from("metric_name"), metric_name)]
-                        )
-                    } else {
-                        Ok(None)
-                    }
-                } else {
-                    Ok(None)
-                }
+                let Some(aws_metric) = metrics_response.metrics.unwrap_or_default().into_iter().next() else {
+                    return Ok(None);
+                };
+
+                let dimensions = aws_metric.dimensions.map(|dims| {
+                    dims.into_iter()
+                        .filter_map(|d| match (d.name, d.value) {
+                            (Some(name), Some(value)) => Some(Dimension { name, value }),
+                            _ => None,
+                        })
+                        .collect()
+                });
+
+                let metric = Metric {
+                    namespace: namespace.clone(),
+                    name: metric_name.clone(),
+                    dimensions,
+                    stat_options: None,
+                    tags: Tags::default(),
+                };
+
+                get_resource_response!(
+                    CloudWatchResource::Metric(metric),
+                    [(String::from("metric_name"), metric_name.clone())]
+                )
             }
 
             CloudWatchResourceAddress::EventRule(region, rule_name) => {
-                let events_client = aws_sdk_eventbridge::Client::new(
-                    &aws_config::defaults(aws_config::BehaviorVersion::latest())
-                        .region(aws_config::Region::new(region.clone()))
-                        .load()
-                        .await,
-                );
-
-                let rule_response = events_client.describe_rule().name(&rule_name).send().await;
-
-                match rule_response {
-                    Ok(response) => {
-                        let event_pattern = response.event_pattern.map(|pattern| {
-                            let pattern_value: ron::Value =
-                                serde_json::from_str(&pattern).unwrap_or_else(|_| ron::Value::String(pattern));
-                            EventPattern { pattern: pattern_value }
-                        });
-
-                        let rule = EventRule {
-                            name: rule_name.clone(),
-                            description: response.description,
-                            schedule_expression: response.schedule_expression,
-                            event_pattern,
-                            state: response
-                                .state
-                                .map(|s| s.as_str().to_string())
-                                .unwrap_or_else(|| "ENABLED".to_string()),
-                            targets: None, // TODO: Fetch targets
-                            role_arn: response.role_arn,
-                            tags: Tags::default(), // TODO: Fetch tags
-                        };
-
-                        get_resource_response!(
-                            CloudWatchResource::EventRule(rule),
-                            [(String::from("rule_name"), rule_name)]
-                        )
-                    }
-                    Err(_) => Ok(None),
-                }
+                let events_client = self.get_or_init_events_client(region).await?;
+
+                let response = match events_client.describe_rule().name(rule_name).send().await {
+                    Ok(response) => response,
+                    Err(e) => match e.as_service_error() {
+                        Some(DescribeRuleError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let rule_arn = response.arn.clone().unwrap_or_default();
+                let tags_resp = events_client.list_tags_for_resource().resource_arn(&rule_arn).send().await?;
+                let tags: HashMap<String, String> = tags_resp
+                    .tags
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|t| (t.key, t.value))
+                    .collect();
+
+                let event_pattern = response.event_pattern.map(|pattern| {
+                    let pattern_value: ron::Value =
+                        serde_json::from_str(&pattern).unwrap_or_else(|_| ron::Value::String(pattern));
+                    EventPattern { pattern: pattern_value }
+                });
+
+                let rule = EventRule {
+                    name: rule_name.clone(),
+                    description: response.description,
+                    schedule_expression: response.schedule_expression,
+                    event_pattern,
+                    state: response
+                        .state
+                        .map(|s| s.as_str().to_string())
+                        .unwrap_or_else(|| "ENABLED".to_string()),
+                    targets: None, // TODO: Fetch targets
+                    role_arn: response.role_arn,
+                    tags: Tags::from(Some(tags)),
+                };
+
+                get_resource_response!(CloudWatchResource::EventRule(rule), [(String::from("rule_arn"), rule_arn)])
             }
         }
     }
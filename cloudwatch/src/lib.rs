@@ -1,4 +1,4 @@
 mod addr;
-mod config;
 mod resource;
-mod tags;
+pub mod op;
+pub mod tags;
@@ -2,37 +2,27 @@ use autoschematic_core::{connector::ConnectorOp, util::RON};
 use serde::{Deserialize, Serialize};
 
 use super::{
-    resource::{
-        Alarm, AlarmAction, Dashboard, Dimension, EventPattern, EventRule, EventTarget, LogGroup, LogStream, MetricDataQuery, MetricTransformation,
-    },
+    resource::{Alarm, CompositeAlarm, Dashboard, Dimension, EventPattern, EventRule, LogGroup, LogStream},
     tags::Tags,
 };
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum CloudWatchConnectorOp {
-    // Alarm operations
+    // Metric alarm operations
     CreateAlarm(Alarm),
-    UpdateAlarm {
-        threshold: Option<f64>,
-        evaluation_periods: Option<i64>,
-        datapoints_to_alarm: Option<i64>,
-        comparison_operator: Option<String>,
-        treat_missing_data: Option<String>,
-        evaluate_low_sample_count_percentile: Option<String>,
-        metrics: Option<Vec<MetricDataQuery>>,
-        alarm_actions: Option<Vec<AlarmAction>>,
-        ok_actions: Option<Vec<AlarmAction>>,
-        insufficient_data_actions: Option<Vec<AlarmAction>>,
-    },
+    /// PutMetricAlarm replaces the whole alarm definition, so updates carry all of it. Tags are managed separately.
+    UpdateAlarm(Alarm),
     UpdateAlarmTags(Tags, Tags),
     DeleteAlarm,
 
+    // Composite alarm operations
+    CreateCompositeAlarm(CompositeAlarm),
+    UpdateCompositeAlarm(CompositeAlarm),
+    UpdateCompositeAlarmTags(Tags, Tags),
+    DeleteCompositeAlarm,
+
     // Dashboard operations
-    CreateDashboard(Dashboard),
-    UpdateDashboardBody {
-        body: ron::Value,
-    },
-    UpdateDashboardTags(Tags, Tags),
+    PutDashboard(Dashboard),
     DeleteDashboard,
 
     // Log Group operations
@@ -49,23 +39,6 @@ pub enum CloudWatchConnectorOp {
     // Log Stream operations
     CreateLogStream(LogStream),
     DeleteLogStream,
-    PutLogEvents {
-        log_group_name:  String,
-        log_stream_name: String,
-        log_events:      Vec<LogEvent>,
-    },
-
-    // Metric Filter operations
-    PutMetricFilter {
-        log_group_name: String,
-        filter_name: String,
-        filter_pattern: String,
-        metric_transformations: Vec<MetricTransformation>,
-    },
-    DeleteMetricFilter {
-        log_group_name: String,
-        filter_name:    String,
-    },
 
     // Metric operations
     PutMetricData {
@@ -85,71 +58,6 @@ pub enum CloudWatchConnectorOp {
     },
     UpdateEventRuleTags(Tags, Tags),
     DeleteEventRule,
-
-    // Event Target operations
-    PutEventTargets {
-        rule_name: String,
-        targets:   Vec<EventTarget>,
-    },
-    RemoveEventTargets {
-        rule_name:  String,
-        target_ids: Vec<String>,
-    },
-
-    // Anomaly Detection operations
-    CreateAnomalyDetector {
-        namespace: String,
-        metric_name: String,
-        dimensions: Option<Vec<Dimension>>,
-        stat: String,
-        configuration: Option<ron::Value>, // AnomalyDetectorConfiguration as ron::Value
-    },
-    UpdateAnomalyDetector {
-        namespace: String,
-        metric_name: String,
-        dimensions: Option<Vec<Dimension>>,
-        stat: String,
-        configuration: ron::Value,
-    },
-    DeleteAnomalyDetector {
-        namespace: String,
-        metric_name: String,
-        dimensions: Option<Vec<Dimension>>,
-        stat: String,
-    },
-
-    // Composite Alarm operations
-    CreateCompositeAlarm {
-        alarm_name: String,
-        alarm_rule: String, // ALARM(alarm1) AND ALARM(alarm2) OR ...
-        actions_enabled: bool,
-        alarm_actions: Option<Vec<String>>,
-        ok_actions: Option<Vec<String>>,
-        insufficient_data_actions: Option<Vec<String>>,
-        tags: Tags,
-    },
-    UpdateCompositeAlarm {
-        alarm_name: String,
-        alarm_rule: Option<String>,
-        actions_enabled: Option<bool>,
-        alarm_actions: Option<Vec<String>>,
-        ok_actions: Option<Vec<String>>,
-        insufficient_data_actions: Option<Vec<String>>,
-    },
-    UpdateCompositeAlarmTags {
-        alarm_name:     String,
-        tags_to_add:    Tags,
-        tags_to_remove: Tags,
-    },
-    DeleteCompositeAlarm {
-        alarm_name: String,
-    },
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct LogEvent {
-    pub timestamp: i64,
-    pub message:   String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -0,0 +1,599 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use autoschematic_core::connector::OpExecResponse;
+use aws_sdk_cloudwatch::types::{ComparisonOperator, StandardUnit, Statistic};
+
+use crate::{
+    op::MetricData,
+    resource::{Alarm, CompositeAlarm, Dashboard, EventPattern, EventRule, LogGroup},
+    tags::{Tags, tag_diff},
+    util::{ron_to_json, to_sdk_dimensions, to_sdk_metric_data_query},
+};
+
+/// Calls PutMetricAlarm, which creates the alarm or replaces the definition of an existing one
+async fn put_metric_alarm(
+    client: &aws_sdk_cloudwatch::Client,
+    alarm_name: &str,
+    alarm: &Alarm,
+    tags: Option<&Tags>,
+) -> anyhow::Result<()> {
+    let metrics = alarm
+        .metrics
+        .as_ref()
+        .map(|metrics| metrics.iter().map(to_sdk_metric_data_query).collect());
+
+    // Single-metric alarms take dimensions; metric math alarms carry them in each query
+    let dimensions = if alarm.metric_name.is_some() {
+        Some(to_sdk_dimensions(&alarm.dimensions))
+    } else {
+        None
+    };
+
+    let mut request = client
+        .put_metric_alarm()
+        .alarm_name(alarm_name)
+        .set_alarm_description(alarm.description.clone())
+        .actions_enabled(alarm.actions_enabled)
+        .set_alarm_actions(Some(alarm.alarm_actions.clone()))
+        .set_ok_actions(Some(alarm.ok_actions.clone()))
+        .set_insufficient_data_actions(Some(alarm.insufficient_data_actions.clone()))
+        .set_namespace(alarm.namespace.clone())
+        .set_metric_name(alarm.metric_name.clone())
+        .set_dimensions(dimensions)
+        .set_statistic(alarm.statistic.as_deref().map(Statistic::from))
+        .set_extended_statistic(alarm.extended_statistic.clone())
+        .set_period(alarm.period)
+        .set_unit(alarm.unit.as_deref().map(StandardUnit::from))
+        .set_metrics(metrics)
+        .comparison_operator(ComparisonOperator::from(alarm.comparison_operator.as_str()))
+        .set_threshold(alarm.threshold)
+        .set_threshold_metric_id(alarm.threshold_metric_id.clone())
+        .evaluation_periods(alarm.evaluation_periods)
+        .set_datapoints_to_alarm(alarm.datapoints_to_alarm)
+        .set_treat_missing_data(alarm.treat_missing_data.clone())
+        .set_evaluate_low_sample_count_percentile(alarm.evaluate_low_sample_count_percentile.clone());
+
+    // Tags passed to PutMetricAlarm only apply to new alarms
+    if let Some(tags) = tags
+        && tags.len() > 0
+    {
+        request = request.set_tags(Some(tags.to_vec()?));
+    }
+
+    request.send().await?;
+    Ok(())
+}
+
+/// Creates a metric alarm using the provided configuration
+pub async fn create_alarm(
+    client: &aws_sdk_cloudwatch::Client,
+    alarm_name: &str,
+    alarm_arn: &str,
+    alarm: &Alarm,
+) -> Result<OpExecResponse, anyhow::Error> {
+    put_metric_alarm(client, alarm_name, alarm, Some(&alarm.tags)).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("alarm_arn"), Some(alarm_arn.to_string()))])),
+        friendly_message: Some(format!("Created CloudWatch alarm {}", alarm_name)),
+    })
+}
+
+/// Replaces a metric alarm's definition in place
+pub async fn update_alarm(
+    client: &aws_sdk_cloudwatch::Client,
+    alarm_name: &str,
+    alarm: &Alarm,
+) -> Result<OpExecResponse, anyhow::Error> {
+    put_metric_alarm(client, alarm_name, alarm, None).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated CloudWatch alarm {}", alarm_name)),
+    })
+}
+
+/// Calls PutCompositeAlarm, which creates the alarm or replaces the definition of an existing one
+async fn put_composite_alarm(
+    client: &aws_sdk_cloudwatch::Client,
+    alarm_name: &str,
+    alarm: &CompositeAlarm,
+    tags: Option<&Tags>,
+) -> anyhow::Result<()> {
+    let mut request = client
+        .put_composite_alarm()
+        .alarm_name(alarm_name)
+        .alarm_rule(&alarm.alarm_rule)
+        .set_alarm_description(alarm.description.clone())
+        .actions_enabled(alarm.actions_enabled)
+        .set_alarm_actions(Some(alarm.alarm_actions.clone()))
+        .set_ok_actions(Some(alarm.ok_actions.clone()))
+        .set_insufficient_data_actions(Some(alarm.insufficient_data_actions.clone()));
+
+    if let Some(suppressor) = &alarm.actions_suppressor {
+        request = request
+            .actions_suppressor(&suppressor.alarm)
+            .set_actions_suppressor_wait_period(suppressor.wait_period)
+            .set_actions_suppressor_extension_period(suppressor.extension_period);
+    }
+
+    // Tags passed to PutCompositeAlarm only apply to new alarms
+    if let Some(tags) = tags
+        && tags.len() > 0
+    {
+        request = request.set_tags(Some(tags.to_vec()?));
+    }
+
+    request.send().await?;
+    Ok(())
+}
+
+/// Creates a composite alarm using the provided configuration
+pub async fn create_composite_alarm(
+    client: &aws_sdk_cloudwatch::Client,
+    alarm_name: &str,
+    alarm_arn: &str,
+    alarm: &CompositeAlarm,
+) -> Result<OpExecResponse, anyhow::Error> {
+    put_composite_alarm(client, alarm_name, alarm, Some(&alarm.tags)).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("alarm_arn"), Some(alarm_arn.to_string()))])),
+        friendly_message: Some(format!("Created CloudWatch composite alarm {}", alarm_name)),
+    })
+}
+
+/// Replaces a composite alarm's definition in place
+pub async fn update_composite_alarm(
+    client: &aws_sdk_cloudwatch::Client,
+    alarm_name: &str,
+    alarm: &CompositeAlarm,
+) -> Result<OpExecResponse, anyhow::Error> {
+    put_composite_alarm(client, alarm_name, alarm, None).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated CloudWatch composite alarm {}", alarm_name)),
+    })
+}
+
+/// Updates tags on a metric or composite alarm
+pub async fn update_alarm_tags(
+    client: &aws_sdk_cloudwatch::Client,
+    alarm_arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags)?;
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(alarm_arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client
+            .tag_resource()
+            .resource_arn(alarm_arn)
+            .set_tags(Some(new_tagset))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for CloudWatch alarm {}", alarm_arn)),
+    })
+}
+
+/// Updates tags on a metric
+pub async fn update_metric_tags(
+    client: &aws_sdk_cloudwatch::Client,
+    metric_arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags)?;
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(metric_arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client
+            .tag_resource()
+            .resource_arn(metric_arn)
+            .set_tags(Some(new_tagset))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for CloudWatch metric {}", metric_arn)),
+    })
+}
+
+/// Deletes a metric or composite alarm
+pub async fn delete_alarm(client: &aws_sdk_cloudwatch::Client, alarm_name: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_alarms().alarm_names(alarm_name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("alarm_arn"), None)])),
+        friendly_message: Some(format!("Deleted CloudWatch alarm {}", alarm_name)),
+    })
+}
+
+/// Creates or replaces a dashboard
+pub async fn put_dashboard(
+    client: &aws_sdk_cloudwatch::Client,
+    dashboard_name: &str,
+    dashboard: &Dashboard,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let resp = client
+        .put_dashboard()
+        .dashboard_name(dashboard_name)
+        .dashboard_body(ron_to_json(&dashboard.body)?)
+        .send()
+        .await?;
+
+    // CloudWatch saves the dashboard even if some widgets are invalid, and reports them here
+    let warnings: Vec<String> = resp
+        .dashboard_validation_messages
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|m| m.message)
+        .collect();
+
+    let friendly_message = if warnings.is_empty() {
+        format!("Saved CloudWatch dashboard {}", dashboard_name)
+    } else {
+        format!(
+            "Saved CloudWatch dashboard {}, with warnings:\n{}",
+            dashboard_name,
+            warnings.join("\n")
+        )
+    };
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(friendly_message),
+    })
+}
+
+/// Deletes a dashboard
+pub async fn delete_dashboard(
+    client: &aws_sdk_cloudwatch::Client,
+    dashboard_name: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_dashboards().dashboard_names(dashboard_name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Deleted CloudWatch dashboard {}", dashboard_name)),
+    })
+}
+
+/// Creates a log group, then sets its retention policy if one is given
+pub async fn create_log_group(
+    client: &aws_sdk_cloudwatchlogs::Client,
+    log_group_name: &str,
+    log_group_arn: &str,
+    log_group: &LogGroup,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let tags = if log_group.tags.len() > 0 {
+        Some(log_group.tags.to_map())
+    } else {
+        None
+    };
+
+    client
+        .create_log_group()
+        .log_group_name(log_group_name)
+        .set_kms_key_id(log_group.kms_key_id.clone())
+        .set_tags(tags)
+        .send()
+        .await?;
+
+    if let Some(retention_policy) = &log_group.retention_policy {
+        client
+            .put_retention_policy()
+            .log_group_name(log_group_name)
+            .retention_in_days(retention_policy.retention_in_days)
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("log_group_arn"), Some(log_group_arn.to_string()))])),
+        friendly_message: Some(format!("Created CloudWatch log group {}", log_group_name)),
+    })
+}
+
+pub async fn update_log_group_retention(
+    client: &aws_sdk_cloudwatchlogs::Client,
+    log_group_name: &str,
+    retention_in_days: i32,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .put_retention_policy()
+        .log_group_name(log_group_name)
+        .retention_in_days(retention_in_days)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated retention policy for CloudWatch log group {}", log_group_name)),
+    })
+}
+
+/// Associates the log group with a KMS key, or disassociates it from its current one if `kms_key_id` is None
+pub async fn update_log_group_kms_key(
+    client: &aws_sdk_cloudwatchlogs::Client,
+    log_group_name: &str,
+    kms_key_id: Option<&str>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    match kms_key_id {
+        Some(kms_key_id) => {
+            client
+                .associate_kms_key()
+                .log_group_name(log_group_name)
+                .kms_key_id(kms_key_id)
+                .send()
+                .await?;
+        }
+        None => {
+            client.disassociate_kms_key().log_group_name(log_group_name).send().await?;
+        }
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated KMS key for CloudWatch log group {}", log_group_name)),
+    })
+}
+
+pub async fn update_log_group_tags(
+    client: &aws_sdk_cloudwatchlogs::Client,
+    log_group_arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags)?;
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(log_group_arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client
+            .tag_resource()
+            .resource_arn(log_group_arn)
+            .set_tags(Some(new_tagset.into_iter().map(|tag| (tag.key, tag.value)).collect()))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for CloudWatch log group {}", log_group_arn)),
+    })
+}
+
+pub async fn delete_log_group(
+    client: &aws_sdk_cloudwatchlogs::Client,
+    log_group_name: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_log_group().log_group_name(log_group_name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("log_group_arn"), None)])),
+        friendly_message: Some(format!("Deleted CloudWatch log group {}", log_group_name)),
+    })
+}
+
+pub async fn create_log_stream(
+    client: &aws_sdk_cloudwatchlogs::Client,
+    log_group_name: &str,
+    log_stream_name: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .create_log_stream()
+        .log_group_name(log_group_name)
+        .log_stream_name(log_stream_name)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Created CloudWatch log stream {} in log group {}",
+            log_stream_name, log_group_name
+        )),
+    })
+}
+
+pub async fn delete_log_stream(
+    client: &aws_sdk_cloudwatchlogs::Client,
+    log_group_name: &str,
+    log_stream_name: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .delete_log_stream()
+        .log_group_name(log_group_name)
+        .log_stream_name(log_stream_name)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Deleted CloudWatch log stream {} in log group {}",
+            log_stream_name, log_group_name
+        )),
+    })
+}
+
+fn event_pattern_to_json(event_pattern: &EventPattern) -> anyhow::Result<String> {
+    serde_json::to_string(&event_pattern.pattern).context("Failed to serialize event pattern")
+}
+
+/// Creates an EventBridge rule. Targets are put separately.
+pub async fn create_event_rule(
+    client: &aws_sdk_eventbridge::Client,
+    rule_name: &str,
+    rule: &EventRule,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut tags = Vec::new();
+    for (key, value) in rule.tags.to_map() {
+        tags.push(aws_sdk_eventbridge::types::Tag::builder().key(key).value(value).build()?);
+    }
+
+    let resp = client
+        .put_rule()
+        .name(rule_name)
+        .set_description(rule.description.clone())
+        .set_schedule_expression(rule.schedule_expression.clone())
+        .set_event_pattern(rule.event_pattern.as_ref().map(event_pattern_to_json).transpose()?)
+        .state(aws_sdk_eventbridge::types::RuleState::from(rule.state.as_str()))
+        .set_role_arn(rule.role_arn.clone())
+        .set_tags(if tags.is_empty() { None } else { Some(tags) })
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("rule_arn"), resp.rule_arn)])),
+        friendly_message: Some(format!("Created EventBridge rule {}", rule_name)),
+    })
+}
+
+/// Calls PutRule on an existing rule. Unset fields are left as they are.
+pub async fn update_event_rule(
+    client: &aws_sdk_eventbridge::Client,
+    rule_name: &str,
+    description: Option<String>,
+    schedule_expression: Option<String>,
+    event_pattern: Option<&EventPattern>,
+    state: Option<&str>,
+    role_arn: Option<String>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .put_rule()
+        .name(rule_name)
+        .set_description(description)
+        .set_schedule_expression(schedule_expression)
+        .set_event_pattern(event_pattern.map(event_pattern_to_json).transpose()?)
+        .set_state(state.map(aws_sdk_eventbridge::types::RuleState::from))
+        .set_role_arn(role_arn)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated EventBridge rule {}", rule_name)),
+    })
+}
+
+pub async fn update_event_rule_tags(
+    client: &aws_sdk_eventbridge::Client,
+    rule_arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags)?;
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(rule_arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        let mut tags = Vec::new();
+        for tag in new_tagset {
+            tags.push(aws_sdk_eventbridge::types::Tag::builder().key(tag.key).value(tag.value).build()?);
+        }
+
+        client.tag_resource().resource_arn(rule_arn).set_tags(Some(tags)).send().await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for EventBridge rule {}", rule_arn)),
+    })
+}
+
+/// Deletes an EventBridge rule. EventBridge refuses while the rule still has targets.
+pub async fn delete_event_rule(
+    client: &aws_sdk_eventbridge::Client,
+    rule_name: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_rule().name(rule_name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("rule_arn"), None)])),
+        friendly_message: Some(format!("Deleted EventBridge rule {}", rule_name)),
+    })
+}
+
+pub async fn put_metric_data(
+    client: &aws_sdk_cloudwatch::Client,
+    namespace: &str,
+    metric_data: &[MetricData],
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut metric_data_items = Vec::new();
+    for data in metric_data {
+        let dimensions = data.dimensions.as_ref().map(|dimensions| {
+            dimensions
+                .iter()
+                .map(|dim| {
+                    aws_sdk_cloudwatch::types::Dimension::builder()
+                        .name(&dim.name)
+                        .value(&dim.value)
+                        .build()
+                })
+                .collect()
+        });
+
+        metric_data_items.push(
+            aws_sdk_cloudwatch::types::MetricDatum::builder()
+                .metric_name(&data.metric_name)
+                .set_dimensions(dimensions)
+                .set_value(data.value)
+                .set_timestamp(data.timestamp.map(aws_smithy_types::DateTime::from_secs))
+                .set_unit(data.unit.as_deref().map(StandardUnit::from))
+                .build(),
+        );
+    }
+
+    client
+        .put_metric_data()
+        .namespace(namespace)
+        .set_metric_data(Some(metric_data_items))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Put metric data for namespace {}", namespace)),
+    })
+}
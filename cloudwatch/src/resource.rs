@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use autoschematic_core::{
     connector::{Resource, ResourceAddress},
     util::RON,
@@ -8,73 +10,114 @@ use crate::tags::Tags;
 
 use super::addr::CloudWatchResourceAddress;
 
+fn default_actions_enabled() -> bool {
+    true
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
-pub struct Dimension {
-    pub name:  String,
-    pub value: String,
+#[serde(deny_unknown_fields)]
+pub struct MetricStat {
+    pub namespace: String,
+    pub metric_name: String,
+    #[serde(default)]
+    pub dimensions: HashMap<String, String>,
+    pub period: i32,
+    pub stat: String, // e.g. Average, Sum, p99
+    pub unit: Option<String>,
 }
 
+/// One metric or expression in a metric math alarm.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
 pub struct MetricDataQuery {
     pub id: String,
+    /// Exactly one of metric_stat and expression must be set.
     pub metric_stat: Option<MetricStat>,
     pub expression: Option<String>,
     pub label: Option<String>,
-    pub period: Option<i64>,
-    pub return_data: Option<bool>,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
-pub struct MetricStat {
-    pub metric: MetricIdentifier,
-    pub period: i64,
-    pub stat:   String,
-    pub unit:   Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
-pub struct MetricIdentifier {
-    pub namespace: String,
-    pub name: String,
-    pub dimensions: Option<Vec<Dimension>>,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
-pub struct AlarmAction {
-    pub arn: String,
+    /// Exactly one query in an alarm returns the data the alarm evaluates.
+    #[serde(default)]
+    pub return_data: bool,
+    pub period: Option<i32>,
 }
 
+/// A metric alarm watches either a single metric (namespace, metric_name, statistic and period)
+/// or the result of a metric math expression (metrics).
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
 pub struct Alarm {
-    pub name: String,
     pub description: Option<String>,
-    pub metric_name: Option<String>,
     pub namespace: Option<String>,
-    pub statistic: Option<String>,
+    pub metric_name: Option<String>,
+    #[serde(default)]
+    pub dimensions: HashMap<String, String>,
+    pub statistic: Option<String>, // SampleCount, Average, Sum, Minimum or Maximum
+    /// A percentile such as `p99`, in place of statistic.
     pub extended_statistic: Option<String>,
-    pub dimensions: Option<Vec<Dimension>>,
-    pub period: Option<i64>,
+    pub period: Option<i32>,
     pub unit: Option<String>,
-    pub evaluation_periods: i64,
-    pub datapoints_to_alarm: Option<i64>,
+    pub metrics: Option<Vec<MetricDataQuery>>,
+    pub comparison_operator: String, // e.g. GreaterThanThreshold
     pub threshold: Option<f64>,
-    pub comparison_operator: String,
-    pub treat_missing_data: Option<String>,
+    /// The ID of an ANOMALY_DETECTION_BAND query in metrics, for anomaly detection alarms. Replaces threshold.
+    pub threshold_metric_id: Option<String>,
+    pub evaluation_periods: i32,
+    /// If None, every evaluation period has to breach.
+    pub datapoints_to_alarm: Option<i32>,
+    pub treat_missing_data: Option<String>, // missing (the default), breaching, notBreaching or ignore
     pub evaluate_low_sample_count_percentile: Option<String>,
-    pub metrics: Option<Vec<MetricDataQuery>>,
-    pub alarm_actions: Option<Vec<AlarmAction>>,
-    pub ok_actions: Option<Vec<AlarmAction>>,
-    pub insufficient_data_actions: Option<Vec<AlarmAction>>,
+    #[serde(default = "default_actions_enabled")]
+    pub actions_enabled: bool,
+    #[serde(default)]
+    pub alarm_actions: Vec<String>,
+    #[serde(default)]
+    pub ok_actions: Vec<String>,
+    #[serde(default)]
+    pub insufficient_data_actions: Vec<String>,
     pub tags: Tags,
 }
 
+/// Suppresses a composite alarm's actions while another alarm is in ALARM.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
-pub struct Dashboard {
-    pub name: String,
-    pub body: ron::Value, // JSON object that contains widget info
+#[serde(deny_unknown_fields)]
+pub struct ActionsSuppressor {
+    /// The name or ARN of the suppressing alarm.
+    pub alarm: String,
+    pub wait_period: Option<i32>,
+    pub extension_period: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CompositeAlarm {
+    pub description: Option<String>,
+    /// e.g. `ALARM("api-5xx") AND NOT ALARM("maintenance-window")`
+    pub alarm_rule: String,
+    #[serde(default = "default_actions_enabled")]
+    pub actions_enabled: bool,
+    #[serde(default)]
+    pub alarm_actions: Vec<String>,
+    #[serde(default)]
+    pub ok_actions: Vec<String>,
+    #[serde(default)]
+    pub insufficient_data_actions: Vec<String>,
+    pub actions_suppressor: Option<ActionsSuppressor>,
     pub tags: Tags,
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Dashboard {
+    /// The dashboard body, with `widgets` and optionally `start`, `periodOverride` etc.
+    pub body: ron::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Dimension {
+    pub name:  String,
+    pub value: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct RetentionPolicy {
     pub retention_in_days: i32,
@@ -137,7 +180,7 @@ pub struct EventTarget {
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct InputTransformer {
-    pub input_paths:      std::collections::HashMap<String, String>,
+    pub input_paths:      HashMap<String, String>,
     pub input_cloudwatch: String,
 }
 
@@ -160,6 +203,7 @@ pub struct EventRule {
 
 pub enum CloudWatchResource {
     Alarm(Alarm),
+    CompositeAlarm(CompositeAlarm),
     Dashboard(Dashboard),
     LogGroup(LogGroup),
     LogStream(LogStream),
@@ -172,30 +216,13 @@ impl Resource for CloudWatchResource {
         let pretty_config = autoschematic_core::util::PrettyConfig::default();
 
         match self {
-            CloudWatchResource::Alarm(alarm) => match RON.to_string_pretty(&alarm, pretty_config) {
-                Ok(s) => Ok(s.into()),
-                Err(e) => Err(e.into()),
-            },
-            CloudWatchResource::Dashboard(dashboard) => match RON.to_string_pretty(&dashboard, pretty_config) {
-                Ok(s) => Ok(s.into()),
-                Err(e) => Err(e.into()),
-            },
-            CloudWatchResource::LogGroup(log_group) => match RON.to_string_pretty(&log_group, pretty_config) {
-                Ok(s) => Ok(s.into()),
-                Err(e) => Err(e.into()),
-            },
-            CloudWatchResource::LogStream(log_stream) => match RON.to_string_pretty(&log_stream, pretty_config) {
-                Ok(s) => Ok(s.into()),
-                Err(e) => Err(e.into()),
-            },
-            CloudWatchResource::Metric(metric) => match RON.to_string_pretty(&metric, pretty_config) {
-                Ok(s) => Ok(s.into()),
-                Err(e) => Err(e.into()),
-            },
-            CloudWatchResource::EventRule(rule) => match RON.to_string_pretty(&rule, pretty_config) {
-                Ok(s) => Ok(s.into()),
-                Err(e) => Err(e.into()),
-            },
+            CloudWatchResource::Alarm(alarm) => Ok(RON.to_string_pretty(&alarm, pretty_config)?.into()),
+            CloudWatchResource::CompositeAlarm(alarm) => Ok(RON.to_string_pretty(&alarm, pretty_config)?.into()),
+            CloudWatchResource::Dashboard(dashboard) => Ok(RON.to_string_pretty(&dashboard, pretty_config)?.into()),
+            CloudWatchResource::LogGroup(log_group) => Ok(RON.to_string_pretty(&log_group, pretty_config)?.into()),
+            CloudWatchResource::LogStream(log_stream) => Ok(RON.to_string_pretty(&log_stream, pretty_config)?.into()),
+            CloudWatchResource::Metric(metric) => Ok(RON.to_string_pretty(&metric, pretty_config)?.into()),
+            CloudWatchResource::EventRule(rule) => Ok(RON.to_string_pretty(&rule, pretty_config)?.into()),
         }
     }
 
@@ -208,21 +235,16 @@ impl Resource for CloudWatchResource {
 
         match addr {
             CloudWatchResourceAddress::Alarm(_region, _name) => Ok(CloudWatchResource::Alarm(RON.from_str(s)?)),
-            CloudWatchResourceAddress::Dashboard(_region, _name) => {
-                Ok(CloudWatchResource::Dashboard(RON.from_str(s)?))
-            }
-            CloudWatchResourceAddress::LogGroup(_region, _name) => {
-                Ok(CloudWatchResource::LogGroup(RON.from_str(s)?))
+            CloudWatchResourceAddress::CompositeAlarm(_region, _name) => {
+                Ok(CloudWatchResource::CompositeAlarm(RON.from_str(s)?))
             }
+            CloudWatchResourceAddress::Dashboard(_region, _name) => Ok(CloudWatchResource::Dashboard(RON.from_str(s)?)),
+            CloudWatchResourceAddress::LogGroup(_region, _name) => Ok(CloudWatchResource::LogGroup(RON.from_str(s)?)),
             CloudWatchResourceAddress::LogStream(_region, _group_name, _stream_name) => {
                 Ok(CloudWatchResource::LogStream(RON.from_str(s)?))
             }
-            CloudWatchResourceAddress::Metric(_region, _namespace, _name) => {
-                Ok(CloudWatchResource::Metric(RON.from_str(s)?))
-            }
-            CloudWatchResourceAddress::EventRule(_region, _name) => {
-                Ok(CloudWatchResource::EventRule(RON.from_str(s)?))
-            }
+            CloudWatchResourceAddress::Metric(_region, _namespace, _name) => Ok(CloudWatchResource::Metric(RON.from_str(s)?)),
+            CloudWatchResourceAddress::EventRule(_region, _name) => Ok(CloudWatchResource::EventRule(RON.from_str(s)?)),
         }
     }
 }
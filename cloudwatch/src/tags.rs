@@ -3,19 +3,17 @@ use std::collections::HashMap;
 use aws_sdk_cloudwatch::types::Tag;
 use serde::{Deserialize, Serialize};
 
+// CloudWatch takes tags as a list of Tag structs
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct Tags(HashMap<String, String>);
 
 impl From<Option<Vec<Tag>>> for Tags {
     fn from(value: Option<Vec<Tag>>) -> Self {
         match value {
-            Some(mut tags) => {
-                tags.sort_by_key(|t| t.key.clone());
+            Some(tags) => {
                 let mut out_map = HashMap::new();
                 for tag in tags {
-                    if let (Some(key), Some(value)) = (tag.key, tag.value) {
-                        out_map.insert(key, value);
-                    }
+                    out_map.insert(tag.key, tag.value);
                 }
                 Tags(out_map)
             }
@@ -24,15 +22,10 @@ impl From<Option<Vec<Tag>>> for Tags {
     }
 }
 
-impl From<Tags> for Option<Vec<Tag>> {
-    fn from(val: Tags) -> Self {
-        let mut out_vec = Vec::new();
-
-        for (k, v) in val.0 {
-            out_vec.push(Tag::builder().key(k).value(v).build());
-        }
-
-        Some(out_vec)
+// CloudWatch Logs and EventBridge tags are read as plain maps
+impl From<Option<HashMap<String, String>>> for Tags {
+    fn from(value: Option<HashMap<String, String>>) -> Self {
+        Tags(value.unwrap_or_default())
     }
 }
 
@@ -40,6 +33,20 @@ impl Tags {
     pub fn len(&self) -> usize {
         self.0.len()
     }
+
+    pub fn to_map(&self) -> HashMap<String, String> {
+        self.0.clone()
+    }
+
+    pub fn to_vec(&self) -> anyhow::Result<Vec<Tag>> {
+        let mut out_vec = Vec::new();
+
+        for (k, v) in &self.0 {
+            out_vec.push(Tag::builder().key(k).value(v).build()?);
+        }
+
+        Ok(out_vec)
+    }
 }
 
 // From a pair of hashmap, determine the set of aws_sdk_cloudwatch::Tag structs to pass to untag and set_tags respectively
@@ -54,11 +61,12 @@ pub fn tag_diff(old_tags: &Tags, new_tags: &Tags) -> anyhow::Result<(Vec<String>
     let mut new_tagset = Vec::new();
     for (key, new_value) in &new_tags.0 {
         if !old_tags.0.contains_key(key) {
-            new_tagset.push(Tag::builder().key(key).value(new_value).build());
+            new_tagset.push(Tag::builder().key(key).value(new_value).build()?);
         } else if let Some(old_value) = old_tags.0.get(key)
-            && old_value != new_value {
-                new_tagset.push(Tag::builder().key(key).value(new_value).build());
-            }
+            && old_value != new_value
+        {
+            new_tagset.push(Tag::builder().key(key).value(new_value).build()?);
+        }
     }
 
     Ok((untag_keys, new_tagset))
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, bail};
+use autoschematic_core::util::RON;
+use aws_sdk_cloudwatch::types as sdk;
+
+use crate::resource::{Alarm, MetricDataQuery, MetricStat};
+
+pub fn alarm_arn(region: &str, account_id: &str, name: &str) -> String {
+    format!("arn:aws:cloudwatch:{region}:{account_id}:alarm:{name}")
+}
+
+/// The ARN that CloudWatch Logs tags a log group by, without the trailing `:*` of DescribeLogGroups' `arn`.
+pub fn log_group_arn(region: &str, account_id: &str, name: &str) -> String {
+    format!("arn:aws:logs:{region}:{account_id}:log-group:{name}")
+}
+
+/// The ARN that CloudWatch tags a metric by, from its namespace and name.
+pub fn metric_arn(region: &str, account_id: &str, namespace: &str, name: &str) -> String {
+    format!("arn:aws:cloudwatch:{region}:{account_id}:metric/{namespace}/{name}")
+}
+
+pub fn event_rule_arn(region: &str, account_id: &str, name: &str) -> String {
+    format!("arn:aws:events:{region}:{account_id}:rule/{name}")
+}
+
+pub fn json_to_ron(json: &str) -> anyhow::Result<ron::Value> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    Ok(RON.from_str(&RON.to_string(&value)?)?)
+}
+
+pub fn ron_to_json(value: &ron::Value) -> anyhow::Result<String> {
+    serde_json::to_string(value).context("Failed to serialize value as JSON")
+}
+
+pub fn to_sdk_dimensions(dimensions: &HashMap<String, String>) -> Vec<sdk::Dimension> {
+    let mut out: Vec<sdk::Dimension> = dimensions
+        .iter()
+        .map(|(name, value)| sdk::Dimension::builder().name(name).value(value).build())
+        .collect();
+    out.sort_by(|a, b| a.name.cmp(&b.name));
+    out
+}
+
+pub fn from_sdk_dimensions(dimensions: Option<Vec<sdk::Dimension>>) -> HashMap<String, String> {
+    dimensions
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|d| Some((d.name?, d.value?)))
+        .collect()
+}
+
+pub fn to_sdk_metric_data_query(query: &MetricDataQuery) -> sdk::MetricDataQuery {
+    let metric_stat = query.metric_stat.as_ref().map(|stat| {
+        sdk::MetricStat::builder()
+            .metric(
+                sdk::Metric::builder()
+                    .namespace(&stat.namespace)
+                    .metric_name(&stat.metric_name)
+                    .set_dimensions(Some(to_sdk_dimensions(&stat.dimensions)))
+                    .build(),
+            )
+            .period(stat.period)
+            .stat(&stat.stat)
+            .set_unit(stat.unit.as_deref().map(sdk::StandardUnit::from))
+            .build()
+    });
+
+    sdk::MetricDataQuery::builder()
+        .id(&query.id)
+        .set_metric_stat(metric_stat)
+        .set_expression(query.expression.clone())
+        .set_label(query.label.clone())
+        .return_data(query.return_data)
+        .set_period(query.period)
+        .build()
+}
+
+pub fn from_sdk_metric_data_query(query: sdk::MetricDataQuery) -> MetricDataQuery {
+    let metric_stat = query.metric_stat.map(|stat| {
+        let metric = stat.metric.unwrap_or_else(|| sdk::Metric::builder().build());
+        MetricStat {
+            namespace: metric.namespace.unwrap_or_default(),
+            metric_name: metric.metric_name.unwrap_or_default(),
+            dimensions: from_sdk_dimensions(metric.dimensions),
+            period: stat.period.unwrap_or_default(),
+            stat: stat.stat.unwrap_or_default(),
+            unit: stat.unit.map(|u| u.as_str().to_string()),
+        }
+    });
+
+    MetricDataQuery {
+        id: query.id.unwrap_or_default(),
+        metric_stat,
+        expression: query.expression,
+        label: query.label,
+        // CloudWatch treats an unset ReturnData as true
+        return_data: query.return_data.unwrap_or(true),
+        period: query.period,
+    }
+}
+
+/// Checks that the alarm watches exactly one kind of data and has something to compare it against.
+pub fn check_alarm(alarm_name: &str, alarm: &Alarm) -> anyhow::Result<()> {
+    match (&alarm.metric_name, &alarm.metrics) {
+        (Some(_), Some(_)) => bail!("CloudWatch alarm {} can't set both metric_name and metrics", alarm_name),
+        (None, None) => bail!("CloudWatch alarm {} needs either metric_name or metrics", alarm_name),
+        (Some(_), None) => {
+            if alarm.namespace.is_none() || alarm.period.is_none() {
+                bail!("CloudWatch alarm {} needs a namespace and period for metric_name", alarm_name);
+            }
+            if alarm.statistic.is_some() == alarm.extended_statistic.is_some() {
+                bail!(
+                    "CloudWatch alarm {} needs exactly one of statistic and extended_statistic",
+                    alarm_name
+                );
+            }
+        }
+        (None, Some(metrics)) => {
+            if metrics.iter().filter(|m| m.return_data).count() != 1 {
+                bail!(
+                    "Exactly one query in the metrics of CloudWatch alarm {} must have return_data set",
+                    alarm_name
+                );
+            }
+            for metric in metrics {
+                if metric.metric_stat.is_some() == metric.expression.is_some() {
+                    bail!(
+                        "Query {} of CloudWatch alarm {} needs exactly one of metric_stat and expression",
+                        metric.id,
+                        alarm_name
+                    );
+                }
+            }
+        }
+    }
+
+    if alarm.threshold.is_some() == alarm.threshold_metric_id.is_some() {
+        bail!(
+            "CloudWatch alarm {} needs exactly one of threshold and threshold_metric_id",
+            alarm_name
+        );
+    }
+
+    if let Some(datapoints_to_alarm) = alarm.datapoints_to_alarm
+        && datapoints_to_alarm > alarm.evaluation_periods
+    {
+        bail!(
+            "CloudWatch alarm {} has datapoints_to_alarm ({}) greater than evaluation_periods ({})",
+            alarm_name,
+            datapoints_to_alarm,
+            alarm.evaluation_periods
+        );
+    }
+
+    Ok(())
+}
+
+fn describe_option<T: std::fmt::Display>(value: &Option<T>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => String::from("(unset)"),
+    }
+}
+
+/// One line per changed evaluation setting, e.g. `threshold: 80 -> 90`, so that the most common
+/// alarm changes read clearly in a plan without digging through the full diff.
+pub fn alarm_evaluation_changes(old: &Alarm, new: &Alarm) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    if old.threshold != new.threshold {
+        changes.push(format!(
+            "threshold: {} -> {}",
+            describe_option(&old.threshold),
+            describe_option(&new.threshold)
+        ));
+    }
+    if old.comparison_operator != new.comparison_operator {
+        changes.push(format!(
+            "comparison_operator: {} -> {}",
+            old.comparison_operator, new.comparison_operator
+        ));
+    }
+    if old.period != new.period {
+        changes.push(format!(
+            "period: {} -> {}",
+            describe_option(&old.period),
+            describe_option(&new.period)
+        ));
+    }
+    if old.evaluation_periods != new.evaluation_periods {
+        changes.push(format!(
+            "evaluation_periods: {} -> {}",
+            old.evaluation_periods, new.evaluation_periods
+        ));
+    }
+    if old.datapoints_to_alarm != new.datapoints_to_alarm {
+        changes.push(format!(
+            "datapoints_to_alarm: {} -> {}",
+            describe_option(&old.datapoints_to_alarm),
+            describe_option(&new.datapoints_to_alarm)
+        ));
+    }
+    if old.treat_missing_data != new.treat_missing_data {
+        changes.push(format!(
+            "treat_missing_data: {} -> {}",
+            describe_option(&old.treat_missing_data),
+            describe_option(&new.treat_missing_data)
+        ));
+    }
+
+    changes
+}
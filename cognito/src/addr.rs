@@ -0,0 +1,163 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum CognitoResourceAddress {
+    /// User pool IDs (`us-east-1_AbCdEf123`) are assigned by Cognito,
+    /// so `user_pool_id` is virtual until the pool exists.
+    UserPool { region: String, user_pool_id: String },
+    /// App client IDs are assigned by Cognito, so `client_id` is virtual until the client exists.
+    UserPoolClient {
+        region:       String,
+        user_pool_id: String,
+        client_id:    String,
+    },
+    /// A SAML, OIDC or social identity provider, named as it appears on the hosted sign-in page.
+    IdentityProvider {
+        region:        String,
+        user_pool_id:  String,
+        provider_name: String,
+    },
+    /// Resource server identifiers are often URLs, so they're percent-encoded in the path.
+    ResourceServer {
+        region:       String,
+        user_pool_id: String,
+        identifier:   String,
+    },
+    /// A user pool has at most one domain, either a Cognito prefix domain or a custom one.
+    UserPoolDomain { region: String, user_pool_id: String },
+    /// Identity pool IDs are assigned by Cognito, so `identity_pool_id` is virtual until the pool exists.
+    IdentityPool { region: String, identity_pool_id: String },
+}
+
+impl ResourceAddress for CognitoResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            CognitoResourceAddress::UserPool { region, user_pool_id } => {
+                PathBuf::from(format!("aws/cognito/{region}/user_pools/{user_pool_id}.ron"))
+            }
+            CognitoResourceAddress::UserPoolClient {
+                region,
+                user_pool_id,
+                client_id,
+            } => PathBuf::from(format!(
+                "aws/cognito/{region}/user_pools/{user_pool_id}/clients/{client_id}.ron"
+            )),
+            CognitoResourceAddress::IdentityProvider {
+                region,
+                user_pool_id,
+                provider_name,
+            } => PathBuf::from(format!(
+                "aws/cognito/{region}/user_pools/{user_pool_id}/identity_providers/{provider_name}.ron"
+            )),
+            CognitoResourceAddress::ResourceServer {
+                region,
+                user_pool_id,
+                identifier,
+            } => PathBuf::from(format!(
+                "aws/cognito/{region}/user_pools/{user_pool_id}/resource_servers/{}.ron",
+                urlencoding::encode(identifier)
+            )),
+            CognitoResourceAddress::UserPoolDomain { region, user_pool_id } => {
+                PathBuf::from(format!("aws/cognito/{region}/user_pools/{user_pool_id}/domain.ron"))
+            }
+            CognitoResourceAddress::IdentityPool {
+                region,
+                identity_pool_id,
+            } => PathBuf::from(format!("aws/cognito/{region}/identity_pools/{identity_pool_id}.ron")),
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "cognito", region, "user_pools", user_pool_id] if user_pool_id.ends_with(".ron") => {
+                let user_pool_id = user_pool_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(CognitoResourceAddress::UserPool {
+                    region: region.to_string(),
+                    user_pool_id,
+                })
+            }
+            ["aws", "cognito", region, "user_pools", user_pool_id, "clients", client_id] if client_id.ends_with(".ron") => {
+                let client_id = client_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(CognitoResourceAddress::UserPoolClient {
+                    region: region.to_string(),
+                    user_pool_id: user_pool_id.to_string(),
+                    client_id,
+                })
+            }
+            ["aws", "cognito", region, "user_pools", user_pool_id, "identity_providers", provider_name]
+                if provider_name.ends_with(".ron") =>
+            {
+                let provider_name = provider_name.strip_suffix(".ron").unwrap().to_string();
+                Ok(CognitoResourceAddress::IdentityProvider {
+                    region: region.to_string(),
+                    user_pool_id: user_pool_id.to_string(),
+                    provider_name,
+                })
+            }
+            ["aws", "cognito", region, "user_pools", user_pool_id, "resource_servers", identifier]
+                if identifier.ends_with(".ron") =>
+            {
+                let identifier = urlencoding::decode(identifier.strip_suffix(".ron").unwrap())?.into_owned();
+                Ok(CognitoResourceAddress::ResourceServer {
+                    region: region.to_string(),
+                    user_pool_id: user_pool_id.to_string(),
+                    identifier,
+                })
+            }
+            ["aws", "cognito", region, "user_pools", user_pool_id, "domain.ron"] => Ok(CognitoResourceAddress::UserPoolDomain {
+                region: region.to_string(),
+                user_pool_id: user_pool_id.to_string(),
+            }),
+            ["aws", "cognito", region, "identity_pools", identity_pool_id] if identity_pool_id.ends_with(".ron") => {
+                let identity_pool_id = identity_pool_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(CognitoResourceAddress::IdentityPool {
+                    region: region.to_string(),
+                    identity_pool_id,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for CognitoResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/cognito/<region>/user_pools/<user_pool_id>.ron",
+                description: "A user pool",
+                example:     "aws/cognito/us-east-1/user_pools/customers.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cognito/<region>/user_pools/<user_pool_id>/clients/<client_id>.ron",
+                description: "An app client of a user pool",
+                example:     "aws/cognito/us-east-1/user_pools/customers/clients/web.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cognito/<region>/user_pools/<user_pool_id>/identity_providers/<provider_name>.ron",
+                description: "A federated identity provider of a user pool",
+                example:     "aws/cognito/us-east-1/user_pools/customers/identity_providers/Google.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cognito/<region>/user_pools/<user_pool_id>/resource_servers/<identifier>.ron",
+                description: "A resource server of a user pool, with its identifier percent-encoded",
+                example:     "aws/cognito/us-east-1/user_pools/customers/resource_servers/https%3A%2F%2Fapi.example.com.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cognito/<region>/user_pools/<user_pool_id>/domain.ron",
+                description: "The hosted sign-in domain of a user pool",
+                example:     "aws/cognito/us-east-1/user_pools/customers/domain.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/cognito/<region>/identity_pools/<identity_pool_id>.ron",
+                description: "An identity pool",
+                example:     "aws/cognito/us-east-1/identity_pools/customers.ron",
+            },
+        ]
+    }
+}
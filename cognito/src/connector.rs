@@ -0,0 +1,559 @@
+pub use crate::addr::CognitoResourceAddress;
+pub use crate::op::CognitoConnectorOp;
+pub use crate::resource::CognitoResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse, VirtToPhyResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    template::ReadOutput,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::CognitoConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{
+    CognitoIdentityProvider, CustomAttribute, IdentityPool, IdentityProvider, LambdaTriggers, PasswordPolicy,
+    ResourceServer, UserPool, UserPoolClient, UserPoolDomain,
+};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct CognitoConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_cognitoidentityprovider::Client>>>,
+    identity_client_cache: Mutex<HashMap<String, Arc<aws_sdk_cognitoidentity::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<CognitoConnectorConfig>,
+    prefix: PathBuf,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl CognitoConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_cognitoidentityprovider::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_cognitoidentityprovider, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+    /// Identity pools belong to a separate service, with its own client.
+    pub async fn get_or_init_identity_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_cognitoidentity::Client>> {
+        let mut cache = self.identity_client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_cognitoidentity, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get identity client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+    /// The ID of a user pool that's referred to by name. Names that aren't user pools
+    /// in this repository are taken to be the IDs of pools made elsewhere.
+    pub fn resolve_user_pool_id(&self, region: &str, user_pool_id: &str) -> anyhow::Result<String> {
+        let user_pool = CognitoResourceAddress::UserPool {
+            region:       region.to_string(),
+            user_pool_id: user_pool_id.to_string(),
+        };
+        Ok(user_pool
+            .get_output(&self.prefix, "user_pool_id")?
+            .unwrap_or_else(|| user_pool_id.to_string()))
+    }
+
+    /// The ID of an app client that's referred to by name, falling back to the name itself as for `resolve_user_pool_id`.
+    pub fn resolve_client_id(&self, region: &str, user_pool_id: &str, client_id: &str) -> anyhow::Result<String> {
+        let pool_client = CognitoResourceAddress::UserPoolClient {
+            region:       region.to_string(),
+            user_pool_id: user_pool_id.to_string(),
+            client_id:    client_id.to_string(),
+        };
+        Ok(pool_client
+            .get_output(&self.prefix, "client_id")?
+            .unwrap_or_else(|| client_id.to_string()))
+    }
+
+    /// An identity pool with its user pool and app client names replaced by their IDs.
+    pub fn resolve_identity_pool(&self, region: &str, identity_pool: &IdentityPool) -> anyhow::Result<IdentityPool> {
+        let mut cognito_identity_providers = Vec::new();
+        for provider in &identity_pool.cognito_identity_providers {
+            cognito_identity_providers.push(CognitoIdentityProvider {
+                user_pool_id: self.resolve_user_pool_id(region, &provider.user_pool_id)?,
+                client_id: self.resolve_client_id(region, &provider.user_pool_id, &provider.client_id)?,
+                server_side_token_check: provider.server_side_token_check,
+            });
+        }
+
+        Ok(IdentityPool {
+            cognito_identity_providers,
+            ..identity_pool.clone()
+        })
+    }
+
+    /// The name in this repository of a user pool, given its ID.
+    pub fn virt_user_pool_id(&self, region: &str, user_pool_id: &str) -> anyhow::Result<String> {
+        let user_pool = CognitoResourceAddress::UserPool {
+            region:       region.to_string(),
+            user_pool_id: user_pool_id.to_string(),
+        };
+        match user_pool.phy_to_virt(&self.prefix)? {
+            Some(CognitoResourceAddress::UserPool { user_pool_id, .. }) => Ok(user_pool_id),
+            _ => Ok(user_pool_id.to_string()),
+        }
+    }
+
+    /// The name in this repository of an app client, given its ID and its pool's ID.
+    pub fn virt_client_id(&self, region: &str, user_pool_id: &str, client_id: &str) -> anyhow::Result<String> {
+        let pool_client = CognitoResourceAddress::UserPoolClient {
+            region:       region.to_string(),
+            user_pool_id: user_pool_id.to_string(),
+            client_id:    client_id.to_string(),
+        };
+        match pool_client.phy_to_virt(&self.prefix)? {
+            Some(CognitoResourceAddress::UserPoolClient { client_id, .. }) => Ok(client_id),
+            _ => Ok(client_id.to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl Connector for CognitoConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = CognitoResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(CognitoConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let cognito_config: CognitoConnectorConfig = CognitoConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = cognito_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.identity_client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(cognito_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = cognito_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn addr_virt_to_phy(&self, addr: &Path) -> anyhow::Result<VirtToPhyResponse> {
+        let addr = CognitoResourceAddress::from_path(addr)?;
+
+        match &addr {
+            CognitoResourceAddress::UserPool { region, .. } => {
+                let Some(user_pool_id) = addr.get_output(&self.prefix, "user_pool_id")? else {
+                    return Ok(VirtToPhyResponse::NotPresent);
+                };
+                Ok(VirtToPhyResponse::Present(
+                    CognitoResourceAddress::UserPool {
+                        region: region.clone(),
+                        user_pool_id,
+                    }
+                    .to_path_buf(),
+                ))
+            }
+            CognitoResourceAddress::UserPoolClient {
+                region, user_pool_id, ..
+            } => {
+                let parent_pool = CognitoResourceAddress::UserPool {
+                    region:       region.clone(),
+                    user_pool_id: user_pool_id.clone(),
+                };
+
+                let Some(user_pool_id) = parent_pool.get_output(&self.prefix, "user_pool_id")? else {
+                    return Ok(VirtToPhyResponse::Deferred(vec![ReadOutput {
+                        addr: parent_pool.to_path_buf(),
+                        key:  String::from("user_pool_id"),
+                    }]));
+                };
+
+                let Some(client_id) = addr.get_output(&self.prefix, "client_id")? else {
+                    return Ok(VirtToPhyResponse::NotPresent);
+                };
+
+                Ok(VirtToPhyResponse::Present(
+                    CognitoResourceAddress::UserPoolClient {
+                        region: region.clone(),
+                        user_pool_id,
+                        client_id,
+                    }
+                    .to_path_buf(),
+                ))
+            }
+            CognitoResourceAddress::IdentityProvider {
+                region, user_pool_id, ..
+            }
+            | CognitoResourceAddress::ResourceServer {
+                region, user_pool_id, ..
+            }
+            | CognitoResourceAddress::UserPoolDomain { region, user_pool_id } => {
+                let parent_pool = CognitoResourceAddress::UserPool {
+                    region:       region.clone(),
+                    user_pool_id: user_pool_id.clone(),
+                };
+
+                let Some(phy_user_pool_id) = parent_pool.get_output(&self.prefix, "user_pool_id")? else {
+                    return Ok(VirtToPhyResponse::Deferred(vec![ReadOutput {
+                        addr: parent_pool.to_path_buf(),
+                        key:  String::from("user_pool_id"),
+                    }]));
+                };
+
+                let phy_addr = match addr.clone() {
+                    CognitoResourceAddress::IdentityProvider {
+                        region, provider_name, ..
+                    } => CognitoResourceAddress::IdentityProvider {
+                        region,
+                        user_pool_id: phy_user_pool_id,
+                        provider_name,
+                    },
+                    CognitoResourceAddress::ResourceServer { region, identifier, .. } => {
+                        CognitoResourceAddress::ResourceServer {
+                            region,
+                            user_pool_id: phy_user_pool_id,
+                            identifier,
+                        }
+                    }
+                    _ => CognitoResourceAddress::UserPoolDomain {
+                        region:       region.clone(),
+                        user_pool_id: phy_user_pool_id,
+                    },
+                };
+
+                Ok(VirtToPhyResponse::Present(phy_addr.to_path_buf()))
+            }
+            CognitoResourceAddress::IdentityPool { region, .. } => {
+                let Some(identity_pool_id) = addr.get_output(&self.prefix, "identity_pool_id")? else {
+                    return Ok(VirtToPhyResponse::NotPresent);
+                };
+                Ok(VirtToPhyResponse::Present(
+                    CognitoResourceAddress::IdentityPool {
+                        region: region.clone(),
+                        identity_pool_id,
+                    }
+                    .to_path_buf(),
+                ))
+            }
+        }
+    }
+
+    async fn addr_phy_to_virt(&self, addr: &Path) -> anyhow::Result<Option<PathBuf>> {
+        let addr = CognitoResourceAddress::from_path(addr)?;
+
+        match &addr {
+            CognitoResourceAddress::UserPoolClient {
+                region,
+                user_pool_id,
+                client_id,
+            } => Ok(Some(
+                CognitoResourceAddress::UserPoolClient {
+                    region: region.clone(),
+                    user_pool_id: self.virt_user_pool_id(region, user_pool_id)?,
+                    client_id: self.virt_client_id(region, user_pool_id, client_id)?,
+                }
+                .to_path_buf(),
+            )),
+            CognitoResourceAddress::IdentityProvider {
+                region,
+                user_pool_id,
+                provider_name,
+            } => Ok(Some(
+                CognitoResourceAddress::IdentityProvider {
+                    region: region.clone(),
+                    user_pool_id: self.virt_user_pool_id(region, user_pool_id)?,
+                    provider_name: provider_name.clone(),
+                }
+                .to_path_buf(),
+            )),
+            CognitoResourceAddress::ResourceServer {
+                region,
+                user_pool_id,
+                identifier,
+            } => Ok(Some(
+                CognitoResourceAddress::ResourceServer {
+                    region: region.clone(),
+                    user_pool_id: self.virt_user_pool_id(region, user_pool_id)?,
+                    identifier: identifier.clone(),
+                }
+                .to_path_buf(),
+            )),
+            CognitoResourceAddress::UserPoolDomain { region, user_pool_id } => Ok(Some(
+                CognitoResourceAddress::UserPoolDomain {
+                    region:       region.clone(),
+                    user_pool_id: self.virt_user_pool_id(region, user_pool_id)?,
+                }
+                .to_path_buf(),
+            )),
+            _ => {
+                if let Some(virt_addr) = addr.phy_to_virt(&self.prefix)? {
+                    return Ok(Some(virt_addr.to_path_buf()));
+                }
+                // Not created from this repository, so there's no virtual address to map back to
+                Ok(Some(addr.to_path_buf()))
+            }
+        }
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // User pool skeleton, signing users in by email address
+        res.push(skeleton!(
+            CognitoResourceAddress::UserPool {
+                region:       String::from("[region]"),
+                user_pool_id: String::from("[user_pool_name]"),
+            },
+            CognitoResource::UserPool(UserPool {
+                name: String::from("[user_pool_name]"),
+                username_attributes: vec![String::from("email")],
+                alias_attributes: Vec::new(),
+                username_case_sensitive: false,
+                required_attributes: vec![String::from("email")],
+                custom_attributes: vec![CustomAttribute {
+                    name: String::from("[attribute_name]"),
+                    data_type: String::from("String"),
+                    mutable: true,
+                }],
+                auto_verified_attributes: vec![String::from("email")],
+                mfa_configuration: String::from("OPTIONAL"),
+                software_token_mfa: true,
+                password_policy: PasswordPolicy {
+                    minimum_length: 12,
+                    require_uppercase: true,
+                    require_lowercase: true,
+                    require_numbers: true,
+                    require_symbols: false,
+                    temporary_password_validity_days: 7,
+                },
+                account_recovery: vec![String::from("verified_email")],
+                admin_create_user_only: false,
+                email_configuration: None,
+                lambda_triggers: LambdaTriggers::default(),
+                deletion_protection: true,
+                tags: Tags::default(),
+            })
+        ));
+
+        // App client skeleton, for a browser app using the authorization code flow
+        res.push(skeleton!(
+            CognitoResourceAddress::UserPoolClient {
+                region:       String::from("[region]"),
+                user_pool_id: String::from("[user_pool_name]"),
+                client_id:    String::from("[client_name]"),
+            },
+            CognitoResource::UserPoolClient(UserPoolClient {
+                client_name: String::from("[client_name]"),
+                generate_secret: false,
+                explicit_auth_flows: vec![
+                    String::from("ALLOW_REFRESH_TOKEN_AUTH"),
+                    String::from("ALLOW_USER_SRP_AUTH"),
+                ],
+                supported_identity_providers: vec![String::from("COGNITO")],
+                allowed_oauth_flows_user_pool_client: true,
+                allowed_oauth_flows: vec![String::from("code")],
+                allowed_oauth_scopes: vec![String::from("email"), String::from("openid"), String::from("profile")],
+                callback_urls: vec![String::from("https://[app_domain]/callback")],
+                logout_urls: vec![String::from("https://[app_domain]/")],
+                access_token_validity_minutes: Some(60),
+                id_token_validity_minutes: Some(60),
+                refresh_token_validity_days: Some(30),
+                read_attributes: Vec::new(),
+                write_attributes: Vec::new(),
+                prevent_user_existence_errors: Some(String::from("ENABLED")),
+                enable_token_revocation: true,
+            })
+        ));
+
+        // OIDC identity provider skeleton
+        res.push(skeleton!(
+            CognitoResourceAddress::IdentityProvider {
+                region:        String::from("[region]"),
+                user_pool_id:  String::from("[user_pool_name]"),
+                provider_name: String::from("[provider_name]"),
+            },
+            CognitoResource::IdentityProvider(IdentityProvider {
+                provider_type: String::from("OIDC"),
+                provider_details: BTreeMap::from([
+                    (String::from("attributes_request_method"), String::from("GET")),
+                    (String::from("authorize_scopes"), String::from("openid email profile")),
+                    (String::from("client_id"), String::from("[oidc_client_id]")),
+                    (String::from("oidc_issuer"), String::from("https://[issuer_domain]")),
+                ]),
+                attribute_mapping: BTreeMap::from([(String::from("email"), String::from("email"))]),
+                idp_identifiers: Vec::new(),
+            })
+        ));
+
+        // Resource server skeleton, with scopes for a backend API
+        res.push(skeleton!(
+            CognitoResourceAddress::ResourceServer {
+                region:       String::from("[region]"),
+                user_pool_id: String::from("[user_pool_name]"),
+                identifier:   String::from("https://[api_domain]"),
+            },
+            CognitoResource::ResourceServer(ResourceServer {
+                name:   String::from("[api_name]"),
+                scopes: BTreeMap::from([
+                    (String::from("read"), String::from("Read access")),
+                    (String::from("write"), String::from("Write access")),
+                ]),
+            })
+        ));
+
+        // Domain skeleton, using a Cognito prefix domain
+        res.push(skeleton!(
+            CognitoResourceAddress::UserPoolDomain {
+                region:       String::from("[region]"),
+                user_pool_id: String::from("[user_pool_name]"),
+            },
+            CognitoResource::UserPoolDomain(UserPoolDomain {
+                domain: String::from("[domain_prefix]"),
+                certificate_arn: None,
+                managed_login_version: Some(2),
+            })
+        ));
+
+        // Identity pool skeleton, giving a user pool's signed-in users AWS credentials
+        res.push(skeleton!(
+            CognitoResourceAddress::IdentityPool {
+                region:           String::from("[region]"),
+                identity_pool_id: String::from("[identity_pool_name]"),
+            },
+            CognitoResource::IdentityPool(IdentityPool {
+                identity_pool_name: String::from("[identity_pool_name]"),
+                allow_unauthenticated_identities: false,
+                allow_classic_flow: false,
+                cognito_identity_providers: vec![CognitoIdentityProvider {
+                    user_pool_id: String::from("[user_pool_name]"),
+                    client_id: String::from("[client_name]"),
+                    server_side_token_check: false,
+                }],
+                supported_login_providers: BTreeMap::new(),
+                open_id_connect_provider_arns: Vec::new(),
+                saml_provider_arns: Vec::new(),
+                developer_provider_name: None,
+                roles: BTreeMap::from([(
+                    String::from("authenticated"),
+                    String::from("arn:aws:iam::[account_id]:role/[role_name]"),
+                )]),
+                tags: Tags::default(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = CognitoResourceAddress::from_path(addr)?;
+
+        match addr {
+            CognitoResourceAddress::UserPool { .. } => ron_check_eq::<UserPool>(a, b),
+            CognitoResourceAddress::UserPoolClient { .. } => ron_check_eq::<UserPoolClient>(a, b),
+            CognitoResourceAddress::IdentityProvider { .. } => ron_check_eq::<IdentityProvider>(a, b),
+            CognitoResourceAddress::ResourceServer { .. } => ron_check_eq::<ResourceServer>(a, b),
+            CognitoResourceAddress::UserPoolDomain { .. } => ron_check_eq::<UserPoolDomain>(a, b),
+            CognitoResourceAddress::IdentityPool { .. } => ron_check_eq::<IdentityPool>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = CognitoResourceAddress::from_path(addr)?;
+
+        match addr {
+            CognitoResourceAddress::UserPool { .. } => ron_check_syntax::<UserPool>(a),
+            CognitoResourceAddress::UserPoolClient { .. } => ron_check_syntax::<UserPoolClient>(a),
+            CognitoResourceAddress::IdentityProvider { .. } => ron_check_syntax::<IdentityProvider>(a),
+            CognitoResourceAddress::ResourceServer { .. } => ron_check_syntax::<ResourceServer>(a),
+            CognitoResourceAddress::UserPoolDomain { .. } => ron_check_syntax::<UserPoolDomain>(a),
+            CognitoResourceAddress::IdentityPool { .. } => ron_check_syntax::<IdentityPool>(a),
+        }
+    }
+}
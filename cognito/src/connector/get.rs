@@ -0,0 +1,315 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_cognitoidentity::operation::describe_identity_pool::DescribeIdentityPoolError;
+use aws_sdk_cognitoidentityprovider::operation::{
+    describe_identity_provider::DescribeIdentityProviderError, describe_resource_server::DescribeResourceServerError,
+    describe_user_pool::DescribeUserPoolError, describe_user_pool_client::DescribeUserPoolClientError,
+};
+
+use crate::{
+    addr::CognitoResourceAddress,
+    resource::{
+        CognitoIdentityProvider, CognitoResource, IdentityPool, IdentityProvider, ResourceServer, UserPoolClient,
+        UserPoolDomain,
+    },
+    util::{parse_cognito_provider_name, to_days, to_minutes, user_pool_arn, user_pool_from_sdk},
+};
+
+use super::CognitoConnector;
+
+fn to_btree_map(map: Option<HashMap<String, String>>) -> BTreeMap<String, String> {
+    map.unwrap_or_default().into_iter().collect()
+}
+
+impl CognitoConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = CognitoResourceAddress::from_path(addr)?;
+
+        let account_id = self.account_id.lock().await.clone();
+
+        match &addr {
+            CognitoResourceAddress::UserPool { region, user_pool_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.describe_user_pool().user_pool_id(user_pool_id).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(DescribeUserPoolError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let Some(pool) = resp.user_pool else {
+                    return Ok(None);
+                };
+
+                let mfa_config = client.get_user_pool_mfa_config().user_pool_id(user_pool_id).send().await?;
+                let software_token_mfa = mfa_config
+                    .software_token_mfa_configuration()
+                    .is_some_and(|c| c.enabled());
+
+                let user_pool = user_pool_from_sdk(pool, software_token_mfa);
+
+                get_resource_response!(
+                    CognitoResource::UserPool(user_pool),
+                    [
+                        (String::from("user_pool_id"), user_pool_id.clone()),
+                        (String::from("user_pool_arn"), user_pool_arn(region, &account_id, user_pool_id))
+                    ]
+                )
+            }
+            CognitoResourceAddress::UserPoolClient {
+                region,
+                user_pool_id,
+                client_id,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client
+                    .describe_user_pool_client()
+                    .user_pool_id(user_pool_id)
+                    .client_id(client_id)
+                    .send()
+                    .await
+                {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(DescribeUserPoolClientError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let Some(pool_client) = resp.user_pool_client else {
+                    return Ok(None);
+                };
+
+                // Clients made elsewhere may count their tokens' validity in other units
+                let units = pool_client.token_validity_units();
+                let user_pool_client = UserPoolClient {
+                    client_name: pool_client.client_name().unwrap_or_default().to_string(),
+                    generate_secret: pool_client.client_secret().is_some(),
+                    explicit_auth_flows: pool_client
+                        .explicit_auth_flows()
+                        .iter()
+                        .map(|f| f.as_str().to_string())
+                        .collect(),
+                    supported_identity_providers: pool_client.supported_identity_providers().to_vec(),
+                    allowed_oauth_flows_user_pool_client: pool_client.allowed_o_auth_flows_user_pool_client().unwrap_or(false),
+                    allowed_oauth_flows: pool_client
+                        .allowed_o_auth_flows()
+                        .iter()
+                        .map(|f| f.as_str().to_string())
+                        .collect(),
+                    allowed_oauth_scopes: pool_client.allowed_o_auth_scopes().to_vec(),
+                    callback_urls: pool_client.callback_urls().to_vec(),
+                    logout_urls: pool_client.logout_urls().to_vec(),
+                    access_token_validity_minutes: pool_client
+                        .access_token_validity()
+                        .map(|v| to_minutes(v, units.and_then(|u| u.access_token()))),
+                    id_token_validity_minutes: pool_client
+                        .id_token_validity()
+                        .map(|v| to_minutes(v, units.and_then(|u| u.id_token()))),
+                    refresh_token_validity_days: Some(pool_client.refresh_token_validity())
+                        .filter(|v| *v > 0)
+                        .map(|v| to_days(v, units.and_then(|u| u.refresh_token()))),
+                    read_attributes: pool_client.read_attributes().to_vec(),
+                    write_attributes: pool_client.write_attributes().to_vec(),
+                    prevent_user_existence_errors: pool_client
+                        .prevent_user_existence_errors()
+                        .map(|p| p.as_str().to_string()),
+                    enable_token_revocation: pool_client.enable_token_revocation().unwrap_or(false),
+                };
+
+                get_resource_response!(
+                    CognitoResource::UserPoolClient(user_pool_client),
+                    [(String::from("client_id"), client_id.clone())]
+                )
+            }
+            CognitoResourceAddress::IdentityProvider {
+                region,
+                user_pool_id,
+                provider_name,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client
+                    .describe_identity_provider()
+                    .user_pool_id(user_pool_id)
+                    .provider_name(provider_name)
+                    .send()
+                    .await
+                {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(DescribeIdentityProviderError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let Some(provider) = resp.identity_provider else {
+                    return Ok(None);
+                };
+
+                let mut provider_details = to_btree_map(provider.provider_details.clone());
+                provider_details.remove("client_secret");
+
+                let identity_provider = IdentityProvider {
+                    provider_type: provider
+                        .provider_type()
+                        .map(|t| t.as_str().to_string())
+                        .unwrap_or_default(),
+                    provider_details,
+                    attribute_mapping: to_btree_map(provider.attribute_mapping.clone()),
+                    idp_identifiers: provider.idp_identifiers().to_vec(),
+                };
+
+                get_resource_response!(CognitoResource::IdentityProvider(identity_provider))
+            }
+            CognitoResourceAddress::ResourceServer {
+                region,
+                user_pool_id,
+                identifier,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client
+                    .describe_resource_server()
+                    .user_pool_id(user_pool_id)
+                    .identifier(identifier)
+                    .send()
+                    .await
+                {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(DescribeResourceServerError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let Some(server) = resp.resource_server else {
+                    return Ok(None);
+                };
+
+                let resource_server = ResourceServer {
+                    name:   server.name().unwrap_or_default().to_string(),
+                    scopes: server
+                        .scopes()
+                        .iter()
+                        .map(|s| (s.scope_name().to_string(), s.scope_description().to_string()))
+                        .collect(),
+                };
+
+                get_resource_response!(CognitoResource::ResourceServer(resource_server))
+            }
+            CognitoResourceAddress::UserPoolDomain { region, user_pool_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.describe_user_pool().user_pool_id(user_pool_id).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(DescribeUserPoolError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                // The pool only knows its domain's name; the rest comes from DescribeUserPoolDomain
+                let Some(domain) = resp
+                    .user_pool
+                    .and_then(|p| p.custom_domain.or(p.domain))
+                    .filter(|d| !d.is_empty())
+                else {
+                    return Ok(None);
+                };
+
+                let resp = client.describe_user_pool_domain().domain(&domain).send().await?;
+                let Some(description) = resp.domain_description else {
+                    return Ok(None);
+                };
+
+                let user_pool_domain = UserPoolDomain {
+                    domain,
+                    certificate_arn: description
+                        .custom_domain_config()
+                        .map(|c| c.certificate_arn().to_string()),
+                    managed_login_version: description.managed_login_version(),
+                };
+
+                let mut outputs = HashMap::new();
+                if let Some(distribution) = description.cloud_front_distribution() {
+                    outputs.insert(String::from("cloudfront_distribution"), distribution.to_string());
+                }
+
+                Ok(Some(GetResourceResponse {
+                    resource_definition: CognitoResource::UserPoolDomain(user_pool_domain).to_bytes()?,
+                    virt_addr: None,
+                    outputs: Some(outputs),
+                }))
+            }
+            CognitoResourceAddress::IdentityPool {
+                region,
+                identity_pool_id,
+            } => {
+                let client = self.get_or_init_identity_client(region).await?;
+
+                let resp = match client.describe_identity_pool().identity_pool_id(identity_pool_id).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(DescribeIdentityPoolError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let roles = client
+                    .get_identity_pool_roles()
+                    .identity_pool_id(identity_pool_id)
+                    .send()
+                    .await?
+                    .roles;
+
+                // Refer to user pools and app clients in this repository by name, as they're written
+                let mut cognito_identity_providers = Vec::new();
+                for provider in resp.cognito_identity_providers() {
+                    let provider_name = provider.provider_name().unwrap_or_default();
+                    let client_id = provider.client_id().unwrap_or_default();
+                    let (user_pool_id, client_id) = match parse_cognito_provider_name(provider_name) {
+                        Some((pool_region, user_pool_id)) => (
+                            self.virt_user_pool_id(&pool_region, &user_pool_id)?,
+                            self.virt_client_id(&pool_region, &user_pool_id, client_id)?,
+                        ),
+                        None => (provider_name.to_string(), client_id.to_string()),
+                    };
+                    cognito_identity_providers.push(CognitoIdentityProvider {
+                        user_pool_id,
+                        client_id,
+                        server_side_token_check: provider.server_side_token_check().unwrap_or(false),
+                    });
+                }
+
+                let identity_pool = IdentityPool {
+                    identity_pool_name: resp.identity_pool_name().to_string(),
+                    allow_unauthenticated_identities: resp.allow_unauthenticated_identities(),
+                    allow_classic_flow: resp.allow_classic_flow().unwrap_or(false),
+                    cognito_identity_providers,
+                    supported_login_providers: to_btree_map(resp.supported_login_providers.clone()),
+                    open_id_connect_provider_arns: resp.open_id_connect_provider_arns().to_vec(),
+                    saml_provider_arns: resp.saml_provider_arns().to_vec(),
+                    developer_provider_name: resp.developer_provider_name().map(String::from),
+                    roles: to_btree_map(roles),
+                    tags: resp.identity_pool_tags.into(),
+                };
+
+                get_resource_response!(
+                    CognitoResource::IdentityPool(identity_pool),
+                    [(String::from("identity_pool_id"), identity_pool_id.clone())]
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::addr::CognitoResourceAddress;
+
+use super::CognitoConnector;
+
+impl CognitoConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut user_pool_ids = Vec::new();
+            let mut next_token = None;
+            loop {
+                let resp = client
+                    .list_user_pools()
+                    .max_results(60)
+                    .set_next_token(next_token)
+                    .send()
+                    .await?;
+                user_pool_ids.extend(resp.user_pools().iter().filter_map(|p| p.id().map(String::from)));
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+
+            for user_pool_id in user_pool_ids {
+                results.push(
+                    CognitoResourceAddress::UserPool {
+                        region:       region.clone(),
+                        user_pool_id: user_pool_id.clone(),
+                    }
+                    .to_path_buf(),
+                );
+
+                let mut next_token = None;
+                loop {
+                    let resp = client
+                        .list_user_pool_clients()
+                        .user_pool_id(&user_pool_id)
+                        .max_results(60)
+                        .set_next_token(next_token)
+                        .send()
+                        .await?;
+                    for pool_client in resp.user_pool_clients() {
+                        if let Some(client_id) = pool_client.client_id() {
+                            results.push(
+                                CognitoResourceAddress::UserPoolClient {
+                                    region:       region.clone(),
+                                    user_pool_id: user_pool_id.clone(),
+                                    client_id:    client_id.to_string(),
+                                }
+                                .to_path_buf(),
+                            );
+                        }
+                    }
+                    next_token = resp.next_token;
+                    if next_token.is_none() {
+                        break;
+                    }
+                }
+
+                let mut next_token = None;
+                loop {
+                    let resp = client
+                        .list_identity_providers()
+                        .user_pool_id(&user_pool_id)
+                        .max_results(60)
+                        .set_next_token(next_token)
+                        .send()
+                        .await?;
+                    for provider in resp.providers() {
+                        if let Some(provider_name) = provider.provider_name() {
+                            results.push(
+                                CognitoResourceAddress::IdentityProvider {
+                                    region:        region.clone(),
+                                    user_pool_id:  user_pool_id.clone(),
+                                    provider_name: provider_name.to_string(),
+                                }
+                                .to_path_buf(),
+                            );
+                        }
+                    }
+                    next_token = resp.next_token;
+                    if next_token.is_none() {
+                        break;
+                    }
+                }
+
+                let mut next_token = None;
+                loop {
+                    let resp = client
+                        .list_resource_servers()
+                        .user_pool_id(&user_pool_id)
+                        .max_results(50)
+                        .set_next_token(next_token)
+                        .send()
+                        .await?;
+                    for resource_server in resp.resource_servers() {
+                        if let Some(identifier) = resource_server.identifier() {
+                            results.push(
+                                CognitoResourceAddress::ResourceServer {
+                                    region:       region.clone(),
+                                    user_pool_id: user_pool_id.clone(),
+                                    identifier:   identifier.to_string(),
+                                }
+                                .to_path_buf(),
+                            );
+                        }
+                    }
+                    next_token = resp.next_token;
+                    if next_token.is_none() {
+                        break;
+                    }
+                }
+
+                // ListUserPools doesn't say which pools have a domain
+                let resp = client.describe_user_pool().user_pool_id(&user_pool_id).send().await?;
+                if resp
+                    .user_pool()
+                    .is_some_and(|p| p.domain().is_some_and(|d| !d.is_empty()) || p.custom_domain().is_some_and(|d| !d.is_empty()))
+                {
+                    results.push(
+                        CognitoResourceAddress::UserPoolDomain {
+                            region:       region.clone(),
+                            user_pool_id: user_pool_id.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+            }
+
+            let identity_client = self.get_or_init_identity_client(&region).await?;
+
+            let mut next_token = None;
+            loop {
+                let resp = identity_client
+                    .list_identity_pools()
+                    .max_results(60)
+                    .set_next_token(next_token)
+                    .send()
+                    .await?;
+                for identity_pool in resp.identity_pools() {
+                    if let Some(identity_pool_id) = identity_pool.identity_pool_id() {
+                        results.push(
+                            CognitoResourceAddress::IdentityPool {
+                                region:           region.clone(),
+                                identity_pool_id: identity_pool_id.to_string(),
+                            }
+                            .to_path_buf(),
+                        );
+                    }
+                }
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,146 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::connector::{ConnectorOp, OpExecResponse, ResourceAddress};
+
+use crate::{
+    addr::CognitoResourceAddress,
+    op::CognitoConnectorOp,
+    op_impl,
+    util::{identity_pool_arn, user_pool_arn},
+};
+
+use super::CognitoConnector;
+
+impl CognitoConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = CognitoResourceAddress::from_path(addr)?;
+        let op = CognitoConnectorOp::from_str(op)?;
+
+        let account_id = self.account_id.lock().await.clone();
+
+        match &addr {
+            CognitoResourceAddress::UserPool { region, user_pool_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    CognitoConnectorOp::CreateUserPool(user_pool) => op_impl::create_user_pool(&client, &user_pool).await,
+                    CognitoConnectorOp::UpdateUserPool(user_pool) => {
+                        op_impl::update_user_pool(&client, user_pool_id, &user_pool).await
+                    }
+                    CognitoConnectorOp::AddCustomAttributes(custom_attributes) => {
+                        op_impl::add_custom_attributes(&client, user_pool_id, &custom_attributes).await
+                    }
+                    CognitoConnectorOp::UpdateUserPoolTags(old_tags, new_tags) => {
+                        let arn = user_pool_arn(region, &account_id, user_pool_id);
+                        op_impl::update_user_pool_tags(&client, &arn, &old_tags, &new_tags).await
+                    }
+                    CognitoConnectorOp::DeleteUserPool => op_impl::delete_user_pool(&client, user_pool_id).await,
+                    _ => bail!("Invalid operation for Cognito user pool resource"),
+                }
+            }
+            CognitoResourceAddress::UserPoolClient {
+                region,
+                user_pool_id,
+                client_id,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    CognitoConnectorOp::CreateUserPoolClient(pool_client) => {
+                        op_impl::create_user_pool_client(&client, user_pool_id, &pool_client).await
+                    }
+                    CognitoConnectorOp::UpdateUserPoolClient(pool_client) => {
+                        op_impl::update_user_pool_client(&client, user_pool_id, client_id, &pool_client).await
+                    }
+                    CognitoConnectorOp::DeleteUserPoolClient => {
+                        op_impl::delete_user_pool_client(&client, user_pool_id, client_id).await
+                    }
+                    _ => bail!("Invalid operation for Cognito app client resource"),
+                }
+            }
+            CognitoResourceAddress::IdentityProvider {
+                region,
+                user_pool_id,
+                provider_name,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    CognitoConnectorOp::CreateIdentityProvider(provider) => {
+                        op_impl::create_identity_provider(&client, user_pool_id, provider_name, &provider).await
+                    }
+                    CognitoConnectorOp::UpdateIdentityProvider(provider) => {
+                        op_impl::update_identity_provider(&client, user_pool_id, provider_name, &provider).await
+                    }
+                    CognitoConnectorOp::DeleteIdentityProvider => {
+                        op_impl::delete_identity_provider(&client, user_pool_id, provider_name).await
+                    }
+                    _ => bail!("Invalid operation for Cognito identity provider resource"),
+                }
+            }
+            CognitoResourceAddress::ResourceServer {
+                region,
+                user_pool_id,
+                identifier,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    CognitoConnectorOp::CreateResourceServer(resource_server) => {
+                        op_impl::create_resource_server(&client, user_pool_id, identifier, &resource_server).await
+                    }
+                    CognitoConnectorOp::UpdateResourceServer(resource_server) => {
+                        op_impl::update_resource_server(&client, user_pool_id, identifier, &resource_server).await
+                    }
+                    CognitoConnectorOp::DeleteResourceServer => {
+                        op_impl::delete_resource_server(&client, user_pool_id, identifier).await
+                    }
+                    _ => bail!("Invalid operation for Cognito resource server resource"),
+                }
+            }
+            CognitoResourceAddress::UserPoolDomain { region, user_pool_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    CognitoConnectorOp::CreateUserPoolDomain(domain) => {
+                        op_impl::create_user_pool_domain(&client, user_pool_id, &domain).await
+                    }
+                    CognitoConnectorOp::UpdateUserPoolDomain(domain) => {
+                        op_impl::update_user_pool_domain(&client, user_pool_id, &domain).await
+                    }
+                    CognitoConnectorOp::DeleteUserPoolDomain(domain) => {
+                        op_impl::delete_user_pool_domain(&client, user_pool_id, &domain).await
+                    }
+                    _ => bail!("Invalid operation for Cognito user pool domain resource"),
+                }
+            }
+            CognitoResourceAddress::IdentityPool {
+                region,
+                identity_pool_id,
+            } => {
+                let client = self.get_or_init_identity_client(region).await?;
+
+                match op {
+                    CognitoConnectorOp::CreateIdentityPool(identity_pool) => {
+                        let identity_pool = self.resolve_identity_pool(region, &identity_pool)?;
+                        op_impl::create_identity_pool(&client, region, &identity_pool).await
+                    }
+                    CognitoConnectorOp::UpdateIdentityPool(identity_pool) => {
+                        let identity_pool = self.resolve_identity_pool(region, &identity_pool)?;
+                        op_impl::update_identity_pool(&client, region, identity_pool_id, &identity_pool).await
+                    }
+                    CognitoConnectorOp::SetIdentityPoolRoles(roles) => {
+                        op_impl::set_identity_pool_roles(&client, identity_pool_id, &roles).await
+                    }
+                    CognitoConnectorOp::UpdateIdentityPoolTags(old_tags, new_tags) => {
+                        let arn = identity_pool_arn(region, &account_id, identity_pool_id);
+                        op_impl::update_identity_pool_tags(&client, &arn, &old_tags, &new_tags).await
+                    }
+                    CognitoConnectorOp::DeleteIdentityPool => op_impl::delete_identity_pool(&client, identity_pool_id).await,
+                    _ => bail!("Invalid operation for Cognito identity pool resource"),
+                }
+            }
+        }
+    }
+}
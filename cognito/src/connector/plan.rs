@@ -0,0 +1,673 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::resource::{IdentityPool, IdentityProvider, ResourceServer, UserPool, UserPoolClient, UserPoolDomain};
+
+use super::{CognitoConnector, CognitoConnectorOp, CognitoResourceAddress};
+
+const PROVIDER_TYPES: [&str; 6] = ["SAML", "OIDC", "Google", "Facebook", "LoginWithAmazon", "SignInWithApple"];
+
+/// Provider details that Cognito fills in itself, for instance from an OIDC issuer's discovery document.
+const DERIVED_PROVIDER_DETAILS: [&str; 5] = [
+    "authorize_url",
+    "token_url",
+    "attributes_url",
+    "jwks_uri",
+    "attributes_url_add_attributes",
+];
+
+fn check_user_pool(user_pool_id: &str, user_pool: &UserPool) -> anyhow::Result<()> {
+    if !["OFF", "ON", "OPTIONAL"].contains(&user_pool.mfa_configuration.as_str()) {
+        bail!(
+            "User pool {} has an invalid mfa_configuration {}; expected OFF, ON or OPTIONAL",
+            user_pool_id,
+            user_pool.mfa_configuration
+        );
+    }
+    // SMS needs an SNS role that this connector doesn't manage, so authenticator apps are the only factor
+    if user_pool.mfa_configuration != "OFF" && !user_pool.software_token_mfa {
+        bail!(
+            "User pool {} has mfa_configuration {}, so it needs software_token_mfa",
+            user_pool_id,
+            user_pool.mfa_configuration
+        );
+    }
+
+    if !user_pool.username_attributes.is_empty() && !user_pool.alias_attributes.is_empty() {
+        bail!(
+            "User pool {} has both username_attributes and alias_attributes; it can only have one or the other",
+            user_pool_id
+        );
+    }
+    for attribute in &user_pool.username_attributes {
+        if !["email", "phone_number"].contains(&attribute.as_str()) {
+            bail!(
+                "User pool {} has an invalid username attribute {}; expected email or phone_number",
+                user_pool_id,
+                attribute
+            );
+        }
+    }
+    for attribute in &user_pool.alias_attributes {
+        if !["email", "phone_number", "preferred_username"].contains(&attribute.as_str()) {
+            bail!(
+                "User pool {} has an invalid alias attribute {}; expected email, phone_number or preferred_username",
+                user_pool_id,
+                attribute
+            );
+        }
+    }
+
+    for attribute in &user_pool.custom_attributes {
+        if attribute.name.starts_with("custom:") {
+            bail!(
+                "User pool {} has custom attribute {}; leave out the custom: prefix",
+                user_pool_id,
+                attribute.name
+            );
+        }
+        if !["String", "Number", "DateTime", "Boolean"].contains(&attribute.data_type.as_str()) {
+            bail!(
+                "User pool {} has an invalid data_type {} for custom attribute {}; expected String, Number, DateTime or Boolean",
+                user_pool_id,
+                attribute.data_type,
+                attribute.name
+            );
+        }
+    }
+
+    if !(6..=99).contains(&user_pool.password_policy.minimum_length) {
+        bail!(
+            "User pool {} has a password minimum_length of {}; it must be between 6 and 99",
+            user_pool_id,
+            user_pool.password_policy.minimum_length
+        );
+    }
+
+    for mechanism in &user_pool.account_recovery {
+        if !["verified_email", "verified_phone_number", "admin_only"].contains(&mechanism.as_str()) {
+            bail!(
+                "User pool {} has an invalid account recovery mechanism {}; expected verified_email, verified_phone_number or admin_only",
+                user_pool_id,
+                mechanism
+            );
+        }
+    }
+    if user_pool.account_recovery.contains(&String::from("admin_only")) && user_pool.account_recovery.len() > 1 {
+        bail!(
+            "User pool {} has admin_only account recovery, which can't be combined with other mechanisms",
+            user_pool_id
+        );
+    }
+
+    Ok(())
+}
+
+fn check_user_pool_client(client_id: &str, pool_client: &UserPoolClient) -> anyhow::Result<()> {
+    for (token, validity) in [
+        ("access_token_validity_minutes", pool_client.access_token_validity_minutes),
+        ("id_token_validity_minutes", pool_client.id_token_validity_minutes),
+    ] {
+        if let Some(validity) = validity
+            && !(5..=1440).contains(&validity)
+        {
+            bail!(
+                "App client {} has {} {}; it must be between 5 and 1440",
+                client_id,
+                token,
+                validity
+            );
+        }
+    }
+    if let Some(validity) = pool_client.refresh_token_validity_days
+        && !(1..=3650).contains(&validity)
+    {
+        bail!(
+            "App client {} has refresh_token_validity_days {}; it must be between 1 and 3650",
+            client_id,
+            validity
+        );
+    }
+
+    for flow in &pool_client.allowed_oauth_flows {
+        if !["code", "implicit", "client_credentials"].contains(&flow.as_str()) {
+            bail!(
+                "App client {} has an invalid OAuth flow {}; expected code, implicit or client_credentials",
+                client_id,
+                flow
+            );
+        }
+    }
+
+    if pool_client.allowed_oauth_flows_user_pool_client {
+        if pool_client.allowed_oauth_flows.is_empty() || pool_client.allowed_oauth_scopes.is_empty() {
+            bail!(
+                "App client {} uses OAuth, so it needs allowed_oauth_flows and allowed_oauth_scopes",
+                client_id
+            );
+        }
+        if pool_client.allowed_oauth_flows.iter().any(|f| f == "client_credentials") && !pool_client.generate_secret {
+            bail!(
+                "App client {} uses the client_credentials flow, so it needs generate_secret",
+                client_id
+            );
+        }
+        if pool_client.allowed_oauth_flows.iter().any(|f| f == "code" || f == "implicit")
+            && pool_client.callback_urls.is_empty()
+        {
+            bail!("App client {} signs users in through OAuth, so it needs callback_urls", client_id);
+        }
+    }
+
+    if let Some(prevent_user_existence_errors) = &pool_client.prevent_user_existence_errors
+        && !["ENABLED", "LEGACY"].contains(&prevent_user_existence_errors.as_str())
+    {
+        bail!(
+            "App client {} has an invalid prevent_user_existence_errors {}; expected ENABLED or LEGACY",
+            client_id,
+            prevent_user_existence_errors
+        );
+    }
+
+    Ok(())
+}
+
+fn check_identity_provider(provider_name: &str, provider: &IdentityProvider) -> anyhow::Result<()> {
+    if !PROVIDER_TYPES.contains(&provider.provider_type.as_str()) {
+        bail!(
+            "Identity provider {} has an invalid provider_type {}; expected one of {}",
+            provider_name,
+            provider.provider_type,
+            PROVIDER_TYPES.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Prefix domains are a single label; custom domains are served by CloudFront, so they need a us-east-1 certificate.
+fn check_user_pool_domain(user_pool_id: &str, domain: &UserPoolDomain) -> anyhow::Result<()> {
+    let is_custom = domain.domain.contains('.');
+    match &domain.certificate_arn {
+        Some(certificate_arn) if !is_custom => bail!(
+            "User pool {} has prefix domain {} with certificate {}; only custom domains take a certificate",
+            user_pool_id,
+            domain.domain,
+            certificate_arn
+        ),
+        Some(certificate_arn) if !certificate_arn.starts_with("arn:aws:acm:us-east-1:") => bail!(
+            "User pool {} has custom domain {} with certificate {}, which isn't in us-east-1",
+            user_pool_id,
+            domain.domain,
+            certificate_arn
+        ),
+        None if is_custom => bail!(
+            "User pool {} has custom domain {}, so it needs a certificate_arn",
+            user_pool_id,
+            domain.domain
+        ),
+        _ => {}
+    }
+
+    if let Some(version) = domain.managed_login_version
+        && ![1, 2].contains(&version)
+    {
+        bail!(
+            "User pool {} has an invalid managed_login_version {}; expected 1 or 2",
+            user_pool_id,
+            version
+        );
+    }
+
+    Ok(())
+}
+
+fn check_identity_pool(identity_pool_id: &str, identity_pool: &IdentityPool) -> anyhow::Result<()> {
+    for role_type in identity_pool.roles.keys() {
+        if !["authenticated", "unauthenticated"].contains(&role_type.as_str()) {
+            bail!(
+                "Identity pool {} has an invalid role type {}; expected authenticated or unauthenticated",
+                identity_pool_id,
+                role_type
+            );
+        }
+    }
+    if identity_pool.roles.contains_key("unauthenticated") && !identity_pool.allow_unauthenticated_identities {
+        bail!(
+            "Identity pool {} has an unauthenticated role, but doesn't allow_unauthenticated_identities",
+            identity_pool_id
+        );
+    }
+    Ok(())
+}
+
+/// The fields of a user pool that can only be set when it's created. Custom attributes can be
+/// added later, but not changed or removed.
+fn user_pool_replacement_fields(old: &UserPool, new: &UserPool) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if old.name != new.name {
+        fields.push("name");
+    }
+    if old.username_attributes != new.username_attributes {
+        fields.push("username_attributes");
+    }
+    if old.alias_attributes != new.alias_attributes {
+        fields.push("alias_attributes");
+    }
+    if old.username_case_sensitive != new.username_case_sensitive {
+        fields.push("username_case_sensitive");
+    }
+    if old.required_attributes != new.required_attributes {
+        fields.push("required_attributes");
+    }
+    if old.custom_attributes.iter().any(|a| !new.custom_attributes.contains(a)) {
+        fields.push("custom_attributes");
+    }
+    fields
+}
+
+impl CognitoConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = CognitoResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            CognitoResourceAddress::UserPool { region, user_pool_id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_pool)) => {
+                    let new_pool: UserPool = RON.from_str(&new_pool)?;
+                    check_user_pool(user_pool_id, &new_pool)?;
+                    Ok(vec![connector_op!(
+                        CognitoConnectorOp::CreateUserPool(new_pool),
+                        format!("Create new user pool {} in region {}", user_pool_id, region)
+                    )])
+                }
+                (Some(old_pool), None) => {
+                    let old_pool: UserPool = RON.from_str(&old_pool)?;
+                    if old_pool.deletion_protection {
+                        bail!(
+                            "User pool {} has deletion_protection; turn it off before deleting the pool",
+                            user_pool_id
+                        );
+                    }
+                    Ok(vec![connector_op!(
+                        CognitoConnectorOp::DeleteUserPool,
+                        format!(
+                            "DELETE user pool {} in region {}, along with all of its users",
+                            user_pool_id, region
+                        )
+                    )])
+                }
+                (Some(old_pool), Some(new_pool)) => {
+                    let old_pool: UserPool = RON.from_str(&old_pool)?;
+                    let mut new_pool: UserPool = RON.from_str(&new_pool)?;
+                    check_user_pool(user_pool_id, &new_pool)?;
+
+                    // Cognito doesn't keep the order of attributes
+                    new_pool.required_attributes.sort();
+                    new_pool.custom_attributes.sort_by(|a, b| a.name.cmp(&b.name));
+
+                    let replacement_fields = user_pool_replacement_fields(&old_pool, &new_pool);
+                    if !replacement_fields.is_empty() {
+                        if old_pool.deletion_protection {
+                            bail!(
+                                "User pool {} must be replaced to change {}, but has deletion_protection; turn it off first",
+                                user_pool_id,
+                                replacement_fields.join(", ")
+                            );
+                        }
+                        return Ok(vec![
+                            connector_op!(
+                                CognitoConnectorOp::DeleteUserPool,
+                                format!(
+                                    "REPLACE user pool `{}` (requires replacement: {}); all of its users, clients and domains will be lost",
+                                    user_pool_id,
+                                    replacement_fields.join(", ")
+                                )
+                            ),
+                            connector_op!(
+                                CognitoConnectorOp::CreateUserPool(new_pool),
+                                format!("Create new user pool {} in region {}", user_pool_id, region)
+                            ),
+                        ]);
+                    }
+
+                    let mut ops = Vec::new();
+
+                    let added_attributes: Vec<_> = new_pool
+                        .custom_attributes
+                        .iter()
+                        .filter(|a| !old_pool.custom_attributes.iter().any(|o| o.name == a.name))
+                        .cloned()
+                        .collect();
+                    if !added_attributes.is_empty() {
+                        let names: Vec<&str> = added_attributes.iter().map(|a| a.name.as_str()).collect();
+                        ops.push(connector_op!(
+                            CognitoConnectorOp::AddCustomAttributes(added_attributes.clone()),
+                            format!(
+                                "Add custom attributes {} to user pool `{}`",
+                                names.join(", "),
+                                user_pool_id
+                            )
+                        ));
+                    }
+
+                    if old_pool.tags != new_pool.tags {
+                        let diff = diff_ron_values(&old_pool.tags, &new_pool.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            CognitoConnectorOp::UpdateUserPoolTags(old_pool.tags.clone(), new_pool.tags.clone()),
+                            format!("Modify tags for user pool `{}`\n{}", user_pool_id, diff)
+                        ));
+                    }
+
+                    let old_settings = UserPool {
+                        tags: new_pool.tags.clone(),
+                        custom_attributes: new_pool.custom_attributes.clone(),
+                        ..old_pool
+                    };
+                    if old_settings != new_pool {
+                        let diff = diff_ron_values(&old_settings, &new_pool).unwrap_or_default();
+                        ops.push(connector_op!(
+                            CognitoConnectorOp::UpdateUserPool(new_pool),
+                            format!("Modify user pool `{}`\n{}", user_pool_id, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            CognitoResourceAddress::UserPoolClient {
+                user_pool_id,
+                client_id,
+                ..
+            } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_client)) => {
+                    let new_client: UserPoolClient = RON.from_str(&new_client)?;
+                    check_user_pool_client(client_id, &new_client)?;
+                    Ok(vec![connector_op!(
+                        CognitoConnectorOp::CreateUserPoolClient(new_client),
+                        format!("Create new app client {} in user pool {}", client_id, user_pool_id)
+                    )])
+                }
+                (Some(_old_client), None) => Ok(vec![connector_op!(
+                    CognitoConnectorOp::DeleteUserPoolClient,
+                    format!("DELETE app client {} in user pool {}", client_id, user_pool_id)
+                )]),
+                (Some(old_client), Some(new_client)) => {
+                    let old_client: UserPoolClient = RON.from_str(&old_client)?;
+                    let new_client: UserPoolClient = RON.from_str(&new_client)?;
+                    check_user_pool_client(client_id, &new_client)?;
+
+                    if old_client.generate_secret != new_client.generate_secret {
+                        return Ok(vec![
+                            connector_op!(
+                                CognitoConnectorOp::DeleteUserPoolClient,
+                                format!(
+                                    "REPLACE app client `{}` in user pool `{}` (requires replacement: generate_secret); its client ID will change",
+                                    client_id, user_pool_id
+                                )
+                            ),
+                            connector_op!(
+                                CognitoConnectorOp::CreateUserPoolClient(new_client),
+                                format!("Create new app client {} in user pool {}", client_id, user_pool_id)
+                            ),
+                        ]);
+                    }
+
+                    if old_client == new_client {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_client, &new_client).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        CognitoConnectorOp::UpdateUserPoolClient(new_client),
+                        format!("Modify app client `{}` in user pool `{}`\n{}", client_id, user_pool_id, diff)
+                    )])
+                }
+            },
+            CognitoResourceAddress::IdentityProvider {
+                user_pool_id,
+                provider_name,
+                ..
+            } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_provider)) => {
+                    let new_provider: IdentityProvider = RON.from_str(&new_provider)?;
+                    check_identity_provider(provider_name, &new_provider)?;
+                    Ok(vec![connector_op!(
+                        CognitoConnectorOp::CreateIdentityProvider(new_provider.clone()),
+                        format!(
+                            "Create new {} identity provider {} in user pool {}",
+                            new_provider.provider_type, provider_name, user_pool_id
+                        )
+                    )])
+                }
+                (Some(_old_provider), None) => Ok(vec![connector_op!(
+                    CognitoConnectorOp::DeleteIdentityProvider,
+                    format!("DELETE identity provider {} in user pool {}", provider_name, user_pool_id)
+                )]),
+                (Some(old_provider), Some(new_provider)) => {
+                    let mut old_provider: IdentityProvider = RON.from_str(&old_provider)?;
+                    let new_provider: IdentityProvider = RON.from_str(&new_provider)?;
+                    check_identity_provider(provider_name, &new_provider)?;
+
+                    if old_provider.provider_type != new_provider.provider_type {
+                        return Ok(vec![
+                            connector_op!(
+                                CognitoConnectorOp::DeleteIdentityProvider,
+                                format!(
+                                    "REPLACE identity provider `{}` in user pool `{}` (requires replacement: provider_type)",
+                                    provider_name, user_pool_id
+                                )
+                            ),
+                            connector_op!(
+                                CognitoConnectorOp::CreateIdentityProvider(new_provider.clone()),
+                                format!(
+                                    "Create new {} identity provider {} in user pool {}",
+                                    new_provider.provider_type, provider_name, user_pool_id
+                                )
+                            ),
+                        ]);
+                    }
+
+                    // The client secret is never read back, and details Cognito derives
+                    // only count if they're written out
+                    let mut comparable_provider = new_provider.clone();
+                    comparable_provider.provider_details.remove("client_secret");
+                    old_provider
+                        .provider_details
+                        .retain(|k, _| new_provider.provider_details.contains_key(k) || !DERIVED_PROVIDER_DETAILS.contains(&k.as_str()));
+
+                    if old_provider == comparable_provider {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_provider, &comparable_provider).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        CognitoConnectorOp::UpdateIdentityProvider(new_provider),
+                        format!(
+                            "Modify identity provider `{}` in user pool `{}`\n{}",
+                            provider_name, user_pool_id, diff
+                        )
+                    )])
+                }
+            },
+            CognitoResourceAddress::ResourceServer {
+                user_pool_id,
+                identifier,
+                ..
+            } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_server)) => {
+                    let new_server: ResourceServer = RON.from_str(&new_server)?;
+                    Ok(vec![connector_op!(
+                        CognitoConnectorOp::CreateResourceServer(new_server),
+                        format!("Create new resource server {} in user pool {}", identifier, user_pool_id)
+                    )])
+                }
+                (Some(_old_server), None) => Ok(vec![connector_op!(
+                    CognitoConnectorOp::DeleteResourceServer,
+                    format!("DELETE resource server {} in user pool {}", identifier, user_pool_id)
+                )]),
+                (Some(old_server), Some(new_server)) => {
+                    let old_server: ResourceServer = RON.from_str(&old_server)?;
+                    let new_server: ResourceServer = RON.from_str(&new_server)?;
+
+                    if old_server == new_server {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_server, &new_server).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        CognitoConnectorOp::UpdateResourceServer(new_server),
+                        format!(
+                            "Modify resource server `{}` in user pool `{}`\n{}",
+                            identifier, user_pool_id, diff
+                        )
+                    )])
+                }
+            },
+            CognitoResourceAddress::UserPoolDomain { user_pool_id, .. } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_domain)) => {
+                    let new_domain: UserPoolDomain = RON.from_str(&new_domain)?;
+                    check_user_pool_domain(user_pool_id, &new_domain)?;
+                    Ok(vec![connector_op!(
+                        CognitoConnectorOp::CreateUserPoolDomain(new_domain.clone()),
+                        format!("Create new domain {} for user pool {}", new_domain.domain, user_pool_id)
+                    )])
+                }
+                (Some(old_domain), None) => {
+                    let old_domain: UserPoolDomain = RON.from_str(&old_domain)?;
+                    Ok(vec![connector_op!(
+                        CognitoConnectorOp::DeleteUserPoolDomain(old_domain.domain.clone()),
+                        format!("DELETE domain {} for user pool {}", old_domain.domain, user_pool_id)
+                    )])
+                }
+                (Some(old_domain), Some(new_domain)) => {
+                    let old_domain: UserPoolDomain = RON.from_str(&old_domain)?;
+                    let new_domain: UserPoolDomain = RON.from_str(&new_domain)?;
+                    check_user_pool_domain(user_pool_id, &new_domain)?;
+
+                    if old_domain.domain != new_domain.domain {
+                        return Ok(vec![
+                            connector_op!(
+                                CognitoConnectorOp::DeleteUserPoolDomain(old_domain.domain.clone()),
+                                format!(
+                                    "REPLACE domain `{}` for user pool `{}` (requires replacement: domain {} -> {})",
+                                    old_domain.domain, user_pool_id, old_domain.domain, new_domain.domain
+                                )
+                            ),
+                            connector_op!(
+                                CognitoConnectorOp::CreateUserPoolDomain(new_domain.clone()),
+                                format!("Create new domain {} for user pool {}", new_domain.domain, user_pool_id)
+                            ),
+                        ]);
+                    }
+
+                    if old_domain == new_domain {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_domain, &new_domain).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        CognitoConnectorOp::UpdateUserPoolDomain(new_domain),
+                        format!("Modify domain for user pool `{}`\n{}", user_pool_id, diff)
+                    )])
+                }
+            },
+            CognitoResourceAddress::IdentityPool {
+                region,
+                identity_pool_id,
+            } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_pool)) => {
+                    let new_pool: IdentityPool = RON.from_str(&new_pool)?;
+                    check_identity_pool(identity_pool_id, &new_pool)?;
+                    Ok(vec![connector_op!(
+                        CognitoConnectorOp::CreateIdentityPool(new_pool),
+                        format!("Create new identity pool {} in region {}", identity_pool_id, region)
+                    )])
+                }
+                (Some(_old_pool), None) => Ok(vec![connector_op!(
+                    CognitoConnectorOp::DeleteIdentityPool,
+                    format!(
+                        "DELETE identity pool {} in region {}, along with all of its identities",
+                        identity_pool_id, region
+                    )
+                )]),
+                (Some(old_pool), Some(new_pool)) => {
+                    let old_pool: IdentityPool = RON.from_str(&old_pool)?;
+                    let new_pool: IdentityPool = RON.from_str(&new_pool)?;
+                    check_identity_pool(identity_pool_id, &new_pool)?;
+
+                    if old_pool.developer_provider_name.is_some()
+                        && old_pool.developer_provider_name != new_pool.developer_provider_name
+                    {
+                        return Ok(vec![
+                            connector_op!(
+                                CognitoConnectorOp::DeleteIdentityPool,
+                                format!(
+                                    "REPLACE identity pool `{}` (requires replacement: developer_provider_name); all of its identities will be lost",
+                                    identity_pool_id
+                                )
+                            ),
+                            connector_op!(
+                                CognitoConnectorOp::CreateIdentityPool(new_pool),
+                                format!("Create new identity pool {} in region {}", identity_pool_id, region)
+                            ),
+                        ]);
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_pool.tags != new_pool.tags {
+                        let diff = diff_ron_values(&old_pool.tags, &new_pool.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            CognitoConnectorOp::UpdateIdentityPoolTags(old_pool.tags.clone(), new_pool.tags.clone()),
+                            format!("Modify tags for identity pool `{}`\n{}", identity_pool_id, diff)
+                        ));
+                    }
+
+                    if old_pool.roles != new_pool.roles {
+                        let diff = diff_ron_values(&old_pool.roles, &new_pool.roles).unwrap_or_default();
+                        ops.push(connector_op!(
+                            CognitoConnectorOp::SetIdentityPoolRoles(new_pool.roles.clone()),
+                            format!("Modify roles for identity pool `{}`\n{}", identity_pool_id, diff)
+                        ));
+                    }
+
+                    let old_settings = IdentityPool {
+                        tags: new_pool.tags.clone(),
+                        roles: new_pool.roles.clone(),
+                        ..old_pool
+                    };
+                    if old_settings != new_pool {
+                        let diff = diff_ron_values(&old_settings, &new_pool).unwrap_or_default();
+                        ops.push(connector_op!(
+                            CognitoConnectorOp::UpdateIdentityPool(new_pool),
+                            format!("Modify identity pool `{}`\n{}", identity_pool_id, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+        }
+    }
+}
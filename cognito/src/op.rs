@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{
+        CustomAttribute, IdentityPool, IdentityProvider, ResourceServer, UserPool, UserPoolClient, UserPoolDomain,
+    },
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum CognitoConnectorOp {
+    // User pool operations
+    CreateUserPool(UserPool),
+    /// UpdateUserPool resets anything it isn't given, so this always carries the whole pool.
+    UpdateUserPool(UserPool),
+    AddCustomAttributes(Vec<CustomAttribute>),
+    UpdateUserPoolTags(Tags, Tags),
+    DeleteUserPool,
+
+    // App client operations
+    CreateUserPoolClient(UserPoolClient),
+    UpdateUserPoolClient(UserPoolClient),
+    DeleteUserPoolClient,
+
+    // Identity provider operations
+    CreateIdentityProvider(IdentityProvider),
+    UpdateIdentityProvider(IdentityProvider),
+    DeleteIdentityProvider,
+
+    // Resource server operations
+    CreateResourceServer(ResourceServer),
+    UpdateResourceServer(ResourceServer),
+    DeleteResourceServer,
+
+    // Domain operations
+    CreateUserPoolDomain(UserPoolDomain),
+    UpdateUserPoolDomain(UserPoolDomain),
+    /// Deleting a domain takes its name, which isn't part of the address.
+    DeleteUserPoolDomain(String),
+
+    // Identity pool operations
+    CreateIdentityPool(IdentityPool),
+    UpdateIdentityPool(IdentityPool),
+    SetIdentityPoolRoles(BTreeMap<String, String>),
+    UpdateIdentityPoolTags(Tags, Tags),
+    DeleteIdentityPool,
+}
+
+impl ConnectorOp for CognitoConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
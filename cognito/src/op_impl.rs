@@ -0,0 +1,741 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Context;
+use autoschematic_core::connector::OpExecResponse;
+use aws_sdk_cognitoidentityprovider::types::{
+    AdminCreateUserConfigType, AliasAttributeType, CustomDomainConfigType, DeletionProtectionType, ExplicitAuthFlowsType,
+    IdentityProviderTypeType, OAuthFlowType, PreventUserExistenceErrorTypes, ResourceServerScopeType,
+    SoftwareTokenMfaConfigType, TimeUnitsType, TokenValidityUnitsType, UserPoolMfaType, UsernameAttributeType,
+    UsernameConfigurationType, VerifiedAttributeType,
+};
+
+use crate::{
+    resource::{CustomAttribute, IdentityPool, IdentityProvider, ResourceServer, UserPool, UserPoolClient, UserPoolDomain},
+    tags::{Tags, tag_diff},
+    util::{
+        account_recovery_setting, cognito_provider_name, custom_attribute_schema, email_configuration, lambda_config,
+        password_policy, required_attribute_schema,
+    },
+};
+
+fn deletion_protection(enabled: bool) -> DeletionProtectionType {
+    if enabled {
+        DeletionProtectionType::Active
+    } else {
+        DeletionProtectionType::Inactive
+    }
+}
+
+fn auto_verified_attributes(user_pool: &UserPool) -> Vec<VerifiedAttributeType> {
+    user_pool
+        .auto_verified_attributes
+        .iter()
+        .map(|a| VerifiedAttributeType::from(a.as_str()))
+        .collect()
+}
+
+// Tokens are always set in the units the resource is written in, so they read back unchanged
+fn token_validity_units() -> TokenValidityUnitsType {
+    TokenValidityUnitsType::builder()
+        .access_token(TimeUnitsType::Minutes)
+        .id_token(TimeUnitsType::Minutes)
+        .refresh_token(TimeUnitsType::Days)
+        .build()
+}
+
+async fn set_mfa_config(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+    user_pool: &UserPool,
+) -> anyhow::Result<()> {
+    client
+        .set_user_pool_mfa_config()
+        .user_pool_id(user_pool_id)
+        .software_token_mfa_configuration(
+            SoftwareTokenMfaConfigType::builder()
+                .enabled(user_pool.software_token_mfa)
+                .build(),
+        )
+        .mfa_configuration(UserPoolMfaType::from(user_pool.mfa_configuration.as_str()))
+        .send()
+        .await
+        .with_context(|| format!("Failed to set MFA configuration for user pool {}", user_pool_id))?;
+    Ok(())
+}
+
+/// Creates a user pool. MFA is switched on afterwards, once the authenticator app factor it relies on is enabled.
+pub async fn create_user_pool(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool: &UserPool,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut schema: Vec<_> = user_pool
+        .required_attributes
+        .iter()
+        .map(|a| required_attribute_schema(a))
+        .collect();
+    schema.extend(user_pool.custom_attributes.iter().map(custom_attribute_schema));
+
+    let resp = client
+        .create_user_pool()
+        .pool_name(&user_pool.name)
+        .set_username_attributes(Some(
+            user_pool
+                .username_attributes
+                .iter()
+                .map(|a| UsernameAttributeType::from(a.as_str()))
+                .collect(),
+        ))
+        .set_alias_attributes(Some(
+            user_pool
+                .alias_attributes
+                .iter()
+                .map(|a| AliasAttributeType::from(a.as_str()))
+                .collect(),
+        ))
+        .username_configuration(
+            UsernameConfigurationType::builder()
+                .case_sensitive(user_pool.username_case_sensitive)
+                .build()?,
+        )
+        .set_schema(Some(schema))
+        .set_auto_verified_attributes(Some(auto_verified_attributes(user_pool)))
+        .mfa_configuration(UserPoolMfaType::Off)
+        .policies(password_policy(&user_pool.password_policy))
+        .account_recovery_setting(account_recovery_setting(&user_pool.account_recovery)?)
+        .admin_create_user_config(
+            AdminCreateUserConfigType::builder()
+                .allow_admin_create_user_only(user_pool.admin_create_user_only)
+                .build(),
+        )
+        .email_configuration(email_configuration(&user_pool.email_configuration))
+        .lambda_config(lambda_config(&user_pool.lambda_triggers))
+        .deletion_protection(deletion_protection(user_pool.deletion_protection))
+        .set_user_pool_tags(user_pool.tags.clone().into())
+        .send()
+        .await?;
+
+    let pool = resp.user_pool.context("No user pool returned")?;
+    let user_pool_id = pool.id.context("No user pool ID returned")?;
+
+    if user_pool.mfa_configuration != "OFF" || user_pool.software_token_mfa {
+        set_mfa_config(client, &user_pool_id, user_pool).await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("user_pool_id"), Some(user_pool_id.clone())),
+            (String::from("user_pool_arn"), pool.arn),
+        ])),
+        friendly_message: Some(format!("Created user pool {} ({})", user_pool.name, user_pool_id)),
+    })
+}
+
+/// Puts every setting that can change after creation. Anything UpdateUserPool isn't given is reset
+/// to its default, so it's always sent the whole pool.
+pub async fn update_user_pool(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+    user_pool: &UserPool,
+) -> Result<OpExecResponse, anyhow::Error> {
+    set_mfa_config(client, user_pool_id, user_pool).await?;
+
+    client
+        .update_user_pool()
+        .user_pool_id(user_pool_id)
+        .set_auto_verified_attributes(Some(auto_verified_attributes(user_pool)))
+        .mfa_configuration(UserPoolMfaType::from(user_pool.mfa_configuration.as_str()))
+        .policies(password_policy(&user_pool.password_policy))
+        .account_recovery_setting(account_recovery_setting(&user_pool.account_recovery)?)
+        .admin_create_user_config(
+            AdminCreateUserConfigType::builder()
+                .allow_admin_create_user_only(user_pool.admin_create_user_only)
+                .build(),
+        )
+        .email_configuration(email_configuration(&user_pool.email_configuration))
+        .lambda_config(lambda_config(&user_pool.lambda_triggers))
+        .deletion_protection(deletion_protection(user_pool.deletion_protection))
+        .set_user_pool_tags(user_pool.tags.clone().into())
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated user pool {}", user_pool_id)),
+    })
+}
+
+pub async fn add_custom_attributes(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+    custom_attributes: &[CustomAttribute],
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .add_custom_attributes()
+        .user_pool_id(user_pool_id)
+        .set_custom_attributes(Some(custom_attributes.iter().map(custom_attribute_schema).collect()))
+        .send()
+        .await?;
+
+    let names: Vec<&str> = custom_attributes.iter().map(|a| a.name.as_str()).collect();
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Added custom attributes {} to user pool {}",
+            names.join(", "),
+            user_pool_id
+        )),
+    })
+}
+
+pub async fn update_user_pool_tags(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    resource_arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(resource_arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client
+            .tag_resource()
+            .resource_arn(resource_arn)
+            .set_tags(Some(new_tagset))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for {}", resource_arn)),
+    })
+}
+
+pub async fn delete_user_pool(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_user_pool().user_pool_id(user_pool_id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("user_pool_id"), None),
+            (String::from("user_pool_arn"), None),
+        ])),
+        friendly_message: Some(format!("Deleted user pool {}", user_pool_id)),
+    })
+}
+
+/// Creates an app client. If it has a secret, fetch it with DescribeUserPoolClient;
+/// it's deliberately not an output, since outputs are committed to the repository.
+pub async fn create_user_pool_client(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+    pool_client: &UserPoolClient,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let resp = client
+        .create_user_pool_client()
+        .user_pool_id(user_pool_id)
+        .client_name(&pool_client.client_name)
+        .generate_secret(pool_client.generate_secret)
+        .set_explicit_auth_flows(Some(
+            pool_client
+                .explicit_auth_flows
+                .iter()
+                .map(|f| ExplicitAuthFlowsType::from(f.as_str()))
+                .collect(),
+        ))
+        .set_supported_identity_providers(Some(pool_client.supported_identity_providers.clone()))
+        .allowed_o_auth_flows_user_pool_client(pool_client.allowed_oauth_flows_user_pool_client)
+        .set_allowed_o_auth_flows(Some(
+            pool_client
+                .allowed_oauth_flows
+                .iter()
+                .map(|f| OAuthFlowType::from(f.as_str()))
+                .collect(),
+        ))
+        .set_allowed_o_auth_scopes(Some(pool_client.allowed_oauth_scopes.clone()))
+        .set_callback_urls(Some(pool_client.callback_urls.clone()))
+        .set_logout_urls(Some(pool_client.logout_urls.clone()))
+        .set_access_token_validity(pool_client.access_token_validity_minutes)
+        .set_id_token_validity(pool_client.id_token_validity_minutes)
+        .set_refresh_token_validity(pool_client.refresh_token_validity_days)
+        .token_validity_units(token_validity_units())
+        .set_read_attributes(Some(pool_client.read_attributes.clone()))
+        .set_write_attributes(Some(pool_client.write_attributes.clone()))
+        .set_prevent_user_existence_errors(
+            pool_client
+                .prevent_user_existence_errors
+                .as_deref()
+                .map(PreventUserExistenceErrorTypes::from),
+        )
+        .enable_token_revocation(pool_client.enable_token_revocation)
+        .send()
+        .await?;
+
+    let client_id = resp
+        .user_pool_client
+        .and_then(|c| c.client_id)
+        .context("No client ID returned")?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("client_id"), Some(client_id.clone()))])),
+        friendly_message: Some(format!(
+            "Created app client {} ({}) in user pool {}",
+            pool_client.client_name, client_id, user_pool_id
+        )),
+    })
+}
+
+/// Like UpdateUserPool, UpdateUserPoolClient resets anything it isn't given.
+pub async fn update_user_pool_client(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+    client_id: &str,
+    pool_client: &UserPoolClient,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .update_user_pool_client()
+        .user_pool_id(user_pool_id)
+        .client_id(client_id)
+        .client_name(&pool_client.client_name)
+        .set_explicit_auth_flows(Some(
+            pool_client
+                .explicit_auth_flows
+                .iter()
+                .map(|f| ExplicitAuthFlowsType::from(f.as_str()))
+                .collect(),
+        ))
+        .set_supported_identity_providers(Some(pool_client.supported_identity_providers.clone()))
+        .allowed_o_auth_flows_user_pool_client(pool_client.allowed_oauth_flows_user_pool_client)
+        .set_allowed_o_auth_flows(Some(
+            pool_client
+                .allowed_oauth_flows
+                .iter()
+                .map(|f| OAuthFlowType::from(f.as_str()))
+                .collect(),
+        ))
+        .set_allowed_o_auth_scopes(Some(pool_client.allowed_oauth_scopes.clone()))
+        .set_callback_urls(Some(pool_client.callback_urls.clone()))
+        .set_logout_urls(Some(pool_client.logout_urls.clone()))
+        .set_access_token_validity(pool_client.access_token_validity_minutes)
+        .set_id_token_validity(pool_client.id_token_validity_minutes)
+        .set_refresh_token_validity(pool_client.refresh_token_validity_days)
+        .token_validity_units(token_validity_units())
+        .set_read_attributes(Some(pool_client.read_attributes.clone()))
+        .set_write_attributes(Some(pool_client.write_attributes.clone()))
+        .set_prevent_user_existence_errors(
+            pool_client
+                .prevent_user_existence_errors
+                .as_deref()
+                .map(PreventUserExistenceErrorTypes::from),
+        )
+        .enable_token_revocation(pool_client.enable_token_revocation)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated app client {} in user pool {}", client_id, user_pool_id)),
+    })
+}
+
+pub async fn delete_user_pool_client(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+    client_id: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .delete_user_pool_client()
+        .user_pool_id(user_pool_id)
+        .client_id(client_id)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("client_id"), None)])),
+        friendly_message: Some(format!("Deleted app client {} from user pool {}", client_id, user_pool_id)),
+    })
+}
+
+fn to_hash_map(map: &BTreeMap<String, String>) -> HashMap<String, String> {
+    map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+}
+
+pub async fn create_identity_provider(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+    provider_name: &str,
+    provider: &IdentityProvider,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .create_identity_provider()
+        .user_pool_id(user_pool_id)
+        .provider_name(provider_name)
+        .provider_type(IdentityProviderTypeType::from(provider.provider_type.as_str()))
+        .set_provider_details(Some(to_hash_map(&provider.provider_details)))
+        .set_attribute_mapping(Some(to_hash_map(&provider.attribute_mapping)))
+        .set_idp_identifiers(Some(provider.idp_identifiers.clone()))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Created {} identity provider {} in user pool {}",
+            provider.provider_type, provider_name, user_pool_id
+        )),
+    })
+}
+
+pub async fn update_identity_provider(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+    provider_name: &str,
+    provider: &IdentityProvider,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .update_identity_provider()
+        .user_pool_id(user_pool_id)
+        .provider_name(provider_name)
+        .set_provider_details(Some(to_hash_map(&provider.provider_details)))
+        .set_attribute_mapping(Some(to_hash_map(&provider.attribute_mapping)))
+        .set_idp_identifiers(Some(provider.idp_identifiers.clone()))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Updated identity provider {} in user pool {}",
+            provider_name, user_pool_id
+        )),
+    })
+}
+
+pub async fn delete_identity_provider(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+    provider_name: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .delete_identity_provider()
+        .user_pool_id(user_pool_id)
+        .provider_name(provider_name)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Deleted identity provider {} from user pool {}",
+            provider_name, user_pool_id
+        )),
+    })
+}
+
+fn resource_server_scopes(resource_server: &ResourceServer) -> anyhow::Result<Vec<ResourceServerScopeType>> {
+    let mut scopes = Vec::new();
+    for (scope_name, scope_description) in &resource_server.scopes {
+        scopes.push(
+            ResourceServerScopeType::builder()
+                .scope_name(scope_name)
+                .scope_description(scope_description)
+                .build()?,
+        );
+    }
+    Ok(scopes)
+}
+
+pub async fn create_resource_server(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+    identifier: &str,
+    resource_server: &ResourceServer,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .create_resource_server()
+        .user_pool_id(user_pool_id)
+        .identifier(identifier)
+        .name(&resource_server.name)
+        .set_scopes(Some(resource_server_scopes(resource_server)?))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Created resource server {} in user pool {}",
+            identifier, user_pool_id
+        )),
+    })
+}
+
+pub async fn update_resource_server(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+    identifier: &str,
+    resource_server: &ResourceServer,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .update_resource_server()
+        .user_pool_id(user_pool_id)
+        .identifier(identifier)
+        .name(&resource_server.name)
+        .set_scopes(Some(resource_server_scopes(resource_server)?))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Updated resource server {} in user pool {}",
+            identifier, user_pool_id
+        )),
+    })
+}
+
+pub async fn delete_resource_server(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+    identifier: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .delete_resource_server()
+        .user_pool_id(user_pool_id)
+        .identifier(identifier)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Deleted resource server {} from user pool {}",
+            identifier, user_pool_id
+        )),
+    })
+}
+
+fn custom_domain_config(domain: &UserPoolDomain) -> anyhow::Result<Option<CustomDomainConfigType>> {
+    match &domain.certificate_arn {
+        Some(certificate_arn) => Ok(Some(
+            CustomDomainConfigType::builder().certificate_arn(certificate_arn).build()?,
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Custom domains can take a while to become available, while their CloudFront distribution deploys.
+pub async fn create_user_pool_domain(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+    domain: &UserPoolDomain,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let resp = client
+        .create_user_pool_domain()
+        .user_pool_id(user_pool_id)
+        .domain(&domain.domain)
+        .set_custom_domain_config(custom_domain_config(domain)?)
+        .set_managed_login_version(domain.managed_login_version)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("cloudfront_distribution"), resp.cloud_front_domain)])),
+        friendly_message: Some(format!("Created domain {} for user pool {}", domain.domain, user_pool_id)),
+    })
+}
+
+pub async fn update_user_pool_domain(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+    domain: &UserPoolDomain,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let resp = client
+        .update_user_pool_domain()
+        .user_pool_id(user_pool_id)
+        .domain(&domain.domain)
+        .set_custom_domain_config(custom_domain_config(domain)?)
+        .set_managed_login_version(domain.managed_login_version)
+        .send()
+        .await?;
+
+    // Only custom domains have a distribution of their own
+    let outputs = resp
+        .cloud_front_domain
+        .map(|d| HashMap::from([(String::from("cloudfront_distribution"), Some(d))]));
+
+    Ok(OpExecResponse {
+        outputs,
+        friendly_message: Some(format!("Updated domain {} for user pool {}", domain.domain, user_pool_id)),
+    })
+}
+
+pub async fn delete_user_pool_domain(
+    client: &aws_sdk_cognitoidentityprovider::Client,
+    user_pool_id: &str,
+    domain: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .delete_user_pool_domain()
+        .user_pool_id(user_pool_id)
+        .domain(domain)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("cloudfront_distribution"), None)])),
+        friendly_message: Some(format!("Deleted domain {} from user pool {}", domain, user_pool_id)),
+    })
+}
+
+fn cognito_identity_providers(
+    identity_pool: &IdentityPool,
+    region: &str,
+) -> Vec<aws_sdk_cognitoidentity::types::CognitoIdentityProvider> {
+    identity_pool
+        .cognito_identity_providers
+        .iter()
+        .map(|provider| {
+            aws_sdk_cognitoidentity::types::CognitoIdentityProvider::builder()
+                .provider_name(cognito_provider_name(region, &provider.user_pool_id))
+                .client_id(&provider.client_id)
+                .server_side_token_check(provider.server_side_token_check)
+                .build()
+        })
+        .collect()
+}
+
+/// Creates an identity pool. `identity_pool` must already have its user pool and app client names
+/// resolved to their IDs.
+pub async fn create_identity_pool(
+    client: &aws_sdk_cognitoidentity::Client,
+    region: &str,
+    identity_pool: &IdentityPool,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let resp = client
+        .create_identity_pool()
+        .identity_pool_name(&identity_pool.identity_pool_name)
+        .allow_unauthenticated_identities(identity_pool.allow_unauthenticated_identities)
+        .allow_classic_flow(identity_pool.allow_classic_flow)
+        .set_cognito_identity_providers(Some(cognito_identity_providers(identity_pool, region)))
+        .set_supported_login_providers(Some(to_hash_map(&identity_pool.supported_login_providers)))
+        .set_open_id_connect_provider_arns(Some(identity_pool.open_id_connect_provider_arns.clone()))
+        .set_saml_provider_arns(Some(identity_pool.saml_provider_arns.clone()))
+        .set_developer_provider_name(identity_pool.developer_provider_name.clone())
+        .set_identity_pool_tags(identity_pool.tags.clone().into())
+        .send()
+        .await?;
+
+    let identity_pool_id = resp.identity_pool_id;
+
+    if !identity_pool.roles.is_empty() {
+        set_identity_pool_roles(client, &identity_pool_id, &identity_pool.roles).await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("identity_pool_id"), Some(identity_pool_id.clone()))])),
+        friendly_message: Some(format!(
+            "Created identity pool {} ({})",
+            identity_pool.identity_pool_name, identity_pool_id
+        )),
+    })
+}
+
+/// UpdateIdentityPool replaces the pool's whole configuration, tags included.
+pub async fn update_identity_pool(
+    client: &aws_sdk_cognitoidentity::Client,
+    region: &str,
+    identity_pool_id: &str,
+    identity_pool: &IdentityPool,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .update_identity_pool()
+        .identity_pool_id(identity_pool_id)
+        .identity_pool_name(&identity_pool.identity_pool_name)
+        .allow_unauthenticated_identities(identity_pool.allow_unauthenticated_identities)
+        .allow_classic_flow(identity_pool.allow_classic_flow)
+        .set_cognito_identity_providers(Some(cognito_identity_providers(identity_pool, region)))
+        .set_supported_login_providers(Some(to_hash_map(&identity_pool.supported_login_providers)))
+        .set_open_id_connect_provider_arns(Some(identity_pool.open_id_connect_provider_arns.clone()))
+        .set_saml_provider_arns(Some(identity_pool.saml_provider_arns.clone()))
+        .set_developer_provider_name(identity_pool.developer_provider_name.clone())
+        .set_identity_pool_tags(identity_pool.tags.clone().into())
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated identity pool {}", identity_pool_id)),
+    })
+}
+
+pub async fn set_identity_pool_roles(
+    client: &aws_sdk_cognitoidentity::Client,
+    identity_pool_id: &str,
+    roles: &BTreeMap<String, String>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .set_identity_pool_roles()
+        .identity_pool_id(identity_pool_id)
+        .set_roles(Some(to_hash_map(roles)))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Set roles for identity pool {}", identity_pool_id)),
+    })
+}
+
+pub async fn update_identity_pool_tags(
+    client: &aws_sdk_cognitoidentity::Client,
+    resource_arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(resource_arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client
+            .tag_resource()
+            .resource_arn(resource_arn)
+            .set_tags(Some(new_tagset))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for {}", resource_arn)),
+    })
+}
+
+pub async fn delete_identity_pool(
+    client: &aws_sdk_cognitoidentity::Client,
+    identity_pool_id: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_identity_pool().identity_pool_id(identity_pool_id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("identity_pool_id"), None)])),
+        friendly_message: Some(format!("Deleted identity pool {}", identity_pool_id)),
+    })
+}
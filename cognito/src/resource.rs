@@ -0,0 +1,228 @@
+use std::collections::BTreeMap;
+
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::CognitoResourceAddress, tags::Tags};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct UserPool {
+    /// Can't be changed once the pool exists.
+    pub name: String,
+    /// Sign in with these instead of a username: email and/or phone_number.
+    /// Can't be changed once the pool exists.
+    pub username_attributes: Vec<String>,
+    /// Sign in with these as well as a username: email, phone_number and/or preferred_username.
+    /// Can't be changed once the pool exists.
+    pub alias_attributes: Vec<String>,
+    /// Can't be changed once the pool exists.
+    pub username_case_sensitive: bool,
+    /// Standard attributes that every user must have, such as email. Can't be changed once the pool exists.
+    pub required_attributes: Vec<String>,
+    /// Custom attributes, without the `custom:` prefix. They can be added, but never changed or removed.
+    pub custom_attributes: Vec<CustomAttribute>,
+    /// email and/or phone_number
+    pub auto_verified_attributes: Vec<String>,
+    pub mfa_configuration: String, // OFF, ON or OPTIONAL
+    /// Whether users can set up a TOTP authenticator app as their second factor.
+    pub software_token_mfa: bool,
+    pub password_policy: PasswordPolicy,
+    /// Ways users can recover their account, in order of preference:
+    /// verified_email, verified_phone_number and/or admin_only.
+    pub account_recovery: Vec<String>,
+    /// If set, users can't sign themselves up.
+    pub admin_create_user_only: bool,
+    /// If None, Cognito sends email itself, with a low daily quota.
+    pub email_configuration: Option<EmailConfiguration>,
+    pub lambda_triggers: LambdaTriggers,
+    pub deletion_protection: bool,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CustomAttribute {
+    pub name: String,
+    pub data_type: String, // String, Number, DateTime or Boolean
+    pub mutable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PasswordPolicy {
+    pub minimum_length: i32,
+    pub require_uppercase: bool,
+    pub require_lowercase: bool,
+    pub require_numbers: bool,
+    pub require_symbols: bool,
+    pub temporary_password_validity_days: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EmailConfiguration {
+    /// The ARN of a verified SES identity to send from, for instance one managed by the ses connector.
+    pub source_arn: String,
+    /// The sender, such as `Example <noreply@example.com>`. Defaults to the SES identity's address.
+    pub from: Option<String>,
+    pub reply_to: Option<String>,
+    /// An SES configuration set to send with.
+    pub configuration_set: Option<String>,
+}
+
+/// Lambda functions, by ARN, to run at each step of the sign-up and sign-in flows.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct LambdaTriggers {
+    pub pre_sign_up: Option<String>,
+    pub custom_message: Option<String>,
+    pub post_confirmation: Option<String>,
+    pub pre_authentication: Option<String>,
+    pub post_authentication: Option<String>,
+    pub define_auth_challenge: Option<String>,
+    pub create_auth_challenge: Option<String>,
+    pub verify_auth_challenge_response: Option<String>,
+    pub pre_token_generation: Option<String>,
+    pub user_migration: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct UserPoolClient {
+    pub client_name: String,
+    /// Confidential (server-side) clients have a secret. Can't be changed once the client exists.
+    /// The secret itself is never written to this repository.
+    pub generate_secret: bool,
+    /// ALLOW_USER_SRP_AUTH, ALLOW_REFRESH_TOKEN_AUTH, ALLOW_USER_PASSWORD_AUTH, ALLOW_CUSTOM_AUTH,
+    /// ALLOW_ADMIN_USER_PASSWORD_AUTH or ALLOW_USER_AUTH
+    pub explicit_auth_flows: Vec<String>,
+    /// COGNITO, and/or the names of the pool's identity providers.
+    pub supported_identity_providers: Vec<String>,
+    /// Whether the client can use the OAuth flows below, through the pool's domain.
+    pub allowed_oauth_flows_user_pool_client: bool,
+    pub allowed_oauth_flows: Vec<String>, // code, implicit or client_credentials
+    /// Such as openid, email, profile, or `<resource server identifier>/<scope>`.
+    pub allowed_oauth_scopes: Vec<String>,
+    pub callback_urls: Vec<String>,
+    pub logout_urls: Vec<String>,
+    pub access_token_validity_minutes: Option<i32>,
+    pub id_token_validity_minutes: Option<i32>,
+    pub refresh_token_validity_days: Option<i32>,
+    /// Attributes the client can read and write. Empty for all of them.
+    pub read_attributes: Vec<String>,
+    pub write_attributes: Vec<String>,
+    /// ENABLED hides whether a user exists from sign-in and password reset errors.
+    pub prevent_user_existence_errors: Option<String>, // ENABLED or LEGACY
+    pub enable_token_revocation: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct IdentityProvider {
+    /// SAML, OIDC, Google, Facebook, LoginWithAmazon or SignInWithApple. Can't be changed once the provider exists.
+    pub provider_type: String,
+    /// Such as client_id, authorize_scopes and oidc_issuer, or MetadataURL for SAML.
+    /// Cognito returns client_secret here, so it's left out when the provider is read back, and
+    /// a change to it alone won't be picked up.
+    pub provider_details: BTreeMap<String, String>,
+    /// User pool attribute name to provider attribute name.
+    pub attribute_mapping: BTreeMap<String, String>,
+    /// Email domains or other identifiers that route users to this provider.
+    pub idp_identifiers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceServer {
+    pub name: String,
+    /// Scope name to description. Clients request them as `<identifier>/<scope name>`.
+    pub scopes: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct UserPoolDomain {
+    /// A prefix such as `example-auth` (for `example-auth.auth.<region>.amazoncognito.com`),
+    /// or a full custom domain such as `auth.example.com`.
+    pub domain: String,
+    /// Custom domains only: an ACM certificate in us-east-1 that covers the domain.
+    /// Point an alias record for the domain at the `cloudfront_distribution` output.
+    pub certificate_arn: Option<String>,
+    /// 1 for the classic hosted UI, 2 for managed login.
+    pub managed_login_version: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct IdentityPool {
+    pub identity_pool_name: String,
+    pub allow_unauthenticated_identities: bool,
+    pub allow_classic_flow: bool,
+    pub cognito_identity_providers: Vec<CognitoIdentityProvider>,
+    /// Social provider domain (such as `accounts.google.com`) to app ID.
+    pub supported_login_providers: BTreeMap<String, String>,
+    pub open_id_connect_provider_arns: Vec<String>,
+    pub saml_provider_arns: Vec<String>,
+    /// Can't be changed once set.
+    pub developer_provider_name: Option<String>,
+    /// `authenticated` and/or `unauthenticated`, to the ARN of the role those identities assume.
+    pub roles: BTreeMap<String, String>,
+    pub tags: Tags,
+}
+
+/// A user pool app client that can exchange its tokens for AWS credentials.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CognitoIdentityProvider {
+    /// The user pool's ID, or its name in this repository.
+    pub user_pool_id: String,
+    /// The app client's ID, or its name in this repository.
+    pub client_id: String,
+    pub server_side_token_check: bool,
+}
+
+pub enum CognitoResource {
+    UserPool(UserPool),
+    UserPoolClient(UserPoolClient),
+    IdentityProvider(IdentityProvider),
+    ResourceServer(ResourceServer),
+    UserPoolDomain(UserPoolDomain),
+    IdentityPool(IdentityPool),
+}
+
+impl Resource for CognitoResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            CognitoResource::UserPool(user_pool) => Ok(RON.to_string_pretty(&user_pool, pretty_config)?.into()),
+            CognitoResource::UserPoolClient(client) => Ok(RON.to_string_pretty(&client, pretty_config)?.into()),
+            CognitoResource::IdentityProvider(provider) => Ok(RON.to_string_pretty(&provider, pretty_config)?.into()),
+            CognitoResource::ResourceServer(resource_server) => {
+                Ok(RON.to_string_pretty(&resource_server, pretty_config)?.into())
+            }
+            CognitoResource::UserPoolDomain(domain) => Ok(RON.to_string_pretty(&domain, pretty_config)?.into()),
+            CognitoResource::IdentityPool(identity_pool) => Ok(RON.to_string_pretty(&identity_pool, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = CognitoResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            CognitoResourceAddress::UserPool { .. } => Ok(CognitoResource::UserPool(RON.from_str(s)?)),
+            CognitoResourceAddress::UserPoolClient { .. } => Ok(CognitoResource::UserPoolClient(RON.from_str(s)?)),
+            CognitoResourceAddress::IdentityProvider { .. } => Ok(CognitoResource::IdentityProvider(RON.from_str(s)?)),
+            CognitoResourceAddress::ResourceServer { .. } => Ok(CognitoResource::ResourceServer(RON.from_str(s)?)),
+            CognitoResourceAddress::UserPoolDomain { .. } => Ok(CognitoResource::UserPoolDomain(RON.from_str(s)?)),
+            CognitoResourceAddress::IdentityPool { .. } => Ok(CognitoResource::IdentityPool(RON.from_str(s)?)),
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use aws_sdk_cognitoidentityprovider::types::{
+    AccountRecoverySettingType, AttributeDataType, EmailConfigurationType, EmailSendingAccountType, LambdaConfigType,
+    PasswordPolicyType, RecoveryOptionNameType, RecoveryOptionType, SchemaAttributeType, TimeUnitsType, UserPoolPolicyType,
+    UserPoolType,
+};
+
+use crate::resource::{CustomAttribute, EmailConfiguration, LambdaTriggers, PasswordPolicy, UserPool};
+
+pub fn user_pool_arn(region: &str, account_id: &str, user_pool_id: &str) -> String {
+    format!("arn:aws:cognito-idp:{region}:{account_id}:userpool/{user_pool_id}")
+}
+
+pub fn identity_pool_arn(region: &str, account_id: &str, identity_pool_id: &str) -> String {
+    format!("arn:aws:cognito-identity:{region}:{account_id}:identitypool/{identity_pool_id}")
+}
+
+/// The provider name an identity pool uses for a user pool.
+pub fn cognito_provider_name(region: &str, user_pool_id: &str) -> String {
+    format!("cognito-idp.{region}.amazonaws.com/{user_pool_id}")
+}
+
+/// Splits an identity pool's provider name for a user pool into its region and user pool ID.
+pub fn parse_cognito_provider_name(provider_name: &str) -> Option<(String, String)> {
+    let (host, user_pool_id) = provider_name.split_once('/')?;
+    let region = host.strip_prefix("cognito-idp.")?.strip_suffix(".amazonaws.com")?;
+    Some((region.to_string(), user_pool_id.to_string()))
+}
+
+pub fn password_policy(policy: &PasswordPolicy) -> UserPoolPolicyType {
+    UserPoolPolicyType::builder()
+        .password_policy(
+            PasswordPolicyType::builder()
+                .minimum_length(policy.minimum_length)
+                .require_uppercase(policy.require_uppercase)
+                .require_lowercase(policy.require_lowercase)
+                .require_numbers(policy.require_numbers)
+                .require_symbols(policy.require_symbols)
+                .temporary_password_validity_days(policy.temporary_password_validity_days)
+                .build(),
+        )
+        .build()
+}
+
+/// With no configuration, Cognito sends email from its own address.
+pub fn email_configuration(email_configuration: &Option<EmailConfiguration>) -> EmailConfigurationType {
+    match email_configuration {
+        Some(email_configuration) => EmailConfigurationType::builder()
+            .email_sending_account(EmailSendingAccountType::Developer)
+            .source_arn(&email_configuration.source_arn)
+            .set_from(email_configuration.from.clone())
+            .set_reply_to_email_address(email_configuration.reply_to.clone())
+            .set_configuration_set(email_configuration.configuration_set.clone())
+            .build(),
+        None => EmailConfigurationType::builder()
+            .email_sending_account(EmailSendingAccountType::CognitoDefault)
+            .build(),
+    }
+}
+
+/// Recovery mechanisms are tried in the order they're listed.
+pub fn account_recovery_setting(mechanisms: &[String]) -> anyhow::Result<AccountRecoverySettingType> {
+    let mut recovery_mechanisms = Vec::new();
+    for (i, mechanism) in mechanisms.iter().enumerate() {
+        recovery_mechanisms.push(
+            RecoveryOptionType::builder()
+                .priority(i as i32 + 1)
+                .name(RecoveryOptionNameType::from(mechanism.as_str()))
+                .build()?,
+        );
+    }
+
+    Ok(AccountRecoverySettingType::builder()
+        .set_recovery_mechanisms(Some(recovery_mechanisms))
+        .build())
+}
+
+pub fn lambda_config(triggers: &LambdaTriggers) -> LambdaConfigType {
+    LambdaConfigType::builder()
+        .set_pre_sign_up(triggers.pre_sign_up.clone())
+        .set_custom_message(triggers.custom_message.clone())
+        .set_post_confirmation(triggers.post_confirmation.clone())
+        .set_pre_authentication(triggers.pre_authentication.clone())
+        .set_post_authentication(triggers.post_authentication.clone())
+        .set_define_auth_challenge(triggers.define_auth_challenge.clone())
+        .set_create_auth_challenge(triggers.create_auth_challenge.clone())
+        .set_verify_auth_challenge_response(triggers.verify_auth_challenge_response.clone())
+        .set_pre_token_generation(triggers.pre_token_generation.clone())
+        .set_user_migration(triggers.user_migration.clone())
+        .build()
+}
+
+pub fn custom_attribute_schema(attribute: &CustomAttribute) -> SchemaAttributeType {
+    SchemaAttributeType::builder()
+        .name(&attribute.name)
+        .attribute_data_type(AttributeDataType::from(attribute.data_type.as_str()))
+        .mutable(attribute.mutable)
+        .build()
+}
+
+pub fn required_attribute_schema(name: &str) -> SchemaAttributeType {
+    SchemaAttributeType::builder().name(name).required(true).mutable(true).build()
+}
+
+/// Converts a token validity to minutes, from whatever unit it was set in.
+pub fn to_minutes(value: i32, unit: Option<&TimeUnitsType>) -> i32 {
+    match unit {
+        Some(TimeUnitsType::Seconds) => value / 60,
+        Some(TimeUnitsType::Hours) | None => value * 60,
+        Some(TimeUnitsType::Days) => value * 60 * 24,
+        _ => value,
+    }
+}
+
+/// Converts a token validity to days, from whatever unit it was set in.
+pub fn to_days(value: i32, unit: Option<&TimeUnitsType>) -> i32 {
+    match unit {
+        Some(TimeUnitsType::Seconds) => value / (60 * 60 * 24),
+        Some(TimeUnitsType::Minutes) => value / (60 * 24),
+        Some(TimeUnitsType::Hours) => value / 24,
+        _ => value,
+    }
+}
+
+pub fn user_pool_from_sdk(pool: UserPoolType, software_token_mfa: bool) -> UserPool {
+    let password_policy = pool
+        .policies()
+        .and_then(|p| p.password_policy())
+        .map(|p| PasswordPolicy {
+            minimum_length: p.minimum_length().unwrap_or(8),
+            require_uppercase: p.require_uppercase(),
+            require_lowercase: p.require_lowercase(),
+            require_numbers: p.require_numbers(),
+            require_symbols: p.require_symbols(),
+            temporary_password_validity_days: p.temporary_password_validity_days(),
+        })
+        .unwrap_or(PasswordPolicy {
+            minimum_length: 8,
+            require_uppercase: true,
+            require_lowercase: true,
+            require_numbers: true,
+            require_symbols: true,
+            temporary_password_validity_days: 7,
+        });
+
+    // Every pool has the full set of standard attributes, and `sub` is always required
+    let mut required_attributes = Vec::new();
+    let mut custom_attributes = Vec::new();
+    for attribute in pool.schema_attributes() {
+        let name = attribute.name().unwrap_or_default();
+        if let Some(name) = name.strip_prefix("custom:") {
+            custom_attributes.push(CustomAttribute {
+                name: name.to_string(),
+                data_type: attribute
+                    .attribute_data_type()
+                    .map(|t| t.as_str().to_string())
+                    .unwrap_or_default(),
+                mutable: attribute.mutable().unwrap_or(true),
+            });
+        } else if attribute.required() == Some(true) && name != "sub" {
+            required_attributes.push(name.to_string());
+        }
+    }
+    required_attributes.sort();
+    custom_attributes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut recovery_mechanisms: Vec<&RecoveryOptionType> = pool
+        .account_recovery_setting()
+        .map(|s| s.recovery_mechanisms().iter().collect())
+        .unwrap_or_default();
+    recovery_mechanisms.sort_by_key(|m| m.priority());
+
+    let email_configuration = pool
+        .email_configuration()
+        .filter(|c| c.email_sending_account() == Some(&EmailSendingAccountType::Developer))
+        .map(|c| EmailConfiguration {
+            source_arn: c.source_arn().unwrap_or_default().to_string(),
+            from: c.from().map(String::from),
+            reply_to: c.reply_to_email_address().map(String::from),
+            configuration_set: c.configuration_set().map(String::from),
+        });
+
+    let lambda_triggers = pool
+        .lambda_config()
+        .map(|l| LambdaTriggers {
+            pre_sign_up: l.pre_sign_up().map(String::from),
+            custom_message: l.custom_message().map(String::from),
+            post_confirmation: l.post_confirmation().map(String::from),
+            pre_authentication: l.pre_authentication().map(String::from),
+            post_authentication: l.post_authentication().map(String::from),
+            define_auth_challenge: l.define_auth_challenge().map(String::from),
+            create_auth_challenge: l.create_auth_challenge().map(String::from),
+            verify_auth_challenge_response: l.verify_auth_challenge_response().map(String::from),
+            pre_token_generation: l.pre_token_generation().map(String::from),
+            user_migration: l.user_migration().map(String::from),
+        })
+        .unwrap_or_default();
+
+    UserPool {
+        name: pool.name().unwrap_or_default().to_string(),
+        username_attributes: pool.username_attributes().iter().map(|a| a.as_str().to_string()).collect(),
+        alias_attributes: pool.alias_attributes().iter().map(|a| a.as_str().to_string()).collect(),
+        username_case_sensitive: pool.username_configuration().is_some_and(|c| c.case_sensitive()),
+        required_attributes,
+        custom_attributes,
+        auto_verified_attributes: pool
+            .auto_verified_attributes()
+            .iter()
+            .map(|a| a.as_str().to_string())
+            .collect(),
+        mfa_configuration: pool
+            .mfa_configuration()
+            .map(|m| m.as_str().to_string())
+            .unwrap_or_else(|| String::from("OFF")),
+        software_token_mfa,
+        password_policy,
+        account_recovery: recovery_mechanisms.iter().map(|m| m.name().as_str().to_string()).collect(),
+        admin_create_user_only: pool
+            .admin_create_user_config()
+            .is_some_and(|c| c.allow_admin_create_user_only()),
+        email_configuration,
+        lambda_triggers,
+        deletion_protection: pool.deletion_protection().map(|d| d.as_str()) == Some("ACTIVE"),
+        tags: pool.user_pool_tags.into(),
+    }
+}
@@ -0,0 +1,73 @@
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::bail;
+use autoschematic_core::connector::ResourceAddress;
+
+/// The argument that makes a connector binary print its address documentation and exit,
+/// e.g. `autoschematic-connector-aws-ecs describe-addresses`.
+pub const DESCRIBE_ADDRESSES_ARG: &str = "describe-addresses";
+
+/// One shape of path that a connector accepts.
+#[derive(Debug, Clone)]
+pub struct AddressPattern {
+    /// The path with each variable segment written as `<name>`,
+    /// e.g. `aws/ecs/<region>/clusters/<cluster_name>/services/<service_name>.ron`.
+    pub pattern: &'static str,
+    /// What lives at this address.
+    pub description: &'static str,
+    /// A concrete path matching `pattern`.
+    pub example: &'static str,
+}
+
+/// Implemented by each connector's resource and task address types to describe their address scheme.
+pub trait DescribeAddresses: ResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern>;
+
+    /// The address patterns, after checking that each example parses as this address type
+    /// and maps back to the same path, so the documentation can't drift from `from_path`.
+    fn checked_address_patterns() -> anyhow::Result<Vec<AddressPattern>> {
+        let patterns = Self::address_patterns();
+
+        for pattern in &patterns {
+            let example = Path::new(pattern.example);
+            let addr = match Self::from_path(example) {
+                Ok(addr) => addr,
+                Err(e) => bail!("Example address {} for {} does not parse: {}", pattern.example, pattern.pattern, e),
+            };
+            if addr.to_path_buf() != PathBuf::from(example) {
+                bail!(
+                    "Example address {} for {} maps back to {}",
+                    pattern.example,
+                    pattern.pattern,
+                    addr.to_path_buf().display()
+                );
+            }
+        }
+
+        Ok(patterns)
+    }
+}
+
+/// Whether the connector binary was invoked as `<binary> describe-addresses`.
+pub fn describe_addresses_requested() -> bool {
+    std::env::args().nth(1).as_deref() == Some(DESCRIBE_ADDRESSES_ARG)
+}
+
+/// Renders address patterns as plain text, in the order they're given, so that the output
+/// for a given connector build is always the same.
+pub fn render_address_docs(connector: &str, patterns: &[AddressPattern]) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "Addresses managed by the {} connector:", connector);
+    for pattern in patterns {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "  {}", pattern.pattern);
+        let _ = writeln!(out, "    {}", pattern.description);
+        let _ = writeln!(out, "    e.g. {}", pattern.example);
+    }
+
+    out
+}
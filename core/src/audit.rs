@@ -0,0 +1,434 @@
+use std::{
+    cell::RefCell,
+    future::Future,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Instant, SystemTime},
+};
+
+use autoschematic_core::{connector::OpExecResponse, util::RON};
+use aws_config::{BehaviorVersion, Region, meta::region::RegionProviderChain};
+use aws_smithy_runtime_api::{
+    box_error::BoxError,
+    client::{
+        interceptors::{Intercept, context::BeforeDeserializationInterceptorContextRef},
+        runtime_components::RuntimeComponents,
+    },
+};
+use aws_smithy_types::{
+    config_bag::ConfigBag,
+    date_time::{DateTime, Format},
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+/// Parameter names containing any of these are redacted from audit records,
+/// as are any listed in `redact_keys`. Matching ignores case.
+pub const REDACTED_KEY_PATTERNS: &[&str] = &["password", "secret", "private_key", "credential", "auth_token"];
+
+const REDACTED: &str = "<redacted>";
+
+tokio::task_local! {
+    static REQUEST_IDS: RefCell<Vec<String>>;
+}
+
+/// Where audit records are written.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub enum AuditSink {
+    /// Appends records as JSON lines to a local file. Relative paths are taken from the repository root.
+    File { path: PathBuf },
+    /// Writes each record as its own object, at `<prefix>/<date>/<timestamp>-<pid>-<n>.json`.
+    /// Pair it with S3 Object Lock for tamper-evident storage.
+    S3 {
+        bucket: String,
+        prefix: String,
+        region: String,
+    },
+    /// Puts each record as a log event. The log group must exist; the stream is created if needed.
+    CloudWatchLogs {
+        log_group:  String,
+        log_stream: String,
+        region:     String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AuditLogConfig {
+    pub sinks: Vec<AuditSink>,
+    /// More parameter names to redact, on top of `REDACTED_KEY_PATTERNS`.
+    #[serde(default)]
+    pub redact_keys: Vec<String>,
+}
+
+/// One executed op, as written to the audit log.
+#[derive(Serialize, Debug)]
+pub struct AuditRecord {
+    /// When the op finished, in RFC 3339 format.
+    pub timestamp:        String,
+    pub addr:             String,
+    pub op:               String,
+    /// The op's parameters, with anything that looks like a secret redacted.
+    pub parameters:       serde_json::Value,
+    /// The request ID of every AWS API call the op made, in order.
+    pub request_ids:      Vec<String>,
+    pub outcome:          AuditOutcome,
+    pub friendly_message: Option<String>,
+    pub error:            Option<String>,
+    pub duration_ms:      u128,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Success,
+    Failure,
+}
+
+/// Records every op a connector executes, to the sinks in `aws/audit.ron`.
+///
+/// The audit log is shared by every AWS connector, so it has a config file of its own rather than
+/// a field in each connector's config. With no config file, nothing is recorded.
+/// Failing to write a record is logged but doesn't fail the op, since the op has already run.
+#[derive(Default)]
+pub struct AuditLog {
+    config:      Option<AuditLogConfig>,
+    prefix:      PathBuf,
+    file_lock:   Mutex<()>,
+    s3_client:   Mutex<Option<Arc<aws_sdk_s3::Client>>>,
+    logs_client: Mutex<Option<Arc<aws_sdk_cloudwatchlogs::Client>>>,
+    sequence:    AtomicU64,
+}
+
+impl AuditLog {
+    pub fn try_load(prefix: &Path) -> anyhow::Result<AuditLog> {
+        let config_path = prefix.join("aws/audit.ron");
+        let config = if config_path.is_file() {
+            let config: AuditLogConfig = RON.from_str(&std::fs::read_to_string(config_path)?)?;
+            Some(config)
+        } else {
+            None
+        };
+
+        Ok(AuditLog {
+            config,
+            prefix: prefix.to_path_buf(),
+            ..Default::default()
+        })
+    }
+
+    /// Runs `op_exec` for the op `op` on `addr`, then records how it went.
+    pub async fn record<F>(&self, addr: &Path, op: &str, op_exec: F) -> anyhow::Result<OpExecResponse>
+    where
+        F: Future<Output = anyhow::Result<OpExecResponse>>,
+    {
+        let Some(config) = &self.config else {
+            return op_exec.await;
+        };
+
+        let start = Instant::now();
+        let (result, request_ids) = REQUEST_IDS
+            .scope(RefCell::new(Vec::new()), async {
+                let result = op_exec.await;
+                (result, REQUEST_IDS.with(|ids| ids.take()))
+            })
+            .await;
+        let duration_ms = start.elapsed().as_millis();
+
+        let (op_name, parameters) = redact_op(op, &config.redact_keys);
+        let record = AuditRecord {
+            timestamp: DateTime::from(SystemTime::now())
+                .fmt(Format::DateTime)
+                .unwrap_or_default(),
+            addr: addr.to_string_lossy().to_string(),
+            op: op_name,
+            parameters,
+            request_ids,
+            outcome: if result.is_ok() {
+                AuditOutcome::Success
+            } else {
+                AuditOutcome::Failure
+            },
+            friendly_message: result.as_ref().ok().and_then(|r| r.friendly_message.clone()),
+            error: result.as_ref().err().map(|e| format!("{:#}", e)),
+            duration_ms,
+        };
+
+        for sink in &config.sinks {
+            if let Err(e) = self.write(sink, &record).await {
+                tracing::error!("Failed to write audit record for {} to {:?}: {:#}", record.addr, sink, e);
+            }
+        }
+
+        result
+    }
+
+    async fn write(&self, sink: &AuditSink, record: &AuditRecord) -> anyhow::Result<()> {
+        let line = serde_json::to_string(record)?;
+
+        match sink {
+            AuditSink::File { path } => {
+                let path = self.prefix.join(path);
+                let _guard = self.file_lock.lock().await;
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+                writeln!(file, "{}", line)?;
+            }
+            AuditSink::S3 { bucket, prefix, region } => {
+                let client = self.get_or_init_s3_client(region).await;
+                let date = record.timestamp.get(..10).unwrap_or("unknown");
+                let key = format!(
+                    "{}/{}/{}-{}-{}.json",
+                    prefix.trim_end_matches('/'),
+                    date,
+                    record.timestamp,
+                    std::process::id(),
+                    self.sequence.fetch_add(1, Ordering::Relaxed)
+                );
+                client
+                    .put_object()
+                    .bucket(bucket)
+                    .key(key.trim_start_matches('/'))
+                    .content_type("application/json")
+                    .body(line.into_bytes().into())
+                    .send()
+                    .await?;
+            }
+            AuditSink::CloudWatchLogs {
+                log_group,
+                log_stream,
+                region,
+            } => {
+                let client = self.get_or_init_logs_client(log_group, log_stream, region).await?;
+                client
+                    .put_log_events()
+                    .log_group_name(log_group)
+                    .log_stream_name(log_stream)
+                    .log_events(
+                        aws_sdk_cloudwatchlogs::types::InputLogEvent::builder()
+                            .timestamp(
+                                SystemTime::now()
+                                    .duration_since(SystemTime::UNIX_EPOCH)?
+                                    .as_millis() as i64,
+                            )
+                            .message(line)
+                            .build()?,
+                    )
+                    .send()
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_or_init_s3_client(&self, region: &str) -> Arc<aws_sdk_s3::Client> {
+        let mut s3_client = self.s3_client.lock().await;
+        if let Some(client) = &*s3_client {
+            return client.clone();
+        }
+
+        let config = aws_config::defaults(BehaviorVersion::latest())
+            .region(RegionProviderChain::first_try(Region::new(region.to_owned())))
+            .load()
+            .await;
+        let client = Arc::new(aws_sdk_s3::Client::new(&config));
+        *s3_client = Some(client.clone());
+        client
+    }
+
+    /// Also creates the log stream, the first time it's used.
+    async fn get_or_init_logs_client(
+        &self,
+        log_group: &str,
+        log_stream: &str,
+        region: &str,
+    ) -> anyhow::Result<Arc<aws_sdk_cloudwatchlogs::Client>> {
+        let mut logs_client = self.logs_client.lock().await;
+        if let Some(client) = &*logs_client {
+            return Ok(client.clone());
+        }
+
+        let config = aws_config::defaults(BehaviorVersion::latest())
+            .region(RegionProviderChain::first_try(Region::new(region.to_owned())))
+            .load()
+            .await;
+        let client = Arc::new(aws_sdk_cloudwatchlogs::Client::new(&config));
+
+        if let Err(e) = client
+            .create_log_stream()
+            .log_group_name(log_group)
+            .log_stream_name(log_stream)
+            .send()
+            .await
+            && !e
+                .as_service_error()
+                .is_some_and(|e| e.is_resource_already_exists_exception())
+        {
+            return Err(e.into());
+        }
+
+        *logs_client = Some(client.clone());
+        Ok(client)
+    }
+}
+
+fn is_redacted_key(key: &str, redact_keys: &[String]) -> bool {
+    let key = key.to_lowercase();
+    REDACTED_KEY_PATTERNS.iter().any(|p| key.contains(p)) || redact_keys.iter().any(|k| key.contains(&k.to_lowercase()))
+}
+
+/// Converts a RON value to JSON, replacing the value of any map entry whose key should be redacted.
+/// Bytes are always redacted, since they're most often secret or key material.
+fn redact_value(value: ron::Value, redact_keys: &[String]) -> serde_json::Value {
+    match value {
+        ron::Value::Bool(b) => serde_json::Value::Bool(b),
+        ron::Value::Char(c) => serde_json::Value::String(c.to_string()),
+        ron::Value::String(s) => serde_json::Value::String(s),
+        ron::Value::Number(n) => serde_json::to_value(n).unwrap_or(serde_json::Value::Null),
+        ron::Value::Bytes(_) => serde_json::Value::String(String::from(REDACTED)),
+        ron::Value::Option(Some(v)) => redact_value(*v, redact_keys),
+        ron::Value::Option(None) | ron::Value::Unit => serde_json::Value::Null,
+        ron::Value::Seq(seq) => serde_json::Value::Array(seq.into_iter().map(|v| redact_value(v, redact_keys)).collect()),
+        ron::Value::Map(map) => {
+            let mut object = serde_json::Map::new();
+            for (k, v) in map.into_iter() {
+                let key = match k {
+                    ron::Value::String(s) => s,
+                    k => redact_value(k, redact_keys).to_string(),
+                };
+                let value = if is_redacted_key(&key, redact_keys) {
+                    serde_json::Value::String(String::from(REDACTED))
+                } else {
+                    redact_value(v, redact_keys)
+                };
+                object.insert(key, value);
+            }
+            serde_json::Value::Object(object)
+        }
+    }
+}
+
+/// Splits a serialized op such as `CreateTopic((...))` into its name and its redacted parameters.
+/// Parameters that can't be parsed are left out entirely rather than risk recording a secret.
+pub fn redact_op(op: &str, redact_keys: &[String]) -> (String, serde_json::Value) {
+    let op = op.trim();
+    let name_len = op.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(op.len());
+    let (name, body) = op.split_at(name_len);
+
+    let body = body.trim();
+    if body.is_empty() {
+        return (name.to_string(), serde_json::Value::Null);
+    }
+
+    let parameters = match RON.from_str::<ron::Value>(body) {
+        Ok(value) => redact_value(value, redact_keys),
+        Err(_) => serde_json::Value::String(String::from("<unparsed>")),
+    };
+    (name.to_string(), parameters)
+}
+
+/// Collects the request ID of every AWS API call made during `AuditLog::record`.
+/// Connectors add it to each client they build, so that audit records can be matched up
+/// with CloudTrail events and AWS support cases.
+#[derive(Debug)]
+pub struct RequestIdRecorder;
+
+impl Intercept for RequestIdRecorder {
+    fn name(&self) -> &'static str {
+        "RequestIdRecorder"
+    }
+
+    fn read_after_transmit(
+        &self,
+        context: &BeforeDeserializationInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let headers = context.response().headers();
+        // S3 uses its own header name
+        if let Some(request_id) = headers.get("x-amzn-requestid").or_else(|| headers.get("x-amz-request-id")) {
+            // Outside of an audited op, there's nowhere to put it
+            let _ = REQUEST_IDS.try_with(|ids| ids.borrow_mut().push(request_id.to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Builds an SDK client whose request IDs are collected for the audit log, in place of `Client::new`.
+/// For example, `audited_client!(aws_sdk_sns, &config)`.
+///
+/// Inside `replay::with_cassette`, the client's traffic goes through the cassette instead.
+#[macro_export]
+macro_rules! audited_client {
+    ($sdk:ident, $config:expr) => {{
+        let mut builder = $sdk::config::Builder::from($config).interceptor($crate::audit::RequestIdRecorder);
+        if let Some(cassette) = $crate::replay::current_cassette() {
+            builder = builder.http_client(cassette.http_client);
+            if cassette.replaying {
+                builder = builder.credentials_provider($sdk::config::Credentials::new(
+                    "AKIDREPLAY",
+                    "replay",
+                    None,
+                    None,
+                    "replay",
+                ));
+            }
+        }
+        $sdk::Client::from_conf(builder.build())
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use super::redact_op;
+
+    #[test]
+    fn nested_secrets_are_redacted() {
+        let op = r#"CreateDBInstance((
+            identifier: "web",
+            master_user_password: Some("hunter2"),
+            options: [(name: "auth", settings: (client_secret: "s3cr3t", port: 5432))],
+        ))"#;
+
+        let (name, parameters) = redact_op(op, &[]);
+        let parameters = parameters.to_string();
+
+        assert_eq!(name, "CreateDBInstance");
+        assert!(!parameters.contains("hunter2"), "{}", parameters);
+        assert!(!parameters.contains("s3cr3t"), "{}", parameters);
+        assert!(parameters.contains("\"web\""), "{}", parameters);
+        assert!(parameters.contains("5432"), "{}", parameters);
+    }
+
+    #[test]
+    fn redact_keys_are_redacted() {
+        let op = r#"PutParameter((name: "deploy", Team_Token: "abc123", nested: (team_token: "def456")))"#;
+
+        let (_, unredacted) = redact_op(op, &[]);
+        assert!(unredacted.to_string().contains("abc123"));
+
+        let (_, parameters) = redact_op(op, &[String::from("team_token")]);
+        let parameters = parameters.to_string();
+
+        assert!(!parameters.contains("abc123"), "{}", parameters);
+        assert!(!parameters.contains("def456"), "{}", parameters);
+        assert!(parameters.contains("\"deploy\""), "{}", parameters);
+    }
+
+    #[test]
+    fn ops_without_parameters() {
+        assert_eq!(redact_op("DeleteAlarm", &[]), (String::from("DeleteAlarm"), serde_json::Value::Null));
+        assert_eq!(
+            redact_op("UpdateSecret((value: \"unterminated", &[]),
+            (String::from("UpdateSecret"), serde_json::Value::String(String::from("<unparsed>")))
+        );
+    }
+}
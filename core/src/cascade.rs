@@ -0,0 +1,456 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock, Mutex},
+};
+
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    util::RON,
+};
+
+/// Declares that a connector's resource bodies may embed, by value, the output `key` of resources
+/// under `producer`, in any of `fields`. For example, ECS services hold subnet IDs from the VPC
+/// connector in `subnets`. Each connector declares the outputs it consumes in its own
+/// `CONSUMED_OUTPUTS`, and passes them to `resolve_stale_references` when planning.
+pub struct ConsumedOutput {
+    pub producer: &'static str,
+    pub key:      &'static str,
+    pub fields:   &'static [&'static str],
+}
+
+/// The output under which a replaced resource records its previous value of output `key`,
+/// so that consumers still holding that value can be pointed at the replacement.
+pub fn replaced_output_key(key: &str) -> String {
+    format!("replaced_{}", key)
+}
+
+/// Any resource file, addressed by its path, so that outputs can be read across connectors.
+#[derive(Debug, Clone)]
+struct AnyAddress(PathBuf);
+
+impl ResourceAddress for AnyAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        self.0.clone()
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        Ok(AnyAddress(path.to_path_buf()))
+    }
+}
+
+/// The paths, relative to `prefix`, of every resource file under `dir`.
+fn resource_files(prefix: &Path, dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    let mut stack = vec![prefix.join(dir)];
+    while let Some(dir) = stack.pop() {
+        if !dir.is_dir() {
+            continue;
+        }
+
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                stack.push(path);
+                continue;
+            }
+
+            if path.extension().is_none_or(|ext| ext != "ron") {
+                continue;
+            }
+
+            if let Ok(rel_path) = path.strip_prefix(prefix) {
+                files.push(rel_path.to_path_buf());
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Whether `value` holds the string `s` anywhere, as a whole value rather than part of one.
+fn holds_string(value: &ron::Value, s: &str) -> bool {
+    match value {
+        ron::Value::String(v) => v == s,
+        ron::Value::Option(Some(v)) => holds_string(v, s),
+        ron::Value::Seq(seq) => seq.iter().any(|v| holds_string(v, s)),
+        ron::Value::Map(map) => map.iter().any(|(k, v)| holds_string(k, s) || holds_string(v, s)),
+        _ => false,
+    }
+}
+
+/// Collects the strings held in any field named in `fields`, anywhere in `value`, along with the field's name.
+fn field_strings(value: &ron::Value, fields: &[&str], res: &mut Vec<(String, String)>) {
+    fn strings(value: &ron::Value, field: &str, res: &mut Vec<(String, String)>) {
+        match value {
+            ron::Value::String(v) => res.push((field.to_string(), v.clone())),
+            ron::Value::Option(Some(v)) => strings(v, field, res),
+            ron::Value::Seq(seq) => seq.iter().for_each(|v| strings(v, field, res)),
+            _ => {}
+        }
+    }
+
+    match value {
+        ron::Value::Option(Some(v)) => field_strings(v, fields, res),
+        ron::Value::Seq(seq) => seq.iter().for_each(|v| field_strings(v, fields, res)),
+        ron::Value::Map(map) => {
+            for (k, v) in map.iter() {
+                if let ron::Value::String(name) = k
+                    && fields.contains(&name.as_str())
+                {
+                    strings(v, name, res);
+                }
+                field_strings(v, fields, res);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Finds every resource file that still holds `old_value`, the value of an output of the resource
+/// at `producer` before it was replaced. Files are parsed, and only match if one of their values is
+/// exactly `old_value`; files that don't parse as RON match if they hold it as a quoted string.
+/// The returned paths are relative to `prefix`, and exclude `producer` itself.
+pub fn find_stale_consumers(prefix: &Path, producer: &Path, old_value: &str) -> anyhow::Result<Vec<PathBuf>> {
+    let mut consumers = Vec::new();
+
+    if old_value.is_empty() {
+        return Ok(consumers);
+    }
+
+    for rel_path in resource_files(prefix, Path::new("aws"))? {
+        if rel_path == producer {
+            continue;
+        }
+
+        let body = std::fs::read_to_string(prefix.join(&rel_path))?;
+        let holds_value = match RON.from_str::<ron::Value>(&body) {
+            Ok(value) => holds_string(&value, old_value),
+            Err(_) => body.contains(&format!("\"{}\"", old_value)),
+        };
+        if holds_value {
+            consumers.push(rel_path);
+        }
+    }
+
+    Ok(consumers)
+}
+
+/// Formats a list of stale consumers for inclusion in a replacement op's message.
+/// Returns an empty string if there are none.
+pub fn stale_consumers_message(old_value: &str, consumers: &[PathBuf]) -> String {
+    if consumers.is_empty() {
+        return String::new();
+    }
+
+    let mut message = format!(
+        "\nThe following resources reference `{}`. Once it's replaced, connectors that declare the reference \
+         will plan updates to point them at the replacement; update the files themselves to match:",
+        old_value
+    );
+    for consumer in consumers {
+        message.push_str(&format!("\n  - {}", consumer.display()));
+    }
+    message
+}
+
+/// A value in a resource body that names a resource that has since been replaced.
+#[derive(Debug, Clone)]
+pub struct StaleReference {
+    pub field:     String,
+    pub old_value: String,
+    pub new_value: String,
+    /// The replaced resource, relative to the prefix.
+    pub producer:  PathBuf,
+}
+
+/// The replaced values of one producer output, mapped to their current value and the resource that holds it.
+type Replacements = HashMap<String, (String, PathBuf)>;
+
+/// The replacements found under each (prefix, producer, key), so that each producer directory is
+/// walked once rather than on every plan. Cleared by `forget_replacements`.
+static REPLACEMENTS: LazyLock<Mutex<HashMap<(PathBuf, &'static str, &'static str), Arc<Replacements>>>> =
+    LazyLock::new(Default::default);
+
+/// Drops the replacements found so far under `prefix`, so that the next plan picks up resources
+/// replaced since. Connectors that consume outputs call this from `init`.
+pub fn forget_replacements(prefix: &Path) {
+    if let Ok(mut replacements) = REPLACEMENTS.lock() {
+        replacements.retain(|(p, _, _), _| p != prefix);
+    }
+}
+
+/// The replaced values of output `consumed.key` on resources under `consumed.producer`.
+/// Only resources with a `replaced_*` output are kept, so most producers yield an empty map.
+fn replacements(prefix: &Path, consumed: &ConsumedOutput) -> anyhow::Result<Arc<Replacements>> {
+    let cache_key = (prefix.to_path_buf(), consumed.producer, consumed.key);
+    if let Some(res) = REPLACEMENTS.lock().ok().and_then(|r| r.get(&cache_key).cloned()) {
+        return Ok(res);
+    }
+
+    let mut res = Replacements::new();
+    for rel_path in resource_files(prefix, Path::new(consumed.producer))? {
+        let addr = AnyAddress(rel_path);
+        if let Some(old_value) = addr.get_output(prefix, &replaced_output_key(consumed.key))?
+            && let Some(new_value) = addr.get_output(prefix, consumed.key)?
+            && old_value != new_value
+        {
+            res.insert(old_value, (new_value, addr.0));
+        }
+    }
+
+    let res = Arc::new(res);
+    if let Ok(mut replacements) = REPLACEMENTS.lock() {
+        replacements.insert(cache_key, res.clone());
+    }
+    Ok(res)
+}
+
+/// Finds the values in `body`'s consumed fields that name a resource that has since been replaced.
+/// Bodies that don't parse are left for the connector's own plan to report.
+pub fn find_stale_references(prefix: &Path, consumed: &[ConsumedOutput], body: &str) -> anyhow::Result<Vec<StaleReference>> {
+    let mut stale = Vec::new();
+
+    let Ok(value) = RON.from_str::<ron::Value>(body) else {
+        return Ok(stale);
+    };
+
+    for consumed_output in consumed {
+        let mut values = Vec::new();
+        field_strings(&value, consumed_output.fields, &mut values);
+        if values.is_empty() {
+            continue;
+        }
+
+        let replacements = replacements(prefix, consumed_output)?;
+        if replacements.is_empty() {
+            continue;
+        }
+
+        for (field, old_value) in values {
+            if let Some((new_value, producer)) = replacements.get(&old_value)
+                && !stale.iter().any(|s: &StaleReference| s.old_value == old_value)
+            {
+                stale.push(StaleReference {
+                    field,
+                    old_value,
+                    new_value: new_value.clone(),
+                    producer: producer.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(stale)
+}
+
+/// A token of a RON body that matters for finding field values, by its byte range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Str(usize, usize),
+    Ident(usize, usize),
+    Punct(char, usize),
+}
+
+/// Splits a RON body into string literals, identifiers and punctuation, skipping comments.
+fn tokenize(body: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = body.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                let mut end = body.len();
+                while let Some((j, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => {
+                            end = j + 1;
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                tokens.push(Token::Str(i, end));
+            }
+            '/' if matches!(chars.peek(), Some((_, '/'))) => {
+                while chars.next_if(|(_, c)| *c != '\n').is_some() {}
+            }
+            '/' if matches!(chars.peek(), Some((_, '*'))) => {
+                chars.next();
+                while let Some((_, c)) = chars.next() {
+                    if c == '*' && chars.next_if(|(_, c)| *c == '/').is_some() {
+                        break;
+                    }
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = i + c.len_utf8();
+                while let Some((j, c)) = chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_') {
+                    end = j + c.len_utf8();
+                }
+                tokens.push(Token::Ident(i, end));
+            }
+            c if c.is_whitespace() => {}
+            c => tokens.push(Token::Punct(c, i)),
+        }
+    }
+
+    tokens
+}
+
+/// The byte ranges of the values of any field named in `fields`, anywhere in a RON body.
+fn field_value_spans(body: &str, tokens: &[Token], fields: &[&str]) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        let Token::Ident(start, end) = *token else {
+            continue;
+        };
+        let Some(Token::Punct(':', colon)) = tokens.get(i + 1).copied() else {
+            continue;
+        };
+        if !fields.contains(&&body[start..end]) {
+            continue;
+        }
+
+        // The value runs until the comma or closing bracket that ends the field
+        let mut depth = 0usize;
+        let mut value_end = body.len();
+        for token in &tokens[i + 2..] {
+            match *token {
+                Token::Punct('(' | '[' | '{', _) => depth += 1,
+                Token::Punct(')' | ']' | '}', j) if depth == 0 => {
+                    value_end = j;
+                    break;
+                }
+                Token::Punct(')' | ']' | '}', _) => depth -= 1,
+                Token::Punct(',', j) if depth == 0 => {
+                    value_end = j;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        spans.push((colon + 1, value_end));
+    }
+
+    spans
+}
+
+/// Replaces each stale value with its replacement, only where it's a whole string held by one of `fields`.
+fn replace_in_fields(body: &str, fields: &[&str], stale: &[StaleReference]) -> String {
+    let tokens = tokenize(body);
+    let spans = field_value_spans(body, &tokens, fields);
+
+    let mut res = String::with_capacity(body.len());
+    let mut copied = 0;
+    for token in &tokens {
+        let Token::Str(start, end) = *token else {
+            continue;
+        };
+        if !spans.iter().any(|(s, e)| *s <= start && end <= *e) {
+            continue;
+        }
+
+        let literal = &body[start..end];
+        if let Some(s) = stale.iter().find(|s| literal == format!("\"{}\"", s.old_value)) {
+            res.push_str(&body[copied..start]);
+            res.push_str(&format!("\"{}\"", s.new_value));
+            copied = end;
+        }
+    }
+    res.push_str(&body[copied..]);
+
+    res
+}
+
+/// Points the stale references in a desired resource body at the resources that replaced them,
+/// so that planning it updates the resource to match. Returns the rewritten body, and a note
+/// describing the rewrite to add to each planned op with `note_stale_references`.
+pub fn resolve_stale_references(
+    prefix: &Path,
+    consumed: &[ConsumedOutput],
+    desired: Option<Vec<u8>>,
+) -> anyhow::Result<(Option<Vec<u8>>, String)> {
+    let Some(desired) = desired else {
+        return Ok((None, String::new()));
+    };
+    let Ok(mut body) = String::from_utf8(desired.clone()) else {
+        return Ok((Some(desired), String::new()));
+    };
+
+    let mut note = String::new();
+    for consumed_output in consumed {
+        let stale = find_stale_references(prefix, std::slice::from_ref(consumed_output), &body)?;
+        if stale.is_empty() {
+            continue;
+        }
+
+        body = replace_in_fields(&body, consumed_output.fields, &stale);
+        for s in &stale {
+            note.push_str(&format!(
+                "\nNOTE: `{}` in {} was replaced by `{}` ({}). Planned against the replacement; update the file to match.",
+                s.old_value,
+                s.field,
+                s.new_value,
+                s.producer.display()
+            ));
+        }
+    }
+
+    if note.is_empty() {
+        return Ok((Some(desired), note));
+    }
+
+    Ok((Some(body.into_bytes()), note))
+}
+
+/// Adds the note from `resolve_stale_references` to each planned op's message.
+pub fn note_stale_references(mut ops: Vec<PlanResponseElement>, note: &str) -> Vec<PlanResponseElement> {
+    if note.is_empty() {
+        return ops;
+    }
+
+    for op in &mut ops {
+        op.friendly_message = Some(format!("{}{}", op.friendly_message.take().unwrap_or_default(), note));
+    }
+    ops
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::{StaleReference, replace_in_fields};
+
+    #[test]
+    fn only_declared_fields_are_rewritten() {
+        let body = r#"Service(
+    // "subnet-old" in a comment is left alone
+    description: "moved off subnet-old",
+    name: "subnet-old",
+    network: Some((subnets: ["subnet-a", "subnet-old"], security_groups: ["subnet-old"])),
+)"#;
+        let stale = [StaleReference {
+            field:     String::from("subnets"),
+            old_value: String::from("subnet-old"),
+            new_value: String::from("subnet-new"),
+            producer:  PathBuf::from("aws/vpc/us-east-1/subnets/a.ron"),
+        }];
+
+        assert_eq!(
+            replace_in_fields(body, &["subnets"], &stale),
+            r#"Service(
+    // "subnet-old" in a comment is left alone
+    description: "moved off subnet-old",
+    name: "subnet-old",
+    network: Some((subnets: ["subnet-a", "subnet-new"], security_groups: ["subnet-old"])),
+)"#
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use tokio::sync::{Mutex, Semaphore};
+
+/// The number of ops a connector will execute at once if `max_concurrent_ops` is not configured.
+pub const DEFAULT_MAX_CONCURRENT_OPS: usize = 8;
+
+type AddrLocks = std::sync::Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>;
+
+/// Bounds how many ops a connector executes at once.
+///
+/// Ops on different addresses run in parallel, up to `max_concurrent_ops` at a time, so that
+/// large plans (e.g. dozens of record sets) don't execute strictly serially but also don't trip
+/// AWS API rate limits. Ops on the same address always run one at a time, in the order they
+/// arrive, because later ops on an address generally depend on the earlier ones
+/// (e.g. create, then tag, then attach).
+///
+/// Only ops on the same address are ordered. Dependencies between addresses, such as a subnet
+/// on its VPC, are not tracked here: it's up to the caller not to submit an op until the ops it
+/// depends on have finished.
+pub struct OpExecLimiter {
+    semaphore:  Semaphore,
+    addr_locks: AddrLocks,
+}
+
+/// A handle on the lock for one address. Dropping the last handle removes the lock,
+/// so that the limiter only holds locks for addresses with ops in flight.
+struct AddrLockHandle<'a> {
+    addr_locks: &'a AddrLocks,
+    addr:       PathBuf,
+    lock:       Arc<Mutex<()>>,
+}
+
+impl Drop for AddrLockHandle<'_> {
+    fn drop(&mut self) {
+        let mut addr_locks = self.addr_locks.lock().unwrap_or_else(|e| e.into_inner());
+        // Handles are only created while the map is locked, so one held by the map and one held
+        // here means nothing else is waiting on this address.
+        if Arc::strong_count(&self.lock) == 2 {
+            addr_locks.remove(&self.addr);
+        }
+    }
+}
+
+impl OpExecLimiter {
+    pub fn new(max_concurrent_ops: usize) -> Self {
+        Self {
+            semaphore:  Semaphore::new(max_concurrent_ops.max(1)),
+            addr_locks: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn from_config(max_concurrent_ops: Option<usize>) -> Self {
+        Self::new(max_concurrent_ops.unwrap_or(DEFAULT_MAX_CONCURRENT_OPS))
+    }
+
+    fn addr_lock(&self, addr: &Path) -> AddrLockHandle<'_> {
+        let lock = self
+            .addr_locks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(addr.to_path_buf())
+            .or_insert_with(|| Arc::new(Mutex::new(())))
+            .clone();
+
+        AddrLockHandle {
+            addr_locks: &self.addr_locks,
+            addr: addr.to_path_buf(),
+            lock,
+        }
+    }
+
+    /// Runs `op` once both a global slot and the lock for `addr` are available.
+    pub async fn run<F, T>(&self, addr: &Path, op: F) -> anyhow::Result<T>
+    where
+        F: Future<Output = anyhow::Result<T>>,
+    {
+        // Declared first so that it's dropped last, after the guard and permit,
+        // including when the op is cancelled.
+        let addr_lock = self.addr_lock(addr);
+
+        // Take the address lock first so that a queue of ops against one address
+        // doesn't hold global slots while it waits.
+        let _addr_guard = addr_lock.lock.lock().await;
+        let _permit = self.semaphore.acquire().await?;
+
+        op.await
+    }
+}
+
+impl Default for OpExecLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_OPS)
+    }
+}
@@ -16,6 +16,10 @@ pub struct AwsConnectorConfig {
     pub timeout_config:  Option<TimeoutConfig>,
     pub sts_region:      String,
     pub enabled_regions: Vec<String>,
+    /// The maximum number of ops each connector will execute at once.
+    /// Defaults to `concurrency::DEFAULT_MAX_CONCURRENT_OPS`.
+    #[serde(default)]
+    pub max_concurrent_ops: Option<usize>,
 }
 
 impl Default for AwsConnectorConfig {
@@ -64,6 +68,7 @@ impl Default for AwsConnectorConfig {
             .iter()
             .map(|s| s.to_string())
             .collect(),
+            max_concurrent_ops: Default::default(),
         }
     }
 }
@@ -87,7 +92,7 @@ impl AwsConnectorConfig {
             .load()
             .await;
 
-        let sts_client = aws_sdk_sts::Client::new(&sts_config);
+        let sts_client = crate::audited_client!(aws_sdk_sts, &sts_config);
         let caller_identity = sts_client.get_caller_identity().send().await;
 
         match caller_identity {
@@ -137,7 +142,7 @@ pub async fn verify_sts_account_id(sts_region: String, account_id: Option<String
         .load()
         .await;
 
-    let sts_client = aws_sdk_sts::Client::new(&sts_config);
+    let sts_client = crate::audited_client!(aws_sdk_sts, &sts_config);
     let caller_identity = sts_client.get_caller_identity().send().await;
 
     match caller_identity {
@@ -166,9 +171,12 @@ pub trait AwsServiceConfig: From<AwsConnectorConfig> {
     async fn verify_sts(&self) -> anyhow::Result<String>;
 }
 
+/// Implements `From<AwsConnectorConfig>`, `Default` and `AwsServiceConfig` for a per-service config struct.
+/// Any service-specific fields beyond the shared AWS ones can be listed after the path;
+/// they are initialized with `Default::default()` when falling back to `aws/config.ron`.
 #[macro_export]
 macro_rules! impl_aws_config {
-    ($type:ty, $path:expr) => {
+    ($type:ty, $path:expr $(, $extra_field:ident)* $(,)?) => {
         impl From<AwsConnectorConfig> for $type {
             fn from(value: AwsConnectorConfig) -> Self {
                 Self {
@@ -177,6 +185,8 @@ macro_rules! impl_aws_config {
                     timeout_config:  value.timeout_config,
                     sts_region:      value.sts_region,
                     enabled_regions: value.enabled_regions,
+                    max_concurrent_ops: value.max_concurrent_ops,
+                    $($extra_field: Default::default(),)*
                 }
             }
         }
@@ -0,0 +1,188 @@
+//! Addresses of the ELB connector's resources, for other connectors that refer to load balancers
+//! and target groups by address instead of by ARN. A reference is resolved through the output
+//! that the ELB connector records for the resource once it's been created.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::bail;
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+/// A kind of resource managed by the ELB connector that other connectors can refer to by address.
+pub trait ElbAddress: ResourceAddress + Sized {
+    /// What the resource is called in messages, e.g. `load balancer`.
+    const KIND: &'static str;
+    /// The directory, under `aws/elb/<region>/`, that holds resources of this kind.
+    const DIR: &'static str;
+    /// The output that holds the resource's ARN.
+    const ARN_OUTPUT: &'static str;
+
+    fn new(region: &str, name: &str) -> Self;
+
+    fn region(&self) -> &str;
+
+    fn parse_elb_path(path: &Path) -> anyhow::Result<Self> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "elb", region, dir, name] if *dir == Self::DIR && name.ends_with(".ron") => {
+                Ok(Self::new(region, name.strip_suffix(".ron").unwrap()))
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+/// The address of a load balancer managed by the ELB connector.
+#[derive(Debug, Clone)]
+pub struct ElbLoadBalancerAddress {
+    pub region: String,
+    pub name:   String,
+}
+
+impl ElbAddress for ElbLoadBalancerAddress {
+    const KIND: &'static str = "load balancer";
+    const DIR: &'static str = "load_balancers";
+    const ARN_OUTPUT: &'static str = "load_balancer_arn";
+
+    fn new(region: &str, name: &str) -> Self {
+        Self {
+            region: region.to_string(),
+            name:   name.to_string(),
+        }
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+}
+
+impl ResourceAddress for ElbLoadBalancerAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        PathBuf::from(format!("aws/elb/{}/load_balancers/{}.ron", self.region, self.name))
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        Self::parse_elb_path(path)
+    }
+}
+
+/// The address of a target group managed by the ELB connector.
+#[derive(Debug, Clone)]
+pub struct ElbTargetGroupAddress {
+    pub region: String,
+    pub name:   String,
+}
+
+impl ElbAddress for ElbTargetGroupAddress {
+    const KIND: &'static str = "target group";
+    const DIR: &'static str = "target_groups";
+    const ARN_OUTPUT: &'static str = "target_group_arn";
+
+    fn new(region: &str, name: &str) -> Self {
+        Self {
+            region: region.to_string(),
+            name:   name.to_string(),
+        }
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+}
+
+impl ResourceAddress for ElbTargetGroupAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        PathBuf::from(format!("aws/elb/{}/target_groups/{}.ron", self.region, self.name))
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        Self::parse_elb_path(path)
+    }
+}
+
+/// Whether a reference names an ELB connector address rather than an ID or ARN.
+pub fn is_elb_address(reference: &str) -> bool {
+    reference.starts_with("aws/elb/")
+}
+
+/// Checks that a reference to an ELB address is a valid address of kind `A` in `region`, the region of
+/// `owner`, the resource that refers to it. Returns the problem, if any.
+/// References by ID or ARN aren't checked.
+pub fn check_elb_address<A: ElbAddress>(reference: &str, region: &str, owner: &str) -> Option<String> {
+    if !is_elb_address(reference) {
+        return None;
+    }
+
+    match A::from_path(Path::new(reference)) {
+        Ok(addr) if addr.region() != region => Some(format!(
+            "{} {} is in {}, but {} is in {}",
+            A::KIND,
+            reference,
+            addr.region(),
+            owner,
+            region
+        )),
+        Ok(_) => None,
+        Err(_) => Some(format!(
+            "{} is not a {} address; expected aws/elb/<region>/{}/<name>.ron",
+            reference,
+            A::KIND,
+            A::DIR
+        )),
+    }
+}
+
+/// Replaces a reference to an ELB address of kind `A` with the ARN from the resource's outputs.
+/// References by ID or ARN are returned as they are. Fails if the resource hasn't been created yet.
+pub fn resolve_elb_address<A: ElbAddress>(prefix: &Path, reference: &str) -> anyhow::Result<String> {
+    if !is_elb_address(reference) {
+        return Ok(reference.to_string());
+    }
+
+    let addr = A::from_path(Path::new(reference))?;
+    let Some(arn) = addr.get_output(prefix, A::ARN_OUTPUT)? else {
+        bail!("{} {} has no {} output. Has it been created yet?", A::KIND, reference, A::ARN_OUTPUT);
+    };
+
+    Ok(arn)
+}
+
+/// The addresses of the ELB resources of kind `A` in `region` that have been created, keyed by
+/// `output`, e.g. their ARN. Used to report references by address when the repo manages them.
+pub fn elb_addresses_by_output<A: ElbAddress>(
+    prefix: &Path,
+    region: &str,
+    output: &str,
+) -> anyhow::Result<HashMap<String, String>> {
+    let mut addresses = HashMap::new();
+
+    let dir = prefix.join(format!("aws/elb/{}/{}", region, A::DIR));
+    if !dir.is_dir() {
+        return Ok(addresses);
+    }
+
+    for entry in std::fs::read_dir(&dir)? {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|f| f.to_str()) else {
+            continue;
+        };
+        let Some(name) = file_name.strip_suffix(".ron") else {
+            continue;
+        };
+
+        let addr = A::new(region, name);
+        if let Some(value) = addr.get_output(prefix, output)? {
+            addresses.insert(value, addr.to_path_buf().to_string_lossy().to_string());
+        }
+    }
+
+    Ok(addresses)
+}
+
+/// The addresses of the ELB resources of kind `A` in `region` that have been created, keyed by ARN.
+pub fn elb_addresses_by_arn<A: ElbAddress>(prefix: &Path, region: &str) -> anyhow::Result<HashMap<String, String>> {
+    elb_addresses_by_output::<A>(prefix, region, A::ARN_OUTPUT)
+}
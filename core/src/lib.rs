@@ -1,4 +1,10 @@
 
 pub mod config;
 pub mod util;
-pub mod arn;
\ No newline at end of file
+pub mod arn;
+pub mod concurrency;
+pub mod cascade;
+pub mod elb;
+pub mod addr_doc;
+pub mod audit;
+pub mod replay;
@@ -0,0 +1,212 @@
+//! Record and replay of AWS API traffic, so that connector tests can run offline.
+//!
+//! A test runs its connector calls inside [`with_cassette`]. Every client built with `audited_client!`
+//! in that scope sends its requests through the cassette instead of the network. Normally the
+//! cassette's recorded responses are replayed in order, and the clients get dummy credentials.
+//! With `AUTOSCHEMATIC_RECORD=1` set, the requests go to AWS with the environment's credentials,
+//! and the traffic is written back to the cassette file.
+//!
+//! Scenarios run against resources that already exist in the recording account. Their IDs differ
+//! from account to account, so tests read them with [`fixture`], which saves them next to the cassette.
+//! A test whose cassette hasn't been recorded yet fails, unless `AUTOSCHEMATIC_SKIP_UNRECORDED=1` is set
+//! to skip it instead, see [`unrecorded`].
+//!
+//! To record a cassette, set up the resources its test describes, then run e.g.
+//! `AUTOSCHEMATIC_RECORD=1 AUTOSCHEMATIC_FIXTURE_OAC_ID=<id> cargo test -p <connector crate> <test name>`.
+//!
+//! Recorded cassettes hold real account IDs and resource names, so check them before committing.
+
+use std::{collections::BTreeMap, future::Future, path::Path};
+
+use anyhow::{Context, anyhow, bail};
+use aws_smithy_http_client::test_util::dvr::{RecordingClient, ReplayingClient};
+use aws_smithy_runtime_api::client::http::SharedHttpClient;
+
+/// Set this to record cassettes against AWS instead of replaying them.
+pub const RECORD_ENV_VAR: &str = "AUTOSCHEMATIC_RECORD";
+
+/// Set this to skip tests whose cassettes haven't been recorded, instead of failing them.
+pub const SKIP_UNRECORDED_ENV_VAR: &str = "AUTOSCHEMATIC_SKIP_UNRECORDED";
+
+/// When recording, a fixture named `oac_id` is read from `AUTOSCHEMATIC_FIXTURE_OAC_ID`.
+pub const FIXTURE_ENV_PREFIX: &str = "AUTOSCHEMATIC_FIXTURE_";
+
+/// Headers that carry credentials, which are left out of recorded cassettes.
+const SECRET_HEADERS: &[&str] = &["authorization", "x-amz-security-token"];
+
+/// Headers that replayed requests must match. Request bodies aren't compared, as they can carry
+/// client tokens and timestamps that change from run to run.
+const CHECKED_HEADERS: &[&str] = &["content-type", "x-amz-target"];
+
+#[derive(Clone)]
+pub struct Cassette {
+    pub http_client: SharedHttpClient,
+    /// Whether the traffic is replayed, in which case clients are given dummy credentials.
+    pub replaying: bool,
+}
+
+tokio::task_local! {
+    static CASSETTE: Cassette;
+}
+
+/// The cassette that the current task is running under, if any.
+pub fn current_cassette() -> Option<Cassette> {
+    CASSETTE.try_with(Clone::clone).ok()
+}
+
+fn env_flag(name: &str) -> bool {
+    std::env::var(name).is_ok_and(|value| !value.is_empty() && value != "0")
+}
+
+pub fn recording() -> bool {
+    env_flag(RECORD_ENV_VAR)
+}
+
+/// Whether the cassette at `path` has yet to be recorded and the test should return early.
+/// A missing cassette is an error, unless `AUTOSCHEMATIC_SKIP_UNRECORDED` is set.
+pub fn unrecorded(path: impl AsRef<Path>) -> anyhow::Result<bool> {
+    let path = path.as_ref();
+    if recording() || path.is_file() {
+        return Ok(false);
+    }
+
+    if !env_flag(SKIP_UNRECORDED_ENV_VAR) {
+        bail!(
+            "No cassette at {}. Record it with {}=1, or set {}=1 to skip this test.",
+            path.display(),
+            RECORD_ENV_VAR,
+            SKIP_UNRECORDED_ENV_VAR
+        );
+    }
+
+    eprintln!("Skipping: no cassette at {}.", path.display());
+    Ok(true)
+}
+
+/// The value of a fixture that the test at `cassette` runs against, such as the ID of a resource
+/// that already exists. When recording, it's read from the environment and saved to
+/// `<cassette>.fixtures.json`, which replays read it back from.
+pub fn fixture(cassette: impl AsRef<Path>, name: &str) -> anyhow::Result<String> {
+    let path = cassette.as_ref().with_extension("fixtures.json");
+
+    let mut fixtures: BTreeMap<String, String> = match std::fs::read_to_string(&path) {
+        Ok(s) => serde_json::from_str(&s).with_context(|| format!("Failed to parse fixtures {}", path.display()))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read fixtures {}", path.display())),
+    };
+
+    if !recording() {
+        return fixtures
+            .remove(name)
+            .with_context(|| format!("Fixture {} isn't in {}", name, path.display()));
+    }
+
+    let env_var = format!("{}{}", FIXTURE_ENV_PREFIX, name.to_uppercase());
+    let value = std::env::var(&env_var).with_context(|| format!("Set {} to record this test", env_var))?;
+
+    fixtures.insert(name.to_string(), value.clone());
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(&fixtures)?)
+        .with_context(|| format!("Failed to write fixtures {}", path.display()))?;
+
+    Ok(value)
+}
+
+/// Runs `f` with its AWS traffic replayed from, or recorded to, the cassette at `path`.
+///
+/// When replaying, fails if `f` didn't make the cassette's requests in order.
+pub async fn with_cassette<F: Future>(path: impl AsRef<Path>, f: F) -> anyhow::Result<F::Output> {
+    let path = path.as_ref();
+
+    if recording() {
+        let recorder = RecordingClient::new(aws_smithy_http_client::Builder::new().build_https());
+        let cassette = Cassette {
+            http_client: SharedHttpClient::new(recorder.clone()),
+            replaying: false,
+        };
+
+        let output = CASSETTE.scope(cassette, f).await;
+
+        let mut traffic = serde_json::to_value(recorder.network_traffic())?;
+        scrub_headers(&mut traffic);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&traffic)?)
+            .with_context(|| format!("Failed to write cassette {}", path.display()))?;
+
+        Ok(output)
+    } else {
+        let replayer =
+            ReplayingClient::from_file(path).map_err(|e| anyhow!("Failed to load cassette {}: {}", path.display(), e))?;
+        let cassette = Cassette {
+            http_client: SharedHttpClient::new(replayer.clone()),
+            replaying: true,
+        };
+
+        let output = CASSETTE.scope(cassette, f).await;
+
+        replayer
+            .validate(CHECKED_HEADERS, |_, _| Ok(()))
+            .await
+            .map_err(|e| anyhow!("Requests didn't match cassette {}: {}", path.display(), e))?;
+
+        Ok(output)
+    }
+}
+
+/// Removes credential headers from every request in a recorded cassette.
+fn scrub_headers(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                if key == "headers"
+                    && let serde_json::Value::Object(headers) = value
+                {
+                    headers.retain(|name, _| !SECRET_HEADERS.contains(&name.to_lowercase().as_str()));
+                } else {
+                    scrub_headers(value);
+                }
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                scrub_headers(value);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::scrub_headers;
+
+    #[test]
+    fn credentials_are_scrubbed() {
+        let mut traffic = serde_json::json!({
+            "events": [{
+                "connection_id": 0,
+                "action": {"Request": {"request": {
+                    "uri": "https://ec2.us-east-1.amazonaws.com/",
+                    "headers": {
+                        "Authorization": ["AWS4-HMAC-SHA256 Credential=AKIA..."],
+                        "x-amz-security-token": ["token"],
+                        "content-type": ["application/x-www-form-urlencoded"]
+                    },
+                    "method": "POST"
+                }}}
+            }]
+        });
+
+        scrub_headers(&mut traffic);
+
+        let headers = &traffic["events"][0]["action"]["Request"]["request"]["headers"];
+        assert_eq!(
+            headers,
+            &serde_json::json!({"content-type": ["application/x-www-form-urlencoded"]})
+        );
+    }
+}
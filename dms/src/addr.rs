@@ -0,0 +1,76 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum DmsResourceAddress {
+    ReplicationInstance { region: String, id: String },
+    Endpoint { region: String, id: String },
+    ReplicationTask { region: String, id: String },
+}
+
+impl ResourceAddress for DmsResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            DmsResourceAddress::ReplicationInstance { region, id } => {
+                PathBuf::from(format!("aws/dms/{region}/replication_instances/{id}.ron"))
+            }
+            DmsResourceAddress::Endpoint { region, id } => PathBuf::from(format!("aws/dms/{region}/endpoints/{id}.ron")),
+            DmsResourceAddress::ReplicationTask { region, id } => {
+                PathBuf::from(format!("aws/dms/{region}/replication_tasks/{id}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "dms", region, "replication_instances", id] if id.ends_with(".ron") => {
+                let id = id.strip_suffix(".ron").unwrap().to_string();
+                Ok(DmsResourceAddress::ReplicationInstance {
+                    region: region.to_string(),
+                    id,
+                })
+            }
+            ["aws", "dms", region, "endpoints", id] if id.ends_with(".ron") => {
+                let id = id.strip_suffix(".ron").unwrap().to_string();
+                Ok(DmsResourceAddress::Endpoint {
+                    region: region.to_string(),
+                    id,
+                })
+            }
+            ["aws", "dms", region, "replication_tasks", id] if id.ends_with(".ron") => {
+                let id = id.strip_suffix(".ron").unwrap().to_string();
+                Ok(DmsResourceAddress::ReplicationTask {
+                    region: region.to_string(),
+                    id,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for DmsResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/dms/<region>/replication_instances/<id>.ron",
+                description: "A DMS replication instance, which runs replication tasks",
+                example:     "aws/dms/us-east-1/replication_instances/migration.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/dms/<region>/endpoints/<id>.ron",
+                description: "A source or target endpoint. Its password is read from Secrets Manager",
+                example:     "aws/dms/us-east-1/endpoints/orders-postgres.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/dms/<region>/replication_tasks/<id>.ron",
+                description: "A replication task. Its table mappings live next to it in <id>.json",
+                example:     "aws/dms/us-east-1/replication_tasks/orders-to-s3.ron",
+            },
+        ]
+    }
+}
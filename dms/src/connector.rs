@@ -0,0 +1,241 @@
+pub use crate::addr::DmsResourceAddress;
+pub use crate::op::DmsConnectorOp;
+pub use crate::resource::DmsResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::DmsConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{Endpoint, ReplicationInstance, ReplicationTask};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct DmsConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_databasemigration::Client>>>,
+    secrets_client_cache: Mutex<HashMap<String, Arc<aws_sdk_secretsmanager::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<DmsConnectorConfig>,
+    prefix: PathBuf,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl DmsConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_databasemigration::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_databasemigration, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+    /// Endpoint passwords are read from Secrets Manager in the endpoint's region.
+    pub async fn get_or_init_secrets_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_secretsmanager::Client>> {
+        let mut cache = self.secrets_client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_secretsmanager, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get Secrets Manager client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for DmsConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = DmsResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(DmsConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let dms_config: DmsConnectorConfig = DmsConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = dms_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.secrets_client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(dms_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = dms_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        res.push(skeleton!(
+            DmsResourceAddress::ReplicationInstance {
+                region: String::from("[region]"),
+                id:     String::from("[replication_instance_id]"),
+            },
+            DmsResource::ReplicationInstance(ReplicationInstance {
+                replication_instance_class: String::from("dms.t3.medium"),
+                allocated_storage: Some(50),
+                engine_version: None,
+                multi_az: false,
+                publicly_accessible: false,
+                replication_subnet_group_identifier: Some(String::from("[replication_subnet_group]")),
+                vpc_security_group_ids: vec![String::from("[security_group_id]")],
+                kms_key_id: None,
+                preferred_maintenance_window: None,
+                auto_minor_version_upgrade: true,
+                tags: Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            DmsResourceAddress::Endpoint {
+                region: String::from("[region]"),
+                id:     String::from("[endpoint_id]"),
+            },
+            DmsResource::Endpoint(Endpoint {
+                endpoint_type: String::from("source"),
+                engine_name: String::from("postgres"),
+                server_name: Some(String::from("[hostname]")),
+                port: Some(5432),
+                database_name: Some(String::from("[database_name]")),
+                username: Some(String::from("[username]")),
+                password_secret_id: Some(String::from("[secret_id]")),
+                ssl_mode: Some(String::from("require")),
+                certificate_arn: None,
+                extra_connection_attributes: None,
+                kms_key_id: None,
+                s3_settings: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        // The task's table mappings go in [replication_task_id].json next to this file
+        res.push(skeleton!(
+            DmsResourceAddress::ReplicationTask {
+                region: String::from("[region]"),
+                id:     String::from("[replication_task_id]"),
+            },
+            DmsResource::ReplicationTask(ReplicationTask {
+                source_endpoint: String::from("[source_endpoint_id]"),
+                target_endpoint: String::from("[target_endpoint_id]"),
+                replication_instance: String::from("[replication_instance_id]"),
+                migration_type: String::from("full-load-and-cdc"),
+                table_mappings: None,
+                running: false,
+                tags: Tags::default(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = DmsResourceAddress::from_path(addr)?;
+
+        match addr {
+            DmsResourceAddress::ReplicationInstance { .. } => ron_check_eq::<ReplicationInstance>(a, b),
+            DmsResourceAddress::Endpoint { .. } => ron_check_eq::<Endpoint>(a, b),
+            DmsResourceAddress::ReplicationTask { .. } => ron_check_eq::<ReplicationTask>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = DmsResourceAddress::from_path(addr)?;
+
+        match addr {
+            DmsResourceAddress::ReplicationInstance { .. } => ron_check_syntax::<ReplicationInstance>(a),
+            DmsResourceAddress::Endpoint { .. } => ron_check_syntax::<Endpoint>(a),
+            DmsResourceAddress::ReplicationTask { .. } => ron_check_syntax::<ReplicationTask>(a),
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use std::{collections::HashMap, path::Path};
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_databasemigration::Client;
+
+use crate::{
+    addr::DmsResourceAddress,
+    op_impl::{find_endpoint, find_replication_instance, find_replication_task},
+    resource::{DmsResource, Endpoint, ReplicationInstance, ReplicationTask, S3Settings},
+    tags::Tags,
+    util::{UNKNOWN_SECRET_ID, resolve_table_mappings},
+};
+
+use super::DmsConnector;
+
+async fn list_tags(client: &Client, arn: &str) -> anyhow::Result<Tags> {
+    let resp = client.list_tags_for_resource().resource_arn(arn).send().await?;
+    Ok(Tags::from(resp.tag_list))
+}
+
+/// Replication tasks refer to their endpoints and instance by ARN, but task files refer to them by ID.
+async fn endpoint_id(client: &Client, arn: &str) -> anyhow::Result<String> {
+    Ok(find_endpoint(client, "endpoint-arn", arn)
+        .await?
+        .and_then(|endpoint| endpoint.endpoint_identifier)
+        .unwrap_or_else(|| arn.to_string()))
+}
+
+async fn replication_instance_id(client: &Client, arn: &str) -> anyhow::Result<String> {
+    Ok(find_replication_instance(client, "replication-instance-arn", arn)
+        .await?
+        .and_then(|instance| instance.replication_instance_identifier)
+        .unwrap_or_else(|| arn.to_string()))
+}
+
+impl DmsConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = DmsResourceAddress::from_path(addr)?;
+
+        match &addr {
+            DmsResourceAddress::ReplicationInstance { region, id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(instance) = find_replication_instance(&client, "replication-instance-id", id).await? else {
+                    return Ok(None);
+                };
+                if instance.replication_instance_status.as_deref() == Some("deleting") {
+                    return Ok(None);
+                }
+
+                let arn = instance.replication_instance_arn.unwrap_or_default();
+
+                let mut vpc_security_group_ids: Vec<String> = instance
+                    .vpc_security_groups
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|sg| sg.vpc_security_group_id)
+                    .collect();
+                vpc_security_group_ids.sort();
+
+                let resource = ReplicationInstance {
+                    replication_instance_class: instance.replication_instance_class.unwrap_or_default(),
+                    allocated_storage: Some(instance.allocated_storage),
+                    engine_version: instance.engine_version,
+                    multi_az: instance.multi_az,
+                    publicly_accessible: instance.publicly_accessible,
+                    replication_subnet_group_identifier: instance
+                        .replication_subnet_group
+                        .and_then(|group| group.replication_subnet_group_identifier),
+                    vpc_security_group_ids,
+                    kms_key_id: instance.kms_key_id,
+                    preferred_maintenance_window: instance.preferred_maintenance_window,
+                    auto_minor_version_upgrade: instance.auto_minor_version_upgrade,
+                    tags: list_tags(&client, &arn).await?,
+                };
+
+                get_resource_response!(
+                    DmsResource::ReplicationInstance(resource),
+                    [(String::from("replication_instance_arn"), arn)]
+                )
+            }
+            DmsResourceAddress::Endpoint { region, id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(endpoint) = find_endpoint(&client, "endpoint-id", id).await? else {
+                    return Ok(None);
+                };
+                if endpoint.status.as_deref() == Some("deleting") {
+                    return Ok(None);
+                }
+
+                let arn = endpoint.endpoint_arn.unwrap_or_default();
+
+                // The password can't be read back
+                let password_secret_id = match addr.get_output(&self.prefix, "password_secret_id")? {
+                    Some(secret_id) => Some(secret_id),
+                    None if endpoint.username.is_some() => Some(String::from(UNKNOWN_SECRET_ID)),
+                    None => None,
+                };
+
+                let resource = Endpoint {
+                    // DMS reports SOURCE or TARGET
+                    endpoint_type: endpoint
+                        .endpoint_type
+                        .map(|t| t.as_str().to_lowercase())
+                        .unwrap_or_default(),
+                    engine_name: endpoint.engine_name.unwrap_or_default(),
+                    server_name: endpoint.server_name,
+                    port: endpoint.port,
+                    database_name: endpoint.database_name,
+                    username: endpoint.username,
+                    password_secret_id: password_secret_id.clone(),
+                    ssl_mode: endpoint.ssl_mode.map(|m| m.as_str().to_string()),
+                    certificate_arn: endpoint.certificate_arn,
+                    extra_connection_attributes: endpoint.extra_connection_attributes.filter(|a| !a.is_empty()),
+                    kms_key_id: endpoint.kms_key_id,
+                    s3_settings: endpoint.s3_settings.map(|settings| S3Settings {
+                        bucket_name: settings.bucket_name.unwrap_or_default(),
+                        bucket_folder: settings.bucket_folder.filter(|f| !f.is_empty()),
+                        service_access_role_arn: settings.service_access_role_arn.unwrap_or_default(),
+                        data_format: settings.data_format.map(|f| f.as_str().to_string()),
+                        compression_type: settings.compression_type.map(|c| c.as_str().to_string()),
+                    }),
+                    tags: list_tags(&client, &arn).await?,
+                };
+
+                let mut outputs = HashMap::from([(String::from("endpoint_arn"), arn)]);
+                if let Some(password_secret_id) = password_secret_id {
+                    outputs.insert(String::from("password_secret_id"), password_secret_id);
+                }
+
+                Ok(Some(GetResourceResponse {
+                    resource_definition: DmsResource::Endpoint(resource).to_bytes()?,
+                    virt_addr: None,
+                    outputs: Some(outputs),
+                }))
+            }
+            DmsResourceAddress::ReplicationTask { region, id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(task) = find_replication_task(&client, id).await? else {
+                    return Ok(None);
+                };
+                if task.status.as_deref() == Some("deleting") {
+                    return Ok(None);
+                }
+
+                let arn = task.replication_task_arn.unwrap_or_default();
+
+                let resource = ReplicationTask {
+                    source_endpoint: endpoint_id(&client, task.source_endpoint_arn.as_deref().unwrap_or_default()).await?,
+                    target_endpoint: endpoint_id(&client, task.target_endpoint_arn.as_deref().unwrap_or_default()).await?,
+                    replication_instance: replication_instance_id(
+                        &client,
+                        task.replication_instance_arn.as_deref().unwrap_or_default(),
+                    )
+                    .await?,
+                    migration_type: task.migration_type.map(|t| t.as_str().to_string()).unwrap_or_default(),
+                    table_mappings: resolve_table_mappings(&self.prefix, region, id, task.table_mappings.unwrap_or_default())?,
+                    running: task.status.as_deref() == Some("running"),
+                    tags: list_tags(&client, &arn).await?,
+                };
+
+                get_resource_response!(
+                    DmsResource::ReplicationTask(resource),
+                    [(String::from("replication_task_arn"), arn)]
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::addr::DmsResourceAddress;
+
+use super::DmsConnector;
+
+impl DmsConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut marker = None;
+            loop {
+                let resp = client.describe_replication_instances().set_marker(marker).send().await?;
+
+                for instance in resp.replication_instances() {
+                    let Some(id) = &instance.replication_instance_identifier else {
+                        continue;
+                    };
+
+                    results.push(
+                        DmsResourceAddress::ReplicationInstance {
+                            region: region.clone(),
+                            id:     id.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                marker = resp.marker;
+                if marker.is_none() {
+                    break;
+                }
+            }
+
+            let mut marker = None;
+            loop {
+                let resp = client.describe_endpoints().set_marker(marker).send().await?;
+
+                for endpoint in resp.endpoints() {
+                    let Some(id) = &endpoint.endpoint_identifier else {
+                        continue;
+                    };
+
+                    results.push(
+                        DmsResourceAddress::Endpoint {
+                            region: region.clone(),
+                            id:     id.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                marker = resp.marker;
+                if marker.is_none() {
+                    break;
+                }
+            }
+
+            let mut marker = None;
+            loop {
+                let resp = client
+                    .describe_replication_tasks()
+                    .without_settings(true)
+                    .set_marker(marker)
+                    .send()
+                    .await?;
+
+                for task in resp.replication_tasks() {
+                    let Some(id) = &task.replication_task_identifier else {
+                        continue;
+                    };
+
+                    results.push(
+                        DmsResourceAddress::ReplicationTask {
+                            region: region.clone(),
+                            id:     id.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                marker = resp.marker;
+                if marker.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
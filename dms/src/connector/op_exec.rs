@@ -0,0 +1,75 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{ConnectorOp, OpExecResponse, ResourceAddress},
+    error_util::invalid_op,
+};
+
+use crate::{addr::DmsResourceAddress, op::DmsConnectorOp, op_impl};
+
+use super::DmsConnector;
+
+impl DmsConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = DmsResourceAddress::from_path(addr)?;
+        let op = DmsConnectorOp::from_str(op)?;
+
+        match &addr {
+            DmsResourceAddress::ReplicationInstance { region, id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    DmsConnectorOp::CreateReplicationInstance(instance) => {
+                        op_impl::create_replication_instance(&client, id, &instance).await
+                    }
+                    DmsConnectorOp::ModifyReplicationInstance(instance) => {
+                        op_impl::modify_replication_instance(&client, id, &instance).await
+                    }
+                    DmsConnectorOp::UpdateReplicationInstanceTags(old_tags, new_tags) => {
+                        op_impl::update_replication_instance_tags(&client, id, &old_tags, &new_tags).await
+                    }
+                    DmsConnectorOp::DeleteReplicationInstance => op_impl::delete_replication_instance(&client, id).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            DmsResourceAddress::Endpoint { region, id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    DmsConnectorOp::CreateEndpoint(endpoint) => {
+                        let sm_client = self.get_or_init_secrets_client(region).await?;
+                        op_impl::create_endpoint(&client, &sm_client, id, &endpoint).await
+                    }
+                    DmsConnectorOp::ModifyEndpoint(endpoint) => {
+                        let sm_client = self.get_or_init_secrets_client(region).await?;
+                        op_impl::modify_endpoint(&client, &sm_client, id, &endpoint).await
+                    }
+                    DmsConnectorOp::UpdateEndpointTags(old_tags, new_tags) => {
+                        op_impl::update_endpoint_tags(&client, id, &old_tags, &new_tags).await
+                    }
+                    DmsConnectorOp::DeleteEndpoint => op_impl::delete_endpoint(&client, id).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            DmsResourceAddress::ReplicationTask { region, id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    DmsConnectorOp::CreateReplicationTask(task, table_mappings) => {
+                        op_impl::create_replication_task(&client, id, &task, &table_mappings).await
+                    }
+                    DmsConnectorOp::ModifyReplicationTask(task, table_mappings) => {
+                        op_impl::modify_replication_task(&client, id, &task, &table_mappings).await
+                    }
+                    DmsConnectorOp::UpdateReplicationTaskTags(old_tags, new_tags) => {
+                        op_impl::update_replication_task_tags(&client, id, &old_tags, &new_tags).await
+                    }
+                    DmsConnectorOp::StartReplicationTask => op_impl::start_replication_task(&client, id).await,
+                    DmsConnectorOp::StopReplicationTask => op_impl::stop_replication_task(&client, id).await,
+                    DmsConnectorOp::DeleteReplicationTask => op_impl::delete_replication_task(&client, id).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::{
+    resource::{Endpoint, ReplicationInstance, ReplicationTask},
+    util::{UNKNOWN_SECRET_ID, table_mappings},
+};
+
+use super::{DmsConnector, DmsConnectorOp, DmsResourceAddress};
+
+const ENDPOINT_TYPES: &[&str] = &["source", "target"];
+const MIGRATION_TYPES: &[&str] = &["full-load", "cdc", "full-load-and-cdc"];
+
+/// Secret IDs reported as unknown by `get` have to be filled in before they can be used.
+fn check_secret_id(secret_id: &str, what: &str) -> anyhow::Result<()> {
+    if secret_id == UNKNOWN_SECRET_ID {
+        bail!(
+            "{} has a secret ID of {}: set it to the ID of the Secrets Manager secret that holds the value",
+            what,
+            UNKNOWN_SECRET_ID
+        );
+    }
+    Ok(())
+}
+
+fn check_endpoint(id: &str, endpoint: &Endpoint) -> anyhow::Result<()> {
+    if !ENDPOINT_TYPES.contains(&endpoint.endpoint_type.as_str()) {
+        bail!(
+            "DMS endpoint {} has endpoint type {}: expected one of {}",
+            id,
+            endpoint.endpoint_type,
+            ENDPOINT_TYPES.join(", ")
+        );
+    }
+    if let Some(secret_id) = &endpoint.password_secret_id {
+        check_secret_id(secret_id, &format!("DMS endpoint {}'s password", id))?;
+    }
+    if endpoint.s3_settings.is_some() && endpoint.engine_name != "s3" {
+        bail!("DMS endpoint {} sets s3_settings, but its engine is {}", id, endpoint.engine_name);
+    }
+    Ok(())
+}
+
+fn check_replication_task(id: &str, task: &ReplicationTask) -> anyhow::Result<()> {
+    if !MIGRATION_TYPES.contains(&task.migration_type.as_str()) {
+        bail!(
+            "DMS replication task {} has migration type {}: expected one of {}",
+            id,
+            task.migration_type,
+            MIGRATION_TYPES.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// DMS fills in these fields when they're left out, so leaving them out doesn't change them.
+fn normalize_replication_instance(old: &ReplicationInstance, new: &mut ReplicationInstance) {
+    if new.allocated_storage.is_none() {
+        new.allocated_storage = old.allocated_storage;
+    }
+    if new.engine_version.is_none() {
+        new.engine_version = old.engine_version.clone();
+    }
+    if new.replication_subnet_group_identifier.is_none() {
+        new.replication_subnet_group_identifier = old.replication_subnet_group_identifier.clone();
+    }
+    if new.vpc_security_group_ids.is_empty() {
+        new.vpc_security_group_ids = old.vpc_security_group_ids.clone();
+    }
+    if new.kms_key_id.is_none() {
+        new.kms_key_id = old.kms_key_id.clone();
+    }
+    if new.preferred_maintenance_window.is_none() {
+        new.preferred_maintenance_window = old.preferred_maintenance_window.clone();
+    }
+}
+
+/// The fields that DMS can't change once a replication instance exists.
+fn replication_instance_fixed_fields(old: &ReplicationInstance, new: &ReplicationInstance) -> Vec<String> {
+    let mut fields = Vec::new();
+    if old.publicly_accessible != new.publicly_accessible {
+        fields.push(String::from("publicly_accessible"));
+    }
+    if old.replication_subnet_group_identifier != new.replication_subnet_group_identifier {
+        fields.push(String::from("replication_subnet_group_identifier"));
+    }
+    if old.kms_key_id != new.kms_key_id {
+        fields.push(String::from("kms_key_id"));
+    }
+    fields
+}
+
+fn normalize_endpoint(old: &Endpoint, new: &mut Endpoint) {
+    if new.port.is_none() {
+        new.port = old.port;
+    }
+    if new.ssl_mode.is_none() {
+        new.ssl_mode = old.ssl_mode.clone();
+    }
+    if new.kms_key_id.is_none() {
+        new.kms_key_id = old.kms_key_id.clone();
+    }
+}
+
+fn normalize_replication_task(old: &ReplicationTask, new: &mut ReplicationTask) {
+    // DMS doesn't keep the formatting of inline table mappings either
+    if let (Some(old_mappings), Some(new_mappings)) = (&old.table_mappings, &new.table_mappings) {
+        let old_value = serde_json::from_str::<serde_json::Value>(old_mappings).ok();
+        let new_value = serde_json::from_str::<serde_json::Value>(new_mappings).ok();
+        if old_value.is_some() && old_value == new_value {
+            new.table_mappings = old.table_mappings.clone();
+        }
+    }
+}
+
+/// The fields that DMS can't change once a replication task exists.
+fn replication_task_fixed_fields(old: &ReplicationTask, new: &ReplicationTask) -> Vec<String> {
+    let mut fields = Vec::new();
+    if old.source_endpoint != new.source_endpoint {
+        fields.push(String::from("source_endpoint"));
+    }
+    if old.target_endpoint != new.target_endpoint {
+        fields.push(String::from("target_endpoint"));
+    }
+    if old.replication_instance != new.replication_instance {
+        fields.push(String::from("replication_instance"));
+    }
+    fields
+}
+
+impl DmsConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = DmsResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            DmsResourceAddress::ReplicationInstance { region, id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_instance)) => {
+                    let new_instance: ReplicationInstance = RON.from_str(&new_instance)?;
+                    Ok(vec![connector_op!(
+                        DmsConnectorOp::CreateReplicationInstance(new_instance),
+                        format!("Create new DMS replication instance {} in region {}", id, region)
+                    )])
+                }
+                (Some(_old_instance), None) => Ok(vec![connector_op!(
+                    DmsConnectorOp::DeleteReplicationInstance,
+                    format!(
+                        "DELETE DMS replication instance {} in region {}. Replication tasks on it must be deleted first.",
+                        id, region
+                    )
+                )]),
+                (Some(old_instance), Some(new_instance)) => {
+                    let old_instance: ReplicationInstance = RON.from_str(&old_instance)?;
+                    let mut new_instance: ReplicationInstance = RON.from_str(&new_instance)?;
+                    normalize_replication_instance(&old_instance, &mut new_instance);
+
+                    let fixed_fields = replication_instance_fixed_fields(&old_instance, &new_instance);
+                    if !fixed_fields.is_empty() {
+                        bail!(
+                            "DMS replication instance {} can't change {} after creation. Create a new instance under another ID instead.",
+                            id,
+                            fixed_fields.join(", ")
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_instance.tags != new_instance.tags {
+                        let diff = diff_ron_values(&old_instance.tags, &new_instance.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            DmsConnectorOp::UpdateReplicationInstanceTags(old_instance.tags.clone(), new_instance.tags.clone()),
+                            format!("Modify tags for DMS replication instance `{}`\n{}", id, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_instance.clone();
+                    old_settings.tags = new_instance.tags.clone();
+                    if old_settings != new_instance {
+                        let diff = diff_ron_values(&old_settings, &new_instance).unwrap_or_default();
+                        ops.push(connector_op!(
+                            DmsConnectorOp::ModifyReplicationInstance(new_instance),
+                            format!(
+                                "Modify DMS replication instance `{}`. Class, storage and engine version changes interrupt running tasks.\n{}",
+                                id, diff
+                            )
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            DmsResourceAddress::Endpoint { region, id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_endpoint)) => {
+                    let new_endpoint: Endpoint = RON.from_str(&new_endpoint)?;
+                    check_endpoint(id, &new_endpoint)?;
+                    Ok(vec![connector_op!(
+                        DmsConnectorOp::CreateEndpoint(new_endpoint),
+                        format!("Create new DMS endpoint {} in region {}", id, region)
+                    )])
+                }
+                (Some(_old_endpoint), None) => Ok(vec![connector_op!(
+                    DmsConnectorOp::DeleteEndpoint,
+                    format!(
+                        "DELETE DMS endpoint {} in region {}. Replication tasks using it must be deleted first.",
+                        id, region
+                    )
+                )]),
+                (Some(old_endpoint), Some(new_endpoint)) => {
+                    let old_endpoint: Endpoint = RON.from_str(&old_endpoint)?;
+                    let mut new_endpoint: Endpoint = RON.from_str(&new_endpoint)?;
+                    check_endpoint(id, &new_endpoint)?;
+                    normalize_endpoint(&old_endpoint, &mut new_endpoint);
+
+                    if old_endpoint.kms_key_id != new_endpoint.kms_key_id {
+                        bail!(
+                            "DMS endpoint {} can't change kms_key_id after creation. Create a new endpoint under another ID instead.",
+                            id
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_endpoint.tags != new_endpoint.tags {
+                        let diff = diff_ron_values(&old_endpoint.tags, &new_endpoint.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            DmsConnectorOp::UpdateEndpointTags(old_endpoint.tags.clone(), new_endpoint.tags.clone()),
+                            format!("Modify tags for DMS endpoint `{}`\n{}", id, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_endpoint.clone();
+                    old_settings.tags = new_endpoint.tags.clone();
+                    if old_settings != new_endpoint {
+                        let diff = diff_ron_values(&old_settings, &new_endpoint).unwrap_or_default();
+                        ops.push(connector_op!(
+                            DmsConnectorOp::ModifyEndpoint(new_endpoint),
+                            format!("Modify DMS endpoint `{}`\n{}", id, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            DmsResourceAddress::ReplicationTask { region, id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_task)) => {
+                    let new_task: ReplicationTask = RON.from_str(&new_task)?;
+                    check_replication_task(id, &new_task)?;
+                    let table_mappings = table_mappings(&self.prefix, region, id, &new_task)?;
+
+                    let running = new_task.running;
+                    let mut ops = vec![connector_op!(
+                        DmsConnectorOp::CreateReplicationTask(new_task, table_mappings),
+                        format!("Create new DMS replication task {} in region {}", id, region)
+                    )];
+                    if running {
+                        ops.push(connector_op!(
+                            DmsConnectorOp::StartReplicationTask,
+                            format!("Start DMS replication task `{}`", id)
+                        ));
+                    }
+                    Ok(ops)
+                }
+                (Some(old_task), None) => {
+                    let old_task: ReplicationTask = RON.from_str(&old_task)?;
+
+                    let mut ops = Vec::new();
+                    if old_task.running {
+                        ops.push(connector_op!(
+                            DmsConnectorOp::StopReplicationTask,
+                            format!("Stop DMS replication task `{}`", id)
+                        ));
+                    }
+                    ops.push(connector_op!(
+                        DmsConnectorOp::DeleteReplicationTask,
+                        format!("DELETE DMS replication task {} in region {}", id, region)
+                    ));
+                    Ok(ops)
+                }
+                (Some(old_task), Some(new_task)) => {
+                    let old_task: ReplicationTask = RON.from_str(&old_task)?;
+                    let mut new_task: ReplicationTask = RON.from_str(&new_task)?;
+                    check_replication_task(id, &new_task)?;
+                    normalize_replication_task(&old_task, &mut new_task);
+
+                    let fixed_fields = replication_task_fixed_fields(&old_task, &new_task);
+                    if !fixed_fields.is_empty() {
+                        bail!(
+                            "DMS replication task {} can't change {} after creation. Create a new task under another ID instead.",
+                            id,
+                            fixed_fields.join(", ")
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_task.tags != new_task.tags {
+                        let diff = diff_ron_values(&old_task.tags, &new_task.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            DmsConnectorOp::UpdateReplicationTaskTags(old_task.tags.clone(), new_task.tags.clone()),
+                            format!("Modify tags for DMS replication task `{}`\n{}", id, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_task.clone();
+                    old_settings.tags = new_task.tags.clone();
+                    old_settings.running = new_task.running;
+                    let modified = old_settings != new_task;
+
+                    // Tasks can only be modified while stopped
+                    if old_task.running && (modified || !new_task.running) {
+                        ops.push(connector_op!(
+                            DmsConnectorOp::StopReplicationTask,
+                            format!("Stop DMS replication task `{}`", id)
+                        ));
+                    }
+
+                    let running = new_task.running;
+                    if modified {
+                        let table_mappings = table_mappings(&self.prefix, region, id, &new_task)?;
+                        let diff = diff_ron_values(&old_settings, &new_task).unwrap_or_default();
+                        ops.push(connector_op!(
+                            DmsConnectorOp::ModifyReplicationTask(new_task, table_mappings),
+                            format!("Modify DMS replication task `{}`\n{}", id, diff)
+                        ));
+                    }
+
+                    if running && (modified || !old_task.running) {
+                        ops.push(connector_op!(
+                            DmsConnectorOp::StartReplicationTask,
+                            format!("Start DMS replication task `{}`", id)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{Endpoint, ReplicationInstance, ReplicationTask},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum DmsConnectorOp {
+    /// Creates the replication instance, and waits for it to be available.
+    CreateReplicationInstance(ReplicationInstance),
+    /// Applies the instance's class, storage, engine version, Multi-AZ, security groups, maintenance
+    /// window and minor version upgrades immediately, and waits for it to be available again.
+    ModifyReplicationInstance(ReplicationInstance),
+    UpdateReplicationInstanceTags(Tags, Tags),
+    DeleteReplicationInstance,
+
+    CreateEndpoint(Endpoint),
+    ModifyEndpoint(Endpoint),
+    UpdateEndpointTags(Tags, Tags),
+    DeleteEndpoint,
+
+    /// Creates the task with the given table mappings, and waits for it to be ready.
+    CreateReplicationTask(ReplicationTask, String),
+    /// Applies the task's migration type and the given table mappings. The task must be stopped.
+    ModifyReplicationTask(ReplicationTask, String),
+    UpdateReplicationTaskTags(Tags, Tags),
+    /// Starts a task that has never run, or resumes a stopped one, and waits for it to be running.
+    StartReplicationTask,
+    StopReplicationTask,
+    DeleteReplicationTask,
+}
+
+impl ConnectorOp for DmsConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
@@ -0,0 +1,513 @@
+use std::{collections::HashMap, future::Future, time::Duration};
+
+use anyhow::{Context, bail};
+use autoschematic_core::{connector::OpExecResponse, op_exec_output};
+use aws_sdk_databasemigration::types::{
+    CompressionTypeValue, DataFormatValue, DmsSslModeValue, Filter, MigrationTypeValue, ReplicationEndpointTypeValue,
+    StartReplicationTaskTypeValue,
+};
+
+use crate::{
+    resource::{Endpoint, ReplicationInstance, ReplicationTask, S3Settings},
+    tags::{Tags, tag_diff},
+    util::read_secret,
+};
+
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(15);
+/// Multi-AZ replication instances can take over half an hour to create or modify.
+const STATUS_MAX_POLLS: usize = 240;
+
+/// Polls `status` until it returns `target`, or until the resource no longer exists if `target` is None.
+async fn wait_for_status<F, Fut>(description: &str, target: Option<&str>, status: F) -> anyhow::Result<()>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = anyhow::Result<Option<String>>>,
+{
+    for _ in 0..STATUS_MAX_POLLS {
+        let state = status().await?;
+        if state.as_deref() == target {
+            return Ok(());
+        }
+        if matches!(state.as_deref(), Some("failed") | Some("failed-move")) {
+            bail!("{} failed", description);
+        }
+        tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    }
+
+    match target {
+        Some(target) => bail!("Timed out waiting for {} to become {}", description, target),
+        None => bail!("Timed out waiting for {} to be deleted", description),
+    }
+}
+
+fn filter(name: &str, value: &str) -> anyhow::Result<Filter> {
+    Ok(Filter::builder().name(name).values(value).build()?)
+}
+
+/// AddTagsToResource and RemoveTagsFromResource take the resource's ARN.
+async fn update_tags(
+    client: &aws_sdk_databasemigration::Client,
+    arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<()> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .remove_tags_from_resource()
+            .resource_arn(arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    let new_tagset: Option<Vec<aws_sdk_databasemigration::types::Tag>> = new_tagset.into();
+    if let Some(new_tagset) = new_tagset {
+        client
+            .add_tags_to_resource()
+            .resource_arn(arn)
+            .set_tags(Some(new_tagset))
+            .send()
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn find_replication_instance(
+    client: &aws_sdk_databasemigration::Client,
+    filter_name: &str,
+    value: &str,
+) -> anyhow::Result<Option<aws_sdk_databasemigration::types::ReplicationInstance>> {
+    match client
+        .describe_replication_instances()
+        .filters(filter(filter_name, value)?)
+        .send()
+        .await
+    {
+        Ok(resp) => Ok(resp.replication_instances.unwrap_or_default().into_iter().next()),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_fault()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub async fn find_endpoint(
+    client: &aws_sdk_databasemigration::Client,
+    filter_name: &str,
+    value: &str,
+) -> anyhow::Result<Option<aws_sdk_databasemigration::types::Endpoint>> {
+    match client.describe_endpoints().filters(filter(filter_name, value)?).send().await {
+        Ok(resp) => Ok(resp.endpoints.unwrap_or_default().into_iter().next()),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_fault()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub async fn find_replication_task(
+    client: &aws_sdk_databasemigration::Client,
+    id: &str,
+) -> anyhow::Result<Option<aws_sdk_databasemigration::types::ReplicationTask>> {
+    match client
+        .describe_replication_tasks()
+        .filters(filter("replication-task-id", id)?)
+        .without_settings(true)
+        .send()
+        .await
+    {
+        Ok(resp) => Ok(resp.replication_tasks.unwrap_or_default().into_iter().next()),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_fault()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn replication_instance_arn(client: &aws_sdk_databasemigration::Client, id: &str) -> anyhow::Result<String> {
+    find_replication_instance(client, "replication-instance-id", id)
+        .await?
+        .and_then(|instance| instance.replication_instance_arn)
+        .with_context(|| format!("DMS replication instance {} not found", id))
+}
+
+async fn endpoint_arn(client: &aws_sdk_databasemigration::Client, id: &str) -> anyhow::Result<String> {
+    find_endpoint(client, "endpoint-id", id)
+        .await?
+        .and_then(|endpoint| endpoint.endpoint_arn)
+        .with_context(|| format!("DMS endpoint {} not found", id))
+}
+
+async fn replication_task_arn(client: &aws_sdk_databasemigration::Client, id: &str) -> anyhow::Result<String> {
+    find_replication_task(client, id)
+        .await?
+        .and_then(|task| task.replication_task_arn)
+        .with_context(|| format!("DMS replication task {} not found", id))
+}
+
+async fn replication_instance_status(client: &aws_sdk_databasemigration::Client, id: &str) -> anyhow::Result<Option<String>> {
+    Ok(find_replication_instance(client, "replication-instance-id", id)
+        .await?
+        .map(|instance| instance.replication_instance_status.unwrap_or_default()))
+}
+
+async fn wait_for_replication_instance(
+    client: &aws_sdk_databasemigration::Client,
+    id: &str,
+    target: Option<&str>,
+) -> anyhow::Result<()> {
+    wait_for_status(&format!("DMS replication instance {}", id), target, || {
+        replication_instance_status(client, id)
+    })
+    .await
+}
+
+async fn replication_task_status(client: &aws_sdk_databasemigration::Client, id: &str) -> anyhow::Result<Option<String>> {
+    Ok(find_replication_task(client, id)
+        .await?
+        .map(|task| task.status.unwrap_or_default()))
+}
+
+async fn wait_for_replication_task(
+    client: &aws_sdk_databasemigration::Client,
+    id: &str,
+    target: Option<&str>,
+) -> anyhow::Result<()> {
+    wait_for_status(&format!("DMS replication task {}", id), target, || replication_task_status(client, id)).await
+}
+
+pub async fn create_replication_instance(
+    client: &aws_sdk_databasemigration::Client,
+    id: &str,
+    instance: &ReplicationInstance,
+) -> anyhow::Result<OpExecResponse> {
+    let mut request = client
+        .create_replication_instance()
+        .replication_instance_identifier(id)
+        .replication_instance_class(&instance.replication_instance_class)
+        .set_allocated_storage(instance.allocated_storage)
+        .set_engine_version(instance.engine_version.clone())
+        .multi_az(instance.multi_az)
+        .publicly_accessible(instance.publicly_accessible)
+        .set_replication_subnet_group_identifier(instance.replication_subnet_group_identifier.clone())
+        .set_kms_key_id(instance.kms_key_id.clone())
+        .set_preferred_maintenance_window(instance.preferred_maintenance_window.clone())
+        .auto_minor_version_upgrade(instance.auto_minor_version_upgrade)
+        .set_tags(instance.tags.clone().into());
+
+    if !instance.vpc_security_group_ids.is_empty() {
+        request = request.set_vpc_security_group_ids(Some(instance.vpc_security_group_ids.clone()));
+    }
+
+    let resp = request.send().await?;
+
+    wait_for_replication_instance(client, id, Some("available")).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(
+            String::from("replication_instance_arn"),
+            resp.replication_instance.and_then(|instance| instance.replication_instance_arn),
+        )])),
+        friendly_message: Some(format!("Created DMS replication instance {}", id)),
+    })
+}
+
+pub async fn modify_replication_instance(
+    client: &aws_sdk_databasemigration::Client,
+    id: &str,
+    instance: &ReplicationInstance,
+) -> anyhow::Result<OpExecResponse> {
+    let current = find_replication_instance(client, "replication-instance-id", id)
+        .await?
+        .with_context(|| format!("DMS replication instance {} not found", id))?;
+
+    let mut request = client
+        .modify_replication_instance()
+        .replication_instance_arn(current.replication_instance_arn.unwrap_or_default())
+        .apply_immediately(true)
+        .replication_instance_class(&instance.replication_instance_class)
+        .set_allocated_storage(instance.allocated_storage)
+        .multi_az(instance.multi_az)
+        .set_preferred_maintenance_window(instance.preferred_maintenance_window.clone())
+        .auto_minor_version_upgrade(instance.auto_minor_version_upgrade);
+
+    // Sending the current engine version counts as an upgrade request, so it's only sent when it changes
+    if instance.engine_version.is_some() && instance.engine_version != current.engine_version {
+        request = request
+            .set_engine_version(instance.engine_version.clone())
+            .allow_major_version_upgrade(true);
+    }
+    if !instance.vpc_security_group_ids.is_empty() {
+        request = request.set_vpc_security_group_ids(Some(instance.vpc_security_group_ids.clone()));
+    }
+
+    request.send().await?;
+
+    // The instance can still report available for a moment after the change is accepted
+    tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    wait_for_replication_instance(client, id, Some("available")).await?;
+
+    op_exec_output!(format!("Modified DMS replication instance {}", id))
+}
+
+pub async fn update_replication_instance_tags(
+    client: &aws_sdk_databasemigration::Client,
+    id: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = replication_instance_arn(client, id).await?;
+
+    update_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for DMS replication instance {}", id))
+}
+
+pub async fn delete_replication_instance(client: &aws_sdk_databasemigration::Client, id: &str) -> anyhow::Result<OpExecResponse> {
+    client
+        .delete_replication_instance()
+        .replication_instance_arn(replication_instance_arn(client, id).await?)
+        .send()
+        .await?;
+
+    wait_for_replication_instance(client, id, None).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("replication_instance_arn"), None)])),
+        friendly_message: Some(format!("Deleted DMS replication instance {}", id)),
+    })
+}
+
+fn to_s3_settings(settings: &S3Settings) -> aws_sdk_databasemigration::types::S3Settings {
+    aws_sdk_databasemigration::types::S3Settings::builder()
+        .bucket_name(&settings.bucket_name)
+        .set_bucket_folder(settings.bucket_folder.clone())
+        .service_access_role_arn(&settings.service_access_role_arn)
+        .set_data_format(settings.data_format.as_deref().map(DataFormatValue::from))
+        .set_compression_type(settings.compression_type.as_deref().map(CompressionTypeValue::from))
+        .build()
+}
+
+async fn endpoint_password(sm_client: &aws_sdk_secretsmanager::Client, endpoint: &Endpoint) -> anyhow::Result<Option<String>> {
+    match &endpoint.password_secret_id {
+        Some(secret_id) => Ok(Some(read_secret(sm_client, secret_id).await?)),
+        None => Ok(None),
+    }
+}
+
+pub async fn create_endpoint(
+    client: &aws_sdk_databasemigration::Client,
+    sm_client: &aws_sdk_secretsmanager::Client,
+    id: &str,
+    endpoint: &Endpoint,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_endpoint()
+        .endpoint_identifier(id)
+        .endpoint_type(ReplicationEndpointTypeValue::from(endpoint.endpoint_type.as_str()))
+        .engine_name(&endpoint.engine_name)
+        .set_server_name(endpoint.server_name.clone())
+        .set_port(endpoint.port)
+        .set_database_name(endpoint.database_name.clone())
+        .set_username(endpoint.username.clone())
+        .set_password(endpoint_password(sm_client, endpoint).await?)
+        .set_ssl_mode(endpoint.ssl_mode.as_deref().map(DmsSslModeValue::from))
+        .set_certificate_arn(endpoint.certificate_arn.clone())
+        .set_extra_connection_attributes(endpoint.extra_connection_attributes.clone())
+        .set_kms_key_id(endpoint.kms_key_id.clone())
+        .set_s3_settings(endpoint.s3_settings.as_ref().map(to_s3_settings))
+        .set_tags(endpoint.tags.clone().into())
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (
+                String::from("endpoint_arn"),
+                resp.endpoint.and_then(|endpoint| endpoint.endpoint_arn),
+            ),
+            (String::from("password_secret_id"), endpoint.password_secret_id.clone()),
+        ])),
+        friendly_message: Some(format!("Created DMS endpoint {}", id)),
+    })
+}
+
+pub async fn modify_endpoint(
+    client: &aws_sdk_databasemigration::Client,
+    sm_client: &aws_sdk_secretsmanager::Client,
+    id: &str,
+    endpoint: &Endpoint,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .modify_endpoint()
+        .endpoint_arn(endpoint_arn(client, id).await?)
+        .endpoint_type(ReplicationEndpointTypeValue::from(endpoint.endpoint_type.as_str()))
+        .engine_name(&endpoint.engine_name)
+        .set_server_name(endpoint.server_name.clone())
+        .set_port(endpoint.port)
+        .set_database_name(endpoint.database_name.clone())
+        .set_username(endpoint.username.clone())
+        .set_password(endpoint_password(sm_client, endpoint).await?)
+        .set_ssl_mode(endpoint.ssl_mode.as_deref().map(DmsSslModeValue::from))
+        .set_certificate_arn(endpoint.certificate_arn.clone())
+        .set_extra_connection_attributes(endpoint.extra_connection_attributes.clone())
+        .set_s3_settings(endpoint.s3_settings.as_ref().map(to_s3_settings))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(
+            String::from("password_secret_id"),
+            endpoint.password_secret_id.clone(),
+        )])),
+        friendly_message: Some(format!("Modified DMS endpoint {}", id)),
+    })
+}
+
+pub async fn update_endpoint_tags(
+    client: &aws_sdk_databasemigration::Client,
+    id: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = endpoint_arn(client, id).await?;
+
+    update_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for DMS endpoint {}", id))
+}
+
+pub async fn delete_endpoint(client: &aws_sdk_databasemigration::Client, id: &str) -> anyhow::Result<OpExecResponse> {
+    client.delete_endpoint().endpoint_arn(endpoint_arn(client, id).await?).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("endpoint_arn"), None),
+            (String::from("password_secret_id"), None),
+        ])),
+        friendly_message: Some(format!("Deleted DMS endpoint {}", id)),
+    })
+}
+
+pub async fn create_replication_task(
+    client: &aws_sdk_databasemigration::Client,
+    id: &str,
+    task: &ReplicationTask,
+    table_mappings: &str,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_replication_task()
+        .replication_task_identifier(id)
+        .source_endpoint_arn(endpoint_arn(client, &task.source_endpoint).await?)
+        .target_endpoint_arn(endpoint_arn(client, &task.target_endpoint).await?)
+        .replication_instance_arn(replication_instance_arn(client, &task.replication_instance).await?)
+        .migration_type(MigrationTypeValue::from(task.migration_type.as_str()))
+        .table_mappings(table_mappings)
+        .set_tags(task.tags.clone().into())
+        .send()
+        .await?;
+
+    wait_for_replication_task(client, id, Some("ready")).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(
+            String::from("replication_task_arn"),
+            resp.replication_task.and_then(|task| task.replication_task_arn),
+        )])),
+        friendly_message: Some(format!("Created DMS replication task {}", id)),
+    })
+}
+
+pub async fn modify_replication_task(
+    client: &aws_sdk_databasemigration::Client,
+    id: &str,
+    task: &ReplicationTask,
+    table_mappings: &str,
+) -> anyhow::Result<OpExecResponse> {
+    let current = find_replication_task(client, id)
+        .await?
+        .with_context(|| format!("DMS replication task {} not found", id))?;
+    let status = current.status.unwrap_or_default();
+    if status != "ready" && status != "stopped" {
+        bail!("DMS replication task {} is {}: it must be stopped to modify it", id, status);
+    }
+
+    client
+        .modify_replication_task()
+        .replication_task_arn(current.replication_task_arn.unwrap_or_default())
+        .migration_type(MigrationTypeValue::from(task.migration_type.as_str()))
+        .table_mappings(table_mappings)
+        .send()
+        .await?;
+
+    // Modified tasks go back to the status they had before
+    tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    wait_for_replication_task(client, id, Some(&status)).await?;
+
+    op_exec_output!(format!("Modified DMS replication task {}", id))
+}
+
+pub async fn update_replication_task_tags(
+    client: &aws_sdk_databasemigration::Client,
+    id: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = replication_task_arn(client, id).await?;
+
+    update_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for DMS replication task {}", id))
+}
+
+pub async fn start_replication_task(client: &aws_sdk_databasemigration::Client, id: &str) -> anyhow::Result<OpExecResponse> {
+    let current = find_replication_task(client, id)
+        .await?
+        .with_context(|| format!("DMS replication task {} not found", id))?;
+
+    // Tasks that have never run are ready, and tasks that have are stopped
+    let start_type = match current.status.as_deref() {
+        Some("ready") => "start-replication",
+        Some("stopped") => "resume-processing",
+        status => bail!(
+            "DMS replication task {} is {}: it can only be started when ready or stopped",
+            id,
+            status.unwrap_or("in an unknown state")
+        ),
+    };
+
+    client
+        .start_replication_task()
+        .replication_task_arn(current.replication_task_arn.unwrap_or_default())
+        .start_replication_task_type(StartReplicationTaskTypeValue::from(start_type))
+        .send()
+        .await?;
+
+    wait_for_replication_task(client, id, Some("running")).await?;
+
+    op_exec_output!(format!("Started DMS replication task {}", id))
+}
+
+pub async fn stop_replication_task(client: &aws_sdk_databasemigration::Client, id: &str) -> anyhow::Result<OpExecResponse> {
+    client
+        .stop_replication_task()
+        .replication_task_arn(replication_task_arn(client, id).await?)
+        .send()
+        .await?;
+
+    wait_for_replication_task(client, id, Some("stopped")).await?;
+
+    op_exec_output!(format!("Stopped DMS replication task {}", id))
+}
+
+pub async fn delete_replication_task(client: &aws_sdk_databasemigration::Client, id: &str) -> anyhow::Result<OpExecResponse> {
+    client
+        .delete_replication_task()
+        .replication_task_arn(replication_task_arn(client, id).await?)
+        .send()
+        .await?;
+
+    wait_for_replication_task(client, id, None).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("replication_task_arn"), None)])),
+        friendly_message: Some(format!("Deleted DMS replication task {}", id)),
+    })
+}
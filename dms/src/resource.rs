@@ -0,0 +1,136 @@
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::DmsResourceAddress, tags::Tags};
+
+fn default_true() -> bool {
+    true
+}
+
+/// A DMS replication instance.
+///
+/// The subnet group, public accessibility and encryption key are fixed at creation. Other changes
+/// are applied immediately rather than in the next maintenance window.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ReplicationInstance {
+    /// e.g. dms.t3.medium or dms.r5.large.
+    pub replication_instance_class: String,
+    /// Storage in GiB. If None, DMS picks a size for the instance class.
+    pub allocated_storage: Option<i32>,
+    /// If None, the latest engine version.
+    pub engine_version: Option<String>,
+    #[serde(default)]
+    pub multi_az: bool,
+    #[serde(default)]
+    pub publicly_accessible: bool,
+    /// If None, the instance is created in the default VPC.
+    pub replication_subnet_group_identifier: Option<String>,
+    #[serde(default)]
+    pub vpc_security_group_ids: Vec<String>,
+    /// If None, the instance's storage is encrypted with the default DMS key.
+    pub kms_key_id: Option<String>,
+    /// e.g. sun:05:00-sun:05:30. If None, DMS picks one.
+    pub preferred_maintenance_window: Option<String>,
+    #[serde(default = "default_true")]
+    pub auto_minor_version_upgrade: bool,
+    pub tags: Tags,
+}
+
+/// A source or target endpoint for replication tasks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Endpoint {
+    /// source or target.
+    pub endpoint_type: String,
+    /// e.g. postgres, mysql, aurora-postgresql, oracle, sqlserver or s3.
+    pub engine_name: String,
+    pub server_name: Option<String>,
+    pub port: Option<i32>,
+    pub database_name: Option<String>,
+    pub username: Option<String>,
+    /// The ID of a Secrets Manager secret holding the password as a plain string.
+    /// Passwords can't be read back, so this is only known for endpoints created or modified here.
+    pub password_secret_id: Option<String>,
+    /// none, require, verify-ca or verify-full.
+    pub ssl_mode: Option<String>,
+    pub certificate_arn: Option<String>,
+    /// Engine-specific settings, as semicolon-separated key=value pairs.
+    pub extra_connection_attributes: Option<String>,
+    /// Fixed at creation. If None, the endpoint's connection details are encrypted with the default DMS key.
+    pub kms_key_id: Option<String>,
+    /// Settings for s3 endpoints.
+    pub s3_settings: Option<S3Settings>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct S3Settings {
+    pub bucket_name: String,
+    pub bucket_folder: Option<String>,
+    /// A role that DMS can assume to read and write the bucket.
+    pub service_access_role_arn: String,
+    /// csv or parquet. If None, csv.
+    pub data_format: Option<String>,
+    /// gzip or none. If None, none.
+    pub compression_type: Option<String>,
+}
+
+/// A replication task, which migrates tables from a source endpoint to a target endpoint on a
+/// replication instance.
+///
+/// The endpoints and replication instance are fixed at creation. A running task is stopped to apply
+/// changes to its migration type or table mappings, and resumed afterwards.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ReplicationTask {
+    /// The ID of an endpoint in the same region.
+    pub source_endpoint: String,
+    /// The ID of an endpoint in the same region.
+    pub target_endpoint: String,
+    /// The ID of a replication instance in the same region.
+    pub replication_instance: String,
+    /// full-load, cdc or full-load-and-cdc.
+    pub migration_type: String,
+    /// The task's table mappings as JSON. If None, they're read from <id>.json next to this file.
+    pub table_mappings: Option<String>,
+    /// Whether the task should be running. Tasks are created stopped, and started once created if this is set.
+    #[serde(default)]
+    pub running: bool,
+    pub tags: Tags,
+}
+
+pub enum DmsResource {
+    ReplicationInstance(ReplicationInstance),
+    Endpoint(Endpoint),
+    ReplicationTask(ReplicationTask),
+}
+
+impl Resource for DmsResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            DmsResource::ReplicationInstance(instance) => Ok(RON.to_string_pretty(&instance, pretty_config)?.into()),
+            DmsResource::Endpoint(endpoint) => Ok(RON.to_string_pretty(&endpoint, pretty_config)?.into()),
+            DmsResource::ReplicationTask(task) => Ok(RON.to_string_pretty(&task, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = DmsResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            DmsResourceAddress::ReplicationInstance { .. } => Ok(DmsResource::ReplicationInstance(RON.from_str(s)?)),
+            DmsResourceAddress::Endpoint { .. } => Ok(DmsResource::Endpoint(RON.from_str(s)?)),
+            DmsResourceAddress::ReplicationTask { .. } => Ok(DmsResource::ReplicationTask(RON.from_str(s)?)),
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+
+use aws_sdk_databasemigration::types::Tag;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Tags(HashMap<String, String>);
+
+impl From<Option<Vec<Tag>>> for Tags {
+    fn from(tags: Option<Vec<Tag>>) -> Self {
+        let mut out_map = HashMap::new();
+        for tag in tags.unwrap_or_default() {
+            let (Some(key), Some(value)) = (tag.key, tag.value) else {
+                continue;
+            };
+            out_map.insert(key, value);
+        }
+        Tags(out_map)
+    }
+}
+
+impl From<Tags> for Option<Vec<Tag>> {
+    fn from(val: Tags) -> Self {
+        if val.0.is_empty() {
+            return None;
+        }
+        Some(val.0.into_iter().map(|(k, v)| Tag::builder().key(k).value(v).build()).collect())
+    }
+}
+
+// From a pair of tag maps, determine the keys to untag and the tags to set respectively
+pub fn tag_diff(old_tags: &Tags, new_tags: &Tags) -> (Vec<String>, Tags) {
+    let untag_keys = old_tags.0.keys().filter(|k| !new_tags.0.contains_key(*k)).cloned().collect();
+
+    let new_tagset = new_tags
+        .0
+        .iter()
+        .filter(|(k, v)| old_tags.0.get(*k) != Some(*v))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+
+    (untag_keys, Tags(new_tagset))
+}
@@ -0,0 +1,68 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::resource::ReplicationTask;
+
+/// Stands in for a secret ID when DMS reports an endpoint whose password this repository didn't set.
+pub const UNKNOWN_SECRET_ID: &str = "[unknown]";
+
+/// Reads an endpoint's password from Secrets Manager. The secret must be a plain string, not JSON.
+pub async fn read_secret(client: &aws_sdk_secretsmanager::Client, secret_id: &str) -> anyhow::Result<String> {
+    let resp = client
+        .get_secret_value()
+        .secret_id(secret_id)
+        .send()
+        .await
+        .with_context(|| format!("Failed to read secret {}", secret_id))?;
+    resp.secret_string
+        .with_context(|| format!("Secret {} has no string value", secret_id))
+}
+
+/// The file next to a replication task's own that holds its table mappings.
+pub fn table_mappings_path(prefix: &Path, region: &str, id: &str) -> PathBuf {
+    prefix.join(format!("aws/dms/{region}/replication_tasks/{id}.json"))
+}
+
+/// Works out whether a task's deployed table mappings came from its JSON file in this repository.
+///
+/// DMS doesn't keep the JSON's formatting, so the two are compared as JSON values. If they're the
+/// same, returns None so that the remote state reads back the same as a task file without inline
+/// table mappings. Otherwise returns `deployed`, so that editing the JSON file shows up as a diff.
+pub fn resolve_table_mappings(prefix: &Path, region: &str, id: &str, deployed: String) -> anyhow::Result<Option<String>> {
+    let path = table_mappings_path(prefix, region, id);
+    if !path.is_file() {
+        return Ok(Some(deployed));
+    }
+
+    let local: Option<serde_json::Value> = serde_json::from_str(&std::fs::read_to_string(&path)?).ok();
+    let remote: Option<serde_json::Value> = serde_json::from_str(&deployed).ok();
+    if local.is_some() && local == remote {
+        Ok(None)
+    } else {
+        Ok(Some(deployed))
+    }
+}
+
+/// Returns the table mappings to deploy for `task`, reading them from its JSON file if they aren't
+/// inline, and checks that they're valid JSON.
+pub fn table_mappings(prefix: &Path, region: &str, id: &str, task: &ReplicationTask) -> anyhow::Result<String> {
+    let table_mappings = match &task.table_mappings {
+        Some(table_mappings) => table_mappings.clone(),
+        None => {
+            let path = table_mappings_path(prefix, region, id);
+            std::fs::read_to_string(&path).with_context(|| {
+                format!(
+                    "Failed to read table mappings file {} for DMS replication task {}",
+                    path.display(),
+                    id
+                )
+            })?
+        }
+    };
+
+    serde_json::from_str::<serde_json::Value>(&table_mappings)
+        .with_context(|| format!("Table mappings for DMS replication task {} aren't valid JSON", id))?;
+
+    Ok(table_mappings)
+}
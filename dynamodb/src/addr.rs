@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum DynamoDbResourceAddress {
+    Table { region: String, name: String },
+}
+
+impl ResourceAddress for DynamoDbResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            DynamoDbResourceAddress::Table { region, name } => PathBuf::from(format!("aws/dynamodb/{region}/tables/{name}.ron")),
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "dynamodb", region, "tables", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(DynamoDbResourceAddress::Table {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for DynamoDbResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/dynamodb/<region>/tables/<table_name>.ron",
+                description: "A DynamoDB table",
+                example:     "aws/dynamodb/us-east-1/tables/sessions.ron",
+            },
+        ]
+    }
+}
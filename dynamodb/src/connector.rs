@@ -0,0 +1,193 @@
+pub use crate::addr::DynamoDbResourceAddress;
+pub use crate::op::DynamoDbConnectorOp;
+pub use crate::resource::DynamoDbResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::DynamoDbConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{BillingMode, GlobalSecondaryIndex, KeySchema, Projection, Table};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct DynamoDbConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_dynamodb::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<DynamoDbConnectorConfig>,
+    prefix: PathBuf,
+}
+
+impl DynamoDbConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_dynamodb::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+            let config = aws_config::defaults(BehaviorVersion::latest())
+                .region(region)
+                .timeout_config(
+                    TimeoutConfig::builder()
+                        .connect_timeout(Duration::from_secs(30))
+                        .operation_timeout(Duration::from_secs(30))
+                        .operation_attempt_timeout(Duration::from_secs(30))
+                        .read_timeout(Duration::from_secs(30))
+                        .build(),
+                )
+                .load()
+                .await;
+            let client = audited_client!(aws_sdk_dynamodb, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for DynamoDbConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = DynamoDbResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(DynamoDbConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let dynamodb_config: DynamoDbConnectorConfig = DynamoDbConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = dynamodb_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(dynamodb_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = dynamodb_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // Table skeleton, with a sort key and a global secondary index
+        res.push(skeleton!(
+            DynamoDbResourceAddress::Table {
+                region: String::from("[region]"),
+                name:   String::from("[table_name]"),
+            },
+            DynamoDbResource::Table(Table {
+                attribute_definitions: HashMap::from([
+                    (String::from("pk"), String::from("S")),
+                    (String::from("sk"), String::from("S")),
+                    (String::from("gsi1pk"), String::from("S")), // S, N or B
+                ]),
+                key_schema: KeySchema {
+                    partition_key: String::from("pk"),
+                    sort_key:      Some(String::from("sk")),
+                },
+                billing_mode: BillingMode::PayPerRequest,
+                global_secondary_indexes: HashMap::from([(
+                    String::from("gsi1"),
+                    GlobalSecondaryIndex {
+                        key_schema: KeySchema {
+                            partition_key: String::from("gsi1pk"),
+                            sort_key:      Some(String::from("sk")),
+                        },
+                        projection: Projection::All,
+                        provisioned_throughput: None,
+                    },
+                )]),
+                local_secondary_indexes: HashMap::new(),
+                ttl_attribute: Some(String::from("expires_at")),
+                stream_view_type: None, // or "NEW_AND_OLD_IMAGES", etc
+                point_in_time_recovery: true,
+                server_side_encryption: None,
+                deletion_protection: true,
+                tags: Tags::default(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = DynamoDbResourceAddress::from_path(addr)?;
+
+        match addr {
+            DynamoDbResourceAddress::Table { .. } => ron_check_eq::<Table>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = DynamoDbResourceAddress::from_path(addr)?;
+
+        match addr {
+            DynamoDbResourceAddress::Table { .. } => ron_check_syntax::<Table>(a),
+        }
+    }
+}
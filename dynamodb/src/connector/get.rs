@@ -0,0 +1,179 @@
+use std::{collections::HashMap, path::Path};
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_dynamodb::{
+    operation::describe_table::DescribeTableError,
+    types::{PointInTimeRecoveryStatus, SseStatus, TableStatus, TimeToLiveStatus},
+};
+
+use crate::{
+    addr::DynamoDbResourceAddress,
+    resource::{
+        BillingMode, DynamoDbResource, GlobalSecondaryIndex, LocalSecondaryIndex, ProvisionedThroughput,
+        ServerSideEncryption, Table,
+    },
+    tags::Tags,
+    util::{key_schema_from_sdk, projection_from_sdk, throughput_from_sdk},
+};
+
+use super::DynamoDbConnector;
+
+impl DynamoDbConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = DynamoDbResourceAddress::from_path(addr)?;
+
+        match &addr {
+            DynamoDbResourceAddress::Table { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.describe_table().table_name(name).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(DescribeTableError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let Some(table) = resp.table else {
+                    return Ok(None);
+                };
+
+                // A table that is being deleted is as good as gone
+                if table.table_status == Some(TableStatus::Deleting) {
+                    return Ok(None);
+                }
+
+                let table_arn = table.table_arn.clone().unwrap_or_default();
+
+                // Tables created before on-demand billing existed have no billing mode summary
+                let billing_mode = match table.billing_mode_summary.as_ref().and_then(|s| s.billing_mode.as_ref()) {
+                    Some(aws_sdk_dynamodb::types::BillingMode::PayPerRequest) => BillingMode::PayPerRequest,
+                    _ => BillingMode::Provisioned(
+                        throughput_from_sdk(table.provisioned_throughput.as_ref()).unwrap_or(ProvisionedThroughput {
+                            read_capacity_units:  0,
+                            write_capacity_units: 0,
+                        }),
+                    ),
+                };
+
+                let mut global_secondary_indexes = HashMap::new();
+                for gsi in table.global_secondary_indexes.unwrap_or_default() {
+                    let Some(index_name) = gsi.index_name else {
+                        continue;
+                    };
+
+                    // Leave the throughput out when it's the same as the table's, which is what None means
+                    let provisioned_throughput = match &billing_mode {
+                        BillingMode::PayPerRequest => None,
+                        BillingMode::Provisioned(table_throughput) => {
+                            throughput_from_sdk(gsi.provisioned_throughput.as_ref()).filter(|t| t != table_throughput)
+                        }
+                    };
+
+                    global_secondary_indexes.insert(
+                        index_name,
+                        GlobalSecondaryIndex {
+                            key_schema: key_schema_from_sdk(gsi.key_schema.as_deref().unwrap_or_default()),
+                            projection: projection_from_sdk(gsi.projection.as_ref()),
+                            provisioned_throughput,
+                        },
+                    );
+                }
+
+                let mut local_secondary_indexes = HashMap::new();
+                for lsi in table.local_secondary_indexes.unwrap_or_default() {
+                    let Some(index_name) = lsi.index_name else {
+                        continue;
+                    };
+
+                    let key_schema = key_schema_from_sdk(lsi.key_schema.as_deref().unwrap_or_default());
+                    local_secondary_indexes.insert(
+                        index_name,
+                        LocalSecondaryIndex {
+                            sort_key:   key_schema.sort_key.unwrap_or_default(),
+                            projection: projection_from_sdk(lsi.projection.as_ref()),
+                        },
+                    );
+                }
+
+                let stream_view_type = table
+                    .stream_specification
+                    .filter(|s| s.stream_enabled)
+                    .and_then(|s| s.stream_view_type)
+                    .map(|v| v.as_str().to_string());
+
+                let server_side_encryption = match table.sse_description {
+                    Some(sse) if matches!(sse.status, Some(SseStatus::Enabled | SseStatus::Enabling | SseStatus::Updating)) => {
+                        Some(ServerSideEncryption {
+                            kms_key: sse.kms_master_key_arn,
+                        })
+                    }
+                    _ => None,
+                };
+
+                let ttl_resp = client.describe_time_to_live().table_name(name).send().await?;
+                let ttl_attribute = ttl_resp
+                    .time_to_live_description
+                    .filter(|d| {
+                        matches!(
+                            d.time_to_live_status,
+                            Some(TimeToLiveStatus::Enabled | TimeToLiveStatus::Enabling)
+                        )
+                    })
+                    .and_then(|d| d.attribute_name);
+
+                let backups_resp = client.describe_continuous_backups().table_name(name).send().await?;
+                let point_in_time_recovery = backups_resp
+                    .continuous_backups_description
+                    .and_then(|d| d.point_in_time_recovery_description)
+                    .and_then(|d| d.point_in_time_recovery_status)
+                    == Some(PointInTimeRecoveryStatus::Enabled);
+
+                let mut tags = Vec::new();
+                let mut next_token: Option<String> = None;
+                loop {
+                    let tags_resp = client
+                        .list_tags_of_resource()
+                        .resource_arn(&table_arn)
+                        .set_next_token(next_token)
+                        .send()
+                        .await?;
+
+                    tags.extend(tags_resp.tags.unwrap_or_default());
+
+                    next_token = tags_resp.next_token;
+                    if next_token.is_none() {
+                        break;
+                    }
+                }
+
+                let table_resource = Table {
+                    attribute_definitions: table
+                        .attribute_definitions
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|d| (d.attribute_name, d.attribute_type.as_str().to_string()))
+                        .collect(),
+                    key_schema: key_schema_from_sdk(table.key_schema.as_deref().unwrap_or_default()),
+                    billing_mode,
+                    global_secondary_indexes,
+                    local_secondary_indexes,
+                    ttl_attribute,
+                    stream_view_type,
+                    point_in_time_recovery,
+                    server_side_encryption,
+                    deletion_protection: table.deletion_protection_enabled.unwrap_or(false),
+                    tags: Tags::from(Some(tags)),
+                };
+
+                get_resource_response!(
+                    DynamoDbResource::Table(table_resource),
+                    [(String::from("table_arn"), table_arn)]
+                )
+            }
+        }
+    }
+}
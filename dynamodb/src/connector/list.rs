@@ -0,0 +1,31 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::addr::DynamoDbResourceAddress;
+
+use super::DynamoDbConnector;
+
+impl DynamoDbConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut tables = client.list_tables().into_paginator().items().send();
+            while let Some(table_name) = tables.next().await {
+                results.push(
+                    DynamoDbResourceAddress::Table {
+                        region: region.clone(),
+                        name:   table_name?,
+                    }
+                    .to_path_buf(),
+                );
+            }
+        }
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use autoschematic_core::connector::{ConnectorOp, OpExecResponse, ResourceAddress};
+
+use crate::{addr::DynamoDbResourceAddress, op::DynamoDbConnectorOp, op_impl};
+
+use super::DynamoDbConnector;
+
+impl DynamoDbConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = DynamoDbResourceAddress::from_path(addr)?;
+        let op = DynamoDbConnectorOp::from_str(op)?;
+
+        match &addr {
+            DynamoDbResourceAddress::Table { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    DynamoDbConnectorOp::CreateTable(table) => op_impl::create_table(&client, name, &table).await,
+                    DynamoDbConnectorOp::UpdateBillingMode {
+                        billing_mode,
+                        index_throughput,
+                    } => op_impl::update_billing_mode(&client, name, &billing_mode, &index_throughput).await,
+                    DynamoDbConnectorOp::UpdateStreamSpecification(stream_view_type) => {
+                        op_impl::update_stream_specification(&client, name, &stream_view_type).await
+                    }
+                    DynamoDbConnectorOp::UpdateTimeToLive { attribute_name, enabled } => {
+                        op_impl::update_time_to_live(&client, name, &attribute_name, enabled).await
+                    }
+                    DynamoDbConnectorOp::UpdatePointInTimeRecovery(enabled) => {
+                        op_impl::update_point_in_time_recovery(&client, name, enabled).await
+                    }
+                    DynamoDbConnectorOp::UpdateServerSideEncryption(sse) => {
+                        op_impl::update_server_side_encryption(&client, name, &sse).await
+                    }
+                    DynamoDbConnectorOp::UpdateDeletionProtection(enabled) => {
+                        op_impl::update_deletion_protection(&client, name, enabled).await
+                    }
+                    DynamoDbConnectorOp::UpdateTableTags(old_tags, new_tags) => {
+                        op_impl::update_table_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    DynamoDbConnectorOp::DeleteTable => op_impl::delete_table(&client, name).await,
+                    DynamoDbConnectorOp::CreateGlobalSecondaryIndex {
+                        index_name,
+                        index,
+                        attribute_definitions,
+                    } => {
+                        op_impl::create_global_secondary_index(&client, name, &index_name, &index, &attribute_definitions)
+                            .await
+                    }
+                    DynamoDbConnectorOp::UpdateGlobalSecondaryIndexThroughput {
+                        index_name,
+                        provisioned_throughput,
+                    } => {
+                        op_impl::update_global_secondary_index_throughput(&client, name, &index_name, &provisioned_throughput)
+                            .await
+                    }
+                    DynamoDbConnectorOp::DeleteGlobalSecondaryIndex { index_name } => {
+                        op_impl::delete_global_secondary_index(&client, name, &index_name).await
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use std::{collections::HashMap, path::Path};
+
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::{
+    resource::{GlobalSecondaryIndex, ServerSideEncryption, Table},
+    util::{index_throughput, key_attributes},
+};
+
+use super::{DynamoDbConnector, DynamoDbConnectorOp, DynamoDbResourceAddress};
+
+/// Whether the table's current encryption already satisfies the desired setting.
+/// `kms_key: None` means the AWS managed key, whose ARN DynamoDB reports back like any other key.
+fn sse_matches(current: &Option<ServerSideEncryption>, desired: &Option<ServerSideEncryption>) -> bool {
+    match (current, desired) {
+        (Some(_), Some(ServerSideEncryption { kms_key: None })) => true,
+        _ => current == desired,
+    }
+}
+
+/// The fields that force an index to be dropped and rebuilt to get from `old` to `new`:
+/// only the throughput of an existing index can be changed in place.
+fn gsi_replacement_fields(
+    old_table: &Table,
+    old: &GlobalSecondaryIndex,
+    new_table: &Table,
+    new: &GlobalSecondaryIndex,
+) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if old.key_schema != new.key_schema {
+        fields.push("key_schema");
+    }
+    if old.projection != new.projection {
+        fields.push("projection");
+    }
+    if key_attributes(&new.key_schema)
+        .into_iter()
+        .any(|attr| old_table.attribute_definitions.get(attr) != new_table.attribute_definitions.get(attr))
+    {
+        fields.push("attribute_definitions");
+    }
+    fields
+}
+
+impl DynamoDbConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = DynamoDbResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            DynamoDbResourceAddress::Table { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_table)) => {
+                    let new_table: Table = RON.from_str(&new_table)?;
+                    Ok(vec![connector_op!(
+                        DynamoDbConnectorOp::CreateTable(new_table),
+                        format!("Create new DynamoDB table {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_table), None) => Ok(vec![connector_op!(
+                    DynamoDbConnectorOp::DeleteTable,
+                    format!("DELETE DynamoDB table {} in region {}", name, region)
+                )]),
+                (Some(old_table), Some(new_table)) => {
+                    let old_table: Table = RON.from_str(&old_table)?;
+                    let new_table: Table = RON.from_str(&new_table)?;
+                    let mut ops = Vec::new();
+
+                    // The table's own keys and its local indexes are fixed at creation
+                    let mut replace_fields = Vec::new();
+                    if old_table.key_schema != new_table.key_schema {
+                        replace_fields.push("key_schema");
+                    }
+                    if old_table.local_secondary_indexes != new_table.local_secondary_indexes {
+                        replace_fields.push("local_secondary_indexes");
+                    }
+                    let mut table_keys = key_attributes(&new_table.key_schema);
+                    table_keys.extend(new_table.local_secondary_indexes.values().map(|lsi| &lsi.sort_key));
+                    if table_keys
+                        .into_iter()
+                        .any(|attr| old_table.attribute_definitions.get(attr) != new_table.attribute_definitions.get(attr))
+                    {
+                        replace_fields.push("attribute_definitions");
+                    }
+
+                    if !replace_fields.is_empty() {
+                        ops.push(connector_op!(
+                            DynamoDbConnectorOp::DeleteTable,
+                            format!(
+                                "REPLACE DynamoDB table `{}` (requires replacement: {})",
+                                name,
+                                replace_fields.join(", ")
+                            )
+                        ));
+                        ops.push(connector_op!(
+                            DynamoDbConnectorOp::CreateTable(new_table),
+                            format!("Create new DynamoDB table {} in region {}", name, region)
+                        ));
+                        return Ok(ops);
+                    }
+
+                    if old_table.tags != new_table.tags {
+                        let diff = diff_ron_values(&old_table.tags, &new_table.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            DynamoDbConnectorOp::UpdateTableTags(old_table.tags.clone(), new_table.tags.clone()),
+                            format!("Modify tags for DynamoDB table `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    if old_table.deletion_protection != new_table.deletion_protection {
+                        ops.push(connector_op!(
+                            DynamoDbConnectorOp::UpdateDeletionProtection(new_table.deletion_protection),
+                            format!(
+                                "{} deletion protection for DynamoDB table `{}`",
+                                if new_table.deletion_protection { "Enable" } else { "Disable" },
+                                name
+                            )
+                        ));
+                    }
+
+                    if old_table.point_in_time_recovery != new_table.point_in_time_recovery {
+                        ops.push(connector_op!(
+                            DynamoDbConnectorOp::UpdatePointInTimeRecovery(new_table.point_in_time_recovery),
+                            format!(
+                                "{} point-in-time recovery for DynamoDB table `{}`",
+                                if new_table.point_in_time_recovery { "Enable" } else { "Disable" },
+                                name
+                            )
+                        ));
+                    }
+
+                    if !sse_matches(&old_table.server_side_encryption, &new_table.server_side_encryption) {
+                        let diff = diff_ron_values(&old_table.server_side_encryption, &new_table.server_side_encryption)
+                            .unwrap_or_default();
+                        ops.push(connector_op!(
+                            DynamoDbConnectorOp::UpdateServerSideEncryption(new_table.server_side_encryption.clone()),
+                            format!("Modify encryption for DynamoDB table `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    // TTL can only be enabled on one attribute, so moving it means disabling it first
+                    if old_table.ttl_attribute != new_table.ttl_attribute {
+                        if let Some(old_attribute) = &old_table.ttl_attribute {
+                            ops.push(connector_op!(
+                                DynamoDbConnectorOp::UpdateTimeToLive {
+                                    attribute_name: old_attribute.clone(),
+                                    enabled: false,
+                                },
+                                format!("Disable TTL on attribute {} for DynamoDB table `{}`", old_attribute, name)
+                            ));
+                        }
+                        if let Some(new_attribute) = &new_table.ttl_attribute {
+                            ops.push(connector_op!(
+                                DynamoDbConnectorOp::UpdateTimeToLive {
+                                    attribute_name: new_attribute.clone(),
+                                    enabled: true,
+                                },
+                                format!("Enable TTL on attribute {} for DynamoDB table `{}`", new_attribute, name)
+                            ));
+                        }
+                    }
+
+                    // DynamoDB only allows one index to be created or deleted per update, and waits for
+                    // each to finish before accepting the next. Deletions go first so that replaced
+                    // indexes free up their names and the per-table index limit before anything is created.
+                    let mut indexes_to_delete = Vec::new();
+                    let mut indexes_to_create = Vec::new();
+                    let mut indexes_to_update = Vec::new();
+
+                    let mut old_index_names: Vec<&String> = old_table.global_secondary_indexes.keys().collect();
+                    old_index_names.sort();
+                    for index_name in old_index_names {
+                        let old_index = &old_table.global_secondary_indexes[index_name];
+                        let Some(new_index) = new_table.global_secondary_indexes.get(index_name) else {
+                            indexes_to_delete.push((index_name.clone(), Vec::new()));
+                            continue;
+                        };
+
+                        let replace_fields = gsi_replacement_fields(&old_table, old_index, &new_table, new_index);
+                        if !replace_fields.is_empty() {
+                            indexes_to_delete.push((index_name.clone(), replace_fields));
+                            indexes_to_create.push(index_name.clone());
+                        } else {
+                            let old_throughput = index_throughput(&old_table.billing_mode, old_index);
+                            let new_throughput = index_throughput(&new_table.billing_mode, new_index);
+                            if old_throughput != new_throughput {
+                                indexes_to_update.push((index_name.clone(), new_throughput));
+                            }
+                        }
+                    }
+
+                    let mut new_index_names: Vec<&String> = new_table.global_secondary_indexes.keys().collect();
+                    new_index_names.sort();
+                    for index_name in new_index_names {
+                        if !old_table.global_secondary_indexes.contains_key(index_name) {
+                            indexes_to_create.push(index_name.clone());
+                        }
+                    }
+
+                    for (index_name, replace_fields) in indexes_to_delete {
+                        let message = if replace_fields.is_empty() {
+                            format!("DELETE global secondary index `{}` on DynamoDB table `{}`", index_name, name)
+                        } else {
+                            format!(
+                                "REPLACE global secondary index `{}` on DynamoDB table `{}` (requires replacement: {})",
+                                index_name,
+                                name,
+                                replace_fields.join(", ")
+                            )
+                        };
+                        ops.push(connector_op!(
+                            DynamoDbConnectorOp::DeleteGlobalSecondaryIndex {
+                                index_name: index_name.clone(),
+                            },
+                            message
+                        ));
+                    }
+
+                    // Billing changes come after index deletions, since switching to provisioned billing
+                    // needs the throughput of every remaining index, and before creations, so that new
+                    // indexes are created with the right billing mode.
+                    if old_table.billing_mode != new_table.billing_mode {
+                        let switching_mode = std::mem::discriminant(&old_table.billing_mode)
+                            != std::mem::discriminant(&new_table.billing_mode);
+
+                        let index_throughput_map: HashMap<_, _> = if switching_mode {
+                            new_table
+                                .global_secondary_indexes
+                                .iter()
+                                .filter(|(index_name, _)| old_table.global_secondary_indexes.contains_key(*index_name))
+                                .filter(|(index_name, _)| !indexes_to_create.contains(*index_name))
+                                .filter_map(|(index_name, index)| {
+                                    index_throughput(&new_table.billing_mode, index).map(|t| (index_name.clone(), t))
+                                })
+                                .collect()
+                        } else {
+                            HashMap::new()
+                        };
+
+                        let diff = diff_ron_values(&old_table.billing_mode, &new_table.billing_mode).unwrap_or_default();
+                        ops.push(connector_op!(
+                            DynamoDbConnectorOp::UpdateBillingMode {
+                                billing_mode: new_table.billing_mode.clone(),
+                                index_throughput: index_throughput_map,
+                            },
+                            format!("Modify billing mode for DynamoDB table `{}`\n{}", name, diff)
+                        ));
+
+                        // Throughput for existing indexes was set as part of the mode switch
+                        if switching_mode {
+                            indexes_to_update.clear();
+                        }
+                    }
+
+                    for (index_name, throughput) in indexes_to_update {
+                        let Some(throughput) = throughput else {
+                            continue;
+                        };
+                        ops.push(connector_op!(
+                            DynamoDbConnectorOp::UpdateGlobalSecondaryIndexThroughput {
+                                index_name: index_name.clone(),
+                                provisioned_throughput: throughput,
+                            },
+                            format!(
+                                "Modify throughput for global secondary index `{}` on DynamoDB table `{}`",
+                                index_name, name
+                            )
+                        ));
+                    }
+
+                    for index_name in indexes_to_create {
+                        let mut index = new_table.global_secondary_indexes[&index_name].clone();
+                        index.provisioned_throughput = index_throughput(&new_table.billing_mode, &index);
+
+                        ops.push(connector_op!(
+                            DynamoDbConnectorOp::CreateGlobalSecondaryIndex {
+                                index_name: index_name.clone(),
+                                index,
+                                attribute_definitions: new_table.attribute_definitions.clone(),
+                            },
+                            format!(
+                                "Create global secondary index `{}` on DynamoDB table `{}`",
+                                index_name, name
+                            )
+                        ));
+                    }
+
+                    // Changing the view type of an existing stream means disabling it first
+                    if old_table.stream_view_type != new_table.stream_view_type {
+                        if old_table.stream_view_type.is_some() && new_table.stream_view_type.is_some() {
+                            ops.push(connector_op!(
+                                DynamoDbConnectorOp::UpdateStreamSpecification(None),
+                                format!("Disable stream for DynamoDB table `{}`", name)
+                            ));
+                        }
+                        let diff = diff_ron_values(&old_table.stream_view_type, &new_table.stream_view_type).unwrap_or_default();
+                        ops.push(connector_op!(
+                            DynamoDbConnectorOp::UpdateStreamSpecification(new_table.stream_view_type.clone()),
+                            format!("Modify stream for DynamoDB table `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+        }
+    }
+}
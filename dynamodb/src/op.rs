@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{BillingMode, GlobalSecondaryIndex, ProvisionedThroughput, ServerSideEncryption, Table},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum DynamoDbConnectorOp {
+    CreateTable(Table),
+    /// Switching to provisioned billing requires the throughput of every global secondary index as well.
+    UpdateBillingMode {
+        billing_mode: BillingMode,
+        index_throughput: HashMap<String, ProvisionedThroughput>,
+    },
+    UpdateStreamSpecification(Option<String>),
+    UpdateTimeToLive {
+        attribute_name: String,
+        enabled: bool,
+    },
+    UpdatePointInTimeRecovery(bool),
+    UpdateServerSideEncryption(Option<ServerSideEncryption>),
+    UpdateDeletionProtection(bool),
+    UpdateTableTags(Tags, Tags),
+    DeleteTable,
+
+    // DynamoDB only accepts one index creation or deletion per UpdateTable call,
+    // so each index change is its own op.
+    /// `attribute_definitions` holds the types of the index's key attributes.
+    CreateGlobalSecondaryIndex {
+        index_name: String,
+        index: GlobalSecondaryIndex,
+        attribute_definitions: HashMap<String, String>,
+    },
+    UpdateGlobalSecondaryIndexThroughput {
+        index_name: String,
+        provisioned_throughput: ProvisionedThroughput,
+    },
+    DeleteGlobalSecondaryIndex {
+        index_name: String,
+    },
+}
+
+impl ConnectorOp for DynamoDbConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
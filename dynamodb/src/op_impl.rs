@@ -0,0 +1,515 @@
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::{Context, bail};
+use autoschematic_core::connector::OpExecResponse;
+use aws_sdk_dynamodb::types::{
+    CreateGlobalSecondaryIndexAction, DeleteGlobalSecondaryIndexAction, GlobalSecondaryIndexUpdate, IndexStatus,
+    PointInTimeRecoverySpecification, SseSpecification, SseType, StreamSpecification, StreamViewType, TableStatus,
+    TimeToLiveSpecification, UpdateGlobalSecondaryIndexAction,
+};
+
+use crate::{
+    resource::{BillingMode, GlobalSecondaryIndex, ProvisionedThroughput, ServerSideEncryption, Table},
+    tags::{Tags, tag_diff},
+    util::{
+        attribute_definitions_to_sdk, index_throughput, key_attributes, key_schema_to_sdk, projection_to_sdk,
+        table_key_attributes, throughput_to_sdk,
+    },
+};
+
+const ACTIVE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// Backfilling a new global secondary index on a large table can take a long time.
+const ACTIVE_MAX_POLLS: usize = 360;
+
+fn build_stream_specification(stream_view_type: &Option<String>) -> anyhow::Result<StreamSpecification> {
+    Ok(match stream_view_type {
+        Some(view_type) => StreamSpecification::builder()
+            .stream_enabled(true)
+            .stream_view_type(StreamViewType::from(view_type.as_str()))
+            .build()?,
+        None => StreamSpecification::builder().stream_enabled(false).build()?,
+    })
+}
+
+fn build_sse_specification(sse: &Option<ServerSideEncryption>) -> SseSpecification {
+    // Disabling SSE switches the table back to an AWS owned key; tables are always encrypted at rest
+    match sse {
+        Some(sse) => SseSpecification::builder()
+            .enabled(true)
+            .sse_type(SseType::Kms)
+            .set_kms_master_key_id(sse.kms_key.clone())
+            .build(),
+        None => SseSpecification::builder().enabled(false).build(),
+    }
+}
+
+async fn table_arn(client: &aws_sdk_dynamodb::Client, table_name: &str) -> anyhow::Result<String> {
+    let resp = client.describe_table().table_name(table_name).send().await?;
+    resp.table
+        .and_then(|t| t.table_arn)
+        .with_context(|| format!("Table {} has no ARN", table_name))
+}
+
+/// DynamoDB rejects most table updates while a previous one is still being applied, including
+/// index creation (which backfills) and deletion. Waits until the table and all of its indexes are ACTIVE.
+pub async fn wait_for_table_active(client: &aws_sdk_dynamodb::Client, table_name: &str) -> anyhow::Result<()> {
+    for _ in 0..ACTIVE_MAX_POLLS {
+        let resp = client.describe_table().table_name(table_name).send().await?;
+        let Some(table) = resp.table else {
+            bail!("Table {} not found", table_name);
+        };
+
+        let table_active = table.table_status == Some(TableStatus::Active);
+        let indexes_active = table
+            .global_secondary_indexes
+            .unwrap_or_default()
+            .iter()
+            .all(|gsi| gsi.index_status == Some(IndexStatus::Active));
+
+        if table_active && indexes_active {
+            return Ok(());
+        }
+
+        tokio::time::sleep(ACTIVE_POLL_INTERVAL).await;
+    }
+
+    bail!("Timed out waiting for table {} and its indexes to become ACTIVE", table_name)
+}
+
+/// Creates a table using the provided configuration, then applies the settings that
+/// CreateTable doesn't accept (TTL and point-in-time recovery).
+pub async fn create_table(
+    client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    table: &Table,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client
+        .create_table()
+        .table_name(table_name)
+        .set_attribute_definitions(Some(attribute_definitions_to_sdk(
+            &table.attribute_definitions,
+            table_key_attributes(table),
+        )?))
+        .set_key_schema(Some(key_schema_to_sdk(&table.key_schema)?))
+        .deletion_protection_enabled(table.deletion_protection);
+
+    match &table.billing_mode {
+        BillingMode::PayPerRequest => {
+            request = request.billing_mode(aws_sdk_dynamodb::types::BillingMode::PayPerRequest);
+        }
+        BillingMode::Provisioned(throughput) => {
+            request = request
+                .billing_mode(aws_sdk_dynamodb::types::BillingMode::Provisioned)
+                .provisioned_throughput(throughput_to_sdk(throughput)?);
+        }
+    }
+
+    for (index_name, gsi) in &table.global_secondary_indexes {
+        let mut index = aws_sdk_dynamodb::types::GlobalSecondaryIndex::builder()
+            .index_name(index_name)
+            .set_key_schema(Some(key_schema_to_sdk(&gsi.key_schema)?))
+            .projection(projection_to_sdk(&gsi.projection));
+
+        if let Some(throughput) = index_throughput(&table.billing_mode, gsi) {
+            index = index.provisioned_throughput(throughput_to_sdk(&throughput)?);
+        }
+
+        request = request.global_secondary_indexes(index.build()?);
+    }
+
+    for (index_name, lsi) in &table.local_secondary_indexes {
+        let mut key_schema = table.key_schema.clone();
+        key_schema.sort_key = Some(lsi.sort_key.clone());
+
+        request = request.local_secondary_indexes(
+            aws_sdk_dynamodb::types::LocalSecondaryIndex::builder()
+                .index_name(index_name)
+                .set_key_schema(Some(key_schema_to_sdk(&key_schema)?))
+                .projection(projection_to_sdk(&lsi.projection))
+                .build()?,
+        );
+    }
+
+    if table.stream_view_type.is_some() {
+        request = request.stream_specification(build_stream_specification(&table.stream_view_type)?);
+    }
+
+    if table.server_side_encryption.is_some() {
+        request = request.sse_specification(build_sse_specification(&table.server_side_encryption));
+    }
+
+    if table.tags.len() > 0 {
+        request = request.set_tags(Some(table.tags.to_vec()?));
+    }
+
+    let resp = request.send().await?;
+
+    wait_for_table_active(client, table_name).await?;
+
+    if let Some(attribute_name) = &table.ttl_attribute {
+        update_time_to_live(client, table_name, attribute_name, true).await?;
+    }
+
+    if table.point_in_time_recovery {
+        update_point_in_time_recovery(client, table_name, true).await?;
+    }
+
+    let table_arn = resp.table_description.and_then(|t| t.table_arn);
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("table_arn"), table_arn)])),
+        friendly_message: Some(format!("Created DynamoDB table {}", table_name)),
+    })
+}
+
+/// Switches between on-demand and provisioned billing, or changes the table's provisioned throughput
+pub async fn update_billing_mode(
+    client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    billing_mode: &BillingMode,
+    index_throughput: &HashMap<String, ProvisionedThroughput>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    wait_for_table_active(client, table_name).await?;
+
+    let mut request = client.update_table().table_name(table_name);
+
+    match billing_mode {
+        BillingMode::PayPerRequest => {
+            request = request.billing_mode(aws_sdk_dynamodb::types::BillingMode::PayPerRequest);
+        }
+        BillingMode::Provisioned(throughput) => {
+            request = request
+                .billing_mode(aws_sdk_dynamodb::types::BillingMode::Provisioned)
+                .provisioned_throughput(throughput_to_sdk(throughput)?);
+
+            for (index_name, throughput) in index_throughput {
+                request = request.global_secondary_index_updates(
+                    GlobalSecondaryIndexUpdate::builder()
+                        .update(
+                            UpdateGlobalSecondaryIndexAction::builder()
+                                .index_name(index_name)
+                                .provisioned_throughput(throughput_to_sdk(throughput)?)
+                                .build()?,
+                        )
+                        .build(),
+                );
+            }
+        }
+    }
+
+    request.send().await?;
+
+    wait_for_table_active(client, table_name).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated billing mode for DynamoDB table {}", table_name)),
+    })
+}
+
+/// Enables, disables or changes the view type of the table's stream
+pub async fn update_stream_specification(
+    client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    stream_view_type: &Option<String>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    wait_for_table_active(client, table_name).await?;
+
+    let resp = client
+        .update_table()
+        .table_name(table_name)
+        .stream_specification(build_stream_specification(stream_view_type)?)
+        .send()
+        .await?;
+
+    wait_for_table_active(client, table_name).await?;
+
+    let stream_arn = resp.table_description.and_then(|t| t.latest_stream_arn);
+
+    let message = match stream_view_type {
+        Some(view_type) => format!("Enabled {} stream for DynamoDB table {}", view_type, table_name),
+        None => format!("Disabled stream for DynamoDB table {}", table_name),
+    };
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(
+            String::from("stream_arn"),
+            stream_view_type.as_ref().and(stream_arn),
+        )])),
+        friendly_message: Some(message),
+    })
+}
+
+/// Enables or disables TTL on the given attribute
+pub async fn update_time_to_live(
+    client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    attribute_name: &str,
+    enabled: bool,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .update_time_to_live()
+        .table_name(table_name)
+        .time_to_live_specification(
+            TimeToLiveSpecification::builder()
+                .attribute_name(attribute_name)
+                .enabled(enabled)
+                .build()?,
+        )
+        .send()
+        .await?;
+
+    let message = if enabled {
+        format!("Enabled TTL on attribute {} for DynamoDB table {}", attribute_name, table_name)
+    } else {
+        format!("Disabled TTL on attribute {} for DynamoDB table {}", attribute_name, table_name)
+    };
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(message),
+    })
+}
+
+/// Enables or disables point-in-time recovery
+pub async fn update_point_in_time_recovery(
+    client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    enabled: bool,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .update_continuous_backups()
+        .table_name(table_name)
+        .point_in_time_recovery_specification(
+            PointInTimeRecoverySpecification::builder()
+                .point_in_time_recovery_enabled(enabled)
+                .build()?,
+        )
+        .send()
+        .await?;
+
+    let message = if enabled {
+        format!("Enabled point-in-time recovery for DynamoDB table {}", table_name)
+    } else {
+        format!("Disabled point-in-time recovery for DynamoDB table {}", table_name)
+    };
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(message),
+    })
+}
+
+/// Changes the key used to encrypt the table at rest
+pub async fn update_server_side_encryption(
+    client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    sse: &Option<ServerSideEncryption>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    wait_for_table_active(client, table_name).await?;
+
+    client
+        .update_table()
+        .table_name(table_name)
+        .sse_specification(build_sse_specification(sse))
+        .send()
+        .await?;
+
+    wait_for_table_active(client, table_name).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated encryption settings for DynamoDB table {}", table_name)),
+    })
+}
+
+/// Enables or disables deletion protection
+pub async fn update_deletion_protection(
+    client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    enabled: bool,
+) -> Result<OpExecResponse, anyhow::Error> {
+    wait_for_table_active(client, table_name).await?;
+
+    client
+        .update_table()
+        .table_name(table_name)
+        .deletion_protection_enabled(enabled)
+        .send()
+        .await?;
+
+    let message = if enabled {
+        format!("Enabled deletion protection for DynamoDB table {}", table_name)
+    } else {
+        format!("Disabled deletion protection for DynamoDB table {}", table_name)
+    };
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(message),
+    })
+}
+
+/// Updates table tags
+pub async fn update_table_tags(
+    client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let table_arn = table_arn(client, table_name).await?;
+
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags)?;
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(&table_arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client
+            .tag_resource()
+            .resource_arn(&table_arn)
+            .set_tags(Some(new_tagset))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for DynamoDB table {}", table_name)),
+    })
+}
+
+/// Deletes a table and all of its items
+pub async fn delete_table(client: &aws_sdk_dynamodb::Client, table_name: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_table().table_name(table_name).send().await?;
+
+    // Wait for the table to disappear so that a replacement with the same name can be created
+    for _ in 0..ACTIVE_MAX_POLLS {
+        match client.describe_table().table_name(table_name).send().await {
+            Ok(_) => tokio::time::sleep(ACTIVE_POLL_INTERVAL).await,
+            Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => {
+                return Ok(OpExecResponse {
+                    outputs: Some(HashMap::from([(String::from("table_arn"), None)])),
+                    friendly_message: Some(format!("Deleted DynamoDB table {}", table_name)),
+                });
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    bail!("Timed out waiting for table {} to be deleted", table_name)
+}
+
+/// Creates a global secondary index and waits for it to finish backfilling
+pub async fn create_global_secondary_index(
+    client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    index_name: &str,
+    index: &GlobalSecondaryIndex,
+    attribute_definitions: &HashMap<String, String>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    wait_for_table_active(client, table_name).await?;
+
+    let mut action = CreateGlobalSecondaryIndexAction::builder()
+        .index_name(index_name)
+        .set_key_schema(Some(key_schema_to_sdk(&index.key_schema)?))
+        .projection(projection_to_sdk(&index.projection));
+
+    if let Some(throughput) = &index.provisioned_throughput {
+        action = action.provisioned_throughput(throughput_to_sdk(throughput)?);
+    }
+
+    client
+        .update_table()
+        .table_name(table_name)
+        .set_attribute_definitions(Some(attribute_definitions_to_sdk(
+            attribute_definitions,
+            key_attributes(&index.key_schema),
+        )?))
+        .global_secondary_index_updates(GlobalSecondaryIndexUpdate::builder().create(action.build()?).build())
+        .send()
+        .await?;
+
+    wait_for_table_active(client, table_name).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Created global secondary index {} on DynamoDB table {}",
+            index_name, table_name
+        )),
+    })
+}
+
+/// Changes the provisioned throughput of a global secondary index
+pub async fn update_global_secondary_index_throughput(
+    client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    index_name: &str,
+    provisioned_throughput: &ProvisionedThroughput,
+) -> Result<OpExecResponse, anyhow::Error> {
+    wait_for_table_active(client, table_name).await?;
+
+    client
+        .update_table()
+        .table_name(table_name)
+        .global_secondary_index_updates(
+            GlobalSecondaryIndexUpdate::builder()
+                .update(
+                    UpdateGlobalSecondaryIndexAction::builder()
+                        .index_name(index_name)
+                        .provisioned_throughput(throughput_to_sdk(provisioned_throughput)?)
+                        .build()?,
+                )
+                .build(),
+        )
+        .send()
+        .await?;
+
+    wait_for_table_active(client, table_name).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Updated throughput for global secondary index {} on DynamoDB table {}",
+            index_name, table_name
+        )),
+    })
+}
+
+/// Deletes a global secondary index and waits for it to be removed
+pub async fn delete_global_secondary_index(
+    client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    index_name: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    wait_for_table_active(client, table_name).await?;
+
+    client
+        .update_table()
+        .table_name(table_name)
+        .global_secondary_index_updates(
+            GlobalSecondaryIndexUpdate::builder()
+                .delete(DeleteGlobalSecondaryIndexAction::builder().index_name(index_name).build()?)
+                .build(),
+        )
+        .send()
+        .await?;
+
+    // The index reports DELETING until it's gone, so this also waits for the deletion to finish
+    wait_for_table_active(client, table_name).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Deleted global secondary index {} from DynamoDB table {}",
+            index_name, table_name
+        )),
+    })
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::DynamoDbResourceAddress, tags::Tags};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct KeySchema {
+    pub partition_key: String,
+    pub sort_key: Option<String>,
+}
+
+/// Which attributes are copied into an index.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum Projection {
+    All,
+    KeysOnly,
+    /// The keys, plus the listed non-key attributes.
+    Include(Vec<String>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ProvisionedThroughput {
+    pub read_capacity_units: i64,
+    pub write_capacity_units: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum BillingMode {
+    PayPerRequest,
+    Provisioned(ProvisionedThroughput),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct GlobalSecondaryIndex {
+    pub key_schema: KeySchema,
+    pub projection: Projection,
+    /// Only used by provisioned tables. If None, the index uses the same throughput as the table.
+    pub provisioned_throughput: Option<ProvisionedThroughput>,
+}
+
+/// Local secondary indexes share the table's partition key and can only be defined when the table is created.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LocalSecondaryIndex {
+    pub sort_key: String,
+    pub projection: Projection,
+}
+
+/// Encryption at rest with a KMS key.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ServerSideEncryption {
+    /// ARN of a customer managed KMS key. If None, the AWS managed key (aws/dynamodb) is used.
+    pub kms_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Table {
+    /// The type (S, N or B) of each attribute used in the key schema of the table or one of its indexes.
+    pub attribute_definitions: HashMap<String, String>,
+    pub key_schema: KeySchema,
+    pub billing_mode: BillingMode,
+    #[serde(default)]
+    pub global_secondary_indexes: HashMap<String, GlobalSecondaryIndex>,
+    #[serde(default)]
+    pub local_secondary_indexes: HashMap<String, LocalSecondaryIndex>,
+    /// The attribute holding each item's expiry time, if TTL is enabled.
+    pub ttl_attribute: Option<String>,
+    pub stream_view_type: Option<String>, // KEYS_ONLY, NEW_IMAGE, OLD_IMAGE or NEW_AND_OLD_IMAGES
+    #[serde(default)]
+    pub point_in_time_recovery: bool,
+    /// If None, the table is encrypted with an AWS owned key.
+    pub server_side_encryption: Option<ServerSideEncryption>,
+    #[serde(default)]
+    pub deletion_protection: bool,
+    pub tags: Tags,
+}
+
+pub enum DynamoDbResource {
+    Table(Table),
+}
+
+impl Resource for DynamoDbResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            DynamoDbResource::Table(table) => Ok(RON.to_string_pretty(&table, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = DynamoDbResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            DynamoDbResourceAddress::Table { .. } => Ok(DynamoDbResource::Table(RON.from_str(s)?)),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use aws_sdk_dynamodb::types::{AttributeDefinition, KeySchemaElement, KeyType, ProjectionType, ScalarAttributeType};
+
+use crate::resource::{BillingMode, GlobalSecondaryIndex, KeySchema, Projection, ProvisionedThroughput, Table};
+
+pub fn key_schema_to_sdk(key_schema: &KeySchema) -> anyhow::Result<Vec<KeySchemaElement>> {
+    let mut elements = vec![
+        KeySchemaElement::builder()
+            .attribute_name(&key_schema.partition_key)
+            .key_type(KeyType::Hash)
+            .build()?,
+    ];
+
+    if let Some(sort_key) = &key_schema.sort_key {
+        elements.push(
+            KeySchemaElement::builder()
+                .attribute_name(sort_key)
+                .key_type(KeyType::Range)
+                .build()?,
+        );
+    }
+
+    Ok(elements)
+}
+
+pub fn key_schema_from_sdk(elements: &[KeySchemaElement]) -> KeySchema {
+    let key_of_type = |key_type: KeyType| {
+        elements
+            .iter()
+            .find(|e| e.key_type == key_type)
+            .map(|e| e.attribute_name.clone())
+    };
+
+    KeySchema {
+        partition_key: key_of_type(KeyType::Hash).unwrap_or_default(),
+        sort_key:      key_of_type(KeyType::Range),
+    }
+}
+
+pub fn projection_to_sdk(projection: &Projection) -> aws_sdk_dynamodb::types::Projection {
+    match projection {
+        Projection::All => aws_sdk_dynamodb::types::Projection::builder()
+            .projection_type(ProjectionType::All)
+            .build(),
+        Projection::KeysOnly => aws_sdk_dynamodb::types::Projection::builder()
+            .projection_type(ProjectionType::KeysOnly)
+            .build(),
+        Projection::Include(attributes) => aws_sdk_dynamodb::types::Projection::builder()
+            .projection_type(ProjectionType::Include)
+            .set_non_key_attributes(Some(attributes.clone()))
+            .build(),
+    }
+}
+
+pub fn projection_from_sdk(projection: Option<&aws_sdk_dynamodb::types::Projection>) -> Projection {
+    match projection.and_then(|p| p.projection_type.as_ref()) {
+        Some(ProjectionType::KeysOnly) => Projection::KeysOnly,
+        Some(ProjectionType::Include) => {
+            Projection::Include(projection.and_then(|p| p.non_key_attributes.clone()).unwrap_or_default())
+        }
+        _ => Projection::All,
+    }
+}
+
+pub fn throughput_to_sdk(throughput: &ProvisionedThroughput) -> anyhow::Result<aws_sdk_dynamodb::types::ProvisionedThroughput> {
+    Ok(aws_sdk_dynamodb::types::ProvisionedThroughput::builder()
+        .read_capacity_units(throughput.read_capacity_units)
+        .write_capacity_units(throughput.write_capacity_units)
+        .build()?)
+}
+
+pub fn throughput_from_sdk(
+    throughput: Option<&aws_sdk_dynamodb::types::ProvisionedThroughputDescription>,
+) -> Option<ProvisionedThroughput> {
+    let throughput = throughput?;
+    Some(ProvisionedThroughput {
+        read_capacity_units:  throughput.read_capacity_units?,
+        write_capacity_units: throughput.write_capacity_units?,
+    })
+}
+
+/// The throughput an index actually runs with: its own if set, otherwise the table's.
+/// None for on-demand tables.
+pub fn index_throughput(billing_mode: &BillingMode, index: &GlobalSecondaryIndex) -> Option<ProvisionedThroughput> {
+    match billing_mode {
+        BillingMode::PayPerRequest => None,
+        BillingMode::Provisioned(table_throughput) => Some(
+            index
+                .provisioned_throughput
+                .clone()
+                .unwrap_or_else(|| table_throughput.clone()),
+        ),
+    }
+}
+
+/// Builds the AttributeDefinitions for the given key attributes. DynamoDB rejects definitions
+/// for attributes that aren't used in any key, so only the attributes that are asked for are included.
+pub fn attribute_definitions_to_sdk<'a>(
+    attribute_definitions: &HashMap<String, String>,
+    attribute_names: impl IntoIterator<Item = &'a String>,
+) -> anyhow::Result<Vec<AttributeDefinition>> {
+    let mut out = Vec::new();
+    for name in attribute_names {
+        if out.iter().any(|d: &AttributeDefinition| &d.attribute_name == name) {
+            continue;
+        }
+
+        let attribute_type = attribute_definitions
+            .get(name)
+            .with_context(|| format!("Key attribute {} has no entry in attribute_definitions", name))?;
+
+        out.push(
+            AttributeDefinition::builder()
+                .attribute_name(name)
+                .attribute_type(ScalarAttributeType::from(attribute_type.as_str()))
+                .build()?,
+        );
+    }
+    Ok(out)
+}
+
+pub fn key_attributes(key_schema: &KeySchema) -> Vec<&String> {
+    std::iter::once(&key_schema.partition_key)
+        .chain(key_schema.sort_key.as_ref())
+        .collect()
+}
+
+/// Every attribute used in the key schema of the table or one of its indexes.
+pub fn table_key_attributes(table: &Table) -> Vec<&String> {
+    let mut attributes = key_attributes(&table.key_schema);
+    attributes.extend(table.local_secondary_indexes.values().map(|lsi| &lsi.sort_key));
+    for gsi in table.global_secondary_indexes.values() {
+        attributes.extend(key_attributes(&gsi.key_schema));
+    }
+    attributes
+}
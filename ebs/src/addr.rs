@@ -0,0 +1,60 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum EbsResourceAddress {
+    Volume { region: String, volume_id: String },
+    LifecyclePolicy { region: String, policy_id: String },
+}
+
+impl ResourceAddress for EbsResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            EbsResourceAddress::Volume { region, volume_id } => PathBuf::from(format!("aws/ebs/{region}/volumes/{volume_id}.ron")),
+            EbsResourceAddress::LifecyclePolicy { region, policy_id } => {
+                PathBuf::from(format!("aws/ebs/{region}/lifecycle_policies/{policy_id}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "ebs", region, "volumes", volume_id] if volume_id.ends_with(".ron") => {
+                let volume_id = volume_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(EbsResourceAddress::Volume {
+                    region: region.to_string(),
+                    volume_id,
+                })
+            }
+            ["aws", "ebs", region, "lifecycle_policies", policy_id] if policy_id.ends_with(".ron") => {
+                let policy_id = policy_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(EbsResourceAddress::LifecyclePolicy {
+                    region: region.to_string(),
+                    policy_id,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for EbsResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/ebs/<region>/volumes/<volume_id>.ron",
+                description: "A standalone EBS volume, optionally attached to an instance",
+                example:     "aws/ebs/us-east-1/volumes/postgres-data.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ebs/<region>/lifecycle_policies/<policy_id>.ron",
+                description: "A Data Lifecycle Manager policy that snapshots tagged volumes or instances on a schedule",
+                example:     "aws/ebs/us-east-1/lifecycle_policies/daily-snapshots.ron",
+            },
+        ]
+    }
+}
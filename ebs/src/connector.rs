@@ -0,0 +1,274 @@
+pub use crate::addr::EbsResourceAddress;
+pub use crate::op::EbsConnectorOp;
+pub use crate::resource::EbsResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse, VirtToPhyResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::EbsConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{LifecyclePolicy, SnapshotSchedule, Volume, VolumeAttachment};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct EbsConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_ec2::Client>>>,
+    dlm_client_cache: Mutex<HashMap<String, Arc<aws_sdk_dlm::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<EbsConnectorConfig>,
+    prefix: PathBuf,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl EbsConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_ec2::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_ec2, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+    pub async fn get_or_init_dlm_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_dlm::Client>> {
+        let mut cache = self.dlm_client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_dlm, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get DLM client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for EbsConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = EbsResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(EbsConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let ebs_config: EbsConnectorConfig = EbsConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = ebs_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.dlm_client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(ebs_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = ebs_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn addr_virt_to_phy(&self, addr: &Path) -> anyhow::Result<VirtToPhyResponse> {
+        let addr = EbsResourceAddress::from_path(addr)?;
+
+        match &addr {
+            EbsResourceAddress::Volume { region, .. } => {
+                let Some(volume_id) = addr.get_output(&self.prefix, "volume_id")? else {
+                    return Ok(VirtToPhyResponse::NotPresent);
+                };
+                Ok(VirtToPhyResponse::Present(
+                    EbsResourceAddress::Volume {
+                        region: region.into(),
+                        volume_id,
+                    }
+                    .to_path_buf(),
+                ))
+            }
+            EbsResourceAddress::LifecyclePolicy { region, .. } => {
+                let Some(policy_id) = addr.get_output(&self.prefix, "policy_id")? else {
+                    return Ok(VirtToPhyResponse::NotPresent);
+                };
+                Ok(VirtToPhyResponse::Present(
+                    EbsResourceAddress::LifecyclePolicy {
+                        region: region.into(),
+                        policy_id,
+                    }
+                    .to_path_buf(),
+                ))
+            }
+        }
+    }
+
+    async fn addr_phy_to_virt(&self, addr: &Path) -> anyhow::Result<Option<PathBuf>> {
+        let addr = EbsResourceAddress::from_path(addr)?;
+
+        match &addr {
+            EbsResourceAddress::Volume { .. } | EbsResourceAddress::LifecyclePolicy { .. } => {
+                if let Some(virt_addr) = addr.phy_to_virt(&self.prefix)? {
+                    return Ok(Some(virt_addr.to_path_buf()));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // Encrypted gp3 data volume attached to an instance
+        res.push(skeleton!(
+            EbsResourceAddress::Volume {
+                region:    String::from("[region]"),
+                volume_id: String::from("[volume_name]"),
+            },
+            EbsResource::Volume(Volume {
+                availability_zone: String::from("[availability_zone]"),
+                size: 100,
+                volume_type: String::from("gp3"),
+                iops: None,
+                throughput: None,
+                encrypted: true,
+                kms_key_id: None,
+                snapshot_id: None,
+                attachment: Some(VolumeAttachment {
+                    instance_id: String::from("[instance_id]"),
+                    device_name: String::from("/dev/sdf"),
+                }),
+                tags: Tags::default(),
+            })
+        ));
+
+        // Daily snapshots of volumes tagged Backup=daily, kept for a week
+        res.push(skeleton!(
+            EbsResourceAddress::LifecyclePolicy {
+                region:    String::from("[region]"),
+                policy_id: String::from("[policy_name]"),
+            },
+            EbsResource::LifecyclePolicy(LifecyclePolicy {
+                description: String::from("Daily snapshots, kept for 7 days"),
+                execution_role_arn: String::from("arn:aws:iam::[account_id]:role/AWSDataLifecycleManagerDefaultRole"),
+                enabled: true,
+                resource_type: String::from("VOLUME"),
+                target_tags: Tags::from(Some(HashMap::from([(String::from("Backup"), String::from("daily"))]))),
+                schedules: vec![SnapshotSchedule {
+                    name: String::from("daily"),
+                    interval_hours: Some(24),
+                    times: vec![String::from("03:00")],
+                    cron_expression: None,
+                    retain_count: Some(7),
+                    retain_interval: None,
+                    retain_interval_unit: None,
+                    copy_tags: true,
+                    tags_to_add: Tags::default(),
+                }],
+                tags: Tags::default(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = EbsResourceAddress::from_path(addr)?;
+
+        match addr {
+            EbsResourceAddress::Volume { .. } => ron_check_eq::<Volume>(a, b),
+            EbsResourceAddress::LifecyclePolicy { .. } => ron_check_eq::<LifecyclePolicy>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = EbsResourceAddress::from_path(addr)?;
+
+        match addr {
+            EbsResourceAddress::Volume { .. } => ron_check_syntax::<Volume>(a),
+            EbsResourceAddress::LifecyclePolicy { .. } => ron_check_syntax::<LifecyclePolicy>(a),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_dlm::{error::ProvideErrorMetadata, types::GettablePolicyStateValues};
+
+use crate::{
+    addr::EbsResourceAddress,
+    op_impl::{current_attachment, find_volume},
+    resource::{EbsResource, LifecyclePolicy, Volume},
+    tags::Tags,
+    util::from_policy_details,
+};
+
+use super::EbsConnector;
+
+impl EbsConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = EbsResourceAddress::from_path(addr)?;
+
+        match &addr {
+            EbsResourceAddress::Volume { region, volume_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(volume) = find_volume(&client, volume_id).await? else {
+                    return Ok(None);
+                };
+
+                let volume_type = volume
+                    .volume_type
+                    .as_ref()
+                    .map(|t| t.as_str().to_string())
+                    .unwrap_or_default();
+
+                // EC2 reports the baseline IOPS of gp2 and magnetic volumes too, but they can't be set
+                let iops = if matches!(volume_type.as_str(), "gp3" | "io1" | "io2") {
+                    volume.iops
+                } else {
+                    None
+                };
+                let throughput = if volume_type == "gp3" { volume.throughput } else { None };
+
+                let ebs_volume = Volume {
+                    availability_zone: volume.availability_zone.clone().unwrap_or_default(),
+                    size: volume.size.unwrap_or_default(),
+                    volume_type,
+                    iops,
+                    throughput,
+                    encrypted: volume.encrypted.unwrap_or(false),
+                    kms_key_id: volume.kms_key_id.clone(),
+                    snapshot_id: volume.snapshot_id.clone().filter(|snapshot_id| !snapshot_id.is_empty()),
+                    attachment: current_attachment(&volume),
+                    tags: Tags::from(volume.tags.clone()),
+                };
+
+                get_resource_response!(
+                    EbsResource::Volume(ebs_volume),
+                    [(String::from("volume_id"), volume_id.clone())]
+                )
+            }
+            EbsResourceAddress::LifecyclePolicy { region, policy_id } => {
+                let client = self.get_or_init_dlm_client(region).await?;
+
+                let policy = match client.get_lifecycle_policy().policy_id(policy_id).send().await {
+                    Ok(resp) => resp.policy,
+                    Err(e) if e.code() == Some("ResourceNotFoundException") => None,
+                    Err(e) => return Err(e.into()),
+                };
+                let Some(policy) = policy else {
+                    return Ok(None);
+                };
+                let Some(policy_details) = policy.policy_details else {
+                    return Ok(None);
+                };
+
+                let (resource_type, target_tags, schedules) = from_policy_details(policy_details)?;
+
+                let lifecycle_policy = LifecyclePolicy {
+                    description: policy.description.unwrap_or_default(),
+                    execution_role_arn: policy.execution_role_arn.unwrap_or_default(),
+                    enabled: policy.state == Some(GettablePolicyStateValues::Enabled),
+                    resource_type,
+                    target_tags,
+                    schedules,
+                    tags: Tags::from(policy.tags),
+                };
+
+                get_resource_response!(
+                    EbsResource::LifecyclePolicy(lifecycle_policy),
+                    [(String::from("policy_id"), policy_id.clone())]
+                )
+            }
+        }
+    }
+}
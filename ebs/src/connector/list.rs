@@ -0,0 +1,77 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+use aws_sdk_dlm::types::PolicyTypeValues;
+use aws_sdk_ec2::types::Filter;
+
+use crate::addr::EbsResourceAddress;
+
+use super::EbsConnector;
+
+impl EbsConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut volumes = client
+                .describe_volumes()
+                .filters(
+                    Filter::builder()
+                        .name("status")
+                        .values("creating")
+                        .values("available")
+                        .values("in-use")
+                        .build(),
+                )
+                .into_paginator()
+                .items()
+                .send();
+
+            while let Some(volume) = volumes.next().await {
+                let volume = volume?;
+                let Some(volume_id) = &volume.volume_id else {
+                    continue;
+                };
+
+                // Volumes deleted along with their instance were created with it, and so belong to
+                // the instance's block_device_mappings in aws/ec2
+                let instance_volume = volume
+                    .attachments()
+                    .iter()
+                    .any(|attachment| attachment.delete_on_termination == Some(true));
+                if instance_volume {
+                    continue;
+                }
+
+                results.push(
+                    EbsResourceAddress::Volume {
+                        region:    region.clone(),
+                        volume_id: volume_id.clone(),
+                    }
+                    .to_path_buf(),
+                );
+            }
+
+            let dlm_client = self.get_or_init_dlm_client(&region).await?;
+
+            let policies = dlm_client.get_lifecycle_policies().send().await?;
+            for policy in policies.policies.unwrap_or_default() {
+                // Default policies are managed through the account's DLM settings, and AMI and
+                // cross-account event policies are out of scope
+                if policy.default_policy == Some(true) || policy.policy_type != Some(PolicyTypeValues::EbsSnapshotManagement) {
+                    continue;
+                }
+                let Some(policy_id) = policy.policy_id else {
+                    continue;
+                };
+
+                results.push(EbsResourceAddress::LifecyclePolicy { region: region.clone(), policy_id }.to_path_buf());
+            }
+        }
+
+        Ok(results)
+    }
+}
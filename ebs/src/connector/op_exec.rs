@@ -0,0 +1,59 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{ConnectorOp, OpExecResponse, ResourceAddress},
+    error_util::invalid_op,
+};
+
+use crate::{
+    addr::EbsResourceAddress,
+    op::EbsConnectorOp,
+    op_impl,
+    util::{get_phy_policy_id, get_phy_volume_id},
+};
+
+use super::EbsConnector;
+
+impl EbsConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = EbsResourceAddress::from_path(addr)?;
+        let op = EbsConnectorOp::from_str(op)?;
+
+        match &addr {
+            EbsResourceAddress::Volume { region, volume_id } => {
+                let volume_id = get_phy_volume_id(&self.prefix, region, volume_id)?.unwrap_or(volume_id.into());
+
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    EbsConnectorOp::CreateVolume(volume) => op_impl::create_volume(&client, &volume).await,
+                    EbsConnectorOp::ModifyVolume(volume) => op_impl::modify_volume(&client, &volume_id, &volume).await,
+                    EbsConnectorOp::AttachVolume(attachment) => op_impl::attach_volume(&client, &volume_id, &attachment).await,
+                    EbsConnectorOp::DetachVolume => op_impl::detach_volume(&client, &volume_id).await,
+                    EbsConnectorOp::UpdateVolumeTags(old_tags, new_tags) => {
+                        op_impl::update_volume_tags(&client, &volume_id, &old_tags, &new_tags).await
+                    }
+                    EbsConnectorOp::DeleteVolume => op_impl::delete_volume(&client, &volume_id).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            EbsResourceAddress::LifecyclePolicy { region, policy_id } => {
+                let policy_id = get_phy_policy_id(&self.prefix, region, policy_id)?.unwrap_or(policy_id.into());
+
+                let client = self.get_or_init_dlm_client(region).await?;
+
+                match op {
+                    EbsConnectorOp::CreateLifecyclePolicy(policy) => op_impl::create_lifecycle_policy(&client, &policy).await,
+                    EbsConnectorOp::UpdateLifecyclePolicy(policy) => {
+                        op_impl::update_lifecycle_policy(&client, &policy_id, &policy).await
+                    }
+                    EbsConnectorOp::UpdateLifecyclePolicyTags(old_tags, new_tags) => {
+                        op_impl::update_lifecycle_policy_tags(&client, &policy_id, &old_tags, &new_tags).await
+                    }
+                    EbsConnectorOp::DeleteLifecyclePolicy => op_impl::delete_lifecycle_policy(&client, &policy_id).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::resource::{LifecyclePolicy, Volume};
+
+use super::{EbsConnector, EbsConnectorOp, EbsResourceAddress};
+
+const VOLUME_TYPES: &[&str] = &["gp3", "gp2", "io1", "io2", "st1", "sc1", "standard"];
+const INTERVAL_HOURS: &[i32] = &[1, 2, 3, 4, 6, 8, 12, 24];
+const RETAIN_INTERVAL_UNITS: &[&str] = &["DAYS", "WEEKS", "MONTHS", "YEARS"];
+
+/// Combinations that EC2 would reject, caught at plan time instead.
+fn check_volume(volume_id: &str, volume: &Volume) -> anyhow::Result<()> {
+    if !VOLUME_TYPES.contains(&volume.volume_type.as_str()) {
+        bail!(
+            "EBS volume {} has volume_type {}: expected one of {}",
+            volume_id,
+            volume.volume_type,
+            VOLUME_TYPES.join(", ")
+        );
+    }
+
+    if volume.iops.is_some() && !matches!(volume.volume_type.as_str(), "gp3" | "io1" | "io2") {
+        bail!("EBS volume {} sets iops, which only gp3, io1 and io2 volumes support", volume_id);
+    }
+    if volume.iops.is_none() && matches!(volume.volume_type.as_str(), "io1" | "io2") {
+        bail!("EBS volume {} is {}, which requires iops", volume_id, volume.volume_type);
+    }
+    if volume.throughput.is_some() && volume.volume_type != "gp3" {
+        bail!("EBS volume {} sets throughput, which only gp3 volumes support", volume_id);
+    }
+    if volume.kms_key_id.is_some() && !volume.encrypted {
+        bail!("EBS volume {} sets kms_key_id, which requires encrypted: true", volume_id);
+    }
+
+    Ok(())
+}
+
+/// Unset performance fields mean "use the default", so keep whatever EBS picked for them.
+fn normalize_volume(old: &Volume, new: &mut Volume) {
+    if new.volume_type == old.volume_type {
+        if new.iops.is_none() {
+            new.iops = old.iops;
+        }
+        if new.throughput.is_none() {
+            new.throughput = old.throughput;
+        }
+    }
+    if new.encrypted && new.kms_key_id.is_none() {
+        new.kms_key_id = old.kms_key_id.clone();
+    }
+}
+
+/// Fields that EBS can't change on an existing volume.
+fn volume_fixed_fields(old: &Volume, new: &Volume) -> Vec<String> {
+    let mut fields = Vec::new();
+    if old.availability_zone != new.availability_zone {
+        fields.push(String::from("availability_zone"));
+    }
+    if old.encrypted != new.encrypted || old.kms_key_id != new.kms_key_id {
+        fields.push(String::from("encrypted"));
+    }
+    if old.snapshot_id != new.snapshot_id {
+        fields.push(String::from("snapshot_id"));
+    }
+    fields
+}
+
+/// Combinations that Data Lifecycle Manager would reject, caught at plan time instead.
+fn check_lifecycle_policy(policy_id: &str, policy: &LifecyclePolicy) -> anyhow::Result<()> {
+    if !matches!(policy.resource_type.as_str(), "VOLUME" | "INSTANCE") {
+        bail!(
+            "Lifecycle policy {} has resource_type {}: expected VOLUME or INSTANCE",
+            policy_id,
+            policy.resource_type
+        );
+    }
+    if policy.target_tags.is_empty() {
+        bail!(
+            "Lifecycle policy {} has no target_tags: it needs at least one to select what to snapshot",
+            policy_id
+        );
+    }
+    if policy.schedules.is_empty() || policy.schedules.len() > 4 {
+        bail!(
+            "Lifecycle policy {} has {} schedules: expected 1 to 4",
+            policy_id,
+            policy.schedules.len()
+        );
+    }
+
+    for schedule in &policy.schedules {
+        let name = &schedule.name;
+
+        match (schedule.interval_hours, &schedule.cron_expression) {
+            (Some(interval_hours), None) => {
+                if !INTERVAL_HOURS.contains(&interval_hours) {
+                    bail!(
+                        "Lifecycle policy {} schedule {} has interval_hours {}: expected one of 1, 2, 3, 4, 6, 8, 12 or 24",
+                        policy_id,
+                        name,
+                        interval_hours
+                    );
+                }
+            }
+            (None, Some(_)) => {
+                if !schedule.times.is_empty() {
+                    bail!(
+                        "Lifecycle policy {} schedule {} sets times, which only interval schedules support",
+                        policy_id,
+                        name
+                    );
+                }
+            }
+            _ => bail!(
+                "Lifecycle policy {} schedule {} must set exactly one of interval_hours and cron_expression",
+                policy_id,
+                name
+            ),
+        }
+
+        match (schedule.retain_count, schedule.retain_interval, &schedule.retain_interval_unit) {
+            (Some(retain_count), None, None) => {
+                if !(1..=1000).contains(&retain_count) {
+                    bail!(
+                        "Lifecycle policy {} schedule {} has retain_count {}: expected 1 to 1000",
+                        policy_id,
+                        name,
+                        retain_count
+                    );
+                }
+            }
+            (None, Some(_), Some(unit)) => {
+                if !RETAIN_INTERVAL_UNITS.contains(&unit.as_str()) {
+                    bail!(
+                        "Lifecycle policy {} schedule {} has retain_interval_unit {}: expected one of {}",
+                        policy_id,
+                        name,
+                        unit,
+                        RETAIN_INTERVAL_UNITS.join(", ")
+                    );
+                }
+            }
+            _ => bail!(
+                "Lifecycle policy {} schedule {} must set either retain_count, or retain_interval with retain_interval_unit",
+                policy_id,
+                name
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+impl EbsConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = EbsResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            EbsResourceAddress::Volume { region, volume_id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_volume)) => {
+                    let new_volume: Volume = RON.from_str(&new_volume)?;
+                    check_volume(volume_id, &new_volume)?;
+
+                    let message = match &new_volume.attachment {
+                        Some(attachment) => format!(
+                            "Create new EBS volume {} in {} and attach it to EC2 instance {} at {}",
+                            volume_id, new_volume.availability_zone, attachment.instance_id, attachment.device_name
+                        ),
+                        None => format!("Create new EBS volume {} in {}", volume_id, new_volume.availability_zone),
+                    };
+                    Ok(vec![connector_op!(EbsConnectorOp::CreateVolume(new_volume), message)])
+                }
+                (Some(old_volume), None) => {
+                    let old_volume: Volume = RON.from_str(&old_volume)?;
+
+                    let mut ops = Vec::new();
+                    if let Some(attachment) = &old_volume.attachment {
+                        ops.push(connector_op!(
+                            EbsConnectorOp::DetachVolume,
+                            format!(
+                                "Detach EBS volume {} from EC2 instance {}. Unmount it first.",
+                                volume_id, attachment.instance_id
+                            )
+                        ));
+                    }
+                    ops.push(connector_op!(
+                        EbsConnectorOp::DeleteVolume,
+                        format!(
+                            "DELETE EBS volume {} in region {}. Its data is lost unless it has been snapshotted.",
+                            volume_id, region
+                        )
+                    ));
+                    Ok(ops)
+                }
+                (Some(old_volume), Some(new_volume)) => {
+                    let old_volume: Volume = RON.from_str(&old_volume)?;
+                    let mut new_volume: Volume = RON.from_str(&new_volume)?;
+                    check_volume(volume_id, &new_volume)?;
+                    normalize_volume(&old_volume, &mut new_volume);
+
+                    let fixed_fields = volume_fixed_fields(&old_volume, &new_volume);
+                    if !fixed_fields.is_empty() {
+                        bail!(
+                            "EBS volume {} can't change {} in place. Snapshot it, and create a new volume from the snapshot instead.",
+                            volume_id,
+                            fixed_fields.join(", ")
+                        );
+                    }
+
+                    if new_volume.size < old_volume.size {
+                        bail!(
+                            "EBS volume {} shrinks from {} to {} GiB: EBS volumes can only grow",
+                            volume_id,
+                            old_volume.size,
+                            new_volume.size
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_volume.tags != new_volume.tags {
+                        let diff = diff_ron_values(&old_volume.tags, &new_volume.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            EbsConnectorOp::UpdateVolumeTags(old_volume.tags.clone(), new_volume.tags.clone()),
+                            format!("Modify tags for EBS volume `{}`\n{}", volume_id, diff)
+                        ));
+                    }
+
+                    if old_volume.size != new_volume.size
+                        || old_volume.volume_type != new_volume.volume_type
+                        || old_volume.iops != new_volume.iops
+                        || old_volume.throughput != new_volume.throughput
+                    {
+                        let mut changes = Vec::new();
+                        if old_volume.size != new_volume.size {
+                            changes.push(format!("size from {} to {} GiB", old_volume.size, new_volume.size));
+                        }
+                        if old_volume.volume_type != new_volume.volume_type {
+                            changes.push(format!(
+                                "volume_type from {} to {}",
+                                old_volume.volume_type, new_volume.volume_type
+                            ));
+                        }
+                        if old_volume.iops != new_volume.iops {
+                            changes.push(String::from("iops"));
+                        }
+                        if old_volume.throughput != new_volume.throughput {
+                            changes.push(String::from("throughput"));
+                        }
+
+                        let mut message = format!(
+                            "Modify EBS volume `{}`: change {} (applied while in use; EBS allows one modification per volume every 6 hours)",
+                            volume_id,
+                            changes.join(", ")
+                        );
+                        if old_volume.size != new_volume.size {
+                            message.push_str(". Grow the file system afterwards to use the new space.");
+                        }
+
+                        ops.push(connector_op!(EbsConnectorOp::ModifyVolume(new_volume.clone()), message));
+                    }
+
+                    if old_volume.attachment != new_volume.attachment {
+                        if let Some(old_attachment) = &old_volume.attachment {
+                            ops.push(connector_op!(
+                                EbsConnectorOp::DetachVolume,
+                                format!(
+                                    "Detach EBS volume {} from EC2 instance {}. Unmount it first.",
+                                    volume_id, old_attachment.instance_id
+                                )
+                            ));
+                        }
+                        if let Some(new_attachment) = &new_volume.attachment {
+                            ops.push(connector_op!(
+                                EbsConnectorOp::AttachVolume(new_attachment.clone()),
+                                format!(
+                                    "Attach EBS volume {} to EC2 instance {} at {}",
+                                    volume_id, new_attachment.instance_id, new_attachment.device_name
+                                )
+                            ));
+                        }
+                    }
+
+                    Ok(ops)
+                }
+            },
+            EbsResourceAddress::LifecyclePolicy { region, policy_id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_policy)) => {
+                    let new_policy: LifecyclePolicy = RON.from_str(&new_policy)?;
+                    check_lifecycle_policy(policy_id, &new_policy)?;
+                    Ok(vec![connector_op!(
+                        EbsConnectorOp::CreateLifecyclePolicy(new_policy),
+                        format!("Create new lifecycle policy {} in region {}", policy_id, region)
+                    )])
+                }
+                (Some(_old_policy), None) => Ok(vec![connector_op!(
+                    EbsConnectorOp::DeleteLifecyclePolicy,
+                    format!(
+                        "DELETE lifecycle policy {} in region {}. Snapshots it already took are kept, but no longer expire.",
+                        policy_id, region
+                    )
+                )]),
+                (Some(old_policy), Some(new_policy)) => {
+                    let old_policy: LifecyclePolicy = RON.from_str(&old_policy)?;
+                    let new_policy: LifecyclePolicy = RON.from_str(&new_policy)?;
+                    check_lifecycle_policy(policy_id, &new_policy)?;
+
+                    let mut ops = Vec::new();
+
+                    if old_policy.tags != new_policy.tags {
+                        let diff = diff_ron_values(&old_policy.tags, &new_policy.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            EbsConnectorOp::UpdateLifecyclePolicyTags(old_policy.tags.clone(), new_policy.tags.clone()),
+                            format!("Modify tags for lifecycle policy `{}`\n{}", policy_id, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_policy.clone();
+                    old_settings.tags = new_policy.tags.clone();
+                    if old_settings != new_policy {
+                        let diff = diff_ron_values(&old_settings, &new_policy).unwrap_or_default();
+                        ops.push(connector_op!(
+                            EbsConnectorOp::UpdateLifecyclePolicy(new_policy),
+                            format!("Modify lifecycle policy `{}`\n{}", policy_id, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+        }
+    }
+}
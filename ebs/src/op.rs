@@ -0,0 +1,38 @@
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{LifecyclePolicy, Volume, VolumeAttachment},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum EbsConnectorOp {
+    CreateVolume(Volume),
+    /// Brings the volume's size, type, IOPS and throughput in line with the given volume.
+    ModifyVolume(Volume),
+    AttachVolume(VolumeAttachment),
+    DetachVolume,
+    UpdateVolumeTags(Tags, Tags),
+    DeleteVolume,
+
+    CreateLifecyclePolicy(LifecyclePolicy),
+    UpdateLifecyclePolicy(LifecyclePolicy),
+    UpdateLifecyclePolicyTags(Tags, Tags),
+    DeleteLifecyclePolicy,
+}
+
+impl ConnectorOp for EbsConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
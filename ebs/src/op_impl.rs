@@ -0,0 +1,340 @@
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::{Context, bail};
+use autoschematic_core::{connector::OpExecResponse, op_exec_output};
+use aws_sdk_dlm::types::SettablePolicyStateValues;
+use aws_sdk_ec2::{
+    error::ProvideErrorMetadata,
+    types::{ResourceType, Tag, TagSpecification, VolumeAttachmentState, VolumeState, VolumeType},
+};
+
+use crate::{
+    resource::{LifecyclePolicy, Volume, VolumeAttachment},
+    tags::{Tags, tag_diff},
+    util::to_policy_details,
+};
+
+const STATE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Volumes created from large snapshots can take a while to become available.
+const STATE_MAX_POLLS: usize = 120;
+
+/// Finds a volume by ID, treating deleted volumes as gone.
+pub async fn find_volume(client: &aws_sdk_ec2::Client, volume_id: &str) -> anyhow::Result<Option<aws_sdk_ec2::types::Volume>> {
+    let resp = match client.describe_volumes().volume_ids(volume_id).send().await {
+        Ok(resp) => resp,
+        Err(e) if e.code() == Some("InvalidVolume.NotFound") => {
+            return Ok(None);
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(resp
+        .volumes()
+        .iter()
+        .find(|volume| !matches!(volume.state, Some(VolumeState::Deleting | VolumeState::Deleted)))
+        .cloned())
+}
+
+/// The instance the volume is attached, or being attached, to.
+pub fn current_attachment(volume: &aws_sdk_ec2::types::Volume) -> Option<VolumeAttachment> {
+    volume
+        .attachments()
+        .iter()
+        .find(|attachment| {
+            matches!(
+                attachment.state,
+                Some(VolumeAttachmentState::Attached | VolumeAttachmentState::Attaching)
+            )
+        })
+        .and_then(|attachment| {
+            Some(VolumeAttachment {
+                instance_id: attachment.instance_id.clone()?,
+                device_name: attachment.device.clone()?,
+            })
+        })
+}
+
+/// Polls the volume until it reaches `target`.
+async fn wait_for_state(client: &aws_sdk_ec2::Client, volume_id: &str, target: VolumeState) -> anyhow::Result<()> {
+    for _ in 0..STATE_MAX_POLLS {
+        let Some(volume) = find_volume(client, volume_id).await? else {
+            bail!("EBS volume {} disappeared while waiting for it to become {}", volume_id, target.as_str());
+        };
+
+        match volume.state {
+            Some(ref state) if *state == target => {
+                // A volume is in-use as soon as attaching starts
+                let attaching = volume
+                    .attachments()
+                    .iter()
+                    .any(|attachment| attachment.state == Some(VolumeAttachmentState::Attaching));
+                if !attaching {
+                    return Ok(());
+                }
+            }
+            Some(VolumeState::Error) => bail!("EBS volume {} is in the error state", volume_id),
+            _ => {}
+        }
+
+        tokio::time::sleep(STATE_POLL_INTERVAL).await;
+    }
+
+    bail!("Timed out waiting for EBS volume {} to become {}", volume_id, target.as_str())
+}
+
+/// Creates the volume, waits for it to be available, and attaches it if it has an attachment.
+pub async fn create_volume(client: &aws_sdk_ec2::Client, volume: &Volume) -> anyhow::Result<OpExecResponse> {
+    let tag_specifications = if volume.tags.is_empty() {
+        None
+    } else {
+        Some(vec![
+            TagSpecification::builder()
+                .resource_type(ResourceType::Volume)
+                .set_tags(volume.tags.clone().into())
+                .build(),
+        ])
+    };
+
+    let resp = client
+        .create_volume()
+        .client_token(uuid::Uuid::new_v4().to_string())
+        .availability_zone(&volume.availability_zone)
+        .size(volume.size)
+        .volume_type(VolumeType::from(volume.volume_type.as_str()))
+        .set_iops(volume.iops)
+        .set_throughput(volume.throughput)
+        .encrypted(volume.encrypted)
+        .set_kms_key_id(volume.kms_key_id.clone())
+        .set_snapshot_id(volume.snapshot_id.clone())
+        .set_tag_specifications(tag_specifications)
+        .send()
+        .await?;
+
+    let volume_id = resp.volume_id.context("CreateVolume returned no volume ID")?;
+
+    wait_for_state(client, &volume_id, VolumeState::Available).await?;
+
+    let mut message = format!("Created EBS volume {}", volume_id);
+    if let Some(attachment) = &volume.attachment {
+        attach_volume(client, &volume_id, attachment).await?;
+        message = format!(
+            "{} and attached it to EC2 instance {} at {}",
+            message, attachment.instance_id, attachment.device_name
+        );
+    }
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("volume_id"), Some(volume_id))])),
+        friendly_message: Some(message),
+    })
+}
+
+pub async fn modify_volume(client: &aws_sdk_ec2::Client, volume_id: &str, volume: &Volume) -> anyhow::Result<OpExecResponse> {
+    let current = find_volume(client, volume_id)
+        .await?
+        .with_context(|| format!("EBS volume {} not found", volume_id))?;
+
+    let volume_type = VolumeType::from(volume.volume_type.as_str());
+    if current.size != Some(volume.size)
+        || current.volume_type.as_ref() != Some(&volume_type)
+        || (volume.iops.is_some() && volume.iops != current.iops)
+        || (volume.throughput.is_some() && volume.throughput != current.throughput)
+    {
+        client
+            .modify_volume()
+            .volume_id(volume_id)
+            .size(volume.size)
+            .volume_type(volume_type)
+            .set_iops(volume.iops)
+            .set_throughput(volume.throughput)
+            .send()
+            .await?;
+    }
+
+    op_exec_output!(format!("Modified EBS volume {}", volume_id))
+}
+
+pub async fn attach_volume(
+    client: &aws_sdk_ec2::Client,
+    volume_id: &str,
+    attachment: &VolumeAttachment,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .attach_volume()
+        .volume_id(volume_id)
+        .instance_id(&attachment.instance_id)
+        .device(&attachment.device_name)
+        .send()
+        .await?;
+
+    wait_for_state(client, volume_id, VolumeState::InUse).await?;
+
+    op_exec_output!(format!(
+        "Attached EBS volume {} to EC2 instance {} at {}",
+        volume_id, attachment.instance_id, attachment.device_name
+    ))
+}
+
+/// Detaches the volume from whichever instance it's attached to, and waits until it's available again.
+pub async fn detach_volume(client: &aws_sdk_ec2::Client, volume_id: &str) -> anyhow::Result<OpExecResponse> {
+    let current = find_volume(client, volume_id)
+        .await?
+        .with_context(|| format!("EBS volume {} not found", volume_id))?;
+
+    let Some(attachment) = current_attachment(&current) else {
+        return op_exec_output!(format!("EBS volume {} is already detached", volume_id));
+    };
+
+    client
+        .detach_volume()
+        .volume_id(volume_id)
+        .instance_id(&attachment.instance_id)
+        .send()
+        .await?;
+
+    wait_for_state(client, volume_id, VolumeState::Available).await?;
+
+    op_exec_output!(format!(
+        "Detached EBS volume {} from EC2 instance {}",
+        volume_id, attachment.instance_id
+    ))
+}
+
+pub async fn update_volume_tags(
+    client: &aws_sdk_ec2::Client,
+    volume_id: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let (delete_keys, tags_to_add) = tag_diff(old_tags, new_tags)?;
+
+    // A tag given with only a key is deleted whatever its value
+    if !delete_keys.is_empty() {
+        client
+            .delete_tags()
+            .resources(volume_id)
+            .set_tags(Some(delete_keys.into_iter().map(|key| Tag::builder().key(key).build()).collect()))
+            .send()
+            .await?;
+    }
+
+    if !tags_to_add.is_empty() {
+        client
+            .create_tags()
+            .resources(volume_id)
+            .set_tags(Some(tags_to_add))
+            .send()
+            .await?;
+    }
+
+    op_exec_output!(format!("Updated tags for EBS volume {}", volume_id))
+}
+
+pub async fn delete_volume(client: &aws_sdk_ec2::Client, volume_id: &str) -> anyhow::Result<OpExecResponse> {
+    client.delete_volume().volume_id(volume_id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("volume_id"), None)])),
+        friendly_message: Some(format!("Deleted EBS volume {}", volume_id)),
+    })
+}
+
+fn policy_state(policy: &LifecyclePolicy) -> SettablePolicyStateValues {
+    if policy.enabled {
+        SettablePolicyStateValues::Enabled
+    } else {
+        SettablePolicyStateValues::Disabled
+    }
+}
+
+pub async fn create_lifecycle_policy(client: &aws_sdk_dlm::Client, policy: &LifecyclePolicy) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_lifecycle_policy()
+        .description(&policy.description)
+        .execution_role_arn(&policy.execution_role_arn)
+        .state(policy_state(policy))
+        .policy_details(to_policy_details(policy)?)
+        .set_tags(policy.tags.clone().into())
+        .send()
+        .await?;
+
+    let policy_id = resp.policy_id.context("CreateLifecyclePolicy returned no policy ID")?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("policy_id"), Some(policy_id.clone()))])),
+        friendly_message: Some(format!("Created lifecycle policy {}", policy_id)),
+    })
+}
+
+pub async fn update_lifecycle_policy(
+    client: &aws_sdk_dlm::Client,
+    policy_id: &str,
+    policy: &LifecyclePolicy,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .update_lifecycle_policy()
+        .policy_id(policy_id)
+        .description(&policy.description)
+        .execution_role_arn(&policy.execution_role_arn)
+        .state(policy_state(policy))
+        .policy_details(to_policy_details(policy)?)
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Updated lifecycle policy {}", policy_id))
+}
+
+pub async fn update_lifecycle_policy_tags(
+    client: &aws_sdk_dlm::Client,
+    policy_id: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let policy_arn = client
+        .get_lifecycle_policy()
+        .policy_id(policy_id)
+        .send()
+        .await?
+        .policy
+        .and_then(|policy| policy.policy_arn)
+        .with_context(|| format!("Lifecycle policy {} not found", policy_id))?;
+
+    let (untag_keys, tags_to_add) = tag_diff(old_tags, new_tags)?;
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(&policy_arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !tags_to_add.is_empty() {
+        client
+            .tag_resource()
+            .resource_arn(&policy_arn)
+            .set_tags(Some(
+                tags_to_add
+                    .into_iter()
+                    .filter_map(|tag| Some((tag.key?, tag.value?)))
+                    .collect(),
+            ))
+            .send()
+            .await?;
+    }
+
+    op_exec_output!(format!("Updated tags for lifecycle policy {}", policy_id))
+}
+
+pub async fn delete_lifecycle_policy(client: &aws_sdk_dlm::Client, policy_id: &str) -> anyhow::Result<OpExecResponse> {
+    client.delete_lifecycle_policy().policy_id(policy_id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("policy_id"), None)])),
+        friendly_message: Some(format!(
+            "Deleted lifecycle policy {}. Snapshots it already took are kept.",
+            policy_id
+        )),
+    })
+}
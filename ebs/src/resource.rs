@@ -0,0 +1,130 @@
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::EbsResourceAddress, tags::Tags};
+
+fn default_volume_type() -> String {
+    String::from("gp3")
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_resource_type() -> String {
+    String::from("VOLUME")
+}
+
+/// A standalone EBS volume. Volumes created along with an instance belong to that instance in aws/ec2.
+///
+/// Size, type, IOPS, throughput, attachment and tags are changed in place. The availability zone,
+/// encryption and source snapshot are fixed at creation: changing them is refused rather than
+/// replacing the volume, since that would lose its data.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Volume {
+    /// e.g. us-east-1a. Must match the availability zone of any instance it's attached to.
+    pub availability_zone: String,
+    /// In GiB. Volumes can grow but not shrink.
+    pub size: i32,
+    /// gp3, gp2, io1, io2, st1, sc1 or standard.
+    #[serde(default = "default_volume_type")]
+    pub volume_type: String,
+    /// Provisioned IOPS, for gp3, io1 and io2 volumes. If None, gp3 volumes get the baseline of 3000.
+    pub iops: Option<i32>,
+    /// Throughput in MiB/s, for gp3 volumes. If None, gp3 volumes get the baseline of 125.
+    pub throughput: Option<i32>,
+    #[serde(default)]
+    pub encrypted: bool,
+    /// The KMS key to encrypt with. If None, encrypted volumes use the `aws/ebs` key.
+    pub kms_key_id: Option<String>,
+    /// The snapshot to create the volume from.
+    pub snapshot_id: Option<String>,
+    /// The instance to attach the volume to, if any.
+    pub attachment: Option<VolumeAttachment>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct VolumeAttachment {
+    pub instance_id: String,
+    /// e.g. /dev/sdf
+    pub device_name: String,
+}
+
+/// A Data Lifecycle Manager policy that snapshots the volumes, or instances, carrying its target tags
+/// and deletes old snapshots according to each schedule's retention.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LifecyclePolicy {
+    pub description: String,
+    /// The role DLM assumes, e.g. the AWSDataLifecycleManagerDefaultRole.
+    pub execution_role_arn: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// VOLUME to snapshot each targeted volume, or INSTANCE to take multi-volume snapshots of targeted instances.
+    #[serde(default = "default_resource_type")]
+    pub resource_type: String,
+    /// Volumes or instances with all of these tags are snapshotted.
+    pub target_tags: Tags,
+    /// Up to four schedules.
+    pub schedules: Vec<SnapshotSchedule>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SnapshotSchedule {
+    pub name: String,
+    /// Take a snapshot every this many hours: 1, 2, 3, 4, 6, 8, 12 or 24. Set this or cron_expression.
+    pub interval_hours: Option<i32>,
+    /// The UTC start time of interval schedules, e.g. ["09:00"].
+    #[serde(default)]
+    pub times: Vec<String>,
+    /// e.g. cron(0 5 ? * SUN *). Set this or interval_hours.
+    pub cron_expression: Option<String>,
+    /// Keep this many of the most recent snapshots. Set this or retain_interval.
+    pub retain_count: Option<i32>,
+    /// Keep snapshots for this long, in retain_interval_unit.
+    pub retain_interval: Option<i32>,
+    /// DAYS, WEEKS, MONTHS or YEARS.
+    pub retain_interval_unit: Option<String>,
+    /// Copy the source volume's tags to its snapshots.
+    #[serde(default)]
+    pub copy_tags: bool,
+    /// Tags to add to each snapshot.
+    #[serde(default)]
+    pub tags_to_add: Tags,
+}
+
+pub enum EbsResource {
+    Volume(Volume),
+    LifecyclePolicy(LifecyclePolicy),
+}
+
+impl Resource for EbsResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            EbsResource::Volume(volume) => Ok(RON.to_string_pretty(&volume, pretty_config)?.into()),
+            EbsResource::LifecyclePolicy(policy) => Ok(RON.to_string_pretty(&policy, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = EbsResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            EbsResourceAddress::Volume { .. } => Ok(EbsResource::Volume(RON.from_str(s)?)),
+            EbsResourceAddress::LifecyclePolicy { .. } => Ok(EbsResource::LifecyclePolicy(RON.from_str(s)?)),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use aws_sdk_ec2::types::Tag;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Tags(HashMap<String, String>);
+
+impl From<Option<Vec<Tag>>> for Tags {
+    fn from(value: Option<Vec<Tag>>) -> Self {
+        match value {
+            Some(mut tags) => {
+                let mut out_map = HashMap::new();
+                tags.sort_by_key(|t| t.key.clone());
+                for tag in tags {
+                    if let (Some(key), Some(value)) = (tag.key, tag.value) {
+                        out_map.insert(key, value);
+                    }
+                }
+                Tags(out_map)
+            }
+            None => Tags(HashMap::new()),
+        }
+    }
+}
+
+impl From<Tags> for Option<Vec<Tag>> {
+    fn from(val: Tags) -> Self {
+        let mut out_vec = Vec::new();
+
+        for (k, v) in val.0 {
+            out_vec.push(Tag::builder().key(k).value(v).build());
+        }
+
+        Some(out_vec)
+    }
+}
+
+impl From<Option<HashMap<String, String>>> for Tags {
+    fn from(value: Option<HashMap<String, String>>) -> Self {
+        Tags(value.unwrap_or_default())
+    }
+}
+
+impl From<Tags> for Option<HashMap<String, String>> {
+    fn from(val: Tags) -> Self {
+        Some(val.0)
+    }
+}
+
+impl From<Option<Vec<aws_sdk_dlm::types::Tag>>> for Tags {
+    fn from(value: Option<Vec<aws_sdk_dlm::types::Tag>>) -> Self {
+        Tags(value.unwrap_or_default().into_iter().map(|tag| (tag.key, tag.value)).collect())
+    }
+}
+
+impl Tags {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&String> {
+        self.0.get(key)
+    }
+
+    /// DLM's target and added tags are a list rather than a map.
+    pub fn to_dlm_tags(&self) -> anyhow::Result<Vec<aws_sdk_dlm::types::Tag>> {
+        let mut tags = Vec::new();
+        for (key, value) in &self.0 {
+            tags.push(aws_sdk_dlm::types::Tag::builder().key(key).value(value).build()?);
+        }
+        tags.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(tags)
+    }
+}
+
+// From a pair of hashmaps, determine the set of aws_ec2::Tag structs to pass to delete_tags and create_tags respectively
+pub fn tag_diff(old_tags: &Tags, new_tags: &Tags) -> anyhow::Result<(Vec<String>, Vec<Tag>)> {
+    let mut delete_keys = Vec::new();
+    for k in old_tags.0.keys() {
+        if !new_tags.0.contains_key(k) {
+            delete_keys.push(k.to_string());
+        }
+    }
+
+    let mut new_tagset = Vec::new();
+    for (key, new_value) in &new_tags.0 {
+        if !old_tags.0.contains_key(key) {
+            new_tagset.push(Tag::builder().key(key).value(new_value).build());
+        } else if let Some(old_value) = old_tags.0.get(key)
+            && old_value != new_value
+        {
+            new_tagset.push(Tag::builder().key(key).value(new_value).build());
+        }
+    }
+
+    Ok((delete_keys, new_tagset))
+}
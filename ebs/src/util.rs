@@ -0,0 +1,104 @@
+use std::path::Path;
+
+use anyhow::Context;
+use autoschematic_core::connector::ResourceAddress;
+use aws_sdk_dlm::types::{
+    CreateRule, IntervalUnitValues, PolicyDetails, PolicyTypeValues, ResourceTypeValues, RetainRule, RetentionIntervalUnitValues,
+    Schedule,
+};
+
+use crate::{
+    addr::EbsResourceAddress,
+    resource::{LifecyclePolicy, SnapshotSchedule},
+    tags::Tags,
+};
+
+pub fn get_phy_volume_id(prefix: &Path, region: &str, virt_volume_id: &str) -> anyhow::Result<Option<String>> {
+    let addr = EbsResourceAddress::Volume {
+        region:    region.to_string(),
+        volume_id: virt_volume_id.to_string(),
+    };
+
+    addr.get_output(prefix, "volume_id")
+}
+
+pub fn get_phy_policy_id(prefix: &Path, region: &str, virt_policy_id: &str) -> anyhow::Result<Option<String>> {
+    let addr = EbsResourceAddress::LifecyclePolicy {
+        region:    region.to_string(),
+        policy_id: virt_policy_id.to_string(),
+    };
+
+    addr.get_output(prefix, "policy_id")
+}
+
+pub fn to_policy_details(policy: &LifecyclePolicy) -> anyhow::Result<PolicyDetails> {
+    let mut schedules = Vec::new();
+    for schedule in &policy.schedules {
+        let create_rule = CreateRule::builder()
+            .set_interval(schedule.interval_hours)
+            .set_interval_unit(schedule.interval_hours.map(|_| IntervalUnitValues::Hours))
+            .set_times((!schedule.times.is_empty()).then(|| schedule.times.clone()))
+            .set_cron_expression(schedule.cron_expression.clone())
+            .build();
+
+        let retain_rule = RetainRule::builder()
+            .set_count(schedule.retain_count)
+            .set_interval(schedule.retain_interval)
+            .set_interval_unit(
+                schedule
+                    .retain_interval_unit
+                    .as_deref()
+                    .map(RetentionIntervalUnitValues::from),
+            )
+            .build();
+
+        schedules.push(
+            Schedule::builder()
+                .name(&schedule.name)
+                .create_rule(create_rule)
+                .retain_rule(retain_rule)
+                .copy_tags(schedule.copy_tags)
+                .set_tags_to_add(Some(schedule.tags_to_add.to_dlm_tags()?))
+                .build(),
+        );
+    }
+
+    Ok(PolicyDetails::builder()
+        .policy_type(PolicyTypeValues::EbsSnapshotManagement)
+        .resource_types(ResourceTypeValues::from(policy.resource_type.as_str()))
+        .set_target_tags(Some(policy.target_tags.to_dlm_tags()?))
+        .set_schedules(Some(schedules))
+        .build())
+}
+
+pub fn from_policy_details(details: PolicyDetails) -> anyhow::Result<(String, Tags, Vec<SnapshotSchedule>)> {
+    let resource_type = details
+        .resource_types
+        .and_then(|resource_types| resource_types.into_iter().next())
+        .context("Lifecycle policy has no resource type")?
+        .as_str()
+        .to_string();
+
+    let mut schedules = Vec::new();
+    for schedule in details.schedules.unwrap_or_default() {
+        let create_rule = schedule.create_rule;
+        let retain_rule = schedule.retain_rule;
+
+        schedules.push(SnapshotSchedule {
+            name: schedule.name.unwrap_or_default(),
+            interval_hours: create_rule.as_ref().and_then(|rule| rule.interval),
+            times: create_rule.as_ref().and_then(|rule| rule.times.clone()).unwrap_or_default(),
+            cron_expression: create_rule.as_ref().and_then(|rule| rule.cron_expression.clone()),
+            retain_count: retain_rule.as_ref().and_then(|rule| rule.count),
+            retain_interval: retain_rule.as_ref().and_then(|rule| rule.interval),
+            retain_interval_unit: retain_rule
+                .as_ref()
+                .and_then(|rule| rule.interval_unit.as_ref())
+                .map(|unit| unit.as_str().to_string()),
+            copy_tags: schedule.copy_tags.unwrap_or(false),
+            tags_to_add: Tags::from(schedule.tags_to_add),
+        });
+    }
+
+    Ok((resource_type, Tags::from(details.target_tags), schedules))
+}
@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum Ec2ResourceAddress {
+    Instance { region: String, instance_id: String },
+}
+
+impl ResourceAddress for Ec2ResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            Ec2ResourceAddress::Instance { region, instance_id } => {
+                PathBuf::from(format!("aws/ec2/{region}/instances/{instance_id}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "ec2", region, "instances", instance_id] if instance_id.ends_with(".ron") => {
+                let instance_id = instance_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(Ec2ResourceAddress::Instance {
+                    region: region.to_string(),
+                    instance_id,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for Ec2ResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![AddressPattern {
+            pattern:     "aws/ec2/<region>/instances/<instance_id>.ron",
+            description: "A single long-lived EC2 instance",
+            example:     "aws/ec2/us-east-1/instances/bastion.ron",
+        }]
+    }
+}
@@ -0,0 +1,221 @@
+pub use crate::addr::Ec2ResourceAddress;
+pub use crate::op::Ec2ConnectorOp;
+pub use crate::resource::Ec2Resource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse, VirtToPhyResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::Ec2ConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{BlockDevice, Instance};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct Ec2Connector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_ec2::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<Ec2ConnectorConfig>,
+    prefix: PathBuf,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl Ec2Connector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_ec2::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_ec2, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for Ec2Connector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = Ec2ResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(Ec2Connector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let ec2_config: Ec2ConnectorConfig = Ec2ConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = ec2_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(ec2_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = ec2_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn addr_virt_to_phy(&self, addr: &Path) -> anyhow::Result<VirtToPhyResponse> {
+        let addr = Ec2ResourceAddress::from_path(addr)?;
+
+        match &addr {
+            Ec2ResourceAddress::Instance { region, .. } => {
+                let Some(instance_id) = addr.get_output(&self.prefix, "instance_id")? else {
+                    return Ok(VirtToPhyResponse::NotPresent);
+                };
+                Ok(VirtToPhyResponse::Present(
+                    Ec2ResourceAddress::Instance {
+                        region: region.into(),
+                        instance_id,
+                    }
+                    .to_path_buf(),
+                ))
+            }
+        }
+    }
+
+    async fn addr_phy_to_virt(&self, addr: &Path) -> anyhow::Result<Option<PathBuf>> {
+        let addr = Ec2ResourceAddress::from_path(addr)?;
+
+        match &addr {
+            Ec2ResourceAddress::Instance { .. } => {
+                if let Some(instance_addr) = addr.phy_to_virt(&self.prefix)? {
+                    return Ok(Some(instance_addr.to_path_buf()));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // Instance skeleton with an encrypted gp3 root volume
+        res.push(skeleton!(
+            Ec2ResourceAddress::Instance {
+                region:      String::from("[region]"),
+                instance_id: String::from("[instance_name]"),
+            },
+            Ec2Resource::Instance(Instance {
+                image_id: String::from("[ami_id]"),
+                instance_type: String::from("t3.micro"),
+                subnet_id: String::from("[subnet_id]"),
+                key_name: None,
+                user_data: None,
+                iam_instance_profile: None,
+                security_group_ids: vec![String::from("[security_group_id]")],
+                block_device_mappings: vec![BlockDevice {
+                    device_name: String::from("/dev/xvda"),
+                    volume_size: Some(20),
+                    volume_type: Some(String::from("gp3")),
+                    iops: None,
+                    throughput: None,
+                    encrypted: true,
+                    kms_key_id: None,
+                    delete_on_termination: true,
+                }],
+                disable_api_termination: false,
+                tags: Tags::default(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = Ec2ResourceAddress::from_path(addr)?;
+
+        match addr {
+            Ec2ResourceAddress::Instance { .. } => ron_check_eq::<Instance>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = Ec2ResourceAddress::from_path(addr)?;
+
+        match addr {
+            Ec2ResourceAddress::Instance { .. } => ron_check_syntax::<Instance>(a),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_ec2::types::{InstanceAttributeName, InstanceStateName};
+
+use crate::{
+    addr::Ec2ResourceAddress,
+    op_impl::find_instance,
+    resource::{BlockDevice, Ec2Resource, Instance},
+    tags::Tags,
+    util::{decode_user_data, instance_profile_name_from_arn},
+};
+
+use super::Ec2Connector;
+
+impl Ec2Connector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = Ec2ResourceAddress::from_path(addr)?;
+
+        match &addr {
+            Ec2ResourceAddress::Instance { region, instance_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(instance) = find_instance(&client, instance_id).await? else {
+                    return Ok(None);
+                };
+
+                // An instance on its way out is as good as gone
+                if matches!(
+                    instance.state.as_ref().and_then(|state| state.name.as_ref()),
+                    Some(InstanceStateName::ShuttingDown)
+                ) {
+                    return Ok(None);
+                }
+
+                let user_data = client
+                    .describe_instance_attribute()
+                    .instance_id(instance_id)
+                    .attribute(InstanceAttributeName::UserData)
+                    .send()
+                    .await?
+                    .user_data
+                    .and_then(|user_data| user_data.value)
+                    .map(|encoded| decode_user_data(&encoded));
+
+                let disable_api_termination = client
+                    .describe_instance_attribute()
+                    .instance_id(instance_id)
+                    .attribute(InstanceAttributeName::DisableApiTermination)
+                    .send()
+                    .await?
+                    .disable_api_termination
+                    .and_then(|attr| attr.value)
+                    .unwrap_or(false);
+
+                let mut block_device_mappings = Vec::new();
+                for mapping in instance.block_device_mappings() {
+                    let (Some(device_name), Some(ebs)) = (&mapping.device_name, &mapping.ebs) else {
+                        continue;
+                    };
+                    let Some(volume_id) = &ebs.volume_id else {
+                        continue;
+                    };
+
+                    let Some(volume) = client
+                        .describe_volumes()
+                        .volume_ids(volume_id)
+                        .send()
+                        .await?
+                        .volumes
+                        .and_then(|volumes| volumes.into_iter().next())
+                    else {
+                        continue;
+                    };
+
+                    block_device_mappings.push(BlockDevice {
+                        device_name: device_name.clone(),
+                        volume_size: volume.size,
+                        volume_type: volume.volume_type.as_ref().map(|t| t.as_str().to_string()),
+                        iops: volume.iops,
+                        throughput: volume.throughput,
+                        encrypted: volume.encrypted.unwrap_or(false),
+                        kms_key_id: volume.kms_key_id.clone(),
+                        delete_on_termination: ebs.delete_on_termination.unwrap_or(false),
+                    });
+                }
+                block_device_mappings.sort_by(|a, b| a.device_name.cmp(&b.device_name));
+
+                let mut security_group_ids: Vec<String> =
+                    instance.security_groups().iter().filter_map(|sg| sg.group_id.clone()).collect();
+                security_group_ids.sort();
+
+                let ec2_instance = Instance {
+                    image_id: instance.image_id.clone().unwrap_or_default(),
+                    instance_type: instance
+                        .instance_type
+                        .as_ref()
+                        .map(|t| t.as_str().to_string())
+                        .unwrap_or_default(),
+                    subnet_id: instance.subnet_id.clone().unwrap_or_default(),
+                    key_name: instance.key_name.clone(),
+                    user_data,
+                    iam_instance_profile: instance
+                        .iam_instance_profile
+                        .as_ref()
+                        .and_then(|profile| profile.arn.as_deref())
+                        .map(instance_profile_name_from_arn),
+                    security_group_ids,
+                    block_device_mappings,
+                    disable_api_termination,
+                    tags: Tags::from(instance.tags.clone()),
+                };
+
+                get_resource_response!(
+                    Ec2Resource::Instance(ec2_instance),
+                    [
+                        (String::from("instance_id"), instance_id.clone()),
+                        (
+                            String::from("private_ip_address"),
+                            instance.private_ip_address.clone().unwrap_or_default()
+                        ),
+                        (
+                            String::from("public_ip_address"),
+                            instance.public_ip_address.clone().unwrap_or_default()
+                        )
+                    ]
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+use aws_sdk_ec2::types::Filter;
+
+use crate::addr::Ec2ResourceAddress;
+
+use super::Ec2Connector;
+
+impl Ec2Connector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            // Instances launched by an auto scaling group or a spot fleet come and go on their own,
+            // so only standalone instances are listed
+            let mut reservations = client
+                .describe_instances()
+                .filters(
+                    Filter::builder()
+                        .name("instance-state-name")
+                        .values("pending")
+                        .values("running")
+                        .values("stopping")
+                        .values("stopped")
+                        .build(),
+                )
+                .into_paginator()
+                .items()
+                .send();
+
+            while let Some(reservation) = reservations.next().await {
+                for instance in reservation?.instances() {
+                    let Some(instance_id) = &instance.instance_id else {
+                        continue;
+                    };
+
+                    let managed = instance
+                        .tags()
+                        .iter()
+                        .any(|tag| matches!(tag.key.as_deref(), Some("aws:autoscaling:groupName" | "aws:ec2spot:fleet-request-id")));
+                    if managed {
+                        continue;
+                    }
+
+                    results.push(
+                        Ec2ResourceAddress::Instance {
+                            region:      region.clone(),
+                            instance_id: instance_id.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
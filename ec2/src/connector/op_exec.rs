@@ -0,0 +1,49 @@
+use std::path::Path;
+
+use autoschematic_core::connector::{ConnectorOp, OpExecResponse, ResourceAddress};
+
+use crate::{addr::Ec2ResourceAddress, op::Ec2ConnectorOp, op_impl, util::get_phy_instance_id};
+
+use super::Ec2Connector;
+
+impl Ec2Connector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = Ec2ResourceAddress::from_path(addr)?;
+        let op = Ec2ConnectorOp::from_str(op)?;
+
+        match &addr {
+            Ec2ResourceAddress::Instance { region, instance_id } => {
+                let instance_id = get_phy_instance_id(&self.prefix, region, instance_id)?.unwrap_or(instance_id.into());
+
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    Ec2ConnectorOp::RunInstance(instance) => op_impl::run_instance(&client, &instance).await,
+                    Ec2ConnectorOp::ReplaceInstance(instance) => op_impl::replace_instance(&client, &instance_id, &instance).await,
+                    Ec2ConnectorOp::TerminateInstance => op_impl::terminate_instance(&client, &instance_id).await,
+                    Ec2ConnectorOp::StopAndModify {
+                        instance_type,
+                        user_data,
+                    } => {
+                        op_impl::stop_and_modify(&client, &instance_id, instance_type.as_deref(), user_data.as_deref()).await
+                    }
+                    Ec2ConnectorOp::ModifySecurityGroups(security_group_ids) => {
+                        op_impl::modify_security_groups(&client, &instance_id, &security_group_ids).await
+                    }
+                    Ec2ConnectorOp::SetInstanceProfile(instance_profile) => {
+                        op_impl::set_instance_profile(&client, &instance_id, instance_profile.as_deref()).await
+                    }
+                    Ec2ConnectorOp::SetDisableApiTermination(disable_api_termination) => {
+                        op_impl::set_disable_api_termination(&client, &instance_id, disable_api_termination).await
+                    }
+                    Ec2ConnectorOp::ModifyVolume(block_device) => {
+                        op_impl::modify_volume(&client, &instance_id, &block_device).await
+                    }
+                    Ec2ConnectorOp::UpdateTags(old_tags, new_tags) => {
+                        op_impl::update_tags(&client, &instance_id, &old_tags, &new_tags).await
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::{
+    resource::{BlockDevice, Instance},
+    util::block_device_replacement_fields,
+};
+
+use super::{Ec2Connector, Ec2ConnectorOp, Ec2ResourceAddress};
+
+const VOLUME_TYPES: &[&str] = &["gp3", "gp2", "io1", "io2", "st1", "sc1", "standard"];
+
+/// Combinations that EC2 would reject, caught at plan time instead.
+fn check_instance(instance_id: &str, instance: &Instance) -> anyhow::Result<()> {
+    for block_device in &instance.block_device_mappings {
+        let device_name = &block_device.device_name;
+
+        if let Some(volume_type) = &block_device.volume_type
+            && !VOLUME_TYPES.contains(&volume_type.as_str())
+        {
+            bail!(
+                "EC2 instance {} has volume_type {} at {}: expected one of {}",
+                instance_id,
+                volume_type,
+                device_name,
+                VOLUME_TYPES.join(", ")
+            );
+        }
+
+        let volume_type = block_device.volume_type.as_deref().unwrap_or("gp2");
+        if block_device.iops.is_some() && !matches!(volume_type, "gp3" | "io1" | "io2") {
+            bail!(
+                "EC2 instance {} sets iops at {}, which only gp3, io1 and io2 volumes support",
+                instance_id,
+                device_name
+            );
+        }
+        if block_device.throughput.is_some() && volume_type != "gp3" {
+            bail!(
+                "EC2 instance {} sets throughput at {}, which only gp3 volumes support",
+                instance_id,
+                device_name
+            );
+        }
+        if block_device.kms_key_id.is_some() && !block_device.encrypted {
+            bail!(
+                "EC2 instance {} sets kms_key_id at {}, which requires encrypted: true",
+                instance_id,
+                device_name
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Unset volume fields mean "use the default", so keep whatever EC2 picked for them,
+/// and compare security groups and volumes regardless of order.
+fn normalize_instance(old: &Instance, new: &mut Instance) {
+    new.security_group_ids.sort();
+
+    for new_device in &mut new.block_device_mappings {
+        let Some(old_device) = old.block_device_mappings.iter().find(|d| d.device_name == new_device.device_name) else {
+            continue;
+        };
+
+        if new_device.volume_size.is_none() {
+            new_device.volume_size = old_device.volume_size;
+        }
+        if new_device.volume_type.is_none() {
+            new_device.volume_type = old_device.volume_type.clone();
+        }
+        if new_device.iops.is_none() {
+            new_device.iops = old_device.iops;
+        }
+        if new_device.throughput.is_none() {
+            new_device.throughput = old_device.throughput;
+        }
+        if new_device.encrypted && new_device.kms_key_id.is_none() {
+            new_device.kms_key_id = old_device.kms_key_id.clone();
+        }
+    }
+    new.block_device_mappings.sort_by(|a, b| a.device_name.cmp(&b.device_name));
+}
+
+fn instance_replacement_fields(old: &Instance, new: &Instance) -> Vec<String> {
+    let mut fields = Vec::new();
+    if old.image_id != new.image_id {
+        fields.push(String::from("image_id"));
+    }
+    if old.subnet_id != new.subnet_id {
+        fields.push(String::from("subnet_id"));
+    }
+    if old.key_name != new.key_name {
+        fields.push(String::from("key_name"));
+    }
+    fields.extend(block_device_replacement_fields(
+        &old.block_device_mappings,
+        &new.block_device_mappings,
+    ));
+    fields
+}
+
+/// The volumes, paired with their current state, whose size, performance or delete_on_termination changed.
+fn changed_volumes<'a>(old: &'a Instance, new: &'a Instance) -> Vec<(&'a BlockDevice, &'a BlockDevice)> {
+    new.block_device_mappings
+        .iter()
+        .filter_map(|new_device| {
+            let old_device = old.block_device_mappings.iter().find(|d| d.device_name == new_device.device_name)?;
+            (old_device != new_device).then_some((old_device, new_device))
+        })
+        .collect()
+}
+
+impl Ec2Connector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = Ec2ResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            Ec2ResourceAddress::Instance { region, instance_id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_instance)) => {
+                    let new_instance: Instance = RON.from_str(&new_instance)?;
+                    check_instance(instance_id, &new_instance)?;
+                    Ok(vec![connector_op!(
+                        Ec2ConnectorOp::RunInstance(new_instance),
+                        format!("Launch new EC2 instance {} in region {}", instance_id, region)
+                    )])
+                }
+                (Some(old_instance), None) => {
+                    let old_instance: Instance = RON.from_str(&old_instance)?;
+                    if old_instance.disable_api_termination {
+                        bail!(
+                            "EC2 instance {} has termination protection enabled. Set `disable_api_termination: false` and apply that first.",
+                            instance_id
+                        );
+                    }
+                    Ok(vec![connector_op!(
+                        Ec2ConnectorOp::TerminateInstance,
+                        format!(
+                            "TERMINATE EC2 instance {} in region {}, along with its volumes that are deleted on termination",
+                            instance_id, region
+                        )
+                    )])
+                }
+                (Some(old_instance), Some(new_instance)) => {
+                    let old_instance: Instance = RON.from_str(&old_instance)?;
+                    let mut new_instance: Instance = RON.from_str(&new_instance)?;
+                    check_instance(instance_id, &new_instance)?;
+                    normalize_instance(&old_instance, &mut new_instance);
+
+                    let replaced_fields = instance_replacement_fields(&old_instance, &new_instance);
+                    if !replaced_fields.is_empty() {
+                        if old_instance.disable_api_termination {
+                            bail!(
+                                "EC2 instance {} needs replacing to change {}, but has termination protection enabled. \
+                                 Set `disable_api_termination: false` and apply that first.",
+                                instance_id,
+                                replaced_fields.join(", ")
+                            );
+                        }
+                        return Ok(vec![connector_op!(
+                            Ec2ConnectorOp::ReplaceInstance(new_instance),
+                            format!(
+                                "REPLACE EC2 instance `{}` (requires replacement: {}). Data on volumes deleted on termination will be lost.",
+                                instance_id,
+                                replaced_fields.join(", ")
+                            )
+                        )]);
+                    }
+
+                    let mut ops = Vec::new();
+
+                    // Changes applied while the instance keeps running
+                    if old_instance.tags != new_instance.tags {
+                        let diff = diff_ron_values(&old_instance.tags, &new_instance.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            Ec2ConnectorOp::UpdateTags(old_instance.tags.clone(), new_instance.tags.clone()),
+                            format!("Modify tags for EC2 instance `{}`\n{}", instance_id, diff)
+                        ));
+                    }
+
+                    if old_instance.security_group_ids != new_instance.security_group_ids {
+                        ops.push(connector_op!(
+                            Ec2ConnectorOp::ModifySecurityGroups(new_instance.security_group_ids.clone()),
+                            format!(
+                                "Set security groups for EC2 instance `{}` to [{}] (applied while running)",
+                                instance_id,
+                                new_instance.security_group_ids.join(", ")
+                            )
+                        ));
+                    }
+
+                    if old_instance.iam_instance_profile != new_instance.iam_instance_profile {
+                        let message = match &new_instance.iam_instance_profile {
+                            Some(name) => format!(
+                                "Set instance profile for EC2 instance `{}` to {} (applied while running)",
+                                instance_id, name
+                            ),
+                            None => format!(
+                                "Remove instance profile from EC2 instance `{}` (applied while running)",
+                                instance_id
+                            ),
+                        };
+                        ops.push(connector_op!(
+                            Ec2ConnectorOp::SetInstanceProfile(new_instance.iam_instance_profile.clone()),
+                            message
+                        ));
+                    }
+
+                    if old_instance.disable_api_termination != new_instance.disable_api_termination {
+                        ops.push(connector_op!(
+                            Ec2ConnectorOp::SetDisableApiTermination(new_instance.disable_api_termination),
+                            format!(
+                                "{} termination protection for EC2 instance `{}`",
+                                if new_instance.disable_api_termination { "Enable" } else { "Disable" },
+                                instance_id
+                            )
+                        ));
+                    }
+
+                    for (old_device, new_device) in changed_volumes(&old_instance, &new_instance) {
+                        if let (Some(old_size), Some(new_size)) = (old_device.volume_size, new_device.volume_size)
+                            && new_size < old_size
+                        {
+                            bail!(
+                                "EC2 instance {} shrinks the volume at {} from {} to {} GiB: EBS volumes can only grow",
+                                instance_id,
+                                new_device.device_name,
+                                old_size,
+                                new_size
+                            );
+                        }
+
+                        let diff = diff_ron_values(old_device, new_device).unwrap_or_default();
+                        ops.push(connector_op!(
+                            Ec2ConnectorOp::ModifyVolume(new_device.clone()),
+                            format!(
+                                "Modify volume {} on EC2 instance `{}` (applied while running; EBS allows one modification per volume every 6 hours)\n{}",
+                                new_device.device_name, instance_id, diff
+                            )
+                        ));
+                    }
+
+                    // Changes that need the instance stopped go last, so that a failed restart
+                    // doesn't hold up the changes above
+                    let instance_type_changed = old_instance.instance_type != new_instance.instance_type;
+                    let user_data_changed = old_instance.user_data != new_instance.user_data;
+                    if instance_type_changed || user_data_changed {
+                        let mut changes = Vec::new();
+                        if instance_type_changed {
+                            changes.push(format!(
+                                "instance_type from {} to {}",
+                                old_instance.instance_type, new_instance.instance_type
+                            ));
+                        }
+                        if user_data_changed {
+                            changes.push(String::from("user_data"));
+                        }
+
+                        ops.push(connector_op!(
+                            Ec2ConnectorOp::StopAndModify {
+                                instance_type: instance_type_changed.then(|| new_instance.instance_type.clone()),
+                                user_data:     user_data_changed
+                                    .then(|| new_instance.user_data.clone().unwrap_or_default()),
+                            },
+                            format!(
+                                "STOP EC2 instance `{}`, change {}, and start it again. \
+                                 Its public IP address changes unless it has an Elastic IP.",
+                                instance_id,
+                                changes.join(" and ")
+                            )
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+        }
+    }
+}
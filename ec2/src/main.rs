@@ -0,0 +1,25 @@
+use addr::Ec2ResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
+use autoschematic_core::tarpc_bridge::tarpc_connector_main;
+use connector::Ec2Connector;
+
+pub mod connector;
+pub mod addr;
+pub mod config;
+pub mod op;
+pub mod op_impl;
+pub mod resource;
+pub mod tags;
+pub mod util;
+
+#[tokio::main]
+pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let patterns = Ec2ResourceAddress::checked_address_patterns()?;
+        print!("{}", render_address_docs("aws/ec2", &patterns));
+        return Ok(());
+    }
+
+    tarpc_connector_main::<Ec2Connector>().await?;
+    Ok(())
+}
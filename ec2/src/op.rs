@@ -0,0 +1,44 @@
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{BlockDevice, Instance},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Ec2ConnectorOp {
+    RunInstance(Instance),
+    /// Terminates the current instance and launches a new one in its place.
+    ReplaceInstance(Instance),
+    TerminateInstance,
+
+    // Changes that need the instance stopped. Fields left as None are unchanged.
+    StopAndModify {
+        instance_type: Option<String>,
+        /// An empty string clears the user data.
+        user_data:     Option<String>,
+    },
+
+    // Changes applied while the instance runs
+    ModifySecurityGroups(Vec<String>),
+    SetInstanceProfile(Option<String>),
+    SetDisableApiTermination(bool),
+    ModifyVolume(BlockDevice),
+    UpdateTags(Tags, Tags),
+}
+
+impl ConnectorOp for Ec2ConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
@@ -0,0 +1,419 @@
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::{Context, bail};
+use autoschematic_core::{connector::OpExecResponse, op_exec_output};
+use aws_sdk_ec2::{
+    error::ProvideErrorMetadata,
+    types::{
+        AttributeBooleanValue, AttributeValue, BlobAttributeValue, EbsInstanceBlockDeviceSpecification, Filter,
+        IamInstanceProfileSpecification, InstanceBlockDeviceMappingSpecification, InstanceStateName, InstanceType,
+        ResourceType, Tag, TagSpecification, VolumeType,
+    },
+};
+use aws_smithy_types::Blob;
+
+use crate::{
+    resource::{BlockDevice, Instance},
+    tags::{Tags, tag_diff},
+    util::{encode_user_data, to_block_device_mapping},
+};
+
+const STATE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Instances usually start or stop within a couple of minutes, but large instances and
+/// Windows AMIs can take much longer.
+const STATE_MAX_POLLS: usize = 180;
+
+/// Finds an instance by ID, treating terminated instances as gone.
+pub async fn find_instance(
+    client: &aws_sdk_ec2::Client,
+    instance_id: &str,
+) -> anyhow::Result<Option<aws_sdk_ec2::types::Instance>> {
+    let resp = match client.describe_instances().instance_ids(instance_id).send().await {
+        Ok(resp) => resp,
+        Err(e) if e.code() == Some("InvalidInstanceID.NotFound") => {
+            return Ok(None);
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(resp
+        .reservations()
+        .iter()
+        .flat_map(|reservation| reservation.instances())
+        .find(|instance| {
+            !matches!(
+                instance.state.as_ref().and_then(|state| state.name.as_ref()),
+                Some(InstanceStateName::Terminated)
+            )
+        })
+        .cloned())
+}
+
+async fn instance_state(client: &aws_sdk_ec2::Client, instance_id: &str) -> anyhow::Result<Option<InstanceStateName>> {
+    Ok(find_instance(client, instance_id)
+        .await?
+        .and_then(|instance| instance.state.and_then(|state| state.name)))
+}
+
+/// Polls the instance until it reaches `target`, or until it's terminated if `target` is None.
+async fn wait_for_state(client: &aws_sdk_ec2::Client, instance_id: &str, target: Option<InstanceStateName>) -> anyhow::Result<()> {
+    for _ in 0..STATE_MAX_POLLS {
+        let state = instance_state(client, instance_id).await?;
+        if state == target {
+            return Ok(());
+        }
+        // An instance that fails to launch, e.g. for lack of capacity, goes straight to terminated
+        if target == Some(InstanceStateName::Running) && state.is_none() {
+            bail!("EC2 instance {} was terminated while waiting for it to start", instance_id);
+        }
+        tokio::time::sleep(STATE_POLL_INTERVAL).await;
+    }
+
+    match target {
+        Some(target) => bail!("Timed out waiting for EC2 instance {} to become {}", instance_id, target.as_str()),
+        None => bail!("Timed out waiting for EC2 instance {} to terminate", instance_id),
+    }
+}
+
+/// Launches the instance and waits for it to be running, so that its addresses are known.
+pub async fn run_instance(client: &aws_sdk_ec2::Client, instance: &Instance) -> anyhow::Result<OpExecResponse> {
+    let mut block_device_mappings = Vec::new();
+    for block_device in &instance.block_device_mappings {
+        block_device_mappings.push(to_block_device_mapping(block_device));
+    }
+
+    let tag_specifications = if instance.tags.is_empty() {
+        None
+    } else {
+        Some(vec![
+            TagSpecification::builder()
+                .resource_type(ResourceType::Instance)
+                .set_tags(instance.tags.clone().into())
+                .build(),
+        ])
+    };
+
+    let resp = client
+        .run_instances()
+        .client_token(uuid::Uuid::new_v4().to_string())
+        .image_id(&instance.image_id)
+        .instance_type(InstanceType::from(instance.instance_type.as_str()))
+        .min_count(1)
+        .max_count(1)
+        .subnet_id(&instance.subnet_id)
+        .set_key_name(instance.key_name.clone())
+        .set_user_data(instance.user_data.as_deref().map(encode_user_data))
+        .set_iam_instance_profile(
+            instance
+                .iam_instance_profile
+                .as_ref()
+                .map(|name| IamInstanceProfileSpecification::builder().name(name).build()),
+        )
+        .set_security_group_ids(Some(instance.security_group_ids.clone()))
+        .set_block_device_mappings(Some(block_device_mappings))
+        .disable_api_termination(instance.disable_api_termination)
+        .set_tag_specifications(tag_specifications)
+        .send()
+        .await?;
+
+    let instance_id = resp
+        .instances()
+        .first()
+        .and_then(|instance| instance.instance_id.clone())
+        .context("RunInstances returned no instance")?;
+
+    wait_for_state(client, &instance_id, Some(InstanceStateName::Running)).await?;
+
+    let running = find_instance(client, &instance_id).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("instance_id"), Some(instance_id.clone())),
+            (
+                String::from("private_ip_address"),
+                running.as_ref().and_then(|i| i.private_ip_address.clone()),
+            ),
+            (
+                String::from("public_ip_address"),
+                running.as_ref().and_then(|i| i.public_ip_address.clone()),
+            ),
+        ])),
+        friendly_message: Some(format!("Launched EC2 instance {}", instance_id)),
+    })
+}
+
+/// Terminates the instance and waits until it's gone, so that a replacement doesn't race it
+/// for its EBS volumes' device names or its place in a target group.
+pub async fn terminate_instance(client: &aws_sdk_ec2::Client, instance_id: &str) -> anyhow::Result<OpExecResponse> {
+    client.terminate_instances().instance_ids(instance_id).send().await?;
+
+    wait_for_state(client, instance_id, None).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("instance_id"), None),
+            (String::from("private_ip_address"), None),
+            (String::from("public_ip_address"), None),
+        ])),
+        friendly_message: Some(format!("Terminated EC2 instance {}", instance_id)),
+    })
+}
+
+pub async fn replace_instance(
+    client: &aws_sdk_ec2::Client,
+    instance_id: &str,
+    instance: &Instance,
+) -> anyhow::Result<OpExecResponse> {
+    terminate_instance(client, instance_id).await?;
+
+    let mut res = run_instance(client, instance).await?;
+    res.friendly_message = res
+        .friendly_message
+        .map(|msg| format!("Terminated EC2 instance {}. {}", instance_id, msg));
+    Ok(res)
+}
+
+/// Stops the instance, applies the changes that need it stopped, and starts it again.
+/// If the instance was already stopped, it's left stopped.
+pub async fn stop_and_modify(
+    client: &aws_sdk_ec2::Client,
+    instance_id: &str,
+    instance_type: Option<&str>,
+    user_data: Option<&str>,
+) -> anyhow::Result<OpExecResponse> {
+    let was_stopped = instance_state(client, instance_id).await? == Some(InstanceStateName::Stopped);
+
+    if !was_stopped {
+        client.stop_instances().instance_ids(instance_id).send().await?;
+        wait_for_state(client, instance_id, Some(InstanceStateName::Stopped)).await?;
+    }
+
+    if let Some(instance_type) = instance_type {
+        client
+            .modify_instance_attribute()
+            .instance_id(instance_id)
+            .instance_type(AttributeValue::builder().value(instance_type).build())
+            .send()
+            .await?;
+    }
+
+    // Unlike RunInstances, the SDK base64-encodes the blob itself here
+    if let Some(user_data) = user_data {
+        client
+            .modify_instance_attribute()
+            .instance_id(instance_id)
+            .user_data(BlobAttributeValue::builder().value(Blob::new(user_data.as_bytes())).build())
+            .send()
+            .await?;
+    }
+
+    if !was_stopped {
+        client.start_instances().instance_ids(instance_id).send().await?;
+        wait_for_state(client, instance_id, Some(InstanceStateName::Running)).await?;
+    }
+
+    // The public IP address changes when an instance without an Elastic IP restarts
+    let running = find_instance(client, instance_id).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (
+                String::from("private_ip_address"),
+                running.as_ref().and_then(|i| i.private_ip_address.clone()),
+            ),
+            (
+                String::from("public_ip_address"),
+                running.as_ref().and_then(|i| i.public_ip_address.clone()),
+            ),
+        ])),
+        friendly_message: Some(format!("Stopped, modified and restarted EC2 instance {}", instance_id)),
+    })
+}
+
+pub async fn modify_security_groups(
+    client: &aws_sdk_ec2::Client,
+    instance_id: &str,
+    security_group_ids: &[String],
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .modify_instance_attribute()
+        .instance_id(instance_id)
+        .set_groups(Some(security_group_ids.to_vec()))
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Set security groups for EC2 instance {}", instance_id))
+}
+
+/// Associates, replaces or removes the instance profile. An instance has at most one association.
+pub async fn set_instance_profile(
+    client: &aws_sdk_ec2::Client,
+    instance_id: &str,
+    instance_profile: Option<&str>,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .describe_iam_instance_profile_associations()
+        .filters(Filter::builder().name("instance-id").values(instance_id).build())
+        .filters(Filter::builder().name("state").values("associated").build())
+        .send()
+        .await?;
+
+    let association_id = resp
+        .iam_instance_profile_associations()
+        .first()
+        .and_then(|association| association.association_id.clone());
+
+    match (instance_profile, association_id) {
+        (Some(name), Some(association_id)) => {
+            client
+                .replace_iam_instance_profile_association()
+                .association_id(association_id)
+                .iam_instance_profile(IamInstanceProfileSpecification::builder().name(name).build())
+                .send()
+                .await?;
+        }
+        (Some(name), None) => {
+            client
+                .associate_iam_instance_profile()
+                .instance_id(instance_id)
+                .iam_instance_profile(IamInstanceProfileSpecification::builder().name(name).build())
+                .send()
+                .await?;
+        }
+        (None, Some(association_id)) => {
+            client
+                .disassociate_iam_instance_profile()
+                .association_id(association_id)
+                .send()
+                .await?;
+        }
+        (None, None) => {}
+    }
+
+    match instance_profile {
+        Some(name) => op_exec_output!(format!("Set instance profile for EC2 instance {} to {}", instance_id, name)),
+        None => op_exec_output!(format!("Removed instance profile from EC2 instance {}", instance_id)),
+    }
+}
+
+pub async fn set_disable_api_termination(
+    client: &aws_sdk_ec2::Client,
+    instance_id: &str,
+    disable_api_termination: bool,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .modify_instance_attribute()
+        .instance_id(instance_id)
+        .disable_api_termination(AttributeBooleanValue::builder().value(disable_api_termination).build())
+        .send()
+        .await?;
+
+    op_exec_output!(format!(
+        "{} termination protection for EC2 instance {}",
+        if disable_api_termination { "Enabled" } else { "Disabled" },
+        instance_id
+    ))
+}
+
+/// Modifies the size and performance of the volume at `block_device.device_name` in place,
+/// and whether it's deleted along with the instance.
+pub async fn modify_volume(
+    client: &aws_sdk_ec2::Client,
+    instance_id: &str,
+    block_device: &BlockDevice,
+) -> anyhow::Result<OpExecResponse> {
+    let Some(instance) = find_instance(client, instance_id).await? else {
+        bail!("EC2 instance {} not found", instance_id);
+    };
+
+    let Some(mapping) = instance
+        .block_device_mappings()
+        .iter()
+        .find(|mapping| mapping.device_name.as_deref() == Some(block_device.device_name.as_str()))
+    else {
+        bail!("EC2 instance {} has no volume at {}", instance_id, block_device.device_name);
+    };
+
+    let ebs = mapping.ebs.as_ref().context("Block device mapping has no EBS volume")?;
+    let volume_id = ebs.volume_id.clone().context("Block device mapping has no volume ID")?;
+
+    let volume = client
+        .describe_volumes()
+        .volume_ids(&volume_id)
+        .send()
+        .await?
+        .volumes
+        .and_then(|volumes| volumes.into_iter().next())
+        .with_context(|| format!("EBS volume {} not found", volume_id))?;
+
+    let volume_type = block_device.volume_type.as_deref().map(VolumeType::from);
+    if (block_device.volume_size.is_some() && block_device.volume_size != volume.size)
+        || (volume_type.is_some() && volume_type != volume.volume_type)
+        || (block_device.iops.is_some() && block_device.iops != volume.iops)
+        || (block_device.throughput.is_some() && block_device.throughput != volume.throughput)
+    {
+        client
+            .modify_volume()
+            .volume_id(&volume_id)
+            .set_size(block_device.volume_size)
+            .set_volume_type(volume_type)
+            .set_iops(block_device.iops)
+            .set_throughput(block_device.throughput)
+            .send()
+            .await?;
+    }
+
+    if ebs.delete_on_termination != Some(block_device.delete_on_termination) {
+        client
+            .modify_instance_attribute()
+            .instance_id(instance_id)
+            .block_device_mappings(
+                InstanceBlockDeviceMappingSpecification::builder()
+                    .device_name(&block_device.device_name)
+                    .ebs(
+                        EbsInstanceBlockDeviceSpecification::builder()
+                            .volume_id(&volume_id)
+                            .delete_on_termination(block_device.delete_on_termination)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .send()
+            .await?;
+    }
+
+    op_exec_output!(format!(
+        "Modified volume {} at {} on EC2 instance {}",
+        volume_id, block_device.device_name, instance_id
+    ))
+}
+
+pub async fn update_tags(
+    client: &aws_sdk_ec2::Client,
+    instance_id: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let (delete_keys, tags_to_add) = tag_diff(old_tags, new_tags)?;
+
+    // A tag given with only a key is deleted whatever its value
+    if !delete_keys.is_empty() {
+        client
+            .delete_tags()
+            .resources(instance_id)
+            .set_tags(Some(delete_keys.into_iter().map(|key| Tag::builder().key(key).build()).collect()))
+            .send()
+            .await?;
+    }
+
+    if !tags_to_add.is_empty() {
+        client
+            .create_tags()
+            .resources(instance_id)
+            .set_tags(Some(tags_to_add))
+            .send()
+            .await?;
+    }
+
+    op_exec_output!(format!("Updated tags for EC2 instance {}", instance_id))
+}
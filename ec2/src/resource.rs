@@ -0,0 +1,91 @@
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::Ec2ResourceAddress, tags::Tags};
+
+fn default_delete_on_termination() -> bool {
+    true
+}
+
+/// A single long-lived instance.
+///
+/// Changes fall into three groups: security groups, instance profile, termination protection, tags and
+/// EBS volume size and performance are applied while the instance runs; instance_type and user_data
+/// need the instance stopped, so changing them stops and restarts it; anything else replaces the instance.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Instance {
+    /// The AMI to launch from. Changing it replaces the instance.
+    pub image_id: String,
+    /// e.g. t3.micro. Changing it stops and restarts the instance.
+    pub instance_type: String,
+    /// The subnet to launch into. Changing it replaces the instance.
+    pub subnet_id: String,
+    /// The key pair to install for SSH. Changing it replaces the instance.
+    pub key_name: Option<String>,
+    /// The user data script, as plain text: the connector base64-encodes it.
+    /// Changing it stops and restarts the instance, but cloud-init only runs it again if the script asks to.
+    pub user_data: Option<String>,
+    /// The name of the instance profile to attach.
+    pub iam_instance_profile: Option<String>,
+    pub security_group_ids: Vec<String>,
+    /// The EBS volumes created along with the instance, including the root volume.
+    /// Volumes attached later are managed by the aws/ebs connector instead.
+    #[serde(default)]
+    pub block_device_mappings: Vec<BlockDevice>,
+    /// Stops the instance from being terminated through the API, including by this connector.
+    #[serde(default)]
+    pub disable_api_termination: bool,
+    pub tags: Tags,
+}
+
+/// An EBS volume created at launch. Adding, removing or renaming a device, or changing its encryption,
+/// replaces the instance; size, type, IOPS and throughput are modified in place.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BlockDevice {
+    /// e.g. /dev/xvda. Use the AMI's root device name to configure the root volume.
+    pub device_name: String,
+    /// In GiB. Volumes can grow but not shrink. If None, the AMI's snapshot size is used.
+    pub volume_size: Option<i32>,
+    /// gp3, gp2, io1, io2, st1, sc1 or standard. If None, EC2 picks gp2 or the AMI's type.
+    pub volume_type: Option<String>,
+    /// Provisioned IOPS, for gp3, io1 and io2 volumes.
+    pub iops: Option<i32>,
+    /// Throughput in MiB/s, for gp3 volumes.
+    pub throughput: Option<i32>,
+    #[serde(default)]
+    pub encrypted: bool,
+    /// The KMS key to encrypt with. If None, encrypted volumes use the `aws/ebs` key.
+    pub kms_key_id: Option<String>,
+    #[serde(default = "default_delete_on_termination")]
+    pub delete_on_termination: bool,
+}
+
+pub enum Ec2Resource {
+    Instance(Instance),
+}
+
+impl Resource for Ec2Resource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            Ec2Resource::Instance(instance) => Ok(RON.to_string_pretty(&instance, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = Ec2ResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            Ec2ResourceAddress::Instance { .. } => Ok(Ec2Resource::Instance(RON.from_str(s)?)),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use autoschematic_core::connector::ResourceAddress;
+use aws_sdk_ec2::types::{BlockDeviceMapping, EbsBlockDevice, VolumeType};
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+use crate::{addr::Ec2ResourceAddress, resource::BlockDevice};
+
+pub fn get_phy_instance_id(prefix: &Path, region: &str, virt_instance_id: &str) -> anyhow::Result<Option<String>> {
+    let addr = Ec2ResourceAddress::Instance {
+        region:      region.to_string(),
+        instance_id: virt_instance_id.to_string(),
+    };
+
+    addr.get_output(prefix, "instance_id")
+}
+
+pub fn encode_user_data(user_data: &str) -> String {
+    STANDARD.encode(user_data)
+}
+
+/// EC2 returns user data base64-encoded. Anything that isn't valid UTF-8, like gzipped user data,
+/// is kept in its encoded form so that it still round-trips.
+pub fn decode_user_data(encoded: &str) -> String {
+    match STANDARD.decode(encoded).ok().and_then(|bytes| String::from_utf8(bytes).ok()) {
+        Some(user_data) => user_data,
+        None => encoded.to_string(),
+    }
+}
+
+/// Turns `arn:aws:iam::123456789012:instance-profile/path/name` into `name`.
+pub fn instance_profile_name_from_arn(arn: &str) -> String {
+    match arn.rsplit_once('/') {
+        Some((_, name)) => name.to_string(),
+        None => arn.to_string(),
+    }
+}
+
+pub fn to_block_device_mapping(block_device: &BlockDevice) -> BlockDeviceMapping {
+    BlockDeviceMapping::builder()
+        .device_name(&block_device.device_name)
+        .ebs(
+            EbsBlockDevice::builder()
+                .set_volume_size(block_device.volume_size)
+                .set_volume_type(block_device.volume_type.as_deref().map(VolumeType::from))
+                .set_iops(block_device.iops)
+                .set_throughput(block_device.throughput)
+                .encrypted(block_device.encrypted)
+                .set_kms_key_id(block_device.kms_key_id.clone())
+                .delete_on_termination(block_device.delete_on_termination)
+                .build(),
+        )
+        .build()
+}
+
+/// Device names that are only in one of the two lists, or whose encryption differs:
+/// these can only be changed by launching a new instance.
+pub fn block_device_replacement_fields(old: &[BlockDevice], new: &[BlockDevice]) -> Vec<String> {
+    let mut fields = Vec::new();
+
+    for new_device in new {
+        match old.iter().find(|d| d.device_name == new_device.device_name) {
+            None => fields.push(format!("block_device_mappings[{}] (added)", new_device.device_name)),
+            Some(old_device) => {
+                if old_device.encrypted != new_device.encrypted || old_device.kms_key_id != new_device.kms_key_id {
+                    fields.push(format!("block_device_mappings[{}].encrypted", new_device.device_name));
+                }
+            }
+        }
+    }
+
+    for old_device in old {
+        if !new.iter().any(|d| d.device_name == old_device.device_name) {
+            fields.push(format!("block_device_mappings[{}] (removed)", old_device.device_name));
+        }
+    }
+
+    fields
+}
@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
 use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 
 #[derive(Debug, Clone)]
@@ -9,6 +10,9 @@ pub enum EcrResourceAddress {
     LifecyclePolicy { region: String, name: String },
     RegistryPolicy { region: String },
     PullThroughCacheRule { region: String, prefix: String },
+    ReplicationConfiguration { region: String },
+    RegistryScanningConfiguration { region: String },
+    PublicRepository { name: String },
 }
 
 impl ResourceAddress for EcrResourceAddress {
@@ -27,6 +31,13 @@ impl ResourceAddress for EcrResourceAddress {
             EcrResourceAddress::PullThroughCacheRule { region, prefix } => {
                 PathBuf::from(format!("aws/ecr/{region}/pull_through_cache_rules/{prefix}.ron"))
             }
+            EcrResourceAddress::ReplicationConfiguration { region } => {
+                PathBuf::from(format!("aws/ecr/{region}/replication_configuration.ron"))
+            }
+            EcrResourceAddress::RegistryScanningConfiguration { region } => {
+                PathBuf::from(format!("aws/ecr/{region}/registry_scanning_configuration.ron"))
+            }
+            EcrResourceAddress::PublicRepository { name } => PathBuf::from(format!("aws/ecr/public/repositories/{name}.ron")),
         }
     }
 
@@ -34,6 +45,11 @@ impl ResourceAddress for EcrResourceAddress {
         let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
 
         match &path_components[..] {
+            // ECR Public isn't regional, so it sits where a region would
+            ["aws", "ecr", "public", "repositories", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(EcrResourceAddress::PublicRepository { name })
+            }
             ["aws", "ecr", region, "repositories", name] if name.ends_with(".ron") => {
                 let name = name.strip_suffix(".ron").unwrap().to_string();
                 Ok(EcrResourceAddress::Repository {
@@ -54,6 +70,14 @@ impl ResourceAddress for EcrResourceAddress {
             ["aws", "ecr", region, "registry_policy.ron"] => Ok(EcrResourceAddress::RegistryPolicy {
                 region: region.to_string(),
             }),
+            ["aws", "ecr", region, "replication_configuration.ron"] => Ok(EcrResourceAddress::ReplicationConfiguration {
+                region: region.to_string(),
+            }),
+            ["aws", "ecr", region, "registry_scanning_configuration.ron"] => {
+                Ok(EcrResourceAddress::RegistryScanningConfiguration {
+                    region: region.to_string(),
+                })
+            }
             ["aws", "ecr", region, "pull_through_cache_rules", prefix] if prefix.ends_with(".ron") => {
                 let prefix = prefix.strip_suffix(".ron").unwrap().to_string();
                 Ok(EcrResourceAddress::PullThroughCacheRule {
@@ -65,3 +89,50 @@ impl ResourceAddress for EcrResourceAddress {
         }
     }
 }
+
+impl DescribeAddresses for EcrResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/ecr/<region>/repositories/<name>.ron",
+                description: "An ECR repository",
+                example:     "aws/ecr/us-east-1/repositories/web.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ecr/<region>/repositories/<name>/policy.ron",
+                description: "A repository's permissions policy",
+                example:     "aws/ecr/us-east-1/repositories/web/policy.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ecr/<region>/repositories/<name>/lifecycle_policy.ron",
+                description: "A repository's lifecycle policy",
+                example:     "aws/ecr/us-east-1/repositories/web/lifecycle_policy.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ecr/<region>/registry_policy.ron",
+                description: "The registry's permissions policy",
+                example:     "aws/ecr/us-east-1/registry_policy.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ecr/<region>/pull_through_cache_rules/<prefix>.ron",
+                description: "A pull through cache rule",
+                example:     "aws/ecr/us-east-1/pull_through_cache_rules/docker-hub.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ecr/<region>/replication_configuration.ron",
+                description: "The registry's replication rules",
+                example:     "aws/ecr/us-east-1/replication_configuration.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ecr/<region>/registry_scanning_configuration.ron",
+                description: "The registry's scan type and scanning rules",
+                example:     "aws/ecr/us-east-1/registry_scanning_configuration.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ecr/public/repositories/<name>.ron",
+                description: "An ECR Public repository and its gallery metadata",
+                example:     "aws/ecr/public/repositories/cli.ron",
+            },
+        ]
+    }
+}
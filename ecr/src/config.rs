@@ -8,6 +8,26 @@ use autoschematic_connector_aws_core::{
 use autoschematic_core::util::RON;
 use serde::{Deserialize, Serialize};
 
+use crate::resource::EncryptionConfiguration;
+
+/// Settings applied to repositories whose files leave them unset.
+/// New repositories are created with them, and existing repositories that deviate
+/// are brought back in line at plan time.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RepositoryDefaults {
+    /// ECR can't change a repository's encryption after creation, so existing repositories
+    /// encrypted differently fail to plan until they're recreated or their file sets
+    /// `encryption_configuration` explicitly.
+    #[serde(default)]
+    pub encryption: Option<EncryptionConfiguration>,
+    /// MUTABLE or IMMUTABLE
+    #[serde(default)]
+    pub image_tag_mutability: Option<String>,
+    #[serde(default)]
+    pub scan_on_push: Option<bool>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct EcrConnectorConfig {
     pub account_id:      Option<String>,
@@ -15,6 +35,10 @@ pub struct EcrConnectorConfig {
     pub timeout_config:  Option<TimeoutConfig>,
     pub sts_region:      String,
     pub enabled_regions: Vec<String>,
+    #[serde(default)]
+    pub max_concurrent_ops: Option<usize>,
+    #[serde(default)]
+    pub repository_defaults: RepositoryDefaults,
 }
 
-impl_aws_config!(EcrConnectorConfig, "aws/ecr/config.ron");
+impl_aws_config!(EcrConnectorConfig, "aws/ecr/config.ron", repository_defaults);
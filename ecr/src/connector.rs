@@ -6,6 +6,8 @@ pub mod get;
 pub mod list;
 pub mod op_exec;
 pub mod plan;
+pub mod task_exec;
+pub mod verify_replication;
 
 use std::{
     collections::HashMap,
@@ -24,7 +26,7 @@ use autoschematic_core::{
 use autoschematic_core::{
     connector::{
         Connector, ConnectorOp, ConnectorOutbox, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource, ResourceAddress,
-        SkeletonResponse,
+        SkeletonResponse, TaskExecResponse,
     },
     diag::DiagnosticResponse,
     util::RON,
@@ -35,15 +37,20 @@ use crate::config::EcrConnectorConfig;
 use tokio::sync::Mutex;
 
 use crate::resource::{
-    EncryptionConfiguration, ImageScanningConfiguration, LifecyclePolicy, PullThroughCacheRule, RegistryPolicy, Repository,
-    RepositoryPolicy,
+    CatalogData, EncryptionConfiguration, ImageScanningConfiguration, LifecyclePolicy, PublicRepository, PullThroughCacheRule,
+    RegistryPolicy, RegistryScanningConfiguration, RegistryScanningRule, ReplicationConfiguration, ReplicationDestination,
+    ReplicationRule, Repository, RepositoryPolicy,
 };
 use crate::tags::Tags;
-use autoschematic_connector_aws_core::config::AwsServiceConfig;
+use crate::task::{EcrTask, EcrTaskAddress, EnforceRepositoryPolicy, VerifyReplication};
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
 
 #[derive(Default)]
 pub struct EcrConnector {
     client_cache: Mutex<HashMap<String, Arc<aws_sdk_ecr::Client>>>,
+    public_client: Mutex<Option<Arc<aws_sdk_ecrpublic::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
     account_id: Mutex<String>,
     config: Mutex<EcrConnectorConfig>,
     prefix: PathBuf,
@@ -68,7 +75,7 @@ impl EcrConnector {
                 )
                 .load()
                 .await;
-            let client = aws_sdk_ecr::Client::new(&config);
+            let client = audited_client!(aws_sdk_ecr, &config);
             cache.insert(region_s.to_string(), Arc::new(client));
         };
 
@@ -78,6 +85,22 @@ impl EcrConnector {
 
         Ok(client.clone())
     }
+
+    /// ECR Public isn't regional, and its API is served from us-east-1.
+    pub async fn get_or_init_public_client(&self) -> anyhow::Result<Arc<aws_sdk_ecrpublic::Client>> {
+        let mut public_client = self.public_client.lock().await;
+
+        if let Some(public_client) = &*public_client {
+            return Ok(public_client.clone());
+        }
+
+        let region = RegionProviderChain::first_try(Region::new("us-east-1"));
+        let config = aws_config::defaults(BehaviorVersion::latest()).region(region).load().await;
+        let new_client = Arc::new(audited_client!(aws_sdk_ecrpublic, &config));
+        *public_client = Some(new_client.clone());
+
+        Ok(new_client)
+    }
 }
 
 #[async_trait]
@@ -85,6 +108,8 @@ impl Connector for EcrConnector {
     async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
         if let Ok(_addr) = EcrResourceAddress::from_path(addr) {
             Ok(FilterResponse::Resource)
+        } else if let Ok(_addr) = EcrTaskAddress::from_path(addr) {
+            Ok(FilterResponse::Task)
         } else {
             Ok(FilterResponse::None)
         }
@@ -106,6 +131,9 @@ impl Connector for EcrConnector {
         let account_id = ecr_config.verify_sts().await?;
 
         *self.client_cache.lock().await = HashMap::new();
+        *self.public_client.lock().await = None;
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(ecr_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
         *self.config.lock().await = ecr_config;
         *self.account_id.lock().await = account_id;
         Ok(())
@@ -129,7 +157,20 @@ impl Connector for EcrConnector {
     }
 
     async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
-        self.do_op_exec(addr, op).await
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn task_exec(
+        &self,
+        addr: &Path,
+        body: Vec<u8>,
+
+        arg: Option<Vec<u8>>,
+        state: Option<Vec<u8>>,
+    ) -> anyhow::Result<TaskExecResponse> {
+        self.do_task_exec(addr, body, arg, state).await
     }
 
     async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
@@ -276,6 +317,93 @@ impl Connector for EcrConnector {
             })
         ));
 
+        // Replicate prod- repositories to a second region and to a backup account
+        res.push(skeleton!(
+            EcrResourceAddress::ReplicationConfiguration {
+                region: String::from("[region]"),
+            },
+            EcrResource::ReplicationConfiguration(ReplicationConfiguration {
+                rules: vec![ReplicationRule {
+                    destinations: vec![
+                        ReplicationDestination {
+                            region: String::from("[destination_region]"),
+                            registry_id: None,
+                        },
+                        ReplicationDestination {
+                            region: String::from("[region]"),
+                            registry_id: Some(String::from("[backup_account_id]")),
+                        },
+                    ],
+                    repository_filters: vec![String::from("prod-")],
+                }],
+            })
+        ));
+
+        // Enhanced scanning: continuous for prod- repositories, on push for the rest
+        res.push(skeleton!(
+            EcrResourceAddress::RegistryScanningConfiguration {
+                region: String::from("[region]"),
+            },
+            EcrResource::RegistryScanningConfiguration(RegistryScanningConfiguration {
+                scan_type: String::from("ENHANCED"),
+                rules: vec![
+                    RegistryScanningRule {
+                        scan_frequency: String::from("CONTINUOUS_SCAN"),
+                        repository_filters: vec![String::from("prod-*")],
+                    },
+                    RegistryScanningRule {
+                        scan_frequency: String::from("SCAN_ON_PUSH"),
+                        repository_filters: vec![String::from("*")],
+                    },
+                ],
+            })
+        ));
+
+        // Public repository skeleton, with its gallery metadata
+        res.push(skeleton!(
+            EcrResourceAddress::PublicRepository {
+                name: String::from("[repository_name]"),
+            },
+            EcrResource::PublicRepository(PublicRepository {
+                catalog_data: CatalogData {
+                    description: Some(String::from("[short_description]")),
+                    about_text: Some(String::from("# [repository_name]\n\n[what the image is for]")),
+                    usage_text: Some(String::from("docker pull public.ecr.aws/[registry_alias]/[repository_name]:latest")),
+                    architectures: vec![String::from("x86-64"), String::from("ARM 64")],
+                    operating_systems: vec![String::from("Linux")],
+                },
+                tags: Tags::default(),
+            })
+        ));
+
+        // Repository compliance task skeleton
+        res.push(skeleton!(
+            EcrTaskAddress::EnforceRepositoryPolicy {
+                name: String::from("[task_name]"),
+            },
+            EcrTask::EnforceRepositoryPolicy(EnforceRepositoryPolicy {
+                regions: None,
+                require_scan_on_push: true,
+                required_encryption: Some(EncryptionConfiguration {
+                    encryption_type: String::from("AES256"), // or "KMS"
+                    kms_key: None,
+                }),
+                fix: false, // When true, rewrites non-compliant repository files
+            })
+        ));
+
+        // Replication verification task skeleton
+        res.push(skeleton!(
+            EcrTaskAddress::VerifyReplication {
+                name: String::from("[task_name]"),
+            },
+            EcrTask::VerifyReplication(VerifyReplication {
+                source_region: String::from("[region]"),
+                repositories: None, // Defaults to every repository matched by a replication rule
+                recent_images: 5,
+            })
+        ));
+
         Ok(res)
     }
 
@@ -287,6 +415,9 @@ impl Connector for EcrConnector {
             EcrResourceAddress::LifecyclePolicy { region, name } => ron_check_eq::<LifecyclePolicy>(a, b),
             EcrResourceAddress::RegistryPolicy { region } => ron_check_eq::<RegistryPolicy>(a, b),
             EcrResourceAddress::PullThroughCacheRule { region, prefix } => ron_check_eq::<PullThroughCacheRule>(a, b),
+            EcrResourceAddress::ReplicationConfiguration { .. } => ron_check_eq::<ReplicationConfiguration>(a, b),
+            EcrResourceAddress::RegistryScanningConfiguration { .. } => ron_check_eq::<RegistryScanningConfiguration>(a, b),
+            EcrResourceAddress::PublicRepository { .. } => ron_check_eq::<PublicRepository>(a, b),
         }
     }
 
@@ -299,6 +430,9 @@ impl Connector for EcrConnector {
             EcrResourceAddress::LifecyclePolicy { region, name } => ron_check_syntax::<LifecyclePolicy>(a),
             EcrResourceAddress::RegistryPolicy { region } => ron_check_syntax::<RegistryPolicy>(a),
             EcrResourceAddress::PullThroughCacheRule { region, prefix } => ron_check_syntax::<PullThroughCacheRule>(a),
+            EcrResourceAddress::ReplicationConfiguration { .. } => ron_check_syntax::<ReplicationConfiguration>(a),
+            EcrResourceAddress::RegistryScanningConfiguration { .. } => ron_check_syntax::<RegistryScanningConfiguration>(a),
+            EcrResourceAddress::PublicRepository { .. } => ron_check_syntax::<PublicRepository>(a),
         }
     }
 }
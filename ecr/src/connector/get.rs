@@ -11,8 +11,9 @@ use autoschematic_core::{
 use crate::{
     addr::EcrResourceAddress,
     resource::{
-        EcrResource, EncryptionConfiguration, ImageScanningConfiguration, LifecyclePolicy, PullThroughCacheRule,
-        RegistryPolicy, Repository, RepositoryPolicy,
+        CatalogData, EcrResource, EncryptionConfiguration, ImageScanningConfiguration, LifecyclePolicy, PublicRepository,
+        PullThroughCacheRule, RegistryPolicy, RegistryScanningConfiguration, RegistryScanningRule, ReplicationConfiguration, ReplicationDestination,
+        ReplicationRule, Repository, RepositoryPolicy,
     },
     tags::Tags,
 };
@@ -213,6 +214,106 @@ impl EcrConnector {
                     }
                 }
             }
+            EcrResourceAddress::PublicRepository { name } => {
+                let client = self.get_or_init_public_client().await?;
+
+                let repository = match client.describe_repositories().repository_names(&name).send().await {
+                    Ok(resp) => resp.repositories.unwrap_or_default().into_iter().next(),
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_repository_not_found_exception()) => None,
+                    Err(e) => return Err(e.into()),
+                };
+                let Some(repository) = repository else {
+                    return Ok(None);
+                };
+
+                let catalog_data = client
+                    .get_repository_catalog_data()
+                    .repository_name(&name)
+                    .send()
+                    .await?
+                    .catalog_data
+                    .map(|data| CatalogData {
+                        description: data.description.filter(|d| !d.is_empty()),
+                        about_text: data.about_text.filter(|t| !t.is_empty()),
+                        usage_text: data.usage_text.filter(|t| !t.is_empty()),
+                        architectures: data.architectures.unwrap_or_default(),
+                        operating_systems: data.operating_systems.unwrap_or_default(),
+                    })
+                    .unwrap_or_default();
+
+                let tags = match &repository.repository_arn {
+                    Some(arn) => Tags::from(client.list_tags_for_resource().resource_arn(arn).send().await?.tags.unwrap_or_default()),
+                    None => Tags::default(),
+                };
+
+                get_resource_response!(
+                    EcrResource::PublicRepository(PublicRepository { catalog_data, tags }),
+                    [
+                        (String::from("repository_arn"), repository.repository_arn.unwrap_or_default()),
+                        (String::from("repository_uri"), repository.repository_uri.unwrap_or_default())
+                    ]
+                )
+            }
+            EcrResourceAddress::ReplicationConfiguration { region } => {
+                let client = self.get_or_init_client(&region).await?;
+                let account_id = self.account_id.lock().await.clone();
+
+                let registry = client.describe_registry().send().await?;
+                let rules = registry.replication_configuration.map(|c| c.rules).unwrap_or_default();
+                if rules.is_empty() {
+                    return Ok(None);
+                }
+
+                let config = ReplicationConfiguration {
+                    rules: rules
+                        .into_iter()
+                        .map(|rule| ReplicationRule {
+                            destinations:       rule
+                                .destinations
+                                .into_iter()
+                                .map(|destination| ReplicationDestination {
+                                    region:      destination.region,
+                                    registry_id: Some(destination.registry_id).filter(|id| *id != account_id),
+                                })
+                                .collect(),
+                            repository_filters: rule
+                                .repository_filters
+                                .unwrap_or_default()
+                                .into_iter()
+                                .map(|filter| filter.filter)
+                                .collect(),
+                        })
+                        .collect(),
+                };
+
+                get_resource_response!(EcrResource::ReplicationConfiguration(config))
+            }
+            EcrResourceAddress::RegistryScanningConfiguration { region } => {
+                let client = self.get_or_init_client(&region).await?;
+
+                let Some(scanning) = client.get_registry_scanning_configuration().send().await?.scanning_configuration else {
+                    return Ok(None);
+                };
+
+                let scan_type = scanning.scan_type.map(|t| t.as_str().to_string()).unwrap_or_else(|| String::from("BASIC"));
+                let rules: Vec<RegistryScanningRule> = scanning
+                    .rules
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|rule| RegistryScanningRule {
+                        scan_frequency:     rule.scan_frequency.as_str().to_string(),
+                        repository_filters: rule.repository_filters.into_iter().map(|filter| filter.filter).collect(),
+                    })
+                    .collect();
+
+                // Basic scanning without rules is where every registry starts
+                if scan_type == "BASIC" && rules.is_empty() {
+                    return Ok(None);
+                }
+
+                let config = RegistryScanningConfiguration { scan_type, rules };
+                get_resource_response!(EcrResource::RegistryScanningConfiguration(config))
+            }
         }
     }
 }
@@ -70,6 +70,33 @@ impl EcrConnector {
                 );
             }
 
+            let registry_resp = client.describe_registry().send().await?;
+            if registry_resp
+                .replication_configuration
+                .is_some_and(|config| !config.rules.is_empty())
+            {
+                results.push(
+                    EcrResourceAddress::ReplicationConfiguration {
+                        region: region_name.clone(),
+                    }
+                    .to_path_buf(),
+                );
+            }
+
+            // Basic scanning without rules is the default, so it isn't listed
+            let scanning_resp = client.get_registry_scanning_configuration().send().await?;
+            if let Some(scanning) = scanning_resp.scanning_configuration
+                && (scanning.scan_type.as_ref().is_some_and(|t| t.as_str() != "BASIC")
+                    || scanning.rules.as_ref().is_some_and(|rules| !rules.is_empty()))
+            {
+                results.push(
+                    EcrResourceAddress::RegistryScanningConfiguration {
+                        region: region_name.clone(),
+                    }
+                    .to_path_buf(),
+                );
+            }
+
             // List and add pull through cache rules
             let pull_through_cache_rules_resp = client.describe_pull_through_cache_rules().send().await;
             if let Ok(rules_resp) = pull_through_cache_rules_resp
@@ -88,6 +115,15 @@ impl EcrConnector {
                 }
         }
 
+        // ECR Public is global, so its repositories are listed once rather than per region
+        let public_client = self.get_or_init_public_client().await?;
+        let public_repositories_resp = public_client.describe_repositories().send().await?;
+        for repo in public_repositories_resp.repositories.unwrap_or_default() {
+            if let Some(name) = repo.repository_name {
+                results.push(EcrResourceAddress::PublicRepository { name }.to_path_buf());
+            }
+        }
+
         Ok(results)
     }
 }
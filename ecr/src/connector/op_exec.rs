@@ -95,6 +95,48 @@ impl EcrConnector {
                     _ => Err(invalid_op(&addr, &op)),
                 }
             }
+            EcrResourceAddress::PublicRepository { name } => {
+                let client = self.get_or_init_public_client().await?;
+
+                match op {
+                    EcrConnectorOp::CreatePublicRepository(repo) => op_impl::create_public_repository(&client, name, &repo).await,
+                    EcrConnectorOp::UpdatePublicRepositoryCatalogData(catalog_data) => {
+                        op_impl::update_public_repository_catalog_data(&client, name, &catalog_data).await
+                    }
+                    EcrConnectorOp::UpdatePublicRepositoryTags(old_tags, new_tags) => {
+                        op_impl::update_public_repository_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    EcrConnectorOp::DeletePublicRepository { force } => {
+                        op_impl::delete_public_repository(&client, name, force).await
+                    }
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            EcrResourceAddress::ReplicationConfiguration { region } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    EcrConnectorOp::SetReplicationConfiguration(config) => {
+                        let account_id = self.account_id.lock().await.clone();
+                        op_impl::set_replication_configuration(&client, &account_id, &config).await
+                    }
+                    EcrConnectorOp::DeleteReplicationConfiguration => op_impl::delete_replication_configuration(&client).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            EcrResourceAddress::RegistryScanningConfiguration { region } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    EcrConnectorOp::SetRegistryScanningConfiguration(config) => {
+                        op_impl::set_registry_scanning_configuration(&client, &config).await
+                    }
+                    EcrConnectorOp::ResetRegistryScanningConfiguration => {
+                        op_impl::reset_registry_scanning_configuration(&client).await
+                    }
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
         }
     }
 }
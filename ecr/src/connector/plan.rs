@@ -1,5 +1,7 @@
 use std::path::Path;
 
+use anyhow::bail;
+
 use autoschematic_core::{
     connector::{PlanResponseElement, ResourceAddress},
     connector_op,
@@ -8,10 +10,129 @@ use autoschematic_core::{
 
 use autoschematic_core::connector::ConnectorOp;
 
-use crate::resource::{LifecyclePolicy, PullThroughCacheRule, RegistryPolicy, Repository, RepositoryPolicy};
+use crate::{
+    config::RepositoryDefaults,
+    resource::{
+        CatalogData, ImageScanningConfiguration, LifecyclePolicy, PublicRepository, PullThroughCacheRule, RegistryPolicy,
+        RegistryScanningConfiguration, ReplicationConfiguration, Repository, RepositoryPolicy,
+    },
+    util::{check_repository_policy, describe_encryption, describe_external_access, encryption_satisfies},
+};
 
 use super::{EcrConnector, EcrConnectorOp, EcrResourceAddress};
 
+const SCAN_TYPES: &[&str] = &["BASIC", "ENHANCED"];
+const SCAN_FREQUENCIES: &[&str] = &["SCAN_ON_PUSH", "CONTINUOUS_SCAN"];
+const PUBLIC_ARCHITECTURES: &[&str] = &["ARM", "ARM 64", "x86", "x86-64"];
+const PUBLIC_OPERATING_SYSTEMS: &[&str] = &["Linux", "Windows"];
+
+fn check_replication_configuration(region: &str, config: &ReplicationConfiguration) -> anyhow::Result<()> {
+    if config.rules.is_empty() {
+        bail!(
+            "Replication configuration in {} has no rules. Delete the file to turn replication off instead.",
+            region
+        );
+    }
+    if config.rules.len() > 10 {
+        bail!("Replication configuration in {} has {} rules: ECR allows at most 10", region, config.rules.len());
+    }
+
+    let destination_count: usize = config.rules.iter().map(|rule| rule.destinations.len()).sum();
+    if destination_count > 25 {
+        bail!(
+            "Replication configuration in {} has {} destinations: ECR allows at most 25 across all rules",
+            region,
+            destination_count
+        );
+    }
+
+    for (i, rule) in config.rules.iter().enumerate() {
+        if rule.destinations.is_empty() {
+            bail!("Replication rule {} in {} has no destinations", i, region);
+        }
+        if rule.repository_filters.len() > 100 {
+            bail!("Replication rule {} in {} has more than 100 repository filters", i, region);
+        }
+    }
+    Ok(())
+}
+
+fn check_registry_scanning_configuration(region: &str, config: &RegistryScanningConfiguration) -> anyhow::Result<()> {
+    if !SCAN_TYPES.contains(&config.scan_type.as_str()) {
+        bail!(
+            "Registry scanning configuration in {} has scan_type {}: expected one of {}",
+            region,
+            config.scan_type,
+            SCAN_TYPES.join(", ")
+        );
+    }
+
+    for rule in &config.rules {
+        if !SCAN_FREQUENCIES.contains(&rule.scan_frequency.as_str()) {
+            bail!(
+                "Registry scanning rule in {} has scan_frequency {}: expected one of {}",
+                region,
+                rule.scan_frequency,
+                SCAN_FREQUENCIES.join(", ")
+            );
+        }
+        if config.scan_type == "BASIC" && rule.scan_frequency != "SCAN_ON_PUSH" {
+            bail!(
+                "Registry scanning rule in {} uses {}, but basic scanning only supports SCAN_ON_PUSH",
+                region,
+                rule.scan_frequency
+            );
+        }
+        if rule.repository_filters.is_empty() {
+            bail!(
+                "Registry scanning rule {} in {} has no repository filters: use `*` to match every repository",
+                rule.scan_frequency,
+                region
+            );
+        }
+    }
+    Ok(())
+}
+
+fn check_catalog_data(name: &str, catalog_data: &CatalogData) -> anyhow::Result<()> {
+    for architecture in &catalog_data.architectures {
+        if !PUBLIC_ARCHITECTURES.contains(&architecture.as_str()) {
+            bail!(
+                "ECR Public repository {} has architecture {}: expected one of {}",
+                name,
+                architecture,
+                PUBLIC_ARCHITECTURES.join(", ")
+            );
+        }
+    }
+    for operating_system in &catalog_data.operating_systems {
+        if !PUBLIC_OPERATING_SYSTEMS.contains(&operating_system.as_str()) {
+            bail!(
+                "ECR Public repository {} has operating system {}: expected one of {}",
+                name,
+                operating_system,
+                PUBLIC_OPERATING_SYSTEMS.join(", ")
+            );
+        }
+    }
+    if let Some(description) = &catalog_data.description
+        && description.chars().count() > 1024
+    {
+        bail!("ECR Public repository {} has a description longer than 1024 characters", name);
+    }
+    if let Some(about_text) = &catalog_data.about_text
+        && about_text.chars().count() > 10240
+    {
+        bail!("ECR Public repository {} has about_text longer than 10240 characters", name);
+    }
+    if let Some(usage_text) = &catalog_data.usage_text
+        && usage_text.chars().count() > 10240
+    {
+        bail!("ECR Public repository {} has usage_text longer than 10240 characters", name);
+    }
+    Ok(())
+}
+
 impl EcrConnector {
     pub async fn do_plan(
         &self,
@@ -29,11 +150,26 @@ impl EcrConnector {
                 match (current, desired) {
                     (None, None) => Ok(Vec::new()),
                     (None, Some(new_repo)) => {
-                        let new_repo: Repository = RON.from_str(&new_repo)?;
-                        Ok(vec![connector_op!(
-                            EcrConnectorOp::CreateRepository(new_repo),
-                            format!("Create new ECR repository {} in region {}", name, region)
-                        )])
+                        let mut new_repo: Repository = RON.from_str(&new_repo)?;
+                        let defaults = self.config.lock().await.repository_defaults.clone();
+
+                        let mut applied = apply_repository_defaults(&mut new_repo, &defaults);
+                        if new_repo.encryption_configuration.is_none()
+                            && let Some(encryption) = defaults.encryption
+                        {
+                            applied.push(format!("encryption {}", describe_encryption(Some(&encryption))));
+                            new_repo.encryption_configuration = Some(encryption);
+                        }
+
+                        let mut message = format!("Create new ECR repository {} in region {}", name, region);
+                        if !applied.is_empty() {
+                            message.push_str(&format!(
+                                "\nApplying repository_defaults from aws/ecr/config.ron: {}",
+                                applied.join(", ")
+                            ));
+                        }
+
+                        Ok(vec![connector_op!(EcrConnectorOp::CreateRepository(new_repo), message)])
                     }
                     (Some(_old_repo), None) => Ok(vec![connector_op!(
                         EcrConnectorOp::DeleteRepository { force: true },
@@ -41,7 +177,32 @@ impl EcrConnector {
                     )]),
                     (Some(old_repo), Some(new_repo)) => {
                         let old_repo: Repository = RON.from_str(&old_repo)?;
-                        let new_repo: Repository = RON.from_str(&new_repo)?;
+                        let mut new_repo: Repository = RON.from_str(&new_repo)?;
+                        let defaults = self.config.lock().await.repository_defaults.clone();
+
+                        if new_repo.encryption_configuration.is_none()
+                            && let Some(required) = &defaults.encryption
+                            && !encryption_satisfies(old_repo.encryption_configuration.as_ref(), required)
+                        {
+                            bail!(
+                                "ECR repository `{}` is encrypted with {}, but repository_defaults in aws/ecr/config.ron requires {}. \
+                                 ECR can't change a repository's encryption in place: recreate the repository, \
+                                 or set encryption_configuration in its file to keep the current encryption.",
+                                name,
+                                describe_encryption(old_repo.encryption_configuration.as_ref()),
+                                describe_encryption(Some(required))
+                            );
+                        }
+
+                        let applied = apply_repository_defaults(&mut new_repo, &defaults);
+                        let default_note = |field: &str| {
+                            if applied.iter().any(|a| a.starts_with(field)) {
+                                " (from repository_defaults in aws/ecr/config.ron)"
+                            } else {
+                                ""
+                            }
+                        };
+
                         let mut ops = Vec::new();
 
                         // Check for tag changes
@@ -60,7 +221,12 @@ impl EcrConnector {
                                     EcrConnectorOp::UpdateImageTagMutability {
                                         image_tag_mutability: mutability.clone(),
                                     },
-                                    format!("Update image tag mutability to {} for ECR repository `{}`", mutability, name)
+                                    format!(
+                                        "Update image tag mutability to {} for ECR repository `{}`{}",
+                                        mutability,
+                                        name,
+                                        default_note("image_tag_mutability")
+                                    )
                                 ));
                             }
 
@@ -72,8 +238,10 @@ impl EcrConnector {
                                         scan_on_push: scanning_config.scan_on_push,
                                     },
                                     format!(
-                                        "Update image scanning configuration (scan_on_push: {}) for ECR repository `{}`",
-                                        scanning_config.scan_on_push, name
+                                        "Update image scanning configuration (scan_on_push: {}) for ECR repository `{}`{}",
+                                        scanning_config.scan_on_push,
+                                        name,
+                                        default_note("scan_on_push")
                                     )
                                 ));
                             }
@@ -92,39 +260,56 @@ impl EcrConnector {
                     }
                 }
             }
-            EcrResourceAddress::RepositoryPolicy { region, name } => match (current, desired) {
-                (None, None) => Ok(Vec::new()),
-                (None, Some(new_policy)) => {
-                    let new_policy: RepositoryPolicy = RON.from_str(&new_policy)?;
-                    Ok(vec![connector_op!(
-                        EcrConnectorOp::SetRepositoryPolicy {
-                            policy_document: new_policy.policy_document,
-                        },
-                        format!("Create repository policy for ECR repository {} in region {}", name, region)
-                    )])
-                }
-                (Some(_old_policy), None) => Ok(vec![connector_op!(
-                    EcrConnectorOp::DeleteRepositoryPolicy,
-                    format!("DELETE repository policy for ECR repository {} in region {}", name, region)
-                )]),
-                (Some(old_policy), Some(new_policy)) => {
-                    let old_policy: RepositoryPolicy = RON.from_str(&old_policy)?;
-                    let new_policy: RepositoryPolicy = RON.from_str(&new_policy)?;
+            EcrResourceAddress::RepositoryPolicy { region, name } => {
+                let account_id = self.account_id.lock().await.clone();
+                match (current, desired) {
+                    (None, None) => Ok(Vec::new()),
+                    (None, Some(new_policy)) => {
+                        let new_policy: RepositoryPolicy = RON.from_str(&new_policy)?;
+                        check_repository_policy(name, &new_policy.policy_document)?;
+
+                        let mut message = format!("Create repository policy for ECR repository {} in region {}", name, region);
+                        if let Some(external_access) = describe_external_access(&new_policy.policy_document, &account_id)? {
+                            message.push_str(&format!("\n{}", external_access));
+                        }
 
-                    if old_policy.policy_document != new_policy.policy_document {
-                        let diff =
-                            diff_ron_values(&old_policy.policy_document, &new_policy.policy_document).unwrap_or_default();
                         Ok(vec![connector_op!(
                             EcrConnectorOp::SetRepositoryPolicy {
                                 policy_document: new_policy.policy_document,
                             },
-                            format!("Update repository policy for ECR repository `{}`\n{}", name, diff)
+                            message
                         )])
-                    } else {
-                        Ok(Vec::new())
+                    }
+                    (Some(_old_policy), None) => Ok(vec![connector_op!(
+                        EcrConnectorOp::DeleteRepositoryPolicy,
+                        format!("DELETE repository policy for ECR repository {} in region {}", name, region)
+                    )]),
+                    (Some(old_policy), Some(new_policy)) => {
+                        let old_policy: RepositoryPolicy = RON.from_str(&old_policy)?;
+                        let new_policy: RepositoryPolicy = RON.from_str(&new_policy)?;
+                        check_repository_policy(name, &new_policy.policy_document)?;
+
+                        if old_policy.policy_document != new_policy.policy_document {
+                            let diff =
+                                diff_ron_values(&old_policy.policy_document, &new_policy.policy_document).unwrap_or_default();
+
+                            let mut message = format!("Update repository policy for ECR repository `{}`\n{}", name, diff);
+                            if let Some(external_access) = describe_external_access(&new_policy.policy_document, &account_id)? {
+                                message.push_str(&format!("\n{}", external_access));
+                            }
+
+                            Ok(vec![connector_op!(
+                                EcrConnectorOp::SetRepositoryPolicy {
+                                    policy_document: new_policy.policy_document,
+                                },
+                                message
+                            )])
+                        } else {
+                            Ok(Vec::new())
+                        }
                     }
                 }
-            },
+            }
             EcrResourceAddress::LifecyclePolicy { region, name } => match (current, desired) {
                 (None, None) => Ok(Vec::new()),
                 (None, Some(new_policy)) => {
@@ -234,6 +419,132 @@ impl EcrConnector {
                     }
                 }
             }
+            EcrResourceAddress::PublicRepository { name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_repo)) => {
+                    let new_repo: PublicRepository = RON.from_str(&new_repo)?;
+                    check_catalog_data(name, &new_repo.catalog_data)?;
+                    Ok(vec![connector_op!(
+                        EcrConnectorOp::CreatePublicRepository(new_repo),
+                        format!("Create new ECR Public repository {}", name)
+                    )])
+                }
+                (Some(_old_repo), None) => Ok(vec![connector_op!(
+                    EcrConnectorOp::DeletePublicRepository { force: true },
+                    format!("DELETE ECR Public repository {} and every image in it", name)
+                )]),
+                (Some(old_repo), Some(new_repo)) => {
+                    let old_repo: PublicRepository = RON.from_str(&old_repo)?;
+                    let new_repo: PublicRepository = RON.from_str(&new_repo)?;
+                    check_catalog_data(name, &new_repo.catalog_data)?;
+
+                    let mut ops = Vec::new();
+
+                    if old_repo.tags != new_repo.tags {
+                        let diff = diff_ron_values(&old_repo.tags, &new_repo.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            EcrConnectorOp::UpdatePublicRepositoryTags(old_repo.tags, new_repo.tags),
+                            format!("Modify tags for ECR Public repository `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    if old_repo.catalog_data != new_repo.catalog_data {
+                        let diff = diff_ron_values(&old_repo.catalog_data, &new_repo.catalog_data).unwrap_or_default();
+                        ops.push(connector_op!(
+                            EcrConnectorOp::UpdatePublicRepositoryCatalogData(new_repo.catalog_data),
+                            format!("Modify gallery catalog data for ECR Public repository `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            EcrResourceAddress::ReplicationConfiguration { region } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_config)) => {
+                    let new_config: ReplicationConfiguration = RON.from_str(&new_config)?;
+                    check_replication_configuration(region, &new_config)?;
+                    Ok(vec![connector_op!(
+                        EcrConnectorOp::SetReplicationConfiguration(new_config),
+                        format!("Create ECR replication configuration in region {}", region)
+                    )])
+                }
+                (Some(_old_config), None) => Ok(vec![connector_op!(
+                    EcrConnectorOp::DeleteReplicationConfiguration,
+                    format!(
+                        "DELETE ECR replication configuration in region {}. Images already replicated are kept.",
+                        region
+                    )
+                )]),
+                (Some(old_config), Some(new_config)) => {
+                    let old_config: ReplicationConfiguration = RON.from_str(&old_config)?;
+                    let new_config: ReplicationConfiguration = RON.from_str(&new_config)?;
+                    check_replication_configuration(region, &new_config)?;
+
+                    if old_config == new_config {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_config, &new_config).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        EcrConnectorOp::SetReplicationConfiguration(new_config),
+                        format!("Update ECR replication configuration in region `{}`\n{}", region, diff)
+                    )])
+                }
+            },
+            EcrResourceAddress::RegistryScanningConfiguration { region } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_config)) => {
+                    let new_config: RegistryScanningConfiguration = RON.from_str(&new_config)?;
+                    check_registry_scanning_configuration(region, &new_config)?;
+                    Ok(vec![connector_op!(
+                        EcrConnectorOp::SetRegistryScanningConfiguration(new_config),
+                        format!("Set ECR registry scanning configuration in region {}", region)
+                    )])
+                }
+                (Some(_old_config), None) => Ok(vec![connector_op!(
+                    EcrConnectorOp::ResetRegistryScanningConfiguration,
+                    format!("Reset ECR registry scanning configuration in region {} to basic scanning with no rules", region)
+                )]),
+                (Some(old_config), Some(new_config)) => {
+                    let old_config: RegistryScanningConfiguration = RON.from_str(&old_config)?;
+                    let new_config: RegistryScanningConfiguration = RON.from_str(&new_config)?;
+                    check_registry_scanning_configuration(region, &new_config)?;
+
+                    if old_config == new_config {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_config, &new_config).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        EcrConnectorOp::SetRegistryScanningConfiguration(new_config),
+                        format!("Update ECR registry scanning configuration in region `{}`\n{}", region, diff)
+                    )])
+                }
+            },
         }
     }
 }
+
+/// Fills in the tag mutability and scan-on-push settings that `repo` leaves unset from `defaults`,
+/// returning a description of each setting that was filled in.
+/// Encryption is left to the caller, since it can only be applied at creation.
+fn apply_repository_defaults(repo: &mut Repository, defaults: &RepositoryDefaults) -> Vec<String> {
+    let mut applied = Vec::new();
+
+    if repo.image_tag_mutability.is_none()
+        && let Some(mutability) = &defaults.image_tag_mutability
+    {
+        applied.push(format!("image_tag_mutability {}", mutability));
+        repo.image_tag_mutability = Some(mutability.clone());
+    }
+
+    if repo.image_scanning_configuration.is_none()
+        && let Some(scan_on_push) = defaults.scan_on_push
+    {
+        applied.push(format!("scan_on_push {}", scan_on_push));
+        repo.image_scanning_configuration = Some(ImageScanningConfiguration { scan_on_push });
+    }
+
+    applied
+}
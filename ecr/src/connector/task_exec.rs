@@ -0,0 +1,151 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::{
+    connector::{Resource, ResourceAddress, TaskExecResponse},
+    util::RON,
+};
+
+use crate::{
+    addr::EcrResourceAddress,
+    resource::{EcrResource, EncryptionConfiguration, ImageScanningConfiguration, Repository},
+    task::{EcrTask, EcrTaskAddress, EnforceRepositoryPolicy},
+    util::{describe_encryption, encryption_satisfies},
+};
+
+use super::EcrConnector;
+
+impl EcrConnector {
+    pub async fn do_task_exec(
+        &self,
+        addr: &Path,
+        body: Vec<u8>,
+        _arg: Option<Vec<u8>>,
+        _state: Option<Vec<u8>>,
+    ) -> anyhow::Result<TaskExecResponse> {
+        let addr = EcrTaskAddress::from_path(addr)?;
+
+        let task = EcrTask::from_bytes(&addr, &body)?;
+        match task {
+            EcrTask::EnforceRepositoryPolicy(enforce) => self.enforce_repository_policy(enforce).await,
+            EcrTask::VerifyReplication(verify) => self.do_verify_replication_task(verify).await,
+        }
+    }
+
+    async fn enforce_repository_policy(&self, enforce: EnforceRepositoryPolicy) -> anyhow::Result<TaskExecResponse> {
+        let regions = match enforce.regions {
+            Some(regions) => regions,
+            None => self.config.lock().await.enabled_regions.clone(),
+        };
+
+        let mut violations = Vec::new();
+        let mut modified_files = Vec::new();
+
+        for region in regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut repositories = client.describe_repositories().into_paginator().items().send();
+            while let Some(repo) = repositories.next().await {
+                let repo = repo?;
+                let Some(name) = repo.repository_name else {
+                    continue;
+                };
+
+                let mut problems = Vec::new();
+
+                let scan_on_push = repo.image_scanning_configuration.map(|c| c.scan_on_push).unwrap_or(false);
+                if enforce.require_scan_on_push && !scan_on_push {
+                    problems.push(String::from("scan-on-push is disabled"));
+                }
+
+                if let Some(required) = &enforce.required_encryption {
+                    let current = repo.encryption_configuration.map(|c| EncryptionConfiguration {
+                        encryption_type: c.encryption_type.as_str().to_string(),
+                        kms_key: c.kms_key,
+                    });
+
+                    if !encryption_satisfies(current.as_ref(), required) {
+                        problems.push(format!(
+                            "encryption is {}, expected {}",
+                            describe_encryption(current.as_ref()),
+                            describe_encryption(Some(required))
+                        ));
+                    }
+                }
+
+                if problems.is_empty() {
+                    continue;
+                }
+
+                let repo_addr = EcrResourceAddress::Repository {
+                    region: region.clone(),
+                    name:   name.clone(),
+                };
+                violations.push(format!("{}: {}", repo_addr.to_path_buf().display(), problems.join(", ")));
+
+                if enforce.fix
+                    && let Some(path) = self.write_compliant_repository(&repo_addr, &enforce).await?
+                {
+                    modified_files.push(path);
+                }
+            }
+        }
+
+        let friendly_message = if violations.is_empty() {
+            String::from("All ECR repositories comply with the required scanning and encryption settings")
+        } else if enforce.fix {
+            format!(
+                "{} ECR repositories do not comply with the required settings; updated {} repository files:\n{}",
+                violations.len(),
+                modified_files.len(),
+                violations.join("\n")
+            )
+        } else {
+            format!(
+                "{} ECR repositories do not comply with the required settings:\n{}",
+                violations.len(),
+                violations.join("\n")
+            )
+        };
+
+        Ok(TaskExecResponse {
+            modified_files: if modified_files.is_empty() { None } else { Some(modified_files) },
+            friendly_message: Some(friendly_message),
+            ..Default::default()
+        })
+    }
+
+    /// Rewrites the repository file at `repo_addr` with the settings required by `enforce`.
+    /// If the repository has no file yet, its current remote state is used as the starting point.
+    async fn write_compliant_repository(
+        &self,
+        repo_addr: &EcrResourceAddress,
+        enforce: &EnforceRepositoryPolicy,
+    ) -> anyhow::Result<Option<PathBuf>> {
+        let path = repo_addr.to_path_buf();
+        let full_path = self.prefix.join(&path);
+
+        let mut repository: Repository = if full_path.is_file() {
+            RON.from_str(&std::fs::read_to_string(&full_path)?)?
+        } else {
+            let Some(get_resp) = self.do_get(&path).await? else {
+                return Ok(None);
+            };
+            RON.from_str(std::str::from_utf8(&get_resp.resource_definition)?)?
+        };
+
+        if enforce.require_scan_on_push {
+            repository.image_scanning_configuration = Some(ImageScanningConfiguration { scan_on_push: true });
+        }
+
+        if let Some(required) = &enforce.required_encryption {
+            repository.encryption_configuration = Some(required.clone());
+        }
+
+        if let Some(parent) = full_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&full_path, EcrResource::Repository(repository).to_bytes()?)?;
+
+        Ok(Some(path))
+    }
+}
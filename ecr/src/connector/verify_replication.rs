@@ -0,0 +1,232 @@
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::bail;
+use autoschematic_core::connector::TaskExecResponse;
+use aws_sdk_ecr::{
+    operation::describe_repositories::DescribeRepositoriesError,
+    types::{ImageIdentifier, ImageReplicationStatus, ReplicationDestination, ReplicationRule, ReplicationStatus},
+};
+
+use crate::task::VerifyReplication;
+
+use super::EcrConnector;
+
+/// The destinations of every rule whose filters match `repository_name`.
+fn destinations_for(rules: &[ReplicationRule], repository_name: &str) -> Vec<ReplicationDestination> {
+    let mut destinations: Vec<ReplicationDestination> = Vec::new();
+
+    for rule in rules {
+        // A rule without filters replicates every repository
+        let matches = match rule.repository_filters() {
+            [] => true,
+            filters => filters.iter().any(|f| repository_name.starts_with(f.filter())),
+        };
+
+        if matches {
+            for destination in rule.destinations() {
+                if !destinations.contains(destination) {
+                    destinations.push(destination.clone());
+                }
+            }
+        }
+    }
+
+    destinations
+}
+
+struct RecentImage {
+    digest: String,
+    pushed_at: i64,
+    replication_statuses: Vec<ImageReplicationStatus>,
+}
+
+impl EcrConnector {
+    pub async fn do_verify_replication_task(&self, verify: VerifyReplication) -> anyhow::Result<TaskExecResponse> {
+        let now_secs = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        let account_id = self.account_id.lock().await.clone();
+
+        let client = self.get_or_init_client(&verify.source_region).await?;
+
+        let registry = client.describe_registry().send().await?;
+        let rules = registry.replication_configuration.map(|c| c.rules).unwrap_or_default();
+        if rules.is_empty() {
+            bail!("The ECR registry in {} has no replication configuration", verify.source_region);
+        }
+
+        let repositories = match verify.repositories {
+            Some(repositories) => repositories,
+            None => {
+                let mut repositories = Vec::new();
+                let mut pages = client.describe_repositories().into_paginator().items().send();
+                while let Some(repo) = pages.next().await {
+                    if let Some(name) = repo?.repository_name {
+                        repositories.push(name);
+                    }
+                }
+                repositories
+            }
+        };
+
+        let mut outputs = HashMap::new();
+        let mut report = Vec::new();
+        let mut problem_count = 0;
+        let mut checked_count = 0;
+
+        for repository in repositories {
+            let destinations = destinations_for(&rules, &repository);
+            if destinations.is_empty() {
+                continue;
+            }
+            checked_count += 1;
+
+            let mut images = Vec::new();
+            let mut pages = client
+                .describe_images()
+                .repository_name(&repository)
+                .into_paginator()
+                .items()
+                .send();
+            while let Some(image) = pages.next().await {
+                images.push(image?);
+            }
+            images.sort_by_key(|i| std::cmp::Reverse(i.image_pushed_at.map(|t| t.secs())));
+            images.truncate(verify.recent_images);
+
+            let mut recent_images = Vec::new();
+            for image in images {
+                let Some(digest) = image.image_digest else {
+                    continue;
+                };
+
+                let resp = client
+                    .describe_image_replication_status()
+                    .repository_name(&repository)
+                    .image_id(ImageIdentifier::builder().image_digest(&digest).build())
+                    .send()
+                    .await?;
+
+                recent_images.push(RecentImage {
+                    digest,
+                    pushed_at: image.image_pushed_at.map(|t| t.secs()).unwrap_or(now_secs),
+                    replication_statuses: resp.replication_statuses.unwrap_or_default(),
+                });
+            }
+
+            for destination in destinations {
+                let region = destination.region();
+                let registry_id = destination.registry_id();
+
+                // Repositories in other accounts can't be described with this account's credentials
+                let destination_client = if registry_id == account_id {
+                    Some(self.get_or_init_client(region).await?)
+                } else {
+                    None
+                };
+
+                let mut problems = Vec::new();
+
+                if let Some(destination_client) = &destination_client {
+                    match destination_client.describe_repositories().repository_names(&repository).send().await {
+                        Ok(_) => {}
+                        Err(e) => match e.as_service_error() {
+                            // ECR creates the destination repository on the first replicated push,
+                            // so it's only missing if there's been something to replicate
+                            Some(DescribeRepositoriesError::RepositoryNotFoundException(_)) => {
+                                if !recent_images.is_empty() {
+                                    problems.push(String::from("destination repository does not exist"));
+                                }
+                            }
+                            _ => return Err(e.into()),
+                        },
+                    }
+                }
+
+                let mut max_lag_seconds = 0;
+                let mut in_progress = 0;
+
+                for image in &recent_images {
+                    let status = image
+                        .replication_statuses
+                        .iter()
+                        .find(|s| s.region() == Some(region) && s.registry_id() == Some(registry_id));
+
+                    let Some(status) = status else {
+                        problems.push(format!("image {} has no replication status for this destination", image.digest));
+                        continue;
+                    };
+
+                    match status.status() {
+                        Some(ReplicationStatus::Complete) => {
+                            // A replica's push time is when replication finished
+                            if let Some(destination_client) = &destination_client {
+                                let replica = destination_client
+                                    .describe_images()
+                                    .repository_name(&repository)
+                                    .image_ids(ImageIdentifier::builder().image_digest(&image.digest).build())
+                                    .send()
+                                    .await;
+
+                                if let Ok(replica) = replica
+                                    && let Some(replicated_at) = replica.image_details().first().and_then(|d| d.image_pushed_at())
+                                {
+                                    max_lag_seconds = max_lag_seconds.max(replicated_at.secs() - image.pushed_at);
+                                }
+                            }
+                        }
+                        Some(ReplicationStatus::Failed) => {
+                            problems.push(format!(
+                                "image {} failed to replicate ({})",
+                                image.digest,
+                                status.failure_code().unwrap_or("unknown failure")
+                            ));
+                        }
+                        _ => {
+                            in_progress += 1;
+                            max_lag_seconds = max_lag_seconds.max(now_secs - image.pushed_at);
+                        }
+                    }
+                }
+
+                let status = if !problems.is_empty() {
+                    "failed"
+                } else if in_progress > 0 {
+                    "in_progress"
+                } else {
+                    "ok"
+                };
+
+                let key = format!("{}/{}/{}", repository, registry_id, region);
+                outputs.insert(format!("{}/status", key), Some(status.to_string()));
+                outputs.insert(format!("{}/max_lag_seconds", key), Some(max_lag_seconds.to_string()));
+
+                report.push(format!(
+                    "{} -> {} ({}): {}, {} in progress, max lag {}s",
+                    repository, region, registry_id, status, in_progress, max_lag_seconds
+                ));
+                for problem in &problems {
+                    report.push(format!("  {}", problem));
+                }
+                problem_count += problems.len();
+            }
+        }
+
+        let summary = if problem_count == 0 {
+            format!("Replication is healthy for {} ECR repositories", checked_count)
+        } else {
+            format!(
+                "Found {} replication problems across {} ECR repositories",
+                problem_count, checked_count
+            )
+        };
+        report.insert(0, summary);
+
+        Ok(TaskExecResponse {
+            outputs: Some(outputs),
+            friendly_message: Some(report.join("\n")),
+            ..Default::default()
+        })
+    }
+}
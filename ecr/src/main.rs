@@ -1,5 +1,8 @@
+use addr::EcrResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
 use autoschematic_core::tarpc_bridge::tarpc_connector_main;
 use connector::EcrConnector;
+use task::EcrTaskAddress;
 
 pub mod connector;
 // pub mod client_cache;
@@ -9,10 +12,18 @@ pub mod op;
 pub mod op_impl;
 pub mod resource;
 pub mod tags;
+pub mod task;
 pub mod util;
 
 #[tokio::main]
 pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let mut patterns = EcrResourceAddress::checked_address_patterns()?;
+        patterns.extend(EcrTaskAddress::checked_address_patterns()?);
+        print!("{}", render_address_docs("aws/ecr", &patterns));
+        return Ok(());
+    }
+
     tarpc_connector_main::<EcrConnector>().await?;
     Ok(())
 }
@@ -4,7 +4,10 @@ use serde::{Deserialize, Serialize};
 use autoschematic_core::util::RON;
 
 use super::{
-    resource::{EncryptionConfiguration, Repository},
+    resource::{
+        CatalogData, EncryptionConfiguration, PublicRepository, RegistryScanningConfiguration, ReplicationConfiguration,
+        Repository,
+    },
     tags::Tags,
 };
 
@@ -64,11 +67,20 @@ pub enum EcrConnectorOp {
     DeletePullThroughCacheRule {
     },
 
-    // Replication Configuration operations
-    SetReplicationConfiguration {
-        rules: Vec<ReplicationRule>,
+    // Public repository operations
+    CreatePublicRepository(PublicRepository),
+    UpdatePublicRepositoryCatalogData(CatalogData),
+    UpdatePublicRepositoryTags(Tags, Tags),
+    DeletePublicRepository {
+        force: bool, // When true, deletes the repository even if it contains images
     },
+
+    // Registry replication and scanning operations
+    SetReplicationConfiguration(ReplicationConfiguration),
     DeleteReplicationConfiguration,
+    SetRegistryScanningConfiguration(RegistryScanningConfiguration),
+    /// Puts the registry back to basic scanning with no rules.
+    ResetRegistryScanningConfiguration,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -77,18 +89,6 @@ pub struct ImageId {
     pub image_digest: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ReplicationRule {
-    pub destinations: Vec<String>, // List of destination registry IDs
-    pub repository_filters: Option<Vec<RepositoryFilter>>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct RepositoryFilter {
-    pub filter_type: String, // PREFIX_MATCH
-    pub filter_value: String,
-}
-
 impl ConnectorOp for EcrConnectorOp {
     fn to_string(&self) -> Result<String, anyhow::Error> {
         Ok(RON.to_string(self)?)
@@ -2,7 +2,10 @@ use anyhow::{Context, bail};
 use std::collections::HashMap;
 
 use super::{
-    resource::{EncryptionConfiguration, Repository},
+    resource::{
+        CatalogData, EncryptionConfiguration, PublicRepository, RegistryScanningConfiguration, ReplicationConfiguration,
+        Repository,
+    },
     tags::Tags,
 };
 use autoschematic_core::connector::OpExecResponse;
@@ -477,43 +480,45 @@ pub async fn delete_pull_through_cache_rule(
     })
 }
 
-/// Sets replication configuration
+/// Sets the registry's replication rules, replacing any it had
 pub async fn set_replication_configuration(
     client: &aws_sdk_ecr::Client,
-    rules: &[super::op::ReplicationRule],
+    account_id: &str,
+    config: &ReplicationConfiguration,
 ) -> Result<OpExecResponse, anyhow::Error> {
     let mut replication_rules = Vec::new();
 
-    for rule in rules {
-        let mut rule_builder = aws_sdk_ecr::types::ReplicationRule::builder();
-
-        // Add destinations
+    for rule in &config.rules {
         let mut destinations = Vec::new();
-        for registry_id in &rule.destinations {
+        for destination in &rule.destinations {
             destinations.push(
                 aws_sdk_ecr::types::ReplicationDestination::builder()
-                    .region(registry_id)
-                    .registry_id(registry_id)
+                    .region(&destination.region)
+                    .registry_id(destination.registry_id.as_deref().unwrap_or(account_id))
                     .build()?,
             );
         }
-        rule_builder = rule_builder.set_destinations(Some(destinations));
-
-        // Add repository filters if present
-        if let Some(filters) = &rule.repository_filters {
-            let mut aws_filters = Vec::new();
-            for filter in filters {
-                aws_filters.push(
-                    aws_sdk_ecr::types::RepositoryFilter::builder()
-                        .filter_type(filter.filter_type.as_str().into())
-                        .filter(filter.filter_value.clone())
-                        .build()?,
-                );
-            }
-            rule_builder = rule_builder.set_repository_filters(Some(aws_filters));
+
+        let mut repository_filters = Vec::new();
+        for prefix in &rule.repository_filters {
+            repository_filters.push(
+                aws_sdk_ecr::types::RepositoryFilter::builder()
+                    .filter_type(aws_sdk_ecr::types::RepositoryFilterType::PrefixMatch)
+                    .filter(prefix)
+                    .build()?,
+            );
         }
 
-        replication_rules.push(rule_builder.build()?);
+        replication_rules.push(
+            aws_sdk_ecr::types::ReplicationRule::builder()
+                .set_destinations(Some(destinations))
+                .set_repository_filters(if repository_filters.is_empty() {
+                    None
+                } else {
+                    Some(repository_filters)
+                })
+                .build()?,
+        );
     }
 
     let replication_config = aws_sdk_ecr::types::ReplicationConfiguration::builder()
@@ -550,3 +555,184 @@ pub async fn delete_replication_configuration(client: &aws_sdk_ecr::Client) -> R
         friendly_message: Some("Deleted ECR replication configuration".to_string()),
     })
 }
+
+/// Sets the registry's scan type and scanning rules, replacing any it had
+pub async fn set_registry_scanning_configuration(
+    client: &aws_sdk_ecr::Client,
+    config: &RegistryScanningConfiguration,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut rules = Vec::new();
+
+    for rule in &config.rules {
+        let mut repository_filters = Vec::new();
+        for filter in &rule.repository_filters {
+            repository_filters.push(
+                aws_sdk_ecr::types::ScanningRepositoryFilter::builder()
+                    .filter(filter)
+                    .filter_type(aws_sdk_ecr::types::ScanningRepositoryFilterType::Wildcard)
+                    .build()?,
+            );
+        }
+
+        rules.push(
+            aws_sdk_ecr::types::RegistryScanningRule::builder()
+                .scan_frequency(rule.scan_frequency.as_str().into())
+                .set_repository_filters(Some(repository_filters))
+                .build()?,
+        );
+    }
+
+    client
+        .put_registry_scanning_configuration()
+        .scan_type(config.scan_type.as_str().into())
+        .set_rules(Some(rules))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Set ECR registry scanning configuration ({} scanning)", config.scan_type)),
+    })
+}
+
+/// Puts the registry back to basic scanning with no rules, which is where new registries start
+pub async fn reset_registry_scanning_configuration(client: &aws_sdk_ecr::Client) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .put_registry_scanning_configuration()
+        .scan_type(aws_sdk_ecr::types::ScanType::Basic)
+        .set_rules(Some(Vec::new()))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some("Reset ECR registry scanning configuration to basic scanning".to_string()),
+    })
+}
+
+fn catalog_data_input(catalog_data: &CatalogData) -> aws_sdk_ecrpublic::types::RepositoryCatalogDataInput {
+    aws_sdk_ecrpublic::types::RepositoryCatalogDataInput::builder()
+        .set_description(catalog_data.description.clone())
+        .set_about_text(catalog_data.about_text.clone())
+        .set_usage_text(catalog_data.usage_text.clone())
+        .set_architectures(Some(catalog_data.architectures.clone()))
+        .set_operating_systems(Some(catalog_data.operating_systems.clone()))
+        .build()
+}
+
+async fn public_repository_arn(client: &aws_sdk_ecrpublic::Client, repository_name: &str) -> anyhow::Result<String> {
+    let resp = client.describe_repositories().repository_names(repository_name).send().await?;
+
+    resp.repositories
+        .unwrap_or_default()
+        .into_iter()
+        .next()
+        .and_then(|repository| repository.repository_arn)
+        .with_context(|| format!("Public repository not found: {}", repository_name))
+}
+
+/// Creates a public repository along with its gallery metadata
+pub async fn create_public_repository(
+    client: &aws_sdk_ecrpublic::Client,
+    repository_name: &str,
+    repo: &PublicRepository,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let resp = client
+        .create_repository()
+        .repository_name(repository_name)
+        .catalog_data(catalog_data_input(&repo.catalog_data))
+        .set_tags(if repo.tags.len() > 0 { Some(repo.tags.to_public_vec()) } else { None })
+        .send()
+        .await?;
+
+    let repository = resp.repository;
+    let mut outputs = HashMap::new();
+    outputs.insert(
+        String::from("repository_arn"),
+        repository.as_ref().and_then(|r| r.repository_arn.clone()),
+    );
+    outputs.insert(
+        String::from("repository_uri"),
+        repository.as_ref().and_then(|r| r.repository_uri.clone()),
+    );
+
+    Ok(OpExecResponse {
+        outputs: Some(outputs),
+        friendly_message: Some(format!("Created ECR Public repository {repository_name}")),
+    })
+}
+
+/// Replaces a public repository's gallery metadata
+pub async fn update_public_repository_catalog_data(
+    client: &aws_sdk_ecrpublic::Client,
+    repository_name: &str,
+    catalog_data: &CatalogData,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .put_repository_catalog_data()
+        .repository_name(repository_name)
+        .catalog_data(catalog_data_input(catalog_data))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated gallery metadata for ECR Public repository {repository_name}")),
+    })
+}
+
+/// Updates a public repository's tags
+pub async fn update_public_repository_tags(
+    client: &aws_sdk_ecrpublic::Client,
+    repository_name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let repository_arn = public_repository_arn(client, repository_name).await?;
+    let (delete_keys, tags_to_add) = super::tags::public_tag_diff(old_tags, new_tags);
+
+    if !delete_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(&repository_arn)
+            .set_tag_keys(Some(delete_keys))
+            .send()
+            .await?;
+    }
+
+    if !tags_to_add.is_empty() {
+        client
+            .tag_resource()
+            .resource_arn(&repository_arn)
+            .set_tags(Some(tags_to_add))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for ECR Public repository {repository_name}")),
+    })
+}
+
+/// Deletes a public repository
+pub async fn delete_public_repository(
+    client: &aws_sdk_ecrpublic::Client,
+    repository_name: &str,
+    force: bool,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .delete_repository()
+        .repository_name(repository_name)
+        .force(force)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("repository_arn"), None),
+            (String::from("repository_uri"), None),
+        ])),
+        friendly_message: Some(format!("Deleted ECR Public repository {repository_name}")),
+    })
+}
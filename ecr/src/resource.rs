@@ -6,7 +6,7 @@ use autoschematic_core::util::RON;
 use super::{addr::EcrResourceAddress, tags::Tags};
 
 // Define encryption configuration struct
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct EncryptionConfiguration {
     pub encryption_type: String, // AES256 or KMS
     pub kms_key: Option<String>, // ARN of the KMS key when encryption_type is KMS
@@ -48,6 +48,80 @@ pub struct PullThroughCacheRule {
     pub credential_arn: Option<String>,
 }
 
+/// The registry's replication rules. Each repository is replicated to the destinations of every
+/// rule whose filters match it.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ReplicationConfiguration {
+    pub rules: Vec<ReplicationRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ReplicationRule {
+    pub destinations: Vec<ReplicationDestination>,
+    /// Repository name prefixes. If empty, every repository is replicated.
+    #[serde(default)]
+    pub repository_filters: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ReplicationDestination {
+    pub region: String,
+    /// The destination account. If None, this account. Other accounts have to allow replication
+    /// from this one in their registry policy.
+    #[serde(default)]
+    pub registry_id: Option<String>,
+}
+
+/// How the registry scans images. Scanning rules here take precedence over the scan_on_push
+/// setting of individual repositories.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RegistryScanningConfiguration {
+    /// BASIC or ENHANCED. Enhanced scanning uses Amazon Inspector.
+    pub scan_type: String,
+    #[serde(default)]
+    pub rules: Vec<RegistryScanningRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RegistryScanningRule {
+    /// SCAN_ON_PUSH or CONTINUOUS_SCAN. Only enhanced scanning supports CONTINUOUS_SCAN.
+    pub scan_frequency: String,
+    /// Repository name filters, where `*` matches any run of characters, e.g. `prod-*`.
+    pub repository_filters: Vec<String>,
+}
+
+/// A repository in ECR Public, which is shown in the ECR Public Gallery under the registry's alias.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PublicRepository {
+    #[serde(default)]
+    pub catalog_data: CatalogData,
+    pub tags: Tags,
+}
+
+/// What the gallery shows about a public repository.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogData {
+    /// A short description, shown in search results.
+    pub description: Option<String>,
+    /// Markdown shown on the repository's About tab.
+    pub about_text: Option<String>,
+    /// Markdown shown on the repository's Usage tab.
+    pub usage_text: Option<String>,
+    /// e.g. x86-64 or ARM 64
+    #[serde(default)]
+    pub architectures: Vec<String>,
+    /// Linux or Windows
+    #[serde(default)]
+    pub operating_systems: Vec<String>,
+}
+
 // Define the EcrResource enum
 pub enum EcrResource {
     Repository(Repository),
@@ -55,6 +129,9 @@ pub enum EcrResource {
     LifecyclePolicy(LifecyclePolicy),
     RegistryPolicy(RegistryPolicy),
     PullThroughCacheRule(PullThroughCacheRule),
+    ReplicationConfiguration(ReplicationConfiguration),
+    RegistryScanningConfiguration(RegistryScanningConfiguration),
+    PublicRepository(PublicRepository),
 }
 
 // Implement the Resource trait
@@ -67,6 +144,9 @@ impl Resource for EcrResource {
             EcrResource::LifecyclePolicy(policy) => Ok(RON.to_string_pretty(&policy, pretty_config)?.into()),
             EcrResource::RegistryPolicy(policy) => Ok(RON.to_string_pretty(&policy, pretty_config)?.into()),
             EcrResource::PullThroughCacheRule(rule) => Ok(RON.to_string_pretty(&rule, pretty_config)?.into()),
+            EcrResource::ReplicationConfiguration(config) => Ok(RON.to_string_pretty(&config, pretty_config)?.into()),
+            EcrResource::RegistryScanningConfiguration(config) => Ok(RON.to_string_pretty(&config, pretty_config)?.into()),
+            EcrResource::PublicRepository(repo) => Ok(RON.to_string_pretty(&repo, pretty_config)?.into()),
         }
     }
 
@@ -86,6 +166,11 @@ impl Resource for EcrResource {
             EcrResourceAddress::PullThroughCacheRule { region, prefix } => {
                 Ok(EcrResource::PullThroughCacheRule(RON.from_str(s)?))
             }
+            EcrResourceAddress::ReplicationConfiguration { .. } => Ok(EcrResource::ReplicationConfiguration(RON.from_str(s)?)),
+            EcrResourceAddress::RegistryScanningConfiguration { .. } => {
+                Ok(EcrResource::RegistryScanningConfiguration(RON.from_str(s)?))
+            }
+            EcrResourceAddress::PublicRepository { .. } => Ok(EcrResource::PublicRepository(RON.from_str(s)?)),
         }
     }
 }
@@ -82,3 +82,38 @@ pub fn tag_diff(old_tags: &Tags, new_tags: &Tags) -> anyhow::Result<(Vec<String>
 
     Ok((untag_keys, new_tagset))
 }
+
+impl From<Vec<aws_sdk_ecrpublic::types::Tag>> for Tags {
+    fn from(tags: Vec<aws_sdk_ecrpublic::types::Tag>) -> Self {
+        let mut out_map = HashMap::new();
+        for tag in tags {
+            if let Some(key) = tag.key {
+                out_map.insert(key, tag.value.unwrap_or_default());
+            }
+        }
+        Tags(out_map)
+    }
+}
+
+impl Tags {
+    pub fn to_public_vec(&self) -> Vec<aws_sdk_ecrpublic::types::Tag> {
+        self.0
+            .iter()
+            .map(|(k, v)| aws_sdk_ecrpublic::types::Tag::builder().key(k).value(v).build())
+            .collect()
+    }
+}
+
+// The same as tag_diff, for ECR Public's Tag type
+pub fn public_tag_diff(old_tags: &Tags, new_tags: &Tags) -> (Vec<String>, Vec<aws_sdk_ecrpublic::types::Tag>) {
+    let untag_keys = old_tags.0.keys().filter(|k| !new_tags.0.contains_key(*k)).cloned().collect();
+
+    let new_tagset = new_tags
+        .0
+        .iter()
+        .filter(|(key, new_value)| old_tags.0.get(*key) != Some(new_value))
+        .map(|(key, new_value)| aws_sdk_ecrpublic::types::Tag::builder().key(key).value(new_value).build())
+        .collect();
+
+    (untag_keys, new_tagset)
+}
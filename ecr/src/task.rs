@@ -0,0 +1,140 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::{PrettyConfig, RON};
+
+use crate::resource::EncryptionConfiguration;
+
+#[derive(Debug, Clone)]
+pub enum EcrTaskAddress {
+    EnforceRepositoryPolicy { name: String },
+    VerifyReplication { name: String },
+}
+
+impl ResourceAddress for EcrTaskAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            EcrTaskAddress::EnforceRepositoryPolicy { name } => {
+                PathBuf::from(format!("aws/ecr/tasks/enforce-repository-policy/{name}.ron"))
+            }
+            EcrTaskAddress::VerifyReplication { name } => PathBuf::from(format!("aws/ecr/tasks/verify-replication/{name}.ron")),
+        }
+    }
+
+    fn from_path(path: &Path) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        let path_components: Vec<&str> = path
+            .components()
+            .map(|s| s.as_os_str().to_str().context("Path component is not valid UTF-8"))
+            .collect::<Result<Vec<&str>, anyhow::Error>>()?;
+
+        match &path_components[..] {
+            ["aws", "ecr", "tasks", "enforce-repository-policy", name] if name.ends_with(".ron") => {
+                Ok(EcrTaskAddress::EnforceRepositoryPolicy {
+                    name: name.strip_suffix(".ron").context("File name must end with .ron")?.to_string(),
+                })
+            }
+            ["aws", "ecr", "tasks", "verify-replication", name] if name.ends_with(".ron") => Ok(EcrTaskAddress::VerifyReplication {
+                name: name.strip_suffix(".ron").context("File name must end with .ron")?.to_string(),
+            }),
+            _ => Err(anyhow::anyhow!("Invalid ECR task address: {}", path.display())),
+        }
+    }
+}
+
+impl DescribeAddresses for EcrTaskAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/ecr/tasks/enforce-repository-policy/<name>.ron",
+                description: "Task: apply a baseline policy to every repository",
+                example:     "aws/ecr/tasks/enforce-repository-policy/baseline.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ecr/tasks/verify-replication/<name>.ron",
+                description: "Task: check replication destinations and report replication lag",
+                example:     "aws/ecr/tasks/verify-replication/us-east-1.ron",
+            },
+        ]
+    }
+}
+
+/// Checks every repository in `regions` against the required settings.
+/// Non-compliant repositories are always reported. If `fix` is set, their repository files
+/// are also rewritten with the required settings, so that the next plan produces the
+/// update ops for all of them at once.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EnforceRepositoryPolicy {
+    /// Defaults to the connector's enabled_regions.
+    #[serde(default)]
+    pub regions: Option<Vec<String>>,
+    #[serde(default)]
+    pub require_scan_on_push: bool,
+    #[serde(default)]
+    pub required_encryption: Option<EncryptionConfiguration>,
+    #[serde(default)]
+    pub fix: bool,
+}
+
+/// Checks that the registry replication configuration in `source_region` is taking effect:
+/// every replicated repository has to exist in each destination region, and its most recently
+/// pushed images have to have replicated. Replication lag is reported in the task outputs as
+/// `{repository}/{registry_id}/{region}/max_lag_seconds`, alongside `.../status`.
+/// Destinations in other accounts can't be inspected, so only their replication status is checked.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct VerifyReplication {
+    pub source_region: String,
+    /// Defaults to every repository in source_region matched by a replication rule.
+    #[serde(default)]
+    pub repositories: Option<Vec<String>>,
+    /// How many of each repository's most recently pushed images to check.
+    #[serde(default = "default_recent_images")]
+    pub recent_images: usize,
+}
+
+fn default_recent_images() -> usize {
+    5
+}
+
+pub enum EcrTask {
+    EnforceRepositoryPolicy(EnforceRepositoryPolicy),
+    VerifyReplication(VerifyReplication),
+}
+
+impl Resource for EcrTask {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = PrettyConfig::default().struct_names(true);
+        match self {
+            EcrTask::EnforceRepositoryPolicy(enforce) => match RON.to_string_pretty(&enforce, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
+            EcrTask::VerifyReplication(verify) => match RON.to_string_pretty(&verify, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = EcrTaskAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+        match addr {
+            EcrTaskAddress::EnforceRepositoryPolicy { .. } => Ok(EcrTask::EnforceRepositoryPolicy(RON.from_str(s)?)),
+            EcrTaskAddress::VerifyReplication { .. } => Ok(EcrTask::VerifyReplication(RON.from_str(s)?)),
+        }
+    }
+}
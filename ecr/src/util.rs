@@ -0,0 +1,112 @@
+use anyhow::bail;
+use autoschematic_connector_aws_core::arn::parse_arn;
+
+use crate::resource::EncryptionConfiguration;
+
+pub fn encryption_satisfies(current: Option<&EncryptionConfiguration>, required: &EncryptionConfiguration) -> bool {
+    // Repositories without an explicit encryption configuration use AES256.
+    let current_type = current.map(|c| c.encryption_type.as_str()).unwrap_or("AES256");
+    if current_type != required.encryption_type {
+        return false;
+    }
+
+    match &required.kms_key {
+        Some(required_key) => current.and_then(|c| c.kms_key.as_ref()) == Some(required_key),
+        None => true,
+    }
+}
+
+pub fn describe_encryption(encryption: Option<&EncryptionConfiguration>) -> String {
+    match encryption {
+        Some(EncryptionConfiguration {
+            encryption_type,
+            kms_key: Some(kms_key),
+        }) => format!("{} ({})", encryption_type, kms_key),
+        Some(EncryptionConfiguration { encryption_type, .. }) => encryption_type.clone(),
+        None => String::from("AES256"),
+    }
+}
+
+/// The most characters ECR accepts in a repository policy.
+const REPOSITORY_POLICY_MAX_SIZE: usize = 10240;
+
+pub fn check_repository_policy(name: &str, policy_document: &ron::Value) -> anyhow::Result<()> {
+    let policy_json = serde_json::to_string(policy_document)?;
+    if policy_json.len() > REPOSITORY_POLICY_MAX_SIZE {
+        bail!(
+            "Repository policy for ECR repository `{}` is {} characters, over ECR's limit of {}",
+            name,
+            policy_json.len(),
+            REPOSITORY_POLICY_MAX_SIZE
+        );
+    }
+    Ok(())
+}
+
+/// A policy field that may hold either one string or a list of them.
+fn string_or_list(value: Option<&serde_json::Value>) -> Vec<String> {
+    match value {
+        Some(serde_json::Value::String(s)) => vec![s.clone()],
+        Some(serde_json::Value::Array(values)) => values.iter().filter_map(|v| v.as_str().map(String::from)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The account an AWS principal belongs to: principals are either an account ID or an IAM ARN.
+fn principal_account(principal: &str) -> Option<String> {
+    if principal.len() == 12 && principal.chars().all(|c| c.is_ascii_digit()) {
+        return Some(principal.to_string());
+    }
+    parse_arn(principal).ok().map(|arn| arn.account_id.to_string())
+}
+
+/// Describes what the policy's Allow statements grant to principals outside `account_id`, one
+/// line per principal, so that cross-account access stands out in the plan.
+/// Returns None if the policy grants nothing outside the account.
+pub fn describe_external_access(policy_document: &ron::Value, account_id: &str) -> anyhow::Result<Option<String>> {
+    let policy = serde_json::to_value(policy_document)?;
+    let statements = match policy.get("Statement") {
+        Some(serde_json::Value::Array(statements)) => statements.clone(),
+        Some(statement) => vec![statement.clone()],
+        None => Vec::new(),
+    };
+
+    let mut lines = Vec::new();
+    for statement in &statements {
+        if statement.get("Effect").and_then(|e| e.as_str()) != Some("Allow") {
+            continue;
+        }
+
+        let principals = match statement.get("Principal") {
+            Some(serde_json::Value::String(s)) => vec![s.clone()],
+            Some(principal) => string_or_list(principal.get("AWS")),
+            None => Vec::new(),
+        };
+
+        let actions = string_or_list(statement.get("Action"));
+        let conditional = if statement.get("Condition").is_some() {
+            " (with conditions)"
+        } else {
+            ""
+        };
+
+        for principal in principals {
+            let who = if principal == "*" {
+                String::from("anyone (*)")
+            } else {
+                match principal_account(&principal) {
+                    Some(account) if account == account_id => continue,
+                    Some(account) if account == principal => account,
+                    Some(account) => format!("{} ({})", account, principal),
+                    None => principal,
+                }
+            };
+            lines.push(format!("  {}: {}{}", who, actions.join(", "), conditional));
+        }
+    }
+
+    if lines.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(format!("Grants access outside account {}:\n{}", account_id, lines.join("\n"))))
+}
@@ -1,12 +1,15 @@
 use std::path::{Path, PathBuf};
 
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
 use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 
 #[derive(Debug, Clone)]
 pub enum EcsResourceAddress {
     Cluster(String, String),         // (region, cluster_name)
     Service(String, String, String), // (region, cluster_name, service_name)
+    TaskSet(String, String, String, String), // (region, cluster_name, service_name, external_id)
     TaskDefinition(String, String),  // (region, task_family)
+    ExternalActivation(String, String, String), // (region, cluster_name, activation_name)
 }
 
 impl ResourceAddress for EcsResourceAddress {
@@ -18,9 +21,15 @@ impl ResourceAddress for EcsResourceAddress {
             EcsResourceAddress::Service(region, cluster_name, service_name) => PathBuf::from(format!(
                 "aws/ecs/{region}/clusters/{cluster_name}/services/{service_name}.ron"
             )),
+            EcsResourceAddress::TaskSet(region, cluster_name, service_name, external_id) => PathBuf::from(format!(
+                "aws/ecs/{region}/clusters/{cluster_name}/services/{service_name}/task_sets/{external_id}.ron"
+            )),
             EcsResourceAddress::TaskDefinition(region, task_def_id) => {
                 PathBuf::from(format!("aws/ecs/{region}/task_definitions/{task_def_id}.ron"))
             }
+            EcsResourceAddress::ExternalActivation(region, cluster_name, activation_name) => PathBuf::from(format!(
+                "aws/ecs/{region}/clusters/{cluster_name}/external_activations/{activation_name}.ron"
+            )),
         }
     }
 
@@ -40,6 +49,27 @@ impl ResourceAddress for EcsResourceAddress {
                     service_name,
                 ))
             }
+            ["aws", "ecs", region, "clusters", cluster_name, "services", service_name, "task_sets", external_id]
+                if external_id.ends_with(".ron") =>
+            {
+                let external_id = external_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(EcsResourceAddress::TaskSet(
+                    region.to_string(),
+                    cluster_name.to_string(),
+                    service_name.to_string(),
+                    external_id,
+                ))
+            }
+            ["aws", "ecs", region, "clusters", cluster_name, "external_activations", activation_name]
+                if activation_name.ends_with(".ron") =>
+            {
+                let activation_name = activation_name.strip_suffix(".ron").unwrap().to_string();
+                Ok(EcsResourceAddress::ExternalActivation(
+                    region.to_string(),
+                    cluster_name.to_string(),
+                    activation_name,
+                ))
+            }
             ["aws", "ecs", region, "task_definitions", task_def_id] if task_def_id.ends_with(".ron") => {
                 let task_def_id = task_def_id.strip_suffix(".ron").unwrap().to_string();
                 Ok(EcsResourceAddress::TaskDefinition(region.to_string(), task_def_id))
@@ -48,3 +78,35 @@ impl ResourceAddress for EcsResourceAddress {
         }
     }
 }
+
+impl DescribeAddresses for EcsResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/ecs/<region>/clusters/<cluster_name>.ron",
+                description: "An ECS cluster",
+                example:     "aws/ecs/us-east-1/clusters/main.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ecs/<region>/clusters/<cluster_name>/services/<service_name>.ron",
+                description: "A service in an ECS cluster",
+                example:     "aws/ecs/us-east-1/clusters/main/services/web.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ecs/<region>/clusters/<cluster_name>/services/<service_name>/task_sets/<external_id>.ron",
+                description: "A task set of a service using the EXTERNAL deployment controller, identified by its external ID",
+                example:     "aws/ecs/us-east-1/clusters/main/services/web/task_sets/blue.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ecs/<region>/clusters/<cluster_name>/external_activations/<activation_name>.ron",
+                description: "An ECS Anywhere activation for registering external instances to a cluster",
+                example:     "aws/ecs/us-east-1/clusters/main/external_activations/datacenter.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ecs/<region>/task_definitions/<family>.ron",
+                description: "The latest revision of a task definition family. May also be written as a TaskDefinitionPatch (base, patch) on top of a base task definition kept in a subdirectory such as task_definitions/base/",
+                example:     "aws/ecs/us-east-1/task_definitions/web.ron",
+            },
+        ]
+    }
+}
@@ -7,6 +7,8 @@ use autoschematic_connector_aws_core::{
 use autoschematic_core::util::RON;
 use serde::{Deserialize, Serialize};
 
+use crate::resource::HealthGate;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct EcsConnectorConfig {
     pub account_id:      Option<String>,
@@ -14,6 +16,31 @@ pub struct EcsConnectorConfig {
     pub timeout_config:  Option<TimeoutConfig>,
     pub sts_region:      String,
     pub enabled_regions: Vec<String>,
+    #[serde(default)]
+    pub max_concurrent_ops: Option<usize>,
+    /// If set, plan checks that every S3 environment file referenced by a task definition
+    /// exists and can be read by the task's execution role.
+    #[serde(default)]
+    pub validate_environment_files: bool,
+    /// If set, plan resolves each ECR image tag in a task definition to the digest it currently
+    /// points to, and registers the task definition with `repository@sha256:...` images.
+    #[serde(default)]
+    pub pin_image_digests: bool,
+    /// If set, every deployment planned for a service is followed by a health gate.
+    /// Services can override individual fields with their own `health_gate`.
+    #[serde(default)]
+    pub health_gate: Option<HealthGate>,
+    /// If set, every deployment planned for a service waits for the rollout to reach steady state,
+    /// even when no health gate is configured. Equivalent to an empty `health_gate`.
+    #[serde(default)]
+    pub wait_for_deployments: bool,
 }
 
-impl_aws_config!(EcsConnectorConfig, "aws/ecs/config.ron");
+impl_aws_config!(
+    EcsConnectorConfig,
+    "aws/ecs/config.ron",
+    validate_environment_files,
+    pin_image_digests,
+    health_gate,
+    wait_for_deployments
+);
@@ -6,56 +6,101 @@ use std::{
 };
 
 use crate::config::EcsConnectorConfig;
-use crate::resource::{Cluster, EcsResource, Service, TaskDefinition};
-use crate::{addr::EcsResourceAddress, resource, tags};
-use anyhow::bail;
+use crate::resource::{Cluster, EcsResource, ExternalInstanceActivation, Service, TaskDefinition, TaskDefinitionPatch};
+use crate::task::{EcsTask, EcsTaskAddress, Exec};
+use crate::{addr::EcsResourceAddress, patch, resource, tags, util};
+use anyhow::{Context, bail};
 use async_trait::async_trait;
 use autoschematic_core::{connector::FilterResponse, skeleton};
 use autoschematic_core::{
     connector::{
         Connector, ConnectorOutbox, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource, ResourceAddress, SkeletonResponse,
+        TaskExecResponse,
     },
     diag::DiagnosticResponse,
-    util::{ron_check_eq, ron_check_syntax},
+    util::{RON, ron_check_eq, ron_check_syntax},
 };
-use aws_config::{BehaviorVersion, Region, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
 use tokio::sync::Mutex;
 
-use autoschematic_connector_aws_core::config::AwsServiceConfig;
+use autoschematic_connector_aws_core::{
+    audit::AuditLog,
+    audited_client,
+    cascade::{ConsumedOutput, forget_replacements, note_stale_references, resolve_stale_references},
+    concurrency::OpExecLimiter,
+    config::AwsServiceConfig,
+};
 
 pub mod get;
+pub mod health_gate;
 pub mod list;
 pub mod op_exec;
 pub mod plan;
+pub mod task_exec;
+#[cfg(test)]
+mod test;
+
+/// The outputs of other resources that this connector's resources embed by value.
+/// Services and task sets name their subnets, security groups and target groups.
+pub const CONSUMED_OUTPUTS: &[ConsumedOutput] = &[
+    ConsumedOutput {
+        producer: "aws/vpc/",
+        key:      "subnet_id",
+        fields:   &["subnets"],
+    },
+    ConsumedOutput {
+        producer: "aws/vpc/",
+        key:      "security_group_id",
+        fields:   &["security_groups"],
+    },
+    ConsumedOutput {
+        producer: "aws/elb/",
+        key:      "target_group_arn",
+        fields:   &["target_group_arn"],
+    },
+];
 
 #[derive(Default)]
 pub struct EcsConnector {
     client_cache: Mutex<HashMap<String, Arc<aws_sdk_ecs::Client>>>,
+    s3_client_cache: Mutex<HashMap<String, Arc<aws_sdk_s3::Client>>>,
+    ssm_client_cache: Mutex<HashMap<String, Arc<aws_sdk_ssm::Client>>>,
+    ecr_client_cache: Mutex<HashMap<String, Arc<aws_sdk_ecr::Client>>>,
+    servicediscovery_client_cache: Mutex<HashMap<String, Arc<aws_sdk_servicediscovery::Client>>>,
+    cloudwatch_client_cache: Mutex<HashMap<String, Arc<aws_sdk_cloudwatch::Client>>>,
+    applicationautoscaling_client_cache: Mutex<HashMap<String, Arc<aws_sdk_applicationautoscaling::Client>>>,
+    iam_client: Mutex<Option<Arc<aws_sdk_iam::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
     account_id: Mutex<String>,
     config: Mutex<EcsConnectorConfig>,
     prefix: PathBuf,
 }
 
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
 impl EcsConnector {
     async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_ecs::Client>> {
         let mut cache = self.client_cache.lock().await;
 
         if !cache.contains_key(region_s) {
-            let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
-
-            let config = aws_config::defaults(BehaviorVersion::latest())
-                .region(region)
-                .timeout_config(
-                    TimeoutConfig::builder()
-                        .connect_timeout(Duration::from_secs(30))
-                        .operation_timeout(Duration::from_secs(30))
-                        .operation_attempt_timeout(Duration::from_secs(30))
-                        .read_timeout(Duration::from_secs(30))
-                        .build(),
-                )
-                .load()
-                .await;
-            let client = aws_sdk_ecs::Client::new(&config);
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_ecs, &config);
             cache.insert(region_s.to_string(), Arc::new(client));
         };
 
@@ -65,6 +110,147 @@ impl EcsConnector {
 
         Ok(client.clone())
     }
+
+    async fn get_or_init_s3_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_s3::Client>> {
+        let mut cache = self.s3_client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_s3, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get S3 client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+    async fn get_or_init_ssm_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_ssm::Client>> {
+        let mut cache = self.ssm_client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_ssm, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get SSM client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+    async fn get_or_init_ecr_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_ecr::Client>> {
+        let mut cache = self.ecr_client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_ecr, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get ECR client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+    async fn get_or_init_servicediscovery_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_servicediscovery::Client>> {
+        let mut cache = self.servicediscovery_client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_servicediscovery, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get Cloud Map client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+    async fn get_or_init_cloudwatch_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_cloudwatch::Client>> {
+        let mut cache = self.cloudwatch_client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_cloudwatch, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get CloudWatch client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+    async fn get_or_init_applicationautoscaling_client(
+        &self,
+        region_s: &str,
+    ) -> anyhow::Result<Arc<aws_sdk_applicationautoscaling::Client>> {
+        let mut cache = self.applicationautoscaling_client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_applicationautoscaling, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get Application Auto Scaling client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+    async fn get_or_init_iam_client(&self) -> anyhow::Result<Arc<aws_sdk_iam::Client>> {
+        let mut iam_client = self.iam_client.lock().await;
+
+        if let Some(client) = &*iam_client {
+            return Ok(client.clone());
+        }
+
+        let sts_region = self.config.lock().await.sts_region.clone();
+        let config = load_sdk_config(&sts_region).await;
+        let client = Arc::new(audited_client!(aws_sdk_iam, &config));
+        *iam_client = Some(client.clone());
+
+        Ok(client)
+    }
+
+    /// Parses a task definition file, which holds either a whole task definition or a
+    /// TaskDefinitionPatch to apply to a base task definition elsewhere in the repo.
+    pub fn parse_task_definition(&self, s: &str) -> anyhow::Result<TaskDefinition> {
+        let err = match RON.from_str::<TaskDefinition>(s) {
+            Ok(task_def) => return Ok(task_def),
+            Err(e) => e,
+        };
+
+        // Report the error against the full task definition unless this is clearly a patch
+        let Ok(task_def_patch) = RON.from_str::<TaskDefinitionPatch>(s) else {
+            return Err(err.into());
+        };
+
+        let base_path = self.prefix.join(&task_def_patch.base);
+        let base = std::fs::read_to_string(&base_path)
+            .with_context(|| format!("Failed to read base task definition {}", base_path.display()))?;
+        let base: TaskDefinition = RON
+            .from_str(&base)
+            .with_context(|| format!("Failed to parse base task definition {}", base_path.display()))?;
+
+        let mut task_def = serde_json::to_value(&base)?;
+        patch::apply(&mut task_def, &task_def_patch.patch)
+            .with_context(|| format!("Failed to apply patch to base task definition {}", task_def_patch.base))?;
+
+        Ok(serde_json::from_value(task_def)?)
+    }
 }
 
 #[async_trait]
@@ -85,6 +271,14 @@ impl Connector for EcsConnector {
         let account_id = ecs_config.verify_sts().await?;
 
         *self.client_cache.lock().await = HashMap::new();
+        *self.s3_client_cache.lock().await = HashMap::new();
+        *self.ssm_client_cache.lock().await = HashMap::new();
+        *self.ecr_client_cache.lock().await = HashMap::new();
+        *self.servicediscovery_client_cache.lock().await = HashMap::new();
+        *self.iam_client.lock().await = None;
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(ecs_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        forget_replacements(&self.prefix);
         *self.config.lock().await = ecs_config;
         *self.account_id.lock().await = account_id;
         tracing::info!("Finished init");
@@ -94,6 +288,8 @@ impl Connector for EcsConnector {
     async fn filter(&self, addr: &Path) -> anyhow::Result<FilterResponse> {
         if let Ok(_addr) = EcsResourceAddress::from_path(addr) {
             Ok(FilterResponse::Resource)
+        } else if let Ok(_addr) = EcsTaskAddress::from_path(addr) {
+            Ok(FilterResponse::Task)
         } else {
             Ok(FilterResponse::None)
         }
@@ -113,11 +309,25 @@ impl Connector for EcsConnector {
         current: Option<Vec<u8>>,
         desired: Option<Vec<u8>>,
     ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
-        self.do_plan(addr, current, desired).await
+        let (desired, stale_note) = resolve_stale_references(&self.prefix, CONSUMED_OUTPUTS, desired)?;
+        let ops = self.do_plan(addr, current, desired).await?;
+        Ok(note_stale_references(ops, &stale_note))
     }
 
     async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
-        self.do_op_exec(addr, op).await
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn task_exec(
+        &self,
+        addr: &Path,
+        body: Vec<u8>,
+        arg: Option<Vec<u8>>,
+        state: Option<Vec<u8>>,
+    ) -> anyhow::Result<TaskExecResponse> {
+        self.do_task_exec(addr, body, arg, state).await
     }
 
     async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
@@ -143,7 +353,7 @@ impl Connector for EcsConnector {
                 ],
                 settings: vec![resource::ClusterSetting {
                     name:  String::from("containerInsights"),
-                    value: String::from("enabled"),
+                    value: String::from("enabled"), // or "enhanced", "disabled"
                 }],
                 configuration: Some(resource::ClusterConfiguration {
                     execute_command_configuration: Some(resource::ExecuteCommandConfiguration {
@@ -191,9 +401,7 @@ impl Connector for EcsConnector {
                 placement_constraints: Vec::new(),
                 placement_strategy: Vec::new(),
                 load_balancers: vec![resource::LoadBalancer {
-                    target_group_arn:   Some(String::from(
-                        "arn:aws:elasticloadbalancing:[region]:[account_id]:targetgroup/[target-group-name]/[target-group-id]"
-                    )),
+                    target_group_arn:   Some(String::from("aws/elb/[region]/target_groups/[target_group_name].ron")),
                     load_balancer_name: None,
                     container_name:     Some(String::from("web")),
                     container_port:     Some(80),
@@ -203,6 +411,42 @@ impl Connector for EcsConnector {
                 enable_ecs_managed_tags: Some(true),
                 propagate_tags: Some(String::from("SERVICE")),
                 enable_execute_command: Some(true),
+                deployment_controller: None,
+                tags: tags::Tags::default(),
+                health_gate: None,
+                ignore_desired_count: None,
+            })
+        ));
+
+        // Task set skeleton - the green side of a blue/green deployment, for a service using the EXTERNAL
+        // deployment controller. Shift traffic by adjusting scale_percent, then set primary to switch over.
+        res.push(skeleton!(
+            EcsResourceAddress::TaskSet(
+                String::from("[region]"),
+                String::from("[cluster_name]"),
+                String::from("[service_name]"),
+                String::from("[external_id]")
+            ),
+            EcsResource::TaskSet(resource::TaskSet {
+                task_definition: String::from("[task_definition_family]:[revision]"),
+                launch_type: Some(String::from("FARGATE")),
+                capacity_provider_strategy: Vec::new(),
+                platform_version: Some(String::from("LATEST")),
+                network_configuration: Some(resource::NetworkConfiguration {
+                    awsvpc_configuration: Some(resource::AwsVpcConfiguration {
+                        subnets: vec![String::from("subnet-0123456789abcdef0")],
+                        security_groups: vec![String::from("sg-0123456789abcdef0")],
+                        assign_public_ip: Some(String::from("DISABLED")),
+                    }),
+                }),
+                load_balancers: vec![resource::LoadBalancer {
+                    target_group_arn:   Some(String::from("aws/elb/[region]/target_groups/[green_target_group_name].ron")),
+                    load_balancer_name: None,
+                    container_name:     Some(String::from("web")),
+                    container_port:     Some(80),
+                }],
+                scale_percent: 100.0,
+                primary: false,
                 tags: tags::Tags::default(),
             })
         ));
@@ -276,6 +520,7 @@ impl Connector for EcsConnector {
                     system_controls: Vec::new(),
                     resource_requirements: Vec::new(),
                     firelens_configuration: None,
+                    credential_specs: Vec::new(),
                 },],
                 volumes: Vec::new(),
                 placement_constraints: Vec::new(),
@@ -289,9 +534,46 @@ impl Connector for EcsConnector {
                     cpu_architecture: Some(String::from("X86_64")),
                     operating_system_family: Some(String::from("LINUX")),
                 }),
+                ephemeral_storage: None,
+                inference_accelerators: Vec::new(),
+                deploy_to: Vec::new(),
+                keep_revisions: None,
             })
         ));
 
+        // External instance activation skeleton - registers on-premises hosts with a cluster (ECS Anywhere).
+        // After apply, run the install command from the apply output on each host, then deploy to it with
+        // EXTERNAL services and task definitions.
+        res.push(skeleton!(
+            EcsResourceAddress::ExternalActivation(
+                String::from("[region]"),
+                String::from("[cluster_name]"),
+                String::from("[activation_name]")
+            ),
+            EcsResource::ExternalInstanceActivation(ExternalInstanceActivation {
+                iam_role: String::from("ecsAnywhereRole"),
+                registration_limit: 10,
+                expiration_date: None,
+                description: Some(String::from("ECS Anywhere hosts for [cluster_name]")),
+            })
+        ));
+
+        // ECS Exec task skeleton - runs a one-off command in one of a service's running tasks
+        res.push(skeleton!(
+            EcsTaskAddress::Exec {
+                name: String::from("[task_name]"),
+            },
+            EcsTask::Exec(Exec {
+                region: String::from("[region]"),
+                cluster: String::from("[cluster_name]"),
+                service: Some(String::from("[service_name]")),
+                task: None,
+                container: Some(String::from("web")),
+                command: String::from("ls -la /app"),
+                timeout_seconds: None,
+                output_path: None, // Defaults to aws/ecs/exec-output/[task_name].log
+            })
+        ));
 
         Ok(res)
     }
@@ -300,18 +582,78 @@ impl Connector for EcsConnector {
         let addr = EcsResourceAddress::from_path(addr)?;
         match addr {
             EcsResourceAddress::Cluster(_, _) => ron_check_eq::<resource::Cluster>(a, b),
-            EcsResourceAddress::Service(_, _, _) => ron_check_eq::<resource::Service>(a, b),
-            EcsResourceAddress::TaskDefinition(_, _) => ron_check_eq::<resource::TaskDefinition>(a, b),
+            EcsResourceAddress::Service(region, cluster_name, service_name) => {
+                // health_gate and ignore_desired_count only exist locally, so they're left out of the comparison
+                let a: resource::Service = RON.from_str(std::str::from_utf8(a)?)?;
+                let b: resource::Service = RON.from_str(std::str::from_utf8(b)?)?;
+
+                // get only ever returns registry ARNs, so a declared create_registry is compared by the service it names
+                let a_registries = self.resolved_service_registries(&region, &a.service_registries).await?;
+                let b_registries = self.resolved_service_registries(&region, &b.service_registries).await?;
+
+                let desired_count = if a.desired_count != b.desired_count
+                    && self.ignores_desired_count(&region, &cluster_name, &service_name, &a, &b).await?
+                {
+                    b.desired_count
+                } else {
+                    a.desired_count
+                };
+
+                // A service naming only the task definition family runs whichever revision was last deployed to it
+                let task_definition = if (util::tracks_latest_revision(&a.task_definition)
+                    || util::tracks_latest_revision(&b.task_definition))
+                    && util::task_definition_family(&a.task_definition) == util::task_definition_family(&b.task_definition)
+                {
+                    b.task_definition.clone()
+                } else {
+                    a.task_definition.clone()
+                };
+
+                Ok(resource::Service {
+                    health_gate: None,
+                    ignore_desired_count: None,
+                    desired_count,
+                    task_definition,
+                    service_registries: a_registries,
+                    ..a
+                } == resource::Service {
+                    health_gate: None,
+                    ignore_desired_count: None,
+                    service_registries: b_registries,
+                    ..b
+                })
+            }
+            EcsResourceAddress::TaskDefinition(_, _) => {
+                let a = self.parse_task_definition(std::str::from_utf8(a)?)?;
+                let b = self.parse_task_definition(std::str::from_utf8(b)?)?;
+                Ok(a.without_rollout() == b.without_rollout())
+            }
+            EcsResourceAddress::TaskSet(_, _, _, _) => ron_check_eq::<resource::TaskSet>(a, b),
+            EcsResourceAddress::ExternalActivation(_, _, _) => ron_check_eq::<resource::ExternalInstanceActivation>(a, b),
         }
     }
 
     async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        if let Ok(addr) = EcsTaskAddress::from_path(addr) {
+            return match addr {
+                EcsTaskAddress::Exec { .. } => ron_check_syntax::<Exec>(a),
+            };
+        }
+
         let addr = EcsResourceAddress::from_path(addr)?;
 
         match addr {
             EcsResourceAddress::Cluster(_, _) => ron_check_syntax::<resource::Cluster>(a),
             EcsResourceAddress::Service(_, _, _) => ron_check_syntax::<resource::Service>(a),
-            EcsResourceAddress::TaskDefinition(_, _) => ron_check_syntax::<resource::TaskDefinition>(a),
+            EcsResourceAddress::TaskDefinition(_, _) => {
+                // A patch's base is only read at plan time
+                if RON.from_str::<TaskDefinitionPatch>(std::str::from_utf8(a)?).is_ok() {
+                    return Ok(None);
+                }
+                ron_check_syntax::<resource::TaskDefinition>(a)
+            }
+            EcsResourceAddress::TaskSet(_, _, _, _) => ron_check_syntax::<resource::TaskSet>(a),
+            EcsResourceAddress::ExternalActivation(_, _, _) => ron_check_syntax::<resource::ExternalInstanceActivation>(a),
         }
     }
 }
@@ -1,5 +1,6 @@
 use std::{collections::HashMap, path::Path};
 
+use autoschematic_connector_aws_core::elb::{ElbTargetGroupAddress, elb_addresses_by_arn};
 use autoschematic_core::{
     connector::{GetResourceResponse, Resource, ResourceAddress},
     get_resource_response,
@@ -80,6 +81,9 @@ impl EcsConnector {
                 let service = util::get_service(&client, &cluster_name, &service_name).await?;
 
                 if let Some(service) = service {
+                    // Target groups that the repo manages are reported by address, as they're written
+                    let target_group_addresses = elb_addresses_by_arn::<ElbTargetGroupAddress>(&self.prefix, &region)?;
+
                     // Convert AWS SDK service to our internal representation
                     let our_service = resource::Service {
                         task_definition: service.task_definition().unwrap_or_default().to_string(),
@@ -135,7 +139,9 @@ impl EcsConnector {
                             .load_balancers()
                             .iter()
                             .map(|lb| resource::LoadBalancer {
-                                target_group_arn: lb.target_group_arn().map(|tg| tg.to_string()),
+                                target_group_arn: lb
+                                    .target_group_arn()
+                                    .map(|tg| target_group_addresses.get(tg).cloned().unwrap_or_else(|| tg.to_string())),
                                 load_balancer_name: lb.load_balancer_name().map(|ln| ln.to_string()),
                                 container_name: lb.container_name().map(|cn| cn.to_string()),
                                 container_port: lb.container_port,
@@ -149,13 +155,19 @@ impl EcsConnector {
                                 port: sr.port,
                                 container_name: sr.container_name().map(|cn| cn.to_string()),
                                 container_port: sr.container_port,
+                                create_registry: None,
                             })
                             .collect(),
                         scheduling_strategy: service.scheduling_strategy().map(|ss| ss.as_str().to_string()),
                         enable_ecs_managed_tags: Some(service.enable_ecs_managed_tags),
                         propagate_tags: service.propagate_tags().map(|pt| pt.as_str().to_string()),
                         enable_execute_command: Some(service.enable_execute_command),
+                        deployment_controller: service
+                            .deployment_controller()
+                            .map(|dc| dc.r#type().as_str().to_string()),
                         tags: tags::Tags::from(service.tags()),
+                        health_gate: None,
+                        ignore_desired_count: None,
                     };
 
                     return get_resource_response!(
@@ -169,6 +181,60 @@ impl EcsConnector {
 
                 Ok(None)
             }
+            EcsResourceAddress::TaskSet(region, cluster_name, service_name, external_id) => {
+                let client = self.get_or_init_client(&region).await?;
+                let Some(task_set) = util::find_task_set(&client, &cluster_name, &service_name, &external_id).await? else {
+                    return Ok(None);
+                };
+
+                // Target groups that the repo manages are reported by address, as they're written
+                let target_group_addresses = elb_addresses_by_arn::<ElbTargetGroupAddress>(&self.prefix, &region)?;
+
+                let our_task_set = resource::TaskSet {
+                    task_definition: task_set.task_definition().unwrap_or_default().to_string(),
+                    launch_type: task_set.launch_type().map(|lt| lt.as_str().to_string()),
+                    capacity_provider_strategy: task_set
+                        .capacity_provider_strategy()
+                        .iter()
+                        .map(|s| resource::CapacityProviderStrategyItem {
+                            capacity_provider: s.capacity_provider().to_string(),
+                            weight: Some(s.weight),
+                            base: Some(s.base),
+                        })
+                        .collect(),
+                    platform_version: task_set.platform_version().map(|p| p.to_string()),
+                    network_configuration: task_set.network_configuration().map(|nc| resource::NetworkConfiguration {
+                        awsvpc_configuration: nc.awsvpc_configuration().map(|vpc| resource::AwsVpcConfiguration {
+                            subnets: vpc.subnets().to_vec(),
+                            security_groups: vpc.security_groups().to_vec(),
+                            assign_public_ip: vpc.assign_public_ip().map(|p| p.as_str().to_string()),
+                        }),
+                    }),
+                    load_balancers: task_set
+                        .load_balancers()
+                        .iter()
+                        .map(|lb| resource::LoadBalancer {
+                            target_group_arn: lb
+                                .target_group_arn()
+                                .map(|tg| target_group_addresses.get(tg).cloned().unwrap_or_else(|| tg.to_string())),
+                            load_balancer_name: lb.load_balancer_name().map(|ln| ln.to_string()),
+                            container_name: lb.container_name().map(|cn| cn.to_string()),
+                            container_port: lb.container_port,
+                        })
+                        .collect(),
+                    scale_percent: task_set.scale().map(|scale| scale.value).unwrap_or_default(),
+                    primary: task_set.status() == Some("PRIMARY"),
+                    tags: tags::Tags::from(task_set.tags()),
+                };
+
+                get_resource_response!(
+                    EcsResource::TaskSet(our_task_set),
+                    [
+                        (String::from("task_set_arn"), task_set.task_set_arn.unwrap_or_default()),
+                        (String::from("task_set_id"), task_set.id.unwrap_or_default())
+                    ]
+                )
+            }
             EcsResourceAddress::TaskDefinition(region, family) => {
                 let client = self.get_or_init_client(&region).await?;
                 let task_def = util::get_task_definition(&client, &family).await;
@@ -353,6 +419,7 @@ impl EcsConnector {
                                             options: fc.options().unwrap_or(&HashMap::default()).clone(),
                                         }
                                     }),
+                                    credential_specs: cd.credential_specs().to_vec(),
                                 }
                             })
                             .collect(),
@@ -428,6 +495,19 @@ impl EcsConnector {
                             cpu_architecture: rp.cpu_architecture().map(|ca| ca.as_str().to_string()),
                             operating_system_family: rp.operating_system_family().map(|osf| osf.to_string()),
                         }),
+                        ephemeral_storage: task_def.ephemeral_storage().map(|es| resource::EphemeralStorage {
+                            size_in_gib: es.size_in_gib,
+                        }),
+                        inference_accelerators: task_def
+                            .inference_accelerators()
+                            .iter()
+                            .map(|ia| resource::InferenceAccelerator {
+                                device_name: ia.device_name().to_string(),
+                                device_type: ia.device_type().to_string(),
+                            })
+                            .collect(),
+                        deploy_to: Vec::new(),
+                        keep_revisions: None,
                     };
 
                     return get_resource_response!(
@@ -436,6 +516,34 @@ impl EcsConnector {
                     );
                 }
 
+                Ok(None)
+            }
+            EcsResourceAddress::ExternalActivation(region, _cluster_name, activation_name) => {
+                let ssm_client = self.get_or_init_ssm_client(&region).await?;
+                let activation = util::get_external_instance_activation(&ssm_client, &activation_name).await?;
+
+                if let Some(activation) = activation {
+                    let expiration_date = match activation.expiration_date() {
+                        Some(date) => Some(date.fmt(aws_sdk_ssm::primitives::DateTimeFormat::DateTime)?),
+                        None => None,
+                    };
+
+                    let our_activation = resource::ExternalInstanceActivation {
+                        iam_role: activation.iam_role().unwrap_or_default().to_string(),
+                        registration_limit: activation.registration_limit().unwrap_or_default(),
+                        expiration_date,
+                        description: activation.description().filter(|d| !d.is_empty()).map(|d| d.to_string()),
+                    };
+
+                    return get_resource_response!(
+                        EcsResource::ExternalInstanceActivation(our_activation),
+                        [(
+                            String::from("activation_id"),
+                            activation.activation_id().unwrap_or_default().to_string()
+                        )]
+                    );
+                }
+
                 Ok(None)
             }
         }
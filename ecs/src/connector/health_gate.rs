@@ -0,0 +1,183 @@
+use std::time::{Duration, SystemTime};
+
+use anyhow::bail;
+use aws_sdk_cloudwatch::types::StateValue;
+use aws_sdk_ecs::types::{Deployment, Service};
+
+use crate::{resource::HealthGate, util};
+
+use super::EcsConnector;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+/// How many of the service's events to include when a deployment fails.
+const MAX_ROLLOUT_EVENTS: usize = 10;
+/// Used when neither the service nor the connector config sets `deployment_timeout_seconds`.
+pub const DEFAULT_DEPLOYMENT_TIMEOUT_SECONDS: u64 = 30 * 60;
+
+impl EcsConnector {
+    /// Waits for the service's primary deployment to finish rolling out, failing with the service's
+    /// recent events if the circuit breaker trips, then watches the gate's alarms for the bake window,
+    /// failing as soon as one of them goes into ALARM.
+    /// Returns a summary for the op's friendly message.
+    pub async fn await_service_health(
+        &self,
+        region: &str,
+        cluster_name: &str,
+        service_name: &str,
+        gate: &HealthGate,
+    ) -> anyhow::Result<String> {
+        let client = self.get_or_init_client(region).await?;
+
+        let timeout_seconds = gate.deployment_timeout_seconds.unwrap_or(DEFAULT_DEPLOYMENT_TIMEOUT_SECONDS);
+        let deadline = SystemTime::now() + Duration::from_secs(timeout_seconds);
+        // The deployment started by the preceding op. It's tracked by ID, since a circuit breaker
+        // rollback replaces it as the primary deployment with one that can complete successfully.
+        let mut deployment_id: Option<String> = None;
+        loop {
+            let Some(service) = util::get_service(&client, cluster_name, service_name).await? else {
+                bail!("ECS service `{}` not found in cluster `{}`", service_name, cluster_name);
+            };
+
+            let Some(primary) = service.deployments().iter().find(|d| d.status() == Some("PRIMARY")) else {
+                bail!("ECS service `{}` in cluster `{}` has no primary deployment", service_name, cluster_name);
+            };
+            let id = deployment_id.get_or_insert_with(|| primary.id().unwrap_or_default().to_string());
+
+            let Some(deployment) = service.deployments().iter().find(|d| d.id() == Some(id.as_str())) else {
+                bail!(
+                    "Deployment {} of ECS service `{}` in cluster `{}` was replaced before it finished{}",
+                    id,
+                    service_name,
+                    cluster_name,
+                    rollout_events(&service, primary)
+                );
+            };
+
+            match deployment.rollout_state().map(|s| s.as_str()) {
+                Some("FAILED") => bail!(
+                    "Deployment of ECS service `{}` in cluster `{}` failed{}: {}{}",
+                    service_name,
+                    cluster_name,
+                    if deployment.status() != Some("PRIMARY") {
+                        " and was rolled back"
+                    } else {
+                        ""
+                    },
+                    deployment.rollout_state_reason().unwrap_or("no reason given"),
+                    rollout_events(&service, deployment)
+                ),
+                Some("COMPLETED") if deployment.status() == Some("PRIMARY") => break,
+                // Services using an external or CODE_DEPLOY controller don't report a rollout state
+                None if service.deployments().len() == 1 && deployment.running_count() == deployment.desired_count() => break,
+                _ => {}
+            }
+
+            if SystemTime::now() > deadline {
+                bail!(
+                    "Deployment of ECS service `{}` in cluster `{}` did not finish within {}s ({} of {} tasks running){}",
+                    service_name,
+                    cluster_name,
+                    timeout_seconds,
+                    deployment.running_count(),
+                    deployment.desired_count(),
+                    rollout_events(&service, deployment)
+                );
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+
+        let alarm_names = gate.alarm_names.clone().unwrap_or_default();
+        let bake_window = Duration::from_secs(gate.bake_window_seconds.unwrap_or(0));
+
+        let bake_end = SystemTime::now() + bake_window;
+        loop {
+            self.check_alarms(region, service_name, &alarm_names).await?;
+            if SystemTime::now() >= bake_end {
+                break;
+            }
+            tokio::time::sleep(POLL_INTERVAL.min(bake_end.duration_since(SystemTime::now()).unwrap_or_default()))
+                .await;
+        }
+
+        let mut summary = format!("ECS service `{}` finished deploying", service_name);
+        if !bake_window.is_zero() {
+            summary.push_str(&format!(" and stayed healthy over a {}s bake window", bake_window.as_secs()));
+        }
+        if !alarm_names.is_empty() {
+            summary.push_str(&format!(" (alarms: {})", alarm_names.join(", ")));
+        }
+        Ok(summary)
+    }
+
+    /// Fails if any of `alarm_names` is in ALARM, or doesn't exist.
+    async fn check_alarms(&self, region: &str, service_name: &str, alarm_names: &[String]) -> anyhow::Result<()> {
+        if alarm_names.is_empty() {
+            return Ok(());
+        }
+
+        let client = self.get_or_init_cloudwatch_client(region).await?;
+        let resp = client
+            .describe_alarms()
+            .set_alarm_names(Some(alarm_names.to_vec()))
+            .send()
+            .await?;
+
+        let missing: Vec<&str> = alarm_names
+            .iter()
+            .filter(|name| !resp.metric_alarms().iter().any(|a| a.alarm_name() == Some(name.as_str())))
+            .filter(|name| !resp.composite_alarms().iter().any(|a| a.alarm_name() == Some(name.as_str())))
+            .map(|name| name.as_str())
+            .collect();
+        if !missing.is_empty() {
+            bail!(
+                "Health gate for ECS service `{}` names CloudWatch alarms that don't exist in {}: {}",
+                service_name,
+                region,
+                missing.join(", ")
+            );
+        }
+
+        let firing: Vec<&str> = resp
+            .metric_alarms()
+            .iter()
+            .filter(|a| a.state_value() == Some(&StateValue::Alarm))
+            .filter_map(|a| a.alarm_name())
+            .chain(
+                resp.composite_alarms()
+                    .iter()
+                    .filter(|a| a.state_value() == Some(&StateValue::Alarm))
+                    .filter_map(|a| a.alarm_name()),
+            )
+            .collect();
+        if !firing.is_empty() {
+            bail!(
+                "Health gate failed for ECS service `{}`: {} in ALARM. The deployment has already been applied; revert it to roll back.",
+                service_name,
+                firing.join(", ")
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// The service events logged since `deployment` started, oldest first, to explain why it failed.
+fn rollout_events(service: &Service, deployment: &Deployment) -> String {
+    let mut events: Vec<_> = service
+        .events()
+        .iter()
+        .filter(|e| match (e.created_at(), deployment.created_at()) {
+            (Some(event_at), Some(deployment_at)) => event_at >= deployment_at,
+            _ => true,
+        })
+        .filter_map(|e| e.message())
+        .take(MAX_ROLLOUT_EVENTS)
+        .collect();
+    if events.is_empty() {
+        return String::new();
+    }
+
+    // ECS returns events newest first
+    events.reverse();
+    format!("\nRecent service events:\n  {}", events.join("\n  "))
+}
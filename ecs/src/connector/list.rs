@@ -45,6 +45,23 @@ impl EcsConnector {
                                 if let Some(services) = describe_services_resp.services {
                                     for service in services {
                                         if let Some(service_name) = service.service_name {
+                                            // Task sets without an external ID weren't created through a plan, and have no address
+                                            for task_set in service.task_sets.unwrap_or_default() {
+                                                if let Some(external_id) = task_set.external_id
+                                                    && task_set.status.as_deref() != Some("DRAINING")
+                                                {
+                                                    results.push(
+                                                        EcsResourceAddress::TaskSet(
+                                                            region_name.to_string(),
+                                                            cluster_name.clone(),
+                                                            service_name.clone(),
+                                                            external_id,
+                                                        )
+                                                        .to_path_buf(),
+                                                    );
+                                                }
+                                            }
+
                                             results.push(
                                                 EcsResourceAddress::Service(
                                                     region_name.to_string(),
@@ -88,6 +105,9 @@ impl EcsConnector {
                     }
                 }
             }
+
+            // External instance activations aren't listed: SSM activations aren't tied to a cluster,
+            // and expire once they've served their purpose of registering hosts.
         }
 
         Ok(results)
@@ -1,15 +1,99 @@
 use std::path::Path;
 
+use anyhow::{Context, bail};
 use autoschematic_core::{
     connector::{ConnectorOp, OpExecResponse, ResourceAddress},
     error_util::invalid_op,
 };
 
-use crate::{addr::EcsResourceAddress, op::EcsConnectorOp, op_impl};
+use crate::{
+    addr::EcsResourceAddress,
+    op::EcsConnectorOp,
+    op_impl,
+    resource::{Service, TaskDefinition, TaskSet},
+    util::{find_cloud_map_service, resolve_target_groups},
+};
 
 use super::EcsConnector;
 
 impl EcsConnector {
+    /// Target groups named by ELB connector address are resolved to ARNs only when the op runs,
+    /// since the target group may be created in the same apply.
+    fn resolve_service_target_groups(&self, service: Service) -> anyhow::Result<Service> {
+        Ok(Service {
+            load_balancers: resolve_target_groups(&self.prefix, service.load_balancers)?,
+            ..service
+        })
+    }
+
+    /// Registries created through `create_registry` are looked up by name, since their ARN
+    /// isn't known until the CreateServiceDiscoveryService op has run.
+    async fn resolve_service_registries(&self, region: &str, mut service: Service) -> anyhow::Result<Service> {
+        for registry in &mut service.service_registries {
+            if registry.registry_arn.is_some() {
+                continue;
+            }
+            let Some(create_registry) = &registry.create_registry else {
+                continue;
+            };
+
+            let client = self.get_or_init_servicediscovery_client(region).await?;
+            let Some(arn) = find_cloud_map_service(&client, &create_registry.namespace_id, &create_registry.name).await? else {
+                bail!(
+                    "Cloud Map service {} not found in namespace {}. Has it been created yet?",
+                    create_registry.name,
+                    create_registry.namespace_id
+                );
+            };
+            registry.registry_arn = Some(arn);
+        }
+
+        Ok(service)
+    }
+
+    /// Registers `task_definition`, rolls it out to the services in its `deploy_to` one at a time,
+    /// and prunes old revisions only once every rollout has succeeded.
+    async fn register_and_deploy(
+        &self,
+        region: &str,
+        family: &str,
+        task_definition: &TaskDefinition,
+    ) -> anyhow::Result<OpExecResponse> {
+        let client = self.get_or_init_client(region).await?;
+        let registered = op_impl::register_task_definition(&client, family, task_definition).await?;
+        let arn = registered
+            .outputs
+            .as_ref()
+            .and_then(|outputs| outputs.get("arn").cloned().flatten())
+            .context("RegisterTaskDefinition returned no ARN")?;
+
+        let mut messages = vec![format!("Registered task definition {}", arn)];
+
+        let gate = self.config.lock().await.health_gate.clone().unwrap_or_default();
+        for target in &task_definition.deploy_to {
+            op_impl::update_service_task_definition(&client, &target.cluster, &target.service, &arn)
+                .await
+                .with_context(|| format!("Registered {}, but couldn't deploy it", arn))?;
+            let summary = self
+                .await_service_health(region, &target.cluster, &target.service, &gate)
+                .await
+                .with_context(|| format!("Registered {}, but its rollout failed", arn))?;
+            messages.push(summary);
+        }
+
+        if let Some(keep) = task_definition.keep_revisions {
+            let deregistered = op_impl::deregister_old_revisions(&client, family, keep).await?;
+            if !deregistered.is_empty() {
+                messages.push(format!("Deregistered {}", deregistered.join(", ")));
+            }
+        }
+
+        Ok(OpExecResponse {
+            outputs: registered.outputs,
+            friendly_message: Some(messages.join("\n")),
+        })
+    }
+
     pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
         let addr = EcsResourceAddress::from_path(addr)?;
         let op = EcsConnectorOp::from_str(op)?;
@@ -44,6 +128,10 @@ impl EcsConnector {
                     )
                     .await
                 }
+                EcsConnectorOp::UpdateClusterConfiguration(configuration) => {
+                    let client = self.get_or_init_client(region).await?;
+                    op_impl::update_cluster_configuration(&client, cluster_name, configuration).await
+                }
                 EcsConnectorOp::DeleteCluster => {
                     let client = self.get_or_init_client(region).await?;
                     op_impl::delete_cluster(&client, cluster_name).await
@@ -52,9 +140,18 @@ impl EcsConnector {
             },
             EcsResourceAddress::Service(region, cluster_name, service_name) => match op {
                 EcsConnectorOp::CreateService(service) => {
+                    let service = self.resolve_service_target_groups(service)?;
+                    let service = self.resolve_service_registries(region, service).await?;
                     let client = self.get_or_init_client(region).await?;
                     op_impl::create_service(&client, cluster_name, &service, service_name).await
                 }
+                EcsConnectorOp::AwaitServiceHealth(gate) => {
+                    let summary = self.await_service_health(region, cluster_name, service_name, &gate).await?;
+                    Ok(OpExecResponse {
+                        outputs: None,
+                        friendly_message: Some(summary),
+                    })
+                }
                 EcsConnectorOp::UpdateServiceTags(old_tags, new_tags) => {
                     let client = self.get_or_init_client(region).await?;
                     op_impl::update_service_tags(&client, cluster_name, service_name, &old_tags, &new_tags).await
@@ -89,6 +186,7 @@ impl EcsConnector {
                     old_load_balancers,
                     new_load_balancers,
                 } => {
+                    let new_load_balancers = resolve_target_groups(&self.prefix, new_load_balancers)?;
                     let client = self.get_or_init_client(region).await?;
                     op_impl::update_service_load_balancers(
                         &client,
@@ -99,6 +197,20 @@ impl EcsConnector {
                     )
                     .await
                 }
+                EcsConnectorOp::UpdateServicePlacement {
+                    placement_constraints,
+                    placement_strategy,
+                } => {
+                    let client = self.get_or_init_client(region).await?;
+                    op_impl::update_service_placement(
+                        &client,
+                        cluster_name,
+                        service_name,
+                        placement_constraints,
+                        placement_strategy,
+                    )
+                    .await
+                }
                 EcsConnectorOp::EnableExecuteCommand(enable) => {
                     let client = self.get_or_init_client(region).await?;
                     op_impl::enable_execute_command(&client, cluster_name, service_name, enable).await
@@ -107,6 +219,39 @@ impl EcsConnector {
                     let client = self.get_or_init_client(region).await?;
                     op_impl::delete_service(&client, cluster_name, service_name).await
                 }
+                EcsConnectorOp::ReplaceService(service) => {
+                    let service = self.resolve_service_target_groups(service)?;
+                    let service = self.resolve_service_registries(region, service).await?;
+                    let client = self.get_or_init_client(region).await?;
+                    op_impl::replace_service(&client, cluster_name, &service, service_name).await
+                }
+                EcsConnectorOp::CreateServiceDiscoveryService(cloud_map_service) => {
+                    let client = self.get_or_init_servicediscovery_client(region).await?;
+                    op_impl::create_cloud_map_service(&client, &cloud_map_service).await
+                }
+                _ => Err(invalid_op(&addr, &op)),
+            },
+            EcsResourceAddress::TaskSet(region, cluster_name, service_name, external_id) => match op {
+                EcsConnectorOp::CreateTaskSet(task_set) => {
+                    let task_set = TaskSet {
+                        load_balancers: resolve_target_groups(&self.prefix, task_set.load_balancers)?,
+                        ..task_set
+                    };
+                    let client = self.get_or_init_client(region).await?;
+                    op_impl::create_task_set(&client, cluster_name, service_name, external_id, &task_set).await
+                }
+                EcsConnectorOp::UpdateTaskSetScale(scale_percent) => {
+                    let client = self.get_or_init_client(region).await?;
+                    op_impl::update_task_set_scale(&client, cluster_name, service_name, external_id, scale_percent).await
+                }
+                EcsConnectorOp::UpdateServicePrimaryTaskSet => {
+                    let client = self.get_or_init_client(region).await?;
+                    op_impl::update_service_primary_task_set(&client, cluster_name, service_name, external_id).await
+                }
+                EcsConnectorOp::DeleteTaskSet { force } => {
+                    let client = self.get_or_init_client(region).await?;
+                    op_impl::delete_task_set(&client, cluster_name, service_name, external_id, force).await
+                }
                 _ => Err(invalid_op(&addr, &op)),
             },
             EcsResourceAddress::TaskDefinition(region, family) => match op {
@@ -114,6 +259,9 @@ impl EcsConnector {
                     let client = self.get_or_init_client(region).await?;
                     op_impl::register_task_definition(&client, family, &task_definition).await
                 }
+                EcsConnectorOp::RegisterTaskDefinitionAndDeploy(task_definition) => {
+                    self.register_and_deploy(region, family, &task_definition).await
+                }
                 EcsConnectorOp::UpdateTaskDefinitionTags(old_tags, new_tags) => {
                     let client = self.get_or_init_client(region).await?;
                     op_impl::update_task_definition_tags(&client, family, &old_tags, &new_tags).await
@@ -124,6 +272,18 @@ impl EcsConnector {
                 }
                 _ => Err(invalid_op(&addr, &op)),
             },
+            EcsResourceAddress::ExternalActivation(region, cluster_name, activation_name) => match op {
+                EcsConnectorOp::CreateExternalInstanceActivation(activation) => {
+                    let ssm_client = self.get_or_init_ssm_client(region).await?;
+                    op_impl::create_external_instance_activation(&ssm_client, region, cluster_name, activation_name, &activation)
+                        .await
+                }
+                EcsConnectorOp::DeleteExternalInstanceActivation => {
+                    let ssm_client = self.get_or_init_ssm_client(region).await?;
+                    op_impl::delete_external_instance_activation(&ssm_client, activation_name).await
+                }
+                _ => Err(invalid_op(&addr, &op)),
+            },
         }
     }
 }
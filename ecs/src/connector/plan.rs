@@ -1,5 +1,7 @@
 use std::path::Path;
 
+use anyhow::{Context, bail};
+
 use autoschematic_core::{
     connector::{PlanResponseElement, ResourceAddress},
     connector_op,
@@ -8,10 +10,239 @@ use autoschematic_core::{
 
 use autoschematic_core::connector::ConnectorOp;
 
-use crate::{addr::EcsResourceAddress, op::EcsConnectorOp, resource};
+use crate::{addr::EcsResourceAddress, op::EcsConnectorOp, patch, resource, util};
 
 use super::EcsConnector;
 
+/// Container definitions are where task definitions get big, so changes to them are shown as a
+/// JSON patch rather than as a diff of the whole list.
+fn task_definition_diff(old: &resource::TaskDefinition, new: &resource::TaskDefinition) -> String {
+    let without_containers = |task_def: &resource::TaskDefinition| resource::TaskDefinition {
+        container_definitions: Vec::new(),
+        ..task_def.clone()
+    };
+    let diff = diff_ron_values(&without_containers(old), &without_containers(new)).unwrap_or_default();
+
+    let ops = patch::diff(
+        &serde_json::json!({ "container_definitions": old.container_definitions }),
+        &serde_json::json!({ "container_definitions": new.container_definitions }),
+    );
+    if ops.is_empty() {
+        return diff;
+    }
+
+    format!("{}Container definitions:\n{}\n", diff, patch::format_patch(&ops))
+}
+
+/// EXTERNAL services run on ECS Anywhere hosts, which lack some of the features of EC2 and Fargate capacity.
+fn validate_launch_type(service_name: &str, service: &resource::Service) -> Result<(), anyhow::Error> {
+    if service.launch_type.as_deref() != Some("EXTERNAL") {
+        return Ok(());
+    }
+
+    let problems = util::check_external_service(service);
+    if !problems.is_empty() {
+        bail!(
+            "ECS service {} uses the EXTERNAL launch type, but:\n{}",
+            service_name,
+            problems.join("\n")
+        );
+    }
+
+    Ok(())
+}
+
+const EXECUTE_COMMAND_LOGGING: &[&str] = &["NONE", "DEFAULT", "OVERRIDE"];
+
+/// Execute command logs only go to the log_configuration destinations when logging is OVERRIDE.
+fn validate_cluster_configuration(cluster_name: &str, cluster: &resource::Cluster) -> Result<(), anyhow::Error> {
+    let Some(exec_config) = cluster
+        .configuration
+        .as_ref()
+        .and_then(|c| c.execute_command_configuration.as_ref())
+    else {
+        return Ok(());
+    };
+
+    let logging = exec_config.logging.as_deref().unwrap_or("DEFAULT");
+    if !EXECUTE_COMMAND_LOGGING.contains(&logging) {
+        bail!(
+            "ECS cluster {} has execute command logging {}: expected one of {}",
+            cluster_name,
+            logging,
+            EXECUTE_COMMAND_LOGGING.join(", ")
+        );
+    }
+
+    match (logging, &exec_config.log_configuration) {
+        ("OVERRIDE", None) => bail!(
+            "ECS cluster {} has execute command logging OVERRIDE, but no log_configuration",
+            cluster_name
+        ),
+        ("OVERRIDE", Some(log_config))
+            if log_config.cloud_watch_log_group_name.is_none() && log_config.s3_bucket_name.is_none() =>
+        {
+            bail!(
+                "ECS cluster {} has an execute command log_configuration with neither cloud_watch_log_group_name nor s3_bucket_name",
+                cluster_name
+            )
+        }
+        ("OVERRIDE", Some(_)) | (_, None) => Ok(()),
+        (_, Some(_)) => bail!(
+            "ECS cluster {} has an execute command log_configuration, which is only used when logging is OVERRIDE",
+            cluster_name
+        ),
+    }
+}
+
+const DEPLOYMENT_CONTROLLERS: &[&str] = &["ECS", "CODE_DEPLOY", "EXTERNAL"];
+
+/// Services using the EXTERNAL deployment controller leave task placement to their task sets.
+fn validate_deployment_controller(service_name: &str, service: &resource::Service) -> Result<(), anyhow::Error> {
+    let Some(deployment_controller) = &service.deployment_controller else {
+        return Ok(());
+    };
+    if !DEPLOYMENT_CONTROLLERS.contains(&deployment_controller.as_str()) {
+        bail!(
+            "ECS service {} has deployment_controller {}: expected one of {}",
+            service_name,
+            deployment_controller,
+            DEPLOYMENT_CONTROLLERS.join(", ")
+        );
+    }
+    if deployment_controller != "EXTERNAL" {
+        return Ok(());
+    }
+
+    let mut problems = Vec::new();
+    if !service.task_definition.is_empty() {
+        problems.push("task_definition must be empty: each task set names its own");
+    }
+    if service.launch_type.is_some() {
+        problems.push("launch_type must be unset: each task set sets its own");
+    }
+    if service.network_configuration.is_some() {
+        problems.push("network_configuration must be unset: each task set sets its own");
+    }
+    if !service.load_balancers.is_empty() {
+        problems.push("load_balancers must be empty: each task set sets its own");
+    }
+    if !problems.is_empty() {
+        bail!(
+            "ECS service {} uses the EXTERNAL deployment controller, but:\n{}",
+            service_name,
+            problems.join("\n")
+        );
+    }
+
+    Ok(())
+}
+
+fn validate_task_set(external_id: &str, task_set: &resource::TaskSet) -> Result<(), anyhow::Error> {
+    if !(0.0..=100.0).contains(&task_set.scale_percent) {
+        bail!(
+            "Task set {} has scale_percent {}: expected 0 to 100",
+            external_id,
+            task_set.scale_percent
+        );
+    }
+    Ok(())
+}
+
+/// Target groups can be named by their ELB connector address instead of by ARN.
+fn validate_target_groups(region: &str, service_name: &str, service: &resource::Service) -> Result<(), anyhow::Error> {
+    let problems = util::check_target_group_addresses(region, &service.load_balancers);
+    if !problems.is_empty() {
+        bail!("ECS service {} has invalid load balancers:\n{}", service_name, problems.join("\n"));
+    }
+
+    Ok(())
+}
+
+fn validate_compatibilities(task_def_id: &str, task_def: &resource::TaskDefinition) -> Result<(), anyhow::Error> {
+    if !task_def.requires_compatibilities.iter().any(|c| c == "EXTERNAL") {
+        return Ok(());
+    }
+
+    let problems = util::check_external_task_definition(task_def);
+    if !problems.is_empty() {
+        bail!(
+            "ECS task definition {} requires EXTERNAL compatibility, but:\n{}",
+            task_def_id,
+            problems.join("\n")
+        );
+    }
+
+    Ok(())
+}
+
+fn validate_ephemeral_storage(task_def_id: &str, task_def: &resource::TaskDefinition) -> Result<(), anyhow::Error> {
+    if let Some(ephemeral_storage) = &task_def.ephemeral_storage
+        && !(21..=200).contains(&ephemeral_storage.size_in_gib)
+    {
+        bail!(
+            "ECS task definition {} has {} GiB of ephemeral storage: expected 21 to 200 GiB",
+            task_def_id,
+            ephemeral_storage.size_in_gib
+        );
+    }
+    Ok(())
+}
+
+fn validate_rollout(task_def_id: &str, task_def: &resource::TaskDefinition) -> Result<(), anyhow::Error> {
+    if task_def.keep_revisions == Some(0) {
+        bail!(
+            "Task definition {} has keep_revisions set to 0, which would deregister the revision just registered",
+            task_def_id
+        );
+    }
+    Ok(())
+}
+
+/// Registers `task_def`, rolling it out and pruning old revisions in the same op when it sets
+/// `deploy_to` or `keep_revisions`.
+fn register_task_definition_op(task_def: resource::TaskDefinition, message: String) -> PlanResponseElement {
+    if task_def.deploy_to.is_empty() && task_def.keep_revisions.is_none() {
+        return connector_op!(
+            EcsConnectorOp::RegisterTaskDefinition(task_def),
+            vec!["arn".to_string(), "task_definition_id".to_string()],
+            message
+        );
+    }
+
+    let mut steps = Vec::new();
+    if !task_def.deploy_to.is_empty() {
+        let services: Vec<String> = task_def
+            .deploy_to
+            .iter()
+            .map(|target| format!("`{}` in cluster `{}`", target.service, target.cluster))
+            .collect();
+        steps.push(format!("deploy it to ECS service {} and wait for each rollout", services.join(", ")));
+    }
+    if let Some(keep) = task_def.keep_revisions {
+        steps.push(format!("deregister all but the newest {} revisions", keep));
+    }
+
+    connector_op!(
+        EcsConnectorOp::RegisterTaskDefinitionAndDeploy(task_def),
+        vec!["arn".to_string(), "task_definition_id".to_string()],
+        format!("{}\nThen {}", message, steps.join(", then "))
+    )
+}
+
+fn validate_credential_specs(task_def_id: &str, task_def: &resource::TaskDefinition) -> Result<(), anyhow::Error> {
+    let problems = util::check_credential_specs(task_def);
+    if !problems.is_empty() {
+        bail!(
+            "ECS task definition {} has invalid credential specs:\n{}",
+            task_def_id,
+            problems.join("\n")
+        );
+    }
+
+    Ok(())
+}
+
 impl EcsConnector {
     pub async fn do_plan(
         &self,
@@ -30,6 +261,7 @@ impl EcsConnector {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_cluster)) => {
                         let new_cluster: resource::Cluster = RON.from_str(&new_cluster)?;
+                        validate_cluster_configuration(&cluster_name, &new_cluster)?;
                         Ok(vec![connector_op!(
                             EcsConnectorOp::CreateCluster(new_cluster),
                             format!("Create new ECS cluster {}", cluster_name)
@@ -42,6 +274,7 @@ impl EcsConnector {
                     (Some(old_cluster), Some(new_cluster)) => {
                         let old_cluster: resource::Cluster = RON.from_str(&old_cluster)?;
                         let new_cluster: resource::Cluster = RON.from_str(&new_cluster)?;
+                        validate_cluster_configuration(&cluster_name, &new_cluster)?;
                         let mut ops = Vec::new();
 
                         // Check for tag changes
@@ -53,16 +286,38 @@ impl EcsConnector {
                             ));
                         }
 
+                        // Check for execute command configuration changes
+                        if old_cluster.configuration != new_cluster.configuration {
+                            let diff =
+                                diff_ron_values(&old_cluster.configuration, &new_cluster.configuration).unwrap_or_default();
+                            ops.push(connector_op!(
+                                EcsConnectorOp::UpdateClusterConfiguration(new_cluster.configuration.clone()),
+                                format!(
+                                    "Modify execute command configuration for ECS cluster `{}`\n{}",
+                                    cluster_name, diff
+                                )
+                            ));
+                        }
+
                         // Check for settings changes
-                        if old_cluster.settings != new_cluster.settings {
-                            let mut new_settings = Vec::new();
-                            for setting in &new_cluster.settings {
+                        // Only changed settings are sent. Names and values are passed through verbatim, so that
+                        // newer ones (such as containerInsights "enhanced") aren't dropped.
+                        let mut new_settings = Vec::new();
+                        for setting in &new_cluster.settings {
+                            if !old_cluster.settings.contains(setting) {
                                 new_settings.push((setting.name.clone(), setting.value.clone()));
                             }
+                        }
 
+                        if !new_settings.is_empty() {
+                            let settings_diff = new_settings
+                                .iter()
+                                .map(|(name, value)| format!("{name} = {value}"))
+                                .collect::<Vec<_>>()
+                                .join("\n");
                             ops.push(connector_op!(
                                 EcsConnectorOp::UpdateClusterSettings { settings: new_settings },
-                                format!("Modify settings for ECS cluster `{}`", cluster_name)
+                                format!("Modify settings for ECS cluster `{}`\n{}", cluster_name, settings_diff)
                             ));
                         }
 
@@ -111,15 +366,22 @@ impl EcsConnector {
                     }
                 }
             }
-            EcsResourceAddress::Service(_region, cluster_name, service_name) => {
+            EcsResourceAddress::Service(region, cluster_name, service_name) => {
                 match (current, desired) {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_service)) => {
                         let new_service: resource::Service = RON.from_str(&new_service)?;
-                        Ok(vec![connector_op!(
+                        validate_launch_type(&service_name, &new_service)?;
+                        validate_deployment_controller(&service_name, &new_service)?;
+                        validate_target_groups(&region, &service_name, &new_service)?;
+                        let mut ops = self.plan_service_registries(&region, &service_name, &new_service).await?;
+                        let health_gate = self.plan_health_gate(&service_name, &cluster_name, &new_service).await;
+                        ops.push(connector_op!(
                             EcsConnectorOp::CreateService(new_service),
                             format!("Create new ECS service {} in cluster {}", service_name, cluster_name)
-                        )])
+                        ));
+                        ops.extend(health_gate);
+                        Ok(ops)
                     }
                     (Some(_old_service), None) => Ok(vec![connector_op!(
                         EcsConnectorOp::DeleteService,
@@ -128,7 +390,43 @@ impl EcsConnector {
                     (Some(old_service), Some(new_service)) => {
                         let old_service: resource::Service = RON.from_str(&old_service)?;
                         let new_service: resource::Service = RON.from_str(&new_service)?;
+                        validate_launch_type(&service_name, &new_service)?;
+                        validate_deployment_controller(&service_name, &new_service)?;
+                        validate_target_groups(&region, &service_name, &new_service)?;
+                        let health_gate = self.plan_health_gate(&service_name, &cluster_name, &new_service).await;
                         let mut ops = Vec::new();
+                        // Whether any of the ops start a new deployment, and so should be followed by the health gate
+                        let mut deploys = false;
+
+                        // Launch type, scheduling strategy and deployment controller can't be changed through UpdateService,
+                        // so the service has to be replaced as a whole.
+                        let mut replacement_fields = Vec::new();
+                        if old_service.launch_type != new_service.launch_type {
+                            replacement_fields.push("launch_type");
+                        }
+                        if old_service.scheduling_strategy != new_service.scheduling_strategy {
+                            replacement_fields.push("scheduling_strategy");
+                        }
+                        if old_service.deployment_controller != new_service.deployment_controller {
+                            replacement_fields.push("deployment_controller");
+                        }
+
+                        if !replacement_fields.is_empty() {
+                            let diff = diff_ron_values(&old_service, &new_service).unwrap_or_default();
+                            let mut ops = self.plan_service_registries(&region, &service_name, &new_service).await?;
+                            ops.push(connector_op!(
+                                EcsConnectorOp::ReplaceService(new_service),
+                                format!(
+                                    "REPLACE ECS service `{}` in cluster `{}` (requires replacement: {} cannot be changed in place)\n{}",
+                                    service_name,
+                                    cluster_name,
+                                    replacement_fields.join(", "),
+                                    diff
+                                )
+                            ));
+                            ops.extend(health_gate);
+                            return Ok(ops);
+                        }
 
                         // Check for tag changes
                         if old_service.tags != new_service.tags {
@@ -142,8 +440,12 @@ impl EcsConnector {
                             ));
                         }
 
-                        // Check for desired count changes
-                        if old_service.desired_count != new_service.desired_count {
+                        // Check for desired count changes, unless they're left to autoscaling
+                        if old_service.desired_count != new_service.desired_count
+                            && !self
+                                .ignores_desired_count(&region, &cluster_name, &service_name, &old_service, &new_service)
+                                .await?
+                        {
                             ops.push(connector_op!(
                                 EcsConnectorOp::UpdateServiceDesiredCount(new_service.desired_count),
                                 format!(
@@ -153,8 +455,13 @@ impl EcsConnector {
                             ));
                         }
 
-                        // Check for task definition changes
-                        if old_service.task_definition != new_service.task_definition {
+                        // Check for task definition changes. A service naming just the family is left on
+                        // whichever revision was last deployed to it.
+                        let follows_family = util::tracks_latest_revision(&new_service.task_definition)
+                            && util::task_definition_family(&old_service.task_definition)
+                                == util::task_definition_family(&new_service.task_definition);
+                        if old_service.task_definition != new_service.task_definition && !follows_family {
+                            deploys = true;
                             ops.push(connector_op!(
                                 EcsConnectorOp::UpdateServiceTaskDefinition(new_service.task_definition),
                                 format!(
@@ -204,6 +511,7 @@ impl EcsConnector {
 
                         // Check for load balancer changes
                         if old_service.load_balancers != new_service.load_balancers {
+                            deploys = true;
                             let diff =
                                 diff_ron_values(&old_service.load_balancers, &new_service.load_balancers).unwrap_or_default();
                             ops.push(connector_op!(
@@ -218,19 +526,144 @@ impl EcsConnector {
                             ));
                         }
 
+                        // Check for placement constraint or strategy changes
+                        if old_service.placement_constraints != new_service.placement_constraints
+                            || old_service.placement_strategy != new_service.placement_strategy
+                        {
+                            deploys = true;
+                            let diff = diff_ron_values(
+                                &(&old_service.placement_constraints, &old_service.placement_strategy),
+                                &(&new_service.placement_constraints, &new_service.placement_strategy),
+                            )
+                            .unwrap_or_default();
+                            ops.push(connector_op!(
+                                EcsConnectorOp::UpdateServicePlacement {
+                                    placement_constraints: new_service.placement_constraints,
+                                    placement_strategy: new_service.placement_strategy,
+                                },
+                                format!(
+                                    "Update placement constraints and strategy for ECS service `{}` in cluster `{}`\n{}",
+                                    service_name, cluster_name, diff
+                                )
+                            ));
+                        }
+
+                        if deploys {
+                            ops.extend(health_gate);
+                        }
+
                         Ok(ops)
                     }
                 }
             }
-            EcsResourceAddress::TaskDefinition(_region, task_def_id) => {
+            EcsResourceAddress::TaskSet(region, cluster_name, service_name, external_id) => match (current, desired) {
+                (None, None) => Ok(vec![]),
+                (None, Some(new_task_set)) => {
+                    let new_task_set: resource::TaskSet = RON.from_str(&new_task_set)?;
+                    validate_task_set(&external_id, &new_task_set)?;
+                    let problems = util::check_target_group_addresses(&region, &new_task_set.load_balancers);
+                    if !problems.is_empty() {
+                        bail!("Task set {} has invalid load balancers:\n{}", external_id, problems.join("\n"));
+                    }
+
+                    let primary = new_task_set.primary;
+                    let mut ops = vec![connector_op!(
+                        EcsConnectorOp::CreateTaskSet(new_task_set),
+                        vec!["task_set_arn".to_string(), "task_set_id".to_string()],
+                        format!(
+                            "Create task set {} in ECS service `{}` in cluster `{}`",
+                            external_id, service_name, cluster_name
+                        )
+                    )];
+                    if primary {
+                        ops.push(connector_op!(
+                            EcsConnectorOp::UpdateServicePrimaryTaskSet,
+                            format!(
+                                "Make task set {} the primary task set of ECS service `{}`",
+                                external_id, service_name
+                            )
+                        ));
+                    }
+                    Ok(ops)
+                }
+                (Some(old_task_set), None) => {
+                    let old_task_set: resource::TaskSet = RON.from_str(&old_task_set)?;
+                    if old_task_set.primary {
+                        bail!(
+                            "Task set {} is the primary task set of ECS service `{}`. Make another task set primary before deleting it.",
+                            external_id,
+                            service_name
+                        );
+                    }
+                    Ok(vec![connector_op!(
+                        EcsConnectorOp::DeleteTaskSet { force: true },
+                        format!(
+                            "DELETE task set {} in ECS service `{}` in cluster `{}`, stopping its tasks",
+                            external_id, service_name, cluster_name
+                        )
+                    )])
+                }
+                (Some(old_task_set), Some(new_task_set)) => {
+                    let old_task_set: resource::TaskSet = RON.from_str(&old_task_set)?;
+                    let new_task_set: resource::TaskSet = RON.from_str(&new_task_set)?;
+                    validate_task_set(&external_id, &new_task_set)?;
+
+                    // Everything but the scale and primary status is fixed when the task set is created
+                    let fixed_fields_changed = resource::TaskSet {
+                        scale_percent: new_task_set.scale_percent,
+                        primary: new_task_set.primary,
+                        ..old_task_set.clone()
+                    } != new_task_set;
+                    if fixed_fields_changed {
+                        let diff = diff_ron_values(&old_task_set, &new_task_set).unwrap_or_default();
+                        bail!(
+                            "Task set {} can only change scale_percent and primary after creation. Create a new task set under another external ID instead.\n{}",
+                            external_id,
+                            diff
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_task_set.scale_percent != new_task_set.scale_percent {
+                        ops.push(connector_op!(
+                            EcsConnectorOp::UpdateTaskSetScale(new_task_set.scale_percent),
+                            format!(
+                                "Scale task set {} in ECS service `{}` from {}% to {}%",
+                                external_id, service_name, old_task_set.scale_percent, new_task_set.scale_percent
+                            )
+                        ));
+                    }
+
+                    // A task set stops being primary when another one is made primary, so only the switch to primary is an op
+                    if !old_task_set.primary && new_task_set.primary {
+                        ops.push(connector_op!(
+                            EcsConnectorOp::UpdateServicePrimaryTaskSet,
+                            format!(
+                                "Make task set {} the primary task set of ECS service `{}`",
+                                external_id, service_name
+                            )
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            EcsResourceAddress::TaskDefinition(region, task_def_id) => {
                 match (current, desired) {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_task_def)) => {
-                        let new_task_def: resource::TaskDefinition = RON.from_str(&new_task_def)?;
-                        Ok(vec![connector_op!(
-                            EcsConnectorOp::RegisterTaskDefinition(new_task_def),
-                            vec!["arn".to_string(), "task_definition_id".to_string()],
-                            format!("Register new ECS task definition {}", task_def_id)
+                        let new_task_def = self.parse_task_definition(&new_task_def)?;
+                        validate_rollout(&task_def_id, &new_task_def)?;
+                        validate_compatibilities(&task_def_id, &new_task_def)?;
+                        validate_ephemeral_storage(&task_def_id, &new_task_def)?;
+                        validate_credential_specs(&task_def_id, &new_task_def)?;
+                        self.validate_environment_files(&region, &task_def_id, &new_task_def).await?;
+                        self.validate_domainless_credential_specs(&region, &task_def_id, &new_task_def).await?;
+                        let (new_task_def, pinned_images) = self.pin_image_digests(&task_def_id, new_task_def).await?;
+                        Ok(vec![register_task_definition_op(
+                            new_task_def,
+                            format!("Register new ECS task definition {}{}", task_def_id, pinned_images),
                         )])
                     }
                     (Some(_old_task_def), None) => Ok(vec![connector_op!(
@@ -242,16 +675,25 @@ impl EcsConnector {
                         // Instead, we register a new one.
 
                         let old_task_def: resource::TaskDefinition = RON.from_str(&old_task_def)?;
-                        let new_task_def: resource::TaskDefinition = RON.from_str(&new_task_def)?;
+                        let new_task_def = self.parse_task_definition(&new_task_def)?;
+                        validate_rollout(&task_def_id, &new_task_def)?;
                         let mut ops = Vec::new();
 
-                        if old_task_def != new_task_def {
-                            let diff = diff_ron_values(&old_task_def, &new_task_def).unwrap_or_default();
+                        // The registered task definition holds pinned images, so compare against the
+                        // digests the tags point to now. A moved tag shows up as a change.
+                        let (new_task_def, pinned_images) = self.pin_image_digests(&task_def_id, new_task_def).await?;
 
-                            ops.push(connector_op!(
-                                EcsConnectorOp::RegisterTaskDefinition(new_task_def),
-                                vec!["arn".to_string(), "task_definition_id".to_string()],
-                                format!("Update ECS task definition {}\n{}", task_def_id, diff)
+                        if old_task_def != new_task_def.without_rollout() {
+                            validate_compatibilities(&task_def_id, &new_task_def)?;
+                            validate_ephemeral_storage(&task_def_id, &new_task_def)?;
+                            validate_credential_specs(&task_def_id, &new_task_def)?;
+                            self.validate_environment_files(&region, &task_def_id, &new_task_def).await?;
+                            self.validate_domainless_credential_specs(&region, &task_def_id, &new_task_def).await?;
+                            let diff = task_definition_diff(&old_task_def, &new_task_def);
+
+                            ops.push(register_task_definition_op(
+                                new_task_def,
+                                format!("Update ECS task definition {}\n{}{}", task_def_id, diff, pinned_images),
                             ));
                         }
 
@@ -259,6 +701,370 @@ impl EcsConnector {
                     }
                 }
             }
+            EcsResourceAddress::ExternalActivation(_region, cluster_name, activation_name) => {
+                match (current, desired) {
+                    (None, None) => Ok(vec![]),
+                    (None, Some(new_activation)) => {
+                        let new_activation: resource::ExternalInstanceActivation = RON.from_str(&new_activation)?;
+                        Ok(vec![connector_op!(
+                            EcsConnectorOp::CreateExternalInstanceActivation(new_activation),
+                            format!(
+                                "Create external instance activation {} for ECS cluster {}",
+                                activation_name, cluster_name
+                            )
+                        )])
+                    }
+                    (Some(_old_activation), None) => Ok(vec![connector_op!(
+                        EcsConnectorOp::DeleteExternalInstanceActivation,
+                        format!(
+                            "DELETE external instance activation {} for ECS cluster {}",
+                            activation_name, cluster_name
+                        )
+                    )]),
+                    (Some(old_activation), Some(new_activation)) => {
+                        let old_activation: resource::ExternalInstanceActivation = RON.from_str(&old_activation)?;
+                        let mut new_activation: resource::ExternalInstanceActivation = RON.from_str(&new_activation)?;
+
+                        // With no expiration date set, SSM picks one, so there's nothing to compare against
+                        if new_activation.expiration_date.is_none() {
+                            new_activation.expiration_date = old_activation.expiration_date.clone();
+                        }
+
+                        if old_activation == new_activation {
+                            return Ok(vec![]);
+                        }
+
+                        // Activations can't be modified
+                        let diff = diff_ron_values(&old_activation, &new_activation).unwrap_or_default();
+                        Ok(vec![
+                            connector_op!(
+                                EcsConnectorOp::DeleteExternalInstanceActivation,
+                                format!(
+                                    "REPLACE external instance activation `{}` for ECS cluster `{}` (requires replacement: activations are immutable)\n{}",
+                                    activation_name, cluster_name, diff
+                                )
+                            ),
+                            connector_op!(
+                                EcsConnectorOp::CreateExternalInstanceActivation(new_activation),
+                                format!(
+                                    "Create external instance activation {} for ECS cluster {}",
+                                    activation_name, cluster_name
+                                )
+                            ),
+                        ])
+                    }
+                }
+            }
+        }
+    }
+
+    /// The AwaitServiceHealth op to follow a deployment of `service` with: the connector-wide health gate
+    /// with the service's own overrides applied, or None if neither is set.
+    /// Whether differences in desired_count should be left out of the plan. An explicit
+    /// `ignore_desired_count` on either side wins; otherwise it's ignored while the service is
+    /// registered as a scalable target, since autoscaling keeps moving it.
+    pub async fn ignores_desired_count(
+        &self,
+        region: &str,
+        cluster_name: &str,
+        service_name: &str,
+        old_service: &resource::Service,
+        new_service: &resource::Service,
+    ) -> anyhow::Result<bool> {
+        if let Some(ignore) = new_service.ignore_desired_count.or(old_service.ignore_desired_count) {
+            return Ok(ignore);
+        }
+
+        let client = self.get_or_init_applicationautoscaling_client(region).await?;
+        util::has_scalable_target(&client, cluster_name, service_name).await
+    }
+
+    /// The registries a service resolves to, for comparison with what get returns. Registries declared
+    /// through `create_registry` are replaced by the ARN of the Cloud Map service they name, once it exists.
+    pub async fn resolved_service_registries(
+        &self,
+        region: &str,
+        registries: &[resource::ServiceRegistry],
+    ) -> anyhow::Result<Vec<resource::ServiceRegistry>> {
+        let mut res = Vec::new();
+
+        for registry in registries {
+            let mut registry = registry.clone();
+            if registry.registry_arn.is_none()
+                && let Some(create_registry) = &registry.create_registry
+            {
+                let client = self.get_or_init_servicediscovery_client(region).await?;
+                registry.registry_arn =
+                    util::find_cloud_map_service(&client, &create_registry.namespace_id, &create_registry.name).await?;
+            }
+            if registry.registry_arn.is_some() {
+                registry.create_registry = None;
+            }
+            res.push(registry);
+        }
+
+        Ok(res)
+    }
+
+    async fn plan_health_gate(
+        &self,
+        service_name: &str,
+        cluster_name: &str,
+        service: &resource::Service,
+    ) -> Option<PlanResponseElement> {
+        // Deployments of services using the EXTERNAL controller happen through their task sets
+        if service.deployment_controller.as_deref() == Some("EXTERNAL") {
+            return None;
         }
+
+        let config = self.config.lock().await;
+        let gate = match (&config.health_gate, &service.health_gate) {
+            (Some(default), Some(overrides)) => default.overlay(overrides),
+            (Some(default), None) => default.clone(),
+            (None, Some(overrides)) => overrides.clone(),
+            (None, None) if config.wait_for_deployments => resource::HealthGate::default(),
+            (None, None) => return None,
+        };
+        drop(config);
+
+        let mut checks = vec![format!(
+            "deployment timeout {}s",
+            gate.deployment_timeout_seconds.unwrap_or(super::health_gate::DEFAULT_DEPLOYMENT_TIMEOUT_SECONDS)
+        )];
+        if let Some(bake_window_seconds) = gate.bake_window_seconds {
+            checks.push(format!("bake window {}s", bake_window_seconds));
+        }
+        if let Some(alarm_names) = &gate.alarm_names {
+            checks.push(format!("alarms: {}", alarm_names.join(", ")));
+        }
+
+        if gate.bake_window_seconds.is_none() && gate.alarm_names.is_none() {
+            return Some(connector_op!(
+                EcsConnectorOp::AwaitServiceHealth(gate),
+                format!(
+                    "Wait for the deployment of ECS service `{}` in cluster `{}` to reach steady state ({})",
+                    service_name,
+                    cluster_name,
+                    checks.join(", ")
+                )
+            ));
+        }
+
+        Some(connector_op!(
+            EcsConnectorOp::AwaitServiceHealth(gate),
+            format!(
+                "Wait for ECS service `{}` in cluster `{}` to pass its health gate ({})",
+                service_name,
+                cluster_name,
+                checks.join(", ")
+            )
+        ))
+    }
+
+    /// Checks the Cloud Map services that `service` registers its tasks with, since ECS only rejects
+    /// a missing registry once CreateService is called. Registries with `create_registry` set that
+    /// don't exist yet are returned as ops to create them, to run ahead of the ECS service.
+    async fn plan_service_registries(
+        &self,
+        region: &str,
+        service_name: &str,
+        service: &resource::Service,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        if service.service_registries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let client = self.get_or_init_servicediscovery_client(region).await?;
+
+        let mut ops = Vec::new();
+        let mut problems = Vec::new();
+        for registry in &service.service_registries {
+            match (&registry.registry_arn, &registry.create_registry) {
+                (Some(registry_arn), None) => {
+                    if let Some(problem) = util::check_registry_arn(&client, region, registry_arn).await? {
+                        problems.push(problem);
+                    }
+                }
+                (None, Some(create_registry)) => {
+                    if util::find_cloud_map_service(&client, &create_registry.namespace_id, &create_registry.name)
+                        .await?
+                        .is_some()
+                    {
+                        continue;
+                    }
+
+                    if !util::cloud_map_namespace_exists(&client, &create_registry.namespace_id).await? {
+                        problems.push(format!("Cloud Map namespace {} does not exist", create_registry.namespace_id));
+                        continue;
+                    }
+
+                    ops.push(connector_op!(
+                        EcsConnectorOp::CreateServiceDiscoveryService(create_registry.clone()),
+                        format!(
+                            "Create new Cloud Map service {} in namespace {} for ECS service {}",
+                            create_registry.name, create_registry.namespace_id, service_name
+                        )
+                    ));
+                }
+                (Some(_), Some(_)) => problems.push(String::from("registry_arn and create_registry can't both be set")),
+                (None, None) => problems.push(String::from("one of registry_arn or create_registry must be set")),
+            }
+        }
+
+        if !problems.is_empty() {
+            bail!(
+                "ECS service {} has invalid service registries:\n{}",
+                service_name,
+                problems.join("\n")
+            );
+        }
+
+        Ok(ops)
+    }
+
+    /// If enabled in the connector config, rewrites each ECR image tag in `task_def` to the digest
+    /// it currently points to, so that the registered task definition keeps running the same image
+    /// even if the tag is later moved. Returns the rewritten task definition and a note listing
+    /// the resolved images, for the plan message.
+    async fn pin_image_digests(
+        &self,
+        task_def_id: &str,
+        mut task_def: resource::TaskDefinition,
+    ) -> Result<(resource::TaskDefinition, String), anyhow::Error> {
+        if !self.config.lock().await.pin_image_digests {
+            return Ok((task_def, String::new()));
+        }
+
+        let mut resolved = Vec::new();
+        let mut unpinned = Vec::new();
+        for container in &mut task_def.container_definitions {
+            let Some(image) = util::EcrImageTag::parse(&container.image) else {
+                if !container.image.contains('@') {
+                    unpinned.push(format!("  {}: {} (not an ECR image)", container.name, container.image));
+                }
+                continue;
+            };
+
+            let ecr_client = self.get_or_init_ecr_client(&image.region).await?;
+            let digest = util::resolve_image_digest(&ecr_client, &image).await.with_context(|| {
+                format!(
+                    "Failed to pin image for container {} in ECS task definition {}",
+                    container.name, task_def_id
+                )
+            })?;
+
+            resolved.push(format!("  {}: {} -> {}", container.name, container.image, digest));
+            container.image = image.pinned(&digest);
+        }
+
+        let mut note = String::new();
+        if !resolved.is_empty() {
+            note.push_str(&format!("\nPinned image digests:\n{}", resolved.join("\n")));
+        }
+        if !unpinned.is_empty() {
+            note.push_str(&format!("\nImages left unpinned:\n{}", unpinned.join("\n")));
+        }
+
+        Ok((task_def, note))
+    }
+
+    /// If enabled in the connector config, checks that every S3 environment file in `task_def`
+    /// exists and is readable by its execution role, since ECS only reports a missing or unreadable
+    /// file when a task fails to start.
+    async fn validate_environment_files(
+        &self,
+        region: &str,
+        task_def_id: &str,
+        task_def: &resource::TaskDefinition,
+    ) -> Result<(), anyhow::Error> {
+        if !self.config.lock().await.validate_environment_files {
+            return Ok(());
+        }
+
+        let file_arns: Vec<&str> = task_def
+            .container_definitions
+            .iter()
+            .flat_map(|cd| cd.environment_files.iter())
+            .filter(|ef| ef.r#type == "s3")
+            .map(|ef| ef.value.as_str())
+            .collect();
+
+        if file_arns.is_empty() {
+            return Ok(());
+        }
+
+        let s3_client = self.get_or_init_s3_client(region).await?;
+        let iam_client = self.get_or_init_iam_client().await?;
+
+        let mut problems = Vec::new();
+        for file_arn in file_arns {
+            if let Some(problem) =
+                util::check_environment_file(&s3_client, &iam_client, task_def.execution_role_arn.as_deref(), file_arn).await?
+            {
+                problems.push(problem);
+            }
+        }
+
+        if !problems.is_empty() {
+            bail!(
+                "Environment file validation failed for ECS task definition {}:\n{}",
+                task_def_id,
+                problems.join("\n")
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Checks that each domainless gMSA credential spec in `task_def` can be read by its execution role,
+    /// and that the role can read the Secrets Manager secret the spec names. ECS only reports
+    /// these once a Windows container fails to start.
+    async fn validate_domainless_credential_specs(
+        &self,
+        region: &str,
+        task_def_id: &str,
+        task_def: &resource::TaskDefinition,
+    ) -> Result<(), anyhow::Error> {
+        let Some(execution_role_arn) = task_def.execution_role_arn.as_deref() else {
+            return Ok(());
+        };
+
+        let specs: Vec<util::CredentialSpec> = task_def
+            .container_definitions
+            .iter()
+            .flat_map(|cd| cd.credential_specs.iter())
+            .filter_map(|spec| util::CredentialSpec::parse(spec))
+            .filter(|spec| spec.domainless)
+            .collect();
+
+        if specs.is_empty() {
+            return Ok(());
+        }
+
+        let s3_client = self.get_or_init_s3_client(region).await?;
+        let iam_client = self.get_or_init_iam_client().await?;
+
+        let mut problems = Vec::new();
+        for spec in &specs {
+            let ssm_region = match &spec.source {
+                util::CredentialSpecSource::Ssm { region } => region.as_str(),
+                util::CredentialSpecSource::S3 { .. } => region,
+            };
+            let ssm_client = self.get_or_init_ssm_client(ssm_region).await?;
+
+            problems.extend(
+                util::check_domainless_credential_spec(&s3_client, &ssm_client, &iam_client, execution_role_arn, spec).await?,
+            );
+        }
+
+        if !problems.is_empty() {
+            bail!(
+                "Credential spec validation failed for ECS task definition {}:\n{}",
+                task_def_id,
+                problems.join("\n")
+            );
+        }
+
+        Ok(())
     }
 }
@@ -0,0 +1,203 @@
+use std::{
+    path::{Component, Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+
+use anyhow::{Context, bail};
+use autoschematic_core::connector::{Resource, ResourceAddress, TaskExecResponse};
+
+use crate::task::{EcsTask, EcsTaskAddress, Exec};
+
+use super::EcsConnector;
+
+/// Used when the task doesn't set `timeout_seconds`.
+const DEFAULT_EXEC_TIMEOUT_SECONDS: u64 = 300;
+/// How many lines of the command's output to repeat in the task's message.
+const OUTPUT_TAIL_LINES: usize = 20;
+const SESSION_MANAGER_PLUGIN: &str = "session-manager-plugin";
+
+/// Where to write the output of an ECS Exec task, relative to the prefix. Paths that could
+/// escape the prefix, being absolute or holding a `..` component, are refused.
+fn output_path(task_name: &str, output_path: Option<&str>) -> anyhow::Result<PathBuf> {
+    let Some(output_path) = output_path else {
+        return Ok(PathBuf::from(format!("aws/ecs/exec-output/{}.log", task_name)));
+    };
+
+    let path = PathBuf::from(output_path);
+    if path.has_root()
+        || path
+            .components()
+            .any(|c| matches!(c, Component::Prefix(_) | Component::RootDir | Component::ParentDir))
+    {
+        bail!(
+            "ECS Exec task {} has output_path `{}`, which must be relative to the prefix and not contain `..`",
+            task_name,
+            output_path
+        );
+    }
+
+    Ok(path)
+}
+
+impl EcsConnector {
+    pub async fn do_task_exec(
+        &self,
+        addr: &Path,
+        body: Vec<u8>,
+        _arg: Option<Vec<u8>>,
+        _state: Option<Vec<u8>>,
+    ) -> anyhow::Result<TaskExecResponse> {
+        let addr = EcsTaskAddress::from_path(addr)?;
+
+        let task = EcsTask::from_bytes(&addr, &body)?;
+        match (&addr, task) {
+            (EcsTaskAddress::Exec { name }, EcsTask::Exec(exec)) => self.exec(name, exec).await,
+        }
+    }
+
+    async fn exec(&self, task_name: &str, exec: Exec) -> anyhow::Result<TaskExecResponse> {
+        let output_path = output_path(task_name, exec.output_path.as_deref())?;
+
+        let client = self.get_or_init_client(&exec.region).await?;
+
+        let task_arn = match (&exec.task, &exec.service) {
+            (Some(task), _) => task.clone(),
+            (None, Some(service)) => client
+                .list_tasks()
+                .cluster(&exec.cluster)
+                .service_name(service)
+                .desired_status(aws_sdk_ecs::types::DesiredStatus::Running)
+                .send()
+                .await?
+                .task_arns()
+                .first()
+                .cloned()
+                .with_context(|| format!("ECS service `{}` in cluster `{}` has no running tasks", service, exec.cluster))?,
+            (None, None) => bail!("ECS Exec task {} sets neither task nor service", task_name),
+        };
+
+        let resp = client.describe_tasks().cluster(&exec.cluster).tasks(&task_arn).send().await?;
+        let Some(task) = resp.tasks().first() else {
+            bail!("ECS task {} not found in cluster `{}`", task_arn, exec.cluster);
+        };
+        if !task.enable_execute_command {
+            bail!(
+                "ECS task {} doesn't have ECS Exec enabled. Set enable_execute_command on its service, and let it start new tasks.",
+                task_arn
+            );
+        }
+
+        let container = match &exec.container {
+            Some(name) => task
+                .containers()
+                .iter()
+                .find(|c| c.name() == Some(name.as_str()))
+                .with_context(|| format!("ECS task {} has no container named {}", task_arn, name))?,
+            None => match task.containers() {
+                [container] => container,
+                _ => bail!("ECS task {} has more than one container: set `container` to pick one", task_arn),
+            },
+        };
+        let container_name = container.name().unwrap_or_default().to_string();
+
+        let agent_running = container
+            .managed_agents()
+            .iter()
+            .any(|agent| agent.name().map(|n| n.as_str()) == Some("ExecuteCommandAgent") && agent.last_status() == Some("RUNNING"));
+        if !agent_running {
+            bail!(
+                "The ECS Exec agent isn't running in container {} of task {}. Check that the task role allows ssmmessages.",
+                container_name,
+                task_arn
+            );
+        }
+        let runtime_id = container
+            .runtime_id()
+            .with_context(|| format!("Container {} of task {} has no runtime ID", container_name, task_arn))?;
+        let task_id = task_arn.rsplit('/').next().unwrap_or(&task_arn);
+
+        // ECS only supports interactive sessions, so the command runs as a session that ends when it exits
+        let resp = client
+            .execute_command()
+            .cluster(&exec.cluster)
+            .task(&task_arn)
+            .container(&container_name)
+            .command(&exec.command)
+            .interactive(true)
+            .send()
+            .await?;
+        let session = resp.session.context("ExecuteCommand returned no session")?;
+
+        let session_json = serde_json::json!({
+            "SessionId": session.session_id,
+            "StreamUrl": session.stream_url,
+            "TokenValue": session.token_value,
+        });
+        let target_json = serde_json::json!({
+            "Target": format!("ecs:{}_{}_{}", exec.cluster, task_id, runtime_id),
+        });
+
+        let plugin = tokio::process::Command::new(SESSION_MANAGER_PLUGIN)
+            .arg(session_json.to_string())
+            .arg(&exec.region)
+            .arg("StartSession")
+            .arg("")
+            .arg(target_json.to_string())
+            .arg(format!("https://ssm.{}.amazonaws.com", exec.region))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn();
+        let plugin = match plugin {
+            Ok(plugin) => plugin,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                bail!("{} not found on the PATH. Install the AWS Session Manager plugin to use ECS Exec tasks.", SESSION_MANAGER_PLUGIN)
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let timeout_seconds = exec.timeout_seconds.unwrap_or(DEFAULT_EXEC_TIMEOUT_SECONDS);
+        let output = tokio::time::timeout(Duration::from_secs(timeout_seconds), plugin.wait_with_output())
+            .await
+            .with_context(|| format!("`{}` didn't finish within {}s", exec.command, timeout_seconds))??;
+
+        // The plugin brackets the command's output with its own session messages
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = stdout
+            .lines()
+            .filter(|line| !line.starts_with("Starting session with SessionId") && !line.starts_with("Exiting session with sessionId"))
+            .collect();
+        if !output.status.success() {
+            bail!(
+                "ECS Exec session for `{}` failed: {}\n{}",
+                exec.command,
+                String::from_utf8_lossy(&output.stderr).trim(),
+                lines.join("\n")
+            );
+        }
+
+        let full_path = self.prefix.join(&output_path);
+        if let Some(parent) = full_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&full_path, lines.join("\n"))?;
+
+        let tail = &lines[lines.len().saturating_sub(OUTPUT_TAIL_LINES)..];
+        let friendly_message = format!(
+            "Ran `{}` in container {} of ECS task {}; wrote the output to {}\n{}",
+            exec.command,
+            container_name,
+            task_id,
+            output_path.display(),
+            tail.join("\n")
+        );
+
+        Ok(TaskExecResponse {
+            modified_files: Some(vec![output_path]),
+            friendly_message: Some(friendly_message),
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,56 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::replay::{unrecorded, with_cassette};
+use autoschematic_core::{
+    connector::{ConnectorOp, ResourceAddress},
+    util::RON,
+};
+
+use crate::{addr::EcsResourceAddress, op::EcsConnectorOp, resource::Cluster};
+
+use super::EcsConnector;
+
+fn cassette(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/cassettes").join(name)
+}
+
+fn connector() -> EcsConnector {
+    EcsConnector {
+        prefix: std::env::temp_dir().join("autoschematic-connector-aws-ecs-test"),
+        ..Default::default()
+    }
+}
+
+/// Records against an active cluster named `web` in us-east-1, with the FARGATE and FARGATE_SPOT
+/// capacity providers and a default strategy of one of them.
+#[tokio::test]
+async fn delete_cluster() -> anyhow::Result<()> {
+    let cassette = cassette("delete_cluster.json");
+    if unrecorded(&cassette)? {
+        return Ok(());
+    }
+
+    with_cassette(&cassette, async {
+        let connector = connector();
+        let addr = EcsResourceAddress::Cluster(String::from("us-east-1"), String::from("web")).to_path_buf();
+
+        let current = connector.do_get(&addr).await?.expect("cluster exists");
+        let cluster: Cluster = RON.from_str(str::from_utf8(&current.resource_definition)?)?;
+        assert_eq!(cluster.status, "ACTIVE");
+        assert_eq!(cluster.capacity_providers, vec!["FARGATE", "FARGATE_SPOT"]);
+        assert_eq!(cluster.default_capacity_provider_strategy.len(), 1);
+
+        let ops = connector.do_plan(&addr, Some(current.resource_definition), None).await?;
+        assert_eq!(ops.len(), 1);
+        assert!(matches!(
+            EcsConnectorOp::from_str(&ops[0].op_definition)?,
+            EcsConnectorOp::DeleteCluster
+        ));
+
+        let output = connector.do_op_exec(&addr, &ops[0].op_definition).await?;
+        assert_eq!(output.friendly_message.as_deref(), Some("Deleted ECS cluster web"));
+
+        anyhow::Ok(())
+    })
+    .await?
+}
@@ -3,5 +3,7 @@ mod resource;
 mod tags;
 mod op;
 mod op_impl;
+mod patch;
 mod config;
+mod task;
 mod util;
\ No newline at end of file
@@ -1,5 +1,8 @@
+use addr::EcsResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
 use autoschematic_core::tarpc_bridge::tarpc_connector_main;
 use connector::EcsConnector;
+use task::EcsTaskAddress;
 
 pub mod connector;
 // pub mod client_cache;
@@ -7,12 +10,21 @@ pub mod config;
 pub mod addr;
 pub mod op;
 pub mod op_impl;
+pub mod patch;
 pub mod resource;
 pub mod tags;
+pub mod task;
 pub mod util;
 
 #[tokio::main]
 pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let mut patterns = EcsResourceAddress::checked_address_patterns()?;
+        patterns.extend(EcsTaskAddress::checked_address_patterns()?);
+        print!("{}", render_address_docs("aws/ecs", &patterns));
+        return Ok(());
+    }
+
     tarpc_connector_main::<EcsConnector>().await?;
     Ok(())
 }
@@ -2,7 +2,7 @@ use autoschematic_core::{connector::ConnectorOp, util::RON};
 use serde::{Deserialize, Serialize};
 
 use super::{
-    resource::{Cluster, Service, TaskDefinition},
+    resource::{CloudMapService, Cluster, ClusterConfiguration, ExternalInstanceActivation, HealthGate, Service, TaskDefinition, TaskSet},
     tags::Tags,
 };
 
@@ -19,6 +19,8 @@ pub enum EcsConnectorOp {
         remove_capacity_providers: Vec<String>,
         default_strategy: Vec<(String, Option<i32>, Option<i32>)>, // (provider, weight, base)
     },
+    /// Sets the cluster's execute command configuration, or resets it to ECS's defaults when None.
+    UpdateClusterConfiguration(Option<ClusterConfiguration>),
     DeleteCluster,
 
     // Service operations
@@ -36,11 +38,36 @@ pub enum EcsConnectorOp {
         old_load_balancers: Vec<super::resource::LoadBalancer>,
         new_load_balancers: Vec<super::resource::LoadBalancer>,
     },
+    UpdateServicePlacement {
+        placement_constraints: Vec<super::resource::PlacementConstraint>,
+        placement_strategy: Vec<super::resource::PlacementStrategy>,
+    },
     EnableExecuteCommand(bool),
     DeleteService,
+    /// Deletes the service, waits for it to become INACTIVE, and creates it again.
+    /// Used when a field that UpdateService cannot change has been modified.
+    ReplaceService(Service),
+    /// Creates a Cloud Map service for one of the ECS service's registries.
+    /// Planned ahead of CreateService or ReplaceService when the registry doesn't exist yet.
+    CreateServiceDiscoveryService(CloudMapService),
+    /// Planned after any op that starts a deployment. Waits for the deployment to finish,
+    /// then fails if any of the gate's alarms go into ALARM during the bake window.
+    AwaitServiceHealth(HealthGate),
+
+    // TaskSet operations
+    CreateTaskSet(TaskSet),
+    UpdateTaskSetScale(f64),
+    /// Makes this task set the service's primary task set.
+    UpdateServicePrimaryTaskSet,
+    DeleteTaskSet {
+        force: bool,
+    },
 
     // TaskDefinition operations
     RegisterTaskDefinition(TaskDefinition),
+    /// Registers a new revision, points each service in its `deploy_to` at it and waits for them
+    /// to roll out, then deregisters the revisions beyond its `keep_revisions`.
+    RegisterTaskDefinitionAndDeploy(TaskDefinition),
     UpdateTaskDefinitionTags(Tags, Tags),
     DeregisterTaskDefinition,
 
@@ -75,6 +102,10 @@ pub enum EcsConnectorOp {
     DeregisterContainerInstance {
         force: bool,
     },
+
+    // ExternalActivation operations
+    CreateExternalInstanceActivation(ExternalInstanceActivation),
+    DeleteExternalInstanceActivation,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -3,22 +3,55 @@ use aws_sdk_ecs::{
     Client,
     types::{
         CapacityProviderStrategyItem, ClusterSetting, ContainerDefinition, DeploymentCircuitBreaker, DeploymentConfiguration,
-        KeyValuePair, LoadBalancer, NetworkConfiguration, PlacementConstraint, PlacementStrategy, ServiceRegistry, Tag,
-        TaskDefinitionPlacementConstraint, TaskOverride,
+        KeyValuePair, LoadBalancer, NetworkConfiguration, PlacementConstraint, PlacementStrategy, Scale, ScaleUnit,
+        ServiceRegistry, Tag, TaskDefinitionPlacementConstraint, TaskOverride,
     },
 };
+use aws_sdk_servicediscovery::types::{DnsConfig, DnsRecord, HealthCheckCustomConfig, RecordType, RoutingPolicy};
 use std::{collections::HashMap, str::FromStr};
 
 use super::{
     op::{NetworkConfigurationRequest, TaskOverride as OpTaskOverride},
-    resource::{Cluster as EcsCluster, Service, TaskDefinition},
+    resource::{
+        CloudMapService, Cluster as EcsCluster, ClusterConfiguration, ExecuteCommandConfiguration, ExternalInstanceActivation,
+        Service, TaskDefinition, TaskSet as EcsTaskSet,
+    },
     tags::Tags,
-    util::{get_cluster, get_service},
+    util::{
+        find_task_set, get_cluster, get_external_instance_activation, get_service, list_active_revisions,
+        wait_for_service_inactive,
+    },
 };
 use autoschematic_core::connector::OpExecResponse;
 
 // Cluster Operations
 
+fn cluster_configuration(configuration: &ClusterConfiguration) -> aws_sdk_ecs::types::ClusterConfiguration {
+    let mut builder = aws_sdk_ecs::types::ClusterConfiguration::builder();
+
+    if let Some(exec_config) = &configuration.execute_command_configuration {
+        let mut exec_builder = aws_sdk_ecs::types::ExecuteCommandConfiguration::builder()
+            .set_kms_key_id(exec_config.kms_key_id.clone())
+            .set_logging(exec_config.logging.as_deref().map(Into::into));
+
+        if let Some(log_config) = &exec_config.log_configuration {
+            exec_builder = exec_builder.log_configuration(
+                aws_sdk_ecs::types::ExecuteCommandLogConfiguration::builder()
+                    .set_cloud_watch_log_group_name(log_config.cloud_watch_log_group_name.clone())
+                    .set_cloud_watch_encryption_enabled(log_config.cloud_watch_encryption_enabled)
+                    .set_s3_bucket_name(log_config.s3_bucket_name.clone())
+                    .set_s3_encryption_enabled(log_config.s3_encryption_enabled)
+                    .set_s3_key_prefix(log_config.s3_key_prefix.clone())
+                    .build(),
+            );
+        }
+
+        builder = builder.execute_command_configuration(exec_builder.build());
+    }
+
+    builder.build()
+}
+
 /// Creates a new ECS cluster
 pub async fn create_cluster(
     client: &Client,
@@ -64,11 +97,8 @@ pub async fn create_cluster(
         let mut settings = Vec::new();
 
         for setting in &cluster.settings {
-            // Convert setting name to ClusterSettingName
-            let setting_name = match setting.name.as_str() {
-                "containerInsights" => aws_sdk_ecs::types::ClusterSettingName::ContainerInsights,
-                _ => continue,
-            };
+            // Unrecognized setting names are passed through as-is rather than dropped
+            let setting_name = aws_sdk_ecs::types::ClusterSettingName::from(setting.name.as_str());
 
             let setting_builder = ClusterSetting::builder().name(setting_name).value(&setting.value);
 
@@ -80,6 +110,11 @@ pub async fn create_cluster(
         }
     }
 
+    // Set execute command configuration if specified
+    if let Some(configuration) = &cluster.configuration {
+        create_cluster = create_cluster.configuration(cluster_configuration(configuration));
+    }
+
     // Apply tags
     let aws_tags: Option<Vec<Tag>> = cluster.tags.clone().into();
 
@@ -155,11 +190,8 @@ pub async fn update_cluster_settings(
     let mut cluster_settings = Vec::new();
 
     for (name, value) in settings {
-        // Convert setting name to ClusterSettingName
-        let setting_name = match name.as_str() {
-            "containerInsights" => aws_sdk_ecs::types::ClusterSettingName::ContainerInsights,
-            _ => continue,
-        };
+        // Unrecognized setting names are passed through as-is rather than dropped
+        let setting_name = aws_sdk_ecs::types::ClusterSettingName::from(name.as_str());
 
         let setting_builder = ClusterSetting::builder().name(setting_name).value(value);
 
@@ -241,6 +273,34 @@ pub async fn update_cluster_capacity_providers(
     })
 }
 
+/// Updates the execute command configuration of a cluster. Unsetting it goes back to ECS's
+/// default logging, with no KMS key.
+pub async fn update_cluster_configuration(
+    client: &Client,
+    cluster_name: &str,
+    configuration: Option<ClusterConfiguration>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let configuration = configuration.unwrap_or(ClusterConfiguration {
+        execute_command_configuration: Some(ExecuteCommandConfiguration {
+            kms_key_id: None,
+            logging: Some(String::from("DEFAULT")),
+            log_configuration: None,
+        }),
+    });
+
+    client
+        .update_cluster()
+        .cluster(cluster_name)
+        .configuration(cluster_configuration(&configuration))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated execute command configuration for ECS cluster {cluster_name}")),
+    })
+}
+
 /// Deletes an ECS cluster
 pub async fn delete_cluster(client: &Client, cluster_name: &str) -> Result<OpExecResponse, anyhow::Error> {
     client.delete_cluster().cluster(cluster_name).send().await?;
@@ -266,9 +326,19 @@ pub async fn create_service(
         .create_service()
         .service_name(service_name)
         .cluster(cluster_name)
-        .task_definition(&service.task_definition)
+        // Services using the EXTERNAL deployment controller take their task definition from their task sets
+        .set_task_definition(Some(service.task_definition.clone()).filter(|td| !td.is_empty()))
         .desired_count(service.desired_count);
 
+    // Set deployment controller if specified
+    if let Some(deployment_controller) = &service.deployment_controller {
+        create_service = create_service.deployment_controller(
+            aws_sdk_ecs::types::DeploymentController::builder()
+                .r#type(deployment_controller.as_str().into())
+                .build()?,
+        );
+    }
+
     // Set launch type if specified
     if let Some(launch_type) = &service.launch_type {
         match launch_type.as_str() {
@@ -700,6 +770,54 @@ pub async fn update_service_load_balancers(
     })
 }
 
+/// Updates placement constraints and placement strategy for a service.
+/// An empty list clears all existing constraints or strategies.
+pub async fn update_service_placement(
+    client: &Client,
+    cluster_name: &str,
+    service_name: &str,
+    placement_constraints: Vec<super::resource::PlacementConstraint>,
+    placement_strategy: Vec<super::resource::PlacementStrategy>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut constraints = Vec::new();
+    for constraint in &placement_constraints {
+        let mut builder = PlacementConstraint::builder().r#type(constraint.r#type.as_str().into());
+
+        if let Some(expression) = &constraint.expression {
+            builder = builder.expression(expression);
+        }
+
+        constraints.push(builder.build());
+    }
+
+    let mut strategies = Vec::new();
+    for strategy in &placement_strategy {
+        let mut builder = PlacementStrategy::builder().r#type(strategy.r#type.as_str().into());
+
+        if let Some(field) = &strategy.field {
+            builder = builder.field(field);
+        }
+
+        strategies.push(builder.build());
+    }
+
+    client
+        .update_service()
+        .cluster(cluster_name)
+        .service(service_name)
+        .set_placement_constraints(Some(constraints))
+        .set_placement_strategy(Some(strategies))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Updated placement constraints and strategy for ECS service {service_name} in cluster {cluster_name}"
+        )),
+    })
+}
+
 /// Enables or disables execute command for a service
 pub async fn enable_execute_command(
     client: &Client,
@@ -741,6 +859,245 @@ pub async fn delete_service(client: &Client, cluster_name: &str, service_name: &
     })
 }
 
+/// Replaces a service by deleting it, waiting until it is INACTIVE, and creating it again.
+/// ECS refuses to create a service with the same name while the old one is still draining.
+pub async fn replace_service(
+    client: &Client,
+    cluster_name: &str,
+    service: &Service,
+    service_name: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    delete_service(client, cluster_name, service_name).await?;
+
+    wait_for_service_inactive(client, cluster_name, service_name).await?;
+
+    let create_resp = create_service(client, cluster_name, service, service_name).await?;
+
+    Ok(OpExecResponse {
+        outputs: create_resp.outputs,
+        friendly_message: Some(format!("Replaced ECS service {service_name} in cluster {cluster_name}")),
+    })
+}
+
+/// Creates a Cloud Map service for an ECS service's registry
+pub async fn create_cloud_map_service(
+    client: &aws_sdk_servicediscovery::Client,
+    cloud_map_service: &CloudMapService,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut create_service = client
+        .create_service()
+        .name(&cloud_map_service.name)
+        .namespace_id(&cloud_map_service.namespace_id)
+        .set_description(cloud_map_service.description.clone());
+
+    if !cloud_map_service.dns_records.is_empty() {
+        let mut dns_records = Vec::new();
+        for record in &cloud_map_service.dns_records {
+            dns_records.push(
+                DnsRecord::builder()
+                    .r#type(RecordType::from(record.r#type.as_str()))
+                    .ttl(record.ttl)
+                    .build()?,
+            );
+        }
+
+        let mut dns_config = DnsConfig::builder().set_dns_records(Some(dns_records));
+        if let Some(routing_policy) = &cloud_map_service.routing_policy {
+            dns_config = dns_config.routing_policy(RoutingPolicy::from(routing_policy.as_str()));
+        }
+
+        create_service = create_service.dns_config(dns_config.build()?);
+    }
+
+    // ECS reports task health to Cloud Map itself
+    create_service = create_service.health_check_custom_config(HealthCheckCustomConfig::builder().build());
+
+    let response = create_service.send().await?;
+    let arn = response
+        .service
+        .and_then(|s| s.arn)
+        .context("CreateService returned no Cloud Map service ARN")?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Created Cloud Map service {} in namespace {} ({})",
+            cloud_map_service.name, cloud_map_service.namespace_id, arn
+        )),
+    })
+}
+
+// TaskSet Operations
+
+async fn task_set_id(client: &Client, cluster_name: &str, service_name: &str, external_id: &str) -> Result<String, anyhow::Error> {
+    find_task_set(client, cluster_name, service_name, external_id)
+        .await?
+        .and_then(|ts| ts.id)
+        .with_context(|| format!("Task set {external_id} not found in ECS service {service_name} in cluster {cluster_name}"))
+}
+
+/// Creates a task set in a service using the EXTERNAL deployment controller
+pub async fn create_task_set(
+    client: &Client,
+    cluster_name: &str,
+    service_name: &str,
+    external_id: &str,
+    task_set: &EcsTaskSet,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut create_task_set = client
+        .create_task_set()
+        .cluster(cluster_name)
+        .service(service_name)
+        .external_id(external_id)
+        .task_definition(&task_set.task_definition)
+        .scale(Scale::builder().value(task_set.scale_percent).unit(ScaleUnit::Percent).build());
+
+    if let Some(launch_type) = &task_set.launch_type {
+        create_task_set = create_task_set.launch_type(launch_type.as_str().into());
+    }
+
+    for item in &task_set.capacity_provider_strategy {
+        create_task_set = create_task_set.capacity_provider_strategy(
+            CapacityProviderStrategyItem::builder()
+                .capacity_provider(&item.capacity_provider)
+                .set_weight(item.weight)
+                .set_base(item.base)
+                .build()?,
+        );
+    }
+
+    if let Some(platform_version) = &task_set.platform_version {
+        create_task_set = create_task_set.platform_version(platform_version);
+    }
+
+    if let Some(network_config) = &task_set.network_configuration
+        && let Some(awsvpc_config) = &network_config.awsvpc_configuration
+    {
+        let vpc_config = aws_sdk_ecs::types::AwsVpcConfiguration::builder()
+            .set_subnets(Some(awsvpc_config.subnets.clone()))
+            .set_security_groups(Some(awsvpc_config.security_groups.clone()))
+            .set_assign_public_ip(awsvpc_config.assign_public_ip.as_deref().map(Into::into))
+            .build()?;
+        create_task_set =
+            create_task_set.network_configuration(NetworkConfiguration::builder().awsvpc_configuration(vpc_config).build());
+    }
+
+    for lb in &task_set.load_balancers {
+        create_task_set = create_task_set.load_balancers(
+            LoadBalancer::builder()
+                .set_target_group_arn(lb.target_group_arn.clone())
+                .set_load_balancer_name(lb.load_balancer_name.clone())
+                .set_container_name(lb.container_name.clone())
+                .set_container_port(lb.container_port)
+                .build(),
+        );
+    }
+
+    let aws_tags: Option<Vec<Tag>> = task_set.tags.clone().into();
+    if let Some(tags) = aws_tags
+        && !tags.is_empty()
+    {
+        create_task_set = create_task_set.set_tags(Some(tags));
+    }
+
+    let resp = create_task_set.send().await?;
+    let created = resp.task_set.context("No task set returned from create_task_set")?;
+
+    let mut outputs = HashMap::new();
+    outputs.insert(String::from("task_set_arn"), created.task_set_arn);
+    outputs.insert(String::from("task_set_id"), created.id);
+
+    Ok(OpExecResponse {
+        outputs: Some(outputs),
+        friendly_message: Some(format!(
+            "Created task set {external_id} in ECS service {service_name} in cluster {cluster_name}"
+        )),
+    })
+}
+
+/// Updates the share of the service's desired count a task set runs
+pub async fn update_task_set_scale(
+    client: &Client,
+    cluster_name: &str,
+    service_name: &str,
+    external_id: &str,
+    scale_percent: f64,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let id = task_set_id(client, cluster_name, service_name, external_id).await?;
+
+    client
+        .update_task_set()
+        .cluster(cluster_name)
+        .service(service_name)
+        .task_set(&id)
+        .scale(Scale::builder().value(scale_percent).unit(ScaleUnit::Percent).build())
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Scaled task set {external_id} in ECS service {service_name} to {scale_percent}%"
+        )),
+    })
+}
+
+/// Makes a task set the primary task set of its service
+pub async fn update_service_primary_task_set(
+    client: &Client,
+    cluster_name: &str,
+    service_name: &str,
+    external_id: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let id = task_set_id(client, cluster_name, service_name, external_id).await?;
+
+    client
+        .update_service_primary_task_set()
+        .cluster(cluster_name)
+        .service(service_name)
+        .primary_task_set(&id)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Made task set {external_id} the primary task set of ECS service {service_name} in cluster {cluster_name}"
+        )),
+    })
+}
+
+/// Deletes a task set
+pub async fn delete_task_set(
+    client: &Client,
+    cluster_name: &str,
+    service_name: &str,
+    external_id: &str,
+    force: bool,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let id = task_set_id(client, cluster_name, service_name, external_id).await?;
+
+    client
+        .delete_task_set()
+        .cluster(cluster_name)
+        .service(service_name)
+        .task_set(&id)
+        .force(force)
+        .send()
+        .await?;
+
+    let mut outputs = HashMap::new();
+    outputs.insert(String::from("task_set_arn"), None);
+    outputs.insert(String::from("task_set_id"), None);
+
+    Ok(OpExecResponse {
+        outputs: Some(outputs),
+        friendly_message: Some(format!(
+            "Deleted task set {external_id} in ECS service {service_name} in cluster {cluster_name}"
+        )),
+    })
+}
+
 // TaskDefinition Operations
 
 /// Registers a new task definition
@@ -1315,6 +1672,11 @@ pub async fn register_task_definition(
             }
         }
 
+        // Set credential specs if specified
+        if !container.credential_specs.is_empty() {
+            builder = builder.set_credential_specs(Some(container.credential_specs.clone()));
+        }
+
         container_defs.push(builder.build());
     }
 
@@ -1423,6 +1785,23 @@ pub async fn register_task_definition(
                 }
             }
 
+            // Set FSx for Windows File Server volume configuration if specified
+            if let Some(fsx_volume) = &vol.fsx_windows_file_server_volume_configuration {
+                let auth_config = aws_sdk_ecs::types::FsxWindowsFileServerAuthorizationConfig::builder()
+                    .set_credentials_parameter(fsx_volume.authorization_config.credentials_parameter.clone())
+                    .set_domain(fsx_volume.authorization_config.domain.clone())
+                    .build()
+                    .with_context(|| format!("FSx volume {} needs both credentials_parameter and domain", vol.name))?;
+
+                vol_builder = vol_builder.fsx_windows_file_server_volume_configuration(
+                    aws_sdk_ecs::types::FsxWindowsFileServerVolumeConfiguration::builder()
+                        .file_system_id(&fsx_volume.file_system_id)
+                        .root_directory(&fsx_volume.root_directory)
+                        .authorization_config(auth_config)
+                        .build()?,
+                );
+            }
+
             volumes.push(vol_builder.build());
         }
 
@@ -1554,6 +1933,25 @@ pub async fn register_task_definition(
         register_task_def = register_task_def.runtime_platform(runtime_builder.build());
     }
 
+    // Set ephemeral storage if specified
+    if let Some(ephemeral_storage) = &task_definition.ephemeral_storage {
+        register_task_def = register_task_def.ephemeral_storage(
+            aws_sdk_ecs::types::EphemeralStorage::builder()
+                .size_in_gib(ephemeral_storage.size_in_gib)
+                .build(),
+        );
+    }
+
+    // Set inference accelerators if specified
+    for accelerator in &task_definition.inference_accelerators {
+        register_task_def = register_task_def.inference_accelerators(
+            aws_sdk_ecs::types::InferenceAccelerator::builder()
+                .device_name(&accelerator.device_name)
+                .device_type(&accelerator.device_type)
+                .build()?,
+        );
+    }
+
     // Register the task definition
     let resp = register_task_def.send().await?;
     let task_def = resp
@@ -1625,6 +2023,21 @@ pub async fn deregister_task_definition(client: &Client, task_definition: &str)
     })
 }
 
+/// Deregisters every ACTIVE revision of `family` but the newest `keep`, returning their ARNs
+pub async fn deregister_old_revisions(client: &Client, family: &str, keep: u32) -> Result<Vec<String>, anyhow::Error> {
+    let superseded: Vec<String> = list_active_revisions(client, family)
+        .await?
+        .into_iter()
+        .skip(keep as usize)
+        .collect();
+
+    for arn in &superseded {
+        client.deregister_task_definition().task_definition(arn).send().await?;
+    }
+
+    Ok(superseded)
+}
+
 // Task Operations
 
 /// Runs a task with specified configuration
@@ -2044,3 +2457,73 @@ pub async fn deregister_container_instance(
         )),
     })
 }
+
+// External Instance Activation Operations
+
+/// Creates an SSM activation for registering ECS Anywhere hosts with a cluster
+pub async fn create_external_instance_activation(
+    ssm_client: &aws_sdk_ssm::Client,
+    region: &str,
+    cluster_name: &str,
+    activation_name: &str,
+    activation: &ExternalInstanceActivation,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut create_activation = ssm_client
+        .create_activation()
+        .default_instance_name(activation_name)
+        .iam_role(&activation.iam_role)
+        .registration_limit(activation.registration_limit);
+
+    if let Some(description) = &activation.description {
+        create_activation = create_activation.description(description);
+    }
+
+    if let Some(expiration_date) = &activation.expiration_date {
+        let expiration_date =
+            aws_sdk_ssm::primitives::DateTime::from_str(expiration_date, aws_sdk_ssm::primitives::DateTimeFormat::DateTime)
+                .with_context(|| format!("Invalid expiration_date {expiration_date}, expected an RFC 3339 timestamp"))?;
+        create_activation = create_activation.expiration_date(expiration_date);
+    }
+
+    let resp = create_activation.send().await?;
+
+    let activation_id = resp.activation_id.context("No activation ID returned")?;
+    let activation_code = resp.activation_code.context("No activation code returned")?;
+
+    // SSM only returns the activation code here, so it has to be recorded as an output
+    // for hosts registered later on.
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("activation_id"), Some(activation_id.clone())),
+            (String::from("activation_code"), Some(activation_code)),
+        ])),
+        friendly_message: Some(format!(
+            "Created SSM activation {activation_id} for ECS cluster {cluster_name}. To register a host as an external instance, run:\n\
+             curl --proto \"https\" -o /tmp/ecs-anywhere-install.sh \"https://amazon-ecs-agent.s3.amazonaws.com/ecs-anywhere-install-latest.sh\" \
+             && sudo bash /tmp/ecs-anywhere-install.sh --region {region} --cluster {cluster_name} --activation-id {activation_id} \
+             --activation-code <activation_code output>"
+        )),
+    })
+}
+
+/// Deletes an external instance activation. Hosts that have already registered are unaffected.
+pub async fn delete_external_instance_activation(
+    ssm_client: &aws_sdk_ssm::Client,
+    activation_name: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let activation = get_external_instance_activation(ssm_client, activation_name)
+        .await?
+        .with_context(|| format!("External instance activation {activation_name} not found"))?;
+
+    let activation_id = activation.activation_id.context("Activation has no ID")?;
+
+    ssm_client.delete_activation().activation_id(&activation_id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("activation_id"), None),
+            (String::from("activation_code"), None),
+        ])),
+        friendly_message: Some(format!("Deleted SSM activation {activation_id} ({activation_name})")),
+    })
+}
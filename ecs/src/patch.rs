@@ -0,0 +1,423 @@
+//! JSON Patch (RFC 6902) style diffs of task definitions.
+//!
+//! Paths are JSON pointers into the task definition as it's written in RON, e.g.
+//! `/container_definitions/web/environment/LOG_LEVEL/value`. Array elements that have a `name`,
+//! such as containers, environment variables and secrets, are addressed by that name rather than
+//! by index, so that a patch still applies after the base reorders or gains elements.
+
+use std::fmt;
+
+use anyhow::{Context, bail};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum PatchOp {
+    /// Sets a field, or inserts an array element. A path ending in `-` appends to the array.
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+}
+
+impl fmt::Display for PatchOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchOp::Add { path, value } => write!(f, "add     {} {}", path, value),
+            PatchOp::Remove { path } => write!(f, "remove  {}", path),
+            PatchOp::Replace { path, value } => write!(f, "replace {} {}", path, value),
+        }
+    }
+}
+
+pub fn format_patch(ops: &[PatchOp]) -> String {
+    ops.iter().map(|op| format!("  {}", op)).collect::<Vec<_>>().join("\n")
+}
+
+fn escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+fn element_name(value: &Value) -> Option<&str> {
+    value.get("name").and_then(Value::as_str)
+}
+
+/// The names of the array's elements, if every element has one and they're all different.
+fn element_names(array: &[Value]) -> Option<Vec<&str>> {
+    let names: Vec<&str> = array.iter().map(element_name).collect::<Option<_>>()?;
+    let mut unique = names.clone();
+    unique.sort();
+    unique.dedup();
+    (unique.len() == names.len()).then_some(names)
+}
+
+/// The smallest set of operations, at the finest granularity, that turns `old` into `new`.
+pub fn diff(old: &Value, new: &Value) -> Vec<PatchOp> {
+    let mut ops = Vec::new();
+    diff_at("", old, new, &mut ops);
+    ops
+}
+
+fn diff_at(path: &str, old: &Value, new: &Value, ops: &mut Vec<PatchOp>) {
+    if old == new {
+        return;
+    }
+
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            for (key, old_value) in old_map {
+                let child = format!("{}/{}", path, escape(key));
+                match new_map.get(key) {
+                    Some(new_value) => diff_at(&child, old_value, new_value, ops),
+                    None => ops.push(PatchOp::Remove { path: child }),
+                }
+            }
+            for (key, new_value) in new_map {
+                if !old_map.contains_key(key) {
+                    ops.push(PatchOp::Add {
+                        path:  format!("{}/{}", path, escape(key)),
+                        value: new_value.clone(),
+                    });
+                }
+            }
+        }
+        (Value::Array(old_array), Value::Array(new_array)) => {
+            if let (Some(old_names), Some(new_names)) = (element_names(old_array), element_names(new_array)) {
+                for (old_value, name) in old_array.iter().zip(&old_names) {
+                    let child = format!("{}/{}", path, escape(name));
+                    match new_array.iter().zip(&new_names).find(|(_, new_name)| *new_name == name) {
+                        Some((new_value, _)) => diff_at(&child, old_value, new_value, ops),
+                        None => ops.push(PatchOp::Remove { path: child }),
+                    }
+                }
+                for (new_value, name) in new_array.iter().zip(&new_names) {
+                    if !old_names.contains(name) {
+                        ops.push(PatchOp::Add {
+                            path:  format!("{}/-", path),
+                            value: new_value.clone(),
+                        });
+                    }
+                }
+                return;
+            }
+
+            let common = old_array.len().min(new_array.len());
+            for i in 0..common {
+                diff_at(&format!("{}/{}", path, i), &old_array[i], &new_array[i], ops);
+            }
+            // Remove from the end, so that each index is still valid when its turn comes
+            for i in (common..old_array.len()).rev() {
+                ops.push(PatchOp::Remove {
+                    path: format!("{}/{}", path, i),
+                });
+            }
+            for new_value in &new_array[common..] {
+                ops.push(PatchOp::Add {
+                    path:  format!("{}/-", path),
+                    value: new_value.clone(),
+                });
+            }
+        }
+        _ => ops.push(PatchOp::Replace {
+            path:  path.to_string(),
+            value: new.clone(),
+        }),
+    }
+}
+
+/// Finds the index of an array element by position, or by name if the segment isn't a number.
+fn array_index(array: &[Value], segment: &str, path: &str) -> anyhow::Result<usize> {
+    if let Ok(index) = segment.parse::<usize>() {
+        if index < array.len() {
+            return Ok(index);
+        }
+        bail!("Patch path {}: index {} is out of range", path, index);
+    }
+
+    array
+        .iter()
+        .position(|element| element_name(element) == Some(segment))
+        .with_context(|| format!("Patch path {}: no element named `{}`", path, segment))
+}
+
+/// Splits a path into the pointer to its parent and its last segment.
+fn split_path(path: &str) -> anyhow::Result<(Vec<String>, String)> {
+    let Some(rest) = path.strip_prefix('/') else {
+        bail!("Patch path `{}` must start with /", path);
+    };
+    let mut segments: Vec<String> = rest.split('/').map(unescape).collect();
+    let last = segments.pop().unwrap_or_default();
+    Ok((segments, last))
+}
+
+fn resolve_mut<'a>(doc: &'a mut Value, segments: &[String], path: &str) -> anyhow::Result<&'a mut Value> {
+    let mut current = doc;
+    for segment in segments {
+        current = match current {
+            Value::Object(map) => map
+                .get_mut(segment)
+                .with_context(|| format!("Patch path {}: no field `{}`", path, segment))?,
+            Value::Array(array) => {
+                let index = array_index(array, segment, path)?;
+                &mut array[index]
+            }
+            _ => bail!("Patch path {}: `{}` is not an object or array", path, segment),
+        };
+    }
+    Ok(current)
+}
+
+pub fn apply(doc: &mut Value, ops: &[PatchOp]) -> anyhow::Result<()> {
+    for op in ops {
+        match op {
+            PatchOp::Add { path, value } => {
+                let (parent, last) = split_path(path)?;
+                match resolve_mut(doc, &parent, path)? {
+                    Value::Object(map) => {
+                        map.insert(last, value.clone());
+                    }
+                    Value::Array(array) if last == "-" => array.push(value.clone()),
+                    Value::Array(array) => {
+                        let index: usize = last
+                            .parse()
+                            .with_context(|| format!("Patch path {}: expected an index or - to add to an array", path))?;
+                        if index > array.len() {
+                            bail!("Patch path {}: index {} is out of range", path, index);
+                        }
+                        array.insert(index, value.clone());
+                    }
+                    _ => bail!("Patch path {}: parent is not an object or array", path),
+                }
+            }
+            PatchOp::Remove { path } => {
+                let (parent, last) = split_path(path)?;
+                match resolve_mut(doc, &parent, path)? {
+                    Value::Object(map) => {
+                        map.remove(&last)
+                            .with_context(|| format!("Patch path {}: no field `{}`", path, last))?;
+                    }
+                    Value::Array(array) => {
+                        let index = array_index(array, &last, path)?;
+                        array.remove(index);
+                    }
+                    _ => bail!("Patch path {}: parent is not an object or array", path),
+                }
+            }
+            PatchOp::Replace { path, value } => {
+                let target = if path.is_empty() {
+                    &mut *doc
+                } else {
+                    let (mut segments, last) = split_path(path)?;
+                    segments.push(last);
+                    resolve_mut(doc, &segments, path)?
+                };
+                *target = value.clone();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{PatchOp, apply, diff};
+
+    fn task_definition() -> serde_json::Value {
+        json!({
+            "family": "web",
+            "cpu": "256",
+            "container_definitions": [
+                {
+                    "name": "web",
+                    "image": "nginx:1.25",
+                    "environment": [
+                        {"name": "LOG_LEVEL", "value": "info"},
+                        {"name": "PORT", "value": "8080"},
+                    ],
+                },
+                {"name": "sidecar", "image": "envoy:1.29"},
+            ],
+        })
+    }
+
+    #[test]
+    fn add_replace_and_remove() {
+        let mut doc = task_definition();
+        apply(
+            &mut doc,
+            &[
+                PatchOp::Add {
+                    path:  String::from("/memory"),
+                    value: json!("512"),
+                },
+                PatchOp::Add {
+                    path:  String::from("/container_definitions/web/environment/-"),
+                    value: json!({"name": "REGION", "value": "us-east-1"}),
+                },
+                PatchOp::Replace {
+                    path:  String::from("/container_definitions/web/environment/LOG_LEVEL/value"),
+                    value: json!("debug"),
+                },
+                PatchOp::Replace {
+                    path:  String::from("/container_definitions/1/image"),
+                    value: json!("envoy:1.30"),
+                },
+                PatchOp::Remove {
+                    path: String::from("/container_definitions/web/environment/PORT"),
+                },
+                PatchOp::Remove {
+                    path: String::from("/cpu"),
+                },
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            doc,
+            json!({
+                "family": "web",
+                "memory": "512",
+                "container_definitions": [
+                    {
+                        "name": "web",
+                        "image": "nginx:1.25",
+                        "environment": [
+                            {"name": "LOG_LEVEL", "value": "debug"},
+                            {"name": "REGION", "value": "us-east-1"},
+                        ],
+                    },
+                    {"name": "sidecar", "image": "envoy:1.30"},
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn add_by_index() {
+        let mut doc = json!({"command": ["serve", "--port"]});
+        apply(
+            &mut doc,
+            &[
+                PatchOp::Add {
+                    path:  String::from("/command/0"),
+                    value: json!("/app/bin"),
+                },
+                PatchOp::Add {
+                    path:  String::from("/command/3"),
+                    value: json!("8080"),
+                },
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(doc, json!({"command": ["/app/bin", "serve", "--port", "8080"]}));
+    }
+
+    #[test]
+    fn escaped_segments() {
+        let mut doc = json!({"docker_labels": {"com.example/team": "web", "a~b": "1"}});
+        apply(
+            &mut doc,
+            &[
+                PatchOp::Replace {
+                    path:  String::from("/docker_labels/com.example~1team"),
+                    value: json!("platform"),
+                },
+                PatchOp::Remove {
+                    path: String::from("/docker_labels/a~0b"),
+                },
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(doc, json!({"docker_labels": {"com.example/team": "platform"}}));
+    }
+
+    #[test]
+    fn invalid_pointers() {
+        let cases = [
+            PatchOp::Remove {
+                path: String::from("cpu"),
+            },
+            PatchOp::Remove {
+                path: String::from("/memory"),
+            },
+            PatchOp::Replace {
+                path:  String::from("/container_definitions/db/image"),
+                value: json!("postgres:16"),
+            },
+            PatchOp::Replace {
+                path:  String::from("/container_definitions/5/image"),
+                value: json!("nginx:1.27"),
+            },
+            PatchOp::Add {
+                path:  String::from("/container_definitions/web/environment/9"),
+                value: json!({"name": "DEBUG", "value": "1"}),
+            },
+            PatchOp::Add {
+                path:  String::from("/container_definitions/web/environment/last"),
+                value: json!({"name": "DEBUG", "value": "1"}),
+            },
+            PatchOp::Add {
+                path:  String::from("/family/suffix"),
+                value: json!("-v2"),
+            },
+            PatchOp::Replace {
+                path:  String::from("/cpu/units"),
+                value: json!(256),
+            },
+        ];
+
+        for op in cases {
+            let mut doc = task_definition();
+            assert!(apply(&mut doc, std::slice::from_ref(&op)).is_err(), "{} should fail", op);
+            assert_eq!(doc, task_definition(), "{} should leave the document unchanged", op);
+        }
+    }
+
+    #[test]
+    fn diff_round_trips() {
+        let old = task_definition();
+        let new = json!({
+            "family": "web",
+            "cpu": "512",
+            "container_definitions": [
+                {"name": "sidecar", "image": "envoy:1.29"},
+                {
+                    "name": "web",
+                    "image": "nginx:1.27",
+                    "environment": [
+                        {"name": "LOG_LEVEL", "value": "info"},
+                        {"name": "TRACING", "value": "on"},
+                    ],
+                },
+            ],
+        });
+
+        let ops = diff(&old, &new);
+        assert!(ops.contains(&PatchOp::Remove {
+            path: String::from("/container_definitions/web/environment/PORT"),
+        }));
+        assert!(ops.contains(&PatchOp::Replace {
+            path:  String::from("/cpu"),
+            value: json!("512"),
+        }));
+
+        let mut patched = old.clone();
+        apply(&mut patched, &ops).unwrap();
+
+        // Named elements are matched by name, so their order follows the base
+        let containers = patched["container_definitions"].as_array().unwrap();
+        assert_eq!(containers[0]["image"], "nginx:1.27");
+        assert_eq!(containers[0]["environment"], new["container_definitions"][1]["environment"]);
+        assert_eq!(containers[1], new["container_definitions"][0]);
+        assert_eq!(patched["cpu"], "512");
+        assert!(diff(&old, &old).is_empty());
+    }
+}
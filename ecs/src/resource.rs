@@ -4,7 +4,7 @@ use autoschematic_core::{
 };
 use serde::{Deserialize, Serialize};
 
-use super::{addr::EcsResourceAddress, tags::Tags};
+use super::{addr::EcsResourceAddress, patch::PatchOp, tags::Tags};
 
 // Cluster resource definition
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -70,7 +70,47 @@ pub struct Service {
     pub enable_ecs_managed_tags: Option<bool>,
     pub propagate_tags: Option<String>,
     pub enable_execute_command: Option<bool>,
+    /// ECS, CODE_DEPLOY or EXTERNAL. Services using the EXTERNAL controller leave task_definition
+    /// empty, and run their tasks through task sets under services/<service_name>/task_sets/.
+    #[serde(default)]
+    pub deployment_controller: Option<String>,
     pub tags: Tags,
+    /// Overrides `health_gate` in aws/ecs/config.ron for this service. Fields left unset
+    /// fall back to the connector-wide value. Not stored in AWS, so it never shows as drift.
+    #[serde(default)]
+    pub health_gate: Option<HealthGate>,
+    /// Whether plan leaves desired_count alone when it differs from the service's running value.
+    /// If unset, it's ignored only while the service is registered as an Application Auto Scaling
+    /// scalable target. Not stored in AWS, so it never shows as drift.
+    #[serde(default)]
+    pub ignore_desired_count: Option<bool>,
+}
+
+/// After a deployment is started, waits for the service to reach steady state, then watches it
+/// for a bake window and fails the apply if any of the alarms went into ALARM.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct HealthGate {
+    /// How long to wait for the new deployment to finish rolling out.
+    #[serde(default)]
+    pub deployment_timeout_seconds: Option<u64>,
+    /// How long to watch the alarms for once the deployment has finished.
+    #[serde(default)]
+    pub bake_window_seconds: Option<u64>,
+    /// CloudWatch alarms in the service's region that must stay out of ALARM for the bake window.
+    #[serde(default)]
+    pub alarm_names: Option<Vec<String>>,
+}
+
+impl HealthGate {
+    /// Returns this gate with any fields set in `overrides` replaced.
+    pub fn overlay(&self, overrides: &HealthGate) -> HealthGate {
+        HealthGate {
+            deployment_timeout_seconds: overrides.deployment_timeout_seconds.or(self.deployment_timeout_seconds),
+            bake_window_seconds: overrides.bake_window_seconds.or(self.bake_window_seconds),
+            alarm_names: overrides.alarm_names.clone().or_else(|| self.alarm_names.clone()),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -112,6 +152,8 @@ pub struct PlacementStrategy {
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct LoadBalancer {
+    /// A target group ARN, or the address of a target group managed by the ELB connector,
+    /// such as `aws/elb/us-east-1/target_groups/web.ron`.
     pub target_group_arn:   Option<String>,
     pub load_balancer_name: Option<String>,
     pub container_name:     Option<String>,
@@ -120,10 +162,56 @@ pub struct LoadBalancer {
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct ServiceRegistry {
+    /// The ARN of an existing Cloud Map service. Leave unset when `create_registry` is set.
     pub registry_arn: Option<String>,
     pub port: Option<i32>,
     pub container_name: Option<String>,
     pub container_port: Option<i32>,
+    /// Registers tasks with the Cloud Map service of this name in the given namespace,
+    /// creating it ahead of the ECS service if it doesn't exist yet.
+    pub create_registry: Option<CloudMapService>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct CloudMapService {
+    pub namespace_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// The DNS records to create for each task. Must be empty for HTTP namespaces.
+    pub dns_records: Vec<CloudMapDnsRecord>,
+    /// MULTIVALUE or WEIGHTED. Only used if `dns_records` is set.
+    pub routing_policy: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct CloudMapDnsRecord {
+    /// A, AAAA, CNAME or SRV
+    pub r#type: String,
+    pub ttl:    i64,
+}
+
+/// A task set of a service using the EXTERNAL deployment controller, identified by its external ID.
+/// Only the scale can change once it's created; roll out a change by creating a new task set
+/// and making it primary.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TaskSet {
+    pub task_definition: String,
+    pub launch_type: Option<String>,
+    #[serde(default)]
+    pub capacity_provider_strategy: Vec<CapacityProviderStrategyItem>,
+    pub platform_version: Option<String>,
+    pub network_configuration: Option<NetworkConfiguration>,
+    #[serde(default)]
+    pub load_balancers: Vec<LoadBalancer>,
+    /// The share of the service's desired count this task set runs, from 0 to 100.
+    pub scale_percent: f64,
+    /// Whether this is the service's primary task set. Setting it switches the primary task set over;
+    /// the previous primary loses it as a consequence.
+    #[serde(default)]
+    pub primary: bool,
+    #[serde(default)]
+    pub tags: Tags,
 }
 
 // TaskDefinition resource definition
@@ -142,6 +230,39 @@ pub struct TaskDefinition {
     pub ipc_mode: Option<String>,
     pub proxy_configuration: Option<ProxyConfiguration>,
     pub runtime_platform: Option<RuntimePlatform>,
+    /// Ephemeral storage for Fargate tasks, above the default 20 GiB.
+    #[serde(default)]
+    pub ephemeral_storage: Option<EphemeralStorage>,
+    /// Elastic Inference accelerators, which containers claim through an InferenceAccelerator resource requirement.
+    #[serde(default)]
+    pub inference_accelerators: Vec<InferenceAccelerator>,
+    /// Services to point at each new revision as soon as it's registered, waiting for each to roll
+    /// out before the op finishes. Such services can name just the family in their `task_definition`,
+    /// so their own files don't have to change with every revision. Not stored in AWS.
+    #[serde(default)]
+    pub deploy_to: Vec<ServiceTarget>,
+    /// Once a new revision is registered and rolled out, deregisters all but this many of the
+    /// family's newest ACTIVE revisions. Not stored in AWS.
+    #[serde(default)]
+    pub keep_revisions: Option<u32>,
+}
+
+impl TaskDefinition {
+    /// This task definition with the fields that only exist locally cleared, for comparison against AWS.
+    pub fn without_rollout(&self) -> TaskDefinition {
+        TaskDefinition {
+            deploy_to: Vec::new(),
+            keep_revisions: None,
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceTarget {
+    pub cluster: String,
+    pub service: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -184,6 +305,11 @@ pub struct ContainerDefinition {
     pub system_controls: Vec<SystemControl>,
     pub resource_requirements: Vec<ResourceRequirement>,
     pub firelens_configuration: Option<FirelensConfiguration>,
+    /// gMSA credential specs for Windows containers, as `credentialspec:<arn>` for
+    /// domain-joined hosts or `credentialspecdomainless:<arn>` for domainless gMSA,
+    /// where `<arn>` is an S3 object or SSM parameter holding the credential spec.
+    #[serde(default)]
+    pub credential_specs: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -359,6 +485,17 @@ pub struct FsxWindowsFileServerAuthorizationConfig {
     pub domain: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct EphemeralStorage {
+    pub size_in_gib: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct InferenceAccelerator {
+    pub device_name: String,
+    pub device_type: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct ProxyConfiguration {
     pub r#type: Option<String>,
@@ -372,6 +509,16 @@ pub struct RuntimePlatform {
     pub operating_system_family: Option<String>,
 }
 
+/// A task definition written as a patch on top of another task definition file, so that variants of a
+/// large task definition don't each repeat it in full. The base is kept in a subdirectory, where it
+/// isn't itself registered, e.g. `aws/ecs/us-east-1/task_definitions/base/web.ron`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct TaskDefinitionPatch {
+    /// Path to the base task definition, relative to the repository root
+    pub base: String,
+    pub patch: Vec<PatchOp>,
+}
+
 // Task resource definition
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Task {
@@ -452,12 +599,26 @@ pub struct Attribute {
     pub target_id: Option<String>,
 }
 
+// External instance activation resource definition (ECS Anywhere)
+/// An SSM activation that on-premises hosts use to register with the cluster as EXTERNAL container instances.
+/// Activations can't be modified, so any change replaces the activation.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct ExternalInstanceActivation {
+    /// The role registered hosts assume. It needs the AmazonSSMManagedInstanceCore and
+    /// AmazonEC2ContainerServiceforEC2Role managed policies.
+    pub iam_role: String,
+    pub registration_limit: i32,
+    pub expiration_date: Option<String>, // RFC 3339. SSM defaults to 24 hours after creation.
+    pub description: Option<String>,
+}
 
 // Enum for ECS resources
 pub enum EcsResource {
     Cluster(Cluster),
     Service(Service),
+    TaskSet(TaskSet),
     TaskDefinition(TaskDefinition),
+    ExternalInstanceActivation(ExternalInstanceActivation),
 }
 
 // Implementation of Resource trait for EcsResource
@@ -467,7 +628,9 @@ impl Resource for EcsResource {
         match self {
             EcsResource::Cluster(cluster) => Ok(RON.to_string_pretty(&cluster, pretty_config)?.into()),
             EcsResource::Service(service) => Ok(RON.to_string_pretty(&service, pretty_config)?.into()),
+            EcsResource::TaskSet(task_set) => Ok(RON.to_string_pretty(&task_set, pretty_config)?.into()),
             EcsResource::TaskDefinition(task_definition) => Ok(RON.to_string_pretty(&task_definition, pretty_config)?.into()),
+            EcsResource::ExternalInstanceActivation(activation) => Ok(RON.to_string_pretty(&activation, pretty_config)?.into()),
         }
     }
 
@@ -482,7 +645,13 @@ impl Resource for EcsResource {
         match addr {
             EcsResourceAddress::Cluster(region, _name) => Ok(EcsResource::Cluster(RON.from_str(s)?)),
             EcsResourceAddress::Service(region, _cluster_name, _service_name) => Ok(EcsResource::Service(RON.from_str(s)?)),
+            EcsResourceAddress::TaskSet(_region, _cluster_name, _service_name, _external_id) => {
+                Ok(EcsResource::TaskSet(RON.from_str(s)?))
+            }
             EcsResourceAddress::TaskDefinition(region, _task_def_id) => Ok(EcsResource::TaskDefinition(RON.from_str(s)?)),
+            EcsResourceAddress::ExternalActivation(_region, _cluster_name, _activation_name) => {
+                Ok(EcsResource::ExternalInstanceActivation(RON.from_str(s)?))
+            }
         }
     }
 }
@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::{PrettyConfig, RON};
+
+#[derive(Debug, Clone)]
+pub enum EcsTaskAddress {
+    Exec { name: String },
+}
+
+impl ResourceAddress for EcsTaskAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            EcsTaskAddress::Exec { name } => PathBuf::from(format!("aws/ecs/tasks/exec/{name}.ron")),
+        }
+    }
+
+    fn from_path(path: &Path) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        let path_components: Vec<&str> = path
+            .components()
+            .map(|s| s.as_os_str().to_str().context("Path component is not valid UTF-8"))
+            .collect::<Result<Vec<&str>, anyhow::Error>>()?;
+
+        match &path_components[..] {
+            ["aws", "ecs", "tasks", "exec", name] if name.ends_with(".ron") => Ok(EcsTaskAddress::Exec {
+                name: name.strip_suffix(".ron").context("File name must end with .ron")?.to_string(),
+            }),
+            _ => Err(anyhow::anyhow!("Invalid ECS task address: {}", path.display())),
+        }
+    }
+}
+
+impl DescribeAddresses for EcsTaskAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![AddressPattern {
+            pattern:     "aws/ecs/tasks/exec/<name>.ron",
+            description: "Task: run a command in a running container through ECS Exec, and capture its output",
+            example:     "aws/ecs/tasks/exec/web-migrate.ron",
+        }]
+    }
+}
+
+/// Runs `command` in a container of a running task through ECS Exec, and writes what it printed
+/// to a log file. The task is either named directly, or picked from the running tasks of `service`.
+/// Needs the Session Manager plugin (`session-manager-plugin`) on the PATH, and a service or task
+/// with enable_execute_command set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Exec {
+    pub region: String,
+    pub cluster: String,
+    /// Runs the command in one of this service's running tasks. Ignored if `task` is set.
+    #[serde(default)]
+    pub service: Option<String>,
+    /// The ID or ARN of the task to run the command in.
+    #[serde(default)]
+    pub task: Option<String>,
+    /// The container to run the command in. Required if the task has more than one container.
+    #[serde(default)]
+    pub container: Option<String>,
+    pub command: String,
+    /// How long to let the command run before giving up on it. Defaults to 300 seconds.
+    #[serde(default)]
+    pub timeout_seconds: Option<u64>,
+    /// Where to write the command's output, relative to the repo root. May not contain `..`.
+    /// Defaults to aws/ecs/exec-output/<task name>.log.
+    #[serde(default)]
+    pub output_path: Option<String>,
+}
+
+pub enum EcsTask {
+    Exec(Exec),
+}
+
+impl Resource for EcsTask {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = PrettyConfig::default().struct_names(true);
+        match self {
+            EcsTask::Exec(exec) => match RON.to_string_pretty(&exec, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = EcsTaskAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+        match addr {
+            EcsTaskAddress::Exec { .. } => Ok(EcsTask::Exec(RON.from_str(s)?)),
+        }
+    }
+}
@@ -1,14 +1,29 @@
-use anyhow::Context;
+use std::path::Path;
+
+use anyhow::{Context, bail};
+use autoschematic_connector_aws_core::{
+    arn::parse_arn,
+    elb::{ElbTargetGroupAddress, check_elb_address, resolve_elb_address},
+};
+use aws_sdk_applicationautoscaling::types::{ScalableDimension, ServiceNamespace};
 use aws_sdk_ecs::Client;
+use aws_sdk_servicediscovery::{
+    operation::{get_namespace::GetNamespaceError, get_service::GetServiceError},
+    types::{FilterCondition, ServiceFilter, ServiceFilterName},
+};
+
+use crate::resource::{LoadBalancer, Service, TaskDefinition};
 
 /// Gets a cluster by name
 pub async fn get_cluster(
     client: &Client,
     cluster_name: &str,
 ) -> Result<Option<aws_sdk_ecs::types::Cluster>, anyhow::Error> {
+    // The execute command configuration is only returned when asked for
     let resp = client
         .describe_clusters()
         .clusters(cluster_name)
+        .include(aws_sdk_ecs::types::ClusterField::Configurations)
         .send()
         .await?;
 
@@ -43,6 +58,110 @@ pub async fn get_service(
     Ok(Some(services[0].clone()))
 }
 
+/// Gets a service's task set by its external ID. Task sets that are draining after being deleted are skipped.
+pub async fn find_task_set(
+    client: &Client,
+    cluster_name: &str,
+    service_name: &str,
+    external_id: &str,
+) -> Result<Option<aws_sdk_ecs::types::TaskSet>, anyhow::Error> {
+    let resp = match client
+        .describe_task_sets()
+        .cluster(cluster_name)
+        .service(service_name)
+        .include(aws_sdk_ecs::types::TaskSetField::Tags)
+        .send()
+        .await
+    {
+        Ok(resp) => resp,
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_service_not_found_exception()) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(resp
+        .task_sets
+        .unwrap_or_default()
+        .into_iter()
+        .find(|ts| ts.external_id() == Some(external_id) && ts.status() != Some("DRAINING")))
+}
+
+/// The family of a task definition given as an ARN, `family:revision` or just `family`.
+pub fn task_definition_family(task_definition: &str) -> &str {
+    let name = match task_definition.split_once(":task-definition/") {
+        Some((_, name)) => name,
+        None => task_definition,
+    };
+    name.split(':').next().unwrap_or(name)
+}
+
+/// Whether a service's task definition names just the family, so it runs whichever revision
+/// was last deployed to it rather than a fixed one.
+pub fn tracks_latest_revision(task_definition: &str) -> bool {
+    !task_definition.starts_with("arn:") && !task_definition.contains(':')
+}
+
+/// The ARNs of every ACTIVE revision of `family`, newest first.
+pub async fn list_active_revisions(client: &Client, family: &str) -> anyhow::Result<Vec<String>> {
+    let mut arns = Vec::new();
+    let mut next_token = None;
+    loop {
+        let resp = client
+            .list_task_definitions()
+            .family_prefix(family)
+            .status(aws_sdk_ecs::types::TaskDefinitionStatus::Active)
+            .sort(aws_sdk_ecs::types::SortOrder::Desc)
+            .set_next_token(next_token)
+            .send()
+            .await?;
+
+        // family_prefix also matches longer family names
+        arns.extend(
+            resp.task_definition_arns()
+                .iter()
+                .filter(|arn| task_definition_family(arn) == family)
+                .cloned(),
+        );
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+    Ok(arns)
+}
+
+/// Whether the service's desired count is registered as an Application Auto Scaling scalable target.
+pub async fn has_scalable_target(
+    client: &aws_sdk_applicationautoscaling::Client,
+    cluster_name: &str,
+    service_name: &str,
+) -> Result<bool, anyhow::Error> {
+    let resp = client
+        .describe_scalable_targets()
+        .service_namespace(ServiceNamespace::Ecs)
+        .scalable_dimension(ScalableDimension::EcsServiceDesiredCount)
+        .resource_ids(format!("service/{}/{}", cluster_name, service_name))
+        .send()
+        .await?;
+
+    Ok(!resp.scalable_targets().is_empty())
+}
+
+/// Polls a service until ECS reports it as INACTIVE (or it disappears entirely).
+/// Gives up after ten minutes.
+pub async fn wait_for_service_inactive(client: &Client, cluster_name: &str, service_name: &str) -> Result<(), anyhow::Error> {
+    for _ in 0..60 {
+        match get_service(client, cluster_name, service_name).await? {
+            Some(service) if service.status() != Some("INACTIVE") => {
+                tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+            }
+            _ => return Ok(()),
+        }
+    }
+
+    bail!("Timed out waiting for ECS service {service_name} in cluster {cluster_name} to become INACTIVE")
+}
+
 /// Gets a task definition by ARN or family:revision
 pub async fn get_task_definition(
     client: &Client,
@@ -102,3 +221,502 @@ pub async fn get_container_instance(
 
     Ok(Some(container_instances[0].clone()))
 }
+
+/// Splits an S3 object ARN (arn:aws:s3:::bucket/key) into its bucket and key.
+pub fn parse_s3_object_arn(arn: &str) -> Option<(String, String)> {
+    let mut parts = arn.splitn(6, ':');
+    if parts.next()? != "arn" {
+        return None;
+    }
+    let _partition = parts.next()?;
+    if parts.next()? != "s3" {
+        return None;
+    }
+    let resource = parts.nth(2)?;
+    let (bucket, key) = resource.split_once('/')?;
+
+    if bucket.is_empty() || key.is_empty() {
+        return None;
+    }
+
+    Some((bucket.to_string(), key.to_string()))
+}
+
+/// Checks that an S3 environment file exists, and, if an execution role is given,
+/// that the role is allowed to read it.
+/// Returns a description of the problem if the file would fail to load at task start.
+pub async fn check_environment_file(
+    s3_client: &aws_sdk_s3::Client,
+    iam_client: &aws_sdk_iam::Client,
+    execution_role_arn: Option<&str>,
+    file_arn: &str,
+) -> Result<Option<String>, anyhow::Error> {
+    let Some((bucket, key)) = parse_s3_object_arn(file_arn) else {
+        return Ok(Some(format!("{file_arn}: not a valid S3 object ARN")));
+    };
+
+    if let Err(e) = s3_client.head_object().bucket(&bucket).key(&key).send().await {
+        let e = e.into_service_error();
+        if e.is_not_found() {
+            return Ok(Some(format!("{file_arn}: object does not exist")));
+        }
+        return Ok(Some(format!("{file_arn}: HeadObject failed: {e}")));
+    }
+
+    let Some(execution_role_arn) = execution_role_arn else {
+        return Ok(Some(format!(
+            "{file_arn}: task definition has no execution_role_arn, so ECS cannot fetch environment files"
+        )));
+    };
+
+    let resp = iam_client
+        .simulate_principal_policy()
+        .policy_source_arn(execution_role_arn)
+        .action_names("s3:GetObject")
+        .resource_arns(file_arn)
+        .send()
+        .await?;
+
+    for result in resp.evaluation_results() {
+        if result.eval_decision() != &aws_sdk_iam::types::PolicyEvaluationDecisionType::Allowed {
+            return Ok(Some(format!(
+                "{file_arn}: execution role {execution_role_arn} is not allowed s3:GetObject ({})",
+                result.eval_decision().as_str()
+            )));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Where a gMSA credential spec is stored.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CredentialSpecSource {
+    S3 { bucket: String, key: String },
+    Ssm { region: String },
+}
+
+/// An entry in a container's `credential_specs`, `credentialspec:<arn>` or `credentialspecdomainless:<arn>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CredentialSpec {
+    pub domainless: bool,
+    pub arn: String,
+    pub source: CredentialSpecSource,
+}
+
+impl CredentialSpec {
+    /// Returns None unless `<arn>` is an S3 object or SSM parameter ARN.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (domainless, arn) = if let Some(arn) = spec.strip_prefix("credentialspecdomainless:") {
+            (true, arn)
+        } else if let Some(arn) = spec.strip_prefix("credentialspec:") {
+            (false, arn)
+        } else {
+            return None;
+        };
+
+        let source = if let Some((bucket, key)) = parse_s3_object_arn(arn) {
+            CredentialSpecSource::S3 { bucket, key }
+        } else {
+            let parsed = parse_arn(arn).ok()?;
+            match (parsed.service, parsed.resource_id.first()) {
+                ("ssm", Some(&"parameter")) if !parsed.region.is_empty() => CredentialSpecSource::Ssm {
+                    region: parsed.region.to_string(),
+                },
+                _ => return None,
+            }
+        };
+
+        Some(Self {
+            domainless,
+            arn: arn.to_string(),
+            source,
+        })
+    }
+
+    /// The IAM action ECS uses to read the credential spec.
+    pub fn read_action(&self) -> &'static str {
+        match self.source {
+            CredentialSpecSource::S3 { .. } => "s3:GetObject",
+            CredentialSpecSource::Ssm { .. } => "ssm:GetParameter",
+        }
+    }
+}
+
+/// Returns the reasons, if any, that ECS would refuse or fail to apply the credential specs in a task definition.
+pub fn check_credential_specs(task_def: &TaskDefinition) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for container in &task_def.container_definitions {
+        if container.credential_specs.len() > 1 {
+            problems.push(format!("container {}: only one credential spec may be given", container.name));
+        }
+
+        for spec in &container.credential_specs {
+            let Some(parsed) = CredentialSpec::parse(spec) else {
+                problems.push(format!(
+                    "container {}: {} is not of the form credentialspec:<arn> or credentialspecdomainless:<arn>, \
+                     where <arn> is an S3 object or SSM parameter",
+                    container.name, spec
+                ));
+                continue;
+            };
+
+            if parsed.domainless && task_def.execution_role_arn.is_none() {
+                problems.push(format!(
+                    "container {}: domainless gMSA needs an execution_role_arn, which ECS uses to read the credential spec and its secret",
+                    container.name
+                ));
+            }
+        }
+    }
+
+    problems
+}
+
+/// Finds the Secrets Manager secret that holds the domain user credentials for domainless gMSA,
+/// under `ActiveDirectoryConfig.HostAccountConfig.PluginInput.CredentialArn`.
+/// PluginInput may be given either as an object or as a JSON-encoded string.
+pub fn credential_spec_secret_arn(credential_spec: &serde_json::Value) -> Option<String> {
+    let plugin_input = credential_spec
+        .get("ActiveDirectoryConfig")?
+        .get("HostAccountConfig")?
+        .get("PluginInput")?;
+
+    let plugin_input = match plugin_input {
+        serde_json::Value::String(s) => serde_json::from_str(s).ok()?,
+        other => other.clone(),
+    };
+
+    plugin_input
+        .get("CredentialArn")
+        .or_else(|| plugin_input.get("credentialArn"))
+        .and_then(|arn| arn.as_str())
+        .map(|arn| arn.to_string())
+}
+
+/// Returns the evaluation decision if `role_arn` is not allowed `action` on `resource_arn`.
+async fn simulate_denied(
+    iam_client: &aws_sdk_iam::Client,
+    role_arn: &str,
+    action: &str,
+    resource_arn: &str,
+) -> Result<Option<String>, anyhow::Error> {
+    let resp = iam_client
+        .simulate_principal_policy()
+        .policy_source_arn(role_arn)
+        .action_names(action)
+        .resource_arns(resource_arn)
+        .send()
+        .await?;
+
+    for result in resp.evaluation_results() {
+        if result.eval_decision() != &aws_sdk_iam::types::PolicyEvaluationDecisionType::Allowed {
+            return Ok(Some(result.eval_decision().as_str().to_string()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Checks that a domainless gMSA credential spec exists and names a Secrets Manager secret, and that
+/// the execution role is allowed to read both the spec and the secret.
+/// Returns a description of each problem that would stop the container from starting.
+pub async fn check_domainless_credential_spec(
+    s3_client: &aws_sdk_s3::Client,
+    ssm_client: &aws_sdk_ssm::Client,
+    iam_client: &aws_sdk_iam::Client,
+    execution_role_arn: &str,
+    spec: &CredentialSpec,
+) -> Result<Vec<String>, anyhow::Error> {
+    let mut problems = Vec::new();
+
+    if let Some(decision) = simulate_denied(iam_client, execution_role_arn, spec.read_action(), &spec.arn).await? {
+        problems.push(format!(
+            "{}: execution role {} is not allowed {} ({})",
+            spec.arn,
+            execution_role_arn,
+            spec.read_action(),
+            decision
+        ));
+    }
+
+    let contents = match &spec.source {
+        CredentialSpecSource::S3 { bucket, key } => match s3_client.get_object().bucket(bucket).key(key).send().await {
+            Ok(resp) => String::from_utf8(resp.body.collect().await?.into_bytes().to_vec())?,
+            Err(e) => {
+                let e = e.into_service_error();
+                if e.is_no_such_key() {
+                    problems.push(format!("{}: object does not exist", spec.arn));
+                } else {
+                    problems.push(format!("{}: GetObject failed: {e}", spec.arn));
+                }
+                return Ok(problems);
+            }
+        },
+        CredentialSpecSource::Ssm { .. } => {
+            match ssm_client.get_parameter().name(&spec.arn).with_decryption(true).send().await {
+                Ok(resp) => resp.parameter.and_then(|p| p.value).unwrap_or_default(),
+                Err(e) => {
+                    let e = e.into_service_error();
+                    if e.is_parameter_not_found() {
+                        problems.push(format!("{}: parameter does not exist", spec.arn));
+                    } else {
+                        problems.push(format!("{}: GetParameter failed: {e}", spec.arn));
+                    }
+                    return Ok(problems);
+                }
+            }
+        }
+    };
+
+    let Some(secret_arn) = serde_json::from_str(&contents)
+        .ok()
+        .and_then(|credential_spec| credential_spec_secret_arn(&credential_spec))
+    else {
+        problems.push(format!(
+            "{}: not a credential spec with a HostAccountConfig plugin input naming a Secrets Manager secret",
+            spec.arn
+        ));
+        return Ok(problems);
+    };
+
+    if let Some(decision) =
+        simulate_denied(iam_client, execution_role_arn, "secretsmanager:GetSecretValue", &secret_arn).await?
+    {
+        problems.push(format!(
+            "{}: execution role {} is not allowed secretsmanager:GetSecretValue on {} ({})",
+            spec.arn, execution_role_arn, secret_arn, decision
+        ));
+    }
+
+    Ok(problems)
+}
+
+/// A container image in an ECR private repository, referenced by tag.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EcrImageTag {
+    pub host: String,
+    pub registry_id: String,
+    pub region: String,
+    pub repository: String,
+    pub tag: String,
+}
+
+impl EcrImageTag {
+    /// Parses `<account>.dkr.ecr.<region>.amazonaws.com/<repository>[:<tag>]`.
+    /// Returns None for images outside ECR and for images already pinned by digest.
+    pub fn parse(image: &str) -> Option<Self> {
+        if image.contains('@') {
+            return None;
+        }
+
+        let (host, path) = image.split_once('/')?;
+        let mut labels = host.split('.');
+        let registry_id = labels.next()?;
+        if labels.next()? != "dkr" || !matches!(labels.next()?, "ecr" | "ecr-fips") {
+            return None;
+        }
+        let region = labels.next()?;
+        if labels.next()? != "amazonaws" {
+            return None;
+        }
+
+        // Docker defaults an untagged image to `latest`
+        let (repository, tag) = path.rsplit_once(':').unwrap_or((path, "latest"));
+        if repository.is_empty() || tag.is_empty() {
+            return None;
+        }
+
+        Some(EcrImageTag {
+            host: host.to_string(),
+            registry_id: registry_id.to_string(),
+            region: region.to_string(),
+            repository: repository.to_string(),
+            tag: tag.to_string(),
+        })
+    }
+
+    /// The same image, pinned to `digest`.
+    pub fn pinned(&self, digest: &str) -> String {
+        format!("{}/{}@{}", self.host, self.repository, digest)
+    }
+}
+
+/// Looks up the digest that an ECR image tag currently points to.
+pub async fn resolve_image_digest(ecr_client: &aws_sdk_ecr::Client, image: &EcrImageTag) -> Result<String, anyhow::Error> {
+    let resp = ecr_client
+        .describe_images()
+        .registry_id(&image.registry_id)
+        .repository_name(&image.repository)
+        .image_ids(aws_sdk_ecr::types::ImageIdentifier::builder().image_tag(&image.tag).build())
+        .send()
+        .await
+        .with_context(|| format!("Failed to describe image {}:{} in ECR", image.repository, image.tag))?;
+
+    let Some(digest) = resp.image_details().iter().find_map(|detail| detail.image_digest.clone()) else {
+        bail!("ECR image {}:{} has no digest", image.repository, image.tag);
+    };
+
+    Ok(digest)
+}
+
+/// Gets the SSM activation for an external instance activation, looked up by its default instance name.
+pub async fn get_external_instance_activation(
+    ssm_client: &aws_sdk_ssm::Client,
+    activation_name: &str,
+) -> Result<Option<aws_sdk_ssm::types::Activation>, anyhow::Error> {
+    let resp = ssm_client
+        .describe_activations()
+        .filters(
+            aws_sdk_ssm::types::DescribeActivationsFilter::builder()
+                .filter_key(aws_sdk_ssm::types::DescribeActivationsFilterKeys::DefaultInstanceName)
+                .filter_values(activation_name)
+                .build(),
+        )
+        .send()
+        .await?;
+
+    Ok(resp.activation_list.unwrap_or_default().into_iter().next())
+}
+
+/// Returns the reasons, if any, that a service can't run with the EXTERNAL launch type on ECS Anywhere hosts.
+pub fn check_external_service(service: &Service) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if service.network_configuration.as_ref().is_some_and(|nc| nc.awsvpc_configuration.is_some()) {
+        problems.push(String::from("awsvpc network configuration is not supported on external instances"));
+    }
+    if !service.load_balancers.is_empty() {
+        problems.push(String::from("load balancers are not supported on external instances"));
+    }
+    if !service.service_registries.is_empty() {
+        problems.push(String::from("service discovery is not supported on external instances"));
+    }
+    if !service.capacity_provider_strategy.is_empty() {
+        problems.push(String::from("a service can't have both a launch type and a capacity provider strategy"));
+    }
+    if service.platform_version.is_some() {
+        problems.push(String::from("platform_version only applies to Fargate"));
+    }
+
+    problems
+}
+
+/// Returns the reasons, if any, that a task definition requiring EXTERNAL compatibility can't run on ECS Anywhere hosts.
+pub fn check_external_task_definition(task_def: &TaskDefinition) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if task_def.network_mode.as_deref() == Some("awsvpc") {
+        problems.push(String::from("awsvpc network mode is not supported on external instances"));
+    }
+    for volume in &task_def.volumes {
+        if volume.efs_volume_configuration.is_some() || volume.fsx_windows_file_server_volume_configuration.is_some() {
+            problems.push(format!(
+                "volume {}: EFS and FSx volumes are not supported on external instances",
+                volume.name
+            ));
+        }
+    }
+    if task_def.ephemeral_storage.is_some() {
+        problems.push(String::from("ephemeral_storage is only supported on Fargate"));
+    }
+    if !task_def.inference_accelerators.is_empty() {
+        problems.push(String::from("inference accelerators are not supported on external instances"));
+    }
+
+    problems
+}
+
+/// Checks that each target group address on a service's load balancers is a valid ELB target group
+/// address in the same region as the service. Target groups named by ARN aren't checked.
+pub fn check_target_group_addresses(service_region: &str, load_balancers: &[LoadBalancer]) -> Vec<String> {
+    load_balancers
+        .iter()
+        .filter_map(|lb| lb.target_group_arn.as_deref())
+        .filter_map(|target_group| {
+            check_elb_address::<ElbTargetGroupAddress>(target_group, service_region, "the service")
+        })
+        .collect()
+}
+
+/// Replaces each target group address on `load_balancers` with the ARN from that target group's outputs.
+/// Fails if a referenced target group hasn't been created yet.
+pub fn resolve_target_groups(prefix: &Path, load_balancers: Vec<LoadBalancer>) -> anyhow::Result<Vec<LoadBalancer>> {
+    let mut resolved = Vec::new();
+
+    for mut lb in load_balancers {
+        if let Some(target_group) = &lb.target_group_arn {
+            lb.target_group_arn = Some(resolve_elb_address::<ElbTargetGroupAddress>(prefix, target_group)?);
+        }
+        resolved.push(lb);
+    }
+
+    Ok(resolved)
+}
+
+/// Checks that `registry_arn` is the ARN of a Cloud Map service that exists in the same region as the service.
+/// ECS only rejects a missing registry once CreateService is called.
+pub async fn check_registry_arn(
+    client: &aws_sdk_servicediscovery::Client,
+    service_region: &str,
+    registry_arn: &str,
+) -> anyhow::Result<Option<String>> {
+    let Ok(arn) = parse_arn(registry_arn) else {
+        return Ok(Some(format!("{} is not a valid ARN", registry_arn)));
+    };
+
+    let service_id = match (arn.service, &arn.resource_id[..]) {
+        ("servicediscovery", ["service", service_id]) => *service_id,
+        _ => return Ok(Some(format!("{} is not a Cloud Map service ARN", registry_arn))),
+    };
+
+    if arn.region != service_region {
+        return Ok(Some(format!(
+            "Cloud Map service {} is in {}, but the service is in {}",
+            registry_arn, arn.region, service_region
+        )));
+    }
+
+    match client.get_service().id(service_id).send().await {
+        Ok(_) => Ok(None),
+        Err(e) => match e.as_service_error() {
+            Some(GetServiceError::ServiceNotFound(_)) => Ok(Some(format!("Cloud Map service {} does not exist", registry_arn))),
+            _ => Err(e.into()),
+        },
+    }
+}
+
+/// Whether the Cloud Map namespace `namespace_id` exists.
+pub async fn cloud_map_namespace_exists(client: &aws_sdk_servicediscovery::Client, namespace_id: &str) -> anyhow::Result<bool> {
+    match client.get_namespace().id(namespace_id).send().await {
+        Ok(_) => Ok(true),
+        Err(e) => match e.as_service_error() {
+            Some(GetNamespaceError::NamespaceNotFound(_)) => Ok(false),
+            _ => Err(e.into()),
+        },
+    }
+}
+
+/// Looks up the ARN of the Cloud Map service named `name` in the namespace `namespace_id`.
+pub async fn find_cloud_map_service(
+    client: &aws_sdk_servicediscovery::Client,
+    namespace_id: &str,
+    name: &str,
+) -> anyhow::Result<Option<String>> {
+    let filter = ServiceFilter::builder()
+        .name(ServiceFilterName::NamespaceId)
+        .values(namespace_id)
+        .condition(FilterCondition::Eq)
+        .build()?;
+
+    let mut services = client.list_services().filters(filter).into_paginator().items().send();
+    while let Some(service) = services.next().await {
+        let service = service?;
+        if service.name() == Some(name) {
+            return Ok(service.arn);
+        }
+    }
+
+    Ok(None)
+}
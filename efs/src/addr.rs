@@ -1,53 +1,110 @@
-use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 use std::path::{Path, PathBuf};
 
-type Region = String;
-type FileSystemId = String;
-type MountTargetId = String;
-type AccessPointId = String;
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 
 #[derive(Debug, Clone)]
 pub enum EfsResourceAddress {
-    FileSystem(Region, FileSystemId),
-    MountTarget(Region, FileSystemId, MountTargetId),
-    AccessPoint(Region, FileSystemId, AccessPointId),
+    FileSystem {
+        region: String,
+        fs_id:  String,
+    },
+    /// A file system has at most one mount target per subnet, so mount targets are addressed by subnet.
+    MountTarget {
+        region:    String,
+        fs_id:     String,
+        subnet_id: String,
+    },
+    AccessPoint {
+        region: String,
+        fs_id:  String,
+        ap_id:  String,
+    },
+    FileSystemPolicy {
+        region: String,
+        fs_id:  String,
+    },
 }
 
 impl ResourceAddress for EfsResourceAddress {
     fn to_path_buf(&self) -> PathBuf {
         match &self {
-            EfsResourceAddress::FileSystem(region, fs_id) => {
-                PathBuf::from(format!("aws/efs/{region}/file_systems/{fs_id}.ron"))
-            }
-            EfsResourceAddress::MountTarget(region, fs_id, mt_id) => PathBuf::from(format!(
-                "aws/efs/{region}/file_systems/{fs_id}/mount_targets/{mt_id}.ron"
+            EfsResourceAddress::FileSystem { region, fs_id } => PathBuf::from(format!("aws/efs/{region}/file_systems/{fs_id}.ron")),
+            EfsResourceAddress::MountTarget {
+                region,
+                fs_id,
+                subnet_id,
+            } => PathBuf::from(format!(
+                "aws/efs/{region}/file_systems/{fs_id}/mount_targets/{subnet_id}.ron"
             )),
-            EfsResourceAddress::AccessPoint(region, fs_id, ap_id) => PathBuf::from(format!(
+            EfsResourceAddress::AccessPoint { region, fs_id, ap_id } => PathBuf::from(format!(
                 "aws/efs/{region}/file_systems/{fs_id}/access_points/{ap_id}.ron"
             )),
+            EfsResourceAddress::FileSystemPolicy { region, fs_id } => {
+                PathBuf::from(format!("aws/efs/{region}/file_systems/{fs_id}/policy.ron"))
+            }
         }
     }
 
     fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
-        let path_components: Vec<&str> = path
-            .components()
-            .map(|s| s.as_os_str().to_str().unwrap())
-            .collect();
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
 
         match &path_components[..] {
             ["aws", "efs", region, "file_systems", fs_id] if fs_id.ends_with(".ron") => {
                 let fs_id = fs_id.strip_suffix(".ron").unwrap().to_string();
-                Ok(EfsResourceAddress::FileSystem(region.to_string(), fs_id))
+                Ok(EfsResourceAddress::FileSystem {
+                    region: region.to_string(),
+                    fs_id,
+                })
             }
-            ["aws", "efs", region, "file_systems", fs_id, "mount_targets", mt_id] if mt_id.ends_with(".ron") => {
-                let mt_id = mt_id.strip_suffix(".ron").unwrap().to_string();
-                Ok(EfsResourceAddress::MountTarget(region.to_string(), fs_id.to_string(), mt_id))
+            ["aws", "efs", region, "file_systems", fs_id, "mount_targets", subnet_id] if subnet_id.ends_with(".ron") => {
+                let subnet_id = subnet_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(EfsResourceAddress::MountTarget {
+                    region: region.to_string(),
+                    fs_id: fs_id.to_string(),
+                    subnet_id,
+                })
             }
             ["aws", "efs", region, "file_systems", fs_id, "access_points", ap_id] if ap_id.ends_with(".ron") => {
                 let ap_id = ap_id.strip_suffix(".ron").unwrap().to_string();
-                Ok(EfsResourceAddress::AccessPoint(region.to_string(), fs_id.to_string(), ap_id))
+                Ok(EfsResourceAddress::AccessPoint {
+                    region: region.to_string(),
+                    fs_id: fs_id.to_string(),
+                    ap_id,
+                })
             }
+            ["aws", "efs", region, "file_systems", fs_id, "policy.ron"] => Ok(EfsResourceAddress::FileSystemPolicy {
+                region: region.to_string(),
+                fs_id:  fs_id.to_string(),
+            }),
             _ => Err(invalid_addr_path(path)),
         }
     }
 }
+
+impl DescribeAddresses for EfsResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/efs/<region>/file_systems/<fs_id>.ron",
+                description: "An EFS file system",
+                example:     "aws/efs/us-east-1/file_systems/shared-data.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/efs/<region>/file_systems/<fs_id>/mount_targets/<subnet_id>.ron",
+                description: "A file system's mount target in a subnet",
+                example:     "aws/efs/us-east-1/file_systems/shared-data/mount_targets/subnet-0123456789abcdef0.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/efs/<region>/file_systems/<fs_id>/access_points/<ap_id>.ron",
+                description: "An access point into a file system",
+                example:     "aws/efs/us-east-1/file_systems/shared-data/access_points/app.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/efs/<region>/file_systems/<fs_id>/policy.ron",
+                description: "A file system's resource policy",
+                example:     "aws/efs/us-east-1/file_systems/shared-data/policy.ron",
+            },
+        ]
+    }
+}
@@ -1,17 +1,194 @@
-use std::{collections::HashMap, path::Path};
+use std::path::Path;
 
-use autoschematic_core::connector::{GetResourceOutput, Resource, ResourceAddress};
-use aws_sdk_route53::types::RrType;
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+    util::RON,
+};
+use aws_sdk_efs::types::LifeCycleState;
 
 use crate::{
     addr::EfsResourceAddress,
-    resource::{HostedZone, RecordSet, EfsResource},
+    op_impl::find_mount_target,
+    resource::{
+        AccessPoint, CreationInfo, EfsResource, FileSystem, FileSystemPolicy, FileSystemProtection, MountTarget, PosixUser,
+        RootDirectory,
+    },
+    tags::Tags,
+    util::from_lifecycle_policies,
 };
 
 use super::EfsConnector;
 
 impl EfsConnector {
-    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceOutput>, anyhow::Error> {
-        todo!();
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = EfsResourceAddress::from_path(addr)?;
+
+        match &addr {
+            EfsResourceAddress::FileSystem { region, fs_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let fs = match client.describe_file_systems().file_system_id(fs_id).send().await {
+                    Ok(resp) => match resp.file_systems.and_then(|file_systems| file_systems.into_iter().next()) {
+                        Some(fs) => fs,
+                        None => return Ok(None),
+                    },
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_file_system_not_found()) => return Ok(None),
+                    Err(e) => return Err(e.into()),
+                };
+
+                if matches!(fs.life_cycle_state, LifeCycleState::Deleting | LifeCycleState::Deleted) {
+                    return Ok(None);
+                }
+
+                let lifecycle_resp = client.describe_lifecycle_configuration().file_system_id(fs_id).send().await?;
+
+                // Only report the provisioned throughput when it's in effect
+                let provisioned_throughput_in_mibps = match fs.throughput_mode.as_ref().map(|mode| mode.as_str()) {
+                    Some("provisioned") => fs.provisioned_throughput_in_mibps,
+                    _ => None,
+                };
+
+                // The default aws/elasticfilesystem key is reported by ARN, but left unset in files
+                let kms_key_id = fs
+                    .kms_key_id
+                    .clone()
+                    .filter(|kms_key_id| !kms_key_id.ends_with(":alias/aws/elasticfilesystem"));
+
+                let file_system = FileSystem {
+                    performance_mode: fs.performance_mode.as_str().to_string(),
+                    throughput_mode: fs
+                        .throughput_mode
+                        .as_ref()
+                        .map(|mode| mode.as_str().to_string())
+                        .unwrap_or_else(|| String::from("bursting")),
+                    provisioned_throughput_in_mibps,
+                    encrypted: fs.encrypted.unwrap_or(false),
+                    kms_key_id,
+                    availability_zone_name: fs.availability_zone_name.clone(),
+                    lifecycle_policies: from_lifecycle_policies(lifecycle_resp.lifecycle_policies()),
+                    file_system_protection: fs.file_system_protection.as_ref().map(|protection| FileSystemProtection {
+                        replication_overwrite_protection: protection
+                            .replication_overwrite_protection
+                            .as_ref()
+                            .map(|p| p.as_str().to_string()),
+                    }),
+                    tags: Tags::from(fs.tags()),
+                };
+
+                get_resource_response!(
+                    EfsResource::FileSystem(file_system),
+                    [
+                        (String::from("file_system_id"), fs.file_system_id.clone()),
+                        (String::from("file_system_arn"), fs.file_system_arn.clone().unwrap_or_default())
+                    ]
+                )
+            }
+            EfsResourceAddress::MountTarget {
+                region,
+                fs_id,
+                subnet_id,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(mt) = find_mount_target(&client, fs_id, subnet_id).await? else {
+                    return Ok(None);
+                };
+
+                if mt.life_cycle_state == LifeCycleState::Deleting {
+                    return Ok(None);
+                }
+
+                let security_groups_resp = client
+                    .describe_mount_target_security_groups()
+                    .mount_target_id(&mt.mount_target_id)
+                    .send()
+                    .await?;
+
+                let mount_target = MountTarget {
+                    security_groups: security_groups_resp.security_groups,
+                    ip_address: mt.ip_address.clone(),
+                };
+
+                get_resource_response!(
+                    EfsResource::MountTarget(mount_target),
+                    [
+                        (String::from("mount_target_id"), mt.mount_target_id.clone()),
+                        (String::from("ip_address"), mt.ip_address.unwrap_or_default())
+                    ]
+                )
+            }
+            EfsResourceAddress::AccessPoint { region, ap_id, .. } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let ap = match client.describe_access_points().access_point_id(ap_id).send().await {
+                    Ok(resp) => match resp.access_points.and_then(|access_points| access_points.into_iter().next()) {
+                        Some(ap) => ap,
+                        None => return Ok(None),
+                    },
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_access_point_not_found()) => return Ok(None),
+                    Err(e) => return Err(e.into()),
+                };
+
+                if matches!(
+                    ap.life_cycle_state,
+                    Some(LifeCycleState::Deleting) | Some(LifeCycleState::Deleted)
+                ) {
+                    return Ok(None);
+                }
+
+                let access_point = AccessPoint {
+                    posix_user: ap.posix_user.as_ref().map(|posix_user| PosixUser {
+                        uid: posix_user.uid,
+                        gid: posix_user.gid,
+                        secondary_gids: posix_user.secondary_gids.clone().filter(|gids| !gids.is_empty()),
+                    }),
+                    root_directory: ap.root_directory.as_ref().map(|root_directory| RootDirectory {
+                        path: root_directory.path.clone(),
+                        creation_info: root_directory.creation_info.as_ref().map(|creation_info| CreationInfo {
+                            owner_uid:   creation_info.owner_uid,
+                            owner_gid:   creation_info.owner_gid,
+                            permissions: creation_info.permissions.clone(),
+                        }),
+                    }),
+                    tags: Tags::from(ap.tags()),
+                };
+
+                get_resource_response!(
+                    EfsResource::AccessPoint(access_point),
+                    [
+                        (String::from("access_point_id"), ap.access_point_id.clone().unwrap_or_default()),
+                        (String::from("access_point_arn"), ap.access_point_arn.clone().unwrap_or_default())
+                    ]
+                )
+            }
+            EfsResourceAddress::FileSystemPolicy { region, fs_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let policy = match client.describe_file_system_policy().file_system_id(fs_id).send().await {
+                    Ok(resp) => match resp.policy {
+                        Some(policy) => policy,
+                        None => return Ok(None),
+                    },
+                    Err(e)
+                        if e
+                            .as_service_error()
+                            .is_some_and(|e| e.is_policy_not_found() || e.is_file_system_not_found()) =>
+                    {
+                        return Ok(None);
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+
+                let val: serde_json::Value = serde_json::from_str(&policy)?;
+                let policy_document: ron::Value = RON.from_str(&RON.to_string(&val)?)?;
+
+                Ok(Some(GetResourceResponse {
+                    resource_definition: EfsResource::FileSystemPolicy(FileSystemPolicy { policy_document }).to_bytes()?,
+                    virt_addr: None,
+                    outputs: None,
+                }))
+            }
+        }
     }
 }
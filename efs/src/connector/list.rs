@@ -1,16 +1,85 @@
 use std::path::{Path, PathBuf};
 
 use autoschematic_core::connector::ResourceAddress;
+use aws_sdk_efs::types::LifeCycleState;
 
-use crate::{
-    addr::EfsResourceAddress,
-    util::{list_hosted_zones, list_resource_record_sets},
-};
+use crate::addr::EfsResourceAddress;
 
 use super::EfsConnector;
 
 impl EfsConnector {
     pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
-        todo!();
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut file_systems = client.describe_file_systems().into_paginator().items().send();
+            while let Some(fs) = file_systems.next().await {
+                let fs = fs?;
+                if matches!(fs.life_cycle_state, LifeCycleState::Deleting | LifeCycleState::Deleted) {
+                    continue;
+                }
+                let fs_id = fs.file_system_id;
+
+                results.push(
+                    EfsResourceAddress::FileSystem {
+                        region: region.clone(),
+                        fs_id:  fs_id.clone(),
+                    }
+                    .to_path_buf(),
+                );
+
+                let mount_targets = client.describe_mount_targets().file_system_id(&fs_id).send().await?;
+                for mt in mount_targets.mount_targets() {
+                    results.push(
+                        EfsResourceAddress::MountTarget {
+                            region:    region.clone(),
+                            fs_id:     fs_id.clone(),
+                            subnet_id: mt.subnet_id.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                let mut access_points = client
+                    .describe_access_points()
+                    .file_system_id(&fs_id)
+                    .into_paginator()
+                    .items()
+                    .send();
+                while let Some(ap) = access_points.next().await {
+                    let Some(ap_id) = ap?.access_point_id else {
+                        continue;
+                    };
+                    results.push(
+                        EfsResourceAddress::AccessPoint {
+                            region: region.clone(),
+                            fs_id: fs_id.clone(),
+                            ap_id,
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                match client.describe_file_system_policy().file_system_id(&fs_id).send().await {
+                    Ok(resp) if resp.policy.is_some() => {
+                        results.push(
+                            EfsResourceAddress::FileSystemPolicy {
+                                region: region.clone(),
+                                fs_id:  fs_id.clone(),
+                            }
+                            .to_path_buf(),
+                        );
+                    }
+                    Ok(_) => {}
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_policy_not_found()) => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+
+        Ok(results)
     }
 }
@@ -1,19 +1,101 @@
-use std::{collections::HashMap, path::Path};
+use std::path::Path;
 
-use anyhow::bail;
 use autoschematic_core::{
-    connector::{ConnectorOp, OpExecOutput, ResourceAddress},
-    error::{AutoschematicError, AutoschematicErrorType},
-    op_exec_output,
+    connector::{ConnectorOp, OpExecResponse, ResourceAddress},
+    error_util::invalid_op,
 };
-use aws_sdk_route53::types::{AliasTarget, Change, ChangeBatch, RrType};
 
-use crate::{addr::EfsResourceAddress, op::EfsConnectorOp};
+use crate::{
+    addr::EfsResourceAddress,
+    op::EfsConnectorOp,
+    op_impl,
+    util::{get_phy_access_point_id, get_phy_file_system_id},
+};
 
 use super::EfsConnector;
 
 impl EfsConnector {
-    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecOutput, anyhow::Error> {
-        todo!();
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = EfsResourceAddress::from_path(addr)?;
+        let op = EfsConnectorOp::from_str(op)?;
+
+        match &addr {
+            EfsResourceAddress::FileSystem { region, fs_id } => {
+                let fs_id = get_phy_file_system_id(&self.prefix, region, fs_id)?.unwrap_or(fs_id.into());
+
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    EfsConnectorOp::CreateFileSystem(file_system) => op_impl::create_file_system(&client, &file_system).await,
+                    EfsConnectorOp::UpdateFileSystemThroughput {
+                        throughput_mode,
+                        provisioned_throughput_in_mibps,
+                    } => op_impl::update_throughput(&client, &fs_id, &throughput_mode, provisioned_throughput_in_mibps).await,
+                    EfsConnectorOp::UpdateFileSystemLifecyclePolicies { lifecycle_policies } => {
+                        op_impl::update_lifecycle_policies(&client, &fs_id, &lifecycle_policies).await
+                    }
+                    EfsConnectorOp::UpdateFileSystemProtection { file_system_protection } => {
+                        op_impl::update_file_system_protection(&client, &fs_id, &file_system_protection).await
+                    }
+                    EfsConnectorOp::UpdateFileSystemTags(old_tags, new_tags) => {
+                        op_impl::update_file_system_tags(&client, &fs_id, &old_tags, &new_tags).await
+                    }
+                    EfsConnectorOp::DeleteFileSystem { bypass_protection } => {
+                        op_impl::delete_file_system(&client, &fs_id, bypass_protection).await
+                    }
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            EfsResourceAddress::MountTarget {
+                region,
+                fs_id,
+                subnet_id,
+            } => {
+                let fs_id = get_phy_file_system_id(&self.prefix, region, fs_id)?.unwrap_or(fs_id.into());
+
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    EfsConnectorOp::CreateMountTarget(mount_target) => {
+                        op_impl::create_mount_target(&client, &fs_id, subnet_id, &mount_target).await
+                    }
+                    EfsConnectorOp::UpdateMountTargetSecurityGroups { security_groups } => {
+                        op_impl::update_mount_target_security_groups(&client, &fs_id, subnet_id, &security_groups).await
+                    }
+                    EfsConnectorOp::DeleteMountTarget => op_impl::delete_mount_target(&client, &fs_id, subnet_id).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            EfsResourceAddress::AccessPoint { region, fs_id, ap_id } => {
+                let ap_id = get_phy_access_point_id(&self.prefix, region, fs_id, ap_id)?.unwrap_or(ap_id.into());
+                let fs_id = get_phy_file_system_id(&self.prefix, region, fs_id)?.unwrap_or(fs_id.into());
+
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    EfsConnectorOp::CreateAccessPoint(access_point) => {
+                        op_impl::create_access_point(&client, &fs_id, &access_point).await
+                    }
+                    EfsConnectorOp::UpdateAccessPointTags(old_tags, new_tags) => {
+                        op_impl::update_access_point_tags(&client, &ap_id, &old_tags, &new_tags).await
+                    }
+                    EfsConnectorOp::DeleteAccessPoint => op_impl::delete_access_point(&client, &ap_id).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            EfsResourceAddress::FileSystemPolicy { region, fs_id } => {
+                let fs_id = get_phy_file_system_id(&self.prefix, region, fs_id)?.unwrap_or(fs_id.into());
+
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    EfsConnectorOp::PutFileSystemPolicy(policy_document) => {
+                        op_impl::put_file_system_policy(&client, &fs_id, &policy_document).await
+                    }
+                    EfsConnectorOp::DeleteFileSystemPolicy => op_impl::delete_file_system_policy(&client, &fs_id).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+        }
     }
 }
@@ -1,28 +1,486 @@
 use std::path::Path;
 
+use anyhow::bail;
 use autoschematic_core::{
-    connector::{OpPlanOutput, ResourceAddress},
+    connector::{PlanResponseElement, ResourceAddress},
     connector_op,
-    util::RON,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
 };
 
 use autoschematic_core::connector::ConnectorOp;
 
-use crate::{
-    addr::EfsResourceAddress,
-    op::EfsConnectorOp,
-    resource::{HostedZone, RecordSet},
+use crate::resource::{
+    AccessPoint, CreationInfo, FileSystem, FileSystemPolicy, FileSystemProtection, LifecyclePolicy, MountTarget,
 };
 
-use super::EfsConnector;
+use super::{EfsConnector, EfsConnectorOp, EfsResourceAddress};
+
+const TRANSITION_RULES: &[&str] = &[
+    "AFTER_1_DAY",
+    "AFTER_7_DAYS",
+    "AFTER_14_DAYS",
+    "AFTER_30_DAYS",
+    "AFTER_60_DAYS",
+    "AFTER_90_DAYS",
+    "AFTER_180_DAYS",
+    "AFTER_270_DAYS",
+    "AFTER_365_DAYS",
+];
+
+/// Combinations that EFS would reject, caught at plan time instead.
+fn check_file_system(fs_id: &str, file_system: &FileSystem) -> anyhow::Result<()> {
+    match file_system.performance_mode.as_str() {
+        "generalPurpose" | "maxIO" => {}
+        performance_mode => bail!(
+            "EFS file system {} has performance_mode {}: expected generalPurpose or maxIO",
+            fs_id,
+            performance_mode
+        ),
+    }
+
+    match (file_system.throughput_mode.as_str(), file_system.provisioned_throughput_in_mibps) {
+        ("provisioned", Some(mibps)) if mibps < 1.0 => bail!(
+            "EFS file system {} has provisioned_throughput_in_mibps {}: it must be at least 1",
+            fs_id,
+            mibps
+        ),
+        ("provisioned", None) => bail!(
+            "EFS file system {} uses provisioned throughput, which requires provisioned_throughput_in_mibps",
+            fs_id
+        ),
+        ("bursting" | "elastic", Some(_)) => bail!(
+            "EFS file system {} uses {} throughput: leave provisioned_throughput_in_mibps unset",
+            fs_id,
+            file_system.throughput_mode
+        ),
+        ("bursting" | "elastic" | "provisioned", _) => {}
+        (throughput_mode, _) => bail!(
+            "EFS file system {} has throughput_mode {}: expected bursting, provisioned or elastic",
+            fs_id,
+            throughput_mode
+        ),
+    }
+
+    if file_system.kms_key_id.is_some() && !file_system.encrypted {
+        bail!("EFS file system {} sets kms_key_id, which requires encrypted: true", fs_id);
+    }
+
+    if file_system.performance_mode == "maxIO" {
+        if file_system.throughput_mode == "elastic" {
+            bail!("EFS file system {} uses maxIO performance mode, which doesn't support elastic throughput", fs_id);
+        }
+        if file_system.availability_zone_name.is_some() {
+            bail!("EFS file system {} uses maxIO performance mode, which One Zone file systems don't support", fs_id);
+        }
+    }
+
+    if let Some(file_system_protection) = &file_system.file_system_protection {
+        check_file_system_protection(fs_id, file_system_protection)?;
+    }
+
+    for lifecycle_policy in &file_system.lifecycle_policies {
+        check_lifecycle_policy(fs_id, &file_system.throughput_mode, lifecycle_policy)?;
+    }
+
+    Ok(())
+}
+
+fn check_file_system_protection(fs_id: &str, file_system_protection: &FileSystemProtection) -> anyhow::Result<()> {
+    match file_system_protection.replication_overwrite_protection.as_deref() {
+        None | Some("ENABLED" | "DISABLED") => Ok(()),
+        Some(protection) => bail!(
+            "EFS file system {} has replication_overwrite_protection {}: expected ENABLED or DISABLED",
+            fs_id,
+            protection
+        ),
+    }
+}
+
+fn check_lifecycle_policy(fs_id: &str, throughput_mode: &str, lifecycle_policy: &LifecyclePolicy) -> anyhow::Result<()> {
+    let transitions = [
+        &lifecycle_policy.transition_to_ia,
+        &lifecycle_policy.transition_to_archive,
+        &lifecycle_policy.transition_to_primary_storage_class,
+    ];
+    let transitions_set = transitions.iter().filter(|t| t.is_some()).count();
+    if transitions_set != 1 {
+        bail!(
+            "EFS file system {} has a lifecycle policy that sets {} transitions: each must set exactly one",
+            fs_id,
+            transitions_set
+        );
+    }
+
+    if let Some(transition_to_ia) = &lifecycle_policy.transition_to_ia
+        && !TRANSITION_RULES.contains(&transition_to_ia.as_str())
+    {
+        bail!(
+            "EFS file system {} has transition_to_ia {}: expected one of {}",
+            fs_id,
+            transition_to_ia,
+            TRANSITION_RULES.join(", ")
+        );
+    }
+
+    if let Some(transition_to_archive) = &lifecycle_policy.transition_to_archive {
+        if !TRANSITION_RULES.contains(&transition_to_archive.as_str()) {
+            bail!(
+                "EFS file system {} has transition_to_archive {}: expected one of {}",
+                fs_id,
+                transition_to_archive,
+                TRANSITION_RULES.join(", ")
+            );
+        }
+        if throughput_mode != "elastic" {
+            bail!(
+                "EFS file system {} sets transition_to_archive, which is only supported with elastic throughput",
+                fs_id
+            );
+        }
+    }
+
+    if let Some(transition_to_primary) = &lifecycle_policy.transition_to_primary_storage_class
+        && transition_to_primary != "AFTER_1_ACCESS"
+    {
+        bail!(
+            "EFS file system {} has transition_to_primary_storage_class {}: expected AFTER_1_ACCESS",
+            fs_id,
+            transition_to_primary
+        );
+    }
+
+    Ok(())
+}
+
+/// The fields of a file system that can only be set when it's created.
+fn file_system_immutable_fields(old: &FileSystem, new: &FileSystem) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if old.performance_mode != new.performance_mode {
+        fields.push("performance_mode");
+    }
+    if old.encrypted != new.encrypted {
+        fields.push("encrypted");
+    }
+    if old.kms_key_id != new.kms_key_id {
+        fields.push("kms_key_id");
+    }
+    if old.availability_zone_name != new.availability_zone_name {
+        fields.push("availability_zone_name");
+    }
+    fields
+}
+
+fn check_mount_target(subnet_id: &str, mount_target: &MountTarget) -> anyhow::Result<()> {
+    if mount_target.security_groups.is_empty() || mount_target.security_groups.len() > 5 {
+        bail!(
+            "Mount target in subnet {} has {} security groups: it must have between 1 and 5",
+            subnet_id,
+            mount_target.security_groups.len()
+        );
+    }
+    Ok(())
+}
+
+fn check_access_point(ap_id: &str, access_point: &AccessPoint) -> anyhow::Result<()> {
+    if let Some(root_directory) = &access_point.root_directory
+        && let Some(path) = &root_directory.path
+        && !path.starts_with('/')
+    {
+        bail!("EFS access point {} has root_directory path {}: it must start with '/'", ap_id, path);
+    }
+    Ok(())
+}
+
+/// EFS reports an access point without a root_directory as rooted at "/", so compare them that way.
+fn root_directory_of(access_point: &AccessPoint) -> (&str, Option<&CreationInfo>) {
+    match &access_point.root_directory {
+        Some(root_directory) => (
+            root_directory.path.as_deref().unwrap_or("/"),
+            root_directory.creation_info.as_ref(),
+        ),
+        None => ("/", None),
+    }
+}
+
+fn access_point_replacement_fields(old: &AccessPoint, new: &AccessPoint) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if old.posix_user != new.posix_user {
+        fields.push("posix_user");
+    }
+    if root_directory_of(old) != root_directory_of(new) {
+        fields.push("root_directory");
+    }
+    fields
+}
 
 impl EfsConnector {
     pub async fn do_plan(
         &self,
         addr: &Path,
-        current: Option<String>,
-        desired: Option<String>,
-    ) -> Result<Vec<OpPlanOutput>, anyhow::Error> {
-        todo!();
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = EfsResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            EfsResourceAddress::FileSystem { region, fs_id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_fs)) => {
+                    let new_fs: FileSystem = RON.from_str(&new_fs)?;
+                    check_file_system(fs_id, &new_fs)?;
+                    Ok(vec![connector_op!(
+                        EfsConnectorOp::CreateFileSystem(new_fs),
+                        format!("Create new EFS file system {} in region {}", fs_id, region)
+                    )])
+                }
+                (Some(_old_fs), None) => Ok(vec![connector_op!(
+                    EfsConnectorOp::DeleteFileSystem {
+                        bypass_protection: false,
+                    },
+                    format!("DELETE EFS file system {} in region {}, along with all of its files", fs_id, region)
+                )]),
+                (Some(old_fs), Some(new_fs)) => {
+                    let old_fs: FileSystem = RON.from_str(&old_fs)?;
+                    let new_fs: FileSystem = RON.from_str(&new_fs)?;
+                    check_file_system(fs_id, &new_fs)?;
+
+                    // Replacing a file system would lose its files, so this is left to the user
+                    let immutable_fields = file_system_immutable_fields(&old_fs, &new_fs);
+                    if !immutable_fields.is_empty() {
+                        bail!(
+                            "EFS file system {} can't change {} after creation: create a new file system and migrate its data instead",
+                            fs_id,
+                            immutable_fields.join(", ")
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_fs.tags != new_fs.tags {
+                        let diff = diff_ron_values(&old_fs.tags, &new_fs.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            EfsConnectorOp::UpdateFileSystemTags(old_fs.tags.clone(), new_fs.tags.clone()),
+                            format!("Modify tags for EFS file system `{}`\n{}", fs_id, diff)
+                        ));
+                    }
+
+                    if old_fs.throughput_mode != new_fs.throughput_mode
+                        || old_fs.provisioned_throughput_in_mibps != new_fs.provisioned_throughput_in_mibps
+                    {
+                        let describe = |fs: &FileSystem| match fs.provisioned_throughput_in_mibps {
+                            Some(mibps) => format!("{} ({} MiB/s)", fs.throughput_mode, mibps),
+                            None => fs.throughput_mode.clone(),
+                        };
+                        ops.push(connector_op!(
+                            EfsConnectorOp::UpdateFileSystemThroughput {
+                                throughput_mode: new_fs.throughput_mode.clone(),
+                                provisioned_throughput_in_mibps: new_fs.provisioned_throughput_in_mibps,
+                            },
+                            format!(
+                                "Change throughput for EFS file system `{}` from {} to {} (EFS allows switching throughput mode or lowering provisioned throughput once every 24 hours)",
+                                fs_id,
+                                describe(&old_fs),
+                                describe(&new_fs)
+                            )
+                        ));
+                    }
+
+                    if old_fs.lifecycle_policies != new_fs.lifecycle_policies {
+                        let diff = diff_ron_values(&old_fs.lifecycle_policies, &new_fs.lifecycle_policies).unwrap_or_default();
+                        ops.push(connector_op!(
+                            EfsConnectorOp::UpdateFileSystemLifecyclePolicies {
+                                lifecycle_policies: new_fs.lifecycle_policies.clone(),
+                            },
+                            format!("Modify lifecycle policies for EFS file system `{}`\n{}", fs_id, diff)
+                        ));
+                    }
+
+                    // Unset protection leaves whatever the file system has
+                    if old_fs.file_system_protection != new_fs.file_system_protection
+                        && let Some(file_system_protection) = &new_fs.file_system_protection
+                    {
+                        let diff = diff_ron_values(&old_fs.file_system_protection, &new_fs.file_system_protection)
+                            .unwrap_or_default();
+                        ops.push(connector_op!(
+                            EfsConnectorOp::UpdateFileSystemProtection {
+                                file_system_protection: file_system_protection.clone(),
+                            },
+                            format!("Modify protection for EFS file system `{}`\n{}", fs_id, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            EfsResourceAddress::MountTarget {
+                region,
+                fs_id,
+                subnet_id,
+            } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_mt)) => {
+                    let new_mt: MountTarget = RON.from_str(&new_mt)?;
+                    check_mount_target(subnet_id, &new_mt)?;
+                    Ok(vec![connector_op!(
+                        EfsConnectorOp::CreateMountTarget(new_mt),
+                        format!(
+                            "Create new mount target for EFS file system {} in subnet {} in region {}",
+                            fs_id, subnet_id, region
+                        )
+                    )])
+                }
+                (Some(_old_mt), None) => Ok(vec![connector_op!(
+                    EfsConnectorOp::DeleteMountTarget,
+                    format!(
+                        "DELETE mount target for EFS file system {} in subnet {} in region {}",
+                        fs_id, subnet_id, region
+                    )
+                )]),
+                (Some(old_mt), Some(new_mt)) => {
+                    let mut old_mt: MountTarget = RON.from_str(&old_mt)?;
+                    let mut new_mt: MountTarget = RON.from_str(&new_mt)?;
+                    check_mount_target(subnet_id, &new_mt)?;
+
+                    // An unset ip_address means "let EFS pick one", so keep whichever it picked
+                    if new_mt.ip_address.is_none() {
+                        new_mt.ip_address = old_mt.ip_address.clone();
+                    }
+
+                    if old_mt.ip_address != new_mt.ip_address {
+                        let diff = diff_ron_values(&old_mt, &new_mt).unwrap_or_default();
+                        return Ok(vec![
+                            connector_op!(
+                                EfsConnectorOp::DeleteMountTarget,
+                                format!(
+                                    "REPLACE mount target for EFS file system `{}` in subnet `{}` (requires replacement: ip_address)\n{}",
+                                    fs_id, subnet_id, diff
+                                )
+                            ),
+                            connector_op!(
+                                EfsConnectorOp::CreateMountTarget(new_mt),
+                                format!(
+                                    "Create new mount target for EFS file system {} in subnet {} in region {}",
+                                    fs_id, subnet_id, region
+                                )
+                            ),
+                        ]);
+                    }
+
+                    // EFS doesn't keep the order of security groups
+                    old_mt.security_groups.sort();
+                    new_mt.security_groups.sort();
+
+                    let mut ops = Vec::new();
+
+                    if old_mt.security_groups != new_mt.security_groups {
+                        let diff = diff_ron_values(&old_mt.security_groups, &new_mt.security_groups).unwrap_or_default();
+                        ops.push(connector_op!(
+                            EfsConnectorOp::UpdateMountTargetSecurityGroups {
+                                security_groups: new_mt.security_groups.clone(),
+                            },
+                            format!(
+                                "Modify security groups for mount target of EFS file system `{}` in subnet `{}`\n{}",
+                                fs_id, subnet_id, diff
+                            )
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            EfsResourceAddress::AccessPoint { region, fs_id, ap_id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_ap)) => {
+                    let new_ap: AccessPoint = RON.from_str(&new_ap)?;
+                    check_access_point(ap_id, &new_ap)?;
+                    Ok(vec![connector_op!(
+                        EfsConnectorOp::CreateAccessPoint(new_ap),
+                        format!(
+                            "Create new access point {} for EFS file system {} in region {}",
+                            ap_id, fs_id, region
+                        )
+                    )])
+                }
+                (Some(_old_ap), None) => Ok(vec![connector_op!(
+                    EfsConnectorOp::DeleteAccessPoint,
+                    format!(
+                        "DELETE access point {} for EFS file system {} in region {}",
+                        ap_id, fs_id, region
+                    )
+                )]),
+                (Some(old_ap), Some(new_ap)) => {
+                    let old_ap: AccessPoint = RON.from_str(&old_ap)?;
+                    let new_ap: AccessPoint = RON.from_str(&new_ap)?;
+                    check_access_point(ap_id, &new_ap)?;
+
+                    let replacement_fields = access_point_replacement_fields(&old_ap, &new_ap);
+                    if !replacement_fields.is_empty() {
+                        let diff = diff_ron_values(&old_ap, &new_ap).unwrap_or_default();
+                        return Ok(vec![
+                            connector_op!(
+                                EfsConnectorOp::DeleteAccessPoint,
+                                format!(
+                                    "REPLACE access point `{}` for EFS file system `{}` (requires replacement: {}); its access point ID will change\n{}",
+                                    ap_id,
+                                    fs_id,
+                                    replacement_fields.join(", "),
+                                    diff
+                                )
+                            ),
+                            connector_op!(
+                                EfsConnectorOp::CreateAccessPoint(new_ap),
+                                format!(
+                                    "Create new access point {} for EFS file system {} in region {}",
+                                    ap_id, fs_id, region
+                                )
+                            ),
+                        ]);
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_ap.tags != new_ap.tags {
+                        let diff = diff_ron_values(&old_ap.tags, &new_ap.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            EfsConnectorOp::UpdateAccessPointTags(old_ap.tags.clone(), new_ap.tags.clone()),
+                            format!("Modify tags for EFS access point `{}`\n{}", ap_id, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            EfsResourceAddress::FileSystemPolicy { region, fs_id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_policy)) => {
+                    let new_policy: FileSystemPolicy = RON.from_str(&new_policy)?;
+                    Ok(vec![connector_op!(
+                        EfsConnectorOp::PutFileSystemPolicy(new_policy.policy_document),
+                        format!("Set file system policy for EFS file system {} in region {}", fs_id, region)
+                    )])
+                }
+                (Some(_old_policy), None) => Ok(vec![connector_op!(
+                    EfsConnectorOp::DeleteFileSystemPolicy,
+                    format!("DELETE file system policy for EFS file system {} in region {}", fs_id, region)
+                )]),
+                (Some(old_policy), Some(new_policy)) => {
+                    let old_policy: FileSystemPolicy = RON.from_str(&old_policy)?;
+                    let new_policy: FileSystemPolicy = RON.from_str(&new_policy)?;
+
+                    if old_policy == new_policy {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_policy.policy_document, &new_policy.policy_document).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        EfsConnectorOp::PutFileSystemPolicy(new_policy.policy_document),
+                        format!("Modify file system policy for EFS file system `{}`\n{}", fs_id, diff)
+                    )])
+                }
+            },
+        }
     }
 }
@@ -1,5 +1,4 @@
 mod addr;
-mod config;
-mod op_impl;
 mod resource;
-mod tags;
+pub mod op;
+pub mod tags;
@@ -1,8 +1,9 @@
+use addr::EfsResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
 use autoschematic_core::tarpc_bridge::tarpc_connector_main;
 use connector::EfsConnector;
 
 pub mod connector;
-// pub mod client_cache;
 pub mod addr;
 pub mod config;
 pub mod op;
@@ -13,6 +14,12 @@ pub mod util;
 
 #[tokio::main]
 pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let patterns = EfsResourceAddress::checked_address_patterns()?;
+        print!("{}", render_address_docs("aws/efs", &patterns));
+        return Ok(());
+    }
+
     tarpc_connector_main::<EfsConnector>().await?;
     Ok(())
 }
@@ -1,6 +1,8 @@
-use autoschematic_core::{connector::ConnectorOp, util::RON};
+use autoschematic_core::connector::ConnectorOp;
 use serde::{Deserialize, Serialize};
 
+use autoschematic_core::util::RON;
+
 use super::{
     resource::{AccessPoint, FileSystem, FileSystemProtection, LifecyclePolicy, MountTarget},
     tags::Tags,
@@ -8,7 +10,7 @@ use super::{
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum EfsConnectorOp {
-    // FileSystem operations
+    // File system operations
     CreateFileSystem(FileSystem),
     UpdateFileSystemThroughput {
         throughput_mode: String,
@@ -21,21 +23,26 @@ pub enum EfsConnectorOp {
         file_system_protection: FileSystemProtection,
     },
     UpdateFileSystemTags(Tags, Tags),
+    /// If `bypass_protection` is set, the file system's mount targets are deleted along with it.
     DeleteFileSystem {
         bypass_protection: bool,
     },
 
-    // MountTarget operations
+    // Mount target operations
     CreateMountTarget(MountTarget),
     UpdateMountTargetSecurityGroups {
         security_groups: Vec<String>,
     },
     DeleteMountTarget,
 
-    // AccessPoint operations
+    // Access point operations
     CreateAccessPoint(AccessPoint),
     UpdateAccessPointTags(Tags, Tags),
     DeleteAccessPoint,
+
+    // File system policy operations
+    PutFileSystemPolicy(ron::Value),
+    DeleteFileSystemPolicy,
 }
 
 impl ConnectorOp for EfsConnectorOp {
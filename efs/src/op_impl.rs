@@ -1,450 +1,527 @@
-use anyhow::bail;
-use aws_sdk_efs::types::{LifecyclePolicy as SdkLifecyclePolicy, Tag};
-use std::collections::HashMap;
+use std::{collections::HashMap, future::Future, time::Duration};
 
-use super::{
-    resource::{AccessPoint, FileSystem, FileSystemProtection, LifecyclePolicy, MountTarget},
-    tags::{tag_diff, Tags},
-};
+use anyhow::{Context, bail};
 use autoschematic_core::connector::OpExecResponse;
+use aws_sdk_efs::types::{
+    LifeCycleState, MountTargetDescription, PerformanceMode, ReplicationOverwriteProtection, ThroughputMode,
+};
 
-// FileSystem operations
-pub async fn create_file_system(
-    client: &aws_sdk_efs::Client,
-    file_system: &FileSystem,
-    file_system_id: &str,
-) -> anyhow::Result<OpExecResponse> {
-    // Build create request
-    let mut request = client
-        .create_file_system()
-        .performance_mode(file_system.performance_mode.as_str().into())
-        .encrypted(file_system.encrypted)
-        .throughput_mode(file_system.throughput_mode.as_str().into());
+use crate::{
+    resource::{AccessPoint, FileSystem, FileSystemProtection, LifecyclePolicy, MountTarget},
+    tags::{Tags, tag_diff},
+    util::to_lifecycle_policies,
+};
 
-    // Add optional parameters
-    if let Some(throughput) = file_system.provisioned_throughput_in_mibps {
-        request = request.provisioned_throughput_in_mibps(throughput);
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Mount targets usually take a minute or two to become available or to be deleted.
+const STATUS_MAX_POLLS: usize = 120;
+
+/// EFS rejects most changes to a file system, mount target or access point until it's available,
+/// and won't delete a file system while it still has mount targets. Polls `status` until it
+/// returns `target`, or until the resource no longer exists if `target` is None.
+async fn wait_for_status<F, Fut>(description: &str, target: Option<LifeCycleState>, status: F) -> anyhow::Result<()>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = anyhow::Result<Option<LifeCycleState>>>,
+{
+    for _ in 0..STATUS_MAX_POLLS {
+        let state = status().await?;
+        if state == target {
+            return Ok(());
+        }
+        if state == Some(LifeCycleState::Error) {
+            bail!("{} is in the error state", description);
+        }
+        tokio::time::sleep(STATUS_POLL_INTERVAL).await;
     }
 
-    if let Some(kms_key_id) = &file_system.kms_key_id {
-        request = request.kms_key_id(kms_key_id);
+    match target {
+        Some(target) => bail!("Timed out waiting for {} to become {}", description, target.as_str()),
+        None => bail!("Timed out waiting for {} to be deleted", description),
     }
+}
 
-    if let Some(az) = &file_system.availability_zone_name {
-        request = request.availability_zone_name(az);
+async fn file_system_state(client: &aws_sdk_efs::Client, fs_id: &str) -> anyhow::Result<Option<LifeCycleState>> {
+    match client.describe_file_systems().file_system_id(fs_id).send().await {
+        Ok(resp) => Ok(resp
+            .file_systems()
+            .first()
+            .map(|fs| fs.life_cycle_state.clone())
+            .filter(|state| *state != LifeCycleState::Deleted)),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_file_system_not_found()) => Ok(None),
+        Err(e) => Err(e.into()),
     }
+}
 
-    // Add tags
-    let aws_tags: Vec<Tag> = file_system.tags.to_vec()?;
-    if !aws_tags.is_empty() {
-        request = request.set_tags(Some(aws_tags));
+async fn mount_target_state(client: &aws_sdk_efs::Client, mount_target_id: &str) -> anyhow::Result<Option<LifeCycleState>> {
+    match client.describe_mount_targets().mount_target_id(mount_target_id).send().await {
+        Ok(resp) => Ok(resp
+            .mount_targets()
+            .first()
+            .map(|mt| mt.life_cycle_state.clone())
+            .filter(|state| *state != LifeCycleState::Deleted)),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_mount_target_not_found()) => Ok(None),
+        Err(e) => Err(e.into()),
     }
+}
 
-    // Send the request
-    let response = request.send().await?;
+async fn access_point_state(client: &aws_sdk_efs::Client, ap_id: &str) -> anyhow::Result<Option<LifeCycleState>> {
+    match client.describe_access_points().access_point_id(ap_id).send().await {
+        Ok(resp) => Ok(resp
+            .access_points()
+            .first()
+            .and_then(|ap| ap.life_cycle_state.clone())
+            .filter(|state| *state != LifeCycleState::Deleted)),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_access_point_not_found()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
 
-    let mut outputs = HashMap::new();
-    let fs_id = response.file_system_id();
-    outputs.insert(String::from("file_system_id"), Some(fs_id.to_string()));
+async fn wait_for_file_system_available(client: &aws_sdk_efs::Client, fs_id: &str) -> anyhow::Result<()> {
+    wait_for_status(&format!("EFS file system {}", fs_id), Some(LifeCycleState::Available), || {
+        file_system_state(client, fs_id)
+    })
+    .await
+}
 
-    // Apply lifecycle policies if any are specified
-    if !file_system.lifecycle_policies.is_empty() {
-        let lifecycle_policies: Vec<SdkLifecyclePolicy> = file_system
-            .lifecycle_policies
-            .iter()
-            .map(|policy| {
-                let mut builder = SdkLifecyclePolicy::builder();
+/// Finds the file system's mount target in `subnet_id`, if it has one.
+pub async fn find_mount_target(
+    client: &aws_sdk_efs::Client,
+    fs_id: &str,
+    subnet_id: &str,
+) -> anyhow::Result<Option<MountTargetDescription>> {
+    let resp = match client.describe_mount_targets().file_system_id(fs_id).send().await {
+        Ok(resp) => resp,
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_file_system_not_found()) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(resp
+        .mount_targets()
+        .iter()
+        .find(|mt| mt.subnet_id == subnet_id && mt.life_cycle_state != LifeCycleState::Deleted)
+        .cloned())
+}
 
-                if let Some(to_ia) = &policy.transition_to_ia {
-                    builder = builder.transition_to_ia(to_ia.as_str().into());
-                }
+/// Creates a file system, waits for it to become available, then applies its lifecycle policies
+/// and protection, which CreateFileSystem doesn't take.
+pub async fn create_file_system(client: &aws_sdk_efs::Client, file_system: &FileSystem) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_file_system()
+        .creation_token(uuid::Uuid::new_v4().to_string())
+        .performance_mode(PerformanceMode::from(file_system.performance_mode.as_str()))
+        .throughput_mode(ThroughputMode::from(file_system.throughput_mode.as_str()))
+        .set_provisioned_throughput_in_mibps(file_system.provisioned_throughput_in_mibps)
+        .encrypted(file_system.encrypted)
+        .set_kms_key_id(file_system.kms_key_id.clone())
+        .set_availability_zone_name(file_system.availability_zone_name.clone())
+        .set_tags(Some(file_system.tags.to_vec()?))
+        .send()
+        .await?;
 
-                if let Some(to_primary) = &policy.transition_to_primary_storage_class {
-                    builder =
-                        builder.transition_to_primary_storage_class(to_primary.as_str().into());
-                }
+    let fs_id = resp.file_system_id().to_string();
 
-                builder.build()
-            })
-            .collect();
+    wait_for_file_system_available(client, &fs_id).await?;
 
+    if !file_system.lifecycle_policies.is_empty() {
         client
             .put_lifecycle_configuration()
-            .file_system_id(response.file_system_id())
-            .set_lifecycle_policies(Some(lifecycle_policies))
+            .file_system_id(&fs_id)
+            .set_lifecycle_policies(Some(to_lifecycle_policies(&file_system.lifecycle_policies)))
             .send()
             .await?;
     }
 
-    // Apply file system protection if specified
-    if let Some(protection) = &file_system.file_system_protection
-        && let Some(replication_protection) = &protection.replication_overwrite_protection {
-            client
-                .update_file_system_protection()
-                .file_system_id(response.file_system_id())
-                .replication_overwrite_protection(replication_protection.as_str().into())
-                .send()
-                .await?;
-        }
+    if let Some(file_system_protection) = &file_system.file_system_protection
+        && file_system_protection.replication_overwrite_protection.is_some()
+    {
+        put_file_system_protection(client, &fs_id, file_system_protection).await?;
+    }
 
     Ok(OpExecResponse {
-        outputs: Some(outputs),
-        friendly_message: Some(format!(
-            "Created EFS file system with ID: {}",
-            response.file_system_id()
-        )),
+        outputs: Some(HashMap::from([
+            (String::from("file_system_id"), Some(fs_id.clone())),
+            (String::from("file_system_arn"), resp.file_system_arn().map(String::from)),
+        ])),
+        friendly_message: Some(format!("Created EFS file system {}", fs_id)),
     })
 }
 
-pub async fn update_file_system_throughput(
+pub async fn update_throughput(
     client: &aws_sdk_efs::Client,
-    file_system_id: &str,
+    fs_id: &str,
     throughput_mode: &str,
     provisioned_throughput_in_mibps: Option<f64>,
 ) -> anyhow::Result<OpExecResponse> {
-    let mut request = client
+    client
         .update_file_system()
-        .file_system_id(file_system_id)
-        .throughput_mode(throughput_mode.into());
-
-    // Add provisioned throughput if mode is provisioned
-    if throughput_mode == "provisioned" {
-        if let Some(throughput) = provisioned_throughput_in_mibps {
-            request = request.provisioned_throughput_in_mibps(throughput);
-        } else {
-            bail!("Provisioned throughput value is required when throughput mode is 'provisioned'");
-        }
-    }
+        .file_system_id(fs_id)
+        .throughput_mode(ThroughputMode::from(throughput_mode))
+        .set_provisioned_throughput_in_mibps(provisioned_throughput_in_mibps)
+        .send()
+        .await?;
 
-    let response = request.send().await?;
+    wait_for_file_system_available(client, fs_id).await?;
+
+    let message = match provisioned_throughput_in_mibps {
+        Some(mibps) => format!(
+            "Set throughput for EFS file system {} to {} ({} MiB/s)",
+            fs_id, throughput_mode, mibps
+        ),
+        None => format!("Set throughput for EFS file system {} to {}", fs_id, throughput_mode),
+    };
 
     Ok(OpExecResponse {
         outputs: None,
-        friendly_message: Some(format!(
-            "Updated throughput mode to '{throughput_mode}' for file system {file_system_id}"
-        )),
+        friendly_message: Some(message),
     })
 }
 
-pub async fn update_file_system_lifecycle_policies(
+pub async fn update_lifecycle_policies(
     client: &aws_sdk_efs::Client,
-    file_system_id: &str,
-    lifecycle_policies: Vec<LifecyclePolicy>,
+    fs_id: &str,
+    lifecycle_policies: &[LifecyclePolicy],
 ) -> anyhow::Result<OpExecResponse> {
-    let lifecycle_policies: Vec<SdkLifecyclePolicy> = lifecycle_policies
-        .iter()
-        .map(|policy| {
-            let mut builder = SdkLifecyclePolicy::builder();
-
-            if let Some(to_ia) = &policy.transition_to_ia {
-                builder = builder.transition_to_ia(to_ia.as_str().into());
-            }
-
-            if let Some(to_primary) = &policy.transition_to_primary_storage_class {
-                builder = builder.transition_to_primary_storage_class(to_primary.as_str().into());
-            }
-
-            builder.build()
-        })
-        .collect();
-
     client
         .put_lifecycle_configuration()
-        .file_system_id(file_system_id)
-        .set_lifecycle_policies(Some(lifecycle_policies))
+        .file_system_id(fs_id)
+        .set_lifecycle_policies(Some(to_lifecycle_policies(lifecycle_policies)))
         .send()
         .await?;
 
     Ok(OpExecResponse {
         outputs: None,
-        friendly_message: Some(format!(
-            "Updated lifecycle policies for file system {file_system_id}"
-        )),
+        friendly_message: Some(format!("Updated lifecycle policies for EFS file system {}", fs_id)),
     })
 }
 
-pub async fn update_file_system_protection(
+async fn put_file_system_protection(
     client: &aws_sdk_efs::Client,
-    file_system_id: &str,
-    file_system_protection: FileSystemProtection,
-) -> anyhow::Result<OpExecResponse> {
-    let mut request = client
+    fs_id: &str,
+    file_system_protection: &FileSystemProtection,
+) -> anyhow::Result<()> {
+    client
         .update_file_system_protection()
-        .file_system_id(file_system_id);
-
-    if let Some(replication_protection) = file_system_protection.replication_overwrite_protection {
-        request = request.replication_overwrite_protection(replication_protection.as_str().into());
-    }
+        .file_system_id(fs_id)
+        .set_replication_overwrite_protection(
+            file_system_protection
+                .replication_overwrite_protection
+                .as_deref()
+                .map(ReplicationOverwriteProtection::from),
+        )
+        .send()
+        .await?;
+    Ok(())
+}
 
-    request.send().await?;
+pub async fn update_file_system_protection(
+    client: &aws_sdk_efs::Client,
+    fs_id: &str,
+    file_system_protection: &FileSystemProtection,
+) -> anyhow::Result<OpExecResponse> {
+    put_file_system_protection(client, fs_id, file_system_protection).await?;
 
     Ok(OpExecResponse {
         outputs: None,
-        friendly_message: Some(format!(
-            "Updated protection settings for file system {file_system_id}"
-        )),
+        friendly_message: Some(format!("Updated protection for EFS file system {}", fs_id)),
     })
 }
 
-pub async fn update_file_system_tags(
-    client: &aws_sdk_efs::Client,
-    file_system_id: &str,
-    old_tags: &Tags,
-    new_tags: &Tags,
-) -> anyhow::Result<OpExecResponse> {
-    // Calculate tag differences
-    let (remove_tags, add_tags) = tag_diff(old_tags, new_tags)?;
+/// File systems and access points are both tagged by their ID.
+async fn update_tags(client: &aws_sdk_efs::Client, resource_id: &str, old_tags: &Tags, new_tags: &Tags) -> anyhow::Result<()> {
+    let (untag_keys, new_tags) = tag_diff(old_tags, new_tags)?;
 
-    // Remove tags if any need to be removed
-    if !remove_tags.is_empty() {
+    if !untag_keys.is_empty() {
         client
             .untag_resource()
-            .resource_id(file_system_id)
-            .set_tag_keys(Some(remove_tags))
+            .resource_id(resource_id)
+            .set_tag_keys(Some(untag_keys))
             .send()
             .await?;
     }
 
-    // Add new tags if any need to be added
-    if !add_tags.is_empty() {
+    if !new_tags.is_empty() {
         client
             .tag_resource()
-            .resource_id(file_system_id)
-            .set_tags(Some(add_tags))
+            .resource_id(resource_id)
+            .set_tags(Some(new_tags))
             .send()
             .await?;
     }
 
+    Ok(())
+}
+
+pub async fn update_file_system_tags(
+    client: &aws_sdk_efs::Client,
+    fs_id: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    update_tags(client, fs_id, old_tags, new_tags).await?;
+
     Ok(OpExecResponse {
         outputs: None,
-        friendly_message: Some(format!("Updated tags for file system {file_system_id}")),
+        friendly_message: Some(format!("Updated tags for EFS file system {}", fs_id)),
     })
 }
 
+/// A file system can't be deleted while it has mount targets. Mount targets that are already
+/// being deleted, e.g. earlier in the same apply, are waited for. Any others are deleted first
+/// if `bypass_protection` is set, and are an error otherwise.
 pub async fn delete_file_system(
     client: &aws_sdk_efs::Client,
-    file_system_id: &str,
+    fs_id: &str,
     bypass_protection: bool,
 ) -> anyhow::Result<OpExecResponse> {
-    // First ensure all mount targets are deleted
-    let mount_targets = client
-        .describe_mount_targets()
-        .file_system_id(file_system_id)
-        .send()
-        .await?;
+    let resp = client.describe_mount_targets().file_system_id(fs_id).send().await?;
 
-    let mts = mount_targets.mount_targets();
-    if !mts.is_empty() {
+    let remaining: Vec<&MountTargetDescription> = resp
+        .mount_targets()
+        .iter()
+        .filter(|mt| !matches!(mt.life_cycle_state, LifeCycleState::Deleting | LifeCycleState::Deleted))
+        .collect();
+    if !remaining.is_empty() {
         if !bypass_protection {
-            bail!("Cannot delete file system with existing mount targets. Set bypass_protection to true or delete mount targets first.");
+            let subnets: Vec<&str> = remaining.iter().map(|mt| mt.subnet_id.as_str()).collect();
+            bail!(
+                "EFS file system {} still has mount targets in subnets {}; delete them first, or set bypass_protection",
+                fs_id,
+                subnets.join(", ")
+            );
         }
 
-        // Delete all mount targets if bypass_protection is true
-        for mt in mts {
-            client
-                .delete_mount_target()
-                .mount_target_id(mt.mount_target_id())
-                .send()
-                .await?;
+        for mt in remaining {
+            client.delete_mount_target().mount_target_id(&mt.mount_target_id).send().await?;
         }
-
-        // Wait for mount targets to be fully deleted
-        // This is a simplified approach, in production you might want to add retries and timeouts
-        tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
     }
 
-    client
-        .delete_file_system()
-        .file_system_id(file_system_id)
-        .send()
+    for mt in resp.mount_targets() {
+        wait_for_status(&format!("EFS mount target {}", mt.mount_target_id), None, || {
+            mount_target_state(client, &mt.mount_target_id)
+        })
         .await?;
+    }
+
+    client.delete_file_system().file_system_id(fs_id).send().await?;
+
+    wait_for_status(&format!("EFS file system {}", fs_id), None, || file_system_state(client, fs_id)).await?;
 
     Ok(OpExecResponse {
-        outputs: None,
-        friendly_message: Some(format!("Deleted file system {file_system_id}")),
+        outputs: Some(HashMap::from([
+            (String::from("file_system_id"), None),
+            (String::from("file_system_arn"), None),
+        ])),
+        friendly_message: Some(format!("Deleted EFS file system {}", fs_id)),
     })
 }
 
-// MountTarget operations
 pub async fn create_mount_target(
     client: &aws_sdk_efs::Client,
+    fs_id: &str,
+    subnet_id: &str,
     mount_target: &MountTarget,
-    file_system_id: &str,
 ) -> anyhow::Result<OpExecResponse> {
-    let mut request = client
+    let resp = client
         .create_mount_target()
-        .file_system_id(file_system_id)
-        .subnet_id(&mount_target.subnet_id)
-        .set_security_groups(Some(mount_target.security_groups.clone()));
-
-    // Add optional IP address if specified
-    if let Some(ip_address) = &mount_target.ip_address {
-        request = request.ip_address(ip_address);
-    }
+        .file_system_id(fs_id)
+        .subnet_id(subnet_id)
+        .set_security_groups(Some(mount_target.security_groups.clone()))
+        .set_ip_address(mount_target.ip_address.clone())
+        .send()
+        .await?;
 
-    let response = request.send().await?;
+    let mount_target_id = resp.mount_target_id().to_string();
 
-    let mut outputs = HashMap::new();
-    let mt_id = response.mount_target_id();
-    outputs.insert(String::from("mount_target_id"), Some(mt_id.to_string()));
+    wait_for_status(
+        &format!("EFS mount target {}", mount_target_id),
+        Some(LifeCycleState::Available),
+        || mount_target_state(client, &mount_target_id),
+    )
+    .await?;
 
     Ok(OpExecResponse {
-        outputs: Some(outputs),
+        outputs: Some(HashMap::from([
+            (String::from("mount_target_id"), Some(mount_target_id.clone())),
+            (String::from("ip_address"), resp.ip_address().map(String::from)),
+        ])),
         friendly_message: Some(format!(
-            "Created mount target with ID: {}",
-            response.mount_target_id()
+            "Created EFS mount target {} for file system {} in subnet {}",
+            mount_target_id, fs_id, subnet_id
         )),
     })
 }
 
 pub async fn update_mount_target_security_groups(
     client: &aws_sdk_efs::Client,
-    mount_target_id: &str,
-    security_groups: Vec<String>,
+    fs_id: &str,
+    subnet_id: &str,
+    security_groups: &[String],
 ) -> anyhow::Result<OpExecResponse> {
+    let mount_target = find_mount_target(client, fs_id, subnet_id)
+        .await?
+        .with_context(|| format!("EFS file system {} has no mount target in subnet {}", fs_id, subnet_id))?;
+
     client
         .modify_mount_target_security_groups()
-        .mount_target_id(mount_target_id)
-        .set_security_groups(Some(security_groups))
+        .mount_target_id(&mount_target.mount_target_id)
+        .set_security_groups(Some(security_groups.to_vec()))
         .send()
         .await?;
 
     Ok(OpExecResponse {
         outputs: None,
         friendly_message: Some(format!(
-            "Updated security groups for mount target {mount_target_id}"
+            "Updated security groups for EFS mount target {}",
+            mount_target.mount_target_id
         )),
     })
 }
 
-pub async fn delete_mount_target(
-    client: &aws_sdk_efs::Client,
-    mount_target_id: &str,
-) -> anyhow::Result<OpExecResponse> {
-    client
-        .delete_mount_target()
-        .mount_target_id(mount_target_id)
-        .send()
-        .await?;
+/// Waits for the mount target to be gone, so that a file system deleted right after can be.
+pub async fn delete_mount_target(client: &aws_sdk_efs::Client, fs_id: &str, subnet_id: &str) -> anyhow::Result<OpExecResponse> {
+    let mount_target = find_mount_target(client, fs_id, subnet_id)
+        .await?
+        .with_context(|| format!("EFS file system {} has no mount target in subnet {}", fs_id, subnet_id))?;
+    let mount_target_id = mount_target.mount_target_id;
+
+    client.delete_mount_target().mount_target_id(&mount_target_id).send().await?;
+
+    wait_for_status(&format!("EFS mount target {}", mount_target_id), None, || {
+        mount_target_state(client, &mount_target_id)
+    })
+    .await?;
 
     Ok(OpExecResponse {
-        outputs: None,
-        friendly_message: Some(format!("Deleted mount target {mount_target_id}")),
+        outputs: Some(HashMap::from([
+            (String::from("mount_target_id"), None),
+            (String::from("ip_address"), None),
+        ])),
+        friendly_message: Some(format!("Deleted EFS mount target {}", mount_target_id)),
     })
 }
 
-// AccessPoint operations
 pub async fn create_access_point(
     client: &aws_sdk_efs::Client,
+    fs_id: &str,
     access_point: &AccessPoint,
-    file_system_id: &str,
 ) -> anyhow::Result<OpExecResponse> {
-    let mut request = client.create_access_point().file_system_id(file_system_id);
-
-    // Add POSIX user if specified
-    if let Some(posix_user) = &access_point.posix_user {
-        let mut posix_user_builder = aws_sdk_efs::types::PosixUser::builder()
-            .uid(posix_user.uid)
-            .gid(posix_user.gid);
-
-        if let Some(secondary_gids) = &posix_user.secondary_gids {
-            posix_user_builder =
-                posix_user_builder.set_secondary_gids(Some(secondary_gids.clone()));
+    let posix_user = match &access_point.posix_user {
+        Some(posix_user) => Some(
+            aws_sdk_efs::types::PosixUser::builder()
+                .uid(posix_user.uid)
+                .gid(posix_user.gid)
+                .set_secondary_gids(posix_user.secondary_gids.clone())
+                .build()?,
+        ),
+        None => None,
+    };
+
+    let root_directory = match &access_point.root_directory {
+        Some(root_directory) => {
+            let creation_info = match &root_directory.creation_info {
+                Some(creation_info) => Some(
+                    aws_sdk_efs::types::CreationInfo::builder()
+                        .owner_uid(creation_info.owner_uid)
+                        .owner_gid(creation_info.owner_gid)
+                        .permissions(&creation_info.permissions)
+                        .build()?,
+                ),
+                None => None,
+            };
+
+            Some(
+                aws_sdk_efs::types::RootDirectory::builder()
+                    .set_path(root_directory.path.clone())
+                    .set_creation_info(creation_info)
+                    .build(),
+            )
         }
+        None => None,
+    };
+
+    let resp = client
+        .create_access_point()
+        .client_token(uuid::Uuid::new_v4().to_string())
+        .file_system_id(fs_id)
+        .set_posix_user(posix_user)
+        .set_root_directory(root_directory)
+        .set_tags(Some(access_point.tags.to_vec()?))
+        .send()
+        .await?;
 
-        request = request.posix_user(posix_user_builder.build()?);
-    }
-
-    // Add root directory if specified
-    if let Some(root_dir) = &access_point.root_directory {
-        let mut root_dir_builder = aws_sdk_efs::types::RootDirectory::builder();
-
-        if let Some(path) = &root_dir.path {
-            root_dir_builder = root_dir_builder.path(path);
-        }
-
-        if let Some(creation_info) = &root_dir.creation_info {
-            root_dir_builder = root_dir_builder.creation_info(
-                aws_sdk_efs::types::CreationInfo::builder()
-                    .owner_uid(creation_info.owner_uid)
-                    .owner_gid(creation_info.owner_gid)
-                    .permissions(&creation_info.permissions)
-                    .build()?,
-            );
-        }
-
-        request = request.root_directory(root_dir_builder.build());
-    }
-
-    // Add tags
-    let aws_tags: Vec<Tag> = access_point.tags.to_vec()?;
-    if !aws_tags.is_empty() {
-        request = request.set_tags(Some(aws_tags));
-    }
-
-    let response = request.send().await?;
+    let ap_id = resp
+        .access_point_id()
+        .context("CreateAccessPoint returned no access point ID")?
+        .to_string();
 
-    let mut outputs = HashMap::new();
-    if let Some(ap_id) = response.access_point_id() {
-        outputs.insert(String::from("access_point_id"), Some(ap_id.to_string()));
-    }
+    wait_for_status(
+        &format!("EFS access point {}", ap_id),
+        Some(LifeCycleState::Available),
+        || access_point_state(client, &ap_id),
+    )
+    .await?;
 
     Ok(OpExecResponse {
-        outputs: Some(outputs),
-        friendly_message: Some(format!(
-            "Created access point with ID: {}",
-            response.access_point_id().unwrap_or("unknown")
-        )),
+        outputs: Some(HashMap::from([
+            (String::from("access_point_id"), Some(ap_id.clone())),
+            (String::from("access_point_arn"), resp.access_point_arn().map(String::from)),
+        ])),
+        friendly_message: Some(format!("Created EFS access point {} for file system {}", ap_id, fs_id)),
     })
 }
 
 pub async fn update_access_point_tags(
     client: &aws_sdk_efs::Client,
-    access_point_id: &str,
+    ap_id: &str,
     old_tags: &Tags,
     new_tags: &Tags,
 ) -> anyhow::Result<OpExecResponse> {
-    // Calculate tag differences
-    let (remove_tags, add_tags) = tag_diff(old_tags, new_tags)?;
+    update_tags(client, ap_id, old_tags, new_tags).await?;
 
-    // Remove tags if any need to be removed
-    if !remove_tags.is_empty() {
-        client
-            .untag_resource()
-            .resource_id(access_point_id)
-            .set_tag_keys(Some(remove_tags))
-            .send()
-            .await?;
-    }
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for EFS access point {}", ap_id)),
+    })
+}
 
-    // Add new tags if any need to be added
-    if !add_tags.is_empty() {
-        client
-            .tag_resource()
-            .resource_id(access_point_id)
-            .set_tags(Some(add_tags))
-            .send()
-            .await?;
-    }
+pub async fn delete_access_point(client: &aws_sdk_efs::Client, ap_id: &str) -> anyhow::Result<OpExecResponse> {
+    client.delete_access_point().access_point_id(ap_id).send().await?;
 
     Ok(OpExecResponse {
-        outputs: None,
-        friendly_message: Some(format!("Updated tags for access point {access_point_id}")),
+        outputs: Some(HashMap::from([
+            (String::from("access_point_id"), None),
+            (String::from("access_point_arn"), None),
+        ])),
+        friendly_message: Some(format!("Deleted EFS access point {}", ap_id)),
     })
 }
 
-pub async fn delete_access_point(
+pub async fn put_file_system_policy(
     client: &aws_sdk_efs::Client,
-    access_point_id: &str,
+    fs_id: &str,
+    policy_document: &ron::Value,
 ) -> anyhow::Result<OpExecResponse> {
+    let policy_json = serde_json::to_string(policy_document).context("Failed to serialize file system policy as JSON")?;
+
     client
-        .delete_access_point()
-        .access_point_id(access_point_id)
+        .put_file_system_policy()
+        .file_system_id(fs_id)
+        .policy(policy_json)
         .send()
         .await?;
 
     Ok(OpExecResponse {
         outputs: None,
-        friendly_message: Some(format!("Deleted access point {access_point_id}")),
+        friendly_message: Some(format!("Set file system policy for EFS file system {}", fs_id)),
+    })
+}
+
+pub async fn delete_file_system_policy(client: &aws_sdk_efs::Client, fs_id: &str) -> anyhow::Result<OpExecResponse> {
+    client.delete_file_system_policy().file_system_id(fs_id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Deleted file system policy for EFS file system {}", fs_id)),
     })
 }
@@ -1,95 +1,133 @@
-use autoschematic_core::{
-    connector::{Resource, ResourceAddress},
-    util::RON,
-};
+use autoschematic_core::connector::{Resource, ResourceAddress};
 use serde::{Deserialize, Serialize};
 
+use autoschematic_core::util::RON;
+
 use super::{addr::EfsResourceAddress, tags::Tags};
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+fn default_performance_mode() -> String {
+    String::from("generalPurpose")
+}
+
+fn default_throughput_mode() -> String {
+    String::from("bursting")
+}
+
+fn default_encrypted() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
 pub struct FileSystem {
-    pub throughput_mode: String,                      // "bursting", "provisioned", or "elastic"
-    pub provisioned_throughput_in_mibps: Option<f64>, // Only required if throughput_mode is "provisioned"
-    pub performance_mode: String,                     // "generalPurpose" or "maxIO"
+    /// generalPurpose or maxIO. Can't be changed after creation.
+    #[serde(default = "default_performance_mode")]
+    pub performance_mode: String,
+    /// bursting, provisioned or elastic.
+    #[serde(default = "default_throughput_mode")]
+    pub throughput_mode: String,
+    /// Required for provisioned throughput, and must be left unset otherwise.
+    pub provisioned_throughput_in_mibps: Option<f64>,
+    /// Can't be changed after creation.
+    #[serde(default = "default_encrypted")]
     pub encrypted: bool,
+    /// The KMS key to encrypt with. If None, encrypted file systems use the `aws/elasticfilesystem` key.
+    /// Can't be changed after creation.
     pub kms_key_id: Option<String>,
-    pub availability_zone_name: Option<String>, // For One Zone file systems
+    /// Set to create a One Zone file system in this availability zone. Can't be changed after creation.
+    pub availability_zone_name: Option<String>,
+    /// When files move between storage classes. Transitions that aren't listed are disabled.
+    #[serde(default)]
     pub lifecycle_policies: Vec<LifecyclePolicy>,
     pub file_system_protection: Option<FileSystemProtection>,
     pub tags: Tags,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+/// A single transition: exactly one field must be set, as in PutLifecycleConfiguration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
 pub struct LifecyclePolicy {
-    pub transition_to_ia: Option<String>, // "AFTER_7_DAYS", "AFTER_14_DAYS", "AFTER_30_DAYS", "AFTER_60_DAYS", "AFTER_90_DAYS"
-    pub transition_to_primary_storage_class: Option<String>, // "AFTER_1_ACCESS"
+    /// e.g. AFTER_30_DAYS
+    pub transition_to_ia: Option<String>,
+    /// e.g. AFTER_90_DAYS. Only supported with elastic throughput.
+    pub transition_to_archive: Option<String>,
+    /// AFTER_1_ACCESS moves files back to Standard storage when they're next accessed.
+    pub transition_to_primary_storage_class: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
 pub struct FileSystemProtection {
-    pub replication_overwrite_protection: Option<String>, // "ENABLED" or "DISABLED"
+    /// ENABLED or DISABLED. While enabled, the file system can't be used as a replication destination.
+    pub replication_overwrite_protection: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
 pub struct MountTarget {
-    pub subnet_id: String,
     pub security_groups: Vec<String>,
-    pub ip_address: Option<String>, // If not provided, AWS assigns automatically
-    pub file_system_id: String,
+    /// If None, EFS picks an address in the subnet. Changing it replaces the mount target.
+    pub ip_address: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+/// Access points can't be modified after creation, except for their tags:
+/// changing `posix_user` or `root_directory` replaces the access point.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
 pub struct AccessPoint {
-    pub file_system_id: String,
+    /// If set, every request through the access point is made as this user.
     pub posix_user: Option<PosixUser>,
     pub root_directory: Option<RootDirectory>,
     pub tags: Tags,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
 pub struct PosixUser {
-    pub uid: i64,                         // User ID
-    pub gid: i64,                         // Group ID
-    pub secondary_gids: Option<Vec<i64>>, // Secondary group IDs
+    pub uid: i64,
+    pub gid: i64,
+    pub secondary_gids: Option<Vec<i64>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
 pub struct RootDirectory {
-    pub path: Option<String>, // Path to the directory, defaults to "/"
+    /// Defaults to "/".
+    pub path: Option<String>,
+    /// If set, EFS creates `path` with this owner and these permissions when it doesn't exist yet.
     pub creation_info: Option<CreationInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
 pub struct CreationInfo {
-    pub owner_uid:   i64,    // POSIX user ID to own the directory
-    pub owner_gid:   i64,    // POSIX group ID to own the directory
-    pub permissions: String, // POSIX permissions in octal string, e.g. "0755"
+    pub owner_uid:   i64,
+    pub owner_gid:   i64,
+    /// In octal, e.g. "0755".
+    pub permissions: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FileSystemPolicy {
+    pub policy_document: ron::Value,
 }
 
 pub enum EfsResource {
     FileSystem(FileSystem),
     MountTarget(MountTarget),
     AccessPoint(AccessPoint),
+    FileSystemPolicy(FileSystemPolicy),
 }
 
-// Implement the Resource trait
 impl Resource for EfsResource {
     fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
         let pretty_config = autoschematic_core::util::PrettyConfig::default().struct_names(true);
-
         match self {
-            EfsResource::FileSystem(fs) => match RON.to_string_pretty(&fs, pretty_config) {
-                Ok(s) => Ok(s.into()),
-                Err(e) => Err(e.into()),
-            },
-            EfsResource::MountTarget(mt) => match RON.to_string_pretty(&mt, pretty_config) {
-                Ok(s) => Ok(s.into()),
-                Err(e) => Err(e.into()),
-            },
-            EfsResource::AccessPoint(ap) => match RON.to_string_pretty(&ap, pretty_config) {
-                Ok(s) => Ok(s.into()),
-                Err(e) => Err(e.into()),
-            },
+            EfsResource::FileSystem(fs) => Ok(RON.to_string_pretty(&fs, pretty_config)?.into()),
+            EfsResource::MountTarget(mt) => Ok(RON.to_string_pretty(&mt, pretty_config)?.into()),
+            EfsResource::AccessPoint(ap) => Ok(RON.to_string_pretty(&ap, pretty_config)?.into()),
+            EfsResource::FileSystemPolicy(policy) => Ok(RON.to_string_pretty(&policy, pretty_config)?.into()),
         }
     }
 
@@ -97,14 +135,15 @@ impl Resource for EfsResource {
     where
         Self: Sized,
     {
-        let addr_option = EfsResourceAddress::from_path(&addr.to_path_buf())?;
+        let addr = EfsResourceAddress::from_path(&addr.to_path_buf())?;
 
         let s = str::from_utf8(s)?;
 
-        match addr_option {
-            EfsResourceAddress::FileSystem(_region, _fs_id) => Ok(EfsResource::FileSystem(RON.from_str(s)?)),
-            EfsResourceAddress::MountTarget(_region, _fs_id, _mt_id) => Ok(EfsResource::MountTarget(RON.from_str(s)?)),
-            EfsResourceAddress::AccessPoint(_region, _fs_id, _ap_id) => Ok(EfsResource::AccessPoint(RON.from_str(s)?)),
+        match addr {
+            EfsResourceAddress::FileSystem { .. } => Ok(EfsResource::FileSystem(RON.from_str(s)?)),
+            EfsResourceAddress::MountTarget { .. } => Ok(EfsResource::MountTarget(RON.from_str(s)?)),
+            EfsResourceAddress::AccessPoint { .. } => Ok(EfsResource::AccessPoint(RON.from_str(s)?)),
+            EfsResourceAddress::FileSystemPolicy { .. } => Ok(EfsResource::FileSystemPolicy(RON.from_str(s)?)),
         }
     }
 }
@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use autoschematic_core::connector::ResourceAddress;
+use aws_sdk_efs::types::{
+    LifecyclePolicy as SdkLifecyclePolicy, TransitionToArchiveRules, TransitionToIaRules, TransitionToPrimaryStorageClassRules,
+};
+
+use crate::{addr::EfsResourceAddress, resource::LifecyclePolicy};
+
+pub fn get_phy_file_system_id(prefix: &Path, region: &str, virt_fs_id: &str) -> anyhow::Result<Option<String>> {
+    let addr = EfsResourceAddress::FileSystem {
+        region: region.to_string(),
+        fs_id:  virt_fs_id.to_string(),
+    };
+
+    addr.get_output(prefix, "file_system_id")
+}
+
+pub fn get_phy_access_point_id(
+    prefix: &Path,
+    region: &str,
+    virt_fs_id: &str,
+    virt_ap_id: &str,
+) -> anyhow::Result<Option<String>> {
+    let addr = EfsResourceAddress::AccessPoint {
+        region: region.to_string(),
+        fs_id:  virt_fs_id.to_string(),
+        ap_id:  virt_ap_id.to_string(),
+    };
+
+    addr.get_output(prefix, "access_point_id")
+}
+
+/// PutLifecycleConfiguration takes one policy per transition. An empty list turns every transition off.
+pub fn to_lifecycle_policies(lifecycle_policies: &[LifecyclePolicy]) -> Vec<SdkLifecyclePolicy> {
+    lifecycle_policies
+        .iter()
+        .map(|policy| {
+            SdkLifecyclePolicy::builder()
+                .set_transition_to_ia(policy.transition_to_ia.as_deref().map(TransitionToIaRules::from))
+                .set_transition_to_archive(policy.transition_to_archive.as_deref().map(TransitionToArchiveRules::from))
+                .set_transition_to_primary_storage_class(
+                    policy
+                        .transition_to_primary_storage_class
+                        .as_deref()
+                        .map(TransitionToPrimaryStorageClassRules::from),
+                )
+                .build()
+        })
+        .collect()
+}
+
+pub fn from_lifecycle_policies(policies: &[SdkLifecyclePolicy]) -> Vec<LifecyclePolicy> {
+    policies
+        .iter()
+        .map(|policy| LifecyclePolicy {
+            transition_to_ia: policy.transition_to_ia.as_ref().map(|t| t.as_str().to_string()),
+            transition_to_archive: policy.transition_to_archive.as_ref().map(|t| t.as_str().to_string()),
+            transition_to_primary_storage_class: policy
+                .transition_to_primary_storage_class
+                .as_ref()
+                .map(|t| t.as_str().to_string()),
+        })
+        .collect()
+}
@@ -0,0 +1,132 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum ElastiCacheResourceAddress {
+    /// A Redis or Valkey replication group, with or without cluster mode.
+    ReplicationGroup { region: String, replication_group_id: String },
+    /// A Memcached cluster. Redis and Valkey nodes are managed through their replication group instead.
+    CacheCluster { region: String, cache_cluster_id: String },
+    ParameterGroup { region: String, name: String },
+    SubnetGroup { region: String, name: String },
+    /// A user for Redis AUTH (role-based access control).
+    User { region: String, user_id: String },
+    UserGroup { region: String, user_group_id: String },
+}
+
+impl ResourceAddress for ElastiCacheResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            ElastiCacheResourceAddress::ReplicationGroup {
+                region,
+                replication_group_id,
+            } => PathBuf::from(format!("aws/elasticache/{region}/replication_groups/{replication_group_id}.ron")),
+            ElastiCacheResourceAddress::CacheCluster { region, cache_cluster_id } => {
+                PathBuf::from(format!("aws/elasticache/{region}/clusters/{cache_cluster_id}.ron"))
+            }
+            ElastiCacheResourceAddress::ParameterGroup { region, name } => {
+                PathBuf::from(format!("aws/elasticache/{region}/parameter_groups/{name}.ron"))
+            }
+            ElastiCacheResourceAddress::SubnetGroup { region, name } => {
+                PathBuf::from(format!("aws/elasticache/{region}/subnet_groups/{name}.ron"))
+            }
+            ElastiCacheResourceAddress::User { region, user_id } => {
+                PathBuf::from(format!("aws/elasticache/{region}/users/{user_id}.ron"))
+            }
+            ElastiCacheResourceAddress::UserGroup { region, user_group_id } => {
+                PathBuf::from(format!("aws/elasticache/{region}/user_groups/{user_group_id}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "elasticache", region, "replication_groups", replication_group_id]
+                if replication_group_id.ends_with(".ron") =>
+            {
+                let replication_group_id = replication_group_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(ElastiCacheResourceAddress::ReplicationGroup {
+                    region: region.to_string(),
+                    replication_group_id,
+                })
+            }
+            ["aws", "elasticache", region, "clusters", cache_cluster_id] if cache_cluster_id.ends_with(".ron") => {
+                let cache_cluster_id = cache_cluster_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(ElastiCacheResourceAddress::CacheCluster {
+                    region: region.to_string(),
+                    cache_cluster_id,
+                })
+            }
+            ["aws", "elasticache", region, "parameter_groups", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(ElastiCacheResourceAddress::ParameterGroup {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "elasticache", region, "subnet_groups", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(ElastiCacheResourceAddress::SubnetGroup {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "elasticache", region, "users", user_id] if user_id.ends_with(".ron") => {
+                let user_id = user_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(ElastiCacheResourceAddress::User {
+                    region: region.to_string(),
+                    user_id,
+                })
+            }
+            ["aws", "elasticache", region, "user_groups", user_group_id] if user_group_id.ends_with(".ron") => {
+                let user_group_id = user_group_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(ElastiCacheResourceAddress::UserGroup {
+                    region: region.to_string(),
+                    user_group_id,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for ElastiCacheResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/elasticache/<region>/replication_groups/<replication_group_id>.ron",
+                description: "A Redis or Valkey replication group",
+                example:     "aws/elasticache/us-east-1/replication_groups/sessions.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/elasticache/<region>/clusters/<cache_cluster_id>.ron",
+                description: "A Memcached cluster",
+                example:     "aws/elasticache/us-east-1/clusters/page-cache.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/elasticache/<region>/parameter_groups/<name>.ron",
+                description: "A cache parameter group",
+                example:     "aws/elasticache/us-east-1/parameter_groups/valkey8-lru.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/elasticache/<region>/subnet_groups/<name>.ron",
+                description: "A cache subnet group",
+                example:     "aws/elasticache/us-east-1/subnet_groups/private.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/elasticache/<region>/users/<user_id>.ron",
+                description: "A user for Redis AUTH",
+                example:     "aws/elasticache/us-east-1/users/app-readwrite.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/elasticache/<region>/user_groups/<user_group_id>.ron",
+                description: "A group of users that can be attached to replication groups",
+                example:     "aws/elasticache/us-east-1/user_groups/app.ron",
+            },
+        ]
+    }
+}
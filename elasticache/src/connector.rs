@@ -0,0 +1,302 @@
+pub use crate::addr::ElastiCacheResourceAddress;
+pub use crate::op::ElastiCacheConnectorOp;
+pub use crate::resource::ElastiCacheResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::ElastiCacheConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{CacheCluster, ParameterGroup, ReplicationGroup, SubnetGroup, User, UserAuthentication, UserGroup};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct ElastiCacheConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_elasticache::Client>>>,
+    secrets_client_cache: Mutex<HashMap<String, Arc<aws_sdk_secretsmanager::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<ElastiCacheConnectorConfig>,
+    prefix: PathBuf,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl ElastiCacheConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_elasticache::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_elasticache, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+    /// Passwords and AUTH tokens are read from Secrets Manager in the resource's region.
+    pub async fn get_or_init_secrets_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_secretsmanager::Client>> {
+        let mut cache = self.secrets_client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_secretsmanager, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get Secrets Manager client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for ElastiCacheConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = ElastiCacheResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(ElastiCacheConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let elasticache_config: ElastiCacheConnectorConfig = ElastiCacheConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = elasticache_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.secrets_client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(elasticache_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = elasticache_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // Replication group skeleton: a Valkey primary with one replica, using RBAC
+        res.push(skeleton!(
+            ElastiCacheResourceAddress::ReplicationGroup {
+                region: String::from("[region]"),
+                replication_group_id: String::from("[replication_group_id]"),
+            },
+            ElastiCacheResource::ReplicationGroup(ReplicationGroup {
+                description: String::from("[description]"),
+                engine: String::from("valkey"),
+                engine_version: None,
+                cache_node_type: String::from("cache.t4g.micro"),
+                cluster_mode: Some(String::from("disabled")),
+                num_node_groups: 1,
+                replicas_per_node_group: 1,
+                automatic_failover_enabled: true,
+                multi_az_enabled: true,
+                cache_parameter_group_name: None,
+                cache_subnet_group_name: Some(String::from("[subnet_group_name]")),
+                security_group_ids: vec![String::from("[security_group_id]")],
+                port: None,
+                at_rest_encryption_enabled: true,
+                kms_key_id: None,
+                transit_encryption_enabled: true,
+                auth_token_secret_id: None,
+                user_group_ids: vec![String::from("[user_group_id]")],
+                snapshot_retention_limit: Some(7),
+                snapshot_window: None,
+                preferred_maintenance_window: None,
+                auto_minor_version_upgrade: true,
+                notification_topic_arn: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        // Memcached cluster skeleton
+        res.push(skeleton!(
+            ElastiCacheResourceAddress::CacheCluster {
+                region: String::from("[region]"),
+                cache_cluster_id: String::from("[cache_cluster_id]"),
+            },
+            ElastiCacheResource::CacheCluster(CacheCluster {
+                engine_version: None,
+                cache_node_type: String::from("cache.t4g.micro"),
+                num_cache_nodes: 2,
+                az_mode: Some(String::from("cross-az")),
+                cache_parameter_group_name: None,
+                cache_subnet_group_name: Some(String::from("[subnet_group_name]")),
+                security_group_ids: vec![String::from("[security_group_id]")],
+                port: None,
+                transit_encryption_enabled: false,
+                preferred_maintenance_window: None,
+                auto_minor_version_upgrade: true,
+                notification_topic_arn: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        // Parameter group skeleton
+        res.push(skeleton!(
+            ElastiCacheResourceAddress::ParameterGroup {
+                region: String::from("[region]"),
+                name:   String::from("[parameter_group_name]"),
+            },
+            ElastiCacheResource::ParameterGroup(ParameterGroup {
+                family: String::from("valkey8"),
+                description: String::from("[description]"),
+                parameters: BTreeMap::from([(String::from("maxmemory-policy"), String::from("allkeys-lru"))]),
+                tags: Tags::default(),
+            })
+        ));
+
+        // Subnet group skeleton
+        res.push(skeleton!(
+            ElastiCacheResourceAddress::SubnetGroup {
+                region: String::from("[region]"),
+                name:   String::from("[subnet_group_name]"),
+            },
+            ElastiCacheResource::SubnetGroup(SubnetGroup {
+                description: String::from("[description]"),
+                subnet_ids:  vec![String::from("[subnet_id_a]"), String::from("[subnet_id_b]")],
+                tags:        Tags::default(),
+            })
+        ));
+
+        // User skeleton, with a password read from Secrets Manager
+        res.push(skeleton!(
+            ElastiCacheResourceAddress::User {
+                region:  String::from("[region]"),
+                user_id: String::from("[user_id]"),
+            },
+            ElastiCacheResource::User(User {
+                user_name: String::from("[user_name]"),
+                engine: String::from("valkey"),
+                access_string: String::from("on ~* +@all -@dangerous"),
+                authentication: UserAuthentication::Password {
+                    secret_ids: vec![String::from("[secret_id]")],
+                },
+                tags: Tags::default(),
+            })
+        ));
+
+        // User group skeleton. Every user group needs a user named "default".
+        res.push(skeleton!(
+            ElastiCacheResourceAddress::UserGroup {
+                region: String::from("[region]"),
+                user_group_id: String::from("[user_group_id]"),
+            },
+            ElastiCacheResource::UserGroup(UserGroup {
+                engine:   String::from("valkey"),
+                user_ids: vec![String::from("[default_user_id]"), String::from("[user_id]")],
+                tags:     Tags::default(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = ElastiCacheResourceAddress::from_path(addr)?;
+
+        match addr {
+            ElastiCacheResourceAddress::ReplicationGroup { .. } => ron_check_eq::<ReplicationGroup>(a, b),
+            ElastiCacheResourceAddress::CacheCluster { .. } => ron_check_eq::<CacheCluster>(a, b),
+            ElastiCacheResourceAddress::ParameterGroup { .. } => ron_check_eq::<ParameterGroup>(a, b),
+            ElastiCacheResourceAddress::SubnetGroup { .. } => ron_check_eq::<SubnetGroup>(a, b),
+            ElastiCacheResourceAddress::User { .. } => ron_check_eq::<User>(a, b),
+            ElastiCacheResourceAddress::UserGroup { .. } => ron_check_eq::<UserGroup>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = ElastiCacheResourceAddress::from_path(addr)?;
+
+        match addr {
+            ElastiCacheResourceAddress::ReplicationGroup { .. } => ron_check_syntax::<ReplicationGroup>(a),
+            ElastiCacheResourceAddress::CacheCluster { .. } => ron_check_syntax::<CacheCluster>(a),
+            ElastiCacheResourceAddress::ParameterGroup { .. } => ron_check_syntax::<ParameterGroup>(a),
+            ElastiCacheResourceAddress::SubnetGroup { .. } => ron_check_syntax::<SubnetGroup>(a),
+            ElastiCacheResourceAddress::User { .. } => ron_check_syntax::<User>(a),
+            ElastiCacheResourceAddress::UserGroup { .. } => ron_check_syntax::<UserGroup>(a),
+        }
+    }
+}
@@ -0,0 +1,405 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_elasticache::types::{AuthenticationType, AutomaticFailoverStatus, MultiAzStatus};
+
+use crate::{
+    addr::ElastiCacheResourceAddress,
+    resource::{
+        CacheCluster, ElastiCacheResource, ParameterGroup, ReplicationGroup, SubnetGroup, User, UserAuthentication,
+        UserGroup,
+    },
+    tags::Tags,
+    util::{
+        DEFAULT_MEMCACHED_PORT, DEFAULT_REDIS_PORT, UNKNOWN_SECRET_ID, cache_cluster_endpoint, elasticache_arn,
+        join_secret_ids, non_default_parameter_group, replication_group_endpoint, split_secret_ids,
+    },
+};
+
+use super::ElastiCacheConnector;
+
+impl ElastiCacheConnector {
+    async fn get_tags(&self, client: &aws_sdk_elasticache::Client, arn: &str) -> anyhow::Result<Tags> {
+        let resp = client.list_tags_for_resource().resource_name(arn).send().await?;
+        Ok(resp.tag_list.into())
+    }
+
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = ElastiCacheResourceAddress::from_path(addr)?;
+
+        let account_id = self.account_id.lock().await.clone();
+
+        match &addr {
+            ElastiCacheResourceAddress::ReplicationGroup {
+                region,
+                replication_group_id,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let replication_group = match client
+                    .describe_replication_groups()
+                    .replication_group_id(replication_group_id)
+                    .send()
+                    .await
+                {
+                    Ok(resp) => match resp.replication_groups.and_then(|rgs| rgs.into_iter().next()) {
+                        Some(replication_group) => replication_group,
+                        None => return Ok(None),
+                    },
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_replication_group_not_found_fault()) => {
+                        return Ok(None);
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+
+                // Engine version, parameter group, networking and maintenance settings apply to every node,
+                // but are only reported on the nodes themselves
+                let member = match replication_group.member_clusters().first() {
+                    Some(cache_cluster_id) => client
+                        .describe_cache_clusters()
+                        .cache_cluster_id(cache_cluster_id)
+                        .send()
+                        .await?
+                        .cache_clusters
+                        .and_then(|cs| cs.into_iter().next()),
+                    None => None,
+                };
+                let member = member.as_ref();
+
+                let port = replication_group
+                    .configuration_endpoint()
+                    .or_else(|| replication_group.node_groups().first().and_then(|ng| ng.primary_endpoint()))
+                    .and_then(|e| e.port())
+                    .filter(|port| *port != DEFAULT_REDIS_PORT);
+
+                let mut security_group_ids: Vec<String> = member
+                    .map(|m| m.security_groups().iter().filter_map(|sg| sg.security_group_id.clone()).collect())
+                    .unwrap_or_default();
+                security_group_ids.sort();
+
+                // The token itself can't be read back, only whether there is one
+                let auth_token_secret_id = if replication_group.auth_token_enabled == Some(true) {
+                    Some(
+                        addr.get_output(&self.prefix, "auth_token_secret_id")?
+                            .unwrap_or_else(|| String::from(UNKNOWN_SECRET_ID)),
+                    )
+                } else {
+                    None
+                };
+
+                let arn = replication_group.arn.clone().unwrap_or_else(|| {
+                    elasticache_arn(region, &account_id, "replicationgroup", replication_group_id)
+                });
+
+                let resource = ReplicationGroup {
+                    description: replication_group.description.clone().unwrap_or_default(),
+                    engine: replication_group.engine.clone().unwrap_or_default(),
+                    engine_version: member.and_then(|m| m.engine_version.clone()),
+                    cache_node_type: replication_group.cache_node_type.clone().unwrap_or_default(),
+                    cluster_mode: replication_group.cluster_mode.as_ref().map(|m| m.as_str().to_string()),
+                    num_node_groups: replication_group.node_groups().len() as i32,
+                    replicas_per_node_group: replication_group
+                        .node_groups()
+                        .first()
+                        .map(|ng| ng.node_group_members().len() as i32 - 1)
+                        .unwrap_or_default(),
+                    automatic_failover_enabled: replication_group.automatic_failover == Some(AutomaticFailoverStatus::Enabled),
+                    multi_az_enabled: replication_group.multi_az == Some(MultiAzStatus::Enabled),
+                    cache_parameter_group_name: non_default_parameter_group(
+                        member
+                            .and_then(|m| m.cache_parameter_group())
+                            .and_then(|pg| pg.cache_parameter_group_name()),
+                    ),
+                    cache_subnet_group_name: member.and_then(|m| m.cache_subnet_group_name.clone()),
+                    security_group_ids,
+                    port,
+                    at_rest_encryption_enabled: replication_group.at_rest_encryption_enabled.unwrap_or(false),
+                    kms_key_id: replication_group.kms_key_id.clone(),
+                    transit_encryption_enabled: replication_group.transit_encryption_enabled.unwrap_or(false),
+                    auth_token_secret_id,
+                    user_group_ids: replication_group.user_group_ids().to_vec(),
+                    snapshot_retention_limit: replication_group.snapshot_retention_limit.filter(|limit| *limit > 0),
+                    snapshot_window: replication_group.snapshot_window.clone(),
+                    preferred_maintenance_window: member.and_then(|m| m.preferred_maintenance_window.clone()),
+                    auto_minor_version_upgrade: replication_group.auto_minor_version_upgrade.unwrap_or(true),
+                    notification_topic_arn: member
+                        .and_then(|m| m.notification_configuration())
+                        .filter(|n| n.topic_status() == Some("active"))
+                        .and_then(|n| n.topic_arn.clone()),
+                    tags: self.get_tags(&client, &arn).await?,
+                };
+
+                let mut outputs = HashMap::from([(String::from("replication_group_arn"), arn)]);
+                if let Some(endpoint) = replication_group_endpoint(&replication_group) {
+                    outputs.insert(String::from("endpoint"), endpoint);
+                }
+                if let Some(auth_token_secret_id) = &resource.auth_token_secret_id {
+                    outputs.insert(String::from("auth_token_secret_id"), auth_token_secret_id.clone());
+                }
+
+                Ok(Some(GetResourceResponse {
+                    resource_definition: ElastiCacheResource::ReplicationGroup(resource).to_bytes()?,
+                    virt_addr: None,
+                    outputs: Some(outputs),
+                }))
+            }
+            ElastiCacheResourceAddress::CacheCluster { region, cache_cluster_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let cache_cluster = match client
+                    .describe_cache_clusters()
+                    .cache_cluster_id(cache_cluster_id)
+                    .show_cache_node_info(true)
+                    .send()
+                    .await
+                {
+                    Ok(resp) => match resp.cache_clusters.and_then(|cs| cs.into_iter().next()) {
+                        Some(cache_cluster) => cache_cluster,
+                        None => return Ok(None),
+                    },
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_cache_cluster_not_found_fault()) => return Ok(None),
+                    Err(e) => return Err(e.into()),
+                };
+
+                // Redis and Valkey nodes belong to a replication group
+                if cache_cluster.engine() != Some("memcached") {
+                    return Ok(None);
+                }
+
+                let mut security_group_ids: Vec<String> = cache_cluster
+                    .security_groups()
+                    .iter()
+                    .filter_map(|sg| sg.security_group_id.clone())
+                    .collect();
+                security_group_ids.sort();
+
+                let arn = cache_cluster
+                    .arn
+                    .clone()
+                    .unwrap_or_else(|| elasticache_arn(region, &account_id, "cluster", cache_cluster_id));
+
+                let resource = CacheCluster {
+                    engine_version: cache_cluster.engine_version.clone(),
+                    cache_node_type: cache_cluster.cache_node_type.clone().unwrap_or_default(),
+                    num_cache_nodes: cache_cluster.num_cache_nodes.unwrap_or_default(),
+                    // A cluster spread across zones reports its zone as "Multiple"
+                    az_mode: (cache_cluster.preferred_availability_zone() == Some("Multiple")).then(|| String::from("cross-az")),
+                    cache_parameter_group_name: non_default_parameter_group(
+                        cache_cluster
+                            .cache_parameter_group()
+                            .and_then(|pg| pg.cache_parameter_group_name()),
+                    ),
+                    cache_subnet_group_name: cache_cluster.cache_subnet_group_name.clone(),
+                    security_group_ids,
+                    port: cache_cluster
+                        .configuration_endpoint()
+                        .and_then(|e| e.port())
+                        .filter(|port| *port != DEFAULT_MEMCACHED_PORT),
+                    transit_encryption_enabled: cache_cluster.transit_encryption_enabled.unwrap_or(false),
+                    preferred_maintenance_window: cache_cluster.preferred_maintenance_window.clone(),
+                    auto_minor_version_upgrade: cache_cluster.auto_minor_version_upgrade.unwrap_or(true),
+                    notification_topic_arn: cache_cluster
+                        .notification_configuration()
+                        .filter(|n| n.topic_status() == Some("active"))
+                        .and_then(|n| n.topic_arn.clone()),
+                    tags: self.get_tags(&client, &arn).await?,
+                };
+
+                let mut outputs = HashMap::from([(String::from("cache_cluster_arn"), arn)]);
+                if let Some(endpoint) = cache_cluster_endpoint(&cache_cluster) {
+                    outputs.insert(String::from("endpoint"), endpoint);
+                }
+
+                Ok(Some(GetResourceResponse {
+                    resource_definition: ElastiCacheResource::CacheCluster(resource).to_bytes()?,
+                    virt_addr: None,
+                    outputs: Some(outputs),
+                }))
+            }
+            ElastiCacheResourceAddress::ParameterGroup { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let parameter_group = match client
+                    .describe_cache_parameter_groups()
+                    .cache_parameter_group_name(name)
+                    .send()
+                    .await
+                {
+                    Ok(resp) => match resp.cache_parameter_groups.and_then(|pgs| pgs.into_iter().next()) {
+                        Some(parameter_group) => parameter_group,
+                        None => return Ok(None),
+                    },
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_cache_parameter_group_not_found_fault()) => {
+                        return Ok(None);
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+
+                // Only the parameters that have been changed from the family's defaults
+                let mut parameters = BTreeMap::new();
+                let mut marker = None;
+                loop {
+                    let resp = client
+                        .describe_cache_parameters()
+                        .cache_parameter_group_name(name)
+                        .source("user")
+                        .set_marker(marker)
+                        .send()
+                        .await?;
+                    for parameter in resp.parameters() {
+                        if let (Some(parameter_name), Some(parameter_value)) =
+                            (parameter.parameter_name(), parameter.parameter_value())
+                        {
+                            parameters.insert(parameter_name.to_string(), parameter_value.to_string());
+                        }
+                    }
+                    marker = resp.marker;
+                    if marker.is_none() {
+                        break;
+                    }
+                }
+
+                let arn = parameter_group
+                    .arn
+                    .clone()
+                    .unwrap_or_else(|| elasticache_arn(region, &account_id, "parametergroup", name));
+
+                let resource = ParameterGroup {
+                    family: parameter_group.cache_parameter_group_family.clone().unwrap_or_default(),
+                    description: parameter_group.description.clone().unwrap_or_default(),
+                    parameters,
+                    tags: self.get_tags(&client, &arn).await?,
+                };
+
+                get_resource_response!(
+                    ElastiCacheResource::ParameterGroup(resource),
+                    [(String::from("parameter_group_arn"), arn)]
+                )
+            }
+            ElastiCacheResourceAddress::SubnetGroup { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let subnet_group = match client.describe_cache_subnet_groups().cache_subnet_group_name(name).send().await {
+                    Ok(resp) => match resp.cache_subnet_groups.and_then(|sgs| sgs.into_iter().next()) {
+                        Some(subnet_group) => subnet_group,
+                        None => return Ok(None),
+                    },
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_cache_subnet_group_not_found_fault()) => {
+                        return Ok(None);
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+
+                let mut subnet_ids: Vec<String> = subnet_group
+                    .subnets()
+                    .iter()
+                    .filter_map(|s| s.subnet_identifier.clone())
+                    .collect();
+                subnet_ids.sort();
+
+                let arn = subnet_group
+                    .arn
+                    .clone()
+                    .unwrap_or_else(|| elasticache_arn(region, &account_id, "subnetgroup", name));
+
+                let resource = SubnetGroup {
+                    description: subnet_group.cache_subnet_group_description.clone().unwrap_or_default(),
+                    subnet_ids,
+                    tags: self.get_tags(&client, &arn).await?,
+                };
+
+                get_resource_response!(
+                    ElastiCacheResource::SubnetGroup(resource),
+                    [(String::from("subnet_group_arn"), arn)]
+                )
+            }
+            ElastiCacheResourceAddress::User { region, user_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let user = match client.describe_users().user_id(user_id).send().await {
+                    Ok(resp) => match resp.users.and_then(|us| us.into_iter().next()) {
+                        Some(user) => user,
+                        None => return Ok(None),
+                    },
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_user_not_found_fault()) => return Ok(None),
+                    Err(e) => return Err(e.into()),
+                };
+
+                // Passwords can't be read back, only how many there are
+                let authentication = match user.authentication().and_then(|a| a.r#type()) {
+                    Some(AuthenticationType::Iam) => UserAuthentication::Iam,
+                    Some(AuthenticationType::Password) => {
+                        let password_count = user.authentication().and_then(|a| a.password_count()).unwrap_or_default();
+                        let secret_ids = match addr.get_output(&self.prefix, "password_secret_ids")? {
+                            Some(secret_ids) => split_secret_ids(&secret_ids),
+                            None => vec![String::from(UNKNOWN_SECRET_ID); password_count as usize],
+                        };
+                        UserAuthentication::Password { secret_ids }
+                    }
+                    _ => UserAuthentication::NoPassword,
+                };
+
+                let arn = user
+                    .arn
+                    .clone()
+                    .unwrap_or_else(|| elasticache_arn(region, &account_id, "user", user_id));
+
+                let resource = User {
+                    user_name: user.user_name.clone().unwrap_or_default(),
+                    engine: user.engine.clone().unwrap_or_default(),
+                    access_string: user.access_string.clone().unwrap_or_default(),
+                    authentication: authentication.clone(),
+                    tags: self.get_tags(&client, &arn).await?,
+                };
+
+                let mut outputs = HashMap::from([(String::from("user_arn"), arn)]);
+                if let UserAuthentication::Password { secret_ids } = &authentication {
+                    outputs.insert(String::from("password_secret_ids"), join_secret_ids(secret_ids));
+                }
+
+                Ok(Some(GetResourceResponse {
+                    resource_definition: ElastiCacheResource::User(resource).to_bytes()?,
+                    virt_addr: None,
+                    outputs: Some(outputs),
+                }))
+            }
+            ElastiCacheResourceAddress::UserGroup { region, user_group_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let user_group = match client.describe_user_groups().user_group_id(user_group_id).send().await {
+                    Ok(resp) => match resp.user_groups.and_then(|ugs| ugs.into_iter().next()) {
+                        Some(user_group) => user_group,
+                        None => return Ok(None),
+                    },
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_user_group_not_found_fault()) => return Ok(None),
+                    Err(e) => return Err(e.into()),
+                };
+
+                let mut user_ids = user_group.user_ids().to_vec();
+                user_ids.sort();
+
+                let arn = user_group
+                    .arn
+                    .clone()
+                    .unwrap_or_else(|| elasticache_arn(region, &account_id, "usergroup", user_group_id));
+
+                let resource = UserGroup {
+                    engine: user_group.engine.clone().unwrap_or_default(),
+                    user_ids,
+                    tags: self.get_tags(&client, &arn).await?,
+                };
+
+                get_resource_response!(
+                    ElastiCacheResource::UserGroup(resource),
+                    [(String::from("user_group_arn"), arn)]
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::addr::ElastiCacheResourceAddress;
+
+use super::ElastiCacheConnector;
+
+impl ElastiCacheConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut replication_groups = client.describe_replication_groups().into_paginator().items().send();
+            while let Some(replication_group) = replication_groups.next().await {
+                if let Some(replication_group_id) = replication_group?.replication_group_id {
+                    results.push(
+                        ElastiCacheResourceAddress::ReplicationGroup {
+                            region: region.clone(),
+                            replication_group_id,
+                        }
+                        .to_path_buf(),
+                    );
+                }
+            }
+
+            // Redis and Valkey nodes are listed through their replication group
+            let mut cache_clusters = client.describe_cache_clusters().into_paginator().items().send();
+            while let Some(cache_cluster) = cache_clusters.next().await {
+                let cache_cluster = cache_cluster?;
+                if cache_cluster.engine() != Some("memcached") {
+                    continue;
+                }
+                if let Some(cache_cluster_id) = cache_cluster.cache_cluster_id {
+                    results.push(
+                        ElastiCacheResourceAddress::CacheCluster {
+                            region: region.clone(),
+                            cache_cluster_id,
+                        }
+                        .to_path_buf(),
+                    );
+                }
+            }
+
+            // Default parameter groups can't be changed, and the default subnet group is managed by ElastiCache
+            let mut parameter_groups = client.describe_cache_parameter_groups().into_paginator().items().send();
+            while let Some(parameter_group) = parameter_groups.next().await {
+                if let Some(name) = parameter_group?.cache_parameter_group_name
+                    && !name.starts_with("default.")
+                {
+                    results.push(
+                        ElastiCacheResourceAddress::ParameterGroup {
+                            region: region.clone(),
+                            name,
+                        }
+                        .to_path_buf(),
+                    );
+                }
+            }
+
+            let mut subnet_groups = client.describe_cache_subnet_groups().into_paginator().items().send();
+            while let Some(subnet_group) = subnet_groups.next().await {
+                if let Some(name) = subnet_group?.cache_subnet_group_name
+                    && name != "default"
+                {
+                    results.push(
+                        ElastiCacheResourceAddress::SubnetGroup {
+                            region: region.clone(),
+                            name,
+                        }
+                        .to_path_buf(),
+                    );
+                }
+            }
+
+            // Every account has a built-in "default" user, which can't be changed or deleted
+            let mut users = client.describe_users().into_paginator().items().send();
+            while let Some(user) = users.next().await {
+                if let Some(user_id) = user?.user_id
+                    && user_id != "default"
+                {
+                    results.push(
+                        ElastiCacheResourceAddress::User {
+                            region: region.clone(),
+                            user_id,
+                        }
+                        .to_path_buf(),
+                    );
+                }
+            }
+
+            let mut user_groups = client.describe_user_groups().into_paginator().items().send();
+            while let Some(user_group) = user_groups.next().await {
+                if let Some(user_group_id) = user_group?.user_group_id {
+                    results.push(
+                        ElastiCacheResourceAddress::UserGroup {
+                            region: region.clone(),
+                            user_group_id,
+                        }
+                        .to_path_buf(),
+                    );
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
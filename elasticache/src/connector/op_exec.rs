@@ -0,0 +1,147 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::connector::{ConnectorOp, OpExecResponse, ResourceAddress};
+
+use crate::{addr::ElastiCacheResourceAddress, op::ElastiCacheConnectorOp, op_impl, util::elasticache_arn};
+
+use super::ElastiCacheConnector;
+
+impl ElastiCacheConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = ElastiCacheResourceAddress::from_path(addr)?;
+        let op = ElastiCacheConnectorOp::from_str(op)?;
+
+        let account_id = self.account_id.lock().await.clone();
+
+        match &addr {
+            ElastiCacheResourceAddress::ReplicationGroup {
+                region,
+                replication_group_id,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    ElastiCacheConnectorOp::CreateReplicationGroup(replication_group) => {
+                        let sm_client = self.get_or_init_secrets_client(region).await?;
+                        op_impl::create_replication_group(&client, &sm_client, replication_group_id, &replication_group).await
+                    }
+                    ElastiCacheConnectorOp::ModifyReplicationGroup(old, new) => {
+                        let sm_client = self.get_or_init_secrets_client(region).await?;
+                        op_impl::modify_replication_group(&client, &sm_client, replication_group_id, &old, &new).await
+                    }
+                    ElastiCacheConnectorOp::ModifyNodeGroupCount(node_group_count) => {
+                        op_impl::modify_node_group_count(&client, replication_group_id, node_group_count).await
+                    }
+                    ElastiCacheConnectorOp::ModifyReplicaCount(replica_count) => {
+                        op_impl::modify_replica_count(&client, replication_group_id, replica_count).await
+                    }
+                    ElastiCacheConnectorOp::UpdateReplicationGroupTags(old_tags, new_tags) => {
+                        let arn = elasticache_arn(region, &account_id, "replicationgroup", replication_group_id);
+                        op_impl::update_tags(&client, &arn, &old_tags, &new_tags).await
+                    }
+                    ElastiCacheConnectorOp::DeleteReplicationGroup => {
+                        op_impl::delete_replication_group(&client, replication_group_id).await
+                    }
+                    _ => bail!("Invalid operation for ElastiCache replication group resource"),
+                }
+            }
+            ElastiCacheResourceAddress::CacheCluster { region, cache_cluster_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    ElastiCacheConnectorOp::CreateCacheCluster(cache_cluster) => {
+                        op_impl::create_cache_cluster(&client, cache_cluster_id, &cache_cluster).await
+                    }
+                    ElastiCacheConnectorOp::ModifyCacheCluster(old, new) => {
+                        op_impl::modify_cache_cluster(&client, cache_cluster_id, &old, &new).await
+                    }
+                    ElastiCacheConnectorOp::UpdateCacheClusterTags(old_tags, new_tags) => {
+                        let arn = elasticache_arn(region, &account_id, "cluster", cache_cluster_id);
+                        op_impl::update_tags(&client, &arn, &old_tags, &new_tags).await
+                    }
+                    ElastiCacheConnectorOp::DeleteCacheCluster => op_impl::delete_cache_cluster(&client, cache_cluster_id).await,
+                    _ => bail!("Invalid operation for ElastiCache cache cluster resource"),
+                }
+            }
+            ElastiCacheResourceAddress::ParameterGroup { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    ElastiCacheConnectorOp::CreateParameterGroup(parameter_group) => {
+                        op_impl::create_parameter_group(&client, name, &parameter_group).await
+                    }
+                    ElastiCacheConnectorOp::ModifyParameters { set, reset } => {
+                        op_impl::modify_parameters(&client, name, &set, &reset).await
+                    }
+                    ElastiCacheConnectorOp::UpdateParameterGroupTags(old_tags, new_tags) => {
+                        let arn = elasticache_arn(region, &account_id, "parametergroup", name);
+                        op_impl::update_tags(&client, &arn, &old_tags, &new_tags).await
+                    }
+                    ElastiCacheConnectorOp::DeleteParameterGroup => op_impl::delete_parameter_group(&client, name).await,
+                    _ => bail!("Invalid operation for ElastiCache parameter group resource"),
+                }
+            }
+            ElastiCacheResourceAddress::SubnetGroup { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    ElastiCacheConnectorOp::CreateSubnetGroup(subnet_group) => {
+                        op_impl::create_subnet_group(&client, name, &subnet_group).await
+                    }
+                    ElastiCacheConnectorOp::ModifySubnetGroup(subnet_group) => {
+                        op_impl::modify_subnet_group(&client, name, &subnet_group).await
+                    }
+                    ElastiCacheConnectorOp::UpdateSubnetGroupTags(old_tags, new_tags) => {
+                        let arn = elasticache_arn(region, &account_id, "subnetgroup", name);
+                        op_impl::update_tags(&client, &arn, &old_tags, &new_tags).await
+                    }
+                    ElastiCacheConnectorOp::DeleteSubnetGroup => op_impl::delete_subnet_group(&client, name).await,
+                    _ => bail!("Invalid operation for ElastiCache subnet group resource"),
+                }
+            }
+            ElastiCacheResourceAddress::User { region, user_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    ElastiCacheConnectorOp::CreateUser(user) => {
+                        let sm_client = self.get_or_init_secrets_client(region).await?;
+                        op_impl::create_user(&client, &sm_client, user_id, &user).await
+                    }
+                    ElastiCacheConnectorOp::ModifyUser {
+                        access_string,
+                        authentication,
+                    } => {
+                        let sm_client = self.get_or_init_secrets_client(region).await?;
+                        op_impl::modify_user(&client, &sm_client, user_id, access_string, authentication).await
+                    }
+                    ElastiCacheConnectorOp::UpdateUserTags(old_tags, new_tags) => {
+                        let arn = elasticache_arn(region, &account_id, "user", user_id);
+                        op_impl::update_tags(&client, &arn, &old_tags, &new_tags).await
+                    }
+                    ElastiCacheConnectorOp::DeleteUser => op_impl::delete_user(&client, user_id).await,
+                    _ => bail!("Invalid operation for ElastiCache user resource"),
+                }
+            }
+            ElastiCacheResourceAddress::UserGroup { region, user_group_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    ElastiCacheConnectorOp::CreateUserGroup(user_group) => {
+                        op_impl::create_user_group(&client, user_group_id, &user_group).await
+                    }
+                    ElastiCacheConnectorOp::ModifyUserGroup {
+                        user_ids_to_add,
+                        user_ids_to_remove,
+                    } => op_impl::modify_user_group(&client, user_group_id, user_ids_to_add, user_ids_to_remove).await,
+                    ElastiCacheConnectorOp::UpdateUserGroupTags(old_tags, new_tags) => {
+                        let arn = elasticache_arn(region, &account_id, "usergroup", user_group_id);
+                        op_impl::update_tags(&client, &arn, &old_tags, &new_tags).await
+                    }
+                    ElastiCacheConnectorOp::DeleteUserGroup => op_impl::delete_user_group(&client, user_group_id).await,
+                    _ => bail!("Invalid operation for ElastiCache user group resource"),
+                }
+            }
+        }
+    }
+}
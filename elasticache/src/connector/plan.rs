@@ -0,0 +1,679 @@
+use std::{collections::BTreeMap, path::Path};
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::{
+    resource::{CacheCluster, ParameterGroup, ReplicationGroup, SubnetGroup, User, UserAuthentication, UserGroup},
+    util::{UNKNOWN_SECRET_ID, id_diff},
+};
+
+use super::{ElastiCacheConnector, ElastiCacheConnectorOp, ElastiCacheResourceAddress};
+
+/// Secret IDs reported as unknown by `get` have to be filled in before they can be used.
+fn check_secret_id(secret_id: &str, what: &str) -> anyhow::Result<()> {
+    if secret_id == UNKNOWN_SECRET_ID {
+        bail!(
+            "{} has a secret ID of {}: set it to the ID of the Secrets Manager secret that holds the value",
+            what,
+            UNKNOWN_SECRET_ID
+        );
+    }
+    Ok(())
+}
+
+/// Combinations that ElastiCache would reject, caught at plan time instead.
+fn check_replication_group(replication_group_id: &str, replication_group: &ReplicationGroup) -> anyhow::Result<()> {
+    let cluster_mode_enabled = replication_group.cluster_mode.as_deref() == Some("enabled");
+
+    if replication_group.num_node_groups < 1 {
+        bail!("Replication group {} needs at least one node group", replication_group_id);
+    }
+    if replication_group.num_node_groups > 1 && !cluster_mode_enabled {
+        bail!(
+            "Replication group {} has {} node groups, which requires cluster_mode: Some(\"enabled\")",
+            replication_group_id,
+            replication_group.num_node_groups
+        );
+    }
+    if cluster_mode_enabled && !replication_group.automatic_failover_enabled {
+        bail!(
+            "Replication group {} has cluster mode enabled, which requires automatic_failover_enabled",
+            replication_group_id
+        );
+    }
+    if replication_group.automatic_failover_enabled && replication_group.replicas_per_node_group < 1 {
+        bail!(
+            "Replication group {} has automatic failover enabled, which requires at least one replica per node group",
+            replication_group_id
+        );
+    }
+    if replication_group.multi_az_enabled && !replication_group.automatic_failover_enabled {
+        bail!(
+            "Replication group {} has Multi-AZ enabled, which requires automatic_failover_enabled",
+            replication_group_id
+        );
+    }
+    if replication_group.kms_key_id.is_some() && !replication_group.at_rest_encryption_enabled {
+        bail!(
+            "Replication group {} has a kms_key_id, which requires at_rest_encryption_enabled",
+            replication_group_id
+        );
+    }
+    if let Some(secret_id) = &replication_group.auth_token_secret_id {
+        check_secret_id(secret_id, &format!("Replication group {}'s AUTH token", replication_group_id))?;
+        if !replication_group.transit_encryption_enabled {
+            bail!(
+                "Replication group {} has an AUTH token, which requires transit_encryption_enabled",
+                replication_group_id
+            );
+        }
+        if !replication_group.user_group_ids.is_empty() {
+            bail!(
+                "Replication group {} can use either an AUTH token or user groups, not both",
+                replication_group_id
+            );
+        }
+    }
+    Ok(())
+}
+
+fn check_cache_cluster(cache_cluster_id: &str, cache_cluster: &CacheCluster) -> anyhow::Result<()> {
+    if cache_cluster.num_cache_nodes < 1 {
+        bail!("Memcached cluster {} needs at least one node", cache_cluster_id);
+    }
+    if cache_cluster.az_mode.as_deref() == Some("cross-az") && cache_cluster.num_cache_nodes < 2 {
+        bail!(
+            "Memcached cluster {} has az_mode cross-az, which requires at least two nodes",
+            cache_cluster_id
+        );
+    }
+    Ok(())
+}
+
+fn check_user(user_id: &str, user: &User) -> anyhow::Result<()> {
+    if let UserAuthentication::Password { secret_ids } = &user.authentication {
+        if secret_ids.is_empty() || secret_ids.len() > 2 {
+            bail!("User {} must have one or two password secrets", user_id);
+        }
+        for secret_id in secret_ids {
+            check_secret_id(secret_id, &format!("User {}'s password", user_id))?;
+        }
+    }
+    Ok(())
+}
+
+/// Default parameter groups are reported as None, so naming one is only a change when switching from another group.
+fn fill_default_parameter_group(new: &Option<String>, old: &Option<String>) -> Option<String> {
+    match (new, old) {
+        (Some(new), None) if new.starts_with("default.") => None,
+        _ => new.clone(),
+    }
+}
+
+/// Settings left as None keep whatever value ElastiCache chose or was last given,
+/// rather than showing up as a change.
+fn fill_unset_replication_group(new: &ReplicationGroup, old: &ReplicationGroup) -> ReplicationGroup {
+    ReplicationGroup {
+        engine_version: new.engine_version.clone().or(old.engine_version.clone()),
+        cache_parameter_group_name: fill_default_parameter_group(
+            &new.cache_parameter_group_name,
+            &old.cache_parameter_group_name,
+        ),
+        cluster_mode: new.cluster_mode.clone().or(old.cluster_mode.clone()),
+        snapshot_retention_limit: new.snapshot_retention_limit.or(old.snapshot_retention_limit),
+        snapshot_window: new.snapshot_window.clone().or(old.snapshot_window.clone()),
+        preferred_maintenance_window: new
+            .preferred_maintenance_window
+            .clone()
+            .or(old.preferred_maintenance_window.clone()),
+        ..new.clone()
+    }
+}
+
+fn fill_unset_cache_cluster(new: &CacheCluster, old: &CacheCluster) -> CacheCluster {
+    CacheCluster {
+        engine_version: new.engine_version.clone().or(old.engine_version.clone()),
+        cache_parameter_group_name: fill_default_parameter_group(
+            &new.cache_parameter_group_name,
+            &old.cache_parameter_group_name,
+        ),
+        az_mode: new.az_mode.clone().or(old.az_mode.clone()),
+        preferred_maintenance_window: new
+            .preferred_maintenance_window
+            .clone()
+            .or(old.preferred_maintenance_window.clone()),
+        ..new.clone()
+    }
+}
+
+fn replication_group_replacement_fields(old: &ReplicationGroup, new: &ReplicationGroup) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if old.cache_subnet_group_name != new.cache_subnet_group_name {
+        fields.push("cache_subnet_group_name");
+    }
+    if old.port != new.port {
+        fields.push("port");
+    }
+    if old.at_rest_encryption_enabled != new.at_rest_encryption_enabled {
+        fields.push("at_rest_encryption_enabled");
+    }
+    if old.kms_key_id != new.kms_key_id {
+        fields.push("kms_key_id");
+    }
+    // Turning transit encryption on in place needs a migration through "preferred" mode,
+    // which isn't modelled here
+    if old.transit_encryption_enabled != new.transit_encryption_enabled {
+        fields.push("transit_encryption_enabled");
+    }
+    fields
+}
+
+fn cache_cluster_replacement_fields(old: &CacheCluster, new: &CacheCluster) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    // Memcached clusters can't be scaled up or down in place
+    if old.cache_node_type != new.cache_node_type {
+        fields.push("cache_node_type");
+    }
+    if old.cache_subnet_group_name != new.cache_subnet_group_name {
+        fields.push("cache_subnet_group_name");
+    }
+    if old.port != new.port {
+        fields.push("port");
+    }
+    if old.transit_encryption_enabled != new.transit_encryption_enabled {
+        fields.push("transit_encryption_enabled");
+    }
+    fields
+}
+
+/// A parameter group can be switched to another, but not unset. Default parameter groups are reported as None.
+fn check_parameter_group_unset(what: &str, old: &Option<String>, new: &Option<String>) -> anyhow::Result<()> {
+    if let (Some(old), None) = (old, new) {
+        bail!(
+            "{} uses parameter group {}. To stop using it, set cache_parameter_group_name \
+             to the engine's default parameter group, e.g. Some(\"default.valkey8\")",
+            what,
+            old
+        );
+    }
+    Ok(())
+}
+
+impl ElastiCacheConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = ElastiCacheResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            ElastiCacheResourceAddress::ReplicationGroup {
+                region,
+                replication_group_id,
+            } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_replication_group)) => {
+                    let new_replication_group: ReplicationGroup = RON.from_str(&new_replication_group)?;
+                    check_replication_group(replication_group_id, &new_replication_group)?;
+                    Ok(vec![connector_op!(
+                        ElastiCacheConnectorOp::CreateReplicationGroup(new_replication_group),
+                        format!(
+                            "Create new ElastiCache replication group {} in region {}",
+                            replication_group_id, region
+                        )
+                    )])
+                }
+                (Some(_old_replication_group), None) => Ok(vec![connector_op!(
+                    ElastiCacheConnectorOp::DeleteReplicationGroup,
+                    format!(
+                        "DELETE ElastiCache replication group {} in region {}, along with all of its data",
+                        replication_group_id, region
+                    )
+                )]),
+                (Some(old_replication_group), Some(new_replication_group)) => {
+                    let old_replication_group: ReplicationGroup = RON.from_str(&old_replication_group)?;
+                    let new_replication_group: ReplicationGroup = RON.from_str(&new_replication_group)?;
+                    check_replication_group(replication_group_id, &new_replication_group)?;
+                    let new_replication_group = fill_unset_replication_group(&new_replication_group, &old_replication_group);
+
+                    let replacement_fields = replication_group_replacement_fields(&old_replication_group, &new_replication_group);
+                    if !replacement_fields.is_empty() {
+                        return Ok(vec![
+                            connector_op!(
+                                ElastiCacheConnectorOp::DeleteReplicationGroup,
+                                format!(
+                                    "REPLACE ElastiCache replication group `{}` (requires replacement: {})",
+                                    replication_group_id,
+                                    replacement_fields.join(", ")
+                                )
+                            ),
+                            connector_op!(
+                                ElastiCacheConnectorOp::CreateReplicationGroup(new_replication_group),
+                                format!(
+                                    "Create new ElastiCache replication group {} in region {}",
+                                    replication_group_id, region
+                                )
+                            ),
+                        ]);
+                    }
+
+                    if old_replication_group.auth_token_secret_id.is_some()
+                        && new_replication_group.auth_token_secret_id.is_none()
+                    {
+                        bail!(
+                            "Replication group {}'s AUTH token can't be removed in place. \
+                             Switch to user groups, or replace the replication group.",
+                            replication_group_id
+                        );
+                    }
+                    check_parameter_group_unset(
+                        &format!("Replication group {}", replication_group_id),
+                        &old_replication_group.cache_parameter_group_name,
+                        &new_replication_group.cache_parameter_group_name,
+                    )?;
+
+                    let mut ops = Vec::new();
+
+                    if old_replication_group.tags != new_replication_group.tags {
+                        let diff =
+                            diff_ron_values(&old_replication_group.tags, &new_replication_group.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ElastiCacheConnectorOp::UpdateReplicationGroupTags(
+                                old_replication_group.tags.clone(),
+                                new_replication_group.tags.clone()
+                            ),
+                            format!("Modify tags for ElastiCache replication group `{}`\n{}", replication_group_id, diff)
+                        ));
+                    }
+
+                    // Settings go first, since e.g. adding shards needs cluster mode enabled
+                    let old_settings = ReplicationGroup {
+                        num_node_groups: new_replication_group.num_node_groups,
+                        replicas_per_node_group: new_replication_group.replicas_per_node_group,
+                        tags: new_replication_group.tags.clone(),
+                        ..old_replication_group.clone()
+                    };
+                    if old_settings != new_replication_group {
+                        let diff = diff_ron_values(&old_settings, &new_replication_group).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ElastiCacheConnectorOp::ModifyReplicationGroup(old_settings, new_replication_group.clone()),
+                            format!("Modify ElastiCache replication group `{}`\n{}", replication_group_id, diff)
+                        ));
+                    }
+
+                    if old_replication_group.num_node_groups != new_replication_group.num_node_groups {
+                        ops.push(connector_op!(
+                            ElastiCacheConnectorOp::ModifyNodeGroupCount(new_replication_group.num_node_groups),
+                            format!(
+                                "Change the number of shards in ElastiCache replication group `{}` from {} to {}",
+                                replication_group_id,
+                                old_replication_group.num_node_groups,
+                                new_replication_group.num_node_groups
+                            )
+                        ));
+                    }
+
+                    if old_replication_group.replicas_per_node_group != new_replication_group.replicas_per_node_group {
+                        ops.push(connector_op!(
+                            ElastiCacheConnectorOp::ModifyReplicaCount(new_replication_group.replicas_per_node_group),
+                            format!(
+                                "Change the number of replicas per shard in ElastiCache replication group `{}` from {} to {}",
+                                replication_group_id,
+                                old_replication_group.replicas_per_node_group,
+                                new_replication_group.replicas_per_node_group
+                            )
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            ElastiCacheResourceAddress::CacheCluster { region, cache_cluster_id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_cache_cluster)) => {
+                    let new_cache_cluster: CacheCluster = RON.from_str(&new_cache_cluster)?;
+                    check_cache_cluster(cache_cluster_id, &new_cache_cluster)?;
+                    Ok(vec![connector_op!(
+                        ElastiCacheConnectorOp::CreateCacheCluster(new_cache_cluster),
+                        format!("Create new ElastiCache Memcached cluster {} in region {}", cache_cluster_id, region)
+                    )])
+                }
+                (Some(_old_cache_cluster), None) => Ok(vec![connector_op!(
+                    ElastiCacheConnectorOp::DeleteCacheCluster,
+                    format!("DELETE ElastiCache Memcached cluster {} in region {}", cache_cluster_id, region)
+                )]),
+                (Some(old_cache_cluster), Some(new_cache_cluster)) => {
+                    let old_cache_cluster: CacheCluster = RON.from_str(&old_cache_cluster)?;
+                    let new_cache_cluster: CacheCluster = RON.from_str(&new_cache_cluster)?;
+                    check_cache_cluster(cache_cluster_id, &new_cache_cluster)?;
+                    let new_cache_cluster = fill_unset_cache_cluster(&new_cache_cluster, &old_cache_cluster);
+
+                    let replacement_fields = cache_cluster_replacement_fields(&old_cache_cluster, &new_cache_cluster);
+                    if !replacement_fields.is_empty() {
+                        return Ok(vec![
+                            connector_op!(
+                                ElastiCacheConnectorOp::DeleteCacheCluster,
+                                format!(
+                                    "REPLACE ElastiCache Memcached cluster `{}` (requires replacement: {})",
+                                    cache_cluster_id,
+                                    replacement_fields.join(", ")
+                                )
+                            ),
+                            connector_op!(
+                                ElastiCacheConnectorOp::CreateCacheCluster(new_cache_cluster),
+                                format!("Create new ElastiCache Memcached cluster {} in region {}", cache_cluster_id, region)
+                            ),
+                        ]);
+                    }
+
+                    check_parameter_group_unset(
+                        &format!("Memcached cluster {}", cache_cluster_id),
+                        &old_cache_cluster.cache_parameter_group_name,
+                        &new_cache_cluster.cache_parameter_group_name,
+                    )?;
+
+                    let mut ops = Vec::new();
+
+                    if old_cache_cluster.tags != new_cache_cluster.tags {
+                        let diff = diff_ron_values(&old_cache_cluster.tags, &new_cache_cluster.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ElastiCacheConnectorOp::UpdateCacheClusterTags(
+                                old_cache_cluster.tags.clone(),
+                                new_cache_cluster.tags.clone()
+                            ),
+                            format!("Modify tags for ElastiCache Memcached cluster `{}`\n{}", cache_cluster_id, diff)
+                        ));
+                    }
+
+                    let old_settings = CacheCluster {
+                        tags: new_cache_cluster.tags.clone(),
+                        ..old_cache_cluster
+                    };
+                    if old_settings != new_cache_cluster {
+                        let diff = diff_ron_values(&old_settings, &new_cache_cluster).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ElastiCacheConnectorOp::ModifyCacheCluster(old_settings, new_cache_cluster),
+                            format!("Modify ElastiCache Memcached cluster `{}`\n{}", cache_cluster_id, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            ElastiCacheResourceAddress::ParameterGroup { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_parameter_group)) => {
+                    let new_parameter_group: ParameterGroup = RON.from_str(&new_parameter_group)?;
+                    Ok(vec![connector_op!(
+                        ElastiCacheConnectorOp::CreateParameterGroup(new_parameter_group),
+                        format!("Create new ElastiCache parameter group {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_parameter_group), None) => Ok(vec![connector_op!(
+                    ElastiCacheConnectorOp::DeleteParameterGroup,
+                    format!("DELETE ElastiCache parameter group {} in region {}", name, region)
+                )]),
+                (Some(old_parameter_group), Some(new_parameter_group)) => {
+                    let old_parameter_group: ParameterGroup = RON.from_str(&old_parameter_group)?;
+                    let new_parameter_group: ParameterGroup = RON.from_str(&new_parameter_group)?;
+
+                    let mut replacement_fields = Vec::new();
+                    if old_parameter_group.family != new_parameter_group.family {
+                        replacement_fields.push("family");
+                    }
+                    if old_parameter_group.description != new_parameter_group.description {
+                        replacement_fields.push("description");
+                    }
+                    if !replacement_fields.is_empty() {
+                        return Ok(vec![
+                            connector_op!(
+                                ElastiCacheConnectorOp::DeleteParameterGroup,
+                                format!(
+                                    "REPLACE ElastiCache parameter group `{}` (requires replacement: {})",
+                                    name,
+                                    replacement_fields.join(", ")
+                                )
+                            ),
+                            connector_op!(
+                                ElastiCacheConnectorOp::CreateParameterGroup(new_parameter_group),
+                                format!("Create new ElastiCache parameter group {} in region {}", name, region)
+                            ),
+                        ]);
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_parameter_group.tags != new_parameter_group.tags {
+                        let diff = diff_ron_values(&old_parameter_group.tags, &new_parameter_group.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ElastiCacheConnectorOp::UpdateParameterGroupTags(
+                                old_parameter_group.tags.clone(),
+                                new_parameter_group.tags.clone()
+                            ),
+                            format!("Modify tags for ElastiCache parameter group `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let set: BTreeMap<String, String> = new_parameter_group
+                        .parameters
+                        .iter()
+                        .filter(|(k, v)| old_parameter_group.parameters.get(*k) != Some(v))
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect();
+                    let reset: Vec<String> = old_parameter_group
+                        .parameters
+                        .keys()
+                        .filter(|k| !new_parameter_group.parameters.contains_key(*k))
+                        .cloned()
+                        .collect();
+                    if !set.is_empty() || !reset.is_empty() {
+                        let diff = diff_ron_values(&old_parameter_group.parameters, &new_parameter_group.parameters)
+                            .unwrap_or_default();
+                        ops.push(connector_op!(
+                            ElastiCacheConnectorOp::ModifyParameters { set, reset },
+                            format!("Modify parameters for ElastiCache parameter group `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            ElastiCacheResourceAddress::SubnetGroup { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_subnet_group)) => {
+                    let new_subnet_group: SubnetGroup = RON.from_str(&new_subnet_group)?;
+                    Ok(vec![connector_op!(
+                        ElastiCacheConnectorOp::CreateSubnetGroup(new_subnet_group),
+                        format!("Create new ElastiCache subnet group {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_subnet_group), None) => Ok(vec![connector_op!(
+                    ElastiCacheConnectorOp::DeleteSubnetGroup,
+                    format!("DELETE ElastiCache subnet group {} in region {}", name, region)
+                )]),
+                (Some(old_subnet_group), Some(new_subnet_group)) => {
+                    let old_subnet_group: SubnetGroup = RON.from_str(&old_subnet_group)?;
+                    let new_subnet_group: SubnetGroup = RON.from_str(&new_subnet_group)?;
+                    let mut ops = Vec::new();
+
+                    if old_subnet_group.tags != new_subnet_group.tags {
+                        let diff = diff_ron_values(&old_subnet_group.tags, &new_subnet_group.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ElastiCacheConnectorOp::UpdateSubnetGroupTags(
+                                old_subnet_group.tags.clone(),
+                                new_subnet_group.tags.clone()
+                            ),
+                            format!("Modify tags for ElastiCache subnet group `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    // Subnet order doesn't matter
+                    let (added_subnets, removed_subnets) = id_diff(&old_subnet_group.subnet_ids, &new_subnet_group.subnet_ids);
+                    if old_subnet_group.description != new_subnet_group.description
+                        || !added_subnets.is_empty()
+                        || !removed_subnets.is_empty()
+                    {
+                        let old_settings = SubnetGroup {
+                            tags: new_subnet_group.tags.clone(),
+                            ..old_subnet_group
+                        };
+                        let diff = diff_ron_values(&old_settings, &new_subnet_group).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ElastiCacheConnectorOp::ModifySubnetGroup(new_subnet_group),
+                            format!("Modify ElastiCache subnet group `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            ElastiCacheResourceAddress::User { region, user_id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_user)) => {
+                    let new_user: User = RON.from_str(&new_user)?;
+                    check_user(user_id, &new_user)?;
+                    Ok(vec![connector_op!(
+                        ElastiCacheConnectorOp::CreateUser(new_user),
+                        format!("Create new ElastiCache user {} in region {}", user_id, region)
+                    )])
+                }
+                (Some(_old_user), None) => Ok(vec![connector_op!(
+                    ElastiCacheConnectorOp::DeleteUser,
+                    format!("DELETE ElastiCache user {} in region {}", user_id, region)
+                )]),
+                (Some(old_user), Some(new_user)) => {
+                    let old_user: User = RON.from_str(&old_user)?;
+                    let new_user: User = RON.from_str(&new_user)?;
+                    check_user(user_id, &new_user)?;
+
+                    let mut replacement_fields = Vec::new();
+                    if old_user.user_name != new_user.user_name {
+                        replacement_fields.push("user_name");
+                    }
+                    if old_user.engine != new_user.engine {
+                        replacement_fields.push("engine");
+                    }
+                    if !replacement_fields.is_empty() {
+                        return Ok(vec![
+                            connector_op!(
+                                ElastiCacheConnectorOp::DeleteUser,
+                                format!(
+                                    "REPLACE ElastiCache user `{}` (requires replacement: {})",
+                                    user_id,
+                                    replacement_fields.join(", ")
+                                )
+                            ),
+                            connector_op!(
+                                ElastiCacheConnectorOp::CreateUser(new_user),
+                                format!("Create new ElastiCache user {} in region {}", user_id, region)
+                            ),
+                        ]);
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_user.tags != new_user.tags {
+                        let diff = diff_ron_values(&old_user.tags, &new_user.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ElastiCacheConnectorOp::UpdateUserTags(old_user.tags.clone(), new_user.tags.clone()),
+                            format!("Modify tags for ElastiCache user `{}`\n{}", user_id, diff)
+                        ));
+                    }
+
+                    let access_string = (old_user.access_string != new_user.access_string).then(|| new_user.access_string.clone());
+                    let authentication =
+                        (old_user.authentication != new_user.authentication).then(|| new_user.authentication.clone());
+                    if access_string.is_some() || authentication.is_some() {
+                        let old_settings = User {
+                            tags: new_user.tags.clone(),
+                            ..old_user
+                        };
+                        let diff = diff_ron_values(&old_settings, &new_user).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ElastiCacheConnectorOp::ModifyUser {
+                                access_string,
+                                authentication,
+                            },
+                            format!("Modify ElastiCache user `{}`\n{}", user_id, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            ElastiCacheResourceAddress::UserGroup { region, user_group_id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_user_group)) => {
+                    let new_user_group: UserGroup = RON.from_str(&new_user_group)?;
+                    Ok(vec![connector_op!(
+                        ElastiCacheConnectorOp::CreateUserGroup(new_user_group),
+                        format!("Create new ElastiCache user group {} in region {}", user_group_id, region)
+                    )])
+                }
+                (Some(_old_user_group), None) => Ok(vec![connector_op!(
+                    ElastiCacheConnectorOp::DeleteUserGroup,
+                    format!("DELETE ElastiCache user group {} in region {}", user_group_id, region)
+                )]),
+                (Some(old_user_group), Some(new_user_group)) => {
+                    let old_user_group: UserGroup = RON.from_str(&old_user_group)?;
+                    let new_user_group: UserGroup = RON.from_str(&new_user_group)?;
+
+                    if old_user_group.engine != new_user_group.engine {
+                        return Ok(vec![
+                            connector_op!(
+                                ElastiCacheConnectorOp::DeleteUserGroup,
+                                format!(
+                                    "REPLACE ElastiCache user group `{}` (requires replacement: engine)",
+                                    user_group_id
+                                )
+                            ),
+                            connector_op!(
+                                ElastiCacheConnectorOp::CreateUserGroup(new_user_group),
+                                format!("Create new ElastiCache user group {} in region {}", user_group_id, region)
+                            ),
+                        ]);
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_user_group.tags != new_user_group.tags {
+                        let diff = diff_ron_values(&old_user_group.tags, &new_user_group.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ElastiCacheConnectorOp::UpdateUserGroupTags(
+                                old_user_group.tags.clone(),
+                                new_user_group.tags.clone()
+                            ),
+                            format!("Modify tags for ElastiCache user group `{}`\n{}", user_group_id, diff)
+                        ));
+                    }
+
+                    let (user_ids_to_add, user_ids_to_remove) = id_diff(&old_user_group.user_ids, &new_user_group.user_ids);
+                    if !user_ids_to_add.is_empty() || !user_ids_to_remove.is_empty() {
+                        let diff = diff_ron_values(&old_user_group.user_ids, &new_user_group.user_ids).unwrap_or_default();
+                        ops.push(connector_op!(
+                            ElastiCacheConnectorOp::ModifyUserGroup {
+                                user_ids_to_add,
+                                user_ids_to_remove,
+                            },
+                            format!("Modify users in ElastiCache user group `{}`\n{}", user_group_id, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+        }
+    }
+}
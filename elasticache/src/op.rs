@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{CacheCluster, ParameterGroup, ReplicationGroup, SubnetGroup, User, UserAuthentication, UserGroup},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ElastiCacheConnectorOp {
+    // Replication group operations
+    CreateReplicationGroup(ReplicationGroup),
+    /// Applies the differences between the old and new settings in one ModifyReplicationGroup call.
+    /// Shard and replica counts are changed by their own ops.
+    ModifyReplicationGroup(ReplicationGroup, ReplicationGroup),
+    ModifyNodeGroupCount(i32),
+    ModifyReplicaCount(i32),
+    UpdateReplicationGroupTags(Tags, Tags),
+    DeleteReplicationGroup,
+
+    // Memcached cluster operations
+    CreateCacheCluster(CacheCluster),
+    ModifyCacheCluster(CacheCluster, CacheCluster),
+    UpdateCacheClusterTags(Tags, Tags),
+    DeleteCacheCluster,
+
+    // Parameter group operations
+    CreateParameterGroup(ParameterGroup),
+    /// Sets the given parameters, and resets any others listed to the family's defaults.
+    ModifyParameters {
+        set:   BTreeMap<String, String>,
+        reset: Vec<String>,
+    },
+    UpdateParameterGroupTags(Tags, Tags),
+    DeleteParameterGroup,
+
+    // Subnet group operations
+    CreateSubnetGroup(SubnetGroup),
+    ModifySubnetGroup(SubnetGroup),
+    UpdateSubnetGroupTags(Tags, Tags),
+    DeleteSubnetGroup,
+
+    // User operations
+    CreateUser(User),
+    ModifyUser {
+        access_string:  Option<String>,
+        authentication: Option<UserAuthentication>,
+    },
+    UpdateUserTags(Tags, Tags),
+    DeleteUser,
+
+    // User group operations
+    CreateUserGroup(UserGroup),
+    ModifyUserGroup {
+        user_ids_to_add:    Vec<String>,
+        user_ids_to_remove: Vec<String>,
+    },
+    UpdateUserGroupTags(Tags, Tags),
+    DeleteUserGroup,
+}
+
+impl ConnectorOp for ElastiCacheConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
@@ -0,0 +1,863 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    time::Duration,
+};
+
+use anyhow::{Context, bail};
+use autoschematic_core::connector::OpExecResponse;
+use aws_sdk_elasticache::types::{
+    AuthTokenUpdateStrategyType, AuthenticationMode, AzMode, ClusterMode, InputAuthenticationType, ParameterNameValue,
+};
+
+use crate::{
+    resource::{CacheCluster, ParameterGroup, ReplicationGroup, SubnetGroup, User, UserAuthentication, UserGroup},
+    tags::{Tags, tag_diff},
+    util::{cache_cluster_endpoint, id_diff, join_secret_ids, read_secret, replication_group_endpoint},
+};
+
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(30);
+/// Creating or resharding a large replication group can take well over an hour.
+const STATUS_MAX_POLLS: usize = 240;
+/// ModifyCacheParameterGroup and ResetCacheParameterGroup take at most 20 parameters per call.
+const MAX_PARAMETERS_PER_CALL: usize = 20;
+
+/// ElastiCache rejects most changes to a resource while a previous one is still being applied, and
+/// a deleted resource's ID can't be reused until it's gone. Polls `status` until it returns `target`,
+/// or until the resource no longer exists if `target` is None.
+async fn wait_for_status<F, Fut>(description: &str, target: Option<&str>, status: F) -> anyhow::Result<()>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = anyhow::Result<Option<String>>>,
+{
+    for _ in 0..STATUS_MAX_POLLS {
+        if status().await?.as_deref() == target {
+            return Ok(());
+        }
+        tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    }
+
+    match target {
+        Some(target) => bail!("Timed out waiting for {} to become {}", description, target),
+        None => bail!("Timed out waiting for {} to be deleted", description),
+    }
+}
+
+async fn replication_group_status(
+    client: &aws_sdk_elasticache::Client,
+    replication_group_id: &str,
+) -> anyhow::Result<Option<String>> {
+    match client
+        .describe_replication_groups()
+        .replication_group_id(replication_group_id)
+        .send()
+        .await
+    {
+        Ok(resp) => Ok(resp.replication_groups().first().and_then(|rg| rg.status.clone())),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_replication_group_not_found_fault()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn cache_cluster_status(client: &aws_sdk_elasticache::Client, cache_cluster_id: &str) -> anyhow::Result<Option<String>> {
+    match client.describe_cache_clusters().cache_cluster_id(cache_cluster_id).send().await {
+        Ok(resp) => Ok(resp.cache_clusters().first().and_then(|c| c.cache_cluster_status.clone())),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_cache_cluster_not_found_fault()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn user_status(client: &aws_sdk_elasticache::Client, user_id: &str) -> anyhow::Result<Option<String>> {
+    match client.describe_users().user_id(user_id).send().await {
+        Ok(resp) => Ok(resp.users().first().and_then(|u| u.status.clone())),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_user_not_found_fault()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn user_group_status(client: &aws_sdk_elasticache::Client, user_group_id: &str) -> anyhow::Result<Option<String>> {
+    match client.describe_user_groups().user_group_id(user_group_id).send().await {
+        Ok(resp) => Ok(resp.user_groups().first().and_then(|g| g.status.clone())),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_user_group_not_found_fault()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn wait_for_replication_group_available(
+    client: &aws_sdk_elasticache::Client,
+    replication_group_id: &str,
+) -> anyhow::Result<aws_sdk_elasticache::types::ReplicationGroup> {
+    wait_for_status(
+        &format!("replication group {}", replication_group_id),
+        Some("available"),
+        || replication_group_status(client, replication_group_id),
+    )
+    .await?;
+
+    let resp = client
+        .describe_replication_groups()
+        .replication_group_id(replication_group_id)
+        .send()
+        .await?;
+    resp.replication_groups
+        .and_then(|rgs| rgs.into_iter().next())
+        .with_context(|| format!("Replication group {} not found", replication_group_id))
+}
+
+/// Updates the tags on any ElastiCache resource
+pub async fn update_tags(
+    client: &aws_sdk_elasticache::Client,
+    arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .remove_tags_from_resource()
+            .resource_name(arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client
+            .add_tags_to_resource()
+            .resource_name(arn)
+            .set_tags(Some(new_tagset))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for ElastiCache resource {}", arn)),
+    })
+}
+
+/// Creates a replication group and waits for it to become available
+pub async fn create_replication_group(
+    client: &aws_sdk_elasticache::Client,
+    sm_client: &aws_sdk_secretsmanager::Client,
+    replication_group_id: &str,
+    replication_group: &ReplicationGroup,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client
+        .create_replication_group()
+        .replication_group_id(replication_group_id)
+        .replication_group_description(&replication_group.description)
+        .engine(&replication_group.engine)
+        .set_engine_version(replication_group.engine_version.clone())
+        .cache_node_type(&replication_group.cache_node_type)
+        .set_cluster_mode(replication_group.cluster_mode.as_deref().map(ClusterMode::from))
+        .num_node_groups(replication_group.num_node_groups)
+        .replicas_per_node_group(replication_group.replicas_per_node_group)
+        .automatic_failover_enabled(replication_group.automatic_failover_enabled)
+        .multi_az_enabled(replication_group.multi_az_enabled)
+        .set_cache_parameter_group_name(replication_group.cache_parameter_group_name.clone())
+        .set_cache_subnet_group_name(replication_group.cache_subnet_group_name.clone())
+        .set_security_group_ids(Some(replication_group.security_group_ids.clone()))
+        .set_port(replication_group.port)
+        .at_rest_encryption_enabled(replication_group.at_rest_encryption_enabled)
+        .set_kms_key_id(replication_group.kms_key_id.clone())
+        .transit_encryption_enabled(replication_group.transit_encryption_enabled)
+        .set_snapshot_retention_limit(replication_group.snapshot_retention_limit)
+        .set_snapshot_window(replication_group.snapshot_window.clone())
+        .set_preferred_maintenance_window(replication_group.preferred_maintenance_window.clone())
+        .auto_minor_version_upgrade(replication_group.auto_minor_version_upgrade)
+        .set_notification_topic_arn(replication_group.notification_topic_arn.clone());
+
+    if !replication_group.user_group_ids.is_empty() {
+        request = request.set_user_group_ids(Some(replication_group.user_group_ids.clone()));
+    }
+
+    if let Some(secret_id) = &replication_group.auth_token_secret_id {
+        request = request.auth_token(read_secret(sm_client, secret_id).await?);
+    }
+
+    if replication_group.tags.len() > 0 {
+        request = request.set_tags(Some(replication_group.tags.to_vec()));
+    }
+
+    request.send().await?;
+
+    let created = wait_for_replication_group_available(client, replication_group_id).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("replication_group_arn"), created.arn.clone()),
+            (String::from("endpoint"), replication_group_endpoint(&created)),
+            (
+                String::from("auth_token_secret_id"),
+                replication_group.auth_token_secret_id.clone(),
+            ),
+        ])),
+        friendly_message: Some(format!("Created ElastiCache replication group {}", replication_group_id)),
+    })
+}
+
+/// Applies everything that differs between `old` and `new` in one ModifyReplicationGroup call.
+/// Settings left as None in `new` are left alone.
+pub async fn modify_replication_group(
+    client: &aws_sdk_elasticache::Client,
+    sm_client: &aws_sdk_secretsmanager::Client,
+    replication_group_id: &str,
+    old: &ReplicationGroup,
+    new: &ReplicationGroup,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client
+        .modify_replication_group()
+        .replication_group_id(replication_group_id)
+        .apply_immediately(true);
+
+    if old.description != new.description {
+        request = request.replication_group_description(&new.description);
+    }
+
+    // Only redis to valkey is supported
+    if old.engine != new.engine {
+        request = request.engine(&new.engine);
+    }
+
+    if new.engine_version.is_some() && old.engine_version != new.engine_version {
+        request = request.set_engine_version(new.engine_version.clone());
+    }
+
+    if old.cache_node_type != new.cache_node_type {
+        request = request.cache_node_type(&new.cache_node_type);
+    }
+
+    if new.cluster_mode.is_some() && old.cluster_mode != new.cluster_mode {
+        request = request.set_cluster_mode(new.cluster_mode.as_deref().map(ClusterMode::from));
+    }
+
+    if old.automatic_failover_enabled != new.automatic_failover_enabled {
+        request = request.automatic_failover_enabled(new.automatic_failover_enabled);
+    }
+
+    if old.multi_az_enabled != new.multi_az_enabled {
+        request = request.multi_az_enabled(new.multi_az_enabled);
+    }
+
+    if new.cache_parameter_group_name.is_some() && old.cache_parameter_group_name != new.cache_parameter_group_name {
+        request = request.set_cache_parameter_group_name(new.cache_parameter_group_name.clone());
+    }
+
+    if old.security_group_ids != new.security_group_ids {
+        request = request.set_security_group_ids(Some(new.security_group_ids.clone()));
+    }
+
+    let (user_groups_to_add, user_groups_to_remove) = id_diff(&old.user_group_ids, &new.user_group_ids);
+    if !user_groups_to_add.is_empty() {
+        request = request.set_user_group_ids_to_add(Some(user_groups_to_add));
+    }
+    if !user_groups_to_remove.is_empty() {
+        request = request.set_user_group_ids_to_remove(Some(user_groups_to_remove));
+    }
+
+    // ROTATE adds a token to a group that doesn't have one. SET replaces the existing token,
+    // so clients still using the old one are disconnected.
+    if old.auth_token_secret_id != new.auth_token_secret_id
+        && let Some(secret_id) = &new.auth_token_secret_id
+    {
+        let strategy = if old.auth_token_secret_id.is_some() {
+            AuthTokenUpdateStrategyType::Set
+        } else {
+            AuthTokenUpdateStrategyType::Rotate
+        };
+        request = request
+            .auth_token(read_secret(sm_client, secret_id).await?)
+            .auth_token_update_strategy(strategy);
+    }
+
+    if new.snapshot_retention_limit.is_some() && old.snapshot_retention_limit != new.snapshot_retention_limit {
+        request = request.set_snapshot_retention_limit(new.snapshot_retention_limit);
+    }
+
+    if new.snapshot_window.is_some() && old.snapshot_window != new.snapshot_window {
+        request = request.set_snapshot_window(new.snapshot_window.clone());
+    }
+
+    if new.preferred_maintenance_window.is_some() && old.preferred_maintenance_window != new.preferred_maintenance_window {
+        request = request.set_preferred_maintenance_window(new.preferred_maintenance_window.clone());
+    }
+
+    if old.auto_minor_version_upgrade != new.auto_minor_version_upgrade {
+        request = request.auto_minor_version_upgrade(new.auto_minor_version_upgrade);
+    }
+
+    if old.notification_topic_arn != new.notification_topic_arn {
+        request = match &new.notification_topic_arn {
+            Some(topic_arn) => request.notification_topic_arn(topic_arn).notification_topic_status("active"),
+            None => request.notification_topic_status("inactive"),
+        };
+    }
+
+    request.send().await?;
+
+    wait_for_replication_group_available(client, replication_group_id).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(
+            String::from("auth_token_secret_id"),
+            new.auth_token_secret_id.clone(),
+        )])),
+        friendly_message: Some(format!("Modified ElastiCache replication group {}", replication_group_id)),
+    })
+}
+
+/// Adds or removes shards. When removing, the shards with the highest IDs are removed.
+pub async fn modify_node_group_count(
+    client: &aws_sdk_elasticache::Client,
+    replication_group_id: &str,
+    node_group_count: i32,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let current = wait_for_replication_group_available(client, replication_group_id).await?;
+
+    let mut node_group_ids: Vec<String> = current.node_groups().iter().filter_map(|ng| ng.node_group_id.clone()).collect();
+    node_group_ids.sort();
+
+    let mut request = client
+        .modify_replication_group_shard_configuration()
+        .replication_group_id(replication_group_id)
+        .node_group_count(node_group_count)
+        .apply_immediately(true);
+
+    if node_group_count < node_group_ids.len() as i32 {
+        request = request.set_node_groups_to_remove(Some(node_group_ids.split_off(node_group_count.max(1) as usize)));
+    }
+
+    request.send().await?;
+
+    wait_for_replication_group_available(client, replication_group_id).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Changed ElastiCache replication group {} to {} shards",
+            replication_group_id, node_group_count
+        )),
+    })
+}
+
+/// Adds or removes replicas in every shard
+pub async fn modify_replica_count(
+    client: &aws_sdk_elasticache::Client,
+    replication_group_id: &str,
+    replica_count: i32,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let current = wait_for_replication_group_available(client, replication_group_id).await?;
+
+    let current_replica_count = current
+        .node_groups()
+        .first()
+        .map(|ng| ng.node_group_members().len() as i32 - 1)
+        .unwrap_or_default();
+
+    if replica_count > current_replica_count {
+        client
+            .increase_replica_count()
+            .replication_group_id(replication_group_id)
+            .new_replica_count(replica_count)
+            .apply_immediately(true)
+            .send()
+            .await?;
+    } else if replica_count < current_replica_count {
+        client
+            .decrease_replica_count()
+            .replication_group_id(replication_group_id)
+            .new_replica_count(replica_count)
+            .apply_immediately(true)
+            .send()
+            .await?;
+    }
+
+    wait_for_replication_group_available(client, replication_group_id).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Changed ElastiCache replication group {} to {} replicas per shard",
+            replication_group_id, replica_count
+        )),
+    })
+}
+
+/// Deletes a replication group, along with all of its nodes, and waits for it to be gone
+pub async fn delete_replication_group(
+    client: &aws_sdk_elasticache::Client,
+    replication_group_id: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .delete_replication_group()
+        .replication_group_id(replication_group_id)
+        .retain_primary_cluster(false)
+        .send()
+        .await?;
+
+    wait_for_status(&format!("replication group {}", replication_group_id), None, || {
+        replication_group_status(client, replication_group_id)
+    })
+    .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("replication_group_arn"), None),
+            (String::from("endpoint"), None),
+            (String::from("auth_token_secret_id"), None),
+        ])),
+        friendly_message: Some(format!("Deleted ElastiCache replication group {}", replication_group_id)),
+    })
+}
+
+/// Creates a Memcached cluster and waits for it to become available
+pub async fn create_cache_cluster(
+    client: &aws_sdk_elasticache::Client,
+    cache_cluster_id: &str,
+    cache_cluster: &CacheCluster,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client
+        .create_cache_cluster()
+        .cache_cluster_id(cache_cluster_id)
+        .engine("memcached")
+        .set_engine_version(cache_cluster.engine_version.clone())
+        .cache_node_type(&cache_cluster.cache_node_type)
+        .num_cache_nodes(cache_cluster.num_cache_nodes)
+        .set_az_mode(cache_cluster.az_mode.as_deref().map(AzMode::from))
+        .set_cache_parameter_group_name(cache_cluster.cache_parameter_group_name.clone())
+        .set_cache_subnet_group_name(cache_cluster.cache_subnet_group_name.clone())
+        .set_security_group_ids(Some(cache_cluster.security_group_ids.clone()))
+        .set_port(cache_cluster.port)
+        .transit_encryption_enabled(cache_cluster.transit_encryption_enabled)
+        .set_preferred_maintenance_window(cache_cluster.preferred_maintenance_window.clone())
+        .auto_minor_version_upgrade(cache_cluster.auto_minor_version_upgrade)
+        .set_notification_topic_arn(cache_cluster.notification_topic_arn.clone());
+
+    if cache_cluster.tags.len() > 0 {
+        request = request.set_tags(Some(cache_cluster.tags.to_vec()));
+    }
+
+    request.send().await?;
+
+    wait_for_status(&format!("cache cluster {}", cache_cluster_id), Some("available"), || {
+        cache_cluster_status(client, cache_cluster_id)
+    })
+    .await?;
+
+    let resp = client.describe_cache_clusters().cache_cluster_id(cache_cluster_id).send().await?;
+    let created = resp.cache_clusters().first();
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("cache_cluster_arn"), created.and_then(|c| c.arn.clone())),
+            (String::from("endpoint"), created.and_then(cache_cluster_endpoint)),
+        ])),
+        friendly_message: Some(format!("Created ElastiCache Memcached cluster {}", cache_cluster_id)),
+    })
+}
+
+/// Applies everything that differs between `old` and `new` in one ModifyCacheCluster call.
+/// When shrinking the cluster, the nodes with the highest IDs are removed.
+pub async fn modify_cache_cluster(
+    client: &aws_sdk_elasticache::Client,
+    cache_cluster_id: &str,
+    old: &CacheCluster,
+    new: &CacheCluster,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client
+        .modify_cache_cluster()
+        .cache_cluster_id(cache_cluster_id)
+        .apply_immediately(true);
+
+    if old.num_cache_nodes != new.num_cache_nodes {
+        request = request.num_cache_nodes(new.num_cache_nodes);
+        if new.num_cache_nodes < old.num_cache_nodes {
+            let node_ids = (new.num_cache_nodes + 1..=old.num_cache_nodes).map(|n| format!("{:04}", n));
+            request = request.set_cache_node_ids_to_remove(Some(node_ids.collect()));
+        }
+    }
+
+    if new.az_mode.is_some() && old.az_mode != new.az_mode {
+        request = request.set_az_mode(new.az_mode.as_deref().map(AzMode::from));
+    }
+
+    if new.engine_version.is_some() && old.engine_version != new.engine_version {
+        request = request.set_engine_version(new.engine_version.clone());
+    }
+
+    if new.cache_parameter_group_name.is_some() && old.cache_parameter_group_name != new.cache_parameter_group_name {
+        request = request.set_cache_parameter_group_name(new.cache_parameter_group_name.clone());
+    }
+
+    if old.security_group_ids != new.security_group_ids {
+        request = request.set_security_group_ids(Some(new.security_group_ids.clone()));
+    }
+
+    if new.preferred_maintenance_window.is_some() && old.preferred_maintenance_window != new.preferred_maintenance_window {
+        request = request.set_preferred_maintenance_window(new.preferred_maintenance_window.clone());
+    }
+
+    if old.auto_minor_version_upgrade != new.auto_minor_version_upgrade {
+        request = request.auto_minor_version_upgrade(new.auto_minor_version_upgrade);
+    }
+
+    if old.notification_topic_arn != new.notification_topic_arn {
+        request = match &new.notification_topic_arn {
+            Some(topic_arn) => request.notification_topic_arn(topic_arn).notification_topic_status("active"),
+            None => request.notification_topic_status("inactive"),
+        };
+    }
+
+    request.send().await?;
+
+    wait_for_status(&format!("cache cluster {}", cache_cluster_id), Some("available"), || {
+        cache_cluster_status(client, cache_cluster_id)
+    })
+    .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Modified ElastiCache Memcached cluster {}", cache_cluster_id)),
+    })
+}
+
+/// Deletes a Memcached cluster and waits for it to be gone
+pub async fn delete_cache_cluster(
+    client: &aws_sdk_elasticache::Client,
+    cache_cluster_id: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_cache_cluster().cache_cluster_id(cache_cluster_id).send().await?;
+
+    wait_for_status(&format!("cache cluster {}", cache_cluster_id), None, || {
+        cache_cluster_status(client, cache_cluster_id)
+    })
+    .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("cache_cluster_arn"), None),
+            (String::from("endpoint"), None),
+        ])),
+        friendly_message: Some(format!("Deleted ElastiCache Memcached cluster {}", cache_cluster_id)),
+    })
+}
+
+/// Creates a parameter group, then sets its parameters
+pub async fn create_parameter_group(
+    client: &aws_sdk_elasticache::Client,
+    name: &str,
+    parameter_group: &ParameterGroup,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client
+        .create_cache_parameter_group()
+        .cache_parameter_group_name(name)
+        .cache_parameter_group_family(&parameter_group.family)
+        .description(&parameter_group.description);
+
+    if parameter_group.tags.len() > 0 {
+        request = request.set_tags(Some(parameter_group.tags.to_vec()));
+    }
+
+    let resp = request.send().await?;
+
+    if !parameter_group.parameters.is_empty() {
+        modify_parameters(client, name, &parameter_group.parameters, &[]).await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(
+            String::from("parameter_group_arn"),
+            resp.cache_parameter_group.and_then(|pg| pg.arn),
+        )])),
+        friendly_message: Some(format!("Created ElastiCache parameter group {}", name)),
+    })
+}
+
+/// Sets parameters, and resets others to the family's defaults
+pub async fn modify_parameters(
+    client: &aws_sdk_elasticache::Client,
+    name: &str,
+    set: &BTreeMap<String, String>,
+    reset: &[String],
+) -> Result<OpExecResponse, anyhow::Error> {
+    let set: Vec<ParameterNameValue> = set
+        .iter()
+        .map(|(k, v)| ParameterNameValue::builder().parameter_name(k).parameter_value(v).build())
+        .collect();
+    for chunk in set.chunks(MAX_PARAMETERS_PER_CALL) {
+        client
+            .modify_cache_parameter_group()
+            .cache_parameter_group_name(name)
+            .set_parameter_name_values(Some(chunk.to_vec()))
+            .send()
+            .await?;
+    }
+
+    let reset: Vec<ParameterNameValue> = reset
+        .iter()
+        .map(|k| ParameterNameValue::builder().parameter_name(k).build())
+        .collect();
+    for chunk in reset.chunks(MAX_PARAMETERS_PER_CALL) {
+        client
+            .reset_cache_parameter_group()
+            .cache_parameter_group_name(name)
+            .reset_all_parameters(false)
+            .set_parameter_name_values(Some(chunk.to_vec()))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated parameters for ElastiCache parameter group {}", name)),
+    })
+}
+
+/// Deletes a parameter group. Fails if any cluster still uses it.
+pub async fn delete_parameter_group(client: &aws_sdk_elasticache::Client, name: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .delete_cache_parameter_group()
+        .cache_parameter_group_name(name)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("parameter_group_arn"), None)])),
+        friendly_message: Some(format!("Deleted ElastiCache parameter group {}", name)),
+    })
+}
+
+/// Creates a subnet group using the provided configuration
+pub async fn create_subnet_group(
+    client: &aws_sdk_elasticache::Client,
+    name: &str,
+    subnet_group: &SubnetGroup,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client
+        .create_cache_subnet_group()
+        .cache_subnet_group_name(name)
+        .cache_subnet_group_description(&subnet_group.description)
+        .set_subnet_ids(Some(subnet_group.subnet_ids.clone()));
+
+    if subnet_group.tags.len() > 0 {
+        request = request.set_tags(Some(subnet_group.tags.to_vec()));
+    }
+
+    let resp = request.send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(
+            String::from("subnet_group_arn"),
+            resp.cache_subnet_group.and_then(|sg| sg.arn),
+        )])),
+        friendly_message: Some(format!("Created ElastiCache subnet group {}", name)),
+    })
+}
+
+/// Updates a subnet group's description and subnets
+pub async fn modify_subnet_group(
+    client: &aws_sdk_elasticache::Client,
+    name: &str,
+    subnet_group: &SubnetGroup,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .modify_cache_subnet_group()
+        .cache_subnet_group_name(name)
+        .cache_subnet_group_description(&subnet_group.description)
+        .set_subnet_ids(Some(subnet_group.subnet_ids.clone()))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Modified ElastiCache subnet group {}", name)),
+    })
+}
+
+/// Deletes a subnet group. Fails if any cluster still uses it.
+pub async fn delete_subnet_group(client: &aws_sdk_elasticache::Client, name: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_cache_subnet_group().cache_subnet_group_name(name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("subnet_group_arn"), None)])),
+        friendly_message: Some(format!("Deleted ElastiCache subnet group {}", name)),
+    })
+}
+
+/// Reads any passwords from Secrets Manager
+async fn authentication_mode(
+    sm_client: &aws_sdk_secretsmanager::Client,
+    authentication: &UserAuthentication,
+) -> anyhow::Result<AuthenticationMode> {
+    Ok(match authentication {
+        UserAuthentication::NoPassword => AuthenticationMode::builder()
+            .r#type(InputAuthenticationType::NoPasswordRequired)
+            .build(),
+        UserAuthentication::Iam => AuthenticationMode::builder().r#type(InputAuthenticationType::Iam).build(),
+        UserAuthentication::Password { secret_ids } => {
+            let mut passwords = Vec::new();
+            for secret_id in secret_ids {
+                passwords.push(read_secret(sm_client, secret_id).await?);
+            }
+            AuthenticationMode::builder()
+                .r#type(InputAuthenticationType::Password)
+                .set_passwords(Some(passwords))
+                .build()
+        }
+    })
+}
+
+fn password_secret_ids(authentication: &UserAuthentication) -> Option<String> {
+    match authentication {
+        UserAuthentication::Password { secret_ids } => Some(join_secret_ids(secret_ids)),
+        _ => None,
+    }
+}
+
+/// Creates a user and waits for it to become active, so that it can be added to user groups
+pub async fn create_user(
+    client: &aws_sdk_elasticache::Client,
+    sm_client: &aws_sdk_secretsmanager::Client,
+    user_id: &str,
+    user: &User,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client
+        .create_user()
+        .user_id(user_id)
+        .user_name(&user.user_name)
+        .engine(&user.engine)
+        .access_string(&user.access_string)
+        .authentication_mode(authentication_mode(sm_client, &user.authentication).await?);
+
+    if user.tags.len() > 0 {
+        request = request.set_tags(Some(user.tags.to_vec()));
+    }
+
+    let resp = request.send().await?;
+
+    wait_for_status(&format!("user {}", user_id), Some("active"), || user_status(client, user_id)).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("user_arn"), resp.arn),
+            (String::from("password_secret_ids"), password_secret_ids(&user.authentication)),
+        ])),
+        friendly_message: Some(format!("Created ElastiCache user {}", user_id)),
+    })
+}
+
+/// Updates a user's access string and/or how it signs in
+pub async fn modify_user(
+    client: &aws_sdk_elasticache::Client,
+    sm_client: &aws_sdk_secretsmanager::Client,
+    user_id: &str,
+    access_string: Option<String>,
+    authentication: Option<UserAuthentication>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client.modify_user().user_id(user_id).set_access_string(access_string);
+
+    let mut outputs = HashMap::new();
+    if let Some(authentication) = &authentication {
+        request = request.authentication_mode(authentication_mode(sm_client, authentication).await?);
+        outputs.insert(String::from("password_secret_ids"), password_secret_ids(authentication));
+    }
+
+    request.send().await?;
+
+    wait_for_status(&format!("user {}", user_id), Some("active"), || user_status(client, user_id)).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(outputs),
+        friendly_message: Some(format!("Modified ElastiCache user {}", user_id)),
+    })
+}
+
+/// Deletes a user and waits for it to be gone
+pub async fn delete_user(client: &aws_sdk_elasticache::Client, user_id: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_user().user_id(user_id).send().await?;
+
+    wait_for_status(&format!("user {}", user_id), None, || user_status(client, user_id)).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("user_arn"), None),
+            (String::from("password_secret_ids"), None),
+        ])),
+        friendly_message: Some(format!("Deleted ElastiCache user {}", user_id)),
+    })
+}
+
+/// Creates a user group and waits for it to become active, so that it can be attached to replication groups
+pub async fn create_user_group(
+    client: &aws_sdk_elasticache::Client,
+    user_group_id: &str,
+    user_group: &UserGroup,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client
+        .create_user_group()
+        .user_group_id(user_group_id)
+        .engine(&user_group.engine)
+        .set_user_ids(Some(user_group.user_ids.clone()));
+
+    if user_group.tags.len() > 0 {
+        request = request.set_tags(Some(user_group.tags.to_vec()));
+    }
+
+    let resp = request.send().await?;
+
+    wait_for_status(&format!("user group {}", user_group_id), Some("active"), || {
+        user_group_status(client, user_group_id)
+    })
+    .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("user_group_arn"), resp.arn)])),
+        friendly_message: Some(format!("Created ElastiCache user group {}", user_group_id)),
+    })
+}
+
+/// Adds and removes users from a user group
+pub async fn modify_user_group(
+    client: &aws_sdk_elasticache::Client,
+    user_group_id: &str,
+    user_ids_to_add: Vec<String>,
+    user_ids_to_remove: Vec<String>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client.modify_user_group().user_group_id(user_group_id);
+
+    if !user_ids_to_add.is_empty() {
+        request = request.set_user_ids_to_add(Some(user_ids_to_add));
+    }
+    if !user_ids_to_remove.is_empty() {
+        request = request.set_user_ids_to_remove(Some(user_ids_to_remove));
+    }
+
+    request.send().await?;
+
+    wait_for_status(&format!("user group {}", user_group_id), Some("active"), || {
+        user_group_status(client, user_group_id)
+    })
+    .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Modified ElastiCache user group {}", user_group_id)),
+    })
+}
+
+/// Deletes a user group and waits for it to be gone. Fails if a replication group still uses it.
+pub async fn delete_user_group(client: &aws_sdk_elasticache::Client, user_group_id: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_user_group().user_group_id(user_group_id).send().await?;
+
+    wait_for_status(&format!("user group {}", user_group_id), None, || {
+        user_group_status(client, user_group_id)
+    })
+    .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("user_group_arn"), None)])),
+        friendly_message: Some(format!("Deleted ElastiCache user group {}", user_group_id)),
+    })
+}
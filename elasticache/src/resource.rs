@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::ElastiCacheResourceAddress, tags::Tags};
+
+fn default_auto_minor_version_upgrade() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ReplicationGroup {
+    pub description: String,
+    pub engine: String, // redis or valkey
+    /// If None, the latest version is used at creation, and left alone afterwards.
+    pub engine_version: Option<String>,
+    pub cache_node_type: String,
+    /// Cluster mode: enabled, disabled or compatible.
+    /// Only enabled allows more than one node group (shard).
+    pub cluster_mode: Option<String>,
+    pub num_node_groups: i32,
+    pub replicas_per_node_group: i32,
+    #[serde(default)]
+    pub automatic_failover_enabled: bool,
+    #[serde(default)]
+    pub multi_az_enabled: bool,
+    pub cache_parameter_group_name: Option<String>,
+    /// Can't be changed once the replication group exists.
+    pub cache_subnet_group_name: Option<String>,
+    #[serde(default)]
+    pub security_group_ids: Vec<String>,
+    /// Can't be changed once the replication group exists.
+    pub port: Option<i32>,
+    /// Can't be changed once the replication group exists.
+    #[serde(default)]
+    pub at_rest_encryption_enabled: bool,
+    /// Can't be changed once the replication group exists.
+    pub kms_key_id: Option<String>,
+    #[serde(default)]
+    pub transit_encryption_enabled: bool,
+    /// The ID or ARN of a Secrets Manager secret holding the AUTH token, for password-only Redis AUTH.
+    /// Requires transit encryption. Use `user_group_ids` for role-based access control instead.
+    pub auth_token_secret_id: Option<String>,
+    #[serde(default)]
+    pub user_group_ids: Vec<String>,
+    pub snapshot_retention_limit: Option<i32>,
+    pub snapshot_window: Option<String>,
+    pub preferred_maintenance_window: Option<String>,
+    #[serde(default = "default_auto_minor_version_upgrade")]
+    pub auto_minor_version_upgrade: bool,
+    pub notification_topic_arn: Option<String>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CacheCluster {
+    pub engine_version: Option<String>,
+    pub cache_node_type: String,
+    pub num_cache_nodes: i32,
+    pub az_mode: Option<String>, // single-az or cross-az
+    pub cache_parameter_group_name: Option<String>,
+    /// Can't be changed once the cluster exists.
+    pub cache_subnet_group_name: Option<String>,
+    #[serde(default)]
+    pub security_group_ids: Vec<String>,
+    /// Can't be changed once the cluster exists.
+    pub port: Option<i32>,
+    /// Can't be changed once the cluster exists.
+    #[serde(default)]
+    pub transit_encryption_enabled: bool,
+    pub preferred_maintenance_window: Option<String>,
+    #[serde(default = "default_auto_minor_version_upgrade")]
+    pub auto_minor_version_upgrade: bool,
+    pub notification_topic_arn: Option<String>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ParameterGroup {
+    /// e.g. redis7, valkey8 or memcached1.6. Can't be changed once the parameter group exists.
+    pub family: String,
+    /// Can't be changed once the parameter group exists.
+    pub description: String,
+    /// Only the parameters that differ from the family's defaults.
+    #[serde(default)]
+    pub parameters: BTreeMap<String, String>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SubnetGroup {
+    pub description: String,
+    pub subnet_ids: Vec<String>,
+    pub tags: Tags,
+}
+
+/// How a user signs in. Passwords are never stored in the repository:
+/// they're read from Secrets Manager when the user is created or modified.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum UserAuthentication {
+    NoPassword,
+    Iam,
+    /// The IDs or ARNs of one or two Secrets Manager secrets, each holding a password.
+    /// Two allow a password to be rotated without downtime.
+    Password { secret_ids: Vec<String> },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct User {
+    /// The name clients sign in with. Can't be changed once the user exists.
+    pub user_name: String,
+    pub engine: String, // redis or valkey
+    /// A Redis ACL rule, e.g. "on ~app:* +@read +@write".
+    pub access_string: String,
+    pub authentication: UserAuthentication,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct UserGroup {
+    pub engine: String, // redis or valkey
+    /// Must include a user named "default".
+    pub user_ids: Vec<String>,
+    pub tags: Tags,
+}
+
+pub enum ElastiCacheResource {
+    ReplicationGroup(ReplicationGroup),
+    CacheCluster(CacheCluster),
+    ParameterGroup(ParameterGroup),
+    SubnetGroup(SubnetGroup),
+    User(User),
+    UserGroup(UserGroup),
+}
+
+impl Resource for ElastiCacheResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            ElastiCacheResource::ReplicationGroup(replication_group) => {
+                Ok(RON.to_string_pretty(&replication_group, pretty_config)?.into())
+            }
+            ElastiCacheResource::CacheCluster(cache_cluster) => Ok(RON.to_string_pretty(&cache_cluster, pretty_config)?.into()),
+            ElastiCacheResource::ParameterGroup(parameter_group) => {
+                Ok(RON.to_string_pretty(&parameter_group, pretty_config)?.into())
+            }
+            ElastiCacheResource::SubnetGroup(subnet_group) => Ok(RON.to_string_pretty(&subnet_group, pretty_config)?.into()),
+            ElastiCacheResource::User(user) => Ok(RON.to_string_pretty(&user, pretty_config)?.into()),
+            ElastiCacheResource::UserGroup(user_group) => Ok(RON.to_string_pretty(&user_group, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = ElastiCacheResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            ElastiCacheResourceAddress::ReplicationGroup { .. } => Ok(ElastiCacheResource::ReplicationGroup(RON.from_str(s)?)),
+            ElastiCacheResourceAddress::CacheCluster { .. } => Ok(ElastiCacheResource::CacheCluster(RON.from_str(s)?)),
+            ElastiCacheResourceAddress::ParameterGroup { .. } => Ok(ElastiCacheResource::ParameterGroup(RON.from_str(s)?)),
+            ElastiCacheResourceAddress::SubnetGroup { .. } => Ok(ElastiCacheResource::SubnetGroup(RON.from_str(s)?)),
+            ElastiCacheResourceAddress::User { .. } => Ok(ElastiCacheResource::User(RON.from_str(s)?)),
+            ElastiCacheResourceAddress::UserGroup { .. } => Ok(ElastiCacheResource::UserGroup(RON.from_str(s)?)),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+
+use aws_sdk_elasticache::types::Tag;
+use serde::{Deserialize, Serialize};
+
+// ElastiCache takes tags as a list of Tag structs, with every field optional
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Tags(HashMap<String, String>);
+
+impl From<Option<Vec<Tag>>> for Tags {
+    fn from(value: Option<Vec<Tag>>) -> Self {
+        let mut out_map = HashMap::new();
+        for tag in value.unwrap_or_default() {
+            let (Some(key), Some(value)) = (tag.key, tag.value) else {
+                continue;
+            };
+            out_map.insert(key, value);
+        }
+        Tags(out_map)
+    }
+}
+
+impl Tags {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn to_vec(&self) -> Vec<Tag> {
+        let mut out_vec = Vec::new();
+
+        for (k, v) in &self.0 {
+            out_vec.push(Tag::builder().key(k).value(v).build());
+        }
+
+        out_vec
+    }
+}
+
+// From a pair of hashmaps determine the set of tag keys to remove and tags to add respectively
+pub fn tag_diff(old_tags: &Tags, new_tags: &Tags) -> (Vec<String>, Vec<Tag>) {
+    let mut untag_keys = Vec::new();
+    for k in old_tags.0.keys() {
+        if !new_tags.0.contains_key(k) {
+            untag_keys.push(k.to_string());
+        }
+    }
+
+    let mut new_tagset = Vec::new();
+    for (key, new_value) in &new_tags.0 {
+        if old_tags.0.get(key) != Some(new_value) {
+            new_tagset.push(Tag::builder().key(key).value(new_value).build());
+        }
+    }
+
+    (untag_keys, new_tagset)
+}
@@ -0,0 +1,59 @@
+use anyhow::Context;
+
+/// Stands in for a secret ID when ElastiCache reports a password or AUTH token that this repository didn't set.
+pub const UNKNOWN_SECRET_ID: &str = "[unknown]";
+
+pub const DEFAULT_REDIS_PORT: i32 = 6379;
+pub const DEFAULT_MEMCACHED_PORT: i32 = 11211;
+
+/// The ARN of an ElastiCache resource. `kind` is e.g. replicationgroup, cluster, parametergroup,
+/// subnetgroup, user or usergroup.
+pub fn elasticache_arn(region: &str, account_id: &str, kind: &str, id: &str) -> String {
+    format!("arn:aws:elasticache:{region}:{account_id}:{kind}:{id}")
+}
+
+/// Reads a password or AUTH token from Secrets Manager. The secret must be a plain string, not JSON.
+pub async fn read_secret(client: &aws_sdk_secretsmanager::Client, secret_id: &str) -> anyhow::Result<String> {
+    let resp = client
+        .get_secret_value()
+        .secret_id(secret_id)
+        .send()
+        .await
+        .with_context(|| format!("Failed to read secret {}", secret_id))?;
+    resp.secret_string
+        .with_context(|| format!("Secret {} has no string value", secret_id))
+}
+
+/// Default parameter groups (`default.redis7` and so on) can't be changed, so they're reported as None.
+pub fn non_default_parameter_group(name: Option<&str>) -> Option<String> {
+    name.filter(|name| !name.starts_with("default.")).map(String::from)
+}
+
+/// Secret IDs are stored in outputs as a comma-separated list.
+pub fn join_secret_ids(secret_ids: &[String]) -> String {
+    secret_ids.join(",")
+}
+
+pub fn split_secret_ids(secret_ids: &str) -> Vec<String> {
+    secret_ids.split(',').filter(|s| !s.is_empty()).map(String::from).collect()
+}
+
+/// The IDs in `new` that aren't in `old`, and the IDs in `old` that aren't in `new`.
+pub fn id_diff(old: &[String], new: &[String]) -> (Vec<String>, Vec<String>) {
+    let added = new.iter().filter(|id| !old.contains(id)).cloned().collect();
+    let removed = old.iter().filter(|id| !new.contains(id)).cloned().collect();
+    (added, removed)
+}
+
+/// The address clients connect to: the configuration endpoint with cluster mode enabled, or else the primary endpoint.
+pub fn replication_group_endpoint(replication_group: &aws_sdk_elasticache::types::ReplicationGroup) -> Option<String> {
+    let endpoint = replication_group
+        .configuration_endpoint()
+        .or_else(|| replication_group.node_groups().first().and_then(|ng| ng.primary_endpoint()))?;
+    Some(format!("{}:{}", endpoint.address()?, endpoint.port()?))
+}
+
+pub fn cache_cluster_endpoint(cache_cluster: &aws_sdk_elasticache::types::CacheCluster) -> Option<String> {
+    let endpoint = cache_cluster.configuration_endpoint()?;
+    Some(format!("{}:{}", endpoint.address()?, endpoint.port()?))
+}
@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
 use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 
 type Region = String;
@@ -11,6 +12,7 @@ type TargetGroupName = String;
 pub enum ElbResourceAddress {
     LoadBalancer(Region, LoadBalancerName),         // (region, load_balancer_name)
     TargetGroup(Region, TargetGroupName),           // (region, target_group_name)
+    TargetGroupTargets(Region, TargetGroupName),    // (region, target_group_name)
     Listener(Region, LoadBalancerName, ListenerId), // (region, load_balancer_name, listener_id)
 }
 
@@ -23,6 +25,9 @@ impl ResourceAddress for ElbResourceAddress {
             ElbResourceAddress::TargetGroup(region, name) => {
                 PathBuf::from(format!("aws/elb/{region}/target_groups/{name}.ron"))
             }
+            ElbResourceAddress::TargetGroupTargets(region, name) => {
+                PathBuf::from(format!("aws/elb/{region}/target_groups/{name}/targets.ron"))
+            }
             ElbResourceAddress::Listener(region, lb_name, listener_id) => PathBuf::from(format!(
                 "aws/elb/{region}/load_balancers/{lb_name}/listeners/{listener_id}.ron"
             )),
@@ -41,6 +46,9 @@ impl ResourceAddress for ElbResourceAddress {
                 let name = name.strip_suffix(".ron").unwrap().to_string();
                 Ok(ElbResourceAddress::TargetGroup(region.to_string(), name))
             }
+            ["aws", "elb", region, "target_groups", name, "targets.ron"] => {
+                Ok(ElbResourceAddress::TargetGroupTargets(region.to_string(), name.to_string()))
+            }
             ["aws", "elb", region, "load_balancers", lb_name, "listeners", listener_id] if listener_id.ends_with(".ron") => {
                 let listener_id = listener_id.strip_suffix(".ron").unwrap().to_string();
                 Ok(ElbResourceAddress::Listener(
@@ -53,3 +61,42 @@ impl ResourceAddress for ElbResourceAddress {
         }
     }
 }
+
+impl DescribeAddresses for ElbResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/elb/<region>/load_balancers/<name>.ron",
+                description: "An Application or Network Load Balancer",
+                example:     "aws/elb/us-east-1/load_balancers/web.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/elb/<region>/load_balancers/<name>/listeners/<listener_id>.ron",
+                description: "A load balancer's listener",
+                example:     "aws/elb/us-east-1/load_balancers/web/listeners/https.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/elb/<region>/target_groups/<name>.ron",
+                description: "A target group",
+                example:     "aws/elb/us-east-1/target_groups/web.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/elb/<region>/target_groups/<name>/targets.ron",
+                description: "The targets registered with a target group",
+                example:     "aws/elb/us-east-1/target_groups/web/targets.ron",
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use autoschematic_connector_aws_core::addr_doc::DescribeAddresses;
+
+    use super::ElbResourceAddress;
+
+    #[test]
+    fn address_examples_parse() {
+        ElbResourceAddress::checked_address_patterns().unwrap();
+    }
+}
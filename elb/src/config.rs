@@ -8,6 +8,16 @@ use autoschematic_connector_aws_core::{
     impl_aws_config,
 };
 
+/// What plan does when asked to delete a load balancer that has deletion protection enabled.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+pub enum DeletionProtectionPolicy {
+    /// Refuse to plan the deletion until deletion protection has been turned off.
+    #[default]
+    Block,
+    /// Plan two steps: turn deletion protection off, then delete.
+    DisableThenDelete,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ElbConnectorConfig {
     pub account_id:      Option<String>,
@@ -15,6 +25,10 @@ pub struct ElbConnectorConfig {
     pub timeout_config:  Option<TimeoutConfig>,
     pub sts_region:      String,
     pub enabled_regions: Vec<String>,
+    #[serde(default)]
+    pub max_concurrent_ops: Option<usize>,
+    #[serde(default)]
+    pub deletion_protection_policy: DeletionProtectionPolicy,
 }
 
-impl_aws_config!(ElbConnectorConfig, "aws/elb/config.ron");
+impl_aws_config!(ElbConnectorConfig, "aws/elb/config.ron", deletion_protection_policy);
@@ -1,6 +1,6 @@
 pub use crate::addr::ElbResourceAddress;
 pub use crate::resource::ElbResource;
-use crate::resource::{self, Action, Certificate, HealthCheck, Listener, LoadBalancer, TargetGroup};
+use crate::resource::{self, Action, Certificate, HealthCheck, Listener, LoadBalancer, Target, TargetGroup, TargetGroupAttributes, TargetGroupTargets};
 use crate::tags::Tags;
 
 use std::{
@@ -30,11 +30,39 @@ use autoschematic_core::{
 use aws_config::{BehaviorVersion, Region, meta::region::RegionProviderChain, timeout::TimeoutConfig};
 use tokio::sync::Mutex;
 
-use autoschematic_connector_aws_core::config::AwsServiceConfig;
+use autoschematic_connector_aws_core::{
+    audit::AuditLog,
+    audited_client,
+    cascade::{ConsumedOutput, forget_replacements, note_stale_references, resolve_stale_references},
+    concurrency::OpExecLimiter,
+    config::AwsServiceConfig,
+};
+
+/// The outputs of other resources that this connector's resources embed by value.
+/// Load balancers name their subnets and security groups, and target groups their VPC.
+pub const CONSUMED_OUTPUTS: &[ConsumedOutput] = &[
+    ConsumedOutput {
+        producer: "aws/vpc/",
+        key:      "vpc_id",
+        fields:   &["vpc_id"],
+    },
+    ConsumedOutput {
+        producer: "aws/vpc/",
+        key:      "subnet_id",
+        fields:   &["subnets"],
+    },
+    ConsumedOutput {
+        producer: "aws/vpc/",
+        key:      "security_group_id",
+        fields:   &["security_groups"],
+    },
+];
 
 #[derive(Default)]
 pub struct ElbConnector {
     client_cache: Mutex<HashMap<String, Arc<aws_sdk_elasticloadbalancingv2::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
     account_id: Mutex<String>,
     config: Mutex<ElbConnectorConfig>,
     prefix: PathBuf,
@@ -59,7 +87,7 @@ impl ElbConnector {
                 )
                 .load()
                 .await;
-            let client = aws_sdk_elasticloadbalancingv2::Client::new(&config);
+            let client = audited_client!(aws_sdk_elasticloadbalancingv2, &config);
             cache.insert(region_s.to_string(), Arc::new(client));
         }
 
@@ -97,6 +125,9 @@ impl Connector for ElbConnector {
         let account_id = elb_config.verify_sts().await?;
 
         *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(elb_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        forget_replacements(&self.prefix);
         *self.config.lock().await = elb_config;
         *self.account_id.lock().await = account_id;
         Ok(())
@@ -116,11 +147,15 @@ impl Connector for ElbConnector {
         current: Option<Vec<u8>>,
         desired: Option<Vec<u8>>,
     ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
-        self.do_plan(addr, current, desired).await
+        let (desired, stale_note) = resolve_stale_references(&self.prefix, CONSUMED_OUTPUTS, desired)?;
+        let ops = self.do_plan(addr, current, desired).await?;
+        Ok(note_stale_references(ops, &stale_note))
     }
 
     async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
-        self.do_op_exec(addr, op).await
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
     }
 
     async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
@@ -139,6 +174,13 @@ impl Connector for ElbConnector {
                 security_groups: vec![String::from("[security_group_id]")],
                 subnets: vec![String::from("[subnet_id_1]"), String::from("[subnet_id_2]")],
                 ip_address_type: String::from("ipv4"),
+                deletion_protection: false,
+                access_logs: None,
+                connection_logs: None,
+                idle_timeout_seconds: None,
+                http2_enabled: None,
+                drop_invalid_header_fields: None,
+                desync_mitigation_mode: None,
                 tags: Tags::default(),
             })
         ));
@@ -146,7 +188,7 @@ impl Connector for ElbConnector {
         // Target Group
         let tg_name = String::from("[target_group_name]");
         res.push(skeleton!(
-            ElbResourceAddress::TargetGroup(region.clone(), tg_name),
+            ElbResourceAddress::TargetGroup(region.clone(), tg_name.clone()),
             ElbResource::TargetGroup(TargetGroup {
                 protocol: String::from("HTTP"),
                 port: Some(80),
@@ -161,12 +203,28 @@ impl Connector for ElbConnector {
                     timeout_seconds: 5,
                     healthy_threshold_count: 2,
                     unhealthy_threshold_count: 5,
+                    matcher: Some(String::from("200")),
                 }),
-                targets: vec![String::from("[instance_id]")],
+                attributes: TargetGroupAttributes {
+                    deregistration_delay_seconds: Some(30),
+                    ..Default::default()
+                },
                 tags: Tags::default(),
             })
         ));
 
+        // Instances registered with the target group
+        res.push(skeleton!(
+            ElbResourceAddress::TargetGroupTargets(region.clone(), tg_name),
+            ElbResource::TargetGroupTargets(TargetGroupTargets {
+                targets: vec![Target {
+                    id: String::from("[instance_id]"),
+                    port: None,
+                    availability_zone: None,
+                }],
+            })
+        ));
+
         // HTTPS Listener
         let listener_id = String::from("[listener_id]");
         res.push(skeleton!(
@@ -201,6 +259,13 @@ impl Connector for ElbConnector {
                 security_groups: vec![],
                 subnets: vec![String::from("[subnet_id_1]"), String::from("[subnet_id_2]")],
                 ip_address_type: String::from("ipv4"),
+                deletion_protection: false,
+                access_logs: None,
+                connection_logs: None,
+                idle_timeout_seconds: None,
+                http2_enabled: None,
+                drop_invalid_header_fields: None,
+                desync_mitigation_mode: None,
                 tags: Tags::default(),
             })
         ));
@@ -208,7 +273,7 @@ impl Connector for ElbConnector {
         // TCP Target Group for Network Load Balancer
         let tcp_tg_name = String::from("[tcp_target_group_name]");
         res.push(skeleton!(
-            ElbResourceAddress::TargetGroup(region.clone(), tcp_tg_name),
+            ElbResourceAddress::TargetGroup(region.clone(), tcp_tg_name.clone()),
             ElbResource::TargetGroup(TargetGroup {
                 protocol: String::from("TCP"),
                 port: Some(80),
@@ -223,12 +288,25 @@ impl Connector for ElbConnector {
                     timeout_seconds: 10,
                     healthy_threshold_count: 3,
                     unhealthy_threshold_count: 3,
+                    matcher: None,
                 }),
-                targets: vec![String::from("[ip_address]:80")],
+                attributes: TargetGroupAttributes::default(),
                 tags: Tags::default(),
             })
         ));
 
+        // IP addresses registered with the TCP target group
+        res.push(skeleton!(
+            ElbResourceAddress::TargetGroupTargets(region.clone(), tcp_tg_name),
+            ElbResource::TargetGroupTargets(TargetGroupTargets {
+                targets: vec![Target {
+                    id: String::from("[ip_address]"),
+                    port: Some(80),
+                    availability_zone: None,
+                }],
+            })
+        ));
+
         // TCP Listener for Network Load Balancer
         let tcp_listener_id = String::from("[tcp_listener_id]");
         res.push(skeleton!(
@@ -258,6 +336,7 @@ impl Connector for ElbConnector {
         match addr {
             ElbResourceAddress::LoadBalancer(_, _) => ron_check_eq::<resource::LoadBalancer>(a, b),
             ElbResourceAddress::TargetGroup(_, _) => ron_check_eq::<resource::TargetGroup>(a, b),
+            ElbResourceAddress::TargetGroupTargets(_, _) => ron_check_eq::<resource::TargetGroupTargets>(a, b),
             ElbResourceAddress::Listener(_, _, _) => ron_check_eq::<resource::Listener>(a, b),
         }
     }
@@ -268,6 +347,7 @@ impl Connector for ElbConnector {
         match addr {
             ElbResourceAddress::LoadBalancer(_, _) => ron_check_syntax::<resource::LoadBalancer>(a),
             ElbResourceAddress::TargetGroup(_, _) => ron_check_syntax::<resource::TargetGroup>(a),
+            ElbResourceAddress::TargetGroupTargets(_, _) => ron_check_syntax::<resource::TargetGroupTargets>(a),
             ElbResourceAddress::Listener(_, _, _) => ron_check_syntax::<resource::Listener>(a),
         }
     }
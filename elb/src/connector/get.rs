@@ -1,10 +1,11 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 use autoschematic_core::connector::{GetResourceResponse, Resource, ResourceAddress};
 
 use crate::{
     addr::ElbResourceAddress,
     resource::{self, ElbResource, FixedResponseConfig, RedirectConfig},
+    util::{listener_arn, load_balancer_attribute_values, s3_logging_from_aws, target_group_attributes_from_aws},
 };
 
 use super::ElbConnector;
@@ -48,6 +49,19 @@ impl ElbConnector {
                     Default::default()
                 };
 
+                let attributes = if let Some(lb_arn) = &lb.load_balancer_arn {
+                    let attributes_resp = client
+                        .describe_load_balancer_attributes()
+                        .load_balancer_arn(lb_arn)
+                        .send()
+                        .await?;
+
+                    load_balancer_attribute_values(attributes_resp.attributes())
+                } else {
+                    HashMap::new()
+                };
+                let enabled = |key: &str| attributes.get(key).map(|value| value == "true");
+
                 let lb_resource = resource::LoadBalancer {
                     load_balancer_type: lb
                         .r#type
@@ -67,6 +81,15 @@ impl ElbConnector {
                         .ip_address_type
                         .as_ref()
                         .map_or_else(|| "ipv4".to_string(), |t| t.as_str().to_string()),
+                    deletion_protection: enabled("deletion_protection.enabled").unwrap_or(false),
+                    access_logs: s3_logging_from_aws("access_logs", &attributes),
+                    connection_logs: s3_logging_from_aws("connection_logs", &attributes),
+                    idle_timeout_seconds: attributes
+                        .get("idle_timeout.timeout_seconds")
+                        .and_then(|value| value.parse().ok()),
+                    http2_enabled: enabled("routing.http2.enabled"),
+                    drop_invalid_header_fields: enabled("routing.http.drop_invalid_header_fields.enabled"),
+                    desync_mitigation_mode: attributes.get("routing.http.desync_mitigation_mode").cloned(),
                     tags,
                 };
 
@@ -78,7 +101,6 @@ impl ElbConnector {
             }
             ElbResourceAddress::TargetGroup(region, target_group_name) => {
                 let client = self.get_or_init_client(&region).await?;
-                
 
                 // TODO can target groups have the same name with a different ARN? I sure as hell think they can!
                 let Ok(target_groups_resp) = client.describe_target_groups().names(&target_group_name).send().await else {
@@ -110,20 +132,6 @@ impl ElbConnector {
                     Default::default()
                 };
 
-                // Get the registered targets
-                let registered_targets = if let Some(tg_arn) = &tg.target_group_arn {
-                    let targets_resp = client.describe_target_health().target_group_arn(tg_arn).send().await?;
-
-                    targets_resp.target_health_descriptions.map_or_else(Vec::new, |descriptions| {
-                        descriptions
-                            .iter()
-                            .filter_map(|desc| desc.target.as_ref().and_then(|target| target.id.clone()))
-                            .collect()
-                    })
-                } else {
-                    Vec::new()
-                };
-
                 // Construct health check
                 let health_check = tg.health_check_protocol.as_ref().map(|protocol| resource::HealthCheck {
                         enabled: tg.health_check_enabled.unwrap_or(false),
@@ -134,8 +142,16 @@ impl ElbConnector {
                         timeout_seconds: tg.health_check_timeout_seconds.unwrap_or(5),
                         healthy_threshold_count: tg.healthy_threshold_count.unwrap_or(5),
                         unhealthy_threshold_count: tg.unhealthy_threshold_count.unwrap_or(2),
+                        matcher: tg.matcher.as_ref().and_then(|matcher| matcher.http_code.clone()),
                     });
 
+                let attributes = if let Some(tg_arn) = &tg.target_group_arn {
+                    let attributes_resp = client.describe_target_group_attributes().target_group_arn(tg_arn).send().await?;
+                    target_group_attributes_from_aws(attributes_resp.attributes())
+                } else {
+                    Default::default()
+                };
+
                 // Construct the TargetGroup resource
                 let tg_resource = resource::TargetGroup {
                     protocol: tg.protocol().map_or_else(|| "HTTP".to_string(), |p| p.as_str().to_string()),
@@ -145,7 +161,7 @@ impl ElbConnector {
                         .target_type()
                         .map_or_else(|| "instance".to_string(), |t| t.as_str().to_string()),
                     health_check,
-                    targets: registered_targets,
+                    attributes,
                     tags,
                 };
 
@@ -155,6 +171,48 @@ impl ElbConnector {
                     outputs: None,
                 }))
             }
+            ElbResourceAddress::TargetGroupTargets(region, target_group_name) => {
+                let client = self.get_or_init_client(&region).await?;
+
+                let Ok(target_groups_resp) = client.describe_target_groups().names(&target_group_name).send().await else {
+                    return Ok(None);
+                };
+
+                let Some(tg) = target_groups_resp.target_groups().first() else {
+                    return Ok(None);
+                };
+
+                let Some(tg_arn) = &tg.target_group_arn else {
+                    return Ok(None);
+                };
+
+                // Every registered target is reported, including those registered by hand, so that they show up as drift
+                let targets_resp = client.describe_target_health().target_group_arn(tg_arn).send().await?;
+                let mut targets: Vec<resource::Target> = targets_resp
+                    .target_health_descriptions()
+                    .iter()
+                    .filter_map(|desc| desc.target.as_ref())
+                    .filter_map(|target| {
+                        Some(resource::Target {
+                            id: target.id.clone()?,
+                            // Targets on the target group's own port are written without one
+                            port: target.port.filter(|port| Some(*port) != tg.port),
+                            availability_zone: target.availability_zone.clone(),
+                        })
+                    })
+                    .collect();
+
+                if targets.is_empty() {
+                    return Ok(None);
+                }
+                targets.sort_by(|a, b| (&a.id, a.port).cmp(&(&b.id, b.port)));
+
+                Ok(Some(GetResourceResponse {
+                    resource_definition: ElbResource::TargetGroupTargets(resource::TargetGroupTargets { targets }).to_bytes()?,
+                    virt_addr: None,
+                    outputs: None,
+                }))
+            }
             ElbResourceAddress::Listener(region, load_balancer_name, listener_id) => {
                 let client = self.get_or_init_client(&region).await?;
 
@@ -175,8 +233,7 @@ impl ElbConnector {
                     return Ok(None);
                 };
 
-                // Now, reconstruct the full listener ARN
-                let listener_arn = format!("{lb_arn}/listener/{listener_id}");
+                let listener_arn = listener_arn(lb_arn, &listener_id);
 
                 // Find the specific listener
                 let Ok(listeners_resp) = client.describe_listeners().listener_arns(&listener_arn).send().await else {
@@ -243,7 +300,7 @@ impl ElbConnector {
                                     port: redirect_config.port.clone(),
                                     protocol: redirect_config.protocol.clone(),
                                     query: redirect_config.query.clone(),
-                                    status_code: redirect_config.status_code.as_ref().map(|s| s.to_string()),
+                                    status_code: redirect_config.status_code.as_ref().map(|s| s.as_str().to_string()),
                                 })
                             } else {
                                 None
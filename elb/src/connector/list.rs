@@ -55,6 +55,17 @@ impl ElbConnector {
                 for tg in target_groups {
                     if let Some(tg_name) = &tg.target_group_name {
                         results.push(ElbResourceAddress::TargetGroup(region_name.clone(), tg_name.clone()).to_path_buf());
+
+                        // List the registered targets, if there are any
+                        if let Some(tg_arn) = &tg.target_group_arn {
+                            let targets_resp = client.describe_target_health().target_group_arn(tg_arn).send().await?;
+
+                            if !targets_resp.target_health_descriptions().is_empty() {
+                                results.push(
+                                    ElbResourceAddress::TargetGroupTargets(region_name.clone(), tg_name.clone()).to_path_buf(),
+                                );
+                            }
+                        }
                     }
                 }
             }
@@ -7,14 +7,165 @@ use autoschematic_core::{
     op_exec_output,
 };
 use aws_sdk_elasticloadbalancingv2::types::{
-    Action as AwsAction, ActionTypeEnum, IpAddressType, LoadBalancerSchemeEnum, LoadBalancerTypeEnum, ProtocolEnum,
-    TargetTypeEnum,
+    Action as AwsAction, ActionTypeEnum, Certificate as AwsCertificate, IpAddressType, LoadBalancerAttribute, LoadBalancerSchemeEnum, LoadBalancerTypeEnum,
+    Matcher, ProtocolEnum, TargetDescription, TargetTypeEnum,
 };
 
-use crate::{addr::ElbResourceAddress, op::ElbConnectorOp, tags::tag_diff};
+use crate::{
+    addr::ElbResourceAddress,
+    op::ElbConnectorOp,
+    resource::{Action, Certificate, Target},
+    tags::{Tags, tag_diff},
+    util::{
+        listener_arn, load_balancer_attribute, load_balancer_attributes_to_aws, s3_logging_to_aws,
+        target_group_attributes_to_aws,
+    },
+};
 
 use super::ElbConnector;
 
+async fn set_deletion_protection(
+    client: &aws_sdk_elasticloadbalancingv2::Client,
+    lb_arn: &str,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    client
+        .modify_load_balancer_attributes()
+        .load_balancer_arn(lb_arn)
+        .attributes(
+            LoadBalancerAttribute::builder()
+                .key("deletion_protection.enabled")
+                .value(enabled.to_string())
+                .build(),
+        )
+        .send()
+        .await?;
+    Ok(())
+}
+
+async fn find_load_balancer_arn(client: &aws_sdk_elasticloadbalancingv2::Client, lb_name: &str) -> anyhow::Result<String> {
+    let response = client.describe_load_balancers().names(lb_name).send().await?;
+
+    response
+        .load_balancers()
+        .first()
+        .and_then(|lb| lb.load_balancer_arn().map(|s| s.to_string()))
+        .context("Load balancer not found")
+}
+
+async fn set_load_balancer_attributes(
+    client: &aws_sdk_elasticloadbalancingv2::Client,
+    lb_name: &str,
+    attributes: Vec<LoadBalancerAttribute>,
+) -> anyhow::Result<()> {
+    let lb_arn = find_load_balancer_arn(client, lb_name).await?;
+
+    client
+        .modify_load_balancer_attributes()
+        .load_balancer_arn(lb_arn)
+        .set_attributes(Some(attributes))
+        .send()
+        .await?;
+    Ok(())
+}
+
+async fn find_target_group_arn(client: &aws_sdk_elasticloadbalancingv2::Client, tg_name: &str) -> anyhow::Result<String> {
+    let response = client.describe_target_groups().names(tg_name).send().await?;
+
+    response
+        .target_groups()
+        .first()
+        .and_then(|tg| tg.target_group_arn().map(|s| s.to_string()))
+        .context("Target group not found")
+}
+
+fn aws_actions(actions: &[Action]) -> Vec<AwsAction> {
+    actions
+        .iter()
+        .map(|action| {
+            let mut aws_action = AwsAction::builder().r#type(match action.action_type.as_str() {
+                "forward" => ActionTypeEnum::Forward,
+                "redirect" => ActionTypeEnum::Redirect,
+                "fixed-response" => ActionTypeEnum::FixedResponse,
+                _ => ActionTypeEnum::Forward,
+            });
+
+            if let Some(ref target_group_arn) = action.target_group_arn {
+                aws_action = aws_action.target_group_arn(target_group_arn);
+            }
+
+            aws_action.build()
+        })
+        .collect()
+}
+
+fn aws_certificates(certificates: &[Certificate]) -> Vec<AwsCertificate> {
+    certificates
+        .iter()
+        .map(|c| AwsCertificate::builder().certificate_arn(c.certificate_arn.clone()).build())
+        .collect()
+}
+
+/// The security groups the load balancer has now.
+async fn current_security_groups(client: &aws_sdk_elasticloadbalancingv2::Client, lb_name: &str) -> anyhow::Result<Vec<String>> {
+    let response = client.describe_load_balancers().names(lb_name).send().await?;
+
+    let lb = response.load_balancers().first().context("Load balancer not found")?;
+    Ok(lb.security_groups().to_vec())
+}
+
+async fn set_security_groups(
+    client: &aws_sdk_elasticloadbalancingv2::Client,
+    lb_name: &str,
+    security_groups: Vec<String>,
+) -> anyhow::Result<()> {
+    let lb_arn = find_load_balancer_arn(client, lb_name).await?;
+
+    client
+        .set_security_groups()
+        .load_balancer_arn(lb_arn)
+        .set_security_groups(Some(security_groups))
+        .send()
+        .await?;
+    Ok(())
+}
+
+async fn update_resource_tags(
+    client: &aws_sdk_elasticloadbalancingv2::Client,
+    arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<()> {
+    let (remove_keys, add_tags) = tag_diff(old_tags, new_tags)?;
+
+    if !remove_keys.is_empty() {
+        client
+            .remove_tags()
+            .resource_arns(arn)
+            .set_tag_keys(Some(remove_keys))
+            .send()
+            .await?;
+    }
+
+    if !add_tags.is_empty() {
+        client.add_tags().resource_arns(arn).set_tags(Some(add_tags)).send().await?;
+    }
+    Ok(())
+}
+
+fn target_descriptions(targets: &[Target]) -> anyhow::Result<Vec<TargetDescription>> {
+    targets
+        .iter()
+        .map(|target| {
+            Ok(TargetDescription::builder()
+                .id(&target.id)
+                .set_port(target.port)
+                .set_availability_zone(target.availability_zone.clone())
+                .build()?)
+        })
+        .collect()
+}
+
 impl ElbConnector {
     pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
         let addr = ElbResourceAddress::from_path(addr)?;
@@ -41,7 +192,7 @@ impl ElbConnector {
                             .ip_address_type(IpAddressType::from_str(&lb.ip_address_type)?);
 
                         if lb.tags.len() > 0 {
-                            request = request.set_tags(lb.tags.into());
+                            request = request.set_tags(lb.tags.clone().into());
                         }
 
                         let response = request.send().await?;
@@ -58,6 +209,20 @@ impl ElbConnector {
                             .and_then(|lb| lb.dns_name().map(|s| s.to_string()))
                             .context("Failed to get DNS name from response")?;
 
+                        if lb.deletion_protection {
+                            set_deletion_protection(&client, &lb_arn, true).await?;
+                        }
+
+                        let attributes = load_balancer_attributes_to_aws(&lb);
+                        if !attributes.is_empty() {
+                            client
+                                .modify_load_balancer_attributes()
+                                .load_balancer_arn(&lb_arn)
+                                .set_attributes(Some(attributes))
+                                .send()
+                                .await?;
+                        }
+
                         op_exec_output!(
                             Some([
                                 ("load_balancer_arn", Some(lb_arn)),
@@ -99,6 +264,126 @@ impl ElbConnector {
 
                         op_exec_output!(format!("Updated tags for load balancer `{}`", lb_name))
                     }
+                    ElbConnectorOp::UpdateDeletionProtection { enabled } => {
+                        let response = client.describe_load_balancers().names(lb_name.clone()).send().await?;
+
+                        let lb_arn = response
+                            .load_balancers()
+                            .first()
+                            .and_then(|lb| lb.load_balancer_arn())
+                            .context("Load balancer not found")?;
+
+                        set_deletion_protection(&client, lb_arn, enabled).await?;
+
+                        if enabled {
+                            op_exec_output!(format!("Enabled deletion protection for load balancer `{}`", lb_name))
+                        } else {
+                            op_exec_output!(format!("Disabled deletion protection for load balancer `{}`", lb_name))
+                        }
+                    }
+                    ElbConnectorOp::UpdateAccessLogs(access_logs) => {
+                        set_load_balancer_attributes(&client, lb_name, s3_logging_to_aws("access_logs", &access_logs)).await?;
+                        op_exec_output!(format!("Updated access logs for load balancer `{}`", lb_name))
+                    }
+                    ElbConnectorOp::UpdateConnectionLogs(connection_logs) => {
+                        set_load_balancer_attributes(&client, lb_name, s3_logging_to_aws("connection_logs", &connection_logs))
+                            .await?;
+                        op_exec_output!(format!("Updated connection logs for load balancer `{}`", lb_name))
+                    }
+                    ElbConnectorOp::UpdateIdleTimeout { seconds } => {
+                        set_load_balancer_attributes(
+                            &client,
+                            lb_name,
+                            vec![load_balancer_attribute("idle_timeout.timeout_seconds", seconds)],
+                        )
+                        .await?;
+                        op_exec_output!(format!("Set idle timeout for load balancer `{}` to {}s", lb_name, seconds))
+                    }
+                    ElbConnectorOp::UpdateHttp2 { enabled } => {
+                        set_load_balancer_attributes(&client, lb_name, vec![load_balancer_attribute("routing.http2.enabled", enabled)])
+                            .await?;
+                        op_exec_output!(format!(
+                            "{} HTTP/2 for load balancer `{}`",
+                            if enabled { "Enabled" } else { "Disabled" },
+                            lb_name
+                        ))
+                    }
+                    ElbConnectorOp::UpdateDropInvalidHeaderFields { enabled } => {
+                        set_load_balancer_attributes(
+                            &client,
+                            lb_name,
+                            vec![load_balancer_attribute("routing.http.drop_invalid_header_fields.enabled", enabled)],
+                        )
+                        .await?;
+                        op_exec_output!(format!(
+                            "{} dropping invalid header fields for load balancer `{}`",
+                            if enabled { "Enabled" } else { "Disabled" },
+                            lb_name
+                        ))
+                    }
+                    ElbConnectorOp::UpdateDesyncMitigationMode { mode } => {
+                        set_load_balancer_attributes(
+                            &client,
+                            lb_name,
+                            vec![load_balancer_attribute("routing.http.desync_mitigation_mode", &mode)],
+                        )
+                        .await?;
+                        op_exec_output!(format!("Set desync mitigation mode for load balancer `{}` to {}", lb_name, mode))
+                    }
+                    ElbConnectorOp::AddSecurityGroups { security_group_ids } => {
+                        // ELB only sets the full list, so add to the groups the load balancer has now
+                        let mut security_groups = current_security_groups(&client, lb_name).await?;
+                        for sg in &security_group_ids {
+                            if !security_groups.contains(sg) {
+                                security_groups.push(sg.clone());
+                            }
+                        }
+                        set_security_groups(&client, lb_name, security_groups).await?;
+
+                        op_exec_output!(format!(
+                            "Added security groups {} to load balancer `{}`",
+                            security_group_ids.join(", "),
+                            lb_name
+                        ))
+                    }
+                    ElbConnectorOp::RemoveSecurityGroups { security_group_ids } => {
+                        let security_groups: Vec<String> = current_security_groups(&client, lb_name)
+                            .await?
+                            .into_iter()
+                            .filter(|sg| !security_group_ids.contains(sg))
+                            .collect();
+                        set_security_groups(&client, lb_name, security_groups).await?;
+
+                        op_exec_output!(format!(
+                            "Removed security groups {} from load balancer `{}`",
+                            security_group_ids.join(", "),
+                            lb_name
+                        ))
+                    }
+                    ElbConnectorOp::UpdateIpAddressType { ip_address_type } => {
+                        let lb_arn = find_load_balancer_arn(&client, lb_name).await?;
+
+                        client
+                            .set_ip_address_type()
+                            .load_balancer_arn(lb_arn)
+                            .ip_address_type(IpAddressType::from_str(&ip_address_type)?)
+                            .send()
+                            .await?;
+
+                        op_exec_output!(format!("Set IP address type for load balancer `{}` to {}", lb_name, ip_address_type))
+                    }
+                    ElbConnectorOp::UpdateSubnets { subnets } => {
+                        let lb_arn = find_load_balancer_arn(&client, lb_name).await?;
+
+                        client
+                            .set_subnets()
+                            .load_balancer_arn(lb_arn)
+                            .set_subnets(Some(subnets.clone()))
+                            .send()
+                            .await?;
+
+                        op_exec_output!(format!("Set subnets for load balancer `{}` to {}", lb_name, subnets.join(", ")))
+                    }
                     ElbConnectorOp::DeleteLoadBalancer => {
                         let response = client.describe_load_balancers().names(lb_name.clone()).send().await?;
 
@@ -138,6 +423,10 @@ impl ElbConnector {
                                 .health_check_timeout_seconds(health_check.timeout_seconds)
                                 .healthy_threshold_count(health_check.healthy_threshold_count)
                                 .unhealthy_threshold_count(health_check.unhealthy_threshold_count);
+
+                            if let Some(ref matcher) = health_check.matcher {
+                                request = request.matcher(Matcher::builder().http_code(matcher).build());
+                            }
                         }
 
                         if tg.tags.len() > 0 {
@@ -192,6 +481,12 @@ impl ElbConnector {
 
                         client
                             .modify_target_group()
+                            .set_matcher(
+                                health_check
+                                    .matcher
+                                    .as_ref()
+                                    .map(|matcher| Matcher::builder().http_code(matcher).build()),
+                            )
                             .target_group_arn(target_group_arn)
                             .health_check_enabled(health_check.enabled)
                             .health_check_interval_seconds(health_check.interval_seconds)
@@ -205,6 +500,55 @@ impl ElbConnector {
                             .await?;
                         op_exec_output!(format!("Updated health check for target group `{}`", tg_name))
                     }
+                    ElbConnectorOp::UpdateTargetGroupTags(old_tags, new_tags) => {
+                        let tg_arn = find_target_group_arn(&client, tg_name).await?;
+                        update_resource_tags(&client, &tg_arn, &old_tags, &new_tags).await?;
+
+                        op_exec_output!(format!("Updated tags for target group `{}`", tg_name))
+                    }
+                    ElbConnectorOp::UpdateTargetGroupAttributes(attributes) => {
+                        let tg_arn = find_target_group_arn(&client, tg_name).await?;
+
+                        client
+                            .modify_target_group_attributes()
+                            .target_group_arn(&tg_arn)
+                            .set_attributes(Some(target_group_attributes_to_aws(&attributes)))
+                            .send()
+                            .await?;
+
+                        op_exec_output!(format!("Updated attributes for target group `{}`", tg_name))
+                    }
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            ElbResourceAddress::TargetGroupTargets(region, tg_name) => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    ElbConnectorOp::RegisterTargets { targets } => {
+                        let tg_arn = find_target_group_arn(&client, tg_name).await?;
+
+                        client
+                            .register_targets()
+                            .target_group_arn(&tg_arn)
+                            .set_targets(Some(target_descriptions(&targets)?))
+                            .send()
+                            .await?;
+
+                        op_exec_output!(format!("Registered {} targets with target group `{}`", targets.len(), tg_name))
+                    }
+                    ElbConnectorOp::DeregisterTargets { targets } => {
+                        let tg_arn = find_target_group_arn(&client, tg_name).await?;
+
+                        client
+                            .deregister_targets()
+                            .target_group_arn(&tg_arn)
+                            .set_targets(Some(target_descriptions(&targets)?))
+                            .send()
+                            .await?;
+
+                        op_exec_output!(format!("Deregistered {} targets from target group `{}`", targets.len(), tg_name))
+                    }
                     _ => Err(invalid_op(&addr, &op)),
                 }
             }
@@ -229,27 +573,8 @@ impl ElbConnector {
                             .protocol(ProtocolEnum::from_str(&listener.protocol)?)
                             .set_ssl_policy(listener.ssl_policy.clone());
 
-                        // Convert actions
                         if !listener.default_actions.is_empty() {
-                            let aws_actions: Vec<AwsAction> = listener
-                                .default_actions
-                                .iter()
-                                .map(|action| {
-                                    let mut aws_action = AwsAction::builder().r#type(match action.action_type.as_str() {
-                                        "forward" => ActionTypeEnum::Forward,
-                                        "redirect" => ActionTypeEnum::Redirect,
-                                        "fixed-response" => ActionTypeEnum::FixedResponse,
-                                        _ => ActionTypeEnum::Forward,
-                                    });
-
-                                    if let Some(ref target_group_arn) = action.target_group_arn {
-                                        aws_action = aws_action.target_group_arn(target_group_arn);
-                                    }
-
-                                    aws_action.build()
-                                })
-                                .collect();
-                            request = request.set_default_actions(Some(aws_actions));
+                            request = request.set_default_actions(Some(aws_actions(&listener.default_actions)));
                         }
 
                         if listener.tags.len() > 0 {
@@ -257,17 +582,7 @@ impl ElbConnector {
                         }
 
                         if let Some(certificates) = listener.certificates {
-                            request = request.set_certificates(Some(
-                                certificates
-                                    .iter()
-                                    .map(|c| {
-                                        aws_sdk_elasticloadbalancingv2::types::Certificate::builder()
-                                            .certificate_arn(c.certificate_arn.clone())
-                                            // .is_default(c.is_default)
-                                            .build()
-                                    })
-                                    .collect(),
-                            ))
+                            request = request.set_certificates(Some(aws_certificates(&certificates)))
                         }
 
                         let response = request.send().await?;
@@ -286,14 +601,88 @@ impl ElbConnector {
                             format!("Created listener `{}` for load balancer `{}`", listener_id, lb_name)
                         )
                     }
-                    ElbConnectorOp::DeleteListener => {
-                        // In a real implementation, you'd need to store and retrieve the listener ARN
-                        // For now, we'll use a placeholder approach
+                    ElbConnectorOp::UpdateListenerTags(old_tags, new_tags) => {
+                        let lb_arn = find_load_balancer_arn(&client, lb_name).await?;
+                        update_resource_tags(&client, &listener_arn(&lb_arn, listener_id), &old_tags, &new_tags).await?;
+
+                        op_exec_output!(format!("Updated tags for listener `{}` on load balancer `{}`", listener_id, lb_name))
+                    }
+                    ElbConnectorOp::ModifyListener {
+                        port,
+                        protocol,
+                        ssl_policy,
+                        default_actions,
+                    } => {
+                        let lb_arn = find_load_balancer_arn(&client, lb_name).await?;
+
+                        let mut request = client
+                            .modify_listener()
+                            .listener_arn(listener_arn(&lb_arn, listener_id))
+                            .set_port(port)
+                            .set_ssl_policy(ssl_policy);
+                        if let Some(protocol) = protocol {
+                            request = request.protocol(ProtocolEnum::from_str(&protocol)?);
+                        }
+                        if let Some(default_actions) = default_actions {
+                            request = request.set_default_actions(Some(aws_actions(&default_actions)));
+                        }
+                        request.send().await?;
+
+                        op_exec_output!(format!("Modified listener `{}` on load balancer `{}`", listener_id, lb_name))
+                    }
+                    ElbConnectorOp::AddCertificates { certificates } => {
+                        let lb_arn = find_load_balancer_arn(&client, lb_name).await?;
+
+                        client
+                            .add_listener_certificates()
+                            .listener_arn(listener_arn(&lb_arn, listener_id))
+                            .set_certificates(Some(aws_certificates(&certificates)))
+                            .send()
+                            .await?;
+
                         op_exec_output!(format!(
-                            "Would delete listener `{}` for load balancer `{}`",
-                            listener_id, lb_name
+                            "Added {} certificates to listener `{}` on load balancer `{}`",
+                            certificates.len(),
+                            listener_id,
+                            lb_name
                         ))
                     }
+                    ElbConnectorOp::RemoveCertificates { certificate_arns } => {
+                        let lb_arn = find_load_balancer_arn(&client, lb_name).await?;
+
+                        client
+                            .remove_listener_certificates()
+                            .listener_arn(listener_arn(&lb_arn, listener_id))
+                            .set_certificates(Some(
+                                certificate_arns
+                                    .iter()
+                                    .map(|arn| AwsCertificate::builder().certificate_arn(arn).build())
+                                    .collect(),
+                            ))
+                            .send()
+                            .await?;
+
+                        op_exec_output!(format!(
+                            "Removed {} certificates from listener `{}` on load balancer `{}`",
+                            certificate_arns.len(),
+                            listener_id,
+                            lb_name
+                        ))
+                    }
+                    ElbConnectorOp::DeleteListener => {
+                        let lb_arn = find_load_balancer_arn(&client, lb_name).await?;
+
+                        client
+                            .delete_listener()
+                            .listener_arn(listener_arn(&lb_arn, listener_id))
+                            .send()
+                            .await?;
+
+                        op_exec_output!(
+                            Some([("listener_arn", Option::<String>::None)]),
+                            format!("Deleted listener `{}` for load balancer `{}`", listener_id, lb_name)
+                        )
+                    }
                     _ => Err(invalid_op(&addr, &op)),
                 }
             }
@@ -1,5 +1,6 @@
-use std::path::Path;
+use std::{collections::HashSet, net::IpAddr, path::Path};
 
+use anyhow::bail;
 use autoschematic_core::{
     connector::{ConnectorOp, PlanResponseElement, ResourceAddress},
     connector_op,
@@ -8,12 +9,252 @@ use autoschematic_core::{
 
 use crate::{
     addr::ElbResourceAddress,
+    config::DeletionProtectionPolicy,
     op::ElbConnectorOp,
-    resource::{Listener, LoadBalancer, TargetGroup},
+    resource::{Listener, LoadBalancer, S3Logging, Stickiness, Target, TargetGroup, TargetGroupAttributes, TargetGroupTargets},
 };
 
 use super::ElbConnector;
 
+/// Describes targets for plan messages, e.g. "i-0123456789abcdef0:8080, 10.0.1.5".
+fn describe_targets(targets: &[Target]) -> String {
+    let mut targets: Vec<String> = targets
+        .iter()
+        .map(|target| match target.port {
+            Some(port) => format!("{}:{}", target.id, port),
+            None => target.id.clone(),
+        })
+        .collect();
+    targets.sort();
+    targets.join(", ")
+}
+
+fn check_s3_logging(lb_name: &str, kind: &str, logging: &S3Logging) -> anyhow::Result<()> {
+    if logging.enabled && logging.bucket.is_empty() {
+        bail!("Load Balancer `{}` has {} enabled, but no bucket to write them to", lb_name, kind);
+    }
+    if let Some(prefix) = &logging.prefix
+        && (prefix.starts_with('/') || prefix.ends_with('/'))
+    {
+        bail!("Load Balancer `{}` has {} prefix `{}`, which can't start or end with a slash", lb_name, kind, prefix);
+    }
+    Ok(())
+}
+
+/// Checks that a load balancer's attributes are ones its type has, and that their values are in range.
+fn check_load_balancer(lb_name: &str, lb: &LoadBalancer) -> anyhow::Result<()> {
+    let application_only = [
+        ("connection_logs", lb.connection_logs.is_some()),
+        ("idle_timeout_seconds", lb.idle_timeout_seconds.is_some()),
+        ("http2_enabled", lb.http2_enabled.is_some()),
+        ("drop_invalid_header_fields", lb.drop_invalid_header_fields.is_some()),
+        ("desync_mitigation_mode", lb.desync_mitigation_mode.is_some()),
+    ];
+    if lb.load_balancer_type != "application" {
+        for (field, set) in application_only {
+            if set {
+                bail!(
+                    "Load Balancer `{}` sets {}, which only application load balancers have",
+                    lb_name,
+                    field
+                );
+            }
+        }
+    }
+    if lb.load_balancer_type == "gateway" && lb.access_logs.is_some() {
+        bail!("Load Balancer `{}` sets access_logs, which gateway load balancers don't have", lb_name);
+    }
+
+    if let Some(access_logs) = &lb.access_logs {
+        check_s3_logging(lb_name, "access_logs", access_logs)?;
+    }
+    if let Some(connection_logs) = &lb.connection_logs {
+        check_s3_logging(lb_name, "connection_logs", connection_logs)?;
+    }
+
+    if let Some(idle_timeout_seconds) = lb.idle_timeout_seconds
+        && !(1..=4000).contains(&idle_timeout_seconds)
+    {
+        bail!("Load Balancer `{}` has idle_timeout_seconds {}; expected 1 to 4000", lb_name, idle_timeout_seconds);
+    }
+
+    if let Some(desync_mitigation_mode) = &lb.desync_mitigation_mode
+        && !["monitor", "defensive", "strictest"].contains(&desync_mitigation_mode.as_str())
+    {
+        bail!(
+            "Load Balancer `{}` has desync_mitigation_mode {}; expected monitor, defensive or strictest",
+            lb_name,
+            desync_mitigation_mode
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks health check and attribute values against the ranges ELB accepts.
+fn check_target_group(tg_name: &str, tg: &TargetGroup) -> anyhow::Result<()> {
+    if let Some(health_check) = &tg.health_check {
+        let ranges = [
+            ("interval_seconds", health_check.interval_seconds, 5, 300),
+            ("timeout_seconds", health_check.timeout_seconds, 2, 120),
+            ("healthy_threshold_count", health_check.healthy_threshold_count, 2, 10),
+            ("unhealthy_threshold_count", health_check.unhealthy_threshold_count, 2, 10),
+        ];
+        for (field, value, min, max) in ranges {
+            if !(min..=max).contains(&value) {
+                bail!("Target Group `{}` has health check {} {}; expected {} to {}", tg_name, field, value, min, max);
+            }
+        }
+        if health_check.timeout_seconds >= health_check.interval_seconds {
+            bail!(
+                "Target Group `{}` has a health check timeout_seconds of {}, which should be less than its interval_seconds of {}",
+                tg_name,
+                health_check.timeout_seconds,
+                health_check.interval_seconds
+            );
+        }
+    }
+
+    let attributes = &tg.attributes;
+    if let Some(deregistration_delay_seconds) = attributes.deregistration_delay_seconds
+        && !(0..=3600).contains(&deregistration_delay_seconds)
+    {
+        bail!(
+            "Target Group `{}` has deregistration_delay_seconds {}; expected 0 to 3600",
+            tg_name,
+            deregistration_delay_seconds
+        );
+    }
+
+    if let Some(slow_start_seconds) = attributes.slow_start_seconds
+        && slow_start_seconds != 0
+        && !(30..=900).contains(&slow_start_seconds)
+    {
+        bail!("Target Group `{}` has slow_start_seconds {}; expected 30 to 900, or 0", tg_name, slow_start_seconds);
+    }
+
+    if let Some(stickiness) = &attributes.stickiness {
+        match stickiness.r#type.as_str() {
+            "lb_cookie" | "source_ip" => {}
+            "app_cookie" if stickiness.cookie_name.is_some() => {}
+            "app_cookie" => bail!("Target Group `{}` uses app_cookie stickiness, which needs a cookie_name", tg_name),
+            other => bail!(
+                "Target Group `{}` has stickiness type {}; expected lb_cookie, app_cookie or source_ip",
+                tg_name,
+                other
+            ),
+        }
+        if let Some(duration_seconds) = stickiness.duration_seconds
+            && !(1..=604800).contains(&duration_seconds)
+        {
+            bail!(
+                "Target Group `{}` has a stickiness duration_seconds of {}; expected 1 to 604800",
+                tg_name,
+                duration_seconds
+            );
+        }
+    }
+
+    if let Some(load_balancing_algorithm) = &attributes.load_balancing_algorithm
+        && !["round_robin", "least_outstanding_requests", "weighted_random"].contains(&load_balancing_algorithm.as_str())
+    {
+        bail!(
+            "Target Group `{}` has load_balancing_algorithm {}; expected round_robin, least_outstanding_requests or weighted_random",
+            tg_name,
+            load_balancing_algorithm
+        );
+    }
+
+    if let Some(cross_zone_enabled) = &attributes.cross_zone_enabled
+        && !["true", "false", "use_load_balancer_configuration"].contains(&cross_zone_enabled.as_str())
+    {
+        bail!(
+            "Target Group `{}` has cross_zone_enabled {}; expected \"true\", \"false\" or \"use_load_balancer_configuration\"",
+            tg_name,
+            cross_zone_enabled
+        );
+    }
+
+    Ok(())
+}
+
+/// The desired value, if it's set and differs from the current one.
+fn changed<T: PartialEq + Clone>(current: &Option<T>, desired: &Option<T>) -> Option<T> {
+    desired.clone().filter(|desired| current.as_ref() != Some(desired))
+}
+
+/// The attributes that `desired` sets to something other than `current`. Unset attributes aren't managed.
+fn changed_target_group_attributes(current: &TargetGroupAttributes, desired: &TargetGroupAttributes) -> TargetGroupAttributes {
+    // Stickiness settings that are left unset keep their current values
+    let stickiness = desired.stickiness.clone().map(|desired| match &current.stickiness {
+        Some(current) => Stickiness {
+            duration_seconds: desired.duration_seconds.or(current.duration_seconds),
+            cookie_name: desired.cookie_name.or(current.cookie_name.clone()),
+            ..desired
+        },
+        None => desired,
+    });
+
+    TargetGroupAttributes {
+        deregistration_delay_seconds: changed(&current.deregistration_delay_seconds, &desired.deregistration_delay_seconds),
+        slow_start_seconds: changed(&current.slow_start_seconds, &desired.slow_start_seconds),
+        stickiness: changed(&current.stickiness, &stickiness),
+        load_balancing_algorithm: changed(&current.load_balancing_algorithm, &desired.load_balancing_algorithm),
+        cross_zone_enabled: changed(&current.cross_zone_enabled, &desired.cross_zone_enabled),
+    }
+}
+
+fn merge_target_group_attributes(attributes: &mut TargetGroupAttributes, changed: &TargetGroupAttributes) {
+    attributes.deregistration_delay_seconds = changed.deregistration_delay_seconds.or(attributes.deregistration_delay_seconds);
+    attributes.slow_start_seconds = changed.slow_start_seconds.or(attributes.slow_start_seconds);
+    attributes.stickiness = changed.stickiness.clone().or(attributes.stickiness.take());
+    attributes.load_balancing_algorithm = changed.load_balancing_algorithm.clone().or(attributes.load_balancing_algorithm.take());
+    attributes.cross_zone_enabled = changed.cross_zone_enabled.clone().or(attributes.cross_zone_enabled.take());
+}
+
+/// Checks targets against the target type of their target group, if it's defined in this repo.
+fn check_targets(prefix: &Path, region: &str, tg_name: &str, targets: &[Target]) -> anyhow::Result<()> {
+    let tg_path = prefix.join(ElbResourceAddress::TargetGroup(region.to_string(), tg_name.to_string()).to_path_buf());
+    let target_type = match std::fs::read_to_string(&tg_path) {
+        Ok(s) => RON.from_str::<TargetGroup>(&s)?.target_type,
+        Err(_) => return Ok(()),
+    };
+
+    if target_type == "lambda" && targets.len() > 1 {
+        bail!("Target Group `{}` has target type lambda, which takes a single target", tg_name);
+    }
+
+    for target in targets {
+        let valid = match target_type.as_str() {
+            "instance" => target.id.starts_with("i-"),
+            "ip" => target.id.parse::<IpAddr>().is_ok(),
+            "lambda" => target.id.starts_with("arn:aws:lambda:") && target.port.is_none(),
+            "alb" => target.id.starts_with("arn:aws:elasticloadbalancing:"),
+            _ => true,
+        };
+        if !valid {
+            bail!(
+                "Target `{}` doesn't fit Target Group `{}`, which has target type {}. Expected {}.",
+                target.id,
+                tg_name,
+                target_type,
+                match target_type.as_str() {
+                    "instance" => "an instance ID",
+                    "ip" => "an IP address",
+                    "lambda" => "a Lambda function ARN, without a port",
+                    _ => "an application load balancer ARN",
+                }
+            );
+        }
+
+        if target.availability_zone.is_some() && target_type != "ip" {
+            bail!("Target `{}` sets availability_zone, which only applies to IP targets", target.id);
+        }
+    }
+
+    Ok(())
+}
+
 impl ElbConnector {
     pub async fn do_plan(
         &self,
@@ -24,25 +265,49 @@ impl ElbConnector {
         let addr = ElbResourceAddress::from_path(addr)?;
         let current = optional_string_from_utf8(current)?;
         let desired = optional_string_from_utf8(desired)?;
-        
+
         match addr {
-            ElbResourceAddress::LoadBalancer(region, lb_name) => {
+            ElbResourceAddress::LoadBalancer(_region, lb_name) => {
                 match (current, desired) {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_lb)) => {
                         let new_lb: LoadBalancer = RON.from_str(&new_lb)?;
+                        check_load_balancer(&lb_name, &new_lb)?;
                         Ok(vec![connector_op!(
                             ElbConnectorOp::CreateLoadBalancer(new_lb),
                             format!("Create new Load Balancer {}", lb_name)
                         )])
                     }
-                    (Some(_old_lb), None) => Ok(vec![connector_op!(
-                        ElbConnectorOp::DeleteLoadBalancer,
-                        format!("DELETE Load Balancer {}", lb_name)
-                    )]),
+                    (Some(old_lb), None) => {
+                        let old_lb: LoadBalancer = RON.from_str(&old_lb)?;
+                        let mut ops = Vec::new();
+
+                        if old_lb.deletion_protection {
+                            match self.config.lock().await.deletion_protection_policy {
+                                DeletionProtectionPolicy::Block => bail!(
+                                    "Load Balancer {} has deletion protection enabled. Set `deletion_protection: false` and apply that first, \
+                                     or set `deletion_protection_policy: DisableThenDelete` in aws/elb/config.ron.",
+                                    lb_name
+                                ),
+                                DeletionProtectionPolicy::DisableThenDelete => {
+                                    ops.push(connector_op!(
+                                        ElbConnectorOp::UpdateDeletionProtection { enabled: false },
+                                        format!("Disable deletion protection for Load Balancer `{}`", lb_name)
+                                    ));
+                                }
+                            }
+                        }
+
+                        ops.push(connector_op!(
+                            ElbConnectorOp::DeleteLoadBalancer,
+                            format!("DELETE Load Balancer {}", lb_name)
+                        ));
+                        Ok(ops)
+                    }
                     (Some(old_lb), Some(new_lb)) => {
                         let old_lb: LoadBalancer = RON.from_str(&old_lb)?;
                         let new_lb: LoadBalancer = RON.from_str(&new_lb)?;
+                        check_load_balancer(&lb_name, &new_lb)?;
                         let mut ops = Vec::new();
 
                         // Check for tag changes
@@ -81,6 +346,66 @@ impl ElbConnector {
                             }
                         }
 
+                        if old_lb.deletion_protection != new_lb.deletion_protection {
+                            ops.push(connector_op!(
+                                ElbConnectorOp::UpdateDeletionProtection {
+                                    enabled: new_lb.deletion_protection,
+                                },
+                                format!(
+                                    "{} deletion protection for Load Balancer `{}`",
+                                    if new_lb.deletion_protection { "Enable" } else { "Disable" },
+                                    lb_name
+                                )
+                            ));
+                        }
+
+                        // Attributes that are set are updated one by one
+                        if let Some(access_logs) = changed(&old_lb.access_logs, &new_lb.access_logs) {
+                            let diff = diff_ron_values(&old_lb.access_logs, &new_lb.access_logs).unwrap_or_default();
+                            ops.push(connector_op!(
+                                ElbConnectorOp::UpdateAccessLogs(access_logs),
+                                format!("Update access logs for Load Balancer `{}`\n{}", lb_name, diff)
+                            ));
+                        }
+
+                        if let Some(connection_logs) = changed(&old_lb.connection_logs, &new_lb.connection_logs) {
+                            let diff = diff_ron_values(&old_lb.connection_logs, &new_lb.connection_logs).unwrap_or_default();
+                            ops.push(connector_op!(
+                                ElbConnectorOp::UpdateConnectionLogs(connection_logs),
+                                format!("Update connection logs for Load Balancer `{}`\n{}", lb_name, diff)
+                            ));
+                        }
+
+                        if let Some(seconds) = changed(&old_lb.idle_timeout_seconds, &new_lb.idle_timeout_seconds) {
+                            ops.push(connector_op!(
+                                ElbConnectorOp::UpdateIdleTimeout { seconds },
+                                format!("Set idle timeout for Load Balancer `{}` to {}s", lb_name, seconds)
+                            ));
+                        }
+
+                        if let Some(enabled) = changed(&old_lb.http2_enabled, &new_lb.http2_enabled) {
+                            ops.push(connector_op!(
+                                ElbConnectorOp::UpdateHttp2 { enabled },
+                                format!("{} HTTP/2 for Load Balancer `{}`", if enabled { "Enable" } else { "Disable" }, lb_name)
+                            ));
+                        }
+
+                        if let Some(enabled) = changed(&old_lb.drop_invalid_header_fields, &new_lb.drop_invalid_header_fields) {
+                            ops.push(connector_op!(
+                                ElbConnectorOp::UpdateDropInvalidHeaderFields { enabled },
+                                format!(
+                                    "{} dropping invalid header fields for Load Balancer `{}`",
+                                    if enabled { "Enable" } else { "Disable" },
+                                    lb_name
+                                )
+                            ));
+                        }
+
+                        if let Some(mode) = changed(&old_lb.desync_mitigation_mode, &new_lb.desync_mitigation_mode) {
+                            let message = format!("Set desync mitigation mode for Load Balancer `{}` to {}", lb_name, mode);
+                            ops.push(connector_op!(ElbConnectorOp::UpdateDesyncMitigationMode { mode }, message));
+                        }
+
                         // Check for IP address type changes
                         if old_lb.ip_address_type != new_lb.ip_address_type {
                             ops.push(connector_op!(
@@ -105,23 +430,25 @@ impl ElbConnector {
                     }
                 }
             }
-            ElbResourceAddress::TargetGroup(region, tg_name) => {
+            ElbResourceAddress::TargetGroup(_region, tg_name) => {
                 match (current, desired) {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_tg)) => {
                         let new_tg: TargetGroup = RON.from_str(&new_tg)?;
+                        check_target_group(&tg_name, &new_tg)?;
+
+                        let attributes = new_tg.attributes.clone();
                         let mut ops = vec![connector_op!(
-                            ElbConnectorOp::CreateTargetGroup(new_tg.clone()),
+                            ElbConnectorOp::CreateTargetGroup(new_tg),
                             format!("Create new Target Group {}", tg_name)
                         )];
 
-                        // Register targets if any
-                        if !new_tg.targets.is_empty() {
+                        // Attributes can only be set once the target group exists
+                        if attributes != TargetGroupAttributes::default() {
+                            let diff = diff_ron_values(&TargetGroupAttributes::default(), &attributes).unwrap_or_default();
                             ops.push(connector_op!(
-                                ElbConnectorOp::RegisterTargets {
-                                    targets: new_tg.targets.clone(),
-                                },
-                                format!("Register targets with Target Group `{}`", tg_name)
+                                ElbConnectorOp::UpdateTargetGroupAttributes(attributes),
+                                format!("Set attributes for Target Group `{}`\n{}", tg_name, diff)
                             ));
                         }
 
@@ -133,9 +460,17 @@ impl ElbConnector {
                     )]),
                     (Some(old_tg), Some(new_tg)) => {
                         let old_tg: TargetGroup = RON.from_str(&old_tg)?;
-                        let new_tg: TargetGroup = RON.from_str(&new_tg)?;
+                        let mut new_tg: TargetGroup = RON.from_str(&new_tg)?;
+                        check_target_group(&tg_name, &new_tg)?;
                         let mut ops = Vec::new();
 
+                        // An unset matcher is left as it is
+                        if let (Some(old_health_check), Some(new_health_check)) = (&old_tg.health_check, &mut new_tg.health_check)
+                            && new_health_check.matcher.is_none()
+                        {
+                            new_health_check.matcher = old_health_check.matcher.clone();
+                        }
+
                         // Check for tag changes
                         if old_tg.tags != new_tg.tags {
                             let diff = diff_ron_values(&old_tg.tags, &new_tg.tags).unwrap_or_default();
@@ -148,42 +483,64 @@ impl ElbConnector {
                         // Check for health check changes
                         if old_tg.health_check != new_tg.health_check
                             && let Some(new_health_check) = &new_tg.health_check {
+                                let diff = diff_ron_values(&old_tg.health_check, &new_tg.health_check).unwrap_or_default();
                                 ops.push(connector_op!(
                                     ElbConnectorOp::UpdateHealthCheck(new_health_check.clone()),
-                                    format!("Update health check for Target Group `{}`", tg_name)
+                                    format!("Update health check for Target Group `{}`\n{}", tg_name, diff)
                                 ));
                             }
 
-                        // Check for target changes
-                        if old_tg.targets != new_tg.targets {
-                            let old_targets: std::collections::HashSet<_> = old_tg.targets.iter().collect();
-                            let new_targets: std::collections::HashSet<_> = new_tg.targets.iter().collect();
-
-                            let to_deregister: Vec<String> =
-                                old_targets.difference(&new_targets).map(|s| s.to_string()).collect();
-                            let to_register: Vec<String> =
-                                new_targets.difference(&old_targets).map(|s| s.to_string()).collect();
-
-                            if !to_deregister.is_empty() {
-                                ops.push(connector_op!(
-                                    ElbConnectorOp::DeregisterTargets { targets: to_deregister },
-                                    format!("Deregister targets from Target Group `{}`", tg_name)
-                                ));
-                            }
-
-                            if !to_register.is_empty() {
-                                ops.push(connector_op!(
-                                    ElbConnectorOp::RegisterTargets { targets: to_register },
-                                    format!("Register targets with Target Group `{}`", tg_name)
-                                ));
-                            }
+                        // Only the attributes that are set, and differ, are updated
+                        let changed = changed_target_group_attributes(&old_tg.attributes, &new_tg.attributes);
+                        if changed != TargetGroupAttributes::default() {
+                            let mut updated = old_tg.attributes.clone();
+                            merge_target_group_attributes(&mut updated, &changed);
+                            let diff = diff_ron_values(&old_tg.attributes, &updated).unwrap_or_default();
+                            ops.push(connector_op!(
+                                ElbConnectorOp::UpdateTargetGroupAttributes(changed),
+                                format!("Update attributes for Target Group `{}`\n{}", tg_name, diff)
+                            ));
                         }
 
                         Ok(ops)
                     }
                 }
             }
-            ElbResourceAddress::Listener(region, lb_name, listener_id) => {
+            ElbResourceAddress::TargetGroupTargets(region, tg_name) => {
+                let current: Option<TargetGroupTargets> = current.map(|s| RON.from_str(&s)).transpose()?;
+                let desired: Option<TargetGroupTargets> = desired.map(|s| RON.from_str(&s)).transpose()?;
+
+                let old_targets: HashSet<Target> = current.map(|t| t.targets.into_iter().collect()).unwrap_or_default();
+                let new_targets: HashSet<Target> = match desired {
+                    Some(desired) => {
+                        check_targets(&self.prefix, &region, &tg_name, &desired.targets)?;
+                        desired.targets.into_iter().collect()
+                    }
+                    None => HashSet::new(),
+                };
+
+                let to_register: Vec<Target> = new_targets.difference(&old_targets).cloned().collect();
+                let to_deregister: Vec<Target> = old_targets.difference(&new_targets).cloned().collect();
+                let mut ops = Vec::new();
+
+                // New targets go first, so that the target group isn't left empty in between
+                if !to_register.is_empty() {
+                    let message = format!("Register targets with Target Group `{}`: {}", tg_name, describe_targets(&to_register));
+                    ops.push(connector_op!(ElbConnectorOp::RegisterTargets { targets: to_register }, message));
+                }
+
+                if !to_deregister.is_empty() {
+                    let message = format!(
+                        "DEREGISTER targets from Target Group `{}`: {}",
+                        tg_name,
+                        describe_targets(&to_deregister)
+                    );
+                    ops.push(connector_op!(ElbConnectorOp::DeregisterTargets { targets: to_deregister }, message));
+                }
+
+                Ok(ops)
+            }
+            ElbResourceAddress::Listener(_region, lb_name, listener_id) => {
                 match (current, desired) {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_listener)) => {
@@ -285,17 +642,15 @@ impl ElbConnector {
                                         format!("Add certificates to Listener `{}`", listener_id)
                                     ));
                                 }
-                            } else if old_listener.certificates.is_some() {
+                            } else if let Some(old_certs) = &old_listener.certificates {
                                 // Remove all certificates
-                                if let Some(old_certs) = &old_listener.certificates {
-                                    let cert_arns: Vec<String> = old_certs.iter().map(|c| c.certificate_arn.clone()).collect();
-                                    ops.push(connector_op!(
-                                        ElbConnectorOp::RemoveCertificates {
-                                            certificate_arns: cert_arns,
-                                        },
-                                        format!("Remove all certificates from Listener `{}`", listener_id)
-                                    ));
-                                }
+                                let cert_arns: Vec<String> = old_certs.iter().map(|c| c.certificate_arn.clone()).collect();
+                                ops.push(connector_op!(
+                                    ElbConnectorOp::RemoveCertificates {
+                                        certificate_arns: cert_arns,
+                                    },
+                                    format!("Remove all certificates from Listener `{}`", listener_id)
+                                ));
                             }
                         }
 
@@ -2,4 +2,5 @@ pub mod addr;
 pub mod config;
 pub mod op;
 pub mod resource;
-pub mod tags;
\ No newline at end of file
+pub mod tags;
+pub mod util;
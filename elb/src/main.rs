@@ -1,3 +1,5 @@
+use addr::ElbResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
 use autoschematic_core::tarpc_bridge::tarpc_connector_main;
 use connector::ElbConnector;
 
@@ -11,6 +13,12 @@ pub mod tags;
 
 #[tokio::main]
 pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let patterns = ElbResourceAddress::checked_address_patterns()?;
+        print!("{}", render_address_docs("aws/elb", &patterns));
+        return Ok(());
+    }
+
     tarpc_connector_main::<ElbConnector>().await?;
     Ok(())
 }
@@ -2,7 +2,7 @@ use autoschematic_core::{connector::ConnectorOp, util::RON};
 use serde::{Deserialize, Serialize};
 
 use super::{
-    resource::{Action, Certificate, HealthCheck, Listener, LoadBalancer, TargetGroup},
+    resource::{Action, Certificate, HealthCheck, Listener, LoadBalancer, S3Logging, Target, TargetGroup, TargetGroupAttributes},
     tags::Tags,
 };
 
@@ -23,26 +23,40 @@ pub enum ElbConnectorOp {
     UpdateSubnets {
         subnets: Vec<String>,
     },
+    UpdateDeletionProtection {
+        enabled: bool,
+    },
+    UpdateAccessLogs(S3Logging),
+    UpdateConnectionLogs(S3Logging),
+    UpdateIdleTimeout {
+        seconds: i32,
+    },
+    UpdateHttp2 {
+        enabled: bool,
+    },
+    UpdateDropInvalidHeaderFields {
+        enabled: bool,
+    },
+    UpdateDesyncMitigationMode {
+        mode: String, // monitor, defensive, or strictest
+    },
     DeleteLoadBalancer,
 
     // Target Group operations
     CreateTargetGroup(TargetGroup),
     UpdateTargetGroupTags(Tags, Tags),
-    UpdateTargetGroupAttributes {
-        deregistration_delay_seconds: Option<i32>,
-        slow_start_seconds: Option<i32>,
-        stickiness_enabled: Option<bool>,
-        stickiness_type: Option<String>,
-        stickiness_cookie_duration_seconds: Option<i32>,
-    },
+    /// Sets the attributes that are set, and leaves the rest as they are.
+    UpdateTargetGroupAttributes(TargetGroupAttributes),
     UpdateHealthCheck(HealthCheck),
+    DeleteTargetGroup,
+
+    // Target registration operations
     RegisterTargets {
-        targets: Vec<String>,
+        targets: Vec<Target>,
     },
     DeregisterTargets {
-        targets: Vec<String>,
+        targets: Vec<Target>,
     },
-    DeleteTargetGroup,
 
     // Listener operations
     CreateListener(Listener),
@@ -16,6 +16,52 @@ pub struct HealthCheck {
     pub timeout_seconds: i32,
     pub healthy_threshold_count: i32,
     pub unhealthy_threshold_count: i32,
+    /// The HTTP codes that count as healthy, e.g. "200" or "200-299". Left as it is when unset.
+    #[serde(default)]
+    pub matcher: Option<String>,
+}
+
+/// Keeps a client on the same target.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Stickiness {
+    pub enabled: bool,
+    /// lb_cookie or app_cookie for application load balancers, source_ip for network load balancers.
+    pub r#type: String,
+    /// How long the cookie keeps the client on its target, from 1 to 604800 seconds.
+    #[serde(default)]
+    pub duration_seconds: Option<i32>,
+    /// The name of the application's cookie, for app_cookie.
+    #[serde(default)]
+    pub cookie_name: Option<String>,
+}
+
+/// Target group attributes. Attributes left unset aren't managed, and keep whatever value they have.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Clone)]
+pub struct TargetGroupAttributes {
+    /// How long to let in-flight requests finish before a target is deregistered, from 0 to 3600 seconds.
+    #[serde(default)]
+    pub deregistration_delay_seconds: Option<i32>,
+    /// How long a new target takes to ramp up to its full share of requests, from 30 to 900 seconds,
+    /// or 0 to turn slow start off.
+    #[serde(default)]
+    pub slow_start_seconds: Option<i32>,
+    #[serde(default)]
+    pub stickiness: Option<Stickiness>,
+    /// round_robin, least_outstanding_requests or weighted_random.
+    #[serde(default)]
+    pub load_balancing_algorithm: Option<String>,
+    /// "true", "false" or "use_load_balancer_configuration".
+    #[serde(default)]
+    pub cross_zone_enabled: Option<String>,
+}
+
+/// Where a load balancer writes its logs in S3.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct S3Logging {
+    pub enabled: bool,
+    pub bucket: String,
+    #[serde(default)]
+    pub prefix: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -26,6 +72,29 @@ pub struct LoadBalancer {
     pub security_groups: Vec<String>,
     pub subnets: Vec<String>,
     pub ip_address_type: String, // ipv4 or dualstack
+    /// The deletion_protection.enabled attribute. While set, the load balancer can't be deleted.
+    #[serde(default)]
+    pub deletion_protection: bool,
+    // The attributes below aren't managed while they're unset.
+    /// Request logs. Application and network load balancers.
+    #[serde(default)]
+    pub access_logs: Option<S3Logging>,
+    /// Client TLS connection logs. Application load balancers only.
+    #[serde(default)]
+    pub connection_logs: Option<S3Logging>,
+    /// How long a connection can sit idle before it's closed, from 1 to 4000 seconds. Application load balancers only.
+    #[serde(default)]
+    pub idle_timeout_seconds: Option<i32>,
+    /// Application load balancers only.
+    #[serde(default)]
+    pub http2_enabled: Option<bool>,
+    /// Whether to drop HTTP headers whose names aren't valid. Application load balancers only.
+    #[serde(default)]
+    pub drop_invalid_header_fields: Option<bool>,
+    /// How to handle requests that could be used for HTTP desync attacks: monitor, defensive or strictest.
+    /// Application load balancers only.
+    #[serde(default)]
+    pub desync_mitigation_mode: Option<String>,
     pub tags: Tags,
 }
 
@@ -36,10 +105,32 @@ pub struct TargetGroup {
     pub vpc_id: Option<String>,
     pub target_type: String, // instance, ip, lambda
     pub health_check: Option<HealthCheck>,
-    pub targets: Vec<String>,
+    #[serde(default)]
+    pub attributes: TargetGroupAttributes,
     pub tags: Tags,
 }
 
+/// A target registered with a target group: an instance ID, an IP address, a Lambda function ARN,
+/// or the ARN of an application load balancer, depending on the target group's target_type.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
+pub struct Target {
+    pub id: String,
+    /// The port the target receives traffic on. Leave unset to use the target group's port.
+    /// Not used for Lambda targets.
+    #[serde(default)]
+    pub port: Option<i32>,
+    /// For IP targets outside the target group's VPC, "all".
+    #[serde(default)]
+    pub availability_zone: Option<String>,
+}
+
+/// The targets registered with a target group. Targets registered by other means show up as
+/// drift, and are deregistered when this is applied.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct TargetGroupTargets {
+    pub targets: Vec<Target>,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Certificate {
     pub certificate_arn: String,
@@ -85,6 +176,7 @@ pub struct Listener {
 pub enum ElbResource {
     LoadBalancer(LoadBalancer),
     TargetGroup(TargetGroup),
+    TargetGroupTargets(TargetGroupTargets),
     Listener(Listener),
 }
 
@@ -101,6 +193,10 @@ impl Resource for ElbResource {
                 Ok(s) => Ok(s.into()),
                 Err(e) => Err(e.into()),
             },
+            ElbResource::TargetGroupTargets(targets) => match RON.to_string_pretty(&targets, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
             ElbResource::Listener(listener) => match RON.to_string_pretty(&listener, pretty_config) {
                 Ok(s) => Ok(s.into()),
                 Err(e) => Err(e.into()),
@@ -119,6 +215,7 @@ impl Resource for ElbResource {
         match addr {
             ElbResourceAddress::LoadBalancer(_region, _name) => Ok(ElbResource::LoadBalancer(RON.from_str(s)?)),
             ElbResourceAddress::TargetGroup(_region, _name) => Ok(ElbResource::TargetGroup(RON.from_str(s)?)),
+            ElbResourceAddress::TargetGroupTargets(_region, _name) => Ok(ElbResource::TargetGroupTargets(RON.from_str(s)?)),
             ElbResourceAddress::Listener(_region, _lb_name, _listener_id) => Ok(ElbResource::Listener(RON.from_str(s)?)),
         }
     }
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use aws_sdk_elasticloadbalancingv2::types::{LoadBalancerAttribute, TargetGroupAttribute};
+
+use crate::resource::{LoadBalancer, S3Logging, Stickiness, TargetGroupAttributes};
+
+/// A listener's ARN: its load balancer's ARN, with `loadbalancer` swapped for `listener`,
+/// followed by the listener ID.
+pub fn listener_arn(lb_arn: &str, listener_id: &str) -> String {
+    format!("{}/{}", lb_arn.replacen(":loadbalancer/", ":listener/", 1), listener_id)
+}
+
+fn target_group_attribute(key: &str, value: impl ToString) -> TargetGroupAttribute {
+    TargetGroupAttribute::builder().key(key).value(value.to_string()).build()
+}
+
+/// The key/value pairs for the attributes that are set.
+pub fn target_group_attributes_to_aws(attributes: &TargetGroupAttributes) -> Vec<TargetGroupAttribute> {
+    let mut res = Vec::new();
+
+    if let Some(deregistration_delay_seconds) = attributes.deregistration_delay_seconds {
+        res.push(target_group_attribute(
+            "deregistration_delay.timeout_seconds",
+            deregistration_delay_seconds,
+        ));
+    }
+
+    if let Some(slow_start_seconds) = attributes.slow_start_seconds {
+        res.push(target_group_attribute("slow_start.duration_seconds", slow_start_seconds));
+    }
+
+    if let Some(stickiness) = &attributes.stickiness {
+        res.push(target_group_attribute("stickiness.enabled", stickiness.enabled));
+        res.push(target_group_attribute("stickiness.type", &stickiness.r#type));
+        if let Some(duration_seconds) = stickiness.duration_seconds {
+            res.push(target_group_attribute(
+                &format!("stickiness.{}.duration_seconds", stickiness.r#type),
+                duration_seconds,
+            ));
+        }
+        if let Some(cookie_name) = &stickiness.cookie_name {
+            res.push(target_group_attribute("stickiness.app_cookie.cookie_name", cookie_name));
+        }
+    }
+
+    if let Some(load_balancing_algorithm) = &attributes.load_balancing_algorithm {
+        res.push(target_group_attribute("load_balancing.algorithm.type", load_balancing_algorithm));
+    }
+
+    if let Some(cross_zone_enabled) = &attributes.cross_zone_enabled {
+        res.push(target_group_attribute("load_balancing.cross_zone.enabled", cross_zone_enabled));
+    }
+
+    res
+}
+
+/// Reads back the attributes this connector manages. Attributes the target group doesn't have,
+/// such as slow start on a network load balancer's target group, are left unset.
+pub fn target_group_attributes_from_aws(attributes: &[TargetGroupAttribute]) -> TargetGroupAttributes {
+    let values: HashMap<&str, &str> = attributes.iter().filter_map(|a| Some((a.key()?, a.value()?))).collect();
+    let int = |key: &str| values.get(key).and_then(|v| v.parse::<i32>().ok());
+    let string = |key: &str| values.get(key).map(|v| v.to_string());
+
+    let stickiness = match (values.get("stickiness.enabled"), values.get("stickiness.type")) {
+        (Some(enabled), Some(r#type)) => Some(Stickiness {
+            enabled: *enabled == "true",
+            r#type: r#type.to_string(),
+            duration_seconds: int(&format!("stickiness.{}.duration_seconds", r#type)),
+            cookie_name: if *r#type == "app_cookie" {
+                string("stickiness.app_cookie.cookie_name")
+            } else {
+                None
+            },
+        }),
+        _ => None,
+    };
+
+    TargetGroupAttributes {
+        deregistration_delay_seconds: int("deregistration_delay.timeout_seconds"),
+        slow_start_seconds: int("slow_start.duration_seconds"),
+        stickiness,
+        load_balancing_algorithm: string("load_balancing.algorithm.type"),
+        cross_zone_enabled: string("load_balancing.cross_zone.enabled"),
+    }
+}
+
+pub fn load_balancer_attribute(key: &str, value: impl ToString) -> LoadBalancerAttribute {
+    LoadBalancerAttribute::builder().key(key).value(value.to_string()).build()
+}
+
+/// The attributes for S3 logging of `kind`, either access_logs or connection_logs.
+pub fn s3_logging_to_aws(kind: &str, logging: &S3Logging) -> Vec<LoadBalancerAttribute> {
+    vec![
+        load_balancer_attribute(&format!("{}.s3.enabled", kind), logging.enabled),
+        load_balancer_attribute(&format!("{}.s3.bucket", kind), &logging.bucket),
+        load_balancer_attribute(&format!("{}.s3.prefix", kind), logging.prefix.as_deref().unwrap_or_default()),
+    ]
+}
+
+/// The key/value pairs for the attributes of a new load balancer that are set, apart from deletion protection.
+pub fn load_balancer_attributes_to_aws(lb: &LoadBalancer) -> Vec<LoadBalancerAttribute> {
+    let mut res = Vec::new();
+
+    if let Some(access_logs) = &lb.access_logs {
+        res.extend(s3_logging_to_aws("access_logs", access_logs));
+    }
+    if let Some(connection_logs) = &lb.connection_logs {
+        res.extend(s3_logging_to_aws("connection_logs", connection_logs));
+    }
+    if let Some(idle_timeout_seconds) = lb.idle_timeout_seconds {
+        res.push(load_balancer_attribute("idle_timeout.timeout_seconds", idle_timeout_seconds));
+    }
+    if let Some(http2_enabled) = lb.http2_enabled {
+        res.push(load_balancer_attribute("routing.http2.enabled", http2_enabled));
+    }
+    if let Some(drop_invalid_header_fields) = lb.drop_invalid_header_fields {
+        res.push(load_balancer_attribute(
+            "routing.http.drop_invalid_header_fields.enabled",
+            drop_invalid_header_fields,
+        ));
+    }
+    if let Some(desync_mitigation_mode) = &lb.desync_mitigation_mode {
+        res.push(load_balancer_attribute("routing.http.desync_mitigation_mode", desync_mitigation_mode));
+    }
+
+    res
+}
+
+/// The attributes of an existing load balancer, by key.
+pub fn load_balancer_attribute_values(attributes: &[LoadBalancerAttribute]) -> HashMap<String, String> {
+    attributes
+        .iter()
+        .filter_map(|a| Some((a.key()?.to_string(), a.value()?.to_string())))
+        .collect()
+}
+
+/// Reads back S3 logging of `kind`, if the load balancer has it.
+pub fn s3_logging_from_aws(kind: &str, values: &HashMap<String, String>) -> Option<S3Logging> {
+    let enabled = values.get(&format!("{}.s3.enabled", kind))?;
+
+    Some(S3Logging {
+        enabled: enabled == "true",
+        bucket: values.get(&format!("{}.s3.bucket", kind)).cloned().unwrap_or_default(),
+        prefix: values
+            .get(&format!("{}.s3.prefix", kind))
+            .filter(|prefix| !prefix.is_empty())
+            .cloned(),
+    })
+}
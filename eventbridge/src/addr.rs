@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum EventBridgeResourceAddress {
+    /// Every region has a bus named `default`, which exists without being created.
+    EventBus { region: String, name: String },
+    /// A rule, along with its targets.
+    Rule {
+        region:    String,
+        event_bus: String,
+        name:      String,
+    },
+    Archive { region: String, name: String },
+}
+
+impl ResourceAddress for EventBridgeResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            EventBridgeResourceAddress::EventBus { region, name } => {
+                PathBuf::from(format!("aws/eventbridge/{region}/event_buses/{name}.ron"))
+            }
+            EventBridgeResourceAddress::Rule { region, event_bus, name } => {
+                PathBuf::from(format!("aws/eventbridge/{region}/event_buses/{event_bus}/rules/{name}.ron"))
+            }
+            EventBridgeResourceAddress::Archive { region, name } => {
+                PathBuf::from(format!("aws/eventbridge/{region}/archives/{name}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "eventbridge", region, "event_buses", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(EventBridgeResourceAddress::EventBus {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "eventbridge", region, "event_buses", event_bus, "rules", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(EventBridgeResourceAddress::Rule {
+                    region: region.to_string(),
+                    event_bus: event_bus.to_string(),
+                    name,
+                })
+            }
+            ["aws", "eventbridge", region, "archives", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(EventBridgeResourceAddress::Archive {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for EventBridgeResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/eventbridge/<region>/event_buses/<name>.ron",
+                description: "An event bus",
+                example:     "aws/eventbridge/us-east-1/event_buses/orders.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/eventbridge/<region>/event_buses/<event_bus>/rules/<name>.ron",
+                description: "A rule on an event bus, along with its targets",
+                example:     "aws/eventbridge/us-east-1/event_buses/default/rules/nightly-report.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/eventbridge/<region>/archives/<name>.ron",
+                description: "An event archive",
+                example:     "aws/eventbridge/us-east-1/archives/orders-archive.ron",
+            },
+        ]
+    }
+}
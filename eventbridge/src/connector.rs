@@ -0,0 +1,284 @@
+pub use crate::addr::EventBridgeResourceAddress;
+pub use crate::op::EventBridgeConnectorOp;
+pub use crate::resource::EventBridgeResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+pub mod task_exec;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse, TaskExecResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::EventBridgeConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{Archive, EventBus, InputTransformer, RetryPolicy, Rule, Target};
+use crate::tags::Tags;
+use crate::task::{EventBridgeTask, EventBridgeTaskAddress, StartReplay};
+use crate::util::json_to_ron;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct EventBridgeConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_eventbridge::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<EventBridgeConnectorConfig>,
+    prefix: PathBuf,
+}
+
+impl EventBridgeConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_eventbridge::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+            let config = aws_config::defaults(BehaviorVersion::latest())
+                .region(region)
+                .timeout_config(
+                    TimeoutConfig::builder()
+                        .connect_timeout(Duration::from_secs(30))
+                        .operation_timeout(Duration::from_secs(30))
+                        .operation_attempt_timeout(Duration::from_secs(30))
+                        .read_timeout(Duration::from_secs(30))
+                        .build(),
+                )
+                .load()
+                .await;
+            let client = audited_client!(aws_sdk_eventbridge, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for EventBridgeConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = EventBridgeResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else if let Ok(_addr) = EventBridgeTaskAddress::from_path(addr) {
+            Ok(FilterResponse::Task)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(EventBridgeConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let eventbridge_config: EventBridgeConnectorConfig = EventBridgeConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = eventbridge_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(eventbridge_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = eventbridge_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn task_exec(
+        &self,
+        addr: &Path,
+        body: Vec<u8>,
+
+        arg: Option<Vec<u8>>,
+        state: Option<Vec<u8>>,
+    ) -> anyhow::Result<TaskExecResponse> {
+        self.do_task_exec(addr, body, arg, state).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // Custom event bus skeleton
+        res.push(skeleton!(
+            EventBridgeResourceAddress::EventBus {
+                region: String::from("[region]"),
+                name:   String::from("[event_bus_name]"),
+            },
+            EventBridgeResource::EventBus(EventBus {
+                description: Some(String::from("[description]")),
+                kms_key_identifier: None,
+                dead_letter_arn: None,
+                policy: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        // Event pattern rule skeleton, delivering to an SQS queue with an input transformer
+        res.push(skeleton!(
+            EventBridgeResourceAddress::Rule {
+                region: String::from("[region]"),
+                event_bus: String::from("[event_bus_name]"),
+                name: String::from("[rule_name]"),
+            },
+            EventBridgeResource::Rule(Rule {
+                description: Some(String::from("[description]")),
+                event_pattern: Some(json_to_ron(r#"{"source": ["[source]"], "detail-type": ["[detail_type]"]}"#)?),
+                schedule_expression: None,
+                state: None,
+                role_arn: None,
+                targets: HashMap::from([(
+                    String::from("[target_id]"),
+                    Target {
+                        arn: String::from("arn:aws:sqs:[region]:[account_id]:[queue_name]"),
+                        role_arn: None,
+                        input: None,
+                        input_path: None,
+                        input_transformer: Some(InputTransformer {
+                            input_paths_map: HashMap::from([(String::from("detail_type"), String::from("$.detail-type"))]),
+                            input_template:  String::from(r#"{"type": <detail_type>}"#),
+                        }),
+                        dead_letter_arn: Some(String::from("arn:aws:sqs:[region]:[account_id]:[dead_letter_queue_name]")),
+                        retry_policy: Some(RetryPolicy {
+                            maximum_retry_attempts: Some(3),
+                            maximum_event_age_in_seconds: Some(3600),
+                        }),
+                    },
+                )]),
+                tags: Tags::default(),
+            })
+        ));
+
+        // Scheduled rule skeleton, invoking a Lambda function
+        res.push(skeleton!(
+            EventBridgeResourceAddress::Rule {
+                region: String::from("[region]"),
+                event_bus: String::from("default"),
+                name: String::from("[rule_name]"),
+            },
+            EventBridgeResource::Rule(Rule {
+                description: Some(String::from("[description]")),
+                event_pattern: None,
+                schedule_expression: Some(String::from("rate(5 minutes)")),
+                state: None,
+                role_arn: None,
+                targets: HashMap::from([(
+                    String::from("[target_id]"),
+                    Target {
+                        arn: String::from("arn:aws:lambda:[region]:[account_id]:function:[function_name]"),
+                        role_arn: None,
+                        input: None,
+                        input_path: None,
+                        input_transformer: None,
+                        dead_letter_arn: None,
+                        retry_policy: None,
+                    },
+                )]),
+                tags: Tags::default(),
+            })
+        ));
+
+        // Archive skeleton
+        res.push(skeleton!(
+            EventBridgeResourceAddress::Archive {
+                region: String::from("[region]"),
+                name:   String::from("[archive_name]"),
+            },
+            EventBridgeResource::Archive(Archive {
+                event_source_arn: String::from("arn:aws:events:[region]:[account_id]:event-bus/[event_bus_name]"),
+                description: Some(String::from("[description]")),
+                event_pattern: None,
+                retention_days: Some(30),
+            })
+        ));
+
+        // Replay task skeleton
+        res.push(skeleton!(
+            EventBridgeTaskAddress::Replay {
+                name: String::from("[task_name]"),
+            },
+            EventBridgeTask::Replay(StartReplay {
+                region: String::from("[region]"),
+                archive_name: String::from("[archive_name]"),
+                event_start_time: String::from("2025-01-01T00:00:00Z"),
+                event_end_time: String::from("2025-01-02T00:00:00Z"),
+                destination_event_bus: String::from("[event_bus_name]"),
+                destination_rules: Vec::new(),
+                description: None,
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = EventBridgeResourceAddress::from_path(addr)?;
+
+        match addr {
+            EventBridgeResourceAddress::EventBus { .. } => ron_check_eq::<EventBus>(a, b),
+            EventBridgeResourceAddress::Rule { .. } => ron_check_eq::<Rule>(a, b),
+            EventBridgeResourceAddress::Archive { .. } => ron_check_eq::<Archive>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = EventBridgeResourceAddress::from_path(addr)?;
+
+        match addr {
+            EventBridgeResourceAddress::EventBus { .. } => ron_check_syntax::<EventBus>(a),
+            EventBridgeResourceAddress::Rule { .. } => ron_check_syntax::<Rule>(a),
+            EventBridgeResourceAddress::Archive { .. } => ron_check_syntax::<Archive>(a),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use std::{collections::HashMap, path::Path};
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_eventbridge::operation::{
+    describe_archive::DescribeArchiveError, describe_event_bus::DescribeEventBusError, describe_rule::DescribeRuleError,
+};
+
+use crate::{
+    addr::EventBridgeResourceAddress,
+    resource::{Archive, EventBridgeResource, EventBus, Rule},
+    util::{archive_arn, event_bus_arn, from_sdk_target, json_to_ron, rule_arn},
+};
+
+use super::EventBridgeConnector;
+
+impl EventBridgeConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = EventBridgeResourceAddress::from_path(addr)?;
+
+        let account_id = self.account_id.lock().await.clone();
+
+        match &addr {
+            EventBridgeResourceAddress::EventBus { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.describe_event_bus().name(name).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(DescribeEventBusError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let event_bus_arn = resp.arn.unwrap_or_else(|| event_bus_arn(region, &account_id, name));
+
+                let policy = match resp.policy.filter(|p| !p.is_empty()) {
+                    Some(policy) => Some(json_to_ron(&policy)?),
+                    None => None,
+                };
+
+                let tags_resp = client.list_tags_for_resource().resource_arn(&event_bus_arn).send().await?;
+
+                let event_bus = EventBus {
+                    description: resp.description.filter(|d| !d.is_empty()),
+                    kms_key_identifier: resp.kms_key_identifier.filter(|k| !k.is_empty()),
+                    dead_letter_arn: resp.dead_letter_config.and_then(|c| c.arn),
+                    policy,
+                    tags: tags_resp.tags.into(),
+                };
+
+                get_resource_response!(
+                    EventBridgeResource::EventBus(event_bus),
+                    [(String::from("event_bus_arn"), event_bus_arn)]
+                )
+            }
+            EventBridgeResourceAddress::Rule { region, event_bus, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.describe_rule().name(name).event_bus_name(event_bus).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(DescribeRuleError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let rule_arn = resp.arn.unwrap_or_else(|| rule_arn(region, &account_id, event_bus, name));
+
+                let event_pattern = match resp.event_pattern.filter(|p| !p.is_empty()) {
+                    Some(event_pattern) => Some(json_to_ron(&event_pattern)?),
+                    None => None,
+                };
+
+                let mut targets = HashMap::new();
+                let mut next_token = None;
+                loop {
+                    let targets_resp = client
+                        .list_targets_by_rule()
+                        .rule(name)
+                        .event_bus_name(event_bus)
+                        .set_next_token(next_token)
+                        .send()
+                        .await?;
+
+                    for target in targets_resp.targets.unwrap_or_default() {
+                        let (id, target) = from_sdk_target(target);
+                        targets.insert(id, target);
+                    }
+
+                    next_token = targets_resp.next_token;
+                    if next_token.is_none() {
+                        break;
+                    }
+                }
+
+                let tags_resp = client.list_tags_for_resource().resource_arn(&rule_arn).send().await?;
+
+                let rule = Rule {
+                    description: resp.description.filter(|d| !d.is_empty()),
+                    event_pattern,
+                    schedule_expression: resp.schedule_expression.filter(|s| !s.is_empty()),
+                    // ENABLED is the default state
+                    state: resp.state.map(|s| s.as_str().to_string()).filter(|s| s != "ENABLED"),
+                    role_arn: resp.role_arn,
+                    targets,
+                    tags: tags_resp.tags.into(),
+                };
+
+                get_resource_response!(EventBridgeResource::Rule(rule), [(String::from("rule_arn"), rule_arn)])
+            }
+            EventBridgeResourceAddress::Archive { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.describe_archive().archive_name(name).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(DescribeArchiveError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let archive_arn = resp.archive_arn.unwrap_or_else(|| archive_arn(region, &account_id, name));
+
+                let event_pattern = match resp.event_pattern.filter(|p| !p.is_empty()) {
+                    Some(event_pattern) => Some(json_to_ron(&event_pattern)?),
+                    None => None,
+                };
+
+                let archive = Archive {
+                    event_source_arn: resp.event_source_arn.unwrap_or_default(),
+                    description: resp.description.filter(|d| !d.is_empty()),
+                    event_pattern,
+                    // Zero means events are kept indefinitely
+                    retention_days: resp.retention_days.filter(|d| *d > 0),
+                };
+
+                get_resource_response!(
+                    EventBridgeResource::Archive(archive),
+                    [(String::from("archive_arn"), archive_arn)]
+                )
+            }
+        }
+    }
+}
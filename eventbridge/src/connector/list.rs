@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::addr::EventBridgeResourceAddress;
+
+use super::EventBridgeConnector;
+
+impl EventBridgeConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut event_buses = Vec::new();
+            let mut next_token = None;
+            loop {
+                let resp = client.list_event_buses().set_next_token(next_token).send().await?;
+                for event_bus in resp.event_buses.unwrap_or_default() {
+                    // Partner event buses are named after their event source, e.g. aws.partner/example.com/123
+                    if let Some(name) = event_bus.name
+                        && !name.contains('/')
+                    {
+                        event_buses.push(name);
+                    }
+                }
+
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+
+            for event_bus in event_buses {
+                // The default bus always exists, so it's only a resource once there's a file for it
+                if event_bus != "default" {
+                    results.push(
+                        EventBridgeResourceAddress::EventBus {
+                            region: region.clone(),
+                            name:   event_bus.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                let mut next_token = None;
+                loop {
+                    let resp = client
+                        .list_rules()
+                        .event_bus_name(&event_bus)
+                        .set_next_token(next_token)
+                        .send()
+                        .await?;
+
+                    for rule in resp.rules.unwrap_or_default() {
+                        // Rules created by other AWS services on our behalf can't be modified
+                        if rule.managed_by.is_some() {
+                            continue;
+                        }
+                        if let Some(name) = rule.name {
+                            results.push(
+                                EventBridgeResourceAddress::Rule {
+                                    region: region.clone(),
+                                    event_bus: event_bus.clone(),
+                                    name,
+                                }
+                                .to_path_buf(),
+                            );
+                        }
+                    }
+
+                    next_token = resp.next_token;
+                    if next_token.is_none() {
+                        break;
+                    }
+                }
+            }
+
+            let mut next_token = None;
+            loop {
+                let resp = client.list_archives().set_next_token(next_token).send().await?;
+                for archive in resp.archives.unwrap_or_default() {
+                    if let Some(name) = archive.archive_name {
+                        results.push(
+                            EventBridgeResourceAddress::Archive {
+                                region: region.clone(),
+                                name,
+                            }
+                            .to_path_buf(),
+                        );
+                    }
+                }
+
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
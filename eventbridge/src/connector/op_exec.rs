@@ -0,0 +1,76 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::connector::{ConnectorOp, OpExecResponse, ResourceAddress};
+
+use crate::{
+    addr::EventBridgeResourceAddress,
+    op::EventBridgeConnectorOp,
+    op_impl,
+    util::{event_bus_arn, rule_arn},
+};
+
+use super::EventBridgeConnector;
+
+impl EventBridgeConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = EventBridgeResourceAddress::from_path(addr)?;
+        let op = EventBridgeConnectorOp::from_str(op)?;
+
+        let account_id = self.account_id.lock().await.clone();
+
+        match &addr {
+            EventBridgeResourceAddress::EventBus { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    EventBridgeConnectorOp::CreateEventBus(event_bus) => {
+                        op_impl::create_event_bus(&client, name, &event_bus).await
+                    }
+                    EventBridgeConnectorOp::UpdateEventBus(event_bus) => {
+                        op_impl::update_event_bus(&client, name, &event_bus).await
+                    }
+                    EventBridgeConnectorOp::SetEventBusPolicy(policy) => {
+                        op_impl::set_event_bus_policy(&client, name, &policy).await
+                    }
+                    EventBridgeConnectorOp::UpdateEventBusTags(old_tags, new_tags) => {
+                        let event_bus_arn = event_bus_arn(region, &account_id, name);
+                        op_impl::update_tags(&client, &event_bus_arn, &old_tags, &new_tags).await
+                    }
+                    EventBridgeConnectorOp::DeleteEventBus => op_impl::delete_event_bus(&client, name).await,
+                    _ => bail!("Invalid operation for EventBridge event bus resource"),
+                }
+            }
+            EventBridgeResourceAddress::Rule { region, event_bus, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    EventBridgeConnectorOp::CreateRule(rule) => op_impl::create_rule(&client, event_bus, name, &rule).await,
+                    EventBridgeConnectorOp::UpdateRule(rule) => op_impl::update_rule(&client, event_bus, name, &rule).await,
+                    EventBridgeConnectorOp::PutTargets(targets) => {
+                        op_impl::put_targets(&client, event_bus, name, &targets).await
+                    }
+                    EventBridgeConnectorOp::RemoveTargets(ids) => {
+                        op_impl::remove_targets(&client, event_bus, name, &ids).await
+                    }
+                    EventBridgeConnectorOp::UpdateRuleTags(old_tags, new_tags) => {
+                        let rule_arn = rule_arn(region, &account_id, event_bus, name);
+                        op_impl::update_tags(&client, &rule_arn, &old_tags, &new_tags).await
+                    }
+                    EventBridgeConnectorOp::DeleteRule => op_impl::delete_rule(&client, event_bus, name).await,
+                    _ => bail!("Invalid operation for EventBridge rule resource"),
+                }
+            }
+            EventBridgeResourceAddress::Archive { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    EventBridgeConnectorOp::CreateArchive(archive) => op_impl::create_archive(&client, name, &archive).await,
+                    EventBridgeConnectorOp::UpdateArchive(archive) => op_impl::update_archive(&client, name, &archive).await,
+                    EventBridgeConnectorOp::DeleteArchive => op_impl::delete_archive(&client, name).await,
+                    _ => bail!("Invalid operation for EventBridge archive resource"),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::resource::{Archive, EventBus, Rule, Target};
+
+use super::{EventBridgeConnector, EventBridgeConnectorOp, EventBridgeResourceAddress};
+
+/// A rule has to match events, run on a schedule, or both, and only the default bus receives scheduled events.
+fn check_rule(event_bus: &str, rule_name: &str, rule: &Rule) -> anyhow::Result<()> {
+    if rule.event_pattern.is_none() && rule.schedule_expression.is_none() {
+        bail!("EventBridge rule {} needs an event_pattern or a schedule_expression", rule_name);
+    }
+
+    if rule.schedule_expression.is_some() && event_bus != "default" {
+        bail!(
+            "EventBridge rule {} has a schedule_expression, but scheduled rules can only be created on the default event bus",
+            rule_name
+        );
+    }
+
+    for (id, target) in &rule.targets {
+        check_target(rule_name, id, target)?;
+    }
+
+    Ok(())
+}
+
+fn check_target(rule_name: &str, id: &str, target: &Target) -> anyhow::Result<()> {
+    let input_count = [
+        target.input.is_some(),
+        target.input_path.is_some(),
+        target.input_transformer.is_some(),
+    ]
+    .iter()
+    .filter(|set| **set)
+    .count();
+
+    if input_count > 1 {
+        bail!(
+            "Target {} of EventBridge rule {} can only set one of input, input_path and input_transformer",
+            id,
+            rule_name
+        );
+    }
+
+    Ok(())
+}
+
+impl EventBridgeConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = EventBridgeResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            EventBridgeResourceAddress::EventBus { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_event_bus)) => {
+                    if name == "default" {
+                        bail!("The default event bus in {} already exists and can't be created", region);
+                    }
+                    let new_event_bus: EventBus = RON.from_str(&new_event_bus)?;
+                    Ok(vec![connector_op!(
+                        EventBridgeConnectorOp::CreateEventBus(new_event_bus),
+                        format!("Create new EventBridge event bus {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_event_bus), None) => {
+                    if name == "default" {
+                        bail!("The default event bus in {} can't be deleted", region);
+                    }
+                    Ok(vec![connector_op!(
+                        EventBridgeConnectorOp::DeleteEventBus,
+                        format!("DELETE EventBridge event bus {} in region {}", name, region)
+                    )])
+                }
+                (Some(old_event_bus), Some(new_event_bus)) => {
+                    let old_event_bus: EventBus = RON.from_str(&old_event_bus)?;
+                    let new_event_bus: EventBus = RON.from_str(&new_event_bus)?;
+                    let mut ops = Vec::new();
+
+                    if old_event_bus.tags != new_event_bus.tags {
+                        let diff = diff_ron_values(&old_event_bus.tags, &new_event_bus.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            EventBridgeConnectorOp::UpdateEventBusTags(old_event_bus.tags.clone(), new_event_bus.tags.clone()),
+                            format!("Modify tags for EventBridge event bus `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    if old_event_bus.policy != new_event_bus.policy {
+                        let diff = diff_ron_values(&old_event_bus.policy, &new_event_bus.policy).unwrap_or_default();
+                        ops.push(connector_op!(
+                            EventBridgeConnectorOp::SetEventBusPolicy(new_event_bus.policy.clone()),
+                            format!("Modify policy for EventBridge event bus `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    if old_event_bus.description != new_event_bus.description
+                        || old_event_bus.kms_key_identifier != new_event_bus.kms_key_identifier
+                        || old_event_bus.dead_letter_arn != new_event_bus.dead_letter_arn
+                    {
+                        let old_settings = EventBus {
+                            policy: new_event_bus.policy.clone(),
+                            tags: new_event_bus.tags.clone(),
+                            ..old_event_bus
+                        };
+                        let diff = diff_ron_values(&old_settings, &new_event_bus).unwrap_or_default();
+                        ops.push(connector_op!(
+                            EventBridgeConnectorOp::UpdateEventBus(new_event_bus),
+                            format!("Modify EventBridge event bus `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            EventBridgeResourceAddress::Rule { region, event_bus, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_rule)) => {
+                    let new_rule: Rule = RON.from_str(&new_rule)?;
+                    check_rule(event_bus, name, &new_rule)?;
+                    Ok(vec![connector_op!(
+                        EventBridgeConnectorOp::CreateRule(new_rule),
+                        format!(
+                            "Create new EventBridge rule {} on event bus {} in region {}",
+                            name, event_bus, region
+                        )
+                    )])
+                }
+                (Some(_old_rule), None) => Ok(vec![connector_op!(
+                    EventBridgeConnectorOp::DeleteRule,
+                    format!(
+                        "DELETE EventBridge rule {} on event bus {} in region {}, along with its targets",
+                        name, event_bus, region
+                    )
+                )]),
+                (Some(old_rule), Some(new_rule)) => {
+                    let old_rule: Rule = RON.from_str(&old_rule)?;
+                    let new_rule: Rule = RON.from_str(&new_rule)?;
+                    check_rule(event_bus, name, &new_rule)?;
+                    let mut ops = Vec::new();
+
+                    if old_rule.tags != new_rule.tags {
+                        let diff = diff_ron_values(&old_rule.tags, &new_rule.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            EventBridgeConnectorOp::UpdateRuleTags(old_rule.tags.clone(), new_rule.tags.clone()),
+                            format!("Modify tags for EventBridge rule `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let old_settings = Rule {
+                        targets: new_rule.targets.clone(),
+                        tags: new_rule.tags.clone(),
+                        ..old_rule.clone()
+                    };
+                    if old_settings != new_rule {
+                        let diff = diff_ron_values(&old_settings, &new_rule).unwrap_or_default();
+                        ops.push(connector_op!(
+                            EventBridgeConnectorOp::UpdateRule(new_rule.clone()),
+                            format!("Modify EventBridge rule `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let mut removed: Vec<String> = old_rule
+                        .targets
+                        .keys()
+                        .filter(|id| !new_rule.targets.contains_key(*id))
+                        .cloned()
+                        .collect();
+                    removed.sort();
+
+                    if !removed.is_empty() {
+                        ops.push(connector_op!(
+                            EventBridgeConnectorOp::RemoveTargets(removed.clone()),
+                            format!("Remove targets {} from EventBridge rule `{}`", removed.join(", "), name)
+                        ));
+                    }
+
+                    let changed: HashMap<String, Target> = new_rule
+                        .targets
+                        .iter()
+                        .filter(|(id, target)| old_rule.targets.get(*id) != Some(*target))
+                        .map(|(id, target)| (id.clone(), target.clone()))
+                        .collect();
+
+                    if !changed.is_empty() {
+                        let old_targets: HashMap<&String, Option<&Target>> =
+                            changed.keys().map(|id| (id, old_rule.targets.get(id))).collect();
+                        let new_targets: HashMap<&String, Option<&Target>> =
+                            changed.iter().map(|(id, target)| (id, Some(target))).collect();
+                        let diff = diff_ron_values(&old_targets, &new_targets).unwrap_or_default();
+                        ops.push(connector_op!(
+                            EventBridgeConnectorOp::PutTargets(changed),
+                            format!("Put targets on EventBridge rule `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            EventBridgeResourceAddress::Archive { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_archive)) => {
+                    let new_archive: Archive = RON.from_str(&new_archive)?;
+                    Ok(vec![connector_op!(
+                        EventBridgeConnectorOp::CreateArchive(new_archive),
+                        format!("Create new EventBridge archive {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_archive), None) => Ok(vec![connector_op!(
+                    EventBridgeConnectorOp::DeleteArchive,
+                    format!("DELETE EventBridge archive {} in region {}, along with its events", name, region)
+                )]),
+                (Some(old_archive), Some(new_archive)) => {
+                    let old_archive: Archive = RON.from_str(&old_archive)?;
+                    let new_archive: Archive = RON.from_str(&new_archive)?;
+
+                    if old_archive == new_archive {
+                        return Ok(Vec::new());
+                    }
+
+                    // An archive's source bus can't be changed in place
+                    if old_archive.event_source_arn != new_archive.event_source_arn {
+                        return Ok(vec![
+                            connector_op!(
+                                EventBridgeConnectorOp::DeleteArchive,
+                                format!(
+                                    "REPLACE EventBridge archive {} in region {}, discarding its events (requires replacement: event_source_arn)",
+                                    name, region
+                                )
+                            ),
+                            connector_op!(
+                                EventBridgeConnectorOp::CreateArchive(new_archive),
+                                format!("Create new EventBridge archive {} in region {}", name, region)
+                            ),
+                        ]);
+                    }
+
+                    let diff = diff_ron_values(&old_archive, &new_archive).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        EventBridgeConnectorOp::UpdateArchive(new_archive),
+                        format!("Modify EventBridge archive `{}`\n{}", name, diff)
+                    )])
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::{
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, bail};
+use autoschematic_core::{
+    connector::{Resource, ResourceAddress, TaskExecResponse},
+    util::RON,
+};
+use aws_sdk_eventbridge::{
+    primitives::{DateTime, DateTimeFormat},
+    types::{ReplayDestination, ReplayState},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    task::{EventBridgeTask, EventBridgeTaskAddress, StartReplay},
+    util::{archive_arn, event_bus_arn, rule_arn},
+};
+
+use super::EventBridgeConnector;
+
+#[derive(Serialize, Deserialize)]
+enum ReplayTaskState {
+    Replaying { replay_name: String },
+}
+
+impl EventBridgeConnector {
+    pub async fn do_task_exec(
+        &self,
+        addr: &Path,
+        body: Vec<u8>,
+        _arg: Option<Vec<u8>>,
+        state: Option<Vec<u8>>,
+    ) -> anyhow::Result<TaskExecResponse> {
+        let addr = EventBridgeTaskAddress::from_path(addr)?;
+
+        let task = EventBridgeTask::from_bytes(&addr, &body)?;
+        match (task, &addr) {
+            (EventBridgeTask::Replay(replay), EventBridgeTaskAddress::Replay { name }) => {
+                self.replay(name, replay, state).await
+            }
+        }
+    }
+
+    /// Replays run asynchronously: the first run starts the replay, and later runs poll until it has finished.
+    async fn replay(&self, task_name: &str, replay: StartReplay, state: Option<Vec<u8>>) -> anyhow::Result<TaskExecResponse> {
+        let now_secs = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let client = self.get_or_init_client(&replay.region).await?;
+
+        let Some(state) = state else {
+            let account_id = self.account_id.lock().await.clone();
+
+            let event_start_time = DateTime::from_str(&replay.event_start_time, DateTimeFormat::DateTime)
+                .with_context(|| format!("Invalid event_start_time: {}", replay.event_start_time))?;
+            let event_end_time = DateTime::from_str(&replay.event_end_time, DateTimeFormat::DateTime)
+                .with_context(|| format!("Invalid event_end_time: {}", replay.event_end_time))?;
+
+            let filter_arns: Vec<String> = replay
+                .destination_rules
+                .iter()
+                .map(|rule| rule_arn(&replay.region, &account_id, &replay.destination_event_bus, rule))
+                .collect();
+
+            let destination = ReplayDestination::builder()
+                .arn(event_bus_arn(&replay.region, &account_id, &replay.destination_event_bus))
+                .set_filter_arns(if filter_arns.is_empty() { None } else { Some(filter_arns) })
+                .build()?;
+
+            // Replay names have to be unique, so every run of the task starts a new one
+            let replay_name = format!("{}-{}", task_name, now_secs);
+
+            client
+                .start_replay()
+                .replay_name(&replay_name)
+                .set_description(replay.description.clone())
+                .event_source_arn(archive_arn(&replay.region, &account_id, &replay.archive_name))
+                .event_start_time(event_start_time)
+                .event_end_time(event_end_time)
+                .destination(destination)
+                .send()
+                .await?;
+
+            let friendly_message = format!(
+                "Started replay {} of EventBridge archive {} onto event bus {}",
+                replay_name, replay.archive_name, replay.destination_event_bus
+            );
+            return Ok(TaskExecResponse {
+                next_state: Some(RON.to_string(&ReplayTaskState::Replaying { replay_name })?.into_bytes()),
+                friendly_message: Some(friendly_message),
+                delay_until: Some(now_secs + 30),
+                ..Default::default()
+            });
+        };
+
+        let ReplayTaskState::Replaying { replay_name } = RON.from_bytes(&state)?;
+
+        let resp = client.describe_replay().replay_name(&replay_name).send().await?;
+
+        match resp.state {
+            Some(ReplayState::Completed) => Ok(TaskExecResponse {
+                friendly_message: Some(format!(
+                    "Replay {} of EventBridge archive {} completed",
+                    replay_name, replay.archive_name
+                )),
+                ..Default::default()
+            }),
+            Some(ReplayState::Failed) | Some(ReplayState::Cancelled) => {
+                bail!(
+                    "Replay {} of EventBridge archive {} did not complete ({}): {}",
+                    replay_name,
+                    replay.archive_name,
+                    resp.state.map(|s| s.as_str().to_string()).unwrap_or_default(),
+                    resp.state_reason.unwrap_or_default()
+                );
+            }
+            _ => {
+                let progress = match resp.event_last_replayed_time {
+                    Some(last_replayed) => format!(", replayed events up to {}", last_replayed),
+                    None => String::new(),
+                };
+                Ok(TaskExecResponse {
+                    next_state: Some(RON.to_string(&ReplayTaskState::Replaying { replay_name })?.into_bytes()),
+                    friendly_message: Some(format!("Waiting for EventBridge replay to complete{}", progress)),
+                    delay_until: Some(now_secs + 30),
+                    ..Default::default()
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use addr::EventBridgeResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
+use autoschematic_core::tarpc_bridge::tarpc_connector_main;
+use connector::EventBridgeConnector;
+use task::EventBridgeTaskAddress;
+
+pub mod connector;
+pub mod addr;
+pub mod config;
+pub mod op;
+pub mod op_impl;
+pub mod resource;
+pub mod tags;
+pub mod task;
+pub mod util;
+
+#[tokio::main]
+pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let mut patterns = EventBridgeResourceAddress::checked_address_patterns()?;
+        patterns.extend(EventBridgeTaskAddress::checked_address_patterns()?);
+        print!("{}", render_address_docs("aws/eventbridge", &patterns));
+        return Ok(());
+    }
+
+    tarpc_connector_main::<EventBridgeConnector>().await?;
+    Ok(())
+}
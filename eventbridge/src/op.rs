@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{Archive, EventBus, Rule, Target},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum EventBridgeConnectorOp {
+    // Event bus operations
+    CreateEventBus(EventBus),
+    /// Sets the description, KMS key and dead-letter queue. The policy and tags are managed separately.
+    UpdateEventBus(EventBus),
+    /// None removes every permission from the bus.
+    SetEventBusPolicy(Option<ron::Value>),
+    UpdateEventBusTags(Tags, Tags),
+    DeleteEventBus,
+
+    // Rule operations
+    CreateRule(Rule),
+    /// Sets everything but the targets and tags.
+    UpdateRule(Rule),
+    /// Adds or replaces the given targets, keyed by target ID.
+    PutTargets(HashMap<String, Target>),
+    RemoveTargets(Vec<String>),
+    UpdateRuleTags(Tags, Tags),
+    DeleteRule,
+
+    // Archive operations
+    CreateArchive(Archive),
+    UpdateArchive(Archive),
+    DeleteArchive,
+}
+
+impl ConnectorOp for EventBridgeConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
@@ -0,0 +1,399 @@
+use std::collections::HashMap;
+
+use anyhow::bail;
+use autoschematic_core::connector::OpExecResponse;
+use aws_sdk_eventbridge::types::{DeadLetterConfig, RuleState};
+
+use crate::{
+    resource::{Archive, EventBus, Rule, Target},
+    tags::{Tags, tag_diff},
+    util::{dead_letter_config, ron_to_json, to_sdk_targets},
+};
+
+/// Creates an event bus using the provided configuration, then attaches its policy
+pub async fn create_event_bus(
+    client: &aws_sdk_eventbridge::Client,
+    name: &str,
+    event_bus: &EventBus,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client
+        .create_event_bus()
+        .name(name)
+        .set_description(event_bus.description.clone())
+        .set_kms_key_identifier(event_bus.kms_key_identifier.clone())
+        .set_dead_letter_config(dead_letter_config(&event_bus.dead_letter_arn));
+
+    if event_bus.tags.len() > 0 {
+        request = request.set_tags(Some(event_bus.tags.to_vec()?));
+    }
+
+    let resp = request.send().await?;
+
+    if let Some(policy) = &event_bus.policy {
+        client
+            .put_permission()
+            .event_bus_name(name)
+            .policy(ron_to_json(policy)?)
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("event_bus_arn"), resp.event_bus_arn)])),
+        friendly_message: Some(format!("Created EventBridge event bus {}", name)),
+    })
+}
+
+/// Updates an event bus's description, KMS key and dead-letter queue
+pub async fn update_event_bus(
+    client: &aws_sdk_eventbridge::Client,
+    name: &str,
+    event_bus: &EventBus,
+) -> Result<OpExecResponse, anyhow::Error> {
+    // An empty dead-letter config removes the queue
+    let dead_letter_config =
+        dead_letter_config(&event_bus.dead_letter_arn).unwrap_or_else(|| DeadLetterConfig::builder().build());
+
+    client
+        .update_event_bus()
+        .name(name)
+        .description(event_bus.description.clone().unwrap_or_default())
+        .set_kms_key_identifier(event_bus.kms_key_identifier.clone())
+        .dead_letter_config(dead_letter_config)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated EventBridge event bus {}", name)),
+    })
+}
+
+/// Replaces an event bus's resource policy, or removes every permission if `policy` is None
+pub async fn set_event_bus_policy(
+    client: &aws_sdk_eventbridge::Client,
+    name: &str,
+    policy: &Option<ron::Value>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    match policy {
+        Some(policy) => {
+            client
+                .put_permission()
+                .event_bus_name(name)
+                .policy(ron_to_json(policy)?)
+                .send()
+                .await?;
+        }
+        None => {
+            client
+                .remove_permission()
+                .event_bus_name(name)
+                .remove_all_permissions(true)
+                .send()
+                .await?;
+        }
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated policy for EventBridge event bus {}", name)),
+    })
+}
+
+/// Updates tags on an event bus, rule or archive
+pub async fn update_tags(
+    client: &aws_sdk_eventbridge::Client,
+    arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags)?;
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client
+            .tag_resource()
+            .resource_arn(arn)
+            .set_tags(Some(new_tagset))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for {}", arn)),
+    })
+}
+
+/// Deletes an event bus. EventBridge refuses while rules remain on it.
+pub async fn delete_event_bus(client: &aws_sdk_eventbridge::Client, name: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_event_bus().name(name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("event_bus_arn"), None)])),
+        friendly_message: Some(format!("Deleted EventBridge event bus {}", name)),
+    })
+}
+
+/// Calls PutRule, which creates the rule or overwrites every setting of an existing one
+async fn put_rule(
+    client: &aws_sdk_eventbridge::Client,
+    event_bus: &str,
+    name: &str,
+    rule: &Rule,
+    tags: Option<&Tags>,
+) -> anyhow::Result<Option<String>> {
+    let event_pattern = match &rule.event_pattern {
+        Some(event_pattern) => Some(ron_to_json(event_pattern)?),
+        None => None,
+    };
+
+    let mut request = client
+        .put_rule()
+        .name(name)
+        .event_bus_name(event_bus)
+        .set_description(rule.description.clone())
+        .set_event_pattern(event_pattern)
+        .set_schedule_expression(rule.schedule_expression.clone())
+        .state(RuleState::from(rule.state.as_deref().unwrap_or("ENABLED")))
+        .set_role_arn(rule.role_arn.clone());
+
+    // Tags passed to PutRule only apply to new rules
+    if let Some(tags) = tags
+        && tags.len() > 0
+    {
+        request = request.set_tags(Some(tags.to_vec()?));
+    }
+
+    let resp = request.send().await?;
+    Ok(resp.rule_arn)
+}
+
+/// Creates a rule and its targets
+pub async fn create_rule(
+    client: &aws_sdk_eventbridge::Client,
+    event_bus: &str,
+    name: &str,
+    rule: &Rule,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let rule_arn = put_rule(client, event_bus, name, rule, Some(&rule.tags)).await?;
+
+    if !rule.targets.is_empty() {
+        put_targets(client, event_bus, name, &rule.targets).await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("rule_arn"), rule_arn)])),
+        friendly_message: Some(format!(
+            "Created EventBridge rule {} on event bus {} with {} targets",
+            name,
+            event_bus,
+            rule.targets.len()
+        )),
+    })
+}
+
+/// Updates a rule's pattern, schedule, state, description and role
+pub async fn update_rule(
+    client: &aws_sdk_eventbridge::Client,
+    event_bus: &str,
+    name: &str,
+    rule: &Rule,
+) -> Result<OpExecResponse, anyhow::Error> {
+    put_rule(client, event_bus, name, rule, None).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated EventBridge rule {} on event bus {}", name, event_bus)),
+    })
+}
+
+/// Adds or replaces targets on a rule. PutTargets reports per-target failures rather than an error.
+pub async fn put_targets(
+    client: &aws_sdk_eventbridge::Client,
+    event_bus: &str,
+    name: &str,
+    targets: &HashMap<String, Target>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let resp = client
+        .put_targets()
+        .rule(name)
+        .event_bus_name(event_bus)
+        .set_targets(Some(to_sdk_targets(targets)?))
+        .send()
+        .await?;
+
+    if resp.failed_entry_count > 0 {
+        let failures: Vec<String> = resp
+            .failed_entries
+            .unwrap_or_default()
+            .into_iter()
+            .map(|e| {
+                format!(
+                    "{}: {}",
+                    e.target_id.unwrap_or_default(),
+                    e.error_message.or(e.error_code).unwrap_or_default()
+                )
+            })
+            .collect();
+        bail!("Failed to put targets on EventBridge rule {}: {}", name, failures.join(", "));
+    }
+
+    let mut ids: Vec<&String> = targets.keys().collect();
+    ids.sort();
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Put targets {} on EventBridge rule {}",
+            ids.iter().map(|id| id.as_str()).collect::<Vec<_>>().join(", "),
+            name
+        )),
+    })
+}
+
+/// Removes targets from a rule
+pub async fn remove_targets(
+    client: &aws_sdk_eventbridge::Client,
+    event_bus: &str,
+    name: &str,
+    ids: &[String],
+) -> Result<OpExecResponse, anyhow::Error> {
+    let resp = client
+        .remove_targets()
+        .rule(name)
+        .event_bus_name(event_bus)
+        .set_ids(Some(ids.to_vec()))
+        .send()
+        .await?;
+
+    if resp.failed_entry_count > 0 {
+        let failures: Vec<String> = resp
+            .failed_entries
+            .unwrap_or_default()
+            .into_iter()
+            .map(|e| {
+                format!(
+                    "{}: {}",
+                    e.target_id.unwrap_or_default(),
+                    e.error_message.or(e.error_code).unwrap_or_default()
+                )
+            })
+            .collect();
+        bail!("Failed to remove targets from EventBridge rule {}: {}", name, failures.join(", "));
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Removed targets {} from EventBridge rule {}", ids.join(", "), name)),
+    })
+}
+
+/// Deletes a rule. EventBridge won't delete a rule that still has targets, so they're removed first.
+pub async fn delete_rule(
+    client: &aws_sdk_eventbridge::Client,
+    event_bus: &str,
+    name: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut ids = Vec::new();
+    let mut next_token = None;
+    loop {
+        let resp = client
+            .list_targets_by_rule()
+            .rule(name)
+            .event_bus_name(event_bus)
+            .set_next_token(next_token)
+            .send()
+            .await?;
+
+        ids.extend(resp.targets.unwrap_or_default().into_iter().map(|t| t.id));
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    if !ids.is_empty() {
+        remove_targets(client, event_bus, name, &ids).await?;
+    }
+
+    client.delete_rule().name(name).event_bus_name(event_bus).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("rule_arn"), None)])),
+        friendly_message: Some(format!("Deleted EventBridge rule {} on event bus {}", name, event_bus)),
+    })
+}
+
+/// Creates an archive of the events sent to an event bus
+pub async fn create_archive(
+    client: &aws_sdk_eventbridge::Client,
+    name: &str,
+    archive: &Archive,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let event_pattern = match &archive.event_pattern {
+        Some(event_pattern) => Some(ron_to_json(event_pattern)?),
+        None => None,
+    };
+
+    let resp = client
+        .create_archive()
+        .archive_name(name)
+        .event_source_arn(&archive.event_source_arn)
+        .set_description(archive.description.clone())
+        .set_event_pattern(event_pattern)
+        .set_retention_days(archive.retention_days)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("archive_arn"), resp.archive_arn)])),
+        friendly_message: Some(format!("Created EventBridge archive {}", name)),
+    })
+}
+
+/// Updates an archive's description, pattern and retention period
+pub async fn update_archive(
+    client: &aws_sdk_eventbridge::Client,
+    name: &str,
+    archive: &Archive,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let event_pattern = match &archive.event_pattern {
+        Some(event_pattern) => ron_to_json(event_pattern)?,
+        None => String::new(),
+    };
+
+    client
+        .update_archive()
+        .archive_name(name)
+        .description(archive.description.clone().unwrap_or_default())
+        .event_pattern(event_pattern)
+        // Zero means events are kept indefinitely
+        .retention_days(archive.retention_days.unwrap_or(0))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated EventBridge archive {}", name)),
+    })
+}
+
+/// Deletes an archive, along with the events in it
+pub async fn delete_archive(client: &aws_sdk_eventbridge::Client, name: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_archive().archive_name(name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("archive_arn"), None)])),
+        friendly_message: Some(format!("Deleted EventBridge archive {}", name)),
+    })
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::EventBridgeResourceAddress, tags::Tags};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EventBus {
+    pub description: Option<String>,
+    /// A customer managed KMS key to encrypt events with. If None, an AWS owned key is used.
+    pub kms_key_identifier: Option<String>,
+    /// ARN of an SQS queue for events that can't be delivered to a target of a rule on this bus.
+    pub dead_letter_arn: Option<String>,
+    /// The bus's resource-based policy, e.g. to let other accounts put events on it.
+    pub policy: Option<ron::Value>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    pub description: Option<String>,
+    /// At least one of event_pattern and schedule_expression must be set.
+    pub event_pattern: Option<ron::Value>,
+    /// e.g. `rate(5 minutes)` or `cron(0 12 * * ? *)`. Only rules on the default bus can have a schedule.
+    pub schedule_expression: Option<String>,
+    pub state: Option<String>, // ENABLED (the default), DISABLED or ENABLED_WITH_ALL_CLOUDTRAIL_MANAGEMENT_EVENTS
+    /// The role EventBridge assumes to deliver to targets that don't have their own role.
+    pub role_arn: Option<String>,
+    /// Keyed by target ID.
+    pub targets: HashMap<String, Target>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Target {
+    pub arn: String,
+    pub role_arn: Option<String>,
+    /// At most one of input, input_path and input_transformer may be set.
+    /// If none are, the whole event is delivered.
+    pub input: Option<String>,
+    pub input_path: Option<String>,
+    pub input_transformer: Option<InputTransformer>,
+    /// ARN of an SQS queue for events that can't be delivered to this target.
+    pub dead_letter_arn: Option<String>,
+    pub retry_policy: Option<RetryPolicy>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct InputTransformer {
+    /// Variable name to JSON path in the event, e.g. `"detail_type": "$.detail-type"`.
+    pub input_paths_map: HashMap<String, String>,
+    /// e.g. `"<detail_type> happened"`.
+    pub input_template: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RetryPolicy {
+    pub maximum_retry_attempts: Option<i32>,
+    pub maximum_event_age_in_seconds: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Archive {
+    /// ARN of the event bus to archive events from. This can't be changed once the archive exists.
+    pub event_source_arn: String,
+    pub description: Option<String>,
+    /// Only events matching the pattern are archived. If None, every event is.
+    pub event_pattern: Option<ron::Value>,
+    /// If None, events are kept indefinitely.
+    pub retention_days: Option<i32>,
+}
+
+pub enum EventBridgeResource {
+    EventBus(EventBus),
+    Rule(Rule),
+    Archive(Archive),
+}
+
+impl Resource for EventBridgeResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            EventBridgeResource::EventBus(event_bus) => Ok(RON.to_string_pretty(&event_bus, pretty_config)?.into()),
+            EventBridgeResource::Rule(rule) => Ok(RON.to_string_pretty(&rule, pretty_config)?.into()),
+            EventBridgeResource::Archive(archive) => Ok(RON.to_string_pretty(&archive, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = EventBridgeResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            EventBridgeResourceAddress::EventBus { .. } => Ok(EventBridgeResource::EventBus(RON.from_str(s)?)),
+            EventBridgeResourceAddress::Rule { .. } => Ok(EventBridgeResource::Rule(RON.from_str(s)?)),
+            EventBridgeResourceAddress::Archive { .. } => Ok(EventBridgeResource::Archive(RON.from_str(s)?)),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::{PrettyConfig, RON};
+
+#[derive(Debug, Clone)]
+pub enum EventBridgeTaskAddress {
+    Replay { name: String },
+}
+
+impl ResourceAddress for EventBridgeTaskAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            EventBridgeTaskAddress::Replay { name } => PathBuf::from(format!("aws/eventbridge/tasks/replay/{name}.ron")),
+        }
+    }
+
+    fn from_path(path: &Path) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        let path_components: Vec<&str> = path
+            .components()
+            .map(|s| s.as_os_str().to_str().context("Path component is not valid UTF-8"))
+            .collect::<Result<Vec<&str>, anyhow::Error>>()?;
+
+        match &path_components[..] {
+            ["aws", "eventbridge", "tasks", "replay", name] if name.ends_with(".ron") => Ok(EventBridgeTaskAddress::Replay {
+                name: name.strip_suffix(".ron").context("File name must end with .ron")?.to_string(),
+            }),
+            _ => Err(anyhow::anyhow!("Invalid EventBridge task address: {}", path.display())),
+        }
+    }
+}
+
+impl DescribeAddresses for EventBridgeTaskAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/eventbridge/tasks/replay/<name>.ron",
+                description: "Task: replay archived events onto an event bus",
+                example:     "aws/eventbridge/tasks/replay/orders-backfill.ron",
+            },
+        ]
+    }
+}
+
+/// Replays the events archived between `event_start_time` and `event_end_time` onto
+/// `destination_event_bus`, and waits for the replay to finish.
+/// The destination has to be the bus the archive was created from.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StartReplay {
+    pub region: String,
+    pub archive_name: String,
+    /// RFC 3339, e.g. `2025-01-01T00:00:00Z`.
+    pub event_start_time: String,
+    pub event_end_time: String,
+    pub destination_event_bus: String,
+    /// Only replay to these rules on the destination bus. If empty, every rule receives the events.
+    #[serde(default)]
+    pub destination_rules: Vec<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+pub enum EventBridgeTask {
+    Replay(StartReplay),
+}
+
+impl Resource for EventBridgeTask {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = PrettyConfig::default().struct_names(true);
+        match self {
+            EventBridgeTask::Replay(replay) => match RON.to_string_pretty(&replay, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = EventBridgeTaskAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+        match addr {
+            EventBridgeTaskAddress::Replay { .. } => Ok(EventBridgeTask::Replay(RON.from_str(s)?)),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use autoschematic_core::util::RON;
+use aws_sdk_eventbridge::types::{self as sdk, DeadLetterConfig};
+
+use crate::resource::{InputTransformer, RetryPolicy, Target};
+
+pub fn event_bus_arn(region: &str, account_id: &str, name: &str) -> String {
+    format!("arn:aws:events:{region}:{account_id}:event-bus/{name}")
+}
+
+/// Rules on the default bus have no bus name in their ARN.
+pub fn rule_arn(region: &str, account_id: &str, event_bus: &str, name: &str) -> String {
+    if event_bus == "default" {
+        format!("arn:aws:events:{region}:{account_id}:rule/{name}")
+    } else {
+        format!("arn:aws:events:{region}:{account_id}:rule/{event_bus}/{name}")
+    }
+}
+
+pub fn archive_arn(region: &str, account_id: &str, name: &str) -> String {
+    format!("arn:aws:events:{region}:{account_id}:archive/{name}")
+}
+
+pub fn json_to_ron(json: &str) -> anyhow::Result<ron::Value> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    Ok(RON.from_str(&RON.to_string(&value)?)?)
+}
+
+pub fn ron_to_json(value: &ron::Value) -> anyhow::Result<String> {
+    serde_json::to_string(value).context("Failed to serialize value as JSON")
+}
+
+pub fn dead_letter_config(arn: &Option<String>) -> Option<DeadLetterConfig> {
+    arn.as_ref().map(|arn| DeadLetterConfig::builder().arn(arn).build())
+}
+
+pub fn to_sdk_target(id: &str, target: &Target) -> anyhow::Result<sdk::Target> {
+    let input_transformer = match &target.input_transformer {
+        Some(input_transformer) => Some(
+            sdk::InputTransformer::builder()
+                .set_input_paths_map(Some(input_transformer.input_paths_map.clone()))
+                .input_template(&input_transformer.input_template)
+                .build()?,
+        ),
+        None => None,
+    };
+
+    let retry_policy = target.retry_policy.as_ref().map(|retry_policy| {
+        sdk::RetryPolicy::builder()
+            .set_maximum_retry_attempts(retry_policy.maximum_retry_attempts)
+            .set_maximum_event_age_in_seconds(retry_policy.maximum_event_age_in_seconds)
+            .build()
+    });
+
+    Ok(sdk::Target::builder()
+        .id(id)
+        .arn(&target.arn)
+        .set_role_arn(target.role_arn.clone())
+        .set_input(target.input.clone())
+        .set_input_path(target.input_path.clone())
+        .set_input_transformer(input_transformer)
+        .set_dead_letter_config(dead_letter_config(&target.dead_letter_arn))
+        .set_retry_policy(retry_policy)
+        .build()?)
+}
+
+pub fn from_sdk_target(target: sdk::Target) -> (String, Target) {
+    let input_transformer = target.input_transformer.map(|t| InputTransformer {
+        input_paths_map: t.input_paths_map.unwrap_or_default(),
+        input_template:  t.input_template,
+    });
+
+    let retry_policy = target.retry_policy.map(|r| RetryPolicy {
+        maximum_retry_attempts: r.maximum_retry_attempts,
+        maximum_event_age_in_seconds: r.maximum_event_age_in_seconds,
+    });
+
+    (
+        target.id,
+        Target {
+            arn: target.arn,
+            role_arn: target.role_arn,
+            input: target.input,
+            input_path: target.input_path,
+            input_transformer,
+            dead_letter_arn: target.dead_letter_config.and_then(|c| c.arn),
+            retry_policy,
+        },
+    )
+}
+
+pub fn to_sdk_targets(targets: &HashMap<String, Target>) -> anyhow::Result<Vec<sdk::Target>> {
+    let mut out = Vec::new();
+    for (id, target) in targets {
+        out.push(to_sdk_target(id, target)?);
+    }
+    // Deterministic order keeps error messages stable
+    out.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(out)
+}
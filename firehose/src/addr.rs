@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum FirehoseResourceAddress {
+    DeliveryStream { region: String, name: String },
+}
+
+impl ResourceAddress for FirehoseResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            FirehoseResourceAddress::DeliveryStream { region, name } => {
+                PathBuf::from(format!("aws/firehose/{region}/delivery_streams/{name}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "firehose", region, "delivery_streams", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(FirehoseResourceAddress::DeliveryStream {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for FirehoseResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![AddressPattern {
+            pattern:     "aws/firehose/<region>/delivery_streams/<delivery_stream_name>.ron",
+            description: "A Firehose delivery stream and its destination",
+            example:     "aws/firehose/us-east-1/delivery_streams/clickstream-to-s3.ron",
+        }]
+    }
+}
@@ -0,0 +1,206 @@
+pub use crate::addr::FirehoseResourceAddress;
+pub use crate::op::FirehoseConnectorOp;
+pub use crate::resource::FirehoseResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::FirehoseConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{
+    BufferingHints, CloudWatchLogging, DeliveryStream, Destination, S3Configuration, S3Destination, TransformationLambda,
+};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct FirehoseConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_firehose::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<FirehoseConnectorConfig>,
+    prefix: PathBuf,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl FirehoseConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_firehose::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_firehose, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for FirehoseConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = FirehoseResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(FirehoseConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let firehose_config: FirehoseConnectorConfig = FirehoseConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = firehose_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(firehose_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = firehose_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // Delivery stream skeleton, delivering directly-put records to S3 through a transformation Lambda.
+        // OpenSearch and HttpEndpoint destinations take a required s3_backup bucket in place of the optional one.
+        res.push(skeleton!(
+            FirehoseResourceAddress::DeliveryStream {
+                region: String::from("[region]"),
+                name:   String::from("[delivery_stream_name]"),
+            },
+            FirehoseResource::DeliveryStream(DeliveryStream {
+                kinesis_source: None,
+                destination: Destination::S3(S3Destination {
+                    bucket_arn: String::from("arn:aws:s3:::[bucket_name]"),
+                    role_arn: String::from("arn:aws:iam::[account_id]:role/[firehose_role_name]"),
+                    prefix: Some(String::from("records/")),
+                    error_output_prefix: Some(String::from("errors/")),
+                    buffering_hints: Some(BufferingHints {
+                        size_in_mbs: Some(5),
+                        interval_in_seconds: Some(300),
+                    }),
+                    compression_format: Some(String::from("GZIP")),
+                    kms_key_arn: None,
+                    transformation_lambda: Some(TransformationLambda {
+                        lambda_arn: String::from("arn:aws:lambda:[region]:[account_id]:function:[function_name]"),
+                        number_of_retries: Some(3),
+                        buffer_size_in_mbs: None,
+                        buffer_interval_in_seconds: None,
+                    }),
+                    s3_backup: Some(S3Configuration {
+                        bucket_arn: String::from("arn:aws:s3:::[backup_bucket_name]"),
+                        role_arn: String::from("arn:aws:iam::[account_id]:role/[firehose_role_name]"),
+                        prefix: Some(String::from("source/")),
+                        error_output_prefix: None,
+                        buffering_hints: None,
+                        compression_format: None,
+                        kms_key_arn: None,
+                        cloudwatch_logging: None,
+                    }),
+                    cloudwatch_logging: Some(CloudWatchLogging {
+                        log_group_name: String::from("/aws/kinesisfirehose/[delivery_stream_name]"),
+                        log_stream_name: String::from("DestinationDelivery"),
+                    }),
+                }),
+                tags: Tags::default(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = FirehoseResourceAddress::from_path(addr)?;
+
+        match addr {
+            FirehoseResourceAddress::DeliveryStream { .. } => ron_check_eq::<DeliveryStream>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = FirehoseResourceAddress::from_path(addr)?;
+
+        match addr {
+            FirehoseResourceAddress::DeliveryStream { .. } => ron_check_syntax::<DeliveryStream>(a),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_firehose::types::{
+    AmazonopensearchserviceDestinationDescription, DeliveryStreamStatus, DestinationDescription,
+    ExtendedS3DestinationDescription, HttpEndpointDestinationDescription,
+};
+
+use crate::{
+    addr::FirehoseResourceAddress,
+    resource::{
+        BufferingHints, DeliveryStream, Destination, FirehoseResource, HttpEndpointDestination, KinesisStreamSource,
+        OpenSearchDestination, S3Destination,
+    },
+    tags::Tags,
+    util::{
+        from_buffering_hints, from_cloudwatch_logging_options, from_compression_format, from_encryption_configuration,
+        from_processing_configuration, from_s3_description,
+    },
+};
+
+use super::FirehoseConnector;
+
+fn s3_destination(s3: &ExtendedS3DestinationDescription) -> S3Destination {
+    // Once enabled, source record backup can't be disabled again, so a backup bucket
+    // is only reported while backup is on
+    let s3_backup = match s3.s3_backup_mode.as_ref().map(|mode| mode.as_str()) {
+        Some("Enabled") => s3.s3_backup_description.as_ref().map(from_s3_description),
+        _ => None,
+    };
+
+    S3Destination {
+        bucket_arn: s3.bucket_arn.clone(),
+        role_arn: s3.role_arn.clone(),
+        prefix: s3.prefix.clone(),
+        error_output_prefix: s3.error_output_prefix.clone(),
+        buffering_hints: from_buffering_hints(s3.buffering_hints.as_ref()),
+        compression_format: from_compression_format(&s3.compression_format),
+        kms_key_arn: from_encryption_configuration(s3.encryption_configuration.as_ref()),
+        transformation_lambda: from_processing_configuration(s3.processing_configuration.as_ref()),
+        s3_backup,
+        cloudwatch_logging: from_cloudwatch_logging_options(s3.cloud_watch_logging_options.as_ref()),
+    }
+}
+
+fn opensearch_destination(
+    delivery_stream_name: &str,
+    opensearch: &AmazonopensearchserviceDestinationDescription,
+) -> anyhow::Result<OpenSearchDestination> {
+    let Some(s3_backup) = &opensearch.s3_destination_description else {
+        bail!(
+            "Firehose delivery stream {}'s OpenSearch destination has no S3 backup configuration",
+            delivery_stream_name
+        );
+    };
+
+    Ok(OpenSearchDestination {
+        domain_arn: opensearch.domain_arn.clone().unwrap_or_default(),
+        role_arn: opensearch.role_arn.clone().unwrap_or_default(),
+        index_name: opensearch.index_name.clone().unwrap_or_default(),
+        index_rotation_period: opensearch
+            .index_rotation_period
+            .as_ref()
+            .map(|period| period.as_str().to_string()),
+        buffering_hints: opensearch.buffering_hints.as_ref().map(|hints| BufferingHints {
+            size_in_mbs: hints.size_in_mbs,
+            interval_in_seconds: hints.interval_in_seconds,
+        }),
+        retry_duration_in_seconds: opensearch
+            .retry_options
+            .as_ref()
+            .and_then(|retry_options| retry_options.duration_in_seconds),
+        transformation_lambda: from_processing_configuration(opensearch.processing_configuration.as_ref()),
+        s3_backup_mode: opensearch.s3_backup_mode.as_ref().map(|mode| mode.as_str().to_string()),
+        s3_backup: from_s3_description(s3_backup),
+        cloudwatch_logging: from_cloudwatch_logging_options(opensearch.cloud_watch_logging_options.as_ref()),
+    })
+}
+
+fn http_endpoint_destination(
+    delivery_stream_name: &str,
+    http_endpoint: &HttpEndpointDestinationDescription,
+) -> anyhow::Result<HttpEndpointDestination> {
+    let Some(s3_backup) = &http_endpoint.s3_destination_description else {
+        bail!(
+            "Firehose delivery stream {}'s HTTP endpoint destination has no S3 backup configuration",
+            delivery_stream_name
+        );
+    };
+
+    let endpoint = http_endpoint.endpoint_configuration.as_ref();
+
+    let access_key_secret_arn = http_endpoint
+        .secrets_manager_configuration
+        .as_ref()
+        .filter(|secrets_manager| secrets_manager.enabled)
+        .and_then(|secrets_manager| secrets_manager.secret_arn.clone());
+
+    let request_configuration = http_endpoint.request_configuration.as_ref();
+
+    Ok(HttpEndpointDestination {
+        url: endpoint.and_then(|endpoint| endpoint.url.clone()).unwrap_or_default(),
+        name: endpoint.and_then(|endpoint| endpoint.name.clone()),
+        access_key_secret_arn,
+        role_arn: http_endpoint.role_arn.clone(),
+        content_encoding: request_configuration
+            .and_then(|request| request.content_encoding.as_ref())
+            .map(|encoding| encoding.as_str().to_string()),
+        common_attributes: request_configuration
+            .map(|request| {
+                request
+                    .common_attributes()
+                    .iter()
+                    .map(|attribute| (attribute.attribute_name.clone(), attribute.attribute_value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        buffering_hints: http_endpoint.buffering_hints.as_ref().map(|hints| BufferingHints {
+            size_in_mbs: hints.size_in_mbs,
+            interval_in_seconds: hints.interval_in_seconds,
+        }),
+        retry_duration_in_seconds: http_endpoint
+            .retry_options
+            .as_ref()
+            .and_then(|retry_options| retry_options.duration_in_seconds),
+        transformation_lambda: from_processing_configuration(http_endpoint.processing_configuration.as_ref()),
+        s3_backup_mode: http_endpoint.s3_backup_mode.as_ref().map(|mode| mode.as_str().to_string()),
+        s3_backup: from_s3_description(s3_backup),
+        cloudwatch_logging: from_cloudwatch_logging_options(http_endpoint.cloud_watch_logging_options.as_ref()),
+    })
+}
+
+fn destination(delivery_stream_name: &str, destination: &DestinationDescription) -> anyhow::Result<Destination> {
+    if let Some(s3) = &destination.extended_s3_destination_description {
+        Ok(Destination::S3(s3_destination(s3)))
+    } else if let Some(opensearch) = &destination.amazonopensearchservice_destination_description {
+        Ok(Destination::OpenSearch(opensearch_destination(delivery_stream_name, opensearch)?))
+    } else if let Some(http_endpoint) = &destination.http_endpoint_destination_description {
+        Ok(Destination::HttpEndpoint(http_endpoint_destination(delivery_stream_name, http_endpoint)?))
+    } else {
+        bail!(
+            "Firehose delivery stream {} has a destination type this connector doesn't support",
+            delivery_stream_name
+        )
+    }
+}
+
+impl FirehoseConnector {
+    async fn get_delivery_stream_tags(
+        &self,
+        client: &aws_sdk_firehose::Client,
+        delivery_stream_name: &str,
+    ) -> anyhow::Result<Tags> {
+        let mut tags = Vec::new();
+        let mut exclusive_start_tag_key = None;
+
+        loop {
+            let resp = client
+                .list_tags_for_delivery_stream()
+                .delivery_stream_name(delivery_stream_name)
+                .set_exclusive_start_tag_key(exclusive_start_tag_key)
+                .send()
+                .await?;
+
+            exclusive_start_tag_key = resp.tags.last().map(|tag| tag.key.clone());
+            tags.extend(resp.tags);
+
+            if !resp.has_more_tags || exclusive_start_tag_key.is_none() {
+                break;
+            }
+        }
+
+        Ok(Tags::from(&tags[..]))
+    }
+
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = FirehoseResourceAddress::from_path(addr)?;
+
+        match &addr {
+            FirehoseResourceAddress::DeliveryStream { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let description = match client.describe_delivery_stream().delivery_stream_name(name).send().await {
+                    Ok(resp) => match resp.delivery_stream_description {
+                        Some(description) => description,
+                        None => return Ok(None),
+                    },
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => {
+                        return Ok(None);
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+
+                if description.delivery_stream_status == DeliveryStreamStatus::Deleting {
+                    return Ok(None);
+                }
+
+                let Some(first_destination) = description.destinations.first() else {
+                    bail!("Firehose delivery stream {} has no destination", name);
+                };
+
+                let kinesis_source = description
+                    .source
+                    .as_ref()
+                    .and_then(|source| source.kinesis_stream_source_description.as_ref())
+                    .and_then(|kinesis| {
+                        Some(KinesisStreamSource {
+                            kinesis_stream_arn: kinesis.kinesis_stream_arn.clone()?,
+                            role_arn: kinesis.role_arn.clone()?,
+                        })
+                    });
+
+                let delivery_stream = DeliveryStream {
+                    kinesis_source,
+                    destination: destination(name, first_destination)?,
+                    tags: self.get_delivery_stream_tags(&client, name).await?,
+                };
+
+                get_resource_response!(
+                    FirehoseResource::DeliveryStream(delivery_stream),
+                    [(String::from("delivery_stream_arn"), description.delivery_stream_arn)]
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::addr::FirehoseResourceAddress;
+
+use super::FirehoseConnector;
+
+impl FirehoseConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut exclusive_start_delivery_stream_name = None;
+            loop {
+                let resp = client
+                    .list_delivery_streams()
+                    .set_exclusive_start_delivery_stream_name(exclusive_start_delivery_stream_name)
+                    .send()
+                    .await?;
+
+                exclusive_start_delivery_stream_name = resp.delivery_stream_names.last().cloned();
+
+                for delivery_stream_name in resp.delivery_stream_names {
+                    results.push(
+                        FirehoseResourceAddress::DeliveryStream {
+                            region: region.clone(),
+                            name:   delivery_stream_name,
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                if !resp.has_more_delivery_streams || exclusive_start_delivery_stream_name.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,33 @@
+use std::path::Path;
+
+use autoschematic_core::connector::{ConnectorOp, OpExecResponse, ResourceAddress};
+
+use crate::{addr::FirehoseResourceAddress, op::FirehoseConnectorOp, op_impl};
+
+use super::FirehoseConnector;
+
+impl FirehoseConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = FirehoseResourceAddress::from_path(addr)?;
+        let op = FirehoseConnectorOp::from_str(op)?;
+
+        match &addr {
+            FirehoseResourceAddress::DeliveryStream { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    FirehoseConnectorOp::CreateDeliveryStream(delivery_stream) => {
+                        op_impl::create_delivery_stream(&client, name, &delivery_stream).await
+                    }
+                    FirehoseConnectorOp::UpdateDestination(destination) => {
+                        op_impl::update_destination(&client, name, &destination).await
+                    }
+                    FirehoseConnectorOp::UpdateDeliveryStreamTags(old_tags, new_tags) => {
+                        op_impl::update_delivery_stream_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    FirehoseConnectorOp::DeleteDeliveryStream => op_impl::delete_delivery_stream(&client, name).await,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::resource::{
+    BufferingHints, DeliveryStream, Destination, HttpEndpointDestination, OpenSearchDestination, S3Configuration, S3Destination,
+    TransformationLambda,
+};
+
+use super::{FirehoseConnector, FirehoseConnectorOp, FirehoseResourceAddress};
+
+fn check_one_of(what: &str, value: &Option<String>, allowed: &[&str]) -> anyhow::Result<()> {
+    if let Some(value) = value
+        && !allowed.contains(&value.as_str())
+    {
+        bail!("{} is {}: expected one of {}", what, value, allowed.join(", "));
+    }
+    Ok(())
+}
+
+fn check_buffering_hints(what: &str, hints: &Option<BufferingHints>) -> anyhow::Result<()> {
+    let Some(hints) = hints else {
+        return Ok(());
+    };
+    if let Some(size_in_mbs) = hints.size_in_mbs
+        && !(1..=128).contains(&size_in_mbs)
+    {
+        bail!("{} has a buffer size of {} MB: it must be between 1 and 128", what, size_in_mbs);
+    }
+    if let Some(interval_in_seconds) = hints.interval_in_seconds
+        && !(0..=900).contains(&interval_in_seconds)
+    {
+        bail!(
+            "{} has a buffer interval of {} seconds: it must be between 0 and 900",
+            what,
+            interval_in_seconds
+        );
+    }
+    Ok(())
+}
+
+fn check_retry_duration(what: &str, retry_duration_in_seconds: Option<i32>) -> anyhow::Result<()> {
+    if let Some(retry_duration_in_seconds) = retry_duration_in_seconds
+        && !(0..=7200).contains(&retry_duration_in_seconds)
+    {
+        bail!(
+            "{} has a retry duration of {} seconds: it must be between 0 and 7200",
+            what,
+            retry_duration_in_seconds
+        );
+    }
+    Ok(())
+}
+
+fn check_s3_configuration(what: &str, s3: &S3Configuration) -> anyhow::Result<()> {
+    check_buffering_hints(what, &s3.buffering_hints)?;
+    check_one_of(
+        &format!("{}'s compression_format", what),
+        &s3.compression_format,
+        &["GZIP", "ZIP", "Snappy", "HADOOP_SNAPPY"],
+    )
+}
+
+/// Settings that Firehose would reject, caught at plan time instead.
+fn check_delivery_stream(delivery_stream_name: &str, delivery_stream: &DeliveryStream) -> anyhow::Result<()> {
+    let what = format!("Firehose delivery stream {}", delivery_stream_name);
+
+    match &delivery_stream.destination {
+        Destination::S3(s3) => {
+            check_buffering_hints(&what, &s3.buffering_hints)?;
+            check_one_of(
+                &format!("{}'s compression_format", what),
+                &s3.compression_format,
+                &["GZIP", "ZIP", "Snappy", "HADOOP_SNAPPY"],
+            )?;
+            if let Some(s3_backup) = &s3.s3_backup {
+                check_s3_configuration(&format!("{}'s S3 backup", what), s3_backup)?;
+            }
+        }
+        Destination::OpenSearch(opensearch) => {
+            check_buffering_hints(&what, &opensearch.buffering_hints)?;
+            check_retry_duration(&what, opensearch.retry_duration_in_seconds)?;
+            check_one_of(
+                &format!("{}'s index_rotation_period", what),
+                &opensearch.index_rotation_period,
+                &["NoRotation", "OneHour", "OneDay", "OneWeek", "OneMonth"],
+            )?;
+            check_one_of(
+                &format!("{}'s s3_backup_mode", what),
+                &opensearch.s3_backup_mode,
+                &["FailedDocumentsOnly", "AllDocuments"],
+            )?;
+            check_s3_configuration(&format!("{}'s S3 backup", what), &opensearch.s3_backup)?;
+        }
+        Destination::HttpEndpoint(http_endpoint) => {
+            if !http_endpoint.url.starts_with("https://") {
+                bail!("{} delivers to {}: HTTP endpoints must use https://", what, http_endpoint.url);
+            }
+            if http_endpoint.access_key_secret_arn.is_some() && http_endpoint.role_arn.is_none() {
+                bail!("{} reads its access key from Secrets Manager, which requires a role_arn", what);
+            }
+            if http_endpoint.transformation_lambda.is_some() && http_endpoint.role_arn.is_none() {
+                bail!("{} has a transformation Lambda, which requires a role_arn", what);
+            }
+            check_buffering_hints(&what, &http_endpoint.buffering_hints)?;
+            check_retry_duration(&what, http_endpoint.retry_duration_in_seconds)?;
+            check_one_of(
+                &format!("{}'s content_encoding", what),
+                &http_endpoint.content_encoding,
+                &["NONE", "GZIP"],
+            )?;
+            check_one_of(
+                &format!("{}'s s3_backup_mode", what),
+                &http_endpoint.s3_backup_mode,
+                &["FailedDataOnly", "AllData"],
+            )?;
+            check_s3_configuration(&format!("{}'s S3 backup", what), &http_endpoint.s3_backup)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn fill_unset_buffering_hints(new: &Option<BufferingHints>, old: &Option<BufferingHints>) -> Option<BufferingHints> {
+    match (new, old) {
+        (Some(new), Some(old)) => Some(BufferingHints {
+            size_in_mbs: new.size_in_mbs.or(old.size_in_mbs),
+            interval_in_seconds: new.interval_in_seconds.or(old.interval_in_seconds),
+        }),
+        (Some(new), None) => Some(new.clone()),
+        (None, old) => old.clone(),
+    }
+}
+
+fn fill_unset_transformation_lambda(
+    new: &Option<TransformationLambda>,
+    old: &Option<TransformationLambda>,
+) -> Option<TransformationLambda> {
+    match (new, old) {
+        (Some(new), Some(old)) if new.lambda_arn == old.lambda_arn => Some(TransformationLambda {
+            number_of_retries: new.number_of_retries.or(old.number_of_retries),
+            buffer_size_in_mbs: new.buffer_size_in_mbs.or(old.buffer_size_in_mbs),
+            buffer_interval_in_seconds: new.buffer_interval_in_seconds.or(old.buffer_interval_in_seconds),
+            ..new.clone()
+        }),
+        _ => new.clone(),
+    }
+}
+
+fn fill_unset_s3_configuration(new: &S3Configuration, old: &S3Configuration) -> S3Configuration {
+    S3Configuration {
+        buffering_hints: fill_unset_buffering_hints(&new.buffering_hints, &old.buffering_hints),
+        ..new.clone()
+    }
+}
+
+/// Settings left as None keep whatever value Firehose defaulted them to or was last given,
+/// rather than showing up as a change.
+fn fill_unset_destination(new: &Destination, old: &Destination) -> Destination {
+    match (new, old) {
+        (Destination::S3(new), Destination::S3(old)) => Destination::S3(S3Destination {
+            buffering_hints: fill_unset_buffering_hints(&new.buffering_hints, &old.buffering_hints),
+            transformation_lambda: fill_unset_transformation_lambda(&new.transformation_lambda, &old.transformation_lambda),
+            s3_backup: match (&new.s3_backup, &old.s3_backup) {
+                (Some(new_backup), Some(old_backup)) => Some(fill_unset_s3_configuration(new_backup, old_backup)),
+                (new_backup, _) => new_backup.clone(),
+            },
+            ..new.clone()
+        }),
+        (Destination::OpenSearch(new), Destination::OpenSearch(old)) => {
+            Destination::OpenSearch(OpenSearchDestination {
+                index_rotation_period: new.index_rotation_period.clone().or(old.index_rotation_period.clone()),
+                buffering_hints: fill_unset_buffering_hints(&new.buffering_hints, &old.buffering_hints),
+                retry_duration_in_seconds: new.retry_duration_in_seconds.or(old.retry_duration_in_seconds),
+                transformation_lambda: fill_unset_transformation_lambda(
+                    &new.transformation_lambda,
+                    &old.transformation_lambda,
+                ),
+                s3_backup_mode: new.s3_backup_mode.clone().or(old.s3_backup_mode.clone()),
+                s3_backup: fill_unset_s3_configuration(&new.s3_backup, &old.s3_backup),
+                ..new.clone()
+            })
+        }
+        (Destination::HttpEndpoint(new), Destination::HttpEndpoint(old)) => {
+            Destination::HttpEndpoint(HttpEndpointDestination {
+                content_encoding: new.content_encoding.clone().or(old.content_encoding.clone()),
+                buffering_hints: fill_unset_buffering_hints(&new.buffering_hints, &old.buffering_hints),
+                retry_duration_in_seconds: new.retry_duration_in_seconds.or(old.retry_duration_in_seconds),
+                transformation_lambda: fill_unset_transformation_lambda(
+                    &new.transformation_lambda,
+                    &old.transformation_lambda,
+                ),
+                s3_backup_mode: new.s3_backup_mode.clone().or(old.s3_backup_mode.clone()),
+                s3_backup: fill_unset_s3_configuration(&new.s3_backup, &old.s3_backup),
+                ..new.clone()
+            })
+        }
+        (new, _) => new.clone(),
+    }
+}
+
+fn delivery_stream_replacement_fields(old: &DeliveryStream, new: &DeliveryStream) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if old.kinesis_source != new.kinesis_source {
+        fields.push("kinesis_source");
+    }
+    match (&old.destination, &new.destination) {
+        (Destination::S3(old), Destination::S3(new)) => {
+            // Source record backup can't be turned off once it's on
+            if old.s3_backup.is_some() && new.s3_backup.is_none() {
+                fields.push("s3_backup");
+            }
+        }
+        (Destination::OpenSearch(old), Destination::OpenSearch(new)) => {
+            if old.s3_backup_mode != new.s3_backup_mode {
+                fields.push("s3_backup_mode");
+            }
+        }
+        (Destination::HttpEndpoint(_), Destination::HttpEndpoint(_)) => {}
+        _ => fields.push("destination type"),
+    }
+    fields
+}
+
+impl FirehoseConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = FirehoseResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            FirehoseResourceAddress::DeliveryStream { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_delivery_stream)) => {
+                    let new_delivery_stream: DeliveryStream = RON.from_str(&new_delivery_stream)?;
+                    check_delivery_stream(name, &new_delivery_stream)?;
+                    Ok(vec![connector_op!(
+                        FirehoseConnectorOp::CreateDeliveryStream(new_delivery_stream),
+                        format!("Create new Firehose delivery stream {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_delivery_stream), None) => Ok(vec![connector_op!(
+                    FirehoseConnectorOp::DeleteDeliveryStream,
+                    format!(
+                        "DELETE Firehose delivery stream {} in region {}, along with any records it hasn't delivered yet",
+                        name, region
+                    )
+                )]),
+                (Some(old_delivery_stream), Some(new_delivery_stream)) => {
+                    let old_delivery_stream: DeliveryStream = RON.from_str(&old_delivery_stream)?;
+                    let new_delivery_stream: DeliveryStream = RON.from_str(&new_delivery_stream)?;
+                    check_delivery_stream(name, &new_delivery_stream)?;
+                    let new_delivery_stream = DeliveryStream {
+                        destination: fill_unset_destination(&new_delivery_stream.destination, &old_delivery_stream.destination),
+                        ..new_delivery_stream
+                    };
+
+                    let replacement_fields = delivery_stream_replacement_fields(&old_delivery_stream, &new_delivery_stream);
+                    if !replacement_fields.is_empty() {
+                        return Ok(vec![
+                            connector_op!(
+                                FirehoseConnectorOp::DeleteDeliveryStream,
+                                format!(
+                                    "REPLACE Firehose delivery stream `{}` (requires replacement: {})",
+                                    name,
+                                    replacement_fields.join(", ")
+                                )
+                            ),
+                            connector_op!(
+                                FirehoseConnectorOp::CreateDeliveryStream(new_delivery_stream),
+                                format!("Create new Firehose delivery stream {} in region {}", name, region)
+                            ),
+                        ]);
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_delivery_stream.tags != new_delivery_stream.tags {
+                        let diff = diff_ron_values(&old_delivery_stream.tags, &new_delivery_stream.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            FirehoseConnectorOp::UpdateDeliveryStreamTags(
+                                old_delivery_stream.tags.clone(),
+                                new_delivery_stream.tags.clone()
+                            ),
+                            format!("Modify tags for Firehose delivery stream `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    if old_delivery_stream.destination != new_delivery_stream.destination {
+                        let diff = diff_ron_values(&old_delivery_stream.destination, &new_delivery_stream.destination)
+                            .unwrap_or_default();
+                        ops.push(connector_op!(
+                            FirehoseConnectorOp::UpdateDestination(new_delivery_stream.destination.clone()),
+                            format!(
+                                "Modify {} destination of Firehose delivery stream `{}`\n{}",
+                                new_delivery_stream.destination.kind(),
+                                name,
+                                diff
+                            )
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+        }
+    }
+}
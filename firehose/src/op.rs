@@ -0,0 +1,31 @@
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{DeliveryStream, Destination},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum FirehoseConnectorOp {
+    CreateDeliveryStream(DeliveryStream),
+    /// Replaces the destination's configuration with the given one, of the same kind.
+    UpdateDestination(Destination),
+    UpdateDeliveryStreamTags(Tags, Tags),
+    DeleteDeliveryStream,
+}
+
+impl ConnectorOp for FirehoseConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
@@ -0,0 +1,451 @@
+use std::{collections::HashMap, future::Future, time::Duration};
+
+use anyhow::{Context, bail};
+use autoschematic_core::connector::OpExecResponse;
+use aws_sdk_firehose::types::{
+    AmazonopensearchserviceBufferingHints, AmazonopensearchserviceDestinationConfiguration,
+    AmazonopensearchserviceDestinationUpdate, AmazonopensearchserviceIndexRotationPeriod, AmazonopensearchserviceRetryOptions,
+    AmazonopensearchserviceS3BackupMode, ContentEncoding, DeliveryStreamStatus, DeliveryStreamType,
+    ExtendedS3DestinationConfiguration, ExtendedS3DestinationUpdate, HttpEndpointBufferingHints, HttpEndpointCommonAttribute,
+    HttpEndpointConfiguration, HttpEndpointDestinationConfiguration, HttpEndpointDestinationUpdate,
+    HttpEndpointRequestConfiguration, HttpEndpointRetryOptions, HttpEndpointS3BackupMode, KinesisStreamSourceConfiguration,
+    S3BackupMode, SecretsManagerConfiguration,
+};
+
+use crate::{
+    resource::{BufferingHints, DeliveryStream, Destination, HttpEndpointDestination, OpenSearchDestination, S3Destination},
+    tags::{Tags, tag_diff},
+    util::{
+        to_buffering_hints, to_cloudwatch_logging_options, to_compression_format, to_encryption_configuration,
+        to_processing_configuration, to_s3_configuration, to_s3_update,
+    },
+};
+
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(10);
+const STATUS_MAX_POLLS: usize = 90;
+/// TagDeliveryStream takes at most 50 tags per call.
+const MAX_TAGS_PER_CALL: usize = 50;
+
+/// Firehose rejects changes to a delivery stream until it's ACTIVE, and a deleted delivery
+/// stream's name can't be reused until it's gone. Polls `status` until it returns `target`,
+/// or until the resource no longer exists if `target` is None.
+async fn wait_for_status<F, Fut>(description: &str, target: Option<&str>, status: F) -> anyhow::Result<()>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = anyhow::Result<Option<String>>>,
+{
+    for _ in 0..STATUS_MAX_POLLS {
+        if status().await?.as_deref() == target {
+            return Ok(());
+        }
+        tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    }
+
+    match target {
+        Some(target) => bail!("Timed out waiting for {} to become {}", description, target),
+        None => bail!("Timed out waiting for {} to be deleted", description),
+    }
+}
+
+async fn delivery_stream_status(
+    client: &aws_sdk_firehose::Client,
+    delivery_stream_name: &str,
+) -> anyhow::Result<Option<String>> {
+    match client
+        .describe_delivery_stream()
+        .delivery_stream_name(delivery_stream_name)
+        .send()
+        .await
+    {
+        Ok(resp) => {
+            let Some(description) = resp.delivery_stream_description else {
+                return Ok(None);
+            };
+
+            // A failed delivery stream never becomes ACTIVE, so there's no point in waiting for it
+            if matches!(
+                description.delivery_stream_status,
+                DeliveryStreamStatus::CreatingFailed | DeliveryStreamStatus::DeletingFailed
+            ) {
+                let details = description
+                    .failure_description
+                    .map(|failure| failure.details)
+                    .unwrap_or_default();
+                bail!(
+                    "Firehose delivery stream {} is {}: {}",
+                    delivery_stream_name,
+                    description.delivery_stream_status.as_str(),
+                    details
+                );
+            }
+
+            Ok(Some(description.delivery_stream_status.as_str().to_string()))
+        }
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn opensearch_buffering_hints(hints: &Option<BufferingHints>) -> Option<AmazonopensearchserviceBufferingHints> {
+    hints.as_ref().map(|hints| {
+        AmazonopensearchserviceBufferingHints::builder()
+            .set_size_in_mbs(hints.size_in_mbs)
+            .set_interval_in_seconds(hints.interval_in_seconds)
+            .build()
+    })
+}
+
+fn http_endpoint_buffering_hints(hints: &Option<BufferingHints>) -> Option<HttpEndpointBufferingHints> {
+    hints.as_ref().map(|hints| {
+        HttpEndpointBufferingHints::builder()
+            .set_size_in_mbs(hints.size_in_mbs)
+            .set_interval_in_seconds(hints.interval_in_seconds)
+            .build()
+    })
+}
+
+/// The endpoint's access key is read by Firehose from Secrets Manager, with the destination's role.
+fn http_endpoint_secrets_manager_configuration(
+    destination: &HttpEndpointDestination,
+) -> anyhow::Result<SecretsManagerConfiguration> {
+    Ok(match &destination.access_key_secret_arn {
+        Some(secret_arn) => SecretsManagerConfiguration::builder()
+            .enabled(true)
+            .secret_arn(secret_arn)
+            .set_role_arn(destination.role_arn.clone())
+            .build()?,
+        None => SecretsManagerConfiguration::builder().enabled(false).build()?,
+    })
+}
+
+fn http_endpoint_request_configuration(
+    destination: &HttpEndpointDestination,
+) -> anyhow::Result<HttpEndpointRequestConfiguration> {
+    let mut common_attributes = Vec::new();
+    for (name, value) in &destination.common_attributes {
+        common_attributes.push(
+            HttpEndpointCommonAttribute::builder()
+                .attribute_name(name)
+                .attribute_value(value)
+                .build()?,
+        );
+    }
+
+    Ok(HttpEndpointRequestConfiguration::builder()
+        .content_encoding(ContentEncoding::from(destination.content_encoding.as_deref().unwrap_or("NONE")))
+        .set_common_attributes(Some(common_attributes))
+        .build())
+}
+
+fn s3_destination_configuration(destination: &S3Destination) -> anyhow::Result<ExtendedS3DestinationConfiguration> {
+    let s3_backup_mode = match destination.s3_backup {
+        Some(_) => S3BackupMode::Enabled,
+        None => S3BackupMode::Disabled,
+    };
+
+    Ok(ExtendedS3DestinationConfiguration::builder()
+        .bucket_arn(&destination.bucket_arn)
+        .role_arn(&destination.role_arn)
+        .set_prefix(destination.prefix.clone())
+        .set_error_output_prefix(destination.error_output_prefix.clone())
+        .set_buffering_hints(to_buffering_hints(&destination.buffering_hints))
+        .compression_format(to_compression_format(&destination.compression_format))
+        .encryption_configuration(to_encryption_configuration(&destination.kms_key_arn)?)
+        .processing_configuration(to_processing_configuration(&destination.transformation_lambda)?)
+        .s3_backup_mode(s3_backup_mode)
+        .set_s3_backup_configuration(destination.s3_backup.as_ref().map(to_s3_configuration).transpose()?)
+        .cloud_watch_logging_options(to_cloudwatch_logging_options(&destination.cloudwatch_logging))
+        .build()?)
+}
+
+fn s3_destination_update(destination: &S3Destination) -> anyhow::Result<ExtendedS3DestinationUpdate> {
+    let s3_backup_mode = match destination.s3_backup {
+        Some(_) => S3BackupMode::Enabled,
+        None => S3BackupMode::Disabled,
+    };
+
+    Ok(ExtendedS3DestinationUpdate::builder()
+        .bucket_arn(&destination.bucket_arn)
+        .role_arn(&destination.role_arn)
+        .set_prefix(destination.prefix.clone())
+        .set_error_output_prefix(destination.error_output_prefix.clone())
+        .set_buffering_hints(to_buffering_hints(&destination.buffering_hints))
+        .compression_format(to_compression_format(&destination.compression_format))
+        .encryption_configuration(to_encryption_configuration(&destination.kms_key_arn)?)
+        .processing_configuration(to_processing_configuration(&destination.transformation_lambda)?)
+        .s3_backup_mode(s3_backup_mode)
+        .set_s3_backup_update(destination.s3_backup.as_ref().map(to_s3_update).transpose()?)
+        .cloud_watch_logging_options(to_cloudwatch_logging_options(&destination.cloudwatch_logging))
+        .build())
+}
+
+fn opensearch_destination_configuration(
+    destination: &OpenSearchDestination,
+) -> anyhow::Result<AmazonopensearchserviceDestinationConfiguration> {
+    Ok(AmazonopensearchserviceDestinationConfiguration::builder()
+        .domain_arn(&destination.domain_arn)
+        .role_arn(&destination.role_arn)
+        .index_name(&destination.index_name)
+        .set_index_rotation_period(
+            destination
+                .index_rotation_period
+                .as_deref()
+                .map(AmazonopensearchserviceIndexRotationPeriod::from),
+        )
+        .set_buffering_hints(opensearch_buffering_hints(&destination.buffering_hints))
+        .set_retry_options(destination.retry_duration_in_seconds.map(|duration_in_seconds| {
+            AmazonopensearchserviceRetryOptions::builder()
+                .duration_in_seconds(duration_in_seconds)
+                .build()
+        }))
+        .processing_configuration(to_processing_configuration(&destination.transformation_lambda)?)
+        .set_s3_backup_mode(
+            destination
+                .s3_backup_mode
+                .as_deref()
+                .map(AmazonopensearchserviceS3BackupMode::from),
+        )
+        .s3_configuration(to_s3_configuration(&destination.s3_backup)?)
+        .cloud_watch_logging_options(to_cloudwatch_logging_options(&destination.cloudwatch_logging))
+        .build()?)
+}
+
+/// The S3 backup mode of an OpenSearch destination can't be changed, so it isn't part of the update.
+fn opensearch_destination_update(
+    destination: &OpenSearchDestination,
+) -> anyhow::Result<AmazonopensearchserviceDestinationUpdate> {
+    Ok(AmazonopensearchserviceDestinationUpdate::builder()
+        .domain_arn(&destination.domain_arn)
+        .role_arn(&destination.role_arn)
+        .index_name(&destination.index_name)
+        .set_index_rotation_period(
+            destination
+                .index_rotation_period
+                .as_deref()
+                .map(AmazonopensearchserviceIndexRotationPeriod::from),
+        )
+        .set_buffering_hints(opensearch_buffering_hints(&destination.buffering_hints))
+        .set_retry_options(destination.retry_duration_in_seconds.map(|duration_in_seconds| {
+            AmazonopensearchserviceRetryOptions::builder()
+                .duration_in_seconds(duration_in_seconds)
+                .build()
+        }))
+        .processing_configuration(to_processing_configuration(&destination.transformation_lambda)?)
+        .s3_update(to_s3_update(&destination.s3_backup)?)
+        .cloud_watch_logging_options(to_cloudwatch_logging_options(&destination.cloudwatch_logging))
+        .build())
+}
+
+fn http_endpoint_destination_configuration(
+    destination: &HttpEndpointDestination,
+) -> anyhow::Result<HttpEndpointDestinationConfiguration> {
+    Ok(HttpEndpointDestinationConfiguration::builder()
+        .endpoint_configuration(
+            HttpEndpointConfiguration::builder()
+                .url(&destination.url)
+                .set_name(destination.name.clone())
+                .build()?,
+        )
+        .secrets_manager_configuration(http_endpoint_secrets_manager_configuration(destination)?)
+        .set_role_arn(destination.role_arn.clone())
+        .request_configuration(http_endpoint_request_configuration(destination)?)
+        .set_buffering_hints(http_endpoint_buffering_hints(&destination.buffering_hints))
+        .set_retry_options(destination.retry_duration_in_seconds.map(|duration_in_seconds| {
+            HttpEndpointRetryOptions::builder()
+                .duration_in_seconds(duration_in_seconds)
+                .build()
+        }))
+        .processing_configuration(to_processing_configuration(&destination.transformation_lambda)?)
+        .set_s3_backup_mode(destination.s3_backup_mode.as_deref().map(HttpEndpointS3BackupMode::from))
+        .s3_configuration(to_s3_configuration(&destination.s3_backup)?)
+        .cloud_watch_logging_options(to_cloudwatch_logging_options(&destination.cloudwatch_logging))
+        .build()?)
+}
+
+fn http_endpoint_destination_update(destination: &HttpEndpointDestination) -> anyhow::Result<HttpEndpointDestinationUpdate> {
+    Ok(HttpEndpointDestinationUpdate::builder()
+        .endpoint_configuration(
+            HttpEndpointConfiguration::builder()
+                .url(&destination.url)
+                .set_name(destination.name.clone())
+                .build()?,
+        )
+        .secrets_manager_configuration(http_endpoint_secrets_manager_configuration(destination)?)
+        .set_role_arn(destination.role_arn.clone())
+        .request_configuration(http_endpoint_request_configuration(destination)?)
+        .set_buffering_hints(http_endpoint_buffering_hints(&destination.buffering_hints))
+        .set_retry_options(destination.retry_duration_in_seconds.map(|duration_in_seconds| {
+            HttpEndpointRetryOptions::builder()
+                .duration_in_seconds(duration_in_seconds)
+                .build()
+        }))
+        .processing_configuration(to_processing_configuration(&destination.transformation_lambda)?)
+        .set_s3_backup_mode(destination.s3_backup_mode.as_deref().map(HttpEndpointS3BackupMode::from))
+        .s3_update(to_s3_update(&destination.s3_backup)?)
+        .cloud_watch_logging_options(to_cloudwatch_logging_options(&destination.cloudwatch_logging))
+        .build())
+}
+
+/// Creates a delivery stream and waits for it to become ACTIVE.
+pub async fn create_delivery_stream(
+    client: &aws_sdk_firehose::Client,
+    delivery_stream_name: &str,
+    delivery_stream: &DeliveryStream,
+) -> anyhow::Result<OpExecResponse> {
+    let mut request = client
+        .create_delivery_stream()
+        .delivery_stream_name(delivery_stream_name)
+        .set_tags(Some(delivery_stream.tags.to_vec()?));
+
+    request = match &delivery_stream.kinesis_source {
+        Some(kinesis_source) => request
+            .delivery_stream_type(DeliveryStreamType::KinesisStreamAsSource)
+            .kinesis_stream_source_configuration(
+                KinesisStreamSourceConfiguration::builder()
+                    .kinesis_stream_arn(&kinesis_source.kinesis_stream_arn)
+                    .role_arn(&kinesis_source.role_arn)
+                    .build()?,
+            ),
+        None => request.delivery_stream_type(DeliveryStreamType::DirectPut),
+    };
+
+    request = match &delivery_stream.destination {
+        Destination::S3(destination) => {
+            request.extended_s3_destination_configuration(s3_destination_configuration(destination)?)
+        }
+        Destination::OpenSearch(destination) => {
+            request.amazonopensearchservice_destination_configuration(opensearch_destination_configuration(destination)?)
+        }
+        Destination::HttpEndpoint(destination) => {
+            request.http_endpoint_destination_configuration(http_endpoint_destination_configuration(destination)?)
+        }
+    };
+
+    let resp = request.send().await?;
+
+    wait_for_status(
+        &format!("Firehose delivery stream {}", delivery_stream_name),
+        Some("ACTIVE"),
+        || delivery_stream_status(client, delivery_stream_name),
+    )
+    .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("delivery_stream_arn"), resp.delivery_stream_arn)])),
+        friendly_message: Some(format!(
+            "Created Firehose delivery stream {} delivering to {}",
+            delivery_stream_name,
+            delivery_stream.destination.kind()
+        )),
+    })
+}
+
+/// UpdateDestination needs the delivery stream's current version and destination IDs,
+/// and fails if the delivery stream was changed since they were read.
+pub async fn update_destination(
+    client: &aws_sdk_firehose::Client,
+    delivery_stream_name: &str,
+    destination: &Destination,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .describe_delivery_stream()
+        .delivery_stream_name(delivery_stream_name)
+        .send()
+        .await?;
+    let description = resp
+        .delivery_stream_description
+        .with_context(|| format!("Firehose delivery stream {} not found", delivery_stream_name))?;
+    let destination_id = description
+        .destinations
+        .first()
+        .map(|destination| destination.destination_id.clone())
+        .with_context(|| format!("Firehose delivery stream {} has no destination", delivery_stream_name))?;
+
+    let mut request = client
+        .update_destination()
+        .delivery_stream_name(delivery_stream_name)
+        .current_delivery_stream_version_id(description.version_id)
+        .destination_id(destination_id);
+
+    request = match destination {
+        Destination::S3(destination) => request.extended_s3_destination_update(s3_destination_update(destination)?),
+        Destination::OpenSearch(destination) => {
+            request.amazonopensearchservice_destination_update(opensearch_destination_update(destination)?)
+        }
+        Destination::HttpEndpoint(destination) => {
+            request.http_endpoint_destination_update(http_endpoint_destination_update(destination)?)
+        }
+    };
+
+    request.send().await?;
+
+    wait_for_status(
+        &format!("Firehose delivery stream {}", delivery_stream_name),
+        Some("ACTIVE"),
+        || delivery_stream_status(client, delivery_stream_name),
+    )
+    .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Updated {} destination of Firehose delivery stream {}",
+            destination.kind(),
+            delivery_stream_name
+        )),
+    })
+}
+
+pub async fn update_delivery_stream_tags(
+    client: &aws_sdk_firehose::Client,
+    delivery_stream_name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let (remove_keys, add_tags) = tag_diff(old_tags, new_tags)?;
+
+    if !remove_keys.is_empty() {
+        client
+            .untag_delivery_stream()
+            .delivery_stream_name(delivery_stream_name)
+            .set_tag_keys(Some(remove_keys))
+            .send()
+            .await?;
+    }
+
+    for chunk in add_tags.chunks(MAX_TAGS_PER_CALL) {
+        client
+            .tag_delivery_stream()
+            .delivery_stream_name(delivery_stream_name)
+            .set_tags(Some(chunk.to_vec()))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for Firehose delivery stream {}", delivery_stream_name)),
+    })
+}
+
+/// Records still buffered in the delivery stream are lost.
+pub async fn delete_delivery_stream(
+    client: &aws_sdk_firehose::Client,
+    delivery_stream_name: &str,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .delete_delivery_stream()
+        .delivery_stream_name(delivery_stream_name)
+        .send()
+        .await?;
+
+    wait_for_status(&format!("Firehose delivery stream {}", delivery_stream_name), None, || {
+        delivery_stream_status(client, delivery_stream_name)
+    })
+    .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("delivery_stream_arn"), None)])),
+        friendly_message: Some(format!("Deleted Firehose delivery stream {}", delivery_stream_name)),
+    })
+}
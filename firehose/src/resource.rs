@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::FirehoseResourceAddress, tags::Tags};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DeliveryStream {
+    /// If None, records are written to the delivery stream directly with PutRecord (DirectPut).
+    pub kinesis_source: Option<KinesisStreamSource>,
+    pub destination: Destination,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct KinesisStreamSource {
+    pub kinesis_stream_arn: String,
+    /// A role that Firehose can assume to read from the stream.
+    pub role_arn: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum Destination {
+    S3(S3Destination),
+    OpenSearch(OpenSearchDestination),
+    HttpEndpoint(HttpEndpointDestination),
+}
+
+impl Destination {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Destination::S3(_) => "S3",
+            Destination::OpenSearch(_) => "OpenSearch",
+            Destination::HttpEndpoint(_) => "HttpEndpoint",
+        }
+    }
+}
+
+/// Firehose delivers once either limit is reached. If None, the destination's defaults are used.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BufferingHints {
+    pub size_in_mbs: Option<i32>,
+    pub interval_in_seconds: Option<i32>,
+}
+
+/// A Lambda function that transforms batches of records before they're delivered.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TransformationLambda {
+    pub lambda_arn: String,
+    pub number_of_retries: Option<i32>,
+    pub buffer_size_in_mbs: Option<i32>,
+    pub buffer_interval_in_seconds: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CloudWatchLogging {
+    pub log_group_name: String,
+    pub log_stream_name: String,
+}
+
+/// Plain delivery to an S3 bucket, used for source record backup and for records
+/// that couldn't be delivered to an OpenSearch or HTTP endpoint destination.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct S3Configuration {
+    pub bucket_arn: String,
+    pub role_arn: String,
+    pub prefix: Option<String>,
+    pub error_output_prefix: Option<String>,
+    pub buffering_hints: Option<BufferingHints>,
+    /// GZIP, ZIP, Snappy or HADOOP_SNAPPY. If None, records are stored uncompressed.
+    pub compression_format: Option<String>,
+    /// If None, records are stored without KMS encryption.
+    pub kms_key_arn: Option<String>,
+    pub cloudwatch_logging: Option<CloudWatchLogging>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct S3Destination {
+    pub bucket_arn: String,
+    pub role_arn: String,
+    pub prefix: Option<String>,
+    pub error_output_prefix: Option<String>,
+    pub buffering_hints: Option<BufferingHints>,
+    /// GZIP, ZIP, Snappy or HADOOP_SNAPPY. If None, records are stored uncompressed.
+    pub compression_format: Option<String>,
+    /// If None, records are stored without KMS encryption.
+    pub kms_key_arn: Option<String>,
+    pub transformation_lambda: Option<TransformationLambda>,
+    /// If set, the source records are also delivered, untransformed, to this bucket.
+    /// Source record backup can't be turned off again once it's on.
+    pub s3_backup: Option<S3Configuration>,
+    pub cloudwatch_logging: Option<CloudWatchLogging>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct OpenSearchDestination {
+    pub domain_arn: String,
+    pub role_arn: String,
+    pub index_name: String,
+    /// NoRotation, OneHour, OneDay, OneWeek or OneMonth
+    pub index_rotation_period: Option<String>,
+    pub buffering_hints: Option<BufferingHints>,
+    pub retry_duration_in_seconds: Option<i32>,
+    pub transformation_lambda: Option<TransformationLambda>,
+    /// FailedDocumentsOnly or AllDocuments. Can't be changed once the delivery stream exists.
+    pub s3_backup_mode: Option<String>,
+    pub s3_backup: S3Configuration,
+    pub cloudwatch_logging: Option<CloudWatchLogging>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HttpEndpointDestination {
+    pub url: String,
+    pub name: Option<String>,
+    /// The ARN of a Secrets Manager secret holding the endpoint's access key.
+    /// Firehose reads it itself, so the key never passes through this repository.
+    pub access_key_secret_arn: Option<String>,
+    /// The role Firehose uses to read the access key secret, invoke the transformation Lambda
+    /// and write to the backup bucket.
+    pub role_arn: Option<String>,
+    /// NONE or GZIP
+    pub content_encoding: Option<String>,
+    /// Sent with every request in the X-Amz-Firehose-Common-Attributes header.
+    #[serde(default)]
+    pub common_attributes: BTreeMap<String, String>,
+    pub buffering_hints: Option<BufferingHints>,
+    pub retry_duration_in_seconds: Option<i32>,
+    pub transformation_lambda: Option<TransformationLambda>,
+    /// FailedDataOnly or AllData
+    pub s3_backup_mode: Option<String>,
+    pub s3_backup: S3Configuration,
+    pub cloudwatch_logging: Option<CloudWatchLogging>,
+}
+
+pub enum FirehoseResource {
+    DeliveryStream(DeliveryStream),
+}
+
+impl Resource for FirehoseResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            FirehoseResource::DeliveryStream(delivery_stream) => Ok(RON.to_string_pretty(&delivery_stream, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = FirehoseResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            FirehoseResourceAddress::DeliveryStream { .. } => Ok(FirehoseResource::DeliveryStream(RON.from_str(s)?)),
+        }
+    }
+}
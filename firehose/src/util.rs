@@ -0,0 +1,175 @@
+use aws_sdk_firehose::types::{
+    self as sdk, CloudWatchLoggingOptions, CompressionFormat, EncryptionConfiguration, KmsEncryptionConfig,
+    NoEncryptionConfig, ProcessingConfiguration, Processor, ProcessorParameter, ProcessorParameterName, ProcessorType,
+    S3DestinationConfiguration, S3DestinationDescription, S3DestinationUpdate,
+};
+
+use crate::resource::{BufferingHints, CloudWatchLogging, S3Configuration, TransformationLambda};
+
+// Conversions to the Firehose API.
+// Update requests replace whatever isn't sent with nothing, so unset settings are sent explicitly disabled.
+
+pub fn to_buffering_hints(hints: &Option<BufferingHints>) -> Option<sdk::BufferingHints> {
+    hints.as_ref().map(|hints| {
+        sdk::BufferingHints::builder()
+            .set_size_in_mbs(hints.size_in_mbs)
+            .set_interval_in_seconds(hints.interval_in_seconds)
+            .build()
+    })
+}
+
+pub fn to_processing_configuration(lambda: &Option<TransformationLambda>) -> anyhow::Result<ProcessingConfiguration> {
+    let Some(lambda) = lambda else {
+        return Ok(ProcessingConfiguration::builder().enabled(false).build());
+    };
+
+    let mut parameters = vec![("LambdaArn", lambda.lambda_arn.clone())];
+    if let Some(number_of_retries) = lambda.number_of_retries {
+        parameters.push(("NumberOfRetries", number_of_retries.to_string()));
+    }
+    if let Some(buffer_size_in_mbs) = lambda.buffer_size_in_mbs {
+        parameters.push(("BufferSizeInMBs", buffer_size_in_mbs.to_string()));
+    }
+    if let Some(buffer_interval_in_seconds) = lambda.buffer_interval_in_seconds {
+        parameters.push(("BufferIntervalInSeconds", buffer_interval_in_seconds.to_string()));
+    }
+
+    let mut processor = Processor::builder().r#type(ProcessorType::Lambda);
+    for (name, value) in parameters {
+        processor = processor.parameters(
+            ProcessorParameter::builder()
+                .parameter_name(ProcessorParameterName::from(name))
+                .parameter_value(value)
+                .build()?,
+        );
+    }
+
+    Ok(ProcessingConfiguration::builder()
+        .enabled(true)
+        .processors(processor.build()?)
+        .build())
+}
+
+pub fn to_cloudwatch_logging_options(logging: &Option<CloudWatchLogging>) -> CloudWatchLoggingOptions {
+    match logging {
+        Some(logging) => CloudWatchLoggingOptions::builder()
+            .enabled(true)
+            .log_group_name(&logging.log_group_name)
+            .log_stream_name(&logging.log_stream_name)
+            .build(),
+        None => CloudWatchLoggingOptions::builder().enabled(false).build(),
+    }
+}
+
+pub fn to_encryption_configuration(kms_key_arn: &Option<String>) -> anyhow::Result<EncryptionConfiguration> {
+    Ok(match kms_key_arn {
+        Some(kms_key_arn) => EncryptionConfiguration::builder()
+            .kms_encryption_config(KmsEncryptionConfig::builder().awskms_key_arn(kms_key_arn).build()?)
+            .build(),
+        None => EncryptionConfiguration::builder()
+            .no_encryption_config(NoEncryptionConfig::NoEncryption)
+            .build(),
+    })
+}
+
+pub fn to_compression_format(compression_format: &Option<String>) -> CompressionFormat {
+    CompressionFormat::from(compression_format.as_deref().unwrap_or("UNCOMPRESSED"))
+}
+
+pub fn to_s3_configuration(s3: &S3Configuration) -> anyhow::Result<S3DestinationConfiguration> {
+    Ok(S3DestinationConfiguration::builder()
+        .bucket_arn(&s3.bucket_arn)
+        .role_arn(&s3.role_arn)
+        .set_prefix(s3.prefix.clone())
+        .set_error_output_prefix(s3.error_output_prefix.clone())
+        .set_buffering_hints(to_buffering_hints(&s3.buffering_hints))
+        .compression_format(to_compression_format(&s3.compression_format))
+        .encryption_configuration(to_encryption_configuration(&s3.kms_key_arn)?)
+        .cloud_watch_logging_options(to_cloudwatch_logging_options(&s3.cloudwatch_logging))
+        .build()?)
+}
+
+pub fn to_s3_update(s3: &S3Configuration) -> anyhow::Result<S3DestinationUpdate> {
+    Ok(S3DestinationUpdate::builder()
+        .bucket_arn(&s3.bucket_arn)
+        .role_arn(&s3.role_arn)
+        .set_prefix(s3.prefix.clone())
+        .set_error_output_prefix(s3.error_output_prefix.clone())
+        .set_buffering_hints(to_buffering_hints(&s3.buffering_hints))
+        .compression_format(to_compression_format(&s3.compression_format))
+        .encryption_configuration(to_encryption_configuration(&s3.kms_key_arn)?)
+        .cloud_watch_logging_options(to_cloudwatch_logging_options(&s3.cloudwatch_logging))
+        .build())
+}
+
+// Conversions from the Firehose API.
+// Disabled settings are reported as None, matching how they're written.
+
+pub fn from_buffering_hints(hints: Option<&sdk::BufferingHints>) -> Option<BufferingHints> {
+    hints.map(|hints| BufferingHints {
+        size_in_mbs: hints.size_in_mbs,
+        interval_in_seconds: hints.interval_in_seconds,
+    })
+}
+
+pub fn from_processing_configuration(processing: Option<&ProcessingConfiguration>) -> Option<TransformationLambda> {
+    let processing = processing?;
+    if processing.enabled != Some(true) {
+        return None;
+    }
+
+    let processor = processing.processors().iter().find(|p| p.r#type == ProcessorType::Lambda)?;
+    let parameter = |name: &str| {
+        processor
+            .parameters()
+            .iter()
+            .find(|p| p.parameter_name.as_str() == name)
+            .map(|p| p.parameter_value.clone())
+    };
+
+    Some(TransformationLambda {
+        lambda_arn: parameter("LambdaArn")?,
+        number_of_retries: parameter("NumberOfRetries").and_then(|v| v.parse().ok()),
+        buffer_size_in_mbs: parameter("BufferSizeInMBs").and_then(|v| v.parse().ok()),
+        buffer_interval_in_seconds: parameter("BufferIntervalInSeconds").and_then(|v| v.parse().ok()),
+    })
+}
+
+pub fn from_cloudwatch_logging_options(logging: Option<&CloudWatchLoggingOptions>) -> Option<CloudWatchLogging> {
+    let logging = logging?;
+    if logging.enabled != Some(true) {
+        return None;
+    }
+
+    Some(CloudWatchLogging {
+        log_group_name: logging.log_group_name.clone()?,
+        log_stream_name: logging.log_stream_name.clone()?,
+    })
+}
+
+pub fn from_encryption_configuration(encryption: Option<&EncryptionConfiguration>) -> Option<String> {
+    encryption?
+        .kms_encryption_config
+        .as_ref()
+        .map(|kms| kms.awskms_key_arn.clone())
+}
+
+pub fn from_compression_format(compression_format: &CompressionFormat) -> Option<String> {
+    match compression_format {
+        CompressionFormat::Uncompressed => None,
+        compression_format => Some(compression_format.as_str().to_string()),
+    }
+}
+
+pub fn from_s3_description(s3: &S3DestinationDescription) -> S3Configuration {
+    S3Configuration {
+        bucket_arn: s3.bucket_arn.clone(),
+        role_arn: s3.role_arn.clone(),
+        prefix: s3.prefix.clone(),
+        error_output_prefix: s3.error_output_prefix.clone(),
+        buffering_hints: from_buffering_hints(s3.buffering_hints.as_ref()),
+        compression_format: from_compression_format(&s3.compression_format),
+        kms_key_arn: from_encryption_configuration(s3.encryption_configuration.as_ref()),
+        cloudwatch_logging: from_cloudwatch_logging_options(s3.cloud_watch_logging_options.as_ref()),
+    }
+}
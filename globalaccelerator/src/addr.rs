@@ -0,0 +1,101 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+/// Accelerators are global and get generated ARNs, so they're addressed by name.
+/// Listeners have no name, so they're addressed by protocol and their lowest port, e.g. `tcp-443`.
+#[derive(Debug, Clone)]
+pub enum GlobalAcceleratorResourceAddress {
+    Accelerator {
+        name: String,
+    },
+    Listener {
+        accelerator: String,
+        listener:    String,
+    },
+    /// A listener has at most one endpoint group per region, so endpoint groups are addressed by region.
+    EndpointGroup {
+        accelerator: String,
+        listener:    String,
+        region:      String,
+    },
+}
+
+impl ResourceAddress for GlobalAcceleratorResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            GlobalAcceleratorResourceAddress::Accelerator { name } => {
+                PathBuf::from(format!("aws/globalaccelerator/accelerators/{name}.ron"))
+            }
+            GlobalAcceleratorResourceAddress::Listener { accelerator, listener } => PathBuf::from(format!(
+                "aws/globalaccelerator/accelerators/{accelerator}/listeners/{listener}.ron"
+            )),
+            GlobalAcceleratorResourceAddress::EndpointGroup {
+                accelerator,
+                listener,
+                region,
+            } => PathBuf::from(format!(
+                "aws/globalaccelerator/accelerators/{accelerator}/listeners/{listener}/endpoint_groups/{region}.ron"
+            )),
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "globalaccelerator", "accelerators", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(GlobalAcceleratorResourceAddress::Accelerator { name })
+            }
+            ["aws", "globalaccelerator", "accelerators", accelerator, "listeners", listener] if listener.ends_with(".ron") => {
+                let listener = listener.strip_suffix(".ron").unwrap().to_string();
+                Ok(GlobalAcceleratorResourceAddress::Listener {
+                    accelerator: accelerator.to_string(),
+                    listener,
+                })
+            }
+            [
+                "aws",
+                "globalaccelerator",
+                "accelerators",
+                accelerator,
+                "listeners",
+                listener,
+                "endpoint_groups",
+                region,
+            ] if region.ends_with(".ron") => {
+                let region = region.strip_suffix(".ron").unwrap().to_string();
+                Ok(GlobalAcceleratorResourceAddress::EndpointGroup {
+                    accelerator: accelerator.to_string(),
+                    listener: listener.to_string(),
+                    region,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for GlobalAcceleratorResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/globalaccelerator/accelerators/<name>.ron",
+                description: "A standard accelerator and its static IP addresses",
+                example:     "aws/globalaccelerator/accelerators/web.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/globalaccelerator/accelerators/<accelerator>/listeners/<protocol>-<port>.ron",
+                description: "A TCP or UDP listener, named by its protocol and lowest port",
+                example:     "aws/globalaccelerator/accelerators/web/listeners/tcp-443.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/globalaccelerator/accelerators/<accelerator>/listeners/<listener>/endpoint_groups/<region>.ron",
+                description: "A listener's endpoints in one region. Endpoints can name ELB load balancer addresses",
+                example:     "aws/globalaccelerator/accelerators/web/listeners/tcp-443/endpoint_groups/us-east-1.ron",
+            },
+        ]
+    }
+}
@@ -0,0 +1,210 @@
+pub use crate::addr::GlobalAcceleratorResourceAddress;
+pub use crate::op::GlobalAcceleratorConnectorOp;
+pub use crate::resource::GlobalAcceleratorResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::GlobalAcceleratorConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{Accelerator, Endpoint, EndpointGroup, Listener, PortRange};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct GlobalAcceleratorConnector {
+    client: Mutex<Option<Arc<aws_sdk_globalaccelerator::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<GlobalAcceleratorConnectorConfig>,
+    prefix: PathBuf,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl GlobalAcceleratorConnector {
+    pub async fn get_or_init_client(&self) -> anyhow::Result<Arc<aws_sdk_globalaccelerator::Client>> {
+        let mut client = self.client.lock().await;
+
+        if let Some(client) = &*client {
+            return Ok(client.clone());
+        }
+
+        // Global Accelerator is global, but its API is served from us-west-2
+        let config = load_sdk_config("us-west-2").await;
+        let new_client = Arc::new(audited_client!(aws_sdk_globalaccelerator, &config));
+        *client = Some(new_client.clone());
+
+        Ok(new_client)
+    }
+}
+
+#[async_trait]
+impl Connector for GlobalAcceleratorConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = GlobalAcceleratorResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(GlobalAcceleratorConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let ga_config: GlobalAcceleratorConnectorConfig = GlobalAcceleratorConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = ga_config.verify_sts().await?;
+
+        *self.client.lock().await = None;
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(ga_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = ga_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        res.push(skeleton!(
+            GlobalAcceleratorResourceAddress::Accelerator {
+                name: String::from("[accelerator_name]"),
+            },
+            GlobalAcceleratorResource::Accelerator(Accelerator {
+                ip_address_type: String::from("IPV4"),
+                ip_addresses: Vec::new(),
+                enabled: true,
+                flow_logs: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            GlobalAcceleratorResourceAddress::Listener {
+                accelerator: String::from("[accelerator_name]"),
+                listener:    String::from("tcp-443"),
+            },
+            GlobalAcceleratorResource::Listener(Listener {
+                port_ranges:     vec![PortRange {
+                    from_port: 443,
+                    to_port:   443,
+                }],
+                client_affinity: None,
+            })
+        ));
+
+        // Sends the listener's traffic in [region] to a load balancer managed by the ELB connector
+        res.push(skeleton!(
+            GlobalAcceleratorResourceAddress::EndpointGroup {
+                accelerator: String::from("[accelerator_name]"),
+                listener:    String::from("tcp-443"),
+                region:      String::from("[region]"),
+            },
+            GlobalAcceleratorResource::EndpointGroup(EndpointGroup {
+                endpoints: vec![Endpoint {
+                    endpoint_id: String::from("aws/elb/[region]/load_balancers/[load_balancer_name].ron"),
+                    weight: None,
+                    client_ip_preservation_enabled: Some(true),
+                }],
+                traffic_dial_percentage: None,
+                health_check_port: None,
+                health_check_protocol: None,
+                health_check_path: None,
+                health_check_interval_seconds: None,
+                threshold_count: None,
+                port_overrides: Vec::new(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = GlobalAcceleratorResourceAddress::from_path(addr)?;
+
+        match addr {
+            GlobalAcceleratorResourceAddress::Accelerator { .. } => ron_check_eq::<Accelerator>(a, b),
+            GlobalAcceleratorResourceAddress::Listener { .. } => ron_check_eq::<Listener>(a, b),
+            GlobalAcceleratorResourceAddress::EndpointGroup { .. } => ron_check_eq::<EndpointGroup>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = GlobalAcceleratorResourceAddress::from_path(addr)?;
+
+        match addr {
+            GlobalAcceleratorResourceAddress::Accelerator { .. } => ron_check_syntax::<Accelerator>(a),
+            GlobalAcceleratorResourceAddress::Listener { .. } => ron_check_syntax::<Listener>(a),
+            GlobalAcceleratorResourceAddress::EndpointGroup { .. } => ron_check_syntax::<EndpointGroup>(a),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::path::Path;
+
+use autoschematic_connector_aws_core::elb::{ElbLoadBalancerAddress, elb_addresses_by_arn};
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+
+use crate::{
+    addr::GlobalAcceleratorResourceAddress,
+    op_impl::{find_accelerator, find_endpoint_group, find_listener, from_sdk_port_range},
+    resource::{Accelerator, Endpoint, EndpointGroup, FlowLogs, GlobalAcceleratorResource, Listener, PortOverride, PortRange},
+    tags::Tags,
+};
+
+use super::GlobalAcceleratorConnector;
+
+impl GlobalAcceleratorConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = GlobalAcceleratorResourceAddress::from_path(addr)?;
+        let client = self.get_or_init_client().await?;
+
+        match &addr {
+            GlobalAcceleratorResourceAddress::Accelerator { name } => {
+                let Some(accelerator) = find_accelerator(&client, name).await? else {
+                    return Ok(None);
+                };
+
+                let arn = accelerator.accelerator_arn.unwrap_or_default();
+
+                let attributes = client
+                    .describe_accelerator_attributes()
+                    .accelerator_arn(&arn)
+                    .send()
+                    .await?
+                    .accelerator_attributes;
+                let flow_logs = attributes
+                    .filter(|attributes| attributes.flow_logs_enabled.unwrap_or(false))
+                    .map(|attributes| FlowLogs {
+                        s3_bucket: attributes.flow_logs_s3_bucket.unwrap_or_default(),
+                        s3_prefix: attributes.flow_logs_s3_prefix.filter(|p| !p.is_empty()),
+                    });
+
+                let mut ip_addresses: Vec<String> = accelerator
+                    .ip_sets
+                    .unwrap_or_default()
+                    .into_iter()
+                    .flat_map(|ip_set| ip_set.ip_addresses.unwrap_or_default())
+                    .collect();
+                ip_addresses.sort();
+
+                let tags = client.list_tags_for_resource().resource_arn(&arn).send().await?.tags;
+
+                let resource = Accelerator {
+                    ip_address_type: accelerator
+                        .ip_address_type
+                        .map(|t| t.as_str().to_string())
+                        .unwrap_or_default(),
+                    ip_addresses,
+                    enabled: accelerator.enabled.unwrap_or(false),
+                    flow_logs,
+                    tags: Tags::from(tags.unwrap_or_default()),
+                };
+
+                get_resource_response!(
+                    GlobalAcceleratorResource::Accelerator(resource),
+                    [
+                        (String::from("accelerator_arn"), arn),
+                        (String::from("dns_name"), accelerator.dns_name.unwrap_or_default())
+                    ]
+                )
+            }
+            GlobalAcceleratorResourceAddress::Listener { accelerator, listener } => {
+                let Some(listener) = find_listener(&client, accelerator, listener).await? else {
+                    return Ok(None);
+                };
+
+                let mut port_ranges: Vec<PortRange> = listener.port_ranges().iter().map(from_sdk_port_range).collect();
+                port_ranges.sort();
+
+                let resource = Listener {
+                    port_ranges,
+                    client_affinity: listener.client_affinity.map(|a| a.as_str().to_string()),
+                };
+
+                get_resource_response!(
+                    GlobalAcceleratorResource::Listener(resource),
+                    [(String::from("listener_arn"), listener.listener_arn.unwrap_or_default())]
+                )
+            }
+            GlobalAcceleratorResourceAddress::EndpointGroup {
+                accelerator,
+                listener,
+                region,
+            } => {
+                let Some(group) = find_endpoint_group(&client, accelerator, listener, region).await? else {
+                    return Ok(None);
+                };
+
+                // Load balancers managed by the ELB connector are reported by address
+                let load_balancer_addresses = elb_addresses_by_arn::<ElbLoadBalancerAddress>(&self.prefix, region)?;
+
+                let endpoints = group
+                    .endpoint_descriptions
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|endpoint| {
+                        let endpoint_id = endpoint.endpoint_id.unwrap_or_default();
+                        Endpoint {
+                            endpoint_id: load_balancer_addresses.get(&endpoint_id).cloned().unwrap_or(endpoint_id),
+                            weight: endpoint.weight,
+                            client_ip_preservation_enabled: endpoint.client_ip_preservation_enabled,
+                        }
+                    })
+                    .collect();
+
+                let mut port_overrides: Vec<PortOverride> = group
+                    .port_overrides
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|port_override| PortOverride {
+                        listener_port: port_override.listener_port.unwrap_or_default(),
+                        endpoint_port: port_override.endpoint_port.unwrap_or_default(),
+                    })
+                    .collect();
+                port_overrides.sort();
+
+                let resource = EndpointGroup {
+                    endpoints,
+                    traffic_dial_percentage: group.traffic_dial_percentage,
+                    health_check_port: group.health_check_port,
+                    health_check_protocol: group.health_check_protocol.map(|p| p.as_str().to_string()),
+                    health_check_path: group.health_check_path,
+                    health_check_interval_seconds: group.health_check_interval_seconds,
+                    threshold_count: group.threshold_count,
+                    port_overrides,
+                };
+
+                get_resource_response!(
+                    GlobalAcceleratorResource::EndpointGroup(resource),
+                    [(String::from("endpoint_group_arn"), group.endpoint_group_arn.unwrap_or_default())]
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::{
+    addr::GlobalAcceleratorResourceAddress,
+    op_impl::{list_endpoint_groups, list_listeners, sdk_listener_name},
+};
+
+use super::GlobalAcceleratorConnector;
+
+impl GlobalAcceleratorConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let client = self.get_or_init_client().await?;
+
+        let mut next_token = None;
+        loop {
+            let resp = client.list_accelerators().set_next_token(next_token).send().await?;
+
+            for accelerator in resp.accelerators() {
+                let (Some(name), Some(accelerator_arn)) = (&accelerator.name, &accelerator.accelerator_arn) else {
+                    continue;
+                };
+
+                results.push(GlobalAcceleratorResourceAddress::Accelerator { name: name.clone() }.to_path_buf());
+
+                for listener in list_listeners(&client, accelerator_arn).await? {
+                    let (Some(listener_name), Some(listener_arn)) = (sdk_listener_name(&listener), &listener.listener_arn) else {
+                        continue;
+                    };
+
+                    results.push(
+                        GlobalAcceleratorResourceAddress::Listener {
+                            accelerator: name.clone(),
+                            listener:    listener_name.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+
+                    for group in list_endpoint_groups(&client, listener_arn).await? {
+                        let Some(region) = group.endpoint_group_region else {
+                            continue;
+                        };
+
+                        results.push(
+                            GlobalAcceleratorResourceAddress::EndpointGroup {
+                                accelerator: name.clone(),
+                                listener: listener_name.clone(),
+                                region,
+                            }
+                            .to_path_buf(),
+                        );
+                    }
+                }
+            }
+
+            next_token = resp.next_token;
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,73 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{ConnectorOp, OpExecResponse, ResourceAddress},
+    error_util::invalid_op,
+};
+
+use crate::{
+    addr::GlobalAcceleratorResourceAddress, op::GlobalAcceleratorConnectorOp, op_impl, resource::EndpointGroup,
+    util::resolve_load_balancers,
+};
+
+use super::GlobalAcceleratorConnector;
+
+impl GlobalAcceleratorConnector {
+    /// Replaces load balancer addresses on the group's endpoints with their ARNs.
+    fn resolve_endpoint_group(&self, group: EndpointGroup) -> anyhow::Result<EndpointGroup> {
+        Ok(EndpointGroup {
+            endpoints: resolve_load_balancers(&self.prefix, group.endpoints)?,
+            ..group
+        })
+    }
+
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = GlobalAcceleratorResourceAddress::from_path(addr)?;
+        let op = GlobalAcceleratorConnectorOp::from_str(op)?;
+        let client = self.get_or_init_client().await?;
+
+        match &addr {
+            GlobalAcceleratorResourceAddress::Accelerator { name } => match op {
+                GlobalAcceleratorConnectorOp::CreateAccelerator(accelerator) => {
+                    op_impl::create_accelerator(&client, name, &accelerator).await
+                }
+                GlobalAcceleratorConnectorOp::UpdateAccelerator(accelerator) => {
+                    op_impl::update_accelerator(&client, name, &accelerator).await
+                }
+                GlobalAcceleratorConnectorOp::UpdateAcceleratorTags(old_tags, new_tags) => {
+                    op_impl::update_accelerator_tags(&client, name, &old_tags, &new_tags).await
+                }
+                GlobalAcceleratorConnectorOp::DeleteAccelerator => op_impl::delete_accelerator(&client, name).await,
+                _ => Err(invalid_op(&addr, &op)),
+            },
+            GlobalAcceleratorResourceAddress::Listener { accelerator, listener } => match op {
+                GlobalAcceleratorConnectorOp::CreateListener(new_listener) => {
+                    op_impl::create_listener(&client, accelerator, listener, &new_listener).await
+                }
+                GlobalAcceleratorConnectorOp::UpdateListener(new_listener) => {
+                    op_impl::update_listener(&client, accelerator, listener, &new_listener).await
+                }
+                GlobalAcceleratorConnectorOp::DeleteListener => op_impl::delete_listener(&client, accelerator, listener).await,
+                _ => Err(invalid_op(&addr, &op)),
+            },
+            GlobalAcceleratorResourceAddress::EndpointGroup {
+                accelerator,
+                listener,
+                region,
+            } => match op {
+                GlobalAcceleratorConnectorOp::CreateEndpointGroup(group) => {
+                    let group = self.resolve_endpoint_group(group)?;
+                    op_impl::create_endpoint_group(&client, accelerator, listener, region, &group).await
+                }
+                GlobalAcceleratorConnectorOp::UpdateEndpointGroup(group) => {
+                    let group = self.resolve_endpoint_group(group)?;
+                    op_impl::update_endpoint_group(&client, accelerator, listener, region, &group).await
+                }
+                GlobalAcceleratorConnectorOp::DeleteEndpointGroup => {
+                    op_impl::delete_endpoint_group(&client, accelerator, listener, region).await
+                }
+                _ => Err(invalid_op(&addr, &op)),
+            },
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::{
+    resource::{Accelerator, EndpointGroup, Listener},
+    util::{check_load_balancer_addresses, parse_listener_name},
+};
+
+use super::{GlobalAcceleratorConnector, GlobalAcceleratorConnectorOp, GlobalAcceleratorResourceAddress};
+
+const IP_ADDRESS_TYPES: &[&str] = &["IPV4", "DUAL_STACK"];
+const CLIENT_AFFINITIES: &[&str] = &["NONE", "SOURCE_IP"];
+const HEALTH_CHECK_PROTOCOLS: &[&str] = &["TCP", "HTTP", "HTTPS"];
+
+fn check_accelerator(name: &str, accelerator: &Accelerator) -> anyhow::Result<()> {
+    if !IP_ADDRESS_TYPES.contains(&accelerator.ip_address_type.as_str()) {
+        bail!(
+            "Accelerator {} has IP address type {}: expected one of {}",
+            name,
+            accelerator.ip_address_type,
+            IP_ADDRESS_TYPES.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Listeners are named by their protocol and lowest port, so the name has to agree with the port ranges.
+fn check_listener(name: &str, listener: &Listener) -> anyhow::Result<()> {
+    let (_, port) = parse_listener_name(name)?;
+
+    if listener.port_ranges.is_empty() {
+        bail!("Listener {} has no port ranges", name);
+    }
+    for range in &listener.port_ranges {
+        if range.from_port > range.to_port {
+            bail!(
+                "Listener {} has port range {}-{}, which ends before it starts",
+                name,
+                range.from_port,
+                range.to_port
+            );
+        }
+    }
+    let lowest_port = listener.port_ranges.iter().map(|range| range.from_port).min().unwrap_or_default();
+    if lowest_port != port {
+        bail!(
+            "Listener {}'s lowest port is {}, but its name says {}. Rename the listener's file to match.",
+            name,
+            lowest_port,
+            port
+        );
+    }
+    if let Some(client_affinity) = &listener.client_affinity
+        && !CLIENT_AFFINITIES.contains(&client_affinity.as_str())
+    {
+        bail!(
+            "Listener {} has client affinity {}: expected one of {}",
+            name,
+            client_affinity,
+            CLIENT_AFFINITIES.join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn check_endpoint_group(region: &str, group: &EndpointGroup) -> anyhow::Result<()> {
+    let problems = check_load_balancer_addresses(region, &group.endpoints);
+    if !problems.is_empty() {
+        bail!("Endpoint group in {} has invalid endpoints: {}", region, problems.join("; "));
+    }
+    if let Some(protocol) = &group.health_check_protocol
+        && !HEALTH_CHECK_PROTOCOLS.contains(&protocol.as_str())
+    {
+        bail!(
+            "Endpoint group in {} has health check protocol {}: expected one of {}",
+            region,
+            protocol,
+            HEALTH_CHECK_PROTOCOLS.join(", ")
+        );
+    }
+    if let Some(percentage) = group.traffic_dial_percentage
+        && !(0.0..=100.0).contains(&percentage)
+    {
+        bail!("Endpoint group in {} has traffic dial {}%: expected 0 to 100", region, percentage);
+    }
+    Ok(())
+}
+
+/// Global Accelerator fills in defaults for these fields when they're left out, so leaving them out doesn't change them.
+fn normalize_endpoint_group(old: &EndpointGroup, new: &mut EndpointGroup) {
+    if new.traffic_dial_percentage.is_none() {
+        new.traffic_dial_percentage = old.traffic_dial_percentage;
+    }
+    if new.health_check_port.is_none() {
+        new.health_check_port = old.health_check_port;
+    }
+    if new.health_check_protocol.is_none() {
+        new.health_check_protocol = old.health_check_protocol.clone();
+    }
+    if new.health_check_path.is_none() {
+        new.health_check_path = old.health_check_path.clone();
+    }
+    if new.health_check_interval_seconds.is_none() {
+        new.health_check_interval_seconds = old.health_check_interval_seconds;
+    }
+    if new.threshold_count.is_none() {
+        new.threshold_count = old.threshold_count;
+    }
+    for endpoint in &mut new.endpoints {
+        let Some(old_endpoint) = old.endpoints.iter().find(|e| e.endpoint_id == endpoint.endpoint_id) else {
+            continue;
+        };
+        if endpoint.weight.is_none() {
+            endpoint.weight = old_endpoint.weight;
+        }
+        if endpoint.client_ip_preservation_enabled.is_none() {
+            endpoint.client_ip_preservation_enabled = old_endpoint.client_ip_preservation_enabled;
+        }
+    }
+    new.port_overrides.sort();
+}
+
+impl GlobalAcceleratorConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = GlobalAcceleratorResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            GlobalAcceleratorResourceAddress::Accelerator { name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_accelerator)) => {
+                    let new_accelerator: Accelerator = RON.from_str(&new_accelerator)?;
+                    check_accelerator(name, &new_accelerator)?;
+                    Ok(vec![connector_op!(
+                        GlobalAcceleratorConnectorOp::CreateAccelerator(new_accelerator),
+                        format!("Create new accelerator {}", name)
+                    )])
+                }
+                (Some(_old_accelerator), None) => Ok(vec![connector_op!(
+                    GlobalAcceleratorConnectorOp::DeleteAccelerator,
+                    format!(
+                        "DELETE accelerator {}. It's disabled first, and its static IP addresses are released.",
+                        name
+                    )
+                )]),
+                (Some(old_accelerator), Some(new_accelerator)) => {
+                    let old_accelerator: Accelerator = RON.from_str(&old_accelerator)?;
+                    let mut new_accelerator: Accelerator = RON.from_str(&new_accelerator)?;
+                    check_accelerator(name, &new_accelerator)?;
+
+                    if new_accelerator.ip_addresses.is_empty() {
+                        new_accelerator.ip_addresses = old_accelerator.ip_addresses.clone();
+                    }
+                    new_accelerator.ip_addresses.sort();
+                    if old_accelerator.ip_addresses != new_accelerator.ip_addresses {
+                        bail!(
+                            "Accelerator {} can't change ip_addresses after creation. Create a new accelerator under another name instead.",
+                            name
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_accelerator.tags != new_accelerator.tags {
+                        let diff = diff_ron_values(&old_accelerator.tags, &new_accelerator.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            GlobalAcceleratorConnectorOp::UpdateAcceleratorTags(
+                                old_accelerator.tags.clone(),
+                                new_accelerator.tags.clone()
+                            ),
+                            format!("Modify tags for accelerator `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_accelerator.clone();
+                    old_settings.tags = new_accelerator.tags.clone();
+                    if old_settings != new_accelerator {
+                        let diff = diff_ron_values(&old_settings, &new_accelerator).unwrap_or_default();
+                        ops.push(connector_op!(
+                            GlobalAcceleratorConnectorOp::UpdateAccelerator(new_accelerator),
+                            format!("Modify accelerator `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            GlobalAcceleratorResourceAddress::Listener { accelerator, listener } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_listener)) => {
+                    let new_listener: Listener = RON.from_str(&new_listener)?;
+                    check_listener(listener, &new_listener)?;
+                    Ok(vec![connector_op!(
+                        GlobalAcceleratorConnectorOp::CreateListener(new_listener),
+                        format!("Create new listener {} on accelerator {}", listener, accelerator)
+                    )])
+                }
+                (Some(_old_listener), None) => Ok(vec![connector_op!(
+                    GlobalAcceleratorConnectorOp::DeleteListener,
+                    format!(
+                        "DELETE listener {} on accelerator {}. Its endpoint groups must be deleted first.",
+                        listener, accelerator
+                    )
+                )]),
+                (Some(old_listener), Some(new_listener)) => {
+                    let old_listener: Listener = RON.from_str(&old_listener)?;
+                    let mut new_listener: Listener = RON.from_str(&new_listener)?;
+                    check_listener(listener, &new_listener)?;
+                    new_listener.port_ranges.sort();
+                    if new_listener.client_affinity.is_none() {
+                        new_listener.client_affinity = old_listener.client_affinity.clone();
+                    }
+
+                    if old_listener == new_listener {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_listener, &new_listener).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        GlobalAcceleratorConnectorOp::UpdateListener(new_listener),
+                        format!("Modify listener `{}` on accelerator `{}`\n{}", listener, accelerator, diff)
+                    )])
+                }
+            },
+            GlobalAcceleratorResourceAddress::EndpointGroup {
+                accelerator,
+                listener,
+                region,
+            } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_group)) => {
+                    let new_group: EndpointGroup = RON.from_str(&new_group)?;
+                    check_endpoint_group(region, &new_group)?;
+                    Ok(vec![connector_op!(
+                        GlobalAcceleratorConnectorOp::CreateEndpointGroup(new_group),
+                        format!(
+                            "Create new endpoint group in {} for listener {} on accelerator {}",
+                            region, listener, accelerator
+                        )
+                    )])
+                }
+                (Some(_old_group), None) => Ok(vec![connector_op!(
+                    GlobalAcceleratorConnectorOp::DeleteEndpointGroup,
+                    format!(
+                        "DELETE endpoint group in {} for listener {} on accelerator {}. Traffic to {} stops being accelerated.",
+                        region, listener, accelerator, region
+                    )
+                )]),
+                (Some(old_group), Some(new_group)) => {
+                    let old_group: EndpointGroup = RON.from_str(&old_group)?;
+                    let mut new_group: EndpointGroup = RON.from_str(&new_group)?;
+                    check_endpoint_group(region, &new_group)?;
+                    normalize_endpoint_group(&old_group, &mut new_group);
+
+                    if old_group == new_group {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_group, &new_group).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        GlobalAcceleratorConnectorOp::UpdateEndpointGroup(new_group),
+                        format!(
+                            "Modify endpoint group in `{}` for listener `{}` on accelerator `{}`\n{}",
+                            region, listener, accelerator, diff
+                        )
+                    )])
+                }
+            },
+        }
+    }
+}
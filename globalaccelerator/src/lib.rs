@@ -0,0 +1,4 @@
+mod addr;
+mod resource;
+pub mod op;
+pub mod tags;
@@ -0,0 +1,41 @@
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{Accelerator, EndpointGroup, Listener},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum GlobalAcceleratorConnectorOp {
+    /// Creates the accelerator, and waits for it to be deployed.
+    CreateAccelerator(Accelerator),
+    /// Applies the accelerator's IP address type, enabled state and flow logs.
+    UpdateAccelerator(Accelerator),
+    UpdateAcceleratorTags(Tags, Tags),
+    /// Disables the accelerator if it's enabled, waits for that to deploy, then deletes it.
+    DeleteAccelerator,
+
+    CreateListener(Listener),
+    UpdateListener(Listener),
+    DeleteListener,
+
+    CreateEndpointGroup(EndpointGroup),
+    UpdateEndpointGroup(EndpointGroup),
+    DeleteEndpointGroup,
+}
+
+impl ConnectorOp for GlobalAcceleratorConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
@@ -0,0 +1,489 @@
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::{Context, bail};
+use autoschematic_core::{connector::OpExecResponse, op_exec_output};
+use aws_sdk_globalaccelerator::types::{ClientAffinity, EndpointConfiguration, HealthCheckProtocol, IpAddressType, Protocol};
+
+use crate::{
+    resource::{Accelerator, EndpointGroup, Listener, PortOverride, PortRange},
+    tags::{Tags, tag_diff},
+    util::{listener_name, parse_listener_name},
+};
+
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// Changes to an accelerator usually deploy within a few minutes.
+const STATUS_MAX_POLLS: usize = 90;
+
+fn idempotency_token() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// Waits for the accelerator's last change to finish deploying.
+async fn wait_for_deployed(client: &aws_sdk_globalaccelerator::Client, name: &str, arn: &str) -> anyhow::Result<()> {
+    for _ in 0..STATUS_MAX_POLLS {
+        let resp = client.describe_accelerator().accelerator_arn(arn).send().await?;
+        let status = resp.accelerator.and_then(|accelerator| accelerator.status);
+        if status.as_ref().is_some_and(|s| s.as_str() == "DEPLOYED") {
+            return Ok(());
+        }
+        tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    }
+
+    bail!("Timed out waiting for accelerator {} to deploy", name)
+}
+
+/// ListAccelerators can't filter by name, so this pages through all of them.
+pub async fn find_accelerator(
+    client: &aws_sdk_globalaccelerator::Client,
+    name: &str,
+) -> anyhow::Result<Option<aws_sdk_globalaccelerator::types::Accelerator>> {
+    let mut next_token = None;
+    loop {
+        let resp = client.list_accelerators().set_next_token(next_token).send().await?;
+
+        if let Some(accelerator) = resp
+            .accelerators
+            .unwrap_or_default()
+            .into_iter()
+            .find(|accelerator| accelerator.name.as_deref() == Some(name))
+        {
+            return Ok(Some(accelerator));
+        }
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            return Ok(None);
+        }
+    }
+}
+
+pub async fn list_listeners(
+    client: &aws_sdk_globalaccelerator::Client,
+    accelerator_arn: &str,
+) -> anyhow::Result<Vec<aws_sdk_globalaccelerator::types::Listener>> {
+    let mut listeners = Vec::new();
+    let mut next_token = None;
+    loop {
+        let resp = client
+            .list_listeners()
+            .accelerator_arn(accelerator_arn)
+            .set_next_token(next_token)
+            .send()
+            .await?;
+
+        listeners.extend(resp.listeners.unwrap_or_default());
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            return Ok(listeners);
+        }
+    }
+}
+
+/// The name of a listener as reported by Global Accelerator, e.g. `tcp-443`.
+pub fn sdk_listener_name(listener: &aws_sdk_globalaccelerator::types::Listener) -> Option<String> {
+    let port_ranges: Vec<PortRange> = listener.port_ranges().iter().map(from_sdk_port_range).collect();
+    listener_name(listener.protocol.as_ref()?.as_str(), &port_ranges)
+}
+
+pub async fn find_listener(
+    client: &aws_sdk_globalaccelerator::Client,
+    accelerator: &str,
+    listener: &str,
+) -> anyhow::Result<Option<aws_sdk_globalaccelerator::types::Listener>> {
+    let Some(accelerator_arn) = find_accelerator(client, accelerator).await?.and_then(|a| a.accelerator_arn) else {
+        return Ok(None);
+    };
+
+    Ok(list_listeners(client, &accelerator_arn)
+        .await?
+        .into_iter()
+        .find(|l| sdk_listener_name(l).as_deref() == Some(listener)))
+}
+
+pub async fn list_endpoint_groups(
+    client: &aws_sdk_globalaccelerator::Client,
+    listener_arn: &str,
+) -> anyhow::Result<Vec<aws_sdk_globalaccelerator::types::EndpointGroup>> {
+    let mut groups = Vec::new();
+    let mut next_token = None;
+    loop {
+        let resp = client
+            .list_endpoint_groups()
+            .listener_arn(listener_arn)
+            .set_next_token(next_token)
+            .send()
+            .await?;
+
+        groups.extend(resp.endpoint_groups.unwrap_or_default());
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            return Ok(groups);
+        }
+    }
+}
+
+pub async fn find_endpoint_group(
+    client: &aws_sdk_globalaccelerator::Client,
+    accelerator: &str,
+    listener: &str,
+    region: &str,
+) -> anyhow::Result<Option<aws_sdk_globalaccelerator::types::EndpointGroup>> {
+    let Some(listener_arn) = find_listener(client, accelerator, listener).await?.and_then(|l| l.listener_arn) else {
+        return Ok(None);
+    };
+
+    Ok(list_endpoint_groups(client, &listener_arn)
+        .await?
+        .into_iter()
+        .find(|group| group.endpoint_group_region.as_deref() == Some(region)))
+}
+
+async fn accelerator_arn(client: &aws_sdk_globalaccelerator::Client, name: &str) -> anyhow::Result<String> {
+    find_accelerator(client, name)
+        .await?
+        .and_then(|accelerator| accelerator.accelerator_arn)
+        .with_context(|| format!("Accelerator {} not found", name))
+}
+
+async fn listener_arn(client: &aws_sdk_globalaccelerator::Client, accelerator: &str, listener: &str) -> anyhow::Result<String> {
+    find_listener(client, accelerator, listener)
+        .await?
+        .and_then(|listener| listener.listener_arn)
+        .with_context(|| format!("Listener {} on accelerator {} not found", listener, accelerator))
+}
+
+async fn endpoint_group_arn(
+    client: &aws_sdk_globalaccelerator::Client,
+    accelerator: &str,
+    listener: &str,
+    region: &str,
+) -> anyhow::Result<String> {
+    find_endpoint_group(client, accelerator, listener, region)
+        .await?
+        .and_then(|group| group.endpoint_group_arn)
+        .with_context(|| {
+            format!(
+                "Endpoint group in {} for listener {} on accelerator {} not found",
+                region, listener, accelerator
+            )
+        })
+}
+
+pub fn from_sdk_port_range(range: &aws_sdk_globalaccelerator::types::PortRange) -> PortRange {
+    PortRange {
+        from_port: range.from_port.unwrap_or_default(),
+        to_port:   range.to_port.unwrap_or_default(),
+    }
+}
+
+fn to_sdk_port_ranges(port_ranges: &[PortRange]) -> Vec<aws_sdk_globalaccelerator::types::PortRange> {
+    port_ranges
+        .iter()
+        .map(|range| {
+            aws_sdk_globalaccelerator::types::PortRange::builder()
+                .from_port(range.from_port)
+                .to_port(range.to_port)
+                .build()
+        })
+        .collect()
+}
+
+fn to_sdk_port_overrides(port_overrides: &[PortOverride]) -> Vec<aws_sdk_globalaccelerator::types::PortOverride> {
+    port_overrides
+        .iter()
+        .map(|port_override| {
+            aws_sdk_globalaccelerator::types::PortOverride::builder()
+                .listener_port(port_override.listener_port)
+                .endpoint_port(port_override.endpoint_port)
+                .build()
+        })
+        .collect()
+}
+
+/// The endpoint group's endpoints must already have any load balancer addresses resolved to ARNs.
+fn to_sdk_endpoints(group: &EndpointGroup) -> Vec<EndpointConfiguration> {
+    group
+        .endpoints
+        .iter()
+        .map(|endpoint| {
+            EndpointConfiguration::builder()
+                .endpoint_id(&endpoint.endpoint_id)
+                .set_weight(endpoint.weight)
+                .set_client_ip_preservation_enabled(endpoint.client_ip_preservation_enabled)
+                .build()
+        })
+        .collect()
+}
+
+async fn update_flow_logs(client: &aws_sdk_globalaccelerator::Client, arn: &str, accelerator: &Accelerator) -> anyhow::Result<()> {
+    let mut request = client
+        .update_accelerator_attributes()
+        .accelerator_arn(arn)
+        .flow_logs_enabled(accelerator.flow_logs.is_some());
+    if let Some(flow_logs) = &accelerator.flow_logs {
+        request = request
+            .flow_logs_s3_bucket(&flow_logs.s3_bucket)
+            .set_flow_logs_s3_prefix(flow_logs.s3_prefix.clone());
+    }
+    request.send().await?;
+    Ok(())
+}
+
+pub async fn create_accelerator(
+    client: &aws_sdk_globalaccelerator::Client,
+    name: &str,
+    accelerator: &Accelerator,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_accelerator()
+        .name(name)
+        .idempotency_token(idempotency_token())
+        .ip_address_type(IpAddressType::from(accelerator.ip_address_type.as_str()))
+        .set_ip_addresses(if accelerator.ip_addresses.is_empty() {
+            None
+        } else {
+            Some(accelerator.ip_addresses.clone())
+        })
+        .enabled(accelerator.enabled)
+        .set_tags(accelerator.tags.to_sdk()?)
+        .send()
+        .await?;
+
+    let created = resp.accelerator.context("CreateAccelerator returned no accelerator")?;
+    let arn = created.accelerator_arn.context("CreateAccelerator returned no accelerator ARN")?;
+
+    if accelerator.flow_logs.is_some() {
+        update_flow_logs(client, &arn, accelerator).await?;
+    }
+
+    wait_for_deployed(client, name, &arn).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("accelerator_arn"), Some(arn)),
+            (String::from("dns_name"), created.dns_name),
+        ])),
+        friendly_message: Some(format!("Created accelerator {}", name)),
+    })
+}
+
+pub async fn update_accelerator(
+    client: &aws_sdk_globalaccelerator::Client,
+    name: &str,
+    accelerator: &Accelerator,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = accelerator_arn(client, name).await?;
+
+    client
+        .update_accelerator()
+        .accelerator_arn(&arn)
+        .ip_address_type(IpAddressType::from(accelerator.ip_address_type.as_str()))
+        .enabled(accelerator.enabled)
+        .send()
+        .await?;
+
+    update_flow_logs(client, &arn, accelerator).await?;
+
+    wait_for_deployed(client, name, &arn).await?;
+
+    op_exec_output!(format!("Updated accelerator {}", name))
+}
+
+pub async fn update_accelerator_tags(
+    client: &aws_sdk_globalaccelerator::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = accelerator_arn(client, name).await?;
+
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(&arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if let Some(new_tagset) = new_tagset.to_sdk()? {
+        client.tag_resource().resource_arn(&arn).set_tags(Some(new_tagset)).send().await?;
+    }
+
+    op_exec_output!(format!("Updated tags for accelerator {}", name))
+}
+
+pub async fn delete_accelerator(client: &aws_sdk_globalaccelerator::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    let current = find_accelerator(client, name)
+        .await?
+        .with_context(|| format!("Accelerator {} not found", name))?;
+    let arn = current.accelerator_arn.unwrap_or_default();
+
+    if current.enabled.unwrap_or(false) {
+        client.update_accelerator().accelerator_arn(&arn).enabled(false).send().await?;
+    }
+    wait_for_deployed(client, name, &arn).await?;
+
+    client.delete_accelerator().accelerator_arn(&arn).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("accelerator_arn"), None),
+            (String::from("dns_name"), None),
+        ])),
+        friendly_message: Some(format!("Deleted accelerator {}", name)),
+    })
+}
+
+pub async fn create_listener(
+    client: &aws_sdk_globalaccelerator::Client,
+    accelerator: &str,
+    name: &str,
+    listener: &Listener,
+) -> anyhow::Result<OpExecResponse> {
+    let (protocol, _) = parse_listener_name(name)?;
+
+    let resp = client
+        .create_listener()
+        .accelerator_arn(accelerator_arn(client, accelerator).await?)
+        .idempotency_token(idempotency_token())
+        .protocol(Protocol::from(protocol.as_str()))
+        .set_port_ranges(Some(to_sdk_port_ranges(&listener.port_ranges)))
+        .set_client_affinity(listener.client_affinity.as_deref().map(ClientAffinity::from))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(
+            String::from("listener_arn"),
+            resp.listener.and_then(|listener| listener.listener_arn),
+        )])),
+        friendly_message: Some(format!("Created listener {} on accelerator {}", name, accelerator)),
+    })
+}
+
+pub async fn update_listener(
+    client: &aws_sdk_globalaccelerator::Client,
+    accelerator: &str,
+    name: &str,
+    listener: &Listener,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .update_listener()
+        .listener_arn(listener_arn(client, accelerator, name).await?)
+        .set_port_ranges(Some(to_sdk_port_ranges(&listener.port_ranges)))
+        .set_client_affinity(listener.client_affinity.as_deref().map(ClientAffinity::from))
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Updated listener {} on accelerator {}", name, accelerator))
+}
+
+pub async fn delete_listener(
+    client: &aws_sdk_globalaccelerator::Client,
+    accelerator: &str,
+    name: &str,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .delete_listener()
+        .listener_arn(listener_arn(client, accelerator, name).await?)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("listener_arn"), None)])),
+        friendly_message: Some(format!("Deleted listener {} on accelerator {}", name, accelerator)),
+    })
+}
+
+pub async fn create_endpoint_group(
+    client: &aws_sdk_globalaccelerator::Client,
+    accelerator: &str,
+    listener: &str,
+    region: &str,
+    group: &EndpointGroup,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_endpoint_group()
+        .listener_arn(listener_arn(client, accelerator, listener).await?)
+        .endpoint_group_region(region)
+        .idempotency_token(idempotency_token())
+        .set_endpoint_configurations(Some(to_sdk_endpoints(group)))
+        .set_traffic_dial_percentage(group.traffic_dial_percentage)
+        .set_health_check_port(group.health_check_port)
+        .set_health_check_protocol(group.health_check_protocol.as_deref().map(HealthCheckProtocol::from))
+        .set_health_check_path(group.health_check_path.clone())
+        .set_health_check_interval_seconds(group.health_check_interval_seconds)
+        .set_threshold_count(group.threshold_count)
+        .set_port_overrides(if group.port_overrides.is_empty() {
+            None
+        } else {
+            Some(to_sdk_port_overrides(&group.port_overrides))
+        })
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(
+            String::from("endpoint_group_arn"),
+            resp.endpoint_group.and_then(|group| group.endpoint_group_arn),
+        )])),
+        friendly_message: Some(format!(
+            "Created endpoint group in {} for listener {} on accelerator {}",
+            region, listener, accelerator
+        )),
+    })
+}
+
+pub async fn update_endpoint_group(
+    client: &aws_sdk_globalaccelerator::Client,
+    accelerator: &str,
+    listener: &str,
+    region: &str,
+    group: &EndpointGroup,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .update_endpoint_group()
+        .endpoint_group_arn(endpoint_group_arn(client, accelerator, listener, region).await?)
+        .set_endpoint_configurations(Some(to_sdk_endpoints(group)))
+        .set_traffic_dial_percentage(group.traffic_dial_percentage)
+        .set_health_check_port(group.health_check_port)
+        .set_health_check_protocol(group.health_check_protocol.as_deref().map(HealthCheckProtocol::from))
+        .set_health_check_path(group.health_check_path.clone())
+        .set_health_check_interval_seconds(group.health_check_interval_seconds)
+        .set_threshold_count(group.threshold_count)
+        // An empty list removes all port overrides
+        .set_port_overrides(Some(to_sdk_port_overrides(&group.port_overrides)))
+        .send()
+        .await?;
+
+    op_exec_output!(format!(
+        "Updated endpoint group in {} for listener {} on accelerator {}",
+        region, listener, accelerator
+    ))
+}
+
+pub async fn delete_endpoint_group(
+    client: &aws_sdk_globalaccelerator::Client,
+    accelerator: &str,
+    listener: &str,
+    region: &str,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .delete_endpoint_group()
+        .endpoint_group_arn(endpoint_group_arn(client, accelerator, listener, region).await?)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("endpoint_group_arn"), None)])),
+        friendly_message: Some(format!(
+            "Deleted endpoint group in {} for listener {} on accelerator {}",
+            region, listener, accelerator
+        )),
+    })
+}
@@ -0,0 +1,124 @@
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::GlobalAcceleratorResourceAddress, tags::Tags};
+
+fn default_true() -> bool {
+    true
+}
+
+/// A standard accelerator. Its static IP addresses are allocated at creation and fixed after.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Accelerator {
+    /// IPV4 or DUAL_STACK.
+    pub ip_address_type: String,
+    /// Addresses from your own IP address pools (BYOIP). If empty, AWS allocates them.
+    #[serde(default)]
+    pub ip_addresses: Vec<String>,
+    /// Accelerators must be disabled before they can be deleted.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    pub flow_logs: Option<FlowLogs>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FlowLogs {
+    pub s3_bucket: String,
+    pub s3_prefix: Option<String>,
+}
+
+/// A listener on an accelerator. Its protocol and lowest port come from its address.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Listener {
+    pub port_ranges: Vec<PortRange>,
+    /// NONE or SOURCE_IP. If None, NONE.
+    pub client_affinity: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, PartialOrd, Eq, Ord)]
+#[serde(deny_unknown_fields)]
+pub struct PortRange {
+    pub from_port: i32,
+    pub to_port:   i32,
+}
+
+/// A listener's endpoints in one region, and how their health is checked.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EndpointGroup {
+    #[serde(default)]
+    pub endpoints: Vec<Endpoint>,
+    /// The percentage of traffic sent to this region. If None, 100.
+    pub traffic_dial_percentage: Option<f32>,
+    /// If None, the listener's port.
+    pub health_check_port: Option<i32>,
+    /// TCP, HTTP or HTTPS. If None, TCP.
+    pub health_check_protocol: Option<String>,
+    /// For HTTP and HTTPS health checks. If None, /.
+    pub health_check_path: Option<String>,
+    /// 10 or 30. If None, 30.
+    pub health_check_interval_seconds: Option<i32>,
+    /// If None, 3.
+    pub threshold_count: Option<i32>,
+    #[serde(default)]
+    pub port_overrides: Vec<PortOverride>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Endpoint {
+    /// A load balancer ARN, an Elastic IP allocation ID or an EC2 instance ID.
+    /// Load balancers managed by the ELB connector can be given by address instead,
+    /// e.g. aws/elb/us-east-1/load_balancers/web.ron.
+    pub endpoint_id: String,
+    /// If None, 128.
+    pub weight: Option<i32>,
+    pub client_ip_preservation_enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, PartialOrd, Eq, Ord)]
+#[serde(deny_unknown_fields)]
+pub struct PortOverride {
+    pub listener_port: i32,
+    pub endpoint_port: i32,
+}
+
+pub enum GlobalAcceleratorResource {
+    Accelerator(Accelerator),
+    Listener(Listener),
+    EndpointGroup(EndpointGroup),
+}
+
+impl Resource for GlobalAcceleratorResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            GlobalAcceleratorResource::Accelerator(accelerator) => Ok(RON.to_string_pretty(&accelerator, pretty_config)?.into()),
+            GlobalAcceleratorResource::Listener(listener) => Ok(RON.to_string_pretty(&listener, pretty_config)?.into()),
+            GlobalAcceleratorResource::EndpointGroup(group) => Ok(RON.to_string_pretty(&group, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = GlobalAcceleratorResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            GlobalAcceleratorResourceAddress::Accelerator { .. } => Ok(GlobalAcceleratorResource::Accelerator(RON.from_str(s)?)),
+            GlobalAcceleratorResourceAddress::Listener { .. } => Ok(GlobalAcceleratorResource::Listener(RON.from_str(s)?)),
+            GlobalAcceleratorResourceAddress::EndpointGroup { .. } => {
+                Ok(GlobalAcceleratorResource::EndpointGroup(RON.from_str(s)?))
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::path::Path;
+
+use anyhow::{Context, bail};
+use autoschematic_connector_aws_core::elb::{ElbLoadBalancerAddress, check_elb_address, resolve_elb_address};
+
+use crate::resource::{Endpoint, PortRange};
+
+const PROTOCOLS: &[&str] = &["TCP", "UDP"];
+
+/// The name a listener is addressed by: its protocol and lowest port, e.g. `tcp-443`.
+pub fn listener_name(protocol: &str, port_ranges: &[PortRange]) -> Option<String> {
+    let port = port_ranges.iter().map(|range| range.from_port).min()?;
+    Some(format!("{}-{}", protocol.to_lowercase(), port))
+}
+
+/// Splits a listener name into its protocol, as Global Accelerator spells it, and its lowest port.
+pub fn parse_listener_name(name: &str) -> anyhow::Result<(String, i32)> {
+    let (protocol, port) = name
+        .split_once('-')
+        .with_context(|| format!("Listener {} should be named <protocol>-<port>, e.g. tcp-443", name))?;
+
+    let protocol = protocol.to_uppercase();
+    if !PROTOCOLS.contains(&protocol.as_str()) {
+        bail!("Listener {} has protocol {}: expected one of tcp, udp", name, protocol.to_lowercase());
+    }
+    let port = port
+        .parse()
+        .with_context(|| format!("Listener {} should be named <protocol>-<port>, e.g. tcp-443", name))?;
+
+    Ok((protocol, port))
+}
+
+/// Checks that each load balancer address on an endpoint group is a valid ELB load balancer address in the
+/// endpoint group's region. Endpoints named by ID or ARN aren't checked.
+pub fn check_load_balancer_addresses(group_region: &str, endpoints: &[Endpoint]) -> Vec<String> {
+    endpoints
+        .iter()
+        .filter_map(|endpoint| {
+            check_elb_address::<ElbLoadBalancerAddress>(&endpoint.endpoint_id, group_region, "the endpoint group")
+        })
+        .collect()
+}
+
+/// Replaces each load balancer address on `endpoints` with the ARN from that load balancer's outputs.
+/// Fails if a referenced load balancer hasn't been created yet.
+pub fn resolve_load_balancers(prefix: &Path, endpoints: Vec<Endpoint>) -> anyhow::Result<Vec<Endpoint>> {
+    let mut resolved = Vec::new();
+
+    for mut endpoint in endpoints {
+        endpoint.endpoint_id = resolve_elb_address::<ElbLoadBalancerAddress>(prefix, &endpoint.endpoint_id)?;
+        resolved.push(endpoint);
+    }
+
+    Ok(resolved)
+}
@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
 use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 
 
@@ -78,3 +79,30 @@ impl ResourceAddress for IamResourceAddress {
         }
     }
 }
+
+impl DescribeAddresses for IamResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/iam/users/<path...>/<user_name>.ron",
+                description: "An IAM user. Directories below `users` form the IAM path.",
+                example:     "aws/iam/users/engineering/alice.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/iam/roles/<path...>/<role_name>.ron",
+                description: "An IAM role. Directories below `roles` form the IAM path.",
+                example:     "aws/iam/roles/service-role/deploy.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/iam/groups/<path...>/<group_name>.ron",
+                description: "An IAM group. Directories below `groups` form the IAM path.",
+                example:     "aws/iam/groups/admins.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/iam/policies/<path...>/<policy_name>.ron",
+                description: "A customer managed IAM policy. Directories below `policies` form the IAM path.",
+                example:     "aws/iam/policies/s3-read-only.ron",
+            },
+        ]
+    }
+}
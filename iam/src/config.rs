@@ -0,0 +1,77 @@
+use std::path::Path;
+
+use autoschematic_connector_aws_core::{
+    config::verify_sts_account_id,
+    config::{AwsConnectorConfig, AwsServiceConfig, TimeoutConfig},
+    impl_aws_config,
+};
+use autoschematic_core::util::RON;
+use serde::{Deserialize, Serialize};
+
+use crate::{addr::IamResourceAddress, util::wildcard_match};
+
+fn default_externally_managed_roles() -> Vec<String> {
+    vec![String::from("/aws-reserved/*"), String::from("*/AWSReservedSSO_*")]
+}
+
+/// Principals that are provisioned by something else, such as IAM Identity Center, and must not be
+/// managed from this repo. They're left out of imports, shown read-only, and any plan that would
+/// change them is refused.
+///
+/// Each pattern is matched against the principal's full path and name, e.g.
+/// `/aws-reserved/sso.amazonaws.com/AWSReservedSSO_Admin_0123456789abcdef`, where `*` matches any
+/// run of characters, including `/`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ExternallyManaged {
+    #[serde(default)]
+    pub users:  Vec<String>,
+    /// Defaults to the roles that IAM Identity Center creates for permission sets.
+    #[serde(default = "default_externally_managed_roles")]
+    pub roles:  Vec<String>,
+    #[serde(default)]
+    pub groups: Vec<String>,
+}
+
+impl Default for ExternallyManaged {
+    fn default() -> Self {
+        Self {
+            users:  Vec::new(),
+            roles:  default_externally_managed_roles(),
+            groups: Vec::new(),
+        }
+    }
+}
+
+impl ExternallyManaged {
+    /// Returns the first pattern that marks this address as externally managed, if any.
+    pub fn matching(&self, addr: &IamResourceAddress) -> Option<&str> {
+        let (patterns, path, name) = match addr {
+            IamResourceAddress::User { path, name } => (&self.users, path, name),
+            IamResourceAddress::Role { path, name } => (&self.roles, path, name),
+            IamResourceAddress::Group { path, name } => (&self.groups, path, name),
+            IamResourceAddress::Policy { .. } => return None,
+        };
+
+        let full_name = format!("{path}{name}");
+        patterns
+            .iter()
+            .find(|pattern| wildcard_match(pattern, &full_name))
+            .map(|pattern| pattern.as_str())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IamConnectorConfig {
+    pub account_id:      Option<String>,
+    pub endpoint_url:    Option<String>,
+    pub timeout_config:  Option<TimeoutConfig>,
+    pub sts_region:      String,
+    pub enabled_regions: Vec<String>,
+    #[serde(default)]
+    pub max_concurrent_ops: Option<usize>,
+    #[serde(default)]
+    pub externally_managed: ExternallyManaged,
+}
+
+impl_aws_config!(IamConnectorConfig, "aws/iam/config.ron", externally_managed);
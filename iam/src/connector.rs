@@ -7,12 +7,14 @@ use std::{
 
 use crate::{
     addr::IamResourceAddress,
+    config::IamConnectorConfig,
     resource::IamGroup,
     task::{IamTask, IamTaskAddress},
+    util::IamQuotas,
 };
 use anyhow::bail;
 use async_trait::async_trait;
-use autoschematic_connector_aws_core::config::AwsConnectorConfig;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
 use autoschematic_core::{
     connector::{
         Connector, ConnectorOutbox, DocIdent, FilterResponse, GetDocResponse, GetResourceResponse, OpExecResponse,
@@ -31,16 +33,26 @@ use tokio::sync::RwLock;
 
 use crate::{resource, tags};
 
+mod access_advisor;
+mod access_analyzer;
+mod analyzer_findings;
 mod get;
 mod list;
 mod op_exec;
 mod plan;
+#[cfg(test)]
+mod test;
 
 #[derive(Default)]
 pub struct IamConnector {
     prefix: PathBuf,
     client: RwLock<Option<Arc<aws_sdk_iam::Client>>>,
+    analyzer_client_cache: RwLock<HashMap<String, Arc<aws_sdk_accessanalyzer::Client>>>,
     account_id: RwLock<Option<String>>,
+    op_limiter: RwLock<Arc<OpExecLimiter>>,
+    audit_log: RwLock<Arc<AuditLog>>,
+    quotas: RwLock<IamQuotas>,
+    config: RwLock<IamConnectorConfig>,
 }
 
 #[async_trait]
@@ -56,7 +68,7 @@ impl Connector for IamConnector {
     }
 
     async fn init(&self) -> anyhow::Result<()> {
-        let config_file = AwsConnectorConfig::try_load(&self.prefix)?;
+        let config_file = IamConnectorConfig::try_load(&self.prefix).await?;
 
         let region = RegionProviderChain::first_try(Region::new("global".to_owned()));
 
@@ -68,9 +80,9 @@ impl Connector for IamConnector {
             .load()
             .await;
 
-        let client = aws_sdk_iam::Client::new(&config);
+        let client = audited_client!(aws_sdk_iam, &config);
 
-        let sts_client = aws_sdk_sts::Client::new(&sts_config);
+        let sts_client = audited_client!(aws_sdk_sts, &sts_config);
 
         let caller_identity = sts_client.get_caller_identity().send().await;
         match caller_identity {
@@ -79,8 +91,8 @@ impl Connector for IamConnector {
                     bail!("Failed to get current account ID!");
                 };
 
-                if let Some(config_account_id) = config_file.account_id
-                    && config_account_id != account_id
+                if let Some(config_account_id) = &config_file.account_id
+                    && *config_account_id != account_id
                 {
                     bail!(
                         "Credentials do not match configured account id: creds = {}, aws/config.ron = {}",
@@ -89,8 +101,18 @@ impl Connector for IamConnector {
                     );
                 }
 
+                // Plans are still checked against the default quotas if the account summary isn't readable
+                match IamQuotas::load(&client).await {
+                    Ok(quotas) => *self.quotas.write().await = quotas,
+                    Err(e) => tracing::warn!("Failed to read IAM quotas, using the defaults: {}", e),
+                }
+
                 *self.client.write().await = Some(Arc::new(client));
+                *self.analyzer_client_cache.write().await = HashMap::new();
                 *self.account_id.write().await = Some(account_id);
+                *self.op_limiter.write().await = Arc::new(OpExecLimiter::from_config(config_file.max_concurrent_ops));
+                *self.audit_log.write().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+                *self.config.write().await = config_file;
 
                 Ok(())
             }
@@ -104,6 +126,8 @@ impl Connector for IamConnector {
     async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
         if let Ok(_addr) = IamResourceAddress::from_path(addr) {
             Ok(FilterResponse::Resource)
+        } else if let Ok(_addr) = IamTaskAddress::from_path(addr) {
+            Ok(FilterResponse::Task)
         } else {
             Ok(FilterResponse::None)
         }
@@ -123,7 +147,22 @@ impl Connector for IamConnector {
     }
 
     async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
-        self.do_get(addr).await
+        let Some(mut resource) = self.do_get(addr).await? else {
+            return Ok(None);
+        };
+
+        // Mark externally managed principals as read-only in the file itself
+        if let Ok(iam_addr) = IamResourceAddress::from_path(addr)
+            && let Some(pattern) = self.config.read().await.externally_managed.matching(&iam_addr)
+        {
+            let marker = format!(
+                "// Externally managed (matches `{}` in aws/iam/config.ron): read-only, changes to this file will be refused.\n",
+                pattern
+            );
+            resource.resource_definition = [marker.into_bytes(), resource.resource_definition].concat();
+        }
+
+        Ok(Some(resource))
     }
 
     async fn plan(
@@ -137,7 +176,9 @@ impl Connector for IamConnector {
     }
 
     async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
-        self.do_op_exec(addr, op).await
+        let op_limiter = self.op_limiter.read().await.clone();
+        let audit_log = self.audit_log.read().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
     }
 
     async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
@@ -258,7 +299,12 @@ impl Connector for IamConnector {
 
         match addr {
             IamResourceAddress::User { .. } => ron_check_syntax::<IamUser>(a),
-            IamResourceAddress::Role { .. } => ron_check_syntax::<IamRole>(a),
+            IamResourceAddress::Role { path, name } => {
+                if let Some(diag) = ron_check_syntax::<IamRole>(a)? {
+                    return Ok(Some(diag));
+                }
+                self.access_analyzer_diagnostics(&path, &name).await
+            }
             IamResourceAddress::Group { .. } => ron_check_syntax::<IamGroup>(a),
             IamResourceAddress::Policy { .. } => ron_check_syntax::<IamPolicy>(a),
         }
@@ -270,7 +316,7 @@ impl Connector for IamConnector {
         body: Vec<u8>,
 
         _arg: Option<Vec<u8>>,
-        _state: Option<Vec<u8>>,
+        state: Option<Vec<u8>>,
     ) -> anyhow::Result<TaskExecResponse> {
         let mut res = TaskExecResponse::default();
 
@@ -373,6 +419,15 @@ impl Connector for IamConnector {
                     res.secrets = Some(secrets);
                 }
             }
+            IamTask::AccessAdvisor(access_advisor) => {
+                return self.do_access_advisor_task(client, access_advisor, state).await;
+            }
+            IamTask::AccessAnalyzerFindings(findings) => {
+                let Some(account_id) = self.account_id.read().await.clone() else {
+                    bail!("No account ID")
+                };
+                return self.do_access_analyzer_findings_task(findings, &account_id).await;
+            }
         }
 
         Ok(res)
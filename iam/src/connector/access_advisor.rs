@@ -0,0 +1,132 @@
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::bail;
+use autoschematic_core::{connector::TaskExecResponse, util::RON};
+use aws_sdk_iam::types::{JobStatusType, ServiceLastAccessed};
+use serde::{Deserialize, Serialize};
+
+use crate::task::AccessAdvisor;
+
+use super::IamConnector;
+
+#[derive(Serialize, Deserialize)]
+enum AccessAdvisorState {
+    /// (principal ARN, job ID) for each GenerateServiceLastAccessedDetails job.
+    Generating { jobs: Vec<(String, String)> },
+}
+
+impl IamConnector {
+    /// Access Advisor reports are generated asynchronously: the first run starts a job for each
+    /// principal, and later runs poll until every job has completed.
+    pub async fn do_access_advisor_task(
+        &self,
+        client: &aws_sdk_iam::Client,
+        access_advisor: AccessAdvisor,
+        state: Option<Vec<u8>>,
+    ) -> anyhow::Result<TaskExecResponse> {
+        let now_secs = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        let Some(state) = state else {
+            let mut jobs = Vec::new();
+            for principal in access_advisor.principals {
+                let job = client.generate_service_last_accessed_details().arn(&principal).send().await?;
+                let Some(job_id) = job.job_id else {
+                    bail!("GenerateServiceLastAccessedDetails returned no job ID for {}", principal);
+                };
+                jobs.push((principal, job_id));
+            }
+
+            let job_count = jobs.len();
+            return Ok(TaskExecResponse {
+                next_state: Some(RON.to_string(&AccessAdvisorState::Generating { jobs })?.into_bytes()),
+                friendly_message: Some(format!("Started {} Access Advisor jobs", job_count)),
+                delay_until: Some(now_secs + 5),
+                ..Default::default()
+            });
+        };
+
+        let AccessAdvisorState::Generating { jobs } = RON.from_bytes(&state)?;
+
+        let mut outputs = HashMap::new();
+        let mut report = Vec::new();
+        for (principal, job_id) in &jobs {
+            let Some(services) = get_service_last_accessed(client, job_id).await? else {
+                return Ok(TaskExecResponse {
+                    next_state: Some(RON.to_string(&AccessAdvisorState::Generating { jobs: jobs.clone() })?.into_bytes()),
+                    friendly_message: Some(String::from("Waiting for Access Advisor jobs to complete")),
+                    delay_until: Some(now_secs + 5),
+                    ..Default::default()
+                });
+            };
+
+            let unused: Vec<&str> = services
+                .iter()
+                .filter(|s| s.last_authenticated.is_none())
+                .map(|s| s.service_namespace.as_str())
+                .collect();
+
+            let mut used: Vec<String> = services
+                .iter()
+                .filter_map(|s| {
+                    s.last_authenticated
+                        .map(|last_authenticated| format!("{} ({})", s.service_namespace, last_authenticated))
+                })
+                .collect();
+            used.sort();
+
+            report.push(format!(
+                "{}:\n  used: {}\n  never used: {}",
+                principal,
+                if used.is_empty() { String::from("-") } else { used.join(", ") },
+                if unused.is_empty() { String::from("-") } else { unused.join(", ") }
+            ));
+
+            outputs.insert(format!("{}/unused_services", principal), Some(unused.join(",")));
+        }
+
+        Ok(TaskExecResponse {
+            outputs: Some(outputs),
+            friendly_message: Some(report.join("\n")),
+            ..Default::default()
+        })
+    }
+}
+
+/// Returns None while the job is still in progress.
+async fn get_service_last_accessed(
+    client: &aws_sdk_iam::Client,
+    job_id: &str,
+) -> anyhow::Result<Option<Vec<ServiceLastAccessed>>> {
+    let mut services = Vec::new();
+    let mut marker = None;
+
+    loop {
+        let resp = client
+            .get_service_last_accessed_details()
+            .job_id(job_id)
+            .set_marker(marker)
+            .send()
+            .await?;
+
+        match resp.job_status {
+            JobStatusType::Completed => {}
+            JobStatusType::Failed => {
+                let message = resp.error.map(|e| e.message).unwrap_or_default();
+                bail!("Access Advisor job {} failed: {}", job_id, message);
+            }
+            _ => return Ok(None),
+        }
+
+        services.extend(resp.services_last_accessed);
+
+        if !resp.is_truncated {
+            break;
+        }
+        marker = resp.marker;
+    }
+
+    Ok(Some(services))
+}
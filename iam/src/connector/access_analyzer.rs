@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use aws_config::{BehaviorVersion, meta::region::RegionProviderChain};
+use aws_sdk_accessanalyzer::{config::Region, error::ProvideErrorMetadata};
+use autoschematic_connector_aws_core::audited_client;
+
+use super::IamConnector;
+
+impl IamConnector {
+    /// Unlike IAM itself, Access Analyzer is regional.
+    pub async fn get_or_init_analyzer_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_accessanalyzer::Client>> {
+        if let Some(client) = self.analyzer_client_cache.read().await.get(region_s) {
+            return Ok(client.clone());
+        }
+
+        let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+        let config = aws_config::defaults(BehaviorVersion::latest()).region(region).load().await;
+        let client = Arc::new(audited_client!(aws_sdk_accessanalyzer, &config));
+
+        self.analyzer_client_cache
+            .write()
+            .await
+            .insert(region_s.to_string(), client.clone());
+
+        Ok(client)
+    }
+}
+
+/// Returns the analyzer's ARN, or None if it doesn't exist. The analyzers themselves are managed
+/// by the accessanalyzer connector.
+pub async fn get_access_analyzer_arn(client: &aws_sdk_accessanalyzer::Client, name: &str) -> anyhow::Result<Option<String>> {
+    match client.get_analyzer().analyzer_name(name).send().await {
+        Ok(output) => Ok(output.analyzer.map(|analyzer| analyzer.arn)),
+        Err(e) if e.code() == Some("ResourceNotFoundException") => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
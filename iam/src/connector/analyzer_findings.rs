@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{ResourceAddress, TaskExecResponse},
+    diag::{Diagnostic, DiagnosticPosition, DiagnosticResponse, DiagnosticSeverity, DiagnosticSpan},
+};
+use aws_sdk_accessanalyzer::types::{Criterion, FindingDetails};
+use walkdir::WalkDir;
+
+use crate::{
+    addr::IamResourceAddress,
+    task::{AccessAnalyzerFindings, IamTaskAddress},
+    util::wildcard_match,
+};
+
+use super::{IamConnector, access_analyzer::get_access_analyzer_arn};
+
+fn findings_output_key(role_arn: &str) -> String {
+    format!("{}/access_analyzer_findings", role_arn)
+}
+
+impl IamConnector {
+    /// Findings are recorded as outputs of the task, one per role, so that `diag` can show them
+    /// on the role's file without calling Access Analyzer on every keystroke.
+    pub async fn do_access_analyzer_findings_task(
+        &self,
+        task: AccessAnalyzerFindings,
+        account_id: &str,
+    ) -> anyhow::Result<TaskExecResponse> {
+        let client = self.get_or_init_analyzer_client(&task.region).await?;
+
+        let mut analyzer_arns = Vec::new();
+        for analyzer_name in &task.analyzers {
+            let Some(arn) = get_access_analyzer_arn(&client, analyzer_name).await? else {
+                bail!("Access Analyzer analyzer {} not found in region {}", analyzer_name, task.region);
+            };
+            analyzer_arns.push(arn);
+        }
+
+        let mut outputs = HashMap::new();
+        let mut report = Vec::new();
+        for (path, name) in self.managed_roles() {
+            let full_name = format!("{}{}", path, name);
+            if !task.roles.is_empty() && !task.roles.iter().any(|pattern| wildcard_match(pattern, &full_name)) {
+                continue;
+            }
+
+            let role_arn = format!("arn:aws:iam::{account_id}:role{path}{name}");
+
+            let mut findings = Vec::new();
+            for analyzer_arn in &analyzer_arns {
+                findings.extend(list_active_findings(&client, analyzer_arn, &role_arn).await?);
+            }
+
+            // Resolved findings clear the role's diagnostics on the next run
+            if findings.is_empty() {
+                outputs.insert(findings_output_key(&role_arn), None);
+            } else {
+                report.push(format!("{}:\n  {}", full_name, findings.join("\n  ")));
+                outputs.insert(findings_output_key(&role_arn), Some(findings.join("\n")));
+            }
+        }
+
+        let friendly_message = if report.is_empty() {
+            String::from("No active Access Analyzer findings for managed roles")
+        } else {
+            report.join("\n")
+        };
+
+        Ok(TaskExecResponse {
+            outputs: Some(outputs),
+            friendly_message: Some(friendly_message),
+            ..Default::default()
+        })
+    }
+
+    /// The (path, name) of each role with a file in this repo.
+    fn managed_roles(&self) -> Vec<(String, String)> {
+        let mut roles = Vec::new();
+
+        for entry in WalkDir::new(self.prefix.join("aws/iam/roles")).into_iter().flatten() {
+            let Ok(rel_path) = entry.path().strip_prefix(&self.prefix) else {
+                continue;
+            };
+
+            if let Ok(IamResourceAddress::Role { path, name }) = IamResourceAddress::from_path(rel_path) {
+                roles.push((path, name));
+            }
+        }
+
+        roles
+    }
+
+    /// Warnings for the findings that any access-analyzer-findings task last recorded for this role.
+    pub async fn access_analyzer_diagnostics(&self, path: &str, name: &str) -> anyhow::Result<Option<DiagnosticResponse>> {
+        let Some(account_id) = self.account_id.read().await.clone() else {
+            return Ok(None);
+        };
+        let role_arn = format!("arn:aws:iam::{account_id}:role{path}{name}");
+
+        let mut diagnostics = Vec::new();
+        for entry in WalkDir::new(self.prefix.join("aws/iam/tasks/access-analyzer-findings"))
+            .max_depth(1)
+            .into_iter()
+            .flatten()
+        {
+            let Ok(rel_path) = entry.path().strip_prefix(&self.prefix) else {
+                continue;
+            };
+            let Ok(task_addr) = IamTaskAddress::from_path(rel_path) else {
+                continue;
+            };
+
+            let Some(findings) = task_addr.get_output(&self.prefix, &findings_output_key(&role_arn))? else {
+                continue;
+            };
+
+            for finding in findings.lines() {
+                diagnostics.push(Diagnostic {
+                    severity: DiagnosticSeverity::WARNING as u8,
+                    span:     DiagnosticSpan {
+                        start: DiagnosticPosition { line: 1, col: 1 },
+                        end:   DiagnosticPosition { line: 1, col: 1 },
+                    },
+                    message:  format!("IAM Access Analyzer: {}", finding),
+                });
+            }
+        }
+
+        if diagnostics.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(DiagnosticResponse { diagnostics }))
+        }
+    }
+}
+
+/// One line per active finding on the role, e.g. `UnusedPermission: s3 (never used), sqs (last used 2025-01-02T03:04:05Z)`.
+async fn list_active_findings(
+    client: &aws_sdk_accessanalyzer::Client,
+    analyzer_arn: &str,
+    role_arn: &str,
+) -> anyhow::Result<Vec<String>> {
+    let mut findings = Vec::new();
+
+    let mut pages = client
+        .list_findings_v2()
+        .analyzer_arn(analyzer_arn)
+        .filter("resource", Criterion::builder().eq(role_arn).build())
+        .filter("status", Criterion::builder().eq("ACTIVE").build())
+        .into_paginator()
+        .send();
+
+    while let Some(page) = pages.next().await {
+        for summary in page?.findings {
+            let finding_type = summary
+                .finding_type
+                .as_ref()
+                .map(|t| t.as_str().to_string())
+                .unwrap_or_else(|| String::from("Finding"));
+
+            let finding = client
+                .get_finding_v2()
+                .analyzer_arn(analyzer_arn)
+                .id(&summary.id)
+                .send()
+                .await?;
+
+            let details: Vec<String> = finding.finding_details.iter().filter_map(describe_finding_details).collect();
+
+            if details.is_empty() {
+                findings.push(format!("{} ({})", finding_type, summary.id));
+            } else {
+                findings.push(format!("{}: {}", finding_type, details.join(", ")));
+            }
+        }
+    }
+
+    Ok(findings)
+}
+
+fn describe_finding_details(details: &FindingDetails) -> Option<String> {
+    match details {
+        FindingDetails::ExternalAccessDetails(external) => {
+            if external.is_public == Some(true) {
+                return Some(String::from("assumable by anyone"));
+            }
+            let mut principals: Vec<&str> = external
+                .principal
+                .iter()
+                .flat_map(|principal| principal.values())
+                .map(|p| p.as_str())
+                .collect();
+            principals.sort();
+            Some(format!("assumable by {}", principals.join(" ")))
+        }
+        FindingDetails::UnusedPermissionDetails(unused) => Some(match &unused.last_accessed {
+            Some(last_accessed) => format!("{} (last used {})", unused.service_namespace, last_accessed),
+            None => format!("{} (never used)", unused.service_namespace),
+        }),
+        FindingDetails::UnusedIamRoleDetails(unused) => Some(match &unused.last_accessed {
+            Some(last_accessed) => format!("role last used {}", last_accessed),
+            None => String::from("role never used"),
+        }),
+        _ => None,
+    }
+}
@@ -65,6 +65,12 @@ impl IamConnector {
 
                         let attached_policies = list_attached_role_policies(&client, &name).await?;
 
+                        // RoleLastUsed is only tracked for the last 400 days, and only returned by GetRole.
+                        let last_used = role
+                            .role_last_used
+                            .clone()
+                            .and_then(|last_used| last_used.last_used_date.map(|date| (date, last_used.region)));
+
                         let iam_role = if let Some(assume_role_policy) = role.assume_role_policy_document {
                             let json_s = urlencoding::decode(&assume_role_policy)?;
                             let val: serde_json::Value = serde_json::from_str(&json_s)?;
@@ -84,7 +90,16 @@ impl IamConnector {
                             }
                         };
 
-                        get_resource_response!(IamResource::Role(iam_role))
+                        match last_used {
+                            Some((last_used_date, last_used_region)) => get_resource_response!(
+                                IamResource::Role(iam_role),
+                                [
+                                    (String::from("last_used_date"), last_used_date.to_string()),
+                                    (String::from("last_used_region"), last_used_region.unwrap_or_default())
+                                ]
+                            ),
+                            None => get_resource_response!(IamResource::Role(iam_role)),
+                        }
                     }
                     Err(e) => match e.as_service_error() {
                         Some(aws_sdk_iam::operation::get_role::GetRoleError::NoSuchEntityException(_)) => Ok(None),
@@ -13,6 +13,8 @@ impl IamConnector {
             bail!("Account ID not set")
         };
 
+        let externally_managed = self.config.read().await.externally_managed.clone();
+
         let mut results = Vec::<PathBuf>::new();
         let Some(ref client) = *self.client.read().await else {
             bail!("No client")
@@ -23,14 +25,17 @@ impl IamConnector {
 
             while let Some(users) = users.next().await {
                 for user in users?.users {
-                    if parse_arn(&user.arn)?.account_id == account_id {
-                        results.push(
-                            IamResourceAddress::User {
-                                path: user.path,
-                                name: user.user_name,
-                            }
-                            .to_path_buf(),
-                        );
+                    if parse_arn(&user.arn)?.account_id != account_id {
+                        continue;
+                    }
+
+                    let addr = IamResourceAddress::User {
+                        path: user.path,
+                        name: user.user_name,
+                    };
+
+                    if externally_managed.matching(&addr).is_none() {
+                        results.push(addr.to_path_buf());
                     }
                 }
             }
@@ -41,14 +46,17 @@ impl IamConnector {
 
             while let Some(roles) = roles.next().await {
                 for role in roles?.roles {
-                    if parse_arn(&role.arn)?.account_id == account_id {
-                        results.push(
-                            IamResourceAddress::Role {
-                                path: role.path,
-                                name: role.role_name,
-                            }
-                            .to_path_buf(),
-                        );
+                    if parse_arn(&role.arn)?.account_id != account_id {
+                        continue;
+                    }
+
+                    let addr = IamResourceAddress::Role {
+                        path: role.path,
+                        name: role.role_name,
+                    };
+
+                    if externally_managed.matching(&addr).is_none() {
+                        results.push(addr.to_path_buf());
                     }
                 }
             }
@@ -59,14 +67,17 @@ impl IamConnector {
 
             while let Some(groups) = groups.next().await {
                 for group in groups?.groups {
-                    if parse_arn(&group.arn)?.account_id == account_id {
-                        results.push(
-                            IamResourceAddress::Group {
-                                path: group.path,
-                                name: group.group_name,
-                            }
-                            .to_path_buf(),
-                        );
+                    if parse_arn(&group.arn)?.account_id != account_id {
+                        continue;
+                    }
+
+                    let addr = IamResourceAddress::Group {
+                        path: group.path,
+                        name: group.group_name,
+                    };
+
+                    if externally_managed.matching(&addr).is_none() {
+                        results.push(addr.to_path_buf());
                     }
                 }
             }
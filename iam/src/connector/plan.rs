@@ -1,8 +1,10 @@
 use std::{collections::HashSet, path::Path};
 
+use anyhow::bail;
 use crate::{
     addr::IamResourceAddress,
     resource::IamGroup,
+    util::{IamQuotas, check_attached_policy_count, check_policy_size},
     util::{policies_added, policies_removed},
     util::{users_added, users_removed},
 };
@@ -18,6 +20,24 @@ use crate::{op, resource};
 
 use super::IamConnector;
 
+fn check_role(path: &str, name: &str, role: &IamRole, quotas: &IamQuotas) -> anyhow::Result<()> {
+    check_attached_policy_count(
+        &format!("IAM role `{}{}`", path, name),
+        &role.attached_policies,
+        quotas.attached_policies_per_role,
+    )?;
+
+    if let Some(assume_role_policy_document) = &role.assume_role_policy_document {
+        check_policy_size(
+            &format!("AssumeRolePolicy for IAM role `{}{}`", path, name),
+            assume_role_policy_document,
+            quotas.assume_role_policy_size,
+        )?;
+    }
+
+    Ok(())
+}
+
 impl IamConnector {
     pub async fn do_plan(
         &self,
@@ -26,15 +46,22 @@ impl IamConnector {
         desired: Option<String>,
     ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
         let addr = IamResourceAddress::from_path(addr)?;
+        let quotas = self.quotas.read().await.clone();
+        let externally_managed = self.config.read().await.externally_managed.clone();
 
         let mut res = Vec::new();
 
-        match addr {
+        match addr.clone() {
             IamResourceAddress::User { path, name } => {
                 match (current, desired) {
                     (None, None) => {}
                     (None, Some(new_user)) => {
                         let new_user: IamUser = RON.from_str(&new_user)?;
+                        check_attached_policy_count(
+                            &format!("IAM user `{}{}`", path, name),
+                            &new_user.attached_policies,
+                            quotas.attached_policies_per_user,
+                        )?;
 
                         res.push(connector_op!(
                             IamConnectorOp::CreateUser(new_user.clone()),
@@ -57,6 +84,11 @@ impl IamConnector {
                     (Some(old_user), Some(new_user)) => {
                         let old_user: IamUser = RON.from_str(&old_user)?;
                         let new_user: IamUser = RON.from_str(&new_user)?;
+                        check_attached_policy_count(
+                            &format!("IAM user `{}{}`", path, name),
+                            &new_user.attached_policies,
+                            quotas.attached_policies_per_user,
+                        )?;
 
                         if old_user == new_user {
                             // pass
@@ -91,6 +123,7 @@ impl IamConnector {
                     (None, None) => {}
                     (None, Some(new_role)) => {
                         let new_role: IamRole = RON.from_str(&new_role)?;
+                        check_role(&path, &name, &new_role, &quotas)?;
                         res.push(connector_op!(
                             IamConnectorOp::CreateRole(new_role.clone()),
                             format!("Create new IAM role {}{}", path, name)
@@ -113,6 +146,7 @@ impl IamConnector {
                     (Some(old_role), Some(new_role)) => {
                         let old_role: IamRole = RON.from_str(&old_role)?;
                         let new_role: IamRole = RON.from_str(&new_role)?;
+                        check_role(&path, &name, &new_role, &quotas)?;
 
                         // #plan_cover(assume_role_policy_document)
                         if old_role.assume_role_policy_document != new_role.assume_role_policy_document {
@@ -159,6 +193,11 @@ impl IamConnector {
                     (None, None) => {}
                     (None, Some(new_group)) => {
                         let new_group: IamGroup = RON.from_str(&new_group)?;
+                        check_attached_policy_count(
+                            &format!("IAM group `{}{}`", path, name),
+                            &new_group.attached_policies,
+                            quotas.attached_policies_per_group,
+                        )?;
                         res.push(connector_op!(
                             IamConnectorOp::CreateGroup,
                             format!("Create new IAM Group `{}{}`", path, name)
@@ -187,6 +226,11 @@ impl IamConnector {
                     (Some(old_group), Some(new_group)) => {
                         let old_group: IamGroup = RON.from_str(&old_group)?;
                         let new_group: IamGroup = RON.from_str(&new_group)?;
+                        check_attached_policy_count(
+                            &format!("IAM group `{}{}`", path, name),
+                            &new_group.attached_policies,
+                            quotas.attached_policies_per_group,
+                        )?;
 
                         if old_group != new_group {
                             for removed_policy in policies_removed(&old_group.attached_policies, &new_group.attached_policies) {
@@ -226,6 +270,11 @@ impl IamConnector {
                 (None, None) => {}
                 (None, Some(new_policy)) => {
                     let new_policy: IamPolicy = RON.from_str(&new_policy)?;
+                    check_policy_size(
+                        &format!("Policy document for IAM policy `{}`", name),
+                        &new_policy.policy_document,
+                        quotas.policy_size,
+                    )?;
                     res.push(connector_op!(
                         IamConnectorOp::CreatePolicy(new_policy),
                         format!("Create new IAM policy {}", name)
@@ -238,6 +287,11 @@ impl IamConnector {
                 (Some(old_policy), Some(new_policy)) => {
                     let old_policy: IamPolicy = RON.from_str(&old_policy)?;
                     let new_policy: IamPolicy = RON.from_str(&new_policy)?;
+                    check_policy_size(
+                        &format!("Policy document for IAM policy `{}`", name),
+                        &new_policy.policy_document,
+                        quotas.policy_size,
+                    )?;
 
                     if old_policy.policy_document != new_policy.policy_document {
                         let diff =
@@ -259,6 +313,19 @@ impl IamConnector {
             },
         }
 
+        // Changing a principal provisioned elsewhere, e.g. by IAM Identity Center, would only be reverted
+        // the next time it syncs, or worse, break its provisioning
+        if !res.is_empty()
+            && let Some(pattern) = externally_managed.matching(&addr)
+        {
+            bail!(
+                "{} is externally managed (matches `{}` in aws/iam/config.ron): refusing to change it. \
+                 Make the change through whatever provisions it, or remove the pattern to manage it here.",
+                addr.to_path_buf().display(),
+                pattern
+            );
+        }
+
         Ok(res)
     }
 }
@@ -0,0 +1,87 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use autoschematic_connector_aws_core::{
+    audited_client,
+    replay::{unrecorded, with_cassette},
+};
+use autoschematic_core::{
+    connector::{ConnectorOp, Resource, ResourceAddress},
+    util::RON,
+};
+use aws_config::BehaviorVersion;
+use aws_sdk_iam::config::Region;
+use tokio::sync::RwLock;
+
+use crate::{
+    addr::IamResourceAddress,
+    op::IamConnectorOp,
+    resource::{IamResource, IamRole},
+};
+
+use super::IamConnector;
+
+fn cassette(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/cassettes").join(name)
+}
+
+/// A connector set up the way `init` leaves it, without the sts:GetCallerIdentity call.
+/// Must be built inside the cassette's scope so that the client uses it.
+async fn connector() -> IamConnector {
+    let config = aws_config::defaults(BehaviorVersion::latest())
+        .region(Region::new("global"))
+        .load()
+        .await;
+
+    IamConnector {
+        prefix: std::env::temp_dir().join("autoschematic-connector-aws-iam-test"),
+        client: RwLock::new(Some(Arc::new(audited_client!(aws_sdk_iam, &config)))),
+        account_id: RwLock::new(Some(String::from("123456789012"))),
+        ..Default::default()
+    }
+}
+
+/// Records against a role named `deploy` at the path `/`, with a trust policy and ReadOnlyAccess
+/// attached, and without AmazonS3ReadOnlyAccess.
+#[tokio::test]
+async fn attach_role_policy() -> anyhow::Result<()> {
+    let cassette = cassette("attach_role_policy.json");
+    if unrecorded(&cassette)? {
+        return Ok(());
+    }
+
+    with_cassette(&cassette, async {
+        let connector = connector().await;
+        let addr = IamResourceAddress::Role {
+            path: String::from("/"),
+            name: String::from("deploy"),
+        }
+        .to_path_buf();
+
+        let current = connector.do_get(&addr).await?.expect("role exists");
+        let mut role: IamRole = RON.from_str(str::from_utf8(&current.resource_definition)?)?;
+        assert!(role.attached_policies.contains("arn:aws:iam::aws:policy/ReadOnlyAccess"));
+        assert!(role.assume_role_policy_document.is_some());
+
+        role.attached_policies
+            .insert(String::from("arn:aws:iam::aws:policy/AmazonS3ReadOnlyAccess"));
+        let desired = IamResource::Role(role).to_bytes()?;
+
+        let current = String::from_utf8(current.resource_definition)?;
+        let ops = connector
+            .do_plan(&addr, Some(current), Some(String::from_utf8(desired)?))
+            .await?;
+        assert_eq!(ops.len(), 1);
+        assert!(matches!(
+            IamConnectorOp::from_str(&ops[0].op_definition)?,
+            IamConnectorOp::AttachRolePolicy(policy_arn) if policy_arn == "arn:aws:iam::aws:policy/AmazonS3ReadOnlyAccess"
+        ));
+
+        connector.do_op_exec(&addr, &ops[0].op_definition).await?;
+
+        anyhow::Ok(())
+    })
+    .await?
+}
@@ -1,5 +1,6 @@
 pub mod connector;
 pub mod addr;
+pub mod config;
 pub mod op;
 pub mod resource;
 pub mod tags;
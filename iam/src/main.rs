@@ -1,8 +1,12 @@
+use addr::IamResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
 use autoschematic_core::tarpc_bridge::tarpc_connector_main;
 use connector::IamConnector;
+use task::IamTaskAddress;
 
 pub mod connector;
 pub mod addr;
+pub mod config;
 pub mod op;
 pub mod resource;
 pub mod tags;
@@ -11,6 +15,13 @@ pub mod util;
 
 #[tokio::main]
 pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let mut patterns = IamResourceAddress::checked_address_patterns()?;
+        patterns.extend(IamTaskAddress::checked_address_patterns()?);
+        print!("{}", render_address_docs("aws/iam", &patterns));
+        return Ok(());
+    }
+
     tarpc_connector_main::<IamConnector>().await?;
     Ok(())
 }
\ No newline at end of file
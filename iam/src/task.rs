@@ -2,6 +2,7 @@ use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
 use autoschematic_core::connector::{Resource, ResourceAddress};
 use serde::{Deserialize, Serialize};
 
@@ -10,12 +11,18 @@ use autoschematic_core::util::{PrettyConfig, RON};
 #[derive(Debug, Clone)]
 pub enum IamTaskAddress {
     RotateCredential { name: String },
+    AccessAdvisor { name: String },
+    AccessAnalyzerFindings { name: String },
 }
 
 impl ResourceAddress for IamTaskAddress {
     fn to_path_buf(&self) -> PathBuf {
         match &self {
             IamTaskAddress::RotateCredential { name } => PathBuf::from(format!("aws/iam/tasks/rotate-credential/{name}.ron")),
+            IamTaskAddress::AccessAdvisor { name } => PathBuf::from(format!("aws/iam/tasks/access-advisor/{name}.ron")),
+            IamTaskAddress::AccessAnalyzerFindings { name } => {
+                PathBuf::from(format!("aws/iam/tasks/access-analyzer-findings/{name}.ron"))
+            }
         }
     }
 
@@ -34,11 +41,41 @@ impl ResourceAddress for IamTaskAddress {
                     name: name.strip_suffix(".ron").context("File name must end with .ron")?.to_string(),
                 })
             }
+            ["aws", "iam", "tasks", "access-advisor", name] if name.ends_with(".ron") => Ok(IamTaskAddress::AccessAdvisor {
+                name: name.strip_suffix(".ron").context("File name must end with .ron")?.to_string(),
+            }),
+            ["aws", "iam", "tasks", "access-analyzer-findings", name] if name.ends_with(".ron") => {
+                Ok(IamTaskAddress::AccessAnalyzerFindings {
+                    name: name.strip_suffix(".ron").context("File name must end with .ron")?.to_string(),
+                })
+            }
             _ => Err(anyhow::anyhow!("Invalid IAM task address: {}", path.display())),
         }
     }
 }
 
+impl DescribeAddresses for IamTaskAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/iam/tasks/rotate-credential/<name>.ron",
+                description: "Task: rotate IAM user access keys",
+                example:     "aws/iam/tasks/rotate-credential/ci.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/iam/tasks/access-advisor/<name>.ron",
+                description: "Task: report unused permissions from IAM access advisor",
+                example:     "aws/iam/tasks/access-advisor/quarterly.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/iam/tasks/access-analyzer-findings/<name>.ron",
+                description: "Task: report IAM Access Analyzer findings for managed roles, shown as diagnostics on their files",
+                example:     "aws/iam/tasks/access-analyzer-findings/us-east-1.ron",
+            },
+        ]
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct Credential {
@@ -53,8 +90,34 @@ pub struct RotateCredential {
     pub credentials: Vec<Credential>,
 }
 
+/// Fetches Access Advisor service-last-accessed data for each principal
+/// (user, group, role or policy ARN) and reports the services it can reach but has never used.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AccessAdvisor {
+    pub principals: Vec<String>,
+}
+
+/// Fetches the active IAM Access Analyzer findings for the roles managed in this repo, such as
+/// trust policies that let other accounts in or permissions that go unused. Each role's findings
+/// are then shown as diagnostics on its file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AccessAnalyzerFindings {
+    /// The region of the analyzers.
+    pub region: String,
+    /// The analyzers to read findings from, e.g. one external access and one unused access analyzer. These are
+    /// managed by the accessanalyzer connector, at aws/accessanalyzer/<region>/analyzers/<name>.ron.
+    pub analyzers: Vec<String>,
+    /// Only roles whose path and name (e.g. `/service-role/*`) match one of these patterns. All managed roles if empty.
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
 pub enum IamTask {
     RotateCredential(RotateCredential),
+    AccessAdvisor(AccessAdvisor),
+    AccessAnalyzerFindings(AccessAnalyzerFindings),
 }
 
 impl Resource for IamTask {
@@ -65,6 +128,14 @@ impl Resource for IamTask {
                 Ok(s) => Ok(s.into()),
                 Err(e) => Err(e.into()),
             },
+            IamTask::AccessAdvisor(access_advisor) => match RON.to_string_pretty(&access_advisor, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
+            IamTask::AccessAnalyzerFindings(findings) => match RON.to_string_pretty(&findings, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
         }
     }
 
@@ -78,6 +149,8 @@ impl Resource for IamTask {
         // IamResourceAddress::User { path, name } => Ok(IamResource::User(RON.from_str(s)?)),
         match addr {
             IamTaskAddress::RotateCredential { .. } => Ok(IamTask::RotateCredential(RON.from_str(s)?)),
+            IamTaskAddress::AccessAdvisor { .. } => Ok(IamTask::AccessAdvisor(RON.from_str(s)?)),
+            IamTaskAddress::AccessAnalyzerFindings { .. } => Ok(IamTask::AccessAnalyzerFindings(RON.from_str(s)?)),
         }
     }
 }
@@ -1,4 +1,7 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Context, bail};
+use aws_sdk_iam::types::SummaryKeyType;
 
 pub async fn list_attached_user_policies(
     client: &aws_sdk_iam::Client,
@@ -147,3 +150,117 @@ pub fn users_removed<'a>(current: &'a HashSet<String>, desired: &'a HashSet<Stri
 pub fn users_added<'a>(current: &'a HashSet<String>, desired: &'a HashSet<String>) -> Vec<&'a String> {
     desired.difference(current).collect()
 }
+
+/// The account's IAM quotas that plans are checked against, so that an oversized policy or
+/// one attachment too many is reported at plan time instead of as LimitExceeded during apply.
+#[derive(Debug, Clone)]
+pub struct IamQuotas {
+    pub policy_size: usize,
+    pub assume_role_policy_size: usize,
+    pub attached_policies_per_user: usize,
+    pub attached_policies_per_role: usize,
+    pub attached_policies_per_group: usize,
+}
+
+impl Default for IamQuotas {
+    /// The quotas of a new account. Only the attachment and trust policy quotas can be raised.
+    fn default() -> Self {
+        Self {
+            policy_size: 6144,
+            assume_role_policy_size: 2048,
+            attached_policies_per_user: 10,
+            attached_policies_per_role: 10,
+            attached_policies_per_group: 10,
+        }
+    }
+}
+
+impl IamQuotas {
+    /// Reads the account's actual quotas from GetAccountSummary, falling back to the defaults for any that are missing.
+    pub async fn load(client: &aws_sdk_iam::Client) -> anyhow::Result<Self> {
+        let resp = client.get_account_summary().send().await?;
+        let summary: HashMap<SummaryKeyType, i32> = resp.summary_map.unwrap_or_default();
+
+        let defaults = Self::default();
+        let quota = |key: SummaryKeyType, default: usize| -> usize {
+            summary.get(&key).map(|v| *v as usize).unwrap_or(default)
+        };
+
+        Ok(Self {
+            policy_size: quota(SummaryKeyType::PolicySizeQuota, defaults.policy_size),
+            assume_role_policy_size: quota(SummaryKeyType::AssumeRolePolicySizeQuota, defaults.assume_role_policy_size),
+            attached_policies_per_user: quota(
+                SummaryKeyType::AttachedPoliciesPerUserQuota,
+                defaults.attached_policies_per_user,
+            ),
+            attached_policies_per_role: quota(
+                SummaryKeyType::AttachedPoliciesPerRoleQuota,
+                defaults.attached_policies_per_role,
+            ),
+            attached_policies_per_group: quota(
+                SummaryKeyType::AttachedPoliciesPerGroupQuota,
+                defaults.attached_policies_per_group,
+            ),
+        })
+    }
+}
+
+/// The size IAM counts a policy document as: its JSON form, not counting whitespace.
+pub fn policy_size(policy_document: &ron::Value) -> anyhow::Result<usize> {
+    let json = serde_json::to_string(policy_document).context("Failed to serialize policy document as JSON")?;
+    Ok(json.chars().filter(|c| !c.is_whitespace()).count())
+}
+
+/// Fails if a policy document is larger than `quota` characters.
+/// `description` names the document in the error, e.g. "Policy document for IAM policy `/foo`".
+pub fn check_policy_size(description: &str, policy_document: &ron::Value, quota: usize) -> anyhow::Result<()> {
+    let size = policy_size(policy_document)?;
+    if size > quota {
+        bail!(
+            "{} is {} characters (not counting whitespace), over the IAM quota of {}. \
+             Split it into several policies or tighten its statements, e.g. with wildcards.",
+            description,
+            size,
+            quota
+        );
+    }
+    Ok(())
+}
+
+/// Fails if more managed policies are attached to an IAM user, role or group than its quota allows.
+/// `principal` names the principal in the error, e.g. "IAM role `/foo`".
+pub fn check_attached_policy_count(principal: &str, attached_policies: &HashSet<String>, quota: usize) -> anyhow::Result<()> {
+    if attached_policies.len() > quota {
+        bail!(
+            "{} has {} attached managed policies, over the IAM quota of {}. \
+             Combine some of them into one policy, or request a quota increase through Service Quotas.",
+            principal,
+            attached_policies.len(),
+            quota
+        );
+    }
+    Ok(())
+}
+
+/// Matches `s` against a pattern where `*` stands for any run of characters, including none.
+pub fn wildcard_match(pattern: &str, s: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+
+    let Some(mut rest) = s.strip_prefix(parts[0]) else {
+        return false;
+    };
+
+    // Without a `*`, the pattern has to match exactly
+    let Some((last, middle)) = parts[1..].split_last() else {
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.ends_with(last)
+}
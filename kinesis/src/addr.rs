@@ -0,0 +1,75 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum KinesisResourceAddress {
+    Stream {
+        region: String,
+        name:   String,
+    },
+    /// An enhanced fan-out consumer registered with a stream.
+    Consumer {
+        region:        String,
+        stream_name:   String,
+        consumer_name: String,
+    },
+}
+
+impl ResourceAddress for KinesisResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            KinesisResourceAddress::Stream { region, name } => PathBuf::from(format!("aws/kinesis/{region}/streams/{name}.ron")),
+            KinesisResourceAddress::Consumer {
+                region,
+                stream_name,
+                consumer_name,
+            } => PathBuf::from(format!(
+                "aws/kinesis/{region}/streams/{stream_name}/consumers/{consumer_name}.ron"
+            )),
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "kinesis", region, "streams", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(KinesisResourceAddress::Stream {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "kinesis", region, "streams", stream_name, "consumers", consumer_name]
+                if consumer_name.ends_with(".ron") =>
+            {
+                let consumer_name = consumer_name.strip_suffix(".ron").unwrap().to_string();
+                Ok(KinesisResourceAddress::Consumer {
+                    region: region.to_string(),
+                    stream_name: stream_name.to_string(),
+                    consumer_name,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for KinesisResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/kinesis/<region>/streams/<stream_name>.ron",
+                description: "A Kinesis data stream",
+                example:     "aws/kinesis/us-east-1/streams/clickstream.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/kinesis/<region>/streams/<stream_name>/consumers/<consumer_name>.ron",
+                description: "An enhanced fan-out consumer of a stream",
+                example:     "aws/kinesis/us-east-1/streams/clickstream/consumers/analytics.ron",
+            },
+        ]
+    }
+}
@@ -0,0 +1,127 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_kinesis::types::{EncryptionType, StreamMode, StreamStatus};
+
+use crate::{
+    addr::KinesisResourceAddress,
+    resource::{Consumer, KinesisResource, Stream},
+    tags::Tags,
+    util::stream_arn,
+};
+
+use super::KinesisConnector;
+
+impl KinesisConnector {
+    async fn get_stream_tags(&self, client: &aws_sdk_kinesis::Client, stream_name: &str) -> anyhow::Result<Tags> {
+        let mut tags = Vec::new();
+        let mut exclusive_start_tag_key = None;
+
+        loop {
+            let resp = client
+                .list_tags_for_stream()
+                .stream_name(stream_name)
+                .set_exclusive_start_tag_key(exclusive_start_tag_key)
+                .send()
+                .await?;
+
+            exclusive_start_tag_key = resp.tags.last().map(|tag| tag.key.clone());
+            tags.extend(resp.tags);
+
+            if !resp.has_more_tags || exclusive_start_tag_key.is_none() {
+                break;
+            }
+        }
+
+        Ok(Tags::from(&tags[..]))
+    }
+
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = KinesisResourceAddress::from_path(addr)?;
+
+        let account_id = self.account_id.lock().await.clone();
+
+        match &addr {
+            KinesisResourceAddress::Stream { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let summary = match client.describe_stream_summary().stream_name(name).send().await {
+                    Ok(resp) => match resp.stream_description_summary {
+                        Some(summary) => summary,
+                        None => return Ok(None),
+                    },
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => {
+                        return Ok(None);
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+
+                if summary.stream_status == StreamStatus::Deleting {
+                    return Ok(None);
+                }
+
+                let stream_mode = summary
+                    .stream_mode_details
+                    .as_ref()
+                    .map(|details| details.stream_mode.clone())
+                    .unwrap_or(StreamMode::Provisioned);
+
+                // On-demand streams scale their own shards
+                let shard_count = match stream_mode {
+                    StreamMode::OnDemand => None,
+                    _ => Some(summary.open_shard_count),
+                };
+
+                let kms_key_id = match summary.encryption_type {
+                    Some(EncryptionType::Kms) => summary.key_id.clone(),
+                    _ => None,
+                };
+
+                let stream = Stream {
+                    stream_mode: stream_mode.as_str().to_string(),
+                    shard_count,
+                    retention_period_hours: summary.retention_period_hours,
+                    kms_key_id,
+                    tags: self.get_stream_tags(&client, name).await?,
+                };
+
+                get_resource_response!(
+                    KinesisResource::Stream(stream),
+                    [(String::from("stream_arn"), summary.stream_arn)]
+                )
+            }
+            KinesisResourceAddress::Consumer {
+                region,
+                stream_name,
+                consumer_name,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let consumer = match client
+                    .describe_stream_consumer()
+                    .stream_arn(stream_arn(region, &account_id, stream_name))
+                    .consumer_name(consumer_name)
+                    .send()
+                    .await
+                {
+                    Ok(resp) => match resp.consumer_description {
+                        Some(consumer) => consumer,
+                        None => return Ok(None),
+                    },
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => {
+                        return Ok(None);
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+
+                get_resource_response!(
+                    KinesisResource::Consumer(Consumer {}),
+                    [(String::from("consumer_arn"), consumer.consumer_arn)]
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::{addr::KinesisResourceAddress, util::stream_arn};
+
+use super::KinesisConnector;
+
+impl KinesisConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let account_id = self.account_id.lock().await.clone();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut stream_names = Vec::new();
+            let mut pages = client.list_streams().into_paginator().send();
+            while let Some(page) = pages.next().await {
+                stream_names.extend(page?.stream_names().iter().cloned());
+            }
+
+            for stream_name in stream_names {
+                let mut consumers = client
+                    .list_stream_consumers()
+                    .stream_arn(stream_arn(&region, &account_id, &stream_name))
+                    .into_paginator()
+                    .send();
+                while let Some(page) = consumers.next().await {
+                    for consumer in page?.consumers() {
+                        results.push(
+                            KinesisResourceAddress::Consumer {
+                                region:        region.clone(),
+                                stream_name:   stream_name.clone(),
+                                consumer_name: consumer.consumer_name.clone(),
+                            }
+                            .to_path_buf(),
+                        );
+                    }
+                }
+
+                results.push(
+                    KinesisResourceAddress::Stream {
+                        region: region.clone(),
+                        name:   stream_name,
+                    }
+                    .to_path_buf(),
+                );
+            }
+        }
+
+        Ok(results)
+    }
+}
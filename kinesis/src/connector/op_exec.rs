@@ -0,0 +1,59 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::connector::{ConnectorOp, OpExecResponse, ResourceAddress};
+
+use crate::{addr::KinesisResourceAddress, op::KinesisConnectorOp, op_impl, util::stream_arn};
+
+use super::KinesisConnector;
+
+impl KinesisConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = KinesisResourceAddress::from_path(addr)?;
+        let op = KinesisConnectorOp::from_str(op)?;
+
+        let account_id = self.account_id.lock().await.clone();
+
+        match &addr {
+            KinesisResourceAddress::Stream { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+                let arn = stream_arn(region, &account_id, name);
+
+                match op {
+                    KinesisConnectorOp::CreateStream(stream) => op_impl::create_stream(&client, name, &arn, &stream).await,
+                    KinesisConnectorOp::UpdateStreamMode(stream_mode) => {
+                        op_impl::update_stream_mode(&client, name, &arn, &stream_mode).await
+                    }
+                    KinesisConnectorOp::UpdateShardCount(shard_count) => {
+                        op_impl::update_shard_count(&client, name, shard_count).await
+                    }
+                    KinesisConnectorOp::UpdateRetentionPeriod(retention_period_hours) => {
+                        op_impl::update_retention_period(&client, name, retention_period_hours).await
+                    }
+                    KinesisConnectorOp::UpdateEncryption(kms_key_id) => op_impl::update_encryption(&client, name, kms_key_id).await,
+                    KinesisConnectorOp::UpdateStreamTags(old_tags, new_tags) => {
+                        op_impl::update_stream_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    KinesisConnectorOp::DeleteStream => op_impl::delete_stream(&client, name).await,
+                    _ => bail!("Invalid operation for Kinesis stream resource"),
+                }
+            }
+            KinesisResourceAddress::Consumer {
+                region,
+                stream_name,
+                consumer_name,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+                let arn = stream_arn(region, &account_id, stream_name);
+
+                match op {
+                    KinesisConnectorOp::RegisterConsumer(_consumer) => {
+                        op_impl::register_consumer(&client, &arn, consumer_name).await
+                    }
+                    KinesisConnectorOp::DeregisterConsumer => op_impl::deregister_consumer(&client, &arn, consumer_name).await,
+                    _ => bail!("Invalid operation for Kinesis consumer resource"),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::{
+    resource::{Consumer, Stream},
+    util::reshard_steps,
+};
+
+use super::{KinesisConnector, KinesisConnectorOp, KinesisResourceAddress};
+
+/// Combinations that Kinesis would reject, caught at plan time instead.
+fn check_stream(stream_name: &str, stream: &Stream) -> anyhow::Result<()> {
+    match (stream.stream_mode.as_str(), stream.shard_count) {
+        ("PROVISIONED", Some(shard_count)) if shard_count < 1 => {
+            bail!("Kinesis stream {} needs at least one shard", stream_name)
+        }
+        ("PROVISIONED", None) => bail!("Kinesis stream {} is PROVISIONED, which requires a shard_count", stream_name),
+        ("ON_DEMAND", Some(_)) => bail!(
+            "Kinesis stream {} is ON_DEMAND, which scales its own shards: leave shard_count unset",
+            stream_name
+        ),
+        ("PROVISIONED" | "ON_DEMAND", _) => {}
+        (stream_mode, _) => bail!(
+            "Kinesis stream {} has stream_mode {}: expected PROVISIONED or ON_DEMAND",
+            stream_name,
+            stream_mode
+        ),
+    }
+
+    if !(24..=8760).contains(&stream.retention_period_hours) {
+        bail!(
+            "Kinesis stream {} has a retention period of {} hours: it must be between 24 and 8760",
+            stream_name,
+            stream.retention_period_hours
+        );
+    }
+
+    Ok(())
+}
+
+/// Describes how a shard count change will be carried out, since UpdateShardCount
+/// can at most double or halve the shard count in one call.
+fn describe_reshard(stream_name: &str, old_shard_count: Option<i32>, new_shard_count: i32) -> String {
+    let Some(old_shard_count) = old_shard_count else {
+        return format!("Reshard Kinesis stream `{}` to {} shards", stream_name, new_shard_count);
+    };
+
+    let steps = reshard_steps(old_shard_count, new_shard_count);
+    let mut message = format!(
+        "Reshard Kinesis stream `{}` from {} to {} shards",
+        stream_name, old_shard_count, new_shard_count
+    );
+    if steps.len() > 1 {
+        let steps: Vec<String> = steps.iter().map(|step| step.to_string()).collect();
+        message.push_str(&format!(" in {} steps: {} -> {}", steps.len(), old_shard_count, steps.join(" -> ")));
+    }
+    message
+}
+
+impl KinesisConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = KinesisResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            KinesisResourceAddress::Stream { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_stream)) => {
+                    let new_stream: Stream = RON.from_str(&new_stream)?;
+                    check_stream(name, &new_stream)?;
+                    Ok(vec![connector_op!(
+                        KinesisConnectorOp::CreateStream(new_stream),
+                        format!("Create new Kinesis stream {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_stream), None) => Ok(vec![connector_op!(
+                    KinesisConnectorOp::DeleteStream,
+                    format!("DELETE Kinesis stream {} in region {}, along with all of its records", name, region)
+                )]),
+                (Some(old_stream), Some(new_stream)) => {
+                    let old_stream: Stream = RON.from_str(&old_stream)?;
+                    let new_stream: Stream = RON.from_str(&new_stream)?;
+                    check_stream(name, &new_stream)?;
+
+                    let mut ops = Vec::new();
+
+                    if old_stream.tags != new_stream.tags {
+                        let diff = diff_ron_values(&old_stream.tags, &new_stream.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            KinesisConnectorOp::UpdateStreamTags(old_stream.tags.clone(), new_stream.tags.clone()),
+                            format!("Modify tags for Kinesis stream `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    // The capacity mode goes first: a stream switched to PROVISIONED keeps its
+                    // current shards, and is resharded afterwards if needed
+                    if old_stream.stream_mode != new_stream.stream_mode {
+                        ops.push(connector_op!(
+                            KinesisConnectorOp::UpdateStreamMode(new_stream.stream_mode.clone()),
+                            format!(
+                                "Switch Kinesis stream `{}` from {} to {} capacity mode",
+                                name, old_stream.stream_mode, new_stream.stream_mode
+                            )
+                        ));
+                    }
+
+                    if let Some(new_shard_count) = new_stream.shard_count
+                        && old_stream.shard_count != Some(new_shard_count)
+                    {
+                        ops.push(connector_op!(
+                            KinesisConnectorOp::UpdateShardCount(new_shard_count),
+                            describe_reshard(name, old_stream.shard_count, new_shard_count)
+                        ));
+                    }
+
+                    if old_stream.retention_period_hours != new_stream.retention_period_hours {
+                        ops.push(connector_op!(
+                            KinesisConnectorOp::UpdateRetentionPeriod(new_stream.retention_period_hours),
+                            format!(
+                                "Change retention period for Kinesis stream `{}` from {} to {} hours",
+                                name, old_stream.retention_period_hours, new_stream.retention_period_hours
+                            )
+                        ));
+                    }
+
+                    if old_stream.kms_key_id != new_stream.kms_key_id {
+                        let diff = diff_ron_values(&old_stream.kms_key_id, &new_stream.kms_key_id).unwrap_or_default();
+                        ops.push(connector_op!(
+                            KinesisConnectorOp::UpdateEncryption(new_stream.kms_key_id.clone()),
+                            format!("Modify encryption for Kinesis stream `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            KinesisResourceAddress::Consumer {
+                region,
+                stream_name,
+                consumer_name,
+            } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_consumer)) => {
+                    let new_consumer: Consumer = RON.from_str(&new_consumer)?;
+                    Ok(vec![connector_op!(
+                        KinesisConnectorOp::RegisterConsumer(new_consumer),
+                        format!(
+                            "Register new Kinesis consumer {} with stream {} in region {}",
+                            consumer_name, stream_name, region
+                        )
+                    )])
+                }
+                (Some(_old_consumer), None) => Ok(vec![connector_op!(
+                    KinesisConnectorOp::DeregisterConsumer,
+                    format!(
+                        "DELETE Kinesis consumer {} of stream {} in region {}",
+                        consumer_name, stream_name, region
+                    )
+                )]),
+                // Consumers have no settings to change
+                (Some(_old_consumer), Some(_new_consumer)) => Ok(Vec::new()),
+            },
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use addr::KinesisResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
+use autoschematic_core::tarpc_bridge::tarpc_connector_main;
+use connector::KinesisConnector;
+
+pub mod connector;
+pub mod addr;
+pub mod config;
+pub mod op;
+pub mod op_impl;
+pub mod resource;
+pub mod tags;
+pub mod util;
+
+#[tokio::main]
+pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let patterns = KinesisResourceAddress::checked_address_patterns()?;
+        print!("{}", render_address_docs("aws/kinesis", &patterns));
+        return Ok(());
+    }
+
+    tarpc_connector_main::<KinesisConnector>().await?;
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{Consumer, Stream},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum KinesisConnectorOp {
+    // Stream operations
+    CreateStream(Stream),
+    /// Switches between PROVISIONED and ON_DEMAND. Kinesis allows this twice in 24 hours.
+    UpdateStreamMode(String),
+    /// Reshards the stream to the given number of open shards, in as many steps as needed.
+    UpdateShardCount(i32),
+    UpdateRetentionPeriod(i32),
+    /// Starts encrypting with the given KMS key, or stops encrypting if None.
+    UpdateEncryption(Option<String>),
+    UpdateStreamTags(Tags, Tags),
+    DeleteStream,
+
+    // Consumer operations
+    RegisterConsumer(Consumer),
+    DeregisterConsumer,
+}
+
+impl ConnectorOp for KinesisConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
@@ -0,0 +1,364 @@
+use std::{collections::HashMap, future::Future, time::Duration};
+
+use anyhow::{Context, bail};
+use autoschematic_core::connector::OpExecResponse;
+use aws_sdk_kinesis::types::{EncryptionType, ScalingType, StreamMode, StreamModeDetails};
+
+use crate::{
+    resource::Stream,
+    tags::{Tags, tag_diff},
+    util::reshard_steps,
+};
+
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// Resharding a stream with many shards can take a long time.
+const STATUS_MAX_POLLS: usize = 360;
+/// AddTagsToStream takes at most 10 tags per call.
+const MAX_TAGS_PER_CALL: usize = 10;
+
+/// Kinesis rejects changes to a stream while a previous one is still being applied, and a deleted
+/// stream's name can't be reused until it's gone. Polls `status` until it returns `target`,
+/// or until the resource no longer exists if `target` is None.
+async fn wait_for_status<F, Fut>(description: &str, target: Option<&str>, status: F) -> anyhow::Result<()>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = anyhow::Result<Option<String>>>,
+{
+    for _ in 0..STATUS_MAX_POLLS {
+        if status().await?.as_deref() == target {
+            return Ok(());
+        }
+        tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    }
+
+    match target {
+        Some(target) => bail!("Timed out waiting for {} to become {}", description, target),
+        None => bail!("Timed out waiting for {} to be deleted", description),
+    }
+}
+
+async fn stream_status(client: &aws_sdk_kinesis::Client, stream_name: &str) -> anyhow::Result<Option<String>> {
+    match client.describe_stream_summary().stream_name(stream_name).send().await {
+        Ok(resp) => Ok(resp
+            .stream_description_summary
+            .map(|summary| summary.stream_status.as_str().to_string())),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn wait_for_stream_active(client: &aws_sdk_kinesis::Client, stream_name: &str) -> anyhow::Result<()> {
+    wait_for_status(&format!("Kinesis stream {}", stream_name), Some("ACTIVE"), || {
+        stream_status(client, stream_name)
+    })
+    .await
+}
+
+async fn consumer_status(
+    client: &aws_sdk_kinesis::Client,
+    stream_arn: &str,
+    consumer_name: &str,
+) -> anyhow::Result<Option<String>> {
+    match client
+        .describe_stream_consumer()
+        .stream_arn(stream_arn)
+        .consumer_name(consumer_name)
+        .send()
+        .await
+    {
+        Ok(resp) => Ok(resp
+            .consumer_description
+            .map(|consumer| consumer.consumer_status.as_str().to_string())),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn stream_mode_details(stream_mode: &str) -> anyhow::Result<StreamModeDetails> {
+    Ok(StreamModeDetails::builder()
+        .stream_mode(StreamMode::from(stream_mode))
+        .build()?)
+}
+
+/// Creates a stream, waits for it to become ACTIVE, then applies the settings that
+/// CreateStream doesn't take.
+pub async fn create_stream(
+    client: &aws_sdk_kinesis::Client,
+    stream_name: &str,
+    stream_arn: &str,
+    stream: &Stream,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .create_stream()
+        .stream_name(stream_name)
+        .set_shard_count(stream.shard_count)
+        .stream_mode_details(stream_mode_details(&stream.stream_mode)?)
+        .send()
+        .await?;
+
+    wait_for_stream_active(client, stream_name).await?;
+
+    if stream.retention_period_hours != 24 {
+        update_retention_period(client, stream_name, stream.retention_period_hours).await?;
+    }
+
+    if stream.kms_key_id.is_some() {
+        update_encryption(client, stream_name, stream.kms_key_id.clone()).await?;
+    }
+
+    if stream.tags.len() > 0 {
+        update_stream_tags(client, stream_name, &Tags::default(), &stream.tags).await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("stream_arn"), Some(stream_arn.to_string()))])),
+        friendly_message: Some(format!("Created Kinesis stream {}", stream_name)),
+    })
+}
+
+pub async fn update_stream_mode(
+    client: &aws_sdk_kinesis::Client,
+    stream_name: &str,
+    stream_arn: &str,
+    stream_mode: &str,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .update_stream_mode()
+        .stream_arn(stream_arn)
+        .stream_mode_details(stream_mode_details(stream_mode)?)
+        .send()
+        .await?;
+
+    wait_for_stream_active(client, stream_name).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Switched Kinesis stream {} to {} capacity mode", stream_name, stream_mode)),
+    })
+}
+
+/// Reshards the stream to `shard_count` open shards. UpdateShardCount can at most double or
+/// halve the shard count in one call, so larger changes are made in steps, waiting for the
+/// stream to become ACTIVE after each one.
+pub async fn update_shard_count(
+    client: &aws_sdk_kinesis::Client,
+    stream_name: &str,
+    shard_count: i32,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client.describe_stream_summary().stream_name(stream_name).send().await?;
+    let summary = resp
+        .stream_description_summary
+        .with_context(|| format!("Kinesis stream {} not found", stream_name))?;
+
+    let steps = reshard_steps(summary.open_shard_count, shard_count);
+    for step in &steps {
+        client
+            .update_shard_count()
+            .stream_name(stream_name)
+            .target_shard_count(*step)
+            .scaling_type(ScalingType::UniformScaling)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reshard Kinesis stream {} to {} shards", stream_name, step))?;
+
+        wait_for_stream_active(client, stream_name).await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Resharded Kinesis stream {} from {} to {} shards in {} steps",
+            stream_name,
+            summary.open_shard_count,
+            shard_count,
+            steps.len()
+        )),
+    })
+}
+
+pub async fn update_retention_period(
+    client: &aws_sdk_kinesis::Client,
+    stream_name: &str,
+    retention_period_hours: i32,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client.describe_stream_summary().stream_name(stream_name).send().await?;
+    let summary = resp
+        .stream_description_summary
+        .with_context(|| format!("Kinesis stream {} not found", stream_name))?;
+
+    if retention_period_hours > summary.retention_period_hours {
+        client
+            .increase_stream_retention_period()
+            .stream_name(stream_name)
+            .retention_period_hours(retention_period_hours)
+            .send()
+            .await?;
+    } else if retention_period_hours < summary.retention_period_hours {
+        client
+            .decrease_stream_retention_period()
+            .stream_name(stream_name)
+            .retention_period_hours(retention_period_hours)
+            .send()
+            .await?;
+    }
+
+    wait_for_stream_active(client, stream_name).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Set retention period for Kinesis stream {} to {} hours",
+            stream_name, retention_period_hours
+        )),
+    })
+}
+
+/// StartStreamEncryption also changes the key of a stream that's already encrypted.
+/// StopStreamEncryption needs the key that's currently in use.
+pub async fn update_encryption(
+    client: &aws_sdk_kinesis::Client,
+    stream_name: &str,
+    kms_key_id: Option<String>,
+) -> anyhow::Result<OpExecResponse> {
+    let friendly_message = match kms_key_id {
+        Some(kms_key_id) => {
+            client
+                .start_stream_encryption()
+                .stream_name(stream_name)
+                .encryption_type(EncryptionType::Kms)
+                .key_id(&kms_key_id)
+                .send()
+                .await?;
+            format!("Enabled encryption for Kinesis stream {} with key {}", stream_name, kms_key_id)
+        }
+        None => {
+            let resp = client.describe_stream_summary().stream_name(stream_name).send().await?;
+            let Some(current_key_id) = resp.stream_description_summary.and_then(|summary| summary.key_id) else {
+                return Ok(OpExecResponse {
+                    outputs: None,
+                    friendly_message: Some(format!("Kinesis stream {} is not encrypted", stream_name)),
+                });
+            };
+
+            client
+                .stop_stream_encryption()
+                .stream_name(stream_name)
+                .encryption_type(EncryptionType::Kms)
+                .key_id(current_key_id)
+                .send()
+                .await?;
+            format!("Disabled encryption for Kinesis stream {}", stream_name)
+        }
+    };
+
+    wait_for_stream_active(client, stream_name).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(friendly_message),
+    })
+}
+
+pub async fn update_stream_tags(
+    client: &aws_sdk_kinesis::Client,
+    stream_name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let (remove_keys, add_tags) = tag_diff(old_tags, new_tags);
+
+    if !remove_keys.is_empty() {
+        client
+            .remove_tags_from_stream()
+            .stream_name(stream_name)
+            .set_tag_keys(Some(remove_keys))
+            .send()
+            .await?;
+    }
+
+    let add_tags: Vec<(String, String)> = add_tags.into_iter().collect();
+    for chunk in add_tags.chunks(MAX_TAGS_PER_CALL) {
+        client
+            .add_tags_to_stream()
+            .stream_name(stream_name)
+            .set_tags(Some(chunk.iter().cloned().collect()))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for Kinesis stream {}", stream_name)),
+    })
+}
+
+/// Fails if the stream still has registered consumers; they have to be deregistered first.
+pub async fn delete_stream(client: &aws_sdk_kinesis::Client, stream_name: &str) -> anyhow::Result<OpExecResponse> {
+    client
+        .delete_stream()
+        .stream_name(stream_name)
+        .enforce_consumer_deletion(false)
+        .send()
+        .await?;
+
+    wait_for_status(&format!("Kinesis stream {}", stream_name), None, || {
+        stream_status(client, stream_name)
+    })
+    .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("stream_arn"), None)])),
+        friendly_message: Some(format!("Deleted Kinesis stream {}", stream_name)),
+    })
+}
+
+pub async fn register_consumer(
+    client: &aws_sdk_kinesis::Client,
+    stream_arn: &str,
+    consumer_name: &str,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .register_stream_consumer()
+        .stream_arn(stream_arn)
+        .consumer_name(consumer_name)
+        .send()
+        .await?;
+
+    let consumer_arn = resp
+        .consumer
+        .map(|consumer| consumer.consumer_arn)
+        .context("RegisterStreamConsumer returned no consumer")?;
+
+    wait_for_status(&format!("Kinesis consumer {}", consumer_name), Some("ACTIVE"), || {
+        consumer_status(client, stream_arn, consumer_name)
+    })
+    .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("consumer_arn"), Some(consumer_arn))])),
+        friendly_message: Some(format!("Registered Kinesis consumer {} with stream {}", consumer_name, stream_arn)),
+    })
+}
+
+pub async fn deregister_consumer(
+    client: &aws_sdk_kinesis::Client,
+    stream_arn: &str,
+    consumer_name: &str,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .deregister_stream_consumer()
+        .stream_arn(stream_arn)
+        .consumer_name(consumer_name)
+        .send()
+        .await?;
+
+    wait_for_status(&format!("Kinesis consumer {}", consumer_name), None, || {
+        consumer_status(client, stream_arn, consumer_name)
+    })
+    .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("consumer_arn"), None)])),
+        friendly_message: Some(format!("Deregistered Kinesis consumer {} from stream {}", consumer_name, stream_arn)),
+    })
+}
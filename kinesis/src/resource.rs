@@ -0,0 +1,61 @@
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::KinesisResourceAddress, tags::Tags};
+
+fn default_retention_period_hours() -> i32 {
+    24
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Stream {
+    /// PROVISIONED or ON_DEMAND
+    pub stream_mode: String,
+    /// The number of open shards. Required for PROVISIONED streams.
+    /// ON_DEMAND streams scale their own shards, so it must be left unset for them.
+    pub shard_count: Option<i32>,
+    /// Between 24 and 8760 hours.
+    #[serde(default = "default_retention_period_hours")]
+    pub retention_period_hours: i32,
+    /// The KMS key to encrypt records with, such as `alias/aws/kinesis`. If None, records aren't encrypted.
+    pub kms_key_id: Option<String>,
+    pub tags: Tags,
+}
+
+/// Enhanced fan-out consumers have no settings of their own: registering one under a name
+/// gives it a dedicated read throughput on every shard of the stream.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Consumer {}
+
+pub enum KinesisResource {
+    Stream(Stream),
+    Consumer(Consumer),
+}
+
+impl Resource for KinesisResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            KinesisResource::Stream(stream) => Ok(RON.to_string_pretty(&stream, pretty_config)?.into()),
+            KinesisResource::Consumer(consumer) => Ok(RON.to_string_pretty(&consumer, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = KinesisResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            KinesisResourceAddress::Stream { .. } => Ok(KinesisResource::Stream(RON.from_str(s)?)),
+            KinesisResourceAddress::Consumer { .. } => Ok(KinesisResource::Consumer(RON.from_str(s)?)),
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+
+use aws_sdk_kinesis::types::Tag;
+use serde::{Deserialize, Serialize};
+
+// Kinesis lists tags as Tag structs, but takes them as a plain map
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Tags(HashMap<String, String>);
+
+impl From<&[Tag]> for Tags {
+    fn from(tags: &[Tag]) -> Self {
+        let mut out_map = HashMap::new();
+        for tag in tags {
+            out_map.insert(tag.key.clone(), tag.value.clone().unwrap_or_default());
+        }
+        Tags(out_map)
+    }
+}
+
+impl Tags {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+// From a pair of hashmaps determine the set of tag keys to remove and tags to add respectively
+pub fn tag_diff(old_tags: &Tags, new_tags: &Tags) -> (Vec<String>, HashMap<String, String>) {
+    let mut untag_keys = Vec::new();
+    for k in old_tags.0.keys() {
+        if !new_tags.0.contains_key(k) {
+            untag_keys.push(k.to_string());
+        }
+    }
+
+    let mut new_tagset = HashMap::new();
+    for (key, new_value) in &new_tags.0 {
+        if old_tags.0.get(key) != Some(new_value) {
+            new_tagset.insert(key.clone(), new_value.clone());
+        }
+    }
+
+    (untag_keys, new_tagset)
+}
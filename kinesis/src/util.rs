@@ -0,0 +1,22 @@
+/// The ARN of a stream. Consumers and capacity mode changes are addressed by stream ARN rather than by name.
+pub fn stream_arn(region: &str, account_id: &str, stream_name: &str) -> String {
+    format!("arn:aws:kinesis:{region}:{account_id}:stream/{stream_name}")
+}
+
+/// UpdateShardCount can at most double or halve the number of open shards in one call.
+/// Returns the shard count to request at each step of resharding from `from` to `to`.
+pub fn reshard_steps(from: i32, to: i32) -> Vec<i32> {
+    let mut steps = Vec::new();
+
+    let mut current = from;
+    while current != to && current > 0 {
+        current = if to > current {
+            to.min(current * 2)
+        } else {
+            to.max((current + 1) / 2)
+        };
+        steps.push(current);
+    }
+
+    steps
+}
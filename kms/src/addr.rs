@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
 use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 
 #[derive(Debug, Clone)]
@@ -48,3 +49,42 @@ impl ResourceAddress for KmsResourceAddress {
         }
     }
 }
+
+impl DescribeAddresses for KmsResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/kms/<region>/keys/<key_id>.ron",
+                description: "A KMS key",
+                example:     "aws/kms/us-east-1/keys/1234abcd-12ab-34cd-56ef-1234567890ab.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/kms/<region>/keys/<key_id>/policy.ron",
+                description: "A key's key policy",
+                example:     "aws/kms/us-east-1/keys/1234abcd-12ab-34cd-56ef-1234567890ab/policy.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/kms/<region>/keys/<key_id>/rotation.ron",
+                description: "Whether a key's material is rotated automatically",
+                example:     "aws/kms/us-east-1/keys/1234abcd-12ab-34cd-56ef-1234567890ab/rotation.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/kms/<region>/aliases/<alias_name>.ron",
+                description: "An alias for a key",
+                example:     "aws/kms/us-east-1/aliases/alias-web.ron",
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use autoschematic_connector_aws_core::addr_doc::DescribeAddresses;
+
+    use super::KmsResourceAddress;
+
+    #[test]
+    fn address_examples_parse() {
+        KmsResourceAddress::checked_address_patterns().unwrap();
+    }
+}
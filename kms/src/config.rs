@@ -14,6 +14,8 @@ pub struct KmsConnectorConfig {
     pub timeout_config:  Option<TimeoutConfig>,
     pub sts_region:      String,
     pub enabled_regions: Vec<String>,
+    #[serde(default)]
+    pub max_concurrent_ops: Option<usize>,
 }
 
 impl_aws_config!(KmsConnectorConfig, "aws/kms/config.ron");
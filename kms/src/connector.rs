@@ -9,10 +9,9 @@ use crate::resource::KmsResource;
 use crate::{addr::KmsResourceAddress, tags};
 use crate::{config::KmsConnectorConfig, resource};
 use crate::{op::KmsConnectorOp, op_impl};
-use anyhow::Context;
 use anyhow::bail;
 use async_trait::async_trait;
-use autoschematic_connector_aws_core::config::AwsServiceConfig;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
 use autoschematic_core::{
     connector::{
         Connector, ConnectorOp, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement,
@@ -32,6 +31,8 @@ pub struct KmsConnector {
     account_id: Mutex<String>,
     config: Mutex<KmsConnectorConfig>,
     prefix: PathBuf,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
 }
 
 impl KmsConnector {
@@ -53,7 +54,7 @@ impl KmsConnector {
                 )
                 .load()
                 .await;
-            let client = aws_sdk_kms::Client::new(&config);
+            let client = audited_client!(aws_sdk_kms, &config);
             cache.insert(region_s.to_string(), Arc::new(client));
         };
 
@@ -102,6 +103,62 @@ impl KmsConnector {
 
         Ok(())
     }
+
+    async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = KmsResourceAddress::from_path(addr)?;
+        let op = KmsConnectorOp::from_str(op)?;
+
+        match addr {
+            KmsResourceAddress::Key(region, key_id) => {
+                let client = self.get_or_init_client(&region).await?;
+
+                match op {
+                    KmsConnectorOp::CreateKey(key) => op_impl::create_key(&client, &key).await,
+                    KmsConnectorOp::UpdateKeyDescription(_, description) => {
+                        op_impl::update_key_description(&client, &key_id, &description).await
+                    }
+                    KmsConnectorOp::UpdateKeyTags(old_tags, new_tags) => {
+                        op_impl::update_key_tags(&client, &key_id, &old_tags, &new_tags).await
+                    }
+                    KmsConnectorOp::EnableKey => op_impl::enable_key(&client, &key_id).await,
+                    KmsConnectorOp::DisableKey => op_impl::disable_key(&client, &key_id).await,
+                    KmsConnectorOp::DeleteKey => op_impl::delete_key(&client, &key_id).await,
+                    _ => bail!("Invalid operation for KMS key: {:?}", op),
+                }
+            }
+            KmsResourceAddress::KeyPolicy(region, key_id) => {
+                let client = self.get_or_init_client(&region).await?;
+
+                match op {
+                    KmsConnectorOp::UpdateKeyPolicy(_, new_policy) => {
+                        op_impl::update_key_policy(&client, &key_id, &new_policy).await
+                    }
+                    _ => bail!("Invalid operation for KMS key policy: {:?}", op),
+                }
+            }
+            KmsResourceAddress::Alias(region, alias_name) => {
+                let client = self.get_or_init_client(&region).await?;
+
+                match op {
+                    KmsConnectorOp::CreateAlias(alias) => op_impl::create_alias(&client, &alias_name, &alias).await,
+                    KmsConnectorOp::UpdateAlias(target_key_id) => {
+                        op_impl::update_alias(&client, &alias_name, &target_key_id).await
+                    }
+                    KmsConnectorOp::DeleteAlias => op_impl::delete_alias(&client, &alias_name).await,
+                    _ => bail!("Invalid operation for KMS alias: {:?}", op),
+                }
+            }
+            KmsResourceAddress::KeyRotation(region, key_id) => {
+                let client = self.get_or_init_client(&region).await?;
+
+                match op {
+                    KmsConnectorOp::EnableKeyRotation => op_impl::enable_key_rotation(&client, &key_id).await,
+                    KmsConnectorOp::DisableKeyRotation => op_impl::disable_key_rotation(&client, &key_id).await,
+                    _ => bail!("Invalid operation for KMS key rotation: {:?}", op),
+                }
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -130,6 +187,8 @@ impl Connector for KmsConnector {
         let account_id = vpc_config.verify_sts().await?;
 
         *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(vpc_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
         *self.config.lock().await = vpc_config;
         *self.account_id.lock().await = account_id;
 
@@ -239,13 +298,13 @@ impl Connector for KmsConnector {
                         policy_document: ron_val,
                     };
 
-                    return Ok(Some(GetResourceResponse {
+                    Ok(Some(GetResourceResponse {
                         resource_definition: KmsResource::KeyPolicy(key_policy).to_bytes()?,
                         virt_addr: None,
                         outputs: None,
-                    }));
+                    }))
                 } else {
-                    return Ok(None);
+                    Ok(None)
                 }
             }
             KmsResourceAddress::Alias(region_name, alias_name) => {
@@ -287,7 +346,7 @@ impl Connector for KmsConnector {
                     }
                 }
 
-                return Ok(None);
+                Ok(None)
             }
             KmsResourceAddress::KeyRotation(region_name, key_id) => {
                 let client = self.get_or_init_client(&region_name).await?;
@@ -307,11 +366,11 @@ impl Connector for KmsConnector {
                     enabled: key_rotation_enabled,
                 };
 
-                return Ok(Some(GetResourceResponse {
+                Ok(Some(GetResourceResponse {
                     resource_definition: KmsResource::KeyRotation(key_rotation).to_bytes()?,
                     virt_addr: None,
                     outputs: None,
-                }));
+                }))
             }
         }
     }
@@ -508,59 +567,9 @@ impl Connector for KmsConnector {
     }
 
     async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
-        let addr = KmsResourceAddress::from_path(addr)?;
-        let op = KmsConnectorOp::from_str(op)?;
-
-        match addr {
-            KmsResourceAddress::Key(region, key_id) => {
-                let client = self.get_or_init_client(&region).await?;
-
-                match op {
-                    KmsConnectorOp::CreateKey(key) => op_impl::create_key(&client, &key).await,
-                    KmsConnectorOp::UpdateKeyDescription(_, description) => {
-                        op_impl::update_key_description(&client, &key_id, &description).await
-                    }
-                    KmsConnectorOp::UpdateKeyTags(old_tags, new_tags) => {
-                        op_impl::update_key_tags(&client, &key_id, &old_tags, &new_tags).await
-                    }
-                    KmsConnectorOp::EnableKey => op_impl::enable_key(&client, &key_id).await,
-                    KmsConnectorOp::DisableKey => op_impl::disable_key(&client, &key_id).await,
-                    KmsConnectorOp::DeleteKey => op_impl::delete_key(&client, &key_id).await,
-                    _ => bail!("Invalid operation for KMS key: {:?}", op),
-                }
-            }
-            KmsResourceAddress::KeyPolicy(region, key_id) => {
-                let client = self.get_or_init_client(&region).await?;
-
-                match op {
-                    KmsConnectorOp::UpdateKeyPolicy(_, new_policy) => {
-                        op_impl::update_key_policy(&client, &key_id, &new_policy).await
-                    }
-                    _ => bail!("Invalid operation for KMS key policy: {:?}", op),
-                }
-            }
-            KmsResourceAddress::Alias(region, alias_name) => {
-                let client = self.get_or_init_client(&region).await?;
-
-                match op {
-                    KmsConnectorOp::CreateAlias(alias) => op_impl::create_alias(&client, &alias_name, &alias).await,
-                    KmsConnectorOp::UpdateAlias(target_key_id) => {
-                        op_impl::update_alias(&client, &alias_name, &target_key_id).await
-                    }
-                    KmsConnectorOp::DeleteAlias => op_impl::delete_alias(&client, &alias_name).await,
-                    _ => bail!("Invalid operation for KMS alias: {:?}", op),
-                }
-            }
-            KmsResourceAddress::KeyRotation(region, key_id) => {
-                let client = self.get_or_init_client(&region).await?;
-
-                match op {
-                    KmsConnectorOp::EnableKeyRotation => op_impl::enable_key_rotation(&client, &key_id).await,
-                    KmsConnectorOp::DisableKeyRotation => op_impl::disable_key_rotation(&client, &key_id).await,
-                    _ => bail!("Invalid operation for KMS key rotation: {:?}", op),
-                }
-            }
-        }
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
     }
 
     async fn addr_virt_to_phy(&self, addr: &Path) -> anyhow::Result<VirtToPhyResponse> {
@@ -1,21 +0,0 @@
-use std::path::Path;
-
-use autoschematic_core::{connector::{OpPlanOutput, ResourceAddress}, connector_op, util::RON};
-
-use autoschematic_core::connector::ConnectorOp;
-
-use crate::{addr::KmsResourceAddress, op::KmsConnectorOp, resource::{HostedZone, RecordSet}};
-
-use super::KmsConnector;
-
-
-impl KmsConnector {
-    pub async fn do_plan(
-        &self,
-        addr: &Path,
-        current: Option<String>,
-        desired: Option<String>,
-    ) -> Result<Vec<OpPlanOutput>, anyhow::Error> {
-        todo!();
-    }
-}
\ No newline at end of file
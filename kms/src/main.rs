@@ -1,3 +1,5 @@
+use addr::KmsResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
 use autoschematic_core::tarpc_bridge::tarpc_connector_main;
 use connector::KmsConnector;
 
@@ -13,6 +15,12 @@ pub mod util;
 
 #[tokio::main]
 pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let patterns = KmsResourceAddress::checked_address_patterns()?;
+        print!("{}", render_address_docs("aws/kms", &patterns));
+        return Ok(());
+    }
+
     tarpc_connector_main::<KmsConnector>().await?;
     Ok(())
 }
@@ -33,12 +33,6 @@ impl From<Tags> for Option<Vec<aws_sdk_kms::types::Tag>> {
     }
 }
 
-impl Tags {
-    pub fn len(&self) -> usize {
-        self.0.len()
-    }
-}
-
 // From a pair of hashmaps, determine the set of aws_kms::Tag structs to add and remove
 pub fn kms_tag_diff(old_tags: &Tags, new_tags: &Tags) -> anyhow::Result<(Vec<String>, Vec<aws_sdk_kms::types::Tag>)> {
     let mut remove_keys = Vec::new();
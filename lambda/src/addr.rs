@@ -0,0 +1,143 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum LambdaResourceAddress {
+    Function {
+        region: String,
+        name:   String,
+    },
+    Alias {
+        region:        String,
+        function_name: String,
+        alias_name:    String,
+    },
+    /// Version numbers are assigned by Lambda when a version is published, so `version` is virtual
+    /// until the version exists.
+    Version {
+        region:        String,
+        function_name: String,
+        version:       String,
+    },
+    /// A statement in the function's resource-based policy.
+    Permission {
+        region:        String,
+        function_name: String,
+        statement_id:  String,
+    },
+    /// Mapping UUIDs are assigned by Lambda, so `uuid` is virtual until the mapping exists.
+    EventSourceMapping {
+        region: String,
+        uuid:   String,
+    },
+}
+
+impl ResourceAddress for LambdaResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            LambdaResourceAddress::Function { region, name } => PathBuf::from(format!("aws/lambda/{region}/functions/{name}.ron")),
+            LambdaResourceAddress::Alias {
+                region,
+                function_name,
+                alias_name,
+            } => PathBuf::from(format!("aws/lambda/{region}/functions/{function_name}/aliases/{alias_name}.ron")),
+            LambdaResourceAddress::Version {
+                region,
+                function_name,
+                version,
+            } => PathBuf::from(format!("aws/lambda/{region}/functions/{function_name}/versions/{version}.ron")),
+            LambdaResourceAddress::Permission {
+                region,
+                function_name,
+                statement_id,
+            } => PathBuf::from(format!(
+                "aws/lambda/{region}/functions/{function_name}/permissions/{statement_id}.ron"
+            )),
+            LambdaResourceAddress::EventSourceMapping { region, uuid } => {
+                PathBuf::from(format!("aws/lambda/{region}/event-source-mappings/{uuid}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "lambda", region, "functions", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(LambdaResourceAddress::Function {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "lambda", region, "functions", function_name, "aliases", alias_name] if alias_name.ends_with(".ron") => {
+                let alias_name = alias_name.strip_suffix(".ron").unwrap().to_string();
+                Ok(LambdaResourceAddress::Alias {
+                    region: region.to_string(),
+                    function_name: function_name.to_string(),
+                    alias_name,
+                })
+            }
+            ["aws", "lambda", region, "functions", function_name, "versions", version] if version.ends_with(".ron") => {
+                let version = version.strip_suffix(".ron").unwrap().to_string();
+                Ok(LambdaResourceAddress::Version {
+                    region: region.to_string(),
+                    function_name: function_name.to_string(),
+                    version,
+                })
+            }
+            ["aws", "lambda", region, "functions", function_name, "permissions", statement_id]
+                if statement_id.ends_with(".ron") =>
+            {
+                let statement_id = statement_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(LambdaResourceAddress::Permission {
+                    region: region.to_string(),
+                    function_name: function_name.to_string(),
+                    statement_id,
+                })
+            }
+            ["aws", "lambda", region, "event-source-mappings", uuid] if uuid.ends_with(".ron") => {
+                let uuid = uuid.strip_suffix(".ron").unwrap().to_string();
+                Ok(LambdaResourceAddress::EventSourceMapping {
+                    region: region.to_string(),
+                    uuid,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for LambdaResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/lambda/<region>/functions/<function_name>.ron",
+                description: "A Lambda function",
+                example:     "aws/lambda/us-east-1/functions/thumbnailer.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/lambda/<region>/functions/<function_name>/aliases/<alias_name>.ron",
+                description: "A function alias",
+                example:     "aws/lambda/us-east-1/functions/thumbnailer/aliases/live.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/lambda/<region>/functions/<function_name>/versions/<version>.ron",
+                description: "A published function version",
+                example:     "aws/lambda/us-east-1/functions/thumbnailer/versions/3.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/lambda/<region>/functions/<function_name>/permissions/<statement_id>.ron",
+                description: "A statement in the function's resource-based policy",
+                example:     "aws/lambda/us-east-1/functions/thumbnailer/permissions/s3-invoke.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/lambda/<region>/event-source-mappings/<uuid>.ron",
+                description: "An event source mapping",
+                example:     "aws/lambda/us-east-1/event-source-mappings/a1b2c3d4-5678-90ab-cdef-11111EXAMPLE.ron",
+            },
+        ]
+    }
+}
@@ -0,0 +1,296 @@
+pub use crate::addr::LambdaResourceAddress;
+pub use crate::op::LambdaConnectorOp;
+pub use crate::resource::LambdaResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse, VirtToPhyResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::LambdaConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{Alias, EventSourceMapping, Function, FunctionCode, Permission, Version};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct LambdaConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_lambda::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<LambdaConnectorConfig>,
+    prefix: PathBuf,
+}
+
+impl LambdaConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_lambda::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+            let config = aws_config::defaults(BehaviorVersion::latest())
+                .region(region)
+                .timeout_config(
+                    TimeoutConfig::builder()
+                        .connect_timeout(Duration::from_secs(30))
+                        .operation_timeout(Duration::from_secs(30))
+                        .operation_attempt_timeout(Duration::from_secs(30))
+                        .read_timeout(Duration::from_secs(30))
+                        .build(),
+                )
+                .load()
+                .await;
+            let client = audited_client!(aws_sdk_lambda, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for LambdaConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = LambdaResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(LambdaConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let lambda_config: LambdaConnectorConfig = LambdaConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = lambda_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(lambda_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = lambda_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn addr_virt_to_phy(&self, addr: &Path) -> anyhow::Result<VirtToPhyResponse> {
+        let addr = LambdaResourceAddress::from_path(addr)?;
+
+        match &addr {
+            LambdaResourceAddress::Version {
+                region, function_name, ..
+            } => {
+                let Some(version) = addr.get_output(&self.prefix, "version")? else {
+                    return Ok(VirtToPhyResponse::NotPresent);
+                };
+                Ok(VirtToPhyResponse::Present(
+                    LambdaResourceAddress::Version {
+                        region: region.clone(),
+                        function_name: function_name.clone(),
+                        version,
+                    }
+                    .to_path_buf(),
+                ))
+            }
+            LambdaResourceAddress::EventSourceMapping { region, .. } => {
+                let Some(uuid) = addr.get_output(&self.prefix, "uuid")? else {
+                    return Ok(VirtToPhyResponse::NotPresent);
+                };
+                Ok(VirtToPhyResponse::Present(
+                    LambdaResourceAddress::EventSourceMapping {
+                        region: region.clone(),
+                        uuid,
+                    }
+                    .to_path_buf(),
+                ))
+            }
+            _ => Ok(VirtToPhyResponse::Present(addr.to_path_buf())),
+        }
+    }
+
+    async fn addr_phy_to_virt(&self, addr: &Path) -> anyhow::Result<Option<PathBuf>> {
+        let addr = LambdaResourceAddress::from_path(addr)?;
+
+        match &addr {
+            LambdaResourceAddress::Version { .. } | LambdaResourceAddress::EventSourceMapping { .. } => {
+                if let Some(virt_addr) = addr.phy_to_virt(&self.prefix)? {
+                    return Ok(Some(virt_addr.to_path_buf()));
+                }
+                // Not created from this repository, so there's no virtual address to map back to
+                Ok(Some(addr.to_path_buf()))
+            }
+            _ => Ok(Some(addr.to_path_buf())),
+        }
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // Function skeleton
+        res.push(skeleton!(
+            LambdaResourceAddress::Function {
+                region: String::from("[region]"),
+                name:   String::from("[function_name]"),
+            },
+            LambdaResource::Function(Function {
+                code: FunctionCode::Zip {
+                    path: String::from("[path/to/package.zip]"),
+                },
+                role: String::from("arn:aws:iam::[account_id]:role/[role_name]"),
+                runtime: Some(String::from("python3.12")),
+                handler: Some(String::from("app.handler")),
+                description: None,
+                memory_size: Some(128),
+                timeout: Some(30),
+                architecture: Some(String::from("arm64")), // or "x86_64"
+                environment: HashMap::from([(String::from("[VARIABLE_NAME]"), String::from("[value]"))]),
+                vpc_config: None,
+                layers: Vec::new(),
+                tags: Tags::default(),
+            })
+        ));
+
+        // Alias skeleton
+        res.push(skeleton!(
+            LambdaResourceAddress::Alias {
+                region: String::from("[region]"),
+                function_name: String::from("[function_name]"),
+                alias_name: String::from("[alias_name]"),
+            },
+            LambdaResource::Alias(Alias {
+                function_version: String::from("[version]"),
+                description: None,
+                routing_config: HashMap::new(),
+            })
+        ));
+
+        // Version skeleton
+        res.push(skeleton!(
+            LambdaResourceAddress::Version {
+                region: String::from("[region]"),
+                function_name: String::from("[function_name]"),
+                version: String::from("[version_label]"),
+            },
+            LambdaResource::Version(Version {
+                description: Some(String::from("[description]")),
+            })
+        ));
+
+        // Permission skeleton, allowing an S3 bucket to invoke the function
+        res.push(skeleton!(
+            LambdaResourceAddress::Permission {
+                region: String::from("[region]"),
+                function_name: String::from("[function_name]"),
+                statement_id: String::from("[statement_id]"),
+            },
+            LambdaResource::Permission(Permission {
+                action: String::from("lambda:InvokeFunction"),
+                principal: String::from("s3.amazonaws.com"),
+                source_arn: Some(String::from("arn:aws:s3:::[bucket_name]")),
+                source_account: Some(String::from("[account_id]")),
+                principal_org_id: None,
+            })
+        ));
+
+        // Event source mapping skeleton, consuming from an SQS queue
+        res.push(skeleton!(
+            LambdaResourceAddress::EventSourceMapping {
+                region: String::from("[region]"),
+                uuid:   String::from("[mapping_name]"),
+            },
+            LambdaResource::EventSourceMapping(EventSourceMapping {
+                function_name: String::from("[function_name]"),
+                event_source_arn: String::from("arn:aws:sqs:[region]:[account_id]:[queue_name]"),
+                starting_position: None,
+                batch_size: Some(10),
+                maximum_batching_window_in_seconds: None,
+                filter_patterns: Vec::new(),
+                enabled: true,
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = LambdaResourceAddress::from_path(addr)?;
+
+        match addr {
+            LambdaResourceAddress::Function { .. } => ron_check_eq::<Function>(a, b),
+            LambdaResourceAddress::Alias { .. } => ron_check_eq::<Alias>(a, b),
+            LambdaResourceAddress::Version { .. } => ron_check_eq::<Version>(a, b),
+            LambdaResourceAddress::Permission { .. } => ron_check_eq::<Permission>(a, b),
+            LambdaResourceAddress::EventSourceMapping { .. } => ron_check_eq::<EventSourceMapping>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = LambdaResourceAddress::from_path(addr)?;
+
+        match addr {
+            LambdaResourceAddress::Function { .. } => ron_check_syntax::<Function>(a),
+            LambdaResourceAddress::Alias { .. } => ron_check_syntax::<Alias>(a),
+            LambdaResourceAddress::Version { .. } => ron_check_syntax::<Version>(a),
+            LambdaResourceAddress::Permission { .. } => ron_check_syntax::<Permission>(a),
+            LambdaResourceAddress::EventSourceMapping { .. } => ron_check_syntax::<EventSourceMapping>(a),
+        }
+    }
+}
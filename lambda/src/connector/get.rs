@@ -0,0 +1,224 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+    util::RON,
+};
+use aws_sdk_lambda::operation::{
+    get_alias::GetAliasError, get_event_source_mapping::GetEventSourceMappingError, get_function::GetFunctionError,
+    get_function_configuration::GetFunctionConfigurationError, get_policy::GetPolicyError,
+};
+
+use crate::{
+    addr::LambdaResourceAddress,
+    resource::{Alias, EventSourceMapping, Function, FunctionCode, LambdaResource, Version, VpcConfig},
+    util::{function_name_from_arn, permission_from_statement, policy_statements, resolve_function_code},
+};
+
+use super::LambdaConnector;
+
+impl LambdaConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = LambdaResourceAddress::from_path(addr)?;
+
+        match &addr {
+            LambdaResourceAddress::Function { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.get_function().function_name(name).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(GetFunctionError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let Some(config) = resp.configuration else {
+                    return Ok(None);
+                };
+
+                let code = match resp.code.as_ref().and_then(|c| c.image_uri.clone()) {
+                    Some(uri) => FunctionCode::Image { uri },
+                    None => {
+                        let recorded_source = match addr.get_output(&self.prefix, "code_source")? {
+                            Some(source) => RON.from_str(&source).ok(),
+                            None => None,
+                        };
+                        resolve_function_code(
+                            &self.prefix,
+                            config.code_sha256.as_deref().unwrap_or_default(),
+                            recorded_source,
+                            addr.get_output(&self.prefix, "code_sha256")?,
+                        )?
+                    }
+                };
+
+                let vpc_config = config.vpc_config.and_then(|vpc_config| {
+                    let subnet_ids = vpc_config.subnet_ids.unwrap_or_default();
+                    if subnet_ids.is_empty() {
+                        None
+                    } else {
+                        Some(VpcConfig {
+                            subnet_ids,
+                            security_group_ids: vpc_config.security_group_ids.unwrap_or_default(),
+                        })
+                    }
+                });
+
+                let function = Function {
+                    code,
+                    role: config.role.unwrap_or_default(),
+                    runtime: config.runtime.map(|r| r.as_str().to_string()),
+                    handler: config.handler,
+                    description: config.description.filter(|d| !d.is_empty()),
+                    memory_size: config.memory_size,
+                    timeout: config.timeout,
+                    architecture: config
+                        .architectures
+                        .and_then(|a| a.first().map(|a| a.as_str().to_string())),
+                    environment: config.environment.and_then(|e| e.variables).unwrap_or_default(),
+                    vpc_config,
+                    layers: config
+                        .layers
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter_map(|layer| layer.arn)
+                        .collect(),
+                    tags: resp.tags.into(),
+                };
+
+                get_resource_response!(
+                    LambdaResource::Function(function),
+                    [(String::from("function_arn"), config.function_arn.unwrap_or_default())]
+                )
+            }
+            LambdaResourceAddress::Alias {
+                region,
+                function_name,
+                alias_name,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.get_alias().function_name(function_name).name(alias_name).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(GetAliasError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let alias = Alias {
+                    function_version: resp.function_version.unwrap_or_default(),
+                    description: resp.description.filter(|d| !d.is_empty()),
+                    routing_config: resp
+                        .routing_config
+                        .and_then(|r| r.additional_version_weights)
+                        .unwrap_or_default(),
+                };
+
+                get_resource_response!(
+                    LambdaResource::Alias(alias),
+                    [(String::from("alias_arn"), resp.alias_arn.unwrap_or_default())]
+                )
+            }
+            LambdaResourceAddress::Version {
+                region,
+                function_name,
+                version,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let config = match client
+                    .get_function_configuration()
+                    .function_name(function_name)
+                    .qualifier(version)
+                    .send()
+                    .await
+                {
+                    Ok(config) => config,
+                    Err(e) => match e.as_service_error() {
+                        Some(GetFunctionConfigurationError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let version_resource = Version {
+                    description: config.description.filter(|d| !d.is_empty()),
+                };
+
+                get_resource_response!(
+                    LambdaResource::Version(version_resource),
+                    [(String::from("version"), version.clone())]
+                )
+            }
+            LambdaResourceAddress::Permission {
+                region,
+                function_name,
+                statement_id,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.get_policy().function_name(function_name).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        // Raised both when the function doesn't exist and when it has no policy
+                        Some(GetPolicyError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let Some(policy) = resp.policy else {
+                    return Ok(None);
+                };
+
+                let statement = policy_statements(&policy)?
+                    .into_iter()
+                    .find(|s| s.get("Sid").and_then(|sid| sid.as_str()) == Some(statement_id.as_str()));
+
+                let Some(permission) = statement.as_ref().and_then(permission_from_statement) else {
+                    return Ok(None);
+                };
+
+                get_resource_response!(LambdaResource::Permission(permission))
+            }
+            LambdaResourceAddress::EventSourceMapping { region, uuid } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.get_event_source_mapping().uuid(uuid).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(GetEventSourceMappingError::ResourceNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                // A mapping that is being deleted is as good as gone
+                if resp.state.as_deref() == Some("Deleting") {
+                    return Ok(None);
+                }
+
+                let mapping = EventSourceMapping {
+                    function_name: function_name_from_arn(resp.function_arn.as_deref().unwrap_or_default()),
+                    event_source_arn: resp.event_source_arn.unwrap_or_default(),
+                    starting_position: resp.starting_position.map(|p| p.as_str().to_string()),
+                    batch_size: resp.batch_size,
+                    maximum_batching_window_in_seconds: resp.maximum_batching_window_in_seconds,
+                    filter_patterns: resp
+                        .filter_criteria
+                        .and_then(|c| c.filters)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter_map(|f| f.pattern)
+                        .collect(),
+                    enabled: matches!(resp.state.as_deref(), Some("Enabled" | "Enabling" | "Creating" | "Updating")),
+                };
+
+                get_resource_response!(
+                    LambdaResource::EventSourceMapping(mapping),
+                    [(String::from("uuid"), uuid.clone())]
+                )
+            }
+        }
+    }
+}
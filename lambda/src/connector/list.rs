@@ -0,0 +1,112 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+use aws_sdk_lambda::operation::get_policy::GetPolicyError;
+
+use crate::{addr::LambdaResourceAddress, util::policy_statements};
+
+use super::LambdaConnector;
+
+impl LambdaConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut functions = client.list_functions().into_paginator().items().send();
+            while let Some(function) = functions.next().await {
+                let Some(function_name) = function?.function_name else {
+                    continue;
+                };
+
+                results.push(
+                    LambdaResourceAddress::Function {
+                        region: region.clone(),
+                        name:   function_name.clone(),
+                    }
+                    .to_path_buf(),
+                );
+
+                let mut aliases = client
+                    .list_aliases()
+                    .function_name(&function_name)
+                    .into_paginator()
+                    .items()
+                    .send();
+                while let Some(alias) = aliases.next().await {
+                    if let Some(alias_name) = alias?.name {
+                        results.push(
+                            LambdaResourceAddress::Alias {
+                                region: region.clone(),
+                                function_name: function_name.clone(),
+                                alias_name,
+                            }
+                            .to_path_buf(),
+                        );
+                    }
+                }
+
+                let mut versions = client
+                    .list_versions_by_function()
+                    .function_name(&function_name)
+                    .into_paginator()
+                    .items()
+                    .send();
+                while let Some(version) = versions.next().await {
+                    if let Some(version) = version?.version
+                        && version != "$LATEST"
+                    {
+                        results.push(
+                            LambdaResourceAddress::Version {
+                                region: region.clone(),
+                                function_name: function_name.clone(),
+                                version,
+                            }
+                            .to_path_buf(),
+                        );
+                    }
+                }
+
+                let policy = match client.get_policy().function_name(&function_name).send().await {
+                    Ok(resp) => resp.policy,
+                    Err(e) => match e.as_service_error() {
+                        Some(GetPolicyError::ResourceNotFoundException(_)) => None,
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                if let Some(policy) = policy {
+                    for statement in policy_statements(&policy)? {
+                        if let Some(statement_id) = statement.get("Sid").and_then(|sid| sid.as_str()) {
+                            results.push(
+                                LambdaResourceAddress::Permission {
+                                    region: region.clone(),
+                                    function_name: function_name.clone(),
+                                    statement_id: statement_id.to_string(),
+                                }
+                                .to_path_buf(),
+                            );
+                        }
+                    }
+                }
+            }
+
+            let mut mappings = client.list_event_source_mappings().into_paginator().items().send();
+            while let Some(mapping) = mappings.next().await {
+                if let Some(uuid) = mapping?.uuid {
+                    results.push(
+                        LambdaResourceAddress::EventSourceMapping {
+                            region: region.clone(),
+                            uuid,
+                        }
+                        .to_path_buf(),
+                    );
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::connector::{ConnectorOp, OpExecResponse, ResourceAddress};
+
+use crate::{addr::LambdaResourceAddress, op::LambdaConnectorOp, op_impl};
+
+use super::LambdaConnector;
+
+impl LambdaConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = LambdaResourceAddress::from_path(addr)?;
+        let op = LambdaConnectorOp::from_str(op)?;
+
+        match &addr {
+            LambdaResourceAddress::Function { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    LambdaConnectorOp::CreateFunction(function) => {
+                        op_impl::create_function(&client, &self.prefix, name, &function).await
+                    }
+                    LambdaConnectorOp::UpdateFunctionCode { code, architecture } => {
+                        op_impl::update_function_code(&client, &self.prefix, name, &code, &architecture).await
+                    }
+                    LambdaConnectorOp::UpdateFunctionConfiguration(function) => {
+                        op_impl::update_function_configuration(&client, name, &function).await
+                    }
+                    LambdaConnectorOp::UpdateFunctionTags(old_tags, new_tags) => {
+                        op_impl::update_function_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    LambdaConnectorOp::DeleteFunction => op_impl::delete_function(&client, name).await,
+                    _ => bail!("Invalid operation for Lambda function resource"),
+                }
+            }
+            LambdaResourceAddress::Alias {
+                region,
+                function_name,
+                alias_name,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    LambdaConnectorOp::CreateAlias(alias) => op_impl::create_alias(&client, function_name, alias_name, &alias).await,
+                    LambdaConnectorOp::UpdateAlias(alias) => op_impl::update_alias(&client, function_name, alias_name, &alias).await,
+                    LambdaConnectorOp::DeleteAlias => op_impl::delete_alias(&client, function_name, alias_name).await,
+                    _ => bail!("Invalid operation for Lambda alias resource"),
+                }
+            }
+            LambdaResourceAddress::Version {
+                region,
+                function_name,
+                version,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    LambdaConnectorOp::PublishVersion(new_version) => {
+                        op_impl::publish_version(&client, function_name, &new_version).await
+                    }
+                    LambdaConnectorOp::DeleteVersion => op_impl::delete_version(&client, function_name, version).await,
+                    _ => bail!("Invalid operation for Lambda version resource"),
+                }
+            }
+            LambdaResourceAddress::Permission {
+                region,
+                function_name,
+                statement_id,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    LambdaConnectorOp::AddPermission(permission) => {
+                        op_impl::add_permission(&client, function_name, statement_id, &permission).await
+                    }
+                    LambdaConnectorOp::RemovePermission => op_impl::remove_permission(&client, function_name, statement_id).await,
+                    _ => bail!("Invalid operation for Lambda permission resource"),
+                }
+            }
+            LambdaResourceAddress::EventSourceMapping { region, uuid } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    LambdaConnectorOp::CreateEventSourceMapping(mapping) => {
+                        op_impl::create_event_source_mapping(&client, &mapping).await
+                    }
+                    LambdaConnectorOp::UpdateEventSourceMapping(mapping) => {
+                        op_impl::update_event_source_mapping(&client, uuid, &mapping).await
+                    }
+                    LambdaConnectorOp::DeleteEventSourceMapping => op_impl::delete_event_source_mapping(&client, uuid).await,
+                    _ => bail!("Invalid operation for Lambda event source mapping resource"),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::resource::{Alias, EventSourceMapping, Function, FunctionCode, Permission, Version};
+
+use super::{LambdaConnector, LambdaConnectorOp, LambdaResourceAddress};
+
+impl LambdaConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = LambdaResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            LambdaResourceAddress::Function { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_function)) => {
+                    let new_function: Function = RON.from_str(&new_function)?;
+                    self.check_code_source(name, &new_function.code)?;
+                    Ok(vec![connector_op!(
+                        LambdaConnectorOp::CreateFunction(new_function),
+                        format!("Create new Lambda function {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_function), None) => Ok(vec![connector_op!(
+                    LambdaConnectorOp::DeleteFunction,
+                    format!("DELETE Lambda function {} in region {}", name, region)
+                )]),
+                (Some(old_function), Some(new_function)) => {
+                    let old_function: Function = RON.from_str(&old_function)?;
+                    let new_function: Function = RON.from_str(&new_function)?;
+                    let mut ops = Vec::new();
+
+                    if old_function.code != new_function.code {
+                        self.check_code_source(name, &new_function.code)?;
+                    }
+
+                    // The package type can't be changed in place
+                    let old_is_image = matches!(old_function.code, FunctionCode::Image { .. });
+                    let new_is_image = matches!(new_function.code, FunctionCode::Image { .. });
+                    if old_is_image != new_is_image {
+                        ops.push(connector_op!(
+                            LambdaConnectorOp::DeleteFunction,
+                            format!("REPLACE Lambda function `{}` (requires replacement: code package type)", name)
+                        ));
+                        ops.push(connector_op!(
+                            LambdaConnectorOp::CreateFunction(new_function),
+                            format!("Create new Lambda function {} in region {}", name, region)
+                        ));
+                        return Ok(ops);
+                    }
+
+                    if old_function.tags != new_function.tags {
+                        let diff = diff_ron_values(&old_function.tags, &new_function.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            LambdaConnectorOp::UpdateFunctionTags(old_function.tags.clone(), new_function.tags.clone()),
+                            format!("Modify tags for Lambda function `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    // Configuration first, so that new code never runs with the old handler or runtime
+                    let old_config = Function {
+                        code: new_function.code.clone(),
+                        architecture: new_function.architecture.clone(),
+                        tags: new_function.tags.clone(),
+                        ..old_function.clone()
+                    };
+                    if old_config != new_function {
+                        let diff = diff_ron_values(&old_config, &new_function).unwrap_or_default();
+                        ops.push(connector_op!(
+                            LambdaConnectorOp::UpdateFunctionConfiguration(new_function.clone()),
+                            format!("Modify configuration for Lambda function `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    if old_function.code != new_function.code || old_function.architecture != new_function.architecture {
+                        let diff = diff_ron_values(&old_function.code, &new_function.code).unwrap_or_default();
+                        ops.push(connector_op!(
+                            LambdaConnectorOp::UpdateFunctionCode {
+                                code: new_function.code.clone(),
+                                architecture: new_function.architecture.clone(),
+                            },
+                            format!("Deploy new code for Lambda function `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            LambdaResourceAddress::Alias {
+                function_name,
+                alias_name,
+                ..
+            } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_alias)) => {
+                    let new_alias: Alias = RON.from_str(&new_alias)?;
+                    Ok(vec![connector_op!(
+                        LambdaConnectorOp::CreateAlias(new_alias),
+                        format!("Create new alias {} for Lambda function {}", alias_name, function_name)
+                    )])
+                }
+                (Some(_old_alias), None) => Ok(vec![connector_op!(
+                    LambdaConnectorOp::DeleteAlias,
+                    format!("DELETE alias {} for Lambda function {}", alias_name, function_name)
+                )]),
+                (Some(old_alias), Some(new_alias)) => {
+                    let old_alias: Alias = RON.from_str(&old_alias)?;
+                    let new_alias: Alias = RON.from_str(&new_alias)?;
+
+                    if old_alias == new_alias {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_alias, &new_alias).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        LambdaConnectorOp::UpdateAlias(new_alias),
+                        format!("Modify alias {} for Lambda function `{}`\n{}", alias_name, function_name, diff)
+                    )])
+                }
+            },
+            LambdaResourceAddress::Version {
+                function_name, version, ..
+            } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_version)) => {
+                    let new_version: Version = RON.from_str(&new_version)?;
+                    Ok(vec![connector_op!(
+                        LambdaConnectorOp::PublishVersion(new_version),
+                        format!("Publish new version of Lambda function {}", function_name)
+                    )])
+                }
+                (Some(_old_version), None) => Ok(vec![connector_op!(
+                    LambdaConnectorOp::DeleteVersion,
+                    format!("DELETE version {} of Lambda function {}", version, function_name)
+                )]),
+                (Some(old_version), Some(new_version)) => {
+                    let old_version: Version = RON.from_str(&old_version)?;
+                    let new_version: Version = RON.from_str(&new_version)?;
+
+                    if old_version != new_version {
+                        bail!(
+                            "Version {} of Lambda function {} can't be modified: published versions are immutable. Publish a new version instead.",
+                            version,
+                            function_name
+                        );
+                    }
+
+                    Ok(Vec::new())
+                }
+            },
+            LambdaResourceAddress::Permission {
+                function_name,
+                statement_id,
+                ..
+            } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_permission)) => {
+                    let new_permission: Permission = RON.from_str(&new_permission)?;
+                    Ok(vec![connector_op!(
+                        LambdaConnectorOp::AddPermission(new_permission),
+                        format!("Add permission {} to Lambda function {}", statement_id, function_name)
+                    )])
+                }
+                (Some(_old_permission), None) => Ok(vec![connector_op!(
+                    LambdaConnectorOp::RemovePermission,
+                    format!("DELETE permission {} from Lambda function {}", statement_id, function_name)
+                )]),
+                (Some(old_permission), Some(new_permission)) => {
+                    let old_permission: Permission = RON.from_str(&old_permission)?;
+                    let new_permission: Permission = RON.from_str(&new_permission)?;
+
+                    if old_permission == new_permission {
+                        return Ok(Vec::new());
+                    }
+
+                    // Policy statements can't be edited, only removed and re-added
+                    let diff = diff_ron_values(&old_permission, &new_permission).unwrap_or_default();
+                    Ok(vec![
+                        connector_op!(
+                            LambdaConnectorOp::RemovePermission,
+                            format!("Remove permission {} from Lambda function {}", statement_id, function_name)
+                        ),
+                        connector_op!(
+                            LambdaConnectorOp::AddPermission(new_permission),
+                            format!(
+                                "Re-add permission {} to Lambda function `{}`\n{}",
+                                statement_id, function_name, diff
+                            )
+                        ),
+                    ])
+                }
+            },
+            LambdaResourceAddress::EventSourceMapping { uuid, .. } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_mapping)) => {
+                    let new_mapping: EventSourceMapping = RON.from_str(&new_mapping)?;
+                    let message = format!(
+                        "Create new event source mapping from {} to Lambda function {}",
+                        new_mapping.event_source_arn, new_mapping.function_name
+                    );
+                    Ok(vec![connector_op!(LambdaConnectorOp::CreateEventSourceMapping(new_mapping), message)])
+                }
+                (Some(_old_mapping), None) => Ok(vec![connector_op!(
+                    LambdaConnectorOp::DeleteEventSourceMapping,
+                    format!("DELETE event source mapping {}", uuid)
+                )]),
+                (Some(old_mapping), Some(new_mapping)) => {
+                    let old_mapping: EventSourceMapping = RON.from_str(&old_mapping)?;
+                    let new_mapping: EventSourceMapping = RON.from_str(&new_mapping)?;
+
+                    if old_mapping == new_mapping {
+                        return Ok(Vec::new());
+                    }
+
+                    let mut replace_fields = Vec::new();
+                    if old_mapping.event_source_arn != new_mapping.event_source_arn {
+                        replace_fields.push("event_source_arn");
+                    }
+                    if old_mapping.starting_position != new_mapping.starting_position {
+                        replace_fields.push("starting_position");
+                    }
+
+                    if !replace_fields.is_empty() {
+                        return Ok(vec![
+                            connector_op!(
+                                LambdaConnectorOp::DeleteEventSourceMapping,
+                                format!(
+                                    "REPLACE event source mapping `{}` (requires replacement: {})",
+                                    uuid,
+                                    replace_fields.join(", ")
+                                )
+                            ),
+                            connector_op!(
+                                LambdaConnectorOp::CreateEventSourceMapping(new_mapping),
+                                format!("Create new event source mapping to replace {}", uuid)
+                            ),
+                        ]);
+                    }
+
+                    let diff = diff_ron_values(&old_mapping, &new_mapping).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        LambdaConnectorOp::UpdateEventSourceMapping(new_mapping),
+                        format!("Modify event source mapping `{}`\n{}", uuid, diff)
+                    )])
+                }
+            },
+        }
+    }
+
+    /// Catches missing deployment packages and code that can't be deployed at plan time.
+    fn check_code_source(&self, function_name: &str, code: &FunctionCode) -> anyhow::Result<()> {
+        match code {
+            FunctionCode::Zip { path } if !self.prefix.join(path).is_file() => {
+                bail!("Deployment package {} for Lambda function {} does not exist", path, function_name)
+            }
+            FunctionCode::Deployed { .. } => {
+                bail!(
+                    "Lambda function {} has no code source to deploy from; set `code` to a Zip, S3 or Image source",
+                    function_name
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+}
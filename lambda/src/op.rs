@@ -0,0 +1,54 @@
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{Alias, EventSourceMapping, Function, FunctionCode, Permission, Version},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum LambdaConnectorOp {
+    // Function operations
+    CreateFunction(Function),
+    UpdateFunctionCode {
+        code: FunctionCode,
+        architecture: Option<String>,
+    },
+    /// Sets every configuration field (runtime, handler, memory, environment, VPC, layers...) at once.
+    UpdateFunctionConfiguration(Function),
+    UpdateFunctionTags(Tags, Tags),
+    DeleteFunction,
+
+    // Alias operations
+    CreateAlias(Alias),
+    UpdateAlias(Alias),
+    DeleteAlias,
+
+    // Version operations
+    PublishVersion(Version),
+    DeleteVersion,
+
+    // Permission operations
+    AddPermission(Permission),
+    RemovePermission,
+
+    // Event source mapping operations
+    CreateEventSourceMapping(EventSourceMapping),
+    UpdateEventSourceMapping(EventSourceMapping),
+    DeleteEventSourceMapping,
+}
+
+impl ConnectorOp for LambdaConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
@@ -0,0 +1,538 @@
+use std::{collections::HashMap, path::Path, time::Duration};
+
+use anyhow::{Context, bail};
+use autoschematic_core::connector::OpExecResponse;
+use aws_sdk_lambda::{
+    operation::create_function::CreateFunctionError,
+    primitives::Blob,
+    types::{
+        AliasRoutingConfiguration, Architecture, Environment, EventSourcePosition, Filter, FilterCriteria, LastUpdateStatus,
+        PackageType, Runtime, State,
+    },
+};
+
+use crate::{
+    resource::{Alias, EventSourceMapping, Function, FunctionCode, Permission, Version},
+    tags::{Tags, tag_diff},
+};
+
+const READY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const READY_MAX_POLLS: usize = 150;
+
+/// Newly created IAM roles can take a few seconds before Lambda is able to assume them.
+const ROLE_PROPAGATION_RETRIES: usize = 10;
+const ROLE_PROPAGATION_DELAY: Duration = Duration::from_secs(3);
+
+/// Reads the deployment package for `code`. Returns None for code that isn't a .zip in this repository.
+fn read_zip_package(prefix: &Path, code: &FunctionCode) -> anyhow::Result<Option<Vec<u8>>> {
+    match code {
+        FunctionCode::Zip { path } => {
+            let full_path = prefix.join(path);
+            let package = std::fs::read(&full_path)
+                .with_context(|| format!("Failed to read deployment package {}", full_path.display()))?;
+            Ok(Some(package))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// The outputs that `get` uses to tell which code source the deployed package came from.
+fn code_outputs(code: &FunctionCode, code_sha256: Option<String>) -> anyhow::Result<HashMap<String, Option<String>>> {
+    let mut outputs = HashMap::new();
+    outputs.insert(String::from("code_source"), Some(autoschematic_core::util::RON.to_string(code)?));
+    outputs.insert(String::from("code_sha256"), code_sha256);
+    Ok(outputs)
+}
+
+fn build_environment(function: &Function) -> Environment {
+    Environment::builder().set_variables(Some(function.environment.clone())).build()
+}
+
+fn build_vpc_config(function: &Function) -> aws_sdk_lambda::types::VpcConfig {
+    // An empty VpcConfig detaches the function from its VPC
+    match &function.vpc_config {
+        Some(vpc_config) => aws_sdk_lambda::types::VpcConfig::builder()
+            .set_subnet_ids(Some(vpc_config.subnet_ids.clone()))
+            .set_security_group_ids(Some(vpc_config.security_group_ids.clone()))
+            .build(),
+        None => aws_sdk_lambda::types::VpcConfig::builder()
+            .set_subnet_ids(Some(Vec::new()))
+            .set_security_group_ids(Some(Vec::new()))
+            .build(),
+    }
+}
+
+/// Lambda rejects updates while a previous create or update is still being applied,
+/// so wait for the function to settle before returning.
+pub async fn wait_for_function_ready(client: &aws_sdk_lambda::Client, function_name: &str) -> anyhow::Result<()> {
+    for _ in 0..READY_MAX_POLLS {
+        let config = client.get_function_configuration().function_name(function_name).send().await?;
+
+        match (&config.state, &config.last_update_status) {
+            (Some(State::Failed), _) | (_, Some(LastUpdateStatus::Failed)) => {
+                bail!(
+                    "Function {} failed to deploy: {}",
+                    function_name,
+                    config
+                        .last_update_status_reason
+                        .or(config.state_reason)
+                        .unwrap_or_default()
+                );
+            }
+            (Some(State::Pending), _) | (_, Some(LastUpdateStatus::InProgress)) => {
+                tokio::time::sleep(READY_POLL_INTERVAL).await;
+            }
+            _ => return Ok(()),
+        }
+    }
+
+    bail!("Timed out waiting for function {} to become ready", function_name)
+}
+
+/// Creates a function using the provided configuration
+pub async fn create_function(
+    client: &aws_sdk_lambda::Client,
+    prefix: &Path,
+    function_name: &str,
+    function: &Function,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut code = aws_sdk_lambda::types::FunctionCode::builder();
+    let mut package_type = PackageType::Zip;
+    match &function.code {
+        FunctionCode::Zip { .. } => {
+            code = code.set_zip_file(read_zip_package(prefix, &function.code)?.map(Blob::new));
+        }
+        FunctionCode::S3 {
+            bucket,
+            key,
+            object_version,
+        } => {
+            code = code
+                .s3_bucket(bucket)
+                .s3_key(key)
+                .set_s3_object_version(object_version.clone());
+        }
+        FunctionCode::Image { uri } => {
+            code = code.image_uri(uri);
+            package_type = PackageType::Image;
+        }
+        FunctionCode::Deployed { .. } => {
+            bail!("Function {} has no code source to deploy from", function_name);
+        }
+    }
+    let code = code.build();
+
+    let mut attempt = 0;
+    let create_resp = loop {
+        let mut request = client
+            .create_function()
+            .function_name(function_name)
+            .role(&function.role)
+            .code(code.clone())
+            .package_type(package_type.clone())
+            .set_runtime(function.runtime.as_deref().map(Runtime::from))
+            .set_handler(function.handler.clone())
+            .set_description(function.description.clone())
+            .set_memory_size(function.memory_size)
+            .set_timeout(function.timeout)
+            .set_architectures(function.architecture.as_deref().map(|a| vec![Architecture::from(a)]))
+            .environment(build_environment(function))
+            .set_tags(function.tags.clone().into());
+
+        if function.vpc_config.is_some() {
+            request = request.vpc_config(build_vpc_config(function));
+        }
+
+        if !function.layers.is_empty() {
+            request = request.set_layers(Some(function.layers.clone()));
+        }
+
+        match request.send().await {
+            Ok(resp) => break resp,
+            Err(e) => match e.as_service_error() {
+                Some(CreateFunctionError::InvalidParameterValueException(ex))
+                    if attempt < ROLE_PROPAGATION_RETRIES
+                        && ex.message().is_some_and(|m| m.contains("cannot be assumed by Lambda")) =>
+                {
+                    attempt += 1;
+                    tokio::time::sleep(ROLE_PROPAGATION_DELAY).await;
+                }
+                _ => return Err(e.into()),
+            },
+        }
+    };
+
+    wait_for_function_ready(client, function_name).await?;
+
+    let mut outputs = code_outputs(&function.code, create_resp.code_sha256.clone())?;
+    outputs.insert(String::from("function_arn"), create_resp.function_arn);
+
+    Ok(OpExecResponse {
+        outputs: Some(outputs),
+        friendly_message: Some(format!("Created Lambda function {}", function_name)),
+    })
+}
+
+/// Deploys a new package for the function
+pub async fn update_function_code(
+    client: &aws_sdk_lambda::Client,
+    prefix: &Path,
+    function_name: &str,
+    code: &FunctionCode,
+    architecture: &Option<String>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client
+        .update_function_code()
+        .function_name(function_name)
+        .set_architectures(architecture.as_deref().map(|a| vec![Architecture::from(a)]));
+
+    match code {
+        FunctionCode::Zip { .. } => {
+            request = request.set_zip_file(read_zip_package(prefix, code)?.map(Blob::new));
+        }
+        FunctionCode::S3 {
+            bucket,
+            key,
+            object_version,
+        } => {
+            request = request
+                .s3_bucket(bucket)
+                .s3_key(key)
+                .set_s3_object_version(object_version.clone());
+        }
+        FunctionCode::Image { uri } => {
+            request = request.image_uri(uri);
+        }
+        FunctionCode::Deployed { .. } => {
+            bail!("Function {} has no code source to deploy from", function_name);
+        }
+    }
+
+    let resp = request.send().await?;
+
+    wait_for_function_ready(client, function_name).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(code_outputs(code, resp.code_sha256)?),
+        friendly_message: Some(format!("Deployed new code for Lambda function {}", function_name)),
+    })
+}
+
+/// Sets the function's configuration to match `function`
+pub async fn update_function_configuration(
+    client: &aws_sdk_lambda::Client,
+    function_name: &str,
+    function: &Function,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .update_function_configuration()
+        .function_name(function_name)
+        .role(&function.role)
+        .set_runtime(function.runtime.as_deref().map(Runtime::from))
+        .set_handler(function.handler.clone())
+        .description(function.description.clone().unwrap_or_default())
+        .set_memory_size(function.memory_size)
+        .set_timeout(function.timeout)
+        .environment(build_environment(function))
+        .vpc_config(build_vpc_config(function))
+        .set_layers(Some(function.layers.clone()))
+        .send()
+        .await?;
+
+    wait_for_function_ready(client, function_name).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated configuration for Lambda function {}", function_name)),
+    })
+}
+
+/// Updates function tags
+pub async fn update_function_tags(
+    client: &aws_sdk_lambda::Client,
+    function_name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let config = client.get_function_configuration().function_name(function_name).send().await?;
+    let Some(function_arn) = config.function_arn else {
+        bail!("Function not found: {}", function_name);
+    };
+
+    let (untag_keys, new_tags) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource(&function_arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tags.is_empty() {
+        client.tag_resource().resource(&function_arn).set_tags(Some(new_tags)).send().await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for Lambda function {}", function_name)),
+    })
+}
+
+/// Deletes a function, along with all of its versions and aliases
+pub async fn delete_function(client: &aws_sdk_lambda::Client, function_name: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_function().function_name(function_name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Deleted Lambda function {}", function_name)),
+    })
+}
+
+fn build_routing_config(alias: &Alias) -> AliasRoutingConfiguration {
+    AliasRoutingConfiguration::builder()
+        .set_additional_version_weights(Some(alias.routing_config.clone()))
+        .build()
+}
+
+/// Creates an alias
+pub async fn create_alias(
+    client: &aws_sdk_lambda::Client,
+    function_name: &str,
+    alias_name: &str,
+    alias: &Alias,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let resp = client
+        .create_alias()
+        .function_name(function_name)
+        .name(alias_name)
+        .function_version(&alias.function_version)
+        .set_description(alias.description.clone())
+        .routing_config(build_routing_config(alias))
+        .send()
+        .await?;
+
+    let mut outputs = HashMap::new();
+    outputs.insert(String::from("alias_arn"), resp.alias_arn);
+
+    Ok(OpExecResponse {
+        outputs: Some(outputs),
+        friendly_message: Some(format!("Created alias {} for Lambda function {}", alias_name, function_name)),
+    })
+}
+
+/// Points an alias at a new version and/or updates its routing
+pub async fn update_alias(
+    client: &aws_sdk_lambda::Client,
+    function_name: &str,
+    alias_name: &str,
+    alias: &Alias,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .update_alias()
+        .function_name(function_name)
+        .name(alias_name)
+        .function_version(&alias.function_version)
+        .description(alias.description.clone().unwrap_or_default())
+        .routing_config(build_routing_config(alias))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Updated alias {} for Lambda function {} to version {}",
+            alias_name, function_name, alias.function_version
+        )),
+    })
+}
+
+/// Deletes an alias
+pub async fn delete_alias(
+    client: &aws_sdk_lambda::Client,
+    function_name: &str,
+    alias_name: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_alias().function_name(function_name).name(alias_name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Deleted alias {} for Lambda function {}", alias_name, function_name)),
+    })
+}
+
+/// Publishes the function's current code and configuration as a new version
+pub async fn publish_version(
+    client: &aws_sdk_lambda::Client,
+    function_name: &str,
+    version: &Version,
+) -> Result<OpExecResponse, anyhow::Error> {
+    wait_for_function_ready(client, function_name).await?;
+
+    let resp = client
+        .publish_version()
+        .function_name(function_name)
+        .set_description(version.description.clone())
+        .send()
+        .await?;
+
+    let Some(version_number) = resp.version else {
+        bail!("PublishVersion returned no version for Lambda function {}", function_name);
+    };
+
+    let mut outputs = HashMap::new();
+    outputs.insert(String::from("version"), Some(version_number.clone()));
+    outputs.insert(String::from("function_arn"), resp.function_arn);
+
+    Ok(OpExecResponse {
+        outputs: Some(outputs),
+        friendly_message: Some(format!("Published version {} of Lambda function {}", version_number, function_name)),
+    })
+}
+
+/// Deletes a published version
+pub async fn delete_version(
+    client: &aws_sdk_lambda::Client,
+    function_name: &str,
+    version: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_function().function_name(function_name).qualifier(version).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Deleted version {} of Lambda function {}", version, function_name)),
+    })
+}
+
+/// Adds a statement to the function's resource-based policy
+pub async fn add_permission(
+    client: &aws_sdk_lambda::Client,
+    function_name: &str,
+    statement_id: &str,
+    permission: &Permission,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .add_permission()
+        .function_name(function_name)
+        .statement_id(statement_id)
+        .action(&permission.action)
+        .principal(&permission.principal)
+        .set_source_arn(permission.source_arn.clone())
+        .set_source_account(permission.source_account.clone())
+        .set_principal_org_id(permission.principal_org_id.clone())
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Granted {} on Lambda function {} to {}",
+            permission.action, function_name, permission.principal
+        )),
+    })
+}
+
+/// Removes a statement from the function's resource-based policy
+pub async fn remove_permission(
+    client: &aws_sdk_lambda::Client,
+    function_name: &str,
+    statement_id: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .remove_permission()
+        .function_name(function_name)
+        .statement_id(statement_id)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Removed permission {} from Lambda function {}", statement_id, function_name)),
+    })
+}
+
+fn build_filter_criteria(mapping: &EventSourceMapping) -> FilterCriteria {
+    FilterCriteria::builder()
+        .set_filters(Some(
+            mapping
+                .filter_patterns
+                .iter()
+                .map(|pattern| Filter::builder().pattern(pattern).build())
+                .collect(),
+        ))
+        .build()
+}
+
+/// Creates an event source mapping
+pub async fn create_event_source_mapping(
+    client: &aws_sdk_lambda::Client,
+    mapping: &EventSourceMapping,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client
+        .create_event_source_mapping()
+        .function_name(&mapping.function_name)
+        .event_source_arn(&mapping.event_source_arn)
+        .set_starting_position(mapping.starting_position.as_deref().map(EventSourcePosition::from))
+        .set_batch_size(mapping.batch_size)
+        .set_maximum_batching_window_in_seconds(mapping.maximum_batching_window_in_seconds)
+        .enabled(mapping.enabled);
+
+    if !mapping.filter_patterns.is_empty() {
+        request = request.filter_criteria(build_filter_criteria(mapping));
+    }
+
+    let resp = request.send().await?;
+
+    let Some(uuid) = resp.uuid else {
+        bail!("CreateEventSourceMapping returned no UUID");
+    };
+
+    let mut outputs = HashMap::new();
+    outputs.insert(String::from("uuid"), Some(uuid.clone()));
+
+    Ok(OpExecResponse {
+        outputs: Some(outputs),
+        friendly_message: Some(format!(
+            "Created event source mapping {} from {} to {}",
+            uuid, mapping.event_source_arn, mapping.function_name
+        )),
+    })
+}
+
+/// Updates an event source mapping's target and batching settings
+pub async fn update_event_source_mapping(
+    client: &aws_sdk_lambda::Client,
+    uuid: &str,
+    mapping: &EventSourceMapping,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .update_event_source_mapping()
+        .uuid(uuid)
+        .function_name(&mapping.function_name)
+        .set_batch_size(mapping.batch_size)
+        .set_maximum_batching_window_in_seconds(mapping.maximum_batching_window_in_seconds)
+        .filter_criteria(build_filter_criteria(mapping))
+        .enabled(mapping.enabled)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated event source mapping {}", uuid)),
+    })
+}
+
+/// Deletes an event source mapping
+pub async fn delete_event_source_mapping(client: &aws_sdk_lambda::Client, uuid: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_event_source_mapping().uuid(uuid).send().await?;
+
+    let mut outputs = HashMap::new();
+    outputs.insert(String::from("uuid"), None);
+
+    Ok(OpExecResponse {
+        outputs: Some(outputs),
+        friendly_message: Some(format!("Deleted event source mapping {}", uuid)),
+    })
+}
+
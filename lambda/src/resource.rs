@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::LambdaResourceAddress, tags::Tags};
+
+/// Where a function's deployment package comes from.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub enum FunctionCode {
+    /// A .zip deployment package in this repository, relative to the repository root.
+    /// The package is redeployed whenever its contents change.
+    Zip { path: String },
+    /// A .zip deployment package in S3. Lambda copies the object at deploy time, so changing
+    /// the object in place won't redeploy the function; change the key or `object_version` instead.
+    S3 {
+        bucket: String,
+        key: String,
+        object_version: Option<String>,
+    },
+    /// A container image in ECR.
+    Image { uri: String },
+    /// Code that was deployed from outside this repository. Only ever produced by `get`.
+    Deployed { code_sha256: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct VpcConfig {
+    pub subnet_ids: Vec<String>,
+    pub security_group_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Function {
+    pub code: FunctionCode,
+    pub role: String,
+    pub runtime: Option<String>,
+    pub handler: Option<String>,
+    pub description: Option<String>,
+    pub memory_size: Option<i32>,
+    pub timeout: Option<i32>,
+    pub architecture: Option<String>, // x86_64 or arm64
+    #[serde(default)]
+    pub environment: HashMap<String, String>,
+    pub vpc_config: Option<VpcConfig>,
+    #[serde(default)]
+    pub layers: Vec<String>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Alias {
+    pub function_version: String,
+    pub description: Option<String>,
+    /// Weighted routing to additional versions, e.g. `{"3": 0.1}` to send 10% of traffic to version 3.
+    #[serde(default)]
+    pub routing_config: HashMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Version {
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Permission {
+    pub action: String,    // e.g. lambda:InvokeFunction
+    pub principal: String, // A service principal such as s3.amazonaws.com, an account ID or ARN, or "*"
+    pub source_arn: Option<String>,
+    pub source_account: Option<String>,
+    pub principal_org_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EventSourceMapping {
+    /// The function name, optionally qualified with an alias or version (`my-function:live`).
+    pub function_name: String,
+    pub event_source_arn: String,
+    pub starting_position: Option<String>, // LATEST or TRIM_HORIZON, for streams
+    pub batch_size: Option<i32>,
+    pub maximum_batching_window_in_seconds: Option<i32>,
+    #[serde(default)]
+    pub filter_patterns: Vec<String>,
+    pub enabled: bool,
+}
+
+pub enum LambdaResource {
+    Function(Function),
+    Alias(Alias),
+    Version(Version),
+    Permission(Permission),
+    EventSourceMapping(EventSourceMapping),
+}
+
+impl Resource for LambdaResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            LambdaResource::Function(function) => Ok(RON.to_string_pretty(&function, pretty_config)?.into()),
+            LambdaResource::Alias(alias) => Ok(RON.to_string_pretty(&alias, pretty_config)?.into()),
+            LambdaResource::Version(version) => Ok(RON.to_string_pretty(&version, pretty_config)?.into()),
+            LambdaResource::Permission(permission) => Ok(RON.to_string_pretty(&permission, pretty_config)?.into()),
+            LambdaResource::EventSourceMapping(mapping) => Ok(RON.to_string_pretty(&mapping, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = LambdaResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            LambdaResourceAddress::Function { .. } => Ok(LambdaResource::Function(RON.from_str(s)?)),
+            LambdaResourceAddress::Alias { .. } => Ok(LambdaResource::Alias(RON.from_str(s)?)),
+            LambdaResourceAddress::Version { .. } => Ok(LambdaResource::Version(RON.from_str(s)?)),
+            LambdaResourceAddress::Permission { .. } => Ok(LambdaResource::Permission(RON.from_str(s)?)),
+            LambdaResourceAddress::EventSourceMapping { .. } => Ok(LambdaResource::EventSourceMapping(RON.from_str(s)?)),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use sha2::{Digest, Sha256};
+
+use crate::resource::{FunctionCode, Permission};
+
+/// Lambda reports CodeSha256 as the base64-encoded SHA-256 of the deployment package.
+pub fn code_sha256(package: &[u8]) -> String {
+    STANDARD.encode(Sha256::digest(package))
+}
+
+/// Works out which code source the deployed package came from.
+///
+/// `recorded_source` and `recorded_sha256` are the outputs written when this repository last
+/// deployed the function. They're only trusted if the package Lambda is running now is still the
+/// one that was deployed; for repository .zip files it must also still match the file on disk, so
+/// that editing the file shows up as a diff.
+pub fn resolve_function_code(
+    prefix: &Path,
+    deployed_sha256: &str,
+    recorded_source: Option<FunctionCode>,
+    recorded_sha256: Option<String>,
+) -> anyhow::Result<FunctionCode> {
+    let deployed = FunctionCode::Deployed {
+        code_sha256: deployed_sha256.to_string(),
+    };
+
+    let Some(source) = recorded_source else {
+        return Ok(deployed);
+    };
+
+    match &source {
+        FunctionCode::Zip { path } => {
+            let full_path = prefix.join(path);
+            if full_path.is_file() && code_sha256(&std::fs::read(&full_path)?) == deployed_sha256 {
+                Ok(source)
+            } else {
+                Ok(deployed)
+            }
+        }
+        FunctionCode::S3 { .. } if recorded_sha256.as_deref() == Some(deployed_sha256) => Ok(source),
+        _ => Ok(deployed),
+    }
+}
+
+/// Turns `arn:aws:lambda:us-east-1:123456789012:function:my-function[:qualifier]`
+/// into `my-function[:qualifier]`.
+pub fn function_name_from_arn(arn: &str) -> String {
+    match arn.split_once(":function:") {
+        Some((_, name)) => name.to_string(),
+        None => arn.to_string(),
+    }
+}
+
+/// Reads a statement from a function's resource-based policy back into a Permission.
+pub fn permission_from_statement(statement: &serde_json::Value) -> Option<Permission> {
+    let action = statement.get("Action")?.as_str()?.to_string();
+
+    let principal = match statement.get("Principal")? {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Object(map) => {
+            let principal = map.get("Service").or_else(|| map.get("AWS"))?.as_str()?;
+            // AddPermission stores account IDs as the account's root ARN
+            match principal.strip_prefix("arn:aws:iam::").and_then(|s| s.strip_suffix(":root")) {
+                Some(account_id) => account_id.to_string(),
+                None => principal.to_string(),
+            }
+        }
+        _ => return None,
+    };
+
+    let condition = statement.get("Condition");
+    let condition_value = |operator: &str, key: &str| {
+        condition
+            .and_then(|c| c.get(operator))
+            .and_then(|c| c.get(key))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    };
+
+    Some(Permission {
+        action,
+        principal,
+        source_arn: condition_value("ArnLike", "AWS:SourceArn"),
+        source_account: condition_value("StringEquals", "AWS:SourceAccount"),
+        principal_org_id: condition_value("StringEquals", "aws:PrincipalOrgID"),
+    })
+}
+
+/// Returns the statements in a function's resource-based policy document.
+pub fn policy_statements(policy: &str) -> anyhow::Result<Vec<serde_json::Value>> {
+    let policy: serde_json::Value = serde_json::from_str(policy)?;
+    match policy.get("Statement") {
+        Some(serde_json::Value::Array(statements)) => Ok(statements.clone()),
+        Some(statement) => Ok(vec![statement.clone()]),
+        None => Ok(Vec::new()),
+    }
+}
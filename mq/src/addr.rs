@@ -0,0 +1,79 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum MqResourceAddress {
+    Broker { region: String, name: String },
+    User { region: String, broker: String, username: String },
+    Configuration { region: String, name: String },
+}
+
+impl ResourceAddress for MqResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            MqResourceAddress::Broker { region, name } => PathBuf::from(format!("aws/mq/{region}/brokers/{name}.ron")),
+            MqResourceAddress::User {
+                region,
+                broker,
+                username,
+            } => PathBuf::from(format!("aws/mq/{region}/brokers/{broker}/users/{username}.ron")),
+            MqResourceAddress::Configuration { region, name } => {
+                PathBuf::from(format!("aws/mq/{region}/configurations/{name}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "mq", region, "brokers", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(MqResourceAddress::Broker {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "mq", region, "brokers", broker, "users", username] if username.ends_with(".ron") => {
+                let username = username.strip_suffix(".ron").unwrap().to_string();
+                Ok(MqResourceAddress::User {
+                    region: region.to_string(),
+                    broker: broker.to_string(),
+                    username,
+                })
+            }
+            ["aws", "mq", region, "configurations", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(MqResourceAddress::Configuration {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for MqResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/mq/<region>/brokers/<name>.ron",
+                description: "An Amazon MQ broker running ActiveMQ or RabbitMQ",
+                example:     "aws/mq/us-east-1/brokers/orders.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/mq/<region>/brokers/<broker>/users/<username>.ron",
+                description: "A broker user. Its password is read from Secrets Manager",
+                example:     "aws/mq/us-east-1/brokers/orders/users/admin.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/mq/<region>/configurations/<name>.ron",
+                description: "A broker configuration. Its data lives next to it in <name>.xml (ActiveMQ) or <name>.conf (RabbitMQ)",
+                example:     "aws/mq/us-east-1/configurations/orders.ron",
+            },
+        ]
+    }
+}
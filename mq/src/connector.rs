@@ -0,0 +1,243 @@
+pub use crate::addr::MqResourceAddress;
+pub use crate::op::MqConnectorOp;
+pub use crate::resource::MqResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::MqConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{Broker, BrokerLogs, Configuration, MaintenanceWindow, User};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct MqConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_mq::Client>>>,
+    secrets_client_cache: Mutex<HashMap<String, Arc<aws_sdk_secretsmanager::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<MqConnectorConfig>,
+    prefix: PathBuf,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl MqConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_mq::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_mq, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+    /// User passwords are read from Secrets Manager in the broker's region.
+    pub async fn get_or_init_secrets_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_secretsmanager::Client>> {
+        let mut cache = self.secrets_client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_secretsmanager, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get Secrets Manager client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for MqConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = MqResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(MqConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let mq_config: MqConnectorConfig = MqConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = mq_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.secrets_client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(mq_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = mq_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // A small single-instance ActiveMQ broker in a private subnet
+        res.push(skeleton!(
+            MqResourceAddress::Broker {
+                region: String::from("[region]"),
+                name:   String::from("[broker_name]"),
+            },
+            MqResource::Broker(Broker {
+                engine_type: String::from("ACTIVEMQ"),
+                engine_version: String::from("5.18"),
+                host_instance_type: String::from("mq.t3.micro"),
+                deployment_mode: String::from("SINGLE_INSTANCE"),
+                publicly_accessible: false,
+                auto_minor_version_upgrade: true,
+                subnet_ids: vec![String::from("[subnet_id]")],
+                security_groups: vec![String::from("[security_group_id]")],
+                storage_type: None,
+                configuration: Some(String::from("[configuration_name]")),
+                configuration_revision: None,
+                logs: BrokerLogs {
+                    general: true,
+                    audit:   false,
+                },
+                maintenance_window: Some(MaintenanceWindow {
+                    day_of_week: String::from("SUNDAY"),
+                    time_of_day: String::from("03:00"),
+                    time_zone:   None,
+                }),
+                kms_key_id: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            MqResourceAddress::User {
+                region:   String::from("[region]"),
+                broker:   String::from("[broker_name]"),
+                username: String::from("[username]"),
+            },
+            MqResource::User(User {
+                password_secret_id: String::from("[secret_id]"),
+                console_access: true,
+                groups: vec![String::from("admins")],
+                replication_user: false,
+            })
+        ));
+
+        // The configuration's XML goes in [configuration_name].xml next to this file
+        res.push(skeleton!(
+            MqResourceAddress::Configuration {
+                region: String::from("[region]"),
+                name:   String::from("[configuration_name]"),
+            },
+            MqResource::Configuration(Configuration {
+                engine_type: String::from("ACTIVEMQ"),
+                engine_version: String::from("5.18"),
+                description: None,
+                data: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = MqResourceAddress::from_path(addr)?;
+
+        match addr {
+            MqResourceAddress::Broker { .. } => ron_check_eq::<Broker>(a, b),
+            MqResourceAddress::User { .. } => ron_check_eq::<User>(a, b),
+            MqResourceAddress::Configuration { .. } => ron_check_eq::<Configuration>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = MqResourceAddress::from_path(addr)?;
+
+        match addr {
+            MqResourceAddress::Broker { .. } => ron_check_syntax::<Broker>(a),
+            MqResourceAddress::User { .. } => ron_check_syntax::<User>(a),
+            MqResourceAddress::Configuration { .. } => ron_check_syntax::<Configuration>(a),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use std::{collections::HashMap, path::Path};
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_mq::types::{BrokerState, EngineType};
+
+use crate::{
+    addr::MqResourceAddress,
+    op_impl::{find_broker, find_configuration},
+    resource::{Broker, BrokerLogs, Configuration, MqResource, User},
+    tags::Tags,
+    util::{UNKNOWN_SECRET_ID, decode_data, from_weekly_start_time, read_local_user, resolve_configuration_data},
+};
+
+use super::MqConnector;
+
+impl MqConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = MqResourceAddress::from_path(addr)?;
+
+        match &addr {
+            MqResourceAddress::Broker { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(broker) = find_broker(&client, name).await? else {
+                    return Ok(None);
+                };
+                if broker.broker_state == Some(BrokerState::DeletionInProgress) {
+                    return Ok(None);
+                }
+
+                // Brokers report their configuration by ID, and the revision only matters if it isn't the latest
+                let (configuration, configuration_revision) =
+                    match broker.configurations.as_ref().and_then(|configurations| configurations.current.as_ref()) {
+                        Some(current) => {
+                            let configuration = client.describe_configuration().configuration_id(&current.id).send().await?;
+                            let latest = configuration.latest_revision.and_then(|latest| latest.revision);
+                            (configuration.name, current.revision.filter(|revision| Some(*revision) != latest))
+                        }
+                        None => (None, None),
+                    };
+
+                let mut subnet_ids = broker.subnet_ids.unwrap_or_default();
+                subnet_ids.sort();
+                let mut security_groups = broker.security_groups.unwrap_or_default();
+                security_groups.sort();
+
+                let resource = Broker {
+                    engine_type: broker.engine_type.map(|e| e.as_str().to_string()).unwrap_or_default(),
+                    engine_version: broker.engine_version.unwrap_or_default(),
+                    host_instance_type: broker.host_instance_type.unwrap_or_default(),
+                    deployment_mode: broker.deployment_mode.map(|m| m.as_str().to_string()).unwrap_or_default(),
+                    publicly_accessible: broker.publicly_accessible.unwrap_or(false),
+                    auto_minor_version_upgrade: broker.auto_minor_version_upgrade.unwrap_or(false),
+                    subnet_ids,
+                    security_groups,
+                    storage_type: broker.storage_type.map(|s| s.as_str().to_string()),
+                    configuration,
+                    configuration_revision,
+                    logs: broker
+                        .logs
+                        .map(|logs| BrokerLogs {
+                            general: logs.general.unwrap_or(false),
+                            audit:   logs.audit.unwrap_or(false),
+                        })
+                        .unwrap_or_default(),
+                    maintenance_window: broker.maintenance_window_start_time.map(from_weekly_start_time),
+                    kms_key_id: broker
+                        .encryption_options
+                        .filter(|encryption| !encryption.use_aws_owned_key)
+                        .and_then(|encryption| encryption.kms_key_id),
+                    tags: Tags::from(broker.tags),
+                };
+
+                get_resource_response!(
+                    MqResource::Broker(resource),
+                    [
+                        (String::from("broker_id"), broker.broker_id.unwrap_or_default()),
+                        (String::from("broker_arn"), broker.broker_arn.unwrap_or_default())
+                    ]
+                )
+            }
+            MqResourceAddress::User { region, broker, username } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(broker) = find_broker(&client, broker).await? else {
+                    return Ok(None);
+                };
+
+                if broker.engine_type == Some(EngineType::Rabbitmq) {
+                    let Some(user) = read_local_user(&self.prefix, &addr)? else {
+                        return Ok(None);
+                    };
+                    return Ok(Some(GetResourceResponse {
+                        resource_definition: MqResource::User(user).to_bytes()?,
+                        virt_addr: None,
+                        outputs: None,
+                    }));
+                }
+
+                let user = match client
+                    .describe_user()
+                    .broker_id(broker.broker_id.unwrap_or_default())
+                    .username(username)
+                    .send()
+                    .await
+                {
+                    Ok(user) => user,
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_not_found_exception()) => return Ok(None),
+                    Err(e) => return Err(e.into()),
+                };
+
+                // The password can't be read back
+                let password_secret_id = addr
+                    .get_output(&self.prefix, "password_secret_id")?
+                    .unwrap_or_else(|| String::from(UNKNOWN_SECRET_ID));
+
+                let mut groups = user.groups.unwrap_or_default();
+                groups.sort();
+
+                let resource = User {
+                    password_secret_id: password_secret_id.clone(),
+                    console_access: user.console_access.unwrap_or(false),
+                    groups,
+                    replication_user: user.replication_user.unwrap_or(false),
+                };
+
+                Ok(Some(GetResourceResponse {
+                    resource_definition: MqResource::User(resource).to_bytes()?,
+                    virt_addr: None,
+                    outputs: Some(HashMap::from([(String::from("password_secret_id"), password_secret_id)])),
+                }))
+            }
+            MqResourceAddress::Configuration { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(configuration) = find_configuration(&client, name).await? else {
+                    return Ok(None);
+                };
+
+                let configuration_id = configuration.id.unwrap_or_default();
+                let engine_type = configuration
+                    .engine_type
+                    .map(|e| e.as_str().to_string())
+                    .unwrap_or_default();
+                let latest = configuration.latest_revision;
+
+                let revision = client
+                    .describe_configuration_revision()
+                    .configuration_id(&configuration_id)
+                    .configuration_revision(latest.as_ref().and_then(|latest| latest.revision).unwrap_or(1).to_string())
+                    .send()
+                    .await?;
+                let deployed_data = decode_data(&revision.data.unwrap_or_default())?;
+
+                let resource = Configuration {
+                    engine_type: engine_type.clone(),
+                    engine_version: configuration.engine_version.unwrap_or_default(),
+                    description: latest.and_then(|latest| latest.description).filter(|d| !d.is_empty()),
+                    data: resolve_configuration_data(&self.prefix, region, name, &engine_type, deployed_data)?,
+                    tags: Tags::from(configuration.tags),
+                };
+
+                get_resource_response!(
+                    MqResource::Configuration(resource),
+                    [
+                        (String::from("configuration_id"), configuration_id),
+                        (String::from("configuration_arn"), configuration.arn.unwrap_or_default())
+                    ]
+                )
+            }
+        }
+    }
+}
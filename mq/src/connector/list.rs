@@ -0,0 +1,93 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+use aws_sdk_mq::types::{BrokerState, EngineType};
+
+use crate::addr::MqResourceAddress;
+
+use super::MqConnector;
+
+impl MqConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut next_token = None;
+            loop {
+                let resp = client.list_brokers().set_next_token(next_token).send().await?;
+
+                for broker in resp.broker_summaries() {
+                    if broker.broker_state == Some(BrokerState::DeletionInProgress) {
+                        continue;
+                    }
+                    let (Some(name), Some(broker_id)) = (&broker.broker_name, &broker.broker_id) else {
+                        continue;
+                    };
+
+                    results.push(
+                        MqResourceAddress::Broker {
+                            region: region.clone(),
+                            name:   name.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+
+                    // Amazon MQ only manages the users of ActiveMQ brokers
+                    if broker.engine_type != Some(EngineType::Activemq) {
+                        continue;
+                    }
+
+                    let users = client.list_users().broker_id(broker_id).send().await?;
+                    for user in users.users() {
+                        let Some(username) = &user.username else {
+                            continue;
+                        };
+
+                        results.push(
+                            MqResourceAddress::User {
+                                region:   region.clone(),
+                                broker:   name.clone(),
+                                username: username.clone(),
+                            }
+                            .to_path_buf(),
+                        );
+                    }
+                }
+
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+
+            let mut next_token = None;
+            loop {
+                let resp = client.list_configurations().set_next_token(next_token).send().await?;
+
+                for configuration in resp.configurations() {
+                    let Some(name) = &configuration.name else {
+                        continue;
+                    };
+
+                    results.push(
+                        MqResourceAddress::Configuration {
+                            region: region.clone(),
+                            name:   name.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
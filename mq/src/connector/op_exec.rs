@@ -0,0 +1,69 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{ConnectorOp, OpExecResponse, ResourceAddress},
+    error_util::invalid_op,
+};
+
+use crate::{addr::MqResourceAddress, op::MqConnectorOp, op_impl};
+
+use super::MqConnector;
+
+impl MqConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = MqResourceAddress::from_path(addr)?;
+        let op = MqConnectorOp::from_str(op)?;
+
+        match &addr {
+            MqResourceAddress::Broker { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    MqConnectorOp::CreateBroker(broker, users) => {
+                        let sm_client = self.get_or_init_secrets_client(region).await?;
+                        op_impl::create_broker(&client, &sm_client, name, &broker, &users).await
+                    }
+                    MqConnectorOp::UpdateBroker(broker) => op_impl::update_broker(&client, name, &broker).await,
+                    MqConnectorOp::UpdateBrokerTags(old_tags, new_tags) => {
+                        op_impl::update_broker_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    MqConnectorOp::RebootBroker => op_impl::reboot_broker(&client, name).await,
+                    MqConnectorOp::DeleteBroker => op_impl::delete_broker(&client, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            MqResourceAddress::User { region, broker, username } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    MqConnectorOp::CreateUser(user) => {
+                        let sm_client = self.get_or_init_secrets_client(region).await?;
+                        op_impl::create_user(&client, &sm_client, broker, username, &user).await
+                    }
+                    MqConnectorOp::UpdateUser(user) => {
+                        let sm_client = self.get_or_init_secrets_client(region).await?;
+                        op_impl::update_user(&client, &sm_client, broker, username, &user).await
+                    }
+                    MqConnectorOp::DeleteUser => op_impl::delete_user(&client, broker, username).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            MqResourceAddress::Configuration { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    MqConnectorOp::CreateConfiguration(configuration, data) => {
+                        op_impl::create_configuration(&client, name, &configuration, &data).await
+                    }
+                    MqConnectorOp::UpdateConfiguration(configuration, data) => {
+                        op_impl::update_configuration(&client, name, &configuration, &data).await
+                    }
+                    MqConnectorOp::UpdateConfigurationTags(old_tags, new_tags) => {
+                        op_impl::update_configuration_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,332 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::{
+    resource::{Broker, Configuration, User},
+    util::{UNKNOWN_SECRET_ID, configuration_data, read_broker_users},
+};
+
+use super::{MqConnector, MqConnectorOp, MqResourceAddress};
+
+const ENGINE_TYPES: &[&str] = &["ACTIVEMQ", "RABBITMQ"];
+const ACTIVEMQ_DEPLOYMENT_MODES: &[&str] = &["SINGLE_INSTANCE", "ACTIVE_STANDBY_MULTI_AZ"];
+const RABBITMQ_DEPLOYMENT_MODES: &[&str] = &["SINGLE_INSTANCE", "CLUSTER_MULTI_AZ"];
+
+/// Secret IDs reported as unknown by `get` have to be filled in before they can be used.
+fn check_secret_id(secret_id: &str, what: &str) -> anyhow::Result<()> {
+    if secret_id == UNKNOWN_SECRET_ID {
+        bail!(
+            "{} has a secret ID of {}: set it to the ID of the Secrets Manager secret that holds the value",
+            what,
+            UNKNOWN_SECRET_ID
+        );
+    }
+    Ok(())
+}
+
+fn check_engine_type(what: &str, engine_type: &str) -> anyhow::Result<()> {
+    if !ENGINE_TYPES.contains(&engine_type) {
+        bail!("{} has engine type {}: expected one of {}", what, engine_type, ENGINE_TYPES.join(", "));
+    }
+    Ok(())
+}
+
+/// Combinations that Amazon MQ would reject, caught at plan time instead.
+fn check_broker(name: &str, broker: &Broker) -> anyhow::Result<()> {
+    let what = format!("Amazon MQ broker {}", name);
+    check_engine_type(&what, &broker.engine_type)?;
+
+    let rabbitmq = broker.engine_type == "RABBITMQ";
+    let deployment_modes = if rabbitmq {
+        RABBITMQ_DEPLOYMENT_MODES
+    } else {
+        ACTIVEMQ_DEPLOYMENT_MODES
+    };
+    if !deployment_modes.contains(&broker.deployment_mode.as_str()) {
+        bail!(
+            "{} has deployment mode {}: expected one of {} for {}",
+            what,
+            broker.deployment_mode,
+            deployment_modes.join(", "),
+            broker.engine_type
+        );
+    }
+
+    if rabbitmq {
+        if broker.logs.audit {
+            bail!("{} runs RabbitMQ, which doesn't support audit logs", what);
+        }
+        if broker.storage_type.as_deref().is_some_and(|storage_type| storage_type != "EBS") {
+            bail!("{} runs RabbitMQ, which only supports EBS storage", what);
+        }
+    }
+
+    if broker.configuration.is_none() && broker.configuration_revision.is_some() {
+        bail!("{} sets configuration_revision without a configuration", what);
+    }
+
+    Ok(())
+}
+
+/// Amazon MQ fills in these fields when they're left out, so leaving them out doesn't change them.
+fn normalize_broker(old: &Broker, new: &mut Broker) {
+    if new.subnet_ids.is_empty() {
+        new.subnet_ids = old.subnet_ids.clone();
+    }
+    if new.security_groups.is_empty() {
+        new.security_groups = old.security_groups.clone();
+    }
+    if new.storage_type.is_none() {
+        new.storage_type = old.storage_type.clone();
+    }
+    // ActiveMQ brokers created without a configuration get a default one
+    if new.configuration.is_none() {
+        new.configuration = old.configuration.clone();
+        new.configuration_revision = old.configuration_revision;
+    }
+    if new.maintenance_window.is_none() {
+        new.maintenance_window = old.maintenance_window.clone();
+    }
+}
+
+/// The fields that Amazon MQ can't change once a broker exists.
+fn broker_fixed_fields(old: &Broker, new: &Broker) -> Vec<String> {
+    let mut fields = Vec::new();
+    if old.engine_type != new.engine_type {
+        fields.push(String::from("engine_type"));
+    }
+    if old.deployment_mode != new.deployment_mode {
+        fields.push(String::from("deployment_mode"));
+    }
+    if old.publicly_accessible != new.publicly_accessible {
+        fields.push(String::from("publicly_accessible"));
+    }
+    if old.subnet_ids != new.subnet_ids {
+        fields.push(String::from("subnet_ids"));
+    }
+    if old.storage_type != new.storage_type {
+        fields.push(String::from("storage_type"));
+    }
+    if old.kms_key_id != new.kms_key_id {
+        fields.push(String::from("kms_key_id"));
+    }
+    fields
+}
+
+/// Changes to these fields are left pending until the broker reboots.
+fn broker_needs_reboot(old: &Broker, new: &Broker) -> bool {
+    old.engine_version != new.engine_version
+        || old.host_instance_type != new.host_instance_type
+        || old.configuration != new.configuration
+        || old.configuration_revision != new.configuration_revision
+}
+
+impl MqConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = MqResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            MqResourceAddress::Broker { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_broker)) => {
+                    let new_broker: Broker = RON.from_str(&new_broker)?;
+                    check_broker(name, &new_broker)?;
+
+                    // Amazon MQ won't create a broker without a user, so the broker's users are created along with it
+                    let users = read_broker_users(&self.prefix, region, name)?;
+                    if users.is_empty() {
+                        bail!(
+                            "Amazon MQ broker {} has no users: add at least one under aws/mq/{}/brokers/{}/users/",
+                            name,
+                            region,
+                            name
+                        );
+                    }
+                    if new_broker.engine_type == "RABBITMQ" && users.len() > 1 {
+                        bail!(
+                            "Amazon MQ broker {} runs RabbitMQ, which is created with exactly one user: manage the others through RabbitMQ",
+                            name
+                        );
+                    }
+                    for (username, user) in &users {
+                        check_secret_id(&user.password_secret_id, &format!("User {}'s password", username))?;
+                    }
+
+                    let usernames: Vec<&str> = users.keys().map(String::as_str).collect();
+                    Ok(vec![connector_op!(
+                        MqConnectorOp::CreateBroker(new_broker, users.clone()),
+                        format!(
+                            "Create new Amazon MQ broker {} in region {} with users {}",
+                            name,
+                            region,
+                            usernames.join(", ")
+                        )
+                    )])
+                }
+                (Some(_old_broker), None) => Ok(vec![connector_op!(
+                    MqConnectorOp::DeleteBroker,
+                    format!(
+                        "DELETE Amazon MQ broker {} in region {}. Messages still on the broker are lost.",
+                        name, region
+                    )
+                )]),
+                (Some(old_broker), Some(new_broker)) => {
+                    let old_broker: Broker = RON.from_str(&old_broker)?;
+                    let mut new_broker: Broker = RON.from_str(&new_broker)?;
+                    check_broker(name, &new_broker)?;
+                    normalize_broker(&old_broker, &mut new_broker);
+
+                    let fixed_fields = broker_fixed_fields(&old_broker, &new_broker);
+                    if !fixed_fields.is_empty() {
+                        bail!(
+                            "Amazon MQ broker {} can't change {} after creation. Create a new broker under another name instead.",
+                            name,
+                            fixed_fields.join(", ")
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_broker.tags != new_broker.tags {
+                        let diff = diff_ron_values(&old_broker.tags, &new_broker.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            MqConnectorOp::UpdateBrokerTags(old_broker.tags.clone(), new_broker.tags.clone()),
+                            format!("Modify tags for Amazon MQ broker `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_broker.clone();
+                    old_settings.tags = new_broker.tags.clone();
+                    if old_settings != new_broker {
+                        let diff = diff_ron_values(&old_settings, &new_broker).unwrap_or_default();
+                        let needs_reboot = broker_needs_reboot(&old_broker, &new_broker);
+                        ops.push(connector_op!(
+                            MqConnectorOp::UpdateBroker(new_broker),
+                            format!("Modify Amazon MQ broker `{}`\n{}", name, diff)
+                        ));
+
+                        if needs_reboot {
+                            ops.push(connector_op!(
+                                MqConnectorOp::RebootBroker,
+                                format!(
+                                    "Reboot Amazon MQ broker `{}` to apply the change. Single-instance brokers are unavailable while rebooting.",
+                                    name
+                                )
+                            ));
+                        }
+                    }
+
+                    Ok(ops)
+                }
+            },
+            MqResourceAddress::User { broker, username, .. } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_user)) => {
+                    let new_user: User = RON.from_str(&new_user)?;
+                    check_secret_id(&new_user.password_secret_id, &format!("User {}'s password", username))?;
+                    Ok(vec![connector_op!(
+                        MqConnectorOp::CreateUser(new_user),
+                        format!("Create user {} on Amazon MQ broker {}", username, broker)
+                    )])
+                }
+                (Some(_old_user), None) => Ok(vec![connector_op!(
+                    MqConnectorOp::DeleteUser,
+                    format!(
+                        "DELETE user {} from Amazon MQ broker {}. This takes effect when the broker next reboots.",
+                        username, broker
+                    )
+                )]),
+                (Some(old_user), Some(new_user)) => {
+                    let old_user: User = RON.from_str(&old_user)?;
+                    let new_user: User = RON.from_str(&new_user)?;
+                    check_secret_id(&new_user.password_secret_id, &format!("User {}'s password", username))?;
+
+                    if old_user == new_user {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_user, &new_user).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        MqConnectorOp::UpdateUser(new_user),
+                        format!(
+                            "Modify user {} on Amazon MQ broker {}. This takes effect when the broker next reboots.\n{}",
+                            username, broker, diff
+                        )
+                    )])
+                }
+            },
+            MqResourceAddress::Configuration { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_configuration)) => {
+                    let new_configuration: Configuration = RON.from_str(&new_configuration)?;
+                    check_engine_type(&format!("Amazon MQ configuration {}", name), &new_configuration.engine_type)?;
+                    let data = configuration_data(&self.prefix, region, name, &new_configuration)?;
+                    Ok(vec![connector_op!(
+                        MqConnectorOp::CreateConfiguration(new_configuration, data),
+                        format!("Create new Amazon MQ configuration {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_configuration), None) => bail!(
+                    "Amazon MQ can't delete configurations, so configuration {} in region {} can't be removed. Restore its file to keep managing it.",
+                    name,
+                    region
+                ),
+                (Some(old_configuration), Some(new_configuration)) => {
+                    let old_configuration: Configuration = RON.from_str(&old_configuration)?;
+                    let new_configuration: Configuration = RON.from_str(&new_configuration)?;
+
+                    if old_configuration.engine_type != new_configuration.engine_type
+                        || old_configuration.engine_version != new_configuration.engine_version
+                    {
+                        bail!(
+                            "Amazon MQ configuration {} can't change its engine type or version after creation. Create a new configuration under another name instead.",
+                            name
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_configuration.tags != new_configuration.tags {
+                        let diff = diff_ron_values(&old_configuration.tags, &new_configuration.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            MqConnectorOp::UpdateConfigurationTags(old_configuration.tags.clone(), new_configuration.tags.clone()),
+                            format!("Modify tags for Amazon MQ configuration `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_configuration.clone();
+                    old_settings.tags = new_configuration.tags.clone();
+                    if old_settings != new_configuration {
+                        let data = configuration_data(&self.prefix, region, name, &new_configuration)?;
+                        let diff = diff_ron_values(&old_settings, &new_configuration).unwrap_or_default();
+                        ops.push(connector_op!(
+                            MqConnectorOp::UpdateConfiguration(new_configuration, data),
+                            format!(
+                                "Create a new revision of Amazon MQ configuration `{}`. Brokers that don't pin a revision move to it on their next plan.\n{}",
+                                name, diff
+                            )
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+        }
+    }
+}
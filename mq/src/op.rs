@@ -0,0 +1,48 @@
+use std::collections::BTreeMap;
+
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{Broker, Configuration, User},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum MqConnectorOp {
+    /// Creates the broker with the given users, and waits for it to be running.
+    CreateBroker(Broker, BTreeMap<String, User>),
+    /// Applies the broker's engine version, instance type, security groups, configuration, logs and
+    /// maintenance window.
+    UpdateBroker(Broker),
+    UpdateBrokerTags(Tags, Tags),
+    /// Reboots the broker to apply pending changes, and waits for it to be running again.
+    RebootBroker,
+    DeleteBroker,
+
+    /// Updates the user instead if it was already created along with its broker.
+    CreateUser(User),
+    UpdateUser(User),
+    DeleteUser,
+
+    /// Creates the configuration and its first revision with the given data.
+    CreateConfiguration(Configuration, String),
+    /// Creates a new revision with the configuration's description and the given data.
+    UpdateConfiguration(Configuration, String),
+    UpdateConfigurationTags(Tags, Tags),
+}
+
+impl ConnectorOp for MqConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
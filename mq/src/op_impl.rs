@@ -0,0 +1,470 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    time::Duration,
+};
+
+use anyhow::{Context, bail};
+use autoschematic_core::{connector::OpExecResponse, op_exec_output};
+use aws_sdk_mq::{
+    operation::{describe_broker::DescribeBrokerOutput, describe_configuration::DescribeConfigurationOutput},
+    types::{BrokerStorageType, ConfigurationId, DeploymentMode, EncryptionOptions, EngineType},
+};
+
+use crate::{
+    resource::{Broker, Configuration, User},
+    tags::{Tags, tag_diff},
+    util::{encode_data, read_secret, to_logs, to_weekly_start_time},
+};
+
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// Brokers can take up to half an hour to create, and multi-AZ brokers several minutes to reboot.
+const STATUS_MAX_POLLS: usize = 180;
+
+/// Polls `status` until it returns `target`, or until the broker no longer exists if `target` is None.
+async fn wait_for_status<F, Fut>(description: &str, target: Option<&str>, status: F) -> anyhow::Result<()>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = anyhow::Result<Option<String>>>,
+{
+    for _ in 0..STATUS_MAX_POLLS {
+        let state = status().await?;
+        if state.as_deref() == target {
+            return Ok(());
+        }
+        if state.as_deref() == Some("CREATION_FAILED") {
+            bail!("{} failed to create", description);
+        }
+        tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    }
+
+    match target {
+        Some(target) => bail!("Timed out waiting for {} to become {}", description, target),
+        None => bail!("Timed out waiting for {} to be deleted", description),
+    }
+}
+
+/// DescribeBroker only takes broker IDs, so this finds the broker by listing them.
+pub async fn find_broker(client: &aws_sdk_mq::Client, name: &str) -> anyhow::Result<Option<DescribeBrokerOutput>> {
+    let mut next_token = None;
+    loop {
+        let resp = client.list_brokers().set_next_token(next_token).send().await?;
+
+        for broker in resp.broker_summaries() {
+            if broker.broker_name.as_deref() != Some(name) {
+                continue;
+            }
+            let Some(broker_id) = &broker.broker_id else {
+                continue;
+            };
+
+            return Ok(Some(client.describe_broker().broker_id(broker_id).send().await?));
+        }
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            return Ok(None);
+        }
+    }
+}
+
+/// DescribeConfiguration only takes configuration IDs, so this finds the configuration by listing them.
+pub async fn find_configuration(client: &aws_sdk_mq::Client, name: &str) -> anyhow::Result<Option<DescribeConfigurationOutput>> {
+    let mut next_token = None;
+    loop {
+        let resp = client.list_configurations().set_next_token(next_token).send().await?;
+
+        for configuration in resp.configurations() {
+            if configuration.name.as_deref() != Some(name) {
+                continue;
+            }
+            let Some(configuration_id) = &configuration.id else {
+                continue;
+            };
+
+            return Ok(Some(
+                client.describe_configuration().configuration_id(configuration_id).send().await?,
+            ));
+        }
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            return Ok(None);
+        }
+    }
+}
+
+async fn broker_status(client: &aws_sdk_mq::Client, name: &str) -> anyhow::Result<Option<String>> {
+    Ok(find_broker(client, name)
+        .await?
+        .map(|broker| broker.broker_state.map(|state| state.as_str().to_string()).unwrap_or_default()))
+}
+
+async fn wait_for_broker(client: &aws_sdk_mq::Client, name: &str, target: Option<&str>) -> anyhow::Result<()> {
+    wait_for_status(&format!("Amazon MQ broker {}", name), target, || broker_status(client, name)).await
+}
+
+async fn broker_id(client: &aws_sdk_mq::Client, name: &str) -> anyhow::Result<String> {
+    find_broker(client, name)
+        .await?
+        .and_then(|broker| broker.broker_id)
+        .with_context(|| format!("Amazon MQ broker {} not found", name))
+}
+
+/// Resolves a configuration name to its ID and the revision to run: `revision`, or the latest if None.
+async fn configuration_id(client: &aws_sdk_mq::Client, name: &str, revision: Option<i32>) -> anyhow::Result<ConfigurationId> {
+    let configuration = find_configuration(client, name)
+        .await?
+        .with_context(|| format!("Amazon MQ configuration {} not found", name))?;
+
+    let revision = revision.or_else(|| configuration.latest_revision.and_then(|latest| latest.revision));
+
+    Ok(ConfigurationId::builder()
+        .set_id(configuration.id)
+        .set_revision(revision)
+        .build()?)
+}
+
+async fn to_user(sm_client: &aws_sdk_secretsmanager::Client, username: &str, user: &User) -> anyhow::Result<aws_sdk_mq::types::User> {
+    Ok(aws_sdk_mq::types::User::builder()
+        .username(username)
+        .password(read_secret(sm_client, &user.password_secret_id).await?)
+        .console_access(user.console_access)
+        .set_groups(Some(user.groups.clone()))
+        .replication_user(user.replication_user)
+        .build()?)
+}
+
+/// CreateTags and DeleteTags take the resource's ARN.
+async fn update_tags(client: &aws_sdk_mq::Client, arn: &str, old_tags: &Tags, new_tags: &Tags) -> anyhow::Result<()> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .delete_tags()
+            .resource_arn(arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client.create_tags().resource_arn(arn).set_tags(Some(new_tagset)).send().await?;
+    }
+
+    Ok(())
+}
+
+pub async fn create_broker(
+    client: &aws_sdk_mq::Client,
+    sm_client: &aws_sdk_secretsmanager::Client,
+    name: &str,
+    broker: &Broker,
+    users: &BTreeMap<String, User>,
+) -> anyhow::Result<OpExecResponse> {
+    let mut request = client
+        .create_broker()
+        .broker_name(name)
+        .engine_type(EngineType::from(broker.engine_type.as_str()))
+        .engine_version(&broker.engine_version)
+        .host_instance_type(&broker.host_instance_type)
+        .deployment_mode(DeploymentMode::from(broker.deployment_mode.as_str()))
+        .publicly_accessible(broker.publicly_accessible)
+        .auto_minor_version_upgrade(broker.auto_minor_version_upgrade)
+        .logs(to_logs(&broker.logs))
+        .set_tags(broker.tags.clone().into());
+
+    if !broker.subnet_ids.is_empty() {
+        request = request.set_subnet_ids(Some(broker.subnet_ids.clone()));
+    }
+    if !broker.security_groups.is_empty() {
+        request = request.set_security_groups(Some(broker.security_groups.clone()));
+    }
+    if let Some(storage_type) = &broker.storage_type {
+        request = request.storage_type(BrokerStorageType::from(storage_type.as_str()));
+    }
+    if let Some(configuration) = &broker.configuration {
+        request = request.configuration(configuration_id(client, configuration, broker.configuration_revision).await?);
+    }
+    if let Some(window) = &broker.maintenance_window {
+        request = request.maintenance_window_start_time(to_weekly_start_time(window)?);
+    }
+    if let Some(kms_key_id) = &broker.kms_key_id {
+        request = request.encryption_options(
+            EncryptionOptions::builder()
+                .use_aws_owned_key(false)
+                .kms_key_id(kms_key_id)
+                .build()?,
+        );
+    }
+    for (username, user) in users {
+        request = request.users(to_user(sm_client, username, user).await?);
+    }
+
+    let resp = request.send().await?;
+
+    wait_for_broker(client, name, Some("RUNNING")).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("broker_id"), resp.broker_id),
+            (String::from("broker_arn"), resp.broker_arn),
+        ])),
+        friendly_message: Some(format!("Created Amazon MQ broker {}", name)),
+    })
+}
+
+pub async fn update_broker(client: &aws_sdk_mq::Client, name: &str, broker: &Broker) -> anyhow::Result<OpExecResponse> {
+    let mut request = client
+        .update_broker()
+        .broker_id(broker_id(client, name).await?)
+        .engine_version(&broker.engine_version)
+        .host_instance_type(&broker.host_instance_type)
+        .auto_minor_version_upgrade(broker.auto_minor_version_upgrade)
+        .logs(to_logs(&broker.logs));
+
+    if !broker.security_groups.is_empty() {
+        request = request.set_security_groups(Some(broker.security_groups.clone()));
+    }
+    if let Some(configuration) = &broker.configuration {
+        request = request.configuration(configuration_id(client, configuration, broker.configuration_revision).await?);
+    }
+    if let Some(window) = &broker.maintenance_window {
+        request = request.maintenance_window_start_time(to_weekly_start_time(window)?);
+    }
+
+    request.send().await?;
+
+    op_exec_output!(format!("Updated Amazon MQ broker {}", name))
+}
+
+pub async fn update_broker_tags(
+    client: &aws_sdk_mq::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = find_broker(client, name)
+        .await?
+        .and_then(|broker| broker.broker_arn)
+        .with_context(|| format!("Amazon MQ broker {} not found", name))?;
+
+    update_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for Amazon MQ broker {}", name))
+}
+
+pub async fn reboot_broker(client: &aws_sdk_mq::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    client.reboot_broker().broker_id(broker_id(client, name).await?).send().await?;
+
+    // The broker can still report RUNNING for a moment after the reboot is accepted
+    tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    wait_for_broker(client, name, Some("RUNNING")).await?;
+
+    op_exec_output!(format!("Rebooted Amazon MQ broker {}", name))
+}
+
+pub async fn delete_broker(client: &aws_sdk_mq::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    client.delete_broker().broker_id(broker_id(client, name).await?).send().await?;
+
+    wait_for_broker(client, name, None).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("broker_id"), None),
+            (String::from("broker_arn"), None),
+        ])),
+        friendly_message: Some(format!("Deleted Amazon MQ broker {}", name)),
+    })
+}
+
+/// Users given in CreateBroker already exist once the broker is running, so a conflict here means
+/// the user just needs its settings applied. RabbitMQ users can only be created with their broker.
+pub async fn create_user(
+    client: &aws_sdk_mq::Client,
+    sm_client: &aws_sdk_secretsmanager::Client,
+    broker: &str,
+    username: &str,
+    user: &User,
+) -> anyhow::Result<OpExecResponse> {
+    let broker_detail = find_broker(client, broker)
+        .await?
+        .with_context(|| format!("Amazon MQ broker {} not found: create it first", broker))?;
+
+    if broker_detail.engine_type == Some(EngineType::Rabbitmq) {
+        return op_exec_output!(format!(
+            "User {} was created along with RabbitMQ broker {}; Amazon MQ doesn't manage RabbitMQ users after that",
+            username, broker
+        ));
+    }
+
+    let broker_id = broker_detail.broker_id.unwrap_or_default();
+
+    let result = client
+        .create_user()
+        .broker_id(&broker_id)
+        .username(username)
+        .password(read_secret(sm_client, &user.password_secret_id).await?)
+        .console_access(user.console_access)
+        .set_groups(Some(user.groups.clone()))
+        .replication_user(user.replication_user)
+        .send()
+        .await;
+
+    match result {
+        Ok(_) => {}
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_conflict_exception()) => {
+            return update_user(client, sm_client, broker, username, user).await;
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(
+            String::from("password_secret_id"),
+            Some(user.password_secret_id.clone()),
+        )])),
+        friendly_message: Some(format!("Created user {} on Amazon MQ broker {}", username, broker)),
+    })
+}
+
+pub async fn update_user(
+    client: &aws_sdk_mq::Client,
+    sm_client: &aws_sdk_secretsmanager::Client,
+    broker: &str,
+    username: &str,
+    user: &User,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .update_user()
+        .broker_id(broker_id(client, broker).await?)
+        .username(username)
+        .password(read_secret(sm_client, &user.password_secret_id).await?)
+        .console_access(user.console_access)
+        .set_groups(Some(user.groups.clone()))
+        .replication_user(user.replication_user)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(
+            String::from("password_secret_id"),
+            Some(user.password_secret_id.clone()),
+        )])),
+        friendly_message: Some(format!("Updated user {} on Amazon MQ broker {}", username, broker)),
+    })
+}
+
+pub async fn delete_user(client: &aws_sdk_mq::Client, broker: &str, username: &str) -> anyhow::Result<OpExecResponse> {
+    client
+        .delete_user()
+        .broker_id(broker_id(client, broker).await?)
+        .username(username)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("password_secret_id"), None)])),
+        friendly_message: Some(format!("Deleted user {} from Amazon MQ broker {}", username, broker)),
+    })
+}
+
+/// CreateConfiguration starts the configuration off with Amazon MQ's default data, so the given
+/// data goes into a second revision straight away.
+pub async fn create_configuration(
+    client: &aws_sdk_mq::Client,
+    name: &str,
+    configuration: &Configuration,
+    data: &str,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_configuration()
+        .name(name)
+        .engine_type(EngineType::from(configuration.engine_type.as_str()))
+        .engine_version(&configuration.engine_version)
+        .set_tags(configuration.tags.clone().into())
+        .send()
+        .await?;
+
+    let configuration_id = resp
+        .id
+        .with_context(|| format!("CreateConfiguration returned no ID for Amazon MQ configuration {}", name))?;
+
+    client
+        .update_configuration()
+        .configuration_id(&configuration_id)
+        .data(encode_data(data))
+        .set_description(configuration.description.clone())
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("configuration_id"), Some(configuration_id)),
+            (String::from("configuration_arn"), resp.arn),
+        ])),
+        friendly_message: Some(format!("Created Amazon MQ configuration {}", name)),
+    })
+}
+
+pub async fn update_configuration(
+    client: &aws_sdk_mq::Client,
+    name: &str,
+    configuration: &Configuration,
+    data: &str,
+) -> anyhow::Result<OpExecResponse> {
+    let configuration_id = find_configuration(client, name)
+        .await?
+        .and_then(|configuration| configuration.id)
+        .with_context(|| format!("Amazon MQ configuration {} not found", name))?;
+
+    let resp = client
+        .update_configuration()
+        .configuration_id(&configuration_id)
+        .data(encode_data(data))
+        .set_description(configuration.description.clone())
+        .send()
+        .await?;
+
+    // Amazon MQ accepts data it can't fully apply, and reports what it dropped as warnings
+    let warnings: Vec<String> = resp
+        .warnings()
+        .iter()
+        .map(|warning| {
+            format!(
+                "{} {}: {}",
+                warning.element_name.as_deref().unwrap_or_default(),
+                warning.attribute_name.as_deref().unwrap_or_default(),
+                warning.reason.as_ref().map(|reason| reason.as_str()).unwrap_or_default()
+            )
+        })
+        .collect();
+
+    let revision = resp.latest_revision.and_then(|latest| latest.revision).unwrap_or_default();
+    if warnings.is_empty() {
+        op_exec_output!(format!("Created revision {} of Amazon MQ configuration {}", revision, name))
+    } else {
+        op_exec_output!(format!(
+            "Created revision {} of Amazon MQ configuration {}, with warnings:\n{}",
+            revision,
+            name,
+            warnings.join("\n")
+        ))
+    }
+}
+
+pub async fn update_configuration_tags(
+    client: &aws_sdk_mq::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = find_configuration(client, name)
+        .await?
+        .and_then(|configuration| configuration.arn)
+        .with_context(|| format!("Amazon MQ configuration {} not found", name))?;
+
+    update_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for Amazon MQ configuration {}", name))
+}
@@ -0,0 +1,148 @@
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::MqResourceAddress, tags::Tags};
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_deployment_mode() -> String {
+    String::from("SINGLE_INSTANCE")
+}
+
+/// An Amazon MQ broker.
+///
+/// The engine type, deployment mode, storage type, subnets, public accessibility and encryption key
+/// are fixed at creation. Changes to the engine version, instance type or configuration take effect
+/// when the broker is rebooted, which the plan does straight after updating it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Broker {
+    /// ACTIVEMQ or RABBITMQ.
+    pub engine_type: String,
+    pub engine_version: String,
+    /// e.g. mq.t3.micro or mq.m5.large.
+    pub host_instance_type: String,
+    /// SINGLE_INSTANCE, ACTIVE_STANDBY_MULTI_AZ (ActiveMQ) or CLUSTER_MULTI_AZ (RabbitMQ).
+    #[serde(default = "default_deployment_mode")]
+    pub deployment_mode: String,
+    #[serde(default)]
+    pub publicly_accessible: bool,
+    #[serde(default = "default_true")]
+    pub auto_minor_version_upgrade: bool,
+    /// One subnet for single-instance brokers, two for ActiveMQ active/standby brokers, and up to
+    /// three for RabbitMQ clusters. If empty, Amazon MQ uses the default VPC's subnets.
+    #[serde(default)]
+    pub subnet_ids: Vec<String>,
+    #[serde(default)]
+    pub security_groups: Vec<String>,
+    /// EBS or EFS. RabbitMQ brokers only support EBS.
+    pub storage_type: Option<String>,
+    /// The name of a configuration in the same region.
+    pub configuration: Option<String>,
+    /// The revision of `configuration` to run. If None, the broker runs the latest revision.
+    pub configuration_revision: Option<i32>,
+    #[serde(default)]
+    pub logs: BrokerLogs,
+    /// If None, Amazon MQ picks a maintenance window.
+    pub maintenance_window: Option<MaintenanceWindow>,
+    /// If None, the broker is encrypted with a key owned by Amazon MQ.
+    pub kms_key_id: Option<String>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct BrokerLogs {
+    #[serde(default)]
+    pub general: bool,
+    /// ActiveMQ only.
+    #[serde(default)]
+    pub audit: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MaintenanceWindow {
+    /// e.g. MONDAY.
+    pub day_of_week: String,
+    /// 24-hour HH:MM.
+    pub time_of_day: String,
+    /// e.g. UTC or America/New_York. If None, UTC.
+    pub time_zone: Option<String>,
+}
+
+/// A broker user.
+///
+/// Amazon MQ only manages the users of ActiveMQ brokers. A RabbitMQ broker's file under users/
+/// gives the administrator that the broker is created with, and other RabbitMQ users are managed
+/// through RabbitMQ itself.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct User {
+    /// The ID of a Secrets Manager secret holding the password as a plain string.
+    /// Passwords can't be read back, so this is only known for users created or updated here.
+    pub password_secret_id: String,
+    /// Whether the user can sign in to the ActiveMQ web console.
+    #[serde(default)]
+    pub console_access: bool,
+    #[serde(default)]
+    pub groups: Vec<String>,
+    /// Whether the user is used for cross-region data replication.
+    #[serde(default)]
+    pub replication_user: bool,
+}
+
+/// A broker configuration. Every change to its description or data creates a new revision. Brokers
+/// that don't pin a configuration_revision are moved to the new revision, and rebooted, the next
+/// time they're planned.
+///
+/// Amazon MQ can't delete configurations.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Configuration {
+    /// ACTIVEMQ or RABBITMQ.
+    pub engine_type: String,
+    pub engine_version: String,
+    /// The description of the latest revision.
+    pub description: Option<String>,
+    /// The configuration's ActiveMQ XML or RabbitMQ config. If None, it's read from the file next
+    /// to this one: <name>.xml for ActiveMQ, <name>.conf for RabbitMQ.
+    pub data: Option<String>,
+    pub tags: Tags,
+}
+
+pub enum MqResource {
+    Broker(Broker),
+    User(User),
+    Configuration(Configuration),
+}
+
+impl Resource for MqResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            MqResource::Broker(broker) => Ok(RON.to_string_pretty(&broker, pretty_config)?.into()),
+            MqResource::User(user) => Ok(RON.to_string_pretty(&user, pretty_config)?.into()),
+            MqResource::Configuration(configuration) => Ok(RON.to_string_pretty(&configuration, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = MqResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            MqResourceAddress::Broker { .. } => Ok(MqResource::Broker(RON.from_str(s)?)),
+            MqResourceAddress::User { .. } => Ok(MqResource::User(RON.from_str(s)?)),
+            MqResourceAddress::Configuration { .. } => Ok(MqResource::Configuration(RON.from_str(s)?)),
+        }
+    }
+}
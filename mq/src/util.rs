@@ -0,0 +1,138 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use autoschematic_core::{connector::ResourceAddress, util::RON};
+use aws_sdk_mq::types::{DayOfWeek, Logs, WeeklyStartTime};
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+use crate::{
+    addr::MqResourceAddress,
+    resource::{BrokerLogs, Configuration, MaintenanceWindow, User},
+};
+
+/// Stands in for a secret ID when Amazon MQ reports a user whose password this repository didn't set.
+pub const UNKNOWN_SECRET_ID: &str = "[unknown]";
+
+/// Reads a user's password from Secrets Manager. The secret must be a plain string, not JSON.
+pub async fn read_secret(client: &aws_sdk_secretsmanager::Client, secret_id: &str) -> anyhow::Result<String> {
+    let resp = client
+        .get_secret_value()
+        .secret_id(secret_id)
+        .send()
+        .await
+        .with_context(|| format!("Failed to read secret {}", secret_id))?;
+    resp.secret_string
+        .with_context(|| format!("Secret {} has no string value", secret_id))
+}
+
+/// The file next to a configuration's own that holds its data.
+pub fn configuration_data_path(prefix: &Path, region: &str, name: &str, engine_type: &str) -> PathBuf {
+    let extension = if engine_type == "RABBITMQ" { "conf" } else { "xml" };
+    prefix.join(format!("aws/mq/{region}/configurations/{name}.{extension}"))
+}
+
+/// Works out whether a configuration's deployed data came from its data file in this repository.
+///
+/// If the data file's contents are exactly `deployed_data`, returns None so that the remote state
+/// reads back the same as a configuration file without inline data. Otherwise returns `deployed_data`,
+/// so that editing the data file shows up as a diff.
+pub fn resolve_configuration_data(
+    prefix: &Path,
+    region: &str,
+    name: &str,
+    engine_type: &str,
+    deployed_data: String,
+) -> anyhow::Result<Option<String>> {
+    let data_path = configuration_data_path(prefix, region, name, engine_type);
+    if data_path.is_file() && std::fs::read_to_string(&data_path)? == deployed_data {
+        Ok(None)
+    } else {
+        Ok(Some(deployed_data))
+    }
+}
+
+/// Returns the data to deploy for `configuration`, reading it from its data file if it isn't inline.
+pub fn configuration_data(prefix: &Path, region: &str, name: &str, configuration: &Configuration) -> anyhow::Result<String> {
+    match &configuration.data {
+        Some(data) => Ok(data.clone()),
+        None => {
+            let data_path = configuration_data_path(prefix, region, name, &configuration.engine_type);
+            std::fs::read_to_string(&data_path).with_context(|| {
+                format!(
+                    "Failed to read data file {} for Amazon MQ configuration {}",
+                    data_path.display(),
+                    name
+                )
+            })
+        }
+    }
+}
+
+pub fn encode_data(data: &str) -> String {
+    STANDARD.encode(data)
+}
+
+pub fn decode_data(data: &str) -> anyhow::Result<String> {
+    Ok(String::from_utf8(STANDARD.decode(data)?)?)
+}
+
+/// Reads the user files under a broker's users/ directory, keyed by username.
+pub fn read_broker_users(prefix: &Path, region: &str, broker: &str) -> anyhow::Result<BTreeMap<String, User>> {
+    let mut users = BTreeMap::new();
+
+    let users_dir = prefix.join(format!("aws/mq/{region}/brokers/{broker}/users"));
+    if !users_dir.is_dir() {
+        return Ok(users);
+    }
+
+    for entry in std::fs::read_dir(&users_dir)? {
+        let path = entry?.path();
+        let Some(username) = path
+            .file_name()
+            .and_then(|f| f.to_str())
+            .and_then(|f| f.strip_suffix(".ron"))
+        else {
+            continue;
+        };
+
+        let user: User = RON
+            .from_str(&std::fs::read_to_string(&path)?)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        users.insert(username.to_string(), user);
+    }
+
+    Ok(users)
+}
+
+/// Amazon MQ can't report a RabbitMQ broker's users, so the user's own file is taken as its state.
+pub fn read_local_user(prefix: &Path, addr: &MqResourceAddress) -> anyhow::Result<Option<User>> {
+    let path = prefix.join(addr.to_path_buf());
+    if !path.is_file() {
+        return Ok(None);
+    }
+    Ok(Some(RON.from_str(&std::fs::read_to_string(&path)?)?))
+}
+
+pub fn to_logs(logs: &BrokerLogs) -> Logs {
+    Logs::builder().general(logs.general).audit(logs.audit).build()
+}
+
+pub fn to_weekly_start_time(window: &MaintenanceWindow) -> anyhow::Result<WeeklyStartTime> {
+    Ok(WeeklyStartTime::builder()
+        .day_of_week(DayOfWeek::from(window.day_of_week.as_str()))
+        .time_of_day(&window.time_of_day)
+        .set_time_zone(window.time_zone.clone())
+        .build()?)
+}
+
+pub fn from_weekly_start_time(window: WeeklyStartTime) -> MaintenanceWindow {
+    MaintenanceWindow {
+        day_of_week: window.day_of_week.as_str().to_string(),
+        time_of_day: window.time_of_day,
+        // Amazon MQ reports UTC when no time zone was given
+        time_zone:   window.time_zone.filter(|tz| tz != "UTC"),
+    }
+}
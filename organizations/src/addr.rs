@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum OrganizationsResourceAddress {
+    /// `path` runs from the top-level OU under the root down to this OU, e.g. `["Workloads", "Prod"]`.
+    OrganizationalUnit { path: Vec<String> },
+    Account { account_id: String },
+    ServiceControlPolicy { name: String },
+    /// The roots, OUs and accounts a policy is attached to. Also covers AWS managed policies like FullAWSAccess.
+    PolicyAttachments { policy_name: String },
+}
+
+impl ResourceAddress for OrganizationsResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            OrganizationsResourceAddress::OrganizationalUnit { path } => {
+                PathBuf::from(format!("aws/organizations/ous/{}.ron", path.join("/")))
+            }
+            OrganizationsResourceAddress::Account { account_id } => {
+                PathBuf::from(format!("aws/organizations/accounts/{account_id}.ron"))
+            }
+            OrganizationsResourceAddress::ServiceControlPolicy { name } => {
+                PathBuf::from(format!("aws/organizations/scps/{name}.ron"))
+            }
+            OrganizationsResourceAddress::PolicyAttachments { policy_name } => {
+                PathBuf::from(format!("aws/organizations/policy_attachments/{policy_name}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "organizations", "ous", parents @ .., name] if name.ends_with(".ron") => {
+                let mut path: Vec<String> = parents.iter().map(|p| p.to_string()).collect();
+                path.push(name.strip_suffix(".ron").unwrap().to_string());
+                Ok(OrganizationsResourceAddress::OrganizationalUnit { path })
+            }
+            ["aws", "organizations", "accounts", account_id] if account_id.ends_with(".ron") => {
+                Ok(OrganizationsResourceAddress::Account {
+                    account_id: account_id.strip_suffix(".ron").unwrap().to_string(),
+                })
+            }
+            ["aws", "organizations", "scps", name] if name.ends_with(".ron") => {
+                Ok(OrganizationsResourceAddress::ServiceControlPolicy {
+                    name: name.strip_suffix(".ron").unwrap().to_string(),
+                })
+            }
+            ["aws", "organizations", "policy_attachments", policy_name] if policy_name.ends_with(".ron") => {
+                Ok(OrganizationsResourceAddress::PolicyAttachments {
+                    policy_name: policy_name.strip_suffix(".ron").unwrap().to_string(),
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for OrganizationsResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/organizations/ous/<parent_ou_names...>/<ou_name>.ron",
+                description: "An organizational unit, addressed by its path of OU names below the root",
+                example:     "aws/organizations/ous/Workloads/Prod.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/organizations/accounts/<account_id>.ron",
+                description: "An existing member account and the OU it sits in (import-only)",
+                example:     "aws/organizations/accounts/123456789012.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/organizations/scps/<policy_name>.ron",
+                description: "A service control policy",
+                example:     "aws/organizations/scps/DenyLeaveOrganization.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/organizations/policy_attachments/<policy_name>.ron",
+                description: "The roots, OUs and accounts a service control policy is attached to",
+                example:     "aws/organizations/policy_attachments/DenyLeaveOrganization.ron",
+            },
+        ]
+    }
+}
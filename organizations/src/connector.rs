@@ -0,0 +1,213 @@
+pub use crate::addr::OrganizationsResourceAddress;
+pub use crate::op::OrganizationsConnectorOp;
+pub use crate::resource::OrganizationsResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{RON, ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::OrganizationsConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{Account, OrganizationalUnit, Parent, PolicyAttachments, PolicyTarget, ServiceControlPolicy};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct OrganizationsConnector {
+    client: Mutex<Option<Arc<aws_sdk_organizations::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<OrganizationsConnectorConfig>,
+    prefix: PathBuf,
+}
+
+impl OrganizationsConnector {
+    pub async fn get_or_init_client(&self) -> anyhow::Result<Arc<aws_sdk_organizations::Client>> {
+        let mut client = self.client.lock().await;
+
+        if let Some(client) = &*client {
+            return Ok(client.clone());
+        }
+
+        // Organizations is a global service, served from us-east-1
+        let region = RegionProviderChain::first_try(Region::new("us-east-1"));
+
+        let config = aws_config::defaults(BehaviorVersion::latest())
+            .region(region)
+            .timeout_config(
+                TimeoutConfig::builder()
+                    .connect_timeout(Duration::from_secs(30))
+                    .operation_timeout(Duration::from_secs(30))
+                    .operation_attempt_timeout(Duration::from_secs(30))
+                    .read_timeout(Duration::from_secs(30))
+                    .build(),
+            )
+            .load()
+            .await;
+        let new_client = Arc::new(audited_client!(aws_sdk_organizations, &config));
+        *client = Some(new_client.clone());
+
+        Ok(new_client)
+    }
+}
+
+#[async_trait]
+impl Connector for OrganizationsConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = OrganizationsResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(OrganizationsConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let organizations_config: OrganizationsConnectorConfig = OrganizationsConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = organizations_config.verify_sts().await?;
+
+        *self.client.lock().await = None;
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(organizations_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = organizations_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        res.push(skeleton!(
+            OrganizationsResourceAddress::OrganizationalUnit {
+                path: vec![String::from("[parent_ou_name]"), String::from("[ou_name]")],
+            },
+            OrganizationsResource::OrganizationalUnit(OrganizationalUnit { tags: Tags::default() })
+        ));
+
+        res.push(skeleton!(
+            OrganizationsResourceAddress::Account {
+                account_id: String::from("[account_id]"),
+            },
+            OrganizationsResource::Account(Account {
+                name: String::from("[account_name]"),
+                email: String::from("[account_email]"),
+                parent: Parent::OrganizationalUnit(String::from("[parent_ou_name]/[ou_name]")),
+                tags: Tags::default(),
+            })
+        ));
+
+        // Stops member accounts from leaving the organization on their own
+        let deny_leave: serde_json::Value = serde_json::json!({
+            "Version": "2012-10-17",
+            "Statement": [{
+                "Effect": "Deny",
+                "Action": "organizations:LeaveOrganization",
+                "Resource": "*"
+            }]
+        });
+        res.push(skeleton!(
+            OrganizationsResourceAddress::ServiceControlPolicy {
+                name: String::from("[policy_name]"),
+            },
+            OrganizationsResource::ServiceControlPolicy(ServiceControlPolicy {
+                description: String::from("Deny leaving the organization"),
+                content: RON.from_str(&RON.to_string(&deny_leave)?)?,
+                tags: Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            OrganizationsResourceAddress::PolicyAttachments {
+                policy_name: String::from("[policy_name]"),
+            },
+            OrganizationsResource::PolicyAttachments(PolicyAttachments {
+                targets: vec![PolicyTarget::OrganizationalUnit(String::from("[parent_ou_name]/[ou_name]"))],
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = OrganizationsResourceAddress::from_path(addr)?;
+
+        match addr {
+            OrganizationsResourceAddress::OrganizationalUnit { .. } => ron_check_eq::<OrganizationalUnit>(a, b),
+            OrganizationsResourceAddress::Account { .. } => ron_check_eq::<Account>(a, b),
+            OrganizationsResourceAddress::ServiceControlPolicy { .. } => ron_check_eq::<ServiceControlPolicy>(a, b),
+            OrganizationsResourceAddress::PolicyAttachments { .. } => {
+                // Attachment order doesn't mean anything
+                let mut a: PolicyAttachments = RON.from_str(std::str::from_utf8(a)?)?;
+                let mut b: PolicyAttachments = RON.from_str(std::str::from_utf8(b)?)?;
+                a.targets.sort_by_key(|t| t.to_string());
+                b.targets.sort_by_key(|t| t.to_string());
+                Ok(a == b)
+            }
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = OrganizationsResourceAddress::from_path(addr)?;
+
+        match addr {
+            OrganizationsResourceAddress::OrganizationalUnit { .. } => ron_check_syntax::<OrganizationalUnit>(a),
+            OrganizationsResourceAddress::Account { .. } => ron_check_syntax::<Account>(a),
+            OrganizationsResourceAddress::ServiceControlPolicy { .. } => ron_check_syntax::<ServiceControlPolicy>(a),
+            OrganizationsResourceAddress::PolicyAttachments { .. } => ron_check_syntax::<PolicyAttachments>(a),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::path::Path;
+
+use anyhow::Context;
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+    util::RON,
+};
+use aws_sdk_organizations::operation::describe_account::DescribeAccountError;
+
+use crate::{
+    addr::OrganizationsResourceAddress,
+    resource::{Account, OrganizationalUnit, OrganizationsResource, PolicyAttachments, ServiceControlPolicy},
+    util::{find_ou_id, find_policy, get_account_parent, list_tags, target_from_summary},
+};
+
+use super::OrganizationsConnector;
+
+impl OrganizationsConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = OrganizationsResourceAddress::from_path(addr)?;
+        let client = self.get_or_init_client().await?;
+
+        match &addr {
+            OrganizationsResourceAddress::OrganizationalUnit { path } => {
+                let Some(ou_id) = find_ou_id(&client, path).await? else {
+                    return Ok(None);
+                };
+
+                let resp = client.describe_organizational_unit().organizational_unit_id(&ou_id).send().await?;
+                let ou_arn = resp.organizational_unit.and_then(|ou| ou.arn).unwrap_or_default();
+
+                let ou = OrganizationalUnit {
+                    tags: list_tags(&client, &ou_id).await?,
+                };
+
+                get_resource_response!(
+                    OrganizationsResource::OrganizationalUnit(ou),
+                    [(String::from("ou_id"), ou_id), (String::from("ou_arn"), ou_arn)]
+                )
+            }
+            OrganizationsResourceAddress::Account { account_id } => {
+                let account = match client.describe_account().account_id(account_id).send().await {
+                    Ok(resp) => resp.account.context("No account in response")?,
+                    Err(e) => match e.as_service_error() {
+                        Some(DescribeAccountError::AccountNotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let account_arn = account.arn.unwrap_or_default();
+                let account = Account {
+                    name: account.name.unwrap_or_default(),
+                    email: account.email.unwrap_or_default(),
+                    parent: get_account_parent(&client, account_id).await?,
+                    tags: list_tags(&client, account_id).await?,
+                };
+
+                get_resource_response!(
+                    OrganizationsResource::Account(account),
+                    [(String::from("account_arn"), account_arn)]
+                )
+            }
+            OrganizationsResourceAddress::ServiceControlPolicy { name } => {
+                let Some((policy_id, aws_managed)) = find_policy(&client, name).await? else {
+                    return Ok(None);
+                };
+
+                // AWS managed policies are only tracked through their attachments
+                if aws_managed {
+                    return Ok(None);
+                }
+
+                let resp = client.describe_policy().policy_id(&policy_id).send().await?;
+                let policy = resp.policy.context("No policy in response")?;
+                let summary = policy.policy_summary.context("No policy summary in response")?;
+
+                let content: serde_json::Value =
+                    serde_json::from_str(&policy.content.unwrap_or_default()).context("Policy content is not valid JSON")?;
+
+                let scp = ServiceControlPolicy {
+                    description: summary.description.unwrap_or_default(),
+                    content: RON.from_str(&RON.to_string(&content)?)?,
+                    tags: list_tags(&client, &policy_id).await?,
+                };
+
+                get_resource_response!(
+                    OrganizationsResource::ServiceControlPolicy(scp),
+                    [
+                        (String::from("policy_id"), policy_id),
+                        (String::from("policy_arn"), summary.arn.unwrap_or_default())
+                    ]
+                )
+            }
+            OrganizationsResourceAddress::PolicyAttachments { policy_name } => {
+                let Some((policy_id, _)) = find_policy(&client, policy_name).await? else {
+                    return Ok(None);
+                };
+
+                let mut targets = Vec::new();
+                let mut summaries = client
+                    .list_targets_for_policy()
+                    .policy_id(&policy_id)
+                    .into_paginator()
+                    .items()
+                    .send();
+                while let Some(summary) = summaries.next().await {
+                    let summary = summary?;
+                    let Some(target_id) = summary.target_id else {
+                        continue;
+                    };
+                    targets.push(target_from_summary(&client, summary.r#type.as_ref(), &target_id).await?);
+                }
+
+                get_resource_response!(
+                    OrganizationsResource::PolicyAttachments(PolicyAttachments { targets }),
+                    [(String::from("policy_id"), policy_id)]
+                )
+            }
+        }
+    }
+}
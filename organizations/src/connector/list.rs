@@ -0,0 +1,65 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+use aws_sdk_organizations::types::PolicyType;
+
+use crate::{addr::OrganizationsResourceAddress, util::get_root_id};
+
+use super::OrganizationsConnector;
+
+impl OrganizationsConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+        let client = self.get_or_init_client().await?;
+
+        // Walk the OU tree down from the root
+        let mut pending: Vec<(String, Vec<String>)> = vec![(get_root_id(&client).await?, Vec::new())];
+        while let Some((parent_id, parent_path)) = pending.pop() {
+            let mut ous = client
+                .list_organizational_units_for_parent()
+                .parent_id(&parent_id)
+                .into_paginator()
+                .items()
+                .send();
+            while let Some(ou) = ous.next().await {
+                let ou = ou?;
+                let (Some(id), Some(name)) = (ou.id, ou.name) else {
+                    continue;
+                };
+
+                let mut path = parent_path.clone();
+                path.push(name);
+                results.push(OrganizationsResourceAddress::OrganizationalUnit { path: path.clone() }.to_path_buf());
+                pending.push((id, path));
+            }
+        }
+
+        let mut accounts = client.list_accounts().into_paginator().items().send();
+        while let Some(account) = accounts.next().await {
+            if let Some(account_id) = account?.id {
+                results.push(OrganizationsResourceAddress::Account { account_id }.to_path_buf());
+            }
+        }
+
+        let mut policies = client
+            .list_policies()
+            .filter(PolicyType::ServiceControlPolicy)
+            .into_paginator()
+            .items()
+            .send();
+        while let Some(policy) = policies.next().await {
+            let policy = policy?;
+            let Some(name) = policy.name else {
+                continue;
+            };
+
+            // AWS managed policies like FullAWSAccess can't be changed, but can be attached and detached
+            if !policy.aws_managed {
+                results.push(OrganizationsResourceAddress::ServiceControlPolicy { name: name.clone() }.to_path_buf());
+            }
+            results.push(OrganizationsResourceAddress::PolicyAttachments { policy_name: name }.to_path_buf());
+        }
+
+        Ok(results)
+    }
+}
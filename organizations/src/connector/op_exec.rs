@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::connector::{ConnectorOp, OpExecResponse, ResourceAddress};
+
+use crate::{addr::OrganizationsResourceAddress, op::OrganizationsConnectorOp, op_impl};
+
+use super::OrganizationsConnector;
+
+impl OrganizationsConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = OrganizationsResourceAddress::from_path(addr)?;
+        let op = OrganizationsConnectorOp::from_str(op)?;
+        let client = self.get_or_init_client().await?;
+
+        match &addr {
+            OrganizationsResourceAddress::OrganizationalUnit { path } => {
+                let Some((name, parent_path)) = path.split_last() else {
+                    bail!("Organizational unit address has an empty path");
+                };
+
+                match op {
+                    OrganizationsConnectorOp::CreateOrganizationalUnit(ou) => {
+                        op_impl::create_organizational_unit(&client, parent_path, name, &ou).await
+                    }
+                    OrganizationsConnectorOp::UpdateOrganizationalUnitTags(old_tags, new_tags) => {
+                        op_impl::update_organizational_unit_tags(&client, path, &old_tags, &new_tags).await
+                    }
+                    OrganizationsConnectorOp::DeleteOrganizationalUnit => {
+                        op_impl::delete_organizational_unit(&client, path).await
+                    }
+                    _ => bail!("Invalid operation for organizational unit resource"),
+                }
+            }
+            OrganizationsResourceAddress::Account { account_id } => match op {
+                OrganizationsConnectorOp::MoveAccount { from, to } => {
+                    op_impl::move_account(&client, account_id, &from, &to).await
+                }
+                OrganizationsConnectorOp::UpdateAccountTags(old_tags, new_tags) => {
+                    op_impl::update_account_tags(&client, account_id, &old_tags, &new_tags).await
+                }
+                _ => bail!("Invalid operation for account resource"),
+            },
+            OrganizationsResourceAddress::ServiceControlPolicy { name } => match op {
+                OrganizationsConnectorOp::CreatePolicy(policy) => op_impl::create_policy(&client, name, &policy).await,
+                OrganizationsConnectorOp::UpdatePolicy(policy) => op_impl::update_policy(&client, name, &policy).await,
+                OrganizationsConnectorOp::UpdatePolicyTags(old_tags, new_tags) => {
+                    op_impl::update_policy_tags(&client, name, &old_tags, &new_tags).await
+                }
+                OrganizationsConnectorOp::DeletePolicy => op_impl::delete_policy(&client, name).await,
+                _ => bail!("Invalid operation for service control policy resource"),
+            },
+            OrganizationsResourceAddress::PolicyAttachments { policy_name } => match op {
+                OrganizationsConnectorOp::AttachPolicy(target) => op_impl::attach_policy(&client, policy_name, &target).await,
+                OrganizationsConnectorOp::DetachPolicy(target) => op_impl::detach_policy(&client, policy_name, &target).await,
+                _ => bail!("Invalid operation for policy attachments resource"),
+            },
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::path::Path;
+
+use anyhow::{Context, bail};
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::{
+    resource::{Account, OrganizationalUnit, PolicyAttachments, ServiceControlPolicy},
+    util::MAX_SCP_LENGTH,
+};
+
+use super::{OrganizationsConnector, OrganizationsConnectorOp, OrganizationsResourceAddress};
+
+/// Organizations measures the limit against the policy as submitted, so this is checked against compact JSON.
+fn check_policy_length(name: &str, policy: &ServiceControlPolicy) -> anyhow::Result<()> {
+    let content = serde_json::to_string(&policy.content).context("Failed to serialize policy content as JSON")?;
+    if content.len() > MAX_SCP_LENGTH {
+        bail!(
+            "Service control policy {} is {} characters long as JSON, over the limit of {}",
+            name,
+            content.len(),
+            MAX_SCP_LENGTH
+        );
+    }
+    Ok(())
+}
+
+impl OrganizationsConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = OrganizationsResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            OrganizationsResourceAddress::OrganizationalUnit { path } => {
+                let path = path.join("/");
+                match (current, desired) {
+                    (None, None) => Ok(Vec::new()),
+                    (None, Some(new_ou)) => {
+                        let new_ou: OrganizationalUnit = RON.from_str(&new_ou)?;
+                        Ok(vec![connector_op!(
+                            OrganizationsConnectorOp::CreateOrganizationalUnit(new_ou),
+                            format!("Create new organizational unit {}", path)
+                        )])
+                    }
+                    (Some(_old_ou), None) => Ok(vec![connector_op!(
+                        OrganizationsConnectorOp::DeleteOrganizationalUnit,
+                        format!("DELETE organizational unit {} (it must not contain any accounts or OUs)", path)
+                    )]),
+                    (Some(old_ou), Some(new_ou)) => {
+                        let old_ou: OrganizationalUnit = RON.from_str(&old_ou)?;
+                        let new_ou: OrganizationalUnit = RON.from_str(&new_ou)?;
+                        let mut ops = Vec::new();
+
+                        if old_ou.tags != new_ou.tags {
+                            let diff = diff_ron_values(&old_ou.tags, &new_ou.tags).unwrap_or_default();
+                            ops.push(connector_op!(
+                                OrganizationsConnectorOp::UpdateOrganizationalUnitTags(old_ou.tags, new_ou.tags),
+                                format!("Modify tags for organizational unit `{}`\n{}", path, diff)
+                            ));
+                        }
+
+                        Ok(ops)
+                    }
+                }
+            }
+            OrganizationsResourceAddress::Account { account_id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(_new_account)) => bail!(
+                    "Account {} is not a member of the organization. Accounts can't be created or invited here: \
+                     add the account to the organization first, then import it.",
+                    account_id
+                ),
+                (Some(_old_account), None) => bail!(
+                    "Account {} can't be removed from the organization here. \
+                     Remove it from the organization directly, or restore its file.",
+                    account_id
+                ),
+                (Some(old_account), Some(new_account)) => {
+                    let old_account: Account = RON.from_str(&old_account)?;
+                    let new_account: Account = RON.from_str(&new_account)?;
+
+                    let mut read_only_fields = Vec::new();
+                    if old_account.name != new_account.name {
+                        read_only_fields.push("name");
+                    }
+                    if old_account.email != new_account.email {
+                        read_only_fields.push("email");
+                    }
+                    if !read_only_fields.is_empty() {
+                        bail!(
+                            "Account {}: {} can't be changed through Organizations",
+                            account_id,
+                            read_only_fields.join(", ")
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_account.parent != new_account.parent {
+                        ops.push(connector_op!(
+                            OrganizationsConnectorOp::MoveAccount {
+                                from: old_account.parent.clone(),
+                                to:   new_account.parent.clone(),
+                            },
+                            format!(
+                                "Move account `{}` ({}) from {} to {}",
+                                account_id, new_account.name, old_account.parent, new_account.parent
+                            )
+                        ));
+                    }
+
+                    if old_account.tags != new_account.tags {
+                        let diff = diff_ron_values(&old_account.tags, &new_account.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            OrganizationsConnectorOp::UpdateAccountTags(old_account.tags, new_account.tags),
+                            format!("Modify tags for account `{}`\n{}", account_id, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            OrganizationsResourceAddress::ServiceControlPolicy { name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_policy)) => {
+                    let new_policy: ServiceControlPolicy = RON.from_str(&new_policy)?;
+                    check_policy_length(name, &new_policy)?;
+                    Ok(vec![connector_op!(
+                        OrganizationsConnectorOp::CreatePolicy(new_policy),
+                        format!("Create new service control policy {}", name)
+                    )])
+                }
+                (Some(_old_policy), None) => Ok(vec![connector_op!(
+                    OrganizationsConnectorOp::DeletePolicy,
+                    format!("DELETE service control policy {} (it must be detached from every target)", name)
+                )]),
+                (Some(old_policy), Some(new_policy)) => {
+                    let old_policy: ServiceControlPolicy = RON.from_str(&old_policy)?;
+                    let new_policy: ServiceControlPolicy = RON.from_str(&new_policy)?;
+                    check_policy_length(name, &new_policy)?;
+                    let mut ops = Vec::new();
+
+                    if old_policy.tags != new_policy.tags {
+                        let diff = diff_ron_values(&old_policy.tags, &new_policy.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            OrganizationsConnectorOp::UpdatePolicyTags(old_policy.tags.clone(), new_policy.tags.clone()),
+                            format!("Modify tags for service control policy `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    if old_policy.description != new_policy.description || old_policy.content != new_policy.content {
+                        let diff = diff_ron_values(
+                            &(&old_policy.description, &old_policy.content),
+                            &(&new_policy.description, &new_policy.content),
+                        )
+                        .unwrap_or_default();
+                        ops.push(connector_op!(
+                            OrganizationsConnectorOp::UpdatePolicy(new_policy),
+                            format!("Modify service control policy `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            OrganizationsResourceAddress::PolicyAttachments { policy_name } => {
+                let old_targets = match current {
+                    Some(old) => RON.from_str::<PolicyAttachments>(&old)?.targets,
+                    None => Vec::new(),
+                };
+                let new_targets = match desired {
+                    Some(new) => RON.from_str::<PolicyAttachments>(&new)?.targets,
+                    None => Vec::new(),
+                };
+
+                let mut ops = Vec::new();
+
+                for target in new_targets.iter().filter(|t| !old_targets.contains(t)) {
+                    ops.push(connector_op!(
+                        OrganizationsConnectorOp::AttachPolicy(target.clone()),
+                        format!("Attach service control policy `{}` to {}", policy_name, target)
+                    ));
+                }
+
+                for target in old_targets.iter().filter(|t| !new_targets.contains(t)) {
+                    ops.push(connector_op!(
+                        OrganizationsConnectorOp::DetachPolicy(target.clone()),
+                        format!("Detach service control policy `{}` from {}", policy_name, target)
+                    ));
+                }
+
+                Ok(ops)
+            }
+        }
+    }
+}
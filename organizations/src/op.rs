@@ -0,0 +1,45 @@
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{OrganizationalUnit, Parent, PolicyTarget, ServiceControlPolicy},
+    tags::Tags,
+};
+
+/// OUs are referred to by path rather than ID, and resolved when the op runs,
+/// since a parent OU may be created earlier in the same apply.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum OrganizationsConnectorOp {
+    CreateOrganizationalUnit(OrganizationalUnit),
+    UpdateOrganizationalUnitTags(Tags, Tags),
+    /// Fails unless the OU is empty.
+    DeleteOrganizationalUnit,
+
+    MoveAccount { from: Parent, to: Parent },
+    UpdateAccountTags(Tags, Tags),
+
+    CreatePolicy(ServiceControlPolicy),
+    /// Replaces the policy's description and content.
+    UpdatePolicy(ServiceControlPolicy),
+    UpdatePolicyTags(Tags, Tags),
+    /// Fails unless the policy has been detached from every target.
+    DeletePolicy,
+
+    AttachPolicy(PolicyTarget),
+    DetachPolicy(PolicyTarget),
+}
+
+impl ConnectorOp for OrganizationsConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
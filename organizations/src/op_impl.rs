@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use autoschematic_core::connector::OpExecResponse;
+use aws_sdk_organizations::types::PolicyType;
+
+use crate::{
+    resource::{OrganizationalUnit, Parent, PolicyTarget, ServiceControlPolicy},
+    tags::Tags,
+    util::{get_policy_id, resolve_ou_id, resolve_parent_id, resolve_target_id, update_tags},
+};
+
+/// Creates an OU under the OU at `parent_path`, or under the root if `parent_path` is empty
+pub async fn create_organizational_unit(
+    client: &aws_sdk_organizations::Client,
+    parent_path: &[String],
+    name: &str,
+    ou: &OrganizationalUnit,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let parent = if parent_path.is_empty() {
+        Parent::Root
+    } else {
+        Parent::OrganizationalUnit(parent_path.join("/"))
+    };
+    let parent_id = resolve_parent_id(client, &parent).await?;
+
+    let resp = client
+        .create_organizational_unit()
+        .parent_id(parent_id)
+        .name(name)
+        .set_tags(Some(ou.tags.to_vec()?).filter(|t| !t.is_empty()))
+        .send()
+        .await?;
+
+    let ou = resp.organizational_unit.context("No organizational unit in response")?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("ou_id"), ou.id),
+            (String::from("ou_arn"), ou.arn),
+        ])),
+        friendly_message: Some(format!("Created organizational unit {}", name)),
+    })
+}
+
+pub async fn update_organizational_unit_tags(
+    client: &aws_sdk_organizations::Client,
+    path: &[String],
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let ou_id = resolve_ou_id(client, &path.join("/")).await?;
+    update_tags(client, &ou_id, old_tags, new_tags).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for organizational unit {}", path.join("/"))),
+    })
+}
+
+pub async fn delete_organizational_unit(
+    client: &aws_sdk_organizations::Client,
+    path: &[String],
+) -> Result<OpExecResponse, anyhow::Error> {
+    let ou_id = resolve_ou_id(client, &path.join("/")).await?;
+    client.delete_organizational_unit().organizational_unit_id(ou_id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("ou_id"), None),
+            (String::from("ou_arn"), None),
+        ])),
+        friendly_message: Some(format!("Deleted organizational unit {}", path.join("/"))),
+    })
+}
+
+pub async fn move_account(
+    client: &aws_sdk_organizations::Client,
+    account_id: &str,
+    from: &Parent,
+    to: &Parent,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let source_parent_id = resolve_parent_id(client, from).await?;
+    let destination_parent_id = resolve_parent_id(client, to).await?;
+
+    client
+        .move_account()
+        .account_id(account_id)
+        .source_parent_id(source_parent_id)
+        .destination_parent_id(destination_parent_id)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Moved account {} from {} to {}", account_id, from, to)),
+    })
+}
+
+pub async fn update_account_tags(
+    client: &aws_sdk_organizations::Client,
+    account_id: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    update_tags(client, account_id, old_tags, new_tags).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for account {}", account_id)),
+    })
+}
+
+pub async fn create_policy(
+    client: &aws_sdk_organizations::Client,
+    name: &str,
+    policy: &ServiceControlPolicy,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let content = serde_json::to_string(&policy.content).context("Failed to serialize policy content as JSON")?;
+
+    let resp = client
+        .create_policy()
+        .name(name)
+        .description(&policy.description)
+        .content(content)
+        .r#type(PolicyType::ServiceControlPolicy)
+        .set_tags(Some(policy.tags.to_vec()?).filter(|t| !t.is_empty()))
+        .send()
+        .await?;
+
+    let summary = resp
+        .policy
+        .and_then(|p| p.policy_summary)
+        .context("No policy summary in response")?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("policy_id"), summary.id),
+            (String::from("policy_arn"), summary.arn),
+        ])),
+        friendly_message: Some(format!("Created service control policy {}", name)),
+    })
+}
+
+pub async fn update_policy(
+    client: &aws_sdk_organizations::Client,
+    name: &str,
+    policy: &ServiceControlPolicy,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let policy_id = get_policy_id(client, name).await?;
+    let content = serde_json::to_string(&policy.content).context("Failed to serialize policy content as JSON")?;
+
+    client
+        .update_policy()
+        .policy_id(policy_id)
+        .description(&policy.description)
+        .content(content)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated service control policy {}", name)),
+    })
+}
+
+pub async fn update_policy_tags(
+    client: &aws_sdk_organizations::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let policy_id = get_policy_id(client, name).await?;
+    update_tags(client, &policy_id, old_tags, new_tags).await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for service control policy {}", name)),
+    })
+}
+
+pub async fn delete_policy(client: &aws_sdk_organizations::Client, name: &str) -> Result<OpExecResponse, anyhow::Error> {
+    let policy_id = get_policy_id(client, name).await?;
+    client.delete_policy().policy_id(policy_id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("policy_id"), None),
+            (String::from("policy_arn"), None),
+        ])),
+        friendly_message: Some(format!("Deleted service control policy {}", name)),
+    })
+}
+
+pub async fn attach_policy(
+    client: &aws_sdk_organizations::Client,
+    policy_name: &str,
+    target: &PolicyTarget,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let policy_id = get_policy_id(client, policy_name).await?;
+    let target_id = resolve_target_id(client, target).await?;
+
+    client.attach_policy().policy_id(policy_id).target_id(target_id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Attached service control policy {} to {}", policy_name, target)),
+    })
+}
+
+pub async fn detach_policy(
+    client: &aws_sdk_organizations::Client,
+    policy_name: &str,
+    target: &PolicyTarget,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let policy_id = get_policy_id(client, policy_name).await?;
+    let target_id = resolve_target_id(client, target).await?;
+
+    client.detach_policy().policy_id(policy_id).target_id(target_id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Detached service control policy {} from {}", policy_name, target)),
+    })
+}
@@ -0,0 +1,118 @@
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::OrganizationsResourceAddress, tags::Tags};
+
+/// An OU's name and place in the tree come from its address.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct OrganizationalUnit {
+    pub tags: Tags,
+}
+
+/// Where an account sits in the organization.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum Parent {
+    Root,
+    /// The OU's path of names below the root, e.g. `"Workloads/Prod"`.
+    OrganizationalUnit(String),
+}
+
+/// Accounts are import-only: they have to be created or invited outside of autoschematic.
+/// Changing `parent` moves the account; `name` and `email` are read-only.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Account {
+    pub name: String,
+    pub email: String,
+    pub parent: Parent,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceControlPolicy {
+    pub description: String,
+    /// The policy document. SCPs are limited to 5,120 characters once serialized to JSON.
+    pub content: ron::Value,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum PolicyTarget {
+    Root,
+    /// The OU's path of names below the root, e.g. `"Workloads/Prod"`.
+    OrganizationalUnit(String),
+    /// An account ID.
+    Account(String),
+}
+
+impl std::fmt::Display for Parent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Parent::Root => write!(f, "the root"),
+            Parent::OrganizationalUnit(path) => write!(f, "OU {}", path),
+        }
+    }
+}
+
+impl std::fmt::Display for PolicyTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyTarget::Root => write!(f, "the root"),
+            PolicyTarget::OrganizationalUnit(path) => write!(f, "OU {}", path),
+            PolicyTarget::Account(account_id) => write!(f, "account {}", account_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyAttachments {
+    pub targets: Vec<PolicyTarget>,
+}
+
+pub enum OrganizationsResource {
+    OrganizationalUnit(OrganizationalUnit),
+    Account(Account),
+    ServiceControlPolicy(ServiceControlPolicy),
+    PolicyAttachments(PolicyAttachments),
+}
+
+impl Resource for OrganizationsResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            OrganizationsResource::OrganizationalUnit(ou) => Ok(RON.to_string_pretty(&ou, pretty_config)?.into()),
+            OrganizationsResource::Account(account) => Ok(RON.to_string_pretty(&account, pretty_config)?.into()),
+            OrganizationsResource::ServiceControlPolicy(policy) => Ok(RON.to_string_pretty(&policy, pretty_config)?.into()),
+            OrganizationsResource::PolicyAttachments(attachments) => {
+                Ok(RON.to_string_pretty(&attachments, pretty_config)?.into())
+            }
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = OrganizationsResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            OrganizationsResourceAddress::OrganizationalUnit { .. } => {
+                Ok(OrganizationsResource::OrganizationalUnit(RON.from_str(s)?))
+            }
+            OrganizationsResourceAddress::Account { .. } => Ok(OrganizationsResource::Account(RON.from_str(s)?)),
+            OrganizationsResourceAddress::ServiceControlPolicy { .. } => {
+                Ok(OrganizationsResource::ServiceControlPolicy(RON.from_str(s)?))
+            }
+            OrganizationsResourceAddress::PolicyAttachments { .. } => {
+                Ok(OrganizationsResource::PolicyAttachments(RON.from_str(s)?))
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+use aws_sdk_organizations::types::Tag;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Tags(HashMap<String, String>);
+
+impl From<&[Tag]> for Tags {
+    fn from(tags: &[Tag]) -> Self {
+        let mut out_map = HashMap::new();
+        for tag in tags {
+            out_map.insert(tag.key.clone(), tag.value.clone());
+        }
+        Tags(out_map)
+    }
+}
+
+impl Tags {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn to_vec(&self) -> anyhow::Result<Vec<Tag>> {
+        let mut out_vec = Vec::new();
+
+        for (k, v) in &self.0 {
+            out_vec.push(Tag::builder().key(k).value(v).build()?);
+        }
+
+        Ok(out_vec)
+    }
+}
+
+// From a pair of tag maps, determine the keys to untag and the tags to set respectively
+pub fn tag_diff(old_tags: &Tags, new_tags: &Tags) -> anyhow::Result<(Vec<String>, Vec<Tag>)> {
+    let untag_keys = old_tags.0.keys().filter(|k| !new_tags.0.contains_key(*k)).cloned().collect();
+
+    let mut new_tagset = Vec::new();
+    for (key, new_value) in &new_tags.0 {
+        if old_tags.0.get(key) != Some(new_value) {
+            new_tagset.push(Tag::builder().key(key).value(new_value).build()?);
+        }
+    }
+
+    Ok((untag_keys, new_tagset))
+}
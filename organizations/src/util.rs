@@ -0,0 +1,186 @@
+use anyhow::{Context, bail};
+use aws_sdk_organizations::types::{ParentType, PolicyType, TargetType};
+
+use crate::{
+    resource::{Parent, PolicyTarget},
+    tags::{Tags, tag_diff},
+};
+
+/// SCPs can't be longer than this once serialized to JSON.
+pub const MAX_SCP_LENGTH: usize = 5120;
+
+pub fn split_ou_path(path: &str) -> Vec<String> {
+    path.split('/').filter(|s| !s.is_empty()).map(|s| s.to_string()).collect()
+}
+
+/// An organization only ever has one root.
+pub async fn get_root_id(client: &aws_sdk_organizations::Client) -> anyhow::Result<String> {
+    let resp = client.list_roots().send().await?;
+    let root = resp.roots().first().context("Organization has no root")?;
+    root.id.clone().context("Root has no ID")
+}
+
+/// Walks down from the root, following OU names, and returns the ID of the OU at `path`.
+pub async fn find_ou_id(client: &aws_sdk_organizations::Client, path: &[String]) -> anyhow::Result<Option<String>> {
+    let mut parent_id = get_root_id(client).await?;
+
+    for name in path {
+        let mut found = None;
+        let mut ous = client
+            .list_organizational_units_for_parent()
+            .parent_id(&parent_id)
+            .into_paginator()
+            .items()
+            .send();
+        while let Some(ou) = ous.next().await {
+            let ou = ou?;
+            if ou.name.as_deref() == Some(name.as_str()) {
+                found = ou.id;
+                break;
+            }
+        }
+
+        match found {
+            Some(id) => parent_id = id,
+            None => return Ok(None),
+        }
+    }
+
+    Ok(Some(parent_id))
+}
+
+pub async fn resolve_ou_id(client: &aws_sdk_organizations::Client, path: &str) -> anyhow::Result<String> {
+    match find_ou_id(client, &split_ou_path(path)).await? {
+        Some(id) => Ok(id),
+        None => bail!("Organizational unit `{}` not found", path),
+    }
+}
+
+pub async fn resolve_parent_id(client: &aws_sdk_organizations::Client, parent: &Parent) -> anyhow::Result<String> {
+    match parent {
+        Parent::Root => get_root_id(client).await,
+        Parent::OrganizationalUnit(path) => resolve_ou_id(client, path).await,
+    }
+}
+
+pub async fn resolve_target_id(client: &aws_sdk_organizations::Client, target: &PolicyTarget) -> anyhow::Result<String> {
+    match target {
+        PolicyTarget::Root => get_root_id(client).await,
+        PolicyTarget::OrganizationalUnit(path) => resolve_ou_id(client, path).await,
+        PolicyTarget::Account(account_id) => Ok(account_id.clone()),
+    }
+}
+
+/// Returns the path of OU names from the root down to `ou_id`, inclusive.
+pub async fn ou_path(client: &aws_sdk_organizations::Client, ou_id: &str) -> anyhow::Result<String> {
+    let mut names = Vec::new();
+    let mut id = ou_id.to_string();
+
+    loop {
+        let resp = client.describe_organizational_unit().organizational_unit_id(&id).send().await?;
+        let ou = resp.organizational_unit.context("No organizational unit in response")?;
+        names.push(ou.name.context("Organizational unit has no name")?);
+
+        match get_parent(client, &id).await? {
+            (ParentType::OrganizationalUnit, parent_id) => id = parent_id,
+            _ => break,
+        }
+    }
+
+    names.reverse();
+    Ok(names.join("/"))
+}
+
+async fn get_parent(client: &aws_sdk_organizations::Client, child_id: &str) -> anyhow::Result<(ParentType, String)> {
+    let resp = client.list_parents().child_id(child_id).send().await?;
+    let parent = resp.parents().first().with_context(|| format!("{} has no parent", child_id))?;
+    Ok((
+        parent.r#type.clone().context("Parent has no type")?,
+        parent.id.clone().context("Parent has no ID")?,
+    ))
+}
+
+pub async fn get_account_parent(client: &aws_sdk_organizations::Client, account_id: &str) -> anyhow::Result<Parent> {
+    match get_parent(client, account_id).await? {
+        (ParentType::OrganizationalUnit, ou_id) => Ok(Parent::OrganizationalUnit(ou_path(client, &ou_id).await?)),
+        _ => Ok(Parent::Root),
+    }
+}
+
+pub async fn target_from_summary(
+    client: &aws_sdk_organizations::Client,
+    target_type: Option<&TargetType>,
+    target_id: &str,
+) -> anyhow::Result<PolicyTarget> {
+    match target_type {
+        Some(TargetType::Root) => Ok(PolicyTarget::Root),
+        Some(TargetType::OrganizationalUnit) => Ok(PolicyTarget::OrganizationalUnit(ou_path(client, target_id).await?)),
+        Some(TargetType::Account) => Ok(PolicyTarget::Account(target_id.to_string())),
+        _ => bail!("Unknown policy target type for {}", target_id),
+    }
+}
+
+/// Returns the ID of the service control policy named `name`, and whether it's AWS managed.
+pub async fn find_policy(
+    client: &aws_sdk_organizations::Client,
+    name: &str,
+) -> anyhow::Result<Option<(String, bool)>> {
+    let mut policies = client
+        .list_policies()
+        .filter(PolicyType::ServiceControlPolicy)
+        .into_paginator()
+        .items()
+        .send();
+    while let Some(policy) = policies.next().await {
+        let policy = policy?;
+        if policy.name.as_deref() == Some(name) {
+            return Ok(Some((policy.id.context("Policy has no ID")?, policy.aws_managed)));
+        }
+    }
+    Ok(None)
+}
+
+pub async fn get_policy_id(client: &aws_sdk_organizations::Client, name: &str) -> anyhow::Result<String> {
+    match find_policy(client, name).await? {
+        Some((id, _)) => Ok(id),
+        None => bail!("Service control policy `{}` not found", name),
+    }
+}
+
+pub async fn list_tags(client: &aws_sdk_organizations::Client, resource_id: &str) -> anyhow::Result<Tags> {
+    let mut tags = Vec::new();
+    let mut pages = client.list_tags_for_resource().resource_id(resource_id).into_paginator().items().send();
+    while let Some(tag) = pages.next().await {
+        tags.push(tag?);
+    }
+    Ok(Tags::from(tags.as_slice()))
+}
+
+pub async fn update_tags(
+    client: &aws_sdk_organizations::Client,
+    resource_id: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<()> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags)?;
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_id(resource_id)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client
+            .tag_resource()
+            .resource_id(resource_id)
+            .set_tags(Some(new_tagset))
+            .send()
+            .await?;
+    }
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
 use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 
 #[derive(Debug, Clone)]
@@ -8,6 +9,7 @@ pub enum RdsResourceAddress {
     DBCluster { region: String, id: String },
     DBSubnetGroup { region: String, name: String },
     DBParameterGroup { region: String, name: String },
+    OptionGroup { region: String, name: String },
 }
 
 impl ResourceAddress for RdsResourceAddress {
@@ -21,6 +23,9 @@ impl ResourceAddress for RdsResourceAddress {
             RdsResourceAddress::DBParameterGroup { region, name } => {
                 PathBuf::from(format!("aws/rds/{region}/parameter-groups/{name}.ron"))
             }
+            RdsResourceAddress::OptionGroup { region, name } => {
+                PathBuf::from(format!("aws/rds/{region}/option-groups/{name}.ron"))
+            }
         }
     }
 
@@ -56,7 +61,46 @@ impl ResourceAddress for RdsResourceAddress {
                     name:   group_name,
                 })
             }
+            ["aws", "rds", region, "option-groups", group_name] if group_name.ends_with(".ron") => {
+                let group_name = group_name.strip_suffix(".ron").unwrap().to_string();
+                Ok(RdsResourceAddress::OptionGroup {
+                    region: region.to_string(),
+                    name:   group_name,
+                })
+            }
             _ => Err(invalid_addr_path(path)),
         }
     }
 }
+
+impl DescribeAddresses for RdsResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/rds/<region>/instances/<instance_id>.ron",
+                description: "A DB instance",
+                example:     "aws/rds/us-east-1/instances/app-db.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/rds/<region>/clusters/<cluster_id>.ron",
+                description: "A DB cluster",
+                example:     "aws/rds/us-east-1/clusters/app-aurora.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/rds/<region>/subnet-groups/<name>.ron",
+                description: "A DB subnet group",
+                example:     "aws/rds/us-east-1/subnet-groups/private.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/rds/<region>/parameter-groups/<name>.ron",
+                description: "A DB parameter group",
+                example:     "aws/rds/us-east-1/parameter-groups/postgres16-tuned.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/rds/<region>/option-groups/<name>.ron",
+                description: "An option group",
+                example:     "aws/rds/us-east-1/option-groups/mysql-audit.ron",
+            },
+        ]
+    }
+}
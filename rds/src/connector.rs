@@ -5,7 +5,13 @@ use std::{
 };
 
 use async_trait::async_trait;
-use autoschematic_connector_aws_core::config::AwsServiceConfig;
+use autoschematic_connector_aws_core::{
+    audit::AuditLog,
+    audited_client,
+    cascade::{ConsumedOutput, forget_replacements, note_stale_references, resolve_stale_references},
+    concurrency::OpExecLimiter,
+    config::AwsServiceConfig,
+};
 use autoschematic_core::{
     connector::{Connector, ConnectorOutbox, Resource, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, ResourceAddress, SkeletonResponse},
     diag::DiagnosticResponse,
@@ -18,7 +24,9 @@ use tokio::sync::Mutex;
 use crate::{
     addr::RdsResourceAddress,
     config::RdsConnectorConfig,
-    resource::{RdsDBCluster, RdsDBInstance, RdsDBParameterGroup, RdsDBSubnetGroup, RdsResource},
+    resource::{
+        RdsDBCluster, RdsDBInstance, RdsDBParameterGroup, RdsDBSubnetGroup, RdsOptionConfiguration, RdsOptionGroup, RdsResource,
+    },
     tags::Tags,
 };
 
@@ -27,12 +35,29 @@ mod list;
 mod op_exec;
 mod plan;
 
+/// The outputs of other resources that this connector's resources embed by value.
+/// Subnet groups name their subnets, and instances and clusters their security groups.
+pub const CONSUMED_OUTPUTS: &[ConsumedOutput] = &[
+    ConsumedOutput {
+        producer: "aws/vpc/",
+        key:      "subnet_id",
+        fields:   &["subnet_ids"],
+    },
+    ConsumedOutput {
+        producer: "aws/vpc/",
+        key:      "security_group_id",
+        fields:   &["vpc_security_group_ids"],
+    },
+];
+
 #[derive(Default)]
 pub struct RdsConnector {
     pub prefix: PathBuf,
     pub client_cache: Mutex<HashMap<String, Arc<aws_sdk_rds::Client>>>,
     pub account_id: Mutex<String>,
     pub config: Mutex<RdsConnectorConfig>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
 }
 
 #[async_trait]
@@ -54,6 +79,9 @@ impl Connector for RdsConnector {
         let account_id = secrets_config.verify_sts().await?;
 
         *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(secrets_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        forget_replacements(&self.prefix);
         *self.config.lock().await = secrets_config;
         *self.account_id.lock().await = account_id;
         Ok(())
@@ -83,11 +111,15 @@ impl Connector for RdsConnector {
         current: Option<Vec<u8>>,
         desired: Option<Vec<u8>>,
     ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
-        self.do_plan(addr, current, desired).await
+        let (desired, stale_note) = resolve_stale_references(&self.prefix, CONSUMED_OUTPUTS, desired)?;
+        let ops = self.do_plan(addr, current, desired).await?;
+        Ok(note_stale_references(ops, &stale_note))
     }
 
     async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
-        self.do_op_exec(addr, op).await
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
     }
 
     async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
@@ -140,6 +172,7 @@ impl Connector for RdsConnector {
                 domain: None,
                 domain_iam_role_name: None,
                 enabled_cloudwatch_logs_exports: vec![String::from("postgresql")],
+                db_cluster_identifier: None,
                 tags: Tags::default(),
             })
         ));
@@ -217,6 +250,29 @@ impl Connector for RdsConnector {
             })
         ));
 
+        // RDS MySQL Option Group skeleton
+        res.push(skeleton!(
+            RdsResourceAddress::OptionGroup {
+                region: String::from("[region]"),
+                name: String::from("[option_group_name]"),
+            },
+            RdsResource::OptionGroup(RdsOptionGroup {
+                engine_name: String::from("mysql"),
+                major_engine_version: String::from("8.0"),
+                description: String::from("MySQL option group"),
+                options: vec![RdsOptionConfiguration {
+                    option_name: String::from("MARIADB_AUDIT_PLUGIN"),
+                    option_version: None,
+                    port: None,
+                    vpc_security_group_ids: Vec::new(),
+                    option_settings: [(String::from("SERVER_AUDIT_EVENTS"), String::from("CONNECT,QUERY"))]
+                        .into_iter()
+                        .collect(),
+                }],
+                tags: Tags::default(),
+            })
+        ));
+
         Ok(res)
     }
 
@@ -224,10 +280,11 @@ impl Connector for RdsConnector {
         let addr = RdsResourceAddress::from_path(addr)?;
 
         match addr {
-            RdsResourceAddress::DBInstance { region, id } => ron_check_eq::<RdsDBInstance>(a, b),
-            RdsResourceAddress::DBCluster { region, id } => ron_check_eq::<RdsDBCluster>(a, b),
-            RdsResourceAddress::DBSubnetGroup { region, name } => ron_check_eq::<RdsDBSubnetGroup>(a, b),
-            RdsResourceAddress::DBParameterGroup { region, name } => ron_check_eq::<RdsDBParameterGroup>(a, b),
+            RdsResourceAddress::DBInstance { .. } => ron_check_eq::<RdsDBInstance>(a, b),
+            RdsResourceAddress::DBCluster { .. } => ron_check_eq::<RdsDBCluster>(a, b),
+            RdsResourceAddress::DBSubnetGroup { .. } => ron_check_eq::<RdsDBSubnetGroup>(a, b),
+            RdsResourceAddress::DBParameterGroup { .. } => ron_check_eq::<RdsDBParameterGroup>(a, b),
+            RdsResourceAddress::OptionGroup { .. } => ron_check_eq::<RdsOptionGroup>(a, b),
         }
     }
 
@@ -235,10 +292,11 @@ impl Connector for RdsConnector {
         let addr = RdsResourceAddress::from_path(addr)?;
 
         match addr {
-            RdsResourceAddress::DBInstance { region, id } => ron_check_syntax::<RdsDBInstance>(a),
-            RdsResourceAddress::DBCluster { region, id } => ron_check_syntax::<RdsDBCluster>(a),
-            RdsResourceAddress::DBSubnetGroup { region, name } => ron_check_syntax::<RdsDBSubnetGroup>(a),
-            RdsResourceAddress::DBParameterGroup { region, name } => ron_check_syntax::<RdsDBParameterGroup>(a),
+            RdsResourceAddress::DBInstance { .. } => ron_check_syntax::<RdsDBInstance>(a),
+            RdsResourceAddress::DBCluster { .. } => ron_check_syntax::<RdsDBCluster>(a),
+            RdsResourceAddress::DBSubnetGroup { .. } => ron_check_syntax::<RdsDBSubnetGroup>(a),
+            RdsResourceAddress::DBParameterGroup { .. } => ron_check_syntax::<RdsDBParameterGroup>(a),
+            RdsResourceAddress::OptionGroup { .. } => ron_check_syntax::<RdsOptionGroup>(a),
         }
     }
 }
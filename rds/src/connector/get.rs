@@ -5,12 +5,12 @@ use autoschematic_core::{
     get_resource_response,
 };
 
-use crate::addr::RdsResourceAddress;
-use anyhow::Context;
+use crate::{addr::RdsResourceAddress, tags::Tags};
 use aws_sdk_rds::{
     operation::{
         describe_db_clusters::DescribeDBClustersError, describe_db_instances::DescribeDBInstancesError,
         describe_db_parameter_groups::DescribeDBParameterGroupsError, describe_db_subnet_groups::DescribeDBSubnetGroupsError,
+        describe_option_groups::DescribeOptionGroupsError,
     },
     types::DbInstance,
 };
@@ -126,9 +126,63 @@ impl RdsConnector {
                     return Ok(None);
                 };
 
-                let group = crate::resource::RdsResource::DBParameterGroup(map_db_parameter_group(db_parameter_group)?);
+                // Only report parameters that have been set on this group, rather than every
+                // engine default for the family.
+                let mut parameters = HashMap::new();
+                let mut pages = client
+                    .describe_db_parameters()
+                    .db_parameter_group_name(&name)
+                    .source("user")
+                    .into_paginator()
+                    .send();
+                while let Some(page) = pages.next().await {
+                    for parameter in page?.parameters.unwrap_or_default() {
+                        if let (Some(key), Some(value)) = (parameter.parameter_name, parameter.parameter_value) {
+                            parameters.insert(key, value);
+                        }
+                    }
+                }
+
+                let group = crate::resource::RdsResource::DBParameterGroup(map_db_parameter_group(db_parameter_group, parameters)?);
                 get_resource_response!(group, [(String::from("name"), name)])
             }
+            RdsResourceAddress::OptionGroup { region, name } => {
+                let client = self.get_or_init_client(&region).await?;
+
+                let resp = match client.describe_option_groups().option_group_name(&name).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(DescribeOptionGroupsError::OptionGroupNotFoundFault(_)) => {
+                            return Ok(None);
+                        }
+                        _ => {
+                            return Err(e.into());
+                        }
+                    },
+                };
+
+                let Some(option_groups) = resp.option_groups_list else {
+                    return Ok(None);
+                };
+
+                let Some(option_group) = option_groups.first() else {
+                    return Ok(None);
+                };
+
+                let tags = match option_group.option_group_arn() {
+                    Some(arn) => client.list_tags_for_resource().resource_name(arn).send().await?.tag_list.into(),
+                    None => Tags::default(),
+                };
+
+                let group = crate::resource::RdsResource::OptionGroup(map_option_group(option_group, tags)?);
+                get_resource_response!(
+                    group,
+                    [
+                        (String::from("name"), name),
+                        (String::from("arn"), option_group.option_group_arn().unwrap_or_default().to_string())
+                    ]
+                )
+            }
         }
     }
 }
@@ -208,6 +262,7 @@ fn map_db_instance(db_instance: &DbInstance) -> Result<crate::resource::RdsDBIns
             .iter()
             .map(|s| s.to_string())
             .collect(),
+        db_cluster_identifier: db_instance.db_cluster_identifier().map(|s| s.to_string()),
         tags: db_instance.tag_list.clone().into(),
     })
 }
@@ -246,7 +301,7 @@ fn map_db_cluster(db_cluster: &aws_sdk_rds::types::DbCluster) -> Result<crate::r
             .as_ref()
             .map(|exports| exports.iter().map(|s| s.to_string()).collect()),
         enable_http_endpoint: db_cluster.http_endpoint_enabled(),
-        global_cluster_identifier: db_cluster.db_cluster_identifier().map(|s| s.to_string()),
+        global_cluster_identifier: None, // Not reported by DescribeDBClusters; see DescribeGlobalClusters
         replication_source_identifier: db_cluster.replication_source_identifier().map(|s| s.to_string()),
         restore_type: None,            // This is a create parameter, not stored in the cluster
         source_engine: None,           // This is a restore parameter, not stored in the cluster
@@ -277,10 +332,45 @@ fn map_db_subnet_group(
 
 fn map_db_parameter_group(
     db_parameter_group: &aws_sdk_rds::types::DbParameterGroup,
+    parameters: HashMap<String, String>,
 ) -> Result<crate::resource::RdsDBParameterGroup, anyhow::Error> {
     Ok(crate::resource::RdsDBParameterGroup {
         description: db_parameter_group.description.clone(),
         family: db_parameter_group.db_parameter_group_family().unwrap_or_default().to_string(),
-        parameters: HashMap::new(), // TODO: Implement parameter retrieval
+        parameters,
+    })
+}
+
+fn map_option_group(
+    option_group: &aws_sdk_rds::types::OptionGroup,
+    tags: Tags,
+) -> Result<crate::resource::RdsOptionGroup, anyhow::Error> {
+    Ok(crate::resource::RdsOptionGroup {
+        engine_name: option_group.engine_name().unwrap_or_default().to_string(),
+        major_engine_version: option_group.major_engine_version().unwrap_or_default().to_string(),
+        description: option_group.option_group_description().unwrap_or_default().to_string(),
+        options: option_group
+            .options()
+            .iter()
+            .map(|option| crate::resource::RdsOptionConfiguration {
+                option_name: option.option_name().unwrap_or_default().to_string(),
+                option_version: option.option_version().map(|s| s.to_string()),
+                port: option.port(),
+                vpc_security_group_ids: option
+                    .vpc_security_group_memberships()
+                    .iter()
+                    .filter_map(|sg| sg.vpc_security_group_id())
+                    .map(|s| s.to_string())
+                    .collect(),
+                // As with parameter groups, skip settings that are still at their default value.
+                option_settings: option
+                    .option_settings()
+                    .iter()
+                    .filter(|setting| setting.value() != setting.default_value())
+                    .filter_map(|setting| Some((setting.name()?.to_string(), setting.value()?.to_string())))
+                    .collect(),
+            })
+            .collect(),
+        tags,
     })
 }
@@ -20,11 +20,11 @@ impl RdsConnector {
                 };
 
                 for instance in instances {
-                    if let Some(name) = instance.db_name {
+                    if let Some(id) = instance.db_instance_identifier {
                         results.push(
                             RdsResourceAddress::DBInstance {
                                 region: region.into(),
-                                id: name,
+                                id,
                             }
                             .to_path_buf(),
                         );
@@ -40,11 +40,11 @@ impl RdsConnector {
                 };
 
                 for cluster in clusters {
-                    if let Some(name) = cluster.database_name {
+                    if let Some(id) = cluster.db_cluster_identifier {
                         results.push(
                             RdsResourceAddress::DBCluster {
                                 region: region.into(),
-                                id: name,
+                                id,
                             }
                             .to_path_buf(),
                         );
@@ -72,7 +72,7 @@ impl RdsConnector {
                 }
             }
 
-            // List subnet_groups
+            // List parameter_groups
             let mut parameter_groups = client.describe_db_parameter_groups().into_paginator().send();
             while let Some(parameter_groups) = parameter_groups.next().await {
                 let Some(parameter_groups) = parameter_groups?.db_parameter_groups else {
@@ -80,7 +80,10 @@ impl RdsConnector {
                 };
 
                 for parameter_group in parameter_groups {
-                    if let Some(name) = parameter_group.db_parameter_group_name {
+                    // Default parameter groups are owned by RDS and can't be modified
+                    if let Some(name) = parameter_group.db_parameter_group_name
+                        && !name.starts_with("default.")
+                    {
                         results.push(
                             RdsResourceAddress::DBParameterGroup {
                                 region: region.into(),
@@ -91,6 +94,29 @@ impl RdsConnector {
                     }
                 }
             }
+
+            // List option_groups
+            let mut option_groups = client.describe_option_groups().into_paginator().send();
+            while let Some(option_groups) = option_groups.next().await {
+                let Some(option_groups) = option_groups?.option_groups_list else {
+                    break;
+                };
+
+                for option_group in option_groups {
+                    // Likewise for default option groups
+                    if let Some(name) = option_group.option_group_name
+                        && !name.starts_with("default:")
+                    {
+                        results.push(
+                            RdsResourceAddress::OptionGroup {
+                                region: region.into(),
+                                name,
+                            }
+                            .to_path_buf(),
+                        );
+                    }
+                }
+            }
         }
 
         Ok(results)
@@ -7,7 +7,7 @@ use autoschematic_core::{
     op_exec_output,
 };
 
-use crate::{addr::RdsResourceAddress, op::RdsConnectorOp, tags::tag_diff};
+use crate::{addr::RdsResourceAddress, op::RdsConnectorOp, resource::RdsOptionConfiguration, tags::tag_diff};
 
 use super::RdsConnector;
 
@@ -169,6 +169,10 @@ impl RdsConnector {
                             request = request.domain_iam_role_name(domain_role);
                         }
 
+                        if let Some(cluster_id) = &instance.db_cluster_identifier {
+                            request = request.db_cluster_identifier(cluster_id);
+                        }
+
                         if !instance.enabled_cloudwatch_logs_exports.is_empty() {
                             request = request
                                 .set_enable_cloudwatch_logs_exports(Some(instance.enabled_cloudwatch_logs_exports.clone()));
@@ -264,6 +268,7 @@ impl RdsConnector {
                     }
                     RdsConnectorOp::ModifyDBInstance {
                         instance_class,
+                        engine_version,
                         allocated_storage,
                         max_allocated_storage,
                         backup_retention_period,
@@ -275,6 +280,9 @@ impl RdsConnector {
                         enable_iam_database_authentication,
                         auto_minor_version_upgrade,
                         deletion_protection,
+                        db_parameter_group_name,
+                        option_group_name,
+                        vpc_security_group_ids,
                         apply_immediately,
                     } => {
                         let mut request = client.modify_db_instance().db_instance_identifier(id);
@@ -283,6 +291,10 @@ impl RdsConnector {
                             request = request.db_instance_class(class);
                         }
 
+                        if let Some(version) = engine_version {
+                            request = request.engine_version(version);
+                        }
+
                         if let Some(storage) = allocated_storage {
                             request = request.allocated_storage(storage);
                         }
@@ -329,6 +341,18 @@ impl RdsConnector {
                             request = request.deletion_protection(deletion_prot);
                         }
 
+                        if let Some(param_group) = db_parameter_group_name {
+                            request = request.db_parameter_group_name(param_group);
+                        }
+
+                        if let Some(option_group) = option_group_name {
+                            request = request.option_group_name(option_group);
+                        }
+
+                        if let Some(security_groups) = vpc_security_group_ids {
+                            request = request.set_vpc_security_group_ids(Some(security_groups));
+                        }
+
                         if let Some(apply_now) = apply_immediately {
                             request = request.apply_immediately(apply_now);
                         }
@@ -388,6 +412,11 @@ impl RdsConnector {
                     RdsConnectorOp::CreateDBCluster(cluster) => {
                         let mut request = client.create_db_cluster().db_cluster_identifier(id).engine(&cluster.engine);
 
+                        // Replicas and global secondaries inherit their credentials from the source.
+                        if cluster.replication_source_identifier.is_none() && cluster.global_cluster_identifier.is_none() {
+                            request = request.manage_master_user_password(true);
+                        }
+
                         if let Some(version) = &cluster.engine_version {
                             request = request.engine_version(version);
                         }
@@ -420,6 +449,67 @@ impl RdsConnector {
                             request = request.deletion_protection(deletion_protection);
                         }
 
+                        if let Some(database_name) = &cluster.database_name {
+                            request = request.database_name(database_name);
+                        }
+
+                        if let Some(kms_key) = &cluster.kms_key_id {
+                            request = request.kms_key_id(kms_key);
+                        }
+
+                        if let Some(iam_auth) = cluster.enable_iam_database_authentication {
+                            request = request.enable_iam_database_authentication(iam_auth);
+                        }
+
+                        if let Some(copy_tags) = cluster.copy_tags_to_snapshot {
+                            request = request.copy_tags_to_snapshot(copy_tags);
+                        }
+
+                        if let Some(azs) = &cluster.availability_zones {
+                            request = request.set_availability_zones(Some(azs.clone()));
+                        }
+
+                        if let Some(subnet_group) = &cluster.db_subnet_group_name {
+                            request = request.db_subnet_group_name(subnet_group);
+                        }
+
+                        if let Some(security_groups) = &cluster.vpc_security_group_ids {
+                            request = request.set_vpc_security_group_ids(Some(security_groups.clone()));
+                        }
+
+                        if let Some(param_group) = &cluster.db_cluster_parameter_group_name {
+                            request = request.db_cluster_parameter_group_name(param_group);
+                        }
+
+                        if let Some(backtrack) = cluster.backtrack_window {
+                            request = request.backtrack_window(backtrack);
+                        }
+
+                        if let Some(log_exports) = &cluster.enabled_cloudwatch_logs_exports {
+                            request = request.set_enable_cloudwatch_logs_exports(Some(log_exports.clone()));
+                        }
+
+                        if let Some(http_endpoint) = cluster.enable_http_endpoint {
+                            request = request.enable_http_endpoint(http_endpoint);
+                        }
+
+                        if let Some(global_cluster) = &cluster.global_cluster_identifier {
+                            request = request.global_cluster_identifier(global_cluster);
+                        }
+
+                        if let Some(source) = &cluster.replication_source_identifier {
+                            request = request.replication_source_identifier(source);
+                        }
+
+                        if let Some(scaling) = &cluster.serverless_v2_scaling_configuration {
+                            request = request.serverless_v2_scaling_configuration(
+                                aws_sdk_rds::types::ServerlessV2ScalingConfiguration::builder()
+                                    .set_min_capacity(scaling.min_capacity)
+                                    .set_max_capacity(scaling.max_capacity)
+                                    .build(),
+                            );
+                        }
+
                         // Add tags if provided
                         request = request.set_tags(cluster.tags.clone().into());
 
@@ -506,6 +596,8 @@ impl RdsConnector {
                         deletion_protection,
                         enable_iam_database_authentication,
                         backtrack_window,
+                        db_cluster_parameter_group_name,
+                        vpc_security_group_ids,
                         master_user_password,
                         apply_immediately,
                     } => {
@@ -539,6 +631,14 @@ impl RdsConnector {
                             request = request.backtrack_window(backtrack);
                         }
 
+                        if let Some(param_group) = db_cluster_parameter_group_name {
+                            request = request.db_cluster_parameter_group_name(param_group);
+                        }
+
+                        if let Some(security_groups) = vpc_security_group_ids {
+                            request = request.set_vpc_security_group_ids(Some(security_groups));
+                        }
+
                         if let Some(password) = master_user_password {
                             request = request.master_user_password(password);
                         }
@@ -631,24 +731,7 @@ impl RdsConnector {
 
                         // Apply parameters if any
                         if !param_group.parameters.is_empty() {
-                            let parameters: Vec<_> = param_group
-                                .parameters
-                                .iter()
-                                .map(|(key, value)| {
-                                    aws_sdk_rds::types::Parameter::builder()
-                                        .parameter_name(key)
-                                        .parameter_value(value)
-                                        .apply_method(aws_sdk_rds::types::ApplyMethod::Immediate)
-                                        .build()
-                                })
-                                .collect();
-
-                            client
-                                .modify_db_parameter_group()
-                                .db_parameter_group_name(name)
-                                .set_parameters(Some(parameters))
-                                .send()
-                                .await?;
+                            modify_db_parameters(&client, name, &param_group.parameters).await?;
                         }
 
                         op_exec_output!(
@@ -660,23 +743,7 @@ impl RdsConnector {
                         )
                     }
                     RdsConnectorOp::ModifyDBParameterGroup { parameters } => {
-                        let parameters: Vec<_> = parameters
-                            .iter()
-                            .map(|(key, value)| {
-                                aws_sdk_rds::types::Parameter::builder()
-                                    .parameter_name(key)
-                                    .parameter_value(value)
-                                    .apply_method(aws_sdk_rds::types::ApplyMethod::Immediate)
-                                    .build()
-                            })
-                            .collect();
-
-                        client
-                            .modify_db_parameter_group()
-                            .db_parameter_group_name(name)
-                            .set_parameters(Some(parameters))
-                            .send()
-                            .await?;
+                        modify_db_parameters(&client, name, &parameters).await?;
 
                         op_exec_output!(format!("Modified DB parameter group `{}`", name))
                     }
@@ -720,6 +787,154 @@ impl RdsConnector {
                     _ => Err(invalid_op(&addr, &op)),
                 }
             }
+            RdsResourceAddress::OptionGroup { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    RdsConnectorOp::CreateOptionGroup(option_group) => {
+                        let response = client
+                            .create_option_group()
+                            .option_group_name(name)
+                            .engine_name(&option_group.engine_name)
+                            .major_engine_version(&option_group.major_engine_version)
+                            .option_group_description(&option_group.description)
+                            .set_tags(option_group.tags.clone().into())
+                            .send()
+                            .await?;
+
+                        let option_group_arn = response
+                            .option_group()
+                            .and_then(|og| og.option_group_arn())
+                            .context("Failed to get option group ARN from response")?;
+
+                        if !option_group.options.is_empty() {
+                            client
+                                .modify_option_group()
+                                .option_group_name(name)
+                                .set_options_to_include(Some(build_option_configurations(&option_group.options)?))
+                                .apply_immediately(true)
+                                .send()
+                                .await?;
+                        }
+
+                        op_exec_output!(
+                            Some([("arn", Some(option_group_arn.to_string())), ("name", Some(name.clone()))]),
+                            format!("Created option group `{}`", name)
+                        )
+                    }
+                    RdsConnectorOp::UpdateOptionGroupTags(old_tags, new_tags) => {
+                        let response = client.describe_option_groups().option_group_name(name).send().await?;
+
+                        let option_group_arn = response
+                            .option_groups_list()
+                            .first()
+                            .and_then(|og| og.option_group_arn())
+                            .context("Option group not found")?;
+
+                        let (remove_keys, add_tags) = tag_diff(&old_tags, &new_tags)?;
+
+                        if !remove_keys.is_empty() {
+                            client
+                                .remove_tags_from_resource()
+                                .resource_name(option_group_arn)
+                                .set_tag_keys(Some(remove_keys))
+                                .send()
+                                .await?;
+                        }
+
+                        if !add_tags.is_empty() {
+                            client
+                                .add_tags_to_resource()
+                                .resource_name(option_group_arn)
+                                .set_tags(Some(add_tags))
+                                .send()
+                                .await?;
+                        }
+
+                        op_exec_output!(format!("Updated tags for option group `{}`", name))
+                    }
+                    RdsConnectorOp::ModifyOptionGroup {
+                        options_to_include,
+                        options_to_remove,
+                        apply_immediately,
+                    } => {
+                        let mut request = client
+                            .modify_option_group()
+                            .option_group_name(name)
+                            .apply_immediately(apply_immediately);
+
+                        if !options_to_include.is_empty() {
+                            request = request.set_options_to_include(Some(build_option_configurations(&options_to_include)?));
+                        }
+
+                        if !options_to_remove.is_empty() {
+                            request = request.set_options_to_remove(Some(options_to_remove));
+                        }
+
+                        request.send().await?;
+
+                        op_exec_output!(format!("Modified option group `{}`", name))
+                    }
+                    RdsConnectorOp::DeleteOptionGroup => {
+                        client.delete_option_group().option_group_name(name).send().await?;
+
+                        op_exec_output!(format!("Deleted option group `{}`", name))
+                    }
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+        }
+    }
+}
+
+/// ModifyDBParameterGroup accepts at most 20 parameters per request.
+async fn modify_db_parameters(
+    client: &aws_sdk_rds::Client,
+    name: &str,
+    parameters: &HashMap<String, String>,
+) -> anyhow::Result<()> {
+    let parameters: Vec<_> = parameters
+        .iter()
+        .map(|(key, value)| {
+            aws_sdk_rds::types::Parameter::builder()
+                .parameter_name(key)
+                .parameter_value(value)
+                .apply_method(aws_sdk_rds::types::ApplyMethod::Immediate)
+                .build()
+        })
+        .collect();
+
+    for chunk in parameters.chunks(20) {
+        client
+            .modify_db_parameter_group()
+            .db_parameter_group_name(name)
+            .set_parameters(Some(chunk.to_vec()))
+            .send()
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn build_option_configurations(
+    options: &[RdsOptionConfiguration],
+) -> anyhow::Result<Vec<aws_sdk_rds::types::OptionConfiguration>> {
+    let mut out = Vec::new();
+    for option in options {
+        let mut builder = aws_sdk_rds::types::OptionConfiguration::builder()
+            .option_name(&option.option_name)
+            .set_option_version(option.option_version.clone())
+            .set_port(option.port);
+
+        if !option.vpc_security_group_ids.is_empty() {
+            builder = builder.set_vpc_security_group_memberships(Some(option.vpc_security_group_ids.clone()));
+        }
+
+        for (key, value) in &option.option_settings {
+            builder = builder.option_settings(aws_sdk_rds::types::OptionSetting::builder().name(key).value(value).build());
         }
+
+        out.push(builder.build()?);
     }
+    Ok(out)
 }
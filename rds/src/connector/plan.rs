@@ -1,9 +1,9 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 use crate::{
     addr::RdsResourceAddress,
     op::RdsConnectorOp,
-    resource::{RdsDBCluster, RdsDBInstance, RdsDBParameterGroup, RdsDBSubnetGroup},
+    resource::{RdsDBCluster, RdsDBInstance, RdsDBParameterGroup, RdsDBSubnetGroup, RdsOptionConfiguration, RdsOptionGroup},
 };
 use autoschematic_core::{
     connector::{ConnectorOp, PlanResponseElement, ResourceAddress},
@@ -27,7 +27,7 @@ impl RdsConnector {
         let mut res = Vec::new();
 
         match addr {
-            RdsResourceAddress::DBInstance { region, id } => {
+            RdsResourceAddress::DBInstance { id, .. } => {
                 match (current, desired) {
                     (None, None) => {}
                     (None, Some(new_instance)) => {
@@ -65,6 +65,7 @@ impl RdsConnector {
 
                             // Check for instance modification
                             let needs_modification = old_instance.instance_class != new_instance.instance_class
+                                || old_instance.engine_version != new_instance.engine_version
                                 || old_instance.allocated_storage != new_instance.allocated_storage
                                 || old_instance.max_allocated_storage != new_instance.max_allocated_storage
                                 || old_instance.backup_retention_period != new_instance.backup_retention_period
@@ -81,7 +82,10 @@ impl RdsConnector {
                                     != new_instance.performance_insights_retention_period
                                 || old_instance.monitoring_interval != new_instance.monitoring_interval
                                 || old_instance.auto_minor_version_upgrade != new_instance.auto_minor_version_upgrade
-                                || old_instance.deletion_protection != new_instance.deletion_protection;
+                                || old_instance.deletion_protection != new_instance.deletion_protection
+                                || old_instance.db_parameter_group_name != new_instance.db_parameter_group_name
+                                || old_instance.option_group_name != new_instance.option_group_name
+                                || old_instance.vpc_security_group_ids != new_instance.vpc_security_group_ids;
 
                             if needs_modification {
                                 res.push(connector_op!(
@@ -91,6 +95,11 @@ impl RdsConnector {
                                         } else {
                                             None
                                         },
+                                        engine_version: if old_instance.engine_version != new_instance.engine_version {
+                                            new_instance.engine_version.clone()
+                                        } else {
+                                            None
+                                        },
                                         allocated_storage: if old_instance.allocated_storage != new_instance.allocated_storage {
                                             new_instance.allocated_storage
                                         } else {
@@ -156,6 +165,25 @@ impl RdsConnector {
                                         } else {
                                             None
                                         },
+                                        db_parameter_group_name: if old_instance.db_parameter_group_name
+                                            != new_instance.db_parameter_group_name
+                                        {
+                                            new_instance.db_parameter_group_name.clone()
+                                        } else {
+                                            None
+                                        },
+                                        option_group_name: if old_instance.option_group_name != new_instance.option_group_name {
+                                            new_instance.option_group_name.clone()
+                                        } else {
+                                            None
+                                        },
+                                        vpc_security_group_ids: if old_instance.vpc_security_group_ids
+                                            != new_instance.vpc_security_group_ids
+                                        {
+                                            new_instance.vpc_security_group_ids.clone()
+                                        } else {
+                                            None
+                                        },
                                         apply_immediately: Some(true), // Apply changes immediately by default
                                     },
                                     format!("Modify RDS DB Instance {}", id)
@@ -183,7 +211,7 @@ impl RdsConnector {
                     }
                 }
             }
-            RdsResourceAddress::DBCluster { region, id } => {
+            RdsResourceAddress::DBCluster { id, .. } => {
                 match (current, desired) {
                     (None, None) => {}
                     (None, Some(new_cluster)) => {
@@ -228,7 +256,9 @@ impl RdsConnector {
                                 || old_cluster.enable_iam_database_authentication
                                     != new_cluster.enable_iam_database_authentication
                                 || old_cluster.backtrack_window != new_cluster.backtrack_window
-                                || old_cluster.enabled_cloudwatch_logs_exports != new_cluster.enabled_cloudwatch_logs_exports;
+                                || old_cluster.enabled_cloudwatch_logs_exports != new_cluster.enabled_cloudwatch_logs_exports
+                                || old_cluster.db_cluster_parameter_group_name != new_cluster.db_cluster_parameter_group_name
+                                || old_cluster.vpc_security_group_ids != new_cluster.vpc_security_group_ids;
 
                             if needs_modification {
                                 res.push(connector_op!(
@@ -278,6 +308,20 @@ impl RdsConnector {
                                         } else {
                                             None
                                         },
+                                        db_cluster_parameter_group_name: if old_cluster.db_cluster_parameter_group_name
+                                            != new_cluster.db_cluster_parameter_group_name
+                                        {
+                                            new_cluster.db_cluster_parameter_group_name.clone()
+                                        } else {
+                                            None
+                                        },
+                                        vpc_security_group_ids: if old_cluster.vpc_security_group_ids
+                                            != new_cluster.vpc_security_group_ids
+                                        {
+                                            new_cluster.vpc_security_group_ids.clone()
+                                        } else {
+                                            None
+                                        },
                                         master_user_password: None, // Don't include password in modify operations
                                         apply_immediately: Some(true), // Apply changes immediately by default
                                     },
@@ -288,7 +332,7 @@ impl RdsConnector {
                     }
                 }
             }
-            RdsResourceAddress::DBSubnetGroup { region, name } => match (current, desired) {
+            RdsResourceAddress::DBSubnetGroup { name, .. } => match (current, desired) {
                 (None, None) => {}
                 (None, Some(new_subnet_group)) => {
                     let new_subnet_group: RdsDBSubnetGroup = RON.from_str(&new_subnet_group)?;
@@ -315,18 +359,15 @@ impl RdsConnector {
                                 } else {
                                     None
                                 },
-                                subnet_ids:  if old_subnet_group.subnet_ids != new_subnet_group.subnet_ids {
-                                    new_subnet_group.subnet_ids.clone()
-                                } else {
-                                    vec![]
-                                },
+                                // ModifyDBSubnetGroup always requires the full set of subnets
+                                subnet_ids: new_subnet_group.subnet_ids.clone(),
                             },
                             format!("Modify RDS DB Subnet Group {}", name)
                         ));
                     }
                 }
             },
-            RdsResourceAddress::DBParameterGroup { region, name } => match (current, desired) {
+            RdsResourceAddress::DBParameterGroup { name, .. } => match (current, desired) {
                 (None, None) => {}
                 (None, Some(new_parameter_group)) => {
                     let new_parameter_group: RdsDBParameterGroup = RON.from_str(&new_parameter_group)?;
@@ -345,13 +386,133 @@ impl RdsConnector {
                     let old_parameter_group: RdsDBParameterGroup = RON.from_str(&old_parameter_group)?;
                     let new_parameter_group: RdsDBParameterGroup = RON.from_str(&new_parameter_group)?;
 
-                    if old_parameter_group.parameters != new_parameter_group.parameters {
+                    let replace_fields = replacement_fields(&[
+                        ("family", old_parameter_group.family != new_parameter_group.family),
+                        ("description", old_parameter_group.description != new_parameter_group.description),
+                    ]);
+
+                    if !replace_fields.is_empty() {
                         res.push(connector_op!(
-                            RdsConnectorOp::ModifyDBParameterGroup {
-                                parameters: new_parameter_group.parameters.clone(),
-                            },
-                            format!("Modify parameters for RDS DB Parameter Group {}", name)
+                            RdsConnectorOp::DeleteDBParameterGroup,
+                            replacement_message("RDS DB Parameter Group", &name, &replace_fields)
+                        ));
+                        res.push(connector_op!(
+                            RdsConnectorOp::CreateDBParameterGroup(new_parameter_group),
+                            format!("Create new RDS DB Parameter Group {}", name)
+                        ));
+                    } else {
+                        let changed: HashMap<String, String> = new_parameter_group
+                            .parameters
+                            .iter()
+                            .filter(|(k, v)| old_parameter_group.parameters.get(*k) != Some(*v))
+                            .map(|(k, v)| (k.clone(), v.clone()))
+                            .collect();
+
+                        let mut removed: Vec<String> = old_parameter_group
+                            .parameters
+                            .keys()
+                            .filter(|k| !new_parameter_group.parameters.contains_key(*k))
+                            .cloned()
+                            .collect();
+                        removed.sort();
+
+                        if !changed.is_empty() {
+                            let diff =
+                                diff_ron_values(&old_parameter_group.parameters, &new_parameter_group.parameters).unwrap_or_default();
+                            res.push(connector_op!(
+                                RdsConnectorOp::ModifyDBParameterGroup { parameters: changed },
+                                format!("Modify parameters for RDS DB Parameter Group {}\n{}", name, diff)
+                            ));
+                        }
+
+                        if !removed.is_empty() {
+                            let message = format!(
+                                "Reset parameters {} to their defaults for RDS DB Parameter Group {}",
+                                removed.join(", "),
+                                name
+                            );
+                            res.push(connector_op!(
+                                RdsConnectorOp::ResetDBParameterGroup {
+                                    reset_all_parameters: None,
+                                    parameters: Some(removed),
+                                },
+                                message
+                            ));
+                        }
+                    }
+                }
+            },
+            RdsResourceAddress::OptionGroup { name, .. } => match (current, desired) {
+                (None, None) => {}
+                (None, Some(new_option_group)) => {
+                    let new_option_group: RdsOptionGroup = RON.from_str(&new_option_group)?;
+                    res.push(connector_op!(
+                        RdsConnectorOp::CreateOptionGroup(new_option_group),
+                        format!("Create new RDS Option Group {}", name)
+                    ));
+                }
+                (Some(_old_option_group), None) => {
+                    res.push(connector_op!(
+                        RdsConnectorOp::DeleteOptionGroup,
+                        format!("DELETE RDS Option Group {}", name)
+                    ));
+                }
+                (Some(old_option_group), Some(new_option_group)) => {
+                    let old_option_group: RdsOptionGroup = RON.from_str(&old_option_group)?;
+                    let new_option_group: RdsOptionGroup = RON.from_str(&new_option_group)?;
+
+                    let replace_fields = replacement_fields(&[
+                        ("engine_name", old_option_group.engine_name != new_option_group.engine_name),
+                        (
+                            "major_engine_version",
+                            old_option_group.major_engine_version != new_option_group.major_engine_version,
+                        ),
+                        ("description", old_option_group.description != new_option_group.description),
+                    ]);
+
+                    if !replace_fields.is_empty() {
+                        res.push(connector_op!(
+                            RdsConnectorOp::DeleteOptionGroup,
+                            replacement_message("RDS Option Group", &name, &replace_fields)
+                        ));
+                        res.push(connector_op!(
+                            RdsConnectorOp::CreateOptionGroup(new_option_group),
+                            format!("Create new RDS Option Group {}", name)
                         ));
+                    } else {
+                        if old_option_group.tags != new_option_group.tags {
+                            let diff = diff_ron_values(&old_option_group.tags, &new_option_group.tags).unwrap_or_default();
+                            res.push(connector_op!(
+                                RdsConnectorOp::UpdateOptionGroupTags(old_option_group.tags.clone(), new_option_group.tags.clone()),
+                                format!("Modify tags for RDS Option Group `{}`\n{}", name, diff)
+                            ));
+                        }
+
+                        let options_to_include: Vec<RdsOptionConfiguration> = new_option_group
+                            .options
+                            .iter()
+                            .filter(|option| !old_option_group.options.contains(option))
+                            .cloned()
+                            .collect();
+
+                        let options_to_remove: Vec<String> = old_option_group
+                            .options
+                            .iter()
+                            .filter(|old| !new_option_group.options.iter().any(|new| new.option_name == old.option_name))
+                            .map(|old| old.option_name.clone())
+                            .collect();
+
+                        if !options_to_include.is_empty() || !options_to_remove.is_empty() {
+                            let diff = diff_ron_values(&old_option_group.options, &new_option_group.options).unwrap_or_default();
+                            res.push(connector_op!(
+                                RdsConnectorOp::ModifyOptionGroup {
+                                    options_to_include,
+                                    options_to_remove,
+                                    apply_immediately: true,
+                                },
+                                format!("Modify options for RDS Option Group {}\n{}", name, diff)
+                            ));
+                        }
                     }
                 }
             },
@@ -360,3 +521,11 @@ impl RdsConnector {
         Ok(res)
     }
 }
+
+fn replacement_fields(fields: &[(&'static str, bool)]) -> Vec<&'static str> {
+    fields.iter().filter(|(_, changed)| *changed).map(|(name, _)| *name).collect()
+}
+
+fn replacement_message(kind: &str, id: &str, fields: &[&str]) -> String {
+    format!("REPLACE {} `{}` (requires replacement: {})", kind, id, fields.join(", "))
+}
@@ -1,3 +1,5 @@
+use addr::RdsResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
 use autoschematic_core::tarpc_bridge::tarpc_connector_main;
 use connector::RdsConnector;
 
@@ -13,6 +15,12 @@ pub mod util;
 
 #[tokio::main]
 pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let patterns = RdsResourceAddress::checked_address_patterns()?;
+        print!("{}", render_address_docs("aws/rds", &patterns));
+        return Ok(());
+    }
+
     tarpc_connector_main::<RdsConnector>().await?;
     Ok(())
 }
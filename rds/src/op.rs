@@ -2,7 +2,7 @@ use autoschematic_core::{connector::ConnectorOp, util::RON};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use super::resource::{RdsDBCluster, RdsDBInstance, RdsDBParameterGroup, RdsDBSubnetGroup};
+use super::resource::{RdsDBCluster, RdsDBInstance, RdsDBParameterGroup, RdsDBSubnetGroup, RdsOptionConfiguration, RdsOptionGroup};
 
 type Tags = crate::tags::Tags;
 
@@ -13,6 +13,7 @@ pub enum RdsConnectorOp {
     UpdateDBInstanceTags(Tags, Tags),
     ModifyDBInstance {
         instance_class: Option<String>,
+        engine_version: Option<String>,
         allocated_storage: Option<i32>,
         max_allocated_storage: Option<i32>,
         backup_retention_period: Option<i32>,
@@ -24,6 +25,9 @@ pub enum RdsConnectorOp {
         enable_iam_database_authentication: Option<bool>,
         auto_minor_version_upgrade: Option<bool>,
         deletion_protection: Option<bool>,
+        db_parameter_group_name: Option<String>,
+        option_group_name: Option<String>,
+        vpc_security_group_ids: Option<Vec<String>>,
         apply_immediately: Option<bool>,
     },
     StartDBInstance,
@@ -31,15 +35,6 @@ pub enum RdsConnectorOp {
     RebootDBInstance {
         force_failover: Option<bool>,
     },
-    CreateDBSnapshot {
-        snapshot_identifier: String,
-        tags: Tags,
-    },
-    RestoreDBInstanceFromSnapshot {
-        snapshot_identifier: String,
-        instance_class:      Option<String>,
-        subnet_group_name:   Option<String>,
-    },
     DeleteDBInstance {
         skip_final_snapshot: bool,
         final_snapshot_identifier: Option<String>,
@@ -57,19 +52,13 @@ pub enum RdsConnectorOp {
         deletion_protection: Option<bool>,
         enable_iam_database_authentication: Option<bool>,
         backtrack_window: Option<i64>,
+        db_cluster_parameter_group_name: Option<String>,
+        vpc_security_group_ids: Option<Vec<String>>,
         master_user_password: Option<String>,
         apply_immediately: Option<bool>,
     },
     StartDBCluster,
     StopDBCluster,
-    CreateDBClusterSnapshot {
-        snapshot_identifier: String,
-        tags: Tags,
-    },
-    RestoreDBClusterFromSnapshot {
-        snapshot_identifier: String,
-        engine: Option<String>,
-    },
     DeleteDBCluster {
         skip_final_snapshot: bool,
         final_snapshot_identifier: Option<String>,
@@ -94,54 +83,13 @@ pub enum RdsConnectorOp {
     },
     DeleteDBParameterGroup,
 
-    // DB Cluster Parameter Group operations
-    CreateDBClusterParameterGroup {
-        name: String,
-        family: String,
-        description: Option<String>,
-        parameters: HashMap<String, String>,
-    },
-    ModifyDBClusterParameterGroup {
-        parameters: HashMap<String, String>,
-    },
-    ResetDBClusterParameterGroup {
-        reset_all_parameters: Option<bool>,
-        parameters: Option<Vec<String>>,
-    },
-    DeleteDBClusterParameterGroup,
-
-    // Security and networking operations
-    CreateDBSecurityGroup {
-        name: String,
-        description: String,
-        tags: Tags,
-    },
-    AuthorizeDBSecurityGroupIngress {
-        cidrip: Option<String>,
-        ec2_security_group_name: Option<String>,
-        ec2_security_group_id: Option<String>,
-        ec2_security_group_owner_id: Option<String>,
-    },
-    RevokeDBSecurityGroupIngress {
-        cidrip: Option<String>,
-        ec2_security_group_name: Option<String>,
-        ec2_security_group_id: Option<String>,
-        ec2_security_group_owner_id: Option<String>,
-    },
-    DeleteDBSecurityGroup,
-
     // Option Group operations
-    CreateOptionGroup {
-        name: String,
-        engine_name: String,
-        major_engine_version: String,
-        description: Option<String>,
-        tags: Tags,
-    },
+    CreateOptionGroup(RdsOptionGroup),
+    UpdateOptionGroupTags(Tags, Tags),
     ModifyOptionGroup {
-        options_to_include: Option<Vec<HashMap<String, String>>>,
-        options_to_remove:  Option<Vec<String>>,
-        apply_immediately:  Option<bool>,
+        options_to_include: Vec<RdsOptionConfiguration>,
+        options_to_remove:  Vec<String>,
+        apply_immediately:  bool,
     },
     DeleteOptionGroup,
 
@@ -152,60 +100,6 @@ pub enum RdsConnectorOp {
         enable_performance_insights: Option<bool>,
         performance_insights_retention_period: Option<i32>,
     },
-    CreateDBInstanceReadReplica {
-        replica_identifier: String,
-        source_db_identifier: String,
-        instance_class: Option<String>,
-        availability_zone: Option<String>,
-        port: Option<i32>,
-        multi_az: Option<bool>,
-        publicly_accessible: Option<bool>,
-        tags: Tags,
-    },
-    PromoteReadReplica {
-        backup_retention_period: Option<i32>,
-        preferred_backup_window: Option<String>,
-    },
-
-    // Backup and maintenance operations
-    CreateDBClusterEndpoint {
-        endpoint_identifier: String,
-        endpoint_type: String,
-        static_members: Option<Vec<String>>,
-        excluded_members: Option<Vec<String>>,
-        tags: Tags,
-    },
-    ModifyDBClusterEndpoint {
-        endpoint_type:    Option<String>,
-        static_members:   Option<Vec<String>>,
-        excluded_members: Option<Vec<String>>,
-    },
-    DeleteDBClusterEndpoint,
-
-    // Cross-region operations
-    CreateDBInstanceAutomatedBackup {
-        source_db_instance_arn: String,
-        kms_key_id: Option<String>,
-    },
-    DeleteDBInstanceAutomatedBackup,
-
-    // Event subscription operations
-    CreateEventSubscription {
-        subscription_name: String,
-        sns_topic_arn: String,
-        source_type: Option<String>,
-        event_categories: Option<Vec<String>>,
-        source_ids: Option<Vec<String>>,
-        enabled: Option<bool>,
-        tags: Tags,
-    },
-    ModifyEventSubscription {
-        sns_topic_arn: Option<String>,
-        source_type: Option<String>,
-        event_categories: Option<Vec<String>>,
-        enabled: Option<bool>,
-    },
-    DeleteEventSubscription,
 }
 
 impl ConnectorOp for RdsConnectorOp {
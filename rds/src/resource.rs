@@ -48,6 +48,9 @@ pub struct RdsDBInstance {
     pub domain: Option<String>,
     pub domain_iam_role_name: Option<String>,
     pub enabled_cloudwatch_logs_exports: Vec<String>,
+    /// Set for Aurora instances to make them members of the given cluster.
+    #[serde(default)]
+    pub db_cluster_identifier: Option<String>,
     pub tags: crate::tags::Tags,
 }
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -115,11 +118,35 @@ pub struct RdsDBParameterGroup {
     pub parameters:  std::collections::HashMap<String, String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RdsOptionConfiguration {
+    pub option_name: String,
+    pub option_version: Option<String>,
+    pub port: Option<i32>,
+    #[serde(default)]
+    pub vpc_security_group_ids: Vec<String>,
+    #[serde(default)]
+    pub option_settings: std::collections::HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RdsOptionGroup {
+    pub engine_name: String,
+    pub major_engine_version: String,
+    pub description: String,
+    #[serde(default)]
+    pub options: Vec<RdsOptionConfiguration>,
+    pub tags: crate::tags::Tags,
+}
+
 pub enum RdsResource {
     DBInstance(RdsDBInstance),
     DBCluster(RdsDBCluster),
     DBSubnetGroup(RdsDBSubnetGroup),
     DBParameterGroup(RdsDBParameterGroup),
+    OptionGroup(RdsOptionGroup),
 }
 
 impl Resource for RdsResource {
@@ -142,6 +169,10 @@ impl Resource for RdsResource {
                 Ok(s) => Ok(s.into()),
                 Err(e) => Err(e.into()),
             },
+            RdsResource::OptionGroup(group) => match RON.to_string_pretty(&group, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
         }
     }
 
@@ -153,10 +184,11 @@ impl Resource for RdsResource {
 
         let s = str::from_utf8(s)?;
         match addr {
-            RdsResourceAddress::DBInstance { region, id } => Ok(RdsResource::DBInstance(RON.from_str(s)?)),
-            RdsResourceAddress::DBCluster { region, id } => Ok(RdsResource::DBCluster(RON.from_str(s)?)),
-            RdsResourceAddress::DBSubnetGroup { region, name } => Ok(RdsResource::DBSubnetGroup(RON.from_str(s)?)),
-            RdsResourceAddress::DBParameterGroup { region, name } => Ok(RdsResource::DBParameterGroup(RON.from_str(s)?)),
+            RdsResourceAddress::DBInstance { .. } => Ok(RdsResource::DBInstance(RON.from_str(s)?)),
+            RdsResourceAddress::DBCluster { .. } => Ok(RdsResource::DBCluster(RON.from_str(s)?)),
+            RdsResourceAddress::DBSubnetGroup { .. } => Ok(RdsResource::DBSubnetGroup(RON.from_str(s)?)),
+            RdsResourceAddress::DBParameterGroup { .. } => Ok(RdsResource::DBParameterGroup(RON.from_str(s)?)),
+            RdsResourceAddress::OptionGroup { .. } => Ok(RdsResource::OptionGroup(RON.from_str(s)?)),
         }
     }
 }
@@ -0,0 +1,124 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum RedshiftResourceAddress {
+    Cluster { region: String, id: String },
+    ParameterGroup { region: String, name: String },
+    SubnetGroup { region: String, name: String },
+    SnapshotSchedule { region: String, id: String },
+    Namespace { region: String, name: String },
+    Workgroup { region: String, name: String },
+}
+
+impl ResourceAddress for RedshiftResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            RedshiftResourceAddress::Cluster { region, id } => PathBuf::from(format!("aws/redshift/{region}/clusters/{id}.ron")),
+            RedshiftResourceAddress::ParameterGroup { region, name } => {
+                PathBuf::from(format!("aws/redshift/{region}/parameter_groups/{name}.ron"))
+            }
+            RedshiftResourceAddress::SubnetGroup { region, name } => {
+                PathBuf::from(format!("aws/redshift/{region}/subnet_groups/{name}.ron"))
+            }
+            RedshiftResourceAddress::SnapshotSchedule { region, id } => {
+                PathBuf::from(format!("aws/redshift/{region}/snapshot_schedules/{id}.ron"))
+            }
+            RedshiftResourceAddress::Namespace { region, name } => {
+                PathBuf::from(format!("aws/redshift/{region}/serverless/namespaces/{name}.ron"))
+            }
+            RedshiftResourceAddress::Workgroup { region, name } => {
+                PathBuf::from(format!("aws/redshift/{region}/serverless/workgroups/{name}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "redshift", region, "clusters", id] if id.ends_with(".ron") => {
+                let id = id.strip_suffix(".ron").unwrap().to_string();
+                Ok(RedshiftResourceAddress::Cluster {
+                    region: region.to_string(),
+                    id,
+                })
+            }
+            ["aws", "redshift", region, "parameter_groups", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(RedshiftResourceAddress::ParameterGroup {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "redshift", region, "subnet_groups", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(RedshiftResourceAddress::SubnetGroup {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "redshift", region, "snapshot_schedules", id] if id.ends_with(".ron") => {
+                let id = id.strip_suffix(".ron").unwrap().to_string();
+                Ok(RedshiftResourceAddress::SnapshotSchedule {
+                    region: region.to_string(),
+                    id,
+                })
+            }
+            ["aws", "redshift", region, "serverless", "namespaces", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(RedshiftResourceAddress::Namespace {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "redshift", region, "serverless", "workgroups", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(RedshiftResourceAddress::Workgroup {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for RedshiftResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/redshift/<region>/clusters/<id>.ron",
+                description: "A provisioned Redshift cluster",
+                example:     "aws/redshift/us-east-1/clusters/analytics.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/redshift/<region>/parameter_groups/<name>.ron",
+                description: "A cluster parameter group",
+                example:     "aws/redshift/us-east-1/parameter_groups/analytics.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/redshift/<region>/subnet_groups/<name>.ron",
+                description: "A cluster subnet group",
+                example:     "aws/redshift/us-east-1/subnet_groups/private.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/redshift/<region>/snapshot_schedules/<id>.ron",
+                description: "A schedule for automated cluster snapshots",
+                example:     "aws/redshift/us-east-1/snapshot_schedules/every-8-hours.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/redshift/<region>/serverless/namespaces/<name>.ron",
+                description: "A Redshift Serverless namespace, which holds databases and users",
+                example:     "aws/redshift/us-east-1/serverless/namespaces/analytics.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/redshift/<region>/serverless/workgroups/<name>.ron",
+                description: "A Redshift Serverless workgroup, which provides compute for a namespace",
+                example:     "aws/redshift/us-east-1/serverless/workgroups/analytics.ron",
+            },
+        ]
+    }
+}
@@ -0,0 +1,292 @@
+pub use crate::addr::RedshiftResourceAddress;
+pub use crate::op::RedshiftConnectorOp;
+pub use crate::resource::RedshiftResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::RedshiftConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{Cluster, Namespace, ParameterGroup, SnapshotSchedule, SubnetGroup, Workgroup};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct RedshiftConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_redshift::Client>>>,
+    serverless_client_cache: Mutex<HashMap<String, Arc<aws_sdk_redshiftserverless::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<RedshiftConnectorConfig>,
+    prefix: PathBuf,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl RedshiftConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_redshift::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_redshift, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+    pub async fn get_or_init_serverless_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_redshiftserverless::Client>> {
+        let mut cache = self.serverless_client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_redshiftserverless, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get Redshift Serverless client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for RedshiftConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = RedshiftResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(RedshiftConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let redshift_config: RedshiftConnectorConfig = RedshiftConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = redshift_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.serverless_client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(redshift_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = redshift_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // A small two-node RA3 cluster in a private subnet group
+        res.push(skeleton!(
+            RedshiftResourceAddress::Cluster {
+                region: String::from("[region]"),
+                id:     String::from("[cluster_id]"),
+            },
+            RedshiftResource::Cluster(Cluster {
+                node_type: String::from("ra3.xlplus"),
+                number_of_nodes: 2,
+                master_username: String::from("admin"),
+                db_name: None,
+                port: None,
+                cluster_subnet_group_name: Some(String::from("[subnet_group_name]")),
+                vpc_security_group_ids: vec![String::from("[security_group_id]")],
+                cluster_parameter_group_name: Some(String::from("[parameter_group_name]")),
+                publicly_accessible: false,
+                encrypted: true,
+                kms_key_id: None,
+                enhanced_vpc_routing: true,
+                automated_snapshot_retention_period: Some(7),
+                preferred_maintenance_window: Some(String::from("sun:05:00-sun:05:30")),
+                iam_roles: vec![String::from("[iam_role_arn]")],
+                default_iam_role_arn: Some(String::from("[iam_role_arn]")),
+                snapshot_schedule: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            RedshiftResourceAddress::ParameterGroup {
+                region: String::from("[region]"),
+                name:   String::from("[parameter_group_name]"),
+            },
+            RedshiftResource::ParameterGroup(ParameterGroup {
+                family: String::from("redshift-2.0"),
+                description: String::from("[description]"),
+                parameters: HashMap::from([(String::from("require_ssl"), String::from("true"))]),
+                tags: Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            RedshiftResourceAddress::SubnetGroup {
+                region: String::from("[region]"),
+                name:   String::from("[subnet_group_name]"),
+            },
+            RedshiftResource::SubnetGroup(SubnetGroup {
+                description: String::from("[description]"),
+                subnet_ids: vec![String::from("[subnet_id_a]"), String::from("[subnet_id_b]")],
+                tags: Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            RedshiftResourceAddress::SnapshotSchedule {
+                region: String::from("[region]"),
+                id:     String::from("[schedule_id]"),
+            },
+            RedshiftResource::SnapshotSchedule(SnapshotSchedule {
+                schedule_definitions: vec![String::from("rate(12 hours)")],
+                description: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            RedshiftResourceAddress::Namespace {
+                region: String::from("[region]"),
+                name:   String::from("[namespace_name]"),
+            },
+            RedshiftResource::Namespace(Namespace {
+                admin_username: Some(String::from("admin")),
+                db_name: None,
+                kms_key_id: None,
+                iam_roles: Vec::new(),
+                default_iam_role_arn: None,
+                log_exports: vec![String::from("userlog"), String::from("connectionlog")],
+                tags: Tags::default(),
+            })
+        ));
+
+        // The smallest base capacity, with a cap on scaling
+        res.push(skeleton!(
+            RedshiftResourceAddress::Workgroup {
+                region: String::from("[region]"),
+                name:   String::from("[workgroup_name]"),
+            },
+            RedshiftResource::Workgroup(Workgroup {
+                namespace: String::from("[namespace_name]"),
+                base_capacity: Some(8),
+                max_capacity: Some(64),
+                subnet_ids: vec![
+                    String::from("[subnet_id_a]"),
+                    String::from("[subnet_id_b]"),
+                    String::from("[subnet_id_c]"),
+                ],
+                security_group_ids: vec![String::from("[security_group_id]")],
+                publicly_accessible: false,
+                enhanced_vpc_routing: false,
+                port: None,
+                config_parameters: HashMap::new(),
+                tags: Tags::default(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = RedshiftResourceAddress::from_path(addr)?;
+
+        match addr {
+            RedshiftResourceAddress::Cluster { .. } => ron_check_eq::<Cluster>(a, b),
+            RedshiftResourceAddress::ParameterGroup { .. } => ron_check_eq::<ParameterGroup>(a, b),
+            RedshiftResourceAddress::SubnetGroup { .. } => ron_check_eq::<SubnetGroup>(a, b),
+            RedshiftResourceAddress::SnapshotSchedule { .. } => ron_check_eq::<SnapshotSchedule>(a, b),
+            RedshiftResourceAddress::Namespace { .. } => ron_check_eq::<Namespace>(a, b),
+            RedshiftResourceAddress::Workgroup { .. } => ron_check_eq::<Workgroup>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = RedshiftResourceAddress::from_path(addr)?;
+
+        match addr {
+            RedshiftResourceAddress::Cluster { .. } => ron_check_syntax::<Cluster>(a),
+            RedshiftResourceAddress::ParameterGroup { .. } => ron_check_syntax::<ParameterGroup>(a),
+            RedshiftResourceAddress::SubnetGroup { .. } => ron_check_syntax::<SubnetGroup>(a),
+            RedshiftResourceAddress::SnapshotSchedule { .. } => ron_check_syntax::<SnapshotSchedule>(a),
+            RedshiftResourceAddress::Namespace { .. } => ron_check_syntax::<Namespace>(a),
+            RedshiftResourceAddress::Workgroup { .. } => ron_check_syntax::<Workgroup>(a),
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use std::{collections::HashMap, path::Path};
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+
+use crate::{
+    addr::RedshiftResourceAddress,
+    op_impl::{find_cluster, find_namespace, find_workgroup},
+    resource::{Cluster, Namespace, ParameterGroup, RedshiftResource, SnapshotSchedule, SubnetGroup, Workgroup},
+    tags::Tags,
+    util::namespace_iam_roles,
+};
+
+use super::RedshiftConnector;
+
+const DEFAULT_DB_NAME: &str = "dev";
+const DEFAULT_PORT: i32 = 5439;
+
+impl RedshiftConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = RedshiftResourceAddress::from_path(addr)?;
+
+        match &addr {
+            RedshiftResourceAddress::Cluster { region, id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(cluster) = find_cluster(&client, id).await? else {
+                    return Ok(None);
+                };
+                if cluster.cluster_status.as_deref() == Some("deleting") {
+                    return Ok(None);
+                }
+
+                let mut vpc_security_group_ids: Vec<String> = cluster
+                    .vpc_security_groups()
+                    .iter()
+                    .filter_map(|group| group.vpc_security_group_id.clone())
+                    .collect();
+                vpc_security_group_ids.sort();
+                let mut iam_roles: Vec<String> = cluster.iam_roles().iter().filter_map(|role| role.iam_role_arn.clone()).collect();
+                iam_roles.sort();
+
+                // Clusters without a parameter group of their own report the default one for their family
+                let cluster_parameter_group_name = cluster
+                    .cluster_parameter_groups()
+                    .first()
+                    .and_then(|group| group.parameter_group_name.clone())
+                    .filter(|name| !name.starts_with("default."));
+
+                let resource = Cluster {
+                    node_type: cluster.node_type.clone().unwrap_or_default(),
+                    number_of_nodes: cluster.number_of_nodes.unwrap_or(1),
+                    master_username: cluster.master_username.clone().unwrap_or_default(),
+                    db_name: cluster.db_name.clone().filter(|db_name| db_name != DEFAULT_DB_NAME),
+                    port: cluster
+                        .endpoint
+                        .as_ref()
+                        .and_then(|endpoint| endpoint.port)
+                        .filter(|port| *port != DEFAULT_PORT),
+                    cluster_subnet_group_name: cluster.cluster_subnet_group_name.clone(),
+                    vpc_security_group_ids,
+                    cluster_parameter_group_name,
+                    publicly_accessible: cluster.publicly_accessible.unwrap_or(false),
+                    encrypted: cluster.encrypted.unwrap_or(false),
+                    kms_key_id: cluster.kms_key_id.clone(),
+                    enhanced_vpc_routing: cluster.enhanced_vpc_routing.unwrap_or(false),
+                    automated_snapshot_retention_period: cluster.automated_snapshot_retention_period,
+                    preferred_maintenance_window: cluster.preferred_maintenance_window.clone(),
+                    iam_roles,
+                    default_iam_role_arn: cluster.default_iam_role_arn.clone(),
+                    snapshot_schedule: cluster.snapshot_schedule_identifier.clone(),
+                    tags: Tags::from(cluster.tags.clone()),
+                };
+
+                get_resource_response!(
+                    RedshiftResource::Cluster(resource),
+                    [
+                        (
+                            String::from("endpoint_address"),
+                            cluster.endpoint.and_then(|endpoint| endpoint.address).unwrap_or_default()
+                        ),
+                        (
+                            String::from("master_password_secret_arn"),
+                            cluster.master_password_secret_arn.unwrap_or_default()
+                        ),
+                        (
+                            String::from("cluster_namespace_arn"),
+                            cluster.cluster_namespace_arn.unwrap_or_default()
+                        )
+                    ]
+                )
+            }
+            RedshiftResourceAddress::ParameterGroup { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let parameter_group = match client.describe_cluster_parameter_groups().parameter_group_name(name).send().await {
+                    Ok(resp) => resp.parameter_groups.unwrap_or_default().into_iter().next(),
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_cluster_parameter_group_not_found_fault()) => None,
+                    Err(e) => return Err(e.into()),
+                };
+                let Some(parameter_group) = parameter_group else {
+                    return Ok(None);
+                };
+
+                // Only the parameters that were changed from the family's defaults
+                let mut parameters = HashMap::new();
+                let mut marker = None;
+                loop {
+                    let resp = client
+                        .describe_cluster_parameters()
+                        .parameter_group_name(name)
+                        .source("user")
+                        .set_marker(marker)
+                        .send()
+                        .await?;
+
+                    for parameter in resp.parameters() {
+                        if let (Some(k), Some(v)) = (&parameter.parameter_name, &parameter.parameter_value) {
+                            parameters.insert(k.clone(), v.clone());
+                        }
+                    }
+
+                    marker = resp.marker;
+                    if marker.is_none() {
+                        break;
+                    }
+                }
+
+                let resource = ParameterGroup {
+                    family: parameter_group.parameter_group_family.unwrap_or_default(),
+                    description: parameter_group.description.unwrap_or_default(),
+                    parameters,
+                    tags: Tags::from(parameter_group.tags),
+                };
+
+                get_resource_response!(RedshiftResource::ParameterGroup(resource))
+            }
+            RedshiftResourceAddress::SubnetGroup { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let subnet_group = match client.describe_cluster_subnet_groups().cluster_subnet_group_name(name).send().await {
+                    Ok(resp) => resp.cluster_subnet_groups.unwrap_or_default().into_iter().next(),
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_cluster_subnet_group_not_found_fault()) => None,
+                    Err(e) => return Err(e.into()),
+                };
+                let Some(subnet_group) = subnet_group else {
+                    return Ok(None);
+                };
+
+                let mut subnet_ids: Vec<String> = subnet_group
+                    .subnets()
+                    .iter()
+                    .filter_map(|subnet| subnet.subnet_identifier.clone())
+                    .collect();
+                subnet_ids.sort();
+
+                let resource = SubnetGroup {
+                    description: subnet_group.description.unwrap_or_default(),
+                    subnet_ids,
+                    tags: Tags::from(subnet_group.tags),
+                };
+
+                get_resource_response!(RedshiftResource::SubnetGroup(resource))
+            }
+            RedshiftResourceAddress::SnapshotSchedule { region, id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = client.describe_snapshot_schedules().schedule_identifier(id).send().await?;
+                let Some(schedule) = resp.snapshot_schedules.unwrap_or_default().into_iter().next() else {
+                    return Ok(None);
+                };
+
+                let resource = SnapshotSchedule {
+                    schedule_definitions: schedule.schedule_definitions.unwrap_or_default(),
+                    description: schedule.schedule_description.filter(|d| !d.is_empty()),
+                    tags: Tags::from(schedule.tags),
+                };
+
+                get_resource_response!(RedshiftResource::SnapshotSchedule(resource))
+            }
+            RedshiftResourceAddress::Namespace { region, name } => {
+                let client = self.get_or_init_serverless_client(region).await?;
+
+                let Some(namespace) = find_namespace(&client, name).await? else {
+                    return Ok(None);
+                };
+                if namespace.status.as_ref().is_some_and(|status| status.as_str() == "DELETING") {
+                    return Ok(None);
+                }
+
+                let namespace_arn = namespace.namespace_arn.clone().unwrap_or_default();
+                let tags = client.list_tags_for_resource().resource_arn(&namespace_arn).send().await?.tags;
+
+                let mut log_exports: Vec<String> = namespace.log_exports().iter().map(|e| e.as_str().to_string()).collect();
+                log_exports.sort();
+
+                let resource = Namespace {
+                    admin_username: namespace.admin_username.clone(),
+                    db_name: namespace.db_name.clone().filter(|db_name| db_name != DEFAULT_DB_NAME),
+                    // Namespaces encrypted with a key owned by Redshift report it by this name
+                    kms_key_id: namespace.kms_key_id.clone().filter(|key| key != "AWS_OWNED_KMS_KEY"),
+                    iam_roles: namespace_iam_roles(&namespace),
+                    default_iam_role_arn: namespace.default_iam_role_arn.clone(),
+                    log_exports,
+                    tags: Tags::from(tags.unwrap_or_default()),
+                };
+
+                get_resource_response!(
+                    RedshiftResource::Namespace(resource),
+                    [
+                        (String::from("namespace_arn"), namespace_arn),
+                        (
+                            String::from("admin_password_secret_arn"),
+                            namespace.admin_password_secret_arn.unwrap_or_default()
+                        )
+                    ]
+                )
+            }
+            RedshiftResourceAddress::Workgroup { region, name } => {
+                let client = self.get_or_init_serverless_client(region).await?;
+
+                let Some(workgroup) = find_workgroup(&client, name).await? else {
+                    return Ok(None);
+                };
+                if workgroup.status.as_ref().is_some_and(|status| status.as_str() == "DELETING") {
+                    return Ok(None);
+                }
+
+                let workgroup_arn = workgroup.workgroup_arn.clone().unwrap_or_default();
+                let tags = client.list_tags_for_resource().resource_arn(&workgroup_arn).send().await?.tags;
+
+                let mut subnet_ids = workgroup.subnet_ids.clone().unwrap_or_default();
+                subnet_ids.sort();
+                let mut security_group_ids = workgroup.security_group_ids.clone().unwrap_or_default();
+                security_group_ids.sort();
+
+                let config_parameters = workgroup
+                    .config_parameters()
+                    .iter()
+                    .filter_map(|p| Some((p.parameter_key.clone()?, p.parameter_value.clone()?)))
+                    .collect();
+
+                let resource = Workgroup {
+                    namespace: workgroup.namespace_name.clone().unwrap_or_default(),
+                    base_capacity: workgroup.base_capacity,
+                    max_capacity: workgroup.max_capacity,
+                    subnet_ids,
+                    security_group_ids,
+                    publicly_accessible: workgroup.publicly_accessible.unwrap_or(false),
+                    enhanced_vpc_routing: workgroup.enhanced_vpc_routing.unwrap_or(false),
+                    port: workgroup.port.filter(|port| *port != DEFAULT_PORT),
+                    config_parameters,
+                    tags: Tags::from(tags.unwrap_or_default()),
+                };
+
+                get_resource_response!(
+                    RedshiftResource::Workgroup(resource),
+                    [
+                        (String::from("workgroup_arn"), workgroup_arn),
+                        (
+                            String::from("endpoint_address"),
+                            workgroup.endpoint.and_then(|endpoint| endpoint.address).unwrap_or_default()
+                        )
+                    ]
+                )
+            }
+        }
+    }
+}
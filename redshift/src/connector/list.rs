@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::addr::RedshiftResourceAddress;
+
+use super::RedshiftConnector;
+
+impl RedshiftConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut marker = None;
+            loop {
+                let resp = client.describe_clusters().set_marker(marker).send().await?;
+
+                for cluster in resp.clusters() {
+                    if cluster.cluster_status.as_deref() == Some("deleting") {
+                        continue;
+                    }
+                    let Some(id) = &cluster.cluster_identifier else {
+                        continue;
+                    };
+
+                    results.push(
+                        RedshiftResourceAddress::Cluster {
+                            region: region.clone(),
+                            id:     id.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                marker = resp.marker;
+                if marker.is_none() {
+                    break;
+                }
+            }
+
+            let mut marker = None;
+            loop {
+                let resp = client.describe_cluster_parameter_groups().set_marker(marker).send().await?;
+
+                for parameter_group in resp.parameter_groups() {
+                    let Some(name) = &parameter_group.parameter_group_name else {
+                        continue;
+                    };
+                    // Redshift's own default parameter groups can't be changed
+                    if name.starts_with("default.") {
+                        continue;
+                    }
+
+                    results.push(
+                        RedshiftResourceAddress::ParameterGroup {
+                            region: region.clone(),
+                            name:   name.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                marker = resp.marker;
+                if marker.is_none() {
+                    break;
+                }
+            }
+
+            let mut marker = None;
+            loop {
+                let resp = client.describe_cluster_subnet_groups().set_marker(marker).send().await?;
+
+                for subnet_group in resp.cluster_subnet_groups() {
+                    let Some(name) = &subnet_group.cluster_subnet_group_name else {
+                        continue;
+                    };
+
+                    results.push(
+                        RedshiftResourceAddress::SubnetGroup {
+                            region: region.clone(),
+                            name:   name.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                marker = resp.marker;
+                if marker.is_none() {
+                    break;
+                }
+            }
+
+            let mut marker = None;
+            loop {
+                let resp = client.describe_snapshot_schedules().set_marker(marker).send().await?;
+
+                for schedule in resp.snapshot_schedules() {
+                    let Some(id) = &schedule.schedule_identifier else {
+                        continue;
+                    };
+
+                    results.push(
+                        RedshiftResourceAddress::SnapshotSchedule {
+                            region: region.clone(),
+                            id:     id.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                marker = resp.marker;
+                if marker.is_none() {
+                    break;
+                }
+            }
+
+            let serverless_client = self.get_or_init_serverless_client(&region).await?;
+
+            let mut next_token = None;
+            loop {
+                let resp = serverless_client.list_namespaces().set_next_token(next_token).send().await?;
+
+                for namespace in resp.namespaces() {
+                    let Some(name) = &namespace.namespace_name else {
+                        continue;
+                    };
+
+                    results.push(
+                        RedshiftResourceAddress::Namespace {
+                            region: region.clone(),
+                            name:   name.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+
+            let mut next_token = None;
+            loop {
+                let resp = serverless_client.list_workgroups().set_next_token(next_token).send().await?;
+
+                for workgroup in resp.workgroups() {
+                    let Some(name) = &workgroup.workgroup_name else {
+                        continue;
+                    };
+
+                    results.push(
+                        RedshiftResourceAddress::Workgroup {
+                            region: region.clone(),
+                            name:   name.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,135 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{ConnectorOp, OpExecResponse, ResourceAddress},
+    error_util::invalid_op,
+};
+
+use crate::{addr::RedshiftResourceAddress, op::RedshiftConnectorOp, op_impl, util::redshift_arn};
+
+use super::RedshiftConnector;
+
+impl RedshiftConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = RedshiftResourceAddress::from_path(addr)?;
+        let op = RedshiftConnectorOp::from_str(op)?;
+        let account_id = self.account_id.lock().await.clone();
+
+        match &addr {
+            RedshiftResourceAddress::Cluster { region, id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    RedshiftConnectorOp::CreateCluster(cluster) => op_impl::create_cluster(&client, id, &cluster).await,
+                    RedshiftConnectorOp::ModifyCluster(cluster) => op_impl::modify_cluster(&client, id, &cluster).await,
+                    RedshiftConnectorOp::ResizeCluster {
+                        node_type,
+                        number_of_nodes,
+                    } => op_impl::resize_cluster(&client, id, &node_type, number_of_nodes).await,
+                    RedshiftConnectorOp::ModifyClusterIamRoles {
+                        add_iam_roles,
+                        remove_iam_roles,
+                        default_iam_role_arn,
+                    } => op_impl::modify_cluster_iam_roles(&client, id, &add_iam_roles, &remove_iam_roles, &default_iam_role_arn).await,
+                    RedshiftConnectorOp::ModifyClusterSnapshotSchedule(schedule) => {
+                        op_impl::modify_cluster_snapshot_schedule(&client, id, &schedule).await
+                    }
+                    RedshiftConnectorOp::UpdateClusterTags(old_tags, new_tags) => {
+                        let arn = redshift_arn(region, &account_id, "cluster", id);
+                        op_impl::update_cluster_tags(&client, id, &arn, &old_tags, &new_tags).await
+                    }
+                    RedshiftConnectorOp::RebootCluster => op_impl::reboot_cluster(&client, id).await,
+                    RedshiftConnectorOp::DeleteCluster { final_snapshot_identifier } => {
+                        op_impl::delete_cluster(&client, id, &final_snapshot_identifier).await
+                    }
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            RedshiftResourceAddress::ParameterGroup { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    RedshiftConnectorOp::CreateParameterGroup(parameter_group) => {
+                        op_impl::create_parameter_group(&client, name, &parameter_group).await
+                    }
+                    RedshiftConnectorOp::ModifyParameterGroup(parameters) => {
+                        op_impl::modify_parameter_group(&client, name, &parameters).await
+                    }
+                    RedshiftConnectorOp::ResetParameterGroup(parameter_names) => {
+                        op_impl::reset_parameter_group(&client, name, &parameter_names).await
+                    }
+                    RedshiftConnectorOp::UpdateParameterGroupTags(old_tags, new_tags) => {
+                        let arn = redshift_arn(region, &account_id, "parametergroup", name);
+                        op_impl::update_parameter_group_tags(&client, name, &arn, &old_tags, &new_tags).await
+                    }
+                    RedshiftConnectorOp::DeleteParameterGroup => op_impl::delete_parameter_group(&client, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            RedshiftResourceAddress::SubnetGroup { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    RedshiftConnectorOp::CreateSubnetGroup(subnet_group) => {
+                        op_impl::create_subnet_group(&client, name, &subnet_group).await
+                    }
+                    RedshiftConnectorOp::ModifySubnetGroup(subnet_group) => {
+                        op_impl::modify_subnet_group(&client, name, &subnet_group).await
+                    }
+                    RedshiftConnectorOp::UpdateSubnetGroupTags(old_tags, new_tags) => {
+                        let arn = redshift_arn(region, &account_id, "subnetgroup", name);
+                        op_impl::update_subnet_group_tags(&client, name, &arn, &old_tags, &new_tags).await
+                    }
+                    RedshiftConnectorOp::DeleteSubnetGroup => op_impl::delete_subnet_group(&client, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            RedshiftResourceAddress::SnapshotSchedule { region, id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    RedshiftConnectorOp::CreateSnapshotSchedule(schedule) => {
+                        op_impl::create_snapshot_schedule(&client, id, &schedule).await
+                    }
+                    RedshiftConnectorOp::ModifySnapshotSchedule(schedule_definitions) => {
+                        op_impl::modify_snapshot_schedule(&client, id, &schedule_definitions).await
+                    }
+                    RedshiftConnectorOp::UpdateSnapshotScheduleTags(old_tags, new_tags) => {
+                        let arn = redshift_arn(region, &account_id, "snapshotschedule", id);
+                        op_impl::update_snapshot_schedule_tags(&client, id, &arn, &old_tags, &new_tags).await
+                    }
+                    RedshiftConnectorOp::DeleteSnapshotSchedule => op_impl::delete_snapshot_schedule(&client, id).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            RedshiftResourceAddress::Namespace { region, name } => {
+                let client = self.get_or_init_serverless_client(region).await?;
+
+                match op {
+                    RedshiftConnectorOp::CreateNamespace(namespace) => op_impl::create_namespace(&client, name, &namespace).await,
+                    RedshiftConnectorOp::UpdateNamespace(namespace) => op_impl::update_namespace(&client, name, &namespace).await,
+                    RedshiftConnectorOp::UpdateNamespaceTags(old_tags, new_tags) => {
+                        op_impl::update_namespace_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    RedshiftConnectorOp::DeleteNamespace { final_snapshot_name } => {
+                        op_impl::delete_namespace(&client, name, &final_snapshot_name).await
+                    }
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            RedshiftResourceAddress::Workgroup { region, name } => {
+                let client = self.get_or_init_serverless_client(region).await?;
+
+                match op {
+                    RedshiftConnectorOp::CreateWorkgroup(workgroup) => op_impl::create_workgroup(&client, name, &workgroup).await,
+                    RedshiftConnectorOp::UpdateWorkgroup(workgroup) => op_impl::update_workgroup(&client, name, &workgroup).await,
+                    RedshiftConnectorOp::UpdateWorkgroupTags(old_tags, new_tags) => {
+                        op_impl::update_workgroup_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    RedshiftConnectorOp::DeleteWorkgroup => op_impl::delete_workgroup(&client, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,602 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::resource::{Cluster, Namespace, ParameterGroup, SnapshotSchedule, SubnetGroup, Workgroup};
+
+use super::{RedshiftConnector, RedshiftConnectorOp, RedshiftResourceAddress};
+
+const LOG_EXPORTS: &[&str] = &["useractivitylog", "userlog", "connectionlog"];
+const MIN_BASE_CAPACITY: i32 = 8;
+const MAX_BASE_CAPACITY: i32 = 512;
+
+fn check_default_iam_role(what: &str, iam_roles: &[String], default_iam_role_arn: &Option<String>) -> anyhow::Result<()> {
+    if let Some(default_iam_role_arn) = default_iam_role_arn
+        && !iam_roles.contains(default_iam_role_arn)
+    {
+        bail!(
+            "{} has default_iam_role_arn {}, which isn't one of its iam_roles",
+            what,
+            default_iam_role_arn
+        );
+    }
+    Ok(())
+}
+
+/// Combinations that Redshift would reject, caught at plan time instead.
+fn check_cluster(id: &str, cluster: &Cluster) -> anyhow::Result<()> {
+    let what = format!("Redshift cluster {}", id);
+
+    if cluster.number_of_nodes < 1 {
+        bail!("{} has {} nodes: it needs at least one", what, cluster.number_of_nodes);
+    }
+    if let Some(retention) = cluster.automated_snapshot_retention_period
+        && !(0..=35).contains(&retention)
+    {
+        bail!(
+            "{} keeps automated snapshots for {} days: expected 0 to 35",
+            what,
+            retention
+        );
+    }
+    if !cluster.encrypted && cluster.kms_key_id.is_some() {
+        bail!("{} sets kms_key_id without being encrypted", what);
+    }
+    check_default_iam_role(&what, &cluster.iam_roles, &cluster.default_iam_role_arn)?;
+
+    Ok(())
+}
+
+fn check_namespace(name: &str, namespace: &Namespace) -> anyhow::Result<()> {
+    let what = format!("Redshift Serverless namespace {}", name);
+
+    for log_export in &namespace.log_exports {
+        if !LOG_EXPORTS.contains(&log_export.as_str()) {
+            bail!("{} exports log {}: expected one of {}", what, log_export, LOG_EXPORTS.join(", "));
+        }
+    }
+    check_default_iam_role(&what, &namespace.iam_roles, &namespace.default_iam_role_arn)?;
+
+    Ok(())
+}
+
+fn check_workgroup(name: &str, workgroup: &Workgroup) -> anyhow::Result<()> {
+    let what = format!("Redshift Serverless workgroup {}", name);
+
+    if let Some(base_capacity) = workgroup.base_capacity
+        && (!(MIN_BASE_CAPACITY..=MAX_BASE_CAPACITY).contains(&base_capacity) || base_capacity % 8 != 0)
+    {
+        bail!(
+            "{} has a base capacity of {} RPUs: expected a multiple of 8 from {} to {}",
+            what,
+            base_capacity,
+            MIN_BASE_CAPACITY,
+            MAX_BASE_CAPACITY
+        );
+    }
+    if let (Some(base_capacity), Some(max_capacity)) = (workgroup.base_capacity, workgroup.max_capacity)
+        && max_capacity < base_capacity
+    {
+        bail!(
+            "{} has a max capacity of {} RPUs, below its base capacity of {}",
+            what,
+            max_capacity,
+            base_capacity
+        );
+    }
+
+    Ok(())
+}
+
+fn check_snapshot_schedule(id: &str, schedule: &SnapshotSchedule) -> anyhow::Result<()> {
+    if schedule.schedule_definitions.is_empty() {
+        bail!("Redshift snapshot schedule {} has no schedule_definitions", id);
+    }
+    Ok(())
+}
+
+/// Redshift fills in these fields when they're left out, so leaving them out doesn't change them.
+fn normalize_cluster(old: &Cluster, new: &mut Cluster) {
+    if new.cluster_subnet_group_name.is_none() {
+        new.cluster_subnet_group_name = old.cluster_subnet_group_name.clone();
+    }
+    if new.vpc_security_group_ids.is_empty() {
+        new.vpc_security_group_ids = old.vpc_security_group_ids.clone();
+    }
+    if new.automated_snapshot_retention_period.is_none() {
+        new.automated_snapshot_retention_period = old.automated_snapshot_retention_period;
+    }
+    if new.preferred_maintenance_window.is_none() {
+        new.preferred_maintenance_window = old.preferred_maintenance_window.clone();
+    }
+    if new.encrypted && new.kms_key_id.is_none() {
+        new.kms_key_id = old.kms_key_id.clone();
+    }
+}
+
+/// The fields that Redshift can't change once a cluster exists.
+fn cluster_fixed_fields(old: &Cluster, new: &Cluster) -> Vec<String> {
+    let mut fields = Vec::new();
+    if old.master_username != new.master_username {
+        fields.push(String::from("master_username"));
+    }
+    if old.db_name != new.db_name {
+        fields.push(String::from("db_name"));
+    }
+    if old.cluster_subnet_group_name != new.cluster_subnet_group_name {
+        fields.push(String::from("cluster_subnet_group_name"));
+    }
+    if old.encrypted != new.encrypted {
+        fields.push(String::from("encrypted"));
+    }
+    if old.kms_key_id != new.kms_key_id {
+        fields.push(String::from("kms_key_id"));
+    }
+    fields
+}
+
+/// Whether any of the fields applied by ModifyCluster changed.
+fn cluster_settings_changed(old: &Cluster, new: &Cluster) -> bool {
+    old.vpc_security_group_ids != new.vpc_security_group_ids
+        || old.cluster_parameter_group_name != new.cluster_parameter_group_name
+        || old.publicly_accessible != new.publicly_accessible
+        || old.enhanced_vpc_routing != new.enhanced_vpc_routing
+        || old.port != new.port
+        || old.automated_snapshot_retention_period != new.automated_snapshot_retention_period
+        || old.preferred_maintenance_window != new.preferred_maintenance_window
+}
+
+/// Redshift Serverless fills in these fields when they're left out, so leaving them out doesn't change them.
+fn normalize_workgroup(old: &Workgroup, new: &mut Workgroup) {
+    if new.base_capacity.is_none() {
+        new.base_capacity = old.base_capacity;
+    }
+    if new.port.is_none() {
+        new.port = old.port;
+    }
+    if new.subnet_ids.is_empty() {
+        new.subnet_ids = old.subnet_ids.clone();
+    }
+    if new.security_group_ids.is_empty() {
+        new.security_group_ids = old.security_group_ids.clone();
+    }
+    for (k, v) in &old.config_parameters {
+        new.config_parameters.entry(k.clone()).or_insert_with(|| v.clone());
+    }
+}
+
+impl RedshiftConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = RedshiftResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            RedshiftResourceAddress::Cluster { region, id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_cluster)) => {
+                    let new_cluster: Cluster = RON.from_str(&new_cluster)?;
+                    check_cluster(id, &new_cluster)?;
+                    Ok(vec![connector_op!(
+                        RedshiftConnectorOp::CreateCluster(new_cluster),
+                        format!("Create new Redshift cluster {} in region {}", id, region)
+                    )])
+                }
+                (Some(_old_cluster), None) => {
+                    let final_snapshot_identifier = format!("{}-final-snapshot", id);
+                    Ok(vec![connector_op!(
+                        RedshiftConnectorOp::DeleteCluster {
+                            final_snapshot_identifier: Some(final_snapshot_identifier.clone()),
+                        },
+                        format!(
+                            "DELETE Redshift cluster {} in region {}, keeping a final snapshot {}",
+                            id, region, final_snapshot_identifier
+                        )
+                    )])
+                }
+                (Some(old_cluster), Some(new_cluster)) => {
+                    let old_cluster: Cluster = RON.from_str(&old_cluster)?;
+                    let mut new_cluster: Cluster = RON.from_str(&new_cluster)?;
+                    check_cluster(id, &new_cluster)?;
+                    normalize_cluster(&old_cluster, &mut new_cluster);
+
+                    let fixed_fields = cluster_fixed_fields(&old_cluster, &new_cluster);
+                    if !fixed_fields.is_empty() {
+                        bail!(
+                            "Redshift cluster {} can't change {} after creation. Restore it from a snapshot into a new cluster instead.",
+                            id,
+                            fixed_fields.join(", ")
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_cluster.tags != new_cluster.tags {
+                        let diff = diff_ron_values(&old_cluster.tags, &new_cluster.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::UpdateClusterTags(old_cluster.tags.clone(), new_cluster.tags.clone()),
+                            format!("Modify tags for Redshift cluster `{}`\n{}", id, diff)
+                        ));
+                    }
+
+                    if old_cluster.node_type != new_cluster.node_type || old_cluster.number_of_nodes != new_cluster.number_of_nodes {
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::ResizeCluster {
+                                node_type: new_cluster.node_type.clone(),
+                                number_of_nodes: new_cluster.number_of_nodes,
+                            },
+                            format!(
+                                "Resize Redshift cluster `{}` from {} {} nodes to {} {} nodes. The cluster is read-only while resizing.",
+                                id,
+                                old_cluster.number_of_nodes,
+                                old_cluster.node_type,
+                                new_cluster.number_of_nodes,
+                                new_cluster.node_type
+                            )
+                        ));
+                    }
+
+                    if cluster_settings_changed(&old_cluster, &new_cluster) {
+                        let diff = diff_ron_values(&old_cluster, &new_cluster).unwrap_or_default();
+                        let needs_reboot = old_cluster.cluster_parameter_group_name != new_cluster.cluster_parameter_group_name;
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::ModifyCluster(new_cluster.clone()),
+                            format!("Modify Redshift cluster `{}`\n{}", id, diff)
+                        ));
+
+                        if needs_reboot {
+                            ops.push(connector_op!(
+                                RedshiftConnectorOp::RebootCluster,
+                                format!(
+                                    "Reboot Redshift cluster `{}` to apply its new parameter group. The cluster is unavailable while rebooting.",
+                                    id
+                                )
+                            ));
+                        }
+                    }
+
+                    if old_cluster.iam_roles != new_cluster.iam_roles
+                        || old_cluster.default_iam_role_arn != new_cluster.default_iam_role_arn
+                    {
+                        let add_iam_roles: Vec<String> = new_cluster
+                            .iam_roles
+                            .iter()
+                            .filter(|role| !old_cluster.iam_roles.contains(role))
+                            .cloned()
+                            .collect();
+                        let remove_iam_roles: Vec<String> = old_cluster
+                            .iam_roles
+                            .iter()
+                            .filter(|role| !new_cluster.iam_roles.contains(role))
+                            .cloned()
+                            .collect();
+                        let diff = diff_ron_values(
+                            &(&old_cluster.iam_roles, &old_cluster.default_iam_role_arn),
+                            &(&new_cluster.iam_roles, &new_cluster.default_iam_role_arn),
+                        )
+                        .unwrap_or_default();
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::ModifyClusterIamRoles {
+                                add_iam_roles,
+                                remove_iam_roles,
+                                default_iam_role_arn: new_cluster.default_iam_role_arn.clone(),
+                            },
+                            format!("Modify IAM roles for Redshift cluster `{}`\n{}", id, diff)
+                        ));
+                    }
+
+                    if old_cluster.snapshot_schedule != new_cluster.snapshot_schedule {
+                        let message = match &new_cluster.snapshot_schedule {
+                            Some(schedule) => format!("Set snapshot schedule for Redshift cluster `{}` to {}", id, schedule),
+                            None => format!("Remove snapshot schedule from Redshift cluster `{}`", id),
+                        };
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::ModifyClusterSnapshotSchedule(new_cluster.snapshot_schedule.clone()),
+                            message
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            RedshiftResourceAddress::ParameterGroup { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_parameter_group)) => {
+                    let new_parameter_group: ParameterGroup = RON.from_str(&new_parameter_group)?;
+                    Ok(vec![connector_op!(
+                        RedshiftConnectorOp::CreateParameterGroup(new_parameter_group),
+                        format!("Create new Redshift parameter group {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_parameter_group), None) => Ok(vec![connector_op!(
+                    RedshiftConnectorOp::DeleteParameterGroup,
+                    format!("DELETE Redshift parameter group {} in region {}", name, region)
+                )]),
+                (Some(old_parameter_group), Some(new_parameter_group)) => {
+                    let old_parameter_group: ParameterGroup = RON.from_str(&old_parameter_group)?;
+                    let new_parameter_group: ParameterGroup = RON.from_str(&new_parameter_group)?;
+
+                    if old_parameter_group.family != new_parameter_group.family
+                        || old_parameter_group.description != new_parameter_group.description
+                    {
+                        bail!(
+                            "Redshift parameter group {} can't change its family or description after creation. Create a new parameter group under another name instead.",
+                            name
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_parameter_group.tags != new_parameter_group.tags {
+                        let diff = diff_ron_values(&old_parameter_group.tags, &new_parameter_group.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::UpdateParameterGroupTags(
+                                old_parameter_group.tags.clone(),
+                                new_parameter_group.tags.clone()
+                            ),
+                            format!("Modify tags for Redshift parameter group `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let changed: HashMap<String, String> = new_parameter_group
+                        .parameters
+                        .iter()
+                        .filter(|(k, v)| old_parameter_group.parameters.get(*k) != Some(*v))
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect();
+                    if !changed.is_empty() {
+                        let diff = diff_ron_values(&old_parameter_group.parameters, &new_parameter_group.parameters)
+                            .unwrap_or_default();
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::ModifyParameterGroup(changed),
+                            format!(
+                                "Modify parameters in Redshift parameter group `{}`. Clusters using it pick up static parameters when they next reboot.\n{}",
+                                name, diff
+                            )
+                        ));
+                    }
+
+                    let mut removed: Vec<String> = old_parameter_group
+                        .parameters
+                        .keys()
+                        .filter(|k| !new_parameter_group.parameters.contains_key(*k))
+                        .cloned()
+                        .collect();
+                    removed.sort();
+                    if !removed.is_empty() {
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::ResetParameterGroup(removed.clone()),
+                            format!(
+                                "Reset parameters {} in Redshift parameter group `{}` to their defaults",
+                                removed.join(", "),
+                                name
+                            )
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            RedshiftResourceAddress::SubnetGroup { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_subnet_group)) => {
+                    let new_subnet_group: SubnetGroup = RON.from_str(&new_subnet_group)?;
+                    Ok(vec![connector_op!(
+                        RedshiftConnectorOp::CreateSubnetGroup(new_subnet_group),
+                        format!("Create new Redshift subnet group {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_subnet_group), None) => Ok(vec![connector_op!(
+                    RedshiftConnectorOp::DeleteSubnetGroup,
+                    format!("DELETE Redshift subnet group {} in region {}", name, region)
+                )]),
+                (Some(old_subnet_group), Some(new_subnet_group)) => {
+                    let old_subnet_group: SubnetGroup = RON.from_str(&old_subnet_group)?;
+                    let new_subnet_group: SubnetGroup = RON.from_str(&new_subnet_group)?;
+
+                    let mut ops = Vec::new();
+
+                    if old_subnet_group.tags != new_subnet_group.tags {
+                        let diff = diff_ron_values(&old_subnet_group.tags, &new_subnet_group.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::UpdateSubnetGroupTags(old_subnet_group.tags.clone(), new_subnet_group.tags.clone()),
+                            format!("Modify tags for Redshift subnet group `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    if old_subnet_group.description != new_subnet_group.description
+                        || old_subnet_group.subnet_ids != new_subnet_group.subnet_ids
+                    {
+                        let diff = diff_ron_values(&old_subnet_group, &new_subnet_group).unwrap_or_default();
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::ModifySubnetGroup(new_subnet_group),
+                            format!("Modify Redshift subnet group `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            RedshiftResourceAddress::SnapshotSchedule { region, id } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_schedule)) => {
+                    let new_schedule: SnapshotSchedule = RON.from_str(&new_schedule)?;
+                    check_snapshot_schedule(id, &new_schedule)?;
+                    Ok(vec![connector_op!(
+                        RedshiftConnectorOp::CreateSnapshotSchedule(new_schedule),
+                        format!("Create new Redshift snapshot schedule {} in region {}", id, region)
+                    )])
+                }
+                (Some(_old_schedule), None) => Ok(vec![connector_op!(
+                    RedshiftConnectorOp::DeleteSnapshotSchedule,
+                    format!("DELETE Redshift snapshot schedule {} in region {}", id, region)
+                )]),
+                (Some(old_schedule), Some(new_schedule)) => {
+                    let old_schedule: SnapshotSchedule = RON.from_str(&old_schedule)?;
+                    let new_schedule: SnapshotSchedule = RON.from_str(&new_schedule)?;
+                    check_snapshot_schedule(id, &new_schedule)?;
+
+                    if old_schedule.description != new_schedule.description {
+                        bail!(
+                            "Redshift snapshot schedule {} can't change its description after creation. Create a new schedule under another ID instead.",
+                            id
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_schedule.tags != new_schedule.tags {
+                        let diff = diff_ron_values(&old_schedule.tags, &new_schedule.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::UpdateSnapshotScheduleTags(old_schedule.tags.clone(), new_schedule.tags.clone()),
+                            format!("Modify tags for Redshift snapshot schedule `{}`\n{}", id, diff)
+                        ));
+                    }
+
+                    if old_schedule.schedule_definitions != new_schedule.schedule_definitions {
+                        let diff = diff_ron_values(&old_schedule.schedule_definitions, &new_schedule.schedule_definitions)
+                            .unwrap_or_default();
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::ModifySnapshotSchedule(new_schedule.schedule_definitions),
+                            format!("Modify Redshift snapshot schedule `{}`\n{}", id, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            RedshiftResourceAddress::Namespace { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_namespace)) => {
+                    let new_namespace: Namespace = RON.from_str(&new_namespace)?;
+                    check_namespace(name, &new_namespace)?;
+                    Ok(vec![connector_op!(
+                        RedshiftConnectorOp::CreateNamespace(new_namespace),
+                        format!("Create new Redshift Serverless namespace {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_namespace), None) => {
+                    let final_snapshot_name = format!("{}-final-snapshot", name);
+                    Ok(vec![connector_op!(
+                        RedshiftConnectorOp::DeleteNamespace {
+                            final_snapshot_name: Some(final_snapshot_name.clone()),
+                        },
+                        format!(
+                            "DELETE Redshift Serverless namespace {} in region {}, keeping a final snapshot {}",
+                            name, region, final_snapshot_name
+                        )
+                    )])
+                }
+                (Some(old_namespace), Some(new_namespace)) => {
+                    let old_namespace: Namespace = RON.from_str(&old_namespace)?;
+                    let mut new_namespace: Namespace = RON.from_str(&new_namespace)?;
+                    check_namespace(name, &new_namespace)?;
+
+                    if old_namespace.admin_username != new_namespace.admin_username
+                        || old_namespace.db_name != new_namespace.db_name
+                    {
+                        bail!(
+                            "Redshift Serverless namespace {} can't change its admin_username or db_name after creation",
+                            name
+                        );
+                    }
+                    // A namespace can move to another customer managed key, but not back to one owned by Redshift
+                    if new_namespace.kms_key_id.is_none() {
+                        new_namespace.kms_key_id = old_namespace.kms_key_id.clone();
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_namespace.tags != new_namespace.tags {
+                        let diff = diff_ron_values(&old_namespace.tags, &new_namespace.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::UpdateNamespaceTags(old_namespace.tags.clone(), new_namespace.tags.clone()),
+                            format!("Modify tags for Redshift Serverless namespace `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_namespace.clone();
+                    old_settings.tags = new_namespace.tags.clone();
+                    if old_settings != new_namespace {
+                        let diff = diff_ron_values(&old_settings, &new_namespace).unwrap_or_default();
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::UpdateNamespace(new_namespace),
+                            format!("Modify Redshift Serverless namespace `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            RedshiftResourceAddress::Workgroup { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_workgroup)) => {
+                    let new_workgroup: Workgroup = RON.from_str(&new_workgroup)?;
+                    check_workgroup(name, &new_workgroup)?;
+                    Ok(vec![connector_op!(
+                        RedshiftConnectorOp::CreateWorkgroup(new_workgroup.clone()),
+                        format!(
+                            "Create new Redshift Serverless workgroup {} for namespace {} in region {}",
+                            name, new_workgroup.namespace, region
+                        )
+                    )])
+                }
+                (Some(_old_workgroup), None) => Ok(vec![connector_op!(
+                    RedshiftConnectorOp::DeleteWorkgroup,
+                    format!(
+                        "DELETE Redshift Serverless workgroup {} in region {}. Its namespace and data are kept.",
+                        name, region
+                    )
+                )]),
+                (Some(old_workgroup), Some(new_workgroup)) => {
+                    let old_workgroup: Workgroup = RON.from_str(&old_workgroup)?;
+                    let mut new_workgroup: Workgroup = RON.from_str(&new_workgroup)?;
+                    check_workgroup(name, &new_workgroup)?;
+                    normalize_workgroup(&old_workgroup, &mut new_workgroup);
+
+                    if old_workgroup.namespace != new_workgroup.namespace {
+                        bail!(
+                            "Redshift Serverless workgroup {} can't move to another namespace. Create a new workgroup under another name instead.",
+                            name
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_workgroup.tags != new_workgroup.tags {
+                        let diff = diff_ron_values(&old_workgroup.tags, &new_workgroup.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::UpdateWorkgroupTags(old_workgroup.tags.clone(), new_workgroup.tags.clone()),
+                            format!("Modify tags for Redshift Serverless workgroup `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_workgroup.clone();
+                    old_settings.tags = new_workgroup.tags.clone();
+                    if old_settings != new_workgroup {
+                        let diff = diff_ron_values(&old_settings, &new_workgroup).unwrap_or_default();
+                        ops.push(connector_op!(
+                            RedshiftConnectorOp::UpdateWorkgroup(new_workgroup),
+                            format!("Modify Redshift Serverless workgroup `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+        }
+    }
+}
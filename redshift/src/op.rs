@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{Cluster, Namespace, ParameterGroup, SnapshotSchedule, SubnetGroup, Workgroup},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum RedshiftConnectorOp {
+    /// Creates the cluster, and waits for it to be available.
+    CreateCluster(Cluster),
+    /// Applies the cluster's security groups, parameter group, public accessibility, enhanced VPC
+    /// routing, port, snapshot retention and maintenance window.
+    ModifyCluster(Cluster),
+    /// Elastic resize to the given node type and number of nodes, waiting for it to finish.
+    ResizeCluster {
+        node_type: String,
+        number_of_nodes: i32,
+    },
+    ModifyClusterIamRoles {
+        add_iam_roles: Vec<String>,
+        remove_iam_roles: Vec<String>,
+        default_iam_role_arn: Option<String>,
+    },
+    /// Associates the cluster with a snapshot schedule, or disassociates it if None.
+    ModifyClusterSnapshotSchedule(Option<String>),
+    UpdateClusterTags(Tags, Tags),
+    /// Reboots the cluster to apply a new parameter group, and waits for it to be available again.
+    RebootCluster,
+    DeleteCluster {
+        final_snapshot_identifier: Option<String>,
+    },
+
+    CreateParameterGroup(ParameterGroup),
+    /// Sets the given parameters.
+    ModifyParameterGroup(HashMap<String, String>),
+    /// Resets the given parameters to the family's defaults.
+    ResetParameterGroup(Vec<String>),
+    UpdateParameterGroupTags(Tags, Tags),
+    DeleteParameterGroup,
+
+    CreateSubnetGroup(SubnetGroup),
+    ModifySubnetGroup(SubnetGroup),
+    UpdateSubnetGroupTags(Tags, Tags),
+    DeleteSubnetGroup,
+
+    CreateSnapshotSchedule(SnapshotSchedule),
+    ModifySnapshotSchedule(Vec<String>),
+    UpdateSnapshotScheduleTags(Tags, Tags),
+    DeleteSnapshotSchedule,
+
+    /// Creates the namespace, and waits for it to be available.
+    CreateNamespace(Namespace),
+    /// Applies the namespace's encryption key, IAM roles and log exports.
+    UpdateNamespace(Namespace),
+    UpdateNamespaceTags(Tags, Tags),
+    DeleteNamespace {
+        final_snapshot_name: Option<String>,
+    },
+
+    /// Creates the workgroup, and waits for it to be available.
+    CreateWorkgroup(Workgroup),
+    /// Applies the workgroup's capacity, networking and config parameters.
+    UpdateWorkgroup(Workgroup),
+    UpdateWorkgroupTags(Tags, Tags),
+    DeleteWorkgroup,
+}
+
+impl ConnectorOp for RedshiftConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
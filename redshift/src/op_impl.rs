@@ -0,0 +1,836 @@
+use std::{collections::HashMap, future::Future, time::Duration};
+
+use anyhow::{Context, bail};
+use autoschematic_core::{connector::OpExecResponse, op_exec_output};
+use aws_sdk_redshift::types::Parameter;
+use aws_sdk_redshiftserverless::types::{ConfigParameter, LogExport};
+
+use crate::{
+    resource::{Cluster, Namespace, ParameterGroup, SnapshotSchedule, SubnetGroup, Workgroup},
+    tags::{Tags, tag_diff},
+};
+
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(15);
+/// Clusters can take over half an hour to create or resize.
+const STATUS_MAX_POLLS: usize = 240;
+
+/// ModifyClusterParameterGroup takes at most 20 parameters per call.
+const MAX_PARAMETERS_PER_CALL: usize = 20;
+
+/// Polls `status` until it returns `target`, or until the resource no longer exists if `target` is None.
+async fn wait_for_status<F, Fut>(description: &str, target: Option<&str>, status: F) -> anyhow::Result<()>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = anyhow::Result<Option<String>>>,
+{
+    for _ in 0..STATUS_MAX_POLLS {
+        let state = status().await?;
+        if state.as_deref() == target {
+            return Ok(());
+        }
+        if let Some(state) = &state
+            && state.starts_with("incompatible")
+        {
+            bail!("{} is in state {}", description, state);
+        }
+        tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    }
+
+    match target {
+        Some(target) => bail!("Timed out waiting for {} to become {}", description, target),
+        None => bail!("Timed out waiting for {} to be deleted", description),
+    }
+}
+
+/// CreateTags and DeleteTags take the resource's ARN.
+async fn update_tags(client: &aws_sdk_redshift::Client, arn: &str, old_tags: &Tags, new_tags: &Tags) -> anyhow::Result<()> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .delete_tags()
+            .resource_name(arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    let new_tagset: Option<Vec<aws_sdk_redshift::types::Tag>> = new_tagset.into();
+    if let Some(new_tagset) = new_tagset {
+        client.create_tags().resource_name(arn).set_tags(Some(new_tagset)).send().await?;
+    }
+
+    Ok(())
+}
+
+async fn update_serverless_tags(
+    client: &aws_sdk_redshiftserverless::Client,
+    arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<()> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if let Some(new_tagset) = new_tagset.to_serverless()? {
+        client.tag_resource().resource_arn(arn).set_tags(Some(new_tagset)).send().await?;
+    }
+
+    Ok(())
+}
+
+pub async fn find_cluster(client: &aws_sdk_redshift::Client, id: &str) -> anyhow::Result<Option<aws_sdk_redshift::types::Cluster>> {
+    match client.describe_clusters().cluster_identifier(id).send().await {
+        Ok(resp) => Ok(resp.clusters.unwrap_or_default().into_iter().next()),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_cluster_not_found_fault()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn cluster_status(client: &aws_sdk_redshift::Client, id: &str) -> anyhow::Result<Option<String>> {
+    Ok(find_cluster(client, id)
+        .await?
+        .map(|cluster| cluster.cluster_status.unwrap_or_default()))
+}
+
+async fn wait_for_cluster(client: &aws_sdk_redshift::Client, id: &str, target: Option<&str>) -> anyhow::Result<()> {
+    wait_for_status(&format!("Redshift cluster {}", id), target, || cluster_status(client, id)).await
+}
+
+/// The outputs of an available cluster.
+async fn cluster_outputs(client: &aws_sdk_redshift::Client, id: &str) -> anyhow::Result<HashMap<String, Option<String>>> {
+    let cluster = find_cluster(client, id)
+        .await?
+        .with_context(|| format!("Redshift cluster {} not found", id))?;
+
+    Ok(HashMap::from([
+        (
+            String::from("endpoint_address"),
+            cluster.endpoint.as_ref().and_then(|endpoint| endpoint.address.clone()),
+        ),
+        (String::from("master_password_secret_arn"), cluster.master_password_secret_arn),
+        (String::from("cluster_namespace_arn"), cluster.cluster_namespace_arn),
+    ]))
+}
+
+fn cluster_type(number_of_nodes: i32) -> &'static str {
+    if number_of_nodes == 1 { "single-node" } else { "multi-node" }
+}
+
+pub async fn create_cluster(client: &aws_sdk_redshift::Client, id: &str, cluster: &Cluster) -> anyhow::Result<OpExecResponse> {
+    let mut request = client
+        .create_cluster()
+        .cluster_identifier(id)
+        .node_type(&cluster.node_type)
+        .cluster_type(cluster_type(cluster.number_of_nodes))
+        .master_username(&cluster.master_username)
+        .manage_master_password(true)
+        .set_db_name(cluster.db_name.clone())
+        .set_port(cluster.port)
+        .set_cluster_subnet_group_name(cluster.cluster_subnet_group_name.clone())
+        .set_cluster_parameter_group_name(cluster.cluster_parameter_group_name.clone())
+        .publicly_accessible(cluster.publicly_accessible)
+        .encrypted(cluster.encrypted)
+        .set_kms_key_id(cluster.kms_key_id.clone())
+        .enhanced_vpc_routing(cluster.enhanced_vpc_routing)
+        .set_automated_snapshot_retention_period(cluster.automated_snapshot_retention_period)
+        .set_preferred_maintenance_window(cluster.preferred_maintenance_window.clone())
+        .set_default_iam_role_arn(cluster.default_iam_role_arn.clone())
+        .set_snapshot_schedule_identifier(cluster.snapshot_schedule.clone())
+        .set_tags(cluster.tags.clone().into());
+
+    if cluster.number_of_nodes > 1 {
+        request = request.number_of_nodes(cluster.number_of_nodes);
+    }
+    if !cluster.vpc_security_group_ids.is_empty() {
+        request = request.set_vpc_security_group_ids(Some(cluster.vpc_security_group_ids.clone()));
+    }
+    if !cluster.iam_roles.is_empty() {
+        request = request.set_iam_roles(Some(cluster.iam_roles.clone()));
+    }
+
+    request.send().await?;
+
+    wait_for_cluster(client, id, Some("available")).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(cluster_outputs(client, id).await?),
+        friendly_message: Some(format!("Created Redshift cluster {}", id)),
+    })
+}
+
+pub async fn modify_cluster(client: &aws_sdk_redshift::Client, id: &str, cluster: &Cluster) -> anyhow::Result<OpExecResponse> {
+    let mut request = client
+        .modify_cluster()
+        .cluster_identifier(id)
+        .set_cluster_parameter_group_name(cluster.cluster_parameter_group_name.clone())
+        .publicly_accessible(cluster.publicly_accessible)
+        .enhanced_vpc_routing(cluster.enhanced_vpc_routing)
+        .set_port(cluster.port)
+        .set_automated_snapshot_retention_period(cluster.automated_snapshot_retention_period)
+        .set_preferred_maintenance_window(cluster.preferred_maintenance_window.clone());
+
+    if !cluster.vpc_security_group_ids.is_empty() {
+        request = request.set_vpc_security_group_ids(Some(cluster.vpc_security_group_ids.clone()));
+    }
+
+    request.send().await?;
+
+    wait_for_cluster(client, id, Some("available")).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(cluster_outputs(client, id).await?),
+        friendly_message: Some(format!("Modified Redshift cluster {}", id)),
+    })
+}
+
+pub async fn resize_cluster(
+    client: &aws_sdk_redshift::Client,
+    id: &str,
+    node_type: &str,
+    number_of_nodes: i32,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .resize_cluster()
+        .cluster_identifier(id)
+        .cluster_type(cluster_type(number_of_nodes))
+        .node_type(node_type)
+        .number_of_nodes(number_of_nodes)
+        .classic(false)
+        .send()
+        .await?;
+
+    // The cluster can still report available for a moment after the resize is accepted
+    tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    wait_for_cluster(client, id, Some("available")).await?;
+
+    op_exec_output!(format!(
+        "Resized Redshift cluster {} to {} {} nodes",
+        id, number_of_nodes, node_type
+    ))
+}
+
+pub async fn modify_cluster_iam_roles(
+    client: &aws_sdk_redshift::Client,
+    id: &str,
+    add_iam_roles: &[String],
+    remove_iam_roles: &[String],
+    default_iam_role_arn: &Option<String>,
+) -> anyhow::Result<OpExecResponse> {
+    let mut request = client.modify_cluster_iam_roles().cluster_identifier(id);
+
+    if !add_iam_roles.is_empty() {
+        request = request.set_add_iam_roles(Some(add_iam_roles.to_vec()));
+    }
+    if !remove_iam_roles.is_empty() {
+        request = request.set_remove_iam_roles(Some(remove_iam_roles.to_vec()));
+    }
+    request = request.set_default_iam_role_arn(default_iam_role_arn.clone());
+
+    request.send().await?;
+
+    wait_for_cluster(client, id, Some("available")).await?;
+
+    op_exec_output!(format!("Modified IAM roles for Redshift cluster {}", id))
+}
+
+pub async fn modify_cluster_snapshot_schedule(
+    client: &aws_sdk_redshift::Client,
+    id: &str,
+    schedule: &Option<String>,
+) -> anyhow::Result<OpExecResponse> {
+    let request = client.modify_cluster_snapshot_schedule().cluster_identifier(id);
+
+    match schedule {
+        Some(schedule) => {
+            request.schedule_identifier(schedule).send().await?;
+            op_exec_output!(format!("Set snapshot schedule {} for Redshift cluster {}", schedule, id))
+        }
+        None => {
+            request.disassociate_schedule(true).send().await?;
+            op_exec_output!(format!("Removed snapshot schedule from Redshift cluster {}", id))
+        }
+    }
+}
+
+pub async fn update_cluster_tags(
+    client: &aws_sdk_redshift::Client,
+    id: &str,
+    arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    update_tags(client, arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for Redshift cluster {}", id))
+}
+
+pub async fn reboot_cluster(client: &aws_sdk_redshift::Client, id: &str) -> anyhow::Result<OpExecResponse> {
+    client.reboot_cluster().cluster_identifier(id).send().await?;
+
+    // The cluster can still report available for a moment after the reboot is accepted
+    tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    wait_for_cluster(client, id, Some("available")).await?;
+
+    op_exec_output!(format!("Rebooted Redshift cluster {}", id))
+}
+
+pub async fn delete_cluster(
+    client: &aws_sdk_redshift::Client,
+    id: &str,
+    final_snapshot_identifier: &Option<String>,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .delete_cluster()
+        .cluster_identifier(id)
+        .skip_final_cluster_snapshot(final_snapshot_identifier.is_none())
+        .set_final_cluster_snapshot_identifier(final_snapshot_identifier.clone())
+        .send()
+        .await?;
+
+    wait_for_cluster(client, id, None).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("endpoint_address"), None),
+            (String::from("master_password_secret_arn"), None),
+            (String::from("cluster_namespace_arn"), None),
+        ])),
+        friendly_message: Some(format!("Deleted Redshift cluster {}", id)),
+    })
+}
+
+pub async fn create_parameter_group(
+    client: &aws_sdk_redshift::Client,
+    name: &str,
+    parameter_group: &ParameterGroup,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .create_cluster_parameter_group()
+        .parameter_group_name(name)
+        .parameter_group_family(&parameter_group.family)
+        .description(&parameter_group.description)
+        .set_tags(parameter_group.tags.clone().into())
+        .send()
+        .await?;
+
+    if !parameter_group.parameters.is_empty() {
+        modify_parameters(client, name, &parameter_group.parameters).await?;
+    }
+
+    op_exec_output!(format!("Created Redshift parameter group {}", name))
+}
+
+async fn modify_parameters(client: &aws_sdk_redshift::Client, name: &str, parameters: &HashMap<String, String>) -> anyhow::Result<()> {
+    let parameters: Vec<Parameter> = parameters
+        .iter()
+        .map(|(k, v)| Parameter::builder().parameter_name(k).parameter_value(v).build())
+        .collect();
+
+    for chunk in parameters.chunks(MAX_PARAMETERS_PER_CALL) {
+        client
+            .modify_cluster_parameter_group()
+            .parameter_group_name(name)
+            .set_parameters(Some(chunk.to_vec()))
+            .send()
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn modify_parameter_group(
+    client: &aws_sdk_redshift::Client,
+    name: &str,
+    parameters: &HashMap<String, String>,
+) -> anyhow::Result<OpExecResponse> {
+    modify_parameters(client, name, parameters).await?;
+
+    op_exec_output!(format!("Modified Redshift parameter group {}", name))
+}
+
+pub async fn reset_parameter_group(
+    client: &aws_sdk_redshift::Client,
+    name: &str,
+    parameter_names: &[String],
+) -> anyhow::Result<OpExecResponse> {
+    let parameters: Vec<Parameter> = parameter_names
+        .iter()
+        .map(|k| Parameter::builder().parameter_name(k).build())
+        .collect();
+
+    for chunk in parameters.chunks(MAX_PARAMETERS_PER_CALL) {
+        client
+            .reset_cluster_parameter_group()
+            .parameter_group_name(name)
+            .reset_all_parameters(false)
+            .set_parameters(Some(chunk.to_vec()))
+            .send()
+            .await?;
+    }
+
+    op_exec_output!(format!("Reset parameters in Redshift parameter group {}", name))
+}
+
+pub async fn update_parameter_group_tags(
+    client: &aws_sdk_redshift::Client,
+    name: &str,
+    arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    update_tags(client, arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for Redshift parameter group {}", name))
+}
+
+pub async fn delete_parameter_group(client: &aws_sdk_redshift::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    client.delete_cluster_parameter_group().parameter_group_name(name).send().await?;
+
+    op_exec_output!(format!("Deleted Redshift parameter group {}", name))
+}
+
+pub async fn create_subnet_group(
+    client: &aws_sdk_redshift::Client,
+    name: &str,
+    subnet_group: &SubnetGroup,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .create_cluster_subnet_group()
+        .cluster_subnet_group_name(name)
+        .description(&subnet_group.description)
+        .set_subnet_ids(Some(subnet_group.subnet_ids.clone()))
+        .set_tags(subnet_group.tags.clone().into())
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Created Redshift subnet group {}", name))
+}
+
+pub async fn modify_subnet_group(
+    client: &aws_sdk_redshift::Client,
+    name: &str,
+    subnet_group: &SubnetGroup,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .modify_cluster_subnet_group()
+        .cluster_subnet_group_name(name)
+        .description(&subnet_group.description)
+        .set_subnet_ids(Some(subnet_group.subnet_ids.clone()))
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Modified Redshift subnet group {}", name))
+}
+
+pub async fn update_subnet_group_tags(
+    client: &aws_sdk_redshift::Client,
+    name: &str,
+    arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    update_tags(client, arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for Redshift subnet group {}", name))
+}
+
+pub async fn delete_subnet_group(client: &aws_sdk_redshift::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    client.delete_cluster_subnet_group().cluster_subnet_group_name(name).send().await?;
+
+    op_exec_output!(format!("Deleted Redshift subnet group {}", name))
+}
+
+pub async fn create_snapshot_schedule(
+    client: &aws_sdk_redshift::Client,
+    id: &str,
+    schedule: &SnapshotSchedule,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .create_snapshot_schedule()
+        .schedule_identifier(id)
+        .set_schedule_definitions(Some(schedule.schedule_definitions.clone()))
+        .set_schedule_description(schedule.description.clone())
+        .set_tags(schedule.tags.clone().into())
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Created Redshift snapshot schedule {}", id))
+}
+
+pub async fn modify_snapshot_schedule(
+    client: &aws_sdk_redshift::Client,
+    id: &str,
+    schedule_definitions: &[String],
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .modify_snapshot_schedule()
+        .schedule_identifier(id)
+        .set_schedule_definitions(Some(schedule_definitions.to_vec()))
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Modified Redshift snapshot schedule {}", id))
+}
+
+pub async fn update_snapshot_schedule_tags(
+    client: &aws_sdk_redshift::Client,
+    id: &str,
+    arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    update_tags(client, arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for Redshift snapshot schedule {}", id))
+}
+
+pub async fn delete_snapshot_schedule(client: &aws_sdk_redshift::Client, id: &str) -> anyhow::Result<OpExecResponse> {
+    client.delete_snapshot_schedule().schedule_identifier(id).send().await?;
+
+    op_exec_output!(format!("Deleted Redshift snapshot schedule {}", id))
+}
+
+pub async fn find_namespace(
+    client: &aws_sdk_redshiftserverless::Client,
+    name: &str,
+) -> anyhow::Result<Option<aws_sdk_redshiftserverless::types::Namespace>> {
+    match client.get_namespace().namespace_name(name).send().await {
+        Ok(resp) => Ok(resp.namespace),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn namespace_status(client: &aws_sdk_redshiftserverless::Client, name: &str) -> anyhow::Result<Option<String>> {
+    Ok(find_namespace(client, name)
+        .await?
+        .map(|namespace| namespace.status.map(|s| s.as_str().to_string()).unwrap_or_default()))
+}
+
+async fn wait_for_namespace(client: &aws_sdk_redshiftserverless::Client, name: &str, target: Option<&str>) -> anyhow::Result<()> {
+    wait_for_status(&format!("Redshift Serverless namespace {}", name), target, || {
+        namespace_status(client, name)
+    })
+    .await
+}
+
+fn to_log_exports(log_exports: &[String]) -> Vec<LogExport> {
+    log_exports.iter().map(|e| LogExport::from(e.as_str())).collect()
+}
+
+pub async fn create_namespace(
+    client: &aws_sdk_redshiftserverless::Client,
+    name: &str,
+    namespace: &Namespace,
+) -> anyhow::Result<OpExecResponse> {
+    let mut request = client
+        .create_namespace()
+        .namespace_name(name)
+        .set_db_name(namespace.db_name.clone())
+        .set_kms_key_id(namespace.kms_key_id.clone())
+        .set_default_iam_role_arn(namespace.default_iam_role_arn.clone())
+        .set_tags(namespace.tags.to_serverless()?);
+
+    if let Some(admin_username) = &namespace.admin_username {
+        request = request.admin_username(admin_username).manage_admin_password(true);
+    }
+    if !namespace.iam_roles.is_empty() {
+        request = request.set_iam_roles(Some(namespace.iam_roles.clone()));
+    }
+    if !namespace.log_exports.is_empty() {
+        request = request.set_log_exports(Some(to_log_exports(&namespace.log_exports)));
+    }
+
+    let resp = request.send().await?;
+
+    wait_for_namespace(client, name, Some("AVAILABLE")).await?;
+
+    let namespace = resp.namespace;
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (
+                String::from("namespace_arn"),
+                namespace.as_ref().and_then(|namespace| namespace.namespace_arn.clone()),
+            ),
+            (
+                String::from("admin_password_secret_arn"),
+                namespace.and_then(|namespace| namespace.admin_password_secret_arn),
+            ),
+        ])),
+        friendly_message: Some(format!("Created Redshift Serverless namespace {}", name)),
+    })
+}
+
+/// Only sends the encryption key if it changed, as a new key re-encrypts the namespace's data.
+/// IAM roles are always sent along with the default role, which must be one of them.
+pub async fn update_namespace(
+    client: &aws_sdk_redshiftserverless::Client,
+    name: &str,
+    namespace: &Namespace,
+) -> anyhow::Result<OpExecResponse> {
+    let current = find_namespace(client, name)
+        .await?
+        .with_context(|| format!("Redshift Serverless namespace {} not found", name))?;
+
+    let mut request = client
+        .update_namespace()
+        .namespace_name(name)
+        .set_iam_roles(Some(namespace.iam_roles.clone()))
+        .set_default_iam_role_arn(namespace.default_iam_role_arn.clone())
+        .set_log_exports(Some(to_log_exports(&namespace.log_exports)));
+
+    if namespace.kms_key_id.is_some() && namespace.kms_key_id != current.kms_key_id {
+        request = request.set_kms_key_id(namespace.kms_key_id.clone());
+    }
+
+    request.send().await?;
+
+    wait_for_namespace(client, name, Some("AVAILABLE")).await?;
+
+    op_exec_output!(format!("Updated Redshift Serverless namespace {}", name))
+}
+
+pub async fn update_namespace_tags(
+    client: &aws_sdk_redshiftserverless::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = find_namespace(client, name)
+        .await?
+        .and_then(|namespace| namespace.namespace_arn)
+        .with_context(|| format!("Redshift Serverless namespace {} not found", name))?;
+
+    update_serverless_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for Redshift Serverless namespace {}", name))
+}
+
+pub async fn delete_namespace(
+    client: &aws_sdk_redshiftserverless::Client,
+    name: &str,
+    final_snapshot_name: &Option<String>,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .delete_namespace()
+        .namespace_name(name)
+        .set_final_snapshot_name(final_snapshot_name.clone())
+        .send()
+        .await?;
+
+    wait_for_namespace(client, name, None).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("namespace_arn"), None),
+            (String::from("admin_password_secret_arn"), None),
+        ])),
+        friendly_message: Some(format!("Deleted Redshift Serverless namespace {}", name)),
+    })
+}
+
+pub async fn find_workgroup(
+    client: &aws_sdk_redshiftserverless::Client,
+    name: &str,
+) -> anyhow::Result<Option<aws_sdk_redshiftserverless::types::Workgroup>> {
+    match client.get_workgroup().workgroup_name(name).send().await {
+        Ok(resp) => Ok(resp.workgroup),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn workgroup_status(client: &aws_sdk_redshiftserverless::Client, name: &str) -> anyhow::Result<Option<String>> {
+    Ok(find_workgroup(client, name)
+        .await?
+        .map(|workgroup| workgroup.status.map(|s| s.as_str().to_string()).unwrap_or_default()))
+}
+
+async fn wait_for_workgroup(client: &aws_sdk_redshiftserverless::Client, name: &str, target: Option<&str>) -> anyhow::Result<()> {
+    wait_for_status(&format!("Redshift Serverless workgroup {}", name), target, || {
+        workgroup_status(client, name)
+    })
+    .await
+}
+
+fn to_config_parameters(config_parameters: &HashMap<String, String>) -> Vec<ConfigParameter> {
+    config_parameters
+        .iter()
+        .map(|(k, v)| ConfigParameter::builder().parameter_key(k).parameter_value(v).build())
+        .collect()
+}
+
+pub async fn create_workgroup(
+    client: &aws_sdk_redshiftserverless::Client,
+    name: &str,
+    workgroup: &Workgroup,
+) -> anyhow::Result<OpExecResponse> {
+    let mut request = client
+        .create_workgroup()
+        .workgroup_name(name)
+        .namespace_name(&workgroup.namespace)
+        .set_base_capacity(workgroup.base_capacity)
+        .set_max_capacity(workgroup.max_capacity)
+        .publicly_accessible(workgroup.publicly_accessible)
+        .enhanced_vpc_routing(workgroup.enhanced_vpc_routing)
+        .set_port(workgroup.port)
+        .set_tags(workgroup.tags.to_serverless()?);
+
+    if !workgroup.subnet_ids.is_empty() {
+        request = request.set_subnet_ids(Some(workgroup.subnet_ids.clone()));
+    }
+    if !workgroup.security_group_ids.is_empty() {
+        request = request.set_security_group_ids(Some(workgroup.security_group_ids.clone()));
+    }
+    if !workgroup.config_parameters.is_empty() {
+        request = request.set_config_parameters(Some(to_config_parameters(&workgroup.config_parameters)));
+    }
+
+    request.send().await?;
+
+    wait_for_workgroup(client, name, Some("AVAILABLE")).await?;
+
+    let workgroup = find_workgroup(client, name).await?;
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (
+                String::from("workgroup_arn"),
+                workgroup.as_ref().and_then(|workgroup| workgroup.workgroup_arn.clone()),
+            ),
+            (
+                String::from("endpoint_address"),
+                workgroup.and_then(|workgroup| workgroup.endpoint.and_then(|endpoint| endpoint.address)),
+            ),
+        ])),
+        friendly_message: Some(format!("Created Redshift Serverless workgroup {}", name)),
+    })
+}
+
+/// Redshift Serverless only changes one kind of workgroup setting per UpdateWorkgroup call, so
+/// each one that differs from the workgroup's current settings gets its own call.
+pub async fn update_workgroup(
+    client: &aws_sdk_redshiftserverless::Client,
+    name: &str,
+    workgroup: &Workgroup,
+) -> anyhow::Result<OpExecResponse> {
+    let current = find_workgroup(client, name)
+        .await?
+        .with_context(|| format!("Redshift Serverless workgroup {} not found", name))?;
+
+    let mut current_subnet_ids = current.subnet_ids.clone().unwrap_or_default();
+    current_subnet_ids.sort();
+    let mut current_security_group_ids = current.security_group_ids.clone().unwrap_or_default();
+    current_security_group_ids.sort();
+    let current_config_parameters: HashMap<String, String> = current
+        .config_parameters
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|p| Some((p.parameter_key?, p.parameter_value?)))
+        .collect();
+
+    let mut updates = Vec::new();
+
+    if (workgroup.base_capacity.is_some() && workgroup.base_capacity != current.base_capacity)
+        || workgroup.max_capacity != current.max_capacity
+    {
+        updates.push(
+            client
+                .update_workgroup()
+                .workgroup_name(name)
+                .set_base_capacity(workgroup.base_capacity)
+                .set_max_capacity(workgroup.max_capacity),
+        );
+    }
+    if workgroup.port.is_some() && workgroup.port != current.port {
+        updates.push(client.update_workgroup().workgroup_name(name).set_port(workgroup.port));
+    }
+    if Some(workgroup.publicly_accessible) != current.publicly_accessible {
+        updates.push(
+            client
+                .update_workgroup()
+                .workgroup_name(name)
+                .publicly_accessible(workgroup.publicly_accessible),
+        );
+    }
+    if Some(workgroup.enhanced_vpc_routing) != current.enhanced_vpc_routing {
+        updates.push(
+            client
+                .update_workgroup()
+                .workgroup_name(name)
+                .enhanced_vpc_routing(workgroup.enhanced_vpc_routing),
+        );
+    }
+    if (!workgroup.subnet_ids.is_empty() && workgroup.subnet_ids != current_subnet_ids)
+        || (!workgroup.security_group_ids.is_empty() && workgroup.security_group_ids != current_security_group_ids)
+    {
+        let mut request = client.update_workgroup().workgroup_name(name);
+        if !workgroup.subnet_ids.is_empty() {
+            request = request.set_subnet_ids(Some(workgroup.subnet_ids.clone()));
+        }
+        if !workgroup.security_group_ids.is_empty() {
+            request = request.set_security_group_ids(Some(workgroup.security_group_ids.clone()));
+        }
+        updates.push(request);
+    }
+    let changed_config_parameters: HashMap<String, String> = workgroup
+        .config_parameters
+        .iter()
+        .filter(|(k, v)| current_config_parameters.get(*k) != Some(*v))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    if !changed_config_parameters.is_empty() {
+        updates.push(
+            client
+                .update_workgroup()
+                .workgroup_name(name)
+                .set_config_parameters(Some(to_config_parameters(&changed_config_parameters))),
+        );
+    }
+
+    for update in updates {
+        update.send().await?;
+        wait_for_workgroup(client, name, Some("AVAILABLE")).await?;
+    }
+
+    op_exec_output!(format!("Updated Redshift Serverless workgroup {}", name))
+}
+
+pub async fn update_workgroup_tags(
+    client: &aws_sdk_redshiftserverless::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = find_workgroup(client, name)
+        .await?
+        .and_then(|workgroup| workgroup.workgroup_arn)
+        .with_context(|| format!("Redshift Serverless workgroup {} not found", name))?;
+
+    update_serverless_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for Redshift Serverless workgroup {}", name))
+}
+
+pub async fn delete_workgroup(client: &aws_sdk_redshiftserverless::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    client.delete_workgroup().workgroup_name(name).send().await?;
+
+    wait_for_workgroup(client, name, None).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("workgroup_arn"), None),
+            (String::from("endpoint_address"), None),
+        ])),
+        friendly_message: Some(format!("Deleted Redshift Serverless workgroup {}", name)),
+    })
+}
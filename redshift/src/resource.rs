@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::RedshiftResourceAddress, tags::Tags};
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_number_of_nodes() -> i32 {
+    1
+}
+
+/// A provisioned Redshift cluster. Its admin password is generated and kept in Secrets Manager by
+/// Redshift itself.
+///
+/// The admin username, database name, subnet group and encryption key are fixed at creation.
+/// Changing the node type or number of nodes resizes the cluster, and changing its parameter group
+/// takes effect when the cluster is rebooted, which the plan does straight after.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Cluster {
+    /// e.g. ra3.xlplus or ra3.4xlarge.
+    pub node_type: String,
+    /// A cluster with one node is a single-node cluster.
+    #[serde(default = "default_number_of_nodes")]
+    pub number_of_nodes: i32,
+    pub master_username: String,
+    /// The database created with the cluster. If None, dev.
+    pub db_name: Option<String>,
+    /// If None, 5439.
+    pub port: Option<i32>,
+    /// The name of a subnet group in the same region. If None, the cluster is created in the default VPC.
+    pub cluster_subnet_group_name: Option<String>,
+    #[serde(default)]
+    pub vpc_security_group_ids: Vec<String>,
+    /// The name of a parameter group in the same region. If None, the default parameter group.
+    pub cluster_parameter_group_name: Option<String>,
+    #[serde(default)]
+    pub publicly_accessible: bool,
+    #[serde(default = "default_true")]
+    pub encrypted: bool,
+    /// If None, encrypted clusters use a key owned by Redshift.
+    pub kms_key_id: Option<String>,
+    #[serde(default)]
+    pub enhanced_vpc_routing: bool,
+    /// Days to keep automated snapshots, from 0 to 35. If None, 1.
+    pub automated_snapshot_retention_period: Option<i32>,
+    /// e.g. sun:05:00-sun:05:30. If None, Redshift picks one.
+    pub preferred_maintenance_window: Option<String>,
+    /// ARNs of the IAM roles the cluster can assume, e.g. for COPY and UNLOAD.
+    #[serde(default)]
+    pub iam_roles: Vec<String>,
+    /// One of `iam_roles`, used when a command doesn't name a role.
+    pub default_iam_role_arn: Option<String>,
+    /// The ID of a snapshot schedule in the same region. If None, Redshift takes a snapshot every
+    /// eight hours or 5 GB of changes.
+    pub snapshot_schedule: Option<String>,
+    pub tags: Tags,
+}
+
+/// A cluster parameter group. Only the parameters given here are managed, and removing one resets
+/// it to the family's default.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ParameterGroup {
+    /// e.g. redshift-2.0.
+    pub family: String,
+    pub description: String,
+    #[serde(default)]
+    pub parameters: HashMap<String, String>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SubnetGroup {
+    pub description: String,
+    pub subnet_ids: Vec<String>,
+    pub tags: Tags,
+}
+
+/// A schedule for a cluster's automated snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SnapshotSchedule {
+    /// e.g. rate(12 hours) or cron(0 6 * * ? *).
+    pub schedule_definitions: Vec<String>,
+    /// Fixed at creation.
+    pub description: Option<String>,
+    pub tags: Tags,
+}
+
+/// A Redshift Serverless namespace. Its admin password is generated and kept in Secrets Manager by
+/// Redshift itself.
+///
+/// The admin username and database name are fixed at creation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Namespace {
+    /// If None, the namespace has no admin user.
+    pub admin_username: Option<String>,
+    /// The database created with the namespace. If None, dev.
+    pub db_name: Option<String>,
+    /// If None, the namespace is encrypted with a key owned by Redshift.
+    pub kms_key_id: Option<String>,
+    #[serde(default)]
+    pub iam_roles: Vec<String>,
+    /// One of `iam_roles`, used when a command doesn't name a role.
+    pub default_iam_role_arn: Option<String>,
+    /// Any of useractivitylog, userlog and connectionlog.
+    #[serde(default)]
+    pub log_exports: Vec<String>,
+    pub tags: Tags,
+}
+
+/// A Redshift Serverless workgroup, which provides compute for a namespace.
+///
+/// The namespace is fixed at creation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Workgroup {
+    pub namespace: String,
+    /// The base capacity in Redshift Processing Units, from 8 to 512 in steps of 8. If None, 128.
+    pub base_capacity: Option<i32>,
+    /// The most RPUs the workgroup can scale up to.
+    pub max_capacity: Option<i32>,
+    #[serde(default)]
+    pub subnet_ids: Vec<String>,
+    #[serde(default)]
+    pub security_group_ids: Vec<String>,
+    #[serde(default)]
+    pub publicly_accessible: bool,
+    #[serde(default)]
+    pub enhanced_vpc_routing: bool,
+    /// If None, 5439.
+    pub port: Option<i32>,
+    /// e.g. require_ssl or max_query_execution_time. Parameters left out keep their current values.
+    #[serde(default)]
+    pub config_parameters: HashMap<String, String>,
+    pub tags: Tags,
+}
+
+pub enum RedshiftResource {
+    Cluster(Cluster),
+    ParameterGroup(ParameterGroup),
+    SubnetGroup(SubnetGroup),
+    SnapshotSchedule(SnapshotSchedule),
+    Namespace(Namespace),
+    Workgroup(Workgroup),
+}
+
+impl Resource for RedshiftResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            RedshiftResource::Cluster(cluster) => Ok(RON.to_string_pretty(&cluster, pretty_config)?.into()),
+            RedshiftResource::ParameterGroup(parameter_group) => Ok(RON.to_string_pretty(&parameter_group, pretty_config)?.into()),
+            RedshiftResource::SubnetGroup(subnet_group) => Ok(RON.to_string_pretty(&subnet_group, pretty_config)?.into()),
+            RedshiftResource::SnapshotSchedule(schedule) => Ok(RON.to_string_pretty(&schedule, pretty_config)?.into()),
+            RedshiftResource::Namespace(namespace) => Ok(RON.to_string_pretty(&namespace, pretty_config)?.into()),
+            RedshiftResource::Workgroup(workgroup) => Ok(RON.to_string_pretty(&workgroup, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = RedshiftResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            RedshiftResourceAddress::Cluster { .. } => Ok(RedshiftResource::Cluster(RON.from_str(s)?)),
+            RedshiftResourceAddress::ParameterGroup { .. } => Ok(RedshiftResource::ParameterGroup(RON.from_str(s)?)),
+            RedshiftResourceAddress::SubnetGroup { .. } => Ok(RedshiftResource::SubnetGroup(RON.from_str(s)?)),
+            RedshiftResourceAddress::SnapshotSchedule { .. } => Ok(RedshiftResource::SnapshotSchedule(RON.from_str(s)?)),
+            RedshiftResourceAddress::Namespace { .. } => Ok(RedshiftResource::Namespace(RON.from_str(s)?)),
+            RedshiftResourceAddress::Workgroup { .. } => Ok(RedshiftResource::Workgroup(RON.from_str(s)?)),
+        }
+    }
+}
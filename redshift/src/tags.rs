@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Tags(HashMap<String, String>);
+
+impl From<Option<Vec<aws_sdk_redshift::types::Tag>>> for Tags {
+    fn from(tags: Option<Vec<aws_sdk_redshift::types::Tag>>) -> Self {
+        let mut out_map = HashMap::new();
+        for tag in tags.unwrap_or_default() {
+            let (Some(key), Some(value)) = (tag.key, tag.value) else {
+                continue;
+            };
+            out_map.insert(key, value);
+        }
+        Tags(out_map)
+    }
+}
+
+impl From<Tags> for Option<Vec<aws_sdk_redshift::types::Tag>> {
+    fn from(val: Tags) -> Self {
+        if val.0.is_empty() {
+            return None;
+        }
+        Some(
+            val.0
+                .into_iter()
+                .map(|(k, v)| aws_sdk_redshift::types::Tag::builder().key(k).value(v).build())
+                .collect(),
+        )
+    }
+}
+
+impl From<Vec<aws_sdk_redshiftserverless::types::Tag>> for Tags {
+    fn from(tags: Vec<aws_sdk_redshiftserverless::types::Tag>) -> Self {
+        Tags(tags.into_iter().map(|tag| (tag.key, tag.value)).collect())
+    }
+}
+
+impl Tags {
+    /// Redshift Serverless tags have a required key and value, so building them can fail.
+    pub fn to_serverless(&self) -> anyhow::Result<Option<Vec<aws_sdk_redshiftserverless::types::Tag>>> {
+        if self.0.is_empty() {
+            return Ok(None);
+        }
+        let mut out_vec = Vec::new();
+        for (k, v) in &self.0 {
+            out_vec.push(aws_sdk_redshiftserverless::types::Tag::builder().key(k).value(v).build()?);
+        }
+        Ok(Some(out_vec))
+    }
+}
+
+// From a pair of tag maps, determine the keys to untag and the tags to set respectively
+pub fn tag_diff(old_tags: &Tags, new_tags: &Tags) -> (Vec<String>, Tags) {
+    let untag_keys = old_tags.0.keys().filter(|k| !new_tags.0.contains_key(*k)).cloned().collect();
+
+    let new_tagset = new_tags
+        .0
+        .iter()
+        .filter(|(k, v)| old_tags.0.get(*k) != Some(*v))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+
+    (untag_keys, Tags(new_tagset))
+}
@@ -0,0 +1,21 @@
+/// Redshift's tagging calls take an ARN, which for provisioned resources can be built from the name.
+/// `resource_type` is e.g. cluster, parametergroup, subnetgroup or snapshotschedule.
+pub fn redshift_arn(region: &str, account_id: &str, resource_type: &str, name: &str) -> String {
+    format!("arn:aws:redshift:{region}:{account_id}:{resource_type}:{name}")
+}
+
+/// GetNamespace reports IAM roles as e.g. `IamRole(applyStatus=in-sync, iamRoleArn=arn:aws:iam::...)`
+/// rather than as plain ARNs, so this pulls the ARN out.
+fn parse_namespace_iam_role(role: &str) -> String {
+    match role.split_once("iamRoleArn=") {
+        Some((_, rest)) => rest.trim_end_matches(')').split(',').next().unwrap_or(rest).trim().to_string(),
+        None => role.to_string(),
+    }
+}
+
+/// The IAM roles of a namespace as plain ARNs.
+pub fn namespace_iam_roles(namespace: &aws_sdk_redshiftserverless::types::Namespace) -> Vec<String> {
+    let mut iam_roles: Vec<String> = namespace.iam_roles().iter().map(|role| parse_namespace_iam_role(role)).collect();
+    iam_roles.sort();
+    iam_roles
+}
@@ -1,12 +1,17 @@
 use std::path::{Path, PathBuf};
 
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
 use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 
 #[derive(Debug, Clone)]
 pub enum Route53ResourceAddress {
     HostedZone(String),
     ResourceRecordSet(String, String, String), // (hosted_zone, name, type)
+    RoutedRecordSet(String, String, String, String), // (hosted_zone, name, type, set_identifier)
     HealthCheck(String),
+    FailoverPair(String, String, String), // (hosted_zone, name, type)
+    KeySigningKey(String, String), // (hosted_zone, name)
+    Delegation(String, String), // (hosted_zone, child_zone)
 }
 
 impl ResourceAddress for Route53ResourceAddress {
@@ -22,6 +27,29 @@ impl ResourceAddress for Route53ResourceAddress {
                 r#type,
                 name.strip_suffix(".").unwrap(),
             )),
+            Route53ResourceAddress::RoutedRecordSet(hosted_zone, name, r#type, set_identifier) => PathBuf::from(format!(
+                "aws/route53/hosted_zones/{}/records/{}/{}/{}.ron",
+                hosted_zone.strip_suffix(".").unwrap(),
+                r#type,
+                name.strip_suffix(".").unwrap(),
+                set_identifier,
+            )),
+            Route53ResourceAddress::FailoverPair(hosted_zone, name, r#type) => PathBuf::from(format!(
+                "aws/route53/hosted_zones/{}/failover/{}/{}.ron",
+                hosted_zone.strip_suffix(".").unwrap(),
+                r#type,
+                name.strip_suffix(".").unwrap(),
+            )),
+            Route53ResourceAddress::KeySigningKey(hosted_zone, name) => PathBuf::from(format!(
+                "aws/route53/hosted_zones/{}/key_signing_keys/{}.ron",
+                hosted_zone.strip_suffix(".").unwrap(),
+                name
+            )),
+            Route53ResourceAddress::Delegation(hosted_zone, child_zone) => PathBuf::from(format!(
+                "aws/route53/hosted_zones/{}/delegations/{}.ron",
+                hosted_zone.strip_suffix(".").unwrap(),
+                child_zone.strip_suffix(".").unwrap(),
+            )),
             Route53ResourceAddress::HealthCheck(name) => {
                 PathBuf::from(format!("aws/route53/health_checks/{}.ron", name.strip_suffix(".").unwrap(),))
             }
@@ -55,6 +83,114 @@ impl ResourceAddress for Route53ResourceAddress {
                     r#type.to_string(),
                 ))
             }
+            ["aws", "route53", "hosted_zones", hosted_zone, "records", r#type, name, set_identifier]
+                if set_identifier.ends_with(".ron") =>
+            {
+                let mut hosted_zone = hosted_zone.to_string();
+                hosted_zone.push('.');
+                let mut name = name.to_string();
+                name.push('.');
+
+                Ok(Route53ResourceAddress::RoutedRecordSet(
+                    hosted_zone,
+                    name,
+                    r#type.to_string(),
+                    set_identifier.strip_suffix(".ron").unwrap().to_string(),
+                ))
+            }
+            ["aws", "route53", "hosted_zones", hosted_zone, "failover", r#type, name] if name.ends_with(".ron") => {
+                let mut hosted_zone = hosted_zone.to_string();
+                hosted_zone.push('.');
+                let mut name = name.strip_suffix(".ron").unwrap().to_string();
+                name.push('.');
+
+                Ok(Route53ResourceAddress::FailoverPair(hosted_zone, name, r#type.to_string()))
+            }
+            ["aws", "route53", "hosted_zones", hosted_zone, "key_signing_keys", name] if name.ends_with(".ron") => {
+                let mut hosted_zone = hosted_zone.to_string();
+                hosted_zone.push('.');
+
+                Ok(Route53ResourceAddress::KeySigningKey(
+                    hosted_zone,
+                    name.strip_suffix(".ron").unwrap().to_string(),
+                ))
+            }
+            ["aws", "route53", "hosted_zones", hosted_zone, "delegations", child_zone] if child_zone.ends_with(".ron") => {
+                let mut hosted_zone = hosted_zone.to_string();
+                hosted_zone.push('.');
+                let mut child_zone = child_zone.strip_suffix(".ron").unwrap().to_string();
+                child_zone.push('.');
+
+                Ok(Route53ResourceAddress::Delegation(hosted_zone, child_zone))
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for Route53ResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/route53/hosted_zones/<zone_name>/config.ron",
+                description: "A hosted zone, named without the trailing dot",
+                example:     "aws/route53/hosted_zones/example.com/config.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/route53/hosted_zones/<zone_name>/records/<type>/<record_name>.ron",
+                description: "A record set in a hosted zone",
+                example:     "aws/route53/hosted_zones/example.com/records/A/www.example.com.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/route53/hosted_zones/<zone_name>/records/<type>/<record_name>/<set_identifier>.ron",
+                description: "One of the record sets at a name and type with a weighted, latency, failover, geolocation, geoproximity or multivalue answer routing policy",
+                example:     "aws/route53/hosted_zones/example.com/records/A/www.example.com/us-east-1.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/route53/hosted_zones/<zone_name>/failover/<type>/<record_name>.ron",
+                description: "A primary/secondary pair of failover record sets and their health checks",
+                example:     "aws/route53/hosted_zones/example.com/failover/A/api.example.com.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/route53/hosted_zones/<zone_name>/key_signing_keys/<name>.ron",
+                description: "A DNSSEC key-signing key for a hosted zone, backed by a KMS key",
+                example:     "aws/route53/hosted_zones/example.com/key_signing_keys/ksk1.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/route53/hosted_zones/<zone_name>/delegations/<child_zone_name>.ron",
+                description: "The NS records that delegate a child hosted zone managed here, kept pointing at the child zone's name servers",
+                example:     "aws/route53/hosted_zones/example.com/delegations/dev.example.com.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/route53/health_checks/<name>.ron",
+                description: "A health check",
+                example:     "aws/route53/health_checks/api.ron",
+            },
+        ]
+    }
+}
+
+/// The address of a CloudFront distribution managed by the CloudFront connector. An alias record can
+/// name one of these in `dns_name`, and it's resolved through the distribution's `domain_name` output.
+#[derive(Debug, Clone)]
+pub struct CloudFrontDistributionAddress {
+    pub distribution_id: String,
+}
+
+impl ResourceAddress for CloudFrontDistributionAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        PathBuf::from(format!("aws/cloudfront/distributions/{}.ron", self.distribution_id))
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "cloudfront", "distributions", distribution_id] if distribution_id.ends_with(".ron") => {
+                Ok(CloudFrontDistributionAddress {
+                    distribution_id: distribution_id.strip_suffix(".ron").unwrap().to_string(),
+                })
+            }
             _ => Err(invalid_addr_path(path)),
         }
     }
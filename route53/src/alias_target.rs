@@ -0,0 +1,143 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::bail;
+use autoschematic_connector_aws_core::elb::{ElbLoadBalancerAddress, elb_addresses_by_output};
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::{
+    addr::CloudFrontDistributionAddress,
+    resource::AliasTarget,
+    util::normalize_dns_name,
+};
+
+/// The hosted zone that alias records for every CloudFront distribution point into.
+pub const CLOUDFRONT_HOSTED_ZONE_ID: &str = "Z2FDTNDATAQYW2";
+
+/// A CloudFront distribution or load balancer that an alias target names by its address
+/// rather than by its DNS name.
+pub enum AliasTargetAddress {
+    CloudFrontDistribution(CloudFrontDistributionAddress),
+    LoadBalancer(ElbLoadBalancerAddress),
+}
+
+impl AliasTargetAddress {
+    /// Parses the `dns_name` of an alias target. Returns None if it's a DNS name rather than an address.
+    pub fn parse(dns_name: &str) -> anyhow::Result<Option<Self>> {
+        if !dns_name.starts_with("aws/") {
+            return Ok(None);
+        }
+
+        let path = Path::new(dns_name);
+        if let Ok(addr) = CloudFrontDistributionAddress::from_path(path) {
+            return Ok(Some(AliasTargetAddress::CloudFrontDistribution(addr)));
+        }
+        if let Ok(addr) = ElbLoadBalancerAddress::from_path(path) {
+            return Ok(Some(AliasTargetAddress::LoadBalancer(addr)));
+        }
+
+        bail!(
+            "Alias target {} is not the address of a CloudFront distribution (aws/cloudfront/distributions/<id>.ron) \
+             or load balancer (aws/elb/<region>/load_balancers/<name>.ron)",
+            dns_name
+        )
+    }
+
+    /// Reads the target's DNS name from its outputs. Fails if the target hasn't been created yet.
+    pub fn dns_name(&self, prefix: &Path) -> anyhow::Result<String> {
+        let (dns_name, addr) = match self {
+            AliasTargetAddress::CloudFrontDistribution(addr) => (addr.get_output(prefix, "domain_name")?, addr.to_path_buf()),
+            AliasTargetAddress::LoadBalancer(addr) => (addr.get_output(prefix, "dns_name")?, addr.to_path_buf()),
+        };
+
+        match dns_name {
+            Some(dns_name) => Ok(dns_name),
+            None => bail!("{} has no DNS name in its outputs. Has it been created yet?", addr.display()),
+        }
+    }
+}
+
+/// Checks an alias target at plan time. Targets named by address can only be aliased from A and AAAA
+/// records, and take their hosted zone from the target.
+pub fn check_alias_target(name: &str, r#type: &str, alias_target: &AliasTarget) -> anyhow::Result<()> {
+    let Some(target) = AliasTargetAddress::parse(&alias_target.dns_name)? else {
+        if alias_target.hosted_zone_id.is_empty() {
+            bail!("Alias target {} of {} record {} needs a hosted_zone_id", alias_target.dns_name, r#type, name);
+        }
+        return Ok(());
+    };
+
+    if r#type != "A" && r#type != "AAAA" {
+        bail!(
+            "{} record {} can't alias {}: only A and AAAA records can alias a CloudFront distribution or load balancer",
+            r#type,
+            name,
+            alias_target.dns_name
+        );
+    }
+
+    if !alias_target.hosted_zone_id.is_empty() {
+        bail!(
+            "Alias target {} of {} record {} should leave hosted_zone_id empty; it's looked up from the target",
+            alias_target.dns_name,
+            r#type,
+            name
+        );
+    }
+
+    if matches!(target, AliasTargetAddress::CloudFrontDistribution(_)) && alias_target.evaluate_target_health {
+        bail!(
+            "Alias target {} of {} record {} can't evaluate target health: CloudFront distributions don't support it",
+            alias_target.dns_name,
+            r#type,
+            name
+        );
+    }
+
+    Ok(())
+}
+
+/// The names, without their extension, of the .ron files directly in `dir`.
+fn ron_file_stems(dir: &Path) -> anyhow::Result<Vec<String>> {
+    let mut stems = Vec::new();
+    if !dir.is_dir() {
+        return Ok(stems);
+    }
+
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if let Some(stem) = path.file_name().and_then(|f| f.to_str()).and_then(|f| f.strip_suffix(".ron")) {
+            stems.push(stem.to_string());
+        }
+    }
+
+    Ok(stems)
+}
+
+/// The addresses of the CloudFront distributions and load balancers that have been created, keyed by
+/// normalized DNS name. Used to report alias targets by address when the repo manages their targets.
+pub fn alias_target_addresses_by_dns_name(prefix: &Path) -> anyhow::Result<HashMap<String, String>> {
+    let mut addresses = HashMap::new();
+
+    for distribution_id in ron_file_stems(&prefix.join("aws/cloudfront/distributions"))? {
+        let addr = CloudFrontDistributionAddress { distribution_id };
+        if let Some(domain_name) = addr.get_output(prefix, "domain_name")? {
+            addresses.insert(normalize_dns_name(&domain_name), addr.to_path_buf().to_string_lossy().to_string());
+        }
+    }
+
+    let elb_dir = prefix.join("aws/elb");
+    if elb_dir.is_dir() {
+        for entry in std::fs::read_dir(&elb_dir)? {
+            let region_dir = entry?.path();
+            let Some(region) = region_dir.file_name().and_then(|r| r.to_str()) else {
+                continue;
+            };
+
+            for (dns_name, addr) in elb_addresses_by_output::<ElbLoadBalancerAddress>(prefix, region, "dns_name")? {
+                addresses.insert(normalize_dns_name(&dns_name), addr);
+            }
+        }
+    }
+
+    Ok(addresses)
+}
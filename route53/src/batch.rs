@@ -0,0 +1,245 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use anyhow::bail;
+use aws_sdk_route53::{
+    config::http::HttpResponse,
+    error::SdkError,
+    operation::change_resource_record_sets::ChangeResourceRecordSetsError,
+    types::{Change, ChangeAction, ChangeBatch, ChangeInfo, ChangeStatus},
+};
+use tokio::sync::{Mutex, oneshot};
+
+/// How long the first change for a hosted zone waits for other changes to join its batch.
+const BATCH_WINDOW: Duration = Duration::from_millis(250);
+
+/// ChangeResourceRecordSets accepts at most 1000 ResourceRecord elements per request...
+const MAX_BATCH_RECORDS: usize = 1000;
+/// ...and at most 32000 characters across all of their values.
+const MAX_BATCH_CHARS: usize = 32000;
+
+const INSYNC_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const INSYNC_MAX_POLLS: usize = 120;
+
+/// How long an op waits for its batch's result. This is longer than the INSYNC wait (10 minutes)
+/// plus the API calls around it, so it only runs out if the task applying the batch is lost.
+const WAITER_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+struct PendingBatch {
+    id:      u64,
+    changes: Vec<Change>,
+    records: usize,
+    chars:   usize,
+    waiters: Vec<oneshot::Sender<Result<(), String>>>,
+}
+
+/// Collects record set changes that arrive close together for the same hosted zone
+/// and applies them as a single change batch, waiting for INSYNC once per batch.
+///
+/// Route53 applies a change batch atomically, so if it rejects a batch, each of the batch's
+/// changes is retried on its own, and only the ops whose changes are rejected again fail.
+///
+/// Batches are sent from their own task rather than by the op that opened them, so cancelling
+/// an op doesn't leave the other ops in its batch waiting. A cancelled op's change is still applied.
+#[derive(Default)]
+pub struct ChangeBatcher {
+    next_id: Mutex<u64>,
+    pending: Arc<Mutex<HashMap<String, PendingBatch>>>,
+}
+
+impl ChangeBatcher {
+    /// Submits `change` to the pending batch for `hosted_zone_id` and waits until that batch
+    /// has been applied and is INSYNC.
+    pub async fn submit(
+        &self,
+        client: &aws_sdk_route53::Client,
+        hosted_zone_id: &str,
+        change: Change,
+    ) -> anyhow::Result<()> {
+        let (records, chars) = change_weight(&change);
+        let (tx, rx) = oneshot::channel();
+
+        let mut overflow = None;
+        let mut lead_id = None;
+        {
+            let mut pending = self.pending.lock().await;
+
+            // If this change doesn't fit, send off the current batch and start a new one.
+            if let Some(batch) = pending.get(hosted_zone_id)
+                && (batch.records + records > MAX_BATCH_RECORDS || batch.chars + chars > MAX_BATCH_CHARS)
+            {
+                overflow = pending.remove(hosted_zone_id);
+            }
+
+            if !pending.contains_key(hosted_zone_id) {
+                let mut next_id = self.next_id.lock().await;
+                *next_id += 1;
+                pending.insert(
+                    hosted_zone_id.to_string(),
+                    PendingBatch {
+                        id:      *next_id,
+                        changes: Vec::new(),
+                        records: 0,
+                        chars:   0,
+                        waiters: Vec::new(),
+                    },
+                );
+                lead_id = Some(*next_id);
+            }
+
+            let Some(batch) = pending.get_mut(hosted_zone_id) else {
+                bail!("Failed to queue change for hosted zone {}", hosted_zone_id);
+            };
+            batch.changes.push(change);
+            batch.records += records;
+            batch.chars += chars;
+            batch.waiters.push(tx);
+        }
+
+        if let Some(overflow) = overflow {
+            tokio::spawn(apply_batch(client.clone(), hosted_zone_id.to_string(), overflow));
+        }
+
+        // A batch is sent once the window after it opened closes,
+        // unless it was already sent off because it filled up.
+        if let Some(lead_id) = lead_id {
+            let pending = self.pending.clone();
+            let client = client.clone();
+            let hosted_zone_id = hosted_zone_id.to_string();
+            tokio::spawn(async move {
+                tokio::time::sleep(BATCH_WINDOW).await;
+
+                let batch = {
+                    let mut pending = pending.lock().await;
+                    match pending.get(&hosted_zone_id) {
+                        Some(batch) if batch.id == lead_id => pending.remove(&hosted_zone_id),
+                        _ => None,
+                    }
+                };
+
+                if let Some(batch) = batch {
+                    apply_batch(client, hosted_zone_id, batch).await;
+                }
+            });
+        }
+
+        match tokio::time::timeout(WAITER_TIMEOUT, rx).await {
+            Ok(Ok(Ok(()))) => Ok(()),
+            Ok(Ok(Err(e))) => bail!("{}", e),
+            Ok(Err(_)) => bail!("Change batch for hosted zone {} was dropped", hosted_zone_id),
+            Err(_) => bail!(
+                "Timed out after {} seconds waiting for the change batch for hosted zone {}",
+                WAITER_TIMEOUT.as_secs(),
+                hosted_zone_id
+            ),
+        }
+    }
+}
+
+async fn apply_batch(client: aws_sdk_route53::Client, hosted_zone_id: String, batch: PendingBatch) {
+    let PendingBatch { changes, waiters, .. } = batch;
+    let count = changes.len();
+
+    let result = match send_changes(&client, &hosted_zone_id, changes.clone()).await {
+        // The rejected batch changed nothing, so apply each change on its own to fail only the bad ones.
+        Err(SdkError::ServiceError(_)) if count > 1 => {
+            for (change, waiter) in changes.into_iter().zip(waiters) {
+                apply_alone(&client, &hosted_zone_id, change, waiter).await;
+            }
+            return;
+        }
+        Err(e) => Err(anyhow::Error::from(e)),
+        Ok(Some(change_info)) => wait_for_insync(&client, change_info).await,
+        Ok(None) => Ok(()),
+    }
+    .map_err(|e| format!("Change batch of {} changes failed: {:#}", count, e));
+
+    for waiter in waiters {
+        let _ = waiter.send(result.clone());
+    }
+}
+
+/// Applies one change from a rejected batch by itself. The change is sent right away so that
+/// the rest of the batch isn't held up, but INSYNC is waited for in the background.
+async fn apply_alone(
+    client: &aws_sdk_route53::Client,
+    hosted_zone_id: &str,
+    change: Change,
+    waiter: oneshot::Sender<Result<(), String>>,
+) {
+    match send_changes(client, hosted_zone_id, vec![change]).await {
+        Ok(Some(change_info)) => {
+            let client = client.clone();
+            tokio::spawn(async move {
+                let result = wait_for_insync(&client, change_info).await.map_err(|e| format!("{:#}", e));
+                let _ = waiter.send(result);
+            });
+        }
+        Ok(None) => {
+            let _ = waiter.send(Ok(()));
+        }
+        Err(e) => {
+            let _ = waiter.send(Err(format!("{:#}", anyhow::Error::from(e))));
+        }
+    }
+}
+
+async fn send_changes(
+    client: &aws_sdk_route53::Client,
+    hosted_zone_id: &str,
+    changes: Vec<Change>,
+) -> Result<Option<ChangeInfo>, SdkError<ChangeResourceRecordSetsError, HttpResponse>> {
+    let change_batch = ChangeBatch::builder()
+        .set_changes(Some(changes))
+        .build()
+        .map_err(SdkError::construction_failure)?;
+
+    let resp = client
+        .change_resource_record_sets()
+        .hosted_zone_id(hosted_zone_id)
+        .change_batch(change_batch)
+        .send()
+        .await?;
+
+    Ok(resp.change_info)
+}
+
+/// Polls a change until Route53 reports it INSYNC.
+pub async fn wait_for_insync(client: &aws_sdk_route53::Client, mut change_info: ChangeInfo) -> anyhow::Result<()> {
+    for _ in 0..INSYNC_MAX_POLLS {
+        if change_info.status == ChangeStatus::Insync {
+            return Ok(());
+        }
+
+        tokio::time::sleep(INSYNC_POLL_INTERVAL).await;
+
+        let Some(next_info) = client.get_change().id(&change_info.id).send().await?.change_info else {
+            bail!("GetChange returned no change info for {}", change_info.id);
+        };
+        change_info = next_info;
+    }
+
+    bail!("Timed out waiting for change {} to reach INSYNC", change_info.id)
+}
+
+/// Returns how many ResourceRecord elements and value characters `change` counts for
+/// against the change batch limits. UPSERTs count twice.
+fn change_weight(change: &Change) -> (usize, usize) {
+    let Some(record_set) = &change.resource_record_set else {
+        return (0, 0);
+    };
+
+    let (records, chars) = match &record_set.resource_records {
+        Some(resource_records) if !resource_records.is_empty() => (
+            resource_records.len(),
+            resource_records.iter().map(|r| r.value.len()).sum(),
+        ),
+        // Alias records count as a single record
+        _ => (1, 0),
+    };
+
+    if change.action == ChangeAction::Upsert {
+        (records * 2, chars * 2)
+    } else {
+        (records, chars)
+    }
+}
@@ -1,31 +1,113 @@
 use std::{
-    path::{Path, PathBuf}, sync::Arc, time::Duration
+    collections::HashMap, path::{Path, PathBuf}, sync::Arc, time::Duration
 };
 
 use addr::Route53ResourceAddress;
 use async_trait::async_trait;
 use autoschematic_core::{
     connector::{
-        Connector, ConnectorOutbox, DocIdent, FilterResponse, GetDocResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource, ResourceAddress, SkeletonResponse
+        Connector, ConnectorOutbox, DocIdent, FilterResponse, GetDocResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource, ResourceAddress, SkeletonResponse, TaskExecResponse
     }, diag::DiagnosticResponse, doc_dispatch, skeleton, util::{optional_string_from_utf8, ron_check_eq, ron_check_syntax}
 };
-use resource::{HealthCheck, HostedZone, RecordSet, Route53Resource};
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsConnectorConfig};
+use resource::{
+    Delegation, FailoverEndpoint, FailoverPair, HealthCheck, HealthCheckConfig, HostedZone, KeySigningKey, RecordSet,
+    Route53Resource, RoutingPolicy,
+};
 
-use aws_config::{BehaviorVersion, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+use aws_config::{BehaviorVersion, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
 use aws_sdk_route53::config::Region;
 use tokio::sync::Mutex;
 
+pub mod failover;
 pub mod get;
 pub mod list;
 pub mod op_exec;
 pub mod plan;
+pub mod task_exec;
+pub mod verify_https_frontend;
 
-use crate::{addr, resource::{self, AliasTarget}};
+use crate::{
+    addr,
+    batch::ChangeBatcher,
+    record_format::acm_caa_records,
+    resource::{self, AliasTarget, Coordinates},
+    task::{Route53Task, Route53TaskAddress, VerifyHttpsFrontend},
+};
 
 #[derive(Default)]
 pub struct Route53Connector {
     prefix: PathBuf,
     client: Mutex<Option<aws_sdk_route53::Client>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    change_batcher: ChangeBatcher,
+    // Used by tasks that follow records to the resources they point at
+    elb_client_cache: Mutex<HashMap<String, Arc<aws_sdk_elasticloadbalancingv2::Client>>>,
+    acm_client_cache: Mutex<HashMap<String, Arc<aws_sdk_acm::Client>>>,
+    cloudfront_client: Mutex<Option<Arc<aws_sdk_cloudfront::Client>>>,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl Route53Connector {
+    async fn get_or_init_elb_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_elasticloadbalancingv2::Client>> {
+        let mut cache = self.elb_client_cache.lock().await;
+
+        if let Some(client) = cache.get(region_s) {
+            return Ok(client.clone());
+        }
+
+        let config = load_sdk_config(region_s).await;
+        let client = Arc::new(audited_client!(aws_sdk_elasticloadbalancingv2, &config));
+        cache.insert(region_s.to_string(), client.clone());
+
+        Ok(client)
+    }
+
+    async fn get_or_init_acm_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_acm::Client>> {
+        let mut cache = self.acm_client_cache.lock().await;
+
+        if let Some(client) = cache.get(region_s) {
+            return Ok(client.clone());
+        }
+
+        let config = load_sdk_config(region_s).await;
+        let client = Arc::new(audited_client!(aws_sdk_acm, &config));
+        cache.insert(region_s.to_string(), client.clone());
+
+        Ok(client)
+    }
+
+    async fn get_or_init_cloudfront_client(&self) -> anyhow::Result<Arc<aws_sdk_cloudfront::Client>> {
+        let mut cloudfront_client = self.cloudfront_client.lock().await;
+
+        if let Some(client) = &*cloudfront_client {
+            return Ok(client.clone());
+        }
+
+        // CloudFront is global, but its API is served from us-east-1
+        let config = load_sdk_config("us-east-1").await;
+        let client = Arc::new(audited_client!(aws_sdk_cloudfront, &config));
+        *cloudfront_client = Some(client.clone());
+
+        Ok(client)
+    }
 }
 
 #[async_trait]
@@ -33,6 +115,8 @@ impl Connector for Route53Connector {
     async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
         if let Ok(_addr) = Route53ResourceAddress::from_path(addr) {
             Ok(FilterResponse::Resource)
+        } else if let Ok(_addr) = Route53TaskAddress::from_path(addr) {
+            Ok(FilterResponse::Task)
         } else {
             Ok(FilterResponse::None)
         }
@@ -49,6 +133,8 @@ impl Connector for Route53Connector {
     }
 
     async fn init(&self) -> anyhow::Result<()> {
+        let config_file = AwsConnectorConfig::try_load(&self.prefix)?;
+
         let region = RegionProviderChain::first_try(Region::new("global".to_owned()));
 
         let config = aws_config::defaults(BehaviorVersion::latest())
@@ -64,7 +150,9 @@ impl Connector for Route53Connector {
             .load()
             .await;
 
-        *self.client.lock().await = Some(aws_sdk_route53::Client::new(&config));
+        *self.client.lock().await = Some(audited_client!(aws_sdk_route53, &config));
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(config_file.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
 
         Ok(())
     }
@@ -88,11 +176,26 @@ impl Connector for Route53Connector {
     }
 
     async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
-        self.do_op_exec(addr, op).await
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn task_exec(
+        &self,
+        addr: &Path,
+        body: Vec<u8>,
+        arg: Option<Vec<u8>>,
+        state: Option<Vec<u8>>,
+    ) -> anyhow::Result<TaskExecResponse> {
+        self.do_task_exec(addr, body, arg, state).await
     }
 
     async fn get_docstring(&self, _addr: &Path, ident: DocIdent) -> anyhow::Result<Option<GetDocResponse>> {
-        doc_dispatch!(ident, [AliasTarget, RecordSet])
+        doc_dispatch!(
+            ident,
+            [AliasTarget, RecordSet, Coordinates, FailoverPair, FailoverEndpoint, HealthCheckConfig, KeySigningKey, Delegation]
+        )
     }
 
     async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
@@ -102,7 +205,7 @@ impl Connector for Route53Connector {
         // res.push(skeleton!(Route53ResourceAddress::HealthCheck(String::from("[name]")), Route53Resource::HealthCheck(HealthCheck {})));
         res.push(skeleton!(
             Route53ResourceAddress::HostedZone(String::from("[domain_name]")),
-            Route53Resource::HostedZone(HostedZone {})
+            Route53Resource::HostedZone(HostedZone { dnssec_signing: false })
         ));
         tracing::error!("route53::get_skeletons");
 
@@ -116,10 +219,142 @@ impl Connector for Route53Connector {
                 ttl: Some(600),
                 alias_target: None,
                 resource_records: Some(vec!["record text goes here".into()]),
+                routing_policy: None,
+                health_check_id: None,
             })
         ));
         tracing::error!("route53::get_skeletons");
 
+        // CAA records that let ACM issue certificates, including wildcards, for the domain
+        res.push(skeleton!(
+            Route53ResourceAddress::ResourceRecordSet(
+                String::from("[domain_name]."),
+                String::from("[domain_name]."),
+                String::from("CAA")
+            ),
+            Route53Resource::RecordSet(RecordSet {
+                ttl: Some(3600),
+                alias_target: None,
+                resource_records: Some(acm_caa_records(true)),
+                routing_policy: None,
+                health_check_id: None,
+            })
+        ));
+
+        // An alias record for a CloudFront distribution managed in this repo
+        res.push(skeleton!(
+            Route53ResourceAddress::ResourceRecordSet(
+                String::from("[domain_name]."),
+                String::from("[record_name]."),
+                String::from("A")
+            ),
+            Route53Resource::RecordSet(RecordSet {
+                ttl: None,
+                alias_target: Some(AliasTarget {
+                    hosted_zone_id: String::new(),
+                    dns_name: String::from("aws/cloudfront/distributions/[distribution_id].ron"),
+                    evaluate_target_health: false,
+                }),
+                resource_records: None,
+                routing_policy: None,
+                health_check_id: None,
+            })
+        ));
+
+        // An alias record for a load balancer managed in this repo
+        res.push(skeleton!(
+            Route53ResourceAddress::ResourceRecordSet(
+                String::from("[domain_name]."),
+                String::from("[record_name]."),
+                String::from("A")
+            ),
+            Route53Resource::RecordSet(RecordSet {
+                ttl: None,
+                alias_target: Some(AliasTarget {
+                    hosted_zone_id: String::new(),
+                    dns_name: String::from("aws/elb/[region]/load_balancers/[load_balancer_name].ron"),
+                    evaluate_target_health: true,
+                }),
+                resource_records: None,
+                routing_policy: None,
+                health_check_id: None,
+            })
+        ));
+
+        // One of several weighted record sets at the same name, each with its own set identifier
+        res.push(skeleton!(
+            Route53ResourceAddress::RoutedRecordSet(
+                String::from("[domain_name]."),
+                String::from("[record_name]."),
+                String::from("A"),
+                String::from("[set_identifier]")
+            ),
+            Route53Resource::RecordSet(RecordSet {
+                ttl: Some(60),
+                alias_target: None,
+                resource_records: Some(vec!["[ip_address]".into()]),
+                routing_policy: Some(RoutingPolicy::Weighted { weight: 10 }),
+                health_check_id: None,
+            })
+        ));
+
+        // A primary endpoint that fails over to a secondary when its health check fails
+        res.push(skeleton!(
+            Route53ResourceAddress::FailoverPair(
+                String::from("[domain_name]."),
+                String::from("[record_name]."),
+                String::from("A")
+            ),
+            Route53Resource::FailoverPair(FailoverPair {
+                ttl: Some(60),
+                primary: FailoverEndpoint {
+                    alias_target: None,
+                    resource_records: Some(vec!["[primary_ip]".into()]),
+                    health_check: Some(HealthCheckConfig {
+                        r#type: String::from("HTTPS"),
+                        fqdn: Some(String::from("[record_name]")),
+                        ip_address: Some(String::from("[primary_ip]")),
+                        port: Some(443),
+                        resource_path: Some(String::from("/health")),
+                        search_string: None,
+                        request_interval: Some(30),
+                        failure_threshold: Some(3),
+                    }),
+                },
+                secondary: FailoverEndpoint {
+                    alias_target: None,
+                    resource_records: Some(vec!["[secondary_ip]".into()]),
+                    health_check: None,
+                },
+            })
+        ));
+
+        // A DNSSEC key-signing key. Set dnssec_signing on the hosted zone once it's active.
+        res.push(skeleton!(
+            Route53ResourceAddress::KeySigningKey(String::from("[domain_name]."), String::from("[key_name]")),
+            Route53Resource::KeySigningKey(KeySigningKey {
+                kms_key_arn: String::from("arn:aws:kms:us-east-1:[account_id]:key/[key_id]"),
+                active: true,
+            })
+        ));
+
+        // NS records in the parent zone that follow the name servers of a child zone managed here
+        res.push(skeleton!(
+            Route53ResourceAddress::Delegation(String::from("[domain_name]."), String::from("[subdomain].[domain_name].")),
+            Route53Resource::Delegation(Delegation { ttl: 172800 })
+        ));
+
+        // HTTPS frontend verification task skeleton
+        res.push(skeleton!(
+            Route53TaskAddress::VerifyHttpsFrontend {
+                name: String::from("[task_name]"),
+            },
+            Route53Task::VerifyHttpsFrontend(VerifyHttpsFrontend {
+                dns_name: String::from("[domain_name]"),
+                port:     443,
+            })
+        ));
+
         Ok(res)
     }
 
@@ -128,8 +363,13 @@ impl Connector for Route53Connector {
 
         match addr {
             Route53ResourceAddress::HostedZone(_) => ron_check_eq::<HostedZone>(a, b),
-            Route53ResourceAddress::ResourceRecordSet(_, _, _) => ron_check_eq::<RecordSet>(a, b),
+            Route53ResourceAddress::ResourceRecordSet(_, _, _) | Route53ResourceAddress::RoutedRecordSet(_, _, _, _) => {
+                ron_check_eq::<RecordSet>(a, b)
+            }
             Route53ResourceAddress::HealthCheck(_) => ron_check_eq::<HealthCheck>(a, b),
+            Route53ResourceAddress::FailoverPair(_, _, _) => ron_check_eq::<FailoverPair>(a, b),
+            Route53ResourceAddress::KeySigningKey(_, _) => ron_check_eq::<KeySigningKey>(a, b),
+            Route53ResourceAddress::Delegation(_, _) => ron_check_eq::<Delegation>(a, b),
         }
     }
 
@@ -138,8 +378,13 @@ impl Connector for Route53Connector {
 
         match addr {
             Route53ResourceAddress::HostedZone(_) => ron_check_syntax::<HostedZone>(a),
-            Route53ResourceAddress::ResourceRecordSet(_, _, _) => ron_check_syntax::<RecordSet>(a),
+            Route53ResourceAddress::ResourceRecordSet(_, _, _) | Route53ResourceAddress::RoutedRecordSet(_, _, _, _) => {
+                ron_check_syntax::<RecordSet>(a)
+            }
             Route53ResourceAddress::HealthCheck(_) => ron_check_syntax::<HealthCheck>(a),
+            Route53ResourceAddress::FailoverPair(_, _, _) => ron_check_syntax::<FailoverPair>(a),
+            Route53ResourceAddress::KeySigningKey(_, _) => ron_check_syntax::<KeySigningKey>(a),
+            Route53ResourceAddress::Delegation(_, _) => ron_check_syntax::<Delegation>(a),
         }
     }
 }
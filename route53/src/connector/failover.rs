@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use autoschematic_core::connector::OpExecResponse;
+use aws_sdk_route53::types::{Change, ChangeAction, ResourceRecordSet, Tag, TagResourceType};
+
+use crate::{
+    failover::{
+        endpoint_from_record_set, failover_record_set, from_sdk_health_check_config, health_check_updatable,
+        to_sdk_health_check_config,
+    },
+    op_impl::{find_hosted_zone_id, get_record_set},
+    resource::{FailoverEndpoint, FailoverPair, FailoverRole, HealthCheckConfig},
+};
+
+use super::Route53Connector;
+
+/// The failover pair's records at `name` and `r#type`, as they currently exist.
+/// Failover records with other set identifiers are managed as record sets of their own.
+async fn get_failover_records(
+    client: &aws_sdk_route53::Client,
+    hosted_zone_id: &str,
+    name: &str,
+    r#type: &str,
+) -> anyhow::Result<Vec<ResourceRecordSet>> {
+    let mut records = Vec::new();
+
+    for role in [FailoverRole::Primary, FailoverRole::Secondary] {
+        if let Some(rec) = get_record_set(client, hosted_zone_id, name, r#type, Some(role.set_identifier())).await?
+            && rec.failover.is_some()
+        {
+            records.push(rec);
+        }
+    }
+
+    Ok(records)
+}
+
+fn find_role(records: &[ResourceRecordSet], role: FailoverRole) -> Option<&ResourceRecordSet> {
+    records.iter().find(|rec| rec.failover.as_ref() == Some(&role.to_sdk()))
+}
+
+async fn get_health_check_config(
+    client: &aws_sdk_route53::Client,
+    health_check_id: &str,
+) -> anyhow::Result<HealthCheckConfig> {
+    let resp = client.get_health_check().health_check_id(health_check_id).send().await?;
+    let config = resp
+        .health_check
+        .and_then(|hc| hc.health_check_config)
+        .with_context(|| format!("No config returned for health check {}", health_check_id))?;
+    Ok(from_sdk_health_check_config(&config))
+}
+
+fn health_check_output_key(role: FailoverRole) -> String {
+    format!("{}_health_check_id", role.set_identifier())
+}
+
+impl Route53Connector {
+    /// Reads back both sides of a failover pair. If only one side exists, as after a partly applied
+    /// create, the pair is reported missing so that plan upserts both sides again.
+    pub async fn get_failover_pair(
+        &self,
+        client: &aws_sdk_route53::Client,
+        hosted_zone_id: &str,
+        name: &str,
+        r#type: &str,
+    ) -> anyhow::Result<Option<(FailoverPair, HashMap<String, String>)>> {
+        let records = get_failover_records(client, hosted_zone_id, name, r#type).await?;
+
+        let (Some(primary), Some(secondary)) = (
+            find_role(&records, FailoverRole::Primary),
+            find_role(&records, FailoverRole::Secondary),
+        ) else {
+            return Ok(None);
+        };
+
+        let mut outputs = HashMap::new();
+        let mut endpoints = Vec::new();
+        for (role, record) in [(FailoverRole::Primary, primary), (FailoverRole::Secondary, secondary)] {
+            let mut endpoint = endpoint_from_record_set(record);
+            if let Some(health_check_id) = &record.health_check_id {
+                endpoint.health_check = Some(get_health_check_config(client, health_check_id).await?);
+                outputs.insert(health_check_output_key(role), health_check_id.clone());
+            }
+            endpoints.push(endpoint);
+        }
+
+        let secondary = endpoints.pop().context("Missing secondary endpoint")?;
+        let primary_endpoint = endpoints.pop().context("Missing primary endpoint")?;
+
+        Ok(Some((
+            FailoverPair {
+                ttl: primary.ttl,
+                primary: primary_endpoint,
+                secondary,
+            },
+            outputs,
+        )))
+    }
+
+    /// Creates or updates the health check for one side of the pair, points its record at it,
+    /// and then deletes the health check the record used before, if it's been replaced or removed.
+    pub async fn upsert_failover_endpoint(
+        &self,
+        client: &aws_sdk_route53::Client,
+        hosted_zone_name: &str,
+        name: &str,
+        r#type: &str,
+        role: FailoverRole,
+        ttl: Option<i64>,
+        endpoint: FailoverEndpoint,
+    ) -> anyhow::Result<OpExecResponse> {
+        let hosted_zone_id = find_hosted_zone_id(client, hosted_zone_name).await?;
+
+        let records = get_failover_records(client, &hosted_zone_id, name, r#type).await?;
+        let old_health_check_id = find_role(&records, role).and_then(|rec| rec.health_check_id.clone());
+
+        let new_health_check_id = match (&endpoint.health_check, &old_health_check_id) {
+            (None, _) => None,
+            (Some(desired), Some(old_id)) if health_check_updatable(&get_health_check_config(client, old_id).await?, desired) => {
+                client
+                    .update_health_check()
+                    .health_check_id(old_id)
+                    .set_fully_qualified_domain_name(desired.fqdn.clone())
+                    .set_ip_address(desired.ip_address.clone())
+                    .set_port(desired.port)
+                    .set_resource_path(desired.resource_path.clone())
+                    .set_search_string(desired.search_string.clone())
+                    .set_failure_threshold(desired.failure_threshold)
+                    .send()
+                    .await?;
+                Some(old_id.clone())
+            }
+            (Some(desired), _) => {
+                let resp = client
+                    .create_health_check()
+                    .caller_reference(uuid::Uuid::new_v4().to_string())
+                    .health_check_config(to_sdk_health_check_config(desired)?)
+                    .send()
+                    .await?;
+                let health_check_id = resp.health_check.context("No health check in response")?.id;
+
+                // Health checks have no name of their own; the console shows the Name tag instead
+                client
+                    .change_tags_for_resource()
+                    .resource_type(TagResourceType::Healthcheck)
+                    .resource_id(&health_check_id)
+                    .add_tags(
+                        Tag::builder()
+                            .key("Name")
+                            .value(format!("{} {} {}", name, r#type, role.set_identifier()))
+                            .build(),
+                    )
+                    .send()
+                    .await?;
+
+                Some(health_check_id)
+            }
+        };
+
+        let record_set = failover_record_set(name, r#type, role, ttl, &endpoint, new_health_check_id.clone())?;
+        let change = Change::builder()
+            .action(ChangeAction::Upsert)
+            .resource_record_set(record_set)
+            .build()?;
+        self.change_batcher.submit(client, &hosted_zone_id, change).await?;
+
+        // Only safe once the record no longer refers to it
+        if let Some(old_id) = old_health_check_id
+            && Some(&old_id) != new_health_check_id.as_ref()
+        {
+            client.delete_health_check().health_check_id(old_id).send().await?;
+        }
+
+        Ok(OpExecResponse {
+            outputs: Some(HashMap::from([(health_check_output_key(role), new_health_check_id)])),
+            friendly_message: Some(format!(
+                "Set {} failover {} record at {} in hosted zone {}",
+                role.set_identifier(),
+                r#type,
+                name,
+                hosted_zone_name
+            )),
+        })
+    }
+
+    pub async fn delete_failover_pair(
+        &self,
+        client: &aws_sdk_route53::Client,
+        hosted_zone_name: &str,
+        name: &str,
+        r#type: &str,
+    ) -> anyhow::Result<OpExecResponse> {
+        let hosted_zone_id = find_hosted_zone_id(client, hosted_zone_name).await?;
+
+        let records = get_failover_records(client, &hosted_zone_id, name, r#type).await?;
+        let health_check_ids: Vec<String> = records.iter().filter_map(|rec| rec.health_check_id.clone()).collect();
+
+        // A DELETE has to match the record exactly, so the records are deleted as read
+        for record in records {
+            let change = Change::builder()
+                .action(ChangeAction::Delete)
+                .resource_record_set(record)
+                .build()?;
+            self.change_batcher.submit(client, &hosted_zone_id, change).await?;
+        }
+
+        for health_check_id in health_check_ids {
+            client.delete_health_check().health_check_id(health_check_id).send().await?;
+        }
+
+        Ok(OpExecResponse {
+            outputs: Some(HashMap::from([
+                (health_check_output_key(FailoverRole::Primary), None),
+                (health_check_output_key(FailoverRole::Secondary), None),
+            ])),
+            friendly_message: Some(format!(
+                "Deleted failover {} records at {} in hosted zone {}",
+                r#type, name, hosted_zone_name
+            )),
+        })
+    }
+}
@@ -2,11 +2,14 @@ use std::{collections::HashMap, path::Path};
 
 use anyhow::bail;
 use autoschematic_core::connector::{GetResourceResponse, Resource, ResourceAddress};
-use aws_sdk_route53::types::RrType;
-
 use crate::{
     addr::Route53ResourceAddress,
-    resource::{AliasTarget, HostedZone, RecordSet, Route53Resource},
+    alias_target::alias_target_addresses_by_dns_name,
+    op_impl::{
+        find_key_signing_key, get_dnssec, get_name_servers, get_record_set, key_signing_key_active, key_signing_key_outputs,
+    },
+    resource::{AliasTarget, Delegation, HostedZone, KeySigningKey, RecordSet, Route53Resource, RoutingPolicy},
+    util::normalize_dns_name,
 };
 
 use super::Route53Connector;
@@ -26,7 +29,14 @@ impl Route53Connector {
                     return Ok(None);
                 };
 
-                let hz_config = HostedZone {};
+                let dnssec = get_dnssec(client, &hz.id).await?;
+                let hz_config = HostedZone {
+                    dnssec_signing: dnssec
+                        .as_ref()
+                        .and_then(|dnssec| dnssec.status.as_ref())
+                        .and_then(|status| status.serve_signature.as_deref())
+                        == Some("SIGNING"),
+                };
 
                 let mut outputs = HashMap::new();
                 outputs.insert(String::from("id"), hz.id.clone());
@@ -38,54 +48,162 @@ impl Route53Connector {
                 }))
             }
             Route53ResourceAddress::ResourceRecordSet(hosted_zone, name, r#type) => {
-                let hz = client
-                    .list_hosted_zones_by_name()
-                    // .dns_name(name.strip_suffix('.').unwrap())
-                    .dns_name(hosted_zone.clone())
-                    .send()
-                    .await?;
+                self.get_record_set_resource(client, &hosted_zone, &name, &r#type, None).await
+            }
+            Route53ResourceAddress::RoutedRecordSet(hosted_zone, name, r#type, set_identifier) => {
+                self.get_record_set_resource(client, &hosted_zone, &name, &r#type, Some(&set_identifier))
+                    .await
+            }
+            Route53ResourceAddress::FailoverPair(hosted_zone, name, r#type) => {
+                let hz = client.list_hosted_zones_by_name().dns_name(hosted_zone.clone()).send().await?;
 
                 match hz.hosted_zones.first() {
                     Some(hz) if hz.name == hosted_zone => {
-                        let rr_type = RrType::try_parse(&r#type)?;
-
-                        let rec = client
-                            .list_resource_record_sets()
-                            .set_hosted_zone_id(Some(hz.id.clone()))
-                            .set_start_record_name(Some(name.to_string()))
-                            .start_record_type(rr_type.clone())
-                            .send()
-                            .await?;
-
-                        match rec.resource_record_sets.first() {
-                            Some(rec) if rec.name == *name && rec.r#type == rr_type => {
-                                // let i = rec.region
-                                let record_set = RecordSet {
-                                    ttl: rec.ttl,
-                                    alias_target: rec.alias_target.as_ref().map(|alias_target| AliasTarget {
-                                        dns_name: alias_target.dns_name.clone(),
-                                        hosted_zone_id: alias_target.hosted_zone_id.clone(),
-                                        evaluate_target_health: alias_target.evaluate_target_health,
-                                    }),
-                                    resource_records: rec
-                                        .resource_records
-                                        .as_ref()
-                                        .map(|records| records.iter().map(|r| r.value.clone()).collect()),
-                                };
-
-                                Ok(Some(GetResourceResponse {
-                                    resource_definition: Route53Resource::RecordSet(record_set).to_bytes()?,
-                                    virt_addr: None,
-                                    outputs: None,
-                                }))
+                        let Some((failover_pair, outputs)) = self.get_failover_pair(client, &hz.id, &name, &r#type).await?
+                        else {
+                            return Ok(None);
+                        };
+
+                        Ok(Some(GetResourceResponse {
+                            resource_definition: Route53Resource::FailoverPair(failover_pair).to_bytes()?,
+                            virt_addr: None,
+                            outputs: Some(outputs),
+                        }))
+                    }
+                    _ => Ok(None),
+                }
+            }
+            Route53ResourceAddress::KeySigningKey(hosted_zone, name) => {
+                let hz = client.list_hosted_zones_by_name().dns_name(hosted_zone.clone()).send().await?;
+
+                match hz.hosted_zones.first() {
+                    Some(hz) if hz.name == hosted_zone => {
+                        let Some(key) = find_key_signing_key(client, &hz.id, &name).await? else {
+                            return Ok(None);
+                        };
+
+                        let key_signing_key = KeySigningKey {
+                            kms_key_arn: key.kms_arn.clone().unwrap_or_default(),
+                            active: key_signing_key_active(&key),
+                        };
+
+                        // Outputs Route53 hasn't filled in yet are left out
+                        let outputs = key_signing_key_outputs(&key)
+                            .into_iter()
+                            .filter_map(|(k, v)| Some((k, v?)))
+                            .collect();
+
+                        Ok(Some(GetResourceResponse {
+                            resource_definition: Route53Resource::KeySigningKey(key_signing_key).to_bytes()?,
+                            virt_addr: None,
+                            outputs: Some(outputs),
+                        }))
+                    }
+                    _ => Ok(None),
+                }
+            }
+            Route53ResourceAddress::Delegation(hosted_zone, child_zone) => {
+                let hz = client.list_hosted_zones_by_name().dns_name(hosted_zone.clone()).send().await?;
+
+                match hz.hosted_zones.first() {
+                    Some(hz) if hz.name == hosted_zone => {
+                        let Some(rec) = get_record_set(client, &hz.id, &child_zone, "NS", None).await? else {
+                            return Ok(None);
+                        };
+                        let name_servers: Vec<String> =
+                            rec.resource_records.unwrap_or_default().into_iter().map(|r| r.value).collect();
+
+                        // NS records that don't point at the child zone's current name servers aren't the
+                        // delegation, so they're reported as missing and planned again
+                        let child_hz = client.list_hosted_zones_by_name().dns_name(child_zone.clone()).send().await?;
+                        if let Some(child_hz) = child_hz.hosted_zones.first()
+                            && child_hz.name == child_zone
+                        {
+                            let normalized = |name_servers: &[String]| {
+                                let mut name_servers: Vec<String> = name_servers.iter().map(|ns| normalize_dns_name(ns)).collect();
+                                name_servers.sort();
+                                name_servers
+                            };
+                            let child_name_servers = get_name_servers(client, &child_hz.id).await?;
+                            if normalized(&name_servers) != normalized(&child_name_servers) {
+                                return Ok(None);
                             }
-                            _ => Ok(None),
                         }
+
+                        let mut outputs = HashMap::new();
+                        outputs.insert(String::from("name_servers"), name_servers.join(","));
+
+                        Ok(Some(GetResourceResponse {
+                            resource_definition: Route53Resource::Delegation(Delegation {
+                                ttl: rec.ttl.unwrap_or_default(),
+                            })
+                            .to_bytes()?,
+                            virt_addr: None,
+                            outputs: Some(outputs),
+                        }))
                     }
                     _ => Ok(None),
                 }
             }
+            _ => Ok(None),
+        }
+    }
+
+    /// Reads back the record set at a name and type with the given set identifier, or with none for simple routing.
+    async fn get_record_set_resource(
+        &self,
+        client: &aws_sdk_route53::Client,
+        hosted_zone: &str,
+        name: &str,
+        r#type: &str,
+        set_identifier: Option<&str>,
+    ) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let hz = client
+            .list_hosted_zones_by_name()
+            // .dns_name(name.strip_suffix('.').unwrap())
+            .dns_name(hosted_zone)
+            .send()
+            .await?;
+
+        match hz.hosted_zones.first() {
+            Some(hz) if hz.name == hosted_zone => {
+                let Some(rec) = get_record_set(client, &hz.id, name, r#type, set_identifier).await? else {
+                    return Ok(None);
+                };
+
+                let mut alias_target = rec.alias_target.as_ref().map(|alias_target| AliasTarget {
+                    dns_name: alias_target.dns_name.clone(),
+                    hosted_zone_id: alias_target.hosted_zone_id.clone(),
+                    evaluate_target_health: alias_target.evaluate_target_health,
+                });
+
+                // Report aliases of distributions and load balancers managed here by address,
+                // as they're written
+                if let Some(alias_target) = &mut alias_target
+                    && let Some(addr) =
+                        alias_target_addresses_by_dns_name(&self.prefix)?.remove(&normalize_dns_name(&alias_target.dns_name))
+                {
+                    alias_target.dns_name = addr;
+                    alias_target.hosted_zone_id = String::new();
+                }
+
+                let record_set = RecordSet {
+                    ttl: rec.ttl,
+                    alias_target,
+                    resource_records: rec
+                        .resource_records
+                        .as_ref()
+                        .map(|records| records.iter().map(|r| r.value.clone()).collect()),
+                    routing_policy: RoutingPolicy::from_record_set(&rec),
+                    health_check_id: rec.health_check_id.clone(),
+                };
 
+                Ok(Some(GetResourceResponse {
+                    resource_definition: Route53Resource::RecordSet(record_set).to_bytes()?,
+                    virt_addr: None,
+                    outputs: None,
+                }))
+            }
             _ => Ok(None),
         }
     }
@@ -5,6 +5,8 @@ use autoschematic_core::connector::ResourceAddress;
 
 use crate::{
     addr::Route53ResourceAddress,
+    op_impl::get_dnssec,
+    resource::FailoverRole,
     util::{list_hosted_zones, list_resource_record_sets},
 };
 
@@ -18,14 +20,43 @@ impl Route53Connector {
         };
 
         let hosted_zones = list_hosted_zones(client).await?;
+        let zone_names: Vec<String> = hosted_zones.iter().map(|(_, name)| name.clone()).collect();
         for (id, name) in hosted_zones {
             results.push(Route53ResourceAddress::HostedZone(name.clone()).to_path_buf());
 
+            if let Some(dnssec) = get_dnssec(client, &id).await? {
+                for key in dnssec.key_signing_keys {
+                    if let Some(key_name) = key.name {
+                        results.push(Route53ResourceAddress::KeySigningKey(name.clone(), key_name).to_path_buf());
+                    }
+                }
+            }
+
             let record_sets = list_resource_record_sets(client, &id).await?;
-            for (record_name, r#type) in record_sets {
-                results.push(
-                    Route53ResourceAddress::ResourceRecordSet(name.clone(), record_name.clone(), r#type.clone()).to_path_buf(),
-                );
+            for (record_name, r#type, set_identifier, is_failover) in record_sets {
+                let in_failover_pair =
+                    is_failover && set_identifier.as_deref().and_then(FailoverRole::from_set_identifier).is_some();
+
+                match set_identifier {
+                    Some(_) if in_failover_pair => {
+                        // Both sides of a failover pair live at the same address
+                        let addr = Route53ResourceAddress::FailoverPair(name.clone(), record_name.clone(), r#type.clone()).to_path_buf();
+                        if !results.contains(&addr) {
+                            results.push(addr);
+                        }
+                    }
+                    Some(set_identifier) => results.push(
+                        Route53ResourceAddress::RoutedRecordSet(name.clone(), record_name.clone(), r#type.clone(), set_identifier)
+                            .to_path_buf(),
+                    ),
+                    // NS records at the name of another zone in the account delegate to it
+                    None if r#type == "NS" && record_name != name && zone_names.contains(&record_name) => {
+                        results.push(Route53ResourceAddress::Delegation(name.clone(), record_name.clone()).to_path_buf())
+                    }
+                    None => results.push(
+                        Route53ResourceAddress::ResourceRecordSet(name.clone(), record_name.clone(), r#type.clone()).to_path_buf(),
+                    ),
+                }
             }
         }
 
@@ -1,13 +1,23 @@
 use std::path::Path;
 
-use anyhow::bail;
+use anyhow::{Context, bail};
 use autoschematic_core::{
     connector::{ConnectorOp, OpExecResponse, ResourceAddress},
+    error_util::invalid_op,
     op_exec_output,
 };
-use aws_sdk_route53::types::{AliasTarget, Change, ChangeBatch, RrType};
-
-use crate::{addr::Route53ResourceAddress, op::Route53ConnectorOp};
+use aws_sdk_route53::types::ChangeAction;
+
+use crate::{
+    addr::Route53ResourceAddress,
+    alias_target::{AliasTargetAddress, CLOUDFRONT_HOSTED_ZONE_ID},
+    op::Route53ConnectorOp,
+    op_impl::{
+        create_hosted_zone, create_key_signing_key, delete_delegation, delete_hosted_zone, delete_key_signing_key,
+        delete_record_set, disable_hosted_zone_dnssec, enable_hosted_zone_dnssec, find_hosted_zone_id, record_set_change, set_key_signing_key_active, upsert_delegation,
+    },
+    resource::RecordSet,
+};
 
 use super::Route53Connector;
 
@@ -16,133 +26,125 @@ impl Route53Connector {
         let addr = Route53ResourceAddress::from_path(addr)?;
         let op = Route53ConnectorOp::from_str(op)?;
 
-        let Some(ref client) = *self.client.lock().await else {
+        // Clone the client out rather than holding the lock, so that ops on other
+        // record sets can run (and be batched) concurrently.
+        let Some(client) = self.client.lock().await.clone() else {
             bail!("No client")
         };
 
-        match addr {
-            Route53ResourceAddress::ResourceRecordSet(hosted_zone_name, record_set_name, r#type) => {
+        match &addr {
+            Route53ResourceAddress::ResourceRecordSet(hosted_zone_name, record_set_name, r#type)
+            | Route53ResourceAddress::RoutedRecordSet(hosted_zone_name, record_set_name, r#type, _) => {
+                let set_identifier = match &addr {
+                    Route53ResourceAddress::RoutedRecordSet(_, _, _, set_identifier) => Some(set_identifier.as_str()),
+                    _ => None,
+                };
+
                 match op {
                     Route53ConnectorOp::CreateResourceRecordSet(record_set) => {
-                        let hz = client
-                            .list_hosted_zones_by_name()
-                            .dns_name(hosted_zone_name.clone())
-                            .send()
-                            .await?;
-
-                        let mut record_set_builder = aws_sdk_route53::types::ResourceRecordSet::builder()
-                            .name(record_set_name)
-                            .r#type(RrType::try_parse(&r#type)?);
-
-                        if let Some(ttl) = record_set.ttl {
-                            record_set_builder = record_set_builder.ttl(ttl);
-                        }
-
-                        if let Some(resource_records) = record_set.resource_records {
-                            for rec in resource_records {
-                                let resource_record_builder = aws_sdk_route53::types::ResourceRecord::builder().value(rec);
-                                record_set_builder = record_set_builder.resource_records(resource_record_builder.build()?);
-                            }
-                        }
-
-                        if let Some(alias_target) = record_set.alias_target {
-                            let alias_target_builder = AliasTarget::builder()
-                                .dns_name(alias_target.dns_name)
-                                .hosted_zone_id(alias_target.hosted_zone_id)
-                                .evaluate_target_health(alias_target.evaluate_target_health);
-                            record_set_builder = record_set_builder.alias_target(alias_target_builder.build()?);
-                        }
-
-                        match hz.hosted_zones.first() {
-                            Some(hz) if hz.name == hosted_zone_name => {
-                                client
-                                    .change_resource_record_sets()
-                                    .hosted_zone_id(hz.id.clone())
-                                    .change_batch(
-                                        ChangeBatch::builder()
-                                            .changes(
-                                                Change::builder()
-                                                    .action(aws_sdk_route53::types::ChangeAction::Create)
-                                                    .resource_record_set(record_set_builder.build()?)
-                                                    .build()?,
-                                            )
-                                            .build()?,
-                                    )
-                                    .send()
-                                    .await?;
-                            }
-                            _ => {
-                                bail!("Hosted zone {} not found!", hosted_zone_name)
-                            }
-                        }
-                        op_exec_output!(format!("Created {} Record on Hosted Zone {}", r#type, hosted_zone_name))
-                        // Ok(OpExecOutput {
-                        //     outputs: Some(HashMap::new()),
-                        //     friendly_message: Some(format!(
-                        //         "Created {} Record on Hosted Zone {}",
-                        //         r#type, hosted_zone_name
-                        //     )),
-                        // })
+                        let record_set = self.resolve_alias_target(record_set).await?;
+                        let hosted_zone_id = find_hosted_zone_id(&client, hosted_zone_name).await?;
+                        let change = record_set_change(ChangeAction::Create, record_set_name, r#type, set_identifier, record_set)?;
+                        self.change_batcher.submit(&client, &hosted_zone_id, change).await?;
+
+                        op_exec_output!(format!(
+                            "Created {} Record {} on Hosted Zone {}",
+                            r#type, record_set_name, hosted_zone_name
+                        ))
                     }
-                    Route53ConnectorOp::DeleteResourceRecordSet(record_set) => {
-                        let hz = client
-                            .list_hosted_zones_by_name()
-                            .dns_name(hosted_zone_name.clone())
-                            .send()
-                            .await?;
-
-                        let mut record_set_builder = aws_sdk_route53::types::ResourceRecordSet::builder()
-                            .name(record_set_name)
-                            .r#type(RrType::try_parse(&r#type)?);
-
-                        if let Some(ttl) = record_set.ttl {
-                            record_set_builder = record_set_builder.ttl(ttl);
-                        }
-
-                        if let Some(resource_records) = record_set.resource_records {
-                            for rec in resource_records {
-                                let resource_record_builder = aws_sdk_route53::types::ResourceRecord::builder().value(rec);
-                                record_set_builder = record_set_builder.resource_records(resource_record_builder.build()?);
-                            }
-                        }
-
-                        if let Some(alias_target) = record_set.alias_target {
-                            let alias_target_builder = AliasTarget::builder()
-                                .dns_name(alias_target.dns_name)
-                                .hosted_zone_id(alias_target.hosted_zone_id)
-                                .evaluate_target_health(alias_target.evaluate_target_health);
-                            record_set_builder = record_set_builder.alias_target(alias_target_builder.build()?);
-                        }
-
-                        match hz.hosted_zones.first() {
-                            Some(hz) if hz.name == hosted_zone_name => {
-                                client
-                                    .change_resource_record_sets()
-                                    .hosted_zone_id(hz.id.clone())
-                                    .change_batch(
-                                        ChangeBatch::builder()
-                                            .changes(
-                                                Change::builder()
-                                                    .action(aws_sdk_route53::types::ChangeAction::Delete)
-                                                    .resource_record_set(record_set_builder.build()?)
-                                                    .build()?,
-                                            )
-                                            .build()?,
-                                    )
-                                    .send()
-                                    .await?;
-                            }
-                            _ => {
-                                bail!("Hosted zone {} not found!", hosted_zone_name)
-                            }
-                        }
-                        op_exec_output!(format!("Deleted {} Record on Hosted Zone {}", r#type, hosted_zone_name))
+                    Route53ConnectorOp::UpsertResourceRecordSet(record_set) => {
+                        let record_set = self.resolve_alias_target(record_set).await?;
+                        let hosted_zone_id = find_hosted_zone_id(&client, hosted_zone_name).await?;
+                        let change = record_set_change(ChangeAction::Upsert, record_set_name, r#type, set_identifier, record_set)?;
+                        self.change_batcher.submit(&client, &hosted_zone_id, change).await?;
+
+                        op_exec_output!(format!(
+                            "Updated {} Record {} on Hosted Zone {}",
+                            r#type, record_set_name, hosted_zone_name
+                        ))
+                    }
+                    Route53ConnectorOp::DeleteResourceRecordSet => {
+                        delete_record_set(
+                            &client,
+                            &self.change_batcher,
+                            hosted_zone_name,
+                            record_set_name,
+                            r#type,
+                            set_identifier,
+                        )
+                        .await
                     }
-                    _ => todo!(),
+                    _ => Err(invalid_op(&addr, &op)),
                 }
             }
-            Route53ResourceAddress::HostedZone(_) => todo!(),
-            Route53ResourceAddress::HealthCheck(_) => todo!(),
+            Route53ResourceAddress::FailoverPair(hosted_zone_name, name, r#type) => match op {
+                Route53ConnectorOp::UpsertFailoverEndpoint { role, ttl, endpoint } => {
+                    self.upsert_failover_endpoint(&client, hosted_zone_name, name, r#type, role, ttl, endpoint)
+                        .await
+                }
+                Route53ConnectorOp::DeleteFailoverPair => {
+                    self.delete_failover_pair(&client, hosted_zone_name, name, r#type).await
+                }
+                _ => Err(invalid_op(&addr, &op)),
+            },
+            Route53ResourceAddress::HostedZone(hosted_zone_name) => match op {
+                Route53ConnectorOp::CreateHostedZone(_) => create_hosted_zone(&client, hosted_zone_name).await,
+                Route53ConnectorOp::DeleteHostedZone => delete_hosted_zone(&client, hosted_zone_name).await,
+                Route53ConnectorOp::EnableHostedZoneDnssec => enable_hosted_zone_dnssec(&client, hosted_zone_name).await,
+                Route53ConnectorOp::DisableHostedZoneDnssec => disable_hosted_zone_dnssec(&client, hosted_zone_name).await,
+                _ => Err(invalid_op(&addr, &op)),
+            },
+            Route53ResourceAddress::KeySigningKey(hosted_zone_name, name) => match op {
+                Route53ConnectorOp::CreateKeySigningKey(key_signing_key) => {
+                    create_key_signing_key(&client, hosted_zone_name, name, &key_signing_key).await
+                }
+                Route53ConnectorOp::ActivateKeySigningKey => {
+                    set_key_signing_key_active(&client, hosted_zone_name, name, true).await
+                }
+                Route53ConnectorOp::DeactivateKeySigningKey => {
+                    set_key_signing_key_active(&client, hosted_zone_name, name, false).await
+                }
+                Route53ConnectorOp::DeleteKeySigningKey => delete_key_signing_key(&client, hosted_zone_name, name).await,
+                _ => Err(invalid_op(&addr, &op)),
+            },
+            Route53ResourceAddress::Delegation(hosted_zone_name, child_zone_name) => match op {
+                Route53ConnectorOp::UpsertDelegation(delegation) => {
+                    upsert_delegation(&client, &self.change_batcher, hosted_zone_name, child_zone_name, &delegation).await
+                }
+                Route53ConnectorOp::DeleteDelegation => {
+                    delete_delegation(&client, &self.change_batcher, hosted_zone_name, child_zone_name).await
+                }
+                _ => Err(invalid_op(&addr, &op)),
+            },
+            Route53ResourceAddress::HealthCheck(_) => Err(invalid_op(&addr, &op)),
         }
     }
+
+    /// Replaces an alias target that names a CloudFront distribution or load balancer by its address
+    /// with the target's DNS name and hosted zone.
+    async fn resolve_alias_target(&self, mut record_set: RecordSet) -> anyhow::Result<RecordSet> {
+        let Some(alias_target) = &mut record_set.alias_target else {
+            return Ok(record_set);
+        };
+        let Some(target) = AliasTargetAddress::parse(&alias_target.dns_name)? else {
+            return Ok(record_set);
+        };
+
+        let dns_name = target.dns_name(&self.prefix)?;
+        alias_target.hosted_zone_id = match &target {
+            AliasTargetAddress::CloudFrontDistribution(_) => CLOUDFRONT_HOSTED_ZONE_ID.to_string(),
+            AliasTargetAddress::LoadBalancer(addr) => {
+                let elb_client = self.get_or_init_elb_client(&addr.region).await?;
+                let resp = elb_client.describe_load_balancers().names(&addr.name).send().await?;
+                resp.load_balancers()
+                    .first()
+                    .and_then(|lb| lb.canonical_hosted_zone_id())
+                    .with_context(|| format!("Load balancer {} in {} has no canonical hosted zone", addr.name, addr.region))?
+                    .to_string()
+            }
+        };
+        alias_target.dns_name = dns_name;
+
+        Ok(record_set)
+    }
 }
@@ -1,13 +1,206 @@
 use std::path::Path;
 
-use autoschematic_core::{connector::{PlanResponseElement, ResourceAddress}, connector_op, util::RON};
+use anyhow::bail;
+
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values},
+};
 
 use autoschematic_core::connector::ConnectorOp;
 
-use crate::{addr::Route53ResourceAddress, op::Route53ConnectorOp, resource::{HostedZone, RecordSet}};
+use crate::{
+    addr::Route53ResourceAddress,
+    failover::{check_failover_pair, describe_endpoint},
+    op::Route53ConnectorOp,
+    record_format::{acm_caa_records, caa_allows_acm, check_record_set},
+    resource::{Delegation, FailoverPair, FailoverRole, HostedZone, KeySigningKey, RecordSet},
+    routing::check_routing_policy,
+};
 
 use super::Route53Connector;
 
+/// Validates a record set's values, and for CAA record sets that would stop ACM issuing certificates,
+/// returns a note to add to the plan message.
+fn check_record(
+    hosted_zone: &str,
+    name: &str,
+    r#type: &str,
+    set_identifier: Option<&str>,
+    record: &RecordSet,
+) -> anyhow::Result<String> {
+    let caa_records = check_record_set(name, r#type, record)?;
+    check_routing_policy(name, set_identifier, record)?;
+
+    // The zone apex already has SOA and NS records, which a CNAME can't coexist with
+    if r#type == "CNAME" && name == hosted_zone {
+        bail!(
+            "CNAME record {} is at the apex of hosted zone {}; use an alias A or AAAA record instead",
+            name,
+            hosted_zone
+        );
+    }
+
+    if r#type == "CAA" && !caa_allows_acm(&caa_records) {
+        return Ok(format!(
+            "\nNote: these CAA records don't allow ACM to issue or renew certificates for {}. To allow it, add:\n  {}",
+            name,
+            acm_caa_records(false).join("\n  ")
+        ));
+    }
+
+    Ok(String::new())
+}
+
+/// Describes a record set for plan messages, e.g. "A Record at www.example.com. (us-east-1, latency in us-east-1)".
+fn describe_record(name: &str, r#type: &str, set_identifier: Option<&str>, record: Option<&RecordSet>) -> String {
+    let routing_policy = record.and_then(|record| record.routing_policy.as_ref());
+    match (set_identifier, routing_policy) {
+        (Some(set_identifier), Some(routing_policy)) => {
+            format!("{} Record at {} ({}, {})", r#type, name, set_identifier, routing_policy.describe())
+        }
+        (Some(set_identifier), None) => format!("{} Record at {} ({})", r#type, name, set_identifier),
+        (None, _) => format!("{} Record at {}", r#type, name),
+    }
+}
+
+fn plan_record_set(
+    hosted_zone: &str,
+    name: &str,
+    r#type: &str,
+    set_identifier: Option<&str>,
+    current: Option<String>,
+    desired: Option<String>,
+) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+    match (current, desired) {
+        (None, None) => Ok(vec![]),
+        (None, Some(new_record)) => {
+            let new_record: RecordSet = RON.from_str(&new_record)?;
+            let note = check_record(hosted_zone, name, r#type, set_identifier, &new_record)?;
+            let description = describe_record(name, r#type, set_identifier, Some(&new_record));
+            Ok(vec![connector_op!(
+                Route53ConnectorOp::CreateResourceRecordSet(new_record),
+                format!("Create {} in hosted zone {}{}", description, hosted_zone, note)
+            )])
+        }
+        (Some(old_record), None) => {
+            let old_record: RecordSet = RON.from_str(&old_record)?;
+            Ok(vec![connector_op!(
+                Route53ConnectorOp::DeleteResourceRecordSet,
+                format!(
+                    "DELETE {} in hosted zone {}",
+                    describe_record(name, r#type, set_identifier, Some(&old_record)),
+                    hosted_zone
+                )
+            )])
+        }
+        (Some(old_record), Some(new_record)) => {
+            let old_record: RecordSet = RON.from_str(&old_record)?;
+            let new_record: RecordSet = RON.from_str(&new_record)?;
+            if old_record == new_record {
+                return Ok(vec![]);
+            }
+
+            let note = check_record(hosted_zone, name, r#type, set_identifier, &new_record)?;
+
+            // Route53 can't switch a record set to another kind of routing policy in place
+            let policy_kind = |record: &RecordSet| record.routing_policy.as_ref().map(std::mem::discriminant);
+            if policy_kind(&old_record) != policy_kind(&new_record) {
+                bail!(
+                    "{} can't change to {} routing in place. Delete it, and create it again under another set identifier.",
+                    describe_record(name, r#type, set_identifier, Some(&old_record)),
+                    new_record
+                        .routing_policy
+                        .as_ref()
+                        .map(|routing_policy| routing_policy.describe())
+                        .unwrap_or_default()
+                );
+            }
+
+            let diff = diff_ron_values(&old_record, &new_record).unwrap_or_default();
+            let description = describe_record(name, r#type, set_identifier, Some(&new_record));
+            Ok(vec![connector_op!(
+                Route53ConnectorOp::UpsertResourceRecordSet(new_record),
+                format!("Modify {} in hosted zone {}\n{}{}", description, hosted_zone, diff, note)
+            )])
+        }
+    }
+}
+
+fn enable_dnssec_op(hosted_zone: &str) -> PlanResponseElement {
+    connector_op!(
+        Route53ConnectorOp::EnableHostedZoneDnssec,
+        format!(
+            "Enable DNSSEC signing for hosted zone {}. Once it's signed, add the ds_record output of its \
+             key-signing key to the parent zone.",
+            hosted_zone
+        )
+    )
+}
+
+fn check_key_signing_key(name: &str, key_signing_key: &KeySigningKey) -> anyhow::Result<()> {
+    if !(3..=128).contains(&name.len()) || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        bail!(
+            "Key-signing key name {} should be 3 to 128 letters, numbers and underscores",
+            name
+        );
+    }
+
+    // Route53 only signs with KMS keys in us-east-1
+    let arn = &key_signing_key.kms_key_arn;
+    if !arn.starts_with("arn:") || !arn.contains(":kms:us-east-1:") || !arn.contains(":key/") {
+        bail!(
+            "Key-signing key {} has kms_key_arn {}; expected the ARN of a KMS key in us-east-1, \
+             as in arn:aws:kms:us-east-1:<account_id>:key/<key_id>",
+            name,
+            arn
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks that a delegation is for a zone directly below its parent that's also managed here, and that
+/// the NS records it manages aren't also written out as a record set.
+fn check_delegation(prefix: &Path, hosted_zone: &str, child_zone: &str) -> anyhow::Result<()> {
+    if child_zone == hosted_zone || !child_zone.ends_with(&format!(".{}", hosted_zone)) {
+        bail!("Hosted zone {} is not below hosted zone {}, so it can't be delegated from it", child_zone, hosted_zone);
+    }
+
+    let child_zone_addr = Route53ResourceAddress::HostedZone(child_zone.to_string()).to_path_buf();
+    if !prefix.join(&child_zone_addr).is_file() {
+        bail!(
+            "Hosted zone {} isn't managed here, so its name servers can't be looked up. Add it at {} first.",
+            child_zone,
+            child_zone_addr.display()
+        );
+    }
+
+    let ns_record_addr =
+        Route53ResourceAddress::ResourceRecordSet(hosted_zone.to_string(), child_zone.to_string(), String::from("NS")).to_path_buf();
+    if prefix.join(&ns_record_addr).is_file() {
+        bail!(
+            "The NS records delegating {} are also written at {}. Remove that file; the delegation manages them.",
+            child_zone,
+            ns_record_addr.display()
+        );
+    }
+
+    Ok(())
+}
+
+fn parse_failover_pair(name: &str, s: &str) -> anyhow::Result<FailoverPair> {
+    let pair: FailoverPair = RON.from_str(s)?;
+
+    let problems = check_failover_pair(&pair);
+    if !problems.is_empty() {
+        bail!("Invalid failover pair at {}:\n  {}", name, problems.join("\n  "));
+    }
+
+    Ok(pair)
+}
+
 
 impl Route53Connector {
     pub async fn do_plan(
@@ -23,10 +216,19 @@ impl Route53Connector {
             Route53ResourceAddress::HostedZone(name) => match (current, desired) {
                 (None, None) => Ok(vec![]),
                 (None, Some(new_zone)) => {
-                    let new_zone: HostedZone = RON.from_str(&new_zone).unwrap();
+                    let new_zone: HostedZone = RON.from_str(&new_zone)?;
+
+                    // Signing needs an active key-signing key, which can only be created once the zone exists,
+                    // so it's enabled by a later plan rather than alongside the create.
+                    let note = if new_zone.dnssec_signing {
+                        "\nDNSSEC signing will be enabled by the next plan, once the zone has an active key-signing key."
+                    } else {
+                        ""
+                    };
+
                     Ok(vec![connector_op!(
                         Route53ConnectorOp::CreateHostedZone(new_zone),
-                        format!("Create new hosted zone {}", name)
+                        format!("Create new hosted zone {}{}", name, note)
                     )])
                 }
                 (Some(old_zone), None) => {
@@ -37,59 +239,167 @@ impl Route53Connector {
                     )])
                 }
                 (Some(old_zone), Some(new_zone)) => {
-                    let old_zone: HostedZone = RON.from_str(&old_zone).unwrap();
-                    let new_zone: HostedZone = RON.from_str(&new_zone).unwrap();
-                    //  TODO can we put a nice diff here?
-                    Ok(vec![connector_op!(
-                        Route53ConnectorOp::ModifyHostedZone(old_zone, new_zone),
-                        format!("MODIFY hosted zone {}", name)
-                    )])
+                    let old_zone: HostedZone = RON.from_str(&old_zone)?;
+                    let new_zone: HostedZone = RON.from_str(&new_zone)?;
+
+                    let mut res = Vec::new();
+                    match (old_zone.dnssec_signing, new_zone.dnssec_signing) {
+                        (false, true) => res.push(enable_dnssec_op(&name)),
+                        (true, false) => res.push(connector_op!(
+                            Route53ConnectorOp::DisableHostedZoneDnssec,
+                            format!(
+                                "DISABLE DNSSEC signing for hosted zone {}. Remove its DS records from the parent zone first, \
+                                 or resolvers that validate DNSSEC will stop resolving it.",
+                                name
+                            )
+                        )),
+                        _ => {}
+                    }
+
+                    Ok(res)
                 }
             },
             Route53ResourceAddress::ResourceRecordSet(hosted_zone, name, r#type) => {
+                plan_record_set(&hosted_zone, &name, &r#type, None, current, desired)
+            }
+            Route53ResourceAddress::RoutedRecordSet(hosted_zone, name, r#type, set_identifier) => {
+                plan_record_set(&hosted_zone, &name, &r#type, Some(&set_identifier), current, desired)
+            }
+            Route53ResourceAddress::KeySigningKey(hosted_zone, name) => {
+                let current: Option<KeySigningKey> = current.map(|s| RON.from_str(&s)).transpose()?;
+                let desired: Option<KeySigningKey> = desired.map(|s| RON.from_str(&s)).transpose()?;
+
                 match (current, desired) {
                     (None, None) => Ok(vec![]),
-                    (None, Some(new_record)) => {
-                        let new_record: RecordSet = RON.from_str(&new_record)?;
+                    (None, Some(new_key)) => {
+                        check_key_signing_key(&name, &new_key)?;
                         Ok(vec![connector_op!(
-                            Route53ConnectorOp::CreateResourceRecordSet(new_record),
-                            format!(
-                                "Create {} Record at {} in hosted zone {}",
-                                r#type, name, hosted_zone
-                            )
+                            Route53ConnectorOp::CreateKeySigningKey(new_key),
+                            format!("Create key-signing key {} for hosted zone {}", name, hosted_zone)
                         )])
                     }
-                    (Some(old_record), None) => {
-                        let old_record: RecordSet = RON.from_str(&old_record)?;
+                    (Some(_old_key), None) => Ok(vec![connector_op!(
+                        Route53ConnectorOp::DeleteKeySigningKey,
+                        format!(
+                            "DELETE key-signing key {} for hosted zone {}. If it's the zone's last active key, \
+                             disable DNSSEC signing first, and remove its DS record from the parent zone.",
+                            name, hosted_zone
+                        )
+                    )]),
+                    (Some(old_key), Some(new_key)) => {
+                        check_key_signing_key(&name, &new_key)?;
+
+                        if old_key.kms_key_arn != new_key.kms_key_arn {
+                            bail!(
+                                "Key-signing key {} can't change kms_key_arn after creation. \
+                                 Create a new key under another name, activate it, and then delete this one.",
+                                name
+                            );
+                        }
+
+                        match (old_key.active, new_key.active) {
+                            (false, true) => Ok(vec![connector_op!(
+                                Route53ConnectorOp::ActivateKeySigningKey,
+                                format!("Activate key-signing key {} for hosted zone {}", name, hosted_zone)
+                            )]),
+                            (true, false) => Ok(vec![connector_op!(
+                                Route53ConnectorOp::DeactivateKeySigningKey,
+                                format!(
+                                    "DEACTIVATE key-signing key {} for hosted zone {}. Its DS record should already be gone \
+                                     from the parent zone.",
+                                    name, hosted_zone
+                                )
+                            )]),
+                            _ => Ok(vec![]),
+                        }
+                    }
+                }
+            }
+            Route53ResourceAddress::FailoverPair(hosted_zone, name, r#type) => {
+                // Only the desired pair is checked, so that a pair that's invalid as it exists can still be fixed or deleted
+                let current: Option<FailoverPair> = current.map(|s| RON.from_str(&s)).transpose()?;
+                let desired = desired.map(|s| parse_failover_pair(&name, &s)).transpose()?;
+
+                match (current, desired) {
+                    (None, None) => Ok(vec![]),
+                    (Some(_), None) => Ok(vec![connector_op!(
+                        Route53ConnectorOp::DeleteFailoverPair,
+                        format!(
+                            "DELETE failover {} records at {} in hosted zone {}, and their health checks",
+                            r#type, name, hosted_zone
+                        )
+                    )]),
+                    (current, Some(desired)) => {
+                        let mut res = Vec::new();
+
+                        // The secondary goes first, so that there's somewhere to fail over to
+                        // by the time the primary's health check starts counting
+                        for role in [FailoverRole::Secondary, FailoverRole::Primary] {
+                            let endpoint = desired.endpoint(role);
+                            let unchanged = current
+                                .as_ref()
+                                .is_some_and(|current| current.ttl == desired.ttl && current.endpoint(role) == endpoint);
+                            if unchanged {
+                                continue;
+                            }
+
+                            res.push(connector_op!(
+                                Route53ConnectorOp::UpsertFailoverEndpoint {
+                                    role,
+                                    ttl: desired.ttl,
+                                    endpoint: endpoint.clone(),
+                                },
+                                format!(
+                                    "Set {} failover {} record at {} in hosted zone {} to {}",
+                                    role.set_identifier(),
+                                    r#type,
+                                    name,
+                                    hosted_zone,
+                                    describe_endpoint(endpoint)
+                                )
+                            ));
+                        }
+
+                        Ok(res)
+                    }
+                }
+            }
+            Route53ResourceAddress::Delegation(hosted_zone, child_zone) => {
+                let current: Option<Delegation> = current.map(|s| RON.from_str(&s)).transpose()?;
+                let desired: Option<Delegation> = desired.map(|s| RON.from_str(&s)).transpose()?;
+
+                match (current, desired) {
+                    (None, None) => Ok(vec![]),
+                    (None, Some(new_delegation)) => {
+                        check_delegation(&self.prefix, &hosted_zone, &child_zone)?;
                         Ok(vec![connector_op!(
-                            Route53ConnectorOp::DeleteResourceRecordSet(old_record),
+                            Route53ConnectorOp::UpsertDelegation(new_delegation),
                             format!(
-                                "DELETE {} Record at {} in hosted zone {}",
-                                r#type, name, hosted_zone
+                                "Point the NS records for {} in hosted zone {} at the name servers of hosted zone {}",
+                                child_zone, hosted_zone, child_zone
                             )
                         )])
                     }
-                    (Some(old_record), Some(new_record)) if old_record != new_record => {
-                        let old_record: RecordSet = RON.from_str(&old_record)?;
-                        let new_record: RecordSet = RON.from_str(&new_record)?;
-                        Ok(vec![
-                            connector_op!(
-                                Route53ConnectorOp::DeleteResourceRecordSet(old_record,),
-                                format!(
-                                    "DELETE {} Record at {} in hosted zone {}",
-                                    r#type, name, hosted_zone
-                                )
-                            ),
-                            connector_op!(
-                                Route53ConnectorOp::CreateResourceRecordSet(new_record,),
-                                format!(
-                                    "Create {} Record at {} in hosted zone {}",
-                                    r#type, name, hosted_zone
-                                )
-                            ),
-                        ])
+                    (Some(_old_delegation), None) => Ok(vec![connector_op!(
+                        Route53ConnectorOp::DeleteDelegation,
+                        format!(
+                            "DELETE the NS records delegating {} from hosted zone {}. Resolvers will stop finding \
+                             hosted zone {} once the records they've cached expire.",
+                            child_zone, hosted_zone, child_zone
+                        )
+                    )]),
+                    (Some(old_delegation), Some(new_delegation)) => {
+                        if old_delegation == new_delegation {
+                            return Ok(vec![]);
+                        }
+
+                        check_delegation(&self.prefix, &hosted_zone, &child_zone)?;
+                        let diff = diff_ron_values(&old_delegation, &new_delegation).unwrap_or_default();
+                        Ok(vec![connector_op!(
+                            Route53ConnectorOp::UpsertDelegation(new_delegation),
+                            format!("Modify the delegation of {} from hosted zone {}\n{}", child_zone, hosted_zone, diff)
+                        )])
                     }
-                    _ => Ok(vec![]),
                 }
             }
             _ => Ok(vec![]),
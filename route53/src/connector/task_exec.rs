@@ -0,0 +1,24 @@
+use std::path::Path;
+
+use autoschematic_core::connector::{Resource, ResourceAddress, TaskExecResponse};
+
+use crate::task::{Route53Task, Route53TaskAddress};
+
+use super::Route53Connector;
+
+impl Route53Connector {
+    pub async fn do_task_exec(
+        &self,
+        addr: &Path,
+        body: Vec<u8>,
+        _arg: Option<Vec<u8>>,
+        _state: Option<Vec<u8>>,
+    ) -> anyhow::Result<TaskExecResponse> {
+        let addr = Route53TaskAddress::from_path(addr)?;
+
+        let task = Route53Task::from_bytes(&addr, &body)?;
+        match task {
+            Route53Task::VerifyHttpsFrontend(verify) => self.do_verify_https_frontend_task(verify).await,
+        }
+    }
+}
@@ -0,0 +1,691 @@
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::bail;
+use autoschematic_core::connector::TaskExecResponse;
+use aws_sdk_acm::types::CertificateStatus;
+use aws_sdk_elasticloadbalancingv2::types::{Action, ActionTypeEnum, LoadBalancerStateEnum, ProtocolEnum, TargetHealthStateEnum};
+use aws_sdk_route53::types::RrType;
+
+use crate::{
+    task::VerifyHttpsFrontend,
+    util::{list_hosted_zones, normalize_dns_name},
+};
+
+use super::Route53Connector;
+
+/// Whether `pattern`, in which `*` matches any run of characters and `?` any single character,
+/// matches `name`. Used for alternate domain names and host header conditions.
+fn dns_pattern_matches(pattern: &str, name: &str) -> bool {
+    fn matches(pattern: &[u8], name: &[u8]) -> bool {
+        match (pattern.first(), name.first()) {
+            (None, None) => true,
+            (Some(b'*'), _) => matches(&pattern[1..], name) || (!name.is_empty() && matches(pattern, &name[1..])),
+            (Some(b'?'), Some(_)) => matches(&pattern[1..], &name[1..]),
+            (Some(p), Some(n)) if p == n => matches(&pattern[1..], &name[1..]),
+            _ => false,
+        }
+    }
+
+    matches(normalize_dns_name(pattern).as_bytes(), name.as_bytes())
+}
+
+/// Whether a certificate name, which may be a wildcard for a single leftmost label, covers `dns_name`.
+fn certificate_name_covers(cert_name: &str, dns_name: &str) -> bool {
+    let cert_name = normalize_dns_name(cert_name);
+    match cert_name.strip_prefix("*.") {
+        Some(parent) => dns_name.split_once('.').is_some_and(|(_, rest)| rest == parent),
+        None => cert_name == dns_name,
+    }
+}
+
+/// The region of a load balancer from its DNS name, e.g. `my-alb-123.us-east-1.elb.amazonaws.com`.
+fn elb_region(dns_name: &str) -> Option<&str> {
+    let labels: Vec<&str> = dns_name.split('.').collect();
+    let elb_index = labels.iter().position(|label| *label == "elb")?;
+    if elb_index < 2 {
+        return None;
+    }
+    Some(labels[elb_index - 1])
+}
+
+/// The name of a target group, from `arn:aws:elasticloadbalancing:...:targetgroup/<name>/<id>`.
+fn target_group_name(target_group_arn: &str) -> &str {
+    target_group_arn.split('/').nth(1).unwrap_or(target_group_arn)
+}
+
+enum CertificateCheck {
+    Valid,
+    /// The certificate doesn't cover the name. Holds the names it does cover.
+    WrongNames(Vec<String>),
+    Invalid(String),
+}
+
+/// Checks that an ACM certificate is issued, unexpired, and covers `dns_name`.
+async fn check_certificate(
+    client: &aws_sdk_acm::Client,
+    certificate_arn: &str,
+    dns_name: &str,
+) -> anyhow::Result<CertificateCheck> {
+    let resp = client.describe_certificate().certificate_arn(certificate_arn).send().await?;
+    let Some(certificate) = resp.certificate else {
+        return Ok(CertificateCheck::Invalid(format!("certificate {certificate_arn} not found")));
+    };
+
+    let names: Vec<String> = certificate
+        .domain_name()
+        .into_iter()
+        .chain(certificate.subject_alternative_names().iter().map(|name| name.as_str()))
+        .map(|name| name.to_string())
+        .collect();
+    if !names.iter().any(|name| certificate_name_covers(name, dns_name)) {
+        return Ok(CertificateCheck::WrongNames(names));
+    }
+
+    if certificate.status() != Some(&CertificateStatus::Issued) {
+        return Ok(CertificateCheck::Invalid(format!(
+            "certificate {} is {}",
+            certificate_arn,
+            certificate.status().map(|status| status.as_str()).unwrap_or("in an unknown state")
+        )));
+    }
+
+    let now_secs = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    if certificate.not_after().is_some_and(|not_after| not_after.secs() <= now_secs) {
+        return Ok(CertificateCheck::Invalid(format!("certificate {certificate_arn} has expired")));
+    }
+
+    Ok(CertificateCheck::Valid)
+}
+
+/// The target groups that `actions` forward to, or a description of what they do instead.
+fn forwarded_target_groups(actions: &[Action]) -> Result<Vec<String>, String> {
+    let mut actions: Vec<&Action> = actions.iter().collect();
+    actions.sort_by_key(|action| action.order().unwrap_or(i32::MAX));
+
+    // Authentication actions come first and hand over to the next action once the user is signed in
+    let Some(action) = actions.into_iter().find(|action| {
+        !matches!(
+            action.r#type(),
+            Some(ActionTypeEnum::AuthenticateCognito) | Some(ActionTypeEnum::AuthenticateOidc)
+        )
+    }) else {
+        return Err(String::from("has no action besides authentication"));
+    };
+
+    match action.r#type() {
+        Some(ActionTypeEnum::Forward) => {
+            let mut target_groups: Vec<String> = action
+                .forward_config()
+                .map(|forward| {
+                    forward
+                        .target_groups()
+                        .iter()
+                        .filter(|tg| tg.weight().unwrap_or(1) > 0)
+                        .filter_map(|tg| tg.target_group_arn().map(|arn| arn.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if target_groups.is_empty()
+                && let Some(arn) = action.target_group_arn()
+            {
+                target_groups.push(arn.to_string());
+            }
+
+            if target_groups.is_empty() {
+                return Err(String::from("forwards to no target group"));
+            }
+            Ok(target_groups)
+        }
+        Some(ActionTypeEnum::Redirect) => {
+            let redirect = action.redirect_config();
+            Err(format!(
+                "redirects ({} to host {}) instead of forwarding to a target group",
+                redirect
+                    .and_then(|r| r.status_code())
+                    .map(|status_code| status_code.as_str())
+                    .unwrap_or("unknown status"),
+                redirect.and_then(|r| r.host()).unwrap_or("#{host}")
+            ))
+        }
+        Some(ActionTypeEnum::FixedResponse) => Err(format!(
+            "returns a fixed {} response instead of forwarding to a target group",
+            action
+                .fixed_response_config()
+                .and_then(|fixed| fixed.status_code())
+                .unwrap_or("unknown")
+        )),
+        other => Err(format!(
+            "has a {} action instead of forwarding to a target group",
+            other.map(|t| t.as_str()).unwrap_or("unknown")
+        )),
+    }
+}
+
+/// The links of the chain checked so far, in order.
+struct Chain {
+    dns_name: String,
+    checked:  Vec<String>,
+}
+
+impl Chain {
+    fn ok(&mut self, link: String) {
+        self.checked.push(link);
+    }
+
+    /// Ends the chain at its first broken link.
+    fn broken(self, link: &str, problem: String) -> TaskExecResponse {
+        let mut report = vec![format!(
+            "HTTPS frontend {} is broken at the {} link: {}",
+            self.dns_name, link, problem
+        )];
+        report.extend(self.checked.iter().map(|checked| format!("  ok: {checked}")));
+
+        let mut outputs = HashMap::new();
+        outputs.insert(String::from("status"), Some(String::from("broken")));
+        outputs.insert(String::from("broken_link"), Some(link.to_string()));
+
+        TaskExecResponse {
+            outputs: Some(outputs),
+            friendly_message: Some(report.join("\n")),
+            ..Default::default()
+        }
+    }
+
+    fn healthy(self) -> TaskExecResponse {
+        let mut report = vec![format!("HTTPS frontend {} is healthy", self.dns_name)];
+        report.extend(self.checked.iter().map(|checked| format!("  ok: {checked}")));
+
+        let mut outputs = HashMap::new();
+        outputs.insert(String::from("status"), Some(String::from("ok")));
+
+        TaskExecResponse {
+            outputs: Some(outputs),
+            friendly_message: Some(report.join("\n")),
+            ..Default::default()
+        }
+    }
+}
+
+impl Route53Connector {
+    pub async fn do_verify_https_frontend_task(&self, verify: VerifyHttpsFrontend) -> anyhow::Result<TaskExecResponse> {
+        let dns_name = normalize_dns_name(&verify.dns_name);
+        let mut chain = Chain {
+            dns_name: dns_name.clone(),
+            checked:  Vec::new(),
+        };
+
+        let (zone_name, private_zone, target) = {
+            let Some(ref client) = *self.client.lock().await else {
+                bail!("No client")
+            };
+
+            // The most specific hosted zone containing the name
+            let zone = list_hosted_zones(client)
+                .await?
+                .into_iter()
+                .filter(|(_, zone_name)| {
+                    let zone_name = normalize_dns_name(zone_name);
+                    dns_name == zone_name || dns_name.ends_with(&format!(".{zone_name}"))
+                })
+                .max_by_key(|(_, zone_name)| zone_name.len());
+            let Some((zone_id, zone_name)) = zone else {
+                return Ok(chain.broken("record", format!("no hosted zone in this account contains {dns_name}")));
+            };
+            let zone_name = normalize_dns_name(&zone_name);
+
+            let zone = client.get_hosted_zone().id(&zone_id).send().await?;
+            let private_zone = zone
+                .hosted_zone()
+                .and_then(|hz| hz.config())
+                .is_some_and(|config| config.private_zone);
+
+            let records = client
+                .list_resource_record_sets()
+                .hosted_zone_id(&zone_id)
+                .start_record_name(format!("{dns_name}."))
+                .max_items(20)
+                .send()
+                .await?;
+            let records: Vec<_> = records
+                .resource_record_sets
+                .into_iter()
+                .filter(|record| normalize_dns_name(&record.name) == dns_name)
+                .collect();
+
+            let alias = records.iter().find_map(|record| match record.r#type {
+                RrType::A | RrType::Aaaa => record.alias_target.as_ref().map(|alias| (record.r#type.clone(), alias)),
+                _ => None,
+            });
+            let cname = records
+                .iter()
+                .find(|record| record.r#type == RrType::Cname)
+                .and_then(|record| record.resource_records().first());
+
+            let target = if let Some((r#type, alias)) = alias {
+                chain.ok(format!(
+                    "{} record {} in hosted zone {} is an alias for {}",
+                    r#type.as_str(),
+                    dns_name,
+                    zone_name,
+                    alias.dns_name
+                ));
+                normalize_dns_name(&alias.dns_name)
+            } else if let Some(cname) = cname {
+                chain.ok(format!(
+                    "CNAME record {} in hosted zone {} points to {}",
+                    dns_name, zone_name, cname.value
+                ));
+                normalize_dns_name(&cname.value)
+            } else if let Some(record) = records
+                .iter()
+                .find(|record| matches!(record.r#type, RrType::A | RrType::Aaaa))
+            {
+                let values: Vec<&str> = record.resource_records().iter().map(|r| r.value.as_str()).collect();
+                return Ok(chain.broken(
+                    "record",
+                    format!(
+                        "{} record {} holds plain addresses ({}), not an alias for a load balancer or CloudFront distribution",
+                        record.r#type.as_str(),
+                        dns_name,
+                        values.join(", ")
+                    ),
+                ));
+            } else {
+                return Ok(chain.broken(
+                    "record",
+                    format!("hosted zone {zone_name} has no A, AAAA or CNAME record for {dns_name}"),
+                ));
+            };
+
+            (zone_name, private_zone, target)
+        };
+
+        // The record may exist but not be reachable, if the domain isn't delegated to this hosted zone
+        if private_zone {
+            chain.ok(format!(
+                "hosted zone {zone_name} is private, so public resolution wasn't checked"
+            ));
+        } else {
+            match tokio::net::lookup_host((dns_name.as_str(), 443)).await.map(|addrs| addrs.count()) {
+                Ok(count) if count > 0 => chain.ok(format!("{dns_name} resolves")),
+                Ok(_) => {
+                    return Ok(chain.broken(
+                        "resolution",
+                        format!(
+                            "{dns_name} resolves to no addresses; check that the domain's NS records delegate to hosted zone {zone_name}"
+                        ),
+                    ));
+                }
+                Err(e) => {
+                    return Ok(chain.broken(
+                        "resolution",
+                        format!(
+                            "{dns_name} doesn't resolve ({e}); check that the domain's NS records delegate to hosted zone {zone_name}, or wait for a cached negative answer to expire"
+                        ),
+                    ));
+                }
+            }
+        }
+
+        if target.ends_with(".cloudfront.net") {
+            self.verify_cloudfront_frontend(chain, &dns_name, &target).await
+        } else if let Some(region) = elb_region(&target) {
+            self.verify_load_balancer_frontend(chain, &dns_name, region, &target, verify.port)
+                .await
+        } else {
+            Ok(chain.broken(
+                "target",
+                format!("{target} is neither a load balancer nor a CloudFront distribution"),
+            ))
+        }
+    }
+
+    async fn verify_cloudfront_frontend(
+        &self,
+        mut chain: Chain,
+        dns_name: &str,
+        target: &str,
+    ) -> anyhow::Result<TaskExecResponse> {
+        let client = self.get_or_init_cloudfront_client().await?;
+
+        let mut next_marker: Option<String> = None;
+        let distribution = loop {
+            let distributions = client.list_distributions().set_marker(next_marker).send().await?;
+            let Some(distribution_list) = distributions.distribution_list else {
+                break None;
+            };
+
+            if let Some(items) = &distribution_list.items
+                && let Some(dist) = items.iter().find(|dist| normalize_dns_name(&dist.domain_name) == target)
+            {
+                break Some(dist.clone());
+            }
+
+            next_marker = distribution_list.next_marker;
+            if next_marker.is_none() {
+                break None;
+            }
+        };
+
+        let Some(distribution) = distribution else {
+            return Ok(chain.broken(
+                "distribution",
+                format!("no CloudFront distribution in this account has the domain name {target}"),
+            ));
+        };
+
+        if !distribution.enabled {
+            return Ok(chain.broken("distribution", format!("distribution {} is disabled", distribution.id)));
+        }
+
+        let aliases = distribution.aliases().map(|aliases| aliases.items()).unwrap_or_default();
+        if !aliases.iter().any(|alias| dns_pattern_matches(alias, dns_name)) {
+            return Ok(chain.broken(
+                "distribution",
+                format!(
+                    "distribution {} doesn't list {} as an alternate domain name, so CloudFront refuses requests for it",
+                    distribution.id, dns_name
+                ),
+            ));
+        }
+
+        if distribution.status == "Deployed" {
+            chain.ok(format!("distribution {} serves {}", distribution.id, dns_name));
+        } else {
+            chain.ok(format!(
+                "distribution {} serves {} (status {}, changes are still deploying)",
+                distribution.id, dns_name, distribution.status
+            ));
+        }
+
+        let viewer_certificate = distribution.viewer_certificate();
+        if let Some(certificate_arn) = viewer_certificate.and_then(|vc| vc.acm_certificate_arn()) {
+            // Certificates for CloudFront always live in us-east-1
+            let acm_client = self.get_or_init_acm_client("us-east-1").await?;
+            match check_certificate(&acm_client, certificate_arn, dns_name).await? {
+                CertificateCheck::Valid => chain.ok(format!("certificate {certificate_arn} is valid for {dns_name}")),
+                CertificateCheck::WrongNames(names) => {
+                    return Ok(chain.broken(
+                        "certificate",
+                        format!(
+                            "certificate {} covers {}, not {}",
+                            certificate_arn,
+                            names.join(", "),
+                            dns_name
+                        ),
+                    ));
+                }
+                CertificateCheck::Invalid(problem) => return Ok(chain.broken("certificate", problem)),
+            }
+        } else if let Some(iam_certificate_id) = viewer_certificate.and_then(|vc| vc.iam_certificate_id()) {
+            chain.ok(format!("IAM server certificate {iam_certificate_id} wasn't checked"));
+        } else {
+            return Ok(chain.broken(
+                "certificate",
+                format!(
+                    "distribution {} serves the default *.cloudfront.net certificate, which doesn't cover {}",
+                    distribution.id, dns_name
+                ),
+            ));
+        }
+
+        chain.ok(String::from("CloudFront origins weren't checked"));
+        Ok(chain.healthy())
+    }
+
+    async fn verify_load_balancer_frontend(
+        &self,
+        mut chain: Chain,
+        dns_name: &str,
+        region: &str,
+        target: &str,
+        port: i32,
+    ) -> anyhow::Result<TaskExecResponse> {
+        let client = self.get_or_init_elb_client(region).await?;
+
+        let mut marker: Option<String> = None;
+        let load_balancer = loop {
+            let resp = client.describe_load_balancers().set_marker(marker).send().await?;
+            if let Some(lb) = resp
+                .load_balancers()
+                .iter()
+                .find(|lb| lb.dns_name().is_some_and(|lb_dns_name| normalize_dns_name(lb_dns_name) == target))
+            {
+                break Some(lb.clone());
+            }
+
+            marker = resp.next_marker;
+            if marker.is_none() {
+                break None;
+            }
+        };
+
+        let Some(load_balancer) = load_balancer else {
+            return Ok(chain.broken(
+                "load_balancer",
+                format!("no load balancer in {region} has the DNS name {target}"),
+            ));
+        };
+        let lb_name = load_balancer.load_balancer_name().unwrap_or(target);
+
+        let state = load_balancer.state().and_then(|state| state.code());
+        if state != Some(&LoadBalancerStateEnum::Active) {
+            return Ok(chain.broken(
+                "load_balancer",
+                format!(
+                    "load balancer {} is {}",
+                    lb_name,
+                    state.map(|state| state.as_str()).unwrap_or("in an unknown state")
+                ),
+            ));
+        }
+        chain.ok(format!("load balancer {lb_name} in {region} is active"));
+
+        let listeners = client
+            .describe_listeners()
+            .set_load_balancer_arn(load_balancer.load_balancer_arn.clone())
+            .send()
+            .await?;
+        let Some(listener) = listeners.listeners().iter().find(|listener| listener.port() == Some(port)) else {
+            return Ok(chain.broken(
+                "listener",
+                format!("load balancer {lb_name} has no listener on port {port}"),
+            ));
+        };
+        let Some(listener_arn) = listener.listener_arn() else {
+            bail!("Listener on port {} of load balancer {} has no ARN", port, lb_name);
+        };
+
+        let protocol = listener.protocol();
+        if !matches!(protocol, Some(ProtocolEnum::Https) | Some(ProtocolEnum::Tls)) {
+            return Ok(chain.broken(
+                "listener",
+                format!(
+                    "listener on port {} of load balancer {} uses {}, not HTTPS",
+                    port,
+                    lb_name,
+                    protocol.map(|protocol| protocol.as_str()).unwrap_or("an unknown protocol")
+                ),
+            ));
+        }
+
+        // The listener serves whichever of its certificates matches the name requested by SNI
+        let mut certificate_arns = Vec::new();
+        let mut marker: Option<String> = None;
+        loop {
+            let resp = client
+                .describe_listener_certificates()
+                .listener_arn(listener_arn)
+                .set_marker(marker)
+                .send()
+                .await?;
+            certificate_arns.extend(
+                resp.certificates()
+                    .iter()
+                    .filter_map(|certificate| certificate.certificate_arn().map(|arn| arn.to_string())),
+            );
+
+            marker = resp.next_marker;
+            if marker.is_none() {
+                break;
+            }
+        }
+
+        let acm_client = self.get_or_init_acm_client(region).await?;
+        let mut certificate_problem = None;
+        let mut has_valid_certificate = false;
+        let mut has_unchecked_certificate = false;
+        for certificate_arn in &certificate_arns {
+            if !certificate_arn.contains(":acm:") {
+                has_unchecked_certificate = true;
+                continue;
+            }
+
+            match check_certificate(&acm_client, certificate_arn, dns_name).await? {
+                CertificateCheck::Valid => {
+                    chain.ok(format!("certificate {certificate_arn} is valid for {dns_name}"));
+                    has_valid_certificate = true;
+                    break;
+                }
+                CertificateCheck::WrongNames(_) => {}
+                CertificateCheck::Invalid(problem) => {
+                    certificate_problem.get_or_insert(problem);
+                }
+            }
+        }
+
+        if !has_valid_certificate {
+            if let Some(problem) = certificate_problem {
+                return Ok(chain.broken("certificate", problem));
+            } else if has_unchecked_certificate {
+                chain.ok(String::from("listener IAM server certificates weren't checked"));
+            } else {
+                return Ok(chain.broken(
+                    "certificate",
+                    format!(
+                        "none of the {} certificates on the listener on port {} covers {}",
+                        certificate_arns.len(),
+                        port,
+                        dns_name
+                    ),
+                ));
+            }
+        }
+
+        // The first rule, by priority, that matches on the host alone decides where the name is routed.
+        // Rules with other conditions only apply to some requests, so they're skipped.
+        let mut actions = listener.default_actions().to_vec();
+        let mut routed_by = String::from("the default action");
+        if protocol == Some(&ProtocolEnum::Https) {
+            let rules = client.describe_rules().listener_arn(listener_arn).send().await?;
+            let mut rules: Vec<_> = rules
+                .rules()
+                .iter()
+                .filter(|rule| !rule.is_default().unwrap_or(false))
+                .filter_map(|rule| rule.priority().and_then(|p| p.parse::<i32>().ok()).map(|p| (p, rule)))
+                .collect();
+            rules.sort_by_key(|(priority, _)| *priority);
+
+            let matching_rule = rules.into_iter().find(|(_, rule)| {
+                !rule.conditions().is_empty()
+                    && rule.conditions().iter().all(|condition| {
+                        condition.field() == Some("host-header") && {
+                            let values = match condition.host_header_config() {
+                                Some(config) if !config.values().is_empty() => config.values(),
+                                _ => condition.values(),
+                            };
+                            values.iter().any(|value| dns_pattern_matches(value, dns_name))
+                        }
+                    })
+            });
+
+            if let Some((priority, rule)) = matching_rule {
+                actions = rule.actions().to_vec();
+                routed_by = format!("rule {priority}");
+            }
+        }
+
+        let target_group_arns = match forwarded_target_groups(&actions) {
+            Ok(target_group_arns) => target_group_arns,
+            Err(problem) => {
+                return Ok(chain.broken(
+                    "listener",
+                    format!("for {}, {} of the listener on port {} {}", dns_name, routed_by, port, problem),
+                ));
+            }
+        };
+        chain.ok(format!(
+            "{} of the listener on port {} forwards {} to {}",
+            routed_by,
+            port,
+            dns_name,
+            target_group_arns
+                .iter()
+                .map(|arn| target_group_name(arn))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+
+        for target_group_arn in &target_group_arns {
+            let tg_name = target_group_name(target_group_arn);
+            let health = client
+                .describe_target_health()
+                .target_group_arn(target_group_arn)
+                .send()
+                .await?;
+            let descriptions = health.target_health_descriptions();
+
+            if descriptions.is_empty() {
+                return Ok(chain.broken(
+                    "target_group",
+                    format!("target group {tg_name} has no registered targets"),
+                ));
+            }
+
+            let healthy = descriptions
+                .iter()
+                .filter(|desc| desc.target_health().and_then(|th| th.state()) == Some(&TargetHealthStateEnum::Healthy))
+                .count();
+            if healthy == 0 {
+                let states: Vec<String> = descriptions
+                    .iter()
+                    .map(|desc| {
+                        let target = desc.target().and_then(|target| target.id()).unwrap_or("unknown target");
+                        let health = desc.target_health();
+                        format!(
+                            "{}: {} ({})",
+                            target,
+                            health.and_then(|th| th.state()).map(|s| s.as_str()).unwrap_or("unknown"),
+                            health
+                                .and_then(|th| th.description())
+                                .or(health.and_then(|th| th.reason()).map(|r| r.as_str()))
+                                .unwrap_or("no reason given")
+                        )
+                    })
+                    .collect();
+
+                return Ok(chain.broken(
+                    "target_group",
+                    format!(
+                        "none of the {} targets in target group {} are healthy: {}",
+                        descriptions.len(),
+                        tg_name,
+                        states.join("; ")
+                    ),
+                ));
+            }
+
+            chain.ok(format!(
+                "target group {} has {} of {} targets healthy",
+                tg_name,
+                healthy,
+                descriptions.len()
+            ));
+        }
+
+        Ok(chain.healthy())
+    }
+}
@@ -0,0 +1,212 @@
+use anyhow::bail;
+use aws_sdk_route53::types::{HealthCheckType, ResourceRecordSet, ResourceRecordSetFailover, RrType};
+
+use crate::resource::{AliasTarget, FailoverEndpoint, FailoverPair, FailoverRole, HealthCheckConfig};
+
+const HEALTH_CHECK_TYPES: [&str; 5] = ["HTTP", "HTTPS", "HTTP_STR_MATCH", "HTTPS_STR_MATCH", "TCP"];
+
+impl FailoverRole {
+    /// The set identifier of this side's record.
+    pub fn set_identifier(&self) -> &'static str {
+        match self {
+            FailoverRole::Primary => "primary",
+            FailoverRole::Secondary => "secondary",
+        }
+    }
+
+    /// The side of a failover pair that a record with this set identifier is, if any.
+    pub fn from_set_identifier(set_identifier: &str) -> Option<Self> {
+        [FailoverRole::Primary, FailoverRole::Secondary]
+            .into_iter()
+            .find(|role| role.set_identifier() == set_identifier)
+    }
+
+    pub fn to_sdk(self) -> ResourceRecordSetFailover {
+        match self {
+            FailoverRole::Primary => ResourceRecordSetFailover::Primary,
+            FailoverRole::Secondary => ResourceRecordSetFailover::Secondary,
+        }
+    }
+}
+
+impl FailoverPair {
+    pub fn endpoint(&self, role: FailoverRole) -> &FailoverEndpoint {
+        match role {
+            FailoverRole::Primary => &self.primary,
+            FailoverRole::Secondary => &self.secondary,
+        }
+    }
+}
+
+fn check_health_check(role: FailoverRole, health_check: &HealthCheckConfig) -> Vec<String> {
+    let mut problems = Vec::new();
+    let side = role.set_identifier();
+
+    if !HEALTH_CHECK_TYPES.contains(&health_check.r#type.as_str()) {
+        problems.push(format!(
+            "{} health check type must be one of {}, not {}",
+            side,
+            HEALTH_CHECK_TYPES.join(", "),
+            health_check.r#type
+        ));
+    }
+
+    if health_check.fqdn.is_none() && health_check.ip_address.is_none() {
+        problems.push(format!("{} health check needs an fqdn or an ip_address", side));
+    }
+
+    if health_check.r#type.ends_with("_STR_MATCH") && health_check.search_string.is_none() {
+        problems.push(format!("{} health check of type {} needs a search_string", side, health_check.r#type));
+    }
+
+    if health_check.r#type == "TCP" && health_check.resource_path.is_some() {
+        problems.push(format!("{} health check of type TCP can't have a resource_path", side));
+    }
+
+    if let Some(request_interval) = health_check.request_interval
+        && request_interval != 10
+        && request_interval != 30
+    {
+        problems.push(format!("{} health check request_interval must be 10 or 30", side));
+    }
+
+    problems
+}
+
+/// Returns every problem with `pair` that Route53 would otherwise only reject partway through applying it,
+/// along with pairs that would apply cleanly but never fail over.
+pub fn check_failover_pair(pair: &FailoverPair) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for role in [FailoverRole::Primary, FailoverRole::Secondary] {
+        let endpoint = pair.endpoint(role);
+        let side = role.set_identifier();
+
+        match (&endpoint.alias_target, &endpoint.resource_records) {
+            (Some(_), Some(_)) => problems.push(format!("{} can't set both alias_target and resource_records", side)),
+            (None, None) => problems.push(format!("{} needs either alias_target or resource_records", side)),
+            (None, Some(records)) if records.is_empty() => problems.push(format!("{} has no resource_records", side)),
+            (None, Some(_)) if pair.ttl.is_none() => problems.push(format!("{} needs a ttl, since it isn't an alias", side)),
+            _ => {}
+        }
+
+        if let Some(health_check) = &endpoint.health_check {
+            problems.extend(check_health_check(role, health_check));
+        }
+    }
+
+    // Without a health check, Route53 treats the primary as always healthy and never fails over
+    let primary_evaluates_target = pair.primary.alias_target.as_ref().is_some_and(|a| a.evaluate_target_health);
+    if pair.primary.health_check.is_none() && !primary_evaluates_target {
+        problems.push(String::from(
+            "primary needs a health_check, or an alias_target with evaluate_target_health, or it will never fail over",
+        ));
+    }
+
+    problems
+}
+
+pub fn describe_endpoint(endpoint: &FailoverEndpoint) -> String {
+    let target = match (&endpoint.alias_target, &endpoint.resource_records) {
+        (Some(alias_target), _) => format!("alias {}", alias_target.dns_name),
+        (None, Some(records)) => records.join(", "),
+        (None, None) => String::from("nothing"),
+    };
+
+    match &endpoint.health_check {
+        Some(health_check) => format!(
+            "{} (health check: {} {})",
+            target,
+            health_check.r#type,
+            health_check.fqdn.as_ref().or(health_check.ip_address.as_ref()).map(|s| s.as_str()).unwrap_or_default()
+        ),
+        None => target,
+    }
+}
+
+pub fn to_sdk_health_check_config(
+    health_check: &HealthCheckConfig,
+) -> anyhow::Result<aws_sdk_route53::types::HealthCheckConfig> {
+    Ok(aws_sdk_route53::types::HealthCheckConfig::builder()
+        .r#type(HealthCheckType::from(health_check.r#type.as_str()))
+        .set_fully_qualified_domain_name(health_check.fqdn.clone())
+        .set_ip_address(health_check.ip_address.clone())
+        .set_port(health_check.port)
+        .set_resource_path(health_check.resource_path.clone())
+        .set_search_string(health_check.search_string.clone())
+        .set_request_interval(health_check.request_interval)
+        .set_failure_threshold(health_check.failure_threshold)
+        .build()?)
+}
+
+pub fn from_sdk_health_check_config(config: &aws_sdk_route53::types::HealthCheckConfig) -> HealthCheckConfig {
+    HealthCheckConfig {
+        r#type: config.r#type.as_str().to_string(),
+        fqdn: config.fully_qualified_domain_name.clone(),
+        ip_address: config.ip_address.clone(),
+        port: config.port,
+        resource_path: config.resource_path.clone(),
+        search_string: config.search_string.clone(),
+        request_interval: config.request_interval,
+        failure_threshold: config.failure_threshold,
+    }
+}
+
+/// Whether the health check behind `current` can be updated in place to `desired`.
+/// Route53 can't change a health check's type or request interval.
+pub fn health_check_updatable(current: &HealthCheckConfig, desired: &HealthCheckConfig) -> bool {
+    current.r#type == desired.r#type && current.request_interval.unwrap_or(30) == desired.request_interval.unwrap_or(30)
+}
+
+pub fn failover_record_set(
+    name: &str,
+    r#type: &str,
+    role: FailoverRole,
+    ttl: Option<i64>,
+    endpoint: &FailoverEndpoint,
+    health_check_id: Option<String>,
+) -> anyhow::Result<ResourceRecordSet> {
+    let mut builder = ResourceRecordSet::builder()
+        .name(name)
+        .r#type(RrType::try_parse(r#type)?)
+        .set_identifier(role.set_identifier())
+        .failover(role.to_sdk())
+        .set_health_check_id(health_check_id);
+
+    match (&endpoint.alias_target, &endpoint.resource_records) {
+        (Some(alias_target), _) => {
+            builder = builder.alias_target(
+                aws_sdk_route53::types::AliasTarget::builder()
+                    .dns_name(&alias_target.dns_name)
+                    .hosted_zone_id(&alias_target.hosted_zone_id)
+                    .evaluate_target_health(alias_target.evaluate_target_health)
+                    .build()?,
+            );
+        }
+        (None, Some(records)) => {
+            builder = builder.set_ttl(ttl);
+            for record in records {
+                builder = builder.resource_records(aws_sdk_route53::types::ResourceRecord::builder().value(record).build()?);
+            }
+        }
+        (None, None) => bail!("Failover {} record {} has no target", role.set_identifier(), name),
+    }
+
+    Ok(builder.build()?)
+}
+
+/// The endpoint a failover record points at, without its health check.
+pub fn endpoint_from_record_set(record: &ResourceRecordSet) -> FailoverEndpoint {
+    FailoverEndpoint {
+        alias_target: record.alias_target.as_ref().map(|alias_target| AliasTarget {
+            hosted_zone_id: alias_target.hosted_zone_id.clone(),
+            dns_name: alias_target.dns_name.clone(),
+            evaluate_target_health: alias_target.evaluate_target_health,
+        }),
+        resource_records: record
+            .resource_records
+            .as_ref()
+            .map(|records| records.iter().map(|r| r.value.clone()).collect()),
+        health_check: None,
+    }
+}
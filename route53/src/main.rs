@@ -1,18 +1,34 @@
+use addr::Route53ResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
 use autoschematic_core::tarpc_bridge::tarpc_connector_main;
 use connector::Route53Connector;
+use task::Route53TaskAddress;
 
 pub mod connector;
 // pub mod client_cache;
 // pub mod config;
 pub mod addr;
+pub mod alias_target;
+pub mod batch;
+pub mod failover;
 pub mod op;
-// pub mod op_impl;
+pub mod record_format;
+pub mod op_impl;
 pub mod resource;
+pub mod routing;
 // pub mod tags;
+pub mod task;
 pub mod util;
 
 #[tokio::main]
 pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let mut patterns = Route53ResourceAddress::checked_address_patterns()?;
+        patterns.extend(Route53TaskAddress::checked_address_patterns()?);
+        print!("{}", render_address_docs("aws/route53", &patterns));
+        return Ok(());
+    }
+
     tarpc_connector_main::<Route53Connector>().await?;
     Ok(())
 }
@@ -3,17 +3,40 @@ use serde::{Deserialize, Serialize};
 
 use autoschematic_core::util::RON;
 
-use super::resource::{HostedZone, RecordSet};
+use super::resource::{Delegation, FailoverEndpoint, FailoverRole, HostedZone, KeySigningKey, RecordSet};
 
 
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Route53ConnectorOp {
     CreateHostedZone(HostedZone),
-    ModifyHostedZone(HostedZone, HostedZone),
     DeleteHostedZone,
+    /// Starts signing the hosted zone with its active key-signing keys.
+    EnableHostedZoneDnssec,
+    /// Stops signing the hosted zone.
+    DisableHostedZoneDnssec,
     CreateResourceRecordSet(RecordSet),
-    DeleteResourceRecordSet(RecordSet),
+    /// Replaces the record set in place, in a single change.
+    UpsertResourceRecordSet(RecordSet),
+    /// Deletes the record set as it currently exists.
+    DeleteResourceRecordSet,
+    /// Creates or updates one side of a failover pair, along with its health check.
+    UpsertFailoverEndpoint {
+        role:     FailoverRole,
+        ttl:      Option<i64>,
+        endpoint: FailoverEndpoint,
+    },
+    /// Deletes both failover records as they currently exist, then their health checks.
+    DeleteFailoverPair,
+    CreateKeySigningKey(KeySigningKey),
+    ActivateKeySigningKey,
+    DeactivateKeySigningKey,
+    /// Deactivates the key-signing key if it's active, then deletes it.
+    DeleteKeySigningKey,
+    /// Creates or updates the NS records in the parent zone with the child zone's current name servers.
+    UpsertDelegation(Delegation),
+    /// Deletes the NS records in the parent zone as they currently exist.
+    DeleteDelegation,
 }
 
 impl ConnectorOp for Route53ConnectorOp {
@@ -0,0 +1,439 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, bail};
+use autoschematic_core::{connector::OpExecResponse, op_exec_output};
+use aws_sdk_route53::{
+    operation::get_dnssec::GetDnssecOutput,
+    types::{AliasTarget, Change, ChangeAction, ChangeInfo, ResourceRecordSet, RrType},
+};
+
+use crate::{
+    batch::{ChangeBatcher, wait_for_insync},
+    resource::{Delegation, KeySigningKey, RecordSet},
+};
+
+/// The status Route53 reports for a key-signing key that signs the zone.
+const KSK_ACTIVE: &str = "ACTIVE";
+const KSK_INACTIVE: &str = "INACTIVE";
+
+/// The outputs a key-signing key reports, which are cleared when it's deleted.
+const KSK_OUTPUTS: [&str; 4] = ["ds_record", "key_tag", "digest_value", "dnskey_record"];
+
+pub async fn find_hosted_zone_id(client: &aws_sdk_route53::Client, hosted_zone_name: &str) -> anyhow::Result<String> {
+    let hz = client.list_hosted_zones_by_name().dns_name(hosted_zone_name).send().await?;
+
+    match hz.hosted_zones.first() {
+        Some(hz) if hz.name == hosted_zone_name => Ok(hz.id.clone()),
+        _ => {
+            bail!("Hosted zone {} not found!", hosted_zone_name)
+        }
+    }
+}
+
+/// The record set at `name` and `r#type` with the given set identifier, or with none for simple routing,
+/// as it currently exists.
+pub async fn get_record_set(
+    client: &aws_sdk_route53::Client,
+    hosted_zone_id: &str,
+    name: &str,
+    r#type: &str,
+    set_identifier: Option<&str>,
+) -> anyhow::Result<Option<ResourceRecordSet>> {
+    let rr_type = RrType::try_parse(r#type)?;
+
+    let mut start_record_identifier = None;
+    loop {
+        let resp = client
+            .list_resource_record_sets()
+            .hosted_zone_id(hosted_zone_id)
+            .start_record_name(name)
+            .start_record_type(rr_type.clone())
+            .set_start_record_identifier(start_record_identifier)
+            .send()
+            .await?;
+
+        // Only keep paging while there are more record sets at this name and type
+        let more = resp.is_truncated
+            && resp.next_record_name.as_deref() == Some(name)
+            && resp.next_record_type.as_ref() == Some(&rr_type);
+        start_record_identifier = resp.next_record_identifier;
+
+        for rec in resp.resource_record_sets {
+            if rec.name != name || rec.r#type != rr_type {
+                return Ok(None);
+            }
+            if rec.set_identifier.as_deref() == set_identifier {
+                return Ok(Some(rec));
+            }
+        }
+
+        if !more {
+            return Ok(None);
+        }
+    }
+}
+
+/// Builds the change for `record_set`. Alias targets named by address must already be resolved.
+pub fn record_set_change(
+    action: ChangeAction,
+    record_set_name: &str,
+    r#type: &str,
+    set_identifier: Option<&str>,
+    record_set: RecordSet,
+) -> anyhow::Result<Change> {
+    let mut record_set_builder = ResourceRecordSet::builder()
+        .name(record_set_name)
+        .r#type(RrType::try_parse(r#type)?)
+        .set_set_identifier(set_identifier.map(String::from))
+        .set_health_check_id(record_set.health_check_id);
+
+    if let Some(routing_policy) = &record_set.routing_policy {
+        record_set_builder = routing_policy.apply(record_set_builder)?;
+    }
+
+    if let Some(ttl) = record_set.ttl {
+        record_set_builder = record_set_builder.ttl(ttl);
+    }
+
+    if let Some(resource_records) = record_set.resource_records {
+        for rec in resource_records {
+            let resource_record_builder = aws_sdk_route53::types::ResourceRecord::builder().value(rec);
+            record_set_builder = record_set_builder.resource_records(resource_record_builder.build()?);
+        }
+    }
+
+    if let Some(alias_target) = record_set.alias_target {
+        let alias_target_builder = AliasTarget::builder()
+            .dns_name(alias_target.dns_name)
+            .hosted_zone_id(alias_target.hosted_zone_id)
+            .evaluate_target_health(alias_target.evaluate_target_health);
+        record_set_builder = record_set_builder.alias_target(alias_target_builder.build()?);
+    }
+
+    Ok(Change::builder()
+        .action(action)
+        .resource_record_set(record_set_builder.build()?)
+        .build()?)
+}
+
+/// Deletes a record set as it currently exists, so that a record whose alias target has since been
+/// deleted can still be removed. Succeeds if the record set is already gone.
+pub async fn delete_record_set(
+    client: &aws_sdk_route53::Client,
+    change_batcher: &ChangeBatcher,
+    hosted_zone_name: &str,
+    record_set_name: &str,
+    r#type: &str,
+    set_identifier: Option<&str>,
+) -> anyhow::Result<OpExecResponse> {
+    let hosted_zone_id = find_hosted_zone_id(client, hosted_zone_name).await?;
+
+    let Some(record_set) = get_record_set(client, &hosted_zone_id, record_set_name, r#type, set_identifier).await? else {
+        return op_exec_output!(format!(
+            "{} Record {} on Hosted Zone {} was already deleted",
+            r#type, record_set_name, hosted_zone_name
+        ));
+    };
+
+    let change = Change::builder()
+        .action(ChangeAction::Delete)
+        .resource_record_set(record_set)
+        .build()?;
+    change_batcher.submit(client, &hosted_zone_id, change).await?;
+
+    op_exec_output!(format!(
+        "Deleted {} Record {} on Hosted Zone {}",
+        r#type, record_set_name, hosted_zone_name
+    ))
+}
+
+/// The DNSSEC status and key-signing keys of a hosted zone, or None for a private zone, which can't be signed.
+pub async fn get_dnssec(client: &aws_sdk_route53::Client, hosted_zone_id: &str) -> anyhow::Result<Option<GetDnssecOutput>> {
+    match client.get_dnssec().hosted_zone_id(hosted_zone_id).send().await {
+        Ok(resp) => Ok(Some(resp)),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_invalid_argument()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub async fn find_key_signing_key(
+    client: &aws_sdk_route53::Client,
+    hosted_zone_id: &str,
+    name: &str,
+) -> anyhow::Result<Option<aws_sdk_route53::types::KeySigningKey>> {
+    let Some(dnssec) = get_dnssec(client, hosted_zone_id).await? else {
+        return Ok(None);
+    };
+
+    Ok(dnssec
+        .key_signing_keys
+        .into_iter()
+        .find(|key| key.name.as_deref() == Some(name)))
+}
+
+/// Whether Route53 considers a key-signing key active. Keys in an error state are neither active nor inactive.
+pub fn key_signing_key_active(key: &aws_sdk_route53::types::KeySigningKey) -> bool {
+    key.status.as_deref() == Some(KSK_ACTIVE)
+}
+
+/// The DS record to add to the parent zone, and the values it's made from.
+pub fn key_signing_key_outputs(key: &aws_sdk_route53::types::KeySigningKey) -> HashMap<String, Option<String>> {
+    HashMap::from([
+        (String::from("ds_record"), key.ds_record.clone()),
+        (String::from("key_tag"), Some(key.key_tag.to_string())),
+        (String::from("digest_value"), key.digest_value.clone()),
+        (String::from("dnskey_record"), key.dnskey_record.clone()),
+    ])
+}
+
+async fn wait_for_change(client: &aws_sdk_route53::Client, change_info: Option<ChangeInfo>) -> anyhow::Result<()> {
+    match change_info {
+        Some(change_info) => wait_for_insync(client, change_info).await,
+        None => Ok(()),
+    }
+}
+
+pub async fn create_hosted_zone(client: &aws_sdk_route53::Client, hosted_zone_name: &str) -> anyhow::Result<OpExecResponse> {
+    // Route53 rejects a repeated caller reference, so a retried create can't make a second zone
+    let resp = client
+        .create_hosted_zone()
+        .name(hosted_zone_name)
+        .caller_reference(uuid::Uuid::new_v4().to_string())
+        .send()
+        .await?;
+    wait_for_change(client, resp.change_info).await?;
+
+    let hosted_zone_id = resp
+        .hosted_zone
+        .map(|hz| hz.id)
+        .with_context(|| format!("CreateHostedZone returned no hosted zone for {}", hosted_zone_name))?;
+
+    op_exec_output!(
+        Some([("id", Some(hosted_zone_id))]),
+        format!("Created hosted zone {}", hosted_zone_name)
+    )
+}
+
+pub async fn delete_hosted_zone(client: &aws_sdk_route53::Client, hosted_zone_name: &str) -> anyhow::Result<OpExecResponse> {
+    let hosted_zone_id = find_hosted_zone_id(client, hosted_zone_name).await?;
+
+    let resp = client.delete_hosted_zone().id(&hosted_zone_id).send().await?;
+    wait_for_change(client, resp.change_info).await?;
+
+    op_exec_output!(
+        Some([("id", Option::<String>::None)]),
+        format!("Deleted hosted zone {}", hosted_zone_name)
+    )
+}
+
+pub async fn enable_hosted_zone_dnssec(
+    client: &aws_sdk_route53::Client,
+    hosted_zone_name: &str,
+) -> anyhow::Result<OpExecResponse> {
+    let hosted_zone_id = find_hosted_zone_id(client, hosted_zone_name).await?;
+
+    let Some(dnssec) = get_dnssec(client, &hosted_zone_id).await? else {
+        bail!("Hosted zone {} is private, and private zones can't be signed with DNSSEC", hosted_zone_name);
+    };
+    // Route53 would reject this too, but without saying where the key goes
+    if !dnssec.key_signing_keys.iter().any(key_signing_key_active) {
+        bail!(
+            "Hosted zone {} has no active key-signing key. Add one at aws/route53/hosted_zones/{}/key_signing_keys/<name>.ron first.",
+            hosted_zone_name,
+            hosted_zone_name.trim_end_matches('.')
+        );
+    }
+
+    let resp = client
+        .enable_hosted_zone_dnssec()
+        .hosted_zone_id(&hosted_zone_id)
+        .send()
+        .await?;
+    wait_for_change(client, resp.change_info).await?;
+
+    op_exec_output!(format!("Enabled DNSSEC signing for hosted zone {}", hosted_zone_name))
+}
+
+pub async fn disable_hosted_zone_dnssec(
+    client: &aws_sdk_route53::Client,
+    hosted_zone_name: &str,
+) -> anyhow::Result<OpExecResponse> {
+    let hosted_zone_id = find_hosted_zone_id(client, hosted_zone_name).await?;
+
+    let resp = client
+        .disable_hosted_zone_dnssec()
+        .hosted_zone_id(&hosted_zone_id)
+        .send()
+        .await?;
+    wait_for_change(client, resp.change_info).await?;
+
+    op_exec_output!(format!("Disabled DNSSEC signing for hosted zone {}", hosted_zone_name))
+}
+
+pub async fn create_key_signing_key(
+    client: &aws_sdk_route53::Client,
+    hosted_zone_name: &str,
+    name: &str,
+    key_signing_key: &KeySigningKey,
+) -> anyhow::Result<OpExecResponse> {
+    let hosted_zone_id = find_hosted_zone_id(client, hosted_zone_name).await?;
+
+    let resp = client
+        .create_key_signing_key()
+        .caller_reference(uuid::Uuid::new_v4().to_string())
+        .hosted_zone_id(&hosted_zone_id)
+        .key_management_service_arn(&key_signing_key.kms_key_arn)
+        .name(name)
+        .status(if key_signing_key.active { KSK_ACTIVE } else { KSK_INACTIVE })
+        .send()
+        .await?;
+    wait_for_change(client, resp.change_info).await?;
+
+    let key = resp.key_signing_key.context("No key-signing key in response")?;
+
+    Ok(OpExecResponse {
+        outputs: Some(key_signing_key_outputs(&key)),
+        friendly_message: Some(format!("Created key-signing key {} for hosted zone {}", name, hosted_zone_name)),
+    })
+}
+
+pub async fn set_key_signing_key_active(
+    client: &aws_sdk_route53::Client,
+    hosted_zone_name: &str,
+    name: &str,
+    active: bool,
+) -> anyhow::Result<OpExecResponse> {
+    let hosted_zone_id = find_hosted_zone_id(client, hosted_zone_name).await?;
+
+    let change_info = if active {
+        client
+            .activate_key_signing_key()
+            .hosted_zone_id(&hosted_zone_id)
+            .name(name)
+            .send()
+            .await?
+            .change_info
+    } else {
+        client
+            .deactivate_key_signing_key()
+            .hosted_zone_id(&hosted_zone_id)
+            .name(name)
+            .send()
+            .await?
+            .change_info
+    };
+    wait_for_change(client, change_info).await?;
+
+    op_exec_output!(format!(
+        "{} key-signing key {} for hosted zone {}",
+        if active { "Activated" } else { "Deactivated" },
+        name,
+        hosted_zone_name
+    ))
+}
+
+pub async fn delete_key_signing_key(
+    client: &aws_sdk_route53::Client,
+    hosted_zone_name: &str,
+    name: &str,
+) -> anyhow::Result<OpExecResponse> {
+    let hosted_zone_id = find_hosted_zone_id(client, hosted_zone_name).await?;
+
+    let Some(key) = find_key_signing_key(client, &hosted_zone_id, name).await? else {
+        bail!("Key-signing key {} not found in hosted zone {}", name, hosted_zone_name);
+    };
+
+    // Route53 only deletes inactive keys
+    if key_signing_key_active(&key) {
+        let resp = client
+            .deactivate_key_signing_key()
+            .hosted_zone_id(&hosted_zone_id)
+            .name(name)
+            .send()
+            .await?;
+        wait_for_change(client, resp.change_info).await?;
+    }
+
+    let resp = client
+        .delete_key_signing_key()
+        .hosted_zone_id(&hosted_zone_id)
+        .name(name)
+        .send()
+        .await?;
+    wait_for_change(client, resp.change_info).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(KSK_OUTPUTS.iter().map(|key| (key.to_string(), None)).collect()),
+        friendly_message: Some(format!("Deleted key-signing key {} for hosted zone {}", name, hosted_zone_name)),
+    })
+}
+
+/// The name servers Route53 assigned to a hosted zone, with trailing dots as NS records hold them.
+/// Private zones have none.
+pub async fn get_name_servers(client: &aws_sdk_route53::Client, hosted_zone_id: &str) -> anyhow::Result<Vec<String>> {
+    let resp = client.get_hosted_zone().id(hosted_zone_id).send().await?;
+
+    Ok(resp
+        .delegation_set
+        .map(|delegation_set| {
+            delegation_set
+                .name_servers
+                .into_iter()
+                .map(|ns| if ns.ends_with('.') { ns } else { format!("{}.", ns) })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Points the NS records at the child zone's name in the parent zone at the child zone's name servers.
+pub async fn upsert_delegation(
+    client: &aws_sdk_route53::Client,
+    change_batcher: &ChangeBatcher,
+    hosted_zone_name: &str,
+    child_zone_name: &str,
+    delegation: &Delegation,
+) -> anyhow::Result<OpExecResponse> {
+    let hosted_zone_id = find_hosted_zone_id(client, hosted_zone_name).await?;
+    let child_zone_id = find_hosted_zone_id(client, child_zone_name).await?;
+
+    let name_servers = get_name_servers(client, &child_zone_id).await?;
+    if name_servers.is_empty() {
+        bail!(
+            "Hosted zone {} has no name servers to delegate to, so it can't be delegated from {}. Is it a private zone?",
+            child_zone_name,
+            hosted_zone_name
+        );
+    }
+
+    let record_set = RecordSet {
+        ttl: Some(delegation.ttl),
+        alias_target: None,
+        resource_records: Some(name_servers.clone()),
+        routing_policy: None,
+        health_check_id: None,
+    };
+    let change = record_set_change(ChangeAction::Upsert, child_zone_name, "NS", None, record_set)?;
+    change_batcher.submit(client, &hosted_zone_id, change).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("name_servers"), Some(name_servers.join(",")))])),
+        friendly_message: Some(format!(
+            "Delegated {} from hosted zone {} to {}",
+            child_zone_name,
+            hosted_zone_name,
+            name_servers.join(", ")
+        )),
+    })
+}
+
+pub async fn delete_delegation(
+    client: &aws_sdk_route53::Client,
+    change_batcher: &ChangeBatcher,
+    hosted_zone_name: &str,
+    child_zone_name: &str,
+) -> anyhow::Result<OpExecResponse> {
+    let mut resp = delete_record_set(client, change_batcher, hosted_zone_name, child_zone_name, "NS", None).await?;
+    resp.outputs = Some(HashMap::from([(String::from("name_servers"), None)]));
+
+    Ok(resp)
+}
@@ -0,0 +1,508 @@
+use std::{
+    fmt,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use anyhow::bail;
+
+use crate::{alias_target::check_alias_target, resource::RecordSet};
+
+/// The record types Route53 accepts.
+pub const RECORD_TYPES: [&str; 17] = [
+    "A", "AAAA", "CAA", "CNAME", "DS", "HTTPS", "MX", "NAPTR", "NS", "PTR", "SOA", "SPF", "SRV", "SSHFP", "SVCB", "TLSA", "TXT",
+];
+
+/// Route53 rejects TXT strings longer than this; longer values have to be split into several quoted strings.
+const MAX_TXT_STRING_LEN: usize = 255;
+
+/// The CA domains that ACM issues certificates under. A CAA record set has to allow one of them
+/// for ACM to issue (or renew) certificates for the domain.
+pub const ACM_CAA_DOMAINS: [&str; 4] = ["amazon.com", "amazontrust.com", "awstrust.com", "amazonaws.com"];
+
+/// The CAA tags Route53 accepts.
+const CAA_TAGS: [&str; 3] = ["issue", "issuewild", "iodef"];
+
+/// One value of a CAA record set, e.g. `0 issue "amazon.com"`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaaRecord {
+    /// 0, or 128 to mark the record critical.
+    pub flags: u8,
+    pub tag: String,
+    /// The value, without its surrounding quotes.
+    pub value: String,
+}
+
+impl CaaRecord {
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        let mut parts = s.trim().splitn(3, ' ');
+        let (Some(flags), Some(tag), Some(value)) = (parts.next(), parts.next(), parts.next()) else {
+            bail!("CAA record `{}` should look like `0 issue \"amazon.com\"`", s);
+        };
+
+        let Ok(flags) = flags.parse::<u8>() else {
+            bail!("CAA record `{}` has flags {}; expected a number from 0 to 255", s, flags);
+        };
+
+        let value = value.trim();
+        let Some(value) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) else {
+            bail!("CAA record `{}` has an unquoted value; Route53 needs it in double quotes", s);
+        };
+
+        let record = CaaRecord {
+            flags,
+            tag: tag.to_string(),
+            value: value.to_string(),
+        };
+        record.check()?;
+        Ok(record)
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        // Only the issuer-critical bit is defined
+        if self.flags != 0 && self.flags != 128 {
+            bail!("CAA record `{}` has flags {}; expected 0 or 128 (critical)", self, self.flags);
+        }
+
+        if !CAA_TAGS.contains(&self.tag.as_str()) {
+            bail!(
+                "CAA record `{}` has tag {}; Route53 accepts {}",
+                self,
+                self.tag,
+                CAA_TAGS.join(", ")
+            );
+        }
+
+        if self.value.contains('"') {
+            bail!("CAA record `{}` has a double quote inside its value", self);
+        }
+
+        match self.tag.as_str() {
+            "issue" | "issuewild" => {
+                // An empty issuer (or a bare `;`) forbids issuance
+                let issuer = self.value.split(';').next().unwrap_or_default().trim();
+                if !issuer.is_empty()
+                    && !issuer
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+                {
+                    bail!("CAA record `{}` has an invalid issuer domain {}", self, issuer);
+                }
+            }
+            "iodef" => {
+                if !["mailto:", "http://", "https://"]
+                    .iter()
+                    .any(|scheme| self.value.starts_with(scheme))
+                {
+                    bail!("CAA record `{}` has an iodef value that isn't a mailto:, http:// or https:// URL", self);
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// The issuer domain of an issue or issuewild record, without any parameters.
+    pub fn issuer(&self) -> Option<&str> {
+        match self.tag.as_str() {
+            "issue" | "issuewild" => self.value.split(';').next().map(str::trim).filter(|i| !i.is_empty()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CaaRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} \"{}\"", self.flags, self.tag, self.value)
+    }
+}
+
+/// The CAA record values that let ACM issue certificates for a domain, and optionally for wildcards under it.
+pub fn acm_caa_records(include_wildcard: bool) -> Vec<String> {
+    let tags: &[&str] = if include_wildcard { &["issue", "issuewild"] } else { &["issue"] };
+
+    let mut records = Vec::new();
+    for tag in tags {
+        for domain in ACM_CAA_DOMAINS {
+            let record = CaaRecord {
+                flags: 0,
+                tag: tag.to_string(),
+                value: domain.to_string(),
+            };
+            records.push(record.to_string());
+        }
+    }
+    records
+}
+
+/// Whether a set of CAA records lets ACM issue non-wildcard certificates.
+/// With no issue records at all, any CA may issue.
+pub fn caa_allows_acm(records: &[CaaRecord]) -> bool {
+    let issue_records: Vec<&CaaRecord> = records.iter().filter(|r| r.tag == "issue").collect();
+    issue_records.is_empty()
+        || issue_records
+            .iter()
+            .any(|r| r.issuer().is_some_and(|issuer| ACM_CAA_DOMAINS.contains(&issuer)))
+}
+
+/// One value of a TLSA record set, e.g. `3 1 1 <sha-256 of the public key, in hex>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TlsaRecord {
+    /// 0 (PKIX-TA), 1 (PKIX-EE), 2 (DANE-TA) or 3 (DANE-EE).
+    pub usage: u8,
+    /// 0 (full certificate) or 1 (public key only).
+    pub selector: u8,
+    /// 0 (exact match), 1 (SHA-256) or 2 (SHA-512).
+    pub matching_type: u8,
+    /// The certificate association data, in hex.
+    pub data: String,
+}
+
+impl TlsaRecord {
+    pub fn new(usage: u8, selector: u8, matching_type: u8, data: &str) -> anyhow::Result<Self> {
+        let record = TlsaRecord {
+            usage,
+            selector,
+            matching_type,
+            data: data.to_ascii_lowercase(),
+        };
+        record.check()?;
+        Ok(record)
+    }
+
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        let [usage, selector, matching_type, data @ ..] = &parts[..] else {
+            bail!("TLSA record `{}` should look like `<usage> <selector> <matching type> <hex data>`", s);
+        };
+        if data.is_empty() {
+            bail!("TLSA record `{}` has no certificate association data", s);
+        }
+
+        let field = |name: &str, value: &str| match value.parse::<u8>() {
+            Ok(value) => Ok(value),
+            Err(_) => Err(anyhow::anyhow!("TLSA record `{}` has {} {}; expected a number", s, name, value)),
+        };
+
+        // Long data can be split into several space-separated chunks
+        TlsaRecord::new(
+            field("usage", *usage)?,
+            field("selector", *selector)?,
+            field("matching type", *matching_type)?,
+            &data.concat(),
+        )
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.usage > 3 {
+            bail!("TLSA record `{}` has usage {}; expected 0 to 3", self, self.usage);
+        }
+        if self.selector > 1 {
+            bail!("TLSA record `{}` has selector {}; expected 0 or 1", self, self.selector);
+        }
+
+        if self.data.is_empty() || !self.data.chars().all(|c| c.is_ascii_hexdigit()) || !self.data.len().is_multiple_of(2) {
+            bail!("TLSA record `{}` has certificate association data that isn't hex", self);
+        }
+
+        let expected_len = match self.matching_type {
+            0 => None,
+            1 => Some(64),
+            2 => Some(128),
+            _ => bail!(
+                "TLSA record `{}` has matching type {}; expected 0 to 2",
+                self,
+                self.matching_type
+            ),
+        };
+        if let Some(expected_len) = expected_len
+            && self.data.len() != expected_len
+        {
+            bail!(
+                "TLSA record `{}` has {} hex digits of data; matching type {} needs {}",
+                self,
+                self.data.len(),
+                self.matching_type,
+                expected_len
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for TlsaRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.usage, self.selector, self.matching_type, self.data)
+    }
+}
+
+/// TLSA records live at `_<port>._<protocol>.<host>`, e.g. `_443._tcp.www.example.com.`.
+pub fn check_tlsa_name(name: &str) -> anyhow::Result<()> {
+    let mut labels = name.split('.');
+    let port = labels.next().and_then(|l| l.strip_prefix('_'));
+    let protocol = labels.next().and_then(|l| l.strip_prefix('_'));
+
+    match (port, protocol) {
+        (Some(port), Some("tcp" | "udp" | "sctp")) if port.parse::<u16>().is_ok() => Ok(()),
+        _ => bail!(
+            "TLSA record name {} should start with _<port>._<protocol>, as in _443._tcp.www.example.com",
+            name
+        ),
+    }
+}
+
+/// Splits a TXT value into its quoted strings, e.g. `"v=spf1 include:amazonses.com" "-all"`.
+/// Returns None if any part of it isn't quoted.
+fn txt_strings(value: &str) -> Option<Vec<String>> {
+    let mut strings = Vec::new();
+    let mut chars = value.trim().chars();
+
+    loop {
+        match chars.next() {
+            None => break,
+            Some(' ') => continue,
+            Some('"') => {}
+            Some(_) => return None,
+        }
+
+        let mut string = String::new();
+        loop {
+            match chars.next()? {
+                '"' => break,
+                '\\' => string.push(chars.next()?),
+                c => string.push(c),
+            }
+        }
+        strings.push(string);
+    }
+
+    Some(strings)
+}
+
+fn check_txt_value(r#type: &str, value: &str) -> anyhow::Result<()> {
+    let Some(strings) = txt_strings(value) else {
+        bail!("{} record `{}` should be in double quotes, e.g. \"v=spf1 -all\"", r#type, value);
+    };
+
+    if let Some(long) = strings.iter().find(|s| s.len() > MAX_TXT_STRING_LEN) {
+        bail!(
+            "{} record has a string of {} characters; split it into quoted strings of at most {}: \"{}...\"",
+            r#type,
+            long.len(),
+            MAX_TXT_STRING_LEN,
+            long.chars().take(32).collect::<String>()
+        );
+    }
+
+    Ok(())
+}
+
+fn check_domain_name(r#type: &str, value: &str) -> anyhow::Result<()> {
+    if value.is_empty() || value.len() > 255 || value.contains(char::is_whitespace) {
+        bail!("{} record `{}` isn't a domain name", r#type, value);
+    }
+    Ok(())
+}
+
+/// Checks the numeric fields at the start of an MX or SRV value, then the domain name after them.
+fn check_priority_fields(r#type: &str, value: &str, fields: &[&str]) -> anyhow::Result<()> {
+    let parts: Vec<&str> = value.split_whitespace().collect();
+    if parts.len() != fields.len() + 1 {
+        bail!(
+            "{} record `{}` should look like `<{}> <domain name>`",
+            r#type,
+            value,
+            fields.join("> <")
+        );
+    }
+
+    for (field, part) in fields.iter().zip(&parts) {
+        if part.parse::<u16>().is_err() {
+            bail!("{} record `{}` has {} {}; expected a number from 0 to 65535", r#type, value, field, part);
+        }
+    }
+
+    check_domain_name(r#type, parts[fields.len()])
+}
+
+/// Checks that a record set has either an alias target or values, and only a ttl if it has values.
+fn check_record_set_shape(name: &str, r#type: &str, record_set: &RecordSet) -> anyhow::Result<()> {
+    match (&record_set.alias_target, &record_set.resource_records) {
+        (Some(_), Some(_)) => bail!("{} record {} can't set both alias_target and resource_records", r#type, name),
+        (None, None) => bail!("{} record {} needs either alias_target or resource_records", r#type, name),
+        (Some(alias_target), None) => {
+            if record_set.ttl.is_some() {
+                bail!("{} record {} is an alias, so it can't have a ttl; Route53 uses the target's", r#type, name);
+            }
+            check_alias_target(name, r#type, alias_target)
+        }
+        (None, Some(values)) => {
+            if values.is_empty() {
+                bail!("{} record {} has no resource_records", r#type, name);
+            }
+            if record_set.ttl.is_none() {
+                bail!("{} record {} needs a ttl, since it isn't an alias", r#type, name);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Catches malformed record values at plan time rather than when Route53 rejects the change.
+/// Returns the parsed CAA records, if any, so callers can check what they allow.
+pub fn check_record_set(name: &str, r#type: &str, record_set: &RecordSet) -> anyhow::Result<Vec<CaaRecord>> {
+    if !RECORD_TYPES.contains(&r#type) {
+        bail!("Record {} has type {}; expected one of {}", name, r#type, RECORD_TYPES.join(", "));
+    }
+
+    check_record_set_shape(name, r#type, record_set)?;
+
+    let values = record_set.resource_records.as_deref().unwrap_or_default();
+
+    match r#type {
+        "A" => {
+            for value in values {
+                if value.parse::<Ipv4Addr>().is_err() {
+                    bail!("A record `{}` isn't an IPv4 address", value);
+                }
+            }
+        }
+        "AAAA" => {
+            for value in values {
+                if value.parse::<Ipv6Addr>().is_err() {
+                    bail!("AAAA record `{}` isn't an IPv6 address", value);
+                }
+            }
+        }
+        "CNAME" => {
+            if values.len() > 1 {
+                bail!("CNAME record {} has {} values; a CNAME can only have one", name, values.len());
+            }
+            for value in values {
+                check_domain_name(r#type, value)?;
+            }
+        }
+        "NS" => {
+            for value in values {
+                check_domain_name(r#type, value)?;
+            }
+        }
+        "MX" => {
+            for value in values {
+                check_priority_fields(r#type, value, &["priority"])?;
+            }
+        }
+        "SRV" => {
+            for value in values {
+                check_priority_fields(r#type, value, &["priority", "weight", "port"])?;
+            }
+        }
+        "TXT" | "SPF" => {
+            for value in values {
+                check_txt_value(r#type, value)?;
+            }
+        }
+        "CAA" => return values.iter().map(|v| CaaRecord::parse(v)).collect(),
+        "TLSA" => {
+            check_tlsa_name(name)?;
+            for value in values {
+                TlsaRecord::parse(value)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(Vec::new())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CaaRecord, TlsaRecord, caa_allows_acm, check_tlsa_name};
+
+    #[test]
+    fn caa_records() {
+        let valid = [
+            "0 issue \"amazon.com\"",
+            "128 issue \"amazontrust.com; validationmethods=dns\"",
+            "0 issuewild \"letsencrypt.org\"",
+            "0 issue \";\"",
+            "0 iodef \"mailto:security@example.com\"",
+            "0 iodef \"https://example.com/caa-report\"",
+        ];
+        for record in valid {
+            let parsed = CaaRecord::parse(record).unwrap_or_else(|e| panic!("{} should be valid: {}", record, e));
+            assert_eq!(parsed.to_string(), record);
+        }
+
+        let invalid = [
+            "issue \"amazon.com\"",
+            "1 issue \"amazon.com\"",
+            "256 issue \"amazon.com\"",
+            "0 contactemail \"security@example.com\"",
+            "0 issue amazon.com",
+            "0 issue \"amazon com\"",
+            "0 issue \"amazon\"com\"",
+            "0 iodef \"security@example.com\"",
+        ];
+        for record in invalid {
+            assert!(CaaRecord::parse(record).is_err(), "{} should be invalid", record);
+        }
+    }
+
+    #[test]
+    fn caa_acm_issuers() {
+        let cases: [(&[&str], bool); 4] = [
+            (&[], true),
+            (&["0 issue \"amazon.com\"", "0 issue \"letsencrypt.org\""], true),
+            (&["0 issue \"letsencrypt.org\""], false),
+            (&["0 issue \";\"", "0 issuewild \"amazon.com\""], false),
+        ];
+        for (records, allows_acm) in cases {
+            let records: Vec<CaaRecord> = records.iter().map(|r| CaaRecord::parse(r).unwrap()).collect();
+            assert_eq!(caa_allows_acm(&records), allows_acm, "{:?}", records);
+        }
+    }
+
+    #[test]
+    fn tlsa_records() {
+        let sha256 = "a".repeat(64);
+        let sha512 = "B".repeat(128);
+
+        let valid = [
+            format!("3 1 1 {}", sha256),
+            format!("2 0 2 {}", sha512),
+            format!("3 1 1 {} {}", &sha256[..32], &sha256[32..]),
+            String::from("0 0 0 308201a2"),
+        ];
+        for record in &valid {
+            TlsaRecord::parse(record).unwrap_or_else(|e| panic!("{} should be valid: {}", record, e));
+        }
+
+        let invalid = [
+            String::from("3 1 1"),
+            format!("4 1 1 {}", sha256),
+            format!("3 2 1 {}", sha256),
+            format!("3 1 3 {}", sha256),
+            format!("3 1 1 {}", &sha256[..62]),
+            format!("3 1 2 {}", sha256),
+            String::from("3 1 0 xyz0"),
+            String::from("3 1 0 abc"),
+            format!("x 1 1 {}", sha256),
+        ];
+        for record in &invalid {
+            assert!(TlsaRecord::parse(record).is_err(), "{} should be invalid", record);
+        }
+    }
+
+    #[test]
+    fn tlsa_names() {
+        for name in ["_443._tcp.www.example.com.", "_25._udp.mail.example.com", "_5061._sctp.sip.example.com"] {
+            assert!(check_tlsa_name(name).is_ok(), "{} should be valid", name);
+        }
+
+        for name in ["www.example.com.", "_https._tcp.example.com", "_443.example.com", "_70000._tcp.example.com"] {
+            assert!(check_tlsa_name(name).is_err(), "{} should be invalid", name);
+        }
+    }
+}
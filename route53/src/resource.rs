@@ -14,24 +14,97 @@ use super::addr::Route53ResourceAddress;
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct HostedZone {
     // id: String,
+    /// Whether Route53 signs the zone with DNSSEC. Needs an active key-signing key.
+    #[serde(default)]
+    pub dnssec_signing: bool,
+}
+
+/// A DNSSEC key-signing key. Once the zone is signed, its DS record (in this resource's outputs)
+/// has to be added to the parent zone.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+pub struct KeySigningKey {
+    /// The ARN of the KMS key that signs the zone. It has to be an asymmetric ECC_NIST_P256 key
+    /// in us-east-1, and can't be changed after creation.
+    pub kms_key_arn: String,
+    /// Whether the key signs the zone. A key has to be inactive before it can be deleted.
+    pub active: bool,
+}
+
+/// Delegates a child hosted zone from this one. The NS records at the child zone's name are pointed
+/// at the child zone's name servers, which are looked up when the delegation is applied, so they
+/// don't have to be copied in by hand.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+pub struct Delegation {
+    /// Time to live in seconds for the NS records. Parent zones usually use 172800 (two days).
+    pub ttl: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct HealthCheck {}
 
 /// Configuration for an alias record that routes traffic to an AWS resource.
-#[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
 pub struct AliasTarget {
     /// The hosted zone ID of the target resource (e.g., CloudFront, ELB, S3).
+    /// Leave empty when dns_name is an address; it's looked up from the target.
+    #[serde(default)]
     pub hosted_zone_id: String,
-    /// The DNS name of the target resource.
+    /// The DNS name of the target resource, or the address of a CloudFront distribution
+    /// (aws/cloudfront/distributions/<id>.ron) or load balancer (aws/elb/<region>/load_balancers/<name>.ron)
+    /// managed in this repo, whose DNS name is read from its outputs.
     pub dns_name: String,
     /// Whether to check the health of the target resource before routing traffic.
     pub evaluate_target_health: bool,
 }
 
 
-/// A Route53 DNS record set.
+/// The latitude and longitude of a geoproximity location, as decimal strings, e.g. "49.22".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+pub struct Coordinates {
+    pub latitude: String,
+    pub longitude: String,
+}
+
+/// How Route53 chooses between the record sets at a name and type. Every record set at the same
+/// name and type has to use the same routing policy, each with its own set identifier.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum RoutingPolicy {
+    /// Answers with each record set in proportion to its weight, from 0 to 255.
+    Weighted { weight: i64 },
+    /// Answers with the record set in the AWS region with the lowest latency to the resolver.
+    Latency { region: String },
+    /// Answers with the primary while it's healthy, and the secondary otherwise.
+    /// For a pair that manages its own health checks, use a failover pair instead.
+    Failover { role: FailoverRole },
+    /// Answers by the resolver's location. Set exactly one of continent_code and country_code;
+    /// country_code "*" is the default for locations no other record set matches.
+    Geolocation {
+        #[serde(default)]
+        continent_code: Option<String>,
+        #[serde(default)]
+        country_code: Option<String>,
+        /// A state or province, with country_code.
+        #[serde(default)]
+        subdivision_code: Option<String>,
+    },
+    /// Answers with the nearest record set by distance, shifted by bias (-99 to 99).
+    /// Set exactly one of aws_region, local_zone_group and coordinates.
+    Geoproximity {
+        #[serde(default)]
+        aws_region: Option<String>,
+        #[serde(default)]
+        local_zone_group: Option<String>,
+        #[serde(default)]
+        coordinates: Option<Coordinates>,
+        #[serde(default)]
+        bias: Option<i32>,
+    },
+    /// Answers with up to eight healthy record sets, picked at random.
+    MultivalueAnswer,
+}
+
+/// A Route53 DNS record set. Record values are written as Route53 expects them,
+/// e.g. `10 mail.example.com.` for MX, `0 5 443 target.example.com.` for SRV and `"quoted text"` for TXT.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
 pub struct RecordSet {
     /// Time to live in seconds. Not used for alias records.
@@ -40,12 +113,75 @@ pub struct RecordSet {
     pub alias_target: Option<AliasTarget>,
     /// List of record values (e.g., IP addresses for A records). Mutually exclusive with alias_target.
     pub resource_records: Option<Vec<String>>,
+    /// The routing policy of a record set with a set identifier, stored at
+    /// records/<type>/<record_name>/<set_identifier>.ron. Unset for simple routing.
+    #[serde(default)]
+    pub routing_policy: Option<RoutingPolicy>,
+    /// The ID of the health check that decides whether Route53 answers with this record set.
+    /// Only used with a routing policy.
+    #[serde(default)]
+    pub health_check_id: Option<String>,
+}
+
+/// Which side of a failover pair a record is.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum FailoverRole {
+    Primary,
+    Secondary,
+}
+
+/// A Route53 health check, created and deleted along with the failover record it's attached to.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+pub struct HealthCheckConfig {
+    /// HTTP, HTTPS, HTTP_STR_MATCH, HTTPS_STR_MATCH or TCP. Changing it replaces the health check.
+    pub r#type: String,
+    /// The domain name to check. At least one of fqdn and ip_address must be set.
+    pub fqdn: Option<String>,
+    /// The IP address to check. If unset, Route53 resolves fqdn.
+    pub ip_address: Option<String>,
+    /// Defaults to 80 for HTTP and 443 for HTTPS.
+    pub port: Option<i32>,
+    /// The path to request, e.g. "/health". HTTP and HTTPS checks only.
+    pub resource_path: Option<String>,
+    /// The string the response body must contain. Required for the *_STR_MATCH types.
+    pub search_string: Option<String>,
+    /// Seconds between checks, 10 or 30. Changing it replaces the health check.
+    pub request_interval: Option<i32>,
+    /// How many consecutive checks must fail or pass to change the endpoint's status.
+    pub failure_threshold: Option<i32>,
+}
+
+/// One side of a failover pair.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+pub struct FailoverEndpoint {
+    /// Alias target configuration. Mutually exclusive with resource_records.
+    pub alias_target: Option<AliasTarget>,
+    /// List of record values. Mutually exclusive with alias_target.
+    pub resource_records: Option<Vec<String>>,
+    /// The health check Route53 uses to decide whether to fail over.
+    pub health_check: Option<HealthCheckConfig>,
+}
+
+/// A primary and secondary failover record set at the same name, each with its own health check.
+/// Expands into two record sets (with set identifiers "primary" and "secondary") and up to two health checks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+pub struct FailoverPair {
+    /// Time to live in seconds for both records. Not used for alias records.
+    pub ttl: Option<i64>,
+    /// Serves traffic while its health check passes. Needs a health check, unless it's an alias
+    /// that evaluates target health.
+    pub primary: FailoverEndpoint,
+    /// Serves traffic while the primary is unhealthy.
+    pub secondary: FailoverEndpoint,
 }
 
 pub enum Route53Resource {
     HostedZone(HostedZone),
     RecordSet(RecordSet),
     HealthCheck(HealthCheck),
+    FailoverPair(FailoverPair),
+    KeySigningKey(KeySigningKey),
+    Delegation(Delegation),
 }
 
 impl Resource for Route53Resource {
@@ -63,6 +199,18 @@ impl Resource for Route53Resource {
                 Ok(s) => Ok(s.into()),
                 Err(e) => Err(e.into()),
             },
+            Route53Resource::FailoverPair(failover_pair) => match RON.to_string_pretty(&failover_pair, PrettyConfig::default()) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
+            Route53Resource::KeySigningKey(key_signing_key) => match RON.to_string_pretty(&key_signing_key, PrettyConfig::default()) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
+            Route53Resource::Delegation(delegation) => match RON.to_string_pretty(&delegation, PrettyConfig::default()) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
         }
     }
 
@@ -75,8 +223,13 @@ impl Resource for Route53Resource {
         let s = str::from_utf8(s)?;
         match addr {
             Route53ResourceAddress::HostedZone(_name) => Ok(Route53Resource::HostedZone(RON.from_str(s)?)),
-            Route53ResourceAddress::ResourceRecordSet(_, _, _) => Ok(Route53Resource::RecordSet(RON.from_str(s)?)),
+            Route53ResourceAddress::ResourceRecordSet(_, _, _) | Route53ResourceAddress::RoutedRecordSet(_, _, _, _) => {
+                Ok(Route53Resource::RecordSet(RON.from_str(s)?))
+            }
             Route53ResourceAddress::HealthCheck(_) => Ok(Route53Resource::HealthCheck(RON.from_str(s)?)),
+            Route53ResourceAddress::FailoverPair(_, _, _) => Ok(Route53Resource::FailoverPair(RON.from_str(s)?)),
+            Route53ResourceAddress::KeySigningKey(_, _) => Ok(Route53Resource::KeySigningKey(RON.from_str(s)?)),
+            Route53ResourceAddress::Delegation(_, _) => Ok(Route53Resource::Delegation(RON.from_str(s)?)),
         }
     }
 }
@@ -0,0 +1,249 @@
+use anyhow::bail;
+use aws_sdk_route53::types::{
+    GeoLocation, GeoProximityLocation, ResourceRecordSet, ResourceRecordSetFailover, ResourceRecordSetRegion,
+    builders::ResourceRecordSetBuilder,
+};
+
+use crate::resource::{Coordinates, FailoverRole, RecordSet, RoutingPolicy};
+
+const CONTINENT_CODES: [&str; 7] = ["AF", "AN", "AS", "EU", "OC", "NA", "SA"];
+
+const MAX_SET_IDENTIFIER_LEN: usize = 128;
+
+impl RoutingPolicy {
+    /// A short description for plan messages, e.g. "weighted 10".
+    pub fn describe(&self) -> String {
+        match self {
+            RoutingPolicy::Weighted { weight } => format!("weighted {}", weight),
+            RoutingPolicy::Latency { region } => format!("latency in {}", region),
+            RoutingPolicy::Failover { role } => format!("failover {}", role.set_identifier()),
+            RoutingPolicy::Geolocation {
+                continent_code,
+                country_code,
+                subdivision_code,
+            } => {
+                let location: Vec<&str> = [continent_code, country_code, subdivision_code]
+                    .into_iter()
+                    .flatten()
+                    .map(|code| code.as_str())
+                    .collect();
+                format!("geolocation {}", location.join("/"))
+            }
+            RoutingPolicy::Geoproximity {
+                aws_region,
+                local_zone_group,
+                coordinates,
+                bias,
+            } => {
+                let location = match (aws_region, local_zone_group, coordinates) {
+                    (Some(aws_region), _, _) => aws_region.clone(),
+                    (None, Some(local_zone_group), _) => local_zone_group.clone(),
+                    (None, None, Some(coordinates)) => format!("{}, {}", coordinates.latitude, coordinates.longitude),
+                    (None, None, None) => String::from("nowhere"),
+                };
+                match bias {
+                    Some(bias) => format!("geoproximity {} (bias {})", location, bias),
+                    None => format!("geoproximity {}", location),
+                }
+            }
+            RoutingPolicy::MultivalueAnswer => String::from("multivalue answer"),
+        }
+    }
+
+    /// Sets the routing fields of a record set that's being built.
+    pub fn apply(&self, builder: ResourceRecordSetBuilder) -> anyhow::Result<ResourceRecordSetBuilder> {
+        Ok(match self {
+            RoutingPolicy::Weighted { weight } => builder.weight(*weight),
+            RoutingPolicy::Latency { region } => builder.region(ResourceRecordSetRegion::from(region.as_str())),
+            RoutingPolicy::Failover { role } => builder.failover(role.to_sdk()),
+            RoutingPolicy::Geolocation {
+                continent_code,
+                country_code,
+                subdivision_code,
+            } => builder.geo_location(
+                GeoLocation::builder()
+                    .set_continent_code(continent_code.clone())
+                    .set_country_code(country_code.clone())
+                    .set_subdivision_code(subdivision_code.clone())
+                    .build(),
+            ),
+            RoutingPolicy::Geoproximity {
+                aws_region,
+                local_zone_group,
+                coordinates,
+                bias,
+            } => {
+                let coordinates = match coordinates {
+                    Some(coordinates) => Some(
+                        aws_sdk_route53::types::Coordinates::builder()
+                            .latitude(&coordinates.latitude)
+                            .longitude(&coordinates.longitude)
+                            .build()?,
+                    ),
+                    None => None,
+                };
+                builder.geo_proximity_location(
+                    GeoProximityLocation::builder()
+                        .set_aws_region(aws_region.clone())
+                        .set_local_zone_group(local_zone_group.clone())
+                        .set_coordinates(coordinates)
+                        .set_bias(*bias)
+                        .build(),
+                )
+            }
+            RoutingPolicy::MultivalueAnswer => builder.multi_value_answer(true),
+        })
+    }
+
+    /// The routing policy of a record set as it exists, or None for simple routing.
+    pub fn from_record_set(record: &ResourceRecordSet) -> Option<Self> {
+        if let Some(weight) = record.weight {
+            return Some(RoutingPolicy::Weighted { weight });
+        }
+        if let Some(region) = &record.region {
+            return Some(RoutingPolicy::Latency {
+                region: region.as_str().to_string(),
+            });
+        }
+        if let Some(failover) = &record.failover {
+            let role = match failover {
+                ResourceRecordSetFailover::Primary => FailoverRole::Primary,
+                _ => FailoverRole::Secondary,
+            };
+            return Some(RoutingPolicy::Failover { role });
+        }
+        if let Some(geo_location) = &record.geo_location {
+            return Some(RoutingPolicy::Geolocation {
+                continent_code: geo_location.continent_code.clone(),
+                country_code: geo_location.country_code.clone(),
+                subdivision_code: geo_location.subdivision_code.clone(),
+            });
+        }
+        if let Some(geo_proximity_location) = &record.geo_proximity_location {
+            return Some(RoutingPolicy::Geoproximity {
+                aws_region: geo_proximity_location.aws_region.clone(),
+                local_zone_group: geo_proximity_location.local_zone_group.clone(),
+                coordinates: geo_proximity_location.coordinates.as_ref().map(|coordinates| Coordinates {
+                    latitude: coordinates.latitude.clone(),
+                    longitude: coordinates.longitude.clone(),
+                }),
+                bias: geo_proximity_location.bias,
+            });
+        }
+        if record.multi_value_answer == Some(true) {
+            return Some(RoutingPolicy::MultivalueAnswer);
+        }
+        None
+    }
+}
+
+fn check_coordinate(name: &str, field: &str, value: &str, limit: f64) -> anyhow::Result<()> {
+    match value.parse::<f64>() {
+        Ok(value) if (-limit..=limit).contains(&value) => Ok(()),
+        _ => bail!("Record {} has {} {}; expected a number from -{} to {}", name, field, value, limit, limit),
+    }
+}
+
+/// Checks a record set's routing fields at plan time. Record sets at a set identifier need a routing
+/// policy; record sets without one can't have a routing policy or health check.
+pub fn check_routing_policy(name: &str, set_identifier: Option<&str>, record_set: &RecordSet) -> anyhow::Result<()> {
+    let Some(set_identifier) = set_identifier else {
+        if record_set.routing_policy.is_some() {
+            bail!(
+                "Record {} has a routing_policy, so it needs a set identifier: move it to records/<type>/<record_name>/<set_identifier>.ron",
+                name
+            );
+        }
+        if record_set.health_check_id.is_some() {
+            bail!("Record {} has a health_check_id, but health checks only apply to record sets with a routing policy", name);
+        }
+        return Ok(());
+    };
+
+    if set_identifier.is_empty() || set_identifier.len() > MAX_SET_IDENTIFIER_LEN {
+        bail!(
+            "Record {} has set identifier {}; expected 1 to {} characters",
+            name,
+            set_identifier,
+            MAX_SET_IDENTIFIER_LEN
+        );
+    }
+
+    let Some(routing_policy) = &record_set.routing_policy else {
+        bail!("Record {} at set identifier {} needs a routing_policy", name, set_identifier);
+    };
+
+    match routing_policy {
+        RoutingPolicy::Weighted { weight } => {
+            if !(0..=255).contains(weight) {
+                bail!("Record {} has weight {}; expected 0 to 255", name, weight);
+            }
+        }
+        RoutingPolicy::Latency { region } => {
+            if region.is_empty() {
+                bail!("Record {} has latency routing, but no region", name);
+            }
+        }
+        RoutingPolicy::Failover { .. } => {
+            // Those set identifiers belong to failover pairs
+            if FailoverRole::from_set_identifier(set_identifier).is_some() {
+                bail!(
+                    "Record {} uses set identifier {}, which failover pairs use; pick another, or manage both records as a failover pair",
+                    name,
+                    set_identifier
+                );
+            }
+        }
+        RoutingPolicy::Geolocation {
+            continent_code,
+            country_code,
+            subdivision_code,
+        } => {
+            match (continent_code, country_code) {
+                (Some(_), Some(_)) | (None, None) => {
+                    bail!("Record {} needs exactly one of continent_code and country_code", name)
+                }
+                (Some(continent_code), None) if !CONTINENT_CODES.contains(&continent_code.as_str()) => bail!(
+                    "Record {} has continent_code {}; expected one of {}",
+                    name,
+                    continent_code,
+                    CONTINENT_CODES.join(", ")
+                ),
+                (None, Some(country_code)) if country_code != "*" && country_code.len() != 2 => {
+                    bail!("Record {} has country_code {}; expected a two-letter code, or *", name, country_code)
+                }
+                _ => {}
+            }
+            if subdivision_code.is_some() && country_code.as_ref().is_none_or(|c| c == "*") {
+                bail!("Record {} has a subdivision_code, which needs a country_code", name);
+            }
+        }
+        RoutingPolicy::Geoproximity {
+            aws_region,
+            local_zone_group,
+            coordinates,
+            bias,
+        } => {
+            let locations = [aws_region.is_some(), local_zone_group.is_some(), coordinates.is_some()];
+            if locations.iter().filter(|set| **set).count() != 1 {
+                bail!("Record {} needs exactly one of aws_region, local_zone_group and coordinates", name);
+            }
+            if let Some(coordinates) = coordinates {
+                check_coordinate(name, "latitude", &coordinates.latitude, 90.0)?;
+                check_coordinate(name, "longitude", &coordinates.longitude, 180.0)?;
+            }
+            if let Some(bias) = bias
+                && !(-99..=99).contains(bias)
+            {
+                bail!("Record {} has bias {}; expected -99 to 99", name, bias);
+            }
+        }
+        RoutingPolicy::MultivalueAnswer => {
+            if record_set.alias_target.is_some() {
+                bail!("Record {} uses multivalue answer routing, which can't be used with an alias_target", name);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::{PrettyConfig, RON};
+
+#[derive(Debug, Clone)]
+pub enum Route53TaskAddress {
+    VerifyHttpsFrontend { name: String },
+}
+
+impl ResourceAddress for Route53TaskAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            Route53TaskAddress::VerifyHttpsFrontend { name } => {
+                PathBuf::from(format!("aws/route53/tasks/verify-https-frontend/{name}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        let path_components: Vec<&str> = path
+            .components()
+            .map(|s| s.as_os_str().to_str().context("Path component is not valid UTF-8"))
+            .collect::<Result<Vec<&str>, anyhow::Error>>()?;
+
+        match &path_components[..] {
+            ["aws", "route53", "tasks", "verify-https-frontend", name] if name.ends_with(".ron") => {
+                Ok(Route53TaskAddress::VerifyHttpsFrontend {
+                    name: name.strip_suffix(".ron").context("File name must end with .ron")?.to_string(),
+                })
+            }
+            _ => Err(anyhow::anyhow!("Invalid Route53 task address: {}", path.display())),
+        }
+    }
+}
+
+impl DescribeAddresses for Route53TaskAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![AddressPattern {
+            pattern:     "aws/route53/tasks/verify-https-frontend/<name>.ron",
+            description: "Task: follow a DNS name to its load balancer or CloudFront distribution and report the first broken link",
+            example:     "aws/route53/tasks/verify-https-frontend/www.ron",
+        }]
+    }
+}
+
+/// Follows an HTTPS endpoint from its Route53 record through to the targets serving it, and reports
+/// the first link in the chain that's broken:
+///  - the record exists in a hosted zone in this account, and the name resolves,
+///  - the record points at an application load balancer or a CloudFront distribution,
+///  - the certificate served for `dns_name` is issued, unexpired and covers the name,
+///  - for load balancers, the HTTPS listener forwards the name to a target group with healthy targets.
+///
+/// CloudFront origins aren't followed. The result is written to the task outputs as `status`
+/// ("ok" or "broken") and, if broken, `broken_link`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct VerifyHttpsFrontend {
+    pub dns_name: String,
+    /// The load balancer listener port.
+    #[serde(default = "default_https_port")]
+    pub port: i32,
+}
+
+fn default_https_port() -> i32 {
+    443
+}
+
+pub enum Route53Task {
+    VerifyHttpsFrontend(VerifyHttpsFrontend),
+}
+
+impl Resource for Route53Task {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = PrettyConfig::default().struct_names(true);
+        match self {
+            Route53Task::VerifyHttpsFrontend(verify) => match RON.to_string_pretty(&verify, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = Route53TaskAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+        match addr {
+            Route53TaskAddress::VerifyHttpsFrontend { .. } => Ok(Route53Task::VerifyHttpsFrontend(RON.from_str(s)?)),
+        }
+    }
+}
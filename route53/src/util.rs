@@ -1,4 +1,14 @@
 
+/// Lowercases a DNS name, and drops its trailing dot and the `dualstack.` prefix that
+/// alias targets for load balancers carry.
+pub fn normalize_dns_name(name: &str) -> String {
+    let name = name.trim_end_matches('.').to_ascii_lowercase();
+    match name.strip_prefix("dualstack.") {
+        Some(name) => name.to_string(),
+        None => name,
+    }
+}
+
 pub async fn list_hosted_zones(
     client: &aws_sdk_route53::Client,
 ) -> Result<Vec<(String, String)>, anyhow::Error> {
@@ -29,10 +39,11 @@ pub async fn list_hosted_zones(
     Ok(results)
 }
 
+/// Lists (name, type, set_identifier, is_failover) for every record set in the hosted zone.
 pub async fn list_resource_record_sets(
     client: &aws_sdk_route53::Client,
     hosted_zone_id: &str,
-) -> Result<Vec<(String, String)>, anyhow::Error> {
+) -> Result<Vec<(String, String, Option<String>, bool)>, anyhow::Error> {
     let mut results = Vec::new();
 
     let hosted_zone_id = String::from(hosted_zone_id);
@@ -44,7 +55,8 @@ pub async fn list_resource_record_sets(
         .await?;
 
     for record in list_result.resource_record_sets {
-        results.push((record.name, record.r#type.to_string()))
+        let is_failover = record.failover.is_some();
+        results.push((record.name, record.r#type.to_string(), record.set_identifier, is_failover))
     }
 
     loop {
@@ -53,13 +65,16 @@ pub async fn list_resource_record_sets(
             list_result = client
                 .list_resource_record_sets()
                 .set_hosted_zone_id(Some(hosted_zone_id.clone()))
+                .set_start_record_name(list_result.next_record_name)
+                .set_start_record_type(list_result.next_record_type)
                 .set_start_record_identifier(list_result.next_record_identifier)
                 .send()
                 .await?;
 
 
             for record in list_result.resource_record_sets {
-                results.push((record.name, record.r#type.to_string()));
+                let is_failover = record.failover.is_some();
+                results.push((record.name, record.r#type.to_string(), record.set_identifier, is_failover));
             }
         } else {
             break;
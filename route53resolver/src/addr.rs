@@ -0,0 +1,110 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+/// Resolver endpoints, rules and firewall rule groups get generated IDs, so they're addressed by name instead.
+#[derive(Debug, Clone)]
+pub enum Route53ResolverResourceAddress {
+    Endpoint {
+        region: String,
+        name:   String,
+    },
+    Rule {
+        region: String,
+        name:   String,
+    },
+    /// A rule has at most one association per VPC, so associations are addressed by VPC.
+    RuleAssociation {
+        region: String,
+        rule:   String,
+        vpc_id: String,
+    },
+    FirewallRuleGroup {
+        region: String,
+        name:   String,
+    },
+}
+
+impl ResourceAddress for Route53ResolverResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            Route53ResolverResourceAddress::Endpoint { region, name } => {
+                PathBuf::from(format!("aws/route53resolver/{region}/endpoints/{name}.ron"))
+            }
+            Route53ResolverResourceAddress::Rule { region, name } => {
+                PathBuf::from(format!("aws/route53resolver/{region}/rules/{name}.ron"))
+            }
+            Route53ResolverResourceAddress::RuleAssociation { region, rule, vpc_id } => PathBuf::from(format!(
+                "aws/route53resolver/{region}/rules/{rule}/associations/{vpc_id}.ron"
+            )),
+            Route53ResolverResourceAddress::FirewallRuleGroup { region, name } => {
+                PathBuf::from(format!("aws/route53resolver/{region}/firewall_rule_groups/{name}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "route53resolver", region, "endpoints", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(Route53ResolverResourceAddress::Endpoint {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "route53resolver", region, "rules", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(Route53ResolverResourceAddress::Rule {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "route53resolver", region, "rules", rule, "associations", vpc_id] if vpc_id.ends_with(".ron") => {
+                let vpc_id = vpc_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(Route53ResolverResourceAddress::RuleAssociation {
+                    region: region.to_string(),
+                    rule: rule.to_string(),
+                    vpc_id,
+                })
+            }
+            ["aws", "route53resolver", region, "firewall_rule_groups", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(Route53ResolverResourceAddress::FirewallRuleGroup {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for Route53ResolverResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/route53resolver/<region>/endpoints/<name>.ron",
+                description: "An inbound or outbound Resolver endpoint and its IP addresses",
+                example:     "aws/route53resolver/us-east-1/endpoints/onprem-outbound.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/route53resolver/<region>/rules/<name>.ron",
+                description: "A Resolver rule, which forwards queries for a domain through an outbound endpoint",
+                example:     "aws/route53resolver/us-east-1/rules/corp-example-com.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/route53resolver/<region>/rules/<rule>/associations/<vpc_id>.ron",
+                description: "The association of a Resolver rule with a VPC",
+                example:     "aws/route53resolver/us-east-1/rules/corp-example-com/associations/vpc-0123456789abcdef0.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/route53resolver/<region>/firewall_rule_groups/<name>.ron",
+                description: "A DNS Firewall rule group and its rules",
+                example:     "aws/route53resolver/us-east-1/firewall_rule_groups/block-malware.ron",
+            },
+        ]
+    }
+}
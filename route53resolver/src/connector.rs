@@ -0,0 +1,239 @@
+pub use crate::addr::Route53ResolverResourceAddress;
+pub use crate::op::Route53ResolverConnectorOp;
+pub use crate::resource::Route53ResolverResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::Route53ResolverConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{
+    FirewallRule, FirewallRuleGroup, IpAddress, ResolverEndpoint, ResolverRule, ResolverRuleAssociation, TargetAddress,
+};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct Route53ResolverConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_route53resolver::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<Route53ResolverConnectorConfig>,
+    prefix: PathBuf,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl Route53ResolverConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_route53resolver::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_route53resolver, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+}
+
+#[async_trait]
+impl Connector for Route53ResolverConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = Route53ResolverResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(Route53ResolverConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let resolver_config: Route53ResolverConnectorConfig = Route53ResolverConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = resolver_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(resolver_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = resolver_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        res.push(skeleton!(
+            Route53ResolverResourceAddress::Endpoint {
+                region: String::from("[region]"),
+                name:   String::from("[endpoint_name]"),
+            },
+            Route53ResolverResource::Endpoint(ResolverEndpoint {
+                direction: String::from("OUTBOUND"),
+                security_group_ids: vec![String::from("[security_group_id]")],
+                ip_addresses: vec![
+                    IpAddress {
+                        subnet_id: String::from("[subnet_id_a]"),
+                        ip: None,
+                    },
+                    IpAddress {
+                        subnet_id: String::from("[subnet_id_b]"),
+                        ip: None,
+                    },
+                ],
+                resolver_endpoint_type: None,
+                protocols: Vec::new(),
+                tags: Tags::default(),
+            })
+        ));
+
+        // Forwards queries for a domain to DNS servers outside the VPC
+        res.push(skeleton!(
+            Route53ResolverResourceAddress::Rule {
+                region: String::from("[region]"),
+                name:   String::from("[rule_name]"),
+            },
+            Route53ResolverResource::Rule(ResolverRule {
+                rule_type: String::from("FORWARD"),
+                domain_name: String::from("[domain_name]"),
+                target_ips: vec![TargetAddress {
+                    ip: String::from("[dns_server_ip]"),
+                    port: None,
+                    protocol: None,
+                }],
+                resolver_endpoint: Some(String::from("[endpoint_name]")),
+                tags: Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            Route53ResolverResourceAddress::RuleAssociation {
+                region: String::from("[region]"),
+                rule:   String::from("[rule_name]"),
+                vpc_id: String::from("[vpc_id]"),
+            },
+            Route53ResolverResource::RuleAssociation(ResolverRuleAssociation { name: None })
+        ));
+
+        res.push(skeleton!(
+            Route53ResolverResourceAddress::FirewallRuleGroup {
+                region: String::from("[region]"),
+                name:   String::from("[rule_group_name]"),
+            },
+            Route53ResolverResource::FirewallRuleGroup(FirewallRuleGroup {
+                rules: vec![FirewallRule {
+                    name: String::from("[rule_name]"),
+                    firewall_domain_list_id: String::from("[firewall_domain_list_id]"),
+                    priority: 100,
+                    action: String::from("BLOCK"),
+                    block_response: Some(String::from("NXDOMAIN")),
+                    block_override_domain: None,
+                    block_override_ttl: None,
+                }],
+                tags: Tags::default(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = Route53ResolverResourceAddress::from_path(addr)?;
+
+        match addr {
+            Route53ResolverResourceAddress::Endpoint { .. } => ron_check_eq::<ResolverEndpoint>(a, b),
+            Route53ResolverResourceAddress::Rule { .. } => ron_check_eq::<ResolverRule>(a, b),
+            Route53ResolverResourceAddress::RuleAssociation { .. } => ron_check_eq::<ResolverRuleAssociation>(a, b),
+            Route53ResolverResourceAddress::FirewallRuleGroup { .. } => ron_check_eq::<FirewallRuleGroup>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = Route53ResolverResourceAddress::from_path(addr)?;
+
+        match addr {
+            Route53ResolverResourceAddress::Endpoint { .. } => ron_check_syntax::<ResolverEndpoint>(a),
+            Route53ResolverResourceAddress::Rule { .. } => ron_check_syntax::<ResolverRule>(a),
+            Route53ResolverResourceAddress::RuleAssociation { .. } => ron_check_syntax::<ResolverRuleAssociation>(a),
+            Route53ResolverResourceAddress::FirewallRuleGroup { .. } => ron_check_syntax::<FirewallRuleGroup>(a),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_route53resolver::Client;
+
+use crate::{
+    addr::Route53ResolverResourceAddress,
+    op_impl::{find_endpoint, find_endpoint_by_id, find_firewall_rule_group, find_rule, find_rule_association, list_firewall_rules},
+    resource::{
+        FirewallRule, FirewallRuleGroup, IpAddress, ResolverEndpoint, ResolverRule, ResolverRuleAssociation,
+        Route53ResolverResource,
+    },
+    tags::Tags,
+    util::from_sdk_target_address,
+};
+
+use super::Route53ResolverConnector;
+
+async fn list_tags(client: &Client, arn: &str) -> anyhow::Result<Tags> {
+    let resp = client.list_tags_for_resource().resource_arn(arn).send().await?;
+    Ok(Tags::from(resp.tags.unwrap_or_default()))
+}
+
+impl Route53ResolverConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = Route53ResolverResourceAddress::from_path(addr)?;
+        let account_id = self.account_id.lock().await.clone();
+
+        match &addr {
+            Route53ResolverResourceAddress::Endpoint { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(endpoint) = find_endpoint(&client, name).await? else {
+                    return Ok(None);
+                };
+                if endpoint.status.as_ref().is_some_and(|s| s.as_str() == "DELETING") {
+                    return Ok(None);
+                }
+
+                let id = endpoint.id.unwrap_or_default();
+                let arn = endpoint.arn.unwrap_or_default();
+
+                let mut ip_addresses: Vec<IpAddress> = client
+                    .list_resolver_endpoint_ip_addresses()
+                    .resolver_endpoint_id(&id)
+                    .send()
+                    .await?
+                    .ip_addresses
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|ip_address| IpAddress {
+                        subnet_id: ip_address.subnet_id.unwrap_or_default(),
+                        ip: ip_address.ip,
+                    })
+                    .collect();
+                ip_addresses.sort();
+
+                let mut security_group_ids = endpoint.security_group_ids.unwrap_or_default();
+                security_group_ids.sort();
+
+                let resource = ResolverEndpoint {
+                    direction: endpoint.direction.map(|d| d.as_str().to_string()).unwrap_or_default(),
+                    security_group_ids,
+                    ip_addresses,
+                    resolver_endpoint_type: endpoint.resolver_endpoint_type.map(|t| t.as_str().to_string()),
+                    protocols: endpoint
+                        .protocols
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|p| p.as_str().to_string())
+                        .collect(),
+                    tags: list_tags(&client, &arn).await?,
+                };
+
+                get_resource_response!(
+                    Route53ResolverResource::Endpoint(resource),
+                    [
+                        (String::from("resolver_endpoint_id"), id),
+                        (String::from("resolver_endpoint_arn"), arn)
+                    ]
+                )
+            }
+            Route53ResolverResourceAddress::Rule { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                // Rules shared from other accounts and Resolver's own rules can be associated, but not managed here
+                let Some(rule) = find_rule(&client, name)
+                    .await?
+                    .filter(|rule| rule.owner_id.as_deref() == Some(account_id.as_str()))
+                else {
+                    return Ok(None);
+                };
+                if rule.status.as_ref().is_some_and(|s| s.as_str() == "DELETING") {
+                    return Ok(None);
+                }
+
+                let id = rule.id.unwrap_or_default();
+                let arn = rule.arn.unwrap_or_default();
+
+                // Rules refer to their outbound endpoint by ID, but rule files refer to it by name
+                let resolver_endpoint = match &rule.resolver_endpoint_id {
+                    Some(endpoint_id) => Some(
+                        find_endpoint_by_id(&client, endpoint_id)
+                            .await?
+                            .and_then(|endpoint| endpoint.name)
+                            .unwrap_or_else(|| endpoint_id.clone()),
+                    ),
+                    None => None,
+                };
+
+                let resource = ResolverRule {
+                    rule_type: rule.rule_type.map(|t| t.as_str().to_string()).unwrap_or_default(),
+                    // Resolver reports domain names with a trailing dot
+                    domain_name: rule
+                        .domain_name
+                        .map(|d| d.trim_end_matches('.').to_string())
+                        .unwrap_or_default(),
+                    target_ips: rule
+                        .target_ips
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(from_sdk_target_address)
+                        .collect(),
+                    resolver_endpoint,
+                    tags: list_tags(&client, &arn).await?,
+                };
+
+                get_resource_response!(
+                    Route53ResolverResource::Rule(resource),
+                    [
+                        (String::from("resolver_rule_id"), id),
+                        (String::from("resolver_rule_arn"), arn)
+                    ]
+                )
+            }
+            Route53ResolverResourceAddress::RuleAssociation { region, rule, vpc_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(rule_id) = find_rule(&client, rule).await?.and_then(|rule| rule.id) else {
+                    return Ok(None);
+                };
+
+                let Some(association) = find_rule_association(&client, &rule_id, vpc_id).await? else {
+                    return Ok(None);
+                };
+                if association.status.as_ref().is_some_and(|s| s.as_str() == "DELETING") {
+                    return Ok(None);
+                }
+
+                let resource = ResolverRuleAssociation {
+                    name: association.name.filter(|n| !n.is_empty()),
+                };
+
+                get_resource_response!(
+                    Route53ResolverResource::RuleAssociation(resource),
+                    [(String::from("resolver_rule_association_id"), association.id.unwrap_or_default())]
+                )
+            }
+            Route53ResolverResourceAddress::FirewallRuleGroup { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(group) = find_firewall_rule_group(&client, name)
+                    .await?
+                    .filter(|group| group.owner_id.as_deref() == Some(account_id.as_str()))
+                else {
+                    return Ok(None);
+                };
+
+                let id = group.id.unwrap_or_default();
+                let arn = group.arn.unwrap_or_default();
+
+                let mut rules: Vec<FirewallRule> = list_firewall_rules(&client, &id)
+                    .await?
+                    .into_iter()
+                    .map(|rule| FirewallRule {
+                        name: rule.name.unwrap_or_default(),
+                        firewall_domain_list_id: rule.firewall_domain_list_id.unwrap_or_default(),
+                        priority: rule.priority.unwrap_or_default(),
+                        action: rule.action.map(|a| a.as_str().to_string()).unwrap_or_default(),
+                        block_response: rule.block_response.map(|r| r.as_str().to_string()),
+                        block_override_domain: rule.block_override_domain,
+                        block_override_ttl: rule.block_override_ttl,
+                    })
+                    .collect();
+                rules.sort_by_key(|rule| rule.priority);
+
+                let resource = FirewallRuleGroup {
+                    rules,
+                    tags: list_tags(&client, &arn).await?,
+                };
+
+                get_resource_response!(
+                    Route53ResolverResource::FirewallRuleGroup(resource),
+                    [
+                        (String::from("firewall_rule_group_id"), id),
+                        (String::from("firewall_rule_group_arn"), arn)
+                    ]
+                )
+            }
+        }
+    }
+}
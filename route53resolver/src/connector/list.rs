@@ -0,0 +1,138 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::addr::Route53ResolverResourceAddress;
+
+use super::Route53ResolverConnector;
+
+impl Route53ResolverConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let account_id = self.account_id.lock().await.clone();
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut next_token = None;
+            loop {
+                let resp = client.list_resolver_endpoints().set_next_token(next_token).send().await?;
+
+                for endpoint in resp.resolver_endpoints() {
+                    let Some(name) = &endpoint.name else {
+                        continue;
+                    };
+
+                    results.push(
+                        Route53ResolverResourceAddress::Endpoint {
+                            region: region.clone(),
+                            name:   name.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+
+            // Associations are listed under their rule's name, so only rules owned by this account are kept
+            let mut rule_names = HashMap::new();
+            let mut next_token = None;
+            loop {
+                let resp = client.list_resolver_rules().set_next_token(next_token).send().await?;
+
+                for rule in resp.resolver_rules() {
+                    if rule.owner_id.as_deref() != Some(account_id.as_str()) {
+                        continue;
+                    }
+                    let (Some(id), Some(name)) = (&rule.id, &rule.name) else {
+                        continue;
+                    };
+
+                    rule_names.insert(id.clone(), name.clone());
+                    results.push(
+                        Route53ResolverResourceAddress::Rule {
+                            region: region.clone(),
+                            name:   name.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+
+            let mut next_token = None;
+            loop {
+                let resp = client
+                    .list_resolver_rule_associations()
+                    .set_next_token(next_token)
+                    .send()
+                    .await?;
+
+                for association in resp.resolver_rule_associations() {
+                    let (Some(rule_id), Some(vpc_id)) = (&association.resolver_rule_id, &association.vpc_id) else {
+                        continue;
+                    };
+                    let Some(rule) = rule_names.get(rule_id) else {
+                        continue;
+                    };
+
+                    results.push(
+                        Route53ResolverResourceAddress::RuleAssociation {
+                            region: region.clone(),
+                            rule:   rule.clone(),
+                            vpc_id: vpc_id.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+
+            let mut next_token = None;
+            loop {
+                let resp = client.list_firewall_rule_groups().set_next_token(next_token).send().await?;
+
+                for group in resp.firewall_rule_groups() {
+                    // Skip groups shared with this account and AWS managed groups
+                    if group.owner_id.as_deref() != Some(account_id.as_str()) {
+                        continue;
+                    }
+                    let Some(name) = &group.name else {
+                        continue;
+                    };
+
+                    results.push(
+                        Route53ResolverResourceAddress::FirewallRuleGroup {
+                            region: region.clone(),
+                            name:   name.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
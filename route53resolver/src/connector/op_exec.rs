@@ -0,0 +1,76 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{ConnectorOp, OpExecResponse, ResourceAddress},
+    error_util::invalid_op,
+};
+
+use crate::{addr::Route53ResolverResourceAddress, op::Route53ResolverConnectorOp, op_impl};
+
+use super::Route53ResolverConnector;
+
+impl Route53ResolverConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = Route53ResolverResourceAddress::from_path(addr)?;
+        let op = Route53ResolverConnectorOp::from_str(op)?;
+
+        match &addr {
+            Route53ResolverResourceAddress::Endpoint { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    Route53ResolverConnectorOp::CreateEndpoint(endpoint) => op_impl::create_endpoint(&client, name, &endpoint).await,
+                    Route53ResolverConnectorOp::UpdateEndpoint(endpoint) => op_impl::update_endpoint(&client, name, &endpoint).await,
+                    Route53ResolverConnectorOp::UpdateEndpointTags(old_tags, new_tags) => {
+                        op_impl::update_endpoint_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    Route53ResolverConnectorOp::DeleteEndpoint => op_impl::delete_endpoint(&client, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            Route53ResolverResourceAddress::Rule { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    Route53ResolverConnectorOp::CreateRule(rule) => op_impl::create_rule(&client, name, &rule).await,
+                    Route53ResolverConnectorOp::UpdateRule(rule) => op_impl::update_rule(&client, name, &rule).await,
+                    Route53ResolverConnectorOp::UpdateRuleTags(old_tags, new_tags) => {
+                        op_impl::update_rule_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    Route53ResolverConnectorOp::DeleteRule => op_impl::delete_rule(&client, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            Route53ResolverResourceAddress::RuleAssociation { region, rule, vpc_id } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    Route53ResolverConnectorOp::AssociateRule(association) => {
+                        op_impl::associate_rule(&client, rule, vpc_id, &association).await
+                    }
+                    Route53ResolverConnectorOp::DisassociateRule => op_impl::disassociate_rule(&client, rule, vpc_id).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            Route53ResolverResourceAddress::FirewallRuleGroup { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    Route53ResolverConnectorOp::CreateFirewallRuleGroup(group) => {
+                        op_impl::create_firewall_rule_group(&client, name, &group).await
+                    }
+                    Route53ResolverConnectorOp::UpdateFirewallRules(rules) => {
+                        op_impl::update_firewall_rules(&client, name, &rules).await
+                    }
+                    Route53ResolverConnectorOp::UpdateFirewallRuleGroupTags(old_tags, new_tags) => {
+                        op_impl::update_firewall_rule_group_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    Route53ResolverConnectorOp::DeleteFirewallRuleGroup => {
+                        op_impl::delete_firewall_rule_group(&client, name).await
+                    }
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use std::{collections::HashSet, path::Path};
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::resource::{FirewallRuleGroup, ResolverEndpoint, ResolverRule, ResolverRuleAssociation};
+
+use super::{Route53ResolverConnector, Route53ResolverConnectorOp, Route53ResolverResourceAddress};
+
+const DIRECTIONS: &[&str] = &["INBOUND", "OUTBOUND"];
+const ENDPOINT_TYPES: &[&str] = &["IPV4", "IPV6", "DUALSTACK"];
+const RULE_TYPES: &[&str] = &["FORWARD", "SYSTEM"];
+const FIREWALL_ACTIONS: &[&str] = &["ALLOW", "BLOCK", "ALERT"];
+const BLOCK_RESPONSES: &[&str] = &["NODATA", "NXDOMAIN", "OVERRIDE"];
+
+fn check_endpoint(name: &str, endpoint: &ResolverEndpoint) -> anyhow::Result<()> {
+    if !DIRECTIONS.contains(&endpoint.direction.as_str()) {
+        bail!(
+            "Resolver endpoint {} has direction {}: expected one of {}",
+            name,
+            endpoint.direction,
+            DIRECTIONS.join(", ")
+        );
+    }
+    if let Some(endpoint_type) = &endpoint.resolver_endpoint_type
+        && !ENDPOINT_TYPES.contains(&endpoint_type.as_str())
+    {
+        bail!(
+            "Resolver endpoint {} has endpoint type {}: expected one of {}",
+            name,
+            endpoint_type,
+            ENDPOINT_TYPES.join(", ")
+        );
+    }
+    if endpoint.ip_addresses.len() < 2 {
+        bail!("Resolver endpoint {} needs at least two IP addresses", name);
+    }
+    Ok(())
+}
+
+/// Resolver picks addresses and fills in defaults for fields left out, so leaving them out doesn't change them.
+fn normalize_endpoint(old: &ResolverEndpoint, new: &mut ResolverEndpoint) {
+    if new.resolver_endpoint_type.is_none() {
+        new.resolver_endpoint_type = old.resolver_endpoint_type.clone();
+    }
+    if new.protocols.is_empty() {
+        new.protocols = old.protocols.clone();
+    }
+    new.security_group_ids.sort();
+
+    // An address without an IP takes the IP of an existing address in the same subnet that isn't otherwise claimed
+    let mut unclaimed: Vec<_> = old
+        .ip_addresses
+        .iter()
+        .filter(|old_ip| !new.ip_addresses.iter().any(|new_ip| new_ip.ip.is_some() && new_ip.ip == old_ip.ip))
+        .collect();
+    for ip_address in new.ip_addresses.iter_mut().filter(|ip_address| ip_address.ip.is_none()) {
+        if let Some(i) = unclaimed.iter().position(|old_ip| old_ip.subnet_id == ip_address.subnet_id) {
+            ip_address.ip = unclaimed.remove(i).ip.clone();
+        }
+    }
+    new.ip_addresses.sort();
+}
+
+fn check_rule(name: &str, rule: &ResolverRule) -> anyhow::Result<()> {
+    if !RULE_TYPES.contains(&rule.rule_type.as_str()) {
+        bail!(
+            "Resolver rule {} has rule type {}: expected one of {}",
+            name,
+            rule.rule_type,
+            RULE_TYPES.join(", ")
+        );
+    }
+    if rule.rule_type == "FORWARD" && (rule.target_ips.is_empty() || rule.resolver_endpoint.is_none()) {
+        bail!("Resolver rule {} is a FORWARD rule, so it needs target_ips and a resolver_endpoint", name);
+    }
+    if rule.rule_type == "SYSTEM" && (!rule.target_ips.is_empty() || rule.resolver_endpoint.is_some()) {
+        bail!("Resolver rule {} is a SYSTEM rule, which can't have target_ips or a resolver_endpoint", name);
+    }
+    Ok(())
+}
+
+fn check_firewall_rule_group(name: &str, group: &FirewallRuleGroup) -> anyhow::Result<()> {
+    let mut domain_lists = HashSet::new();
+    let mut priorities = HashSet::new();
+
+    for rule in &group.rules {
+        let what = format!("Rule {} in DNS Firewall rule group {}", rule.name, name);
+        if !FIREWALL_ACTIONS.contains(&rule.action.as_str()) {
+            bail!("{} has action {}: expected one of {}", what, rule.action, FIREWALL_ACTIONS.join(", "));
+        }
+        match (rule.action.as_str(), rule.block_response.as_deref()) {
+            ("BLOCK", None) => bail!("{} blocks queries, so it needs a block_response", what),
+            ("BLOCK", Some(block_response)) if !BLOCK_RESPONSES.contains(&block_response) => bail!(
+                "{} has block response {}: expected one of {}",
+                what,
+                block_response,
+                BLOCK_RESPONSES.join(", ")
+            ),
+            ("BLOCK", Some("OVERRIDE")) if rule.block_override_domain.is_none() => {
+                bail!("{} overrides responses, so it needs a block_override_domain", what)
+            }
+            ("ALLOW" | "ALERT", Some(_)) => bail!("{} doesn't block queries, so it can't have a block_response", what),
+            _ => {}
+        }
+        if !domain_lists.insert(&rule.firewall_domain_list_id) {
+            bail!(
+                "DNS Firewall rule group {} has more than one rule for domain list {}",
+                name,
+                rule.firewall_domain_list_id
+            );
+        }
+        if !priorities.insert(rule.priority) {
+            bail!("DNS Firewall rule group {} has more than one rule with priority {}", name, rule.priority);
+        }
+    }
+    Ok(())
+}
+
+impl Route53ResolverConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = Route53ResolverResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            Route53ResolverResourceAddress::Endpoint { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_endpoint)) => {
+                    let new_endpoint: ResolverEndpoint = RON.from_str(&new_endpoint)?;
+                    check_endpoint(name, &new_endpoint)?;
+                    Ok(vec![connector_op!(
+                        Route53ResolverConnectorOp::CreateEndpoint(new_endpoint),
+                        format!("Create new Resolver endpoint {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_endpoint), None) => Ok(vec![connector_op!(
+                    Route53ResolverConnectorOp::DeleteEndpoint,
+                    format!(
+                        "DELETE Resolver endpoint {} in region {}. Rules using it must be deleted first.",
+                        name, region
+                    )
+                )]),
+                (Some(old_endpoint), Some(new_endpoint)) => {
+                    let old_endpoint: ResolverEndpoint = RON.from_str(&old_endpoint)?;
+                    let mut new_endpoint: ResolverEndpoint = RON.from_str(&new_endpoint)?;
+                    check_endpoint(name, &new_endpoint)?;
+                    normalize_endpoint(&old_endpoint, &mut new_endpoint);
+
+                    if old_endpoint.direction != new_endpoint.direction
+                        || old_endpoint.security_group_ids != new_endpoint.security_group_ids
+                    {
+                        bail!(
+                            "Resolver endpoint {} can't change direction or security_group_ids after creation. Create a new endpoint under another name instead.",
+                            name
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_endpoint.tags != new_endpoint.tags {
+                        let diff = diff_ron_values(&old_endpoint.tags, &new_endpoint.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            Route53ResolverConnectorOp::UpdateEndpointTags(old_endpoint.tags.clone(), new_endpoint.tags.clone()),
+                            format!("Modify tags for Resolver endpoint `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_endpoint.clone();
+                    old_settings.tags = new_endpoint.tags.clone();
+                    if old_settings != new_endpoint {
+                        let diff = diff_ron_values(&old_settings, &new_endpoint).unwrap_or_default();
+                        ops.push(connector_op!(
+                            Route53ResolverConnectorOp::UpdateEndpoint(new_endpoint),
+                            format!("Modify Resolver endpoint `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            Route53ResolverResourceAddress::Rule { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_rule)) => {
+                    let new_rule: ResolverRule = RON.from_str(&new_rule)?;
+                    check_rule(name, &new_rule)?;
+                    Ok(vec![connector_op!(
+                        Route53ResolverConnectorOp::CreateRule(new_rule),
+                        format!("Create new Resolver rule {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_rule), None) => Ok(vec![connector_op!(
+                    Route53ResolverConnectorOp::DeleteRule,
+                    format!(
+                        "DELETE Resolver rule {} in region {}. It must be disassociated from all VPCs first.",
+                        name, region
+                    )
+                )]),
+                (Some(old_rule), Some(new_rule)) => {
+                    let old_rule: ResolverRule = RON.from_str(&old_rule)?;
+                    let mut new_rule: ResolverRule = RON.from_str(&new_rule)?;
+                    check_rule(name, &new_rule)?;
+                    new_rule.domain_name = new_rule.domain_name.trim_end_matches('.').to_string();
+
+                    if old_rule.rule_type != new_rule.rule_type || old_rule.domain_name != new_rule.domain_name {
+                        bail!(
+                            "Resolver rule {} can't change rule_type or domain_name after creation. Create a new rule under another name instead.",
+                            name
+                        );
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_rule.tags != new_rule.tags {
+                        let diff = diff_ron_values(&old_rule.tags, &new_rule.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            Route53ResolverConnectorOp::UpdateRuleTags(old_rule.tags.clone(), new_rule.tags.clone()),
+                            format!("Modify tags for Resolver rule `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_rule.clone();
+                    old_settings.tags = new_rule.tags.clone();
+                    if old_settings != new_rule {
+                        let diff = diff_ron_values(&old_settings, &new_rule).unwrap_or_default();
+                        ops.push(connector_op!(
+                            Route53ResolverConnectorOp::UpdateRule(new_rule),
+                            format!("Modify Resolver rule `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            Route53ResolverResourceAddress::RuleAssociation { rule, vpc_id, .. } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_association)) => {
+                    let new_association: ResolverRuleAssociation = RON.from_str(&new_association)?;
+                    Ok(vec![connector_op!(
+                        Route53ResolverConnectorOp::AssociateRule(new_association),
+                        format!("Associate Resolver rule {} with {}", rule, vpc_id)
+                    )])
+                }
+                (Some(_old_association), None) => Ok(vec![connector_op!(
+                    Route53ResolverConnectorOp::DisassociateRule,
+                    format!("DELETE association of Resolver rule {} with {}", rule, vpc_id)
+                )]),
+                (Some(old_association), Some(new_association)) => {
+                    let old_association: ResolverRuleAssociation = RON.from_str(&old_association)?;
+                    let new_association: ResolverRuleAssociation = RON.from_str(&new_association)?;
+
+                    if old_association == new_association {
+                        return Ok(Vec::new());
+                    }
+
+                    // Associations can't be renamed, so they're replaced
+                    let diff = diff_ron_values(&old_association, &new_association).unwrap_or_default();
+                    Ok(vec![
+                        connector_op!(
+                            Route53ResolverConnectorOp::DisassociateRule,
+                            format!(
+                                "Disassociate Resolver rule {} from {} to replace the association. Queries in the VPC aren't forwarded until it's recreated.",
+                                rule, vpc_id
+                            )
+                        ),
+                        connector_op!(
+                            Route53ResolverConnectorOp::AssociateRule(new_association),
+                            format!("Associate Resolver rule {} with {}\n{}", rule, vpc_id, diff)
+                        ),
+                    ])
+                }
+            },
+            Route53ResolverResourceAddress::FirewallRuleGroup { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_group)) => {
+                    let new_group: FirewallRuleGroup = RON.from_str(&new_group)?;
+                    check_firewall_rule_group(name, &new_group)?;
+                    Ok(vec![connector_op!(
+                        Route53ResolverConnectorOp::CreateFirewallRuleGroup(new_group),
+                        format!("Create new DNS Firewall rule group {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_group), None) => Ok(vec![connector_op!(
+                    Route53ResolverConnectorOp::DeleteFirewallRuleGroup,
+                    format!(
+                        "DELETE DNS Firewall rule group {} in region {} and its rules. It must be disassociated from all VPCs first.",
+                        name, region
+                    )
+                )]),
+                (Some(old_group), Some(new_group)) => {
+                    let old_group: FirewallRuleGroup = RON.from_str(&old_group)?;
+                    let mut new_group: FirewallRuleGroup = RON.from_str(&new_group)?;
+                    check_firewall_rule_group(name, &new_group)?;
+                    new_group.rules.sort_by_key(|rule| rule.priority);
+
+                    let mut ops = Vec::new();
+
+                    if old_group.tags != new_group.tags {
+                        let diff = diff_ron_values(&old_group.tags, &new_group.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            Route53ResolverConnectorOp::UpdateFirewallRuleGroupTags(old_group.tags.clone(), new_group.tags.clone()),
+                            format!("Modify tags for DNS Firewall rule group `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    if old_group.rules != new_group.rules {
+                        let diff = diff_ron_values(&old_group.rules, &new_group.rules).unwrap_or_default();
+                        ops.push(connector_op!(
+                            Route53ResolverConnectorOp::UpdateFirewallRules(new_group.rules),
+                            format!("Modify rules for DNS Firewall rule group `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{FirewallRule, FirewallRuleGroup, ResolverEndpoint, ResolverRule, ResolverRuleAssociation},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Route53ResolverConnectorOp {
+    /// Creates the endpoint, and waits for it to be operational.
+    CreateEndpoint(ResolverEndpoint),
+    /// Applies the endpoint's type and protocols, and adds and removes IP addresses to match.
+    UpdateEndpoint(ResolverEndpoint),
+    UpdateEndpointTags(Tags, Tags),
+    DeleteEndpoint,
+
+    CreateRule(ResolverRule),
+    /// Applies the rule's target IPs and outbound endpoint.
+    UpdateRule(ResolverRule),
+    UpdateRuleTags(Tags, Tags),
+    DeleteRule,
+
+    AssociateRule(ResolverRuleAssociation),
+    DisassociateRule,
+
+    /// Creates the rule group along with its rules.
+    CreateFirewallRuleGroup(FirewallRuleGroup),
+    /// Creates, updates and deletes the group's rules to match. Rules are matched up by domain list.
+    UpdateFirewallRules(Vec<FirewallRule>),
+    UpdateFirewallRuleGroupTags(Tags, Tags),
+    /// Deletes the group's rules, then the group.
+    DeleteFirewallRuleGroup,
+}
+
+impl ConnectorOp for Route53ResolverConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
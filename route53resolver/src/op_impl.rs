@@ -0,0 +1,656 @@
+use std::{collections::HashMap, future::Future, time::Duration};
+
+use anyhow::{Context, bail};
+use autoschematic_core::{connector::OpExecResponse, op_exec_output};
+use aws_sdk_route53resolver::types::{
+    Action, BlockOverrideDnsType, BlockResponse, IpAddressRequest, IpAddressUpdate, Protocol, ResolverEndpointDirection,
+    ResolverEndpointType, ResolverRuleConfig, RuleTypeOption,
+};
+
+use crate::{
+    resource::{FirewallRule, FirewallRuleGroup, ResolverEndpoint, ResolverRule, ResolverRuleAssociation},
+    tags::{Tags, tag_diff},
+    util::{filter, to_sdk_target_address},
+};
+
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Endpoints take a few minutes to create, since Resolver creates a network interface per IP address.
+const STATUS_MAX_POLLS: usize = 120;
+
+/// Polls `status` until it returns `target`, or until the resource no longer exists if `target` is None.
+async fn wait_for_status<F, Fut>(description: &str, target: Option<&str>, status: F) -> anyhow::Result<()>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = anyhow::Result<Option<String>>>,
+{
+    for _ in 0..STATUS_MAX_POLLS {
+        let state = status().await?;
+        if state.as_deref() == target {
+            return Ok(());
+        }
+        if matches!(state.as_deref(), Some("FAILED") | Some("ACTION_NEEDED")) {
+            bail!("{} is {}", description, state.unwrap_or_default());
+        }
+        tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    }
+
+    match target {
+        Some(target) => bail!("Timed out waiting for {} to become {}", description, target),
+        None => bail!("Timed out waiting for {} to be deleted", description),
+    }
+}
+
+fn creator_request_id() -> String {
+    format!("autoschematic-{}", uuid::Uuid::new_v4())
+}
+
+/// TagResource and UntagResource take the resource's ARN.
+async fn update_tags(
+    client: &aws_sdk_route53resolver::Client,
+    arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<()> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if let Some(new_tagset) = new_tagset.to_sdk()? {
+        client.tag_resource().resource_arn(arn).set_tags(Some(new_tagset)).send().await?;
+    }
+
+    Ok(())
+}
+
+pub async fn find_endpoint(
+    client: &aws_sdk_route53resolver::Client,
+    name: &str,
+) -> anyhow::Result<Option<aws_sdk_route53resolver::types::ResolverEndpoint>> {
+    let resp = client.list_resolver_endpoints().filters(filter("Name", name)).send().await?;
+    Ok(resp.resolver_endpoints.unwrap_or_default().into_iter().next())
+}
+
+/// Rules refer to their outbound endpoint by ID.
+pub async fn find_endpoint_by_id(
+    client: &aws_sdk_route53resolver::Client,
+    id: &str,
+) -> anyhow::Result<Option<aws_sdk_route53resolver::types::ResolverEndpoint>> {
+    match client.get_resolver_endpoint().resolver_endpoint_id(id).send().await {
+        Ok(resp) => Ok(resp.resolver_endpoint),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub async fn find_rule(
+    client: &aws_sdk_route53resolver::Client,
+    name: &str,
+) -> anyhow::Result<Option<aws_sdk_route53resolver::types::ResolverRule>> {
+    let resp = client.list_resolver_rules().filters(filter("Name", name)).send().await?;
+    Ok(resp.resolver_rules.unwrap_or_default().into_iter().next())
+}
+
+pub async fn find_rule_association(
+    client: &aws_sdk_route53resolver::Client,
+    rule_id: &str,
+    vpc_id: &str,
+) -> anyhow::Result<Option<aws_sdk_route53resolver::types::ResolverRuleAssociation>> {
+    let resp = client
+        .list_resolver_rule_associations()
+        .filters(filter("ResolverRuleId", rule_id))
+        .filters(filter("VPCId", vpc_id))
+        .send()
+        .await?;
+    Ok(resp.resolver_rule_associations.unwrap_or_default().into_iter().next())
+}
+
+/// ListFirewallRuleGroups can't filter by name, so this pages through all of them.
+pub async fn find_firewall_rule_group(
+    client: &aws_sdk_route53resolver::Client,
+    name: &str,
+) -> anyhow::Result<Option<aws_sdk_route53resolver::types::FirewallRuleGroupMetadata>> {
+    let mut next_token = None;
+    loop {
+        let resp = client.list_firewall_rule_groups().set_next_token(next_token).send().await?;
+
+        if let Some(group) = resp
+            .firewall_rule_groups
+            .unwrap_or_default()
+            .into_iter()
+            .find(|group| group.name.as_deref() == Some(name))
+        {
+            return Ok(Some(group));
+        }
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            return Ok(None);
+        }
+    }
+}
+
+pub async fn list_firewall_rules(
+    client: &aws_sdk_route53resolver::Client,
+    group_id: &str,
+) -> anyhow::Result<Vec<aws_sdk_route53resolver::types::FirewallRule>> {
+    let mut rules = Vec::new();
+    let mut next_token = None;
+    loop {
+        let resp = client
+            .list_firewall_rules()
+            .firewall_rule_group_id(group_id)
+            .set_next_token(next_token)
+            .send()
+            .await?;
+
+        rules.extend(resp.firewall_rules.unwrap_or_default());
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            return Ok(rules);
+        }
+    }
+}
+
+async fn endpoint_status(client: &aws_sdk_route53resolver::Client, id: &str) -> anyhow::Result<Option<String>> {
+    Ok(find_endpoint_by_id(client, id)
+        .await?
+        .map(|endpoint| endpoint.status.map(|s| s.as_str().to_string()).unwrap_or_default()))
+}
+
+async fn wait_for_endpoint(
+    client: &aws_sdk_route53resolver::Client,
+    name: &str,
+    id: &str,
+    target: Option<&str>,
+) -> anyhow::Result<()> {
+    wait_for_status(&format!("Resolver endpoint {}", name), target, || endpoint_status(client, id)).await
+}
+
+async fn endpoint_id(client: &aws_sdk_route53resolver::Client, name: &str) -> anyhow::Result<String> {
+    find_endpoint(client, name)
+        .await?
+        .and_then(|endpoint| endpoint.id)
+        .with_context(|| format!("Resolver endpoint {} not found", name))
+}
+
+async fn rule_id(client: &aws_sdk_route53resolver::Client, name: &str) -> anyhow::Result<String> {
+    find_rule(client, name)
+        .await?
+        .and_then(|rule| rule.id)
+        .with_context(|| format!("Resolver rule {} not found", name))
+}
+
+async fn firewall_rule_group_id(client: &aws_sdk_route53resolver::Client, name: &str) -> anyhow::Result<String> {
+    find_firewall_rule_group(client, name)
+        .await?
+        .and_then(|group| group.id)
+        .with_context(|| format!("DNS Firewall rule group {} not found", name))
+}
+
+fn to_ip_address_request(ip_address: &crate::resource::IpAddress) -> anyhow::Result<IpAddressRequest> {
+    Ok(IpAddressRequest::builder()
+        .subnet_id(&ip_address.subnet_id)
+        .set_ip(ip_address.ip.clone())
+        .build()?)
+}
+
+pub async fn create_endpoint(
+    client: &aws_sdk_route53resolver::Client,
+    name: &str,
+    endpoint: &ResolverEndpoint,
+) -> anyhow::Result<OpExecResponse> {
+    let mut ip_addresses = Vec::new();
+    for ip_address in &endpoint.ip_addresses {
+        ip_addresses.push(to_ip_address_request(ip_address)?);
+    }
+
+    let resp = client
+        .create_resolver_endpoint()
+        .creator_request_id(creator_request_id())
+        .name(name)
+        .direction(ResolverEndpointDirection::from(endpoint.direction.as_str()))
+        .set_security_group_ids(Some(endpoint.security_group_ids.clone()))
+        .set_ip_addresses(Some(ip_addresses))
+        .set_resolver_endpoint_type(endpoint.resolver_endpoint_type.as_deref().map(ResolverEndpointType::from))
+        .set_protocols(if endpoint.protocols.is_empty() {
+            None
+        } else {
+            Some(endpoint.protocols.iter().map(|p| Protocol::from(p.as_str())).collect())
+        })
+        .set_tags(endpoint.tags.to_sdk()?)
+        .send()
+        .await?;
+
+    let id = resp
+        .resolver_endpoint
+        .and_then(|endpoint| endpoint.id)
+        .context("CreateResolverEndpoint returned no endpoint ID")?;
+
+    wait_for_endpoint(client, name, &id, Some("OPERATIONAL")).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("resolver_endpoint_id"), Some(id))])),
+        friendly_message: Some(format!("Created Resolver endpoint {}", name)),
+    })
+}
+
+pub async fn update_endpoint(
+    client: &aws_sdk_route53resolver::Client,
+    name: &str,
+    endpoint: &ResolverEndpoint,
+) -> anyhow::Result<OpExecResponse> {
+    let current = find_endpoint(client, name)
+        .await?
+        .with_context(|| format!("Resolver endpoint {} not found", name))?;
+    let id = current.id.unwrap_or_default();
+
+    client
+        .update_resolver_endpoint()
+        .resolver_endpoint_id(&id)
+        .set_resolver_endpoint_type(endpoint.resolver_endpoint_type.as_deref().map(ResolverEndpointType::from))
+        .set_protocols(if endpoint.protocols.is_empty() {
+            None
+        } else {
+            Some(endpoint.protocols.iter().map(|p| Protocol::from(p.as_str())).collect())
+        })
+        .send()
+        .await?;
+    wait_for_endpoint(client, name, &id, Some("OPERATIONAL")).await?;
+
+    // Match desired addresses against the current ones: by IP where one is given, otherwise by subnet
+    let mut current_ips = client
+        .list_resolver_endpoint_ip_addresses()
+        .resolver_endpoint_id(&id)
+        .send()
+        .await?
+        .ip_addresses
+        .unwrap_or_default();
+
+    let mut to_add = Vec::new();
+    for ip_address in &endpoint.ip_addresses {
+        let matching = current_ips.iter().position(|current| {
+            current.subnet_id.as_deref() == Some(ip_address.subnet_id.as_str())
+                && (ip_address.ip.is_none() || current.ip == ip_address.ip)
+        });
+        match matching {
+            Some(i) => {
+                current_ips.remove(i);
+            }
+            None => to_add.push(ip_address),
+        }
+    }
+
+    // Endpoints need at least two addresses at all times, so new addresses are added before old ones are removed
+    for ip_address in to_add {
+        client
+            .associate_resolver_endpoint_ip_address()
+            .resolver_endpoint_id(&id)
+            .ip_address(
+                IpAddressUpdate::builder()
+                    .subnet_id(&ip_address.subnet_id)
+                    .set_ip(ip_address.ip.clone())
+                    .build(),
+            )
+            .send()
+            .await?;
+        wait_for_endpoint(client, name, &id, Some("OPERATIONAL")).await?;
+    }
+
+    for ip_address in current_ips {
+        client
+            .disassociate_resolver_endpoint_ip_address()
+            .resolver_endpoint_id(&id)
+            .ip_address(IpAddressUpdate::builder().set_ip_id(ip_address.ip_id).build())
+            .send()
+            .await?;
+        wait_for_endpoint(client, name, &id, Some("OPERATIONAL")).await?;
+    }
+
+    op_exec_output!(format!("Updated Resolver endpoint {}", name))
+}
+
+pub async fn update_endpoint_tags(
+    client: &aws_sdk_route53resolver::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = find_endpoint(client, name)
+        .await?
+        .and_then(|endpoint| endpoint.arn)
+        .with_context(|| format!("Resolver endpoint {} not found", name))?;
+
+    update_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for Resolver endpoint {}", name))
+}
+
+pub async fn delete_endpoint(client: &aws_sdk_route53resolver::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    let id = endpoint_id(client, name).await?;
+
+    client.delete_resolver_endpoint().resolver_endpoint_id(&id).send().await?;
+
+    wait_for_endpoint(client, name, &id, None).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("resolver_endpoint_id"), None)])),
+        friendly_message: Some(format!("Deleted Resolver endpoint {}", name)),
+    })
+}
+
+async fn rule_status(client: &aws_sdk_route53resolver::Client, id: &str) -> anyhow::Result<Option<String>> {
+    match client.get_resolver_rule().resolver_rule_id(id).send().await {
+        Ok(resp) => Ok(resp
+            .resolver_rule
+            .map(|rule| rule.status.map(|s| s.as_str().to_string()).unwrap_or_default())),
+        Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// A rule's outbound endpoint is given by name in the rule's file.
+async fn rule_endpoint_id(client: &aws_sdk_route53resolver::Client, rule: &ResolverRule) -> anyhow::Result<Option<String>> {
+    match &rule.resolver_endpoint {
+        Some(endpoint) => Ok(Some(endpoint_id(client, endpoint).await?)),
+        None => Ok(None),
+    }
+}
+
+pub async fn create_rule(
+    client: &aws_sdk_route53resolver::Client,
+    name: &str,
+    rule: &ResolverRule,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_resolver_rule()
+        .creator_request_id(creator_request_id())
+        .name(name)
+        .rule_type(RuleTypeOption::from(rule.rule_type.as_str()))
+        .domain_name(&rule.domain_name)
+        .set_target_ips(if rule.target_ips.is_empty() {
+            None
+        } else {
+            Some(rule.target_ips.iter().map(to_sdk_target_address).collect())
+        })
+        .set_resolver_endpoint_id(rule_endpoint_id(client, rule).await?)
+        .set_tags(rule.tags.to_sdk()?)
+        .send()
+        .await?;
+
+    let id = resp
+        .resolver_rule
+        .and_then(|rule| rule.id)
+        .context("CreateResolverRule returned no rule ID")?;
+
+    wait_for_status(&format!("Resolver rule {}", name), Some("COMPLETE"), || rule_status(client, &id)).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("resolver_rule_id"), Some(id))])),
+        friendly_message: Some(format!("Created Resolver rule {}", name)),
+    })
+}
+
+pub async fn update_rule(
+    client: &aws_sdk_route53resolver::Client,
+    name: &str,
+    rule: &ResolverRule,
+) -> anyhow::Result<OpExecResponse> {
+    let id = rule_id(client, name).await?;
+
+    client
+        .update_resolver_rule()
+        .resolver_rule_id(&id)
+        .config(
+            ResolverRuleConfig::builder()
+                .set_target_ips(Some(rule.target_ips.iter().map(to_sdk_target_address).collect()))
+                .set_resolver_endpoint_id(rule_endpoint_id(client, rule).await?)
+                .build(),
+        )
+        .send()
+        .await?;
+
+    wait_for_status(&format!("Resolver rule {}", name), Some("COMPLETE"), || rule_status(client, &id)).await?;
+
+    op_exec_output!(format!("Updated Resolver rule {}", name))
+}
+
+pub async fn update_rule_tags(
+    client: &aws_sdk_route53resolver::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = find_rule(client, name)
+        .await?
+        .and_then(|rule| rule.arn)
+        .with_context(|| format!("Resolver rule {} not found", name))?;
+
+    update_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for Resolver rule {}", name))
+}
+
+pub async fn delete_rule(client: &aws_sdk_route53resolver::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    let id = rule_id(client, name).await?;
+
+    client.delete_resolver_rule().resolver_rule_id(&id).send().await?;
+
+    wait_for_status(&format!("Resolver rule {}", name), None, || rule_status(client, &id)).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("resolver_rule_id"), None)])),
+        friendly_message: Some(format!("Deleted Resolver rule {}", name)),
+    })
+}
+
+async fn rule_association_status(
+    client: &aws_sdk_route53resolver::Client,
+    rule_id: &str,
+    vpc_id: &str,
+) -> anyhow::Result<Option<String>> {
+    Ok(find_rule_association(client, rule_id, vpc_id)
+        .await?
+        .map(|association| association.status.map(|s| s.as_str().to_string()).unwrap_or_default()))
+}
+
+pub async fn associate_rule(
+    client: &aws_sdk_route53resolver::Client,
+    rule: &str,
+    vpc_id: &str,
+    association: &ResolverRuleAssociation,
+) -> anyhow::Result<OpExecResponse> {
+    let rule_id = rule_id(client, rule).await?;
+
+    let resp = client
+        .associate_resolver_rule()
+        .resolver_rule_id(&rule_id)
+        .vpc_id(vpc_id)
+        .set_name(association.name.clone())
+        .send()
+        .await?;
+
+    wait_for_status(
+        &format!("association of Resolver rule {} with {}", rule, vpc_id),
+        Some("COMPLETE"),
+        || rule_association_status(client, &rule_id, vpc_id),
+    )
+    .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(
+            String::from("resolver_rule_association_id"),
+            resp.resolver_rule_association.and_then(|association| association.id),
+        )])),
+        friendly_message: Some(format!("Associated Resolver rule {} with {}", rule, vpc_id)),
+    })
+}
+
+pub async fn disassociate_rule(
+    client: &aws_sdk_route53resolver::Client,
+    rule: &str,
+    vpc_id: &str,
+) -> anyhow::Result<OpExecResponse> {
+    let rule_id = rule_id(client, rule).await?;
+
+    client
+        .disassociate_resolver_rule()
+        .resolver_rule_id(&rule_id)
+        .vpc_id(vpc_id)
+        .send()
+        .await?;
+
+    wait_for_status(&format!("association of Resolver rule {} with {}", rule, vpc_id), None, || {
+        rule_association_status(client, &rule_id, vpc_id)
+    })
+    .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("resolver_rule_association_id"), None)])),
+        friendly_message: Some(format!("Disassociated Resolver rule {} from {}", rule, vpc_id)),
+    })
+}
+
+async fn create_firewall_rule(
+    client: &aws_sdk_route53resolver::Client,
+    group_id: &str,
+    rule: &FirewallRule,
+) -> anyhow::Result<()> {
+    client
+        .create_firewall_rule()
+        .creator_request_id(creator_request_id())
+        .firewall_rule_group_id(group_id)
+        .firewall_domain_list_id(&rule.firewall_domain_list_id)
+        .name(&rule.name)
+        .priority(rule.priority)
+        .action(Action::from(rule.action.as_str()))
+        .set_block_response(rule.block_response.as_deref().map(BlockResponse::from))
+        .set_block_override_domain(rule.block_override_domain.clone())
+        .set_block_override_dns_type(rule.block_override_domain.as_ref().map(|_| BlockOverrideDnsType::Cname))
+        .set_block_override_ttl(rule.block_override_ttl)
+        .send()
+        .await?;
+    Ok(())
+}
+
+pub async fn create_firewall_rule_group(
+    client: &aws_sdk_route53resolver::Client,
+    name: &str,
+    group: &FirewallRuleGroup,
+) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_firewall_rule_group()
+        .creator_request_id(creator_request_id())
+        .name(name)
+        .set_tags(group.tags.to_sdk()?)
+        .send()
+        .await?;
+
+    let id = resp
+        .firewall_rule_group
+        .and_then(|group| group.id)
+        .context("CreateFirewallRuleGroup returned no rule group ID")?;
+
+    for rule in &group.rules {
+        create_firewall_rule(client, &id, rule).await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("firewall_rule_group_id"), Some(id))])),
+        friendly_message: Some(format!("Created DNS Firewall rule group {}", name)),
+    })
+}
+
+pub async fn update_firewall_rules(
+    client: &aws_sdk_route53resolver::Client,
+    name: &str,
+    rules: &[FirewallRule],
+) -> anyhow::Result<OpExecResponse> {
+    let id = firewall_rule_group_id(client, name).await?;
+
+    let current_rules = list_firewall_rules(client, &id).await?;
+
+    for current in &current_rules {
+        let Some(domain_list_id) = &current.firewall_domain_list_id else {
+            continue;
+        };
+        if !rules.iter().any(|rule| &rule.firewall_domain_list_id == domain_list_id) {
+            client
+                .delete_firewall_rule()
+                .firewall_rule_group_id(&id)
+                .firewall_domain_list_id(domain_list_id)
+                .send()
+                .await?;
+        }
+    }
+
+    for rule in rules {
+        let exists = current_rules
+            .iter()
+            .any(|current| current.firewall_domain_list_id.as_deref() == Some(rule.firewall_domain_list_id.as_str()));
+        if !exists {
+            create_firewall_rule(client, &id, rule).await?;
+            continue;
+        }
+
+        client
+            .update_firewall_rule()
+            .firewall_rule_group_id(&id)
+            .firewall_domain_list_id(&rule.firewall_domain_list_id)
+            .name(&rule.name)
+            .priority(rule.priority)
+            .action(Action::from(rule.action.as_str()))
+            .set_block_response(rule.block_response.as_deref().map(BlockResponse::from))
+            .set_block_override_domain(rule.block_override_domain.clone())
+            .set_block_override_dns_type(rule.block_override_domain.as_ref().map(|_| BlockOverrideDnsType::Cname))
+            .set_block_override_ttl(rule.block_override_ttl)
+            .send()
+            .await?;
+    }
+
+    op_exec_output!(format!("Updated rules for DNS Firewall rule group {}", name))
+}
+
+pub async fn update_firewall_rule_group_tags(
+    client: &aws_sdk_route53resolver::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = find_firewall_rule_group(client, name)
+        .await?
+        .and_then(|group| group.arn)
+        .with_context(|| format!("DNS Firewall rule group {} not found", name))?;
+
+    update_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for DNS Firewall rule group {}", name))
+}
+
+pub async fn delete_firewall_rule_group(client: &aws_sdk_route53resolver::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    let id = firewall_rule_group_id(client, name).await?;
+
+    // Rule groups can't be deleted while they still have rules
+    for rule in list_firewall_rules(client, &id).await? {
+        client
+            .delete_firewall_rule()
+            .firewall_rule_group_id(&id)
+            .set_firewall_domain_list_id(rule.firewall_domain_list_id)
+            .send()
+            .await?;
+    }
+
+    client.delete_firewall_rule_group().firewall_rule_group_id(&id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("firewall_rule_group_id"), None)])),
+        friendly_message: Some(format!("Deleted DNS Firewall rule group {}", name)),
+    })
+}
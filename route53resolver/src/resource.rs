@@ -0,0 +1,135 @@
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::Route53ResolverResourceAddress, tags::Tags};
+
+/// A Resolver endpoint, through which DNS queries enter (INBOUND) or leave (OUTBOUND) a VPC.
+///
+/// The direction and security groups are fixed at creation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ResolverEndpoint {
+    /// INBOUND or OUTBOUND.
+    pub direction: String,
+    pub security_group_ids: Vec<String>,
+    /// At least two, which should be in different availability zones. All subnets must be in the same VPC.
+    pub ip_addresses: Vec<IpAddress>,
+    /// IPV4, IPV6 or DUALSTACK. If None, IPV4.
+    pub resolver_endpoint_type: Option<String>,
+    /// Any of Do53, DoH and DoH-FIPS. If empty, Do53.
+    #[serde(default)]
+    pub protocols: Vec<String>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, PartialOrd, Eq, Ord)]
+#[serde(deny_unknown_fields)]
+pub struct IpAddress {
+    pub subnet_id: String,
+    /// If None, an address is picked from the subnet.
+    pub ip: Option<String>,
+}
+
+/// A Resolver rule. FORWARD rules send queries for `domain_name` to `target_ips` through an outbound endpoint,
+/// and SYSTEM rules override a FORWARD rule for a subdomain.
+///
+/// The rule type and domain name are fixed at creation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ResolverRule {
+    /// FORWARD or SYSTEM.
+    pub rule_type: String,
+    pub domain_name: String,
+    #[serde(default)]
+    pub target_ips: Vec<TargetAddress>,
+    /// The name of an outbound endpoint in the same region. Required for FORWARD rules.
+    pub resolver_endpoint: Option<String>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TargetAddress {
+    pub ip: String,
+    /// If None, 53.
+    pub port: Option<i32>,
+    /// Do53, DoH or DoH-FIPS. If None, Do53.
+    pub protocol: Option<String>,
+}
+
+/// The association of a Resolver rule with a VPC. Associations can't be modified, only replaced.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ResolverRuleAssociation {
+    pub name: Option<String>,
+}
+
+/// A DNS Firewall rule group. Its rules are kept inline and applied in priority order.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FirewallRuleGroup {
+    #[serde(default)]
+    pub rules: Vec<FirewallRule>,
+    pub tags: Tags,
+}
+
+/// A rule matching the domains in a firewall domain list. A rule group has one rule per domain list.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FirewallRule {
+    pub name: String,
+    pub firewall_domain_list_id: String,
+    /// Rules with lower priorities are evaluated first. Must be unique within the group.
+    pub priority: i32,
+    /// ALLOW, BLOCK or ALERT.
+    pub action: String,
+    /// NODATA, NXDOMAIN or OVERRIDE, for BLOCK rules.
+    pub block_response: Option<String>,
+    /// The domain to answer with, for OVERRIDE responses.
+    pub block_override_domain: Option<String>,
+    /// The TTL in seconds of OVERRIDE responses.
+    pub block_override_ttl: Option<i32>,
+}
+
+pub enum Route53ResolverResource {
+    Endpoint(ResolverEndpoint),
+    Rule(ResolverRule),
+    RuleAssociation(ResolverRuleAssociation),
+    FirewallRuleGroup(FirewallRuleGroup),
+}
+
+impl Resource for Route53ResolverResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            Route53ResolverResource::Endpoint(endpoint) => Ok(RON.to_string_pretty(&endpoint, pretty_config)?.into()),
+            Route53ResolverResource::Rule(rule) => Ok(RON.to_string_pretty(&rule, pretty_config)?.into()),
+            Route53ResolverResource::RuleAssociation(association) => {
+                Ok(RON.to_string_pretty(&association, pretty_config)?.into())
+            }
+            Route53ResolverResource::FirewallRuleGroup(group) => Ok(RON.to_string_pretty(&group, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = Route53ResolverResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            Route53ResolverResourceAddress::Endpoint { .. } => Ok(Route53ResolverResource::Endpoint(RON.from_str(s)?)),
+            Route53ResolverResourceAddress::Rule { .. } => Ok(Route53ResolverResource::Rule(RON.from_str(s)?)),
+            Route53ResolverResourceAddress::RuleAssociation { .. } => {
+                Ok(Route53ResolverResource::RuleAssociation(RON.from_str(s)?))
+            }
+            Route53ResolverResourceAddress::FirewallRuleGroup { .. } => {
+                Ok(Route53ResolverResource::FirewallRuleGroup(RON.from_str(s)?))
+            }
+        }
+    }
+}
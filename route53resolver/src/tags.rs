@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+
+use aws_sdk_route53resolver::types::Tag;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Tags(HashMap<String, String>);
+
+impl From<Vec<Tag>> for Tags {
+    fn from(tags: Vec<Tag>) -> Self {
+        Tags(tags.into_iter().map(|tag| (tag.key, tag.value)).collect())
+    }
+}
+
+impl Tags {
+    /// Resolver tags have a required key and value, so building them can fail.
+    pub fn to_sdk(&self) -> anyhow::Result<Option<Vec<Tag>>> {
+        if self.0.is_empty() {
+            return Ok(None);
+        }
+        let mut out_vec = Vec::new();
+        for (k, v) in &self.0 {
+            out_vec.push(Tag::builder().key(k).value(v).build()?);
+        }
+        Ok(Some(out_vec))
+    }
+}
+
+// From a pair of tag maps, determine the keys to untag and the tags to set respectively
+pub fn tag_diff(old_tags: &Tags, new_tags: &Tags) -> (Vec<String>, Tags) {
+    let untag_keys = old_tags.0.keys().filter(|k| !new_tags.0.contains_key(*k)).cloned().collect();
+
+    let new_tagset = new_tags
+        .0
+        .iter()
+        .filter(|(k, v)| old_tags.0.get(*k) != Some(*v))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+
+    (untag_keys, Tags(new_tagset))
+}
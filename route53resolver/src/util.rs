@@ -0,0 +1,24 @@
+use aws_sdk_route53resolver::types::{Filter, Protocol};
+
+use crate::resource::TargetAddress;
+
+/// A filter for the List* operations, most often `Name`.
+pub fn filter(name: &str, value: &str) -> Filter {
+    Filter::builder().name(name).values(value).build()
+}
+
+pub fn to_sdk_target_address(target: &TargetAddress) -> aws_sdk_route53resolver::types::TargetAddress {
+    aws_sdk_route53resolver::types::TargetAddress::builder()
+        .ip(&target.ip)
+        .set_port(target.port)
+        .set_protocol(target.protocol.as_deref().map(Protocol::from))
+        .build()
+}
+
+pub fn from_sdk_target_address(target: aws_sdk_route53resolver::types::TargetAddress) -> TargetAddress {
+    TargetAddress {
+        ip: target.ip.unwrap_or_default(),
+        port: target.port,
+        protocol: target.protocol.map(|p| p.as_str().to_string()),
+    }
+}
@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
 use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 
 #[derive(Debug, Clone)]
@@ -30,3 +31,15 @@ impl ResourceAddress for S3ResourceAddress {
         }
     }
 }
+
+impl DescribeAddresses for S3ResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/s3/<region>/buckets/<bucket_name>.ron",
+                description: "An S3 bucket",
+                example:     "aws/s3/us-east-1/buckets/example-assets.ron",
+            },
+        ]
+    }
+}
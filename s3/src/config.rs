@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Debug)]
 pub struct S3ConnectorConfig {
     pub enabled_regions: Vec<String>,
+    #[serde(default)]
+    pub max_concurrent_ops: Option<usize>,
 }
 
 impl Default for S3ConnectorConfig {
@@ -52,6 +54,7 @@ impl Default for S3ConnectorConfig {
             .iter()
             .map(|s| s.to_string())
             .collect(),
+            max_concurrent_ops: None,
         }
     }
 }
@@ -71,6 +74,7 @@ impl S3ConnectorConfig {
     pub fn from_aws_config(cfg: &AwsConnectorConfig) -> Self {
         Self {
             enabled_regions: cfg.enabled_regions.clone(),
+            max_concurrent_ops: cfg.max_concurrent_ops,
         }
     }
 }
@@ -7,6 +7,7 @@ use std::{
 
 use crate::addr::S3ResourceAddress;
 use crate::config::S3ConnectorConfig;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter};
 use anyhow::bail;
 use async_trait::async_trait;
 use autoschematic_core::{
@@ -34,6 +35,8 @@ pub struct S3Connector {
     prefix: PathBuf,
     client_cache: Mutex<HashMap<String, Arc<aws_sdk_s3::Client>>>,
     config: Mutex<S3ConnectorConfig>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
 }
 
 impl S3Connector {
@@ -55,7 +58,7 @@ impl S3Connector {
                 )
                 .load()
                 .await;
-            let client = aws_sdk_s3::Client::new(&config);
+            let client = audited_client!(aws_sdk_s3, &config);
             cache.insert(region_s.to_string(), Arc::new(client));
         };
 
@@ -82,6 +85,8 @@ impl Connector for S3Connector {
     async fn init(&self) -> anyhow::Result<()> {
         let config: S3ConnectorConfig = S3ConnectorConfig::try_load(&self.prefix)?.unwrap_or_default();
 
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
         *self.config.lock().await = config;
         Ok(())
     }
@@ -112,7 +117,9 @@ impl Connector for S3Connector {
     }
 
     async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
-        self.do_op_exec(addr, op).await
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
     }
 
     async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
@@ -1,10 +1,12 @@
 use std::path::{Path, PathBuf};
 
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
 use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 
 #[derive(Debug, Clone)]
 pub enum SecretsManagerResourceAddress {
     Secret { region: String, name: String },
+    Rotation { region: String, name: String },
 }
 
 impl ResourceAddress for SecretsManagerResourceAddress {
@@ -13,6 +15,9 @@ impl ResourceAddress for SecretsManagerResourceAddress {
             SecretsManagerResourceAddress::Secret { region, name } => {
                 PathBuf::from(format!("aws/secretsmanager/{region}/secrets/{name}.ron"))
             }
+            SecretsManagerResourceAddress::Rotation { region, name } => {
+                PathBuf::from(format!("aws/secretsmanager/{region}/rotations/{name}.ron"))
+            }
         }
     }
 
@@ -32,7 +37,48 @@ impl ResourceAddress for SecretsManagerResourceAddress {
                     Err(invalid_addr_path(path))
                 }
             }
+            ["aws", "secretsmanager", region, "rotations", name @ ..] => {
+                let name = name.join("/");
+                if name.ends_with(".ron") {
+                    let name = name.strip_suffix(".ron").unwrap().to_string();
+                    Ok(SecretsManagerResourceAddress::Rotation {
+                        region: region.to_string(),
+                        name,
+                    })
+                } else {
+                    Err(invalid_addr_path(path))
+                }
+            }
             _ => Err(invalid_addr_path(path)),
         }
     }
 }
+
+impl DescribeAddresses for SecretsManagerResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/secretsmanager/<region>/secrets/<name>.ron",
+                description: "A secret. Names may contain `/`",
+                example:     "aws/secretsmanager/us-east-1/secrets/prod/db-password.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/secretsmanager/<region>/rotations/<name>.ron",
+                description: "A secret's rotation schedule and Lambda function",
+                example:     "aws/secretsmanager/us-east-1/rotations/prod/db-password.ron",
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use autoschematic_connector_aws_core::addr_doc::DescribeAddresses;
+
+    use super::SecretsManagerResourceAddress;
+
+    #[test]
+    fn address_examples_parse() {
+        SecretsManagerResourceAddress::checked_address_patterns().unwrap();
+    }
+}
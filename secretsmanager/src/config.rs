@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 use autoschematic_connector_aws_core::{
     config::{AwsConnectorConfig, AwsServiceConfig, TimeoutConfig, verify_sts_account_id},
@@ -14,6 +14,24 @@ pub struct SecretsManagerConnectorConfig {
     pub timeout_config:  Option<TimeoutConfig>,
     pub sts_region:      String,
     pub enabled_regions: Vec<String>,
+    #[serde(default)]
+    pub max_concurrent_ops: Option<usize>,
+    /// If non-empty, only secrets whose name starts with one of these prefixes are listed.
+    #[serde(default)]
+    pub list_name_prefixes: Vec<String>,
+    /// If non-empty, only secrets carrying all of these tags are listed.
+    /// A value of "*" matches any value for that tag key.
+    #[serde(default)]
+    pub list_tag_selectors: HashMap<String, String>,
+    /// If set, secrets owned by another AWS service (e.g. RDS-managed master user passwords) are not listed.
+    #[serde(default)]
+    pub list_exclude_aws_managed: bool,
 }
 
-impl_aws_config!(SecretsManagerConnectorConfig, "aws/secretsmanager/config.ron");
+impl_aws_config!(
+    SecretsManagerConnectorConfig,
+    "aws/secretsmanager/config.ron",
+    list_name_prefixes,
+    list_tag_selectors,
+    list_exclude_aws_managed
+);
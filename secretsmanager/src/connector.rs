@@ -2,7 +2,7 @@ pub use crate::addr::SecretsManagerResourceAddress;
 pub use crate::op::SecretsManagerConnectorOp;
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     path::{Path, PathBuf},
     sync::Arc,
     time::Duration,
@@ -10,35 +10,45 @@ use std::{
 
 use crate::{
     config::SecretsManagerConnectorConfig,
-    resource::{Secret, SecretsManagerResource},
+    resource::{RotationConfig, RotationRules, Secret, SecretRotation, SecretsManagerResource},
     tags,
+    task::{PendingDeletionSweep, SecretsManagerTask, SecretsManagerTaskAddress},
 };
 use anyhow::{Context, bail};
 use async_trait::async_trait;
 use autoschematic_core::{
     connector::{
-        Connector, ConnectorOp, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
-        ResourceAddress, SkeletonResponse,
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse, TaskExecResponse,
     },
     diag::DiagnosticResponse,
-    util::{RON, ron_check_eq, ron_check_syntax},
+    util::{RON, ron_check_syntax},
 };
 use autoschematic_core::{get_resource_response, skeleton};
-use aws_config::{BehaviorVersion, Region, meta::region::RegionProviderChain, timeout::TimeoutConfig};
-use serde_json;
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
 use tokio::sync::{Mutex, RwLock};
 
 use crate::resource;
-use autoschematic_connector_aws_core::config::AwsServiceConfig;
+use crate::util::{
+    DEFAULT_KMS_KEY_ALIAS, ROTATION_LAMBDA_ARN_OUTPUT, ROTATION_TEMPLATE_TAG, find_stack, rotation_rules_from_sdk,
+    resolve_secret_binary_file, rotation_stack_name, split_list_parameter, stack_output, stack_parameter, stack_tag,
+};
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
 use tags::Tags;
 
 pub mod get;
 pub mod list;
 pub mod op_exec;
 pub mod plan;
-
-// Helper function to get a secret
-async fn get_secret(client: &aws_sdk_secretsmanager::Client, secret_name: &str) -> anyhow::Result<(resource::Secret, String)> {
+pub mod task_exec;
+
+// Helper function to get a secret. Rotation through `stack_rotation_lambda_arn`, the function in the
+// secret's rotation stack, belongs to the rotation address and isn't reported here.
+async fn get_secret(
+    client: &aws_sdk_secretsmanager::Client,
+    secret_name: &str,
+    stack_rotation_lambda_arn: Option<String>,
+) -> anyhow::Result<(resource::Secret, String)> {
     // Describe the secret to get its metadata
     let describe_resp = client.describe_secret().secret_id(secret_name).send().await?;
 
@@ -61,46 +71,157 @@ async fn get_secret(client: &aws_sdk_secretsmanager::Client, secret_name: &str)
         .from_str(&policy_json.to_string())
         .context("Failed to convert policy to RON value")?;
 
+    let rotation = match describe_resp.rotation_lambda_arn {
+        Some(rotation_lambda_arn)
+            if describe_resp.rotation_enabled == Some(true) && Some(&rotation_lambda_arn) != stack_rotation_lambda_arn.as_ref() =>
+        {
+            Some(resource::RotationConfig {
+                rotation_lambda_arn,
+                rotation_rules: rotation_rules_from_sdk(describe_resp.rotation_rules),
+                rotate_immediately: false,
+            })
+        }
+        _ => None,
+    };
+
+    let mut replica_regions: Vec<resource::ReplicaRegion> = describe_resp
+        .replication_status
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|status| {
+            Some(resource::ReplicaRegion {
+                region:     status.region?,
+                kms_key_id: status.kms_key_id.filter(|key| key != DEFAULT_KMS_KEY_ALIAS),
+            })
+        })
+        .collect();
+    replica_regions.sort_by(|a, b| a.region.cmp(&b.region));
+
+    // AWSPREVIOUS moves along with AWSCURRENT, so it's left out
+    let version_stages: BTreeMap<String, String> = describe_resp
+        .version_ids_to_stages
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|(version_id, stages)| stages.into_iter().map(move |stage| (stage, version_id.clone())))
+        .filter(|(stage, _)| stage != "AWSPREVIOUS")
+        .collect();
+
     // Create the Secret struct
     let secret = resource::Secret {
         description: describe_resp.description.clone(),
         kms_key_id: describe_resp.kms_key_id.clone(),
         secret_ref: None, // By default, we don't include the secret value for security reasons
+        secret_binary_file: None,
         tags,
         policy_document: policy_value,
+        rotation,
+        replica_regions,
+        version_stages,
     };
 
     Ok((secret, describe_resp.arn.unwrap_or_default()))
 }
 
+/// Reads a secret's rotation back from the secret and its rotation stack. Rotation that wasn't set
+/// up from a rotation stack isn't reported.
+async fn get_rotation(
+    client: &aws_sdk_secretsmanager::Client,
+    cfn_client: &aws_sdk_cloudformation::Client,
+    secret_name: &str,
+) -> anyhow::Result<Option<(resource::SecretRotation, String)>> {
+    let Some(stack) = find_stack(cfn_client, &rotation_stack_name(secret_name)).await? else {
+        return Ok(None);
+    };
+    let Some(template) = stack_tag(&stack, ROTATION_TEMPLATE_TAG) else {
+        return Ok(None);
+    };
+
+    let describe_resp = client.describe_secret().secret_id(secret_name).send().await?;
+    let rotation_lambda_arn = stack_output(&stack, ROTATION_LAMBDA_ARN_OUTPUT);
+    // The stack may outlive the secret's rotation, e.g. if rotation was turned off by hand
+    if describe_resp.rotation_enabled != Some(true) || describe_resp.rotation_lambda_arn != rotation_lambda_arn {
+        return Ok(None);
+    }
+
+    let rotation = resource::SecretRotation {
+        template,
+        template_version: stack_tag(&stack, "serverlessrepo:semanticVersion"),
+        vpc_subnet_ids: split_list_parameter(stack_parameter(&stack, "vpcSubnetIds")),
+        vpc_security_group_ids: split_list_parameter(stack_parameter(&stack, "vpcSecurityGroupIds")),
+        master_secret_arn: stack_parameter(&stack, "masterSecretArn"),
+        kms_key_arn: stack_parameter(&stack, "kmsKeyArn"),
+        exclude_characters: stack_parameter(&stack, "excludeCharacters"),
+        rotation_rules: rotation_rules_from_sdk(describe_resp.rotation_rules),
+        // Only meaningful when rotation is turned on
+        rotate_immediately: false,
+    };
+
+    Ok(Some((rotation, rotation_lambda_arn.unwrap_or_default())))
+}
+
 #[derive(Default)]
 pub struct SecretsManagerConnector {
     client_cache: Mutex<HashMap<String, Arc<aws_sdk_secretsmanager::Client>>>,
+    cfn_client_cache: Mutex<HashMap<String, Arc<aws_sdk_cloudformation::Client>>>,
+    sar_client_cache: Mutex<HashMap<String, Arc<aws_sdk_serverlessapplicationrepository::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
     account_id: Mutex<String>,
     config: RwLock<SecretsManagerConnectorConfig>,
     prefix: PathBuf,
 }
 
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+/// rotate_immediately only applies when rotation is turned on or moved to another function, and
+/// replicas are compared regardless of order.
+pub fn normalize_secret(secret: Secret) -> Secret {
+    let mut replica_regions = secret.replica_regions;
+    replica_regions.sort_by(|a, b| a.region.cmp(&b.region));
+    Secret {
+        rotation: secret.rotation.map(|rotation| RotationConfig {
+            rotate_immediately: false,
+            ..rotation
+        }),
+        replica_regions,
+        ..secret
+    }
+}
+
+/// rotate_immediately only applies when rotation is turned on, and a rotation without a
+/// template_version takes whichever version is deployed, so neither counts as a difference.
+pub fn normalize_rotations(a: SecretRotation, b: SecretRotation) -> (SecretRotation, SecretRotation) {
+    let pinned = a.template_version.is_some() && b.template_version.is_some();
+    let normalize = |rotation: SecretRotation| SecretRotation {
+        template_version: if pinned { rotation.template_version } else { None },
+        rotate_immediately: false,
+        ..rotation
+    };
+    (normalize(a), normalize(b))
+}
+
 impl SecretsManagerConnector {
     pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_secretsmanager::Client>> {
         let mut cache = self.client_cache.lock().await;
 
         if !cache.contains_key(region_s) {
-            let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
-
-            let config = aws_config::defaults(BehaviorVersion::latest())
-                .region(region)
-                .timeout_config(
-                    TimeoutConfig::builder()
-                        .connect_timeout(Duration::from_secs(30))
-                        .operation_timeout(Duration::from_secs(30))
-                        .operation_attempt_timeout(Duration::from_secs(30))
-                        .read_timeout(Duration::from_secs(30))
-                        .build(),
-                )
-                .load()
-                .await;
-            let client = aws_sdk_secretsmanager::Client::new(&config);
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_secretsmanager, &config);
             cache.insert(region_s.to_string(), Arc::new(client));
         };
 
@@ -110,6 +231,41 @@ impl SecretsManagerConnector {
 
         Ok(client.clone())
     }
+
+    pub async fn get_or_init_cfn_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_cloudformation::Client>> {
+        let mut cache = self.cfn_client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_cloudformation, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get CloudFormation client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+    pub async fn get_or_init_sar_client(
+        &self,
+        region_s: &str,
+    ) -> anyhow::Result<Arc<aws_sdk_serverlessapplicationrepository::Client>> {
+        let mut cache = self.sar_client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_serverlessapplicationrepository, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get Serverless Application Repository client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
 }
 
 #[async_trait]
@@ -130,6 +286,10 @@ impl Connector for SecretsManagerConnector {
         let account_id = secrets_config.verify_sts().await?;
 
         *self.client_cache.lock().await = HashMap::new();
+        *self.cfn_client_cache.lock().await = HashMap::new();
+        *self.sar_client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(secrets_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
         *self.config.write().await = secrets_config;
         *self.account_id.lock().await = account_id;
         Ok(())
@@ -138,6 +298,8 @@ impl Connector for SecretsManagerConnector {
     async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
         if let Ok(_addr) = SecretsManagerResourceAddress::from_path(addr) {
             Ok(FilterResponse::Resource)
+        } else if let Ok(_addr) = SecretsManagerTaskAddress::from_path(addr) {
+            Ok(FilterResponse::Task)
         } else {
             Ok(FilterResponse::None)
         }
@@ -160,19 +322,42 @@ impl Connector for SecretsManagerConnector {
     async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
         let addr = SecretsManagerResourceAddress::from_path(addr)?;
 
-        match addr {
+        match &addr {
             SecretsManagerResourceAddress::Secret { region, name } => {
-                let client = self.get_or_init_client(&region).await?;
-                match get_secret(&client, &name).await {
-                    Ok((secret, arn)) => {
-                        return get_resource_response!(SecretsManagerResource::Secret(secret), [(String::from("arn"), arn)]);
+                let client = self.get_or_init_client(region).await?;
+                let cfn_client = self.get_or_init_cfn_client(region).await?;
+                let stack_rotation_lambda_arn = find_stack(&cfn_client, &rotation_stack_name(name))
+                    .await?
+                    .and_then(|stack| stack_output(&stack, ROTATION_LAMBDA_ARN_OUTPUT));
+                match get_secret(&client, name, stack_rotation_lambda_arn).await {
+                    Ok((mut secret, arn)) => {
+                        let recorded_file = addr.get_output(&self.prefix, "secret_binary_file")?;
+                        secret.secret_binary_file =
+                            resolve_secret_binary_file(&client, &self.prefix, &addr, name, recorded_file).await?;
+                        get_resource_response!(SecretsManagerResource::Secret(secret), [(String::from("arn"), arn)])
                     }
                     Err(e) => {
                         tracing::error!("{}", e);
                         Ok(None)
-                    },
+                    }
                 }
             }
+            SecretsManagerResourceAddress::Rotation { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+                let cfn_client = self.get_or_init_cfn_client(region).await?;
+
+                let Some((rotation, rotation_lambda_arn)) = get_rotation(&client, &cfn_client, name).await? else {
+                    return Ok(None);
+                };
+
+                get_resource_response!(
+                    SecretsManagerResource::Rotation(rotation),
+                    [
+                        (String::from("rotation_stack_name"), rotation_stack_name(name)),
+                        (String::from("rotation_lambda_arn"), rotation_lambda_arn)
+                    ]
+                )
+            }
         }
     }
 
@@ -186,7 +371,19 @@ impl Connector for SecretsManagerConnector {
     }
 
     async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
-        self.do_op_exec(addr, op).await
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn task_exec(
+        &self,
+        addr: &Path,
+        body: Vec<u8>,
+        arg: Option<Vec<u8>>,
+        state: Option<Vec<u8>>,
+    ) -> anyhow::Result<TaskExecResponse> {
+        self.do_task_exec(addr, body, arg, state).await
     }
 
     async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
@@ -223,8 +420,48 @@ impl Connector for SecretsManagerConnector {
                 description: Some(String::from("Example secret description")),
                 kms_key_id: None,
                 secret_ref: Some(String::from("secret://aws/secretmanager/some/secret.sealed")),
+                secret_binary_file: None,
                 tags: Tags::default(),
                 policy_document: default_policy,
+                rotation: None,
+                replica_regions: Vec::new(),
+                version_stages: BTreeMap::new(),
+            })
+        ));
+
+        // Monthly rotation for an RDS PostgreSQL secret, with the function in the database's VPC
+        res.push(skeleton!(
+            SecretsManagerResourceAddress::Rotation {
+                region: String::from("[region]"),
+                name:   String::from("[secret_name]"),
+            },
+            SecretsManagerResource::Rotation(SecretRotation {
+                template: String::from("SecretsManagerRDSPostgreSQLRotationSingleUser"),
+                template_version: None,
+                vpc_subnet_ids: vec![String::from("[subnet_id]")],
+                vpc_security_group_ids: vec![String::from("[security_group_id]")],
+                master_secret_arn: None,
+                kms_key_arn: None,
+                exclude_characters: Some(String::from("/@\"\\'")),
+                rotation_rules: RotationRules {
+                    automatically_after_days: None,
+                    duration: Some(String::from("3h")),
+                    schedule_expression: Some(String::from("rate(30 days)")),
+                },
+                rotate_immediately: false,
+            })
+        ));
+
+        // Pending deletion sweep task skeleton
+        res.push(skeleton!(
+            SecretsManagerTaskAddress::PendingDeletionSweep {
+                name: String::from("[task_name]"),
+            },
+            SecretsManagerTask::PendingDeletionSweep(PendingDeletionSweep {
+                regions: None,
+                within_days: 7,
+                assumed_recovery_window_days: 7,
+                fail_on_pending: false, // When true, the task fails if any secret is found
             })
         ));
 
@@ -235,7 +472,23 @@ impl Connector for SecretsManagerConnector {
         let addr = SecretsManagerResourceAddress::from_path(addr)?;
 
         match addr {
-            SecretsManagerResourceAddress::Secret { region, name } => ron_check_eq::<resource::Secret>(a, b),
+            SecretsManagerResourceAddress::Secret { .. } => {
+                let a: Secret = RON.from_str(std::str::from_utf8(a)?)?;
+                let b: Secret = RON.from_str(std::str::from_utf8(b)?)?;
+                let (mut a, mut b) = (normalize_secret(a), normalize_secret(b));
+                // Staging labels are only managed when they're listed
+                if a.version_stages.is_empty() || b.version_stages.is_empty() {
+                    a.version_stages.clear();
+                    b.version_stages.clear();
+                }
+                Ok(a == b)
+            }
+            SecretsManagerResourceAddress::Rotation { .. } => {
+                let a: SecretRotation = RON.from_str(std::str::from_utf8(a)?)?;
+                let b: SecretRotation = RON.from_str(std::str::from_utf8(b)?)?;
+                let (a, b) = normalize_rotations(a, b);
+                Ok(a == b)
+            }
         }
     }
 
@@ -243,7 +496,8 @@ impl Connector for SecretsManagerConnector {
         let addr = SecretsManagerResourceAddress::from_path(addr)?;
 
         match addr {
-            SecretsManagerResourceAddress::Secret { region, name } => ron_check_syntax::<resource::Secret>(a),
+            SecretsManagerResourceAddress::Secret { .. } => ron_check_syntax::<resource::Secret>(a),
+            SecretsManagerResourceAddress::Rotation { .. } => ron_check_syntax::<SecretRotation>(a),
         }
     }
 }
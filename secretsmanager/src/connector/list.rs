@@ -1,6 +1,8 @@
 use std::path::{Path, PathBuf};
 
+use anyhow::bail;
 use autoschematic_core::{connector::ResourceAddress, glob::addr_matches_filter};
+use aws_sdk_secretsmanager::types::{Filter, FilterNameStringType};
 
 use super::{SecretsManagerConnector, SecretsManagerResourceAddress};
 
@@ -10,6 +12,25 @@ impl SecretsManagerConnector {
 
         let config = self.config.read().await;
 
+        // ListSecrets ANDs separate filters together, and ORs the values within a single filter.
+        let mut filters = Vec::new();
+        if !config.list_name_prefixes.is_empty() {
+            if config.list_name_prefixes.len() > 10 {
+                bail!("list_name_prefixes: at most 10 name prefixes are supported");
+            }
+            filters.push(
+                Filter::builder()
+                    .key(FilterNameStringType::Name)
+                    .set_values(Some(config.list_name_prefixes.clone()))
+                    .build(),
+            );
+        }
+        // tag-key and tag-value filters aren't paired server-side, so only the keys are filtered here
+        // and the values are checked against each secret's tags below.
+        for tag_key in config.list_tag_selectors.keys() {
+            filters.push(Filter::builder().key(FilterNameStringType::TagKey).values(tag_key).build());
+        }
+
         for region_name in &config.enabled_regions {
             if !addr_matches_filter(&PathBuf::from(format!("aws/secretsmanager/{region_name}")), subpath) {
                 continue;
@@ -21,7 +42,11 @@ impl SecretsManagerConnector {
             let mut next_token: Option<String> = None;
 
             loop {
-                let mut list_secrets_request = client.list_secrets();
+                let mut list_secrets_request = client.list_secrets().set_filters(if filters.is_empty() {
+                    None
+                } else {
+                    Some(filters.clone())
+                });
 
                 if let Some(token) = next_token {
                     list_secrets_request = list_secrets_request.next_token(token);
@@ -31,6 +56,26 @@ impl SecretsManagerConnector {
 
                 if let Some(secrets) = secrets_resp.secret_list {
                     for secret in secrets {
+                        if config.list_exclude_aws_managed && secret.owning_service.is_some() {
+                            continue;
+                        }
+
+                        // Replicas are managed through the secret in its primary region
+                        if secret.primary_region.as_ref().is_some_and(|primary| primary != region_name) {
+                            continue;
+                        }
+
+                        let tags = secret.tags.unwrap_or_default();
+                        let tags_match = config.list_tag_selectors.iter().all(|(key, value)| {
+                            tags.iter().any(|tag| {
+                                tag.key.as_deref() == Some(key.as_str())
+                                    && (value == "*" || tag.value.as_deref() == Some(value.as_str()))
+                            })
+                        });
+                        if !tags_match {
+                            continue;
+                        }
+
                         if let Some(secret_name) = secret.name {
                             // Add the secret to results
                             results.push(
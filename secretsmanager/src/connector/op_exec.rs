@@ -4,7 +4,9 @@ use autoschematic_core::{
     connector::{ConnectorOp, OpExecResponse, ResourceAddress}, connector_util::read_mounted_secret, error_util::invalid_op
 };
 
-use crate::tags;
+use aws_sdk_secretsmanager::primitives::Blob;
+
+use crate::{op_impl, tags, util::read_secret_binary};
 
 use super::{SecretsManagerConnector, SecretsManagerConnectorOp, SecretsManagerResourceAddress};
 
@@ -34,12 +36,20 @@ impl SecretsManagerConnector {
                             request = request.secret_string(read_mounted_secret(&self.prefix, secret_ref)?);
                         }
 
+                        if let Some(secret_binary_file) = &secret.secret_binary_file {
+                            request = request.secret_binary(Blob::new(read_secret_binary(&self.prefix, &addr, secret_binary_file)?));
+                        }
+
                         // Add tags if provided
-                        if secret.tags.len() > 0 {
+                        if !secret.tags.is_empty() {
                             let aws_tags = secret.tags.to_vec()?;
                             request = request.set_tags(Some(aws_tags));
                         }
 
+                        if !secret.replica_regions.is_empty() {
+                            request = request.set_add_replica_regions(Some(op_impl::replica_region_types(&secret.replica_regions)));
+                        }
+
                         // Send the request
                         let result = request.send().await?;
 
@@ -50,6 +60,9 @@ impl SecretsManagerConnector {
                         if let Some(arn) = result.arn {
                             outputs.insert(String::from("arn"), Some(arn));
                         }
+                        if let Some(secret_binary_file) = secret.secret_binary_file {
+                            outputs.insert(String::from("secret_binary_file"), Some(secret_binary_file));
+                        }
 
                         Ok(OpExecResponse {
                             outputs: Some(outputs),
@@ -58,7 +71,7 @@ impl SecretsManagerConnector {
                     }
                     SecretsManagerConnectorOp::UpdateSecretDescription { description } => {
                         // Update the secret description
-                        let result = client.update_secret().secret_id(name).description(description).send().await?;
+                        client.update_secret().secret_id(name).description(description).send().await?;
 
                         Ok(OpExecResponse {
                             outputs: None,
@@ -78,13 +91,36 @@ impl SecretsManagerConnector {
                             request = request.client_request_token(token);
                         }
 
-                        let result = request.send().await?;
+                        request.send().await?;
 
                         Ok(OpExecResponse {
                             outputs: None,
                             friendly_message: Some(format!("Updated value for secret '{name}'")),
                         })
                     }
+                    SecretsManagerConnectorOp::UpdateSecretBinary {
+                        secret_binary_file,
+                        client_request_token,
+                    } => {
+                        let mut request = client
+                            .put_secret_value()
+                            .secret_id(name)
+                            .secret_binary(Blob::new(read_secret_binary(&self.prefix, &addr, &secret_binary_file)?));
+
+                        if let Some(token) = client_request_token {
+                            request = request.client_request_token(token);
+                        }
+
+                        request.send().await?;
+
+                        Ok(OpExecResponse {
+                            outputs: Some(HashMap::from([(
+                                String::from("secret_binary_file"),
+                                Some(secret_binary_file.clone()),
+                            )])),
+                            friendly_message: Some(format!("Updated binary value for secret '{name}' from {secret_binary_file}")),
+                        })
+                    }
                     SecretsManagerConnectorOp::UpdateSecretTags(old_tags, new_tags) => {
                         // Calculate tag differences
                         let (untag_keys, new_tagset) = tags::tag_diff(&old_tags, &new_tags)?;
@@ -116,7 +152,7 @@ impl SecretsManagerConnector {
                     }
                     SecretsManagerConnectorOp::UpdateSecretKmsKeyId { kms_key_id } => {
                         // Update the KMS key ID used to encrypt the secret
-                        let result = client.update_secret().secret_id(name).kms_key_id(kms_key_id).send().await?;
+                        client.update_secret().secret_id(name).kms_key_id(kms_key_id).send().await?;
 
                         Ok(OpExecResponse {
                             outputs: None,
@@ -139,7 +175,7 @@ impl SecretsManagerConnector {
                                 request = request.force_delete_without_recovery(true);
                             }
 
-                        let result = request.send().await?;
+                        request.send().await?;
 
                         Ok(OpExecResponse {
                             outputs: None,
@@ -148,7 +184,7 @@ impl SecretsManagerConnector {
                     }
                     SecretsManagerConnectorOp::RestoreSecret => {
                         // Restore a previously deleted secret
-                        let result = client.restore_secret().secret_id(name).send().await?;
+                        client.restore_secret().secret_id(name).send().await?;
 
                         Ok(OpExecResponse {
                             outputs: None,
@@ -182,13 +218,32 @@ impl SecretsManagerConnector {
 
                         request = request.rotation_rules(rotation_rules_builder.build());
 
-                        let result = request.send().await?;
+                        // RotateSecret rotates straight away unless told otherwise; that's RotateSecretImmediately's job
+                        request = request.rotate_immediately(false);
+
+                        request.send().await?;
 
                         Ok(OpExecResponse {
                             outputs: None,
                             friendly_message: Some(format!("Configured rotation for secret '{name}'")),
                         })
                     }
+                    SecretsManagerConnectorOp::RotateSecretImmediately => op_impl::rotate_secret_immediately(&client, name).await,
+                    SecretsManagerConnectorOp::DisableRotation => op_impl::disable_rotation(&client, name).await,
+                    SecretsManagerConnectorOp::UpdateSecretVersionStage {
+                        version_stage,
+                        move_to_version_id,
+                        remove_from_version_id,
+                    } => {
+                        op_impl::update_secret_version_stage(&client, name, &version_stage, move_to_version_id, remove_from_version_id)
+                            .await
+                    }
+                    SecretsManagerConnectorOp::ReplicateSecretToRegions(replica_regions) => {
+                        op_impl::replicate_secret_to_regions(&client, name, &replica_regions).await
+                    }
+                    SecretsManagerConnectorOp::RemoveRegionsFromReplication(regions) => {
+                        op_impl::remove_regions_from_replication(&client, name, &regions).await
+                    }
                     SecretsManagerConnectorOp::SetSecretPolicy {
                         policy_document,
                         block_public_policy,
@@ -202,7 +257,7 @@ impl SecretsManagerConnector {
                             request = request.block_public_policy(block);
                         }
 
-                        let result = request.send().await?;
+                        request.send().await?;
 
                         Ok(OpExecResponse {
                             outputs: None,
@@ -211,7 +266,7 @@ impl SecretsManagerConnector {
                     }
                     SecretsManagerConnectorOp::DeleteSecretPolicy => {
                         // Delete the resource policy
-                        let result = client.delete_resource_policy().secret_id(name).send().await?;
+                        client.delete_resource_policy().secret_id(name).send().await?;
 
                         Ok(OpExecResponse {
                             outputs: None,
@@ -221,6 +276,25 @@ impl SecretsManagerConnector {
                     op => Err(invalid_op(&addr, &op)),
                 }
             }
+            SecretsManagerResourceAddress::Rotation { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+                let cfn_client = self.get_or_init_cfn_client(region).await?;
+
+                match op {
+                    SecretsManagerConnectorOp::DeployRotationFunction(rotation) => {
+                        let sar_client = self.get_or_init_sar_client(region).await?;
+                        op_impl::deploy_rotation_function(&sar_client, &cfn_client, region, name, &rotation).await
+                    }
+                    SecretsManagerConnectorOp::EnableRotation(rotation) => {
+                        op_impl::enable_rotation(&client, &cfn_client, name, &rotation).await
+                    }
+                    SecretsManagerConnectorOp::DisableRotation => op_impl::disable_rotation(&client, name).await,
+                    SecretsManagerConnectorOp::DeleteRotationFunction => {
+                        op_impl::delete_rotation_function(&cfn_client, name).await
+                    }
+                    op => Err(invalid_op(&addr, &op)),
+                }
+            }
         }
     }
 }
@@ -1,5 +1,9 @@
-use std::path::Path;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::Path,
+};
 
+use anyhow::bail;
 use autoschematic_core::{
     connector::{PlanResponseElement, ResourceAddress},
     connector_op,
@@ -8,9 +12,187 @@ use autoschematic_core::{
 
 use autoschematic_core::connector::ConnectorOp;
 
-use crate::resource::Secret;
+use crate::{
+    resource::{ReplicaRegion, RotationConfig, Secret, SecretRotation},
+    util::{check_rotation, check_rotation_config, read_secret_binary},
+};
+
+use super::{normalize_rotations, SecretsManagerConnector, SecretsManagerConnectorOp, SecretsManagerResourceAddress};
+
+fn check_secret_value(prefix: &Path, addr: &SecretsManagerResourceAddress, name: &str, secret: &Secret) -> anyhow::Result<()> {
+    if secret.secret_ref.is_some() && secret.secret_binary_file.is_some() {
+        bail!("Secret '{}' sets both secret_ref and secret_binary_file: set at most one", name);
+    }
+    if let Some(secret_binary_file) = &secret.secret_binary_file {
+        read_secret_binary(prefix, addr, secret_binary_file)?;
+    }
+    Ok(())
+}
+
+/// The ops that move a secret's staging labels from `old` to `new`. AWSCURRENT moves last, so that
+/// a blue/green rollout can label the new version (e.g. AWSPENDING) in the same plan that promotes it.
+fn version_stage_ops(
+    name: &str,
+    old: &BTreeMap<String, String>,
+    new: &BTreeMap<String, String>,
+) -> anyhow::Result<Vec<PlanResponseElement>> {
+    // Leaving version_stages empty leaves the labels alone
+    if new.is_empty() {
+        return Ok(Vec::new());
+    }
+    if new.contains_key("AWSPREVIOUS") {
+        bail!(
+            "Secret '{}' lists AWSPREVIOUS in version_stages, but it follows AWSCURRENT on its own",
+            name
+        );
+    }
+    if !new.contains_key("AWSCURRENT") {
+        bail!("Secret '{}' must keep AWSCURRENT on one of its versions", name);
+    }
+
+    let mut stages: Vec<&String> = old.keys().chain(new.keys()).collect();
+    stages.sort_by_key(|stage| (stage.as_str() == "AWSCURRENT", stage.as_str()));
+    stages.dedup();
+
+    let mut ops = Vec::new();
+    for stage in stages {
+        let old_version_id = old.get(stage);
+        let new_version_id = new.get(stage);
+        if old_version_id == new_version_id {
+            continue;
+        }
+
+        let message = match (old_version_id, new_version_id) {
+            (Some(old_version_id), Some(new_version_id)) => format!(
+                "Move {} on secret '{}' from version {} to {}",
+                stage, name, old_version_id, new_version_id
+            ),
+            (None, Some(new_version_id)) => format!("Attach {} to version {} of secret '{}'", stage, new_version_id, name),
+            _ => format!("Remove {} from secret '{}'", stage, name),
+        };
+        ops.push(connector_op!(
+            SecretsManagerConnectorOp::UpdateSecretVersionStage {
+                version_stage: stage.clone(),
+                move_to_version_id: new_version_id.cloned(),
+                remove_from_version_id: old_version_id.cloned(),
+            },
+            message
+        ));
+    }
+    Ok(ops)
+}
+
+/// The ops that take a secret's own rotation config from `old` to `new`.
+fn rotation_config_ops(
+    name: &str,
+    old: Option<RotationConfig>,
+    new: Option<RotationConfig>,
+) -> anyhow::Result<Vec<PlanResponseElement>> {
+    if let Some(new) = &new {
+        check_rotation_config(name, new)?;
+    }
 
-use super::{SecretsManagerConnector, SecretsManagerConnectorOp, SecretsManagerResourceAddress};
+    let mut ops = Vec::new();
+    match (old, new) {
+        (None, None) => {}
+        (None, Some(new)) => {
+            ops.push(connector_op!(
+                SecretsManagerConnectorOp::RotateSecret {
+                    rotation_lambda_arn: new.rotation_lambda_arn.clone(),
+                    rotation_rules:      new.rotation_rules.clone(),
+                },
+                format!("Enable rotation for secret '{}' with {}", name, new.rotation_lambda_arn)
+            ));
+            if new.rotate_immediately {
+                ops.push(connector_op!(
+                    SecretsManagerConnectorOp::RotateSecretImmediately,
+                    format!("Rotate secret '{}' now", name)
+                ));
+            }
+        }
+        (Some(_), None) => ops.push(connector_op!(
+            SecretsManagerConnectorOp::DisableRotation,
+            format!("Disable rotation for secret '{}'", name)
+        )),
+        (Some(old), Some(new)) => {
+            let old = RotationConfig {
+                rotate_immediately: new.rotate_immediately,
+                ..old
+            };
+            if old != new {
+                let diff = diff_ron_values(&old, &new).unwrap_or_default();
+                ops.push(connector_op!(
+                    SecretsManagerConnectorOp::RotateSecret {
+                        rotation_lambda_arn: new.rotation_lambda_arn.clone(),
+                        rotation_rules:      new.rotation_rules.clone(),
+                    },
+                    format!("Update rotation for secret '{}'\n{}", name, diff)
+                ));
+                if new.rotate_immediately && old.rotation_lambda_arn != new.rotation_lambda_arn {
+                    ops.push(connector_op!(
+                        SecretsManagerConnectorOp::RotateSecretImmediately,
+                        format!("Rotate secret '{}' now with {}", name, new.rotation_lambda_arn)
+                    ));
+                }
+            }
+        }
+    }
+    Ok(ops)
+}
+
+/// The ops that take a secret's replicas from `old` to `new`. A replica can't change its key, so a
+/// replica whose key changes is removed and replicated again.
+fn replica_ops(
+    name: &str,
+    region: &str,
+    old: &[ReplicaRegion],
+    new: &[ReplicaRegion],
+) -> anyhow::Result<Vec<PlanResponseElement>> {
+    let mut seen = HashSet::new();
+    for replica in new {
+        if replica.region == region {
+            bail!("Secret '{}' can't be replicated to its own region {}", name, region);
+        }
+        if !seen.insert(&replica.region) {
+            bail!("Secret '{}' lists replica region {} more than once", name, replica.region);
+        }
+    }
+
+    let old_by_region: HashMap<&str, &ReplicaRegion> = old.iter().map(|replica| (replica.region.as_str(), replica)).collect();
+    let new_by_region: HashMap<&str, &ReplicaRegion> = new.iter().map(|replica| (replica.region.as_str(), replica)).collect();
+
+    let mut remove = Vec::new();
+    let mut replicate = Vec::new();
+    for replica in old {
+        match new_by_region.get(replica.region.as_str()) {
+            None => remove.push(replica.region.clone()),
+            Some(new_replica) if new_replica.kms_key_id != replica.kms_key_id => {
+                remove.push(replica.region.clone());
+                replicate.push((*new_replica).clone());
+            }
+            Some(_) => {}
+        }
+    }
+    for replica in new {
+        if !old_by_region.contains_key(replica.region.as_str()) {
+            replicate.push(replica.clone());
+        }
+    }
+
+    let mut ops = Vec::new();
+    if !remove.is_empty() {
+        ops.push(connector_op!(
+            SecretsManagerConnectorOp::RemoveRegionsFromReplication(remove.clone()),
+            format!("DELETE replicas of secret '{}' in {}", name, remove.join(", "))
+        ));
+    }
+    if !replicate.is_empty() {
+        let regions: Vec<&str> = replicate.iter().map(|replica| replica.region.as_str()).collect();
+        let message = format!("Replicate secret '{}' to {}", name, regions.join(", "));
+        ops.push(connector_op!(SecretsManagerConnectorOp::ReplicateSecretToRegions(replicate), message));
+    }
+    Ok(ops)
+}
 
 impl SecretsManagerConnector {
     pub async fn do_plan(
@@ -24,17 +206,30 @@ impl SecretsManagerConnector {
         let current = optional_string_from_utf8(current)?;
         let desired = optional_string_from_utf8(desired)?;
 
-        match addr {
+        match &addr {
             SecretsManagerResourceAddress::Secret { region, name } => {
                 match (current, desired) {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_secret_str)) => {
                         // Create a new secret
                         let new_secret: Secret = RON.from_str(&new_secret_str)?;
-                        Ok(vec![connector_op!(
+                        check_secret_value(&self.prefix, &addr, name, &new_secret)?;
+                        if !new_secret.version_stages.is_empty() {
+                            bail!(
+                                "Secret '{}' can't set version_stages before it exists. Create it first, then label its versions.",
+                                name
+                            );
+                        }
+                        let rotation_ops = rotation_config_ops(name, None, new_secret.rotation.clone())?;
+                        // CreateSecret replicates the secret as well, so this only checks the replicas
+                        replica_ops(name, region, &[], &new_secret.replica_regions)?;
+
+                        let mut ops = vec![connector_op!(
                             SecretsManagerConnectorOp::CreateSecret(new_secret),
                             format!("Create new secret '{}'", name)
-                        )])
+                        )];
+                        ops.extend(rotation_ops);
+                        Ok(ops)
                     }
                     (Some(_), None) => {
                         // Delete an existing secret
@@ -50,6 +245,7 @@ impl SecretsManagerConnector {
                         // Compare old and new secret to determine what needs to be updated
                         let old_secret: Secret = RON.from_str(&old_secret_str)?;
                         let new_secret: Secret = RON.from_str(&new_secret_str)?;
+                        check_secret_value(&self.prefix, &addr, name, &new_secret)?;
                         let mut ops = Vec::new();
 
                         // Check for description changes
@@ -87,10 +283,10 @@ impl SecretsManagerConnector {
                         // } else {
                         if let Some(secret_ref) = new_secret.secret_ref {
                             let secret_value = self
-                                .get_or_init_client(&region)
+                                .get_or_init_client(region)
                                 .await?
                                 .get_secret_value()
-                                .secret_id(&name)
+                                .secret_id(name)
                                 .send()
                                 .await;
                             // TODO something something compare the secret value, you know...
@@ -98,6 +294,18 @@ impl SecretsManagerConnector {
                         // }
                         // }
 
+                        // get only reports the binary file while the secret's value still matches it
+                        if old_secret.secret_binary_file != new_secret.secret_binary_file
+                            && let Some(secret_binary_file) = &new_secret.secret_binary_file {
+                                ops.push(connector_op!(
+                                    SecretsManagerConnectorOp::UpdateSecretBinary {
+                                        secret_binary_file:   secret_binary_file.clone(),
+                                        client_request_token: None,
+                                    },
+                                    format!("Update binary value for secret '{}' from {}", name, secret_binary_file)
+                                ));
+                            }
+
                         if old_secret.policy_document != new_secret.policy_document {
                             let diff =
                                 diff_ron_values(&old_secret.policy_document, &old_secret.policy_document).unwrap_or_default();
@@ -111,6 +319,10 @@ impl SecretsManagerConnector {
                             ))
                         }
 
+                        ops.extend(rotation_config_ops(name, old_secret.rotation.clone(), new_secret.rotation.clone())?);
+                        ops.extend(version_stage_ops(name, &old_secret.version_stages, &new_secret.version_stages)?);
+                        ops.extend(replica_ops(name, region, &old_secret.replica_regions, &new_secret.replica_regions)?);
+
                         // Check for tag changes
                         if old_secret.tags != new_secret.tags {
                             let diff = diff_ron_values(&old_secret.tags, &new_secret.tags).unwrap_or_default();
@@ -120,6 +332,67 @@ impl SecretsManagerConnector {
                             ));
                         }
 
+                        Ok(ops)
+                    }
+                }
+            }
+            SecretsManagerResourceAddress::Rotation { name, .. } => {
+                match (current, desired) {
+                    (None, None) => Ok(vec![]),
+                    (None, Some(new_rotation_str)) => {
+                        let new_rotation: SecretRotation = RON.from_str(&new_rotation_str)?;
+                        check_rotation(name, &new_rotation)?;
+
+                        Ok(vec![
+                            connector_op!(
+                                SecretsManagerConnectorOp::DeployRotationFunction(new_rotation.clone()),
+                                format!("Deploy {} rotation function for secret '{}'", new_rotation.template, name)
+                            ),
+                            connector_op!(
+                                SecretsManagerConnectorOp::EnableRotation(new_rotation),
+                                format!("Enable rotation for secret '{}'", name)
+                            ),
+                        ])
+                    }
+                    (Some(_), None) => Ok(vec![
+                        connector_op!(
+                            SecretsManagerConnectorOp::DisableRotation,
+                            format!("Disable rotation for secret '{}'", name)
+                        ),
+                        connector_op!(
+                            SecretsManagerConnectorOp::DeleteRotationFunction,
+                            format!("DELETE rotation function and stack for secret '{}'", name)
+                        ),
+                    ]),
+                    (Some(old_rotation_str), Some(new_rotation_str)) => {
+                        let old_rotation: SecretRotation = RON.from_str(&old_rotation_str)?;
+                        let new_rotation: SecretRotation = RON.from_str(&new_rotation_str)?;
+                        check_rotation(name, &new_rotation)?;
+
+                        let (old, new) = normalize_rotations(old_rotation, new_rotation.clone());
+                        let mut ops = Vec::new();
+
+                        // Everything but the schedule lives in the rotation stack
+                        let old_function = SecretRotation {
+                            rotation_rules: new.rotation_rules.clone(),
+                            ..old.clone()
+                        };
+                        if old_function != new {
+                            let diff = diff_ron_values(&old_function, &new).unwrap_or_default();
+                            ops.push(connector_op!(
+                                SecretsManagerConnectorOp::DeployRotationFunction(new_rotation.clone()),
+                                format!("Update rotation function for secret '{}'\n{}", name, diff)
+                            ));
+                        }
+
+                        if old.rotation_rules != new.rotation_rules {
+                            let diff = diff_ron_values(&old.rotation_rules, &new.rotation_rules).unwrap_or_default();
+                            ops.push(connector_op!(
+                                SecretsManagerConnectorOp::EnableRotation(new_rotation),
+                                format!("Update rotation schedule for secret '{}'\n{}", name, diff)
+                            ));
+                        }
+
                         Ok(ops)
                     }
                 }
@@ -0,0 +1,131 @@
+use std::{
+    collections::HashMap,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::bail;
+use autoschematic_core::connector::{ConnectorOp, Resource, ResourceAddress, TaskExecResponse};
+use aws_sdk_secretsmanager::primitives::{DateTime, DateTimeFormat};
+
+use crate::task::{PendingDeletionSweep, SecretsManagerTask, SecretsManagerTaskAddress};
+
+use super::{SecretsManagerConnector, SecretsManagerConnectorOp, SecretsManagerResourceAddress};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+impl SecretsManagerConnector {
+    pub async fn do_task_exec(
+        &self,
+        addr: &Path,
+        body: Vec<u8>,
+        _arg: Option<Vec<u8>>,
+        _state: Option<Vec<u8>>,
+    ) -> anyhow::Result<TaskExecResponse> {
+        let addr = SecretsManagerTaskAddress::from_path(addr)?;
+
+        let task = SecretsManagerTask::from_bytes(&addr, &body)?;
+        match task {
+            SecretsManagerTask::PendingDeletionSweep(sweep) => self.pending_deletion_sweep(sweep).await,
+        }
+    }
+
+    async fn pending_deletion_sweep(&self, sweep: PendingDeletionSweep) -> anyhow::Result<TaskExecResponse> {
+        if sweep.within_days < 0 {
+            bail!("within_days must not be negative");
+        }
+        if !(7..=30).contains(&sweep.assumed_recovery_window_days) {
+            bail!("assumed_recovery_window_days must be between 7 and 30");
+        }
+
+        let regions = match sweep.regions {
+            Some(regions) => regions,
+            None => self.config.read().await.enabled_regions.clone(),
+        };
+
+        let now_secs = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        let cutoff_secs = now_secs + sweep.within_days * SECONDS_PER_DAY;
+
+        let restore_op = SecretsManagerConnectorOp::RestoreSecret.to_string()?;
+
+        let mut pending = Vec::new();
+        let mut outputs = HashMap::new();
+
+        for region in regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut secrets = client
+                .list_secrets()
+                .include_planned_deletion(true)
+                .into_paginator()
+                .items()
+                .send();
+            while let Some(secret) = secrets.next().await {
+                let secret = secret?;
+                let (Some(name), Some(deleted_date)) = (secret.name, secret.deleted_date) else {
+                    continue;
+                };
+
+                let deletion_secs = deleted_date.secs() + sweep.assumed_recovery_window_days * SECONDS_PER_DAY;
+                if deletion_secs > cutoff_secs {
+                    continue;
+                }
+
+                let deleted_at = deleted_date.fmt(DateTimeFormat::DateTime)?;
+                let deletion_at = DateTime::from_secs(deletion_secs).fmt(DateTimeFormat::DateTime)?;
+
+                let secret_addr = SecretsManagerResourceAddress::Secret {
+                    region: region.clone(),
+                    name:   name.clone(),
+                };
+                let path = secret_addr.to_path_buf();
+
+                pending.push((
+                    deletion_secs,
+                    format!(
+                        "{}: deleted at {}, may be permanently deleted from {}\n    restore with op: {}",
+                        path.display(),
+                        deleted_at,
+                        deletion_at,
+                        restore_op
+                    ),
+                ));
+
+                let key = format!("{}/{}", region, name);
+                outputs.insert(format!("{}/deleted_date", key), Some(deleted_at));
+                outputs.insert(format!("{}/earliest_deletion_date", key), Some(deletion_at));
+            }
+        }
+
+        if pending.is_empty() {
+            return Ok(TaskExecResponse {
+                friendly_message: Some(format!(
+                    "No Secrets Manager secrets can be permanently deleted within the next {} days",
+                    sweep.within_days
+                )),
+                ..Default::default()
+            });
+        }
+
+        // Soonest deletions first
+        pending.sort_by_key(|(deletion_secs, _)| *deletion_secs);
+        let report: Vec<String> = pending.into_iter().map(|(_, line)| line).collect();
+
+        let friendly_message = format!(
+            "{} Secrets Manager secrets are scheduled for deletion and may be permanently deleted within the next {} days:\n{}",
+            report.len(),
+            sweep.within_days,
+            report.join("\n")
+        );
+
+        if sweep.fail_on_pending {
+            bail!("{}", friendly_message);
+        }
+
+        Ok(TaskExecResponse {
+            outputs: Some(outputs),
+            friendly_message: Some(friendly_message),
+            ..Default::default()
+        })
+    }
+}
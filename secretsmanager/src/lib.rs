@@ -1,3 +1,5 @@
-mod addr;
-mod resource;
-mod tags;
\ No newline at end of file
+pub mod addr;
+pub mod config;
+pub mod op;
+pub mod resource;
+pub mod tags;
@@ -2,7 +2,7 @@ use autoschematic_core::{connector::ConnectorOp, util::RON};
 use serde::{Deserialize, Serialize};
 
 use super::{
-    resource::Secret,
+    resource::{ReplicaRegion, RotationRules, Secret, SecretRotation},
     tags::Tags,
 };
 
@@ -17,6 +17,11 @@ pub enum SecretsManagerConnectorOp {
         secret_ref: String,
         client_request_token: Option<String>,
     },
+    /// Puts the contents of the secret's binary file as its new SecretBinary value.
+    UpdateSecretBinary {
+        secret_binary_file: String,
+        client_request_token: Option<String>,
+    },
     UpdateSecretTags(Tags, Tags),
     UpdateSecretKmsKeyId {
         kms_key_id: String,
@@ -26,10 +31,23 @@ pub enum SecretsManagerConnectorOp {
         force_delete_without_recovery: Option<bool>,
     },
     RestoreSecret,
+    /// Sets the secret's rotation function and rules, without starting a rotation.
     RotateSecret {
         rotation_lambda_arn: String,
         rotation_rules: RotationRules,
     },
+    /// Starts a rotation now, with the rotation function and rules the secret already has.
+    RotateSecretImmediately,
+    /// Attaches a staging label to a version, taking it off the version that had it.
+    /// With no move_to_version_id, the label is just removed.
+    UpdateSecretVersionStage {
+        version_stage: String,
+        move_to_version_id: Option<String>,
+        remove_from_version_id: Option<String>,
+    },
+    ReplicateSecretToRegions(Vec<ReplicaRegion>),
+    /// Deletes the replicas in these regions.
+    RemoveRegionsFromReplication(Vec<String>),
 
     // Secret Policy operations
     SetSecretPolicy {
@@ -37,13 +55,14 @@ pub enum SecretsManagerConnectorOp {
         block_public_policy: Option<bool>,
     },
     DeleteSecretPolicy,
-}
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct RotationRules {
-    pub automatically_after_days: Option<i64>,
-    pub duration: Option<String>,
-    pub schedule_expression: Option<String>,
+    // Rotation operations
+    /// Creates or updates the CloudFormation stack for the rotation function.
+    DeployRotationFunction(SecretRotation),
+    /// Points the secret at the deployed rotation function, with the rotation's rules.
+    EnableRotation(SecretRotation),
+    DisableRotation,
+    DeleteRotationFunction,
 }
 
 impl ConnectorOp for SecretsManagerConnectorOp {
@@ -1,12 +1,23 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::{Context, bail};
+use aws_sdk_cloudformation::types::{ChangeSetStatus, StackStatus};
+use aws_sdk_serverlessapplicationrepository::types::ParameterValue;
 
 use super::{
-    resource::{Secret },
+    resource::{ReplicaRegion, RotationRules, Secret, SecretRotation},
     tags::Tags, 
-    op::RotationRules,
+    util::{
+        ROTATION_LAMBDA_ARN_OUTPUT, ROTATION_TEMPLATE_TAG, find_stack, rotation_application_id, rotation_function_name,
+        rotation_stack_base_name, rotation_stack_name, stack_output,
+    },
 };
 use autoschematic_core::{connector::OpExecResponse, util::RON};
 
+const STACK_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Rotation stacks create a role and a function, which usually takes a minute or two.
+const STACK_MAX_POLLS: usize = 120;
+
 /// Creates a Secret using the provided configuration
 pub async fn create_secret(
     client: &aws_sdk_secretsmanager::Client,
@@ -28,7 +39,7 @@ pub async fn create_secret(
     }
     
     // Add tags if provided
-    if secret.tags.len() > 0 {
+    if !secret.tags.is_empty() {
         let aws_tags = secret.tags.to_vec()?;
         request = request.set_tags(Some(aws_tags));
     }
@@ -213,6 +224,7 @@ pub async fn rotate_secret(
     }
     
     request = request.rotation_rules(rotation_rules_builder.build());
+    request = request.rotate_immediately(false);
     
     let result = request.send().await?;
     
@@ -222,6 +234,114 @@ pub async fn rotate_secret(
     })
 }
 
+/// Starts a rotation with the secret's current rotation function and rules
+pub async fn rotate_secret_immediately(
+    client: &aws_sdk_secretsmanager::Client,
+    secret_id: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    // With no function or rules given, RotateSecret rotates using the ones already configured
+    let result = client.rotate_secret().secret_id(secret_id).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Started rotation for secret '{secret_id}' (version {})",
+            result.version_id().unwrap_or("unknown")
+        )),
+    })
+}
+
+/// Moves a staging label between a secret's versions
+pub async fn update_secret_version_stage(
+    client: &aws_sdk_secretsmanager::Client,
+    secret_id: &str,
+    version_stage: &str,
+    move_to_version_id: Option<String>,
+    remove_from_version_id: Option<String>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .update_secret_version_stage()
+        .secret_id(secret_id)
+        .version_stage(version_stage)
+        .set_move_to_version_id(move_to_version_id.clone())
+        .set_remove_from_version_id(remove_from_version_id)
+        .send()
+        .await?;
+
+    let friendly_message = match move_to_version_id {
+        Some(version_id) => format!("Moved {version_stage} to version {version_id} of secret '{secret_id}'"),
+        None => format!("Removed {version_stage} from secret '{secret_id}'"),
+    };
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(friendly_message),
+    })
+}
+
+pub fn replica_region_types(replica_regions: &[ReplicaRegion]) -> Vec<aws_sdk_secretsmanager::types::ReplicaRegionType> {
+    replica_regions
+        .iter()
+        .map(|replica| {
+            aws_sdk_secretsmanager::types::ReplicaRegionType::builder()
+                .region(&replica.region)
+                .set_kms_key_id(replica.kms_key_id.clone())
+                .build()
+        })
+        .collect()
+}
+
+/// Replicates a secret to more regions
+pub async fn replicate_secret_to_regions(
+    client: &aws_sdk_secretsmanager::Client,
+    secret_id: &str,
+    replica_regions: &[ReplicaRegion],
+) -> Result<OpExecResponse, anyhow::Error> {
+    let result = client
+        .replicate_secret_to_regions()
+        .secret_id(secret_id)
+        .set_add_replica_regions(Some(replica_region_types(replica_regions)))
+        .send()
+        .await?;
+
+    // Failures are reported per region rather than as an error
+    for status in result.replication_status() {
+        if status.status.as_ref().is_some_and(|s| s.as_str() == "Failed") {
+            bail!(
+                "Failed to replicate secret '{}' to {}: {}",
+                secret_id,
+                status.region().unwrap_or("unknown"),
+                status.status_message().unwrap_or("no reason given")
+            );
+        }
+    }
+
+    let regions: Vec<&str> = replica_regions.iter().map(|replica| replica.region.as_str()).collect();
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Replicated secret '{secret_id}' to {}", regions.join(", "))),
+    })
+}
+
+/// Deletes a secret's replicas in the given regions
+pub async fn remove_regions_from_replication(
+    client: &aws_sdk_secretsmanager::Client,
+    secret_id: &str,
+    regions: &[String],
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .remove_regions_from_replication()
+        .secret_id(secret_id)
+        .set_remove_replica_regions(Some(regions.to_vec()))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Removed replicas of secret '{secret_id}' in {}", regions.join(", "))),
+    })
+}
+
 /// Sets a resource policy for a secret
 pub async fn set_secret_policy(
     client: &aws_sdk_secretsmanager::Client,
@@ -268,3 +388,197 @@ pub async fn delete_secret_policy(
             result.arn().unwrap_or("unknown"))),
     })
 }
+
+fn rotation_rules_type(rotation_rules: &RotationRules) -> aws_sdk_secretsmanager::types::RotationRulesType {
+    aws_sdk_secretsmanager::types::RotationRulesType::builder()
+        .set_automatically_after_days(rotation_rules.automatically_after_days)
+        .set_duration(rotation_rules.duration.clone())
+        .set_schedule_expression(rotation_rules.schedule_expression.clone())
+        .build()
+}
+
+fn parameter(name: &str, value: impl Into<String>) -> anyhow::Result<ParameterValue> {
+    Ok(ParameterValue::builder().name(name).value(value).build()?)
+}
+
+/// Polls the stack until it settles, failing if it ends up anywhere but `*_COMPLETE`.
+async fn wait_for_stack(cfn_client: &aws_sdk_cloudformation::Client, stack_name: &str) -> anyhow::Result<()> {
+    for _ in 0..STACK_MAX_POLLS {
+        let Some(stack) = find_stack(cfn_client, stack_name).await? else {
+            return Ok(());
+        };
+
+        match stack.stack_status {
+            Some(StackStatus::CreateComplete | StackStatus::UpdateComplete | StackStatus::DeleteComplete) => return Ok(()),
+            Some(ref status) if status.as_str().ends_with("_FAILED") || status.as_str().ends_with("ROLLBACK_COMPLETE") => {
+                bail!(
+                    "Rotation stack {} is {}: {}",
+                    stack_name,
+                    status.as_str(),
+                    stack.stack_status_reason.as_deref().unwrap_or("no reason given")
+                );
+            }
+            _ => {}
+        }
+
+        tokio::time::sleep(STACK_POLL_INTERVAL).await;
+    }
+
+    bail!("Timed out waiting for rotation stack {}", stack_name)
+}
+
+/// Deploys the rotation template as a CloudFormation stack through the Serverless Application
+/// Repository, creating the stack or updating it in place.
+pub async fn deploy_rotation_function(
+    sar_client: &aws_sdk_serverlessapplicationrepository::Client,
+    cfn_client: &aws_sdk_cloudformation::Client,
+    region: &str,
+    secret_name: &str,
+    rotation: &SecretRotation,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let stack_name = rotation_stack_name(secret_name);
+
+    let mut parameters = vec![
+        parameter("endpoint", format!("https://secretsmanager.{}.amazonaws.com", region))?,
+        parameter("functionName", rotation_function_name(secret_name))?,
+    ];
+    if !rotation.vpc_subnet_ids.is_empty() {
+        parameters.push(parameter("vpcSubnetIds", rotation.vpc_subnet_ids.join(","))?);
+        parameters.push(parameter("vpcSecurityGroupIds", rotation.vpc_security_group_ids.join(","))?);
+    }
+    if let Some(master_secret_arn) = &rotation.master_secret_arn {
+        parameters.push(parameter("masterSecretArn", master_secret_arn)?);
+    }
+    if let Some(kms_key_arn) = &rotation.kms_key_arn {
+        parameters.push(parameter("kmsKeyArn", kms_key_arn)?);
+    }
+    if let Some(exclude_characters) = &rotation.exclude_characters {
+        parameters.push(parameter("excludeCharacters", exclude_characters)?);
+    }
+
+    let resp = sar_client
+        .create_cloud_formation_change_set()
+        .application_id(rotation_application_id(&rotation.template))
+        .set_semantic_version(rotation.template_version.clone())
+        .stack_name(rotation_stack_base_name(secret_name))
+        .capabilities("CAPABILITY_IAM")
+        .capabilities("CAPABILITY_RESOURCE_POLICY")
+        .set_parameter_overrides(Some(parameters))
+        .tags(
+            aws_sdk_serverlessapplicationrepository::types::Tag::builder()
+                .key(ROTATION_TEMPLATE_TAG)
+                .value(&rotation.template)
+                .build()?,
+        )
+        .send()
+        .await?;
+
+    let change_set_id = resp
+        .change_set_id
+        .context("CreateCloudFormationChangeSet returned no change set ID")?;
+
+    // The change set has to finish computing before it can be executed
+    let mut ready = false;
+    for _ in 0..STACK_MAX_POLLS {
+        let change_set = cfn_client.describe_change_set().change_set_name(&change_set_id).send().await?;
+        match change_set.status {
+            Some(ChangeSetStatus::CreateComplete) => {
+                ready = true;
+                break;
+            }
+            Some(ChangeSetStatus::Failed) => {
+                let reason = change_set.status_reason.unwrap_or_default();
+                // An update that changes nothing fails to create a change set
+                if reason.contains("didn't contain changes") || reason.contains("No updates") {
+                    cfn_client.delete_change_set().change_set_name(&change_set_id).send().await?;
+                    return Ok(OpExecResponse {
+                        outputs: None,
+                        friendly_message: Some(format!("Rotation function for secret '{secret_name}' is already up to date")),
+                    });
+                }
+                bail!("Change set for rotation stack {} failed: {}", stack_name, reason);
+            }
+            _ => tokio::time::sleep(STACK_POLL_INTERVAL).await,
+        }
+    }
+    if !ready {
+        bail!("Timed out waiting for the change set for rotation stack {}", stack_name);
+    }
+
+    cfn_client.execute_change_set().change_set_name(&change_set_id).send().await?;
+    wait_for_stack(cfn_client, &stack_name).await?;
+
+    let stack = find_stack(cfn_client, &stack_name)
+        .await?
+        .with_context(|| format!("Rotation stack {} not found after deploying it", stack_name))?;
+    let rotation_lambda_arn = stack_output(&stack, ROTATION_LAMBDA_ARN_OUTPUT)
+        .with_context(|| format!("Rotation stack {} has no {} output", stack_name, ROTATION_LAMBDA_ARN_OUTPUT))?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("rotation_stack_name"), Some(stack_name)),
+            (String::from("rotation_lambda_arn"), Some(rotation_lambda_arn)),
+        ])),
+        friendly_message: Some(format!(
+            "Deployed {} rotation function for secret '{secret_name}'",
+            rotation.template
+        )),
+    })
+}
+
+/// Turns on rotation using the function in the secret's rotation stack.
+pub async fn enable_rotation(
+    client: &aws_sdk_secretsmanager::Client,
+    cfn_client: &aws_sdk_cloudformation::Client,
+    secret_name: &str,
+    rotation: &SecretRotation,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let stack_name = rotation_stack_name(secret_name);
+    let stack = find_stack(cfn_client, &stack_name)
+        .await?
+        .with_context(|| format!("Rotation stack {} not found. Deploy the rotation function first.", stack_name))?;
+    let rotation_lambda_arn = stack_output(&stack, ROTATION_LAMBDA_ARN_OUTPUT)
+        .with_context(|| format!("Rotation stack {} has no {} output", stack_name, ROTATION_LAMBDA_ARN_OUTPUT))?;
+
+    client
+        .rotate_secret()
+        .secret_id(secret_name)
+        .rotation_lambda_arn(&rotation_lambda_arn)
+        .rotation_rules(rotation_rules_type(&rotation.rotation_rules))
+        .rotate_immediately(rotation.rotate_immediately)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Enabled rotation for secret '{secret_name}' with {rotation_lambda_arn}")),
+    })
+}
+
+pub async fn disable_rotation(client: &aws_sdk_secretsmanager::Client, secret_name: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.cancel_rotate_secret().secret_id(secret_name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Disabled rotation for secret '{secret_name}'")),
+    })
+}
+
+/// Deletes the rotation stack, along with the function, role and permission it created.
+pub async fn delete_rotation_function(
+    cfn_client: &aws_sdk_cloudformation::Client,
+    secret_name: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let stack_name = rotation_stack_name(secret_name);
+
+    cfn_client.delete_stack().stack_name(&stack_name).send().await?;
+    wait_for_stack(cfn_client, &stack_name).await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("rotation_stack_name"), None),
+            (String::from("rotation_lambda_arn"), None),
+        ])),
+        friendly_message: Some(format!("Deleted rotation function for secret '{secret_name}'")),
+    })
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use autoschematic_core::{
     connector::{Resource, ResourceAddress},
     util::RON,
@@ -11,12 +13,92 @@ pub struct Secret {
     pub description: Option<String>,
     pub kms_key_id: Option<String>,
     pub secret_ref: Option<String>,
+    /// A file holding a binary value for the secret, relative to the directory this file is in.
+    /// Secrets Manager stores it as SecretBinary rather than SecretString, so set at most one of
+    /// this and secret_ref.
+    #[serde(default)]
+    pub secret_binary_file: Option<String>,
     pub policy_document: ron::Value,
     pub tags: Tags,
+    /// Rotation with a function that isn't deployed by this connector. Leave this unset for secrets
+    /// whose rotation is managed at their rotation address instead.
+    #[serde(default)]
+    pub rotation: Option<RotationConfig>,
+    /// Other regions to keep a read-only copy of the secret in. Replicas follow the secret's value,
+    /// and are listed and planned through the secret in its primary region.
+    #[serde(default)]
+    pub replica_regions: Vec<ReplicaRegion>,
+    /// The version each staging label is attached to, e.g. `{"AWSCURRENT": "<version id>"}`.
+    /// AWSPREVIOUS follows AWSCURRENT on its own, so it isn't listed. If empty, labels are left alone,
+    /// which is what rotated secrets want, since rotation moves AWSCURRENT and AWSPENDING itself.
+    #[serde(default)]
+    pub version_stages: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ReplicaRegion {
+    pub region: String,
+    /// The key to encrypt the replica with, in the replica's region. If None, the replica uses
+    /// that region's aws/secretsmanager key.
+    pub kms_key_id: Option<String>,
+}
+
+/// Points the secret at an existing rotation function, e.g. one deployed from your own template.
+/// The function's resource policy must allow Secrets Manager to invoke it.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RotationConfig {
+    pub rotation_lambda_arn: String,
+    pub rotation_rules: RotationRules,
+    /// Rotate as soon as rotation is enabled or moved to another function, rather than waiting for
+    /// the next scheduled rotation.
+    #[serde(default)]
+    pub rotate_immediately: bool,
+}
+
+/// Rotation for the secret of the same name, using one of the rotation functions AWS publishes in
+/// the Serverless Application Repository. The function is deployed as a CloudFormation stack, which
+/// also creates its execution role and the permission for Secrets Manager to invoke it.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SecretRotation {
+    /// The rotation template, e.g. SecretsManagerRDSPostgreSQLRotationSingleUser.
+    pub template: String,
+    /// The template version to deploy. If None, the latest version is deployed.
+    pub template_version: Option<String>,
+    /// The subnets to run the function in, which must be able to reach both the database and
+    /// Secrets Manager (through a NAT gateway or a secretsmanager VPC endpoint).
+    #[serde(default)]
+    pub vpc_subnet_ids: Vec<String>,
+    /// Security groups for the function, which the database's security group must allow.
+    #[serde(default)]
+    pub vpc_security_group_ids: Vec<String>,
+    /// For MultiUser templates: the secret holding the credentials that can change the user's password.
+    pub master_secret_arn: Option<String>,
+    /// The KMS key the secret is encrypted with, if it isn't the aws/secretsmanager key.
+    pub kms_key_arn: Option<String>,
+    /// Characters to leave out of generated passwords.
+    pub exclude_characters: Option<String>,
+    pub rotation_rules: RotationRules,
+    /// Rotate as soon as rotation is enabled, rather than waiting for the first scheduled rotation.
+    #[serde(default)]
+    pub rotate_immediately: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RotationRules {
+    pub automatically_after_days: Option<i64>,
+    /// How long the rotation window lasts, e.g. 3h.
+    pub duration: Option<String>,
+    /// e.g. rate(10 days) or cron(0 16 1,15 * ? *)
+    pub schedule_expression: Option<String>,
 }
 
 pub enum SecretsManagerResource {
     Secret(Secret),
+    Rotation(SecretRotation),
 }
 
 impl Resource for SecretsManagerResource {
@@ -24,6 +106,7 @@ impl Resource for SecretsManagerResource {
         let pretty_config = autoschematic_core::util::PrettyConfig::default();
         match self {
             SecretsManagerResource::Secret(secret) => Ok(RON.to_string_pretty(secret, pretty_config)?.into()),
+            SecretsManagerResource::Rotation(rotation) => Ok(RON.to_string_pretty(rotation, pretty_config)?.into()),
         }
     }
 
@@ -40,6 +123,7 @@ impl Resource for SecretsManagerResource {
                 let secret: Secret = RON.from_str(s)?;
                 Ok(SecretsManagerResource::Secret(secret))
             }
+            SecretsManagerResourceAddress::Rotation { .. } => Ok(SecretsManagerResource::Rotation(RON.from_str(s)?)),
         }
     }
 }
@@ -51,8 +51,8 @@ impl From<&[Tag]> for Tags {
 // }
 
 impl Tags {
-    pub fn len(&self) -> usize {
-        self.0.len()
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
     }
 
     pub fn to_vec(&self) -> anyhow::Result<Vec<Tag>> {
@@ -0,0 +1,123 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::{PrettyConfig, RON};
+
+#[derive(Debug, Clone)]
+pub enum SecretsManagerTaskAddress {
+    PendingDeletionSweep { name: String },
+}
+
+impl ResourceAddress for SecretsManagerTaskAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            SecretsManagerTaskAddress::PendingDeletionSweep { name } => {
+                PathBuf::from(format!("aws/secretsmanager/tasks/pending-deletion-sweep/{name}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        let path_components: Vec<&str> = path
+            .components()
+            .map(|s| s.as_os_str().to_str().context("Path component is not valid UTF-8"))
+            .collect::<Result<Vec<&str>, anyhow::Error>>()?;
+
+        match &path_components[..] {
+            ["aws", "secretsmanager", "tasks", "pending-deletion-sweep", name] if name.ends_with(".ron") => {
+                Ok(SecretsManagerTaskAddress::PendingDeletionSweep {
+                    name: name.strip_suffix(".ron").context("File name must end with .ron")?.to_string(),
+                })
+            }
+            _ => Err(anyhow::anyhow!("Invalid Secrets Manager task address: {}", path.display())),
+        }
+    }
+}
+
+impl DescribeAddresses for SecretsManagerTaskAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/secretsmanager/tasks/pending-deletion-sweep/<name>.ron",
+                description: "Task: report secrets scheduled for deletion",
+                example:     "aws/secretsmanager/tasks/pending-deletion-sweep/weekly.ron",
+            },
+        ]
+    }
+}
+
+/// Reports every secret in `regions` that is scheduled for deletion and could be permanently
+/// deleted within `within_days`, along with the RestoreSecret op that would recover it.
+/// Secrets Manager doesn't report the recovery window a secret was deleted with, so the
+/// earliest possible deletion date is computed from the shortest window (7 days) unless
+/// `assumed_recovery_window_days` says otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PendingDeletionSweep {
+    /// Defaults to the connector's enabled_regions.
+    #[serde(default)]
+    pub regions: Option<Vec<String>>,
+    #[serde(default = "default_within_days")]
+    pub within_days: i64,
+    #[serde(default = "default_assumed_recovery_window_days")]
+    pub assumed_recovery_window_days: i64,
+    /// If set, the task fails when any secret is found, so that a scheduled run is flagged.
+    #[serde(default)]
+    pub fail_on_pending: bool,
+}
+
+fn default_within_days() -> i64 {
+    7
+}
+
+fn default_assumed_recovery_window_days() -> i64 {
+    7
+}
+
+pub enum SecretsManagerTask {
+    PendingDeletionSweep(PendingDeletionSweep),
+}
+
+impl Resource for SecretsManagerTask {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = PrettyConfig::default().struct_names(true);
+        match self {
+            SecretsManagerTask::PendingDeletionSweep(sweep) => match RON.to_string_pretty(&sweep, pretty_config) {
+                Ok(s) => Ok(s.into()),
+                Err(e) => Err(e.into()),
+            },
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = SecretsManagerTaskAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+        match addr {
+            SecretsManagerTaskAddress::PendingDeletionSweep { .. } => Ok(SecretsManagerTask::PendingDeletionSweep(RON.from_str(s)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use autoschematic_connector_aws_core::addr_doc::DescribeAddresses;
+
+    use super::SecretsManagerTaskAddress;
+
+    #[test]
+    fn address_examples_parse() {
+        SecretsManagerTaskAddress::checked_address_patterns().unwrap();
+    }
+}
@@ -0,0 +1,250 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, bail};
+use autoschematic_core::connector::ResourceAddress;
+use aws_sdk_cloudformation::{error::ProvideErrorMetadata, types::Stack};
+
+use crate::addr::SecretsManagerResourceAddress;
+
+/// The rotation functions AWS publishes in the Serverless Application Repository.
+pub const ROTATION_TEMPLATES: &[&str] = &[
+    "SecretsManagerRDSPostgreSQLRotationSingleUser",
+    "SecretsManagerRDSPostgreSQLRotationMultiUser",
+    "SecretsManagerRDSMySQLRotationSingleUser",
+    "SecretsManagerRDSMySQLRotationMultiUser",
+    "SecretsManagerRDSMariaDBRotationSingleUser",
+    "SecretsManagerRDSMariaDBRotationMultiUser",
+    "SecretsManagerRDSOracleRotationSingleUser",
+    "SecretsManagerRDSOracleRotationMultiUser",
+    "SecretsManagerRDSSQLServerRotationSingleUser",
+    "SecretsManagerRDSSQLServerRotationMultiUser",
+    "SecretsManagerRDSDb2RotationSingleUser",
+    "SecretsManagerRDSDb2RotationMultiUser",
+    "SecretsManagerRedshiftRotationSingleUser",
+    "SecretsManagerRedshiftRotationMultiUser",
+    "SecretsManagerMongoDBRotationSingleUser",
+    "SecretsManagerMongoDBRotationMultiUser",
+];
+
+/// The account AWS publishes the rotation templates from.
+const ROTATION_TEMPLATE_OWNER: &str = "297356227824";
+
+/// The stack tag that records which template a rotation stack was deployed from.
+pub const ROTATION_TEMPLATE_TAG: &str = "autoschematic:rotation-template";
+
+/// The stack output the rotation templates put the function's ARN in.
+pub const ROTATION_LAMBDA_ARN_OUTPUT: &str = "RotationLambdaARN";
+
+/// The largest value Secrets Manager will store, in bytes.
+const MAX_SECRET_SIZE: usize = 65536;
+
+/// The key Secrets Manager encrypts with when none is given.
+pub const DEFAULT_KMS_KEY_ALIAS: &str = "alias/aws/secretsmanager";
+
+/// The Serverless Application Repository prefixes this to the stack names it's given.
+const SAR_STACK_PREFIX: &str = "serverlessrepo-";
+
+pub fn rotation_application_id(template: &str) -> String {
+    format!(
+        "arn:aws:serverlessrepo:us-east-1:{}:applications/{}",
+        ROTATION_TEMPLATE_OWNER, template
+    )
+}
+
+pub fn is_multi_user_template(template: &str) -> bool {
+    template.ends_with("MultiUser")
+}
+
+/// Secret names can hold characters that stack and function names can't, so those become dashes.
+fn sanitize(secret_name: &str, max_len: usize) -> String {
+    let sanitized: String = secret_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '-' })
+        .collect();
+    format!("rotation-{}", sanitized).chars().take(max_len).collect()
+}
+
+/// The stack name to give the Serverless Application Repository for the secret's rotation function.
+pub fn rotation_stack_base_name(secret_name: &str) -> String {
+    sanitize(secret_name, 128 - SAR_STACK_PREFIX.len())
+}
+
+/// The name of the CloudFormation stack that holds the secret's rotation function.
+pub fn rotation_stack_name(secret_name: &str) -> String {
+    format!("{}{}", SAR_STACK_PREFIX, rotation_stack_base_name(secret_name))
+}
+
+pub fn rotation_function_name(secret_name: &str) -> String {
+    sanitize(secret_name, 64)
+}
+
+pub async fn find_stack(client: &aws_sdk_cloudformation::Client, stack_name: &str) -> anyhow::Result<Option<Stack>> {
+    match client.describe_stacks().stack_name(stack_name).send().await {
+        Ok(resp) => Ok(resp.stacks.unwrap_or_default().into_iter().next()),
+        // CloudFormation reports a missing stack as a validation error
+        Err(e) if e.code() == Some("ValidationError") && e.message().is_some_and(|m| m.contains("does not exist")) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn stack_parameter(stack: &Stack, key: &str) -> Option<String> {
+    stack
+        .parameters()
+        .iter()
+        .find(|parameter| parameter.parameter_key.as_deref() == Some(key))
+        .and_then(|parameter| parameter.parameter_value.clone())
+        .filter(|value| !value.is_empty())
+}
+
+pub fn stack_output(stack: &Stack, key: &str) -> Option<String> {
+    stack
+        .outputs()
+        .iter()
+        .find(|output| output.output_key.as_deref() == Some(key))
+        .and_then(|output| output.output_value.clone())
+}
+
+pub fn stack_tag(stack: &Stack, key: &str) -> Option<String> {
+    stack
+        .tags()
+        .iter()
+        .find(|tag| tag.key.as_deref() == Some(key))
+        .and_then(|tag| tag.value.clone())
+}
+
+/// Splits a comma-separated stack parameter into its values.
+pub fn split_list_parameter(value: Option<String>) -> Vec<String> {
+    value
+        .map(|value| value.split(',').map(|item| item.trim().to_string()).filter(|item| !item.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+/// Checks that the rotation names a known template and has what that template needs.
+pub fn check_rotation(name: &str, rotation: &crate::resource::SecretRotation) -> anyhow::Result<()> {
+    if !ROTATION_TEMPLATES.contains(&rotation.template.as_str()) {
+        bail!(
+            "Rotation for secret '{}' has template {}: expected one of {}",
+            name,
+            rotation.template,
+            ROTATION_TEMPLATES.join(", ")
+        );
+    }
+
+    if is_multi_user_template(&rotation.template) && rotation.master_secret_arn.is_none() {
+        bail!(
+            "Rotation for secret '{}' uses {}, which requires master_secret_arn",
+            name,
+            rotation.template
+        );
+    }
+    if !is_multi_user_template(&rotation.template) && rotation.master_secret_arn.is_some() {
+        bail!(
+            "Rotation for secret '{}' sets master_secret_arn, which only MultiUser templates use",
+            name
+        );
+    }
+
+    if rotation.vpc_subnet_ids.is_empty() != rotation.vpc_security_group_ids.is_empty() {
+        bail!(
+            "Rotation for secret '{}' must set both vpc_subnet_ids and vpc_security_group_ids, or neither",
+            name
+        );
+    }
+
+    check_rotation_rules(name, &rotation.rotation_rules)
+}
+
+/// Checks a secret's own rotation config, as opposed to one at its rotation address.
+pub fn check_rotation_config(name: &str, rotation: &crate::resource::RotationConfig) -> anyhow::Result<()> {
+    if !(rotation.rotation_lambda_arn.starts_with("arn:") && rotation.rotation_lambda_arn.contains(":lambda:")) {
+        bail!(
+            "Rotation for secret '{}' has rotation_lambda_arn {}: expected a Lambda function ARN",
+            name,
+            rotation.rotation_lambda_arn
+        );
+    }
+
+    check_rotation_rules(name, &rotation.rotation_rules)
+}
+
+fn check_rotation_rules(name: &str, rules: &crate::resource::RotationRules) -> anyhow::Result<()> {
+    if rules.automatically_after_days.is_some() == rules.schedule_expression.is_some() {
+        bail!(
+            "Rotation for secret '{}' must set exactly one of automatically_after_days and schedule_expression",
+            name
+        );
+    }
+
+    Ok(())
+}
+
+/// Secrets Manager reports automatically_after_days as well when the schedule is a rate expression,
+/// so it's dropped whenever there's a schedule expression.
+pub fn rotation_rules_from_sdk(rules: Option<aws_sdk_secretsmanager::types::RotationRulesType>) -> crate::resource::RotationRules {
+    let rules = rules.unwrap_or_else(|| aws_sdk_secretsmanager::types::RotationRulesType::builder().build());
+    crate::resource::RotationRules {
+        automatically_after_days: if rules.schedule_expression.is_some() {
+            None
+        } else {
+            rules.automatically_after_days
+        },
+        duration: rules.duration,
+        schedule_expression: rules.schedule_expression,
+    }
+}
+
+/// Binary files sit next to the secret's .ron file, so they're resolved from its directory.
+pub fn secret_binary_path(prefix: &Path, addr: &SecretsManagerResourceAddress, secret_binary_file: &str) -> PathBuf {
+    let addr_path = prefix.join(addr.to_path_buf());
+    addr_path.parent().unwrap_or(prefix).join(secret_binary_file)
+}
+
+/// Reads a secret's binary file as-is; the SDK base64-encodes it on the wire.
+pub fn read_secret_binary(
+    prefix: &Path,
+    addr: &SecretsManagerResourceAddress,
+    secret_binary_file: &str,
+) -> anyhow::Result<Vec<u8>> {
+    let path = secret_binary_path(prefix, addr, secret_binary_file);
+    let value = std::fs::read(&path).with_context(|| format!("Failed to read secret binary file {}", path.display()))?;
+
+    if value.is_empty() {
+        bail!("Secret binary file {} is empty", path.display());
+    }
+    if value.len() > MAX_SECRET_SIZE {
+        bail!(
+            "Secret binary file {} is {} bytes: Secrets Manager stores at most {} bytes",
+            path.display(),
+            value.len(),
+            MAX_SECRET_SIZE
+        );
+    }
+
+    Ok(value)
+}
+
+/// Reports the binary file this repository last put as the secret's value, but only while the value
+/// in Secrets Manager still matches the file, so that editing the file, or changing the value some
+/// other way, shows up as a diff.
+pub async fn resolve_secret_binary_file(
+    client: &aws_sdk_secretsmanager::Client,
+    prefix: &Path,
+    addr: &SecretsManagerResourceAddress,
+    secret_name: &str,
+    recorded_file: Option<String>,
+) -> anyhow::Result<Option<String>> {
+    let Some(recorded_file) = recorded_file else {
+        return Ok(None);
+    };
+
+    let path = secret_binary_path(prefix, addr, &recorded_file);
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let resp = client.get_secret_value().secret_id(secret_name).send().await?;
+    match resp.secret_binary {
+        Some(value) if value.as_ref() == std::fs::read(&path)?.as_slice() => Ok(Some(recorded_file)),
+        _ => Ok(None),
+    }
+}
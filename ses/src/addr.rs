@@ -0,0 +1,106 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum SesResourceAddress {
+    /// A domain (`example.com`) or a single email address (`noreply@example.com`).
+    EmailIdentity { region: String, identity: String },
+    ConfigurationSet { region: String, name: String },
+    EventDestination {
+        region:            String,
+        configuration_set: String,
+        name:              String,
+    },
+    DedicatedIpPool { region: String, name: String },
+}
+
+impl ResourceAddress for SesResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            SesResourceAddress::EmailIdentity { region, identity } => {
+                PathBuf::from(format!("aws/ses/{region}/identities/{identity}.ron"))
+            }
+            SesResourceAddress::ConfigurationSet { region, name } => {
+                PathBuf::from(format!("aws/ses/{region}/configuration_sets/{name}.ron"))
+            }
+            SesResourceAddress::EventDestination {
+                region,
+                configuration_set,
+                name,
+            } => PathBuf::from(format!(
+                "aws/ses/{region}/configuration_sets/{configuration_set}/event_destinations/{name}.ron"
+            )),
+            SesResourceAddress::DedicatedIpPool { region, name } => {
+                PathBuf::from(format!("aws/ses/{region}/dedicated_ip_pools/{name}.ron"))
+            }
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "ses", region, "identities", identity] if identity.ends_with(".ron") => {
+                let identity = identity.strip_suffix(".ron").unwrap().to_string();
+                Ok(SesResourceAddress::EmailIdentity {
+                    region: region.to_string(),
+                    identity,
+                })
+            }
+            ["aws", "ses", region, "configuration_sets", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(SesResourceAddress::ConfigurationSet {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "ses", region, "configuration_sets", configuration_set, "event_destinations", name]
+                if name.ends_with(".ron") =>
+            {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(SesResourceAddress::EventDestination {
+                    region: region.to_string(),
+                    configuration_set: configuration_set.to_string(),
+                    name,
+                })
+            }
+            ["aws", "ses", region, "dedicated_ip_pools", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(SesResourceAddress::DedicatedIpPool {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for SesResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/ses/<region>/identities/<domain_or_email>.ron",
+                description: "An email identity: a domain, or a single email address",
+                example:     "aws/ses/us-east-1/identities/example.com.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ses/<region>/configuration_sets/<configuration_set_name>.ron",
+                description: "A configuration set",
+                example:     "aws/ses/us-east-1/configuration_sets/transactional.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ses/<region>/configuration_sets/<configuration_set_name>/event_destinations/<destination_name>.ron",
+                description: "An event destination of a configuration set",
+                example:     "aws/ses/us-east-1/configuration_sets/transactional/event_destinations/bounces.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/ses/<region>/dedicated_ip_pools/<pool_name>.ron",
+                description: "A dedicated IP pool",
+                example:     "aws/ses/us-east-1/dedicated_ip_pools/marketing.ron",
+            },
+        ]
+    }
+}
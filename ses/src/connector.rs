@@ -0,0 +1,249 @@
+pub use crate::addr::SesResourceAddress;
+pub use crate::op::SesConnectorOp;
+pub use crate::resource::SesResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::SesConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{
+    CloudWatchDimension, ConfigurationSet, DedicatedIpPool, Destination, Dkim, EmailIdentity, EventDestination, MailFrom,
+};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct SesConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_sesv2::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<SesConnectorConfig>,
+    prefix: PathBuf,
+}
+
+impl SesConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_sesv2::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+            let config = aws_config::defaults(BehaviorVersion::latest())
+                .region(region)
+                .timeout_config(
+                    TimeoutConfig::builder()
+                        .connect_timeout(Duration::from_secs(30))
+                        .operation_timeout(Duration::from_secs(30))
+                        .operation_attempt_timeout(Duration::from_secs(30))
+                        .read_timeout(Duration::from_secs(30))
+                        .build(),
+                )
+                .load()
+                .await;
+            let client = audited_client!(aws_sdk_sesv2, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for SesConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = SesResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(SesConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let ses_config: SesConnectorConfig = SesConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = ses_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(ses_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = ses_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // Domain identity skeleton. Publish the dkim_record_<n> outputs as CNAME records to verify it.
+        res.push(skeleton!(
+            SesResourceAddress::EmailIdentity {
+                region:   String::from("[region]"),
+                identity: String::from("[domain_name]"),
+            },
+            SesResource::EmailIdentity(EmailIdentity {
+                dkim: Some(Dkim {
+                    signing_enabled: true,
+                    key_length: String::from("RSA_2048_BIT"),
+                }),
+                configuration_set: Some(String::from("[configuration_set_name]")),
+                mail_from: Some(MailFrom {
+                    domain: String::from("mail.[domain_name]"),
+                    behavior_on_mx_failure: String::from("USE_DEFAULT_VALUE"),
+                }),
+                feedback_forwarding: true,
+                tags: Tags::default(),
+            })
+        ));
+
+        // Configuration set skeleton
+        res.push(skeleton!(
+            SesResourceAddress::ConfigurationSet {
+                region: String::from("[region]"),
+                name:   String::from("[configuration_set_name]"),
+            },
+            SesResource::ConfigurationSet(ConfigurationSet {
+                sending_enabled: true,
+                reputation_metrics_enabled: true,
+                tls_policy: Some(String::from("REQUIRE")),
+                sending_pool: None,
+                suppressed_reasons: Some(vec![String::from("BOUNCE"), String::from("COMPLAINT")]),
+                custom_redirect_domain: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        // Event destination skeleton, publishing bounces and complaints to an SNS topic
+        res.push(skeleton!(
+            SesResourceAddress::EventDestination {
+                region: String::from("[region]"),
+                configuration_set: String::from("[configuration_set_name]"),
+                name: String::from("[destination_name]"),
+            },
+            SesResource::EventDestination(EventDestination {
+                enabled: true,
+                matching_event_types: vec![String::from("BOUNCE"), String::from("COMPLAINT")],
+                destination: Destination::Sns {
+                    topic_arn: String::from("arn:aws:sns:[region]:[account_id]:[topic_name]"),
+                },
+            })
+        ));
+
+        // Event destination skeleton, publishing send and delivery metrics to CloudWatch
+        res.push(skeleton!(
+            SesResourceAddress::EventDestination {
+                region: String::from("[region]"),
+                configuration_set: String::from("[configuration_set_name]"),
+                name: String::from("[metrics_destination_name]"),
+            },
+            SesResource::EventDestination(EventDestination {
+                enabled: true,
+                matching_event_types: vec![String::from("DELIVERY"), String::from("SEND")],
+                destination: Destination::CloudWatch {
+                    dimensions: vec![CloudWatchDimension {
+                        name: String::from("ses:configuration-set"),
+                        value_source: String::from("MESSAGE_TAG"),
+                        default_value: String::from("[configuration_set_name]"),
+                    }],
+                },
+            })
+        ));
+
+        // Dedicated IP pool skeleton
+        res.push(skeleton!(
+            SesResourceAddress::DedicatedIpPool {
+                region: String::from("[region]"),
+                name:   String::from("[pool_name]"),
+            },
+            SesResource::DedicatedIpPool(DedicatedIpPool {
+                scaling_mode: String::from("MANAGED"),
+                tags: Tags::default(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = SesResourceAddress::from_path(addr)?;
+
+        match addr {
+            SesResourceAddress::EmailIdentity { .. } => ron_check_eq::<EmailIdentity>(a, b),
+            SesResourceAddress::ConfigurationSet { .. } => ron_check_eq::<ConfigurationSet>(a, b),
+            SesResourceAddress::EventDestination { .. } => ron_check_eq::<EventDestination>(a, b),
+            SesResourceAddress::DedicatedIpPool { .. } => ron_check_eq::<DedicatedIpPool>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = SesResourceAddress::from_path(addr)?;
+
+        match addr {
+            SesResourceAddress::EmailIdentity { .. } => ron_check_syntax::<EmailIdentity>(a),
+            SesResourceAddress::ConfigurationSet { .. } => ron_check_syntax::<ConfigurationSet>(a),
+            SesResourceAddress::EventDestination { .. } => ron_check_syntax::<EventDestination>(a),
+            SesResourceAddress::DedicatedIpPool { .. } => ron_check_syntax::<DedicatedIpPool>(a),
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use std::{collections::HashMap, path::Path};
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_sesv2::operation::{
+    get_configuration_set::GetConfigurationSetError,
+    get_configuration_set_event_destinations::GetConfigurationSetEventDestinationsError,
+    get_dedicated_ip_pool::GetDedicatedIpPoolError, get_email_identity::GetEmailIdentityError,
+};
+
+use crate::{
+    addr::SesResourceAddress,
+    resource::{ConfigurationSet, DedicatedIpPool, Dkim, EmailIdentity, MailFrom, SesResource},
+    util::{
+        configuration_set_arn, dedicated_ip_pool_arn, dkim_outputs, event_destination_from_sdk, identity_arn, is_domain,
+        list_tags,
+    },
+};
+
+use super::SesConnector;
+
+impl SesConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = SesResourceAddress::from_path(addr)?;
+
+        let account_id = self.account_id.lock().await.clone();
+
+        match &addr {
+            SesResourceAddress::EmailIdentity { region, identity } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.get_email_identity().email_identity(identity).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(GetEmailIdentityError::NotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                // Email address identities sign with their domain's DKIM settings, if any
+                let dkim = match resp.dkim_attributes() {
+                    Some(dkim) if is_domain(identity) => Some(Dkim {
+                        signing_enabled: dkim.signing_enabled,
+                        key_length: dkim
+                            .next_signing_key_length()
+                            .or(dkim.current_signing_key_length())
+                            .map(|l| l.as_str().to_string())
+                            .unwrap_or_default(),
+                    }),
+                    _ => None,
+                };
+
+                let mail_from = resp
+                    .mail_from_attributes()
+                    .filter(|m| !m.mail_from_domain().is_empty())
+                    .map(|m| MailFrom {
+                        domain: m.mail_from_domain().to_string(),
+                        behavior_on_mx_failure: m.behavior_on_mx_failure().as_str().to_string(),
+                    });
+
+                let mut outputs = HashMap::from([(String::from("identity_arn"), identity_arn(region, &account_id, identity))]);
+                if is_domain(identity) {
+                    let tokens = resp.dkim_attributes().map(|dkim| dkim.tokens().to_vec()).unwrap_or_default();
+                    outputs.extend(dkim_outputs(identity, &tokens));
+                }
+
+                let email_identity = EmailIdentity {
+                    dkim,
+                    configuration_set: resp.configuration_set_name.clone(),
+                    mail_from,
+                    feedback_forwarding: resp.feedback_forwarding_status,
+                    tags: resp.tags.into(),
+                };
+
+                Ok(Some(GetResourceResponse {
+                    resource_definition: SesResource::EmailIdentity(email_identity).to_bytes()?,
+                    virt_addr: None,
+                    outputs: Some(outputs),
+                }))
+            }
+            SesResourceAddress::ConfigurationSet { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.get_configuration_set().configuration_set_name(name).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(GetConfigurationSetError::NotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let suppressed_reasons = resp.suppression_options().map(|options| {
+                    let mut reasons: Vec<String> = options
+                        .suppressed_reasons()
+                        .iter()
+                        .map(|r| r.as_str().to_string())
+                        .collect();
+                    reasons.sort();
+                    reasons
+                });
+
+                let configuration_set = ConfigurationSet {
+                    sending_enabled: resp.sending_options().is_none_or(|o| o.sending_enabled),
+                    reputation_metrics_enabled: resp.reputation_options().is_some_and(|o| o.reputation_metrics_enabled),
+                    // OPTIONAL is the default
+                    tls_policy: resp
+                        .delivery_options()
+                        .and_then(|o| o.tls_policy())
+                        .map(|p| p.as_str().to_string())
+                        .filter(|p| p != "OPTIONAL"),
+                    sending_pool: resp.delivery_options().and_then(|o| o.sending_pool_name.clone()),
+                    suppressed_reasons,
+                    custom_redirect_domain: resp
+                        .tracking_options()
+                        .map(|o| o.custom_redirect_domain().to_string())
+                        .filter(|d| !d.is_empty()),
+                    tags: resp.tags.into(),
+                };
+
+                get_resource_response!(
+                    SesResource::ConfigurationSet(configuration_set),
+                    [(
+                        String::from("configuration_set_arn"),
+                        configuration_set_arn(region, &account_id, name)
+                    )]
+                )
+            }
+            SesResourceAddress::EventDestination {
+                region,
+                configuration_set,
+                name,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client
+                    .get_configuration_set_event_destinations()
+                    .configuration_set_name(configuration_set)
+                    .send()
+                    .await
+                {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(GetConfigurationSetEventDestinationsError::NotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let Some(destination) = resp.event_destinations().iter().find(|d| d.name() == name) else {
+                    return Ok(None);
+                };
+
+                get_resource_response!(SesResource::EventDestination(event_destination_from_sdk(destination)?))
+            }
+            SesResourceAddress::DedicatedIpPool { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let resp = match client.get_dedicated_ip_pool().pool_name(name).send().await {
+                    Ok(resp) => resp,
+                    Err(e) => match e.as_service_error() {
+                        Some(GetDedicatedIpPoolError::NotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let Some(pool) = resp.dedicated_ip_pool() else {
+                    return Ok(None);
+                };
+
+                let pool_arn = dedicated_ip_pool_arn(region, &account_id, name);
+
+                let dedicated_ip_pool = DedicatedIpPool {
+                    scaling_mode: pool.scaling_mode().as_str().to_string(),
+                    tags: list_tags(&client, &pool_arn).await?,
+                };
+
+                get_resource_response!(
+                    SesResource::DedicatedIpPool(dedicated_ip_pool),
+                    [(String::from("dedicated_ip_pool_arn"), pool_arn)]
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::addr::SesResourceAddress;
+
+use super::SesConnector;
+
+impl SesConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut next_token = None;
+            loop {
+                let resp = client.list_email_identities().set_next_token(next_token).send().await?;
+                for identity in resp.email_identities() {
+                    if let Some(identity) = &identity.identity_name {
+                        results.push(
+                            SesResourceAddress::EmailIdentity {
+                                region:   region.clone(),
+                                identity: identity.clone(),
+                            }
+                            .to_path_buf(),
+                        );
+                    }
+                }
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+
+            let mut configuration_sets = Vec::new();
+            let mut next_token = None;
+            loop {
+                let resp = client.list_configuration_sets().set_next_token(next_token).send().await?;
+                configuration_sets.extend(resp.configuration_sets().iter().cloned());
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+
+            for configuration_set in configuration_sets {
+                results.push(
+                    SesResourceAddress::ConfigurationSet {
+                        region: region.clone(),
+                        name:   configuration_set.clone(),
+                    }
+                    .to_path_buf(),
+                );
+
+                let resp = client
+                    .get_configuration_set_event_destinations()
+                    .configuration_set_name(&configuration_set)
+                    .send()
+                    .await?;
+                for destination in resp.event_destinations() {
+                    results.push(
+                        SesResourceAddress::EventDestination {
+                            region: region.clone(),
+                            configuration_set: configuration_set.clone(),
+                            name: destination.name().to_string(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+            }
+
+            let mut next_token = None;
+            loop {
+                let resp = client.list_dedicated_ip_pools().set_next_token(next_token).send().await?;
+                for pool in resp.dedicated_ip_pools() {
+                    results.push(
+                        SesResourceAddress::DedicatedIpPool {
+                            region: region.clone(),
+                            name:   pool.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
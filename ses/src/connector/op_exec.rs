@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::connector::{ConnectorOp, OpExecResponse, ResourceAddress};
+
+use crate::{
+    addr::SesResourceAddress,
+    op::SesConnectorOp,
+    op_impl,
+    util::{configuration_set_arn, dedicated_ip_pool_arn, identity_arn},
+};
+
+use super::SesConnector;
+
+impl SesConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = SesResourceAddress::from_path(addr)?;
+        let op = SesConnectorOp::from_str(op)?;
+
+        let account_id = self.account_id.lock().await.clone();
+
+        match &addr {
+            SesResourceAddress::EmailIdentity { region, identity } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    SesConnectorOp::CreateEmailIdentity(email_identity) => {
+                        op_impl::create_email_identity(&client, identity, &email_identity).await
+                    }
+                    SesConnectorOp::UpdateEmailIdentity(old_identity, new_identity) => {
+                        op_impl::update_email_identity(&client, identity, &old_identity, &new_identity).await
+                    }
+                    SesConnectorOp::UpdateEmailIdentityTags(old_tags, new_tags) => {
+                        op_impl::update_tags(&client, &identity_arn(region, &account_id, identity), &old_tags, &new_tags).await
+                    }
+                    SesConnectorOp::DeleteEmailIdentity => op_impl::delete_email_identity(&client, identity).await,
+                    _ => bail!("Invalid operation for SES email identity resource"),
+                }
+            }
+            SesResourceAddress::ConfigurationSet { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    SesConnectorOp::CreateConfigurationSet(configuration_set) => {
+                        op_impl::create_configuration_set(&client, name, &configuration_set).await
+                    }
+                    SesConnectorOp::UpdateConfigurationSet(old_configuration_set, new_configuration_set) => {
+                        op_impl::update_configuration_set(&client, name, &old_configuration_set, &new_configuration_set).await
+                    }
+                    SesConnectorOp::UpdateConfigurationSetTags(old_tags, new_tags) => {
+                        let arn = configuration_set_arn(region, &account_id, name);
+                        op_impl::update_tags(&client, &arn, &old_tags, &new_tags).await
+                    }
+                    SesConnectorOp::DeleteConfigurationSet => op_impl::delete_configuration_set(&client, name).await,
+                    _ => bail!("Invalid operation for SES configuration set resource"),
+                }
+            }
+            SesResourceAddress::EventDestination {
+                region,
+                configuration_set,
+                name,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    SesConnectorOp::CreateEventDestination(destination) => {
+                        op_impl::create_event_destination(&client, configuration_set, name, &destination).await
+                    }
+                    SesConnectorOp::UpdateEventDestination(destination) => {
+                        op_impl::update_event_destination(&client, configuration_set, name, &destination).await
+                    }
+                    SesConnectorOp::DeleteEventDestination => {
+                        op_impl::delete_event_destination(&client, configuration_set, name).await
+                    }
+                    _ => bail!("Invalid operation for SES event destination resource"),
+                }
+            }
+            SesResourceAddress::DedicatedIpPool { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    SesConnectorOp::CreateDedicatedIpPool(pool) => op_impl::create_dedicated_ip_pool(&client, name, &pool).await,
+                    SesConnectorOp::SetDedicatedIpPoolScalingMode(scaling_mode) => {
+                        op_impl::set_dedicated_ip_pool_scaling_mode(&client, name, &scaling_mode).await
+                    }
+                    SesConnectorOp::UpdateDedicatedIpPoolTags(old_tags, new_tags) => {
+                        let arn = dedicated_ip_pool_arn(region, &account_id, name);
+                        op_impl::update_tags(&client, &arn, &old_tags, &new_tags).await
+                    }
+                    SesConnectorOp::DeleteDedicatedIpPool => op_impl::delete_dedicated_ip_pool(&client, name).await,
+                    _ => bail!("Invalid operation for SES dedicated IP pool resource"),
+                }
+            }
+        }
+    }
+}
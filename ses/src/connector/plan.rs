@@ -0,0 +1,373 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::{
+    resource::{ConfigurationSet, DedicatedIpPool, Destination, EmailIdentity, EventDestination},
+    util::is_domain,
+};
+
+use super::{SesConnector, SesConnectorOp, SesResourceAddress};
+
+const EVENT_TYPES: [&str; 10] = [
+    "SEND",
+    "REJECT",
+    "BOUNCE",
+    "COMPLAINT",
+    "DELIVERY",
+    "OPEN",
+    "CLICK",
+    "RENDERING_FAILURE",
+    "DELIVERY_DELAY",
+    "SUBSCRIPTION",
+];
+
+/// DKIM and custom MAIL FROM domains only apply to domain identities, and domain identities
+/// are verified through DKIM, so they need it.
+fn check_email_identity(identity: &str, email_identity: &EmailIdentity) -> anyhow::Result<()> {
+    if !is_domain(identity) {
+        if email_identity.dkim.is_some() {
+            bail!("SES identity {} is an email address, so it can't have its own DKIM settings", identity);
+        }
+        if email_identity.mail_from.is_some() {
+            bail!("SES identity {} is an email address, so it can't have a MAIL FROM domain", identity);
+        }
+        return Ok(());
+    }
+
+    let Some(dkim) = &email_identity.dkim else {
+        bail!("SES domain identity {} needs dkim settings, since it's verified through its DKIM records", identity);
+    };
+    if !["RSA_1024_BIT", "RSA_2048_BIT"].contains(&dkim.key_length.as_str()) {
+        bail!(
+            "SES identity {} has an invalid DKIM key_length {}; expected RSA_1024_BIT or RSA_2048_BIT",
+            identity,
+            dkim.key_length
+        );
+    }
+
+    if let Some(mail_from) = &email_identity.mail_from {
+        if !mail_from.domain.ends_with(&format!(".{}", identity)) {
+            bail!(
+                "SES identity {} has MAIL FROM domain {}, which isn't a subdomain of it",
+                identity,
+                mail_from.domain
+            );
+        }
+        if !["USE_DEFAULT_VALUE", "REJECT_MESSAGE"].contains(&mail_from.behavior_on_mx_failure.as_str()) {
+            bail!(
+                "SES identity {} has an invalid behavior_on_mx_failure {}; expected USE_DEFAULT_VALUE or REJECT_MESSAGE",
+                identity,
+                mail_from.behavior_on_mx_failure
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn check_configuration_set(name: &str, configuration_set: &ConfigurationSet) -> anyhow::Result<()> {
+    if let Some(tls_policy) = &configuration_set.tls_policy
+        && !["REQUIRE", "OPTIONAL"].contains(&tls_policy.as_str())
+    {
+        bail!(
+            "SES configuration set {} has an invalid tls_policy {}; expected REQUIRE or OPTIONAL",
+            name,
+            tls_policy
+        );
+    }
+
+    for reason in configuration_set.suppressed_reasons.iter().flatten() {
+        if !["BOUNCE", "COMPLAINT"].contains(&reason.as_str()) {
+            bail!(
+                "SES configuration set {} has an invalid suppressed reason {}; expected BOUNCE or COMPLAINT",
+                name,
+                reason
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn check_event_destination(name: &str, destination: &EventDestination) -> anyhow::Result<()> {
+    if destination.matching_event_types.is_empty() {
+        bail!("SES event destination {} has no matching_event_types", name);
+    }
+    for event_type in &destination.matching_event_types {
+        if !EVENT_TYPES.contains(&event_type.as_str()) {
+            bail!(
+                "SES event destination {} has an invalid event type {}; expected one of {}",
+                name,
+                event_type,
+                EVENT_TYPES.join(", ")
+            );
+        }
+    }
+
+    if let Destination::CloudWatch { dimensions } = &destination.destination {
+        if dimensions.is_empty() {
+            bail!("SES event destination {} publishes to CloudWatch, so it needs at least one dimension", name);
+        }
+        for dimension in dimensions {
+            if !["MESSAGE_TAG", "EMAIL_HEADER", "LINK_TAG"].contains(&dimension.value_source.as_str()) {
+                bail!(
+                    "SES event destination {} has an invalid value_source {} for dimension {}; expected MESSAGE_TAG, EMAIL_HEADER or LINK_TAG",
+                    name,
+                    dimension.value_source,
+                    dimension.name
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn check_dedicated_ip_pool(name: &str, pool: &DedicatedIpPool) -> anyhow::Result<()> {
+    if !["STANDARD", "MANAGED"].contains(&pool.scaling_mode.as_str()) {
+        bail!(
+            "SES dedicated IP pool {} has an invalid scaling_mode {}; expected STANDARD or MANAGED",
+            name,
+            pool.scaling_mode
+        );
+    }
+    Ok(())
+}
+
+impl SesConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = SesResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            SesResourceAddress::EmailIdentity { region, identity } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_identity)) => {
+                    let new_identity: EmailIdentity = RON.from_str(&new_identity)?;
+                    check_email_identity(identity, &new_identity)?;
+                    let message = if is_domain(identity) {
+                        format!(
+                            "Create new SES domain identity {} in region {}; publish the DKIM records from its outputs to verify it",
+                            identity, region
+                        )
+                    } else {
+                        format!(
+                            "Create new SES email identity {} in region {}; SES will send it a verification email",
+                            identity, region
+                        )
+                    };
+                    Ok(vec![connector_op!(SesConnectorOp::CreateEmailIdentity(new_identity), message)])
+                }
+                (Some(_old_identity), None) => Ok(vec![connector_op!(
+                    SesConnectorOp::DeleteEmailIdentity,
+                    format!("DELETE SES identity {} in region {}", identity, region)
+                )]),
+                (Some(old_identity), Some(new_identity)) => {
+                    let old_identity: EmailIdentity = RON.from_str(&old_identity)?;
+                    let new_identity: EmailIdentity = RON.from_str(&new_identity)?;
+                    check_email_identity(identity, &new_identity)?;
+                    let mut ops = Vec::new();
+
+                    if old_identity.tags != new_identity.tags {
+                        let diff = diff_ron_values(&old_identity.tags, &new_identity.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            SesConnectorOp::UpdateEmailIdentityTags(old_identity.tags.clone(), new_identity.tags.clone()),
+                            format!("Modify tags for SES identity `{}`\n{}", identity, diff)
+                        ));
+                    }
+
+                    let old_settings = EmailIdentity {
+                        tags: new_identity.tags.clone(),
+                        ..old_identity
+                    };
+                    if old_settings != new_identity {
+                        let diff = diff_ron_values(&old_settings, &new_identity).unwrap_or_default();
+                        ops.push(connector_op!(
+                            SesConnectorOp::UpdateEmailIdentity(old_settings, new_identity),
+                            format!("Modify SES identity `{}`\n{}", identity, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            SesResourceAddress::ConfigurationSet { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_configuration_set)) => {
+                    let new_configuration_set: ConfigurationSet = RON.from_str(&new_configuration_set)?;
+                    check_configuration_set(name, &new_configuration_set)?;
+                    Ok(vec![connector_op!(
+                        SesConnectorOp::CreateConfigurationSet(new_configuration_set),
+                        format!("Create new SES configuration set {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_configuration_set), None) => Ok(vec![connector_op!(
+                    SesConnectorOp::DeleteConfigurationSet,
+                    format!(
+                        "DELETE SES configuration set {} in region {}, along with its event destinations",
+                        name, region
+                    )
+                )]),
+                (Some(old_configuration_set), Some(new_configuration_set)) => {
+                    let old_configuration_set: ConfigurationSet = RON.from_str(&old_configuration_set)?;
+                    let new_configuration_set: ConfigurationSet = RON.from_str(&new_configuration_set)?;
+                    check_configuration_set(name, &new_configuration_set)?;
+                    let mut ops = Vec::new();
+
+                    if old_configuration_set.tags != new_configuration_set.tags {
+                        let diff =
+                            diff_ron_values(&old_configuration_set.tags, &new_configuration_set.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            SesConnectorOp::UpdateConfigurationSetTags(
+                                old_configuration_set.tags.clone(),
+                                new_configuration_set.tags.clone()
+                            ),
+                            format!("Modify tags for SES configuration set `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let old_settings = ConfigurationSet {
+                        tags: new_configuration_set.tags.clone(),
+                        ..old_configuration_set
+                    };
+                    if old_settings != new_configuration_set {
+                        let diff = diff_ron_values(&old_settings, &new_configuration_set).unwrap_or_default();
+                        ops.push(connector_op!(
+                            SesConnectorOp::UpdateConfigurationSet(old_settings, new_configuration_set),
+                            format!("Modify SES configuration set `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            SesResourceAddress::EventDestination {
+                configuration_set,
+                name,
+                ..
+            } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_destination)) => {
+                    let new_destination: EventDestination = RON.from_str(&new_destination)?;
+                    check_event_destination(name, &new_destination)?;
+                    Ok(vec![connector_op!(
+                        SesConnectorOp::CreateEventDestination(new_destination),
+                        format!(
+                            "Create new event destination {} for SES configuration set {}",
+                            name, configuration_set
+                        )
+                    )])
+                }
+                (Some(_old_destination), None) => Ok(vec![connector_op!(
+                    SesConnectorOp::DeleteEventDestination,
+                    format!("DELETE event destination {} for SES configuration set {}", name, configuration_set)
+                )]),
+                (Some(old_destination), Some(new_destination)) => {
+                    let old_destination: EventDestination = RON.from_str(&old_destination)?;
+                    let mut new_destination: EventDestination = RON.from_str(&new_destination)?;
+                    check_event_destination(name, &new_destination)?;
+
+                    // SES doesn't keep the order of event types
+                    new_destination.matching_event_types.sort();
+                    new_destination.matching_event_types.dedup();
+
+                    if old_destination == new_destination {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_destination, &new_destination).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        SesConnectorOp::UpdateEventDestination(new_destination),
+                        format!(
+                            "Modify event destination `{}` for SES configuration set `{}`\n{}",
+                            name, configuration_set, diff
+                        )
+                    )])
+                }
+            },
+            SesResourceAddress::DedicatedIpPool { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_pool)) => {
+                    let new_pool: DedicatedIpPool = RON.from_str(&new_pool)?;
+                    check_dedicated_ip_pool(name, &new_pool)?;
+                    Ok(vec![connector_op!(
+                        SesConnectorOp::CreateDedicatedIpPool(new_pool.clone()),
+                        format!(
+                            "Create new {} SES dedicated IP pool {} in region {}",
+                            new_pool.scaling_mode, name, region
+                        )
+                    )])
+                }
+                (Some(_old_pool), None) => Ok(vec![connector_op!(
+                    SesConnectorOp::DeleteDedicatedIpPool,
+                    format!(
+                        "DELETE SES dedicated IP pool {} in region {}; its IPs move back to the default pool",
+                        name, region
+                    )
+                )]),
+                (Some(old_pool), Some(new_pool)) => {
+                    let old_pool: DedicatedIpPool = RON.from_str(&old_pool)?;
+                    let new_pool: DedicatedIpPool = RON.from_str(&new_pool)?;
+                    check_dedicated_ip_pool(name, &new_pool)?;
+
+                    // SES can turn a STANDARD pool into a MANAGED one, but not the other way around
+                    if old_pool.scaling_mode == "MANAGED" && new_pool.scaling_mode == "STANDARD" {
+                        return Ok(vec![
+                            connector_op!(
+                                SesConnectorOp::DeleteDedicatedIpPool,
+                                format!(
+                                    "REPLACE SES dedicated IP pool `{}` (requires replacement: scaling_mode MANAGED to STANDARD)",
+                                    name
+                                )
+                            ),
+                            connector_op!(
+                                SesConnectorOp::CreateDedicatedIpPool(new_pool.clone()),
+                                format!(
+                                    "Create new {} SES dedicated IP pool {} in region {}",
+                                    new_pool.scaling_mode, name, region
+                                )
+                            ),
+                        ]);
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_pool.tags != new_pool.tags {
+                        let diff = diff_ron_values(&old_pool.tags, &new_pool.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            SesConnectorOp::UpdateDedicatedIpPoolTags(old_pool.tags.clone(), new_pool.tags.clone()),
+                            format!("Modify tags for SES dedicated IP pool `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    if old_pool.scaling_mode != new_pool.scaling_mode {
+                        ops.push(connector_op!(
+                            SesConnectorOp::SetDedicatedIpPoolScalingMode(new_pool.scaling_mode.clone()),
+                            format!(
+                                "Modify SES dedicated IP pool `{}` scaling mode: {} -> {}",
+                                name, old_pool.scaling_mode, new_pool.scaling_mode
+                            )
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{ConfigurationSet, DedicatedIpPool, EmailIdentity, EventDestination},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum SesConnectorOp {
+    // Email identity operations
+    CreateEmailIdentity(EmailIdentity),
+    /// Applies whichever of DKIM, configuration set, MAIL FROM and feedback settings differ.
+    UpdateEmailIdentity(EmailIdentity, EmailIdentity),
+    UpdateEmailIdentityTags(Tags, Tags),
+    DeleteEmailIdentity,
+
+    // Configuration set operations
+    CreateConfigurationSet(ConfigurationSet),
+    /// Applies whichever of the sending, reputation, delivery, suppression and tracking options differ.
+    UpdateConfigurationSet(ConfigurationSet, ConfigurationSet),
+    UpdateConfigurationSetTags(Tags, Tags),
+    DeleteConfigurationSet,
+
+    // Event destination operations
+    CreateEventDestination(EventDestination),
+    UpdateEventDestination(EventDestination),
+    DeleteEventDestination,
+
+    // Dedicated IP pool operations
+    CreateDedicatedIpPool(DedicatedIpPool),
+    /// SES only allows a pool to go from STANDARD to MANAGED.
+    SetDedicatedIpPoolScalingMode(String),
+    UpdateDedicatedIpPoolTags(Tags, Tags),
+    DeleteDedicatedIpPool,
+}
+
+impl ConnectorOp for SesConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
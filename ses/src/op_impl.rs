@@ -0,0 +1,455 @@
+use anyhow::Context;
+use autoschematic_core::connector::OpExecResponse;
+use aws_sdk_sesv2::types::{
+    BehaviorOnMxFailure, DeliveryOptions, DkimSigningAttributes, DkimSigningAttributesOrigin, DkimSigningKeyLength,
+    ReputationOptions, ScalingMode, SendingOptions, SuppressionListReason, SuppressionOptions, TlsPolicy, TrackingOptions,
+};
+
+use crate::{
+    resource::{ConfigurationSet, DedicatedIpPool, EmailIdentity, EventDestination},
+    tags::{Tags, tag_diff},
+    util::{dkim_output_keys, dkim_outputs, event_destination_definition, is_domain},
+};
+
+fn suppressed_reasons(reasons: &Option<Vec<String>>) -> Option<Vec<SuppressionListReason>> {
+    reasons
+        .as_ref()
+        .map(|reasons| reasons.iter().map(|r| SuppressionListReason::from(r.as_str())).collect())
+}
+
+async fn put_mail_from(client: &aws_sdk_sesv2::Client, identity: &str, email_identity: &EmailIdentity) -> anyhow::Result<()> {
+    // No domain resets MAIL FROM to the SES default
+    client
+        .put_email_identity_mail_from_attributes()
+        .email_identity(identity)
+        .set_mail_from_domain(email_identity.mail_from.as_ref().map(|m| m.domain.clone()))
+        .set_behavior_on_mx_failure(
+            email_identity
+                .mail_from
+                .as_ref()
+                .map(|m| BehaviorOnMxFailure::from(m.behavior_on_mx_failure.as_str())),
+        )
+        .send()
+        .await
+        .with_context(|| format!("Failed to set MAIL FROM domain for SES identity {}", identity))?;
+    Ok(())
+}
+
+/// Creates an email identity. Domain identities are verified once their DKIM records resolve;
+/// email address identities once the recipient follows the link SES sends them.
+pub async fn create_email_identity(
+    client: &aws_sdk_sesv2::Client,
+    identity: &str,
+    email_identity: &EmailIdentity,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client
+        .create_email_identity()
+        .email_identity(identity)
+        .set_configuration_set_name(email_identity.configuration_set.clone());
+
+    if let Some(dkim) = &email_identity.dkim {
+        request = request.dkim_signing_attributes(
+            DkimSigningAttributes::builder()
+                .next_signing_key_length(DkimSigningKeyLength::from(dkim.key_length.as_str()))
+                .build(),
+        );
+    }
+
+    if email_identity.tags.len() > 0 {
+        request = request.set_tags(Some(email_identity.tags.to_vec()?));
+    }
+
+    let resp = request.send().await?;
+
+    // Easy DKIM signing starts out enabled, and so does feedback forwarding
+    if let Some(dkim) = &email_identity.dkim
+        && !dkim.signing_enabled
+    {
+        client
+            .put_email_identity_dkim_attributes()
+            .email_identity(identity)
+            .signing_enabled(false)
+            .send()
+            .await?;
+    }
+
+    if email_identity.mail_from.is_some() {
+        put_mail_from(client, identity, email_identity).await?;
+    }
+
+    if !email_identity.feedback_forwarding {
+        client
+            .put_email_identity_feedback_attributes()
+            .email_identity(identity)
+            .email_forwarding_enabled(false)
+            .send()
+            .await?;
+    }
+
+    let tokens = resp.dkim_attributes().map(|dkim| dkim.tokens().to_vec()).unwrap_or_default();
+    let outputs = dkim_outputs(identity, &tokens)
+        .into_iter()
+        .map(|(key, value)| (key, Some(value)))
+        .collect();
+
+    let friendly_message = if is_domain(identity) {
+        format!(
+            "Created SES domain identity {}; it will be verified once its DKIM records are published",
+            identity
+        )
+    } else {
+        format!("Created SES email identity {}; SES has sent it a verification email", identity)
+    };
+
+    Ok(OpExecResponse {
+        outputs: Some(outputs),
+        friendly_message: Some(friendly_message),
+    })
+}
+
+/// Applies the settings that differ between `old` and `new`. SES takes each group of settings in its own call.
+pub async fn update_email_identity(
+    client: &aws_sdk_sesv2::Client,
+    identity: &str,
+    old: &EmailIdentity,
+    new: &EmailIdentity,
+) -> Result<OpExecResponse, anyhow::Error> {
+    if let Some(new_dkim) = &new.dkim {
+        let old_key_length = old.dkim.as_ref().map(|dkim| dkim.key_length.as_str());
+        if old_key_length != Some(new_dkim.key_length.as_str()) {
+            // Takes effect at the next key rotation, without any change to the DKIM records
+            client
+                .put_email_identity_dkim_signing_attributes()
+                .email_identity(identity)
+                .signing_attributes_origin(DkimSigningAttributesOrigin::AwsSes)
+                .signing_attributes(
+                    DkimSigningAttributes::builder()
+                        .next_signing_key_length(DkimSigningKeyLength::from(new_dkim.key_length.as_str()))
+                        .build(),
+                )
+                .send()
+                .await
+                .with_context(|| format!("Failed to set DKIM key length for SES identity {}", identity))?;
+        }
+    }
+
+    let old_signing = old.dkim.as_ref().is_some_and(|dkim| dkim.signing_enabled);
+    let new_signing = new.dkim.as_ref().is_some_and(|dkim| dkim.signing_enabled);
+    if old_signing != new_signing {
+        client
+            .put_email_identity_dkim_attributes()
+            .email_identity(identity)
+            .signing_enabled(new_signing)
+            .send()
+            .await
+            .with_context(|| format!("Failed to set DKIM signing for SES identity {}", identity))?;
+    }
+
+    if old.configuration_set != new.configuration_set {
+        client
+            .put_email_identity_configuration_set_attributes()
+            .email_identity(identity)
+            .set_configuration_set_name(new.configuration_set.clone())
+            .send()
+            .await
+            .with_context(|| format!("Failed to set configuration set for SES identity {}", identity))?;
+    }
+
+    if old.mail_from != new.mail_from {
+        put_mail_from(client, identity, new).await?;
+    }
+
+    if old.feedback_forwarding != new.feedback_forwarding {
+        client
+            .put_email_identity_feedback_attributes()
+            .email_identity(identity)
+            .email_forwarding_enabled(new.feedback_forwarding)
+            .send()
+            .await
+            .with_context(|| format!("Failed to set feedback forwarding for SES identity {}", identity))?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated SES identity {}", identity)),
+    })
+}
+
+/// Updates the tags on any SES resource
+pub async fn update_tags(
+    client: &aws_sdk_sesv2::Client,
+    resource_arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags)?;
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(resource_arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client
+            .tag_resource()
+            .resource_arn(resource_arn)
+            .set_tags(Some(new_tagset))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for {}", resource_arn)),
+    })
+}
+
+pub async fn delete_email_identity(client: &aws_sdk_sesv2::Client, identity: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_email_identity().email_identity(identity).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(dkim_output_keys().into_iter().map(|key| (key, None)).collect()),
+        friendly_message: Some(format!("Deleted SES identity {}", identity)),
+    })
+}
+
+pub async fn create_configuration_set(
+    client: &aws_sdk_sesv2::Client,
+    name: &str,
+    configuration_set: &ConfigurationSet,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client
+        .create_configuration_set()
+        .configuration_set_name(name)
+        .sending_options(
+            SendingOptions::builder()
+                .sending_enabled(configuration_set.sending_enabled)
+                .build(),
+        )
+        .reputation_options(
+            ReputationOptions::builder()
+                .reputation_metrics_enabled(configuration_set.reputation_metrics_enabled)
+                .build(),
+        )
+        .delivery_options(
+            DeliveryOptions::builder()
+                .set_tls_policy(configuration_set.tls_policy.as_deref().map(TlsPolicy::from))
+                .set_sending_pool_name(configuration_set.sending_pool.clone())
+                .build(),
+        )
+        .set_suppression_options(
+            suppressed_reasons(&configuration_set.suppressed_reasons)
+                .map(|reasons| SuppressionOptions::builder().set_suppressed_reasons(Some(reasons)).build()),
+        );
+
+    if let Some(domain) = &configuration_set.custom_redirect_domain {
+        request = request.tracking_options(TrackingOptions::builder().custom_redirect_domain(domain).build()?);
+    }
+
+    if configuration_set.tags.len() > 0 {
+        request = request.set_tags(Some(configuration_set.tags.to_vec()?));
+    }
+
+    request.send().await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Created SES configuration set {}", name)),
+    })
+}
+
+/// Applies the options that differ between `old` and `new`. SES takes each group of options in its own call.
+pub async fn update_configuration_set(
+    client: &aws_sdk_sesv2::Client,
+    name: &str,
+    old: &ConfigurationSet,
+    new: &ConfigurationSet,
+) -> Result<OpExecResponse, anyhow::Error> {
+    if old.sending_enabled != new.sending_enabled {
+        client
+            .put_configuration_set_sending_options()
+            .configuration_set_name(name)
+            .sending_enabled(new.sending_enabled)
+            .send()
+            .await
+            .with_context(|| format!("Failed to set sending options for SES configuration set {}", name))?;
+    }
+
+    if old.reputation_metrics_enabled != new.reputation_metrics_enabled {
+        client
+            .put_configuration_set_reputation_options()
+            .configuration_set_name(name)
+            .reputation_metrics_enabled(new.reputation_metrics_enabled)
+            .send()
+            .await
+            .with_context(|| format!("Failed to set reputation options for SES configuration set {}", name))?;
+    }
+
+    if old.tls_policy != new.tls_policy || old.sending_pool != new.sending_pool {
+        client
+            .put_configuration_set_delivery_options()
+            .configuration_set_name(name)
+            .tls_policy(TlsPolicy::from(new.tls_policy.as_deref().unwrap_or("OPTIONAL")))
+            .set_sending_pool_name(new.sending_pool.clone())
+            .send()
+            .await
+            .with_context(|| format!("Failed to set delivery options for SES configuration set {}", name))?;
+    }
+
+    if old.suppressed_reasons != new.suppressed_reasons {
+        client
+            .put_configuration_set_suppression_options()
+            .configuration_set_name(name)
+            .set_suppressed_reasons(suppressed_reasons(&new.suppressed_reasons))
+            .send()
+            .await
+            .with_context(|| format!("Failed to set suppression options for SES configuration set {}", name))?;
+    }
+
+    if old.custom_redirect_domain != new.custom_redirect_domain {
+        client
+            .put_configuration_set_tracking_options()
+            .configuration_set_name(name)
+            .set_custom_redirect_domain(new.custom_redirect_domain.clone())
+            .send()
+            .await
+            .with_context(|| format!("Failed to set tracking options for SES configuration set {}", name))?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated SES configuration set {}", name)),
+    })
+}
+
+/// Deletes a configuration set, along with its event destinations
+pub async fn delete_configuration_set(client: &aws_sdk_sesv2::Client, name: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_configuration_set().configuration_set_name(name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Deleted SES configuration set {}", name)),
+    })
+}
+
+pub async fn create_event_destination(
+    client: &aws_sdk_sesv2::Client,
+    configuration_set: &str,
+    name: &str,
+    destination: &EventDestination,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .create_configuration_set_event_destination()
+        .configuration_set_name(configuration_set)
+        .event_destination_name(name)
+        .event_destination(event_destination_definition(destination)?)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Created event destination {} for SES configuration set {}",
+            name, configuration_set
+        )),
+    })
+}
+
+pub async fn update_event_destination(
+    client: &aws_sdk_sesv2::Client,
+    configuration_set: &str,
+    name: &str,
+    destination: &EventDestination,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .update_configuration_set_event_destination()
+        .configuration_set_name(configuration_set)
+        .event_destination_name(name)
+        .event_destination(event_destination_definition(destination)?)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Updated event destination {} for SES configuration set {}",
+            name, configuration_set
+        )),
+    })
+}
+
+pub async fn delete_event_destination(
+    client: &aws_sdk_sesv2::Client,
+    configuration_set: &str,
+    name: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .delete_configuration_set_event_destination()
+        .configuration_set_name(configuration_set)
+        .event_destination_name(name)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!(
+            "Deleted event destination {} for SES configuration set {}",
+            name, configuration_set
+        )),
+    })
+}
+
+pub async fn create_dedicated_ip_pool(
+    client: &aws_sdk_sesv2::Client,
+    name: &str,
+    pool: &DedicatedIpPool,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let mut request = client
+        .create_dedicated_ip_pool()
+        .pool_name(name)
+        .scaling_mode(ScalingMode::from(pool.scaling_mode.as_str()));
+
+    if pool.tags.len() > 0 {
+        request = request.set_tags(Some(pool.tags.to_vec()?));
+    }
+
+    request.send().await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Created SES dedicated IP pool {}", name)),
+    })
+}
+
+pub async fn set_dedicated_ip_pool_scaling_mode(
+    client: &aws_sdk_sesv2::Client,
+    name: &str,
+    scaling_mode: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .put_dedicated_ip_pool_scaling_attributes()
+        .pool_name(name)
+        .scaling_mode(ScalingMode::from(scaling_mode))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Set scaling mode of SES dedicated IP pool {} to {}", name, scaling_mode)),
+    })
+}
+
+/// Deletes a dedicated IP pool. Any dedicated IPs in it move back to the default pool.
+pub async fn delete_dedicated_ip_pool(client: &aws_sdk_sesv2::Client, name: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_dedicated_ip_pool().pool_name(name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Deleted SES dedicated IP pool {}", name)),
+    })
+}
+
@@ -0,0 +1,127 @@
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::SesResourceAddress, tags::Tags};
+
+/// A domain or email address that SES can send from. Domain identities are verified through
+/// their DKIM records: each of the `dkim_record_<n>_name` / `dkim_record_<n>_value` outputs is a
+/// CNAME record to create in the domain's hosted zone (for instance, with the route53 connector).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EmailIdentity {
+    /// Easy DKIM signing. Domain identities only.
+    pub dkim: Option<Dkim>,
+    /// The configuration set to use for mail sent from this identity, unless the sender names another.
+    pub configuration_set: Option<String>,
+    /// A custom MAIL FROM subdomain, such as `mail.example.com`. Domain identities only.
+    pub mail_from: Option<MailFrom>,
+    /// Whether bounces and complaints are forwarded by email. Can only be turned off once
+    /// the identity has an SNS or event destination for them.
+    pub feedback_forwarding: bool,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Dkim {
+    pub signing_enabled: bool,
+    pub key_length: String, // RSA_1024_BIT or RSA_2048_BIT
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MailFrom {
+    /// A subdomain of the identity, which needs an MX and an SPF record of its own.
+    pub domain: String,
+    pub behavior_on_mx_failure: String, // USE_DEFAULT_VALUE or REJECT_MESSAGE
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigurationSet {
+    pub sending_enabled: bool,
+    pub reputation_metrics_enabled: bool,
+    pub tls_policy: Option<String>, // REQUIRE or OPTIONAL (the default)
+    /// The dedicated IP pool to send from.
+    pub sending_pool: Option<String>,
+    /// Addresses that bounce or complain for these reasons are added to the account's suppression list.
+    /// None to use the account-level setting.
+    pub suppressed_reasons: Option<Vec<String>>, // BOUNCE, COMPLAINT
+    /// A domain to serve open and click tracking links from, instead of the SES default.
+    pub custom_redirect_domain: Option<String>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EventDestination {
+    pub enabled: bool,
+    /// SEND, REJECT, BOUNCE, COMPLAINT, DELIVERY, OPEN, CLICK, RENDERING_FAILURE,
+    /// DELIVERY_DELAY or SUBSCRIPTION
+    pub matching_event_types: Vec<String>,
+    pub destination: Destination,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum Destination {
+    CloudWatch { dimensions: Vec<CloudWatchDimension> },
+    KinesisFirehose { delivery_stream_arn: String, iam_role_arn: String },
+    Sns { topic_arn: String },
+    EventBridge { event_bus_arn: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CloudWatchDimension {
+    pub name: String,
+    pub value_source: String, // MESSAGE_TAG, EMAIL_HEADER or LINK_TAG
+    pub default_value: String,
+}
+
+/// Dedicated IPs are leased through the SES console; a STANDARD pool holds the ones
+/// assigned to it there, while SES leases and warms up IPs for a MANAGED pool itself.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DedicatedIpPool {
+    pub scaling_mode: String, // STANDARD or MANAGED
+    pub tags: Tags,
+}
+
+pub enum SesResource {
+    EmailIdentity(EmailIdentity),
+    ConfigurationSet(ConfigurationSet),
+    EventDestination(EventDestination),
+    DedicatedIpPool(DedicatedIpPool),
+}
+
+impl Resource for SesResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            SesResource::EmailIdentity(identity) => Ok(RON.to_string_pretty(&identity, pretty_config)?.into()),
+            SesResource::ConfigurationSet(configuration_set) => {
+                Ok(RON.to_string_pretty(&configuration_set, pretty_config)?.into())
+            }
+            SesResource::EventDestination(destination) => Ok(RON.to_string_pretty(&destination, pretty_config)?.into()),
+            SesResource::DedicatedIpPool(pool) => Ok(RON.to_string_pretty(&pool, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = SesResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            SesResourceAddress::EmailIdentity { .. } => Ok(SesResource::EmailIdentity(RON.from_str(s)?)),
+            SesResourceAddress::ConfigurationSet { .. } => Ok(SesResource::ConfigurationSet(RON.from_str(s)?)),
+            SesResourceAddress::EventDestination { .. } => Ok(SesResource::EventDestination(RON.from_str(s)?)),
+            SesResourceAddress::DedicatedIpPool { .. } => Ok(SesResource::DedicatedIpPool(RON.from_str(s)?)),
+        }
+    }
+}
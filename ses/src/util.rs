@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use anyhow::bail;
+use aws_sdk_sesv2::types::{
+    CloudWatchDestination, CloudWatchDimensionConfiguration, DimensionValueSource, EventBridgeDestination,
+    EventDestinationDefinition, EventType, KinesisFirehoseDestination, SnsDestination,
+};
+
+use crate::{
+    resource::{CloudWatchDimension, Destination, EventDestination},
+    tags::Tags,
+};
+
+/// Easy DKIM always hands out three tokens, so a domain identity has three DKIM records.
+pub const DKIM_RECORD_COUNT: usize = 3;
+
+pub fn identity_arn(region: &str, account_id: &str, identity: &str) -> String {
+    format!("arn:aws:ses:{region}:{account_id}:identity/{identity}")
+}
+
+pub fn configuration_set_arn(region: &str, account_id: &str, name: &str) -> String {
+    format!("arn:aws:ses:{region}:{account_id}:configuration-set/{name}")
+}
+
+pub fn dedicated_ip_pool_arn(region: &str, account_id: &str, name: &str) -> String {
+    format!("arn:aws:ses:{region}:{account_id}:dedicated-ip-pool/{name}")
+}
+
+/// Whether an identity is a domain rather than a single email address.
+pub fn is_domain(identity: &str) -> bool {
+    !identity.contains('@')
+}
+
+/// The DKIM records for a domain identity, as outputs: `dkim_record_<n>_name` is the CNAME record
+/// to create, and `dkim_record_<n>_value` is what it points to.
+pub fn dkim_outputs(identity: &str, tokens: &[String]) -> HashMap<String, String> {
+    let mut outputs = HashMap::new();
+    for (i, token) in tokens.iter().enumerate() {
+        outputs.insert(
+            format!("dkim_record_{}_name", i + 1),
+            format!("{token}._domainkey.{identity}"),
+        );
+        outputs.insert(format!("dkim_record_{}_value", i + 1), format!("{token}.dkim.amazonses.com"));
+    }
+    outputs
+}
+
+/// The output keys set by `dkim_outputs`, so they can be cleared when the identity is deleted.
+pub fn dkim_output_keys() -> Vec<String> {
+    (1..=DKIM_RECORD_COUNT)
+        .flat_map(|i| [format!("dkim_record_{i}_name"), format!("dkim_record_{i}_value")])
+        .collect()
+}
+
+pub fn event_destination_definition(destination: &EventDestination) -> anyhow::Result<EventDestinationDefinition> {
+    let mut definition = EventDestinationDefinition::builder()
+        .enabled(destination.enabled)
+        .set_matching_event_types(Some(
+            destination
+                .matching_event_types
+                .iter()
+                .map(|t| EventType::from(t.as_str()))
+                .collect(),
+        ));
+
+    definition = match &destination.destination {
+        Destination::CloudWatch { dimensions } => {
+            let mut dimension_configurations = Vec::new();
+            for dimension in dimensions {
+                dimension_configurations.push(
+                    CloudWatchDimensionConfiguration::builder()
+                        .dimension_name(&dimension.name)
+                        .dimension_value_source(DimensionValueSource::from(dimension.value_source.as_str()))
+                        .default_dimension_value(&dimension.default_value)
+                        .build()?,
+                );
+            }
+            definition.cloud_watch_destination(
+                CloudWatchDestination::builder()
+                    .set_dimension_configurations(Some(dimension_configurations))
+                    .build()?,
+            )
+        }
+        Destination::KinesisFirehose {
+            delivery_stream_arn,
+            iam_role_arn,
+        } => definition.kinesis_firehose_destination(
+            KinesisFirehoseDestination::builder()
+                .delivery_stream_arn(delivery_stream_arn)
+                .iam_role_arn(iam_role_arn)
+                .build()?,
+        ),
+        Destination::Sns { topic_arn } => {
+            definition.sns_destination(SnsDestination::builder().topic_arn(topic_arn).build()?)
+        }
+        Destination::EventBridge { event_bus_arn } => {
+            definition.event_bridge_destination(EventBridgeDestination::builder().event_bus_arn(event_bus_arn).build()?)
+        }
+    };
+
+    Ok(definition.build())
+}
+
+pub fn event_destination_from_sdk(destination: &aws_sdk_sesv2::types::EventDestination) -> anyhow::Result<EventDestination> {
+    let target = if let Some(cloud_watch) = destination.cloud_watch_destination() {
+        Destination::CloudWatch {
+            dimensions: cloud_watch
+                .dimension_configurations()
+                .iter()
+                .map(|d| CloudWatchDimension {
+                    name: d.dimension_name().to_string(),
+                    value_source: d.dimension_value_source().as_str().to_string(),
+                    default_value: d.default_dimension_value().to_string(),
+                })
+                .collect(),
+        }
+    } else if let Some(firehose) = destination.kinesis_firehose_destination() {
+        Destination::KinesisFirehose {
+            delivery_stream_arn: firehose.delivery_stream_arn().to_string(),
+            iam_role_arn: firehose.iam_role_arn().to_string(),
+        }
+    } else if let Some(sns) = destination.sns_destination() {
+        Destination::Sns {
+            topic_arn: sns.topic_arn().to_string(),
+        }
+    } else if let Some(event_bridge) = destination.event_bridge_destination() {
+        Destination::EventBridge {
+            event_bus_arn: event_bridge.event_bus_arn().to_string(),
+        }
+    } else {
+        bail!(
+            "SES event destination {} sends to a destination type this connector doesn't manage",
+            destination.name()
+        );
+    };
+
+    let mut matching_event_types: Vec<String> = destination
+        .matching_event_types()
+        .iter()
+        .map(|t| t.as_str().to_string())
+        .collect();
+    matching_event_types.sort();
+
+    Ok(EventDestination {
+        enabled: destination.enabled(),
+        matching_event_types,
+        destination: target,
+    })
+}
+
+/// Gets the tags on an SES resource, for resources whose Get* call doesn't include them.
+pub async fn list_tags(client: &aws_sdk_sesv2::Client, resource_arn: &str) -> anyhow::Result<Tags> {
+    let resp = client.list_tags_for_resource().resource_arn(resource_arn).send().await?;
+    Ok(Tags::from(resp.tags()))
+}
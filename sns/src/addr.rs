@@ -0,0 +1,74 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum SnsResourceAddress {
+    /// FIFO topic names end in `.fifo`.
+    Topic { region: String, name: String },
+    /// Subscription IDs (the last part of the subscription ARN) are assigned by SNS,
+    /// so `subscription_id` is virtual until the subscription exists.
+    Subscription {
+        region:          String,
+        topic_name:      String,
+        subscription_id: String,
+    },
+}
+
+impl ResourceAddress for SnsResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            SnsResourceAddress::Topic { region, name } => PathBuf::from(format!("aws/sns/{region}/topics/{name}.ron")),
+            SnsResourceAddress::Subscription {
+                region,
+                topic_name,
+                subscription_id,
+            } => PathBuf::from(format!(
+                "aws/sns/{region}/topics/{topic_name}/subscriptions/{subscription_id}.ron"
+            )),
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "sns", region, "topics", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(SnsResourceAddress::Topic {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "sns", region, "topics", topic_name, "subscriptions", subscription_id]
+                if subscription_id.ends_with(".ron") =>
+            {
+                let subscription_id = subscription_id.strip_suffix(".ron").unwrap().to_string();
+                Ok(SnsResourceAddress::Subscription {
+                    region: region.to_string(),
+                    topic_name: topic_name.to_string(),
+                    subscription_id,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for SnsResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/sns/<region>/topics/<topic_name>.ron",
+                description: "An SNS topic",
+                example:     "aws/sns/us-east-1/topics/alerts.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/sns/<region>/topics/<topic_name>/subscriptions/<subscription_id>.ron",
+                description: "A subscription to a topic",
+                example:     "aws/sns/us-east-1/topics/alerts/subscriptions/oncall-email.ron",
+            },
+        ]
+    }
+}
@@ -0,0 +1,228 @@
+pub use crate::addr::SnsResourceAddress;
+pub use crate::op::SnsConnectorOp;
+pub use crate::resource::SnsResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse, VirtToPhyResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::SnsConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{Subscription, Topic};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct SnsConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_sns::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<SnsConnectorConfig>,
+    prefix: PathBuf,
+}
+
+impl SnsConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_sns::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+            let config = aws_config::defaults(BehaviorVersion::latest())
+                .region(region)
+                .timeout_config(
+                    TimeoutConfig::builder()
+                        .connect_timeout(Duration::from_secs(30))
+                        .operation_timeout(Duration::from_secs(30))
+                        .operation_attempt_timeout(Duration::from_secs(30))
+                        .read_timeout(Duration::from_secs(30))
+                        .build(),
+                )
+                .load()
+                .await;
+            let client = audited_client!(aws_sdk_sns, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for SnsConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = SnsResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(SnsConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let sns_config: SnsConnectorConfig = SnsConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = sns_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(sns_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = sns_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn addr_virt_to_phy(&self, addr: &Path) -> anyhow::Result<VirtToPhyResponse> {
+        let addr = SnsResourceAddress::from_path(addr)?;
+
+        match &addr {
+            SnsResourceAddress::Subscription { region, topic_name, .. } => {
+                let Some(subscription_id) = addr.get_output(&self.prefix, "subscription_id")? else {
+                    return Ok(VirtToPhyResponse::NotPresent);
+                };
+                Ok(VirtToPhyResponse::Present(
+                    SnsResourceAddress::Subscription {
+                        region: region.clone(),
+                        topic_name: topic_name.clone(),
+                        subscription_id,
+                    }
+                    .to_path_buf(),
+                ))
+            }
+            _ => Ok(VirtToPhyResponse::Present(addr.to_path_buf())),
+        }
+    }
+
+    async fn addr_phy_to_virt(&self, addr: &Path) -> anyhow::Result<Option<PathBuf>> {
+        let addr = SnsResourceAddress::from_path(addr)?;
+
+        match &addr {
+            SnsResourceAddress::Subscription { .. } => {
+                if let Some(virt_addr) = addr.phy_to_virt(&self.prefix)? {
+                    return Ok(Some(virt_addr.to_path_buf()));
+                }
+                // Not created from this repository, so there's no virtual address to map back to
+                Ok(Some(addr.to_path_buf()))
+            }
+            _ => Ok(Some(addr.to_path_buf())),
+        }
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // Topic skeleton
+        res.push(skeleton!(
+            SnsResourceAddress::Topic {
+                region: String::from("[region]"),
+                name:   String::from("[topic_name]"),
+            },
+            SnsResource::Topic(Topic {
+                fifo_topic: false,
+                content_based_deduplication: false,
+                display_name: Some(String::from("[display_name]")),
+                kms_master_key_id: Some(String::from("alias/aws/sns")),
+                policy: None,
+                delivery_policy: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        // Subscription skeleton, delivering to an SQS queue
+        res.push(skeleton!(
+            SnsResourceAddress::Subscription {
+                region: String::from("[region]"),
+                topic_name: String::from("[topic_name]"),
+                subscription_id: String::from("[subscription_name]"),
+            },
+            SnsResource::Subscription(Subscription {
+                protocol: String::from("sqs"),
+                endpoint: String::from("arn:aws:sqs:[region]:[account_id]:[queue_name]"),
+                filter_policy: None,
+                filter_policy_scope: None,
+                raw_message_delivery: true,
+                dead_letter_target_arn: None,
+                subscription_role_arn: None,
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = SnsResourceAddress::from_path(addr)?;
+
+        match addr {
+            SnsResourceAddress::Topic { .. } => ron_check_eq::<Topic>(a, b),
+            SnsResourceAddress::Subscription { .. } => ron_check_eq::<Subscription>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = SnsResourceAddress::from_path(addr)?;
+
+        match addr {
+            SnsResourceAddress::Topic { .. } => ron_check_syntax::<Topic>(a),
+            SnsResourceAddress::Subscription { .. } => ron_check_syntax::<Subscription>(a),
+        }
+    }
+}
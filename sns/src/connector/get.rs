@@ -0,0 +1,123 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_sns::operation::{
+    get_subscription_attributes::GetSubscriptionAttributesError, get_topic_attributes::GetTopicAttributesError,
+};
+
+use crate::{
+    addr::SnsResourceAddress,
+    resource::{SnsResource, Subscription, Topic},
+    util::{is_default_topic_policy, json_to_ron, subscription_arn, topic_arn},
+};
+
+use super::SnsConnector;
+
+impl SnsConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = SnsResourceAddress::from_path(addr)?;
+
+        let account_id = self.account_id.lock().await.clone();
+
+        match &addr {
+            SnsResourceAddress::Topic { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+                let topic_arn = topic_arn(region, &account_id, name);
+
+                let attributes = match client.get_topic_attributes().topic_arn(&topic_arn).send().await {
+                    Ok(resp) => resp.attributes.unwrap_or_default(),
+                    Err(e) => match e.as_service_error() {
+                        Some(GetTopicAttributesError::NotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let non_empty = |name: &str| attributes.get(name).filter(|v| !v.is_empty()).cloned();
+                let is_true = |name: &str| attributes.get(name).map(String::as_str) == Some("true");
+
+                // Every topic has a policy, so only report it if it's been changed from SNS's default
+                let policy = match non_empty("Policy") {
+                    Some(policy) if !is_default_topic_policy(&policy) => Some(json_to_ron(&policy)?),
+                    _ => None,
+                };
+
+                let delivery_policy = match non_empty("DeliveryPolicy") {
+                    Some(delivery_policy) => Some(json_to_ron(&delivery_policy)?),
+                    None => None,
+                };
+
+                let tags_resp = client.list_tags_for_resource().resource_arn(&topic_arn).send().await?;
+
+                let topic = Topic {
+                    fifo_topic: is_true("FifoTopic"),
+                    content_based_deduplication: is_true("ContentBasedDeduplication"),
+                    display_name: non_empty("DisplayName"),
+                    kms_master_key_id: non_empty("KmsMasterKeyId"),
+                    policy,
+                    delivery_policy,
+                    tags: tags_resp.tags.into(),
+                };
+
+                get_resource_response!(SnsResource::Topic(topic), [(String::from("topic_arn"), topic_arn)])
+            }
+            SnsResourceAddress::Subscription {
+                region,
+                topic_name,
+                subscription_id,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+                let subscription_arn = subscription_arn(region, &account_id, topic_name, subscription_id);
+
+                let attributes = match client
+                    .get_subscription_attributes()
+                    .subscription_arn(&subscription_arn)
+                    .send()
+                    .await
+                {
+                    Ok(resp) => resp.attributes.unwrap_or_default(),
+                    Err(e) => match e.as_service_error() {
+                        Some(GetSubscriptionAttributesError::NotFoundException(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let non_empty = |name: &str| attributes.get(name).filter(|v| !v.is_empty()).cloned();
+
+                let filter_policy = match non_empty("FilterPolicy").filter(|p| p != "{}") {
+                    Some(filter_policy) => Some(json_to_ron(&filter_policy)?),
+                    None => None,
+                };
+
+                let dead_letter_target_arn = match non_empty("RedrivePolicy") {
+                    Some(redrive_policy) => {
+                        let redrive_policy: serde_json::Value = serde_json::from_str(&redrive_policy)?;
+                        redrive_policy["deadLetterTargetArn"].as_str().map(String::from)
+                    }
+                    None => None,
+                };
+
+                let subscription = Subscription {
+                    protocol: non_empty("Protocol").unwrap_or_default(),
+                    endpoint: non_empty("Endpoint").unwrap_or_default(),
+                    filter_policy,
+                    // MessageAttributes is the default scope
+                    filter_policy_scope: non_empty("FilterPolicyScope").filter(|s| s != "MessageAttributes"),
+                    raw_message_delivery: attributes.get("RawMessageDelivery").map(String::as_str) == Some("true"),
+                    dead_letter_target_arn,
+                    subscription_role_arn: non_empty("SubscriptionRoleArn"),
+                };
+
+                get_resource_response!(
+                    SnsResource::Subscription(subscription),
+                    [
+                        (String::from("subscription_id"), subscription_id.clone()),
+                        (String::from("subscription_arn"), subscription_arn)
+                    ]
+                )
+            }
+        }
+    }
+}
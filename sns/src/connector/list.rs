@@ -0,0 +1,58 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::{addr::SnsResourceAddress, util::arn_suffix};
+
+use super::SnsConnector;
+
+impl SnsConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut topics = client.list_topics().into_paginator().items().send();
+            while let Some(topic) = topics.next().await {
+                let Some(topic_arn) = topic?.topic_arn else {
+                    continue;
+                };
+                let Some(topic_name) = arn_suffix(&topic_arn) else {
+                    continue;
+                };
+
+                results.push(
+                    SnsResourceAddress::Topic {
+                        region: region.clone(),
+                        name:   topic_name.clone(),
+                    }
+                    .to_path_buf(),
+                );
+
+                let mut subscriptions = client
+                    .list_subscriptions_by_topic()
+                    .topic_arn(&topic_arn)
+                    .into_paginator()
+                    .items()
+                    .send();
+                while let Some(subscription) = subscriptions.next().await {
+                    // Unconfirmed subscriptions have no ARN yet
+                    if let Some(subscription_id) = subscription?.subscription_arn.as_deref().and_then(arn_suffix) {
+                        results.push(
+                            SnsResourceAddress::Subscription {
+                                region: region.clone(),
+                                topic_name: topic_name.clone(),
+                                subscription_id,
+                            }
+                            .to_path_buf(),
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,63 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::connector::{ConnectorOp, OpExecResponse, ResourceAddress};
+
+use crate::{
+    addr::SnsResourceAddress,
+    op::SnsConnectorOp,
+    op_impl,
+    util::{subscription_arn, topic_arn},
+};
+
+use super::SnsConnector;
+
+impl SnsConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = SnsResourceAddress::from_path(addr)?;
+        let op = SnsConnectorOp::from_str(op)?;
+
+        let account_id = self.account_id.lock().await.clone();
+
+        match &addr {
+            SnsResourceAddress::Topic { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+                let topic_arn = topic_arn(region, &account_id, name);
+
+                match op {
+                    SnsConnectorOp::CreateTopic(topic) => op_impl::create_topic(&client, name, &topic).await,
+                    SnsConnectorOp::SetTopicAttributes(attributes) => {
+                        op_impl::set_topic_attributes(&client, &topic_arn, &account_id, attributes).await
+                    }
+                    SnsConnectorOp::UpdateTopicTags(old_tags, new_tags) => {
+                        op_impl::update_topic_tags(&client, &topic_arn, &old_tags, &new_tags).await
+                    }
+                    SnsConnectorOp::DeleteTopic => op_impl::delete_topic(&client, &topic_arn).await,
+                    _ => bail!("Invalid operation for SNS topic resource"),
+                }
+            }
+            SnsResourceAddress::Subscription {
+                region,
+                topic_name,
+                subscription_id,
+            } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    SnsConnectorOp::Subscribe(subscription) => {
+                        op_impl::subscribe(&client, &topic_arn(region, &account_id, topic_name), &subscription).await
+                    }
+                    SnsConnectorOp::SetSubscriptionAttributes(attributes) => {
+                        let subscription_arn = subscription_arn(region, &account_id, topic_name, subscription_id);
+                        op_impl::set_subscription_attributes(&client, &subscription_arn, attributes).await
+                    }
+                    SnsConnectorOp::Unsubscribe => {
+                        let subscription_arn = subscription_arn(region, &account_id, topic_name, subscription_id);
+                        op_impl::unsubscribe(&client, &subscription_arn).await
+                    }
+                    _ => bail!("Invalid operation for SNS subscription resource"),
+                }
+            }
+        }
+    }
+}
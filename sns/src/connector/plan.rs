@@ -0,0 +1,154 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::{
+    resource::{Subscription, Topic},
+    util::{changed_attributes, subscription_attributes, topic_attributes},
+};
+
+use super::{SnsConnector, SnsConnectorOp, SnsResourceAddress};
+
+/// FIFO topics have to be named `*.fifo`, and standard topics can't be.
+fn check_topic_name(topic_name: &str, topic: &Topic) -> anyhow::Result<()> {
+    if topic.fifo_topic != topic_name.ends_with(".fifo") {
+        if topic.fifo_topic {
+            bail!("FIFO topic {} must have a name ending in .fifo", topic_name);
+        } else {
+            bail!("Topic {} has a name ending in .fifo, but fifo_topic is false", topic_name);
+        }
+    }
+    Ok(())
+}
+
+impl SnsConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = SnsResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            SnsResourceAddress::Topic { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_topic)) => {
+                    let new_topic: Topic = RON.from_str(&new_topic)?;
+                    check_topic_name(name, &new_topic)?;
+                    Ok(vec![connector_op!(
+                        SnsConnectorOp::CreateTopic(new_topic),
+                        format!("Create new SNS topic {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_topic), None) => Ok(vec![connector_op!(
+                    SnsConnectorOp::DeleteTopic,
+                    format!("DELETE SNS topic {} in region {}, along with its subscriptions", name, region)
+                )]),
+                (Some(old_topic), Some(new_topic)) => {
+                    let old_topic: Topic = RON.from_str(&old_topic)?;
+                    let new_topic: Topic = RON.from_str(&new_topic)?;
+                    check_topic_name(name, &new_topic)?;
+                    let mut ops = Vec::new();
+
+                    if old_topic.tags != new_topic.tags {
+                        let diff = diff_ron_values(&old_topic.tags, &new_topic.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            SnsConnectorOp::UpdateTopicTags(old_topic.tags.clone(), new_topic.tags.clone()),
+                            format!("Modify tags for SNS topic `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let attributes = changed_attributes(&topic_attributes(&old_topic)?, topic_attributes(&new_topic)?);
+                    if !attributes.is_empty() {
+                        let old_attributes = Topic {
+                            tags: new_topic.tags.clone(),
+                            ..old_topic
+                        };
+                        let diff = diff_ron_values(&old_attributes, &new_topic).unwrap_or_default();
+                        ops.push(connector_op!(
+                            SnsConnectorOp::SetTopicAttributes(attributes),
+                            format!("Modify attributes for SNS topic `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            SnsResourceAddress::Subscription {
+                region,
+                topic_name,
+                subscription_id,
+            } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_subscription)) => {
+                    let new_subscription: Subscription = RON.from_str(&new_subscription)?;
+                    Ok(vec![connector_op!(
+                        SnsConnectorOp::Subscribe(new_subscription.clone()),
+                        format!(
+                            "Subscribe {} endpoint {} to SNS topic {} in region {}",
+                            new_subscription.protocol, new_subscription.endpoint, topic_name, region
+                        )
+                    )])
+                }
+                (Some(_old_subscription), None) => Ok(vec![connector_op!(
+                    SnsConnectorOp::Unsubscribe,
+                    format!("DELETE subscription `{}` to SNS topic {}", subscription_id, topic_name)
+                )]),
+                (Some(old_subscription), Some(new_subscription)) => {
+                    let old_subscription: Subscription = RON.from_str(&old_subscription)?;
+                    let new_subscription: Subscription = RON.from_str(&new_subscription)?;
+
+                    // A subscription's protocol and endpoint can't be changed in place
+                    if old_subscription.protocol != new_subscription.protocol
+                        || old_subscription.endpoint != new_subscription.endpoint
+                    {
+                        return Ok(vec![
+                            connector_op!(
+                                SnsConnectorOp::Unsubscribe,
+                                format!(
+                                    "REPLACE subscription `{}` to SNS topic {} (requires replacement: protocol, endpoint)",
+                                    subscription_id, topic_name
+                                )
+                            ),
+                            connector_op!(
+                                SnsConnectorOp::Subscribe(new_subscription.clone()),
+                                format!(
+                                    "Subscribe {} endpoint {} to SNS topic {} in region {}",
+                                    new_subscription.protocol, new_subscription.endpoint, topic_name, region
+                                )
+                            ),
+                        ]);
+                    }
+
+                    let attributes = changed_attributes(
+                        &subscription_attributes(&old_subscription)?,
+                        subscription_attributes(&new_subscription)?,
+                    );
+                    if attributes.is_empty() {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_subscription, &new_subscription).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        SnsConnectorOp::SetSubscriptionAttributes(attributes),
+                        format!(
+                            "Modify attributes for subscription `{}` to SNS topic {}\n{}",
+                            subscription_id, topic_name, diff
+                        )
+                    )])
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{Subscription, Topic},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum SnsConnectorOp {
+    // Topic operations
+    CreateTopic(Topic),
+    /// Sets the given attributes, keyed by SNS attribute name (e.g. "DisplayName").
+    /// An empty value clears the attribute.
+    SetTopicAttributes(HashMap<String, String>),
+    UpdateTopicTags(Tags, Tags),
+    DeleteTopic,
+
+    // Subscription operations
+    Subscribe(Subscription),
+    SetSubscriptionAttributes(HashMap<String, String>),
+    Unsubscribe,
+}
+
+impl ConnectorOp for SnsConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
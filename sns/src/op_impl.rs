@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use autoschematic_core::connector::OpExecResponse;
+
+use crate::{
+    resource::{Subscription, Topic},
+    tags::{Tags, tag_diff},
+    util::{arn_suffix, default_topic_policy, subscription_attributes, topic_attributes},
+};
+
+/// Creates a topic using the provided configuration
+pub async fn create_topic(client: &aws_sdk_sns::Client, topic_name: &str, topic: &Topic) -> Result<OpExecResponse, anyhow::Error> {
+    // Empty values only mean something when clearing an attribute on an existing topic
+    let mut attributes: HashMap<String, String> = topic_attributes(topic)?
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .collect();
+
+    if topic.fifo_topic {
+        attributes.insert(String::from("FifoTopic"), String::from("true"));
+    }
+
+    let mut request = client.create_topic().name(topic_name).set_attributes(Some(attributes));
+
+    if topic.tags.len() > 0 {
+        request = request.set_tags(Some(topic.tags.to_vec()?));
+    }
+
+    let resp = request.send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("topic_arn"), resp.topic_arn)])),
+        friendly_message: Some(format!("Created SNS topic {}", topic_name)),
+    })
+}
+
+/// Sets topic attributes. SNS only takes one attribute per call.
+pub async fn set_topic_attributes(
+    client: &aws_sdk_sns::Client,
+    topic_arn: &str,
+    account_id: &str,
+    attributes: HashMap<String, String>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    for (name, value) in attributes {
+        let value = if name == "Policy" && value.is_empty() {
+            default_topic_policy(topic_arn, account_id)
+        } else {
+            value
+        };
+
+        client
+            .set_topic_attributes()
+            .topic_arn(topic_arn)
+            .attribute_name(&name)
+            .attribute_value(value)
+            .send()
+            .await
+            .with_context(|| format!("Failed to set {} on SNS topic {}", name, topic_arn))?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated attributes for SNS topic {}", topic_arn)),
+    })
+}
+
+/// Updates topic tags
+pub async fn update_topic_tags(
+    client: &aws_sdk_sns::Client,
+    topic_arn: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags)?;
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(topic_arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tagset.is_empty() {
+        client
+            .tag_resource()
+            .resource_arn(topic_arn)
+            .set_tags(Some(new_tagset))
+            .send()
+            .await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for SNS topic {}", topic_arn)),
+    })
+}
+
+/// Deletes a topic, along with all of its subscriptions
+pub async fn delete_topic(client: &aws_sdk_sns::Client, topic_arn: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.delete_topic().topic_arn(topic_arn).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("topic_arn"), None)])),
+        friendly_message: Some(format!("Deleted SNS topic {}", topic_arn)),
+    })
+}
+
+/// Subscribes an endpoint to a topic. Email and HTTP/S subscriptions stay pending until the endpoint confirms them.
+pub async fn subscribe(
+    client: &aws_sdk_sns::Client,
+    topic_arn: &str,
+    subscription: &Subscription,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let attributes: HashMap<String, String> = subscription_attributes(subscription)?
+        .into_iter()
+        .filter(|(name, value)| !value.is_empty() && !(name == "FilterPolicy" && value == "{}"))
+        .collect();
+
+    let resp = client
+        .subscribe()
+        .topic_arn(topic_arn)
+        .protocol(&subscription.protocol)
+        .endpoint(&subscription.endpoint)
+        .set_attributes(Some(attributes))
+        .return_subscription_arn(true)
+        .send()
+        .await?;
+
+    let subscription_arn = resp.subscription_arn.context("No subscription ARN returned")?;
+    let subscription_id = arn_suffix(&subscription_arn);
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("subscription_id"), subscription_id),
+            (String::from("subscription_arn"), Some(subscription_arn)),
+        ])),
+        friendly_message: Some(format!(
+            "Subscribed {} endpoint {} to SNS topic {}",
+            subscription.protocol, subscription.endpoint, topic_arn
+        )),
+    })
+}
+
+/// Sets subscription attributes. SNS only takes one attribute per call.
+pub async fn set_subscription_attributes(
+    client: &aws_sdk_sns::Client,
+    subscription_arn: &str,
+    attributes: HashMap<String, String>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    // FilterPolicyScope can only be set alongside a filter policy, so set the policy first
+    let mut attributes: Vec<(String, String)> = attributes.into_iter().collect();
+    attributes.sort_by_key(|(name, _)| name != "FilterPolicy");
+
+    for (name, value) in attributes {
+        client
+            .set_subscription_attributes()
+            .subscription_arn(subscription_arn)
+            .attribute_name(&name)
+            .attribute_value(value)
+            .send()
+            .await
+            .with_context(|| format!("Failed to set {} on SNS subscription {}", name, subscription_arn))?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated attributes for SNS subscription {}", subscription_arn)),
+    })
+}
+
+/// Deletes a subscription
+pub async fn unsubscribe(client: &aws_sdk_sns::Client, subscription_arn: &str) -> Result<OpExecResponse, anyhow::Error> {
+    client.unsubscribe().subscription_arn(subscription_arn).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("subscription_id"), None),
+            (String::from("subscription_arn"), None),
+        ])),
+        friendly_message: Some(format!("Deleted SNS subscription {}", subscription_arn)),
+    })
+}
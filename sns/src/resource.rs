@@ -0,0 +1,70 @@
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::SnsResourceAddress, tags::Tags};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Topic {
+    /// FIFO topics must be named `*.fifo`. This can't be changed once the topic exists.
+    #[serde(default)]
+    pub fifo_topic: bool,
+    /// FIFO topics only.
+    #[serde(default)]
+    pub content_based_deduplication: bool,
+    pub display_name: Option<String>,
+    /// A KMS key ID, ARN or alias (such as `alias/aws/sns`) to encrypt messages at rest with.
+    pub kms_master_key_id: Option<String>,
+    /// The topic's access policy. If None, SNS's default policy (owner-only access) applies.
+    pub policy: Option<ron::Value>,
+    /// Retry policy for HTTP/S endpoints.
+    pub delivery_policy: Option<ron::Value>,
+    pub tags: Tags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Subscription {
+    pub protocol: String, // sqs, lambda, firehose, http, https, email, email-json, sms or application
+    /// The queue, function or stream ARN, URL, email address or phone number to deliver to.
+    pub endpoint: String,
+    pub filter_policy: Option<ron::Value>,
+    pub filter_policy_scope: Option<String>, // MessageAttributes (the default) or MessageBody
+    #[serde(default)]
+    pub raw_message_delivery: bool,
+    /// ARN of an SQS queue to send undeliverable messages to.
+    pub dead_letter_target_arn: Option<String>,
+    /// Required for firehose subscriptions.
+    pub subscription_role_arn: Option<String>,
+}
+
+pub enum SnsResource {
+    Topic(Topic),
+    Subscription(Subscription),
+}
+
+impl Resource for SnsResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            SnsResource::Topic(topic) => Ok(RON.to_string_pretty(&topic, pretty_config)?.into()),
+            SnsResource::Subscription(subscription) => Ok(RON.to_string_pretty(&subscription, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = SnsResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            SnsResourceAddress::Topic { .. } => Ok(SnsResource::Topic(RON.from_str(s)?)),
+            SnsResourceAddress::Subscription { .. } => Ok(SnsResource::Subscription(RON.from_str(s)?)),
+        }
+    }
+}
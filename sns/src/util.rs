@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use autoschematic_core::util::RON;
+
+use crate::resource::{Subscription, Topic};
+
+pub fn topic_arn(region: &str, account_id: &str, topic_name: &str) -> String {
+    format!("arn:aws:sns:{region}:{account_id}:{topic_name}")
+}
+
+pub fn subscription_arn(region: &str, account_id: &str, topic_name: &str, subscription_id: &str) -> String {
+    format!("arn:aws:sns:{region}:{account_id}:{topic_name}:{subscription_id}")
+}
+
+/// The last part of an ARN, e.g. the topic name of a topic ARN or the ID of a subscription ARN.
+/// Returns None for the placeholders SNS reports for unconfirmed or deleted subscriptions.
+pub fn arn_suffix(arn: &str) -> Option<String> {
+    if !arn.starts_with("arn:") {
+        return None;
+    }
+    arn.rsplit(':').next().map(String::from)
+}
+
+pub fn json_to_ron(json: &str) -> anyhow::Result<ron::Value> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    Ok(RON.from_str(&RON.to_string(&value)?)?)
+}
+
+fn ron_to_json(value: &ron::Value) -> anyhow::Result<String> {
+    serde_json::to_string(value).context("Failed to serialize policy as JSON")
+}
+
+/// Whether a topic policy is the one SNS attaches to every new topic.
+pub fn is_default_topic_policy(policy: &str) -> bool {
+    let Ok(policy) = serde_json::from_str::<serde_json::Value>(policy) else {
+        return false;
+    };
+    match policy["Statement"].as_array() {
+        Some(statements) => {
+            statements.len() == 1 && statements[0]["Sid"].as_str() == Some("__default_statement_ID")
+        }
+        None => false,
+    }
+}
+
+/// The policy SNS attaches to new topics, which lets only the owning account manage and publish to the topic.
+/// Used to reset a topic's policy, since SNS doesn't accept an empty one.
+pub fn default_topic_policy(topic_arn: &str, account_id: &str) -> String {
+    serde_json::json!({
+        "Version": "2008-10-17",
+        "Id": "__default_policy_ID",
+        "Statement": [{
+            "Sid": "__default_statement_ID",
+            "Effect": "Allow",
+            "Principal": {"AWS": "*"},
+            "Action": [
+                "SNS:GetTopicAttributes",
+                "SNS:SetTopicAttributes",
+                "SNS:AddPermission",
+                "SNS:RemovePermission",
+                "SNS:DeleteTopic",
+                "SNS:Subscribe",
+                "SNS:ListSubscriptionsByTopic",
+                "SNS:Publish"
+            ],
+            "Resource": topic_arn,
+            "Condition": {"StringEquals": {"AWS:SourceOwner": account_id}}
+        }]
+    })
+    .to_string()
+}
+
+/// The topic's settable attributes, keyed by SNS attribute name. An empty value clears the attribute.
+pub fn topic_attributes(topic: &Topic) -> anyhow::Result<HashMap<String, String>> {
+    let mut attributes = HashMap::new();
+
+    if topic.fifo_topic {
+        attributes.insert(
+            String::from("ContentBasedDeduplication"),
+            topic.content_based_deduplication.to_string(),
+        );
+    }
+
+    attributes.insert(String::from("DisplayName"), topic.display_name.clone().unwrap_or_default());
+    attributes.insert(
+        String::from("KmsMasterKeyId"),
+        topic.kms_master_key_id.clone().unwrap_or_default(),
+    );
+
+    let policy = match &topic.policy {
+        Some(policy) => ron_to_json(policy)?,
+        None => String::new(),
+    };
+    attributes.insert(String::from("Policy"), policy);
+
+    let delivery_policy = match &topic.delivery_policy {
+        Some(delivery_policy) => ron_to_json(delivery_policy)?,
+        None => String::new(),
+    };
+    attributes.insert(String::from("DeliveryPolicy"), delivery_policy);
+
+    Ok(attributes)
+}
+
+/// The subscription's settable attributes, keyed by SNS attribute name. An empty value clears the attribute.
+pub fn subscription_attributes(subscription: &Subscription) -> anyhow::Result<HashMap<String, String>> {
+    let mut attributes = HashMap::new();
+
+    // An empty filter policy is "{}" rather than ""
+    let filter_policy = match &subscription.filter_policy {
+        Some(filter_policy) => ron_to_json(filter_policy)?,
+        None => String::from("{}"),
+    };
+    attributes.insert(String::from("FilterPolicy"), filter_policy);
+
+    // The scope can only be set alongside a filter policy
+    if subscription.filter_policy.is_some() {
+        attributes.insert(
+            String::from("FilterPolicyScope"),
+            subscription
+                .filter_policy_scope
+                .clone()
+                .unwrap_or_else(|| String::from("MessageAttributes")),
+        );
+    }
+
+    attributes.insert(
+        String::from("RawMessageDelivery"),
+        subscription.raw_message_delivery.to_string(),
+    );
+
+    let redrive_policy = match &subscription.dead_letter_target_arn {
+        Some(dead_letter_target_arn) => serde_json::json!({ "deadLetterTargetArn": dead_letter_target_arn }).to_string(),
+        None => String::new(),
+    };
+    attributes.insert(String::from("RedrivePolicy"), redrive_policy);
+
+    attributes.insert(
+        String::from("SubscriptionRoleArn"),
+        subscription.subscription_role_arn.clone().unwrap_or_default(),
+    );
+
+    Ok(attributes)
+}
+
+/// Attribute values are equal if they're the same string, or the same JSON document
+/// (key order in a policy doesn't matter).
+fn attribute_values_equal(a: &str, b: &str) -> bool {
+    if a == b {
+        return true;
+    }
+    match (
+        serde_json::from_str::<serde_json::Value>(a),
+        serde_json::from_str::<serde_json::Value>(b),
+    ) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// The attributes in `new` that differ from `old`.
+pub fn changed_attributes(old: &HashMap<String, String>, new: HashMap<String, String>) -> HashMap<String, String> {
+    new.into_iter()
+        .filter(|(name, value)| match old.get(name) {
+            Some(old_value) => !attribute_values_equal(old_value, value),
+            None => true,
+        })
+        .collect()
+}
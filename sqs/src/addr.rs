@@ -0,0 +1,45 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum SqsResourceAddress {
+    /// FIFO queue names end in `.fifo`, e.g. `aws/sqs/us-east-1/queues/orders.fifo.ron`.
+    Queue { region: String, name: String },
+}
+
+impl ResourceAddress for SqsResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            SqsResourceAddress::Queue { region, name } => PathBuf::from(format!("aws/sqs/{region}/queues/{name}.ron")),
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "sqs", region, "queues", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(SqsResourceAddress::Queue {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for SqsResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/sqs/<region>/queues/<queue_name>.ron",
+                description: "An SQS queue",
+                example:     "aws/sqs/us-east-1/queues/jobs.ron",
+            },
+        ]
+    }
+}
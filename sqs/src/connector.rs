@@ -0,0 +1,201 @@
+pub use crate::addr::SqsResourceAddress;
+pub use crate::op::SqsConnectorOp;
+pub use crate::resource::SqsResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::SqsConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{Queue, QueueEncryption, RedrivePolicy};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct SqsConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_sqs::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<SqsConnectorConfig>,
+    prefix: PathBuf,
+}
+
+impl SqsConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_sqs::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+            let config = aws_config::defaults(BehaviorVersion::latest())
+                .region(region)
+                .timeout_config(
+                    TimeoutConfig::builder()
+                        .connect_timeout(Duration::from_secs(30))
+                        .operation_timeout(Duration::from_secs(30))
+                        .operation_attempt_timeout(Duration::from_secs(30))
+                        .read_timeout(Duration::from_secs(30))
+                        .build(),
+                )
+                .load()
+                .await;
+            let client = audited_client!(aws_sdk_sqs, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+}
+
+#[async_trait]
+impl Connector for SqsConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = SqsResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(SqsConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let sqs_config: SqsConnectorConfig = SqsConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = sqs_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(sqs_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = sqs_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // Standard queue skeleton, with a dead-letter queue
+        res.push(skeleton!(
+            SqsResourceAddress::Queue {
+                region: String::from("[region]"),
+                name:   String::from("[queue_name]"),
+            },
+            SqsResource::Queue(Queue {
+                fifo_queue: false,
+                content_based_deduplication: false,
+                visibility_timeout: Some(60),
+                message_retention_period: None,
+                delay_seconds: None,
+                receive_message_wait_time_seconds: Some(20),
+                redrive_policy: Some(RedrivePolicy {
+                    dead_letter_target_arn: String::from("arn:aws:sqs:[region]:[account_id]:[dead_letter_queue_name]"),
+                    max_receive_count: 5,
+                }),
+                encryption: QueueEncryption::SqsManaged,
+                policy: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        // FIFO queue skeleton, encrypted with a KMS key
+        res.push(skeleton!(
+            SqsResourceAddress::Queue {
+                region: String::from("[region]"),
+                name:   String::from("[queue_name].fifo"),
+            },
+            SqsResource::Queue(Queue {
+                fifo_queue: true,
+                content_based_deduplication: true,
+                visibility_timeout: None,
+                message_retention_period: Some(1_209_600), // 14 days
+                delay_seconds: None,
+                receive_message_wait_time_seconds: None,
+                redrive_policy: None,
+                encryption: QueueEncryption::Kms {
+                    kms_key_id: String::from("alias/aws/sqs"),
+                    data_key_reuse_period_seconds: None,
+                },
+                policy: None,
+                tags: Tags::default(),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = SqsResourceAddress::from_path(addr)?;
+
+        match addr {
+            SqsResourceAddress::Queue { .. } => ron_check_eq::<Queue>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = SqsResourceAddress::from_path(addr)?;
+
+        match addr {
+            SqsResourceAddress::Queue { .. } => ron_check_syntax::<Queue>(a),
+        }
+    }
+}
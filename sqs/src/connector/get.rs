@@ -0,0 +1,60 @@
+use std::{collections::HashMap, path::Path};
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_sqs::{operation::get_queue_url::GetQueueUrlError, types::QueueAttributeName};
+
+use crate::{addr::SqsResourceAddress, resource::SqsResource, util::queue_from_attributes};
+
+use super::SqsConnector;
+
+impl SqsConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = SqsResourceAddress::from_path(addr)?;
+
+        match &addr {
+            SqsResourceAddress::Queue { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let queue_url = match client.get_queue_url().queue_name(name).send().await {
+                    Ok(resp) => resp.queue_url.unwrap_or_default(),
+                    Err(e) => match e.as_service_error() {
+                        Some(GetQueueUrlError::QueueDoesNotExist(_)) => return Ok(None),
+                        _ => return Err(e.into()),
+                    },
+                };
+
+                let attributes_resp = client
+                    .get_queue_attributes()
+                    .queue_url(&queue_url)
+                    .attribute_names(QueueAttributeName::All)
+                    .send()
+                    .await?;
+
+                let attributes: HashMap<String, String> = attributes_resp
+                    .attributes
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(name, value)| (name.as_str().to_string(), value))
+                    .collect();
+
+                let tags_resp = client.list_queue_tags().queue_url(&queue_url).send().await?;
+
+                let queue = queue_from_attributes(&attributes, tags_resp.tags.into())?;
+
+                get_resource_response!(
+                    SqsResource::Queue(queue),
+                    [
+                        (String::from("queue_url"), queue_url),
+                        (
+                            String::from("queue_arn"),
+                            attributes.get("QueueArn").cloned().unwrap_or_default()
+                        )
+                    ]
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+
+use crate::{addr::SqsResourceAddress, util::queue_name_from_url};
+
+use super::SqsConnector;
+
+impl SqsConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut queue_urls = client.list_queues().into_paginator().items().send();
+            while let Some(queue_url) = queue_urls.next().await {
+                results.push(
+                    SqsResourceAddress::Queue {
+                        region: region.clone(),
+                        name:   queue_name_from_url(&queue_url?),
+                    }
+                    .to_path_buf(),
+                );
+            }
+        }
+
+        Ok(results)
+    }
+}
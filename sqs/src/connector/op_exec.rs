@@ -0,0 +1,31 @@
+use std::path::Path;
+
+use autoschematic_core::connector::{ConnectorOp, OpExecResponse, ResourceAddress};
+
+use crate::{addr::SqsResourceAddress, op::SqsConnectorOp, op_impl};
+
+use super::SqsConnector;
+
+impl SqsConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = SqsResourceAddress::from_path(addr)?;
+        let op = SqsConnectorOp::from_str(op)?;
+
+        match &addr {
+            SqsResourceAddress::Queue { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    SqsConnectorOp::CreateQueue(queue) => op_impl::create_queue(&client, name, &queue).await,
+                    SqsConnectorOp::SetQueueAttributes(attributes) => {
+                        op_impl::set_queue_attributes(&client, name, attributes).await
+                    }
+                    SqsConnectorOp::UpdateQueueTags(old_tags, new_tags) => {
+                        op_impl::update_queue_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    SqsConnectorOp::DeleteQueue => op_impl::delete_queue(&client, name).await,
+                }
+            }
+        }
+    }
+}
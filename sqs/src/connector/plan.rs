@@ -0,0 +1,87 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::{resource::Queue, util::changed_attributes};
+
+use super::{SqsConnector, SqsConnectorOp, SqsResourceAddress};
+
+/// FIFO queues have to be named `*.fifo`, and standard queues can't be.
+fn check_queue_name(queue_name: &str, queue: &Queue) -> anyhow::Result<()> {
+    if queue.fifo_queue != queue_name.ends_with(".fifo") {
+        if queue.fifo_queue {
+            bail!("FIFO queue {} must have a name ending in .fifo", queue_name);
+        } else {
+            bail!("Queue {} has a name ending in .fifo, but fifo_queue is false", queue_name);
+        }
+    }
+    Ok(())
+}
+
+impl SqsConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = SqsResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            SqsResourceAddress::Queue { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_queue)) => {
+                    let new_queue: Queue = RON.from_str(&new_queue)?;
+                    check_queue_name(name, &new_queue)?;
+                    Ok(vec![connector_op!(
+                        SqsConnectorOp::CreateQueue(new_queue),
+                        format!("Create new SQS queue {} in region {}", name, region)
+                    )])
+                }
+                (Some(_old_queue), None) => Ok(vec![connector_op!(
+                    SqsConnectorOp::DeleteQueue,
+                    format!("DELETE SQS queue {} in region {}", name, region)
+                )]),
+                (Some(old_queue), Some(new_queue)) => {
+                    let old_queue: Queue = RON.from_str(&old_queue)?;
+                    let new_queue: Queue = RON.from_str(&new_queue)?;
+                    check_queue_name(name, &new_queue)?;
+                    let mut ops = Vec::new();
+
+                    if old_queue.tags != new_queue.tags {
+                        let diff = diff_ron_values(&old_queue.tags, &new_queue.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            SqsConnectorOp::UpdateQueueTags(old_queue.tags.clone(), new_queue.tags.clone()),
+                            format!("Modify tags for SQS queue `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    let attributes = changed_attributes(&old_queue, &new_queue)?;
+                    if !attributes.is_empty() {
+                        let old_attributes = Queue {
+                            tags: new_queue.tags.clone(),
+                            ..old_queue
+                        };
+                        let diff = diff_ron_values(&old_attributes, &new_queue).unwrap_or_default();
+                        ops.push(connector_op!(
+                            SqsConnectorOp::SetQueueAttributes(attributes),
+                            format!("Modify attributes for SQS queue `{}`\n{}", name, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{resource::Queue, tags::Tags};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum SqsConnectorOp {
+    CreateQueue(Queue),
+    /// Sets the given attributes, keyed by SQS attribute name (e.g. "VisibilityTimeout").
+    /// An empty value clears the attribute.
+    SetQueueAttributes(HashMap<String, String>),
+    UpdateQueueTags(Tags, Tags),
+    DeleteQueue,
+}
+
+impl ConnectorOp for SqsConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
@@ -0,0 +1,150 @@
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::Context;
+use autoschematic_core::connector::OpExecResponse;
+use aws_sdk_sqs::types::QueueAttributeName;
+
+use crate::{
+    resource::Queue,
+    tags::{Tags, tag_diff},
+    util::queue_attributes,
+};
+
+/// SQS won't create a queue with the name of one deleted in the last 60 seconds.
+const QUEUE_DELETED_RECENTLY_RETRIES: usize = 8;
+const QUEUE_DELETED_RECENTLY_DELAY: Duration = Duration::from_secs(10);
+
+fn to_sdk_attributes(attributes: HashMap<String, String>) -> HashMap<QueueAttributeName, String> {
+    attributes
+        .into_iter()
+        .map(|(name, value)| (QueueAttributeName::from(name.as_str()), value))
+        .collect()
+}
+
+pub async fn get_queue_url(client: &aws_sdk_sqs::Client, queue_name: &str) -> anyhow::Result<String> {
+    let resp = client.get_queue_url().queue_name(queue_name).send().await?;
+    resp.queue_url.with_context(|| format!("No URL returned for queue {}", queue_name))
+}
+
+/// Creates a queue using the provided configuration
+pub async fn create_queue(
+    client: &aws_sdk_sqs::Client,
+    queue_name: &str,
+    queue: &Queue,
+) -> Result<OpExecResponse, anyhow::Error> {
+    // Empty values only mean something when clearing an attribute on an existing queue
+    let attributes: HashMap<String, String> = queue_attributes(queue)?
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .collect();
+
+    let mut attempt = 0;
+    let create_resp = loop {
+        let result = client
+            .create_queue()
+            .queue_name(queue_name)
+            .set_attributes(Some(to_sdk_attributes(attributes.clone())))
+            .set_tags(queue.tags.clone().into())
+            .send()
+            .await;
+
+        match result {
+            Ok(resp) => break resp,
+            Err(e)
+                if attempt < QUEUE_DELETED_RECENTLY_RETRIES
+                    && e.as_service_error().is_some_and(|e| e.is_queue_deleted_recently()) =>
+            {
+                attempt += 1;
+                tokio::time::sleep(QUEUE_DELETED_RECENTLY_DELAY).await;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    };
+
+    let queue_url = create_resp.queue_url.context("No URL returned for new queue")?;
+
+    let attributes_resp = client
+        .get_queue_attributes()
+        .queue_url(&queue_url)
+        .attribute_names(QueueAttributeName::QueueArn)
+        .send()
+        .await?;
+
+    let queue_arn = attributes_resp
+        .attributes
+        .and_then(|mut a| a.remove(&QueueAttributeName::QueueArn));
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("queue_url"), Some(queue_url)),
+            (String::from("queue_arn"), queue_arn),
+        ])),
+        friendly_message: Some(format!("Created SQS queue {}", queue_name)),
+    })
+}
+
+/// Sets queue attributes
+pub async fn set_queue_attributes(
+    client: &aws_sdk_sqs::Client,
+    queue_name: &str,
+    attributes: HashMap<String, String>,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let queue_url = get_queue_url(client, queue_name).await?;
+
+    client
+        .set_queue_attributes()
+        .queue_url(&queue_url)
+        .set_attributes(Some(to_sdk_attributes(attributes)))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated attributes for SQS queue {}", queue_name)),
+    })
+}
+
+/// Updates queue tags
+pub async fn update_queue_tags(
+    client: &aws_sdk_sqs::Client,
+    queue_name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let queue_url = get_queue_url(client, queue_name).await?;
+
+    let (untag_keys, new_tags) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_queue()
+            .queue_url(&queue_url)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if !new_tags.is_empty() {
+        client.tag_queue().queue_url(&queue_url).set_tags(Some(new_tags)).send().await?;
+    }
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated tags for SQS queue {}", queue_name)),
+    })
+}
+
+/// Deletes a queue, along with any messages in it
+pub async fn delete_queue(client: &aws_sdk_sqs::Client, queue_name: &str) -> Result<OpExecResponse, anyhow::Error> {
+    let queue_url = get_queue_url(client, queue_name).await?;
+
+    client.delete_queue().queue_url(&queue_url).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([
+            (String::from("queue_url"), None),
+            (String::from("queue_arn"), None),
+        ])),
+        friendly_message: Some(format!("Deleted SQS queue {}", queue_name)),
+    })
+}
@@ -0,0 +1,75 @@
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::SqsResourceAddress, tags::Tags};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RedrivePolicy {
+    /// ARN of the dead-letter queue. It must be the same type (standard or FIFO) as this queue.
+    pub dead_letter_target_arn: String,
+    pub max_receive_count: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub enum QueueEncryption {
+    Disabled,
+    /// SQS owned keys (SSE-SQS). This is what new queues get by default.
+    #[default]
+    SqsManaged,
+    /// A KMS key (SSE-KMS), given as a key ID, ARN or alias such as `alias/aws/sqs`.
+    Kms {
+        kms_key_id: String,
+        /// How long SQS may reuse a data key before calling KMS again. Defaults to 300.
+        data_key_reuse_period_seconds: Option<i32>,
+    },
+}
+
+/// Attributes left as None take the SQS default.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Queue {
+    /// FIFO queues must be named `*.fifo`. This can't be changed once the queue exists.
+    #[serde(default)]
+    pub fifo_queue: bool,
+    /// FIFO queues only.
+    #[serde(default)]
+    pub content_based_deduplication: bool,
+    pub visibility_timeout: Option<i32>,                // seconds, defaults to 30
+    pub message_retention_period: Option<i32>,          // seconds, defaults to 345600 (4 days)
+    pub delay_seconds: Option<i32>,                     // defaults to 0
+    pub receive_message_wait_time_seconds: Option<i32>, // defaults to 0 (short polling)
+    pub redrive_policy: Option<RedrivePolicy>,
+    #[serde(default)]
+    pub encryption: QueueEncryption,
+    pub policy: Option<ron::Value>,
+    pub tags: Tags,
+}
+
+pub enum SqsResource {
+    Queue(Queue),
+}
+
+impl Resource for SqsResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            SqsResource::Queue(queue) => Ok(RON.to_string_pretty(&queue, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = SqsResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            SqsResourceAddress::Queue { .. } => Ok(SqsResource::Queue(RON.from_str(s)?)),
+        }
+    }
+}
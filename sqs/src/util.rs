@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use autoschematic_core::util::RON;
+
+use crate::{
+    resource::{Queue, QueueEncryption, RedrivePolicy},
+    tags::Tags,
+};
+
+const DEFAULT_VISIBILITY_TIMEOUT: i32 = 30;
+const DEFAULT_MESSAGE_RETENTION_PERIOD: i32 = 345_600;
+const DEFAULT_DELAY_SECONDS: i32 = 0;
+const DEFAULT_RECEIVE_MESSAGE_WAIT_TIME_SECONDS: i32 = 0;
+const DEFAULT_KMS_DATA_KEY_REUSE_PERIOD_SECONDS: i32 = 300;
+
+/// The queue's settable attributes, keyed by SQS attribute name, with defaults filled in.
+/// An empty value clears the attribute.
+pub fn queue_attributes(queue: &Queue) -> anyhow::Result<HashMap<String, String>> {
+    let mut attributes = HashMap::new();
+
+    if queue.fifo_queue {
+        attributes.insert(String::from("FifoQueue"), String::from("true"));
+        attributes.insert(
+            String::from("ContentBasedDeduplication"),
+            queue.content_based_deduplication.to_string(),
+        );
+    }
+
+    attributes.insert(
+        String::from("VisibilityTimeout"),
+        queue.visibility_timeout.unwrap_or(DEFAULT_VISIBILITY_TIMEOUT).to_string(),
+    );
+    attributes.insert(
+        String::from("MessageRetentionPeriod"),
+        queue
+            .message_retention_period
+            .unwrap_or(DEFAULT_MESSAGE_RETENTION_PERIOD)
+            .to_string(),
+    );
+    attributes.insert(
+        String::from("DelaySeconds"),
+        queue.delay_seconds.unwrap_or(DEFAULT_DELAY_SECONDS).to_string(),
+    );
+    attributes.insert(
+        String::from("ReceiveMessageWaitTimeSeconds"),
+        queue
+            .receive_message_wait_time_seconds
+            .unwrap_or(DEFAULT_RECEIVE_MESSAGE_WAIT_TIME_SECONDS)
+            .to_string(),
+    );
+
+    let redrive_policy = match &queue.redrive_policy {
+        Some(redrive_policy) => serde_json::json!({
+            "deadLetterTargetArn": redrive_policy.dead_letter_target_arn,
+            "maxReceiveCount": redrive_policy.max_receive_count,
+        })
+        .to_string(),
+        None => String::new(),
+    };
+    attributes.insert(String::from("RedrivePolicy"), redrive_policy);
+
+    match &queue.encryption {
+        QueueEncryption::Disabled => {
+            attributes.insert(String::from("SqsManagedSseEnabled"), String::from("false"));
+            attributes.insert(String::from("KmsMasterKeyId"), String::new());
+        }
+        QueueEncryption::SqsManaged => {
+            attributes.insert(String::from("SqsManagedSseEnabled"), String::from("true"));
+            attributes.insert(String::from("KmsMasterKeyId"), String::new());
+        }
+        QueueEncryption::Kms {
+            kms_key_id,
+            data_key_reuse_period_seconds,
+        } => {
+            attributes.insert(String::from("SqsManagedSseEnabled"), String::from("false"));
+            attributes.insert(String::from("KmsMasterKeyId"), kms_key_id.clone());
+            attributes.insert(
+                String::from("KmsDataKeyReusePeriodSeconds"),
+                data_key_reuse_period_seconds
+                    .unwrap_or(DEFAULT_KMS_DATA_KEY_REUSE_PERIOD_SECONDS)
+                    .to_string(),
+            );
+        }
+    }
+
+    let policy = match &queue.policy {
+        Some(policy) => serde_json::to_string(policy).context("Failed to serialize queue policy as JSON")?,
+        None => String::new(),
+    };
+    attributes.insert(String::from("Policy"), policy);
+
+    Ok(attributes)
+}
+
+/// The attributes that need to be set to get from `old` to `new`.
+pub fn changed_attributes(old: &Queue, new: &Queue) -> anyhow::Result<HashMap<String, String>> {
+    let old_attributes = queue_attributes(old)?;
+
+    let mut changed: HashMap<String, String> = queue_attributes(new)?
+        .into_iter()
+        .filter(|(name, value)| old_attributes.get(name) != Some(value))
+        .collect();
+
+    // Policies are compared as values, since key order in the serialized JSON doesn't matter
+    if old.policy == new.policy {
+        changed.remove("Policy");
+    }
+
+    Ok(changed)
+}
+
+fn parse_attribute(attributes: &HashMap<String, String>, name: &str, default: i32) -> anyhow::Result<Option<i32>> {
+    match attributes.get(name) {
+        Some(value) => {
+            let value: i32 = value
+                .parse()
+                .with_context(|| format!("Invalid value for queue attribute {}: {}", name, value))?;
+            Ok(Some(value).filter(|v| *v != default))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Reads a queue back from its attributes. Attributes at their default value are left as None.
+pub fn queue_from_attributes(attributes: &HashMap<String, String>, tags: Tags) -> anyhow::Result<Queue> {
+    let is_true = |name: &str| attributes.get(name).map(String::as_str) == Some("true");
+
+    let redrive_policy = match attributes.get("RedrivePolicy").filter(|p| !p.is_empty()) {
+        Some(redrive_policy) => {
+            let redrive_policy: serde_json::Value = serde_json::from_str(redrive_policy)?;
+            // maxReceiveCount has been seen as both a number and a string
+            let max_receive_count = match &redrive_policy["maxReceiveCount"] {
+                serde_json::Value::Number(n) => n.as_i64().unwrap_or_default() as i32,
+                serde_json::Value::String(s) => s.parse().unwrap_or_default(),
+                _ => 0,
+            };
+            Some(RedrivePolicy {
+                dead_letter_target_arn: redrive_policy["deadLetterTargetArn"].as_str().unwrap_or_default().to_string(),
+                max_receive_count,
+            })
+        }
+        None => None,
+    };
+
+    let encryption = match attributes.get("KmsMasterKeyId").filter(|k| !k.is_empty()) {
+        Some(kms_key_id) => QueueEncryption::Kms {
+            kms_key_id: kms_key_id.clone(),
+            data_key_reuse_period_seconds: parse_attribute(
+                attributes,
+                "KmsDataKeyReusePeriodSeconds",
+                DEFAULT_KMS_DATA_KEY_REUSE_PERIOD_SECONDS,
+            )?,
+        },
+        None if is_true("SqsManagedSseEnabled") => QueueEncryption::SqsManaged,
+        None => QueueEncryption::Disabled,
+    };
+
+    let policy = match attributes.get("Policy").filter(|p| !p.is_empty()) {
+        Some(policy) => {
+            let policy: serde_json::Value = serde_json::from_str(policy)?;
+            Some(RON.from_str(&RON.to_string(&policy)?)?)
+        }
+        None => None,
+    };
+
+    Ok(Queue {
+        fifo_queue: is_true("FifoQueue"),
+        content_based_deduplication: is_true("ContentBasedDeduplication"),
+        visibility_timeout: parse_attribute(attributes, "VisibilityTimeout", DEFAULT_VISIBILITY_TIMEOUT)?,
+        message_retention_period: parse_attribute(attributes, "MessageRetentionPeriod", DEFAULT_MESSAGE_RETENTION_PERIOD)?,
+        delay_seconds: parse_attribute(attributes, "DelaySeconds", DEFAULT_DELAY_SECONDS)?,
+        receive_message_wait_time_seconds: parse_attribute(
+            attributes,
+            "ReceiveMessageWaitTimeSeconds",
+            DEFAULT_RECEIVE_MESSAGE_WAIT_TIME_SECONDS,
+        )?,
+        redrive_policy,
+        encryption,
+        policy,
+        tags,
+    })
+}
+
+/// Turns `https://sqs.us-east-1.amazonaws.com/123456789012/my-queue` into `my-queue`.
+pub fn queue_name_from_url(queue_url: &str) -> String {
+    queue_url.rsplit('/').next().unwrap_or(queue_url).to_string()
+}
@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
 use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
 
 #[derive(Debug, Clone)]
@@ -96,3 +97,35 @@ impl ResourceAddress for VpcResourceAddress {
         }
     }
 }
+
+impl DescribeAddresses for VpcResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/vpc/<region>/vpcs/<vpc_id>.ron",
+                description: "A VPC",
+                example:     "aws/vpc/us-east-1/vpcs/vpc-0a1b2c3d4e5f67890.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/vpc/<region>/vpcs/<vpc_id>/subnets/<subnet_id>.ron",
+                description: "A subnet in a VPC",
+                example:     "aws/vpc/us-east-1/vpcs/vpc-0a1b2c3d4e5f67890/subnets/subnet-0123456789abcdef0.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/vpc/<region>/internet_gateways/<igw_id>.ron",
+                description: "An internet gateway",
+                example:     "aws/vpc/us-east-1/internet_gateways/igw-0123456789abcdef0.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/vpc/<region>/vpcs/<vpc_id>/route_tables/<rt_id>.ron",
+                description: "A route table in a VPC",
+                example:     "aws/vpc/us-east-1/vpcs/vpc-0a1b2c3d4e5f67890/route_tables/rtb-0123456789abcdef0.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/vpc/<region>/vpcs/<vpc_id>/security_groups/<sg_id>.ron",
+                description: "A security group in a VPC",
+                example:     "aws/vpc/us-east-1/vpcs/vpc-0a1b2c3d4e5f67890/security_groups/sg-0123456789abcdef0.ron",
+            },
+        ]
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use anyhow::bail;
+
+use crate::resource::Subnet;
+
+use crate::connector::VpcConnector;
+
+/// Availability zone names are mapped to physical zones separately for each account, so us-east-1a
+/// in one account can be a different zone from us-east-1a in another. Zone IDs, such as use1-az1,
+/// name the same physical zone everywhere.
+pub struct AzMap {
+    region:     String,
+    name_to_id: HashMap<String, String>,
+}
+
+impl AzMap {
+    pub async fn load(client: &aws_sdk_ec2::Client, region: &str) -> anyhow::Result<Self> {
+        let resp = client.describe_availability_zones().send().await?;
+
+        let name_to_id = resp
+            .availability_zones()
+            .iter()
+            .filter_map(|zone| Some((zone.zone_name.clone()?, zone.zone_id.clone()?)))
+            .collect();
+
+        Ok(AzMap {
+            region: region.to_string(),
+            name_to_id,
+        })
+    }
+
+    fn name_for_id(&self, zone_id: &str) -> Option<&str> {
+        self.name_to_id
+            .iter()
+            .find(|(_, id)| id.as_str() == zone_id)
+            .map(|(name, _)| name.as_str())
+    }
+
+    fn known_zones(&self) -> String {
+        let mut zones: Vec<String> = self.name_to_id.iter().map(|(name, id)| format!("{} ({})", name, id)).collect();
+        zones.sort();
+        zones.join(", ")
+    }
+
+    /// Fills in whichever of availability_zone and availability_zone_id the subnet leaves out,
+    /// as this account maps them, and checks that the two agree if both are given.
+    pub fn resolve(&self, subnet_id: &str, subnet: &mut Subnet) -> anyhow::Result<()> {
+        match (subnet.availability_zone.as_str(), subnet.availability_zone_id.as_deref()) {
+            ("", None) => bail!(
+                "Subnet {} must set availability_zone or availability_zone_id",
+                subnet_id
+            ),
+            ("", Some(zone_id)) => {
+                let Some(name) = self.name_for_id(zone_id) else {
+                    bail!(
+                        "Subnet {} has availability_zone_id {}, which doesn't exist in region {}. Known zones: {}",
+                        subnet_id,
+                        zone_id,
+                        self.region,
+                        self.known_zones()
+                    );
+                };
+                subnet.availability_zone = name.to_string();
+            }
+            (name, None) => {
+                let Some(zone_id) = self.name_to_id.get(name) else {
+                    bail!(
+                        "Subnet {} has availability_zone {}, which doesn't exist in region {}. Known zones: {}",
+                        subnet_id,
+                        name,
+                        self.region,
+                        self.known_zones()
+                    );
+                };
+                subnet.availability_zone_id = Some(zone_id.clone());
+            }
+            (name, Some(zone_id)) => {
+                if self.name_to_id.get(name).map(String::as_str) != Some(zone_id) {
+                    bail!(
+                        "Subnet {} has availability_zone {} and availability_zone_id {}, but in this account {} is {}. \
+                         Set only availability_zone_id to keep the file portable across accounts.",
+                        subnet_id,
+                        name,
+                        zone_id,
+                        zone_id,
+                        self.name_for_id(zone_id).unwrap_or("not in this region")
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether two subnets are in the same zone, going by their zone IDs if both have one.
+/// A subnet that only gives a name and one that only gives an ID can't be compared without
+/// the account's mapping, and are treated as different.
+pub fn same_availability_zone(a: &Subnet, b: &Subnet) -> bool {
+    match (&a.availability_zone_id, &b.availability_zone_id) {
+        (Some(a_id), Some(b_id)) => a_id == b_id,
+        _ => !a.availability_zone.is_empty() && a.availability_zone == b.availability_zone,
+    }
+}
+
+impl VpcConnector {
+    pub async fn resolve_availability_zone(&self, region: &str, subnet_id: &str, subnet: &mut Subnet) -> anyhow::Result<()> {
+        let client = self.get_or_init_client(region).await?;
+        AzMap::load(&client, region).await?.resolve(subnet_id, subnet)
+    }
+}
@@ -0,0 +1,232 @@
+use std::{
+    fmt,
+    net::Ipv4Addr,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, bail};
+use autoschematic_core::{connector::ResourceAddress, util::RON};
+
+use crate::{
+    addr::VpcResourceAddress,
+    resource::{Subnet, Vpc},
+};
+
+/// An IPv4 CIDR block, e.g. 10.0.0.0/16.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ipv4Cidr {
+    network:    u32,
+    prefix_len: u32,
+}
+
+impl Ipv4Cidr {
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        let Some((addr, prefix_len)) = s.split_once('/') else {
+            bail!("Invalid CIDR block `{}`: expected an address and prefix length, e.g. 10.0.0.0/16", s);
+        };
+
+        let addr: Ipv4Addr = addr.parse().with_context(|| format!("Invalid CIDR block `{}`", s))?;
+        let prefix_len: u32 = prefix_len.parse().with_context(|| format!("Invalid CIDR block `{}`", s))?;
+        if prefix_len > 32 {
+            bail!("Invalid CIDR block `{}`: prefix length must be at most 32", s);
+        }
+
+        Ok(Ipv4Cidr {
+            network: u32::from(addr) & Self::mask(prefix_len),
+            prefix_len,
+        })
+    }
+
+    fn mask(prefix_len: u32) -> u32 {
+        u32::MAX.checked_shl(32 - prefix_len).unwrap_or(0)
+    }
+
+    /// Whether every address in `other` is also in `self`.
+    pub fn contains(&self, other: &Ipv4Cidr) -> bool {
+        self.prefix_len <= other.prefix_len && other.network & Self::mask(self.prefix_len) == self.network
+    }
+
+    pub fn overlaps(&self, other: &Ipv4Cidr) -> bool {
+        self.contains(other) || other.contains(self)
+    }
+}
+
+impl fmt::Display for Ipv4Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", Ipv4Addr::from(self.network), self.prefix_len)
+    }
+}
+
+/// A CIDR block that a planned VPC or subnet was checked against, and where it came from:
+/// a resource file in the repo, or a peered VPC discovered live.
+pub struct KnownCidr {
+    pub source:     String,
+    pub cidr_block: String,
+}
+
+/// Returns the entries of `known` whose CIDR block overlaps `cidr_block`.
+/// Entries with a CIDR block that doesn't parse are skipped, since their own plan will reject them.
+pub fn find_overlaps<'a>(cidr_block: &Ipv4Cidr, known: &'a [KnownCidr]) -> Vec<&'a KnownCidr> {
+    known
+        .iter()
+        .filter(|k| Ipv4Cidr::parse(&k.cidr_block).is_ok_and(|c| c.overlaps(cidr_block)))
+        .collect()
+}
+
+pub fn overlaps_message(kind: &str, id: &str, cidr_block: &Ipv4Cidr, overlaps: &[&KnownCidr]) -> String {
+    let mut message = format!("{} `{}` has CIDR block {}, which overlaps with:", kind, id, cidr_block);
+    for overlap in overlaps {
+        message.push_str(&format!("\n  - {} ({})", overlap.source, overlap.cidr_block));
+    }
+    message
+}
+
+/// Lists the `.ron` files directly under `dir`, or nothing if it doesn't exist.
+fn ron_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if !dir.is_dir() {
+        return Ok(files);
+    }
+
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "ron") {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Every VPC defined in the repo, in any region, along with its address.
+/// Files that don't parse are skipped, since their own plan will reject them.
+pub fn repo_vpcs(prefix: &Path) -> anyhow::Result<Vec<(VpcResourceAddress, Vpc)>> {
+    let mut vpcs = Vec::new();
+
+    let vpc_root = prefix.join("aws/vpc");
+    if !vpc_root.is_dir() {
+        return Ok(vpcs);
+    }
+
+    for region_entry in std::fs::read_dir(&vpc_root)? {
+        let region_dir = region_entry?.path();
+        if !region_dir.is_dir() {
+            continue;
+        }
+
+        for path in ron_files(&region_dir.join("vpcs"))? {
+            let Ok(rel_path) = path.strip_prefix(prefix) else {
+                continue;
+            };
+            let Ok(addr @ VpcResourceAddress::Vpc { .. }) = VpcResourceAddress::from_path(rel_path) else {
+                continue;
+            };
+            let Ok(vpc) = RON.from_str::<Vpc>(&std::fs::read_to_string(&path)?) else {
+                continue;
+            };
+            vpcs.push((addr, vpc));
+        }
+    }
+
+    vpcs.sort_by_key(|(addr, _)| addr.to_path_buf());
+    Ok(vpcs)
+}
+
+/// Every subnet defined in the repo under the VPC `vpc_id` in `region`, along with its address.
+pub fn repo_subnets(prefix: &Path, region: &str, vpc_id: &str) -> anyhow::Result<Vec<(VpcResourceAddress, Subnet)>> {
+    let mut subnets = Vec::new();
+
+    let subnet_dir = prefix.join(format!("aws/vpc/{}/vpcs/{}/subnets", region, vpc_id));
+    for path in ron_files(&subnet_dir)? {
+        let Ok(rel_path) = path.strip_prefix(prefix) else {
+            continue;
+        };
+        let Ok(addr @ VpcResourceAddress::Subnet { .. }) = VpcResourceAddress::from_path(rel_path) else {
+            continue;
+        };
+        let Ok(subnet) = RON.from_str::<Subnet>(&std::fs::read_to_string(&path)?) else {
+            continue;
+        };
+        subnets.push((addr, subnet));
+    }
+
+    Ok(subnets)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Ipv4Cidr, KnownCidr, find_overlaps};
+
+    fn cidr(s: &str) -> Ipv4Cidr {
+        Ipv4Cidr::parse(s).unwrap()
+    }
+
+    #[test]
+    fn overlapping_ranges() {
+        let cases = [
+            // Adjacent ranges share no addresses
+            ("10.0.0.0/24", "10.0.1.0/24", false),
+            ("10.0.0.0/25", "10.0.0.128/25", false),
+            ("10.0.0.0/16", "10.1.0.0/16", false),
+            // Nested ranges overlap, whichever way round
+            ("10.0.0.0/16", "10.0.4.0/24", true),
+            ("10.0.4.0/24", "10.0.0.0/16", true),
+            ("0.0.0.0/0", "192.168.1.1/32", true),
+            // Identical ranges, including ones written with host bits set
+            ("10.0.0.0/16", "10.0.0.0/16", true),
+            ("10.0.3.7/16", "10.0.0.0/16", true),
+            ("172.16.0.1/32", "172.16.0.1/32", true),
+            ("172.16.0.1/32", "172.16.0.2/32", false),
+        ];
+
+        for (a, b, overlaps) in cases {
+            assert_eq!(cidr(a).overlaps(&cidr(b)), overlaps, "{} and {}", a, b);
+        }
+    }
+
+    #[test]
+    fn nested_ranges_contain_one_way() {
+        assert!(cidr("10.0.0.0/16").contains(&cidr("10.0.4.0/24")));
+        assert!(!cidr("10.0.4.0/24").contains(&cidr("10.0.0.0/16")));
+        assert!(cidr("10.0.0.0/16").contains(&cidr("10.0.0.0/16")));
+    }
+
+    #[test]
+    fn network_is_normalized() {
+        assert_eq!(cidr("10.0.3.7/16").to_string(), "10.0.0.0/16");
+        assert_eq!(cidr("10.0.3.7/32").to_string(), "10.0.3.7/32");
+        assert_eq!(cidr("10.0.3.7/0").to_string(), "0.0.0.0/0");
+    }
+
+    #[test]
+    fn invalid_ranges() {
+        for s in ["10.0.0.0", "10.0.0.0/33", "10.0.0/16", "10.0.0.0/x", "2001:db8::/32", "::/0"] {
+            assert!(Ipv4Cidr::parse(s).is_err(), "{} should be invalid", s);
+        }
+    }
+
+    #[test]
+    fn ipv6_ranges_are_not_checked() {
+        let known = [
+            KnownCidr {
+                source:     String::from("ipv6"),
+                cidr_block: String::from("2001:db8::/32"),
+            },
+            KnownCidr {
+                source:     String::from("nested"),
+                cidr_block: String::from("10.0.8.0/21"),
+            },
+            KnownCidr {
+                source:     String::from("adjacent"),
+                cidr_block: String::from("10.1.0.0/16"),
+            },
+        ];
+
+        let overlaps: Vec<&str> = find_overlaps(&cidr("10.0.0.0/16"), &known)
+            .into_iter()
+            .map(|k| k.source.as_str())
+            .collect();
+        assert_eq!(overlaps, vec!["nested"]);
+    }
+}
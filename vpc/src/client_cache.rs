@@ -1,6 +1,7 @@
 use std::{sync::Arc, time::Duration};
 
 use anyhow::bail;
+use autoschematic_connector_aws_core::audited_client;
 use aws_config::{meta::region::RegionProviderChain, timeout::TimeoutConfig, BehaviorVersion};
 
 use super::VpcConnector;
@@ -26,7 +27,7 @@ impl VpcConnector {
                 )
                 .load()
                 .await;
-            let client = aws_sdk_ec2::Client::new(&config);
+            let client = audited_client!(aws_sdk_ec2, &config);
             cache.insert(region_s.to_string(), Arc::new(client));
         };
 
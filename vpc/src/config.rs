@@ -8,6 +8,21 @@ use autoschematic_connector_aws_core::{
 use autoschematic_core::util::RON;
 use serde::{Deserialize, Serialize};
 
+/// Plan-time checks that new or changed VPC and subnet CIDR blocks don't overlap with the
+/// other VPCs in this repo. Overlapping VPCs can't be peered or routed to each other through
+/// a transit gateway, which tends to be found out long after they're created.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct CidrOverlapCheck {
+    /// Also check against the VPCs on either side of active peering connections in the enabled regions,
+    /// which may belong to other accounts.
+    #[serde(default)]
+    pub include_peered_vpcs: bool,
+    /// Report overlaps in the plan instead of failing it.
+    #[serde(default)]
+    pub warn_only: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct VpcConnectorConfig {
     pub account_id:      Option<String>,
@@ -15,6 +30,10 @@ pub struct VpcConnectorConfig {
     pub timeout_config:  Option<TimeoutConfig>,
     pub sts_region:      String,
     pub enabled_regions: Vec<String>,
+    #[serde(default)]
+    pub max_concurrent_ops: Option<usize>,
+    #[serde(default)]
+    pub cidr_overlap_check: CidrOverlapCheck,
 }
 
-impl_aws_config!(VpcConnectorConfig, "aws/vpc/config.ron");
+impl_aws_config!(VpcConnectorConfig, "aws/vpc/config.ron", cidr_overlap_check);
@@ -6,11 +6,19 @@ use std::{
 
 use crate::{
     addr::VpcResourceAddress,
+    az::same_availability_zone,
     resource::{InternetGateway, Route, RouteTable, SecurityGroup, SecurityGroupRule, Subnet, Vpc, VpcResource},
     tags::Tags,
+    util::canonical_rules,
 };
 use async_trait::async_trait;
-use autoschematic_connector_aws_core::config::AwsServiceConfig;
+use autoschematic_connector_aws_core::{
+    audit::AuditLog,
+    audited_client,
+    cascade::{ConsumedOutput, forget_replacements, note_stale_references, resolve_stale_references},
+    concurrency::OpExecLimiter,
+    config::AwsServiceConfig,
+};
 use autoschematic_core::{
     connector::{
         Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource, ResourceAddress,
@@ -19,7 +27,7 @@ use autoschematic_core::{
     diag::DiagnosticResponse,
     template::ReadOutput,
     skeleton,
-    util::{optional_string_from_utf8, ron_check_eq, ron_check_syntax},
+    util::{RON, optional_string_from_utf8, ron_check_eq, ron_check_syntax},
 };
 
 use tokio::sync::{Mutex, RwLock};
@@ -30,10 +38,29 @@ pub mod get;
 pub mod list;
 pub mod op_exec;
 pub mod plan;
+#[cfg(test)]
+mod test;
+
+/// The outputs of other resources that this connector's resources embed by value.
+/// Security group rules name other groups, and routes name internet gateways.
+pub const CONSUMED_OUTPUTS: &[ConsumedOutput] = &[
+    ConsumedOutput {
+        producer: "aws/vpc/",
+        key:      "security_group_id",
+        fields:   &["security_group_ids"],
+    },
+    ConsumedOutput {
+        producer: "aws/vpc/",
+        key:      "internet_gateway_id",
+        fields:   &["gateway_id"],
+    },
+];
 
 #[derive(Default)]
 pub struct VpcConnector {
     pub client_cache: Mutex<HashMap<String, Arc<aws_sdk_ec2::Client>>>,
+    pub op_limiter: Mutex<Arc<OpExecLimiter>>,
+    pub audit_log: Mutex<Arc<AuditLog>>,
     pub account_id: Mutex<String>,
     pub config: RwLock<VpcConnectorConfig>,
     pub prefix: PathBuf,
@@ -57,6 +84,9 @@ impl Connector for VpcConnector {
         let account_id = vpc_config.verify_sts().await?;
 
         *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(vpc_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        forget_replacements(&self.prefix);
         *self.config.write().await = vpc_config;
         *self.account_id.lock().await = account_id;
 
@@ -95,12 +125,17 @@ impl Connector for VpcConnector {
         current: Option<Vec<u8>>,
         desired: Option<Vec<u8>>,
     ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
-        self.do_plan(addr, optional_string_from_utf8(current)?, optional_string_from_utf8(desired)?)
-            .await
+        let (desired, stale_note) = resolve_stale_references(&self.prefix, CONSUMED_OUTPUTS, desired)?;
+        let ops = self
+            .do_plan(addr, optional_string_from_utf8(current)?, optional_string_from_utf8(desired)?)
+            .await?;
+        Ok(note_stale_references(ops, &stale_note))
     }
 
     async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
-        self.do_op_exec(addr, op).await
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
     }
 
     async fn addr_virt_to_phy(&self, addr: &Path) -> anyhow::Result<VirtToPhyResponse> {
@@ -331,7 +366,8 @@ impl Connector for VpcConnector {
             VpcResource::Subnet(Subnet {
                 cidr_block: String::from("[cidr_block]"),
                 tags: Tags::default(),
-                availability_zone: String::from("[availability_zone]"),
+                availability_zone: String::new(),
+                availability_zone_id: Some(String::from("[availability_zone_id]")),
                 map_public_ip_on_launch: false,
             })
         ));
@@ -342,12 +378,15 @@ impl Connector for VpcConnector {
             VpcResourceAddress::SecurityGroup { region, vpc_id, sg_id },
             VpcResource::SecurityGroup(SecurityGroup {
                 description: String::from("[description]"),
+                group_name: None,
                 ingress_rules: vec![SecurityGroupRule {
                     protocol: String::from("TCP"),
                     from_port: Some(8080),
                     to_port: Some(8080),
                     cidr_blocks: vec![String::from("[cidr_block]")],
                     security_group_ids: vec![String::from("[security_group_id]")],
+                    prefix_list_ids: vec![],
+                    description: Some(String::from("[rule_description]")),
                 }],
                 egress_rules: vec![],
                 tags: Tags::default(),
@@ -365,8 +404,11 @@ impl Connector for VpcConnector {
                     gateway_id: Some(String::from("[gateway_id]")),
                     instance_id: None,
                     nat_gateway_id: None,
+                    transit_gateway_id: None,
+                    vpc_peering_connection_id: None,
                 }],
                 associations: vec![],
+                propagating_vgws: vec![],
                 tags: Tags::default(),
             })
         ));
@@ -388,10 +430,29 @@ impl Connector for VpcConnector {
 
         match addr {
             VpcResourceAddress::Vpc { .. } => ron_check_eq::<Vpc>(a, b),
-            VpcResourceAddress::Subnet { .. } => ron_check_eq::<Subnet>(a, b),
+            VpcResourceAddress::Subnet { .. } => {
+                // A subnet may give its zone by name, by ID or both, as long as they point at the same zone
+                let a: Subnet = RON.from_str(str::from_utf8(a)?)?;
+                let b: Subnet = RON.from_str(str::from_utf8(b)?)?;
+                Ok(a.cidr_block == b.cidr_block
+                    && a.map_public_ip_on_launch == b.map_public_ip_on_launch
+                    && a.tags == b.tags
+                    && same_availability_zone(&a, &b))
+            }
             VpcResourceAddress::InternetGateway { .. } => ron_check_eq::<InternetGateway>(a, b),
             VpcResourceAddress::RouteTable { .. } => ron_check_eq::<RouteTable>(a, b),
-            VpcResourceAddress::SecurityGroup { .. } => ron_check_eq::<SecurityGroup>(a, b),
+            VpcResourceAddress::SecurityGroup { .. } => {
+                // Rules are compared in canonical form, the same way plan compares them
+                let a: SecurityGroup = RON.from_str(str::from_utf8(a)?)?;
+                let b: SecurityGroup = RON.from_str(str::from_utf8(b)?)?;
+                // An unset group_name leaves the name alone
+                let same_group_name = a.group_name.is_none() || b.group_name.is_none() || a.group_name == b.group_name;
+                Ok(a.description == b.description
+                    && same_group_name
+                    && a.tags == b.tags
+                    && canonical_rules(&a.ingress_rules) == canonical_rules(&b.ingress_rules)
+                    && canonical_rules(&a.egress_rules) == canonical_rules(&b.egress_rules))
+            }
         }
     }
 
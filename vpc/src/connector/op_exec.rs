@@ -59,6 +59,7 @@ impl VpcConnector {
                     VpcConnectorOp::UpdateSubnetAttributes { map_public_ip_on_launch } => {
                         op_impl::update_subnet_attributes(&client, &subnet_id, map_public_ip_on_launch).await
                     }
+                    VpcConnectorOp::ReplaceSubnet(subnet) => op_impl::replace_subnet(&client, &vpc_id, &subnet_id, &subnet).await,
                     VpcConnectorOp::DeleteSubnet => op_impl::delete_subnet(&client, &subnet_id).await,
                     _ => Err(invalid_op(&addr, &op)),
                 }
@@ -103,6 +104,12 @@ impl VpcConnector {
                     VpcConnectorOp::DisassociateRouteTable { association_id } => {
                         op_impl::disassociate_route_table(&client, &association_id).await
                     }
+                    VpcConnectorOp::EnableVgwRoutePropagation { gateway_id } => {
+                        op_impl::enable_vgw_route_propagation(&client, &rt_id, &gateway_id).await
+                    }
+                    VpcConnectorOp::DisableVgwRoutePropagation { gateway_id } => {
+                        op_impl::disable_vgw_route_propagation(&client, &rt_id, &gateway_id).await
+                    }
                     VpcConnectorOp::DeleteRouteTable => op_impl::delete_route_table(&client, &rt_id).await,
                     _ => Err(invalid_op(&addr, &op)),
                 }
@@ -131,6 +138,15 @@ impl VpcConnector {
                     VpcConnectorOp::RevokeSecurityGroupEgress(rule) => {
                         op_impl::revoke_security_group_egress(&client, &sg_id, &rule).await
                     }
+                    VpcConnectorOp::UpdateSecurityGroupRuleDescriptionsIngress(rule) => {
+                        op_impl::update_security_group_rule_descriptions_ingress(&client, &sg_id, &rule).await
+                    }
+                    VpcConnectorOp::UpdateSecurityGroupRuleDescriptionsEgress(rule) => {
+                        op_impl::update_security_group_rule_descriptions_egress(&client, &sg_id, &rule).await
+                    }
+                    VpcConnectorOp::ReplaceSecurityGroup(sg) => {
+                        op_impl::replace_security_group(&client, &sg, &vpc_id, &sg_id).await
+                    }
                     VpcConnectorOp::DeleteSecurityGroup => op_impl::delete_security_group(&client, &sg_id).await,
                     _ => Err(invalid_op(&addr, &op)),
                 }
@@ -2,13 +2,20 @@ use crate::addr::VpcResourceAddress;
 
 use super::VpcConnector;
 
-use std::path::Path;
+use std::{collections::BTreeMap, path::Path};
 
 use crate::{
+    cidr::{Ipv4Cidr, KnownCidr, find_overlaps, overlaps_message, repo_subnets, repo_vpcs},
     op::VpcConnectorOp,
-    resource::{InternetGateway, Route, RouteTable, SecurityGroup, SecurityGroupRule, Subnet, Vpc},
+    resource::{InternetGateway, RouteTable, SecurityGroup, Subnet, Vpc},
+    util::{
+        describe_network_interface, diff_rules, get_blackhole_routes, get_network_interfaces, get_phy_route_table_id,
+        get_phy_security_group_id, get_phy_subnet_id, get_phy_vpc_id,
+    },
 };
 use anyhow::bail;
+use aws_sdk_ec2::types::Filter;
+use autoschematic_connector_aws_core::cascade::{find_stale_consumers, stale_consumers_message};
 use autoschematic_core::{
     connector::{ConnectorOp, PlanResponseElement, ResourceAddress},
     connector_op,
@@ -29,9 +36,10 @@ impl VpcConnector {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_vpc)) => {
                         let new_vpc: Vpc = RON.from_str(&new_vpc)?;
+                        let overlap_warning = self.check_vpc_cidr_overlaps(&region, &vpc_id, &new_vpc).await?;
                         Ok(vec![connector_op!(
                             VpcConnectorOp::CreateVpc(new_vpc),
-                            format!("Create new VPC {}", vpc_id)
+                            format!("Create new VPC {}{}", vpc_id, overlap_warning)
                         )])
                     }
                     (Some(_old_vpc), None) => Ok(vec![connector_op!(
@@ -102,10 +110,12 @@ impl VpcConnector {
                 match (current, desired) {
                     (None, None) => Ok(vec![]),
                     (None, Some(new_subnet)) => {
-                        let new_subnet: Subnet = RON.from_str(&new_subnet)?;
+                        let mut new_subnet: Subnet = RON.from_str(&new_subnet)?;
+                        self.resolve_availability_zone(&region, &subnet_id, &mut new_subnet).await?;
+                        let overlap_warning = self.check_subnet_cidr_overlaps(&region, &vpc_id, &subnet_id, &new_subnet).await?;
                         Ok(vec![connector_op!(
                             VpcConnectorOp::CreateSubnet(new_subnet),
-                            format!("Create new Subnet {}", subnet_id)
+                            format!("Create new Subnet {}{}", subnet_id, overlap_warning)
                         )])
                     }
                     (Some(_old_subnet), None) => Ok(vec![connector_op!(
@@ -113,8 +123,47 @@ impl VpcConnector {
                         format!("DELETE Subnet {}", subnet_id)
                     )]),
                     (Some(old_subnet), Some(new_subnet)) => {
-                        let old_subnet: Subnet = RON.from_str(&old_subnet)?;
-                        let new_subnet: Subnet = RON.from_str(&new_subnet)?;
+                        let mut old_subnet: Subnet = RON.from_str(&old_subnet)?;
+                        let mut new_subnet: Subnet = RON.from_str(&new_subnet)?;
+
+                        // Compare zones by ID, so that switching a file between zone names and IDs
+                        // doesn't replace the subnet
+                        self.resolve_availability_zone(&region, &subnet_id, &mut old_subnet).await?;
+                        self.resolve_availability_zone(&region, &subnet_id, &mut new_subnet).await?;
+
+                        let replaced_fields = replacement_fields(&[
+                            ("cidr_block", old_subnet.cidr_block != new_subnet.cidr_block),
+                            (
+                                "availability_zone",
+                                old_subnet.availability_zone_id != new_subnet.availability_zone_id,
+                            ),
+                        ]);
+                        if !replaced_fields.is_empty() {
+                            self.check_subnet_replaceable(&region, &vpc_id, &subnet_id).await?;
+                            let overlap_warning = if old_subnet.cidr_block != new_subnet.cidr_block {
+                                self.check_subnet_cidr_overlaps(&region, &vpc_id, &subnet_id, &new_subnet).await?
+                            } else {
+                                String::new()
+                            };
+                            let stale_consumers = self.stale_consumers(
+                                &VpcResourceAddress::Subnet {
+                                    region:    region.clone(),
+                                    vpc_id:    vpc_id.clone(),
+                                    subnet_id: subnet_id.clone(),
+                                },
+                                "subnet_id",
+                            )?;
+                            return Ok(vec![connector_op!(
+                                VpcConnectorOp::ReplaceSubnet(new_subnet),
+                                format!(
+                                    "{}{}{}",
+                                    replacement_message("Subnet", &subnet_id, &replaced_fields),
+                                    stale_consumers,
+                                    overlap_warning
+                                )
+                            )]);
+                        }
+
                         let mut ops = Vec::new();
 
                         // Check for tag changes
@@ -260,6 +309,46 @@ impl VpcConnector {
                             ));
                         }
 
+                        // Routes whose target has been deleted drop their traffic. They can't be recreated,
+                        // so ones that are still desired have to be removed or pointed at a live target first.
+                        let client = self.get_or_init_client(&region).await?;
+                        let phy_vpc_id = get_phy_vpc_id(&self.prefix, &region, &vpc_id)?.unwrap_or(vpc_id.clone());
+                        let phy_rt_id =
+                            get_phy_route_table_id(&self.prefix, &region, &phy_vpc_id, &rt_id)?.unwrap_or(rt_id.clone());
+                        let blackholes = get_blackhole_routes(&client, &phy_rt_id).await?;
+
+                        let desired_blackholes: Vec<String> = blackholes
+                            .iter()
+                            .filter(|b| new_rt.routes.contains(&b.route))
+                            .map(|b| format!("  {} -> {}", b.destination(), b.target))
+                            .collect();
+                        if !desired_blackholes.is_empty() {
+                            bail!(
+                                "Route Table `{}` has blackhole routes, whose targets no longer exist:\n{}\nRemove these routes from the route table, or point them at an existing target.",
+                                rt_id,
+                                desired_blackholes.join("\n")
+                            );
+                        }
+
+                        // Find routes to delete. These go first, so that a route can be pointed at a new
+                        // target by deleting and recreating it.
+                        for old_route in &old_rt.routes {
+                            let still_exists = new_rt.routes.iter().any(|r| r == old_route);
+
+                            if !still_exists {
+                                let message = match blackholes.iter().find(|b| b.route == *old_route) {
+                                    Some(blackhole) => format!(
+                                        "Delete blackhole route to {} from Route Table `{}` (target {} no longer exists)",
+                                        blackhole.destination(),
+                                        rt_id,
+                                        blackhole.target
+                                    ),
+                                    None => format!("Delete route from Route Table `{}`", rt_id),
+                                };
+                                ops.push(connector_op!(VpcConnectorOp::DeleteRoute(old_route.clone()), message));
+                            }
+                        }
+
                         // Compare routes - find routes to add
                         for new_route in &new_rt.routes {
                             let existing_route = old_rt
@@ -270,26 +359,33 @@ impl VpcConnector {
                             if existing_route.is_none() || existing_route != Some(new_route) {
                                 // Either the route is new or has changed
                                 ops.push(connector_op!(
-                                    VpcConnectorOp::CreateRoute(Route {
-                                        destination_cidr_block: new_route.destination_cidr_block.clone(),
-                                        destination_ipv6_cidr_block: new_route.destination_ipv6_cidr_block.clone(),
-                                        gateway_id: new_route.gateway_id.clone(),
-                                        instance_id: new_route.instance_id.clone(),
-                                        nat_gateway_id: new_route.nat_gateway_id.clone(),
-                                    }),
+                                    VpcConnectorOp::CreateRoute(new_route.clone()),
                                     format!("Create route in Route Table `{}`", rt_id)
                                 ));
                             }
                         }
 
-                        // Find routes to delete
-                        for old_route in &old_rt.routes {
-                            let still_exists = new_rt.routes.iter().any(|r| r == old_route);
+                        for gateway_id in &new_rt.propagating_vgws {
+                            if !old_rt.propagating_vgws.contains(gateway_id) {
+                                ops.push(connector_op!(
+                                    VpcConnectorOp::EnableVgwRoutePropagation {
+                                        gateway_id: gateway_id.clone(),
+                                    },
+                                    format!("Enable route propagation from `{}` to Route Table `{}`", gateway_id, rt_id)
+                                ));
+                            }
+                        }
 
-                            if !still_exists {
+                        for gateway_id in &old_rt.propagating_vgws {
+                            if !new_rt.propagating_vgws.contains(gateway_id) {
                                 ops.push(connector_op!(
-                                    VpcConnectorOp::DeleteRoute(old_route.clone()),
-                                    format!("Delete route from Route Table `{}`", rt_id)
+                                    VpcConnectorOp::DisableVgwRoutePropagation {
+                                        gateway_id: gateway_id.clone(),
+                                    },
+                                    format!(
+                                        "Disable route propagation from `{}` to Route Table `{}`, removing its propagated routes",
+                                        gateway_id, rt_id
+                                    )
                                 ));
                             }
                         }
@@ -342,6 +438,45 @@ impl VpcConnector {
                     (Some(old_sg), Some(new_sg)) => {
                         let old_sg: SecurityGroup = RON.from_str(&old_sg)?;
                         let new_sg: SecurityGroup = RON.from_str(&new_sg)?;
+
+                        // An unset group_name keeps whatever name the group has
+                        let group_name_changed = new_sg.group_name.is_some() && old_sg.group_name != new_sg.group_name;
+                        let replaced_fields = replacement_fields(&[
+                            ("description", old_sg.description != new_sg.description),
+                            ("group_name", group_name_changed),
+                        ]);
+                        if !replaced_fields.is_empty() {
+                            if let Some(group_name) = &new_sg.group_name
+                                && !group_name_changed
+                            {
+                                bail!(
+                                    "Security Group `{}` needs replacing, but its group_name `{}` is pinned and group names \
+                                     must be unique within a VPC. Set a new group_name, or remove it to let the replacement be named automatically.",
+                                    sg_id,
+                                    group_name
+                                );
+                            }
+                            self.check_security_group_replaceable(&region, &vpc_id, &sg_id).await?;
+                            // The replacement group is created with the full desired definition,
+                            // so no further rule or tag ops are needed.
+                            let stale_consumers = self.stale_consumers(
+                                &VpcResourceAddress::SecurityGroup {
+                                    region: region.clone(),
+                                    vpc_id: vpc_id.clone(),
+                                    sg_id:  sg_id.clone(),
+                                },
+                                "security_group_id",
+                            )?;
+                            return Ok(vec![connector_op!(
+                                VpcConnectorOp::ReplaceSecurityGroup(new_sg),
+                                format!(
+                                    "{}{}",
+                                    replacement_message("Security Group", &sg_id, &replaced_fields),
+                                    stale_consumers
+                                )
+                            )]);
+                        }
+
                         let mut ops = Vec::new();
 
                         // Check for tag changes
@@ -353,82 +488,307 @@ impl VpcConnector {
                             ));
                         }
 
-                        // Compare ingress rules - find rules to add
-                        for new_rule in &new_sg.ingress_rules {
-                            let rule_exists = old_sg.ingress_rules.iter().any(|r| r == new_rule);
+                        // Rules are compared source by source, so that rules that allow the same traffic
+                        // don't produce ops just because they're split up or ordered differently.
+                        let ingress_changes = diff_rules(&old_sg.ingress_rules, &new_sg.ingress_rules);
+                        for rule in ingress_changes.authorize {
+                            ops.push(connector_op!(
+                                VpcConnectorOp::AuthorizeSecurityGroupIngress(rule),
+                                format!("Add ingress rule in Security Group `{}`", sg_id)
+                            ));
+                        }
+                        for rule in ingress_changes.revoke {
+                            ops.push(connector_op!(
+                                VpcConnectorOp::RevokeSecurityGroupIngress(rule),
+                                format!("Remove ingress rule from Security Group `{}`", sg_id)
+                            ));
+                        }
+                        for rule in ingress_changes.update_descriptions {
+                            ops.push(connector_op!(
+                                VpcConnectorOp::UpdateSecurityGroupRuleDescriptionsIngress(rule),
+                                format!("Update ingress rule descriptions in Security Group `{}`", sg_id)
+                            ));
+                        }
 
-                            if !rule_exists {
-                                ops.push(connector_op!(
-                                    VpcConnectorOp::AuthorizeSecurityGroupIngress(SecurityGroupRule {
-                                        protocol: new_rule.protocol.clone(),
-                                        from_port: new_rule.from_port,
-                                        to_port: new_rule.to_port,
-                                        cidr_blocks: new_rule.cidr_blocks.clone(),
-                                        security_group_ids: new_rule.security_group_ids.clone(),
-                                    },),
-                                    format!("Add ingress rule in Security Group `{}`", sg_id)
-                                ));
-                            }
+                        let egress_changes = diff_rules(&old_sg.egress_rules, &new_sg.egress_rules);
+                        for rule in egress_changes.authorize {
+                            ops.push(connector_op!(
+                                VpcConnectorOp::AuthorizeSecurityGroupEgress(rule),
+                                format!("Add egress rule in Security Group `{}`", sg_id)
+                            ));
+                        }
+                        for rule in egress_changes.revoke {
+                            ops.push(connector_op!(
+                                VpcConnectorOp::RevokeSecurityGroupEgress(rule),
+                                format!("Remove egress rule from Security Group `{}`", sg_id)
+                            ));
+                        }
+                        for rule in egress_changes.update_descriptions {
+                            ops.push(connector_op!(
+                                VpcConnectorOp::UpdateSecurityGroupRuleDescriptionsEgress(rule),
+                                format!("Update egress rule descriptions in Security Group `{}`", sg_id)
+                            ));
                         }
 
-                        // Find ingress rules to delete
-                        for old_rule in &old_sg.ingress_rules {
-                            let rule_exists = new_sg.ingress_rules.iter().any(|r| r == old_rule);
+                        Ok(ops)
+                    }
+                }
+            }
+        }
+    }
+}
 
-                            if !rule_exists {
-                                ops.push(connector_op!(
-                                    VpcConnectorOp::RevokeSecurityGroupIngress(SecurityGroupRule {
-                                        protocol: old_rule.protocol.clone(),
-                                        from_port: old_rule.from_port,
-                                        to_port: old_rule.to_port,
-                                        cidr_blocks: old_rule.cidr_blocks.clone(),
-                                        security_group_ids: old_rule.security_group_ids.clone(),
-                                    },),
-                                    format!("Remove ingress rule from Security Group `{}`", sg_id)
-                                ));
-                            }
-                        }
+impl VpcConnector {
+    /// Lists the resources, in this or other connectors, that still hold the current value
+    /// of output `key` on `producer` and so will be left stale when it is replaced.
+    fn stale_consumers(&self, producer: &VpcResourceAddress, key: &str) -> anyhow::Result<String> {
+        let Some(old_value) = producer.get_output(&self.prefix, key)? else {
+            return Ok(String::new());
+        };
+
+        let consumers = find_stale_consumers(&self.prefix, &producer.to_path_buf(), &old_value)?;
+        Ok(stale_consumers_message(&old_value, &consumers))
+    }
+}
 
-                        // Compare egress rules - find rules to add
-                        for new_rule in &new_sg.egress_rules {
-                            let rule_exists = old_sg.egress_rules.iter().any(|r| r == new_rule);
+impl VpcConnector {
+    /// Refuses to replace a subnet that still has network interfaces in it, since it can't be deleted
+    /// until they're gone and the replacement would fail halfway.
+    async fn check_subnet_replaceable(&self, region: &str, vpc_id: &str, subnet_id: &str) -> anyhow::Result<()> {
+        let Some(phy_subnet_id) = get_phy_subnet_id(&self.prefix, region, vpc_id, subnet_id)? else {
+            return Ok(());
+        };
+
+        let client = self.get_or_init_client(region).await?;
+        let enis = get_network_interfaces(&client, "subnet-id", &phy_subnet_id).await?;
+        if !enis.is_empty() {
+            bail!(
+                "Subnet `{}` can't be replaced while it still has network interfaces, delete or move them first:\n{}",
+                subnet_id,
+                enis.iter().map(describe_network_interface).collect::<Vec<_>>().join("\n")
+            );
+        }
+        Ok(())
+    }
 
-                            if !rule_exists {
-                                ops.push(connector_op!(
-                                    VpcConnectorOp::AuthorizeSecurityGroupEgress(SecurityGroupRule {
-                                        protocol: new_rule.protocol.clone(),
-                                        from_port: new_rule.from_port,
-                                        to_port: new_rule.to_port,
-                                        cidr_blocks: new_rule.cidr_blocks.clone(),
-                                        security_group_ids: new_rule.security_group_ids.clone(),
-                                    },),
-                                    format!("Add egress rule in Security Group `{}`", sg_id)
-                                ));
-                            }
-                        }
+    /// Refuses to replace a security group used by requester-managed network interfaces,
+    /// such as those of load balancers, NAT gateways or VPC endpoints. Their groups can't be
+    /// changed directly, so they couldn't be moved to the replacement group.
+    async fn check_security_group_replaceable(&self, region: &str, vpc_id: &str, sg_id: &str) -> anyhow::Result<()> {
+        let Some(phy_sg_id) = get_phy_security_group_id(&self.prefix, region, vpc_id, sg_id)? else {
+            return Ok(());
+        };
+
+        let client = self.get_or_init_client(region).await?;
+        let requester_managed: Vec<String> = get_network_interfaces(&client, "group-id", &phy_sg_id)
+            .await?
+            .iter()
+            .filter(|eni| eni.requester_managed == Some(true))
+            .map(describe_network_interface)
+            .collect();
+        if !requester_managed.is_empty() {
+            bail!(
+                "Security Group `{}` can't be replaced while it's used by requester-managed network interfaces, \
+                 move their owning resources to another group first:\n{}",
+                sg_id,
+                requester_managed.join("\n")
+            );
+        }
+        Ok(())
+    }
+}
 
-                        // Find egress rules to delete
-                        for old_rule in &old_sg.egress_rules {
-                            let rule_exists = new_sg.egress_rules.iter().any(|r| r == old_rule);
+impl VpcConnector {
+    /// The CIDR blocks of the VPCs on either side of active peering connections in the enabled regions,
+    /// leaving out the VPCs that are defined in the repo.
+    async fn peered_vpc_cidrs(&self) -> anyhow::Result<Vec<KnownCidr>> {
+        let mut repo_vpc_ids = Vec::new();
+        for (addr, _) in repo_vpcs(&self.prefix)? {
+            if let VpcResourceAddress::Vpc { vpc_id, .. } = &addr {
+                repo_vpc_ids.push(addr.get_output(&self.prefix, "vpc_id")?.unwrap_or(vpc_id.clone()));
+            }
+        }
 
-                            if !rule_exists {
-                                ops.push(connector_op!(
-                                    VpcConnectorOp::RevokeSecurityGroupEgress(SecurityGroupRule {
-                                        protocol: old_rule.protocol.clone(),
-                                        from_port: old_rule.from_port,
-                                        to_port: old_rule.to_port,
-                                        cidr_blocks: old_rule.cidr_blocks.clone(),
-                                        security_group_ids: old_rule.security_group_ids.clone(),
-                                    },),
-                                    format!("Remove egress rule from Security Group `{}`", sg_id)
-                                ));
-                            }
+        // Keyed by VPC ID and CIDR block, since a VPC may be peered with several others
+        let mut peered = BTreeMap::new();
+
+        let enabled_regions = self.config.read().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            let mut pages = client
+                .describe_vpc_peering_connections()
+                .filters(Filter::builder().name("status-code").values("active").build())
+                .into_paginator()
+                .send();
+            while let Some(page) = pages.next().await {
+                for connection in page?.vpc_peering_connections() {
+                    for vpc_info in [&connection.requester_vpc_info, &connection.accepter_vpc_info]
+                        .into_iter()
+                        .flatten()
+                    {
+                        let Some(peer_vpc_id) = &vpc_info.vpc_id else {
+                            continue;
+                        };
+                        if repo_vpc_ids.contains(peer_vpc_id) {
+                            continue;
                         }
 
-                        Ok(ops)
+                        let source = format!(
+                            "peered VPC {} in account {}, region {}",
+                            peer_vpc_id,
+                            vpc_info.owner_id.as_deref().unwrap_or("unknown"),
+                            vpc_info.region.as_deref().unwrap_or(&region)
+                        );
+                        for cidr_block in vpc_info.cidr_block_set().iter().filter_map(|c| c.cidr_block.clone()) {
+                            peered.insert((peer_vpc_id.clone(), cidr_block.clone()), source.clone());
+                        }
+                        if let Some(cidr_block) = &vpc_info.cidr_block {
+                            peered.insert((peer_vpc_id.clone(), cidr_block.clone()), source.clone());
+                        }
                     }
                 }
             }
         }
+
+        Ok(peered
+            .into_iter()
+            .map(|((_, cidr_block), source)| KnownCidr { source, cidr_block })
+            .collect())
+    }
+
+    /// Fails the plan over overlapping CIDR blocks or, if the config only asks for a warning,
+    /// returns one to append to the op's message.
+    async fn report_cidr_overlaps(&self, message: String) -> anyhow::Result<String> {
+        if self.config.read().await.cidr_overlap_check.warn_only {
+            return Ok(format!("\nWARNING: {}", message));
+        }
+
+        bail!(
+            "{}\nOverlapping VPCs can't be peered or routed to each other through a transit gateway. \
+             Choose a different CIDR block, or set cidr_overlap_check.warn_only in aws/vpc/config.ron to allow it.",
+            message
+        )
+    }
+
+    /// Checks a new VPC's CIDR block against every other VPC in the repo, in any region,
+    /// and against peered VPCs if the config asks for it.
+    async fn check_vpc_cidr_overlaps(&self, region: &str, vpc_id: &str, vpc: &Vpc) -> anyhow::Result<String> {
+        let cidr_block = Ipv4Cidr::parse(&vpc.cidr_block)?;
+
+        let this_addr = VpcResourceAddress::Vpc {
+            region: region.to_string(),
+            vpc_id: vpc_id.to_string(),
+        }
+        .to_path_buf();
+
+        let mut known: Vec<KnownCidr> = repo_vpcs(&self.prefix)?
+            .into_iter()
+            .filter(|(addr, _)| addr.to_path_buf() != this_addr)
+            .map(|(addr, other_vpc)| KnownCidr {
+                source:     addr.to_path_buf().display().to_string(),
+                cidr_block: other_vpc.cidr_block,
+            })
+            .collect();
+
+        if self.config.read().await.cidr_overlap_check.include_peered_vpcs {
+            known.extend(self.peered_vpc_cidrs().await?);
+        }
+
+        let overlaps = find_overlaps(&cidr_block, &known);
+        if overlaps.is_empty() {
+            return Ok(String::new());
+        }
+
+        self.report_cidr_overlaps(overlaps_message("VPC", vpc_id, &cidr_block, &overlaps))
+            .await
+    }
+
+    /// Checks a new or moved subnet's CIDR block against its sibling subnets and its VPC in the repo,
+    /// which EC2 would reject anyway. If its VPC isn't defined in the repo, the subnet is checked
+    /// against the other VPCs instead, as the VPC itself would have been.
+    async fn check_subnet_cidr_overlaps(
+        &self,
+        region: &str,
+        vpc_id: &str,
+        subnet_id: &str,
+        subnet: &Subnet,
+    ) -> anyhow::Result<String> {
+        let cidr_block = Ipv4Cidr::parse(&subnet.cidr_block)?;
+
+        let vpc_addr = VpcResourceAddress::Vpc {
+            region: region.to_string(),
+            vpc_id: vpc_id.to_string(),
+        }
+        .to_path_buf();
+        let this_addr = VpcResourceAddress::Subnet {
+            region:    region.to_string(),
+            vpc_id:    vpc_id.to_string(),
+            subnet_id: subnet_id.to_string(),
+        }
+        .to_path_buf();
+
+        let siblings: Vec<KnownCidr> = repo_subnets(&self.prefix, region, vpc_id)?
+            .into_iter()
+            .filter(|(addr, _)| addr.to_path_buf() != this_addr)
+            .map(|(addr, sibling)| KnownCidr {
+                source:     addr.to_path_buf().display().to_string(),
+                cidr_block: sibling.cidr_block,
+            })
+            .collect();
+
+        let overlaps = find_overlaps(&cidr_block, &siblings);
+        if !overlaps.is_empty() {
+            bail!(
+                "{}\nSubnets in the same VPC can't overlap.",
+                overlaps_message("Subnet", subnet_id, &cidr_block, &overlaps)
+            );
+        }
+
+        let repo_vpcs = repo_vpcs(&self.prefix)?;
+        if let Some((_, vpc)) = repo_vpcs.iter().find(|(addr, _)| addr.to_path_buf() == vpc_addr) {
+            if let Ok(vpc_cidr_block) = Ipv4Cidr::parse(&vpc.cidr_block)
+                && !vpc_cidr_block.contains(&cidr_block)
+            {
+                bail!(
+                    "Subnet `{}` has CIDR block {}, which is outside of its VPC `{}`'s CIDR block {}",
+                    subnet_id,
+                    cidr_block,
+                    vpc_id,
+                    vpc_cidr_block
+                );
+            }
+            return Ok(String::new());
+        }
+
+        let known: Vec<KnownCidr> = repo_vpcs
+            .into_iter()
+            .map(|(addr, other_vpc)| KnownCidr {
+                source:     addr.to_path_buf().display().to_string(),
+                cidr_block: other_vpc.cidr_block,
+            })
+            .collect();
+
+        let overlaps = find_overlaps(&cidr_block, &known);
+        if !overlaps.is_empty() {
+            return self
+                .report_cidr_overlaps(overlaps_message("Subnet", subnet_id, &cidr_block, &overlaps))
+                .await;
+        }
+
+        Ok(String::new())
     }
 }
+
+/// Returns the names of the immutable fields that have changed.
+/// Each entry is a field name paired with whether it differs between the current and desired state.
+fn replacement_fields(fields: &[(&'static str, bool)]) -> Vec<&'static str> {
+    fields
+        .iter()
+        .filter_map(|(name, changed)| if *changed { Some(*name) } else { None })
+        .collect()
+}
+
+fn replacement_message(kind: &str, id: &str, fields: &[&str]) -> String {
+    format!("REPLACE {} `{}` (requires replacement: {})", kind, id, fields.join(", "))
+}
@@ -0,0 +1,80 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::replay::{fixture, unrecorded, with_cassette};
+use autoschematic_core::{
+    connector::{ConnectorOp, Resource, ResourceAddress},
+    util::RON,
+};
+
+use crate::{
+    addr::VpcResourceAddress,
+    op::VpcConnectorOp,
+    resource::{InternetGateway, VpcResource},
+};
+
+use super::VpcConnector;
+
+fn cassette(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/cassettes").join(name)
+}
+
+/// A connector whose prefix holds no outputs, so addresses are taken as physical IDs.
+fn connector() -> VpcConnector {
+    VpcConnector {
+        prefix: std::env::temp_dir().join("autoschematic-connector-aws-vpc-test"),
+        ..Default::default()
+    }
+}
+
+/// Records against an internet gateway in us-east-1 attached to one VPC (`vpc_id`), and moves it to
+/// another VPC (`new_vpc_id`) that has none.
+#[tokio::test]
+async fn move_internet_gateway() -> anyhow::Result<()> {
+    let cassette = cassette("move_internet_gateway.json");
+    if unrecorded(&cassette)? {
+        return Ok(());
+    }
+    let igw_id = fixture(&cassette, "igw_id")?;
+    let vpc_id = fixture(&cassette, "vpc_id")?;
+    let new_vpc_id = fixture(&cassette, "new_vpc_id")?;
+
+    with_cassette(&cassette, async {
+        let connector = connector();
+        let addr = VpcResourceAddress::InternetGateway {
+            region: String::from("us-east-1"),
+            igw_id,
+        }
+        .to_path_buf();
+
+        let current = connector.do_get(&addr).await?.expect("internet gateway exists");
+        let igw: InternetGateway = RON.from_str(str::from_utf8(&current.resource_definition)?)?;
+        assert_eq!(igw.vpc_id.as_ref(), Some(&vpc_id));
+
+        let desired = VpcResource::InternetGateway(InternetGateway {
+            vpc_id: Some(new_vpc_id.clone()),
+            ..igw
+        })
+        .to_bytes()?;
+
+        let current = String::from_utf8(current.resource_definition)?;
+        let ops = connector
+            .do_plan(&addr, Some(current), Some(String::from_utf8(desired)?))
+            .await?;
+        assert_eq!(ops.len(), 2);
+        assert!(matches!(
+            VpcConnectorOp::from_str(&ops[0].op_definition)?,
+            VpcConnectorOp::DetachInternetGateway { vpc_id: detached } if detached == vpc_id
+        ));
+        assert!(matches!(
+            VpcConnectorOp::from_str(&ops[1].op_definition)?,
+            VpcConnectorOp::AttachInternetGateway { vpc_id: attached } if attached == new_vpc_id
+        ));
+
+        for op in ops {
+            connector.do_op_exec(&addr, &op.op_definition).await?;
+        }
+
+        anyhow::Ok(())
+    })
+    .await?
+}
@@ -1,3 +1,5 @@
+use addr::VpcResourceAddress;
+use autoschematic_connector_aws_core::addr_doc::{DescribeAddresses, describe_addresses_requested, render_address_docs};
 use autoschematic_core::tarpc_bridge::tarpc_connector_main;
 use connector::VpcConnector;
 
@@ -5,6 +7,8 @@ pub mod connector;
 pub mod client_cache;
 pub mod config;
 pub mod addr;
+pub mod az;
+pub mod cidr;
 pub mod op;
 pub mod op_impl;
 pub mod resource;
@@ -13,6 +17,12 @@ pub mod util;
 
 #[tokio::main]
 pub async fn main() -> anyhow::Result<()> {
+    if describe_addresses_requested() {
+        let patterns = VpcResourceAddress::checked_address_patterns()?;
+        print!("{}", render_address_docs("aws/vpc", &patterns));
+        return Ok(());
+    }
+
     tarpc_connector_main::<VpcConnector>().await?;
     Ok(())
 }
\ No newline at end of file
@@ -25,6 +25,10 @@ pub enum VpcConnectorOp {
     UpdateSubnetAttributes {
         map_public_ip_on_launch: Option<bool>,
     },
+    /// Delete the subnet and recreate it with the given definition.
+    /// Planned when an immutable field (cidr_block, availability zone) changes,
+    /// and only while no network interfaces remain in the subnet.
+    ReplaceSubnet(Subnet),
     DeleteSubnet,
 
     // Internet Gateway operations
@@ -49,6 +53,12 @@ pub enum VpcConnectorOp {
     DisassociateRouteTable {
         association_id: String,
     },
+    EnableVgwRoutePropagation {
+        gateway_id: String,
+    },
+    DisableVgwRoutePropagation {
+        gateway_id: String,
+    },
     DeleteRouteTable,
 
     // Security Group operations
@@ -58,6 +68,14 @@ pub enum VpcConnectorOp {
     AuthorizeSecurityGroupEgress(SecurityGroupRule),
     RevokeSecurityGroupIngress(SecurityGroupRule),
     RevokeSecurityGroupEgress(SecurityGroupRule),
+    /// Set new descriptions on sources that the group already allows.
+    UpdateSecurityGroupRuleDescriptionsIngress(SecurityGroupRule),
+    UpdateSecurityGroupRuleDescriptionsEgress(SecurityGroupRule),
+    /// Create a new security group with the given definition, move all network interfaces
+    /// over to it, then delete the old group.
+    /// Planned when an immutable field (description, group_name) changes, and only while
+    /// no requester-managed network interfaces use the group, since those can't be moved.
+    ReplaceSecurityGroup(SecurityGroup),
     DeleteSecurityGroup,
 }
 
@@ -1,11 +1,16 @@
 use anyhow::{Context, bail};
-use aws_sdk_ec2::types::{AttributeBooleanValue, IpPermission, IpRange, Tag, UserIdGroupPair};
-use std::collections::HashMap;
+use aws_sdk_ec2::{
+    error::ProvideErrorMetadata,
+    types::{AttributeBooleanValue, Tag},
+};
+use std::{collections::HashMap, time::Duration};
 
 use super::{
     resource::{InternetGateway, Route, RouteTable, SecurityGroup, SecurityGroupRule, Subnet, Vpc},
     tags::Tags,
+    util::{get_network_interfaces, to_ip_permission},
 };
+use autoschematic_connector_aws_core::cascade::replaced_output_key;
 use autoschematic_core::{connector::OpExecResponse, op_exec_output};
 
 /// Creates a VPC using the provided configuration
@@ -166,13 +171,15 @@ pub async fn delete_vpc(client: &aws_sdk_ec2::Client, vpc_id: &str) -> Result<Op
 
 /// Creates a subnet
 pub async fn create_subnet(client: &aws_sdk_ec2::Client, vpc_id: &str, subnet: &Subnet) -> Result<OpExecResponse, anyhow::Error> {
-    let create_subnet_resp = client
-        .create_subnet()
-        .vpc_id(vpc_id)
-        .cidr_block(&subnet.cidr_block)
-        .availability_zone(&subnet.availability_zone)
-        .send()
-        .await?;
+    let create_subnet = client.create_subnet().vpc_id(vpc_id).cidr_block(&subnet.cidr_block);
+
+    // The zone ID means the same physical zone in every account, so it wins if both are set
+    let create_subnet = match &subnet.availability_zone_id {
+        Some(availability_zone_id) => create_subnet.availability_zone_id(availability_zone_id),
+        None => create_subnet.availability_zone(&subnet.availability_zone),
+    };
+
+    let create_subnet_resp = create_subnet.send().await?;
 
     let Some(new_subnet) = create_subnet_resp.subnet else {
         bail!("Failed to create subnet: response did not contain subnet details");
@@ -291,6 +298,33 @@ pub async fn delete_subnet(client: &aws_sdk_ec2::Client, subnet_id: &str) -> Res
     })
 }
 
+/// Replaces a subnet by deleting it and recreating it with the new definition.
+/// The old subnet must be deleted first, since the new CIDR block may overlap with it.
+pub async fn replace_subnet(
+    client: &aws_sdk_ec2::Client,
+    vpc_id: &str,
+    old_subnet_id: &str,
+    subnet: &Subnet,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .delete_subnet()
+        .subnet_id(old_subnet_id)
+        .send()
+        .await
+        .with_context(|| format!("Failed to delete subnet {} for replacement", old_subnet_id))?;
+
+    let create_resp = create_subnet(client, vpc_id, subnet).await?;
+
+    // Recorded so that resources still naming the old subnet can be pointed at the new one
+    let mut outputs = create_resp.outputs.unwrap_or_default();
+    outputs.insert(replaced_output_key("subnet_id"), Some(old_subnet_id.to_string()));
+
+    Ok(OpExecResponse {
+        outputs: Some(outputs),
+        friendly_message: Some(format!("Replaced subnet {} in VPC {}", old_subnet_id, vpc_id)),
+    })
+}
+
 /// Creates an internet gateway
 pub async fn create_internet_gateway(
     client: &aws_sdk_ec2::Client,
@@ -489,29 +523,7 @@ pub async fn create_route_table(
 
     // Create routes
     for route in &rt.routes {
-        let mut create_route = client.create_route().route_table_id(&new_rt_id);
-
-        if let Some(destination_cidr_block) = &route.destination_cidr_block {
-            create_route = create_route.destination_cidr_block(destination_cidr_block);
-        }
-
-        if let Some(destination_ipv6_cidr_block) = &route.destination_ipv6_cidr_block {
-            create_route = create_route.destination_ipv6_cidr_block(destination_ipv6_cidr_block);
-        }
-
-        if let Some(gateway_id) = &route.gateway_id {
-            create_route = create_route.gateway_id(gateway_id);
-        }
-
-        if let Some(instance_id) = &route.instance_id {
-            create_route = create_route.instance_id(instance_id);
-        }
-
-        if let Some(nat_gateway_id) = &route.nat_gateway_id {
-            create_route = create_route.nat_gateway_id(nat_gateway_id);
-        }
-
-        create_route.send().await?;
+        send_create_route(client, &new_rt_id, route).await?;
     }
 
     // Associate with subnets
@@ -526,6 +538,16 @@ pub async fn create_route_table(
         }
     }
 
+    for gateway_id in &rt.propagating_vgws {
+        client
+            .enable_vgw_route_propagation()
+            .route_table_id(&new_rt_id)
+            .gateway_id(gateway_id)
+            .send()
+            .await
+            .with_context(|| format!("Failed to enable route propagation from {} to {}", gateway_id, new_rt_id))?;
+    }
+
     let mut outputs = HashMap::new();
     outputs.insert(String::from("route_table_id"), Some(new_rt_id.clone()));
 
@@ -581,31 +603,66 @@ pub async fn update_route_table_tags(
     })
 }
 
-/// Creates a route in a route table
-pub async fn create_route(client: &aws_sdk_ec2::Client, rt_id: &str, route: &Route) -> Result<OpExecResponse, anyhow::Error> {
-    let mut create_route = client.create_route().route_table_id(rt_id);
+/// A freshly created gateway or route table isn't always visible to CreateRoute straight away.
+const ROUTE_TARGET_NOT_FOUND_RETRIES: usize = 6;
+const ROUTE_TARGET_NOT_FOUND_DELAY: Duration = Duration::from_secs(5);
 
-    if let Some(destination_cidr_block) = &route.destination_cidr_block {
-        create_route = create_route.destination_cidr_block(destination_cidr_block);
-    }
+/// Sends a CreateRoute request, retrying while EC2 reports that the route table or gateway doesn't exist yet.
+async fn send_create_route(client: &aws_sdk_ec2::Client, rt_id: &str, route: &Route) -> anyhow::Result<()> {
+    let mut attempt = 0;
+    loop {
+        let mut create_route = client.create_route().route_table_id(rt_id);
 
-    if let Some(destination_ipv6_cidr_block) = &route.destination_ipv6_cidr_block {
-        create_route = create_route.destination_ipv6_cidr_block(destination_ipv6_cidr_block);
-    }
+        if let Some(destination_cidr_block) = &route.destination_cidr_block {
+            create_route = create_route.destination_cidr_block(destination_cidr_block);
+        }
 
-    if let Some(gateway_id) = &route.gateway_id {
-        create_route = create_route.gateway_id(gateway_id);
-    }
+        if let Some(destination_ipv6_cidr_block) = &route.destination_ipv6_cidr_block {
+            create_route = create_route.destination_ipv6_cidr_block(destination_ipv6_cidr_block);
+        }
 
-    if let Some(instance_id) = &route.instance_id {
-        create_route = create_route.instance_id(instance_id);
-    }
+        if let Some(gateway_id) = &route.gateway_id {
+            create_route = create_route.gateway_id(gateway_id);
+        }
 
-    if let Some(nat_gateway_id) = &route.nat_gateway_id {
-        create_route = create_route.nat_gateway_id(nat_gateway_id);
+        if let Some(instance_id) = &route.instance_id {
+            create_route = create_route.instance_id(instance_id);
+        }
+
+        if let Some(nat_gateway_id) = &route.nat_gateway_id {
+            create_route = create_route.nat_gateway_id(nat_gateway_id);
+        }
+
+        if let Some(transit_gateway_id) = &route.transit_gateway_id {
+            create_route = create_route.transit_gateway_id(transit_gateway_id);
+        }
+
+        if let Some(vpc_peering_connection_id) = &route.vpc_peering_connection_id {
+            create_route = create_route.vpc_peering_connection_id(vpc_peering_connection_id);
+        }
+
+        match create_route.send().await {
+            Ok(_) => return Ok(()),
+            Err(e)
+                if attempt < ROUTE_TARGET_NOT_FOUND_RETRIES
+                    && matches!(
+                        e.code(),
+                        Some("InvalidGatewayID.NotFound") | Some("InvalidRouteTableID.NotFound")
+                    ) =>
+            {
+                attempt += 1;
+                tokio::time::sleep(ROUTE_TARGET_NOT_FOUND_DELAY).await;
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to create route in route table {}", rt_id));
+            }
+        }
     }
+}
 
-    create_route.send().await?;
+/// Creates a route in a route table
+pub async fn create_route(client: &aws_sdk_ec2::Client, rt_id: &str, route: &Route) -> Result<OpExecResponse, anyhow::Error> {
+    send_create_route(client, rt_id, route).await?;
 
     Ok(OpExecResponse {
         outputs: None,
@@ -633,6 +690,44 @@ pub async fn delete_route(client: &aws_sdk_ec2::Client, rt_id: &str, route: &Rou
     })
 }
 
+/// Propagates the routes learned by a virtual private gateway into a route table
+pub async fn enable_vgw_route_propagation(
+    client: &aws_sdk_ec2::Client,
+    rt_id: &str,
+    gateway_id: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .enable_vgw_route_propagation()
+        .route_table_id(rt_id)
+        .gateway_id(gateway_id)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Enabled route propagation from {} to route table {}", gateway_id, rt_id)),
+    })
+}
+
+/// Stops propagating a virtual private gateway's routes into a route table, removing the routes it propagated
+pub async fn disable_vgw_route_propagation(
+    client: &aws_sdk_ec2::Client,
+    rt_id: &str,
+    gateway_id: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .disable_vgw_route_propagation()
+        .route_table_id(rt_id)
+        .gateway_id(gateway_id)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Disabled route propagation from {} to route table {}", gateway_id, rt_id)),
+    })
+}
+
 /// Associates a route table with a subnet
 pub async fn associate_route_table(
     client: &aws_sdk_ec2::Client,
@@ -716,7 +811,7 @@ pub async fn create_security_group(
     let create_sg_resp = client
         .create_security_group()
         .vpc_id(vpc_id)
-        .group_name(sg_id)
+        .group_name(sg.group_name.as_deref().unwrap_or(sg_id))
         .description(&sg.description)
         .send()
         .await?;
@@ -725,6 +820,19 @@ pub async fn create_security_group(
         .group_id
         .context("Failed to get security group ID from create response")?;
 
+    configure_security_group(client, sg, &new_sg_id).await?;
+
+    let mut outputs = HashMap::new();
+    outputs.insert(String::from("security_group_id"), Some(new_sg_id.clone()));
+
+    Ok(OpExecResponse {
+        outputs: Some(outputs),
+        friendly_message: Some(format!("Created security group {} in VPC {}", new_sg_id, vpc_id)),
+    })
+}
+
+/// Applies a new group's tags and rules.
+async fn configure_security_group(client: &aws_sdk_ec2::Client, sg: &SecurityGroup, new_sg_id: &str) -> anyhow::Result<()> {
     // Apply tags
     let aws_tags: Option<Vec<Tag>> = sg.tags.clone().into();
     let aws_tags = aws_tags.unwrap_or_default();
@@ -732,7 +840,7 @@ pub async fn create_security_group(
     if !aws_tags.is_empty() {
         client
             .create_tags()
-            .resources(new_sg_id.clone())
+            .resources(new_sg_id)
             .set_tags(Some(aws_tags))
             .send()
             .await?;
@@ -740,37 +848,10 @@ pub async fn create_security_group(
 
     // Add ingress rules
     for rule in &sg.ingress_rules {
-        let mut ip_permissions = IpPermission::builder().ip_protocol(&rule.protocol);
-
-        if let Some(from_port) = rule.from_port {
-            ip_permissions = ip_permissions.from_port(from_port);
-        }
-        if let Some(to_port) = rule.to_port {
-            ip_permissions = ip_permissions.to_port(to_port);
-        }
-
-        // Add CIDR ranges
-        let mut ip_ranges = Vec::new();
-        for cidr in &rule.cidr_blocks {
-            ip_ranges.push(IpRange::builder().cidr_ip(cidr).build());
-        }
-        if !ip_ranges.is_empty() {
-            ip_permissions = ip_permissions.set_ip_ranges(Some(ip_ranges));
-        }
-
-        // Add security group references
-        let mut user_id_group_pairs = Vec::new();
-        for sg_id in &rule.security_group_ids {
-            user_id_group_pairs.push(UserIdGroupPair::builder().group_id(sg_id).build());
-        }
-        if !user_id_group_pairs.is_empty() {
-            ip_permissions = ip_permissions.set_user_id_group_pairs(Some(user_id_group_pairs));
-        }
-
-        let ip_permission = ip_permissions.build();
+        let ip_permission = to_ip_permission(rule);
         client
             .authorize_security_group_ingress()
-            .group_id(&new_sg_id)
+            .group_id(new_sg_id)
             .ip_permissions(ip_permission)
             .send()
             .await?;
@@ -778,49 +859,16 @@ pub async fn create_security_group(
 
     // Add egress rules
     for rule in &sg.egress_rules {
-        let mut ip_permissions = IpPermission::builder().ip_protocol(&rule.protocol);
-
-        if let Some(from_port) = rule.from_port {
-            ip_permissions = ip_permissions.from_port(from_port);
-        }
-        if let Some(to_port) = rule.to_port {
-            ip_permissions = ip_permissions.to_port(to_port);
-        }
-
-        // Add CIDR ranges
-        let mut ip_ranges = Vec::new();
-        for cidr in &rule.cidr_blocks {
-            ip_ranges.push(IpRange::builder().cidr_ip(cidr).build());
-        }
-        if !ip_ranges.is_empty() {
-            ip_permissions = ip_permissions.set_ip_ranges(Some(ip_ranges));
-        }
-
-        // Add security group references
-        let mut user_id_group_pairs = Vec::new();
-        for sg_id in &rule.security_group_ids {
-            user_id_group_pairs.push(UserIdGroupPair::builder().group_id(sg_id).build());
-        }
-        if !user_id_group_pairs.is_empty() {
-            ip_permissions = ip_permissions.set_user_id_group_pairs(Some(user_id_group_pairs));
-        }
-
-        let ip_permission = ip_permissions.build();
+        let ip_permission = to_ip_permission(rule);
         client
             .authorize_security_group_egress()
-            .group_id(&new_sg_id)
+            .group_id(new_sg_id)
             .ip_permissions(ip_permission)
             .send()
             .await?;
     }
 
-    let mut outputs = HashMap::new();
-    outputs.insert(String::from("security_group_id"), Some(new_sg_id.clone()));
-
-    Ok(OpExecResponse {
-        outputs: Some(outputs),
-        friendly_message: Some(format!("Created security group {} in VPC {}", new_sg_id, vpc_id)),
-    })
+    Ok(())
 }
 
 /// Updates security group tags
@@ -875,34 +923,7 @@ pub async fn authorize_security_group_ingress(
     sg_id: &str,
     rule: &SecurityGroupRule,
 ) -> Result<OpExecResponse, anyhow::Error> {
-    let mut ip_permissions = IpPermission::builder().ip_protocol(&rule.protocol);
-
-    if let Some(from_port) = rule.from_port {
-        ip_permissions = ip_permissions.from_port(from_port);
-    }
-    if let Some(to_port) = rule.to_port {
-        ip_permissions = ip_permissions.to_port(to_port);
-    }
-
-    // Add CIDR ranges
-    let mut ip_ranges = Vec::new();
-    for cidr in &rule.cidr_blocks {
-        ip_ranges.push(IpRange::builder().cidr_ip(cidr).build());
-    }
-    if !ip_ranges.is_empty() {
-        ip_permissions = ip_permissions.set_ip_ranges(Some(ip_ranges));
-    }
-
-    // Add security group references
-    let mut user_id_group_pairs = Vec::new();
-    for sg_id in &rule.security_group_ids {
-        user_id_group_pairs.push(UserIdGroupPair::builder().group_id(sg_id).build());
-    }
-    if !user_id_group_pairs.is_empty() {
-        ip_permissions = ip_permissions.set_user_id_group_pairs(Some(user_id_group_pairs));
-    }
-
-    let ip_permission = ip_permissions.build();
+    let ip_permission = to_ip_permission(rule);
     client
         .authorize_security_group_ingress()
         .group_id(sg_id)
@@ -932,34 +953,7 @@ pub async fn authorize_security_group_egress(
     sg_id: &str,
     rule: &SecurityGroupRule,
 ) -> Result<OpExecResponse, anyhow::Error> {
-    let mut ip_permissions = IpPermission::builder().ip_protocol(&rule.protocol);
-
-    if let Some(from_port) = rule.from_port {
-        ip_permissions = ip_permissions.from_port(from_port);
-    }
-    if let Some(to_port) = rule.to_port {
-        ip_permissions = ip_permissions.to_port(to_port);
-    }
-
-    // Add CIDR ranges
-    let mut ip_ranges = Vec::new();
-    for cidr in &rule.cidr_blocks {
-        ip_ranges.push(IpRange::builder().cidr_ip(cidr).build());
-    }
-    if !ip_ranges.is_empty() {
-        ip_permissions = ip_permissions.set_ip_ranges(Some(ip_ranges));
-    }
-
-    // Add security group references
-    let mut user_id_group_pairs = Vec::new();
-    for sg_id in &rule.security_group_ids {
-        user_id_group_pairs.push(UserIdGroupPair::builder().group_id(sg_id).build());
-    }
-    if !user_id_group_pairs.is_empty() {
-        ip_permissions = ip_permissions.set_user_id_group_pairs(Some(user_id_group_pairs));
-    }
-
-    let ip_permission = ip_permissions.build();
+    let ip_permission = to_ip_permission(rule);
     client
         .authorize_security_group_egress()
         .group_id(sg_id)
@@ -989,34 +983,11 @@ pub async fn revoke_security_group_ingress(
     sg_id: &str,
     rule: &SecurityGroupRule,
 ) -> Result<OpExecResponse, anyhow::Error> {
-    let mut ip_permissions = IpPermission::builder().ip_protocol(&rule.protocol);
-
-    if let Some(from_port) = rule.from_port {
-        ip_permissions = ip_permissions.from_port(from_port);
-    }
-    if let Some(to_port) = rule.to_port {
-        ip_permissions = ip_permissions.to_port(to_port);
-    }
-
-    // Add CIDR ranges
-    let mut ip_ranges = Vec::new();
-    for cidr in &rule.cidr_blocks {
-        ip_ranges.push(IpRange::builder().cidr_ip(cidr).build());
-    }
-    if !ip_ranges.is_empty() {
-        ip_permissions = ip_permissions.set_ip_ranges(Some(ip_ranges));
-    }
-
-    // Add security group references
-    let mut user_id_group_pairs = Vec::new();
-    for sg_id in &rule.security_group_ids {
-        user_id_group_pairs.push(UserIdGroupPair::builder().group_id(sg_id).build());
-    }
-    if !user_id_group_pairs.is_empty() {
-        ip_permissions = ip_permissions.set_user_id_group_pairs(Some(user_id_group_pairs));
-    }
-
-    let ip_permission = ip_permissions.build();
+    // Sources are matched without their descriptions
+    let ip_permission = to_ip_permission(&SecurityGroupRule {
+        description: None,
+        ..rule.clone()
+    });
     client
         .revoke_security_group_ingress()
         .group_id(sg_id)
@@ -1046,34 +1017,11 @@ pub async fn revoke_security_group_egress(
     sg_id: &str,
     rule: &SecurityGroupRule,
 ) -> Result<OpExecResponse, anyhow::Error> {
-    let mut ip_permissions = IpPermission::builder().ip_protocol(&rule.protocol);
-
-    if let Some(from_port) = rule.from_port {
-        ip_permissions = ip_permissions.from_port(from_port);
-    }
-    if let Some(to_port) = rule.to_port {
-        ip_permissions = ip_permissions.to_port(to_port);
-    }
-
-    // Add CIDR ranges
-    let mut ip_ranges = Vec::new();
-    for cidr in &rule.cidr_blocks {
-        ip_ranges.push(IpRange::builder().cidr_ip(cidr).build());
-    }
-    if !ip_ranges.is_empty() {
-        ip_permissions = ip_permissions.set_ip_ranges(Some(ip_ranges));
-    }
-
-    // Add security group references
-    let mut user_id_group_pairs = Vec::new();
-    for sg_id in &rule.security_group_ids {
-        user_id_group_pairs.push(UserIdGroupPair::builder().group_id(sg_id).build());
-    }
-    if !user_id_group_pairs.is_empty() {
-        ip_permissions = ip_permissions.set_user_id_group_pairs(Some(user_id_group_pairs));
-    }
-
-    let ip_permission = ip_permissions.build();
+    // Sources are matched without their descriptions
+    let ip_permission = to_ip_permission(&SecurityGroupRule {
+        description: None,
+        ..rule.clone()
+    });
     client
         .revoke_security_group_egress()
         .group_id(sg_id)
@@ -1097,6 +1045,44 @@ pub async fn revoke_security_group_egress(
     })
 }
 
+/// Updates the descriptions of a security group's existing ingress sources
+pub async fn update_security_group_rule_descriptions_ingress(
+    client: &aws_sdk_ec2::Client,
+    sg_id: &str,
+    rule: &SecurityGroupRule,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .update_security_group_rule_descriptions_ingress()
+        .group_id(sg_id)
+        .ip_permissions(to_ip_permission(rule))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated ingress rule descriptions in security group {}", sg_id)),
+    })
+}
+
+/// Updates the descriptions of a security group's existing egress destinations
+pub async fn update_security_group_rule_descriptions_egress(
+    client: &aws_sdk_ec2::Client,
+    sg_id: &str,
+    rule: &SecurityGroupRule,
+) -> Result<OpExecResponse, anyhow::Error> {
+    client
+        .update_security_group_rule_descriptions_egress()
+        .group_id(sg_id)
+        .ip_permissions(to_ip_permission(rule))
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("Updated egress rule descriptions in security group {}", sg_id)),
+    })
+}
+
 /// Deletes a security group
 pub async fn delete_security_group(client: &aws_sdk_ec2::Client, sg_id: &str) -> Result<OpExecResponse, anyhow::Error> {
     client.delete_security_group().group_id(sg_id).send().await?;
@@ -1106,3 +1092,172 @@ pub async fn delete_security_group(client: &aws_sdk_ec2::Client, sg_id: &str) ->
         friendly_message: Some(format!("Deleted security group {}", sg_id)),
     })
 }
+
+/// The name of a group replacing the one named `old_name`. Group names must be unique within a VPC
+/// and the old group still exists while its replacement is created, so replacements alternate
+/// between the name with and without a `-replacement` suffix.
+pub fn replacement_group_name(old_name: &str) -> String {
+    match old_name.strip_suffix("-replacement") {
+        Some(name) => name.to_string(),
+        None => format!("{}-replacement", old_name),
+    }
+}
+
+/// Moves every network interface using `old_sg_id` over to `new_sg_id`.
+/// If any move fails, the interfaces already moved are given their original groups back.
+async fn move_network_interfaces(client: &aws_sdk_ec2::Client, old_sg_id: &str, new_sg_id: &str) -> anyhow::Result<()> {
+    let enis = get_network_interfaces(client, "group-id", old_sg_id).await?;
+
+    let mut moved: Vec<(String, Vec<String>)> = Vec::new();
+    let mut result = Ok(());
+    for eni in enis {
+        let Some(eni_id) = eni.network_interface_id else {
+            continue;
+        };
+
+        let old_groups: Vec<String> = eni.groups.unwrap_or_default().into_iter().filter_map(|g| g.group_id).collect();
+        let new_groups: Vec<String> = old_groups
+            .iter()
+            .map(|g| if g == old_sg_id { new_sg_id.to_string() } else { g.clone() })
+            .collect();
+
+        if let Err(e) = client
+            .modify_network_interface_attribute()
+            .network_interface_id(&eni_id)
+            .set_groups(Some(new_groups))
+            .send()
+            .await
+        {
+            result = Err(anyhow::Error::from(e)
+                .context(format!("Failed to move network interface {} to security group {}", eni_id, new_sg_id)));
+            break;
+        }
+        moved.push((eni_id, old_groups));
+    }
+
+    if result.is_err() {
+        for (eni_id, old_groups) in moved {
+            if let Err(e) = client
+                .modify_network_interface_attribute()
+                .network_interface_id(&eni_id)
+                .set_groups(Some(old_groups))
+                .send()
+                .await
+            {
+                tracing::error!(
+                    "Failed to move network interface {} back to security group {}: {}",
+                    eni_id,
+                    old_sg_id,
+                    e
+                );
+            }
+        }
+    }
+    result
+}
+
+/// Replaces a security group.
+/// The new group is created while the old one still exists, named `sg.group_name` if it's set,
+/// or else after the old group's name (see [`replacement_group_name`]). Every network interface
+/// using the old group is then moved over to the new group before the old group is deleted.
+/// If the new group can't be set up or the interfaces can't be moved, the interfaces are moved
+/// back and the new group is deleted, leaving the old group as it was. If a group already has
+/// the new name, the replacement fails without creating or deleting anything.
+pub async fn replace_security_group(
+    client: &aws_sdk_ec2::Client,
+    sg: &SecurityGroup,
+    vpc_id: &str,
+    old_sg_id: &str,
+) -> Result<OpExecResponse, anyhow::Error> {
+    let old_group = client
+        .describe_security_groups()
+        .group_ids(old_sg_id)
+        .send()
+        .await?
+        .security_groups
+        .unwrap_or_default()
+        .into_iter()
+        .next()
+        .with_context(|| format!("Security group {} not found", old_sg_id))?;
+    let old_group_name = old_group.group_name.unwrap_or_default();
+
+    let new_group_name = match &sg.group_name {
+        Some(group_name) => group_name.clone(),
+        None => replacement_group_name(&old_group_name),
+    };
+    if new_group_name == old_group_name {
+        bail!(
+            "Can't replace security group {}: the new group would have the same name, `{}`",
+            old_sg_id,
+            new_group_name
+        );
+    }
+
+    let new_sg = SecurityGroup {
+        group_name: Some(new_group_name.clone()),
+        ..sg.clone()
+    };
+
+    // Only a group created here is ever cleaned up. If the name is already taken, the group
+    // holding it belongs to something else and is left alone.
+    let create_sg_resp = match client
+        .create_security_group()
+        .vpc_id(vpc_id)
+        .group_name(&new_group_name)
+        .description(&new_sg.description)
+        .send()
+        .await
+    {
+        Ok(resp) => resp,
+        Err(e) if e.code() == Some("InvalidGroup.Duplicate") => bail!(
+            "Can't replace security group {}: a group named `{}` already exists in VPC {}. \
+             Set a different group_name, or delete that group if it's left over.",
+            old_sg_id,
+            new_group_name,
+            vpc_id
+        ),
+        Err(e) => {
+            return Err(anyhow::Error::from(e)
+                .context(format!("Failed to create replacement for security group {}", old_sg_id)));
+        }
+    };
+    let new_sg_id = create_sg_resp
+        .group_id
+        .context("Failed to get security group ID from create response")?;
+
+    if let Err(e) = configure_security_group(client, &new_sg, &new_sg_id).await {
+        if let Err(cleanup_err) = client.delete_security_group().group_id(&new_sg_id).send().await {
+            tracing::error!("Failed to delete partially created security group {}: {}", new_sg_id, cleanup_err);
+        }
+        return Err(e.context(format!("Failed to set up replacement for security group {}", old_sg_id)));
+    }
+
+    // Rewire every network interface from the old group to the new one
+    if let Err(e) = move_network_interfaces(client, old_sg_id, &new_sg_id).await {
+        if let Err(cleanup_err) = client.delete_security_group().group_id(&new_sg_id).send().await {
+            tracing::error!("Failed to delete replacement security group {}: {}", new_sg_id, cleanup_err);
+        }
+        return Err(e);
+    }
+
+    // If the old group is still referenced elsewhere (e.g. by another group's rules),
+    // keep the new group in place and report the leftover rather than failing the op,
+    // so that the new group ID is still recorded.
+    let friendly_message = match client.delete_security_group().group_id(old_sg_id).send().await {
+        Ok(_) => format!("Replaced security group {} with {} in VPC {}", old_sg_id, new_sg_id, vpc_id),
+        Err(e) => format!(
+            "Replaced security group {} with {} in VPC {}, but failed to delete the old group: {}",
+            old_sg_id, new_sg_id, vpc_id, e
+        ),
+    };
+
+    // Recorded so that resources still naming the old group can be pointed at the new one
+    let mut outputs = HashMap::new();
+    outputs.insert(String::from("security_group_id"), Some(new_sg_id.clone()));
+    outputs.insert(replaced_output_key("security_group_id"), Some(old_sg_id.to_string()));
+
+    Ok(OpExecResponse {
+        outputs: Some(outputs),
+        friendly_message: Some(friendly_message),
+    })
+}
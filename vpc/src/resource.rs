@@ -33,7 +33,14 @@ pub enum CidrBlockAssociation {
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Subnet {
     pub cidr_block: String,
+    /// e.g. us-east-1a. Zone names map to different physical zones in each account, so prefer
+    /// availability_zone_id in files shared between accounts, and leave this empty.
+    #[serde(default)]
     pub availability_zone: String,
+    /// e.g. use1-az1, which is the same physical zone in every account. Either field is filled in
+    /// from the other at plan time, and they must agree if both are set.
+    #[serde(default)]
+    pub availability_zone_id: Option<String>,
     pub map_public_ip_on_launch: bool,
     pub tags: Tags,
 }
@@ -48,6 +55,10 @@ pub struct InternetGateway {
 pub struct RouteTable {
     pub routes: Vec<Route>,
     pub associations: Vec<String>,
+    /// Virtual private gateways whose routes are propagated into this table.
+    /// Propagated routes aren't listed in `routes`.
+    #[serde(default)]
+    pub propagating_vgws: Vec<String>,
     pub tags: Tags,
 }
 
@@ -58,16 +69,26 @@ pub struct Route {
     pub gateway_id: Option<String>,
     pub instance_id: Option<String>,
     pub nat_gateway_id: Option<String>,
+    #[serde(default)]
+    pub transit_gateway_id: Option<String>,
+    #[serde(default)]
+    pub vpc_peering_connection_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct SecurityGroup {
     pub description: String,
+    /// The group's name in AWS. Left unset, a new group is named after its address.
+    /// Group names can't be changed, so changing this replaces the group.
+    #[serde(default)]
+    pub group_name: Option<String>,
     pub ingress_rules: Vec<SecurityGroupRule>,
     pub egress_rules: Vec<SecurityGroupRule>,
     pub tags: Tags,
 }
 
+/// Sources (destinations, for egress rules) allowed on a protocol and port range.
+/// AWS keeps a description per source, so imported rules are grouped by description as well.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct SecurityGroupRule {
     pub protocol: String,
@@ -75,6 +96,10 @@ pub struct SecurityGroupRule {
     pub to_port: Option<i32>,
     pub cidr_blocks: Vec<String>,
     pub security_group_ids: Vec<String>,
+    #[serde(default)]
+    pub prefix_list_ids: Vec<String>,
+    #[serde(default)]
+    pub description: Option<String>,
 }
 
 pub enum VpcResource {
@@ -1,7 +1,10 @@
-use std::path::Path;
+use std::{collections::BTreeMap, path::Path};
 
 use autoschematic_core::connector::ResourceAddress;
-use aws_sdk_ec2::types::{AttributeBooleanValue, Filter};
+use aws_sdk_ec2::types::{
+    AttributeBooleanValue, Filter, IpPermission, IpRange, NetworkInterface, PrefixListId, RouteOrigin, RouteState,
+    UserIdGroupPair,
+};
 
 use super::{
     addr::VpcResourceAddress,
@@ -89,6 +92,7 @@ pub async fn get_subnet(client: &aws_sdk_ec2::Client, vpc_id: &str, subnet_id: &
             // Get subnet details
             let cidr_block = subnet.cidr_block.clone().unwrap_or_default();
             let availability_zone = subnet.availability_zone.clone().unwrap_or_default();
+            let availability_zone_id = subnet.availability_zone_id.clone();
             let map_public_ip_on_launch = subnet.map_public_ip_on_launch.unwrap_or(false);
 
             let tags: Tags = subnet.tags.clone().into();
@@ -96,6 +100,7 @@ pub async fn get_subnet(client: &aws_sdk_ec2::Client, vpc_id: &str, subnet_id: &
             let subnet_resource = Subnet {
                 cidr_block,
                 availability_zone,
+                availability_zone_id,
                 map_public_ip_on_launch,
                 tags,
             };
@@ -116,11 +121,15 @@ pub async fn get_igw(client: &aws_sdk_ec2::Client, igw_id: &str) -> anyhow::Resu
 
     if let Some(igws) = igw_resp.internet_gateways {
         if let Some(igw) = igws.first() {
-            // Get VPC ID if attached
+            // Get VPC ID if attached. Internet gateways report an attachment as "available" rather than "attached".
             let mut vpc_id = None;
             if let Some(attachments) = &igw.attachments {
                 for attachment in attachments {
-                    if attachment.state.as_ref().is_some_and(|state| state.as_str() == "attached") {
+                    if attachment
+                        .state
+                        .as_ref()
+                        .is_some_and(|state| matches!(state.as_str(), "attached" | "available"))
+                    {
                         vpc_id = attachment.vpc_id.clone();
                         break;
                     }
@@ -161,11 +170,18 @@ pub async fn get_route_table(client: &aws_sdk_ec2::Client, vpc_id: &str, rt_id:
             let mut routes = Vec::new();
             if let Some(aws_routes) = &rt.routes {
                 for route in aws_routes {
+                    // Propagated routes come and go with the gateway, and are managed through propagating_vgws
+                    if route.origin == Some(RouteOrigin::EnableVgwRoutePropagation) {
+                        continue;
+                    }
+
                     let destination_cidr_block = route.destination_cidr_block.clone();
                     let destination_ipv6_cidr_block = route.destination_ipv6_cidr_block.clone();
                     let gateway_id = route.gateway_id.clone();
                     let instance_id = route.instance_id.clone();
                     let nat_gateway_id = route.nat_gateway_id.clone();
+                    let transit_gateway_id = route.transit_gateway_id.clone();
+                    let vpc_peering_connection_id = route.vpc_peering_connection_id.clone();
 
                     routes.push(Route {
                         destination_cidr_block,
@@ -173,6 +189,8 @@ pub async fn get_route_table(client: &aws_sdk_ec2::Client, vpc_id: &str, rt_id:
                         gateway_id,
                         instance_id,
                         nat_gateway_id,
+                        transit_gateway_id,
+                        vpc_peering_connection_id,
                     });
                 }
             }
@@ -189,12 +207,20 @@ pub async fn get_route_table(client: &aws_sdk_ec2::Client, vpc_id: &str, rt_id:
                 }
             }
 
+            let mut propagating_vgws: Vec<String> = rt
+                .propagating_vgws()
+                .iter()
+                .filter_map(|vgw| vgw.gateway_id.clone())
+                .collect();
+            propagating_vgws.sort();
+
             // Get tags
             let tags: Tags = rt.tags.clone().into();
 
             let rt_resource = RouteTable {
                 routes,
                 associations,
+                propagating_vgws,
                 tags,
             };
             Ok(Some(rt_resource))
@@ -206,6 +232,65 @@ pub async fn get_route_table(client: &aws_sdk_ec2::Client, vpc_id: &str, rt_id:
     }
 }
 
+/// A route whose target no longer exists, so that traffic to its destination is dropped.
+pub struct BlackholeRoute {
+    pub route:  Route,
+    /// The ID of the deleted gateway, instance, NAT gateway, transit gateway or peering connection.
+    pub target: String,
+}
+
+impl BlackholeRoute {
+    pub fn destination(&self) -> &str {
+        self.route
+            .destination_cidr_block
+            .as_deref()
+            .or(self.route.destination_ipv6_cidr_block.as_deref())
+            .unwrap_or("(unknown destination)")
+    }
+}
+
+/// The blackhole routes in a live route table.
+pub async fn get_blackhole_routes(client: &aws_sdk_ec2::Client, rt_id: &str) -> anyhow::Result<Vec<BlackholeRoute>> {
+    let Ok(rt_resp) = client.describe_route_tables().route_table_ids(rt_id).send().await else {
+        return Ok(Vec::new());
+    };
+
+    let mut blackholes = Vec::new();
+
+    for rt in rt_resp.route_tables() {
+        for route in rt.routes() {
+            if route.state != Some(RouteState::Blackhole) || route.origin == Some(RouteOrigin::EnableVgwRoutePropagation) {
+                continue;
+            }
+
+            let target = route
+                .gateway_id
+                .clone()
+                .or(route.instance_id.clone())
+                .or(route.nat_gateway_id.clone())
+                .or(route.transit_gateway_id.clone())
+                .or(route.vpc_peering_connection_id.clone())
+                .or(route.network_interface_id.clone())
+                .unwrap_or_default();
+
+            blackholes.push(BlackholeRoute {
+                route: Route {
+                    destination_cidr_block: route.destination_cidr_block.clone(),
+                    destination_ipv6_cidr_block: route.destination_ipv6_cidr_block.clone(),
+                    gateway_id: route.gateway_id.clone(),
+                    instance_id: route.instance_id.clone(),
+                    nat_gateway_id: route.nat_gateway_id.clone(),
+                    transit_gateway_id: route.transit_gateway_id.clone(),
+                    vpc_peering_connection_id: route.vpc_peering_connection_id.clone(),
+                },
+                target,
+            });
+        }
+    }
+
+    Ok(blackholes)
+}
+
 pub async fn get_security_group(
     client: &aws_sdk_ec2::Client,
     vpc_id: &str,
@@ -227,83 +312,15 @@ pub async fn get_security_group(
             // Get basic info
             let description = sg.description.clone().unwrap_or_default();
 
-            // Get ingress rules
-            let mut ingress_rules = Vec::new();
-            if let Some(ip_permissions) = &sg.ip_permissions {
-                for perm in ip_permissions {
-                    let protocol = perm.ip_protocol.clone().unwrap_or_default();
-                    let from_port = perm.from_port;
-                    let to_port = perm.to_port;
-
-                    let mut cidr_blocks = Vec::new();
-                    if let Some(ip_ranges) = &perm.ip_ranges {
-                        for ip_range in ip_ranges {
-                            if let Some(cidr) = &ip_range.cidr_ip {
-                                cidr_blocks.push(cidr.clone());
-                            }
-                        }
-                    }
-
-                    let mut security_group_ids = Vec::new();
-                    if let Some(sg_references) = &perm.user_id_group_pairs {
-                        for sg_ref in sg_references {
-                            if let Some(sg_id) = &sg_ref.group_id {
-                                security_group_ids.push(sg_id.clone());
-                            }
-                        }
-                    }
-
-                    ingress_rules.push(SecurityGroupRule {
-                        protocol,
-                        from_port,
-                        to_port,
-                        cidr_blocks,
-                        security_group_ids,
-                    });
-                }
-            }
-
-            // Get egress rules
-            let mut egress_rules = Vec::new();
-            if let Some(ip_permissions_egress) = &sg.ip_permissions_egress {
-                for perm in ip_permissions_egress {
-                    let protocol = perm.ip_protocol.clone().unwrap_or_default();
-                    let from_port = perm.from_port;
-                    let to_port = perm.to_port;
-
-                    let mut cidr_blocks = Vec::new();
-                    if let Some(ip_ranges) = &perm.ip_ranges {
-                        for ip_range in ip_ranges {
-                            if let Some(cidr) = &ip_range.cidr_ip {
-                                cidr_blocks.push(cidr.clone());
-                            }
-                        }
-                    }
-
-                    let mut security_group_ids = Vec::new();
-                    if let Some(sg_references) = &perm.user_id_group_pairs {
-                        for sg_ref in sg_references {
-                            if let Some(sg_id) = &sg_ref.group_id {
-                                security_group_ids.push(sg_id.clone());
-                            }
-                        }
-                    }
-
-                    egress_rules.push(SecurityGroupRule {
-                        protocol,
-                        from_port,
-                        to_port,
-                        cidr_blocks,
-                        security_group_ids,
-                    });
-                }
-            }
+            let ingress_rules = rules_from_ip_permissions(sg.ip_permissions());
+            let egress_rules = rules_from_ip_permissions(sg.ip_permissions_egress());
 
             // Get tags
             let tags: Tags = sg.tags.clone().into();
 
             let sg_resource = SecurityGroup {
                 description,
+                group_name: sg.group_name.clone(),
                 ingress_rules,
                 egress_rules,
                 tags,
@@ -317,6 +334,232 @@ pub async fn get_security_group(
     }
 }
 
+/// The network interfaces matching `filter_name`, e.g. the ones in a subnet (`subnet-id`)
+/// or using a security group (`group-id`).
+pub async fn get_network_interfaces(
+    client: &aws_sdk_ec2::Client,
+    filter_name: &str,
+    value: &str,
+) -> anyhow::Result<Vec<NetworkInterface>> {
+    let mut enis = client
+        .describe_network_interfaces()
+        .filters(Filter::builder().name(filter_name).values(value).build())
+        .into_paginator()
+        .items()
+        .send();
+
+    let mut res = Vec::new();
+    while let Some(eni) = enis.next().await {
+        res.push(eni?);
+    }
+    Ok(res)
+}
+
+/// A network interface's ID, followed by its description if it has one.
+pub fn describe_network_interface(eni: &NetworkInterface) -> String {
+    let eni_id = eni.network_interface_id().unwrap_or_default();
+    match eni.description() {
+        Some(description) if !description.is_empty() => format!("{} ({})", eni_id, description),
+        _ => eni_id.to_string(),
+    }
+}
+
+/// A single source (or destination) of a security group rule.
+/// AWS tracks descriptions per source rather than per rule.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum RuleSource {
+    Cidr(String),
+    SecurityGroup(String),
+    PrefixList(String),
+}
+
+/// (protocol, from_port, to_port, source)
+type RuleKey = (String, Option<i32>, Option<i32>, RuleSource);
+
+/// AWS reports protocols by name where it has one, whichever form they were created with.
+fn canonical_protocol(protocol: &str) -> String {
+    match protocol.to_ascii_lowercase().as_str() {
+        "6" => String::from("tcp"),
+        "17" => String::from("udp"),
+        "1" => String::from("icmp"),
+        "58" => String::from("icmpv6"),
+        other => other.to_string(),
+    }
+}
+
+/// Splits rules into their individual sources, each with its description.
+fn flatten_rules(rules: &[SecurityGroupRule]) -> BTreeMap<RuleKey, Option<String>> {
+    let mut entries = BTreeMap::new();
+
+    for rule in rules {
+        let protocol = canonical_protocol(&rule.protocol);
+        let sources = rule
+            .cidr_blocks
+            .iter()
+            .map(|cidr| RuleSource::Cidr(cidr.clone()))
+            .chain(rule.security_group_ids.iter().map(|id| RuleSource::SecurityGroup(id.clone())))
+            .chain(rule.prefix_list_ids.iter().map(|id| RuleSource::PrefixList(id.clone())));
+
+        for source in sources {
+            entries.insert(
+                (protocol.clone(), rule.from_port, rule.to_port, source),
+                rule.description.clone(),
+            );
+        }
+    }
+
+    entries
+}
+
+/// Groups individual sources back into rules, one per (protocol, port range, description), in sorted order.
+fn collect_rules(entries: impl IntoIterator<Item = (RuleKey, Option<String>)>) -> Vec<SecurityGroupRule> {
+    let mut rules: BTreeMap<(String, Option<i32>, Option<i32>, Option<String>), SecurityGroupRule> = BTreeMap::new();
+
+    for ((protocol, from_port, to_port, source), description) in entries {
+        let rule = rules
+            .entry((protocol.clone(), from_port, to_port, description.clone()))
+            .or_insert_with(|| SecurityGroupRule {
+                protocol,
+                from_port,
+                to_port,
+                cidr_blocks: Vec::new(),
+                security_group_ids: Vec::new(),
+                prefix_list_ids: Vec::new(),
+                description,
+            });
+
+        match source {
+            RuleSource::Cidr(cidr) => rule.cidr_blocks.push(cidr),
+            RuleSource::SecurityGroup(id) => rule.security_group_ids.push(id),
+            RuleSource::PrefixList(id) => rule.prefix_list_ids.push(id),
+        }
+    }
+
+    rules.into_values().collect()
+}
+
+/// Puts rules in the canonical form that import produces: sources grouped by protocol, port range
+/// and description, sorted, and deduplicated. Rules that allow the same traffic compare equal in this
+/// form however they're split up or ordered in the file.
+pub fn canonical_rules(rules: &[SecurityGroupRule]) -> Vec<SecurityGroupRule> {
+    collect_rules(flatten_rules(rules))
+}
+
+/// Converts the permissions reported by DescribeSecurityGroups into rules in canonical form,
+/// keeping each source's description and prefix list references.
+pub fn rules_from_ip_permissions(ip_permissions: &[IpPermission]) -> Vec<SecurityGroupRule> {
+    let mut entries = Vec::new();
+
+    for perm in ip_permissions {
+        let protocol = canonical_protocol(perm.ip_protocol().unwrap_or_default());
+        let key = |source| (protocol.clone(), perm.from_port, perm.to_port, source);
+
+        for ip_range in perm.ip_ranges() {
+            if let Some(cidr) = ip_range.cidr_ip() {
+                entries.push((key(RuleSource::Cidr(cidr.to_string())), ip_range.description().map(String::from)));
+            }
+        }
+        for pair in perm.user_id_group_pairs() {
+            if let Some(group_id) = pair.group_id() {
+                entries.push((
+                    key(RuleSource::SecurityGroup(group_id.to_string())),
+                    pair.description().map(String::from),
+                ));
+            }
+        }
+        for prefix_list in perm.prefix_list_ids() {
+            if let Some(prefix_list_id) = prefix_list.prefix_list_id() {
+                entries.push((
+                    key(RuleSource::PrefixList(prefix_list_id.to_string())),
+                    prefix_list.description().map(String::from),
+                ));
+            }
+        }
+    }
+
+    collect_rules(entries)
+}
+
+/// The changes needed to take a security group's rules in one direction from `old` to `new`.
+#[derive(Debug, Default)]
+pub struct RuleChanges {
+    pub authorize: Vec<SecurityGroupRule>,
+    pub revoke: Vec<SecurityGroupRule>,
+    /// Sources allowed in both, whose descriptions changed.
+    pub update_descriptions: Vec<SecurityGroupRule>,
+}
+
+pub fn diff_rules(old: &[SecurityGroupRule], new: &[SecurityGroupRule]) -> RuleChanges {
+    let old = flatten_rules(old);
+    let new = flatten_rules(new);
+
+    let authorize = new
+        .iter()
+        .filter(|(key, _)| !old.contains_key(*key))
+        .map(|(key, description)| (key.clone(), description.clone()));
+    let revoke = old
+        .iter()
+        .filter(|(key, _)| !new.contains_key(*key))
+        .map(|(key, description)| (key.clone(), description.clone()));
+    let update_descriptions = new
+        .iter()
+        .filter(|(key, description)| old.get(*key).is_some_and(|old_description| old_description != *description))
+        .map(|(key, description)| (key.clone(), description.clone()));
+
+    RuleChanges {
+        authorize: collect_rules(authorize),
+        revoke: collect_rules(revoke),
+        update_descriptions: collect_rules(update_descriptions),
+    }
+}
+
+/// Builds the permission for a rule, with the rule's description set on each of its sources.
+pub fn to_ip_permission(rule: &SecurityGroupRule) -> IpPermission {
+    let mut ip_permission = IpPermission::builder()
+        .ip_protocol(&rule.protocol)
+        .set_from_port(rule.from_port)
+        .set_to_port(rule.to_port);
+
+    let ip_ranges: Vec<IpRange> = rule
+        .cidr_blocks
+        .iter()
+        .map(|cidr| IpRange::builder().cidr_ip(cidr).set_description(rule.description.clone()).build())
+        .collect();
+    if !ip_ranges.is_empty() {
+        ip_permission = ip_permission.set_ip_ranges(Some(ip_ranges));
+    }
+
+    let user_id_group_pairs: Vec<UserIdGroupPair> = rule
+        .security_group_ids
+        .iter()
+        .map(|sg_id| {
+            UserIdGroupPair::builder()
+                .group_id(sg_id)
+                .set_description(rule.description.clone())
+                .build()
+        })
+        .collect();
+    if !user_id_group_pairs.is_empty() {
+        ip_permission = ip_permission.set_user_id_group_pairs(Some(user_id_group_pairs));
+    }
+
+    let prefix_list_ids: Vec<PrefixListId> = rule
+        .prefix_list_ids
+        .iter()
+        .map(|prefix_list_id| {
+            PrefixListId::builder()
+                .prefix_list_id(prefix_list_id)
+                .set_description(rule.description.clone())
+                .build()
+        })
+        .collect();
+    if !prefix_list_ids.is_empty() {
+        ip_permission = ip_permission.set_prefix_list_ids(Some(prefix_list_ids));
+    }
+
+    ip_permission.build()
+}
+
 pub fn get_phy_vpc_id(prefix: &Path, region: &str, virt_vpc_id: &str) -> anyhow::Result<Option<String>> {
     let addr = VpcResourceAddress::Vpc {
         region: region.to_string(),
@@ -0,0 +1,71 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_connector_aws_core::addr_doc::{AddressPattern, DescribeAddresses};
+use autoschematic_core::{connector::ResourceAddress, error_util::invalid_addr_path};
+
+#[derive(Debug, Clone)]
+pub enum XrayResourceAddress {
+    SamplingRule { region: String, name: String },
+    Group { region: String, name: String },
+    /// Each region has exactly one encryption configuration.
+    EncryptionConfig { region: String },
+}
+
+impl ResourceAddress for XrayResourceAddress {
+    fn to_path_buf(&self) -> PathBuf {
+        match &self {
+            XrayResourceAddress::SamplingRule { region, name } => {
+                PathBuf::from(format!("aws/xray/{region}/sampling_rules/{name}.ron"))
+            }
+            XrayResourceAddress::Group { region, name } => PathBuf::from(format!("aws/xray/{region}/groups/{name}.ron")),
+            XrayResourceAddress::EncryptionConfig { region } => PathBuf::from(format!("aws/xray/{region}/encryption_config.ron")),
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
+
+        match &path_components[..] {
+            ["aws", "xray", region, "sampling_rules", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(XrayResourceAddress::SamplingRule {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "xray", region, "groups", name] if name.ends_with(".ron") => {
+                let name = name.strip_suffix(".ron").unwrap().to_string();
+                Ok(XrayResourceAddress::Group {
+                    region: region.to_string(),
+                    name,
+                })
+            }
+            ["aws", "xray", region, "encryption_config.ron"] => Ok(XrayResourceAddress::EncryptionConfig {
+                region: region.to_string(),
+            }),
+            _ => Err(invalid_addr_path(path)),
+        }
+    }
+}
+
+impl DescribeAddresses for XrayResourceAddress {
+    fn address_patterns() -> Vec<AddressPattern> {
+        vec![
+            AddressPattern {
+                pattern:     "aws/xray/<region>/sampling_rules/<name>.ron",
+                description: "A sampling rule, which decides which requests are traced",
+                example:     "aws/xray/us-east-1/sampling_rules/checkout-api.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/xray/<region>/groups/<name>.ron",
+                description: "A group of traces selected by a filter expression",
+                example:     "aws/xray/us-east-1/groups/slow-checkouts.ron",
+            },
+            AddressPattern {
+                pattern:     "aws/xray/<region>/encryption_config.ron",
+                description: "Encrypts the region's traces with a KMS key. Without it, X-Ray uses its default encryption",
+                example:     "aws/xray/us-east-1/encryption_config.ron",
+            },
+        ]
+    }
+}
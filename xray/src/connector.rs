@@ -0,0 +1,207 @@
+pub use crate::addr::XrayResourceAddress;
+pub use crate::op::XrayConnectorOp;
+pub use crate::resource::XrayResource;
+
+pub mod get;
+pub mod list;
+pub mod op_exec;
+pub mod plan;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use autoschematic_core::{
+    connector::{
+        Connector, ConnectorOutbox, FilterResponse, GetResourceResponse, OpExecResponse, PlanResponseElement, Resource,
+        ResourceAddress, SkeletonResponse,
+    },
+    diag::DiagnosticResponse,
+    skeleton,
+    util::{ron_check_eq, ron_check_syntax},
+};
+use aws_config::{BehaviorVersion, Region, SdkConfig, meta::region::RegionProviderChain, timeout::TimeoutConfig};
+
+use crate::config::XrayConnectorConfig;
+use tokio::sync::Mutex;
+
+use crate::resource::{EncryptionConfig, Group, SamplingRule};
+use crate::tags::Tags;
+use autoschematic_connector_aws_core::{audit::AuditLog, audited_client, concurrency::OpExecLimiter, config::AwsServiceConfig};
+
+#[derive(Default)]
+pub struct XrayConnector {
+    client_cache: Mutex<HashMap<String, Arc<aws_sdk_xray::Client>>>,
+    op_limiter: Mutex<Arc<OpExecLimiter>>,
+    audit_log: Mutex<Arc<AuditLog>>,
+    account_id: Mutex<String>,
+    config: Mutex<XrayConnectorConfig>,
+    prefix: PathBuf,
+}
+
+async fn load_sdk_config(region_s: &str) -> SdkConfig {
+    let region = RegionProviderChain::first_try(Region::new(region_s.to_owned()));
+
+    aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .timeout_config(
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .operation_timeout(Duration::from_secs(30))
+                .operation_attempt_timeout(Duration::from_secs(30))
+                .read_timeout(Duration::from_secs(30))
+                .build(),
+        )
+        .load()
+        .await
+}
+
+impl XrayConnector {
+    pub async fn get_or_init_client(&self, region_s: &str) -> anyhow::Result<Arc<aws_sdk_xray::Client>> {
+        let mut cache = self.client_cache.lock().await;
+
+        if !cache.contains_key(region_s) {
+            let config = load_sdk_config(region_s).await;
+            let client = audited_client!(aws_sdk_xray, &config);
+            cache.insert(region_s.to_string(), Arc::new(client));
+        };
+
+        let Some(client) = cache.get(region_s) else {
+            bail!("Failed to get client for region {}", region_s);
+        };
+
+        Ok(client.clone())
+    }
+
+}
+
+#[async_trait]
+impl Connector for XrayConnector {
+    async fn filter(&self, addr: &Path) -> Result<FilterResponse, anyhow::Error> {
+        if let Ok(_addr) = XrayResourceAddress::from_path(addr) {
+            Ok(FilterResponse::Resource)
+        } else {
+            Ok(FilterResponse::None)
+        }
+    }
+
+    async fn new(_name: &str, prefix: &Path, _outbox: ConnectorOutbox) -> Result<Arc<dyn Connector>, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Arc::new(XrayConnector {
+            prefix: prefix.into(),
+            ..Default::default()
+        }))
+    }
+
+    async fn init(&self) -> Result<(), anyhow::Error> {
+        let xray_config: XrayConnectorConfig = XrayConnectorConfig::try_load(&self.prefix).await?;
+
+        let account_id = xray_config.verify_sts().await?;
+
+        *self.client_cache.lock().await = HashMap::new();
+        *self.op_limiter.lock().await = Arc::new(OpExecLimiter::from_config(xray_config.max_concurrent_ops));
+        *self.audit_log.lock().await = Arc::new(AuditLog::try_load(&self.prefix)?);
+        *self.config.lock().await = xray_config;
+        *self.account_id.lock().await = account_id;
+        Ok(())
+    }
+
+    async fn list(&self, subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        self.do_list(subpath).await
+    }
+
+    async fn get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        self.do_get(addr).await
+    }
+
+    async fn plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        self.do_plan(addr, current, desired).await
+    }
+
+    async fn op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let op_limiter = self.op_limiter.lock().await.clone();
+        let audit_log = self.audit_log.lock().await.clone();
+        op_limiter.run(addr, audit_log.record(addr, op, self.do_op_exec(addr, op))).await
+    }
+
+    async fn get_skeletons(&self) -> Result<Vec<SkeletonResponse>, anyhow::Error> {
+        let mut res = Vec::new();
+
+        // Traces one request a second and 5% of the rest for a single service
+        res.push(skeleton!(
+            XrayResourceAddress::SamplingRule {
+                region: String::from("[region]"),
+                name:   String::from("[rule_name]"),
+            },
+            XrayResource::SamplingRule(SamplingRule {
+                priority: 1000,
+                fixed_rate: 0.05,
+                reservoir_size: 1,
+                service_name: String::from("[service_name]"),
+                service_type: String::from("*"),
+                host: String::from("*"),
+                http_method: String::from("*"),
+                url_path: String::from("*"),
+                resource_arn: String::from("*"),
+                attributes: HashMap::new(),
+                tags: Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            XrayResourceAddress::Group {
+                region: String::from("[region]"),
+                name:   String::from("[group_name]"),
+            },
+            XrayResource::Group(Group {
+                filter_expression: String::from("service(\"[service_name]\") AND fault"),
+                insights_enabled: true,
+                notifications_enabled: false,
+                tags: Tags::default(),
+            })
+        ));
+
+        res.push(skeleton!(
+            XrayResourceAddress::EncryptionConfig {
+                region: String::from("[region]"),
+            },
+            XrayResource::EncryptionConfig(EncryptionConfig {
+                key_id: Some(String::from("[kms_key_id]")),
+            })
+        ));
+
+        Ok(res)
+    }
+
+    async fn eq(&self, addr: &Path, a: &[u8], b: &[u8]) -> anyhow::Result<bool> {
+        let addr = XrayResourceAddress::from_path(addr)?;
+
+        match addr {
+            XrayResourceAddress::SamplingRule { .. } => ron_check_eq::<SamplingRule>(a, b),
+            XrayResourceAddress::Group { .. } => ron_check_eq::<Group>(a, b),
+            XrayResourceAddress::EncryptionConfig { .. } => ron_check_eq::<EncryptionConfig>(a, b),
+        }
+    }
+
+    async fn diag(&self, addr: &Path, a: &[u8]) -> Result<Option<DiagnosticResponse>, anyhow::Error> {
+        let addr = XrayResourceAddress::from_path(addr)?;
+
+        match addr {
+            XrayResourceAddress::SamplingRule { .. } => ron_check_syntax::<SamplingRule>(a),
+            XrayResourceAddress::Group { .. } => ron_check_syntax::<Group>(a),
+            XrayResourceAddress::EncryptionConfig { .. } => ron_check_syntax::<EncryptionConfig>(a),
+        }
+    }
+}
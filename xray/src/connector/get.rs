@@ -0,0 +1,77 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{GetResourceResponse, Resource, ResourceAddress},
+    get_resource_response,
+};
+use aws_sdk_xray::types::EncryptionType;
+
+use crate::{
+    addr::XrayResourceAddress,
+    op_impl::{find_group, find_sampling_rule, list_tags},
+    resource::{EncryptionConfig, Group, XrayResource},
+    util::from_sdk_sampling_rule,
+};
+
+use super::XrayConnector;
+
+impl XrayConnector {
+    pub async fn do_get(&self, addr: &Path) -> Result<Option<GetResourceResponse>, anyhow::Error> {
+        let addr = XrayResourceAddress::from_path(addr)?;
+
+        match &addr {
+            XrayResourceAddress::SamplingRule { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(rule) = find_sampling_rule(&client, name).await? else {
+                    return Ok(None);
+                };
+
+                let arn = rule.rule_arn.clone().unwrap_or_default();
+                let tags = list_tags(&client, &arn).await?;
+
+                get_resource_response!(
+                    XrayResource::SamplingRule(from_sdk_sampling_rule(rule, tags)),
+                    [(String::from("rule_arn"), arn)]
+                )
+            }
+            XrayResourceAddress::Group { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(group) = find_group(&client, name).await? else {
+                    return Ok(None);
+                };
+
+                let arn = group.group_arn.unwrap_or_default();
+                let insights = group.insights_configuration;
+
+                let resource = Group {
+                    filter_expression: group.filter_expression.unwrap_or_default(),
+                    insights_enabled: insights.as_ref().and_then(|i| i.insights_enabled).unwrap_or(false),
+                    notifications_enabled: insights.as_ref().and_then(|i| i.notifications_enabled).unwrap_or(false),
+                    tags: list_tags(&client, &arn).await?,
+                };
+
+                get_resource_response!(XrayResource::Group(resource), [(String::from("group_arn"), arn)])
+            }
+            XrayResourceAddress::EncryptionConfig { region } => {
+                let client = self.get_or_init_client(region).await?;
+
+                let Some(config) = client.get_encryption_config().send().await?.encryption_config else {
+                    return Ok(None);
+                };
+
+                // Regions without a KMS key use X-Ray's default encryption, which reads as no configuration
+                if config.r#type != Some(EncryptionType::Kms) {
+                    return Ok(None);
+                }
+
+                Ok(Some(GetResourceResponse {
+                    resource_definition: XrayResource::EncryptionConfig(EncryptionConfig { key_id: config.key_id }).to_bytes()?,
+                    virt_addr: None,
+                    outputs: None,
+                }))
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::path::{Path, PathBuf};
+
+use autoschematic_core::connector::ResourceAddress;
+use aws_sdk_xray::types::EncryptionType;
+
+use crate::{addr::XrayResourceAddress, util::DEFAULT_NAME};
+
+use super::XrayConnector;
+
+impl XrayConnector {
+    pub async fn do_list(&self, _subpath: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut results = Vec::new();
+
+        let enabled_regions = self.config.lock().await.enabled_regions.clone();
+        for region in enabled_regions {
+            let client = self.get_or_init_client(&region).await?;
+
+            // The Default sampling rule is listed, since it can be modified
+            let mut next_token = None;
+            loop {
+                let resp = client.get_sampling_rules().set_next_token(next_token).send().await?;
+
+                for record in resp.sampling_rule_records() {
+                    let Some(name) = record.sampling_rule.as_ref().and_then(|rule| rule.rule_name.as_ref()) else {
+                        continue;
+                    };
+
+                    results.push(
+                        XrayResourceAddress::SamplingRule {
+                            region: region.clone(),
+                            name:   name.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+
+            // The Default group isn't, since it matches all traces and can't be changed
+            let mut next_token = None;
+            loop {
+                let resp = client.get_groups().set_next_token(next_token).send().await?;
+
+                for group in resp.groups() {
+                    let Some(name) = &group.group_name else {
+                        continue;
+                    };
+                    if name == DEFAULT_NAME {
+                        continue;
+                    }
+
+                    results.push(
+                        XrayResourceAddress::Group {
+                            region: region.clone(),
+                            name:   name.clone(),
+                        }
+                        .to_path_buf(),
+                    );
+                }
+
+                next_token = resp.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+
+            let encryption_config = client.get_encryption_config().send().await?.encryption_config;
+            if encryption_config.is_some_and(|config| config.r#type == Some(EncryptionType::Kms)) {
+                results.push(XrayResourceAddress::EncryptionConfig { region: region.clone() }.to_path_buf());
+            }
+        }
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,57 @@
+use std::path::Path;
+
+use autoschematic_core::{
+    connector::{ConnectorOp, OpExecResponse, ResourceAddress},
+    error_util::invalid_op,
+};
+
+use crate::{addr::XrayResourceAddress, op::XrayConnectorOp, op_impl};
+
+use super::XrayConnector;
+
+impl XrayConnector {
+    pub async fn do_op_exec(&self, addr: &Path, op: &str) -> Result<OpExecResponse, anyhow::Error> {
+        let addr = XrayResourceAddress::from_path(addr)?;
+        let op = XrayConnectorOp::from_str(op)?;
+
+        match &addr {
+            XrayResourceAddress::SamplingRule { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    XrayConnectorOp::CreateSamplingRule(rule) => op_impl::create_sampling_rule(&client, name, &rule).await,
+                    XrayConnectorOp::UpdateSamplingRule(rule) => op_impl::update_sampling_rule(&client, name, &rule).await,
+                    XrayConnectorOp::UpdateSamplingRuleTags(old_tags, new_tags) => {
+                        op_impl::update_sampling_rule_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    XrayConnectorOp::DeleteSamplingRule => op_impl::delete_sampling_rule(&client, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            XrayResourceAddress::Group { region, name } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    XrayConnectorOp::CreateGroup(group) => op_impl::create_group(&client, name, &group).await,
+                    XrayConnectorOp::UpdateGroup(group) => op_impl::update_group(&client, name, &group).await,
+                    XrayConnectorOp::UpdateGroupTags(old_tags, new_tags) => {
+                        op_impl::update_group_tags(&client, name, &old_tags, &new_tags).await
+                    }
+                    XrayConnectorOp::DeleteGroup => op_impl::delete_group(&client, name).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            XrayResourceAddress::EncryptionConfig { region } => {
+                let client = self.get_or_init_client(region).await?;
+
+                match op {
+                    XrayConnectorOp::PutEncryptionConfig(config) => {
+                        op_impl::put_encryption_config(&client, region, &config).await
+                    }
+                    XrayConnectorOp::ResetEncryptionConfig => op_impl::reset_encryption_config(&client, region).await,
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use std::path::Path;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{PlanResponseElement, ResourceAddress},
+    connector_op,
+    util::{RON, diff_ron_values, optional_string_from_utf8},
+};
+
+use autoschematic_core::connector::ConnectorOp;
+
+use crate::{
+    resource::{EncryptionConfig, Group, SamplingRule},
+    util::DEFAULT_NAME,
+};
+
+use super::{XrayConnector, XrayConnectorOp, XrayResourceAddress};
+
+/// The alias X-Ray reports when traces are encrypted with the AWS managed key.
+const MANAGED_KEY_ALIAS: &str = "alias/aws/xray";
+
+fn check_sampling_rule(name: &str, rule: &SamplingRule) -> anyhow::Result<()> {
+    if name != DEFAULT_NAME && !(1..=9999).contains(&rule.priority) {
+        bail!("Sampling rule {} has priority {}: expected 1 to 9999", name, rule.priority);
+    }
+    if !(0.0..=1.0).contains(&rule.fixed_rate) {
+        bail!("Sampling rule {} has fixed rate {}: expected 0 to 1", name, rule.fixed_rate);
+    }
+    if rule.reservoir_size < 0 {
+        bail!("Sampling rule {} has reservoir size {}: expected at least 0", name, rule.reservoir_size);
+    }
+    Ok(())
+}
+
+fn check_group(name: &str, group: &Group) -> anyhow::Result<()> {
+    if group.notifications_enabled && !group.insights_enabled {
+        bail!("Group {} has notifications_enabled without insights_enabled", name);
+    }
+    Ok(())
+}
+
+impl XrayConnector {
+    pub async fn do_plan(
+        &self,
+        addr: &Path,
+        current: Option<Vec<u8>>,
+        desired: Option<Vec<u8>>,
+    ) -> Result<Vec<PlanResponseElement>, anyhow::Error> {
+        let addr = XrayResourceAddress::from_path(addr)?;
+
+        let current = optional_string_from_utf8(current)?;
+        let desired = optional_string_from_utf8(desired)?;
+
+        match &addr {
+            XrayResourceAddress::SamplingRule { region, name } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_rule)) => {
+                    let new_rule: SamplingRule = RON.from_str(&new_rule)?;
+                    check_sampling_rule(name, &new_rule)?;
+                    Ok(vec![connector_op!(
+                        XrayConnectorOp::CreateSamplingRule(new_rule),
+                        format!("Create new X-Ray sampling rule {} in {}", name, region)
+                    )])
+                }
+                (Some(_old_rule), None) => {
+                    if name == DEFAULT_NAME {
+                        bail!(
+                            "X-Ray won't delete the {} sampling rule in {}. Modify it to change how unmatched requests are sampled instead.",
+                            DEFAULT_NAME,
+                            region
+                        );
+                    }
+                    Ok(vec![connector_op!(
+                        XrayConnectorOp::DeleteSamplingRule,
+                        format!("DELETE X-Ray sampling rule {} in {}", name, region)
+                    )])
+                }
+                (Some(old_rule), Some(new_rule)) => {
+                    let old_rule: SamplingRule = RON.from_str(&old_rule)?;
+                    let mut new_rule: SamplingRule = RON.from_str(&new_rule)?;
+                    check_sampling_rule(name, &new_rule)?;
+
+                    // The Default rule's priority is fixed at 10000 and can't be updated
+                    if name == DEFAULT_NAME {
+                        new_rule.priority = old_rule.priority;
+                    }
+
+                    let mut ops = Vec::new();
+
+                    if old_rule.tags != new_rule.tags {
+                        let diff = diff_ron_values(&old_rule.tags, &new_rule.tags).unwrap_or_default();
+                        ops.push(connector_op!(
+                            XrayConnectorOp::UpdateSamplingRuleTags(old_rule.tags.clone(), new_rule.tags.clone()),
+                            format!("Modify tags for X-Ray sampling rule `{}` in {}\n{}", name, region, diff)
+                        ));
+                    }
+
+                    let mut old_settings = old_rule.clone();
+                    old_settings.tags = new_rule.tags.clone();
+                    if old_settings != new_rule {
+                        let diff = diff_ron_values(&old_settings, &new_rule).unwrap_or_default();
+                        ops.push(connector_op!(
+                            XrayConnectorOp::UpdateSamplingRule(new_rule),
+                            format!("Modify X-Ray sampling rule `{}` in {}\n{}", name, region, diff)
+                        ));
+                    }
+
+                    Ok(ops)
+                }
+            },
+            XrayResourceAddress::Group { region, name } => {
+                if name == DEFAULT_NAME {
+                    bail!("X-Ray manages the {} group in {}, which can't be created, modified or deleted", DEFAULT_NAME, region);
+                }
+
+                match (current, desired) {
+                    (None, None) => Ok(Vec::new()),
+                    (None, Some(new_group)) => {
+                        let new_group: Group = RON.from_str(&new_group)?;
+                        check_group(name, &new_group)?;
+                        Ok(vec![connector_op!(
+                            XrayConnectorOp::CreateGroup(new_group),
+                            format!("Create new X-Ray group {} in {}", name, region)
+                        )])
+                    }
+                    (Some(_old_group), None) => Ok(vec![connector_op!(
+                        XrayConnectorOp::DeleteGroup,
+                        format!("DELETE X-Ray group {} in {}", name, region)
+                    )]),
+                    (Some(old_group), Some(new_group)) => {
+                        let old_group: Group = RON.from_str(&old_group)?;
+                        let new_group: Group = RON.from_str(&new_group)?;
+                        check_group(name, &new_group)?;
+
+                        let mut ops = Vec::new();
+
+                        if old_group.tags != new_group.tags {
+                            let diff = diff_ron_values(&old_group.tags, &new_group.tags).unwrap_or_default();
+                            ops.push(connector_op!(
+                                XrayConnectorOp::UpdateGroupTags(old_group.tags.clone(), new_group.tags.clone()),
+                                format!("Modify tags for X-Ray group `{}` in {}\n{}", name, region, diff)
+                            ));
+                        }
+
+                        let mut old_settings = old_group.clone();
+                        old_settings.tags = new_group.tags.clone();
+                        if old_settings != new_group {
+                            let diff = diff_ron_values(&old_settings, &new_group).unwrap_or_default();
+                            ops.push(connector_op!(
+                                XrayConnectorOp::UpdateGroup(new_group),
+                                format!("Modify X-Ray group `{}` in {}\n{}", name, region, diff)
+                            ));
+                        }
+
+                        Ok(ops)
+                    }
+                }
+            }
+            XrayResourceAddress::EncryptionConfig { region } => match (current, desired) {
+                (None, None) => Ok(Vec::new()),
+                (None, Some(new_config)) => {
+                    let new_config: EncryptionConfig = RON.from_str(&new_config)?;
+                    Ok(vec![connector_op!(
+                        XrayConnectorOp::PutEncryptionConfig(new_config),
+                        format!("Encrypt X-Ray traces in {} with KMS", region)
+                    )])
+                }
+                (Some(_old_config), None) => Ok(vec![connector_op!(
+                    XrayConnectorOp::ResetEncryptionConfig,
+                    format!("DELETE X-Ray encryption configuration in {}. Traces return to default encryption.", region)
+                )]),
+                (Some(old_config), Some(new_config)) => {
+                    let old_config: EncryptionConfig = RON.from_str(&old_config)?;
+                    let mut new_config: EncryptionConfig = RON.from_str(&new_config)?;
+
+                    // Leaving out key_id means the AWS managed key, which X-Ray reports by its alias
+                    if new_config.key_id.is_none()
+                        && old_config
+                            .key_id
+                            .as_deref()
+                            .is_some_and(|key_id| key_id.ends_with(MANAGED_KEY_ALIAS))
+                    {
+                        new_config.key_id = old_config.key_id.clone();
+                    }
+
+                    if old_config == new_config {
+                        return Ok(Vec::new());
+                    }
+
+                    let diff = diff_ron_values(&old_config, &new_config).unwrap_or_default();
+                    Ok(vec![connector_op!(
+                        XrayConnectorOp::PutEncryptionConfig(new_config),
+                        format!("Modify X-Ray encryption configuration in {}\n{}", region, diff)
+                    )])
+                }
+            },
+        }
+    }
+}
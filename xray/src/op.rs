@@ -0,0 +1,40 @@
+use autoschematic_core::connector::ConnectorOp;
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{
+    resource::{EncryptionConfig, Group, SamplingRule},
+    tags::Tags,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum XrayConnectorOp {
+    CreateSamplingRule(SamplingRule),
+    UpdateSamplingRule(SamplingRule),
+    UpdateSamplingRuleTags(Tags, Tags),
+    DeleteSamplingRule,
+
+    CreateGroup(Group),
+    UpdateGroup(Group),
+    UpdateGroupTags(Tags, Tags),
+    DeleteGroup,
+
+    /// Encrypts the region's traces with the given key, and waits for the change to finish.
+    PutEncryptionConfig(EncryptionConfig),
+    /// Returns the region to X-Ray's default encryption.
+    ResetEncryptionConfig,
+}
+
+impl ConnectorOp for XrayConnectorOp {
+    fn to_string(&self) -> Result<String, anyhow::Error> {
+        Ok(RON.to_string(self)?)
+    }
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        Ok(RON.from_str(s)?)
+    }
+}
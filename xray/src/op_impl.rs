@@ -0,0 +1,308 @@
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::{Context, bail};
+use autoschematic_core::{connector::OpExecResponse, op_exec_output};
+use aws_sdk_xray::types::{EncryptionStatus, EncryptionType, InsightsConfiguration, SamplingRuleUpdate};
+
+use crate::{
+    resource::{EncryptionConfig, Group, SamplingRule},
+    tags::{Tags, tag_diff},
+};
+
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Re-encrypting a region's traces usually finishes within a few minutes.
+const STATUS_MAX_POLLS: usize = 60;
+
+/// TagResource and UntagResource take the resource's ARN.
+async fn update_tags(client: &aws_sdk_xray::Client, arn: &str, old_tags: &Tags, new_tags: &Tags) -> anyhow::Result<()> {
+    let (untag_keys, new_tagset) = tag_diff(old_tags, new_tags);
+
+    if !untag_keys.is_empty() {
+        client
+            .untag_resource()
+            .resource_arn(arn)
+            .set_tag_keys(Some(untag_keys))
+            .send()
+            .await?;
+    }
+
+    if let Some(new_tagset) = new_tagset.to_sdk()? {
+        client.tag_resource().resource_arn(arn).set_tags(Some(new_tagset)).send().await?;
+    }
+
+    Ok(())
+}
+
+pub async fn list_tags(client: &aws_sdk_xray::Client, arn: &str) -> anyhow::Result<Tags> {
+    let mut tags = Vec::new();
+    let mut next_token = None;
+    loop {
+        let resp = client
+            .list_tags_for_resource()
+            .resource_arn(arn)
+            .set_next_token(next_token)
+            .send()
+            .await?;
+
+        tags.extend(resp.tags.unwrap_or_default());
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            return Ok(Tags::from(tags));
+        }
+    }
+}
+
+/// GetSamplingRules returns every rule in the region, so this finds the rule by paging through them.
+pub async fn find_sampling_rule(
+    client: &aws_sdk_xray::Client,
+    name: &str,
+) -> anyhow::Result<Option<aws_sdk_xray::types::SamplingRule>> {
+    let mut next_token = None;
+    loop {
+        let resp = client.get_sampling_rules().set_next_token(next_token).send().await?;
+
+        if let Some(rule) = resp
+            .sampling_rule_records
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|record| record.sampling_rule)
+            .find(|rule| rule.rule_name.as_deref() == Some(name))
+        {
+            return Ok(Some(rule));
+        }
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            return Ok(None);
+        }
+    }
+}
+
+/// GetGroup fails with a generic InvalidRequestException for missing groups, so this pages through GetGroups instead.
+pub async fn find_group(client: &aws_sdk_xray::Client, name: &str) -> anyhow::Result<Option<aws_sdk_xray::types::GroupSummary>> {
+    let mut next_token = None;
+    loop {
+        let resp = client.get_groups().set_next_token(next_token).send().await?;
+
+        if let Some(group) = resp
+            .groups
+            .unwrap_or_default()
+            .into_iter()
+            .find(|group| group.group_name.as_deref() == Some(name))
+        {
+            return Ok(Some(group));
+        }
+
+        next_token = resp.next_token;
+        if next_token.is_none() {
+            return Ok(None);
+        }
+    }
+}
+
+async fn sampling_rule_arn(client: &aws_sdk_xray::Client, name: &str) -> anyhow::Result<String> {
+    find_sampling_rule(client, name)
+        .await?
+        .and_then(|rule| rule.rule_arn)
+        .with_context(|| format!("X-Ray sampling rule {} not found", name))
+}
+
+async fn group_arn(client: &aws_sdk_xray::Client, name: &str) -> anyhow::Result<String> {
+    find_group(client, name)
+        .await?
+        .and_then(|group| group.group_arn)
+        .with_context(|| format!("X-Ray group {} not found", name))
+}
+
+pub async fn create_sampling_rule(
+    client: &aws_sdk_xray::Client,
+    name: &str,
+    rule: &SamplingRule,
+) -> anyhow::Result<OpExecResponse> {
+    let sampling_rule = aws_sdk_xray::types::SamplingRule::builder()
+        .rule_name(name)
+        .priority(rule.priority)
+        .fixed_rate(rule.fixed_rate)
+        .reservoir_size(rule.reservoir_size)
+        .service_name(&rule.service_name)
+        .service_type(&rule.service_type)
+        .host(&rule.host)
+        .http_method(&rule.http_method)
+        .url_path(&rule.url_path)
+        .resource_arn(&rule.resource_arn)
+        .version(1)
+        .set_attributes(if rule.attributes.is_empty() {
+            None
+        } else {
+            Some(rule.attributes.clone())
+        })
+        .build()?;
+
+    let resp = client
+        .create_sampling_rule()
+        .sampling_rule(sampling_rule)
+        .set_tags(rule.tags.to_sdk()?)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(
+            String::from("rule_arn"),
+            resp.sampling_rule_record
+                .and_then(|record| record.sampling_rule)
+                .and_then(|rule| rule.rule_arn),
+        )])),
+        friendly_message: Some(format!("Created X-Ray sampling rule {}", name)),
+    })
+}
+
+pub async fn update_sampling_rule(
+    client: &aws_sdk_xray::Client,
+    name: &str,
+    rule: &SamplingRule,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .update_sampling_rule()
+        .sampling_rule_update(
+            SamplingRuleUpdate::builder()
+                .rule_name(name)
+                .priority(rule.priority)
+                .fixed_rate(rule.fixed_rate)
+                .reservoir_size(rule.reservoir_size)
+                .service_name(&rule.service_name)
+                .service_type(&rule.service_type)
+                .host(&rule.host)
+                .http_method(&rule.http_method)
+                .url_path(&rule.url_path)
+                .resource_arn(&rule.resource_arn)
+                // An empty map removes all attributes
+                .set_attributes(Some(rule.attributes.clone()))
+                .build(),
+        )
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Updated X-Ray sampling rule {}", name))
+}
+
+pub async fn update_sampling_rule_tags(
+    client: &aws_sdk_xray::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = sampling_rule_arn(client, name).await?;
+
+    update_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for X-Ray sampling rule {}", name))
+}
+
+pub async fn delete_sampling_rule(client: &aws_sdk_xray::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    client.delete_sampling_rule().rule_name(name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("rule_arn"), None)])),
+        friendly_message: Some(format!("Deleted X-Ray sampling rule {}", name)),
+    })
+}
+
+fn insights_configuration(group: &Group) -> InsightsConfiguration {
+    InsightsConfiguration::builder()
+        .insights_enabled(group.insights_enabled)
+        .notifications_enabled(group.notifications_enabled)
+        .build()
+}
+
+pub async fn create_group(client: &aws_sdk_xray::Client, name: &str, group: &Group) -> anyhow::Result<OpExecResponse> {
+    let resp = client
+        .create_group()
+        .group_name(name)
+        .filter_expression(&group.filter_expression)
+        .insights_configuration(insights_configuration(group))
+        .set_tags(group.tags.to_sdk()?)
+        .send()
+        .await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(
+            String::from("group_arn"),
+            resp.group.and_then(|group| group.group_arn),
+        )])),
+        friendly_message: Some(format!("Created X-Ray group {}", name)),
+    })
+}
+
+pub async fn update_group(client: &aws_sdk_xray::Client, name: &str, group: &Group) -> anyhow::Result<OpExecResponse> {
+    client
+        .update_group()
+        .group_name(name)
+        .filter_expression(&group.filter_expression)
+        .insights_configuration(insights_configuration(group))
+        .send()
+        .await?;
+
+    op_exec_output!(format!("Updated X-Ray group {}", name))
+}
+
+pub async fn update_group_tags(
+    client: &aws_sdk_xray::Client,
+    name: &str,
+    old_tags: &Tags,
+    new_tags: &Tags,
+) -> anyhow::Result<OpExecResponse> {
+    let arn = group_arn(client, name).await?;
+
+    update_tags(client, &arn, old_tags, new_tags).await?;
+
+    op_exec_output!(format!("Updated tags for X-Ray group {}", name))
+}
+
+pub async fn delete_group(client: &aws_sdk_xray::Client, name: &str) -> anyhow::Result<OpExecResponse> {
+    client.delete_group().group_name(name).send().await?;
+
+    Ok(OpExecResponse {
+        outputs: Some(HashMap::from([(String::from("group_arn"), None)])),
+        friendly_message: Some(format!("Deleted X-Ray group {}", name)),
+    })
+}
+
+/// Waits for X-Ray to finish applying the region's new encryption configuration.
+async fn wait_for_encryption_active(client: &aws_sdk_xray::Client, region: &str) -> anyhow::Result<()> {
+    for _ in 0..STATUS_MAX_POLLS {
+        let resp = client.get_encryption_config().send().await?;
+        let status = resp.encryption_config.and_then(|config| config.status);
+        if status == Some(EncryptionStatus::Active) {
+            return Ok(());
+        }
+        tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+    }
+
+    bail!("Timed out waiting for the X-Ray encryption configuration in {} to update", region)
+}
+
+pub async fn put_encryption_config(
+    client: &aws_sdk_xray::Client,
+    region: &str,
+    config: &EncryptionConfig,
+) -> anyhow::Result<OpExecResponse> {
+    client
+        .put_encryption_config()
+        .r#type(EncryptionType::Kms)
+        .set_key_id(config.key_id.clone())
+        .send()
+        .await?;
+
+    wait_for_encryption_active(client, region).await?;
+
+    op_exec_output!(format!("Encrypted X-Ray traces in {} with KMS", region))
+}
+
+pub async fn reset_encryption_config(client: &aws_sdk_xray::Client, region: &str) -> anyhow::Result<OpExecResponse> {
+    client.put_encryption_config().r#type(EncryptionType::None).send().await?;
+
+    wait_for_encryption_active(client, region).await?;
+
+    op_exec_output!(format!("Returned X-Ray traces in {} to default encryption", region))
+}
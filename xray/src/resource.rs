@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use autoschematic_core::connector::{Resource, ResourceAddress};
+use serde::{Deserialize, Serialize};
+
+use autoschematic_core::util::RON;
+
+use super::{addr::XrayResourceAddress, tags::Tags};
+
+fn wildcard() -> String {
+    String::from("*")
+}
+
+/// A sampling rule. Requests are matched against rules in priority order, and the first match decides
+/// whether they're traced. Every field left out matches any request.
+///
+/// Each region has a rule named Default, which can be modified but not deleted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SamplingRule {
+    /// From 1 to 9999. Ignored for the Default rule, which is always evaluated last.
+    pub priority: i32,
+    /// The fraction of matching requests traced after the reservoir is used up, from 0 to 1.
+    pub fixed_rate: f64,
+    /// The number of matching requests traced each second before `fixed_rate` applies.
+    pub reservoir_size: i32,
+    #[serde(default = "wildcard")]
+    pub service_name: String,
+    /// e.g. AWS::EC2::Instance.
+    #[serde(default = "wildcard")]
+    pub service_type: String,
+    #[serde(default = "wildcard")]
+    pub host: String,
+    #[serde(default = "wildcard")]
+    pub http_method: String,
+    #[serde(default = "wildcard")]
+    pub url_path: String,
+    #[serde(default = "wildcard")]
+    pub resource_arn: String,
+    /// Segment attributes that requests must match, which can contain wildcards.
+    #[serde(default)]
+    pub attributes: HashMap<String, String>,
+    pub tags: Tags,
+}
+
+/// A group of traces matching a filter expression, e.g. `service("checkout") AND responsetime > 2`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Group {
+    pub filter_expression: String,
+    /// Whether X-Ray Insights looks for anomalies in the group's traces.
+    #[serde(default)]
+    pub insights_enabled: bool,
+    /// Whether Insights notifications are sent to EventBridge. Requires `insights_enabled`.
+    #[serde(default)]
+    pub notifications_enabled: bool,
+    pub tags: Tags,
+}
+
+/// Encrypts the region's traces with a KMS key. Removing it returns the region to X-Ray's default encryption.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptionConfig {
+    /// A key ID, ARN or alias. If None, the AWS managed key alias/aws/xray.
+    pub key_id: Option<String>,
+}
+
+pub enum XrayResource {
+    SamplingRule(SamplingRule),
+    Group(Group),
+    EncryptionConfig(EncryptionConfig),
+}
+
+impl Resource for XrayResource {
+    fn to_bytes(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let pretty_config = autoschematic_core::util::PrettyConfig::default();
+        match self {
+            XrayResource::SamplingRule(rule) => Ok(RON.to_string_pretty(&rule, pretty_config)?.into()),
+            XrayResource::Group(group) => Ok(RON.to_string_pretty(&group, pretty_config)?.into()),
+            XrayResource::EncryptionConfig(config) => Ok(RON.to_string_pretty(&config, pretty_config)?.into()),
+        }
+    }
+
+    fn from_bytes(addr: &impl ResourceAddress, s: &[u8]) -> Result<Self, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let addr = XrayResourceAddress::from_path(&addr.to_path_buf())?;
+
+        let s = str::from_utf8(s)?;
+
+        match addr {
+            XrayResourceAddress::SamplingRule { .. } => Ok(XrayResource::SamplingRule(RON.from_str(s)?)),
+            XrayResourceAddress::Group { .. } => Ok(XrayResource::Group(RON.from_str(s)?)),
+            XrayResourceAddress::EncryptionConfig { .. } => Ok(XrayResource::EncryptionConfig(RON.from_str(s)?)),
+        }
+    }
+}
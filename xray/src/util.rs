@@ -0,0 +1,20 @@
+use crate::{resource::SamplingRule, tags::Tags};
+
+/// Each region has a sampling rule and a group with this name, which X-Ray creates and won't delete.
+pub const DEFAULT_NAME: &str = "Default";
+
+pub fn from_sdk_sampling_rule(rule: aws_sdk_xray::types::SamplingRule, tags: Tags) -> SamplingRule {
+    SamplingRule {
+        priority: rule.priority,
+        fixed_rate: rule.fixed_rate,
+        reservoir_size: rule.reservoir_size,
+        service_name: rule.service_name,
+        service_type: rule.service_type,
+        host: rule.host,
+        http_method: rule.http_method,
+        url_path: rule.url_path,
+        resource_arn: rule.resource_arn,
+        attributes: rule.attributes.unwrap_or_default(),
+        tags,
+    }
+}